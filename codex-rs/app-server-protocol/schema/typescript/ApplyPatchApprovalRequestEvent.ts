@@ -2,6 +2,8 @@
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { FileChange } from "./FileChange";
+import type { FileOperation } from "./FileOperation";
+import type { PatchHunkReport } from "./PatchHunkReport";
 
 export type ApplyPatchApprovalRequestEvent = { 
 /**
@@ -20,4 +22,14 @@ reason: string | null,
 /**
  * When set, the agent is asking the user to allow writes under this root for the remainder of the session.
  */
-grant_root: string | null, };
+grant_root: string | null, 
+/**
+ * Dry-run result for each hunk, so clients can flag hunks that only
+ * matched fuzzily or that conflict before the user approves.
+ */
+hunks?: Array<PatchHunkReport>, 
+/**
+ * Classes of file operation the patch performs, so clients can tell a
+ * deletion from a write without inspecting `changes`.
+ */
+file_operations?: Array<FileOperation>, };
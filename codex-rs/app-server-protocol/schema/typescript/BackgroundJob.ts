@@ -0,0 +1,20 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A long-running command kept alive across turns as a background job.
+ */
+export type BackgroundJob = { 
+/**
+ * Identifier used to read the job's output or stop it.
+ */
+id: string, 
+/**
+ * The command line the job was started with.
+ */
+command: string, 
+/**
+ * Exit code once the job has exited; `None` while it is running.
+ */
+exit_code: number | null, };
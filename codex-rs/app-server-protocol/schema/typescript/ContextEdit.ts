@@ -0,0 +1,8 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A user-requested change to the conversation context.
+ */
+export type ContextEdit = { "type": "compact" } | { "type": "clear" } | { "type": "drop_turns", first: number, last: number, };
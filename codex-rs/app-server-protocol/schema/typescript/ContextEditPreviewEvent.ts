@@ -0,0 +1,24 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ContextEdit } from "./ContextEdit";
+
+export type ContextEditPreviewEvent = { edit: ContextEdit, 
+/**
+ * Number of user turns currently in context.
+ */
+total_turns: number, 
+/**
+ * First line of each user message the edit removes (or summarizes),
+ * oldest first.
+ */
+removed_turns: Array<string>, 
+/**
+ * Estimated tokens in context now.
+ */
+tokens_before: number, 
+/**
+ * Estimated tokens in context after the edit. For compaction this
+ * excludes the summary the model has yet to write.
+ */
+tokens_after: number, };
@@ -0,0 +1,10 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ContextEdit } from "./ContextEdit";
+
+export type ContextEditedEvent = { edit: ContextEdit, 
+/**
+ * Number of user turns that were removed from context.
+ */
+removed_turns: number, tokens_before: number, tokens_after: number, };
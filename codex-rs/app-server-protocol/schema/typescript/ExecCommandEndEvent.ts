@@ -56,4 +56,10 @@ duration: string,
 /**
  * Formatted output from the command, as seen by the model.
  */
-formatted_output: string, };
+formatted_output: string, 
+/**
+ * Unified diff of `aggregated_output` against the previous run of the
+ * same command in this session. Empty when the output is unchanged;
+ * absent the first time the command runs.
+ */
+previous_run_diff?: string, };
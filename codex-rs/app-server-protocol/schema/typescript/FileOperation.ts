@@ -0,0 +1,9 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Class of file operation an approval request covers. These match the
+ * `file_approvals` policies in config.toml.
+ */
+export type FileOperation = "read" | "write" | "delete";
@@ -9,4 +9,15 @@ export type InitializeCapabilities = {
 /**
  * Opt into receiving experimental API methods and fields.
  */
-experimentalApi: boolean, };
+experimentalApi: boolean, 
+/**
+ * Protocol version the client was built against. Clients that predate
+ * versioning omit it and are treated as version 1.
+ */
+protocolVersion: number | null, 
+/**
+ * Server notification methods the client can handle. When set, the server
+ * only sends these, so notifications added after the client was built
+ * are dropped instead of reaching a client that cannot parse them.
+ */
+notificationMethods: Array<string> | null, };
@@ -2,4 +2,19 @@
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type InitializeResponse = { userAgent: string, };
+export type InitializeResponse = { userAgent: string, 
+/**
+ * Protocol version used on this connection: the client's
+ * `protocolVersion`, which must not be newer than this server's.
+ */
+protocolVersion: number, 
+/**
+ * Notification methods the server may send on this connection: every
+ * method it knows, narrowed to the client's `notificationMethods` when
+ * given.
+ */
+notificationMethods: Array<string>, 
+/**
+ * Whether experimental API methods and fields are enabled.
+ */
+experimentalApi: boolean, };
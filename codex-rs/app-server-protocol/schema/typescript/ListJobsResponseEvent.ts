@@ -0,0 +1,9 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BackgroundJob } from "./BackgroundJob";
+
+/**
+ * Response payload for `Op::ListJobs`, `Op::StartJob`, and `Op::StopJob`.
+ */
+export type ListJobsResponseEvent = { jobs: Array<BackgroundJob>, };
@@ -0,0 +1,9 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TrashEntry } from "./TrashEntry";
+
+/**
+ * Response payload for `Op::ListTrash`, `Op::RestoreTrash`, and `Op::PurgeTrash`.
+ */
+export type ListTrashResponseEvent = { entries: Array<TrashEntry>, };
@@ -2,6 +2,7 @@
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { McpAuthStatus } from "./McpAuthStatus";
+import type { Prompt } from "./Prompt";
 import type { Resource } from "./Resource";
 import type { ResourceTemplate } from "./ResourceTemplate";
 import type { Tool } from "./Tool";
@@ -19,6 +20,10 @@ resources: { [key in string]?: Array<Resource> },
  * Known resource templates grouped by server name.
  */
 resource_templates: { [key in string]?: Array<ResourceTemplate> }, 
+/**
+ * Known prompts grouped by server name.
+ */
+prompts: { [key in string]?: Array<Prompt> }, 
 /**
  * Authentication status for each configured MCP server.
  */
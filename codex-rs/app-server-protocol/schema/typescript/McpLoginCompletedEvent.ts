@@ -0,0 +1,16 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Response payload for `Op::McpLogin`.
+ */
+export type McpLoginCompletedEvent = { 
+/**
+ * Name of the MCP server.
+ */
+name: string, success: boolean, 
+/**
+ * Why the login failed, when it did.
+ */
+error?: string, };
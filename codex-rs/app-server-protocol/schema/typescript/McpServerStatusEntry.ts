@@ -0,0 +1,18 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { McpServerState } from "./McpServerState";
+
+export type McpServerStatusEntry = { name: string, state: McpServerState, 
+/**
+ * Names of the tools the server currently exposes.
+ */
+tools: Array<string>, 
+/**
+ * Times the server was restarted after it stopped responding.
+ */
+restarts: number, 
+/**
+ * Most recent errors, oldest first.
+ */
+recent_errors: Array<string>, };
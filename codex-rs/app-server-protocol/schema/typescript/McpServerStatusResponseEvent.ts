@@ -0,0 +1,13 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { McpServerStatusEntry } from "./McpServerStatusEntry";
+
+/**
+ * Response payload for `Op::McpServerStatus`.
+ */
+export type McpServerStatusResponseEvent = { 
+/**
+ * One entry per enabled server, sorted by name.
+ */
+servers: Array<McpServerStatusEntry>, };
@@ -0,0 +1,21 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type McpToolCallProgressEvent = { 
+/**
+ * Identifier of the McpToolCallBegin this progress belongs to.
+ */
+call_id: string, 
+/**
+ * Progress so far; increases with each notification.
+ */
+progress: number, 
+/**
+ * Total amount of work, when the server knows it.
+ */
+total: number | null, 
+/**
+ * Human-readable description of the current step.
+ */
+message: string | null, };
@@ -0,0 +1,24 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+export type ModelFailoverEvent = { 
+/**
+ * Provider id that kept failing, e.g. `openai`.
+ */
+from_provider: string, 
+/**
+ * Model requested from `from_provider`.
+ */
+from_model: string, 
+/**
+ * Fallback provider id that answers the rest of the turn.
+ */
+to_provider: string, 
+/**
+ * Model requested from `to_provider`.
+ */
+to_model: string, 
+/**
+ * Error returned by the failing provider.
+ */
+reason: string, };
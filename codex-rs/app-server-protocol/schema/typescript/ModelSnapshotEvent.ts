@@ -0,0 +1,13 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ModelSnapshotEvent = { 
+/**
+ * Model name the turn requested, e.g. `gpt-5`.
+ */
+requested_model: string, 
+/**
+ * Dated snapshot the provider reported serving, e.g. `gpt-5-2025-08-07`.
+ */
+served_model: string, };
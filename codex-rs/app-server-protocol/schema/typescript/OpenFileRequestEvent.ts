@@ -0,0 +1,22 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type OpenFileRequestEvent = { 
+/**
+ * Identifier for the originating tool call.
+ */
+call_id: string, 
+/**
+ * Absolute path of the file to open.
+ */
+path: string, 
+/**
+ * 1-based line to reveal, when the agent gave one.
+ */
+line: number | null, 
+/**
+ * Whether `open_file_command` already opened the file. Hosts should only
+ * open it themselves when this is false.
+ */
+opened_by_command: boolean, };
@@ -0,0 +1,12 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Identifies one hunk of a proposed patch, numbered as in [`PatchHunkReport`].
+ */
+export type PatchHunkId = { path: string, 
+/**
+ * 1-based position of the hunk among the hunks for `path`.
+ */
+hunk: number, };
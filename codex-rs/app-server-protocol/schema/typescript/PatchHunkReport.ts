@@ -0,0 +1,21 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PatchHunkStatus } from "./PatchHunkStatus";
+
+/**
+ * Dry-run result for one hunk of a proposed patch.
+ */
+export type PatchHunkReport = { path: string, 
+/**
+ * 1-based position of the hunk among the hunks for `path`.
+ */
+hunk: number, 
+/**
+ * 1-based line of the original file where the hunk applies, if it does.
+ */
+line: number | null, status: PatchHunkStatus, 
+/**
+ * How a fuzzy hunk matched, or why a hunk conflicts.
+ */
+detail: string | null, };
@@ -0,0 +1,9 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Whether a hunk applies verbatim (`clean`), only after relaxing whitespace
+ * or punctuation (`fuzzy`), or not at all (`conflict`).
+ */
+export type PatchHunkStatus = "clean" | "fuzzy" | "conflict";
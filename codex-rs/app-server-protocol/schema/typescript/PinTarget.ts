@@ -0,0 +1,8 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Content pinned into the model's context.
+ */
+export type PinTarget = { "type": "file", path: string, } | { "type": "message", text: string | null, };
@@ -0,0 +1,15 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PinTarget } from "./PinTarget";
+
+export type PinnedContextEntry = { id: number, 
+/**
+ * The pinned content, with file paths made absolute and message text
+ * filled in.
+ */
+target: PinTarget, 
+/**
+ * Estimated tokens the pin adds to every request.
+ */
+tokens: number, };
@@ -0,0 +1,10 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PinnedContextEntry } from "./PinnedContextEntry";
+
+export type PinnedContextEvent = { 
+/**
+ * Current pins, oldest first.
+ */
+pins: Array<PinnedContextEntry>, };
@@ -0,0 +1,8 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Describes an argument that a prompt can accept.
+ */
+export type PromptArgument = { description?: string, name: string, required?: boolean, title?: string, };
@@ -0,0 +1,16 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A file the agent deleted that is still recoverable from the session trash.
+ */
+export type TrashEntry = { 
+/**
+ * Location the file was deleted from.
+ */
+path: string, 
+/**
+ * Location of the preserved copy inside the trash directory.
+ */
+trash_path: string, };
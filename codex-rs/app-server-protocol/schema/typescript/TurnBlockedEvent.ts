@@ -0,0 +1,13 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type TurnBlockedEvent = { 
+/**
+ * Why the response was blocked, e.g. `refusal` or `content_filter`.
+ */
+category: string, 
+/**
+ * Explanation supplied by the provider.
+ */
+message: string, };
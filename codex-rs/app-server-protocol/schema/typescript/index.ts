@@ -25,11 +25,13 @@ export type { AudioContent } from "./AudioContent";
 export type { AuthMode } from "./AuthMode";
 export type { AuthStatusChangeNotification } from "./AuthStatusChangeNotification";
 export type { BackgroundEventEvent } from "./BackgroundEventEvent";
+export type { BackgroundJob } from "./BackgroundJob";
 export type { BlobResourceContents } from "./BlobResourceContents";
 export type { ByteRange } from "./ByteRange";
 export type { CallToolResult } from "./CallToolResult";
 export type { CancelLoginChatGptParams } from "./CancelLoginChatGptParams";
 export type { CancelLoginChatGptResponse } from "./CancelLoginChatGptResponse";
+export type { CheckpointCreatedEvent } from "./CheckpointCreatedEvent";
 export type { ClientInfo } from "./ClientInfo";
 export type { ClientNotification } from "./ClientNotification";
 export type { ClientRequest } from "./ClientRequest";
@@ -48,6 +50,9 @@ export type { ContentBlock } from "./ContentBlock";
 export type { ContentItem } from "./ContentItem";
 export type { ContextCompactedEvent } from "./ContextCompactedEvent";
 export type { ContextCompactionItem } from "./ContextCompactionItem";
+export type { ContextEdit } from "./ContextEdit";
+export type { ContextEditPreviewEvent } from "./ContextEditPreviewEvent";
+export type { ContextEditedEvent } from "./ContextEditedEvent";
 export type { ConversationGitInfo } from "./ConversationGitInfo";
 export type { ConversationSummary } from "./ConversationSummary";
 export type { CreditsSnapshot } from "./CreditsSnapshot";
@@ -72,6 +77,7 @@ export type { ExecOutputStream } from "./ExecOutputStream";
 export type { ExecPolicyAmendment } from "./ExecPolicyAmendment";
 export type { ExitedReviewModeEvent } from "./ExitedReviewModeEvent";
 export type { FileChange } from "./FileChange";
+export type { FileOperation } from "./FileOperation";
 export type { ForcedLoginMethod } from "./ForcedLoginMethod";
 export type { ForkConversationParams } from "./ForkConversationParams";
 export type { ForkConversationResponse } from "./ForkConversationResponse";
@@ -104,7 +110,9 @@ export type { ItemStartedEvent } from "./ItemStartedEvent";
 export type { ListConversationsParams } from "./ListConversationsParams";
 export type { ListConversationsResponse } from "./ListConversationsResponse";
 export type { ListCustomPromptsResponseEvent } from "./ListCustomPromptsResponseEvent";
+export type { ListJobsResponseEvent } from "./ListJobsResponseEvent";
 export type { ListSkillsResponseEvent } from "./ListSkillsResponseEvent";
+export type { ListTrashResponseEvent } from "./ListTrashResponseEvent";
 export type { LocalShellAction } from "./LocalShellAction";
 export type { LocalShellExecAction } from "./LocalShellExecAction";
 export type { LocalShellStatus } from "./LocalShellStatus";
@@ -116,25 +124,41 @@ export type { LogoutChatGptResponse } from "./LogoutChatGptResponse";
 export type { McpAuthStatus } from "./McpAuthStatus";
 export type { McpInvocation } from "./McpInvocation";
 export type { McpListToolsResponseEvent } from "./McpListToolsResponseEvent";
+export type { McpLoginCompletedEvent } from "./McpLoginCompletedEvent";
+export type { McpServerState } from "./McpServerState";
+export type { McpServerStatusEntry } from "./McpServerStatusEntry";
+export type { McpServerStatusResponseEvent } from "./McpServerStatusResponseEvent";
 export type { McpStartupCompleteEvent } from "./McpStartupCompleteEvent";
 export type { McpStartupFailure } from "./McpStartupFailure";
 export type { McpStartupStatus } from "./McpStartupStatus";
 export type { McpStartupUpdateEvent } from "./McpStartupUpdateEvent";
 export type { McpToolCallBeginEvent } from "./McpToolCallBeginEvent";
 export type { McpToolCallEndEvent } from "./McpToolCallEndEvent";
+export type { McpToolCallProgressEvent } from "./McpToolCallProgressEvent";
 export type { ModeKind } from "./ModeKind";
+export type { ModelFailoverEvent } from "./ModelFailoverEvent";
+export type { ModelSnapshotEvent } from "./ModelSnapshotEvent";
 export type { NetworkAccess } from "./NetworkAccess";
 export type { NewConversationParams } from "./NewConversationParams";
 export type { NewConversationResponse } from "./NewConversationResponse";
+export type { OpenFileRequestEvent } from "./OpenFileRequestEvent";
 export type { ParsedCommand } from "./ParsedCommand";
 export type { PatchApplyBeginEvent } from "./PatchApplyBeginEvent";
 export type { PatchApplyEndEvent } from "./PatchApplyEndEvent";
+export type { PatchHunkId } from "./PatchHunkId";
+export type { PatchHunkReport } from "./PatchHunkReport";
+export type { PatchHunkStatus } from "./PatchHunkStatus";
 export type { Personality } from "./Personality";
+export type { PinTarget } from "./PinTarget";
+export type { PinnedContextEntry } from "./PinnedContextEntry";
+export type { PinnedContextEvent } from "./PinnedContextEvent";
 export type { PlanDeltaEvent } from "./PlanDeltaEvent";
 export type { PlanItem } from "./PlanItem";
 export type { PlanItemArg } from "./PlanItemArg";
 export type { PlanType } from "./PlanType";
 export type { Profile } from "./Profile";
+export type { Prompt } from "./Prompt";
+export type { PromptArgument } from "./PromptArgument";
 export type { RateLimitSnapshot } from "./RateLimitSnapshot";
 export type { RateLimitWindow } from "./RateLimitWindow";
 export type { RawResponseItemEvent } from "./RawResponseItemEvent";
@@ -205,8 +229,10 @@ export type { ToolAnnotations } from "./ToolAnnotations";
 export type { ToolInputSchema } from "./ToolInputSchema";
 export type { ToolOutputSchema } from "./ToolOutputSchema";
 export type { Tools } from "./Tools";
+export type { TrashEntry } from "./TrashEntry";
 export type { TurnAbortReason } from "./TurnAbortReason";
 export type { TurnAbortedEvent } from "./TurnAbortedEvent";
+export type { TurnBlockedEvent } from "./TurnBlockedEvent";
 export type { TurnCompleteEvent } from "./TurnCompleteEvent";
 export type { TurnDiffEvent } from "./TurnDiffEvent";
 export type { TurnItem } from "./TurnItem";
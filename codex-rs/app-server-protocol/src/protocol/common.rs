@@ -517,6 +517,22 @@ macro_rules! server_request_definitions {
     };
 }
 
+/// Wire method for a notification variant: its explicit `=> "method"` or,
+/// like `#[serde(rename_all = "camelCase")]`, the camelCased variant name.
+macro_rules! server_notification_method {
+    ($variant:ident, $wire:literal) => {
+        $wire.to_string()
+    };
+    ($variant:ident) => {{
+        let name = stringify!($variant);
+        let mut chars = name.chars();
+        chars
+            .next()
+            .map(|first| first.to_ascii_lowercase().to_string() + chars.as_str())
+            .unwrap_or_default()
+    }};
+}
+
 /// Generates `ServerNotification` enum and helpers, including a JSON Schema
 /// exporter for each notification.
 macro_rules! server_notification_definitions {
@@ -544,6 +560,11 @@ macro_rules! server_notification_definitions {
                     $(Self::$variant(params) => serde_json::to_value(params),)*
                 }
             }
+
+            /// Wire method of every notification, as sent in `method`.
+            pub fn methods() -> Vec<String> {
+                vec![$(server_notification_method!($variant $(, $wire)?),)*]
+            }
         }
 
         impl TryFrom<JSONRPCNotification> for ServerNotification {
@@ -708,10 +729,7 @@ server_notification_definitions! {
     /// Notifies the user of world-writable directories on Windows, which cannot be protected by the sandbox.
     WindowsWorldWritableWarning => "windows/worldWritableWarning" (v2::WindowsWorldWritableWarningNotification),
 
-    #[serde(rename = "account/login/completed")]
-    #[ts(rename = "account/login/completed")]
-    #[strum(serialize = "account/login/completed")]
-    AccountLoginCompleted(v2::AccountLoginCompletedNotification),
+    AccountLoginCompleted => "account/login/completed" (v2::AccountLoginCompletedNotification),
 
     /// DEPRECATED NOTIFICATIONS below
     AuthStatusChange(v1::AuthStatusChangeNotification),
@@ -811,6 +829,31 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn server_notification_methods_match_wire_names() -> Result<()> {
+        let notifications = [
+            ServerNotification::AuthStatusChange(v1::AuthStatusChangeNotification {
+                auth_method: None,
+            }),
+            ServerNotification::AccountLoginCompleted(v2::AccountLoginCompletedNotification {
+                login_id: None,
+                success: true,
+                error: None,
+            }),
+            ServerNotification::DeprecationNotice(v2::DeprecationNoticeNotification {
+                summary: "old".to_string(),
+                details: None,
+            }),
+        ];
+        let methods = ServerNotification::methods();
+        for notification in notifications {
+            let method = serde_json::to_value(&notification)?["method"].clone();
+            assert_eq!(json!(notification.to_string()), method);
+            assert!(methods.contains(&notification.to_string()), "{method}");
+        }
+        Ok(())
+    }
+
     #[test]
     fn serialize_server_request() -> Result<()> {
         let conversation_id = ThreadId::from_string("67e55044-10b1-426f-9247-bb680e5fe0c8")?;
@@ -58,10 +58,11 @@ impl ThreadHistoryBuilder {
             }
             EventMsg::ItemCompleted(payload) => self.handle_item_completed(payload),
             EventMsg::TokenCount(_) => {}
+            EventMsg::ModelSnapshot(_) | EventMsg::ModelFailover(_) => {}
             EventMsg::EnteredReviewMode(_) => {}
             EventMsg::ExitedReviewMode(_) => {}
             EventMsg::ThreadRolledBack(payload) => self.handle_thread_rollback(payload),
-            EventMsg::UndoCompleted(_) => {}
+            EventMsg::UndoCompleted(_) | EventMsg::CheckpointCreated(_) => {}
             EventMsg::TurnAborted(payload) => self.handle_turn_aborted(payload),
             _ => {}
         }
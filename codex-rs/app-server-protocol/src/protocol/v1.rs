@@ -29,6 +29,18 @@ use uuid::Uuid;
 use crate::protocol::common::AuthMode;
 use crate::protocol::common::GitSha;
 
+/// Version of the app-server protocol, exchanged during initialize. Bump it
+/// when existing methods or payloads change in ways older clients cannot
+/// handle or a new kind of `ThreadItem` is added, and give that item the new
+/// version in `ThreadItem::protocol_version`. New notifications and optional
+/// fields are covered by `InitializeCapabilities::notification_methods`
+/// instead.
+pub const APP_SERVER_PROTOCOL_VERSION: u32 = 1;
+
+/// Oldest protocol version the server still speaks, assumed for clients that
+/// do not send one.
+pub const MIN_APP_SERVER_PROTOCOL_VERSION: u32 = 1;
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 pub struct InitializeParams {
@@ -46,18 +58,34 @@ pub struct ClientInfo {
 }
 
 /// Client-declared capabilities negotiated during initialize.
-#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, JsonSchema, TS)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 pub struct InitializeCapabilities {
     /// Opt into receiving experimental API methods and fields.
     #[serde(default)]
     pub experimental_api: bool,
+    /// Protocol version the client was built against. Clients that predate
+    /// versioning omit it and are treated as version 1.
+    pub protocol_version: Option<u32>,
+    /// Server notification methods the client can handle. When set, the server
+    /// only sends these, so notifications added after the client was built
+    /// are dropped instead of reaching a client that cannot parse them.
+    pub notification_methods: Option<Vec<String>>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 pub struct InitializeResponse {
     pub user_agent: String,
+    /// Protocol version used on this connection: the client's
+    /// `protocolVersion`, which must not be newer than this server's.
+    pub protocol_version: u32,
+    /// Notification methods the server may send on this connection: every
+    /// method it knows, narrowed to the client's `notificationMethods` when
+    /// given.
+    pub notification_methods: Vec<String>,
+    /// Whether experimental API methods and fields are enabled.
+    pub experimental_api: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema, TS)]
@@ -18,6 +18,7 @@ use anyhow::bail;
 use clap::ArgAction;
 use clap::Parser;
 use clap::Subcommand;
+use codex_app_server_protocol::APP_SERVER_PROTOCOL_VERSION;
 use codex_app_server_protocol::AddConversationListenerParams;
 use codex_app_server_protocol::AddConversationSubscriptionResponse;
 use codex_app_server_protocol::AskForApproval;
@@ -515,6 +516,8 @@ impl CodexClient {
                 },
                 capabilities: Some(InitializeCapabilities {
                     experimental_api: true,
+                    protocol_version: Some(APP_SERVER_PROTOCOL_VERSION),
+                    notification_methods: None,
                 }),
             },
         };
@@ -135,6 +135,8 @@ pub(crate) async fn apply_bespoke_event_handling(
             changes,
             reason,
             grant_root,
+            hunks: _,
+            file_operations: _,
         }) => match api_version {
             ApiVersion::V1 => {
                 let params = ApplyPatchApprovalParams {
@@ -211,6 +213,7 @@ pub(crate) async fn apply_bespoke_event_handling(
             reason,
             proposed_execpolicy_amendment,
             parsed_cmd,
+            file_operation: _,
         }) => match api_version {
             ApiVersion::V1 => {
                 let params = ExecCommandApprovalParams {
@@ -157,6 +157,7 @@ use codex_core::config::types::McpServerTransportConfig;
 use codex_core::config_loader::CloudRequirementsLoader;
 use codex_core::default_client::get_codex_user_agent;
 use codex_core::error::CodexErr;
+use codex_core::exec::ExecLimits;
 use codex_core::exec::ExecParams;
 use codex_core::exec_env::create_env;
 use codex_core::features::Feature;
@@ -690,7 +691,7 @@ impl CodexMessageProcessor {
         }
 
         match login_with_api_key(
-            &self.config.codex_home,
+            &self.config.auth_home(),
             &params.api_key,
             self.config.cli_auth_credentials_store_mode,
         ) {
@@ -785,7 +786,7 @@ impl CodexMessageProcessor {
         Ok(LoginServerOptions {
             open_browser: false,
             ..LoginServerOptions::new(
-                config.codex_home.clone(),
+                config.auth_home(),
                 CLIENT_ID.to_string(),
                 config.forced_chatgpt_workspace_id.clone(),
                 config.cli_auth_credentials_store_mode,
@@ -1095,7 +1096,7 @@ impl CodexMessageProcessor {
         }
 
         if let Err(err) =
-            login_with_chatgpt_auth_tokens(&self.config.codex_home, &id_token, &access_token)
+            login_with_chatgpt_auth_tokens(&self.config.auth_home(), &id_token, &access_token)
         {
             let error = JSONRPCErrorError {
                 code: INTERNAL_ERROR_CODE,
@@ -1444,6 +1445,7 @@ impl CodexMessageProcessor {
             windows_sandbox_level,
             justification: None,
             arg0: None,
+            limits: ExecLimits::default(),
         };
 
         let requested_policy = params.sandbox_policy.map(|policy| policy.to_core());
@@ -2250,6 +2252,7 @@ impl CodexMessageProcessor {
             match read_event_msgs_from_rollout(rollout_path).await {
                 Ok(events) => {
                     thread.turns = build_turns_from_event_msgs(&events);
+                    self.outgoing.retain_supported_items(&mut thread.turns);
                 }
                 Err(err) => {
                     self.send_internal_error(
@@ -2477,6 +2480,7 @@ impl CodexMessageProcessor {
                 thread.turns = initial_messages
                     .as_deref()
                     .map_or_else(Vec::new, build_turns_from_event_msgs);
+                self.outgoing.retain_supported_items(&mut thread.turns);
 
                 let response = ThreadResumeResponse {
                     thread,
@@ -2689,6 +2693,7 @@ impl CodexMessageProcessor {
         thread.turns = initial_messages
             .as_deref()
             .map_or_else(Vec::new, build_turns_from_event_msgs);
+        self.outgoing.retain_supported_items(&mut thread.turns);
 
         let response = ThreadForkResponse {
             thread: thread.clone(),
@@ -4150,6 +4155,7 @@ impl CodexMessageProcessor {
                     summary: params.summary,
                     collaboration_mode: params.collaboration_mode,
                     personality: params.personality,
+                    verbosity: None,
                 })
                 .await;
         }
@@ -233,7 +233,7 @@ pub async fn run_main(
             }
 
             let auth_manager = AuthManager::shared(
-                config.codex_home.clone(),
+                config.auth_home(),
                 false,
                 config.cli_auth_credentials_store_mode,
             );
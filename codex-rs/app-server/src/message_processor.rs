@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
@@ -9,6 +10,7 @@ use crate::config_api::ConfigApi;
 use crate::error_code::INVALID_REQUEST_ERROR_CODE;
 use crate::outgoing_message::OutgoingMessageSender;
 use async_trait::async_trait;
+use codex_app_server_protocol::APP_SERVER_PROTOCOL_VERSION;
 use codex_app_server_protocol::ChatgptAuthTokensRefreshParams;
 use codex_app_server_protocol::ChatgptAuthTokensRefreshReason;
 use codex_app_server_protocol::ChatgptAuthTokensRefreshResponse;
@@ -19,12 +21,14 @@ use codex_app_server_protocol::ConfigReadParams;
 use codex_app_server_protocol::ConfigValueWriteParams;
 use codex_app_server_protocol::ConfigWarningNotification;
 use codex_app_server_protocol::ExperimentalApi;
+use codex_app_server_protocol::InitializeCapabilities;
 use codex_app_server_protocol::InitializeResponse;
 use codex_app_server_protocol::JSONRPCError;
 use codex_app_server_protocol::JSONRPCErrorError;
 use codex_app_server_protocol::JSONRPCNotification;
 use codex_app_server_protocol::JSONRPCRequest;
 use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::MIN_APP_SERVER_PROTOCOL_VERSION;
 use codex_app_server_protocol::RequestId;
 use codex_app_server_protocol::ServerNotification;
 use codex_app_server_protocol::ServerRequestPayload;
@@ -143,7 +147,7 @@ impl MessageProcessor {
         let outgoing = Arc::new(outgoing);
         let experimental_api_enabled = Arc::new(AtomicBool::new(false));
         let auth_manager = AuthManager::shared(
-            config.codex_home.clone(),
+            config.auth_home(),
             false,
             config.cli_auth_credentials_store_mode,
         );
@@ -225,10 +229,27 @@ impl MessageProcessor {
                     self.outgoing.send_error(request_id, error).await;
                     return;
                 } else {
-                    let experimental_api_enabled = params
-                        .capabilities
-                        .as_ref()
-                        .is_some_and(|cap| cap.experimental_api);
+                    let InitializeCapabilities {
+                        experimental_api: experimental_api_enabled,
+                        protocol_version: client_protocol_version,
+                        notification_methods: client_notification_methods,
+                    } = params.capabilities.unwrap_or_default();
+                    let protocol_version =
+                        client_protocol_version.unwrap_or(MIN_APP_SERVER_PROTOCOL_VERSION);
+                    if !(MIN_APP_SERVER_PROTOCOL_VERSION..=APP_SERVER_PROTOCOL_VERSION)
+                        .contains(&protocol_version)
+                    {
+                        let error = JSONRPCErrorError {
+                            code: INVALID_REQUEST_ERROR_CODE,
+                            message: format!(
+                                "Unsupported app-server protocol version {protocol_version}: this server supports versions {MIN_APP_SERVER_PROTOCOL_VERSION} through {APP_SERVER_PROTOCOL_VERSION}."
+                            ),
+                            data: None,
+                        };
+                        self.outgoing.send_error(request_id, error).await;
+                        return;
+                    }
+                    self.outgoing.set_protocol_version(protocol_version);
                     self.experimental_api_enabled
                         .store(experimental_api_enabled, Ordering::Relaxed);
                     let ClientInfo {
@@ -264,7 +285,18 @@ impl MessageProcessor {
                     }
 
                     let user_agent = get_codex_user_agent();
-                    let response = InitializeResponse { user_agent };
+                    let mut notification_methods = ServerNotification::methods();
+                    if let Some(client_methods) = client_notification_methods {
+                        let client_methods: HashSet<String> = client_methods.into_iter().collect();
+                        notification_methods.retain(|method| client_methods.contains(method));
+                        self.outgoing.restrict_notifications(client_methods);
+                    }
+                    let response = InitializeResponse {
+                        user_agent,
+                        protocol_version,
+                        notification_methods,
+                        experimental_api: experimental_api_enabled,
+                    };
                     self.outgoing.send_response(request_id, response).await;
 
                     self.initialized = true;
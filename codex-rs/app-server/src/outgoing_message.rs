@@ -1,17 +1,27 @@
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::OnceLock;
 use std::sync::atomic::AtomicI64;
 use std::sync::atomic::Ordering;
 
+use codex_app_server_protocol::APP_SERVER_PROTOCOL_VERSION;
+use codex_app_server_protocol::ItemCompletedNotification;
+use codex_app_server_protocol::ItemStartedNotification;
 use codex_app_server_protocol::JSONRPCErrorError;
 use codex_app_server_protocol::RequestId;
 use codex_app_server_protocol::Result;
 use codex_app_server_protocol::ServerNotification;
 use codex_app_server_protocol::ServerRequest;
 use codex_app_server_protocol::ServerRequestPayload;
+use codex_app_server_protocol::ThreadItem;
+use codex_app_server_protocol::Turn;
+use codex_app_server_protocol::TurnCompletedNotification;
+use codex_app_server_protocol::TurnStartedNotification;
 use serde::Serialize;
 use tokio::sync::Mutex;
 use tokio::sync::mpsc;
 use tokio::sync::oneshot;
+use tracing::debug;
 use tracing::warn;
 
 use crate::error_code::INTERNAL_ERROR_CODE;
@@ -24,6 +34,12 @@ pub(crate) struct OutgoingMessageSender {
     next_request_id: AtomicI64,
     sender: mpsc::Sender<OutgoingMessage>,
     request_id_to_callback: Mutex<HashMap<RequestId, oneshot::Sender<Result>>>,
+    /// Notification methods the client declared during initialize; others
+    /// are dropped. Unset means the client accepts everything.
+    client_notification_methods: OnceLock<HashSet<String>>,
+    /// Protocol version negotiated during initialize; items introduced in
+    /// later versions are withheld from the client.
+    protocol_version: OnceLock<u32>,
 }
 
 impl OutgoingMessageSender {
@@ -32,9 +48,52 @@ impl OutgoingMessageSender {
             next_request_id: AtomicI64::new(0),
             sender,
             request_id_to_callback: Mutex::new(HashMap::new()),
+            client_notification_methods: OnceLock::new(),
+            protocol_version: OnceLock::new(),
         }
     }
 
+    /// Records the protocol version negotiated for the rest of the connection.
+    pub(crate) fn set_protocol_version(&self, version: u32) {
+        if self.protocol_version.set(version).is_err() {
+            warn!("app-server protocol version was already negotiated");
+        }
+    }
+
+    fn client_supports_item(&self, item: &ThreadItem) -> bool {
+        let version = self
+            .protocol_version
+            .get()
+            .copied()
+            .unwrap_or(APP_SERVER_PROTOCOL_VERSION);
+        item.protocol_version() <= version
+    }
+
+    /// Drops items the client's protocol version does not know from `turns`.
+    pub(crate) fn retain_supported_items(&self, turns: &mut [Turn]) {
+        for turn in turns {
+            turn.items.retain(|item| self.client_supports_item(item));
+        }
+    }
+
+    /// Limits notifications to `methods` for the rest of the connection.
+    pub(crate) fn restrict_notifications(&self, methods: HashSet<String>) {
+        if self.client_notification_methods.set(methods).is_err() {
+            warn!("client notification methods were already negotiated");
+        }
+    }
+
+    fn client_accepts(&self, method: &str) -> bool {
+        let accepts = self
+            .client_notification_methods
+            .get()
+            .is_none_or(|methods| methods.contains(method));
+        if !accepts {
+            debug!("dropping {method} notification the client did not declare");
+        }
+        accepts
+    }
+
     pub(crate) async fn send_request(
         &self,
         request: ServerRequestPayload,
@@ -129,7 +188,24 @@ impl OutgoingMessageSender {
         }
     }
 
-    pub(crate) async fn send_server_notification(&self, notification: ServerNotification) {
+    pub(crate) async fn send_server_notification(&self, mut notification: ServerNotification) {
+        if !self.client_accepts(&notification.to_string()) {
+            return;
+        }
+        if let ServerNotification::ItemStarted(ItemStartedNotification { item, .. })
+        | ServerNotification::ItemCompleted(ItemCompletedNotification { item, .. }) =
+            &notification
+            && !self.client_supports_item(item)
+        {
+            debug!("dropping item the client's protocol version does not know");
+            return;
+        }
+        if let ServerNotification::TurnStarted(TurnStartedNotification { turn, .. })
+        | ServerNotification::TurnCompleted(TurnCompletedNotification { turn, .. }) =
+            &mut notification
+        {
+            turn.items.retain(|item| self.client_supports_item(item));
+        }
         if let Err(err) = self
             .sender
             .send(OutgoingMessage::AppServerNotification(notification))
@@ -142,6 +218,9 @@ impl OutgoingMessageSender {
     /// All notifications should be migrated to [`ServerNotification`] and
     /// [`OutgoingMessage::Notification`] should be removed.
     pub(crate) async fn send_notification(&self, notification: OutgoingNotification) {
+        if !self.client_accepts(&notification.method) {
+            return;
+        }
         let outgoing_message = OutgoingMessage::Notification(notification);
         if let Err(err) = self.sender.send(outgoing_message).await {
             warn!("failed to send notification to client: {err:?}");
@@ -336,4 +415,60 @@ mod tests {
             "ensure the notification serializes correctly"
         );
     }
+
+    #[tokio::test]
+    async fn notifications_the_client_did_not_declare_are_dropped() {
+        let (tx, mut rx) = mpsc::channel(8);
+        let outgoing = OutgoingMessageSender::new(tx);
+        outgoing.restrict_notifications(HashSet::from([
+            "account/login/completed".to_string(),
+            "codex/event/task_complete".to_string(),
+        ]));
+
+        outgoing
+            .send_server_notification(ServerNotification::ConfigWarning(
+                ConfigWarningNotification {
+                    summary: "Config error: using defaults".to_string(),
+                    details: None,
+                    path: None,
+                    range: None,
+                },
+            ))
+            .await;
+        outgoing
+            .send_notification(OutgoingNotification {
+                method: "codex/event/agent_message".to_string(),
+                params: None,
+            })
+            .await;
+        let login_completed =
+            ServerNotification::AccountLoginCompleted(AccountLoginCompletedNotification {
+                login_id: None,
+                success: true,
+                error: None,
+            });
+        outgoing
+            .send_server_notification(login_completed.clone())
+            .await;
+        let task_complete = OutgoingNotification {
+            method: "codex/event/task_complete".to_string(),
+            params: None,
+        };
+        outgoing.send_notification(task_complete.clone()).await;
+        drop(outgoing);
+
+        let mut sent = Vec::new();
+        while let Some(message) = rx.recv().await {
+            sent.push(serde_json::to_value(message).expect("message serializes"));
+        }
+        assert_eq!(
+            sent,
+            vec![
+                serde_json::to_value(OutgoingMessage::AppServerNotification(login_completed))
+                    .expect("message serializes"),
+                serde_json::to_value(OutgoingMessage::Notification(task_complete))
+                    .expect("message serializes"),
+            ]
+        );
+    }
 }
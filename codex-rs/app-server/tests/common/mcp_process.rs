@@ -170,6 +170,8 @@ impl McpProcess {
             client_info,
             Some(InitializeCapabilities {
                 experimental_api: true,
+                protocol_version: None,
+                notification_methods: None,
             }),
         )
         .await
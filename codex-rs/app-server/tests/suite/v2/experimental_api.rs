@@ -30,6 +30,8 @@ async fn mock_experimental_method_requires_experimental_api_capability() -> Resu
             default_client_info(),
             Some(InitializeCapabilities {
                 experimental_api: false,
+                protocol_version: None,
+                notification_methods: None,
             }),
         )
         .await?;
@@ -61,6 +63,8 @@ async fn thread_start_mock_field_requires_experimental_api_capability() -> Resul
             default_client_info(),
             Some(InitializeCapabilities {
                 experimental_api: false,
+                protocol_version: None,
+                notification_methods: None,
             }),
         )
         .await?;
@@ -97,6 +101,8 @@ async fn thread_start_without_dynamic_tools_allows_without_experimental_api_capa
             default_client_info(),
             Some(InitializeCapabilities {
                 experimental_api: false,
+                protocol_version: None,
+                notification_methods: None,
             }),
         )
         .await?;
@@ -2,9 +2,12 @@ use anyhow::Result;
 use app_test_support::McpProcess;
 use app_test_support::create_mock_responses_server_sequence_unchecked;
 use app_test_support::to_response;
+use codex_app_server_protocol::APP_SERVER_PROTOCOL_VERSION;
 use codex_app_server_protocol::ClientInfo;
+use codex_app_server_protocol::InitializeCapabilities;
 use codex_app_server_protocol::InitializeResponse;
 use codex_app_server_protocol::JSONRPCMessage;
+use codex_app_server_protocol::MIN_APP_SERVER_PROTOCOL_VERSION;
 use pretty_assertions::assert_eq;
 use std::path::Path;
 use tempfile::TempDir;
@@ -33,7 +36,7 @@ async fn initialize_uses_client_info_name_as_originator() -> Result<()> {
     let JSONRPCMessage::Response(response) = message else {
         anyhow::bail!("expected initialize response, got {message:?}");
     };
-    let InitializeResponse { user_agent } = to_response::<InitializeResponse>(response)?;
+    let InitializeResponse { user_agent, .. } = to_response::<InitializeResponse>(response)?;
 
     assert!(user_agent.starts_with("codex_vscode/"));
     Ok(())
@@ -67,7 +70,7 @@ async fn initialize_respects_originator_override_env_var() -> Result<()> {
     let JSONRPCMessage::Response(response) = message else {
         anyhow::bail!("expected initialize response, got {message:?}");
     };
-    let InitializeResponse { user_agent } = to_response::<InitializeResponse>(response)?;
+    let InitializeResponse { user_agent, .. } = to_response::<InitializeResponse>(response)?;
 
     assert!(user_agent.starts_with("codex_originator_via_env_var/"));
     Ok(())
@@ -108,6 +111,89 @@ async fn initialize_rejects_invalid_client_name() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn initialize_negotiates_protocol_version_and_notification_methods() -> Result<()> {
+    let responses = Vec::new();
+    let server = create_mock_responses_server_sequence_unchecked(responses).await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri(), "never")?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+
+    let message = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.initialize_with_capabilities(
+            ClientInfo {
+                name: "codex_vscode".to_string(),
+                title: None,
+                version: "0.1.0".to_string(),
+            },
+            Some(InitializeCapabilities {
+                experimental_api: false,
+                protocol_version: Some(APP_SERVER_PROTOCOL_VERSION),
+                notification_methods: Some(vec![
+                    "turn/started".to_string(),
+                    "item/futureKind/delta".to_string(),
+                ]),
+            }),
+        ),
+    )
+    .await??;
+
+    let JSONRPCMessage::Response(response) = message else {
+        anyhow::bail!("expected initialize response, got {message:?}");
+    };
+    let InitializeResponse {
+        protocol_version,
+        notification_methods,
+        experimental_api,
+        ..
+    } = to_response::<InitializeResponse>(response)?;
+
+    assert_eq!(protocol_version, APP_SERVER_PROTOCOL_VERSION);
+    assert_eq!(notification_methods, vec!["turn/started".to_string()]);
+    assert!(!experimental_api);
+    Ok(())
+}
+
+#[tokio::test]
+async fn initialize_rejects_protocol_version_newer_than_server() -> Result<()> {
+    let responses = Vec::new();
+    let server = create_mock_responses_server_sequence_unchecked(responses).await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri(), "never")?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+
+    let client_version = APP_SERVER_PROTOCOL_VERSION + 1;
+    let message = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.initialize_with_capabilities(
+            ClientInfo {
+                name: "codex_vscode".to_string(),
+                title: None,
+                version: "0.1.0".to_string(),
+            },
+            Some(InitializeCapabilities {
+                experimental_api: false,
+                protocol_version: Some(client_version),
+                notification_methods: None,
+            }),
+        ),
+    )
+    .await??;
+
+    let JSONRPCMessage::Error(error) = message else {
+        anyhow::bail!("expected initialize error, got {message:?}");
+    };
+    assert_eq!(error.error.code, -32600);
+    assert_eq!(
+        error.error.message,
+        format!(
+            "Unsupported app-server protocol version {client_version}: this server supports versions {MIN_APP_SERVER_PROTOCOL_VERSION} through {APP_SERVER_PROTOCOL_VERSION}."
+        )
+    );
+    Ok(())
+}
+
 // Helper to create a config.toml pointing at the mock model server.
 fn create_config_toml(
     codex_home: &Path,
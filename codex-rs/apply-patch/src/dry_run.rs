@@ -0,0 +1,210 @@
+//! Check where each hunk of a patch would apply without touching the files.
+//!
+//! A dry run locates every chunk the same way [`crate::apply_patch`] does, but
+//! keeps going past chunks it cannot place so the caller sees every conflict at
+//! once, along with the chunks that only matched fuzzily (see [`HunkMatch`]).
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::ApplyPatchError;
+use crate::ChunkConflict;
+use crate::Hunk;
+use crate::HunkMatch;
+use crate::locate_chunk;
+use crate::parse_patch;
+use crate::split_lines;
+
+/// Whether one hunk of a patch would apply.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HunkStatus {
+    /// The hunk's lines were found verbatim.
+    Clean,
+    /// The hunk was found only after relaxing whitespace or punctuation,
+    /// dropping context lines, or ignoring its `@@` line.
+    Fuzzy(HunkMatch),
+    /// The hunk cannot be applied; the string says why.
+    Conflict(String),
+}
+
+/// Dry-run result for one hunk of a patch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HunkReport {
+    /// Absolute path of the file the hunk edits.
+    pub path: PathBuf,
+    /// 1-based position of the hunk among the hunks for `path`.
+    pub hunk: usize,
+    /// 1-based line of the original file where the hunk applies, if it does.
+    pub line: Option<usize>,
+    pub status: HunkStatus,
+}
+
+/// Per-hunk results of [`dry_run_patch`], in patch order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PatchDryRun {
+    pub hunks: Vec<HunkReport>,
+}
+
+impl PatchDryRun {
+    /// True when every hunk applies verbatim.
+    pub fn is_clean(&self) -> bool {
+        self.hunks
+            .iter()
+            .all(|hunk| hunk.status == HunkStatus::Clean)
+    }
+
+    pub fn has_conflicts(&self) -> bool {
+        self.hunks
+            .iter()
+            .any(|hunk| matches!(hunk.status, HunkStatus::Conflict(_)))
+    }
+
+    /// One line per hunk, with paths shown relative to `cwd`.
+    pub fn summary(&self, cwd: &Path) -> String {
+        self.hunks
+            .iter()
+            .map(|report| {
+                let path = report.path.strip_prefix(cwd).unwrap_or(&report.path);
+                let at = report
+                    .line
+                    .map(|line| format!(" at line {line}"))
+                    .unwrap_or_default();
+                let outcome = match &report.status {
+                    HunkStatus::Clean => format!("applies cleanly{at}"),
+                    HunkStatus::Fuzzy(how) => format!("matched {}{at}", how.description()),
+                    HunkStatus::Conflict(reason) => format!("conflicts: {reason}"),
+                };
+                format!("{}: hunk {} {outcome}", path.display(), report.hunk)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Report, hunk by hunk, whether `patch` would apply to the files under `cwd`
+/// with the given fuzz factor. Only a patch that fails to parse is an error;
+/// hunks that cannot be placed are reported as conflicts.
+pub fn dry_run_patch(patch: &str, cwd: &Path, fuzz: usize) -> Result<PatchDryRun, ApplyPatchError> {
+    let args = parse_patch(patch)?;
+    let cwd = match &args.workdir {
+        Some(workdir) => cwd.join(workdir),
+        None => cwd.to_path_buf(),
+    };
+    let mut hunks = Vec::new();
+    for hunk in &args.hunks {
+        let path = hunk.resolve_path(&cwd);
+        match hunk {
+            Hunk::AddFile { .. } => hunks.push(HunkReport {
+                path,
+                hunk: 1,
+                line: None,
+                status: HunkStatus::Clean,
+            }),
+            Hunk::DeleteFile { .. } => {
+                let status = if path.is_file() {
+                    HunkStatus::Clean
+                } else {
+                    HunkStatus::Conflict("file to delete does not exist".to_string())
+                };
+                hunks.push(HunkReport {
+                    path,
+                    hunk: 1,
+                    line: None,
+                    status,
+                });
+            }
+            Hunk::UpdateFile { chunks, .. } => {
+                let contents = match std::fs::read_to_string(&path) {
+                    Ok(contents) => contents,
+                    Err(err) => {
+                        hunks.push(HunkReport {
+                            path,
+                            hunk: 1,
+                            line: None,
+                            status: HunkStatus::Conflict(format!("cannot read file: {err}")),
+                        });
+                        continue;
+                    }
+                };
+                let original_lines = split_lines(&contents);
+                let mut line_index = 0;
+                for (index, chunk) in chunks.iter().enumerate() {
+                    let (line, status) =
+                        match locate_chunk(&original_lines, chunk, line_index, fuzz) {
+                            Ok(found) => {
+                                line_index = found.next_index;
+                                let status = if found.how.is_exact() {
+                                    HunkStatus::Clean
+                                } else {
+                                    HunkStatus::Fuzzy(found.how)
+                                };
+                                (Some(found.start + 1), status)
+                            }
+                            Err(ChunkConflict::MissingContext(context)) => (
+                                None,
+                                HunkStatus::Conflict(format!("context `{context}` not found")),
+                            ),
+                            Err(ChunkConflict::MissingLines) => (
+                                None,
+                                HunkStatus::Conflict("expected lines not found".to_string()),
+                            ),
+                        };
+                    hunks.push(HunkReport {
+                        path: path.clone(),
+                        hunk: index + 1,
+                        line,
+                        status,
+                    });
+                }
+            }
+        }
+    }
+    Ok(PatchDryRun { hunks })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn reports_clean_fuzzy_and_conflicting_hunks() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("lib.rs"),
+            "fn a() {\n    one();\n}\n\nfn b() {\n    two();  \n}\n",
+        )
+        .unwrap();
+        let patch = "*** Begin Patch
+*** Update File: lib.rs
+@@ fn a() {
+-    one();
++    uno();
+@@
+-    two();
++    dos();
+@@
+-    three();
++    tres();
+*** Delete File: missing.rs
+*** End Patch";
+
+        let report = dry_run_patch(patch, dir.path(), 0).unwrap();
+        assert!(report.has_conflicts());
+        assert!(!report.is_clean());
+        assert_eq!(
+            report.summary(dir.path()),
+            "lib.rs: hunk 1 applies cleanly at line 2\n\
+             lib.rs: hunk 2 matched ignoring trailing whitespace at line 6\n\
+             lib.rs: hunk 3 conflicts: expected lines not found\n\
+             missing.rs: hunk 1 conflicts: file to delete does not exist"
+        );
+        // A dry run leaves the file untouched.
+        assert_eq!(
+            fs::read_to_string(dir.path().join("lib.rs")).unwrap(),
+            "fn a() {\n    one();\n}\n\nfn b() {\n    two();  \n}\n"
+        );
+    }
+}
@@ -0,0 +1,173 @@
+//! Drop individual hunks from a patch, for when the user approves only part
+//! of it.
+//!
+//! Hunks are numbered the way [`crate::dry_run_patch`] reports them: each file
+//! section counts its `@@` chunks from 1, and an added or deleted file is a
+//! single hunk.
+
+use std::path::Path;
+
+use crate::Hunk;
+use crate::ParseError;
+use crate::UpdateFileChunk;
+use crate::parse_patch;
+
+/// Re-render `patch` keeping only the hunks for which `keep(path, hunk)`
+/// returns true, where `path` is resolved against `cwd` and `hunk` is 1-based.
+/// A file section that loses all of its hunks is dropped; `None` means no hunk
+/// was kept.
+pub fn filter_patch(
+    patch: &str,
+    cwd: &Path,
+    keep: impl Fn(&Path, usize) -> bool,
+) -> Result<Option<String>, ParseError> {
+    let args = parse_patch(patch)?;
+    let cwd = match &args.workdir {
+        Some(workdir) => cwd.join(workdir),
+        None => cwd.to_path_buf(),
+    };
+    let mut sections = Vec::new();
+    for hunk in &args.hunks {
+        let path = hunk.resolve_path(&cwd);
+        match hunk {
+            Hunk::AddFile {
+                path: relative,
+                contents,
+            } => {
+                if keep(&path, 1) {
+                    let mut section = format!("*** Add File: {}\n", relative.display());
+                    for line in contents.lines() {
+                        section.push_str(&format!("+{line}\n"));
+                    }
+                    sections.push(section);
+                }
+            }
+            Hunk::DeleteFile { path: relative } => {
+                if keep(&path, 1) {
+                    sections.push(format!("*** Delete File: {}\n", relative.display()));
+                }
+            }
+            Hunk::UpdateFile {
+                path: relative,
+                move_path,
+                chunks,
+            } => {
+                let kept: Vec<&UpdateFileChunk> = chunks
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| keep(&path, index + 1))
+                    .map(|(_, chunk)| chunk)
+                    .collect();
+                if kept.is_empty() {
+                    continue;
+                }
+                let mut section = format!("*** Update File: {}\n", relative.display());
+                if let Some(move_path) = move_path {
+                    section.push_str(&format!("*** Move to: {}\n", move_path.display()));
+                }
+                for chunk in kept {
+                    render_chunk(chunk, &mut section);
+                }
+                sections.push(section);
+            }
+        }
+    }
+    if sections.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(format!(
+        "*** Begin Patch\n{}*** End Patch",
+        sections.concat()
+    )))
+}
+
+/// Lines shared by the start and end of `old_lines` and `new_lines` become
+/// context; whatever lies between is removed and re-added, which parses back
+/// to the same chunk.
+fn render_chunk(chunk: &UpdateFileChunk, out: &mut String) {
+    match &chunk.change_context {
+        Some(context) => out.push_str(&format!("@@ {context}\n")),
+        None => out.push_str("@@\n"),
+    }
+    let (old, new) = (&chunk.old_lines, &chunk.new_lines);
+    let prefix = old
+        .iter()
+        .zip(new.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    for line in &old[..prefix] {
+        out.push_str(&format!(" {line}\n"));
+    }
+    for line in &old[prefix..old.len() - suffix] {
+        out.push_str(&format!("-{line}\n"));
+    }
+    for line in &new[prefix..new.len() - suffix] {
+        out.push_str(&format!("+{line}\n"));
+    }
+    for line in &old[old.len() - suffix..] {
+        out.push_str(&format!(" {line}\n"));
+    }
+    if chunk.is_end_of_file {
+        out.push_str("*** End of File\n");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn keeps_only_accepted_hunks() {
+        let cwd = Path::new("/repo");
+        let patch = "*** Begin Patch
+*** Add File: new.txt
++hello
+*** Update File: lib.rs
+@@ fn a() {
+ let x = 1;
+-    one();
++    uno();
+ }
+@@
+-    two();
++    dos();
+*** End of File
+*** Delete File: old.rs
+*** End Patch";
+
+        let filtered = filter_patch(patch, cwd, |path, hunk| {
+            !((path == Path::new("/repo/lib.rs") && hunk == 1) || path.ends_with("old.rs"))
+        })
+        .unwrap();
+        assert_eq!(
+            filtered.as_deref(),
+            Some(
+                "*** Begin Patch
+*** Add File: new.txt
++hello
+*** Update File: lib.rs
+@@
+-    two();
++    dos();
+*** End of File
+*** End Patch"
+            )
+        );
+
+        // Rendering every hunk round-trips to the same parsed patch.
+        let all = filter_patch(patch, cwd, |_, _| true).unwrap().unwrap();
+        assert_eq!(
+            parse_patch(&all).unwrap().hunks,
+            parse_patch(patch).unwrap().hunks
+        );
+
+        assert_eq!(filter_patch(patch, cwd, |_, _| false).unwrap(), None);
+    }
+}
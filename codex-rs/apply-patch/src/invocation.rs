@@ -13,12 +13,13 @@ use crate::ApplyPatchArgs;
 use crate::ApplyPatchError;
 use crate::ApplyPatchFileChange;
 use crate::ApplyPatchFileUpdate;
+use crate::DEFAULT_FUZZ_FACTOR;
 use crate::IoError;
 use crate::MaybeApplyPatchVerified;
 use crate::parser::Hunk;
 use crate::parser::ParseError;
 use crate::parser::parse_patch;
-use crate::unified_diff_from_chunks;
+use crate::unified_diff_with_fuzz;
 use std::str::Utf8Error;
 use tree_sitter::LanguageError;
 
@@ -130,6 +131,16 @@ pub fn maybe_parse_apply_patch(argv: &[String]) -> MaybeApplyPatch {
 /// cwd must be an absolute path so that we can resolve relative paths in the
 /// patch.
 pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApplyPatchVerified {
+    maybe_parse_apply_patch_verified_with_fuzz(argv, cwd, DEFAULT_FUZZ_FACTOR)
+}
+
+/// Like [`maybe_parse_apply_patch_verified`], locating hunks with the given
+/// fuzz factor.
+pub fn maybe_parse_apply_patch_verified_with_fuzz(
+    argv: &[String],
+    cwd: &Path,
+    fuzz: usize,
+) -> MaybeApplyPatchVerified {
     // Detect a raw patch body passed directly as the command or as the body of a shell
     // script. In these cases, report an explicit error rather than applying the patch.
     if let [body] = argv
@@ -187,7 +198,7 @@ pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApp
                         let ApplyPatchFileUpdate {
                             unified_diff,
                             content: contents,
-                        } = match unified_diff_from_chunks(&path, &chunks) {
+                        } = match unified_diff_with_fuzz(&path, &chunks, 1, fuzz) {
                             Ok(diff) => diff,
                             Err(e) => {
                                 return MaybeApplyPatchVerified::CorrectnessError(e);
@@ -371,6 +382,7 @@ fn extract_apply_patch_from_bash(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::unified_diff_from_chunks;
     use assert_matches::assert_matches;
     use pretty_assertions::assert_eq;
     use std::fs;
@@ -1,3 +1,5 @@
+mod dry_run;
+mod filter;
 mod invocation;
 mod parser;
 mod seek_sequence;
@@ -17,11 +19,23 @@ pub use parser::parse_patch;
 use similar::TextDiff;
 use thiserror::Error;
 
+pub use dry_run::HunkReport;
+pub use dry_run::HunkStatus;
+pub use dry_run::PatchDryRun;
+pub use dry_run::dry_run_patch;
+pub use filter::filter_patch;
 pub use invocation::maybe_parse_apply_patch_verified;
+pub use invocation::maybe_parse_apply_patch_verified_with_fuzz;
+pub use seek_sequence::MatchKind;
 pub use standalone_executable::main;
 
 use crate::invocation::ExtractHeredocError;
 
+/// Number of context lines a hunk may drop from each end when its full context
+/// is not found, and whether a hunk may be located without its `@@` context
+/// line when that line has drifted. Zero requires every context line to match.
+pub const DEFAULT_FUZZ_FACTOR: usize = 2;
+
 /// Detailed instructions for gpt-4.1 on how to use the `apply_patch` tool.
 pub const APPLY_PATCH_TOOL_INSTRUCTIONS: &str = include_str!("../apply_patch_tool_instructions.md");
 
@@ -175,6 +189,16 @@ pub fn apply_patch(
     patch: &str,
     stdout: &mut impl std::io::Write,
     stderr: &mut impl std::io::Write,
+) -> Result<(), ApplyPatchError> {
+    apply_patch_with_fuzz(patch, DEFAULT_FUZZ_FACTOR, stdout, stderr)
+}
+
+/// Like [`apply_patch`], locating hunks with the given fuzz factor.
+pub fn apply_patch_with_fuzz(
+    patch: &str,
+    fuzz: usize,
+    stdout: &mut impl std::io::Write,
+    stderr: &mut impl std::io::Write,
 ) -> Result<(), ApplyPatchError> {
     let hunks = match parse_patch(patch) {
         Ok(source) => source.hunks,
@@ -198,7 +222,7 @@ pub fn apply_patch(
         }
     };
 
-    apply_hunks(&hunks, stdout, stderr)?;
+    apply_hunks_with_fuzz(&hunks, fuzz, stdout, stderr)?;
 
     Ok(())
 }
@@ -208,6 +232,15 @@ pub fn apply_hunks(
     hunks: &[Hunk],
     stdout: &mut impl std::io::Write,
     stderr: &mut impl std::io::Write,
+) -> Result<(), ApplyPatchError> {
+    apply_hunks_with_fuzz(hunks, DEFAULT_FUZZ_FACTOR, stdout, stderr)
+}
+
+fn apply_hunks_with_fuzz(
+    hunks: &[Hunk],
+    fuzz: usize,
+    stdout: &mut impl std::io::Write,
+    stderr: &mut impl std::io::Write,
 ) -> Result<(), ApplyPatchError> {
     let _existing_paths: Vec<&Path> = hunks
         .iter()
@@ -236,7 +269,7 @@ pub fn apply_hunks(
         .collect::<Vec<&Path>>();
 
     // Delegate to a helper that applies each hunk to the filesystem.
-    match apply_hunks_to_files(hunks) {
+    match apply_hunks_to_files(hunks, fuzz) {
         Ok(affected) => {
             print_summary(&affected, stdout).map_err(ApplyPatchError::from)?;
             Ok(())
@@ -267,7 +300,7 @@ pub struct AffectedPaths {
 
 /// Apply the hunks to the filesystem, returning which files were added, modified, or deleted.
 /// Returns an error if the patch could not be applied.
-fn apply_hunks_to_files(hunks: &[Hunk]) -> anyhow::Result<AffectedPaths> {
+fn apply_hunks_to_files(hunks: &[Hunk], fuzz: usize) -> anyhow::Result<AffectedPaths> {
     if hunks.is_empty() {
         anyhow::bail!("No files were modified.");
     }
@@ -300,7 +333,7 @@ fn apply_hunks_to_files(hunks: &[Hunk]) -> anyhow::Result<AffectedPaths> {
                 chunks,
             } => {
                 let AppliedPatch { new_contents, .. } =
-                    derive_new_contents_from_chunks(path, chunks)?;
+                    derive_new_contents_from_chunks(path, chunks, fuzz)?;
                 if let Some(dest) = move_path {
                     if let Some(parent) = dest.parent()
                         && !parent.as_os_str().is_empty()
@@ -339,6 +372,7 @@ struct AppliedPatch {
 fn derive_new_contents_from_chunks(
     path: &Path,
     chunks: &[UpdateFileChunk],
+    fuzz: usize,
 ) -> std::result::Result<AppliedPatch, ApplyPatchError> {
     let original_contents = match std::fs::read_to_string(path) {
         Ok(contents) => contents,
@@ -350,15 +384,8 @@ fn derive_new_contents_from_chunks(
         }
     };
 
-    let mut original_lines: Vec<String> = original_contents.split('\n').map(String::from).collect();
-
-    // Drop the trailing empty element that results from the final newline so
-    // that line counts match the behaviour of standard `diff`.
-    if original_lines.last().is_some_and(String::is_empty) {
-        original_lines.pop();
-    }
-
-    let replacements = compute_replacements(&original_lines, path, chunks)?;
+    let original_lines = split_lines(&original_contents);
+    let replacements = compute_replacements(&original_lines, path, chunks, fuzz)?;
     let new_lines = apply_replacements(original_lines, &replacements);
     let mut new_lines = new_lines;
     if !new_lines.last().is_some_and(String::is_empty) {
@@ -371,6 +398,17 @@ fn derive_new_contents_from_chunks(
     })
 }
 
+/// Split file contents into lines, dropping the trailing empty element that
+/// results from the final newline so that line counts match the behaviour of
+/// standard `diff`.
+fn split_lines(contents: &str) -> Vec<String> {
+    let mut lines: Vec<String> = contents.split('\n').map(String::from).collect();
+    if lines.last().is_some_and(String::is_empty) {
+        lines.pop();
+    }
+    lines
+}
+
 /// Compute a list of replacements needed to transform `original_lines` into the
 /// new lines, given the patch `chunks`. Each replacement is returned as
 /// `(start_index, old_len, new_lines)`.
@@ -378,90 +416,275 @@ fn compute_replacements(
     original_lines: &[String],
     path: &Path,
     chunks: &[UpdateFileChunk],
+    fuzz: usize,
 ) -> std::result::Result<Vec<(usize, usize, Vec<String>)>, ApplyPatchError> {
     let mut replacements: Vec<(usize, usize, Vec<String>)> = Vec::new();
     let mut line_index: usize = 0;
 
     for chunk in chunks {
-        // If a chunk has a `change_context`, we use seek_sequence to find it, then
-        // adjust our `line_index` to continue from there.
-        if let Some(ctx_line) = &chunk.change_context {
-            if let Some(idx) = seek_sequence::seek_sequence(
-                original_lines,
-                std::slice::from_ref(ctx_line),
-                line_index,
-                false,
-            ) {
-                line_index = idx + 1;
-            } else {
+        match locate_chunk(original_lines, chunk, line_index, fuzz) {
+            Ok(found) => {
+                line_index = found.next_index;
+                replacements.push((found.start, found.old_len, found.new_lines));
+            }
+            Err(ChunkConflict::MissingContext(ctx_line)) => {
                 return Err(ApplyPatchError::ComputeReplacements(format!(
                     "Failed to find context '{}' in {}",
                     ctx_line,
                     path.display()
                 )));
             }
+            Err(ChunkConflict::MissingLines) => {
+                return Err(ApplyPatchError::ComputeReplacements(format!(
+                    "Failed to find expected lines in {}:\n{}",
+                    path.display(),
+                    chunk.old_lines.join("\n"),
+                )));
+            }
         }
+    }
 
-        if chunk.old_lines.is_empty() {
-            // Pure addition (no old lines). We'll add them at the end or just
-            // before the final empty line if one exists.
-            let insertion_idx = if original_lines.last().is_some_and(String::is_empty) {
-                original_lines.len() - 1
-            } else {
-                original_lines.len()
-            };
-            replacements.push((insertion_idx, 0, chunk.new_lines.clone()));
-            continue;
-        }
+    replacements.sort_by(|(lhs_idx, _, _), (rhs_idx, _, _)| lhs_idx.cmp(rhs_idx));
 
-        // Otherwise, try to match the existing lines in the file with the old lines
-        // from the chunk. If found, schedule that region for replacement.
-        // Attempt to locate the `old_lines` verbatim within the file.  In many
-        // real‑world diffs the last element of `old_lines` is an *empty* string
-        // representing the terminating newline of the region being replaced.
-        // This sentinel is not present in `original_lines` because we strip the
-        // trailing empty slice emitted by `split('\n')`.  If a direct search
-        // fails and the pattern ends with an empty string, retry without that
-        // final element so that modifications touching the end‑of‑file can be
-        // located reliably.
-
-        let mut pattern: &[String] = &chunk.old_lines;
-        let mut found =
-            seek_sequence::seek_sequence(original_lines, pattern, line_index, chunk.is_end_of_file);
-
-        let mut new_slice: &[String] = &chunk.new_lines;
-
-        if found.is_none() && pattern.last().is_some_and(String::is_empty) {
-            // Retry without the trailing empty line which represents the final
-            // newline in the file.
-            pattern = &pattern[..pattern.len() - 1];
-            if new_slice.last().is_some_and(String::is_empty) {
-                new_slice = &new_slice[..new_slice.len() - 1];
-            }
+    Ok(replacements)
+}
+
+/// How a hunk was located in the file it edits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HunkMatch {
+    /// The most lenient line comparison needed.
+    pub kind: MatchKind,
+    /// Context lines dropped from each end of the hunk to find it.
+    pub fuzz: usize,
+    /// The hunk's `@@` context line was missing or did not lead to the hunk,
+    /// so the hunk was located without it.
+    pub context_ignored: bool,
+}
+
+impl HunkMatch {
+    const EXACT: HunkMatch = HunkMatch {
+        kind: MatchKind::Exact,
+        fuzz: 0,
+        context_ignored: false,
+    };
 
-            found = seek_sequence::seek_sequence(
-                original_lines,
-                pattern,
-                line_index,
-                chunk.is_end_of_file,
-            );
+    pub fn is_exact(&self) -> bool {
+        *self == Self::EXACT
+    }
+
+    /// How the hunk matched, e.g. "ignoring indentation, with fuzz 1".
+    pub fn description(&self) -> String {
+        let mut parts = Vec::new();
+        if self.kind != MatchKind::Exact {
+            parts.push(self.kind.description().to_string());
+        }
+        if self.fuzz > 0 {
+            parts.push(format!("with fuzz {}", self.fuzz));
+        }
+        if self.context_ignored {
+            parts.push("without its @@ context line".to_string());
         }
+        if parts.is_empty() {
+            MatchKind::Exact.description().to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+}
+
+/// Where a single update chunk applies within the original file.
+struct ChunkMatch {
+    start: usize,
+    old_len: usize,
+    new_lines: Vec<String>,
+    how: HunkMatch,
+    /// Line from which the next chunk is searched.
+    next_index: usize,
+}
+
+/// Why a chunk could not be located.
+enum ChunkConflict {
+    /// The `@@` context line was not found.
+    MissingContext(String),
+    /// The lines the chunk removes or keeps were not found.
+    MissingLines,
+}
+
+/// Locate `chunk` within `original_lines`, searching from `line_index`.
+///
+/// With a non-zero `fuzz`, a chunk whose lines are not found verbatim may drop
+/// up to `fuzz` context lines from each end, and a chunk whose `@@` context
+/// line is missing (or sits below the chunk) is searched for from
+/// `line_index` without it. Both fallbacks only accept a match that is unique.
+fn locate_chunk(
+    original_lines: &[String],
+    chunk: &UpdateFileChunk,
+    line_index: usize,
+    fuzz: usize,
+) -> std::result::Result<ChunkMatch, ChunkConflict> {
+    // If a chunk has a `change_context`, we use seek_sequence to find it, then
+    // continue the search from there.
+    let mut context = Some((line_index, MatchKind::Exact));
+    if let Some(ctx_line) = &chunk.change_context {
+        context = seek_sequence::seek_sequence(
+            original_lines,
+            std::slice::from_ref(ctx_line),
+            line_index,
+            false,
+        )
+        .map(|(idx, kind)| (idx + 1, kind));
+    }
 
-        if let Some(start_idx) = found {
-            replacements.push((start_idx, pattern.len(), new_slice.to_vec()));
-            line_index = start_idx + pattern.len();
+    if chunk.old_lines.is_empty() {
+        let Some((next_index, kind)) = context else {
+            return Err(ChunkConflict::MissingContext(
+                chunk.change_context.clone().unwrap_or_default(),
+            ));
+        };
+        // Pure addition (no old lines). We'll add them at the end or just
+        // before the final empty line if one exists.
+        let insertion_idx = if original_lines.last().is_some_and(String::is_empty) {
+            original_lines.len() - 1
         } else {
-            return Err(ApplyPatchError::ComputeReplacements(format!(
-                "Failed to find expected lines in {}:\n{}",
-                path.display(),
-                chunk.old_lines.join("\n"),
-            )));
+            original_lines.len()
+        };
+        return Ok(ChunkMatch {
+            start: insertion_idx,
+            old_len: 0,
+            new_lines: chunk.new_lines.clone(),
+            how: HunkMatch {
+                kind,
+                ..HunkMatch::EXACT
+            },
+            next_index,
+        });
+    }
+
+    if let Some((search_from, context_kind)) = context
+        && let Some(mut found) = find_chunk_lines(original_lines, chunk, search_from, fuzz, false)
+    {
+        found.how.kind = found.how.kind.max(context_kind);
+        return Ok(found);
+    }
+    // The `@@` line may have been edited, or the chunk's lines may have
+    // drifted above it; try to place the chunk on its own lines.
+    if fuzz > 0
+        && chunk.change_context.is_some()
+        && let Some(found) = find_chunk_lines(original_lines, chunk, line_index, fuzz, true)
+    {
+        return Ok(found);
+    }
+    match (&chunk.change_context, context) {
+        (Some(ctx_line), None) => Err(ChunkConflict::MissingContext(ctx_line.clone())),
+        _ => Err(ChunkConflict::MissingLines),
+    }
+}
+
+/// Find the chunk's old lines at or after `from`, dropping up to `fuzz`
+/// context lines from each end if they are not found whole. Matches found
+/// with fuzz, or without the chunk's `@@` line (`context_ignored`), must be
+/// unique.
+fn find_chunk_lines(
+    original_lines: &[String],
+    chunk: &UpdateFileChunk,
+    from: usize,
+    fuzz: usize,
+    context_ignored: bool,
+) -> Option<ChunkMatch> {
+    let old = chunk.old_lines.as_slice();
+    let new = chunk.new_lines.as_slice();
+    let (leading, trailing) = shared_context(old, new);
+    // Chunks anchored to the end of the file keep their trailing lines.
+    let trailing = if chunk.is_end_of_file { 0 } else { trailing };
+
+    let mut dropped = None;
+    for level in 0..=fuzz {
+        let lead = level.min(leading);
+        let trail = level.min(trailing);
+        if dropped == Some((lead, trail)) {
+            break;
+        }
+        dropped = Some((lead, trail));
+        let pattern = &old[lead..old.len() - trail];
+        if pattern.is_empty() {
+            break;
         }
+        let Some((start, pattern, new_slice, kind)) = seek_lines(
+            original_lines,
+            pattern,
+            &new[lead..new.len() - trail],
+            from,
+            chunk.is_end_of_file,
+        ) else {
+            continue;
+        };
+        if (level > 0 || context_ignored)
+            && seek_sequence::seek_sequence(original_lines, pattern, start + 1, false).is_some()
+        {
+            // Ambiguous; dropping more context would not help.
+            return None;
+        }
+        return Some(ChunkMatch {
+            start,
+            old_len: pattern.len(),
+            new_lines: new_slice.to_vec(),
+            how: HunkMatch {
+                kind,
+                fuzz: level,
+                context_ignored,
+            },
+            next_index: start + pattern.len(),
+        });
     }
+    None
+}
 
-    replacements.sort_by(|(lhs_idx, _, _), (rhs_idx, _, _)| lhs_idx.cmp(rhs_idx));
+/// Attempt to locate `pattern` verbatim within the file, returning its start,
+/// the pattern and replacement actually used, and how it matched.
+///
+/// In many real‑world diffs the last element of `old_lines` is an *empty*
+/// string representing the terminating newline of the region being replaced.
+/// This sentinel is not present in `original_lines` because we strip the
+/// trailing empty slice emitted by `split('\n')`.  If a direct search fails and
+/// the pattern ends with an empty string, retry without that final element so
+/// that modifications touching the end‑of‑file can be located reliably.
+fn seek_lines<'a>(
+    original_lines: &[String],
+    pattern: &'a [String],
+    new_slice: &'a [String],
+    from: usize,
+    eof: bool,
+) -> Option<(usize, &'a [String], &'a [String], MatchKind)> {
+    if let Some((start, kind)) = seek_sequence::seek_sequence(original_lines, pattern, from, eof) {
+        return Some((start, pattern, new_slice, kind));
+    }
+    // Retry without the trailing empty line which represents the final
+    // newline in the file.
+    let (last, rest) = pattern.split_last()?;
+    if !last.is_empty() {
+        return None;
+    }
+    let new_slice = match new_slice.split_last() {
+        Some((last, new_rest)) if last.is_empty() => new_rest,
+        _ => new_slice,
+    };
+    seek_sequence::seek_sequence(original_lines, rest, from, eof)
+        .map(|(start, kind)| (start, rest, new_slice, kind))
+}
 
-    Ok(replacements)
+/// Number of lines shared by the start and by the end of `old` and `new`,
+/// i.e. the chunk's leading and trailing context.
+fn shared_context(old: &[String], new: &[String]) -> (usize, usize) {
+    let leading = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let remaining = old.len().min(new.len()) - leading;
+    let trailing = old
+        .iter()
+        .rev()
+        .zip(new.iter().rev())
+        .take(remaining)
+        .take_while(|(a, b)| a == b)
+        .count();
+    (leading, trailing)
 }
 
 /// Apply the `(start_index, old_len, new_lines)` replacements to `original_lines`,
@@ -510,11 +733,20 @@ pub fn unified_diff_from_chunks_with_context(
     path: &Path,
     chunks: &[UpdateFileChunk],
     context: usize,
+) -> std::result::Result<ApplyPatchFileUpdate, ApplyPatchError> {
+    unified_diff_with_fuzz(path, chunks, context, DEFAULT_FUZZ_FACTOR)
+}
+
+fn unified_diff_with_fuzz(
+    path: &Path,
+    chunks: &[UpdateFileChunk],
+    context: usize,
+    fuzz: usize,
 ) -> std::result::Result<ApplyPatchFileUpdate, ApplyPatchError> {
     let AppliedPatch {
         original_contents,
         new_contents,
-    } = derive_new_contents_from_chunks(path, chunks)?;
+    } = derive_new_contents_from_chunks(path, chunks, fuzz)?;
     let text_diff = TextDiff::from_lines(&original_contents, &new_contents);
     let unified_diff = text_diff.unified_diff().context_radius(context).to_string();
     Ok(ApplyPatchFileUpdate {
@@ -779,6 +1011,66 @@ mod tests {
         );
     }
 
+    /// A hunk whose surrounding context drifted still applies once the fuzz
+    /// factor lets it drop those context lines, but not with fuzz disabled.
+    #[test]
+    fn test_fuzz_drops_drifted_context_lines() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("main.rs");
+        let original = "fn main() {\n    let x = 1;\n    println!(\"{x}\");\n}\n";
+        fs::write(&path, original).unwrap();
+        let patch = wrap_patch(&format!(
+            r#"*** Update File: {}
+@@
+ fn main() {{
+-    let x = 1;
++    let x = 2;
+     println!("{{}}", x);"#,
+            path.display()
+        ));
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        assert!(apply_patch_with_fuzz(&patch, 0, &mut stdout, &mut stderr).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), original);
+
+        apply_patch(&patch, &mut stdout, &mut stderr).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "fn main() {\n    let x = 2;\n    println!(\"{x}\");\n}\n"
+        );
+    }
+
+    /// A chunk whose `@@` line no longer exists is placed by its own lines,
+    /// but only when they occur once.
+    #[test]
+    fn test_fuzz_ignores_missing_context_line_only_when_unambiguous() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("lib.py");
+        fs::write(&path, "def run(a):\n    step()\n    done()\n").unwrap();
+        let patch = wrap_patch(&format!(
+            r#"*** Update File: {}
+@@ def run():
+-    done()
++    finish()"#,
+            path.display()
+        ));
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch(&patch, &mut stdout, &mut stderr).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "def run(a):\n    step()\n    finish()\n"
+        );
+
+        fs::write(&path, "def run(a):\n    done()\n    done()\n").unwrap();
+        assert!(apply_patch(&patch, &mut stdout, &mut stderr).is_err());
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "def run(a):\n    done()\n    done()\n"
+        );
+    }
+
     /// Ensure that patches authored with ASCII characters can update lines that
     /// contain typographic Unicode punctuation (e.g. EN DASH, NON-BREAKING
     /// HYPHEN). Historically `git apply` succeeds in such scenarios but our
@@ -1,12 +1,62 @@
+/// How strictly a sequence of patch lines had to be relaxed to match the file.
+/// Variants are ordered from strictest to most lenient.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MatchKind {
+    /// Every line matched byte for byte.
+    Exact,
+    /// Lines matched once trailing whitespace was ignored.
+    IgnoringTrailingWhitespace,
+    /// Lines matched once leading and trailing whitespace was ignored.
+    IgnoringWhitespace,
+    /// Lines matched once Unicode dashes, quotes and spaces were treated as
+    /// their ASCII equivalents.
+    NormalizedPunctuation,
+}
+
+impl MatchKind {
+    const ALL: [MatchKind; 4] = [
+        MatchKind::Exact,
+        MatchKind::IgnoringTrailingWhitespace,
+        MatchKind::IgnoringWhitespace,
+        MatchKind::NormalizedPunctuation,
+    ];
+
+    /// Short description for reports, e.g. "ignoring trailing whitespace".
+    pub fn description(self) -> &'static str {
+        match self {
+            MatchKind::Exact => "exact",
+            MatchKind::IgnoringTrailingWhitespace => "ignoring trailing whitespace",
+            MatchKind::IgnoringWhitespace => "ignoring indentation",
+            MatchKind::NormalizedPunctuation => "after normalizing Unicode punctuation",
+        }
+    }
+
+    fn lines_match(self, line: &str, pattern: &str) -> bool {
+        match self {
+            MatchKind::Exact => line == pattern,
+            MatchKind::IgnoringTrailingWhitespace => line.trim_end() == pattern.trim_end(),
+            MatchKind::IgnoringWhitespace => line.trim() == pattern.trim(),
+            // Attempt to match after *normalising* common Unicode punctuation
+            // to their ASCII equivalents so that diffs authored with plain
+            // ASCII characters can still be applied to source files that
+            // contain typographic dashes / quotes, etc. This mirrors the fuzzy
+            // behaviour of `git apply` which ignores minor byte-level
+            // differences when locating context lines.
+            MatchKind::NormalizedPunctuation => normalise(line) == normalise(pattern),
+        }
+    }
+}
+
 /// Attempt to find the sequence of `pattern` lines within `lines` beginning at or after `start`.
-/// Returns the starting index of the match or `None` if not found. Matches are attempted with
-/// decreasing strictness: exact match, then ignoring trailing whitespace, then ignoring leading
-/// and trailing whitespace. When `eof` is true, we first try starting at the end-of-file (so that
-/// patterns intended to match file endings are applied at the end), and fall back to searching
-/// from `start` if needed.
+/// Returns the starting index of the match, along with the [`MatchKind`] of the pass that found
+/// it, or `None` if not found. Matches are attempted with decreasing strictness: exact match,
+/// then ignoring trailing whitespace, then ignoring leading and trailing whitespace, then
+/// normalising Unicode punctuation. When `eof` is true, we first try starting at the end-of-file
+/// (so that patterns intended to match file endings are applied at the end), and fall back to
+/// searching from `start` if needed.
 ///
 /// Special cases handled defensively:
-///  • Empty `pattern` → returns `Some(start)` (no-op match)
+///  • Empty `pattern` → returns `Some((start, MatchKind::Exact))` (no-op match)
 ///  • `pattern.len() > lines.len()` → returns `None` (cannot match, avoids
 ///    out‑of‑bounds panic that occurred pre‑2025‑04‑12)
 pub(crate) fn seek_sequence(
@@ -14,9 +64,9 @@ pub(crate) fn seek_sequence(
     pattern: &[String],
     start: usize,
     eof: bool,
-) -> Option<usize> {
+) -> Option<(usize, MatchKind)> {
     if pattern.is_empty() {
-        return Some(start);
+        return Some((start, MatchKind::Exact));
     }
 
     // When the pattern is longer than the available input there is no possible
@@ -31,86 +81,45 @@ pub(crate) fn seek_sequence(
     } else {
         start
     };
-    // Exact match first.
-    for i in search_start..=lines.len().saturating_sub(pattern.len()) {
-        if lines[i..i + pattern.len()] == *pattern {
-            return Some(i);
-        }
-    }
-    // Then rstrip match.
-    for i in search_start..=lines.len().saturating_sub(pattern.len()) {
-        let mut ok = true;
-        for (p_idx, pat) in pattern.iter().enumerate() {
-            if lines[i + p_idx].trim_end() != pat.trim_end() {
-                ok = false;
-                break;
-            }
-        }
-        if ok {
-            return Some(i);
-        }
-    }
-    // Finally, trim both sides to allow more lenience.
-    for i in search_start..=lines.len().saturating_sub(pattern.len()) {
-        let mut ok = true;
-        for (p_idx, pat) in pattern.iter().enumerate() {
-            if lines[i + p_idx].trim() != pat.trim() {
-                ok = false;
-                break;
+    // Exact match first, then progressively more lenient comparisons.
+    for kind in MatchKind::ALL {
+        for i in search_start..=lines.len().saturating_sub(pattern.len()) {
+            if pattern
+                .iter()
+                .enumerate()
+                .all(|(p_idx, pat)| kind.lines_match(&lines[i + p_idx], pat))
+            {
+                return Some((i, kind));
             }
         }
-        if ok {
-            return Some(i);
-        }
-    }
-
-    // ------------------------------------------------------------------
-    // Final, most permissive pass – attempt to match after *normalising*
-    // common Unicode punctuation to their ASCII equivalents so that diffs
-    // authored with plain ASCII characters can still be applied to source
-    // files that contain typographic dashes / quotes, etc.  This mirrors the
-    // fuzzy behaviour of `git apply` which ignores minor byte-level
-    // differences when locating context lines.
-    // ------------------------------------------------------------------
-
-    fn normalise(s: &str) -> String {
-        s.trim()
-            .chars()
-            .map(|c| match c {
-                // Various dash / hyphen code-points → ASCII '-'
-                '\u{2010}' | '\u{2011}' | '\u{2012}' | '\u{2013}' | '\u{2014}' | '\u{2015}'
-                | '\u{2212}' => '-',
-                // Fancy single quotes → '\''
-                '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' => '\'',
-                // Fancy double quotes → '"'
-                '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' => '"',
-                // Non-breaking space and other odd spaces → normal space
-                '\u{00A0}' | '\u{2002}' | '\u{2003}' | '\u{2004}' | '\u{2005}' | '\u{2006}'
-                | '\u{2007}' | '\u{2008}' | '\u{2009}' | '\u{200A}' | '\u{202F}' | '\u{205F}'
-                | '\u{3000}' => ' ',
-                other => other,
-            })
-            .collect::<String>()
-    }
-
-    for i in search_start..=lines.len().saturating_sub(pattern.len()) {
-        let mut ok = true;
-        for (p_idx, pat) in pattern.iter().enumerate() {
-            if normalise(&lines[i + p_idx]) != normalise(pat) {
-                ok = false;
-                break;
-            }
-        }
-        if ok {
-            return Some(i);
-        }
     }
 
     None
 }
 
+fn normalise(s: &str) -> String {
+    s.trim()
+        .chars()
+        .map(|c| match c {
+            // Various dash / hyphen code-points → ASCII '-'
+            '\u{2010}' | '\u{2011}' | '\u{2012}' | '\u{2013}' | '\u{2014}' | '\u{2015}'
+            | '\u{2212}' => '-',
+            // Fancy single quotes → '\''
+            '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' => '\'',
+            // Fancy double quotes → '"'
+            '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' => '"',
+            // Non-breaking space and other odd spaces → normal space
+            '\u{00A0}' | '\u{2002}' | '\u{2003}' | '\u{2004}' | '\u{2005}' | '\u{2006}'
+            | '\u{2007}' | '\u{2008}' | '\u{2009}' | '\u{200A}' | '\u{202F}' | '\u{205F}'
+            | '\u{3000}' => ' ',
+            other => other,
+        })
+        .collect::<String>()
+}
+
 #[cfg(test)]
 mod tests {
+    use super::MatchKind;
     use super::seek_sequence;
     use std::string::ToString;
 
@@ -122,7 +131,10 @@ mod tests {
     fn test_exact_match_finds_sequence() {
         let lines = to_vec(&["foo", "bar", "baz"]);
         let pattern = to_vec(&["bar", "baz"]);
-        assert_eq!(seek_sequence(&lines, &pattern, 0, false), Some(1));
+        assert_eq!(
+            seek_sequence(&lines, &pattern, 0, false),
+            Some((1, MatchKind::Exact))
+        );
     }
 
     #[test]
@@ -130,7 +142,10 @@ mod tests {
         let lines = to_vec(&["foo   ", "bar\t\t"]);
         // Pattern omits trailing whitespace.
         let pattern = to_vec(&["foo", "bar"]);
-        assert_eq!(seek_sequence(&lines, &pattern, 0, false), Some(0));
+        assert_eq!(
+            seek_sequence(&lines, &pattern, 0, false),
+            Some((0, MatchKind::IgnoringTrailingWhitespace))
+        );
     }
 
     #[test]
@@ -138,7 +153,31 @@ mod tests {
         let lines = to_vec(&["    foo   ", "   bar\t"]);
         // Pattern omits any additional whitespace.
         let pattern = to_vec(&["foo", "bar"]);
-        assert_eq!(seek_sequence(&lines, &pattern, 0, false), Some(0));
+        assert_eq!(
+            seek_sequence(&lines, &pattern, 0, false),
+            Some((0, MatchKind::IgnoringWhitespace))
+        );
+    }
+
+    #[test]
+    fn test_match_reports_the_pass_that_matched() {
+        let lines = to_vec(&["a", "    b  ", "c \u{2014} d"]);
+        assert_eq!(
+            seek_sequence(&lines, &to_vec(&["a"]), 0, false),
+            Some((0, MatchKind::Exact))
+        );
+        assert_eq!(
+            seek_sequence(&lines, &to_vec(&["    b"]), 0, false),
+            Some((1, MatchKind::IgnoringTrailingWhitespace))
+        );
+        assert_eq!(
+            seek_sequence(&lines, &to_vec(&["b"]), 0, false),
+            Some((1, MatchKind::IgnoringWhitespace))
+        );
+        assert_eq!(
+            seek_sequence(&lines, &to_vec(&["c - d"]), 0, false),
+            Some((2, MatchKind::NormalizedPunctuation))
+        );
     }
 
     #[test]
@@ -30,11 +30,21 @@ pub fn arg0_dispatch() -> Option<TempDir> {
     let argv1 = args.next().unwrap_or_default();
     if argv1 == CODEX_APPLY_PATCH_ARG1 {
         let patch_arg = args.next().and_then(|s| s.to_str().map(str::to_owned));
+        // An optional second argument sets the fuzz factor used to locate hunks.
+        let fuzz = args
+            .next()
+            .and_then(|s| s.to_str().and_then(|s| s.parse().ok()))
+            .unwrap_or(codex_apply_patch::DEFAULT_FUZZ_FACTOR);
         let exit_code = match patch_arg {
             Some(patch_arg) => {
                 let mut stdout = std::io::stdout();
                 let mut stderr = std::io::stderr();
-                match codex_apply_patch::apply_patch(&patch_arg, &mut stdout, &mut stderr) {
+                match codex_apply_patch::apply_patch_with_fuzz(
+                    &patch_arg,
+                    fuzz,
+                    &mut stdout,
+                    &mut stderr,
+                ) {
                     Ok(()) => 0,
                     Err(_) => 1,
                 }
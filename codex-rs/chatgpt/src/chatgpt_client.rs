@@ -22,7 +22,7 @@ pub(crate) async fn chatgpt_get_request_with_timeout<T: DeserializeOwned>(
     timeout: Option<Duration>,
 ) -> anyhow::Result<T> {
     let chatgpt_base_url = &config.chatgpt_base_url;
-    init_chatgpt_token_from_auth(&config.codex_home, config.cli_auth_credentials_store_mode)
+    init_chatgpt_token_from_auth(&config.auth_home(), config.cli_auth_credentials_store_mode)
         .await?;
 
     // Make direct HTTP request to ChatGPT backend API with the token
@@ -0,0 +1,224 @@
+use std::io::IsTerminal;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use anyhow::Context;
+use anyhow::Result;
+use codex_common::CliConfigOverrides;
+use codex_core::batch::BatchSpec;
+use codex_core::batch::TaskProgress;
+use codex_core::batch::TaskReport;
+use codex_core::batch::TaskStatus;
+use codex_core::batch::run_batch;
+use codex_core::config::Config;
+use codex_core::config::ConfigOverrides;
+use codex_core::git_info::get_git_repo_root;
+use codex_core::protocol::AskForApproval;
+use codex_protocol::config_types::SandboxMode;
+
+/// Subcommands:
+/// - `run` — run the tasks in a batch file, each in its own worktree
+#[derive(Debug, clap::Parser)]
+pub struct BatchCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    pub subcommand: BatchSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum BatchSubcommand {
+    Run(BatchRunArgs),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct BatchRunArgs {
+    /// YAML file listing the tasks.
+    pub tasks: PathBuf,
+
+    /// Tasks to run at once. Overrides `concurrency` in the batch file.
+    #[arg(long, short = 'j')]
+    pub concurrency: Option<usize>,
+
+    /// Also write the results as JSON to this file.
+    #[arg(long, value_name = "FILE")]
+    pub report: Option<PathBuf>,
+}
+
+impl BatchCli {
+    pub async fn run(self, codex_linux_sandbox_exe: Option<PathBuf>) -> Result<()> {
+        let BatchCli {
+            config_overrides,
+            subcommand,
+        } = self;
+        let BatchSubcommand::Run(args) = subcommand;
+
+        let contents = std::fs::read_to_string(&args.tasks)
+            .with_context(|| format!("failed to read {}", args.tasks.display()))?;
+        let mut spec = BatchSpec::from_yaml(&contents).map_err(anyhow::Error::msg)?;
+        if let Some(concurrency) = args.concurrency {
+            anyhow::ensure!(concurrency > 0, "--concurrency must be at least 1");
+            spec.concurrency = Some(concurrency);
+        }
+
+        let overrides = config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        // Nobody is around to approve anything, so tasks run without asking
+        // and may only write inside their worktree.
+        let harness_overrides = ConfigOverrides {
+            approval_policy: Some(AskForApproval::Never),
+            sandbox_mode: Some(SandboxMode::WorkspaceWrite),
+            codex_linux_sandbox_exe,
+            ..Default::default()
+        };
+        let config =
+            Config::load_with_cli_overrides_and_harness_overrides(overrides, harness_overrides)
+                .await
+                .context("failed to load configuration")?;
+        if get_git_repo_root(&config.cwd).is_none() {
+            anyhow::bail!("{} is not inside a git repository", config.cwd.display());
+        }
+
+        let dashboard = Dashboard::new(&spec);
+        let reports = run_batch(&spec, &config, |id, progress| {
+            dashboard.update(id, progress);
+        })
+        .await;
+
+        if let Some(path) = &args.report {
+            let json = serde_json::to_string_pretty(&reports)?;
+            std::fs::write(path, json)
+                .with_context(|| format!("failed to write {}", path.display()))?;
+        }
+        print!("{}", summary(&reports));
+
+        if reports
+            .iter()
+            .any(|report| report.status != TaskStatus::Completed)
+        {
+            std::process::exit(1);
+        }
+        Ok(())
+    }
+}
+
+/// Status of every task on stderr. On a terminal the whole table is redrawn
+/// in place; otherwise a line is printed when a task starts or finishes.
+struct Dashboard {
+    rows: Mutex<Vec<DashboardRow>>,
+    redraw: bool,
+}
+
+struct DashboardRow {
+    id: String,
+    state: &'static str,
+    tokens: i64,
+}
+
+impl Dashboard {
+    fn new(spec: &BatchSpec) -> Self {
+        let rows = spec
+            .tasks
+            .iter()
+            .map(|task| DashboardRow {
+                id: task.id.clone(),
+                state: "queued",
+                tokens: 0,
+            })
+            .collect();
+        let dashboard = Self {
+            rows: Mutex::new(rows),
+            redraw: std::io::stderr().is_terminal(),
+        };
+        if dashboard.redraw
+            && let Ok(rows) = dashboard.rows.lock()
+        {
+            eprint!("{}", render_rows(&rows));
+        }
+        dashboard
+    }
+
+    fn update(&self, id: &str, progress: TaskProgress) {
+        let Ok(mut rows) = self.rows.lock() else {
+            return;
+        };
+        let Some(row) = rows.iter_mut().find(|row| row.id == id) else {
+            return;
+        };
+        match progress {
+            TaskProgress::Started => row.state = "running",
+            TaskProgress::Tokens(tokens) => row.tokens = tokens,
+            TaskProgress::Finished(status) => row.state = status_label(status),
+        }
+        let state = row.state;
+        if self.redraw {
+            let mut stderr = std::io::stderr().lock();
+            let _ = write!(stderr, "\x1b[{}A{}", rows.len(), render_rows(&rows));
+            let _ = stderr.flush();
+        } else if !matches!(progress, TaskProgress::Tokens(_)) {
+            let done = rows
+                .iter()
+                .filter(|row| !matches!(row.state, "queued" | "running"))
+                .count();
+            eprintln!("[{done}/{}] {id}: {state}", rows.len());
+        }
+    }
+}
+
+fn render_rows(rows: &[DashboardRow]) -> String {
+    let width = rows.iter().map(|row| row.id.len()).max().unwrap_or(0);
+    rows.iter()
+        .map(|row| {
+            format!(
+                "\x1b[2K{:<width$}  {:<11}  {} tokens\n",
+                row.id, row.state, row.tokens
+            )
+        })
+        .collect()
+}
+
+fn status_label(status: TaskStatus) -> &'static str {
+    match status {
+        TaskStatus::Completed => "completed",
+        TaskStatus::Failed => "failed",
+        TaskStatus::OverBudget => "over budget",
+    }
+}
+
+/// Markdown summary of a finished batch.
+fn summary(reports: &[TaskReport]) -> String {
+    let completed = reports
+        .iter()
+        .filter(|report| report.status == TaskStatus::Completed)
+        .count();
+    let mut out = format!(
+        "# Batch results\n\n{completed} of {} tasks completed.\n\n| Task | Status | Tokens | Time | Branch | Commit |\n| --- | --- | --- | --- | --- | --- |\n",
+        reports.len()
+    );
+    for report in reports {
+        out.push_str(&format!(
+            "| {} | {} | {} | {:.0}s | {} | {} |\n",
+            report.id,
+            status_label(report.status),
+            report.tokens,
+            report.duration.as_secs_f64(),
+            report.branch.as_deref().unwrap_or("-"),
+            report
+                .commit
+                .as_deref()
+                .map_or("no changes", |commit| &commit[..commit.len().min(12)]),
+        ));
+    }
+    for report in reports {
+        out.push_str(&format!("\n## {}\n\n", report.id));
+        if let Some(error) = &report.error {
+            out.push_str(&format!("Error: {error}\n\n"));
+        }
+        out.push_str(report.last_message.as_deref().unwrap_or("(no reply)"));
+        out.push('\n');
+    }
+    out
+}
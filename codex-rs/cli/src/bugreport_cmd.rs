@@ -0,0 +1,360 @@
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use chrono::DateTime;
+use chrono::FixedOffset;
+use codex_common::CliConfigOverrides;
+use codex_core::INTERACTIVE_SESSION_SOURCES;
+use codex_core::RolloutRecorder;
+use codex_core::ThreadSortKey;
+use codex_core::config::Config;
+use codex_core::config::log_dir;
+use codex_core::config::types::RedactionConfig;
+use codex_core::decode_rollout_line;
+use codex_core::default_client::get_codex_user_agent;
+use codex_core::redaction::REDACTED;
+use codex_core::redaction::Redactor;
+use serde_json::Value as JsonValue;
+use serde_json::json;
+use toml::Value as TomlValue;
+
+/// Only the end of the TUI log is bundled; older lines rarely matter.
+const LOG_TAIL_BYTES: u64 = 2 * 1024 * 1024;
+
+/// Config keys whose values are dropped from the bundled config, matched
+/// against the last `_`- or `-`-separated word of the key.
+const SECRET_KEY_WORDS: &[&str] = &[
+    "key",
+    "token",
+    "secret",
+    "password",
+    "auth",
+    "headers",
+    "cookie",
+    "credentials",
+    "env",
+];
+
+/// Collects logs, config, version info, and the last session's timeline into
+/// a zip archive to attach to a bug report.
+#[derive(Debug, clap::Parser)]
+pub struct BugreportCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Where to write the archive. Defaults to
+    /// `codex-bugreport-<timestamp>.zip` in the current directory.
+    #[arg(long, short = 'o', value_name = "FILE")]
+    pub output: Option<PathBuf>,
+}
+
+impl BugreportCli {
+    pub async fn run(self) -> Result<()> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+        // Built-in patterns always apply here, whatever `[redaction]` says.
+        let redactor = Redactor::from_config(&RedactionConfig {
+            enabled: true,
+            builtin_patterns: true,
+            patterns: config.redaction.patterns.clone(),
+        });
+        let redact = |text: &str| match &redactor {
+            Some(redactor) => redactor.redact(text).into_owned(),
+            None => text.to_string(),
+        };
+
+        let mut entries: Vec<(String, String)> = vec![
+            ("system.txt".to_string(), system_info(&config)),
+            (
+                "config.toml".to_string(),
+                redact(&effective_config_without_secrets(&config)?),
+            ),
+        ];
+
+        let log_path = log_dir(&config)?.join("codex-tui.log");
+        match read_tail(&log_path, LOG_TAIL_BYTES) {
+            Ok(log) => entries.push(("logs/codex-tui.log".to_string(), redact(&log))),
+            Err(err) => eprintln!("Skipping {}: {err}", log_path.display()),
+        }
+
+        match last_session_rollout(&config).await {
+            Some(rollout_path) => {
+                let contents = std::fs::read_to_string(&rollout_path).with_context(|| {
+                    format!("failed to read session {}", rollout_path.display())
+                })?;
+                let events = session_events(&contents);
+                entries.push(("session/timeline.txt".to_string(), format_timeline(&events)));
+                entries.push((
+                    "session/metrics.json".to_string(),
+                    serde_json::to_string_pretty(&session_metrics(&events))?,
+                ));
+            }
+            None => eprintln!("No recorded session found; the bundle has no session timeline."),
+        }
+
+        let output = match self.output {
+            Some(output) => output,
+            None => PathBuf::from(format!(
+                "codex-bugreport-{}.zip",
+                chrono::Local::now().format("%Y%m%d-%H%M%S")
+            )),
+        };
+        write_archive(&output, &entries)
+            .with_context(|| format!("failed to write {}", output.display()))?;
+
+        println!("Wrote {}", output.display());
+        for (name, _) in &entries {
+            println!("  {name}");
+        }
+        println!("Secrets are stripped, but review the archive before attaching it to an issue.");
+        Ok(())
+    }
+}
+
+fn system_info(config: &Config) -> String {
+    format!(
+        "codex-cli {}\nuser agent: {}\nos: {} {}\nmodel: {}\nprovider: {}\n",
+        env!("CARGO_PKG_VERSION"),
+        get_codex_user_agent(),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        config.model.as_deref().unwrap_or("(default)"),
+        config.model_provider_id,
+    )
+}
+
+fn effective_config_without_secrets(config: &Config) -> Result<String> {
+    let mut effective = config.config_layer_stack.effective_config();
+    strip_secrets(&mut effective);
+    toml::to_string_pretty(&effective).context("failed to serialize config")
+}
+
+/// Replaces the value of every key that can hold a credential, such as
+/// `experimental_bearer_token`, MCP server `env` tables, or `http_headers`.
+fn strip_secrets(value: &mut TomlValue) {
+    match value {
+        TomlValue::Table(table) => {
+            for (key, value) in table.iter_mut() {
+                let last_word = key.rsplit(['_', '-']).next().unwrap_or(key);
+                if SECRET_KEY_WORDS.contains(&last_word.to_ascii_lowercase().as_str()) {
+                    *value = TomlValue::String(REDACTED.to_string());
+                } else {
+                    strip_secrets(value);
+                }
+            }
+        }
+        TomlValue::Array(values) => values.iter_mut().for_each(strip_secrets),
+        TomlValue::String(_)
+        | TomlValue::Integer(_)
+        | TomlValue::Float(_)
+        | TomlValue::Boolean(_)
+        | TomlValue::Datetime(_) => {}
+    }
+}
+
+fn read_tail(path: &Path, max_bytes: u64) -> std::io::Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let len = file.metadata()?.len();
+    file.seek(SeekFrom::Start(len.saturating_sub(max_bytes)))?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+async fn last_session_rollout(config: &Config) -> Option<PathBuf> {
+    let page = RolloutRecorder::list_threads(
+        &config.codex_home,
+        1,
+        None,
+        ThreadSortKey::UpdatedAt,
+        INTERACTIVE_SESSION_SOURCES,
+        None,
+        &config.model_provider_id,
+    )
+    .await
+    .ok()?;
+    page.items.into_iter().next().map(|item| item.path)
+}
+
+/// One rollout line, reduced to when it was written and what kind of item it
+/// is. Message text, commands, and tool output are never kept.
+#[derive(Debug, Clone, PartialEq)]
+struct SessionEvent {
+    timestamp: String,
+    kind: String,
+    total_tokens: Option<i64>,
+}
+
+fn session_events(rollout: &str) -> Vec<SessionEvent> {
+    rollout
+        .lines()
+        .filter_map(|line| decode_rollout_line(line).ok())
+        .filter_map(|line| serde_json::from_str::<JsonValue>(&line).ok())
+        .map(|line| {
+            let item_type = line["type"].as_str().unwrap_or("unknown");
+            let kind = match line["payload"]["type"].as_str() {
+                Some(payload_type) => format!("{item_type}/{payload_type}"),
+                None => item_type.to_string(),
+            };
+            SessionEvent {
+                timestamp: line["timestamp"].as_str().unwrap_or_default().to_string(),
+                kind,
+                total_tokens: line["payload"]["info"]["total_token_usage"]["total_tokens"].as_i64(),
+            }
+        })
+        .collect()
+}
+
+fn format_timeline(events: &[SessionEvent]) -> String {
+    events
+        .iter()
+        .map(|event| format!("{}  {}\n", event.timestamp, event.kind))
+        .collect()
+}
+
+fn session_metrics(events: &[SessionEvent]) -> JsonValue {
+    let parse = |event: &SessionEvent| DateTime::parse_from_rfc3339(&event.timestamp).ok();
+    let seconds_between = |start: DateTime<FixedOffset>, end: DateTime<FixedOffset>| {
+        (end - start).num_milliseconds() as f64 / 1000.0
+    };
+
+    let mut turn_seconds = Vec::new();
+    let mut aborted_turns = 0;
+    let mut turn_started = None;
+    for event in events {
+        match event.kind.as_str() {
+            "event_msg/task_started" | "event_msg/turn_started" => turn_started = parse(event),
+            "event_msg/task_complete" | "event_msg/turn_complete" | "event_msg/turn_aborted" => {
+                if event.kind == "event_msg/turn_aborted" {
+                    aborted_turns += 1;
+                }
+                if let (Some(start), Some(end)) = (turn_started.take(), parse(event)) {
+                    turn_seconds.push(seconds_between(start, end));
+                }
+            }
+            _ => {}
+        }
+    }
+    let count = |kinds: &[&str]| {
+        events
+            .iter()
+            .filter(|event| kinds.contains(&event.kind.as_str()))
+            .count()
+    };
+    let started = events.first().and_then(parse);
+    let ended = events.last().and_then(parse);
+
+    json!({
+        "started_at": events.first().map(|event| event.timestamp.as_str()),
+        "ended_at": events.last().map(|event| event.timestamp.as_str()),
+        "duration_seconds": started.zip(ended).map(|(start, end)| seconds_between(start, end)),
+        "turns": count(&["event_msg/user_message"]),
+        "aborted_turns": aborted_turns,
+        "turn_seconds": turn_seconds,
+        "tool_calls": count(&[
+            "response_item/function_call",
+            "response_item/custom_tool_call",
+            "response_item/local_shell_call",
+        ]),
+        "compactions": count(&["compacted"]),
+        "total_tokens": events.iter().rev().find_map(|event| event.total_tokens),
+    })
+}
+
+fn write_archive(path: &Path, entries: &[(String, String)]) -> Result<()> {
+    let file = std::fs::File::create(path)?;
+    let mut archive = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    for (name, contents) in entries {
+        archive.start_file(name.as_str(), options)?;
+        archive.write_all(contents.as_bytes())?;
+    }
+    archive.finish()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn strip_secrets_masks_credentials_but_keeps_settings() {
+        let mut config: TomlValue = toml::from_str(
+            r#"
+model = "gpt-5.1-codex"
+model_auto_compact_token_limit = 180000
+experimental_bearer_token = "abc123"
+
+[mcp_servers.docs]
+command = "docs-server"
+env = { DOCS_TOKEN = "secret" }
+http_headers = { Authorization = "Bearer xyz" }
+"#,
+        )
+        .expect("valid toml");
+
+        strip_secrets(&mut config);
+
+        let expected: TomlValue = toml::from_str(
+            r#"
+model = "gpt-5.1-codex"
+model_auto_compact_token_limit = 180000
+experimental_bearer_token = "[REDACTED]"
+
+[mcp_servers.docs]
+command = "docs-server"
+env = "[REDACTED]"
+http_headers = "[REDACTED]"
+"#,
+        )
+        .expect("valid toml");
+        assert_eq!(config, expected);
+    }
+
+    #[test]
+    fn session_metrics_summarize_turns_without_content() {
+        let rollout = [
+            r#"{"timestamp":"2025-01-01T10:00:00.000Z","type":"session_meta","payload":{"id":"x"}}"#,
+            r#"{"timestamp":"2025-01-01T10:00:01.000Z","type":"event_msg","payload":{"type":"task_started"}}"#,
+            r#"{"timestamp":"2025-01-01T10:00:01.000Z","type":"event_msg","payload":{"type":"user_message","message":"private text"}}"#,
+            r#"{"timestamp":"2025-01-01T10:00:02.000Z","type":"response_item","payload":{"type":"function_call","arguments":"rm -rf"}}"#,
+            r#"{"timestamp":"2025-01-01T10:00:04.500Z","type":"event_msg","payload":{"type":"token_count","info":{"total_token_usage":{"total_tokens":1234}}}}"#,
+            r#"{"timestamp":"2025-01-01T10:00:05.000Z","type":"event_msg","payload":{"type":"task_complete"}}"#,
+        ]
+        .join("\n");
+
+        let events = session_events(&rollout);
+        let timeline = format_timeline(&events);
+        assert!(!timeline.contains("private text"));
+        assert!(!timeline.contains("rm -rf"));
+        assert!(timeline.contains("2025-01-01T10:00:02.000Z  response_item/function_call"));
+
+        assert_eq!(
+            session_metrics(&events),
+            json!({
+                "started_at": "2025-01-01T10:00:00.000Z",
+                "ended_at": "2025-01-01T10:00:05.000Z",
+                "duration_seconds": 5.0,
+                "turns": 1,
+                "aborted_turns": 0,
+                "turn_seconds": [4.0],
+                "tool_calls": 1,
+                "compactions": 0,
+                "total_tokens": 1234,
+            })
+        );
+    }
+}
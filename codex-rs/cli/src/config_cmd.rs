@@ -0,0 +1,275 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use codex_app_server_protocol::ConfigLayerSource;
+use codex_app_server_protocol::ConfigReadParams;
+use codex_app_server_protocol::ConfigValueWriteParams;
+use codex_app_server_protocol::ConfigWriteResponse;
+use codex_app_server_protocol::MergeStrategy;
+use codex_app_server_protocol::WriteStatus;
+use codex_common::CliConfigOverrides;
+use codex_core::config::CONFIG_TOML_FILE;
+use codex_core::config::ConfigService;
+use codex_core::config::find_codex_home;
+use codex_core::config::schema::validate_key_path;
+use codex_core::config_loader::CloudRequirementsLoader;
+use codex_core::config_loader::LoaderOverrides;
+use codex_core::config_loader::format_config_error;
+use codex_core::config_loader::validate_config_contents;
+use serde_json::Value as JsonValue;
+
+/// Subcommands:
+/// - `get`      — print the effective value of a key and the layer that set it
+/// - `set`      — write a key to `~/.codex/config.toml`, preserving comments
+/// - `unset`    — remove a key from `~/.codex/config.toml`
+/// - `validate` — check `config.toml` for unknown keys and invalid values
+#[derive(Debug, clap::Parser)]
+pub struct ConfigCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    pub subcommand: ConfigSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum ConfigSubcommand {
+    Get(GetArgs),
+    Set(SetArgs),
+    Unset(UnsetArgs),
+    Validate(ValidateArgs),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct GetArgs {
+    /// Dotted key path, e.g. `model` or `tui.notifications`.
+    pub key: String,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct SetArgs {
+    /// Dotted key path, e.g. `model` or `tui.notifications`.
+    pub key: String,
+
+    /// Value to store, as a TOML literal. Bare words are stored as strings.
+    pub value: String,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct UnsetArgs {
+    /// Dotted key path to remove.
+    pub key: String,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ValidateArgs {
+    /// Config file to validate. Defaults to `$CODEX_HOME/config.toml`.
+    #[arg(long, value_name = "FILE")]
+    pub path: Option<PathBuf>,
+}
+
+impl ConfigCli {
+    pub async fn run(self) -> Result<()> {
+        let ConfigCli {
+            config_overrides,
+            subcommand,
+        } = self;
+
+        match subcommand {
+            ConfigSubcommand::Get(args) => run_get(&config_overrides, args).await?,
+            ConfigSubcommand::Set(args) => run_set(args).await?,
+            ConfigSubcommand::Unset(args) => run_unset(args).await?,
+            ConfigSubcommand::Validate(args) => run_validate(args)?,
+        }
+
+        Ok(())
+    }
+}
+
+async fn run_get(config_overrides: &CliConfigOverrides, get_args: GetArgs) -> Result<()> {
+    let cli_overrides = config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let segments = split_key_path(&get_args.key)?;
+    validate_key_path(&segments).map_err(anyhow::Error::msg)?;
+
+    let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+    let cwd = std::env::current_dir().context("failed to resolve current directory")?;
+    let service = ConfigService::new(
+        codex_home,
+        cli_overrides,
+        LoaderOverrides::default(),
+        CloudRequirementsLoader::default(),
+    );
+    let response = service
+        .read(ConfigReadParams {
+            include_layers: false,
+            cwd: Some(cwd.to_string_lossy().into_owned()),
+        })
+        .await?;
+
+    let config = serde_json::to_value(&response.config)?;
+    let Some(value) = lookup_json(&config, &segments) else {
+        bail!("`{}` is not set", get_args.key);
+    };
+    println!("{}", format_toml_value(value)?);
+
+    if let Some(origin) = response.origins.get(&get_args.key) {
+        eprintln!("(from {})", describe_layer(&origin.name));
+    }
+
+    Ok(())
+}
+
+async fn run_set(set_args: SetArgs) -> Result<()> {
+    let SetArgs { key, value } = set_args;
+    let segments = split_key_path(&key)?;
+    validate_key_path(&segments).map_err(anyhow::Error::msg)?;
+
+    let value = serde_json::to_value(parse_toml_literal(&value))?;
+    let response = write_value(&key, value).await?;
+    report_write(&key, &response, "Set");
+    Ok(())
+}
+
+async fn run_unset(unset_args: UnsetArgs) -> Result<()> {
+    let key = unset_args.key;
+    split_key_path(&key)?;
+
+    let response = write_value(&key, JsonValue::Null).await?;
+    report_write(&key, &response, "Removed");
+    Ok(())
+}
+
+fn run_validate(validate_args: ValidateArgs) -> Result<()> {
+    let path = match validate_args.path {
+        Some(path) => path,
+        None => find_codex_home()
+            .context("failed to resolve CODEX_HOME")?
+            .join(CONFIG_TOML_FILE),
+    };
+    let contents = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+
+    let errors = validate_config_contents(&path, &contents);
+    if errors.is_empty() {
+        println!("{} is valid.", path.display());
+        return Ok(());
+    }
+
+    for error in &errors {
+        eprintln!("{}", format_config_error(error, &contents));
+    }
+    let count = errors.len();
+    let noun = if count == 1 { "problem" } else { "problems" };
+    bail!("found {count} {noun} in {}", path.display());
+}
+
+async fn write_value(key: &str, value: JsonValue) -> Result<ConfigWriteResponse> {
+    let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+    let response = ConfigService::new_with_defaults(codex_home)
+        .write_value(ConfigValueWriteParams {
+            key_path: key.to_string(),
+            value,
+            merge_strategy: MergeStrategy::Replace,
+            file_path: None,
+            expected_version: None,
+        })
+        .await?;
+    Ok(response)
+}
+
+fn report_write(key: &str, response: &ConfigWriteResponse, verb: &str) {
+    println!("{verb} `{key}` in {}.", response.file_path.display());
+    match response.status {
+        WriteStatus::Ok => {}
+        WriteStatus::OkOverridden => {
+            if let Some(metadata) = &response.overridden_metadata {
+                eprintln!("warning: {}", metadata.message);
+            }
+        }
+    }
+}
+
+fn split_key_path(key: &str) -> Result<Vec<String>> {
+    let segments: Vec<String> = key.split('.').map(str::to_string).collect();
+    if segments.iter().any(String::is_empty) {
+        bail!("invalid key path `{key}`");
+    }
+    Ok(segments)
+}
+
+/// Parses `raw` as a TOML literal (`true`, `42`, `["a", "b"]`, `{ x = 1 }`),
+/// falling back to a plain string so `codex config set model o3` works
+/// without extra quoting.
+fn parse_toml_literal(raw: &str) -> toml::Value {
+    let wrapped = format!("_x_ = {raw}");
+    toml::from_str::<toml::Table>(&wrapped)
+        .ok()
+        .and_then(|mut table| table.remove("_x_"))
+        .unwrap_or_else(|| toml::Value::String(raw.to_string()))
+}
+
+fn lookup_json<'a>(value: &'a JsonValue, segments: &[String]) -> Option<&'a JsonValue> {
+    let mut current = value;
+    for segment in segments {
+        current = current.as_object()?.get(segment)?;
+    }
+    (!current.is_null()).then_some(current)
+}
+
+fn format_toml_value(value: &JsonValue) -> Result<String> {
+    let value = toml::Value::try_from(value)?;
+    let formatted = match value {
+        toml::Value::Table(table) => toml::to_string_pretty(&table)?.trim_end().to_string(),
+        other => other.to_string(),
+    };
+    Ok(formatted)
+}
+
+fn describe_layer(source: &ConfigLayerSource) -> String {
+    match source {
+        ConfigLayerSource::Mdm { domain, key } => format!("managed preferences {domain}:{key}"),
+        ConfigLayerSource::System { file } => format!("system config {}", file.display()),
+        ConfigLayerSource::User { file } => format!("user config {}", file.display()),
+        ConfigLayerSource::Project { dot_codex_folder } => {
+            format!("project config {}", dot_codex_folder.display())
+        }
+        ConfigLayerSource::SessionFlags => "-c/--config flags".to_string(),
+        ConfigLayerSource::LegacyManagedConfigTomlFromFile { file } => {
+            format!("managed config {}", file.display())
+        }
+        ConfigLayerSource::LegacyManagedConfigTomlFromMdm => "managed preferences".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parse_toml_literal_falls_back_to_string() {
+        assert_eq!(parse_toml_literal("true"), toml::Value::Boolean(true));
+        assert_eq!(parse_toml_literal("42"), toml::Value::Integer(42));
+        assert_eq!(
+            parse_toml_literal("\"gpt-5\""),
+            toml::Value::String("gpt-5".to_string())
+        );
+        assert_eq!(
+            parse_toml_literal("gpt-5"),
+            toml::Value::String("gpt-5".to_string())
+        );
+    }
+
+    #[test]
+    fn split_key_path_rejects_empty_segments() {
+        assert_eq!(
+            split_key_path("tui.notifications").expect("valid key"),
+            vec!["tui".to_string(), "notifications".to_string()]
+        );
+        assert!(split_key_path("tui..notifications").is_err());
+    }
+}
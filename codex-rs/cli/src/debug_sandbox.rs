@@ -10,12 +10,15 @@ use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
 use codex_core::exec_env::create_env;
 use codex_core::landlock::spawn_command_under_linux_sandbox;
+use codex_core::sandbox_check::CheckStatus;
+use codex_core::sandbox_check::check_sandbox;
 #[cfg(target_os = "macos")]
 use codex_core::seatbelt::spawn_command_under_seatbelt;
 use codex_core::spawn::StdioPolicy;
 use codex_protocol::config_types::SandboxMode;
 
 use crate::LandlockCommand;
+use crate::SandboxCheckCommand;
 use crate::SeatbeltCommand;
 use crate::WindowsCommand;
 use crate::exit_status::handle_exit_status;
@@ -93,6 +96,42 @@ pub async fn run_command_under_windows(
     .await
 }
 
+/// Prints what the native sandbox can enforce on this machine. Fails when a
+/// part of it is unavailable.
+pub async fn run_sandbox_check(
+    command: SandboxCheckCommand,
+    codex_linux_sandbox_exe: Option<PathBuf>,
+) -> anyhow::Result<()> {
+    let config = Config::load_with_cli_overrides_and_harness_overrides(
+        command
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?,
+        ConfigOverrides {
+            codex_linux_sandbox_exe,
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    let checks = check_sandbox(&config);
+    for check in &checks {
+        let status = match check.status {
+            CheckStatus::Ok => "ok",
+            CheckStatus::Warning => "warning",
+            CheckStatus::Unavailable => "unavailable",
+        };
+        println!("{status:<12}{}: {}", check.name, check.detail);
+    }
+    if checks
+        .iter()
+        .any(|check| check.status == CheckStatus::Unavailable)
+    {
+        anyhow::bail!("the sandbox is not fully available on this machine");
+    }
+    Ok(())
+}
+
 enum SandboxType {
     #[cfg(target_os = "macos")]
     Seatbelt,
@@ -0,0 +1,88 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use codex_common::CliConfigOverrides;
+use codex_core::code_index::Embedder;
+use codex_core::code_index::build_index;
+use codex_core::code_index::clear_index;
+use codex_core::code_index::index_path;
+use codex_core::config::Config;
+use codex_core::git_info::get_git_repo_root;
+
+/// Subcommands:
+/// - `build` — create or update the local code index used by `search_code`,
+///   embedding changed files with the model configured in `[code_index]`
+/// - `clear` — delete the code index
+#[derive(Debug, clap::Parser)]
+pub struct IndexCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    pub subcommand: IndexSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum IndexSubcommand {
+    Build(IndexArgs),
+    Clear(IndexArgs),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct IndexArgs {
+    /// Directory inside the repository to index. Defaults to the current
+    /// directory; the whole repository is indexed either way.
+    pub path: Option<PathBuf>,
+}
+
+impl IndexCli {
+    pub async fn run(self) -> Result<()> {
+        let IndexCli {
+            config_overrides,
+            subcommand,
+        } = self;
+
+        let overrides = config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+
+        match subcommand {
+            IndexSubcommand::Build(args) => {
+                let repo_root = repo_root(&config, args)?;
+                let embedder = Embedder::new(&config.code_index);
+                let report = build_index(&embedder, &repo_root)
+                    .await
+                    .context("failed to build the code index")?;
+                println!(
+                    "Indexed {} chunks from {} files into {} ({} new or changed, {} unchanged, {} removed).",
+                    report.chunks,
+                    report.indexed_files + report.reused_files,
+                    index_path(&repo_root).display(),
+                    report.indexed_files,
+                    report.reused_files,
+                    report.removed_files
+                );
+            }
+            IndexSubcommand::Clear(args) => {
+                let repo_root = repo_root(&config, args)?;
+                if clear_index(&repo_root).context("failed to delete the code index")? {
+                    println!("Deleted the code index for {}.", repo_root.display());
+                } else {
+                    println!("{} has no code index.", repo_root.display());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn repo_root(config: &Config, args: IndexArgs) -> Result<PathBuf> {
+    let path = args.path.unwrap_or_else(|| config.cwd.clone());
+    get_git_repo_root(&path)
+        .with_context(|| format!("{} is not inside a git repository", path.display()))
+}
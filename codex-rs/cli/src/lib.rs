@@ -50,3 +50,9 @@ pub struct WindowsCommand {
     #[arg(trailing_var_arg = true)]
     pub command: Vec<String>,
 }
+
+#[derive(Debug, Parser)]
+pub struct SandboxCheckCommand {
+    #[clap(skip)]
+    pub config_overrides: CliConfigOverrides,
+}
@@ -3,8 +3,10 @@ use codex_common::CliConfigOverrides;
 use codex_core::CodexAuth;
 use codex_core::auth::AuthCredentialsStoreMode;
 use codex_core::auth::CLIENT_ID;
+use codex_core::auth::list_accounts;
 use codex_core::auth::login_with_api_key;
 use codex_core::auth::logout;
+use codex_core::auth::validate_account_name;
 use codex_core::config::Config;
 use codex_login::ServerOptions;
 use codex_login::run_device_code_login;
@@ -44,25 +46,29 @@ pub async fn login_with_chatgpt(
     server.block_until_done().await
 }
 
-pub async fn run_login_with_chatgpt(cli_config_overrides: CliConfigOverrides) -> ! {
-    let config = load_config_or_exit(cli_config_overrides).await;
+pub async fn run_login_with_chatgpt(
+    cli_config_overrides: CliConfigOverrides,
+    account: Option<String>,
+) -> ! {
+    let config = load_config_or_exit(cli_config_overrides, account).await;
 
     if matches!(config.forced_login_method, Some(ForcedLoginMethod::Api)) {
         eprintln!("{CHATGPT_LOGIN_DISABLED_MESSAGE}");
         std::process::exit(1);
     }
 
+    let auth_home = create_auth_home_or_exit(&config);
     let forced_chatgpt_workspace_id = config.forced_chatgpt_workspace_id.clone();
 
     match login_with_chatgpt(
-        config.codex_home,
+        auth_home,
         forced_chatgpt_workspace_id,
         config.cli_auth_credentials_store_mode,
     )
     .await
     {
         Ok(_) => {
-            eprintln!("{LOGIN_SUCCESS_MESSAGE}");
+            print_login_success(&config);
             std::process::exit(0);
         }
         Err(e) => {
@@ -75,21 +81,19 @@ pub async fn run_login_with_chatgpt(cli_config_overrides: CliConfigOverrides) ->
 pub async fn run_login_with_api_key(
     cli_config_overrides: CliConfigOverrides,
     api_key: String,
+    account: Option<String>,
 ) -> ! {
-    let config = load_config_or_exit(cli_config_overrides).await;
+    let config = load_config_or_exit(cli_config_overrides, account).await;
 
     if matches!(config.forced_login_method, Some(ForcedLoginMethod::Chatgpt)) {
         eprintln!("{API_KEY_LOGIN_DISABLED_MESSAGE}");
         std::process::exit(1);
     }
 
-    match login_with_api_key(
-        &config.codex_home,
-        &api_key,
-        config.cli_auth_credentials_store_mode,
-    ) {
+    let auth_home = create_auth_home_or_exit(&config);
+    match login_with_api_key(&auth_home, &api_key, config.cli_auth_credentials_store_mode) {
         Ok(_) => {
-            eprintln!("{LOGIN_SUCCESS_MESSAGE}");
+            print_login_success(&config);
             std::process::exit(0);
         }
         Err(e) => {
@@ -131,15 +135,17 @@ pub async fn run_login_with_device_code(
     cli_config_overrides: CliConfigOverrides,
     issuer_base_url: Option<String>,
     client_id: Option<String>,
+    account: Option<String>,
 ) -> ! {
-    let config = load_config_or_exit(cli_config_overrides).await;
+    let config = load_config_or_exit(cli_config_overrides, account).await;
     if matches!(config.forced_login_method, Some(ForcedLoginMethod::Api)) {
         eprintln!("{CHATGPT_LOGIN_DISABLED_MESSAGE}");
         std::process::exit(1);
     }
+    let auth_home = create_auth_home_or_exit(&config);
     let forced_chatgpt_workspace_id = config.forced_chatgpt_workspace_id.clone();
     let mut opts = ServerOptions::new(
-        config.codex_home,
+        auth_home,
         client_id.unwrap_or(CLIENT_ID.to_string()),
         forced_chatgpt_workspace_id,
         config.cli_auth_credentials_store_mode,
@@ -149,7 +155,7 @@ pub async fn run_login_with_device_code(
     }
     match run_device_code_login(opts).await {
         Ok(()) => {
-            eprintln!("{LOGIN_SUCCESS_MESSAGE}");
+            print_login_success(&config);
             std::process::exit(0);
         }
         Err(e) => {
@@ -167,16 +173,18 @@ pub async fn run_login_with_device_code_fallback_to_browser(
     cli_config_overrides: CliConfigOverrides,
     issuer_base_url: Option<String>,
     client_id: Option<String>,
+    account: Option<String>,
 ) -> ! {
-    let config = load_config_or_exit(cli_config_overrides).await;
+    let config = load_config_or_exit(cli_config_overrides, account).await;
     if matches!(config.forced_login_method, Some(ForcedLoginMethod::Api)) {
         eprintln!("{CHATGPT_LOGIN_DISABLED_MESSAGE}");
         std::process::exit(1);
     }
 
+    let auth_home = create_auth_home_or_exit(&config);
     let forced_chatgpt_workspace_id = config.forced_chatgpt_workspace_id.clone();
     let mut opts = ServerOptions::new(
-        config.codex_home,
+        auth_home,
         client_id.unwrap_or(CLIENT_ID.to_string()),
         forced_chatgpt_workspace_id,
         config.cli_auth_credentials_store_mode,
@@ -188,7 +196,7 @@ pub async fn run_login_with_device_code_fallback_to_browser(
 
     match run_device_code_login(opts.clone()).await {
         Ok(()) => {
-            eprintln!("{LOGIN_SUCCESS_MESSAGE}");
+            print_login_success(&config);
             std::process::exit(0);
         }
         Err(e) => {
@@ -199,7 +207,7 @@ pub async fn run_login_with_device_code_fallback_to_browser(
                         print_login_server_start(server.actual_port, &server.auth_url);
                         match server.block_until_done().await {
                             Ok(()) => {
-                                eprintln!("{LOGIN_SUCCESS_MESSAGE}");
+                                print_login_success(&config);
                                 std::process::exit(0);
                             }
                             Err(e) => {
@@ -221,10 +229,24 @@ pub async fn run_login_with_device_code_fallback_to_browser(
     }
 }
 
-pub async fn run_login_status(cli_config_overrides: CliConfigOverrides) -> ! {
-    let config = load_config_or_exit(cli_config_overrides).await;
+pub async fn run_login_status(
+    cli_config_overrides: CliConfigOverrides,
+    account: Option<String>,
+) -> ! {
+    let config = load_config_or_exit(cli_config_overrides, account).await;
+    if let Some(account) = config.account.as_deref() {
+        eprintln!("Account: {account}");
+    }
+    match list_accounts(&config.codex_home) {
+        Ok(accounts) if !accounts.is_empty() => {
+            eprintln!("Named accounts: {}", accounts.join(", "));
+        }
+        Ok(_) => {}
+        Err(e) => eprintln!("Failed to list named accounts: {e}"),
+    }
 
-    match CodexAuth::from_auth_storage(&config.codex_home, config.cli_auth_credentials_store_mode) {
+    match CodexAuth::from_auth_storage(&config.auth_home(), config.cli_auth_credentials_store_mode)
+    {
         Ok(Some(auth)) => match auth.api_auth_mode() {
             AuthMode::ApiKey => match auth.get_token() {
                 Ok(api_key) => {
@@ -256,10 +278,10 @@ pub async fn run_login_status(cli_config_overrides: CliConfigOverrides) -> ! {
     }
 }
 
-pub async fn run_logout(cli_config_overrides: CliConfigOverrides) -> ! {
-    let config = load_config_or_exit(cli_config_overrides).await;
+pub async fn run_logout(cli_config_overrides: CliConfigOverrides, account: Option<String>) -> ! {
+    let config = load_config_or_exit(cli_config_overrides, account).await;
 
-    match logout(&config.codex_home, config.cli_auth_credentials_store_mode) {
+    match logout(&config.auth_home(), config.cli_auth_credentials_store_mode) {
         Ok(true) => {
             eprintln!("Successfully logged out");
             std::process::exit(0);
@@ -275,7 +297,37 @@ pub async fn run_logout(cli_config_overrides: CliConfigOverrides) -> ! {
     }
 }
 
-async fn load_config_or_exit(cli_config_overrides: CliConfigOverrides) -> Config {
+fn print_login_success(config: &Config) {
+    match config.account.as_deref() {
+        Some(account) => eprintln!("{LOGIN_SUCCESS_MESSAGE} as account '{account}'"),
+        None => eprintln!("{LOGIN_SUCCESS_MESSAGE}"),
+    }
+}
+
+/// Creates the directory holding the selected account's credentials so the
+/// credential store resolves the same location on later loads.
+fn create_auth_home_or_exit(config: &Config) -> PathBuf {
+    let auth_home = config.auth_home();
+    if let Err(e) = std::fs::create_dir_all(&auth_home) {
+        eprintln!("Error creating {}: {e}", auth_home.display());
+        std::process::exit(1);
+    }
+    auth_home
+}
+
+/// Loads the config, replacing the configured `account` with `account` when
+/// `--name` was passed.
+async fn load_config_or_exit(
+    cli_config_overrides: CliConfigOverrides,
+    account: Option<String>,
+) -> Config {
+    if let Some(account) = account.as_deref()
+        && let Err(e) = validate_account_name(account)
+    {
+        eprintln!("{e}");
+        std::process::exit(1);
+    }
+
     let cli_overrides = match cli_config_overrides.parse_overrides() {
         Ok(v) => v,
         Err(e) => {
@@ -285,7 +337,12 @@ async fn load_config_or_exit(cli_config_overrides: CliConfigOverrides) -> Config
     };
 
     match Config::load_with_cli_overrides(cli_overrides).await {
-        Ok(config) => config,
+        Ok(mut config) => {
+            if account.is_some() {
+                config.account = account;
+            }
+            config
+        }
         Err(e) => {
             eprintln!("Error loading configuration: {e}");
             std::process::exit(1);
@@ -7,6 +7,7 @@ use codex_arg0::arg0_dispatch_or_else;
 use codex_chatgpt::apply_command::ApplyCommand;
 use codex_chatgpt::apply_command::run_apply_command;
 use codex_cli::LandlockCommand;
+use codex_cli::SandboxCheckCommand;
 use codex_cli::SeatbeltCommand;
 use codex_cli::WindowsCommand;
 use codex_cli::login::read_api_key_from_stdin;
@@ -31,11 +32,25 @@ use std::io::IsTerminal;
 use std::path::PathBuf;
 use supports_color::Stream;
 
+mod batch_cmd;
+mod bugreport_cmd;
+mod config_cmd;
+mod index_cmd;
 mod mcp_cmd;
+mod ollama_cmd;
+mod sessions_cmd;
+mod trust_cmd;
 #[cfg(not(windows))]
 mod wsl_paths;
 
+use crate::batch_cmd::BatchCli;
+use crate::bugreport_cmd::BugreportCli;
+use crate::config_cmd::ConfigCli;
+use crate::index_cmd::IndexCli;
 use crate::mcp_cmd::McpCli;
+use crate::ollama_cmd::OllamaCli;
+use crate::sessions_cmd::SessionsCli;
+use crate::trust_cmd::TrustCli;
 
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
@@ -119,6 +134,18 @@ enum Subcommand {
     /// Fork a previous interactive session (picker by default; use --last to fork the most recent).
     Fork(ForkCommand),
 
+    /// List recorded sessions with a short summary of each.
+    Sessions(SessionsCli),
+
+    /// Build the local code index used by the `search_code` tool.
+    Index(IndexCli),
+
+    /// Run independent tasks from a YAML file in parallel sessions, each in its own worktree.
+    Batch(BatchCli),
+
+    /// List and download models for the local Ollama provider.
+    Ollama(OllamaCli),
+
     /// [EXPERIMENTAL] Browse tasks from Codex Cloud and apply changes locally.
     #[clap(name = "cloud", alias = "cloud-tasks")]
     Cloud(CloudTasksCli),
@@ -133,6 +160,15 @@ enum Subcommand {
 
     /// Inspect feature flags.
     Features(FeaturesCli),
+
+    /// Inspect, edit, and validate config.toml.
+    Config(ConfigCli),
+
+    /// List and set the trust level of project directories.
+    Trust(TrustCli),
+
+    /// Bundle redacted logs, config, and the last session's timeline for a bug report.
+    Bugreport(BugreportCli),
 }
 
 #[derive(Debug, Parser)]
@@ -198,6 +234,9 @@ enum SandboxCommand {
 
     /// Run a command under Windows restricted token (Windows only).
     Windows(WindowsCommand),
+
+    /// Report which sandbox this machine supports and what it enforces.
+    Check(SandboxCheckCommand),
 }
 
 #[derive(Debug, Parser)]
@@ -235,6 +274,11 @@ struct LoginCommand {
     #[arg(long = "device-auth")]
     use_device_code: bool,
 
+    /// Store the credentials under this account name instead of the default
+    /// login. Select it later with `account = "<NAME>"` in config.toml.
+    #[arg(long = "name", value_name = "NAME", global = true)]
+    name: Option<String>,
+
     /// EXPERIMENTAL: Use custom OAuth issuer base URL (advanced)
     /// Override the OAuth issuer base URL (advanced)
     #[arg(long = "experimental_issuer", value_name = "URL", hide = true)]
@@ -258,6 +302,10 @@ enum LoginSubcommand {
 struct LogoutCommand {
     #[clap(skip)]
     config_overrides: CliConfigOverrides,
+
+    /// Log out of this named account instead of the default login.
+    #[arg(long = "name", value_name = "NAME")]
+    name: Option<String>,
 }
 
 #[derive(Debug, Parser)]
@@ -536,6 +584,55 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             prepend_config_flags(&mut mcp_cli.config_overrides, root_config_overrides.clone());
             mcp_cli.run().await?;
         }
+        Some(Subcommand::Config(mut config_cli)) => {
+            prepend_config_flags(
+                &mut config_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            config_cli.run().await?;
+        }
+        Some(Subcommand::Sessions(mut sessions_cli)) => {
+            prepend_config_flags(
+                &mut sessions_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            sessions_cli.run().await?;
+        }
+        Some(Subcommand::Index(mut index_cli)) => {
+            prepend_config_flags(
+                &mut index_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            index_cli.run().await?;
+        }
+        Some(Subcommand::Batch(mut batch_cli)) => {
+            prepend_config_flags(
+                &mut batch_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            batch_cli.run(codex_linux_sandbox_exe).await?;
+        }
+        Some(Subcommand::Trust(mut trust_cli)) => {
+            prepend_config_flags(
+                &mut trust_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            trust_cli.run().await?;
+        }
+        Some(Subcommand::Bugreport(mut bugreport_cli)) => {
+            prepend_config_flags(
+                &mut bugreport_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            bugreport_cli.run().await?;
+        }
+        Some(Subcommand::Ollama(mut ollama_cli)) => {
+            prepend_config_flags(
+                &mut ollama_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            ollama_cli.run().await?;
+        }
         Some(Subcommand::AppServer(app_server_cli)) => match app_server_cli.subcommand {
             None => {
                 codex_app_server::run_main(
@@ -605,7 +702,7 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             );
             match login_cli.action {
                 Some(LoginSubcommand::Status) => {
-                    run_login_status(login_cli.config_overrides).await;
+                    run_login_status(login_cli.config_overrides, login_cli.name).await;
                 }
                 None => {
                     if login_cli.use_device_code {
@@ -613,6 +710,7 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                             login_cli.config_overrides,
                             login_cli.issuer_base_url,
                             login_cli.client_id,
+                            login_cli.name,
                         )
                         .await;
                     } else if login_cli.api_key.is_some() {
@@ -622,9 +720,10 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                         std::process::exit(1);
                     } else if login_cli.with_api_key {
                         let api_key = read_api_key_from_stdin();
-                        run_login_with_api_key(login_cli.config_overrides, api_key).await;
+                        run_login_with_api_key(login_cli.config_overrides, api_key, login_cli.name)
+                            .await;
                     } else {
-                        run_login_with_chatgpt(login_cli.config_overrides).await;
+                        run_login_with_chatgpt(login_cli.config_overrides, login_cli.name).await;
                     }
                 }
             }
@@ -634,7 +733,7 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                 &mut logout_cli.config_overrides,
                 root_config_overrides.clone(),
             );
-            run_logout(logout_cli.config_overrides).await;
+            run_logout(logout_cli.config_overrides, logout_cli.name).await;
         }
         Some(Subcommand::Completion(completion_cli)) => {
             print_completion(completion_cli);
@@ -680,6 +779,14 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                 )
                 .await?;
             }
+            SandboxCommand::Check(mut check_cli) => {
+                prepend_config_flags(
+                    &mut check_cli.config_overrides,
+                    root_config_overrides.clone(),
+                );
+                codex_cli::debug_sandbox::run_sandbox_check(check_cli, codex_linux_sandbox_exe)
+                    .await?;
+            }
         },
         Some(Subcommand::Execpolicy(ExecpolicyCommand { sub })) => match sub {
             ExecpolicySubcommand::Check(cmd) => run_execpolicycheck(cmd)?,
@@ -917,6 +1024,12 @@ fn merge_interactive_cli_flags(interactive: &mut TuiCli, subcommand_cli: TuiCli)
     if subcommand_cli.oss {
         interactive.oss = true;
     }
+    if let Some(effort) = subcommand_cli.reasoning_effort {
+        interactive.reasoning_effort = Some(effort);
+    }
+    if let Some(verbosity) = subcommand_cli.verbosity {
+        interactive.verbosity = Some(verbosity);
+    }
     if let Some(profile) = subcommand_cli.config_profile {
         interactive.config_profile = Some(profile);
     }
@@ -944,6 +1057,12 @@ fn merge_interactive_cli_flags(interactive: &mut TuiCli, subcommand_cli: TuiCli)
     if !subcommand_cli.add_dir.is_empty() {
         interactive.add_dir.extend(subcommand_cli.add_dir);
     }
+    if let Some(dir) = subcommand_cli.record_api {
+        interactive.record_api = Some(dir);
+    }
+    if let Some(path) = subcommand_cli.replay_api {
+        interactive.replay_api = Some(path);
+    }
     if let Some(prompt) = subcommand_cli.prompt {
         // Normalize CRLF/CR to LF so CLI-provided text can't leak `\r` into TUI state.
         interactive.prompt = Some(prompt.replace("\r\n", "\n").replace('\r', "\n"));
@@ -1305,6 +1424,34 @@ mod tests {
         assert_eq!(feature, "shell_tool");
     }
 
+    #[test]
+    fn config_set_parses_key_and_value() {
+        let cli = MultitoolCli::try_parse_from(["codex", "config", "set", "model", "gpt-5"])
+            .expect("parse should succeed");
+        let Some(Subcommand::Config(config_cli)) = cli.subcommand else {
+            panic!("expected config subcommand");
+        };
+        let config_cmd::ConfigSubcommand::Set(args) = config_cli.subcommand else {
+            panic!("expected config set");
+        };
+        assert_eq!(args.key, "model");
+        assert_eq!(args.value, "gpt-5");
+    }
+
+    #[test]
+    fn trust_set_parses_level_and_path() {
+        let cli = MultitoolCli::try_parse_from(["codex", "trust", "set", "limited", "/tmp/repo"])
+            .expect("parse should succeed");
+        let Some(Subcommand::Trust(trust_cli)) = cli.subcommand else {
+            panic!("expected trust subcommand");
+        };
+        let trust_cmd::TrustSubcommand::Set(args) = trust_cli.subcommand else {
+            panic!("expected trust set");
+        };
+        assert_eq!(args.level, trust_cmd::TrustLevelArg::Limited);
+        assert_eq!(args.path, Some(PathBuf::from("/tmp/repo")));
+    }
+
     #[test]
     fn feature_toggles_known_features_generate_overrides() {
         let toggles = FeatureToggles {
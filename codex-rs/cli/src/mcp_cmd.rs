@@ -124,6 +124,10 @@ pub struct AddMcpStreamableHttpArgs {
         requires = "url"
     )]
     pub bearer_token_env_var: Option<String>,
+
+    /// Connect with the older HTTP+SSE transport instead of streamable HTTP.
+    #[arg(long, requires = "url")]
+    pub sse: bool,
 }
 
 #[derive(Debug, clap::Parser)]
@@ -229,14 +233,26 @@ async fn run_add(config_overrides: &CliConfigOverrides, add_args: AddArgs) -> Re
                 Some(AddMcpStreamableHttpArgs {
                     url,
                     bearer_token_env_var,
+                    sse,
                 }),
             ..
-        } => McpServerTransportConfig::StreamableHttp {
-            url,
-            bearer_token_env_var,
-            http_headers: None,
-            env_http_headers: None,
-        },
+        } => {
+            if sse {
+                McpServerTransportConfig::Sse {
+                    url,
+                    bearer_token_env_var,
+                    http_headers: None,
+                    env_http_headers: None,
+                }
+            } else {
+                McpServerTransportConfig::StreamableHttp {
+                    url,
+                    bearer_token_env_var,
+                    http_headers: None,
+                    env_http_headers: None,
+                }
+            }
+        }
         AddMcpTransportArgs { .. } => bail!("exactly one of --command or --url must be provided"),
     };
 
@@ -249,6 +265,12 @@ async fn run_add(config_overrides: &CliConfigOverrides, add_args: AddArgs) -> Re
         enabled_tools: None,
         disabled_tools: None,
         scopes: None,
+        lazy_start: false,
+        tool_aliases: None,
+        tool_approval: None,
+        tool_approvals: None,
+        tool_output_token_limit: None,
+        tool_output_token_limits: None,
     };
 
     servers.insert(name.clone(), new_entry);
@@ -435,9 +457,15 @@ async fn run_list(config_overrides: &CliConfigOverrides, list_args: ListArgs) ->
                         bearer_token_env_var,
                         http_headers,
                         env_http_headers,
+                    }
+                    | McpServerTransportConfig::Sse {
+                        url,
+                        bearer_token_env_var,
+                        http_headers,
+                        env_http_headers,
                     } => {
                         serde_json::json!({
-                            "type": "streamable_http",
+                            "type": transport_name(&cfg.transport),
                             "url": url,
                             "bearer_token_env_var": bearer_token_env_var,
                             "http_headers": http_headers,
@@ -514,6 +542,11 @@ async fn run_list(config_overrides: &CliConfigOverrides, list_args: ListArgs) ->
                 url,
                 bearer_token_env_var,
                 ..
+            }
+            | McpServerTransportConfig::Sse {
+                url,
+                bearer_token_env_var,
+                ..
             } => {
                 let status = format_mcp_status(cfg);
                 let auth_status = auth_statuses
@@ -674,8 +707,14 @@ async fn run_get(config_overrides: &CliConfigOverrides, get_args: GetArgs) -> Re
                 bearer_token_env_var,
                 http_headers,
                 env_http_headers,
+            }
+            | McpServerTransportConfig::Sse {
+                url,
+                bearer_token_env_var,
+                http_headers,
+                env_http_headers,
             } => serde_json::json!({
-                "type": "streamable_http",
+                "type": transport_name(&server.transport),
                 "url": url,
                 "bearer_token_env_var": bearer_token_env_var,
                 "http_headers": http_headers,
@@ -756,8 +795,14 @@ async fn run_get(config_overrides: &CliConfigOverrides, get_args: GetArgs) -> Re
             bearer_token_env_var,
             http_headers,
             env_http_headers,
+        }
+        | McpServerTransportConfig::Sse {
+            url,
+            bearer_token_env_var,
+            http_headers,
+            env_http_headers,
         } => {
-            println!("  transport: streamable_http");
+            println!("  transport: {}", transport_name(&server.transport));
             println!("  url: {url}");
             let bearer_token_display = bearer_token_env_var.as_deref().unwrap_or("-");
             println!("  bearer_token_env_var: {bearer_token_display}");
@@ -837,3 +882,11 @@ fn format_mcp_status(config: &McpServerConfig) -> String {
         "disabled".to_string()
     }
 }
+
+fn transport_name(transport: &McpServerTransportConfig) -> &'static str {
+    match transport {
+        McpServerTransportConfig::Stdio { .. } => "stdio",
+        McpServerTransportConfig::StreamableHttp { .. } => "streamable_http",
+        McpServerTransportConfig::Sse { .. } => "sse",
+    }
+}
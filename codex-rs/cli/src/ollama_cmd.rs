@@ -0,0 +1,99 @@
+use anyhow::Context;
+use anyhow::Result;
+use codex_common::CliConfigOverrides;
+use codex_common::oss::is_ollama_provider;
+use codex_core::config::Config;
+use codex_ollama::CliProgressReporter;
+use codex_ollama::OllamaClient;
+
+/// Subcommands:
+/// - `list` — show the models installed in the local Ollama server
+/// - `pull` — download a model into the local Ollama server
+#[derive(Debug, clap::Parser)]
+pub struct OllamaCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    pub subcommand: OllamaSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum OllamaSubcommand {
+    List,
+    Pull(PullArgs),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct PullArgs {
+    /// Model to download, e.g. `qwen3:8b`.
+    pub model: String,
+}
+
+impl OllamaCli {
+    pub async fn run(self) -> Result<()> {
+        let OllamaCli {
+            config_overrides,
+            subcommand,
+        } = self;
+
+        let overrides = config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+        let client = connect(&config).await?;
+
+        match subcommand {
+            OllamaSubcommand::List => run_list(&client).await?,
+            OllamaSubcommand::Pull(args) => {
+                let mut reporter = CliProgressReporter::new();
+                client
+                    .pull_with_reporter(&args.model, &mut reporter)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Connect to the selected provider when it is an Ollama provider, otherwise
+/// to the built-in `ollama` provider.
+async fn connect(config: &Config) -> Result<OllamaClient> {
+    let client = if is_ollama_provider(&config.model_provider_id)
+        && config.model_provider.base_url.is_some()
+    {
+        OllamaClient::try_from_provider(&config.model_provider).await?
+    } else {
+        OllamaClient::try_from_oss_provider(config).await?
+    };
+    Ok(client)
+}
+
+async fn run_list(client: &OllamaClient) -> Result<()> {
+    let mut models = client.fetch_models().await?;
+    if models.is_empty() {
+        println!("No models installed. Download one with `codex ollama pull <model>`.");
+        return Ok(());
+    }
+    models.sort();
+
+    let width = models.iter().map(String::len).max().unwrap_or_default();
+    for model in models {
+        let details = client.fetch_model_details(&model).await.ok().flatten();
+        let context = details
+            .as_ref()
+            .and_then(|details| details.context_length)
+            .map(|tokens| format!("{tokens} tokens"))
+            .unwrap_or_else(|| "-".to_string());
+        let capabilities = details
+            .map(|details| details.capabilities.join(", "))
+            .filter(|capabilities| !capabilities.is_empty())
+            .unwrap_or_else(|| "-".to_string());
+        println!("{model:<width$}  {context:>14}  {capabilities}");
+    }
+
+    Ok(())
+}
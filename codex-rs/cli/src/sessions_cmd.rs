@@ -0,0 +1,198 @@
+use std::collections::HashSet;
+
+use anyhow::Context;
+use anyhow::Result;
+use codex_common::CliConfigOverrides;
+use codex_core::INTERACTIVE_SESSION_SOURCES;
+use codex_core::RolloutRecorder;
+use codex_core::ThreadItem;
+use codex_core::ThreadSortKey;
+use codex_core::config::Config;
+use codex_core::config::log_dir;
+use codex_core::find_session_summaries_by_ids;
+use codex_core::find_thread_names_by_ids;
+use codex_core::prune_sessions;
+use codex_core::summarize_rollout_file;
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::SessionMetaLine;
+
+/// Subcommands:
+/// - `list` — show recent sessions with a two-line summary of each
+/// - `prune` — delete sessions and logs beyond the `[session_retention]` limits
+#[derive(Debug, clap::Parser)]
+pub struct SessionsCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    pub subcommand: SessionsSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum SessionsSubcommand {
+    List(ListArgs),
+    Prune(PruneArgs),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ListArgs {
+    /// Maximum number of sessions to show, newest first.
+    #[arg(long, short = 'n', default_value_t = 20)]
+    pub limit: usize,
+}
+
+/// Limits passed on the command line replace the configured ones.
+#[derive(Debug, clap::Parser)]
+pub struct PruneArgs {
+    /// List what would be deleted without deleting anything.
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Delete sessions and logs not modified for this many days.
+    #[arg(long, value_name = "DAYS")]
+    pub max_age_days: Option<u64>,
+
+    /// Delete the oldest sessions once together they exceed this many megabytes.
+    #[arg(long, value_name = "MB")]
+    pub max_total_size_mb: Option<u64>,
+
+    /// Keep at most this many sessions per working directory.
+    #[arg(long, value_name = "N")]
+    pub max_sessions_per_project: Option<usize>,
+}
+
+impl SessionsCli {
+    pub async fn run(self) -> Result<()> {
+        let SessionsCli {
+            config_overrides,
+            subcommand,
+        } = self;
+
+        match subcommand {
+            SessionsSubcommand::List(args) => run_list(&config_overrides, args).await?,
+            SessionsSubcommand::Prune(args) => run_prune(&config_overrides, args).await?,
+        }
+
+        Ok(())
+    }
+}
+
+async fn run_list(config_overrides: &CliConfigOverrides, list_args: ListArgs) -> Result<()> {
+    let overrides = config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let config = Config::load_with_cli_overrides(overrides)
+        .await
+        .context("failed to load configuration")?;
+
+    let page = RolloutRecorder::list_threads(
+        &config.codex_home,
+        list_args.limit,
+        None,
+        ThreadSortKey::UpdatedAt,
+        INTERACTIVE_SESSION_SOURCES,
+        None,
+        &config.model_provider_id,
+    )
+    .await
+    .context("failed to list sessions")?;
+    if page.items.is_empty() {
+        println!("No sessions found.");
+        return Ok(());
+    }
+
+    let sessions: Vec<_> = page
+        .items
+        .iter()
+        .filter_map(|item| session_meta(item).map(|meta| (item, meta)))
+        .collect();
+    let ids: HashSet<ThreadId> = sessions.iter().map(|(_, meta)| meta.meta.id).collect();
+    let names = find_thread_names_by_ids(&config.codex_home, &ids)
+        .await
+        .unwrap_or_default();
+    let summaries = find_session_summaries_by_ids(&config.codex_home, &ids)
+        .await
+        .unwrap_or_default();
+
+    for (item, meta) in sessions {
+        let id = meta.meta.id;
+        let updated = item
+            .updated_at
+            .as_deref()
+            .or(item.created_at.as_deref())
+            .unwrap_or("-");
+        let mut header = format!("{id}  {updated}  {}", meta.meta.cwd.display());
+        if let Some(name) = names.get(&id) {
+            header.push_str(&format!("  ({name})"));
+        }
+        println!("{header}");
+
+        // Sessions that ended without a clean shutdown have no stored summary yet.
+        let summary = match summaries.get(&id) {
+            Some(summary) => Some(summary.clone()),
+            None => summarize_rollout_file(&item.path).await.ok().flatten(),
+        };
+        match summary {
+            Some(summary) => {
+                println!("    {}", summary.title);
+                println!("    {}", summary.outcome);
+            }
+            None => println!("    (no messages yet)"),
+        }
+    }
+
+    Ok(())
+}
+
+async fn run_prune(config_overrides: &CliConfigOverrides, prune_args: PruneArgs) -> Result<()> {
+    let overrides = config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let config = Config::load_with_cli_overrides(overrides)
+        .await
+        .context("failed to load configuration")?;
+
+    let mut policy = config.session_retention;
+    if let Some(days) = prune_args.max_age_days {
+        policy.max_age_days = Some(days);
+    }
+    if let Some(megabytes) = prune_args.max_total_size_mb {
+        policy.max_total_bytes = Some(megabytes.saturating_mul(1024 * 1024));
+    }
+    if let Some(max) = prune_args.max_sessions_per_project {
+        policy.max_sessions_per_project = Some(max);
+    }
+    if policy.is_unbounded() {
+        anyhow::bail!(
+            "no retention limits set; configure [session_retention] in config.toml or pass --max-age-days, --max-total-size-mb, or --max-sessions-per-project"
+        );
+    }
+
+    let log_dir = log_dir(&config).context("failed to resolve the log directory")?;
+    let codex_home = config.codex_home.clone();
+    let dry_run = prune_args.dry_run;
+    let report = tokio::task::spawn_blocking(move || {
+        prune_sessions(&codex_home, &log_dir, &policy, dry_run)
+    })
+    .await?
+    .context("failed to prune sessions")?;
+
+    for path in &report.removed {
+        println!("{}", path.display());
+    }
+    let verb = if dry_run { "Would remove" } else { "Removed" };
+    println!(
+        "{verb} {} files ({:.1} MB); {} sessions kept.",
+        report.removed.len(),
+        report.freed_bytes as f64 / (1024.0 * 1024.0),
+        report.kept
+    );
+
+    Ok(())
+}
+
+fn session_meta(item: &ThreadItem) -> Option<SessionMetaLine> {
+    item.head
+        .iter()
+        .find_map(|value| serde_json::from_value::<SessionMetaLine>(value.clone()).ok())
+}
@@ -0,0 +1,154 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use codex_common::CliConfigOverrides;
+use codex_core::config::Config;
+use codex_core::config::ConfigToml;
+use codex_core::config::find_codex_home;
+use codex_core::config::set_project_trust_level;
+use codex_core::git_info::resolve_root_git_project_for_trust;
+use codex_protocol::config_types::TrustLevel;
+
+/// Subcommands:
+/// - `list` — show the trust level recorded for each project directory
+/// - `set`  — record the trust level for a directory
+#[derive(Debug, clap::Parser)]
+pub struct TrustCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    pub subcommand: TrustSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum TrustSubcommand {
+    List,
+    Set(SetArgs),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum TrustLevelArg {
+    /// Workspace-write sandbox; MCP servers and web search available.
+    Trusted,
+    /// Read-only sandbox; no MCP servers or web search.
+    Limited,
+    /// Read-only sandbox; approval for anything not known to be safe.
+    Untrusted,
+}
+
+impl From<TrustLevelArg> for TrustLevel {
+    fn from(level: TrustLevelArg) -> Self {
+        match level {
+            TrustLevelArg::Trusted => TrustLevel::Trusted,
+            TrustLevelArg::Limited => TrustLevel::Limited,
+            TrustLevelArg::Untrusted => TrustLevel::Untrusted,
+        }
+    }
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct SetArgs {
+    /// Trust level to record.
+    #[arg(value_enum)]
+    pub level: TrustLevelArg,
+
+    /// Directory to set the level for. Defaults to the current directory.
+    /// Inside a git repository the level applies to the whole repository.
+    pub path: Option<PathBuf>,
+}
+
+impl TrustCli {
+    pub async fn run(self) -> Result<()> {
+        let TrustCli {
+            config_overrides,
+            subcommand,
+        } = self;
+
+        match subcommand {
+            TrustSubcommand::List => run_list(&config_overrides).await?,
+            TrustSubcommand::Set(args) => run_set(args)?,
+        }
+
+        Ok(())
+    }
+}
+
+async fn run_list(config_overrides: &CliConfigOverrides) -> Result<()> {
+    let overrides = config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let config = Config::load_with_cli_overrides(overrides)
+        .await
+        .context("failed to load configuration")?;
+    let config_toml: ConfigToml = config
+        .config_layer_stack
+        .effective_config()
+        .try_into()
+        .context("failed to read configured projects")?;
+
+    let mut projects: Vec<(String, TrustLevel)> = config_toml
+        .projects
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|(path, project)| project.trust_level.map(|level| (path, level)))
+        .collect();
+    projects.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    if projects.is_empty() {
+        println!("No trust levels recorded.");
+    }
+    let current = trust_target(config.cwd.clone());
+    for (path, level) in &projects {
+        let marker = if PathBuf::from(path) == current {
+            "*"
+        } else {
+            " "
+        };
+        let level = level.to_string();
+        println!("{marker} {level:<9}  {path}");
+    }
+
+    println!();
+    match (config.active_project.trust_level, config.trust_preset) {
+        (Some(level), Some(preset)) => println!(
+            "{} is {level}: sandbox {}, approval policy {}, MCP servers {}, web search {}",
+            current.display(),
+            preset.sandbox_mode,
+            preset.approval_policy,
+            on_off(preset.mcp_servers),
+            on_off(preset.web_search),
+        ),
+        _ => println!("{} has no trust level.", current.display()),
+    }
+
+    Ok(())
+}
+
+fn run_set(set_args: SetArgs) -> Result<()> {
+    let SetArgs { level, path } = set_args;
+    let path = match path {
+        Some(path) => path,
+        None => std::env::current_dir().context("failed to resolve current directory")?,
+    };
+    let path = std::fs::canonicalize(&path)
+        .with_context(|| format!("failed to resolve {}", path.display()))?;
+    let target = trust_target(path);
+    let level = TrustLevel::from(level);
+
+    let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+    set_project_trust_level(&codex_home, &target, level)?;
+    println!("Marked {} as {level}.", target.display());
+    Ok(())
+}
+
+/// The directory a trust level is recorded for: the root of the enclosing
+/// git repository, or the directory itself.
+fn trust_target(dir: PathBuf) -> PathBuf {
+    resolve_root_git_project_for_trust(&dir).unwrap_or(dir)
+}
+
+fn on_off(enabled: bool) -> &'static str {
+    if enabled { "on" } else { "off" }
+}
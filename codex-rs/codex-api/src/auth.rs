@@ -1,4 +1,7 @@
 use codex_client::Request;
+use http::HeaderMap;
+use http::HeaderValue;
+use http::header::HeaderName;
 
 /// Provides bearer and account identity information for API requests.
 ///
@@ -10,18 +13,34 @@ pub trait AuthProvider: Send + Sync {
     fn account_id(&self) -> Option<String> {
         None
     }
+    /// Header that carries the token. Defaults to `Authorization`.
+    fn auth_header_name(&self) -> HeaderName {
+        http::header::AUTHORIZATION
+    }
+    /// Scheme written before the token, or `None` to send the raw token.
+    fn auth_scheme(&self) -> Option<String> {
+        Some("Bearer".to_string())
+    }
 }
 
 pub(crate) fn add_auth_headers<A: AuthProvider>(auth: &A, mut req: Request) -> Request {
-    if let Some(token) = auth.bearer_token()
-        && let Ok(header) = format!("Bearer {token}").parse()
-    {
-        let _ = req.headers.insert(http::header::AUTHORIZATION, header);
+    apply_auth_headers(&mut req.headers, auth);
+    req
+}
+
+pub(crate) fn apply_auth_headers(headers: &mut HeaderMap, auth: &impl AuthProvider) {
+    if let Some(token) = auth.bearer_token() {
+        let value = match auth.auth_scheme() {
+            Some(scheme) => format!("{scheme} {token}"),
+            None => token,
+        };
+        if let Ok(header) = HeaderValue::from_str(&value) {
+            let _ = headers.insert(auth.auth_header_name(), header);
+        }
     }
     if let Some(account_id) = auth.account_id()
-        && let Ok(header) = account_id.parse()
+        && let Ok(header) = HeaderValue::from_str(&account_id)
     {
-        let _ = req.headers.insert("ChatGPT-Account-ID", header);
+        let _ = headers.insert("ChatGPT-Account-ID", header);
     }
-    req
 }
@@ -64,6 +64,9 @@ pub enum ResponseEvent {
     },
     RateLimits(RateLimitSnapshot),
     ModelsEtag(String),
+    /// The exact model snapshot the provider reported serving the request
+    /// with, such as `gpt-5-2025-08-07`.
+    ServedModel(String),
 }
 
 #[derive(Debug, Serialize, Clone)]
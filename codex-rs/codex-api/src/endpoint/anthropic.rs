@@ -0,0 +1,72 @@
+use crate::auth::AuthProvider;
+use crate::common::Prompt as ApiPrompt;
+use crate::common::ResponseStream;
+use crate::endpoint::streaming::StreamingClient;
+use crate::error::ApiError;
+use crate::provider::Provider;
+use crate::requests::AnthropicRequest;
+use crate::requests::AnthropicRequestBuilder;
+use crate::sse::anthropic::spawn_anthropic_stream;
+use crate::telemetry::SseTelemetry;
+use codex_client::HttpTransport;
+use codex_client::RequestCompression;
+use codex_client::RequestTelemetry;
+use codex_protocol::protocol::SessionSource;
+use std::sync::Arc;
+
+/// Streams turns through the Anthropic Messages API (`/v1/messages`).
+pub struct AnthropicClient<T: HttpTransport, A: AuthProvider> {
+    streaming: StreamingClient<T, A>,
+}
+
+impl<T: HttpTransport, A: AuthProvider> AnthropicClient<T, A> {
+    pub fn new(transport: T, provider: Provider, auth: A) -> Self {
+        Self {
+            streaming: StreamingClient::new(transport, provider, auth),
+        }
+    }
+
+    pub fn with_telemetry(
+        self,
+        request: Option<Arc<dyn RequestTelemetry>>,
+        sse: Option<Arc<dyn SseTelemetry>>,
+    ) -> Self {
+        Self {
+            streaming: self.streaming.with_telemetry(request, sse),
+        }
+    }
+
+    pub async fn stream_request(
+        &self,
+        request: AnthropicRequest,
+    ) -> Result<ResponseStream, ApiError> {
+        self.streaming
+            .stream(
+                "messages",
+                request.body,
+                request.headers,
+                RequestCompression::None,
+                spawn_anthropic_stream,
+                None,
+            )
+            .await
+    }
+
+    pub async fn stream_prompt(
+        &self,
+        model: &str,
+        prompt: &ApiPrompt,
+        conversation_id: Option<String>,
+        session_source: Option<SessionSource>,
+        prompt_caching: bool,
+    ) -> Result<ResponseStream, ApiError> {
+        let request =
+            AnthropicRequestBuilder::new(model, &prompt.instructions, &prompt.input, &prompt.tools)
+                .conversation_id(conversation_id)
+                .session_source(session_source)
+                .prompt_caching(prompt_caching)
+                .build(self.streaming.provider())?;
+
+        self.stream_request(request).await
+    }
+}
@@ -166,6 +166,9 @@ impl Stream for AggregatedStream {
                 Poll::Ready(Some(Ok(ResponseEvent::ModelsEtag(etag)))) => {
                     return Poll::Ready(Some(Ok(ResponseEvent::ModelsEtag(etag))));
                 }
+                Poll::Ready(Some(Ok(ResponseEvent::ServedModel(model)))) => {
+                    return Poll::Ready(Some(Ok(ResponseEvent::ServedModel(model))));
+                }
                 Poll::Ready(Some(Ok(ResponseEvent::Completed {
                     response_id,
                     token_usage,
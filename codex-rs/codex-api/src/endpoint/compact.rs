@@ -39,7 +39,7 @@ impl<T: HttpTransport, A: AuthProvider> CompactClient<T, A> {
     fn path(&self) -> Result<&'static str, ApiError> {
         match self.provider.wire {
             WireApi::Compact | WireApi::Responses => Ok("responses/compact"),
-            WireApi::Chat => Err(ApiError::Stream(
+            WireApi::Chat | WireApi::Anthropic => Err(ApiError::Stream(
                 "compact endpoint requires responses wire api".to_string(),
             )),
         }
@@ -131,9 +131,11 @@ mod tests {
             retry: RetryConfig {
                 max_attempts: 1,
                 base_delay: Duration::from_millis(1),
+                max_delay: Duration::from_secs(1),
                 retry_429: false,
                 retry_5xx: true,
                 retry_transport: true,
+                retry_statuses: Vec::new(),
             },
             stream_idle_timeout: Duration::from_secs(1),
         }
@@ -5,10 +5,12 @@ use crate::provider::Provider;
 use crate::telemetry::run_with_request_telemetry;
 use codex_client::HttpTransport;
 use codex_client::RequestTelemetry;
+use codex_client::TransportError;
 use codex_protocol::openai_models::ModelInfo;
 use codex_protocol::openai_models::ModelsResponse;
 use http::HeaderMap;
 use http::Method;
+use http::StatusCode;
 use http::header::ETAG;
 use std::sync::Arc;
 
@@ -77,6 +79,32 @@ impl<T: HttpTransport, A: AuthProvider> ModelsClient<T, A> {
 
         Ok((models, header_etag))
     }
+
+    /// Asks the provider whether it still serves `model`, using the
+    /// OpenAI-compatible `GET /models/{model}`. A 404 means it does not.
+    pub async fn model_served(&self, model: &str) -> Result<bool, ApiError> {
+        let builder = || {
+            let req = self
+                .provider
+                .build_request(Method::GET, &format!("{}/{model}", self.path()));
+            add_auth_headers(&self.auth, req)
+        };
+
+        match run_with_request_telemetry(
+            self.provider.retry.to_policy(),
+            self.request_telemetry.clone(),
+            builder,
+            |req| self.transport.execute(req),
+        )
+        .await
+        {
+            Ok(_) => Ok(true),
+            Err(TransportError::Http { status, .. }) if status == StatusCode::NOT_FOUND => {
+                Ok(false)
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -154,9 +182,11 @@ mod tests {
             retry: RetryConfig {
                 max_attempts: 1,
                 base_delay: Duration::from_millis(1),
+                max_delay: Duration::from_secs(1),
                 retry_429: false,
                 retry_5xx: true,
                 retry_transport: true,
+                retry_statuses: Vec::new(),
             },
             stream_idle_timeout: Duration::from_secs(1),
         }
@@ -276,4 +306,54 @@ mod tests {
         assert_eq!(models.len(), 0);
         assert_eq!(etag, Some("\"abc\"".to_string()));
     }
+
+    #[derive(Clone)]
+    struct NotFoundTransport;
+
+    #[async_trait]
+    impl HttpTransport for NotFoundTransport {
+        async fn execute(&self, req: Request) -> Result<Response, TransportError> {
+            if req.url.ends_with("/models/gpt-5-2025-08-07") {
+                return Ok(Response {
+                    status: StatusCode::OK,
+                    headers: HeaderMap::new(),
+                    body: Vec::new().into(),
+                });
+            }
+            Err(TransportError::Http {
+                status: StatusCode::NOT_FOUND,
+                url: Some(req.url),
+                headers: None,
+                body: None,
+            })
+        }
+
+        async fn stream(&self, _req: Request) -> Result<StreamResponse, TransportError> {
+            Err(TransportError::Build("stream should not run".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn model_served_treats_not_found_as_retired() {
+        let client = ModelsClient::new(
+            NotFoundTransport,
+            provider("https://example.com/v1"),
+            DummyAuth,
+        );
+
+        assert_eq!(
+            client
+                .model_served("gpt-5-2025-08-07")
+                .await
+                .expect("request should succeed"),
+            true
+        );
+        assert_eq!(
+            client
+                .model_served("gpt-4-0314")
+                .await
+                .expect("request should succeed"),
+            false
+        );
+    }
 }
@@ -1,4 +1,5 @@
 use crate::auth::AuthProvider;
+use crate::auth::apply_auth_headers;
 use crate::common::ResponseEvent;
 use crate::common::ResponseStream;
 use crate::common::ResponsesWsRequest;
@@ -6,12 +7,12 @@ use crate::error::ApiError;
 use crate::provider::Provider;
 use crate::sse::responses::ResponsesStreamEvent;
 use crate::sse::responses::process_responses_event;
+use crate::sse::responses::served_model;
 use crate::telemetry::WebsocketTelemetry;
 use codex_client::TransportError;
 use futures::SinkExt;
 use futures::StreamExt;
 use http::HeaderMap;
-use http::HeaderValue;
 use serde_json::Value;
 use std::sync::Arc;
 use std::sync::OnceLock;
@@ -147,20 +148,6 @@ impl<A: AuthProvider> ResponsesWebsocketClient<A> {
     }
 }
 
-// TODO (pakrym): share with /auth
-fn apply_auth_headers(headers: &mut HeaderMap, auth: &impl AuthProvider) {
-    if let Some(token) = auth.bearer_token()
-        && let Ok(header) = HeaderValue::from_str(&format!("Bearer {token}"))
-    {
-        let _ = headers.insert(http::header::AUTHORIZATION, header);
-    }
-    if let Some(account_id) = auth.account_id()
-        && let Ok(header) = HeaderValue::from_str(&account_id)
-    {
-        let _ = headers.insert("ChatGPT-Account-ID", header);
-    }
-}
-
 async fn connect_websocket(
     url: Url,
     headers: HeaderMap,
@@ -287,6 +274,9 @@ async fn run_websocket_response_stream(
                         continue;
                     }
                 };
+                if let Some(model) = served_model(&event) {
+                    let _ = tx_event.send(Ok(ResponseEvent::ServedModel(model))).await;
+                }
                 match process_responses_event(event) {
                     Ok(Some(event)) => {
                         let is_completed = matches!(event, ResponseEvent::Completed { .. });
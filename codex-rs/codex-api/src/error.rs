@@ -27,6 +27,9 @@ pub enum ApiError {
     RateLimit(String),
     #[error("invalid request: {message}")]
     InvalidRequest { message: String },
+    /// The provider refused the request or a safety system blocked its output.
+    #[error("response blocked ({category}): {message}")]
+    Blocked { category: String, message: String },
 }
 
 impl From<RateLimitError> for ApiError {
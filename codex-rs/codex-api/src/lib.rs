@@ -22,6 +22,7 @@ pub use crate::common::ResponseEvent;
 pub use crate::common::ResponseStream;
 pub use crate::common::ResponsesApiRequest;
 pub use crate::common::create_text_param_for_request;
+pub use crate::endpoint::anthropic::AnthropicClient;
 pub use crate::endpoint::chat::AggregateStreamExt;
 pub use crate::endpoint::chat::ChatClient;
 pub use crate::endpoint::compact::CompactClient;
@@ -34,6 +35,8 @@ pub use crate::error::ApiError;
 pub use crate::provider::Provider;
 pub use crate::provider::WireApi;
 pub use crate::provider::is_azure_responses_wire_base_url;
+pub use crate::requests::AnthropicRequest;
+pub use crate::requests::AnthropicRequestBuilder;
 pub use crate::requests::ChatRequest;
 pub use crate::requests::ChatRequestBuilder;
 pub use crate::requests::ResponsesRequest;
@@ -14,6 +14,7 @@ pub enum WireApi {
     Responses,
     Chat,
     Compact,
+    Anthropic,
 }
 
 /// High-level retry configuration for a provider.
@@ -24,9 +25,12 @@ pub enum WireApi {
 pub struct RetryConfig {
     pub max_attempts: u64,
     pub base_delay: Duration,
+    pub max_delay: Duration,
     pub retry_429: bool,
     pub retry_5xx: bool,
     pub retry_transport: bool,
+    /// HTTP status codes retried in addition to those selected by the flags above.
+    pub retry_statuses: Vec<u16>,
 }
 
 impl RetryConfig {
@@ -34,10 +38,12 @@ impl RetryConfig {
         RetryPolicy {
             max_attempts: self.max_attempts,
             base_delay: self.base_delay,
+            max_delay: self.max_delay,
             retry_on: RetryOn {
                 retry_429: self.retry_429,
                 retry_5xx: self.retry_5xx,
                 retry_transport: self.retry_transport,
+                retry_statuses: self.retry_statuses.clone(),
             },
         }
     }
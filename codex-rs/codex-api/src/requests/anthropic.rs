@@ -0,0 +1,455 @@
+use crate::error::ApiError;
+use crate::provider::Provider;
+use crate::requests::headers::build_conversation_headers;
+use crate::requests::headers::insert_header;
+use crate::requests::headers::subagent_header;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::SessionSource;
+use http::HeaderMap;
+use serde_json::Value;
+use serde_json::json;
+
+/// Value sent in the `anthropic-version` header.
+pub const ANTHROPIC_API_VERSION: &str = "2023-06-01";
+
+/// Output token cap sent as `max_tokens`, which the Messages API requires.
+pub const DEFAULT_ANTHROPIC_MAX_TOKENS: u64 = 32_000;
+
+/// Assembled request body plus headers for Anthropic Messages streaming calls.
+pub struct AnthropicRequest {
+    pub body: Value,
+    pub headers: HeaderMap,
+}
+
+pub struct AnthropicRequestBuilder<'a> {
+    model: &'a str,
+    instructions: &'a str,
+    input: &'a [ResponseItem],
+    tools: &'a [Value],
+    max_tokens: u64,
+    conversation_id: Option<String>,
+    session_source: Option<SessionSource>,
+    prompt_caching: bool,
+}
+
+impl<'a> AnthropicRequestBuilder<'a> {
+    /// `tools` are expected in the Messages API shape:
+    /// `{"name", "description", "input_schema"}`.
+    pub fn new(
+        model: &'a str,
+        instructions: &'a str,
+        input: &'a [ResponseItem],
+        tools: &'a [Value],
+    ) -> Self {
+        Self {
+            model,
+            instructions,
+            input,
+            tools,
+            max_tokens: DEFAULT_ANTHROPIC_MAX_TOKENS,
+            conversation_id: None,
+            session_source: None,
+            prompt_caching: false,
+        }
+    }
+
+    pub fn max_tokens(mut self, max_tokens: u64) -> Self {
+        self.max_tokens = max_tokens;
+        self
+    }
+
+    pub fn conversation_id(mut self, id: Option<String>) -> Self {
+        self.conversation_id = id;
+        self
+    }
+
+    pub fn session_source(mut self, source: Option<SessionSource>) -> Self {
+        self.session_source = source;
+        self
+    }
+
+    /// Marks the system prompt and the end of the conversation as prompt
+    /// cache breakpoints, so the next request reads everything before them
+    /// from the cache.
+    pub fn prompt_caching(mut self, enabled: bool) -> Self {
+        self.prompt_caching = enabled;
+        self
+    }
+
+    pub fn build(self, _provider: &Provider) -> Result<AnthropicRequest, ApiError> {
+        // The Messages API has no system or developer roles inside `messages`,
+        // so those turns are folded into the top-level `system` prompt.
+        let mut system = vec![self.instructions.to_string()];
+        let mut messages = Vec::<Value>::new();
+
+        for item in self.input {
+            match item {
+                ResponseItem::Message { role, content, .. } => match role.as_str() {
+                    "system" | "developer" => {
+                        let text = content
+                            .iter()
+                            .filter_map(|c| match c {
+                                ContentItem::InputText { text }
+                                | ContentItem::OutputText { text } => Some(text.as_str()),
+                                ContentItem::InputImage { .. } => None,
+                            })
+                            .collect::<Vec<_>>()
+                            .join("\n");
+                        if !text.trim().is_empty() {
+                            system.push(text);
+                        }
+                    }
+                    "assistant" => {
+                        for c in content {
+                            if let ContentItem::InputText { text }
+                            | ContentItem::OutputText { text } = c
+                            {
+                                push_block(&mut messages, "assistant", text_block(text));
+                            }
+                        }
+                    }
+                    _ => {
+                        for c in content {
+                            let block = match c {
+                                ContentItem::InputText { text }
+                                | ContentItem::OutputText { text } => text_block(text),
+                                ContentItem::InputImage { image_url } => {
+                                    Some(image_block(image_url))
+                                }
+                            };
+                            push_block(&mut messages, "user", block);
+                        }
+                    }
+                },
+                ResponseItem::FunctionCall {
+                    name,
+                    arguments,
+                    call_id,
+                    ..
+                } => {
+                    let input = serde_json::from_str::<Value>(arguments)
+                        .ok()
+                        .filter(Value::is_object)
+                        .unwrap_or_else(|| json!({}));
+                    push_block(
+                        &mut messages,
+                        "assistant",
+                        Some(json!({
+                            "type": "tool_use",
+                            "id": call_id,
+                            "name": name,
+                            "input": input,
+                        })),
+                    );
+                }
+                ResponseItem::CustomToolCall {
+                    call_id,
+                    name,
+                    input,
+                    ..
+                } => {
+                    push_block(
+                        &mut messages,
+                        "assistant",
+                        Some(json!({
+                            "type": "tool_use",
+                            "id": call_id,
+                            "name": name,
+                            "input": {"input": input},
+                        })),
+                    );
+                }
+                ResponseItem::LocalShellCall {
+                    id,
+                    call_id,
+                    action,
+                    ..
+                } => {
+                    let id = call_id.clone().or_else(|| id.clone()).unwrap_or_default();
+                    push_block(
+                        &mut messages,
+                        "assistant",
+                        Some(json!({
+                            "type": "tool_use",
+                            "id": id,
+                            "name": "local_shell",
+                            "input": action,
+                        })),
+                    );
+                }
+                ResponseItem::FunctionCallOutput { call_id, output } => {
+                    let content = match &output.content_items {
+                        Some(items) => Value::Array(
+                            items
+                                .iter()
+                                .filter_map(|it| match it {
+                                    FunctionCallOutputContentItem::InputText { text } => {
+                                        text_block(text)
+                                    }
+                                    FunctionCallOutputContentItem::InputImage { image_url } => {
+                                        Some(image_block(image_url))
+                                    }
+                                })
+                                .collect(),
+                        ),
+                        None => json!(output.content),
+                    };
+                    let mut block = json!({
+                        "type": "tool_result",
+                        "tool_use_id": call_id,
+                        "content": content,
+                    });
+                    if output.success == Some(false)
+                        && let Some(obj) = block.as_object_mut()
+                    {
+                        obj.insert("is_error".to_string(), Value::Bool(true));
+                    }
+                    push_block(&mut messages, "user", Some(block));
+                }
+                ResponseItem::CustomToolCallOutput { call_id, output } => {
+                    push_block(
+                        &mut messages,
+                        "user",
+                        Some(json!({
+                            "type": "tool_result",
+                            "tool_use_id": call_id,
+                            "content": output,
+                        })),
+                    );
+                }
+                // Thinking blocks can only be replayed with the provider's
+                // signature, which the internal reasoning item does not carry.
+                ResponseItem::Reasoning { .. }
+                | ResponseItem::WebSearchCall { .. }
+                | ResponseItem::GhostSnapshot { .. }
+                | ResponseItem::Compaction { .. }
+                | ResponseItem::Other => {}
+            }
+        }
+
+        let system = system.join("\n\n");
+        let system = if self.prompt_caching {
+            mark_last_message_for_caching(&mut messages);
+            json!([{"type": "text", "text": system, "cache_control": ephemeral_cache_control()}])
+        } else {
+            json!(system)
+        };
+
+        let mut payload = json!({
+            "model": self.model,
+            "system": system,
+            "messages": messages,
+            "max_tokens": self.max_tokens,
+            "stream": true,
+        });
+        if !self.tools.is_empty()
+            && let Some(obj) = payload.as_object_mut()
+        {
+            obj.insert("tools".to_string(), json!(self.tools));
+        }
+
+        let mut headers = build_conversation_headers(self.conversation_id);
+        insert_header(&mut headers, "anthropic-version", ANTHROPIC_API_VERSION);
+        if let Some(subagent) = subagent_header(&self.session_source) {
+            insert_header(&mut headers, "x-openai-subagent", &subagent);
+        }
+
+        Ok(AnthropicRequest {
+            body: payload,
+            headers,
+        })
+    }
+}
+
+fn ephemeral_cache_control() -> Value {
+    json!({"type": "ephemeral"})
+}
+
+/// Sets a cache breakpoint on the final content block of the conversation.
+fn mark_last_message_for_caching(messages: &mut [Value]) {
+    if let Some(Value::Object(block)) = messages
+        .last_mut()
+        .and_then(|message| message.get_mut("content"))
+        .and_then(Value::as_array_mut)
+        .and_then(|content| content.last_mut())
+    {
+        block.insert("cache_control".to_string(), ephemeral_cache_control());
+    }
+}
+
+/// The Messages API rejects empty text blocks.
+fn text_block(text: &str) -> Option<Value> {
+    if text.is_empty() {
+        None
+    } else {
+        Some(json!({"type": "text", "text": text}))
+    }
+}
+
+/// Maps a `data:` URL to an inline base64 image and anything else to a URL
+/// image source.
+fn image_block(image_url: &str) -> Value {
+    if let Some(rest) = image_url.strip_prefix("data:")
+        && let Some((media_type, data)) = rest.split_once(";base64,")
+    {
+        return json!({
+            "type": "image",
+            "source": {"type": "base64", "media_type": media_type, "data": data},
+        });
+    }
+    json!({
+        "type": "image",
+        "source": {"type": "url", "url": image_url},
+    })
+}
+
+/// Appends a content block, merging it into the previous message when the
+/// role matches: the Messages API requires user and assistant turns to
+/// alternate, and all `tool_use` blocks of one turn to share a message.
+fn push_block(messages: &mut Vec<Value>, role: &str, block: Option<Value>) {
+    let Some(block) = block else {
+        return;
+    };
+    if let Some(Value::Object(obj)) = messages.last_mut()
+        && obj.get("role").and_then(Value::as_str) == Some(role)
+        && let Some(content) = obj.get_mut("content").and_then(Value::as_array_mut)
+    {
+        content.push(block);
+        return;
+    }
+    messages.push(json!({"role": role, "content": [block]}));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::RetryConfig;
+    use crate::provider::WireApi;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use http::HeaderValue;
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    fn provider() -> Provider {
+        Provider {
+            name: "anthropic".to_string(),
+            base_url: "https://api.anthropic.com/v1".to_string(),
+            query_params: None,
+            wire: WireApi::Anthropic,
+            headers: HeaderMap::new(),
+            retry: RetryConfig {
+                max_attempts: 1,
+                base_delay: Duration::from_millis(10),
+                max_delay: Duration::from_secs(1),
+                retry_429: false,
+                retry_5xx: true,
+                retry_transport: true,
+                retry_statuses: Vec::new(),
+            },
+            stream_idle_timeout: Duration::from_secs(1),
+        }
+    }
+
+    fn message(role: &str, text: &str) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: role.to_string(),
+            content: vec![ContentItem::InputText {
+                text: text.to_string(),
+            }],
+            end_turn: None,
+        }
+    }
+
+    #[test]
+    fn maps_turns_to_messages_and_system_prompt() {
+        let input = vec![
+            message("developer", "sandbox: read-only"),
+            message("user", "read a.txt"),
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "read_file".to_string(),
+                arguments: r#"{"path":"a.txt"}"#.to_string(),
+                call_id: "toolu_1".to_string(),
+            },
+            ResponseItem::FunctionCallOutput {
+                call_id: "toolu_1".to_string(),
+                output: FunctionCallOutputPayload {
+                    content: "A".to_string(),
+                    ..Default::default()
+                },
+            },
+            message("assistant", "It says A."),
+        ];
+        let tools = vec![json!({"name": "read_file", "input_schema": {"type": "object"}})];
+
+        let req = AnthropicRequestBuilder::new("claude-test", "inst", &input, &tools)
+            .build(&provider())
+            .expect("request");
+
+        assert_eq!(
+            req.body,
+            json!({
+                "model": "claude-test",
+                "system": "inst\n\nsandbox: read-only",
+                "messages": [
+                    {"role": "user", "content": [{"type": "text", "text": "read a.txt"}]},
+                    {"role": "assistant", "content": [{
+                        "type": "tool_use",
+                        "id": "toolu_1",
+                        "name": "read_file",
+                        "input": {"path": "a.txt"},
+                    }]},
+                    {"role": "user", "content": [{
+                        "type": "tool_result",
+                        "tool_use_id": "toolu_1",
+                        "content": "A",
+                    }]},
+                    {"role": "assistant", "content": [{"type": "text", "text": "It says A."}]},
+                ],
+                "max_tokens": DEFAULT_ANTHROPIC_MAX_TOKENS,
+                "stream": true,
+                "tools": tools,
+            })
+        );
+        assert_eq!(
+            req.headers.get("anthropic-version"),
+            Some(&HeaderValue::from_static(ANTHROPIC_API_VERSION))
+        );
+    }
+
+    #[test]
+    fn prompt_caching_marks_system_and_last_block() {
+        let input = vec![message("user", "first"), message("user", "second")];
+
+        let req = AnthropicRequestBuilder::new("claude-test", "inst", &input, &[])
+            .prompt_caching(true)
+            .build(&provider())
+            .expect("request");
+
+        assert_eq!(
+            req.body["system"],
+            json!([{"type": "text", "text": "inst", "cache_control": {"type": "ephemeral"}}])
+        );
+        assert_eq!(
+            req.body["messages"],
+            json!([{"role": "user", "content": [
+                {"type": "text", "text": "first"},
+                {"type": "text", "text": "second", "cache_control": {"type": "ephemeral"}},
+            ]}])
+        );
+    }
+
+    #[test]
+    fn data_url_images_become_base64_sources() {
+        assert_eq!(
+            image_block("data:image/png;base64,AAAA"),
+            json!({
+                "type": "image",
+                "source": {"type": "base64", "media_type": "image/png", "data": "AAAA"},
+            })
+        );
+    }
+}
@@ -370,9 +370,11 @@ mod tests {
             retry: RetryConfig {
                 max_attempts: 1,
                 base_delay: Duration::from_millis(10),
+                max_delay: Duration::from_secs(1),
                 retry_429: false,
                 retry_5xx: true,
                 retry_transport: true,
+                retry_statuses: Vec::new(),
             },
             stream_idle_timeout: Duration::from_secs(1),
         }
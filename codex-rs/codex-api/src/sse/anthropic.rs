@@ -0,0 +1,492 @@
+use crate::common::ResponseEvent;
+use crate::common::ResponseStream;
+use crate::error::ApiError;
+use crate::telemetry::SseTelemetry;
+use codex_client::StreamResponse;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::TokenUsage;
+use eventsource_stream::Eventsource;
+use futures::Stream;
+use futures::StreamExt;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::OnceLock;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio::time::timeout;
+use tracing::debug;
+use tracing::trace;
+
+pub(crate) fn spawn_anthropic_stream(
+    stream_response: StreamResponse,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+    _turn_state: Option<Arc<OnceLock<String>>>,
+) -> ResponseStream {
+    let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent, ApiError>>(1600);
+    tokio::spawn(async move {
+        process_anthropic_sse(stream_response.bytes, tx_event, idle_timeout, telemetry).await;
+    });
+    ResponseStream { rx_event }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum StreamEvent {
+    MessageStart {
+        message: MessageStart,
+    },
+    ContentBlockStart {
+        index: usize,
+        content_block: ContentBlock,
+    },
+    ContentBlockDelta {
+        index: usize,
+        delta: BlockDelta,
+    },
+    ContentBlockStop {
+        index: usize,
+    },
+    MessageDelta {
+        delta: MessageDelta,
+        #[serde(default)]
+        usage: Option<Usage>,
+    },
+    MessageStop,
+    Ping,
+    Error {
+        error: ErrorBody,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessageStart {
+    #[serde(default)]
+    id: String,
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    usage: Option<Usage>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ContentBlock {
+    Text,
+    ToolUse {
+        id: String,
+        name: String,
+    },
+    Thinking,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum BlockDelta {
+    TextDelta {
+        text: String,
+    },
+    InputJsonDelta {
+        partial_json: String,
+    },
+    ThinkingDelta {
+        thinking: String,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessageDelta {
+    #[serde(default)]
+    stop_reason: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Usage {
+    #[serde(default)]
+    input_tokens: Option<i64>,
+    #[serde(default)]
+    cache_creation_input_tokens: Option<i64>,
+    #[serde(default)]
+    cache_read_input_tokens: Option<i64>,
+    #[serde(default)]
+    output_tokens: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorBody {
+    #[serde(rename = "type", default)]
+    kind: String,
+    #[serde(default)]
+    message: String,
+}
+
+/// A content block that is still streaming, keyed by its index in the message.
+enum BlockState {
+    Text(String),
+    ToolUse {
+        id: String,
+        name: String,
+        arguments: String,
+    },
+    Thinking(String),
+}
+
+/// Processes Server-Sent Events from the Anthropic Messages streaming API.
+///
+/// Each content block is surfaced as `OutputItemAdded`, followed by deltas,
+/// and an `OutputItemDone` when the block stops. `message_stop` completes the
+/// response with the token usage accumulated from `message_start` and
+/// `message_delta`.
+pub async fn process_anthropic_sse<S>(
+    stream: S,
+    tx_event: mpsc::Sender<Result<ResponseEvent, ApiError>>,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+) where
+    S: Stream<Item = Result<bytes::Bytes, codex_client::TransportError>> + Unpin,
+{
+    let mut stream = stream.eventsource();
+    let mut blocks: HashMap<usize, BlockState> = HashMap::new();
+    let mut response_id = String::new();
+    let mut usage = Usage::default();
+
+    loop {
+        let start = Instant::now();
+        let response = timeout(idle_timeout, stream.next()).await;
+        if let Some(t) = telemetry.as_ref() {
+            t.on_sse_poll(&response, start.elapsed());
+        }
+        let sse = match response {
+            Ok(Some(Ok(sse))) => sse,
+            Ok(Some(Err(e))) => {
+                let _ = tx_event.send(Err(ApiError::Stream(e.to_string()))).await;
+                return;
+            }
+            Ok(None) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream(
+                        "stream closed before message_stop".into(),
+                    )))
+                    .await;
+                return;
+            }
+            Err(_) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream("idle timeout waiting for SSE".into())))
+                    .await;
+                return;
+            }
+        };
+
+        trace!("SSE event: {}", sse.data);
+
+        let event: StreamEvent = match serde_json::from_str(&sse.data) {
+            Ok(event) => event,
+            Err(err) => {
+                debug!(
+                    "Failed to parse Anthropic SSE event: {err}, data: {}",
+                    sse.data
+                );
+                continue;
+            }
+        };
+
+        match event {
+            StreamEvent::MessageStart { message } => {
+                response_id = message.id;
+                if let Some(start_usage) = message.usage {
+                    usage = start_usage;
+                }
+                let _ = tx_event.send(Ok(ResponseEvent::Created)).await;
+                if let Some(model) = message.model {
+                    let _ = tx_event.send(Ok(ResponseEvent::ServedModel(model))).await;
+                }
+            }
+            StreamEvent::ContentBlockStart {
+                index,
+                content_block,
+            } => {
+                let (state, item) = match content_block {
+                    ContentBlock::Text => (
+                        BlockState::Text(String::new()),
+                        assistant_message(String::new()),
+                    ),
+                    ContentBlock::ToolUse { id, name } => {
+                        let item = ResponseItem::FunctionCall {
+                            id: None,
+                            name: name.clone(),
+                            arguments: String::new(),
+                            call_id: id.clone(),
+                        };
+                        (
+                            BlockState::ToolUse {
+                                id,
+                                name,
+                                arguments: String::new(),
+                            },
+                            item,
+                        )
+                    }
+                    ContentBlock::Thinking => {
+                        (BlockState::Thinking(String::new()), reasoning(Vec::new()))
+                    }
+                    ContentBlock::Unknown => continue,
+                };
+                blocks.insert(index, state);
+                let _ = tx_event
+                    .send(Ok(ResponseEvent::OutputItemAdded(item)))
+                    .await;
+            }
+            StreamEvent::ContentBlockDelta { index, delta } => {
+                match (blocks.get_mut(&index), delta) {
+                    (Some(BlockState::Text(text)), BlockDelta::TextDelta { text: delta }) => {
+                        text.push_str(&delta);
+                        let _ = tx_event
+                            .send(Ok(ResponseEvent::OutputTextDelta(delta)))
+                            .await;
+                    }
+                    (
+                        Some(BlockState::ToolUse { arguments, .. }),
+                        BlockDelta::InputJsonDelta { partial_json },
+                    ) => arguments.push_str(&partial_json),
+                    (Some(BlockState::Thinking(text)), BlockDelta::ThinkingDelta { thinking }) => {
+                        text.push_str(&thinking);
+                        let _ = tx_event
+                            .send(Ok(ResponseEvent::ReasoningContentDelta {
+                                delta: thinking,
+                                content_index: 0,
+                            }))
+                            .await;
+                    }
+                    _ => {}
+                }
+            }
+            StreamEvent::ContentBlockStop { index } => {
+                let item = match blocks.remove(&index) {
+                    Some(BlockState::Text(text)) => assistant_message(text),
+                    Some(BlockState::ToolUse {
+                        id,
+                        name,
+                        arguments,
+                    }) => ResponseItem::FunctionCall {
+                        id: None,
+                        name,
+                        // Tools without parameters stream no input deltas.
+                        arguments: if arguments.is_empty() {
+                            "{}".to_string()
+                        } else {
+                            arguments
+                        },
+                        call_id: id,
+                    },
+                    Some(BlockState::Thinking(text)) => {
+                        reasoning(vec![ReasoningItemContent::ReasoningText { text }])
+                    }
+                    None => continue,
+                };
+                let _ = tx_event.send(Ok(ResponseEvent::OutputItemDone(item))).await;
+            }
+            StreamEvent::MessageDelta {
+                delta,
+                usage: delta_usage,
+            } => {
+                if let Some(delta_usage) = delta_usage {
+                    usage.output_tokens = delta_usage.output_tokens.or(usage.output_tokens);
+                }
+                if delta.stop_reason.as_deref() == Some("refusal") {
+                    let _ = tx_event
+                        .send(Err(ApiError::Blocked {
+                            category: "refusal".to_string(),
+                            message: "the model declined to respond".to_string(),
+                        }))
+                        .await;
+                    return;
+                }
+            }
+            StreamEvent::MessageStop => {
+                let _ = tx_event
+                    .send(Ok(ResponseEvent::Completed {
+                        response_id,
+                        token_usage: Some(usage.into()),
+                    }))
+                    .await;
+                return;
+            }
+            StreamEvent::Error { error } => {
+                let err = match error.kind.as_str() {
+                    "overloaded_error" | "api_error" => ApiError::Retryable {
+                        message: error.message,
+                        delay: None,
+                    },
+                    "rate_limit_error" => ApiError::RateLimit(error.message),
+                    _ if error.message.contains("prompt is too long") => {
+                        ApiError::ContextWindowExceeded
+                    }
+                    _ => ApiError::Stream(error.message),
+                };
+                let _ = tx_event.send(Err(err)).await;
+                return;
+            }
+            StreamEvent::Ping | StreamEvent::Unknown => {}
+        }
+    }
+}
+
+/// The Messages API reports cached prompt tokens separately from
+/// `input_tokens`; fold them back in so usage matches the Responses API,
+/// where `input_tokens` includes the cached portion.
+impl From<Usage> for TokenUsage {
+    fn from(usage: Usage) -> Self {
+        let cached_input_tokens = usage.cache_read_input_tokens.unwrap_or(0);
+        let input_tokens = usage.input_tokens.unwrap_or(0)
+            + usage.cache_creation_input_tokens.unwrap_or(0)
+            + cached_input_tokens;
+        let output_tokens = usage.output_tokens.unwrap_or(0);
+        TokenUsage {
+            input_tokens,
+            cached_input_tokens,
+            output_tokens,
+            reasoning_output_tokens: 0,
+            total_tokens: input_tokens + output_tokens,
+        }
+    }
+}
+
+fn assistant_message(text: String) -> ResponseItem {
+    ResponseItem::Message {
+        id: None,
+        role: "assistant".to_string(),
+        content: if text.is_empty() {
+            Vec::new()
+        } else {
+            vec![ContentItem::OutputText { text }]
+        },
+        end_turn: None,
+    }
+}
+
+fn reasoning(content: Vec<ReasoningItemContent>) -> ResponseItem {
+    ResponseItem::Reasoning {
+        id: String::new(),
+        summary: Vec::new(),
+        content: Some(content),
+        encrypted_content: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use futures::TryStreamExt;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tokio_util::io::ReaderStream;
+
+    fn build_body(events: &[serde_json::Value]) -> String {
+        let mut body = String::new();
+        for e in events {
+            let kind = e["type"].as_str().unwrap_or_default();
+            body.push_str(&format!("event: {kind}\ndata: {e}\n\n"));
+        }
+        body
+    }
+
+    async fn collect_events(body: &str) -> Vec<Result<ResponseEvent, ApiError>> {
+        let reader = ReaderStream::new(std::io::Cursor::new(body.to_string()))
+            .map_err(|err| codex_client::TransportError::Network(err.to_string()));
+        let (tx, mut rx) = mpsc::channel::<Result<ResponseEvent, ApiError>>(16);
+        tokio::spawn(process_anthropic_sse(
+            reader,
+            tx,
+            Duration::from_millis(1000),
+            None,
+        ));
+
+        let mut out = Vec::new();
+        while let Some(event) = rx.recv().await {
+            out.push(event);
+        }
+        out
+    }
+
+    #[tokio::test]
+    async fn streams_text_and_tool_use_blocks() {
+        let body = build_body(&[
+            json!({"type": "message_start", "message": {
+                "id": "msg_1",
+                "model": "claude-test-20250101",
+                "usage": {"input_tokens": 10, "cache_read_input_tokens": 5, "output_tokens": 1},
+            }}),
+            json!({"type": "content_block_start", "index": 0, "content_block": {"type": "text", "text": ""}}),
+            json!({"type": "content_block_delta", "index": 0, "delta": {"type": "text_delta", "text": "Hi"}}),
+            json!({"type": "content_block_stop", "index": 0}),
+            json!({"type": "content_block_start", "index": 1, "content_block": {
+                "type": "tool_use", "id": "toolu_1", "name": "shell", "input": {},
+            }}),
+            json!({"type": "content_block_delta", "index": 1, "delta": {"type": "input_json_delta", "partial_json": "{\"cmd\":"}}),
+            json!({"type": "content_block_delta", "index": 1, "delta": {"type": "input_json_delta", "partial_json": "\"ls\"}"}}),
+            json!({"type": "content_block_stop", "index": 1}),
+            json!({"type": "message_delta", "delta": {"stop_reason": "tool_use"}, "usage": {"output_tokens": 7}}),
+            json!({"type": "message_stop"}),
+        ]);
+
+        let events: Vec<ResponseEvent> = collect_events(&body)
+            .await
+            .into_iter()
+            .map(|event| event.expect("event"))
+            .collect();
+
+        assert_matches!(events[0], ResponseEvent::Created);
+        assert_matches!(&events[1], ResponseEvent::ServedModel(model) if model == "claude-test-20250101");
+        assert_matches!(&events[3], ResponseEvent::OutputTextDelta(delta) if delta == "Hi");
+        assert_matches!(
+            &events[4],
+            ResponseEvent::OutputItemDone(ResponseItem::Message { content, .. })
+                if content == &vec![ContentItem::OutputText { text: "Hi".to_string() }]
+        );
+        assert_matches!(
+            &events[6],
+            ResponseEvent::OutputItemDone(ResponseItem::FunctionCall { name, arguments, call_id, .. })
+                if name == "shell" && arguments == r#"{"cmd":"ls"}"# && call_id == "toolu_1"
+        );
+        let ResponseEvent::Completed {
+            response_id,
+            token_usage,
+        } = &events[7]
+        else {
+            panic!("expected Completed, got {:?}", events[7]);
+        };
+        assert_eq!(response_id, "msg_1");
+        assert_eq!(
+            token_usage,
+            &Some(TokenUsage {
+                input_tokens: 15,
+                cached_input_tokens: 5,
+                output_tokens: 7,
+                reasoning_output_tokens: 0,
+                total_tokens: 22,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn overloaded_error_is_retryable() {
+        let body = build_body(&[json!({"type": "error", "error": {
+            "type": "overloaded_error", "message": "Overloaded",
+        }})]);
+
+        let events = collect_events(&body).await;
+        assert_matches!(&events[..], [Err(ApiError::Retryable { message, .. })] if message == "Overloaded");
+    }
+}
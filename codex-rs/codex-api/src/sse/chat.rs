@@ -74,6 +74,7 @@ pub async fn process_chat_sse<S>(
     let mut assistant_item: Option<ResponseItem> = None;
     let mut reasoning_item: Option<ResponseItem> = None;
     let mut completed_sent = false;
+    let mut served_model_sent = false;
 
     async fn flush_and_complete(
         tx_event: &mpsc::Sender<Result<ResponseEvent, ApiError>>,
@@ -152,6 +153,16 @@ pub async fn process_chat_sse<S>(
             }
         };
 
+        if !served_model_sent
+            && let Some(model) = value.get("model").and_then(|m| m.as_str())
+            && !model.is_empty()
+        {
+            served_model_sent = true;
+            let _ = tx_event
+                .send(Ok(ResponseEvent::ServedModel(model.to_string())))
+                .await;
+        }
+
         let Some(choices) = value.get("choices").and_then(|c| c.as_array()) else {
             continue;
         };
@@ -387,6 +398,7 @@ mod tests {
     use assert_matches::assert_matches;
     use codex_protocol::models::ResponseItem;
     use futures::TryStreamExt;
+    use pretty_assertions::assert_eq;
     use serde_json::json;
     use tokio::sync::mpsc;
     use tokio_util::io::ReaderStream;
@@ -427,6 +439,29 @@ mod tests {
         out
     }
 
+    #[tokio::test]
+    async fn reports_the_served_model_once() {
+        let first = json!({
+            "model": "gpt-4o-2024-08-06",
+            "choices": [{ "delta": { "content": "Hel" } }]
+        });
+        let second = json!({
+            "model": "gpt-4o-2024-08-06",
+            "choices": [{ "delta": { "content": "lo" } }]
+        });
+        let body = build_body(&[first, second]);
+
+        let events = collect_events(&body).await;
+        let served: Vec<&str> = events
+            .iter()
+            .filter_map(|event| match event {
+                ResponseEvent::ServedModel(model) => Some(model.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(served, vec!["gpt-4o-2024-08-06"]);
+    }
+
     #[tokio::test]
     async fn concatenates_tool_call_arguments_across_deltas() {
         let delta_name = json!({
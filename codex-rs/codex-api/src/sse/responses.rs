@@ -26,6 +26,10 @@ use tracing::debug;
 use tracing::trace;
 
 const X_REASONING_INCLUDED_HEADER: &str = "x-reasoning-included";
+/// Category reported when the model declines to answer.
+const REFUSAL_CATEGORY: &str = "refusal";
+/// Category reported when a content filter stops or rejects the response.
+const CONTENT_FILTER_CATEGORY: &str = "content_filter";
 
 /// Streams SSE events from an on-disk fixture for tests.
 pub fn stream_from_fixture(
@@ -178,12 +182,32 @@ impl ResponsesEventError {
     }
 }
 
+/// Returns the model snapshot reported by a `response.created` event.
+pub fn served_model(event: &ResponsesStreamEvent) -> Option<String> {
+    if event.kind != "response.created" {
+        return None;
+    }
+    event
+        .response
+        .as_ref()?
+        .get("model")?
+        .as_str()
+        .filter(|model| !model.is_empty())
+        .map(ToString::to_string)
+}
+
 pub fn process_responses_event(
     event: ResponsesStreamEvent,
 ) -> std::result::Result<Option<ResponseEvent>, ResponsesEventError> {
     match event.kind.as_str() {
         "response.output_item.done" => {
             if let Some(item_val) = event.item {
+                if let Some(refusal) = refusal_text(&item_val) {
+                    return Err(ResponsesEventError::Api(ApiError::Blocked {
+                        category: REFUSAL_CATEGORY.to_string(),
+                        message: refusal,
+                    }));
+                }
                 if let Ok(item) = serde_json::from_value::<ResponseItem>(item_val) {
                     return Ok(Some(ResponseEvent::OutputItemDone(item)));
                 }
@@ -228,6 +252,11 @@ pub fn process_responses_event(
                         response_error = ApiError::QuotaExceeded;
                     } else if is_usage_not_included(&error) {
                         response_error = ApiError::UsageNotIncluded;
+                    } else if let Some(category) = blocked_error_category(&error) {
+                        let message = error
+                            .message
+                            .unwrap_or_else(|| "The response was blocked.".to_string());
+                        response_error = ApiError::Blocked { category, message };
                     } else if is_invalid_prompt_error(&error) {
                         let message = error
                             .message
@@ -246,6 +275,22 @@ pub fn process_responses_event(
                 "response.failed event received".into(),
             )));
         }
+        "response.incomplete" => {
+            let reason = event
+                .response
+                .as_ref()
+                .and_then(|resp| resp.get("incomplete_details"))
+                .and_then(|details| details.get("reason"))
+                .and_then(Value::as_str);
+            if reason == Some(CONTENT_FILTER_CATEGORY) {
+                return Err(ResponsesEventError::Api(ApiError::Blocked {
+                    category: CONTENT_FILTER_CATEGORY.to_string(),
+                    message: "The response was stopped by the provider's content filter."
+                        .to_string(),
+                }));
+            }
+            trace!("unhandled incomplete response reason: {reason:?}");
+        }
         "response.completed" => {
             if let Some(resp_val) = event.response {
                 match serde_json::from_value::<ResponseCompleted>(resp_val) {
@@ -356,6 +401,15 @@ pub async fn process_sse(
             }
         };
 
+        if let Some(model) = served_model(&event)
+            && tx_event
+                .send(Ok(ResponseEvent::ServedModel(model)))
+                .await
+                .is_err()
+        {
+            return;
+        }
+
         match process_responses_event(event) {
             Ok(Some(event)) => {
                 let is_completed = matches!(event, ResponseEvent::Completed { .. });
@@ -368,7 +422,14 @@ pub async fn process_sse(
             }
             Ok(None) => {}
             Err(error) => {
-                response_error = Some(error.into_api_error());
+                let error = error.into_api_error();
+                // A blocked response will not recover, and the provider may still
+                // send `response.completed` afterwards, so report it right away.
+                if matches!(error, ApiError::Blocked { .. }) {
+                    let _ = tx_event.send(Err(error)).await;
+                    return;
+                }
+                response_error = Some(error);
             }
         };
     }
@@ -416,6 +477,35 @@ fn is_invalid_prompt_error(error: &Error) -> bool {
     error.code.as_deref() == Some("invalid_prompt")
 }
 
+fn blocked_error_category(error: &Error) -> Option<String> {
+    match error.code.as_deref() {
+        Some(code @ (CONTENT_FILTER_CATEGORY | "content_policy_violation")) => {
+            Some(code.to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Returns the refusal text of an assistant message whose content is a refusal
+/// rather than output text.
+fn refusal_text(item: &Value) -> Option<String> {
+    if item.get("type").and_then(Value::as_str) != Some("message") {
+        return None;
+    }
+    let refusals: Vec<&str> = item
+        .get("content")?
+        .as_array()?
+        .iter()
+        .filter(|part| part.get("type").and_then(Value::as_str) == Some("refusal"))
+        .filter_map(|part| part.get("refusal").and_then(Value::as_str))
+        .collect();
+    if refusals.is_empty() {
+        None
+    } else {
+        Some(refusals.join("\n"))
+    }
+}
+
 fn rate_limit_regex() -> &'static regex_lite::Regex {
     static RE: std::sync::OnceLock<regex_lite::Regex> = std::sync::OnceLock::new();
     #[expect(clippy::unwrap_used)]
@@ -752,6 +842,78 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn refusal_message_is_reported_as_blocked() {
+        let item = json!({
+            "type": "response.output_item.done",
+            "item": {
+                "type": "message",
+                "role": "assistant",
+                "content": [{"type": "refusal", "refusal": "I can't help with that."}]
+            }
+        })
+        .to_string();
+        let completed = json!({
+            "type": "response.completed",
+            "response": {"id": "resp_refusal"}
+        })
+        .to_string();
+        let sse1 = format!("event: response.output_item.done\ndata: {item}\n\n");
+        let sse2 = format!("event: response.completed\ndata: {completed}\n\n");
+
+        let events = collect_events(&[sse1.as_bytes(), sse2.as_bytes()]).await;
+
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            Err(ApiError::Blocked { category, message }) => {
+                assert_eq!(category, "refusal");
+                assert_eq!(message, "I can't help with that.");
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn content_filter_incomplete_response_is_blocked() {
+        let incomplete = json!({
+            "type": "response.incomplete",
+            "response": {
+                "id": "resp_filtered",
+                "status": "incomplete",
+                "incomplete_details": {"reason": "content_filter"}
+            }
+        })
+        .to_string();
+        let sse1 = format!("event: response.incomplete\ndata: {incomplete}\n\n");
+
+        let events = collect_events(&[sse1.as_bytes()]).await;
+
+        assert_eq!(events.len(), 1);
+        assert_matches!(
+            &events[0],
+            Err(ApiError::Blocked { category, .. }) if category == "content_filter"
+        );
+    }
+
+    #[tokio::test]
+    async fn response_created_reports_served_model() {
+        let created = json!({
+            "type": "response.created",
+            "response": {"id": "resp1", "model": "gpt-5-2025-08-07"}
+        })
+        .to_string();
+        let sse1 = format!("event: response.created\ndata: {created}\n\n");
+
+        let events = collect_events(&[sse1.as_bytes()]).await;
+
+        assert_eq!(events.len(), 3);
+        assert_matches!(
+            &events[0],
+            Ok(ResponseEvent::ServedModel(model)) if model == "gpt-5-2025-08-07"
+        );
+        assert_matches!(&events[1], Ok(ResponseEvent::Created));
+    }
+
     #[tokio::test]
     async fn table_driven_event_kinds() {
         struct TestCase {
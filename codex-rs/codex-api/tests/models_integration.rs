@@ -38,9 +38,11 @@ fn provider(base_url: &str) -> Provider {
         retry: RetryConfig {
             max_attempts: 1,
             base_delay: std::time::Duration::from_millis(1),
+            max_delay: std::time::Duration::from_secs(1),
             retry_429: false,
             retry_5xx: true,
             retry_transport: true,
+            retry_statuses: Vec::new(),
         },
         stream_idle_timeout: std::time::Duration::from_secs(1),
     }
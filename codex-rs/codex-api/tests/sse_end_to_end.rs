@@ -71,9 +71,11 @@ fn provider(name: &str, wire: WireApi) -> Provider {
         retry: codex_api::provider::RetryConfig {
             max_attempts: 1,
             base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_secs(1),
             retry_429: false,
             retry_5xx: false,
             retry_transport: true,
+            retry_statuses: Vec::new(),
         },
         stream_idle_timeout: Duration::from_millis(50),
     }
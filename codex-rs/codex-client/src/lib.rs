@@ -1,5 +1,6 @@
 mod default_client;
 mod error;
+mod recording;
 mod request;
 mod retry;
 mod sse;
@@ -10,6 +11,11 @@ pub use crate::default_client::CodexHttpClient;
 pub use crate::default_client::CodexRequestBuilder;
 pub use crate::error::StreamError;
 pub use crate::error::TransportError;
+pub use crate::recording::ApiRecord;
+pub use crate::recording::ApiRecordEvent;
+pub use crate::recording::ApiRecorder;
+pub use crate::recording::RecordingTransport;
+pub use crate::recording::ReplayTransport;
 pub use crate::request::Request;
 pub use crate::request::RequestCompression;
 pub use crate::request::Response;
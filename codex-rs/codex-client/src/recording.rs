@@ -0,0 +1,594 @@
+//! Debug recording of raw model API traffic, and replay of a recording in place
+//! of the network so a session can be re-driven deterministically.
+//!
+//! A recording is a JSON Lines file of [`ApiRecord`]s. Every record carries the
+//! correlation id of the request it belongs to, so the request, its response
+//! head, and each streamed chunk can be matched up even when requests overlap.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::StreamExt;
+use http::HeaderMap;
+use http::HeaderName;
+use http::HeaderValue;
+use http::StatusCode;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::error::TransportError;
+use crate::request::Request;
+use crate::request::Response;
+use crate::transport::HttpTransport;
+use crate::transport::StreamResponse;
+
+/// Header values replaced by this marker are never written to a recording.
+const REDACTED: &str = "[redacted]";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiRecord {
+    /// Milliseconds since the Unix epoch.
+    pub timestamp_ms: u64,
+    /// Correlation id shared by a request and everything recorded for it.
+    pub id: u64,
+    #[serde(flatten)]
+    pub event: ApiRecordEvent,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ApiRecordEvent {
+    Request {
+        method: String,
+        url: String,
+        headers: BTreeMap<String, String>,
+        body: Option<Value>,
+    },
+    /// Response head. `body` is set for unary calls and error responses.
+    Response {
+        status: u16,
+        headers: BTreeMap<String, String>,
+        body: Option<String>,
+    },
+    /// A streamed chunk that is valid UTF-8 (the common case for SSE).
+    Chunk {
+        data: String,
+    },
+    /// A streamed chunk that is not valid UTF-8 on its own.
+    BinaryChunk {
+        bytes: Vec<u8>,
+    },
+    StreamError {
+        error: String,
+    },
+    StreamEnd,
+    /// The request failed before a response arrived (timeout, network error).
+    Failed {
+        error: String,
+    },
+}
+
+/// Appends [`ApiRecord`]s to a recording file. Cheap to clone; clones share
+/// the file and the correlation id counter.
+#[derive(Debug, Clone)]
+pub struct ApiRecorder {
+    inner: Arc<RecorderInner>,
+}
+
+#[derive(Debug)]
+struct RecorderInner {
+    path: PathBuf,
+    file: Mutex<File>,
+    next_id: AtomicU64,
+}
+
+impl ApiRecorder {
+    /// Creates (or truncates) the recording at `path`, creating parent
+    /// directories as needed.
+    pub fn create(path: &Path) -> std::io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = File::create(path)?;
+        Ok(Self {
+            inner: Arc::new(RecorderInner {
+                path: path.to_path_buf(),
+                file: Mutex::new(file),
+                next_id: AtomicU64::new(1),
+            }),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.inner.path
+    }
+
+    fn next_id(&self) -> u64 {
+        self.inner.next_id.fetch_add(1, Ordering::Relaxed)
+    }
+
+    fn record(&self, id: u64, event: ApiRecordEvent) {
+        let record = ApiRecord {
+            timestamp_ms: now_ms(),
+            id,
+            event,
+        };
+        let line = match serde_json::to_string(&record) {
+            Ok(line) => line,
+            Err(err) => {
+                tracing::warn!(error = %err, "failed to serialize API record");
+                return;
+            }
+        };
+        let Ok(mut file) = self.inner.file.lock() else {
+            return;
+        };
+        if let Err(err) = writeln!(file, "{line}").and_then(|()| file.flush()) {
+            tracing::warn!(error = %err, path = %self.inner.path.display(), "failed to write API record");
+        }
+    }
+
+    fn record_error(&self, id: u64, err: &TransportError) {
+        let event = match err {
+            TransportError::Http {
+                status,
+                headers,
+                body,
+                ..
+            } => ApiRecordEvent::Response {
+                status: status.as_u16(),
+                headers: headers.as_ref().map(header_record).unwrap_or_default(),
+                body: body.clone(),
+            },
+            TransportError::RetryLimit
+            | TransportError::Timeout
+            | TransportError::Network(_)
+            | TransportError::Build(_) => ApiRecordEvent::Failed {
+                error: err.to_string(),
+            },
+        };
+        self.record(id, event);
+    }
+}
+
+/// Wraps a transport and records every request and response it carries.
+#[derive(Debug, Clone)]
+pub struct RecordingTransport<T> {
+    inner: T,
+    recorder: ApiRecorder,
+}
+
+impl<T: HttpTransport> RecordingTransport<T> {
+    pub fn new(inner: T, recorder: ApiRecorder) -> Self {
+        Self { inner, recorder }
+    }
+
+    fn record_request(&self, req: &Request) -> u64 {
+        let id = self.recorder.next_id();
+        self.recorder.record(
+            id,
+            ApiRecordEvent::Request {
+                method: req.method.to_string(),
+                url: req.url.clone(),
+                headers: header_record(&req.headers),
+                body: req.body.clone(),
+            },
+        );
+        id
+    }
+}
+
+#[async_trait]
+impl<T: HttpTransport> HttpTransport for RecordingTransport<T> {
+    async fn execute(&self, req: Request) -> Result<Response, TransportError> {
+        let id = self.record_request(&req);
+        match self.inner.execute(req).await {
+            Ok(response) => {
+                self.recorder.record(
+                    id,
+                    ApiRecordEvent::Response {
+                        status: response.status.as_u16(),
+                        headers: header_record(&response.headers),
+                        body: Some(String::from_utf8_lossy(&response.body).into_owned()),
+                    },
+                );
+                Ok(response)
+            }
+            Err(err) => {
+                self.recorder.record_error(id, &err);
+                Err(err)
+            }
+        }
+    }
+
+    async fn stream(&self, req: Request) -> Result<StreamResponse, TransportError> {
+        let id = self.record_request(&req);
+        let response = match self.inner.stream(req).await {
+            Ok(response) => response,
+            Err(err) => {
+                self.recorder.record_error(id, &err);
+                return Err(err);
+            }
+        };
+        self.recorder.record(
+            id,
+            ApiRecordEvent::Response {
+                status: response.status.as_u16(),
+                headers: header_record(&response.headers),
+                body: None,
+            },
+        );
+
+        let chunk_recorder = self.recorder.clone();
+        let end_recorder = self.recorder.clone();
+        let bytes = response
+            .bytes
+            .map(move |chunk| {
+                let event = match &chunk {
+                    Ok(bytes) => match std::str::from_utf8(bytes) {
+                        Ok(data) => ApiRecordEvent::Chunk {
+                            data: data.to_string(),
+                        },
+                        Err(_) => ApiRecordEvent::BinaryChunk {
+                            bytes: bytes.to_vec(),
+                        },
+                    },
+                    Err(err) => ApiRecordEvent::StreamError {
+                        error: err.to_string(),
+                    },
+                };
+                chunk_recorder.record(id, event);
+                chunk
+            })
+            .chain(
+                futures::stream::once(async move {
+                    end_recorder.record(id, ApiRecordEvent::StreamEnd);
+                })
+                .filter_map(|()| async { None }),
+            );
+        Ok(StreamResponse {
+            status: response.status,
+            headers: response.headers,
+            bytes: Box::pin(bytes),
+        })
+    }
+}
+
+/// Serves responses from a recording instead of the network. Requests are
+/// answered in the order they were recorded; a request whose method or path
+/// differs from the recorded one fails, since the replay has diverged.
+#[derive(Debug)]
+pub struct ReplayTransport {
+    path: PathBuf,
+    exchanges: Mutex<VecDeque<RecordedExchange>>,
+}
+
+#[derive(Debug, Default)]
+struct RecordedExchange {
+    method: String,
+    url: String,
+    status: Option<u16>,
+    headers: BTreeMap<String, String>,
+    body: Option<String>,
+    failure: Option<String>,
+    chunks: Vec<ApiRecordEvent>,
+}
+
+impl ReplayTransport {
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        let reader = BufReader::new(File::open(path)?);
+        let mut exchanges: Vec<RecordedExchange> = Vec::new();
+        let mut index_by_id: HashMap<u64, usize> = HashMap::new();
+        for (line_number, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record: ApiRecord = serde_json::from_str(&line).map_err(|err| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("{}:{}: {err}", path.display(), line_number + 1),
+                )
+            })?;
+            if let ApiRecordEvent::Request { method, url, .. } = &record.event {
+                index_by_id.insert(record.id, exchanges.len());
+                exchanges.push(RecordedExchange {
+                    method: method.clone(),
+                    url: url.clone(),
+                    ..Default::default()
+                });
+                continue;
+            }
+            let Some(exchange) = index_by_id
+                .get(&record.id)
+                .and_then(|index| exchanges.get_mut(*index))
+            else {
+                continue;
+            };
+            match record.event {
+                ApiRecordEvent::Response {
+                    status,
+                    headers,
+                    body,
+                } => {
+                    exchange.status = Some(status);
+                    exchange.headers = headers;
+                    exchange.body = body;
+                }
+                ApiRecordEvent::Failed { error } => exchange.failure = Some(error),
+                event @ (ApiRecordEvent::Chunk { .. }
+                | ApiRecordEvent::BinaryChunk { .. }
+                | ApiRecordEvent::StreamError { .. }) => exchange.chunks.push(event),
+                ApiRecordEvent::Request { .. } | ApiRecordEvent::StreamEnd => {}
+            }
+        }
+        Ok(Self {
+            path: path.to_path_buf(),
+            exchanges: Mutex::new(exchanges.into()),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Number of recorded exchanges not yet replayed.
+    pub fn remaining(&self) -> usize {
+        match self.exchanges.lock() {
+            Ok(queue) => queue.len(),
+            Err(_) => 0,
+        }
+    }
+
+    fn next_exchange(&self, req: &Request) -> Result<RecordedExchange, TransportError> {
+        let mut queue = self
+            .exchanges
+            .lock()
+            .map_err(|_| TransportError::Build("replay state is poisoned".to_string()))?;
+        let Some(exchange) = queue.pop_front() else {
+            return Err(TransportError::Build(format!(
+                "replay of {} has no response for {} {}",
+                self.path.display(),
+                req.method,
+                req.url
+            )));
+        };
+        let method = req.method.to_string();
+        if exchange.method != method || url_path(&exchange.url) != url_path(&req.url) {
+            return Err(TransportError::Build(format!(
+                "replay diverged: recorded {} {} but got {method} {}",
+                exchange.method, exchange.url, req.url
+            )));
+        }
+        Ok(exchange)
+    }
+
+    /// The recorded response head, or the recorded failure as an error.
+    fn response_head(
+        exchange: &RecordedExchange,
+    ) -> Result<(StatusCode, HeaderMap), TransportError> {
+        if let Some(error) = &exchange.failure {
+            return Err(TransportError::Network(error.clone()));
+        }
+        let status = exchange
+            .status
+            .and_then(|status| StatusCode::from_u16(status).ok())
+            .ok_or_else(|| {
+                TransportError::Network(format!(
+                    "recording has no response for {} {}",
+                    exchange.method, exchange.url
+                ))
+            })?;
+        let headers = header_map(&exchange.headers);
+        if !status.is_success() {
+            return Err(TransportError::Http {
+                status,
+                url: Some(exchange.url.clone()),
+                headers: Some(headers),
+                body: exchange.body.clone(),
+            });
+        }
+        Ok((status, headers))
+    }
+}
+
+#[async_trait]
+impl HttpTransport for ReplayTransport {
+    async fn execute(&self, req: Request) -> Result<Response, TransportError> {
+        let exchange = self.next_exchange(&req)?;
+        let (status, headers) = Self::response_head(&exchange)?;
+        Ok(Response {
+            status,
+            headers,
+            body: Bytes::from(exchange.body.unwrap_or_default()),
+        })
+    }
+
+    async fn stream(&self, req: Request) -> Result<StreamResponse, TransportError> {
+        let exchange = self.next_exchange(&req)?;
+        let (status, headers) = Self::response_head(&exchange)?;
+        let chunks = exchange.chunks.into_iter().filter_map(|event| match event {
+            ApiRecordEvent::Chunk { data } => Some(Ok(Bytes::from(data))),
+            ApiRecordEvent::BinaryChunk { bytes } => Some(Ok(Bytes::from(bytes))),
+            ApiRecordEvent::StreamError { error } => Some(Err(TransportError::Network(error))),
+            ApiRecordEvent::Request { .. }
+            | ApiRecordEvent::Response { .. }
+            | ApiRecordEvent::StreamEnd
+            | ApiRecordEvent::Failed { .. } => None,
+        });
+        Ok(StreamResponse {
+            status,
+            headers,
+            bytes: Box::pin(futures::stream::iter(chunks)),
+        })
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// Header names whose values are credentials.
+fn is_secret_header(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    name == "authorization"
+        || name == "cookie"
+        || name == "set-cookie"
+        || name.contains("api-key")
+        || name.contains("token")
+}
+
+fn header_record(headers: &HeaderMap) -> BTreeMap<String, String> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if is_secret_header(name.as_str()) {
+                REDACTED.to_string()
+            } else {
+                String::from_utf8_lossy(value.as_bytes()).into_owned()
+            };
+            (name.to_string(), value)
+        })
+        .collect()
+}
+
+fn header_map(headers: &BTreeMap<String, String>) -> HeaderMap {
+    headers
+        .iter()
+        .filter_map(|(name, value)| {
+            Some((
+                HeaderName::from_bytes(name.as_bytes()).ok()?,
+                HeaderValue::from_str(value).ok()?,
+            ))
+        })
+        .collect()
+}
+
+/// The part of `url` after the authority, so a replay against a different
+/// host or port still matches.
+fn url_path(url: &str) -> &str {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    rest.find('/').map_or("/", |index| &rest[index..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::stream::BoxStream;
+    use http::Method;
+    use pretty_assertions::assert_eq;
+
+    struct FakeTransport;
+
+    #[async_trait]
+    impl HttpTransport for FakeTransport {
+        async fn execute(&self, _req: Request) -> Result<Response, TransportError> {
+            Err(TransportError::Http {
+                status: StatusCode::TOO_MANY_REQUESTS,
+                url: None,
+                headers: None,
+                body: Some("slow down".to_string()),
+            })
+        }
+
+        async fn stream(&self, _req: Request) -> Result<StreamResponse, TransportError> {
+            let chunks: BoxStream<'static, Result<Bytes, TransportError>> =
+                Box::pin(futures::stream::iter(vec![
+                    Ok(Bytes::from_static(b"data: one\n\n")),
+                    Ok(Bytes::from_static(b"data: two\n\n")),
+                ]));
+            Ok(StreamResponse {
+                status: StatusCode::OK,
+                headers: HeaderMap::new(),
+                bytes: chunks,
+            })
+        }
+    }
+
+    fn request() -> Request {
+        let mut request = Request::new(Method::POST, "https://api.example.com/v1/responses".into());
+        request.headers.insert(
+            "authorization",
+            HeaderValue::from_static("Bearer sk-secret"),
+        );
+        request
+    }
+
+    async fn collect(response: StreamResponse) -> Vec<Bytes> {
+        response
+            .bytes
+            .map(|chunk| chunk.expect("chunk"))
+            .collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn recording_replays_the_same_exchanges() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("recordings").join("session.jsonl");
+        let recorder = ApiRecorder::create(&path).expect("create recording");
+        let transport = RecordingTransport::new(FakeTransport, recorder);
+
+        let streamed = collect(transport.stream(request()).await.expect("stream")).await;
+        let failed = transport.execute(request()).await;
+        assert!(matches!(failed, Err(TransportError::Http { .. })));
+
+        let recording = std::fs::read_to_string(&path).expect("read recording");
+        assert!(!recording.contains("sk-secret"));
+
+        let replay = ReplayTransport::load(&path).expect("load recording");
+        assert_eq!(replay.remaining(), 2);
+        let mut diverged = request();
+        diverged.url = "http://localhost:1234/v1/responses".to_string();
+        let replayed = collect(replay.stream(diverged).await.expect("replay stream")).await;
+        assert_eq!(replayed, streamed);
+        match replay.execute(request()).await {
+            Err(TransportError::Http { status, body, .. }) => {
+                assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+                assert_eq!(body.as_deref(), Some("slow down"));
+            }
+            other => panic!("expected recorded 429, got {other:?}"),
+        }
+        assert!(matches!(
+            replay.execute(request()).await,
+            Err(TransportError::Build(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn replay_rejects_a_diverging_request() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("session.jsonl");
+        let transport = RecordingTransport::new(
+            FakeTransport,
+            ApiRecorder::create(&path).expect("create recording"),
+        );
+        let _ = collect(transport.stream(request()).await.expect("stream")).await;
+
+        let replay = ReplayTransport::load(&path).expect("load recording");
+        let other = Request::new(Method::GET, "https://api.example.com/v1/models".into());
+        assert!(matches!(
+            replay.stream(other).await,
+            Err(TransportError::Build(message)) if message.starts_with("replay diverged")
+        ));
+    }
+}
@@ -9,6 +9,8 @@ use tokio::time::sleep;
 pub struct RetryPolicy {
     pub max_attempts: u64,
     pub base_delay: Duration,
+    /// Upper bound on the delay before any single retry.
+    pub max_delay: Duration,
     pub retry_on: RetryOn,
 }
 
@@ -17,6 +19,8 @@ pub struct RetryOn {
     pub retry_429: bool,
     pub retry_5xx: bool,
     pub retry_transport: bool,
+    /// Additional HTTP status codes to retry.
+    pub retry_statuses: Vec<u16>,
 }
 
 impl RetryOn {
@@ -28,6 +32,7 @@ impl RetryOn {
             TransportError::Http { status, .. } => {
                 (self.retry_429 && status.as_u16() == 429)
                     || (self.retry_5xx && status.is_server_error())
+                    || self.retry_statuses.contains(&status.as_u16())
             }
             TransportError::Timeout | TransportError::Network(_) => self.retry_transport,
             _ => false,
@@ -64,7 +69,7 @@ where
                     .retry_on
                     .should_retry(&err, attempt, policy.max_attempts) =>
             {
-                sleep(backoff(policy.base_delay, attempt + 1)).await;
+                sleep(backoff(policy.base_delay, attempt + 1).min(policy.max_delay)).await;
             }
             Err(err) => return Err(err),
         }
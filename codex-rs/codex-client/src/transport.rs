@@ -11,6 +11,7 @@ use futures::stream::BoxStream;
 use http::HeaderMap;
 use http::Method;
 use http::StatusCode;
+use std::sync::Arc;
 use tracing::Level;
 use tracing::enabled;
 use tracing::trace;
@@ -29,6 +30,18 @@ pub trait HttpTransport: Send + Sync {
     async fn stream(&self, req: Request) -> Result<StreamResponse, TransportError>;
 }
 
+/// Lets callers pick a transport at runtime, e.g. `Arc<dyn HttpTransport>`.
+#[async_trait]
+impl<T: HttpTransport + ?Sized> HttpTransport for Arc<T> {
+    async fn execute(&self, req: Request) -> Result<Response, TransportError> {
+        (**self).execute(req).await
+    }
+
+    async fn stream(&self, req: Request) -> Result<StreamResponse, TransportError> {
+        (**self).stream(req).await
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct ReqwestTransport {
     client: CodexHttpClient,
@@ -13,6 +13,15 @@ mod sandbox_mode_cli_arg;
 #[cfg(feature = "cli")]
 pub use sandbox_mode_cli_arg::SandboxModeCliArg;
 
+#[cfg(feature = "cli")]
+mod reasoning_cli_arg;
+
+#[cfg(feature = "cli")]
+pub use reasoning_cli_arg::ReasoningEffortCliArg;
+
+#[cfg(feature = "cli")]
+pub use reasoning_cli_arg::VerbosityCliArg;
+
 #[cfg(feature = "cli")]
 pub mod format_env_display;
 
@@ -6,6 +6,7 @@ use codex_core::OLLAMA_OSS_PROVIDER_ID;
 use codex_core::WireApi;
 use codex_core::config::Config;
 use codex_core::protocol::DeprecationNoticeEvent;
+pub use codex_ollama::OllamaModelDetails;
 use std::io;
 
 /// Returns the default model for a given OSS provider.
@@ -47,27 +48,47 @@ pub async fn ollama_chat_deprecation_notice(
     Ok(None)
 }
 
+/// Returns true for the built-in Ollama providers, which get model pulls and
+/// capability detection even without `--oss`.
+pub fn is_ollama_provider(provider_id: &str) -> bool {
+    matches!(
+        provider_id,
+        OLLAMA_OSS_PROVIDER_ID | OLLAMA_CHAT_PROVIDER_ID
+    )
+}
+
 /// Ensures the specified OSS provider is ready (models downloaded, service reachable).
+///
+/// For Ollama, also returns the model's metadata; pass it to
+/// [`apply_ollama_model_details`] once the session config is final.
 pub async fn ensure_oss_provider_ready(
     provider_id: &str,
     config: &Config,
-) -> Result<(), std::io::Error> {
+) -> Result<Option<OllamaModelDetails>, std::io::Error> {
     match provider_id {
         LMSTUDIO_OSS_PROVIDER_ID => {
             codex_lmstudio::ensure_oss_ready(config)
                 .await
                 .map_err(|e| std::io::Error::other(format!("OSS setup failed: {e}")))?;
+            Ok(None)
         }
-        OLLAMA_OSS_PROVIDER_ID | OLLAMA_CHAT_PROVIDER_ID => {
-            codex_ollama::ensure_oss_ready(config)
-                .await
-                .map_err(|e| std::io::Error::other(format!("OSS setup failed: {e}")))?;
-        }
+        OLLAMA_OSS_PROVIDER_ID | OLLAMA_CHAT_PROVIDER_ID => codex_ollama::ensure_oss_ready(config)
+            .await
+            .map_err(|e| std::io::Error::other(format!("OSS setup failed: {e}"))),
         _ => {
             // Unknown provider, skip setup
+            Ok(None)
         }
     }
-    Ok(())
+}
+
+/// Adjusts `config` to the capabilities of the local Ollama model and returns
+/// warnings to show the user.
+pub fn apply_ollama_model_details(
+    config: &mut Config,
+    details: &OllamaModelDetails,
+) -> Vec<String> {
+    codex_ollama::apply_model_details(config, details)
 }
 
 #[cfg(test)]
@@ -0,0 +1,69 @@
+//! Standard types to use with the `--reasoning-effort` and `--verbosity` CLI
+//! options.
+//!
+//! These mirror [`ReasoningEffort`] and [`Verbosity`] so the protocol crate
+//! does not need to depend on `clap`.
+
+use clap::ValueEnum;
+use codex_protocol::config_types::Verbosity;
+use codex_protocol::openai_models::ReasoningEffort;
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+#[value(rename_all = "lowercase")]
+pub enum ReasoningEffortCliArg {
+    None,
+    Minimal,
+    Low,
+    Medium,
+    High,
+    XHigh,
+}
+
+impl From<ReasoningEffortCliArg> for ReasoningEffort {
+    fn from(value: ReasoningEffortCliArg) -> Self {
+        match value {
+            ReasoningEffortCliArg::None => ReasoningEffort::None,
+            ReasoningEffortCliArg::Minimal => ReasoningEffort::Minimal,
+            ReasoningEffortCliArg::Low => ReasoningEffort::Low,
+            ReasoningEffortCliArg::Medium => ReasoningEffort::Medium,
+            ReasoningEffortCliArg::High => ReasoningEffort::High,
+            ReasoningEffortCliArg::XHigh => ReasoningEffort::XHigh,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+#[value(rename_all = "lowercase")]
+pub enum VerbosityCliArg {
+    Low,
+    Medium,
+    High,
+}
+
+impl From<VerbosityCliArg> for Verbosity {
+    fn from(value: VerbosityCliArg) -> Self {
+        match value {
+            VerbosityCliArg::Low => Verbosity::Low,
+            VerbosityCliArg::Medium => Verbosity::Medium,
+            VerbosityCliArg::High => Verbosity::High,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn cli_names_match_config_values() {
+        for arg in ReasoningEffortCliArg::value_variants() {
+            let name = arg.to_possible_value().expect("visible value");
+            assert_eq!(name.get_name(), ReasoningEffort::from(*arg).to_string());
+        }
+        for arg in VerbosityCliArg::value_variants() {
+            let name = arg.to_possible_value().expect("visible value");
+            assert_eq!(name.get_name(), Verbosity::from(*arg).to_string());
+        }
+    }
+}
@@ -6,6 +6,7 @@ use codex_api::error::ApiError;
 use codex_api::rate_limits::parse_promo_message;
 use codex_api::rate_limits::parse_rate_limit;
 use http::HeaderMap;
+use http::header::HeaderName;
 use serde::Deserialize;
 
 use crate::auth::CodexAuth;
@@ -31,6 +32,7 @@ pub(crate) fn map_api_error(err: ApiError) -> CodexErr {
             request_id: None,
         }),
         ApiError::InvalidRequest { message } => CodexErr::InvalidRequest(message),
+        ApiError::Blocked { category, message } => CodexErr::Blocked { category, message },
         ApiError::Transport(transport) => match transport {
             TransportError::Http {
                 status,
@@ -123,6 +125,33 @@ mod tests {
     use http::HeaderMap;
     use http::StatusCode;
 
+    #[test]
+    fn auth_provider_uses_configured_auth_header() {
+        let provider: ModelProviderInfo = toml::from_str(
+            r#"
+name = "Corp"
+base_url = "https://gateway.example.com/v1"
+experimental_bearer_token = "secret"
+auth_header = "api-key"
+auth_scheme = ""
+"#,
+        )
+        .expect("provider");
+        let auth = auth_provider_from_auth(None, &provider).expect("auth provider");
+        assert_eq!(auth.auth_header_name().as_str(), "api-key");
+        assert_eq!(auth.auth_scheme(), None);
+        assert_eq!(auth.bearer_token().as_deref(), Some("secret"));
+
+        let default_provider = ModelProviderInfo {
+            auth_header: None,
+            auth_scheme: None,
+            ..provider
+        };
+        let auth = auth_provider_from_auth(None, &default_provider).expect("auth provider");
+        assert_eq!(auth.auth_header_name(), http::header::AUTHORIZATION);
+        assert_eq!(auth.auth_scheme().as_deref(), Some("Bearer"));
+    }
+
     #[test]
     fn map_api_error_maps_model_cap_headers() {
         let mut headers = HeaderMap::new();
@@ -165,32 +194,22 @@ pub(crate) fn auth_provider_from_auth(
     auth: Option<CodexAuth>,
     provider: &ModelProviderInfo,
 ) -> crate::error::Result<CoreAuthProvider> {
-    if let Some(api_key) = provider.api_key()? {
-        return Ok(CoreAuthProvider {
-            token: Some(api_key),
-            account_id: None,
-        });
-    }
-
-    if let Some(token) = provider.experimental_bearer_token.clone() {
-        return Ok(CoreAuthProvider {
-            token: Some(token),
-            account_id: None,
-        });
-    }
-
-    if let Some(auth) = auth {
-        let token = auth.get_token()?;
-        Ok(CoreAuthProvider {
-            token: Some(token),
-            account_id: auth.get_account_id(),
-        })
+    let (header_name, scheme) = provider.auth_header_format()?;
+    let (token, account_id) = if let Some(api_key) = provider.api_key()? {
+        (Some(api_key), None)
+    } else if let Some(token) = provider.experimental_bearer_token.clone() {
+        (Some(token), None)
+    } else if let Some(auth) = auth {
+        (Some(auth.get_token()?), auth.get_account_id())
     } else {
-        Ok(CoreAuthProvider {
-            token: None,
-            account_id: None,
-        })
-    }
+        (None, None)
+    };
+    Ok(CoreAuthProvider {
+        token,
+        account_id,
+        header_name,
+        scheme,
+    })
 }
 
 #[derive(Debug, Deserialize)]
@@ -206,10 +225,13 @@ struct UsageErrorBody {
     resets_at: Option<i64>,
 }
 
-#[derive(Clone, Default)]
+#[derive(Clone)]
 pub(crate) struct CoreAuthProvider {
     token: Option<String>,
     account_id: Option<String>,
+    /// Header carrying `token`, and the scheme written before it.
+    header_name: HeaderName,
+    scheme: Option<String>,
 }
 
 impl ApiAuthProvider for CoreAuthProvider {
@@ -220,4 +242,12 @@ impl ApiAuthProvider for CoreAuthProvider {
     fn account_id(&self) -> Option<String> {
         self.account_id.clone()
     }
+
+    fn auth_header_name(&self) -> HeaderName {
+        self.header_name.clone()
+    }
+
+    fn auth_scheme(&self) -> Option<String> {
+        self.scheme.clone()
+    }
 }
@@ -1,12 +1,26 @@
+use crate::approval_rules::RuleSubject;
+use crate::approval_rules::deciding_rule;
+use crate::approval_rules::rule_reason;
 use crate::codex::TurnContext;
+use crate::config::types::ApprovalRuleDecision;
 use crate::function_tool::FunctionCallError;
+use crate::protocol::AskForApproval;
 use crate::protocol::FileChange;
+use crate::protocol::PatchHunkId;
+use crate::protocol::PatchHunkReport;
+use crate::protocol::PatchHunkStatus;
+use crate::protocol::SandboxPolicy;
 use crate::safety::SafetyCheck;
+use crate::safety::assess_patch_file_approvals;
 use crate::safety::assess_patch_safety;
 use crate::tools::sandboxing::ExecApprovalRequirement;
+use crate::write_protected::first_write_protected;
 use codex_apply_patch::ApplyPatchAction;
 use codex_apply_patch::ApplyPatchFileChange;
+use codex_apply_patch::HunkStatus;
+use codex_apply_patch::dry_run_patch;
 use std::collections::HashMap;
+use std::path::Path;
 use std::path::PathBuf;
 
 pub const CODEX_APPLY_PATCH_ARG1: &str = "--codex-run-as-apply-patch";
@@ -31,19 +45,79 @@ pub(crate) struct ApplyPatchExec {
     pub(crate) action: ApplyPatchAction,
     pub(crate) auto_approved: bool,
     pub(crate) exec_approval_requirement: ExecApprovalRequirement,
+    /// The patch touches a `write_protected` path and needs explicit approval.
+    pub(crate) write_protected: bool,
 }
 
 pub(crate) async fn apply_patch(
     turn_context: &TurnContext,
     action: ApplyPatchAction,
 ) -> InternalApplyPatchInvocation {
-    match assess_patch_safety(
-        &action,
-        turn_context.approval_policy,
-        &turn_context.sandbox_policy,
-        &turn_context.cwd,
-        turn_context.windows_sandbox_level,
-    ) {
+    if let Some(path) = write_protected_path(&action, turn_context) {
+        let path = path.strip_prefix(&turn_context.cwd).unwrap_or(&path);
+        if turn_context.approval_policy == AskForApproval::Never {
+            return InternalApplyPatchInvocation::Output(Err(FunctionCallError::RespondToModel(
+                format!(
+                    "patch rejected: {} is write-protected and approval_policy is never",
+                    path.display()
+                ),
+            )));
+        }
+        // A reason skips cached session approvals, so every such patch is
+        // shown to the user.
+        return InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
+            action,
+            auto_approved: false,
+            exec_approval_requirement: ExecApprovalRequirement::NeedsApproval {
+                reason: Some(format!("{} is write-protected", path.display())),
+                proposed_execpolicy_amendment: None,
+            },
+            write_protected: true,
+        });
+    }
+
+    if let Some((decision, reason)) = matching_approval_rule(&action, turn_context) {
+        return match decision {
+            // An `allow` rule skips the prompt, not a rejection.
+            ApprovalRuleDecision::Allow => match patch_safety(&action, turn_context) {
+                SafetyCheck::Reject { reason } => InternalApplyPatchInvocation::Output(Err(
+                    FunctionCallError::RespondToModel(format!("patch rejected: {reason}")),
+                )),
+                SafetyCheck::AutoApprove { .. } | SafetyCheck::AskUser => {
+                    InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
+                        action,
+                        auto_approved: true,
+                        exec_approval_requirement: ExecApprovalRequirement::Skip {
+                            bypass_sandbox: false,
+                            proposed_execpolicy_amendment: None,
+                        },
+                        write_protected: false,
+                    })
+                }
+            },
+            ApprovalRuleDecision::Deny => InternalApplyPatchInvocation::Output(Err(
+                FunctionCallError::RespondToModel(format!("patch rejected: {reason}")),
+            )),
+            ApprovalRuleDecision::Ask if turn_context.approval_policy == AskForApproval::Never => {
+                InternalApplyPatchInvocation::Output(Err(FunctionCallError::RespondToModel(
+                    format!("patch rejected: {reason}, and approval_policy is never"),
+                )))
+            }
+            ApprovalRuleDecision::Ask => {
+                InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
+                    action,
+                    auto_approved: false,
+                    exec_approval_requirement: ExecApprovalRequirement::NeedsApproval {
+                        reason: Some(reason),
+                        proposed_execpolicy_amendment: None,
+                    },
+                    write_protected: false,
+                })
+            }
+        };
+    }
+
+    match patch_safety(&action, turn_context) {
         SafetyCheck::AutoApprove {
             user_explicitly_approved,
             ..
@@ -51,9 +125,11 @@ pub(crate) async fn apply_patch(
             action,
             auto_approved: !user_explicitly_approved,
             exec_approval_requirement: ExecApprovalRequirement::Skip {
+                // `file_approvals = "auto"` skips the prompt, not the sandbox.
                 bypass_sandbox: false,
                 proposed_execpolicy_amendment: None,
             },
+            write_protected: false,
         }),
         SafetyCheck::AskUser => {
             // Delegate the approval prompt (including cached approvals) to the
@@ -66,6 +142,7 @@ pub(crate) async fn apply_patch(
                     reason: None,
                     proposed_execpolicy_amendment: None,
                 },
+                write_protected: false,
             })
         }
         SafetyCheck::Reject { reason } => InternalApplyPatchInvocation::Output(Err(
@@ -74,6 +151,63 @@ pub(crate) async fn apply_patch(
     }
 }
 
+/// Whether the patch can be applied without asking, per `[file_approvals]`
+/// and the sandbox policy.
+fn patch_safety(action: &ApplyPatchAction, turn_context: &TurnContext) -> SafetyCheck {
+    assess_patch_file_approvals(
+        action,
+        turn_context.client.config().file_approvals,
+        turn_context.approval_policy,
+    )
+    .unwrap_or_else(|| {
+        assess_patch_safety(
+            action,
+            turn_context.approval_policy,
+            &turn_context.sandbox_policy,
+            &turn_context.cwd,
+            turn_context.windows_sandbox_level,
+        )
+    })
+}
+
+/// The decision and reason of the `[[approval.rules]]` entry deciding the
+/// patch, if any.
+fn matching_approval_rule(
+    action: &ApplyPatchAction,
+    turn_context: &TurnContext,
+) -> Option<(ApprovalRuleDecision, String)> {
+    let config = turn_context.client.config();
+    let subject = RuleSubject::Patch {
+        paths: changed_paths(action).collect(),
+        cwd: &turn_context.cwd,
+    };
+    let sandboxed = !matches!(turn_context.sandbox_policy, SandboxPolicy::DangerFullAccess);
+    deciding_rule(&config.approval.rules, &subject, sandboxed)
+        .map(|rule| (rule.decision, rule_reason(rule)))
+}
+
+/// Every path the patch writes, deletes, or moves to.
+fn changed_paths(action: &ApplyPatchAction) -> impl Iterator<Item = &Path> {
+    action.changes().iter().flat_map(|(path, change)| {
+        let move_path = match change {
+            ApplyPatchFileChange::Update { move_path, .. } => move_path.as_deref(),
+            ApplyPatchFileChange::Add { .. } | ApplyPatchFileChange::Delete { .. } => None,
+        };
+        std::iter::once(path.as_path()).chain(move_path)
+    })
+}
+
+/// The first file the patch writes, deletes, or moves to that matches
+/// `write_protected`.
+fn write_protected_path(action: &ApplyPatchAction, turn_context: &TurnContext) -> Option<PathBuf> {
+    first_write_protected(
+        &turn_context.client.config().write_protected,
+        &turn_context.cwd,
+        changed_paths(action),
+    )
+    .map(Path::to_path_buf)
+}
+
 pub(crate) fn convert_apply_patch_to_protocol(
     action: &ApplyPatchAction,
 ) -> HashMap<PathBuf, FileChange> {
@@ -101,6 +235,69 @@ pub(crate) fn convert_apply_patch_to_protocol(
     result
 }
 
+/// Dry-run `action`'s patch and report, hunk by hunk, whether it applies
+/// cleanly, only fuzzily, or conflicts. Empty when the patch cannot be parsed.
+pub(crate) fn dry_run_hunks(action: &ApplyPatchAction, fuzz: usize) -> Vec<PatchHunkReport> {
+    let Ok(report) = dry_run_patch(&action.patch, &action.cwd, fuzz) else {
+        return Vec::new();
+    };
+    report
+        .hunks
+        .into_iter()
+        .map(|hunk| {
+            let (status, detail) = match hunk.status {
+                HunkStatus::Clean => (PatchHunkStatus::Clean, None),
+                HunkStatus::Fuzzy(how) => (
+                    PatchHunkStatus::Fuzzy,
+                    Some(format!("matched {}", how.description())),
+                ),
+                HunkStatus::Conflict(reason) => (PatchHunkStatus::Conflict, Some(reason)),
+            };
+            PatchHunkReport {
+                path: hunk.path,
+                hunk: u32::try_from(hunk.hunk).unwrap_or(u32::MAX),
+                line: hunk.line.and_then(|line| u32::try_from(line).ok()),
+                status,
+                detail,
+            }
+        })
+        .collect()
+}
+
+/// How the hunks of `action` that did not match verbatim were located, for
+/// the model. Must be called before the patch is applied.
+pub(crate) fn inexact_hunk_summary(action: &ApplyPatchAction, fuzz: usize) -> Option<String> {
+    let mut report = dry_run_patch(&action.patch, &action.cwd, fuzz).ok()?;
+    report
+        .hunks
+        .retain(|hunk| matches!(hunk.status, HunkStatus::Fuzzy(_)));
+    (!report.hunks.is_empty()).then(|| {
+        format!(
+            "Some hunks did not match exactly; check that they landed where intended:\n{}",
+            report.summary(&action.cwd)
+        )
+    })
+}
+
+/// Tells the model which hunks the user left out of a partially approved
+/// patch, so it does not assume they were applied.
+pub(crate) fn declined_hunks_note(declined: &[PatchHunkId], cwd: &Path) -> Option<String> {
+    if declined.is_empty() {
+        return None;
+    }
+    let hunks = declined
+        .iter()
+        .map(|id| {
+            let path = id.path.strip_prefix(cwd).unwrap_or(&id.path);
+            format!("{}: hunk {}", path.display(), id.hunk)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    Some(format!(
+        "The user declined these hunks, so they were not applied:\n{hunks}"
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -124,4 +321,29 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn declined_hunks_note_lists_paths_relative_to_cwd() {
+        let cwd = Path::new("/repo");
+        let declined = vec![
+            PatchHunkId {
+                path: PathBuf::from("/repo/src/lib.rs"),
+                hunk: 2,
+            },
+            PatchHunkId {
+                path: PathBuf::from("/elsewhere/notes.md"),
+                hunk: 1,
+            },
+        ];
+
+        assert_eq!(
+            declined_hunks_note(&declined, cwd).as_deref(),
+            Some(
+                "The user declined these hunks, so they were not applied:\n\
+                 src/lib.rs: hunk 2\n\
+                 /elsewhere/notes.md: hunk 1"
+            )
+        );
+        assert_eq!(declined_hunks_note(&[], cwd), None);
+    }
 }
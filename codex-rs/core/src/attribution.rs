@@ -0,0 +1,121 @@
+//! Attribution trailers for commits and patches created by the agent.
+//!
+//! When `[attribution] enabled = true`, the model is instructed to end the
+//! messages of commits it creates with standard git trailers, and commits that
+//! Codex makes itself (e.g. worker task commits) get the same trailers
+//! appended directly.
+
+use codex_protocol::ThreadId;
+
+use crate::config::types::AttributionConfig;
+
+const INSTRUCTIONS_TEMPLATE: &str = include_str!("../templates/attribution/instructions.md");
+
+/// Trailer key used to record the session that produced a change.
+const SESSION_TRAILER_KEY: &str = "Codex-Session";
+
+/// Returns the trailers to attach to agent-created commits, or an empty list
+/// when attribution is disabled.
+pub(crate) fn commit_trailers(config: &AttributionConfig, thread_id: &ThreadId) -> Vec<String> {
+    if !config.enabled {
+        return Vec::new();
+    }
+    let mut trailers = vec![format!("Co-authored-by: {}", config.co_author.trim())];
+    if config.session_id {
+        trailers.push(format!("{SESSION_TRAILER_KEY}: {thread_id}"));
+    }
+    trailers
+}
+
+/// Appends `trailers` to a commit message, separated from the body by a
+/// blank line. Trailers already present in the message are not repeated.
+pub(crate) fn append_trailers(message: &str, trailers: &[String]) -> String {
+    let message = message.trim_end();
+    let missing: Vec<&str> = trailers
+        .iter()
+        .map(String::as_str)
+        .filter(|trailer| !message.lines().any(|line| line.trim() == *trailer))
+        .collect();
+    if missing.is_empty() {
+        return message.to_string();
+    }
+    let last_paragraph = message.rsplit("\n\n").next().unwrap_or_default();
+    let ends_with_trailers = message.contains("\n\n")
+        && last_paragraph
+            .lines()
+            .all(|line| line.trim().contains(": "));
+    let separator = if message.is_empty() {
+        ""
+    } else if ends_with_trailers {
+        "\n"
+    } else {
+        "\n\n"
+    };
+    format!("{message}{separator}{}", missing.join("\n"))
+}
+
+/// Developer instructions asking the model to attribute its commits, or
+/// `None` when attribution is disabled.
+pub(crate) fn developer_instructions(
+    config: &AttributionConfig,
+    thread_id: &ThreadId,
+) -> Option<String> {
+    let trailers = commit_trailers(config, thread_id);
+    if trailers.is_empty() {
+        return None;
+    }
+    Some(INSTRUCTIONS_TEMPLATE.replace("{trailers}", &trailers.join("\n")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn enabled_config() -> AttributionConfig {
+        AttributionConfig {
+            enabled: true,
+            ..AttributionConfig::default()
+        }
+    }
+
+    #[test]
+    fn disabled_attribution_adds_nothing() {
+        let thread_id = ThreadId::new();
+        assert_eq!(
+            commit_trailers(&AttributionConfig::default(), &thread_id),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            developer_instructions(&AttributionConfig::default(), &thread_id),
+            None
+        );
+    }
+
+    #[test]
+    fn appends_trailers_after_a_blank_line_once() {
+        let thread_id = ThreadId::new();
+        let trailers = commit_trailers(&enabled_config(), &thread_id);
+        let message = append_trailers("task-1: rename foo\n", &trailers);
+
+        assert_eq!(
+            message,
+            format!(
+                "task-1: rename foo\n\nCo-authored-by: Codex <noreply@openai.com>\nCodex-Session: {thread_id}"
+            )
+        );
+        assert_eq!(append_trailers(&message, &trailers), message);
+    }
+
+    #[test]
+    fn extends_an_existing_trailer_block() {
+        let trailers = vec!["Co-authored-by: Codex <noreply@openai.com>".to_string()];
+        assert_eq!(
+            append_trailers(
+                "Fix parser\n\nSigned-off-by: Dev <dev@example.com>",
+                &trailers
+            ),
+            "Fix parser\n\nSigned-off-by: Dev <dev@example.com>\nCo-authored-by: Codex <noreply@openai.com>"
+        );
+    }
+}
@@ -344,6 +344,56 @@ impl ChatgptAuth {
     }
 }
 
+/// Subdirectory of `CODEX_HOME` holding the credentials of named accounts.
+const ACCOUNTS_DIR: &str = "accounts";
+
+/// Returns the directory whose credential store holds the login for
+/// `account`: `codex_home` itself for the default account, otherwise
+/// `codex_home/accounts/<account>`.
+pub fn account_auth_home(codex_home: &Path, account: Option<&str>) -> PathBuf {
+    match account {
+        Some(account) => codex_home.join(ACCOUNTS_DIR).join(account),
+        None => codex_home.to_path_buf(),
+    }
+}
+
+/// Checks that `name` is usable as an account name: ASCII letters, digits,
+/// `-`, and `_`.
+pub fn validate_account_name(name: &str) -> Result<(), String> {
+    if !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        Ok(())
+    } else {
+        Err(format!(
+            "invalid account name `{name}`: use letters, digits, `-`, and `_`"
+        ))
+    }
+}
+
+/// Lists the named accounts that have a directory under `codex_home`.
+pub fn list_accounts(codex_home: &Path) -> std::io::Result<Vec<String>> {
+    let entries = match std::fs::read_dir(codex_home.join(ACCOUNTS_DIR)) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let mut accounts = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        if entry.file_type()?.is_dir()
+            && let Some(name) = entry.file_name().to_str()
+            && validate_account_name(name).is_ok()
+        {
+            accounts.push(name.to_string());
+        }
+    }
+    accounts.sort();
+    Ok(accounts)
+}
+
 pub const OPENAI_API_KEY_ENV_VAR: &str = "OPENAI_API_KEY";
 pub const CODEX_API_KEY_ENV_VAR: &str = "CODEX_API_KEY";
 
@@ -425,7 +475,7 @@ pub fn load_auth_dot_json(
 
 pub fn enforce_login_restrictions(config: &Config) -> std::io::Result<()> {
     let Some(auth) = load_auth(
-        &config.codex_home,
+        &config.auth_home(),
         true,
         config.cli_auth_credentials_store_mode,
     )?
@@ -449,7 +499,7 @@ pub fn enforce_login_restrictions(config: &Config) -> std::io::Result<()> {
 
         if let Some(message) = method_violation {
             return logout_with_message(
-                &config.codex_home,
+                &config.auth_home(),
                 message,
                 config.cli_auth_credentials_store_mode,
             );
@@ -465,7 +515,7 @@ pub fn enforce_login_restrictions(config: &Config) -> std::io::Result<()> {
             Ok(data) => data,
             Err(err) => {
                 return logout_with_message(
-                    &config.codex_home,
+                    &config.auth_home(),
                     format!(
                         "Failed to load ChatGPT credentials while enforcing workspace restrictions: {err}. Logging out."
                     ),
@@ -486,7 +536,7 @@ pub fn enforce_login_restrictions(config: &Config) -> std::io::Result<()> {
                 ),
             };
             return logout_with_message(
-                &config.codex_home,
+                &config.auth_home(),
                 message,
                 config.cli_auth_credentials_store_mode,
             );
@@ -1347,6 +1397,36 @@ mod tests {
         assert!(auth.tokens.is_none(), "tokens should be cleared");
     }
 
+    #[test]
+    fn named_accounts_keep_separate_credentials() {
+        let dir = tempdir().unwrap();
+        let work_home = account_auth_home(dir.path(), Some("work"));
+        std::fs::create_dir_all(&work_home).unwrap();
+
+        super::login_with_api_key(dir.path(), "sk-default", AuthCredentialsStoreMode::File)
+            .expect("default login should succeed");
+        super::login_with_api_key(&work_home, "sk-work", AuthCredentialsStoreMode::File)
+            .expect("work login should succeed");
+
+        let default_auth = load_auth_dot_json(dir.path(), AuthCredentialsStoreMode::File)
+            .unwrap()
+            .expect("default auth should exist");
+        let work_auth = load_auth_dot_json(&work_home, AuthCredentialsStoreMode::File)
+            .unwrap()
+            .expect("work auth should exist");
+        assert_eq!(default_auth.openai_api_key.as_deref(), Some("sk-default"));
+        assert_eq!(work_auth.openai_api_key.as_deref(), Some("sk-work"));
+        assert_eq!(list_accounts(dir.path()).unwrap(), vec!["work".to_string()]);
+    }
+
+    #[test]
+    fn validate_account_name_rejects_path_components() {
+        assert_eq!(validate_account_name("work_2-a"), Ok(()));
+        assert!(validate_account_name("").is_err());
+        assert!(validate_account_name("../work").is_err());
+        assert!(validate_account_name("a/b").is_err());
+    }
+
     #[test]
     fn missing_auth_json_returns_none() {
         let dir = tempdir().unwrap();
@@ -6,6 +6,7 @@ use serde::Serialize;
 use sha2::Digest;
 use sha2::Sha256;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fmt::Debug;
 use std::fs::File;
 use std::fs::OpenOptions;
@@ -17,6 +18,7 @@ use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::Mutex;
+use tracing::info;
 use tracing::warn;
 
 use crate::token_data::TokenData;
@@ -190,12 +192,30 @@ impl KeyringAuthStorage {
             }
         }
     }
+
+    /// Moves credentials from a plaintext `auth.json` left by file storage into
+    /// the keyring, removing the file once the keyring holds them.
+    fn migrate_auth_file(&self, key: &str) -> std::io::Result<Option<AuthDotJson>> {
+        let Some(auth) = FileAuthStorage::new(self.codex_home.clone()).load()? else {
+            return Ok(None);
+        };
+        let serialized = serde_json::to_string(&auth).map_err(std::io::Error::other)?;
+        self.save_to_keyring(key, &serialized)?;
+        if let Err(err) = delete_file_if_exists(&self.codex_home) {
+            warn!("failed to remove CLI auth file after migrating it to the keyring: {err}");
+        }
+        info!("migrated CLI auth from auth.json to the keyring");
+        Ok(Some(auth))
+    }
 }
 
 impl AuthStorageBackend for KeyringAuthStorage {
     fn load(&self) -> std::io::Result<Option<AuthDotJson>> {
         let key = compute_store_key(&self.codex_home)?;
-        self.load_from_keyring(&key)
+        match self.load_from_keyring(&key)? {
+            Some(auth) => Ok(Some(auth)),
+            None => self.migrate_auth_file(&key),
+        }
     }
 
     fn save(&self, auth: &AuthDotJson) -> std::io::Result<()> {
@@ -228,6 +248,11 @@ struct AutoAuthStorage {
     file_storage: Arc<FileAuthStorage>,
 }
 
+// Homes whose keyring could not be used in this process. `Auto` storage reads
+// them from auth.json without trying the keyring, or migrating into it, again.
+static KEYRING_UNAVAILABLE: Lazy<Mutex<HashSet<PathBuf>>> =
+    Lazy::new(|| Mutex::new(HashSet::new()));
+
 impl AutoAuthStorage {
     fn new(codex_home: PathBuf, keyring_store: Arc<dyn KeyringStore>) -> Self {
         Self {
@@ -235,15 +260,36 @@ impl AutoAuthStorage {
             file_storage: Arc::new(FileAuthStorage::new(codex_home)),
         }
     }
+
+    fn keyring_unavailable(&self) -> bool {
+        KEYRING_UNAVAILABLE
+            .lock()
+            .is_ok_and(|homes| homes.contains(&self.keyring_storage.codex_home))
+    }
+
+    fn set_keyring_unavailable(&self, unavailable: bool) {
+        let Ok(mut homes) = KEYRING_UNAVAILABLE.lock() else {
+            return;
+        };
+        if unavailable {
+            homes.insert(self.keyring_storage.codex_home.clone());
+        } else {
+            homes.remove(&self.keyring_storage.codex_home);
+        }
+    }
 }
 
 impl AuthStorageBackend for AutoAuthStorage {
     fn load(&self) -> std::io::Result<Option<AuthDotJson>> {
+        if self.keyring_unavailable() {
+            return self.file_storage.load();
+        }
         match self.keyring_storage.load() {
             Ok(Some(auth)) => Ok(Some(auth)),
             Ok(None) => self.file_storage.load(),
             Err(err) => {
                 warn!("failed to load CLI auth from keyring, falling back to file storage: {err}");
+                self.set_keyring_unavailable(true);
                 self.file_storage.load()
             }
         }
@@ -251,9 +297,13 @@ impl AuthStorageBackend for AutoAuthStorage {
 
     fn save(&self, auth: &AuthDotJson) -> std::io::Result<()> {
         match self.keyring_storage.save(auth) {
-            Ok(()) => Ok(()),
+            Ok(()) => {
+                self.set_keyring_unavailable(false);
+                Ok(())
+            }
             Err(err) => {
                 warn!("failed to save auth to keyring, falling back to file storage: {err}");
+                self.set_keyring_unavailable(true);
                 self.file_storage.save(auth)
             }
         }
@@ -341,8 +391,44 @@ mod tests {
     use serde_json::json;
     use tempfile::tempdir;
 
+    use codex_keyring_store::CredentialStoreError;
     use codex_keyring_store::tests::MockKeyringStore;
     use keyring::Error as KeyringError;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
+    /// A keyring that is readable but rejects every write.
+    #[derive(Debug, Default)]
+    struct ReadOnlyKeyringStore {
+        save_attempts: AtomicUsize,
+    }
+
+    impl KeyringStore for ReadOnlyKeyringStore {
+        fn load(
+            &self,
+            _service: &str,
+            _account: &str,
+        ) -> Result<Option<String>, CredentialStoreError> {
+            Ok(None)
+        }
+
+        fn save(
+            &self,
+            _service: &str,
+            _account: &str,
+            _value: &str,
+        ) -> Result<(), CredentialStoreError> {
+            self.save_attempts.fetch_add(1, Ordering::SeqCst);
+            Err(CredentialStoreError::new(KeyringError::Invalid(
+                "error".into(),
+                "save".into(),
+            )))
+        }
+
+        fn delete(&self, _service: &str, _account: &str) -> Result<bool, CredentialStoreError> {
+            Ok(false)
+        }
+    }
 
     #[tokio::test]
     async fn file_storage_load_returns_auth_dot_json() -> anyhow::Result<()> {
@@ -640,16 +726,74 @@ mod tests {
     }
 
     #[test]
-    fn auto_auth_storage_load_uses_file_when_keyring_empty() -> anyhow::Result<()> {
+    fn keyring_auth_storage_load_migrates_auth_file() -> anyhow::Result<()> {
+        let codex_home = tempdir()?;
+        let mock_keyring = MockKeyringStore::default();
+        let storage = KeyringAuthStorage::new(
+            codex_home.path().to_path_buf(),
+            Arc::new(mock_keyring.clone()),
+        );
+        let expected = auth_with_prefix("legacy");
+        FileAuthStorage::new(codex_home.path().to_path_buf()).save(&expected)?;
+
+        let loaded = storage.load()?;
+
+        assert_eq!(loaded, Some(expected.clone()));
+        let key = compute_store_key(codex_home.path())?;
+        assert_keyring_saved_auth_and_removed_fallback(
+            &mock_keyring,
+            &key,
+            codex_home.path(),
+            &expected,
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn auto_auth_storage_load_migrates_file_into_keyring() -> anyhow::Result<()> {
         let codex_home = tempdir()?;
         let mock_keyring = MockKeyringStore::default();
-        let storage = AutoAuthStorage::new(codex_home.path().to_path_buf(), Arc::new(mock_keyring));
+        let storage = AutoAuthStorage::new(
+            codex_home.path().to_path_buf(),
+            Arc::new(mock_keyring.clone()),
+        );
 
         let expected = auth_with_prefix("file-only");
         storage.file_storage.save(&expected)?;
 
         let loaded = storage.load()?;
-        assert_eq!(loaded, Some(expected));
+        assert_eq!(loaded, Some(expected.clone()));
+        let key = compute_store_key(codex_home.path())?;
+        assert_keyring_saved_auth_and_removed_fallback(
+            &mock_keyring,
+            &key,
+            codex_home.path(),
+            &expected,
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn auto_auth_storage_load_keeps_file_when_keyring_write_fails() -> anyhow::Result<()> {
+        let codex_home = tempdir()?;
+        let keyring = Arc::new(ReadOnlyKeyringStore::default());
+        let storage = AutoAuthStorage::new(codex_home.path().to_path_buf(), keyring.clone());
+
+        let expected = auth_with_prefix("file-only");
+        storage.file_storage.save(&expected)?;
+
+        let loaded = storage.load()?;
+        assert_eq!(loaded, Some(expected.clone()));
+        assert!(
+            get_auth_file(codex_home.path()).exists(),
+            "auth.json should be kept when the keyring write fails"
+        );
+        assert_eq!(storage.file_storage.load()?, Some(expected.clone()));
+
+        // A later load, even through a new storage, does not retry the migration.
+        let storage = AutoAuthStorage::new(codex_home.path().to_path_buf(), keyring.clone());
+        assert_eq!(storage.load()?, Some(expected));
+        assert_eq!(keyring.save_attempts.load(Ordering::SeqCst), 1);
         Ok(())
     }
 
@@ -0,0 +1,423 @@
+//! Parallel batch runs for `codex batch run`.
+//!
+//! A batch file lists independent tasks. Unlike the workers in
+//! [`crate::workers`], which share a queue, every task runs as its own session
+//! in a dedicated worktree on a `codex/batch-<timestamp>/<id>` branch, several
+//! at a time, and its changes are committed to that branch when it finishes.
+//! The worktrees and branches are kept so the results can be reviewed and
+//! merged. Approval requests that still reach a task are declined.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
+
+use codex_protocol::ThreadId;
+use codex_protocol::approvals::ElicitationAction;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::Op;
+use codex_protocol::protocol::ReviewDecision;
+use codex_protocol::protocol::SessionSource;
+use codex_protocol::user_input::UserInput;
+use futures::StreamExt;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::AuthManager;
+use crate::ThreadManager;
+use crate::attribution;
+use crate::config::Config;
+use crate::session_worktree::SessionWorktree;
+
+/// Tasks run at once when the batch file does not say.
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Contents of a batch file.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct BatchSpec {
+    /// Tasks run at once.
+    #[serde(default)]
+    pub concurrency: Option<usize>,
+    /// Token budget for every task that does not set its own.
+    #[serde(default)]
+    pub token_budget: Option<i64>,
+    /// Model for every task that does not set its own.
+    #[serde(default)]
+    pub model: Option<String>,
+    pub tasks: Vec<BatchTask>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct BatchTask {
+    /// Names the task in the report and its worktree branch.
+    pub id: String,
+    pub prompt: String,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub token_budget: Option<i64>,
+}
+
+impl BatchSpec {
+    pub fn from_yaml(contents: &str) -> Result<Self, String> {
+        let spec: Self =
+            serde_yaml::from_str(contents).map_err(|err| format!("invalid batch file: {err}"))?;
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.tasks.is_empty() {
+            return Err("batch file has no tasks".to_string());
+        }
+        if self.concurrency == Some(0) {
+            return Err("concurrency must be at least 1".to_string());
+        }
+        if self.token_budget.is_some_and(|budget| budget < 1) {
+            return Err("token_budget must be at least 1".to_string());
+        }
+        let mut ids = HashSet::new();
+        for task in &self.tasks {
+            let id = task.id.as_str();
+            if id.is_empty()
+                || !id
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            {
+                return Err(format!(
+                    "task id `{id}` may only use letters, digits, `-` and `_`"
+                ));
+            }
+            if !ids.insert(id) {
+                return Err(format!("task id `{id}` is used more than once"));
+            }
+            if task.token_budget.is_some_and(|budget| budget < 1) {
+                return Err(format!("task `{id}`: token_budget must be at least 1"));
+            }
+        }
+        Ok(())
+    }
+
+    fn budget_for(&self, task: &BatchTask) -> Option<i64> {
+        task.token_budget.or(self.token_budget)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskStatus {
+    Completed,
+    Failed,
+    /// Stopped after using its token budget.
+    OverBudget,
+}
+
+/// Progress reported while a batch runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskProgress {
+    Started,
+    Tokens(i64),
+    Finished(TaskStatus),
+}
+
+/// Outcome of one task.
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskReport {
+    pub id: String,
+    pub status: TaskStatus,
+    pub last_message: Option<String>,
+    pub error: Option<String>,
+    pub tokens: i64,
+    pub thread_id: Option<ThreadId>,
+    pub branch: Option<String>,
+    pub worktree: Option<PathBuf>,
+    /// Commit holding the task's changes, or `None` when it changed nothing.
+    pub commit: Option<String>,
+    #[serde(with = "duration_secs")]
+    pub duration: Duration,
+}
+
+/// Run every task in `spec` from `config.cwd`, which must be inside a git
+/// repository. Reports come back in the order of the batch file.
+pub async fn run_batch(
+    spec: &BatchSpec,
+    config: &Config,
+    on_progress: impl Fn(&str, TaskProgress) + Sync,
+) -> Vec<TaskReport> {
+    let auth_manager = AuthManager::shared(
+        config.auth_home(),
+        true,
+        config.cli_auth_credentials_store_mode,
+    );
+    let thread_manager =
+        ThreadManager::new(config.codex_home.clone(), auth_manager, SessionSource::Exec);
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
+    let concurrency = spec.concurrency.unwrap_or(DEFAULT_CONCURRENCY);
+
+    let mut reports: Vec<(usize, TaskReport)> =
+        futures::stream::iter(spec.tasks.iter().enumerate())
+            .map(|(index, task)| {
+                let thread_manager = &thread_manager;
+                let stamp = stamp.as_str();
+                let on_progress = &on_progress;
+                async move {
+                    on_progress(&task.id, TaskProgress::Started);
+                    let report =
+                        run_task(spec, task, config, thread_manager, stamp, on_progress).await;
+                    on_progress(&task.id, TaskProgress::Finished(report.status));
+                    (index, report)
+                }
+            })
+            .buffer_unordered(concurrency)
+            .collect()
+            .await;
+    reports.sort_by_key(|(index, _)| *index);
+    reports.into_iter().map(|(_, report)| report).collect()
+}
+
+async fn run_task(
+    spec: &BatchSpec,
+    task: &BatchTask,
+    config: &Config,
+    thread_manager: &ThreadManager,
+    stamp: &str,
+    on_progress: &(impl Fn(&str, TaskProgress) + Sync),
+) -> TaskReport {
+    let started = Instant::now();
+    let mut report = TaskReport {
+        id: task.id.clone(),
+        status: TaskStatus::Failed,
+        last_message: None,
+        error: None,
+        tokens: 0,
+        thread_id: None,
+        branch: None,
+        worktree: None,
+        commit: None,
+        duration: Duration::ZERO,
+    };
+
+    let name = format!("batch-{stamp}/{}", task.id);
+    let worktree = match SessionWorktree::create_named(&config.codex_home, &config.cwd, &name).await
+    {
+        Ok(Some(worktree)) => worktree,
+        Ok(None) => {
+            report.error = Some(format!(
+                "{} is not inside a git repository",
+                config.cwd.display()
+            ));
+            return report;
+        }
+        Err(err) => {
+            report.error = Some(format!("failed to create a worktree: {err}"));
+            return report;
+        }
+    };
+    report.branch = Some(worktree.branch().to_string());
+    report.worktree = Some(worktree.path().to_path_buf());
+
+    let mut task_config = config.clone();
+    task_config.cwd = worktree.cwd();
+    if let Some(model) = task.model.clone().or_else(|| spec.model.clone()) {
+        task_config.model = Some(model);
+    }
+    if let Err(err) = drive_session(
+        thread_manager,
+        task_config,
+        task,
+        spec.budget_for(task),
+        &mut report,
+        on_progress,
+    )
+    .await
+    {
+        report.status = TaskStatus::Failed;
+        report.error = Some(err);
+    }
+
+    let subject = task.prompt.lines().next().unwrap_or_default();
+    let mut message = format!("{}: {subject}", task.id);
+    if let Some(thread_id) = &report.thread_id {
+        message = attribution::append_trailers(
+            &message,
+            &attribution::commit_trailers(&config.attribution, thread_id),
+        );
+    }
+    match worktree.commit(&message).await {
+        Ok(commit) => report.commit = commit,
+        Err(err) => {
+            report.status = TaskStatus::Failed;
+            report.error = Some(format!("failed to commit the task's changes: {err}"));
+        }
+    }
+    report.duration = started.elapsed();
+    report
+}
+
+/// Run `task` as one turn of a new session, filling in `report`.
+async fn drive_session(
+    thread_manager: &ThreadManager,
+    config: Config,
+    task: &BatchTask,
+    token_budget: Option<i64>,
+    report: &mut TaskReport,
+    on_progress: &(impl Fn(&str, TaskProgress) + Sync),
+) -> Result<(), String> {
+    let new_thread = thread_manager
+        .start_thread(config)
+        .await
+        .map_err(|err| format!("failed to start a session: {err}"))?;
+    let thread = new_thread.thread;
+    report.thread_id = Some(new_thread.thread_id);
+    thread
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: task.prompt.clone(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await
+        .map_err(|err| format!("failed to submit the prompt: {err}"))?;
+
+    let mut over_budget = false;
+    let result = loop {
+        let event = match thread.next_event().await {
+            Ok(event) => event,
+            Err(err) => break Err(format!("session ended unexpectedly: {err}")),
+        };
+        match event.msg {
+            EventMsg::TokenCount(ev) => {
+                let Some(info) = ev.info else {
+                    continue;
+                };
+                report.tokens = info.total_token_usage.total_tokens;
+                on_progress(&task.id, TaskProgress::Tokens(report.tokens));
+                if !over_budget && token_budget.is_some_and(|budget| report.tokens >= budget) {
+                    over_budget = true;
+                    if let Err(err) = thread.submit(Op::Interrupt).await {
+                        break Err(format!("failed to stop the task: {err}"));
+                    }
+                }
+            }
+            EventMsg::AgentMessage(ev) => report.last_message = Some(ev.message),
+            EventMsg::ExecApprovalRequest(_) => {
+                if let Err(err) = thread
+                    .submit(Op::ExecApproval {
+                        id: event.id,
+                        decision: ReviewDecision::Denied,
+                    })
+                    .await
+                {
+                    break Err(format!("failed to decline an approval: {err}"));
+                }
+            }
+            EventMsg::ApplyPatchApprovalRequest(_) => {
+                if let Err(err) = thread
+                    .submit(Op::PatchApproval {
+                        id: event.id,
+                        decision: ReviewDecision::Denied,
+                    })
+                    .await
+                {
+                    break Err(format!("failed to decline an approval: {err}"));
+                }
+            }
+            EventMsg::ElicitationRequest(ev) => {
+                // Nobody is around to answer, as in `codex exec`.
+                if let Err(err) = thread
+                    .submit(Op::ResolveElicitation {
+                        server_name: ev.server_name,
+                        request_id: ev.id,
+                        decision: ElicitationAction::Cancel,
+                    })
+                    .await
+                {
+                    break Err(format!("failed to cancel an elicitation: {err}"));
+                }
+            }
+            EventMsg::Error(ev) => report.error = Some(ev.message),
+            EventMsg::TurnComplete(ev) => {
+                if ev.last_agent_message.is_some() {
+                    report.last_message = ev.last_agent_message;
+                }
+                break Ok(());
+            }
+            EventMsg::TurnAborted(_) if over_budget => break Ok(()),
+            EventMsg::TurnAborted(ev) => break Err(format!("turn aborted: {:?}", ev.reason)),
+            EventMsg::ShutdownComplete => {
+                break Err("session shut down unexpectedly".to_string());
+            }
+            _ => {}
+        }
+    };
+
+    report.status = if over_budget {
+        TaskStatus::OverBudget
+    } else if report.error.is_some() {
+        TaskStatus::Failed
+    } else {
+        TaskStatus::Completed
+    };
+    let _ = thread.submit(Op::Shutdown).await;
+    thread_manager.remove_thread(&new_thread.thread_id).await;
+    result
+}
+
+mod duration_secs {
+    use std::time::Duration;
+
+    use serde::Serializer;
+
+    pub(super) fn serialize<S: Serializer>(
+        duration: &Duration,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(duration.as_secs_f64())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_and_validates_batch_files() {
+        let spec = BatchSpec::from_yaml(
+            r#"
+concurrency: 2
+token_budget: 200000
+tasks:
+  - id: fix-flaky-test
+    prompt: Make the flaky parser test deterministic.
+  - id: docs
+    prompt: Document the new config option.
+    model: gpt-5.1-codex-mini
+    token_budget: 50000
+"#,
+        )
+        .unwrap();
+        assert_eq!(spec.concurrency, Some(2));
+        assert_eq!(spec.tasks.len(), 2);
+        assert_eq!(spec.budget_for(&spec.tasks[0]), Some(200000));
+        assert_eq!(spec.budget_for(&spec.tasks[1]), Some(50000));
+        assert_eq!(spec.tasks[1].model.as_deref(), Some("gpt-5.1-codex-mini"));
+
+        assert_eq!(
+            BatchSpec::from_yaml("tasks: []"),
+            Err("batch file has no tasks".to_string())
+        );
+        assert_eq!(
+            BatchSpec::from_yaml("tasks:\n  - {id: a, prompt: x}\n  - {id: a, prompt: y}"),
+            Err("task id `a` is used more than once".to_string())
+        );
+        assert_eq!(
+            BatchSpec::from_yaml("tasks:\n  - {id: a b, prompt: x}"),
+            Err("task id `a b` may only use letters, digits, `-` and `_`".to_string())
+        );
+        assert!(BatchSpec::from_yaml("tasks:\n  - {id: a, prompt: x, retries: 3}").is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::OnceLock;
@@ -9,12 +10,12 @@ use crate::api_bridge::map_api_error;
 use crate::auth::UnauthorizedRecovery;
 use crate::turn_metadata::build_turn_metadata_header;
 use codex_api::AggregateStreamExt;
+use codex_api::AnthropicClient as ApiAnthropicClient;
 use codex_api::ChatClient as ApiChatClient;
 use codex_api::CompactClient as ApiCompactClient;
 use codex_api::CompactionInput as ApiCompactionInput;
 use codex_api::Prompt as ApiPrompt;
 use codex_api::RequestTelemetry;
-use codex_api::ReqwestTransport;
 use codex_api::ResponseAppendWsRequest;
 use codex_api::ResponseCreateWsRequest;
 use codex_api::ResponseStream as ApiResponseStream;
@@ -44,10 +45,13 @@ use eventsource_stream::Event;
 use eventsource_stream::EventStreamError;
 use futures::StreamExt;
 use http::HeaderMap as ApiHeaderMap;
+use http::HeaderName;
 use http::HeaderValue;
 use http::StatusCode as HttpStatusCode;
 use reqwest::StatusCode;
 use serde_json::Value;
+use sha2::Digest;
+use sha2::Sha256;
 use std::time::Duration;
 use tokio::sync::mpsc;
 use tokio_tungstenite::tungstenite::Error;
@@ -61,7 +65,7 @@ use crate::client_common::Prompt;
 use crate::client_common::ResponseEvent;
 use crate::client_common::ResponseStream;
 use crate::config::Config;
-use crate::default_client::build_reqwest_client;
+use crate::config::types::PromptCacheKeyScope;
 use crate::error::CodexErr;
 use crate::error::Result;
 use crate::features::FEATURES;
@@ -69,6 +73,8 @@ use crate::features::Feature;
 use crate::flags::CODEX_RS_SSE_FIXTURE;
 use crate::model_provider_info::ModelProviderInfo;
 use crate::model_provider_info::WireApi;
+use crate::redaction::Redactor;
+use crate::tools::spec::create_tools_json_for_anthropic_api;
 use crate::tools::spec::create_tools_json_for_chat_completions_api;
 use crate::tools::spec::create_tools_json_for_responses_api;
 use crate::transport_manager::TransportManager;
@@ -96,6 +102,7 @@ struct ModelClientState {
     session_source: SessionSource,
     transport_manager: TransportManager,
     turn_metadata_cache: Arc<RwLock<TurnMetadataCache>>,
+    redactor: Option<Redactor>,
 }
 
 #[derive(Debug, Clone)]
@@ -119,6 +126,8 @@ pub struct ModelClientSession {
     /// keep sending it unchanged between turn requests (e.g., for retries, incremental
     /// appends, or continuation requests), and must not send it between different turns.
     turn_state: Arc<OnceLock<String>>,
+    /// Responses continued after their stream dropped mid-output this turn.
+    stream_continuations: u64,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -135,6 +144,7 @@ impl ModelClient {
         session_source: SessionSource,
         transport_manager: TransportManager,
     ) -> Self {
+        let redactor = Redactor::from_config(&config.redaction);
         Self {
             state: Arc::new(ModelClientState {
                 config,
@@ -148,10 +158,38 @@ impl ModelClient {
                 session_source,
                 transport_manager,
                 turn_metadata_cache: Arc::new(RwLock::new(TurnMetadataCache::default())),
+                redactor,
             }),
         }
     }
 
+    /// Returns a client for the same conversation that sends requests to
+    /// `provider` for `model_info` instead.
+    pub(crate) fn with_provider(
+        &self,
+        config: Arc<Config>,
+        provider: ModelProviderInfo,
+        model_info: ModelInfo,
+    ) -> Self {
+        let state = &self.state;
+        let otel_manager = state
+            .otel_manager
+            .clone()
+            .with_model(model_info.slug.as_str(), model_info.slug.as_str());
+        Self::new(
+            config,
+            state.auth_manager.clone(),
+            model_info,
+            otel_manager,
+            provider,
+            state.effort,
+            state.summary,
+            state.conversation_id,
+            state.session_source.clone(),
+            state.transport_manager.clone(),
+        )
+    }
+
     pub fn new_session(&self, turn_metadata_cwd: Option<PathBuf>) -> ModelClientSession {
         self.prewarm_turn_metadata_header(turn_metadata_cwd);
         ModelClientSession {
@@ -160,6 +198,7 @@ impl ModelClient {
             websocket_last_items: Vec::new(),
             transport_manager: self.state.transport_manager.clone(),
             turn_state: Arc::new(OnceLock::new()),
+            stream_continuations: 0,
         }
     }
 
@@ -270,15 +309,19 @@ impl ModelClient {
             .provider
             .to_api_provider(auth.as_ref().map(CodexAuth::internal_auth_mode))?;
         let api_auth = auth_provider_from_auth(auth.clone(), &self.state.provider)?;
-        let transport = ReqwestTransport::new(build_reqwest_client());
+        let transport = self.state.transport_manager.http_transport();
         let request_telemetry = self.build_request_telemetry();
         let client = ApiCompactClient::new(transport, api_provider, api_auth)
             .with_telemetry(Some(request_telemetry));
 
         let instructions = prompt.base_instructions.text.clone();
+        let mut input = prompt.input.clone();
+        if let Some(redactor) = &self.state.redactor {
+            redactor.redact_items(&mut input);
+        }
         let payload = ApiCompactionInput {
             model: &self.state.model_info.slug,
-            input: &prompt.input,
+            input: &input,
             instructions: &instructions,
         };
 
@@ -310,8 +353,35 @@ impl ModelClientSession {
             .and_then(|cache| cache.header.clone())
     }
 
-    /// Streams a single model turn using either the Responses or Chat
-    /// Completions wire API, depending on the configured provider.
+    /// Key that routes this session's requests to the provider's prompt cache.
+    fn prompt_cache_key(&self) -> Option<String> {
+        prompt_cache_key(
+            self.state.config.prompt_cache_key,
+            &self.state.conversation_id,
+            &self.state.config.cwd,
+            &self.state.model_info.slug,
+        )
+    }
+
+    /// The API provider for one request, with the prompt cache key header when
+    /// the provider reads the key from one.
+    fn api_provider(&self, auth: Option<&CodexAuth>) -> Result<codex_api::Provider> {
+        let mut api_provider = self
+            .state
+            .provider
+            .to_api_provider(auth.map(CodexAuth::internal_auth_mode))?;
+        if let Some(header) = &self.state.provider.prompt_cache_key_header
+            && let Some(key) = self.prompt_cache_key()
+            && let (Ok(name), Ok(value)) =
+                (HeaderName::try_from(header), HeaderValue::try_from(key))
+        {
+            api_provider.headers.insert(name, value);
+        }
+        Ok(api_provider)
+    }
+
+    /// Streams a single model turn using the Responses, Chat Completions, or
+    /// Anthropic Messages wire API, depending on the configured provider.
     ///
     /// For Chat providers, the underlying stream is optionally aggregated
     /// based on the `show_raw_agent_reasoning` flag in the config.
@@ -343,7 +413,26 @@ impl ModelClientSession {
                     ))
                 }
             }
+            WireApi::Anthropic => {
+                let api_stream = self.stream_anthropic_messages(prompt).await?;
+                Ok(map_response_stream(
+                    api_stream,
+                    self.state.otel_manager.clone(),
+                ))
+            }
+        }
+    }
+
+    /// Claims one follow-up request after a stream dropped once the model had
+    /// produced output, returning its number. A turn gets up to the
+    /// provider's `stream_max_retries` of them, so a connection that keeps
+    /// dropping ends the turn instead of reconnecting forever.
+    pub(crate) fn try_continue_dropped_stream(&mut self) -> Option<u64> {
+        if self.stream_continuations >= self.state.provider.stream_max_retries() {
+            return None;
         }
+        self.stream_continuations += 1;
+        Some(self.stream_continuations)
     }
 
     pub(crate) fn try_switch_fallback_transport(&mut self) -> bool {
@@ -377,7 +466,12 @@ impl ModelClientSession {
     fn build_responses_request(&self, prompt: &Prompt) -> Result<ApiPrompt> {
         let instructions = prompt.base_instructions.text.clone();
         let tools_json: Vec<Value> = create_tools_json_for_responses_api(&prompt.tools)?;
-        Ok(build_api_prompt(prompt, instructions, tools_json))
+        Ok(build_api_prompt(
+            prompt,
+            instructions,
+            tools_json,
+            self.state.redactor.as_ref(),
+        ))
     }
 
     fn build_responses_options(
@@ -429,7 +523,7 @@ impl ModelClientSession {
         ApiResponsesOptions {
             reasoning,
             include,
-            prompt_cache_key: Some(conversation_id.clone()),
+            prompt_cache_key: self.prompt_cache_key(),
             text,
             store_override: None,
             conversation_id: Some(conversation_id),
@@ -558,7 +652,12 @@ impl ModelClientSession {
         let auth_manager = self.state.auth_manager.clone();
         let instructions = prompt.base_instructions.text.clone();
         let tools_json = create_tools_json_for_chat_completions_api(&prompt.tools)?;
-        let api_prompt = build_api_prompt(prompt, instructions, tools_json);
+        let api_prompt = build_api_prompt(
+            prompt,
+            instructions,
+            tools_json,
+            self.state.redactor.as_ref(),
+        );
         let conversation_id = self.state.conversation_id.to_string();
         let session_source = self.state.session_source.clone();
 
@@ -570,12 +669,9 @@ impl ModelClientSession {
                 Some(manager) => manager.auth().await,
                 None => None,
             };
-            let api_provider = self
-                .state
-                .provider
-                .to_api_provider(auth.as_ref().map(CodexAuth::internal_auth_mode))?;
+            let api_provider = self.api_provider(auth.as_ref())?;
             let api_auth = auth_provider_from_auth(auth.clone(), &self.state.provider)?;
-            let transport = ReqwestTransport::new(build_reqwest_client());
+            let transport = self.state.transport_manager.http_transport();
             let (request_telemetry, sse_telemetry) = self.build_streaming_telemetry();
             let client = ApiChatClient::new(transport, api_provider, api_auth)
                 .with_telemetry(Some(request_telemetry), Some(sse_telemetry));
@@ -602,6 +698,67 @@ impl ModelClientSession {
         }
     }
 
+    /// Streams a turn via the Anthropic Messages API.
+    async fn stream_anthropic_messages(&self, prompt: &Prompt) -> Result<ApiResponseStream> {
+        if prompt.output_schema.is_some() {
+            return Err(CodexErr::UnsupportedOperation(
+                "output_schema is not supported for the Anthropic Messages API".to_string(),
+            ));
+        }
+
+        let auth_manager = self.state.auth_manager.clone();
+        let instructions = prompt.base_instructions.text.clone();
+        let tools_json = create_tools_json_for_anthropic_api(&prompt.tools)?;
+        let api_prompt = build_api_prompt(
+            prompt,
+            instructions,
+            tools_json,
+            self.state.redactor.as_ref(),
+        );
+        let conversation_id = self.state.conversation_id.to_string();
+        let session_source = self.state.session_source.clone();
+        // Anthropic caches only up to explicit breakpoints, so mark them
+        // unless the user opted out of prompt caching.
+        let prompt_caching = self.prompt_cache_key().is_some();
+
+        let mut auth_recovery = auth_manager
+            .as_ref()
+            .map(super::auth::AuthManager::unauthorized_recovery);
+        loop {
+            let auth = match auth_manager.as_ref() {
+                Some(manager) => manager.auth().await,
+                None => None,
+            };
+            let api_provider = self.api_provider(auth.as_ref())?;
+            let api_auth = auth_provider_from_auth(auth.clone(), &self.state.provider)?;
+            let transport = self.state.transport_manager.http_transport();
+            let (request_telemetry, sse_telemetry) = self.build_streaming_telemetry();
+            let client = ApiAnthropicClient::new(transport, api_provider, api_auth)
+                .with_telemetry(Some(request_telemetry), Some(sse_telemetry));
+
+            let stream_result = client
+                .stream_prompt(
+                    &self.state.model_info.slug,
+                    &api_prompt,
+                    Some(conversation_id.clone()),
+                    Some(session_source.clone()),
+                    prompt_caching,
+                )
+                .await;
+
+            match stream_result {
+                Ok(stream) => return Ok(stream),
+                Err(ApiError::Transport(TransportError::Http { status, .. }))
+                    if status == StatusCode::UNAUTHORIZED =>
+                {
+                    handle_unauthorized(status, &mut auth_recovery).await?;
+                    continue;
+                }
+                Err(err) => return Err(map_api_error(err)),
+            }
+        }
+    }
+
     /// Streams a turn via the OpenAI Responses API.
     ///
     /// Handles SSE fixtures, reasoning summaries, verbosity, and the
@@ -626,12 +783,9 @@ impl ModelClientSession {
                 Some(manager) => manager.auth().await,
                 None => None,
             };
-            let api_provider = self
-                .state
-                .provider
-                .to_api_provider(auth.as_ref().map(CodexAuth::internal_auth_mode))?;
+            let api_provider = self.api_provider(auth.as_ref())?;
             let api_auth = auth_provider_from_auth(auth.clone(), &self.state.provider)?;
-            let transport = ReqwestTransport::new(build_reqwest_client());
+            let transport = self.state.transport_manager.http_transport();
             let (request_telemetry, sse_telemetry) = self.build_streaming_telemetry();
             let compression = self.responses_request_compression(auth.as_ref());
 
@@ -672,10 +826,7 @@ impl ModelClientSession {
                 Some(manager) => manager.auth().await,
                 None => None,
             };
-            let api_provider = self
-                .state
-                .provider
-                .to_api_provider(auth.as_ref().map(CodexAuth::internal_auth_mode))?;
+            let api_provider = self.api_provider(auth.as_ref())?;
             let api_auth = auth_provider_from_auth(auth.clone(), &self.state.provider)?;
             let compression = self.responses_request_compression(auth.as_ref());
 
@@ -734,11 +885,25 @@ impl ModelClient {
     }
 }
 
-/// Adapts the core `Prompt` type into the `codex-api` payload shape.
-fn build_api_prompt(prompt: &Prompt, instructions: String, tools_json: Vec<Value>) -> ApiPrompt {
+/// Adapts the core `Prompt` type into the `codex-api` payload shape, masking
+/// secrets when redaction is enabled.
+fn build_api_prompt(
+    prompt: &Prompt,
+    instructions: String,
+    tools_json: Vec<Value>,
+    redactor: Option<&Redactor>,
+) -> ApiPrompt {
+    let mut input = prompt.get_formatted_input();
+    let instructions = match redactor {
+        Some(redactor) => {
+            redactor.redact_items(&mut input);
+            redactor.redact(&instructions).into_owned()
+        }
+        None => instructions,
+    };
     ApiPrompt {
         instructions,
-        input: prompt.get_formatted_input(),
+        input,
         tools: tools_json,
         parallel_tool_calls: prompt.parallel_tool_calls,
         output_schema: prompt.output_schema.clone(),
@@ -768,6 +933,30 @@ fn experimental_feature_headers(config: &Config) -> ApiHeaderMap {
     headers
 }
 
+/// Derives the prompt cache key for `scope`. Workspace keys hash the working
+/// directory and model, so they are stable across sessions without exposing
+/// the path.
+fn prompt_cache_key(
+    scope: PromptCacheKeyScope,
+    conversation_id: &ThreadId,
+    cwd: &Path,
+    model: &str,
+) -> Option<String> {
+    match scope {
+        PromptCacheKeyScope::Session => Some(conversation_id.to_string()),
+        PromptCacheKeyScope::Workspace => {
+            let mut hasher = Sha256::new();
+            hasher.update(cwd.to_string_lossy().as_bytes());
+            hasher.update([0]);
+            hasher.update(model.as_bytes());
+            let digest = format!("{:x}", hasher.finalize());
+            let truncated = digest.get(..32).unwrap_or(&digest);
+            Some(format!("workspace-{truncated}"))
+        }
+        PromptCacheKeyScope::Disabled => None,
+    }
+}
+
 fn build_responses_headers(
     config: &Config,
     turn_state: Option<&Arc<OnceLock<String>>>,
@@ -0,0 +1,747 @@
+//! Local semantic code search.
+//!
+//! `codex index build` splits the repository's text files into chunks (one per
+//! top-level definition where the language is understood, fixed windows of
+//! lines otherwise), embeds each chunk with the model configured in
+//! `[code_index]`, and stores the vectors under `.codex/index/` at the
+//! repository root.
+//!
+//! Embeddings come from an OpenAI-compatible `/embeddings` endpoint, by default
+//! a local Ollama server running `nomic-embed-text`, so the code never leaves
+//! the machine unless the user points `base_url` elsewhere. The `search_code`
+//! tool embeds the query with the same model and ranks chunks by cosine
+//! similarity, which finds code by what it does rather than the words it uses.
+//!
+//! Searches keep the index they loaded in a [`CodeIndexCache`] and rescan the
+//! repository at most once every few seconds, re-embedding only files whose
+//! size or modification time changed. Changing the model rebuilds the index.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::UNIX_EPOCH;
+
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::sync::Mutex;
+
+use crate::config::types::CodeIndexConfig;
+use crate::default_client::create_client;
+use crate::repo_map::list_files;
+use crate::syntax_outline::outline;
+
+/// Index location relative to the repository root.
+pub const CODE_INDEX_DIR: &str = ".codex/index";
+const INDEX_FILE: &str = "embeddings.json";
+/// Bumped whenever chunking or the stored format change, forcing a full
+/// rebuild.
+const INDEX_VERSION: u32 = 2;
+/// How long a search trusts the index it last checked against the
+/// repository, so a burst of searches does not rescan it each time.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+/// Local models on a CPU can take a while over a full batch.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+const EMBED_BATCH_SIZE: usize = 32;
+/// Longer chunk text is cut before embedding to stay within the context of
+/// small embedding models.
+const MAX_EMBED_CHARS: usize = 6000;
+/// Larger files are usually generated or data, and are not indexed.
+const MAX_FILE_BYTES: u64 = 512 * 1024;
+/// Chunk size for files without a syntax outline, and for long definitions.
+const WINDOW_LINES: usize = 60;
+const MAX_SNIPPET_LINES: usize = 30;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct IndexReport {
+    /// Files (re)indexed because they were new or changed.
+    pub indexed_files: usize,
+    /// Unchanged files whose embeddings were kept.
+    pub reused_files: usize,
+    /// Files dropped because they no longer exist or are no longer indexable.
+    pub removed_files: usize,
+    pub chunks: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchHit {
+    /// Relative to the repository root.
+    pub path: PathBuf,
+    /// 1-indexed, inclusive.
+    pub start_line: usize,
+    pub end_line: usize,
+    pub score: f32,
+    /// The chunk's current lines, numbered `L<n>: `, cut at a few dozen lines.
+    pub snippet: String,
+}
+
+/// Client for the embeddings endpoint configured in `[code_index]`.
+#[derive(Debug, Clone)]
+pub struct Embedder {
+    endpoint: String,
+    model: String,
+    api_key_env: Option<String>,
+}
+
+#[derive(Serialize)]
+struct EmbeddingsRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+#[derive(Deserialize)]
+struct EmbeddingsResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingData {
+    #[serde(default)]
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+impl Embedder {
+    pub fn new(config: &CodeIndexConfig) -> Self {
+        Self {
+            endpoint: format!("{}/embeddings", config.base_url.trim_end_matches('/')),
+            model: config.model.clone(),
+            api_key_env: config.api_key_env.clone(),
+        }
+    }
+
+    /// Unit-length embeddings of `inputs`, in order, sent in batches.
+    async fn embed(&self, inputs: &[String]) -> io::Result<Vec<Vec<f32>>> {
+        let mut vectors = Vec::with_capacity(inputs.len());
+        for batch in inputs.chunks(EMBED_BATCH_SIZE) {
+            vectors.extend(self.embed_batch(batch).await?);
+        }
+        Ok(vectors)
+    }
+
+    async fn embed_batch(&self, inputs: &[String]) -> io::Result<Vec<Vec<f32>>> {
+        let mut request = create_client()
+            .post(&self.endpoint)
+            .json(&EmbeddingsRequest {
+                model: &self.model,
+                input: inputs,
+            })
+            .timeout(REQUEST_TIMEOUT);
+        if let Some(env_var) = &self.api_key_env {
+            let api_key = std::env::var(env_var).map_err(|_| {
+                io::Error::other(format!(
+                    "{env_var} is not set; [code_index] api_key_env names it"
+                ))
+            })?;
+            request = request.bearer_auth(api_key);
+        }
+        let response = request.send().await.map_err(|err| {
+            io::Error::other(format!(
+                "could not reach the embedding endpoint {}: {err}; start a local server (for example `ollama pull {}` and `ollama serve`) or set base_url under [code_index]",
+                self.endpoint, self.model
+            ))
+        })?;
+        let status = response.status();
+        let body = response.text().await.map_err(io::Error::other)?;
+        if !status.is_success() {
+            return Err(io::Error::other(format!(
+                "embedding endpoint returned {status}: {}",
+                body.trim()
+            )));
+        }
+        let mut data = serde_json::from_str::<EmbeddingsResponse>(&body)
+            .map_err(|err| io::Error::other(format!("unexpected embeddings response: {err}")))?
+            .data;
+        if data.len() != inputs.len() {
+            return Err(io::Error::other(format!(
+                "embedding endpoint returned {} vectors for {} inputs",
+                data.len(),
+                inputs.len()
+            )));
+        }
+        data.sort_by_key(|item| item.index);
+        Ok(data
+            .into_iter()
+            .map(|item| normalize(item.embedding))
+            .collect())
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StoredIndex {
+    version: u32,
+    /// Embedding model the vectors came from.
+    #[serde(default)]
+    model: String,
+    /// Keyed by path relative to the repository root.
+    files: BTreeMap<String, IndexedFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexedFile {
+    modified_ms: u64,
+    len: u64,
+    chunks: Vec<Chunk>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Chunk {
+    start_line: usize,
+    end_line: usize,
+    /// Unit-length embedding quantized to `i8` to keep the index small.
+    vector: Vec<i8>,
+}
+
+/// Where the index for `repo_root` is stored.
+pub fn index_path(repo_root: &Path) -> PathBuf {
+    repo_root.join(CODE_INDEX_DIR).join(INDEX_FILE)
+}
+
+/// Build the index for `repo_root`, or bring an existing one up to date.
+pub async fn build_index(embedder: &Embedder, repo_root: &Path) -> io::Result<IndexReport> {
+    let previous = load_index(repo_root).await?.unwrap_or_default();
+    let (index, report) = refresh(embedder, repo_root, previous).await?;
+    save_index(repo_root, &index).await?;
+    Ok(report)
+}
+
+/// Delete the index. Returns whether there was one.
+pub fn clear_index(repo_root: &Path) -> io::Result<bool> {
+    match std::fs::remove_dir_all(repo_root.join(CODE_INDEX_DIR)) {
+        Ok(()) => Ok(true),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+/// Indexes loaded for searches, kept between them.
+#[derive(Debug)]
+pub struct CodeIndexCache {
+    loaded: Mutex<HashMap<PathBuf, LoadedIndex>>,
+    refresh_interval: Duration,
+}
+
+#[derive(Debug)]
+struct LoadedIndex {
+    index: StoredIndex,
+    checked_at: Instant,
+}
+
+impl Default for CodeIndexCache {
+    fn default() -> Self {
+        Self {
+            loaded: Mutex::default(),
+            refresh_interval: REFRESH_INTERVAL,
+        }
+    }
+}
+
+impl CodeIndexCache {
+    /// The `limit` chunks closest in meaning to `query`, best first. `None`
+    /// when no index has been built for `repo_root`.
+    ///
+    /// The index is brought up to date first unless it was checked within
+    /// the last few seconds; the update re-embeds only changed files and is
+    /// written back only when something changed.
+    pub async fn search(
+        &self,
+        embedder: &Embedder,
+        repo_root: &Path,
+        query: &str,
+        limit: usize,
+    ) -> io::Result<Option<Vec<SearchHit>>> {
+        let mut loaded = self.loaded.lock().await;
+        let stale = loaded
+            .get(repo_root)
+            .is_none_or(|entry| entry.checked_at.elapsed() >= self.refresh_interval);
+        if stale {
+            // Loading from disk also notices `codex index clear` and a
+            // rebuild by another process.
+            let Some(previous) = load_index(repo_root).await? else {
+                loaded.remove(repo_root);
+                return Ok(None);
+            };
+            let (index, report) = refresh(embedder, repo_root, previous).await?;
+            if report.indexed_files > 0 || report.removed_files > 0 {
+                save_index(repo_root, &index).await?;
+            }
+            loaded.insert(
+                repo_root.to_path_buf(),
+                LoadedIndex {
+                    index,
+                    checked_at: Instant::now(),
+                },
+            );
+        }
+        let Some(entry) = loaded.get(repo_root) else {
+            return Ok(None);
+        };
+        let query = embedder
+            .embed(&[query.to_string()])
+            .await?
+            .pop()
+            .unwrap_or_default();
+        let ranked = rank(&entry.index, &query, limit);
+        drop(loaded);
+
+        let mut sources: HashMap<String, String> = HashMap::new();
+        let mut hits = Vec::with_capacity(ranked.len());
+        for (score, path, start_line, end_line) in ranked {
+            if !sources.contains_key(&path) {
+                let source = tokio::fs::read_to_string(repo_root.join(&path))
+                    .await
+                    .unwrap_or_default();
+                sources.insert(path.clone(), source);
+            }
+            hits.push(SearchHit {
+                snippet: snippet(&sources[&path], start_line, end_line),
+                path: PathBuf::from(path),
+                start_line,
+                end_line,
+                score,
+            });
+        }
+        Ok(Some(hits))
+    }
+}
+
+/// Score, path, and line range of the `limit` chunks closest to `query`.
+fn rank(index: &StoredIndex, query: &[f32], limit: usize) -> Vec<(f32, String, usize, usize)> {
+    let mut scored: Vec<(f32, &String, &Chunk)> = index
+        .files
+        .iter()
+        .flat_map(|(path, file)| file.chunks.iter().map(move |chunk| (path, chunk)))
+        .map(|(path, chunk)| (cosine(query, &chunk.vector), path, chunk))
+        .collect();
+    scored.sort_by(|a, b| {
+        b.0.total_cmp(&a.0)
+            .then_with(|| a.1.cmp(b.1))
+            .then_with(|| a.2.start_line.cmp(&b.2.start_line))
+    });
+    scored
+        .into_iter()
+        .take(limit)
+        .map(|(score, path, chunk)| (score, path.clone(), chunk.start_line, chunk.end_line))
+        .collect()
+}
+
+/// Files in the repository compared with a stored index.
+#[derive(Debug, Default)]
+struct Scan {
+    /// Unchanged files, with their stored chunks.
+    unchanged: BTreeMap<String, IndexedFile>,
+    changed: Vec<ChangedFile>,
+    /// Text to embed for every chunk of the changed files, in order.
+    texts: Vec<String>,
+    report: IndexReport,
+}
+
+#[derive(Debug)]
+struct ChangedFile {
+    key: String,
+    modified_ms: u64,
+    len: u64,
+    ranges: Vec<(usize, usize)>,
+}
+
+async fn refresh(
+    embedder: &Embedder,
+    repo_root: &Path,
+    previous: StoredIndex,
+) -> io::Result<(StoredIndex, IndexReport)> {
+    let previous = if previous.version == INDEX_VERSION && previous.model == embedder.model {
+        previous.files
+    } else {
+        BTreeMap::new()
+    };
+    let root = repo_root.to_path_buf();
+    let Scan {
+        mut unchanged,
+        changed,
+        texts,
+        mut report,
+    } = tokio::task::spawn_blocking(move || scan(&root, previous))
+        .await
+        .map_err(io::Error::other)??;
+
+    let mut vectors = embedder.embed(&texts).await?.into_iter();
+    for file in changed {
+        let chunks = file
+            .ranges
+            .into_iter()
+            .zip(&mut vectors)
+            .map(|((start_line, end_line), vector)| Chunk {
+                start_line,
+                end_line,
+                vector: vector
+                    .into_iter()
+                    .map(|value| (value * 127.0).round().clamp(-127.0, 127.0) as i8)
+                    .collect(),
+            })
+            .collect();
+        unchanged.insert(
+            file.key,
+            IndexedFile {
+                modified_ms: file.modified_ms,
+                len: file.len,
+                chunks,
+            },
+        );
+    }
+    report.chunks = unchanged.values().map(|file| file.chunks.len()).sum();
+    Ok((
+        StoredIndex {
+            version: INDEX_VERSION,
+            model: embedder.model.clone(),
+            files: unchanged,
+        },
+        report,
+    ))
+}
+
+fn scan(repo_root: &Path, mut previous: BTreeMap<String, IndexedFile>) -> io::Result<Scan> {
+    let mut scan = Scan::default();
+    for relative in list_files(repo_root)? {
+        if relative.starts_with(CODE_INDEX_DIR) {
+            continue;
+        }
+        let path = repo_root.join(&relative);
+        let Ok(metadata) = std::fs::metadata(&path) else {
+            continue;
+        };
+        if !metadata.is_file() || metadata.len() > MAX_FILE_BYTES {
+            continue;
+        }
+        let modified_ms = metadata
+            .modified()?
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX))
+            .unwrap_or_default();
+        let key = relative.to_string_lossy().into_owned();
+        match previous.remove(&key) {
+            Some(file) if file.modified_ms == modified_ms && file.len == metadata.len() => {
+                scan.report.reused_files += 1;
+                scan.unchanged.insert(key, file);
+            }
+            _ => {
+                // Binary and non-UTF-8 files are skipped.
+                let Some(source) = std::fs::read_to_string(&path)
+                    .ok()
+                    .filter(|source| !source.contains('\0'))
+                else {
+                    continue;
+                };
+                scan.report.indexed_files += 1;
+                let mut ranges = Vec::new();
+                for (range, text) in chunk_file(&relative, &source) {
+                    // The path says a lot about what a chunk is for.
+                    let text: String = format!("{key}\n{text}")
+                        .chars()
+                        .take(MAX_EMBED_CHARS)
+                        .collect();
+                    scan.texts.push(text);
+                    ranges.push(range);
+                }
+                scan.changed.push(ChangedFile {
+                    key,
+                    modified_ms,
+                    len: metadata.len(),
+                    ranges,
+                });
+            }
+        }
+    }
+    scan.report.removed_files = previous.len();
+    Ok(scan)
+}
+
+async fn load_index(repo_root: &Path) -> io::Result<Option<StoredIndex>> {
+    let contents = match tokio::fs::read_to_string(index_path(repo_root)).await {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+    // An unreadable index is rebuilt rather than reported.
+    Ok(Some(serde_json::from_str(&contents).unwrap_or_default()))
+}
+
+async fn save_index(repo_root: &Path, index: &StoredIndex) -> io::Result<()> {
+    let dir = repo_root.join(CODE_INDEX_DIR);
+    tokio::fs::create_dir_all(&dir).await?;
+    let gitignore = dir.join(".gitignore");
+    if !tokio::fs::try_exists(&gitignore).await? {
+        tokio::fs::write(gitignore, "*\n").await?;
+    }
+    let temp = dir.join(format!("{INDEX_FILE}.tmp"));
+    tokio::fs::write(&temp, serde_json::to_vec(index).map_err(io::Error::other)?).await?;
+    tokio::fs::rename(temp, index_path(repo_root)).await
+}
+
+/// Line ranges and text of the chunks of `source`: one per top-level
+/// definition where the language has a syntax outline, with the code between
+/// definitions and any overlong definition split into windows; plain windows
+/// otherwise.
+fn chunk_file(path: &Path, source: &str) -> Vec<((usize, usize), String)> {
+    let lines: Vec<&str> = source.lines().collect();
+    let mut ranges = Vec::new();
+    let mut next_line = 1;
+    let definitions = outline(path, source).unwrap_or_default();
+    for entry in definitions.iter().filter(|entry| entry.depth == 0) {
+        if entry.start_line < next_line {
+            continue;
+        }
+        push_windows(&mut ranges, next_line, entry.start_line - 1);
+        push_windows(&mut ranges, entry.start_line, entry.end_line);
+        next_line = entry.end_line + 1;
+    }
+    push_windows(&mut ranges, next_line, lines.len());
+
+    ranges
+        .into_iter()
+        .filter_map(|(start_line, end_line)| {
+            let text = lines.get(start_line - 1..end_line)?.join("\n");
+            if text.trim().is_empty() {
+                return None;
+            }
+            Some(((start_line, end_line), text))
+        })
+        .collect()
+}
+
+fn push_windows(ranges: &mut Vec<(usize, usize)>, start_line: usize, end_line: usize) {
+    let mut start = start_line;
+    while start <= end_line {
+        let end = (start + WINDOW_LINES - 1).min(end_line);
+        ranges.push((start, end));
+        start = end + 1;
+    }
+}
+
+fn normalize(mut vector: Vec<f32>) -> Vec<f32> {
+    let norm = vector.iter().map(|value| value * value).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|value| *value /= norm);
+    }
+    vector
+}
+
+fn cosine(query: &[f32], vector: &[i8]) -> f32 {
+    let (dot, norm) = query
+        .iter()
+        .zip(vector)
+        .fold((0.0f32, 0.0f32), |(dot, norm), (q, v)| {
+            let v = f32::from(*v);
+            (dot + q * v, norm + v * v)
+        });
+    if norm == 0.0 { 0.0 } else { dot / norm.sqrt() }
+}
+
+fn snippet(source: &str, start_line: usize, end_line: usize) -> String {
+    let shown_end = end_line.min(start_line + MAX_SNIPPET_LINES - 1);
+    let mut lines: Vec<String> = source
+        .lines()
+        .enumerate()
+        .skip(start_line - 1)
+        .take(shown_end + 1 - start_line)
+        .map(|(index, line)| format!("L{}: {line}", index + 1))
+        .collect();
+    if shown_end < end_line {
+        lines.push(format!(
+            "[... {} more lines to L{end_line}]",
+            end_line - shown_end
+        ));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::process::Command;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::Request;
+    use wiremock::ResponseTemplate;
+    use wiremock::matchers::method;
+    use wiremock::matchers::path;
+
+    /// Stands in for an embedding model: one dimension per topic the test
+    /// code touches, plus a constant so no vector is zero.
+    fn embed_topics(request: &Request) -> ResponseTemplate {
+        let body: serde_json::Value = serde_json::from_slice(&request.body).expect("json body");
+        let data: Vec<serde_json::Value> = body["input"]
+            .as_array()
+            .expect("input array")
+            .iter()
+            .enumerate()
+            .map(|(index, input)| {
+                let text = input.as_str().expect("string input").to_lowercase();
+                let embedding: Vec<f32> = ["config", "server", "stop"]
+                    .iter()
+                    .map(|topic| if text.contains(topic) { 1.0 } else { 0.0 })
+                    .chain([0.1])
+                    .collect();
+                serde_json::json!({ "index": index, "embedding": embedding })
+            })
+            .collect();
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({ "data": data }))
+    }
+
+    async fn embedding_requests(server: &MockServer) -> usize {
+        server.received_requests().await.unwrap_or_default().len()
+    }
+
+    #[tokio::test]
+    async fn search_ranks_by_embedding_and_reembeds_changed_files_lazily() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/embeddings"))
+            .respond_with(embed_topics)
+            .mount(&server)
+            .await;
+        let embedder = Embedder::new(&CodeIndexConfig {
+            base_url: format!("{}/v1/", server.uri()),
+            model: "test-embed".to_string(),
+            api_key_env: None,
+        });
+
+        let repo = tempfile::tempdir().expect("tempdir");
+        let root = repo.path();
+        let status = Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(root)
+            .status()
+            .expect("git init");
+        assert!(status.success());
+        std::fs::write(
+            root.join("config.rs"),
+            "fn load_config_file(path: &Path) -> Config {\n    parse_toml(read(path))\n}\n",
+        )
+        .expect("write");
+        std::fs::write(
+            root.join("server.py"),
+            "def start_http_server(port):\n    listen_on_socket(port)\n",
+        )
+        .expect("write");
+
+        let cache = CodeIndexCache {
+            refresh_interval: Duration::from_secs(3600),
+            ..Default::default()
+        };
+        assert_eq!(
+            cache
+                .search(&embedder, root, "config", 5)
+                .await
+                .expect("search"),
+            None
+        );
+        let report = build_index(&embedder, root).await.expect("build");
+        assert_eq!(
+            report,
+            IndexReport {
+                indexed_files: 2,
+                reused_files: 0,
+                removed_files: 0,
+                chunks: 2,
+            }
+        );
+        assert_eq!(
+            std::fs::read_to_string(root.join(CODE_INDEX_DIR).join(".gitignore")).expect("read"),
+            "*\n"
+        );
+
+        let hits = cache
+            .search(&embedder, root, "load the config", 5)
+            .await
+            .expect("search")
+            .expect("index exists");
+        assert_eq!(hits[0].path, PathBuf::from("config.rs"));
+        assert_eq!(
+            hits[0].snippet,
+            "L1: fn load_config_file(path: &Path) -> Config {\nL2:     parse_toml(read(path))\nL3: }"
+        );
+
+        std::fs::remove_file(root.join("config.rs")).expect("remove");
+        std::fs::write(
+            root.join("server.py"),
+            "def start_http_server(port):\n    listen_on_socket(port)\n\ndef stop_http_server():\n    pass\n",
+        )
+        .expect("write");
+        // Checked moments ago, so the loaded index is used as is.
+        let hits = cache
+            .search(&embedder, root, "stop the server", 1)
+            .await
+            .expect("search")
+            .expect("index exists");
+        assert_eq!((hits[0].start_line, hits[0].end_line), (1, 2));
+
+        let cache = CodeIndexCache {
+            refresh_interval: Duration::ZERO,
+            ..Default::default()
+        };
+        let hits = cache
+            .search(&embedder, root, "stop the server", 1)
+            .await
+            .expect("search")
+            .expect("index exists");
+        assert_eq!((hits[0].start_line, hits[0].end_line), (4, 5));
+        // The search wrote the updated index back, and an unchanged
+        // repository is not sent to the model again.
+        let requests = embedding_requests(&server).await;
+        assert_eq!(
+            build_index(&embedder, root).await.expect("build"),
+            IndexReport {
+                indexed_files: 0,
+                reused_files: 1,
+                removed_files: 0,
+                chunks: 2,
+            }
+        );
+        assert_eq!(embedding_requests(&server).await, requests);
+
+        // Another model's vectors are not comparable, so everything is
+        // embedded again.
+        let other = Embedder {
+            model: "other-embed".to_string(),
+            ..embedder.clone()
+        };
+        assert_eq!(
+            build_index(&other, root).await.expect("build"),
+            IndexReport {
+                indexed_files: 1,
+                reused_files: 0,
+                removed_files: 0,
+                chunks: 2,
+            }
+        );
+
+        assert!(clear_index(root).expect("clear"));
+        assert_eq!(
+            cache
+                .search(&embedder, root, "stop the server", 1)
+                .await
+                .expect("search"),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn unreachable_endpoint_explains_how_to_start_one() {
+        let embedder = Embedder::new(&CodeIndexConfig {
+            base_url: "http://127.0.0.1:9/v1".to_string(),
+            ..CodeIndexConfig::default()
+        });
+        let err = embedder
+            .embed(&["query".to_string()])
+            .await
+            .expect_err("nothing listens on the discard port");
+        assert!(
+            err.to_string().contains("ollama pull nomic-embed-text"),
+            "{err}"
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt::Debug;
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
@@ -16,30 +17,41 @@ use crate::agent::MAX_THREAD_SPAWN_DEPTH;
 use crate::agent::agent_status_from_event;
 use crate::analytics_client::AnalyticsEventsClient;
 use crate::analytics_client::build_track_events_context;
+use crate::attribution;
+use crate::auth::AuthMode;
 use crate::compact;
 use crate::compact::run_inline_auto_compact_task;
 use crate::compact::should_use_remote_compact_task;
 use crate::compact_remote::run_inline_remote_auto_compact_task;
 use crate::connectors;
+use crate::container::ContainerManager;
 use crate::exec_policy::ExecPolicyManager;
 use crate::features::Feature;
 use crate::features::Features;
 use crate::features::maybe_push_unstable_features_warning;
+use crate::lsp::LspManager;
 use crate::models_manager::manager::ModelsManager;
+use crate::models_manager::manager::model_served;
+use crate::output_store::SessionOutputs;
+use crate::output_store::page_for_model;
 use crate::parse_command::parse_command;
 use crate::parse_turn_item;
+use crate::remote_exec::RemoteManager;
 use crate::rollout::session_index;
+use crate::sandboxing::NetworkProxyManager;
 use crate::stream_events_utils::HandleOutputCtx;
 use crate::stream_events_utils::handle_non_tool_response_item;
 use crate::stream_events_utils::handle_output_item_done;
 use crate::stream_events_utils::last_assistant_message_from_item;
 use crate::terminal;
 use crate::transport_manager::TransportManager;
+use crate::trash::SessionTrash;
 use crate::truncate::TruncationPolicy;
 use crate::user_notification::UserNotifier;
 use crate::util::error_or_panic;
 use async_channel::Receiver;
 use async_channel::Sender;
+use codex_git::GhostCommit;
 use codex_protocol::ThreadId;
 use codex_protocol::approvals::ExecPolicyAmendment;
 use codex_protocol::config_types::ModeKind;
@@ -53,7 +65,9 @@ use codex_protocol::items::UserMessageItem;
 use codex_protocol::models::BaseInstructions;
 use codex_protocol::models::format_allow_prefixes;
 use codex_protocol::openai_models::ModelInfo;
+use codex_protocol::protocol::ContextEdit;
 use codex_protocol::protocol::FileChange;
+use codex_protocol::protocol::FileOperation;
 use codex_protocol::protocol::HasLegacyEvent;
 use codex_protocol::protocol::ItemCompletedEvent;
 use codex_protocol::protocol::ItemStartedEvent;
@@ -63,12 +77,14 @@ use codex_protocol::protocol::RolloutItem;
 use codex_protocol::protocol::SessionSource;
 use codex_protocol::protocol::SubAgentSource;
 use codex_protocol::protocol::TurnAbortReason;
+use codex_protocol::protocol::TurnBlockedEvent;
 use codex_protocol::protocol::TurnContextItem;
 use codex_protocol::protocol::TurnStartedEvent;
 use codex_protocol::request_user_input::RequestUserInputArgs;
 use codex_protocol::request_user_input::RequestUserInputResponse;
 use codex_rmcp_client::ElicitationResponse;
 use codex_rmcp_client::OAuthCredentialsStoreMode;
+use codex_rmcp_client::ToolCallProgressSender;
 use futures::future::BoxFuture;
 use futures::prelude::*;
 use futures::stream::FuturesOrdered;
@@ -108,8 +124,14 @@ use crate::config::Config;
 use crate::config::Constrained;
 use crate::config::ConstraintResult;
 use crate::config::GhostSnapshotConfig;
+use crate::config::edit::ConfigEdit;
+use crate::config::edit::ConfigEditsBuilder;
 use crate::config::resolve_web_search_mode_for_turn;
+use crate::config::types::ApprovalRuleDecision;
+use crate::config::types::ApprovalRuleToml;
+use crate::config::types::ApprovalRuleTool;
 use crate::config::types::McpServerConfig;
+use crate::config::types::ModelFallback;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::context_manager::ContextManager;
 use crate::environment_context::EnvironmentContext;
@@ -118,6 +140,8 @@ use crate::error::Result as CodexResult;
 #[cfg(test)]
 use crate::exec::StreamOutput;
 use crate::exec_policy::ExecPolicyUpdateError;
+use crate::external_edits::ExternalEditWatcher;
+use crate::external_edits::external_edits_message;
 use crate::feedback_tags;
 use crate::git_info::get_git_repo_root;
 use crate::instructions::UserInstructions;
@@ -125,6 +149,10 @@ use crate::mcp::CODEX_APPS_MCP_SERVER_NAME;
 use crate::mcp::auth::compute_auth_statuses;
 use crate::mcp::effective_mcp_servers;
 use crate::mcp::maybe_prompt_and_install_mcp_dependencies;
+use crate::mcp::mention::McpMentionInjections;
+use crate::mcp::mention::build_mcp_mention_injections;
+use crate::mcp::mention::collect_mcp_mentions;
+use crate::mcp::output_budget::mcp_output_budgets_configured;
 use crate::mcp::with_codex_apps_mcp;
 use crate::mcp_connection_manager::McpConnectionManager;
 use crate::mentions::build_connector_slug_counts;
@@ -132,6 +160,8 @@ use crate::mentions::build_skill_name_counts;
 use crate::mentions::collect_explicit_app_paths;
 use crate::mentions::collect_tool_mentions_from_messages;
 use crate::model_provider_info::CHAT_WIRE_API_DEPRECATION_SUMMARY;
+use crate::patch_undo::AppliedPatch;
+use crate::persistent_shell::PersistentShellManager;
 use crate::project_doc::get_user_instructions;
 use crate::proposed_plan_parser::ProposedPlanParser;
 use crate::proposed_plan_parser::ProposedPlanSegment;
@@ -147,7 +177,10 @@ use crate::protocol::Event;
 use crate::protocol::EventMsg;
 use crate::protocol::ExecApprovalRequestEvent;
 use crate::protocol::McpServerRefreshConfig;
+use crate::protocol::ModelFailoverEvent;
+use crate::protocol::ModelSnapshotEvent;
 use crate::protocol::Op;
+use crate::protocol::PatchHunkReport;
 use crate::protocol::PlanDeltaEvent;
 use crate::protocol::RateLimitSnapshot;
 use crate::protocol::ReasoningContentDeltaEvent;
@@ -168,10 +201,14 @@ use crate::protocol::TokenUsage;
 use crate::protocol::TokenUsageInfo;
 use crate::protocol::TurnDiffEvent;
 use crate::protocol::WarningEvent;
+use crate::redaction::Redactor;
+use crate::repo_map::RepoMap;
+use crate::repo_map::insert_repo_map;
 use crate::rollout::RolloutRecorder;
 use crate::rollout::RolloutRecorderParams;
 use crate::rollout::map_session_init_error;
 use crate::rollout::metadata;
+use crate::safety::file_change_operations;
 use crate::shell;
 use crate::shell_snapshot::ShellSnapshot;
 use crate::skills::SkillError;
@@ -195,20 +232,22 @@ use crate::tasks::SessionTask;
 use crate::tasks::SessionTaskContext;
 use crate::tools::ToolRouter;
 use crate::tools::context::SharedTurnDiffTracker;
+use crate::tools::output_diff::diff_command_output;
 use crate::tools::parallel::ToolCallRuntime;
 use crate::tools::sandboxing::ApprovalStore;
 use crate::tools::spec::ToolsConfig;
 use crate::tools::spec::ToolsConfigParams;
 use crate::turn_diff_tracker::TurnDiffTracker;
 use crate::unified_exec::UnifiedExecProcessManager;
+use crate::user_notification::TurnTokenUsage;
 use crate::user_notification::UserNotification;
-use crate::util::backoff;
 use crate::windows_sandbox::WindowsSandboxLevelExt;
 use codex_async_utils::OrCancelExt;
 use codex_otel::OtelManager;
 use codex_protocol::config_types::CollaborationMode;
 use codex_protocol::config_types::Personality;
 use codex_protocol::config_types::ReasoningSummary as ReasoningSummaryConfig;
+use codex_protocol::config_types::Verbosity;
 use codex_protocol::config_types::WindowsSandboxLevel;
 use codex_protocol::models::ContentItem;
 use codex_protocol::models::DeveloperInstructions;
@@ -382,6 +421,7 @@ impl Codex {
             provider: config.model_provider.clone(),
             collaboration_mode,
             model_reasoning_summary: config.model_reasoning_summary,
+            model_verbosity: config.model_verbosity,
             developer_instructions: config.developer_instructions.clone(),
             user_instructions,
             personality: config.personality,
@@ -544,6 +584,12 @@ impl TurnContext {
             .as_deref()
             .unwrap_or(compact::SUMMARIZATION_PROMPT)
     }
+
+    /// Whether this turn runs on the thread a run started with rather than
+    /// on a sub-agent.
+    pub(crate) fn is_primary_thread(&self) -> bool {
+        !matches!(self.client.get_session_source(), SessionSource::SubAgent(_))
+    }
 }
 
 #[derive(Clone)]
@@ -553,6 +599,7 @@ pub(crate) struct SessionConfiguration {
 
     collaboration_mode: CollaborationMode,
     model_reasoning_summary: ReasoningSummaryConfig,
+    model_verbosity: Option<Verbosity>,
 
     /// Developer instructions that supplement the base instructions.
     developer_instructions: Option<String>,
@@ -624,6 +671,9 @@ impl SessionConfiguration {
         if let Some(personality) = updates.personality {
             next_configuration.personality = Some(personality);
         }
+        if let Some(verbosity) = updates.verbosity {
+            next_configuration.model_verbosity = Some(verbosity);
+        }
         if let Some(approval_policy) = updates.approval_policy {
             next_configuration.approval_policy.set(approval_policy)?;
         }
@@ -650,6 +700,7 @@ pub(crate) struct SessionSettingsUpdate {
     pub(crate) reasoning_summary: Option<ReasoningSummaryConfig>,
     pub(crate) final_output_json_schema: Option<Option<Value>>,
     pub(crate) personality: Option<Personality>,
+    pub(crate) verbosity: Option<Verbosity>,
 }
 
 impl Session {
@@ -662,6 +713,7 @@ impl Session {
             session_configuration.collaboration_mode.reasoning_effort();
         per_turn_config.model_reasoning_summary = session_configuration.model_reasoning_summary;
         per_turn_config.personality = session_configuration.personality;
+        per_turn_config.model_verbosity = session_configuration.model_verbosity;
         per_turn_config.web_search_mode = Some(resolve_web_search_mode_for_turn(
             per_turn_config.web_search_mode,
             session_configuration.provider.is_azure_responses_endpoint(),
@@ -710,7 +762,10 @@ impl Session {
             model_info: &model_info,
             features: &per_turn_config.features,
             web_search_mode: per_turn_config.web_search_mode,
-        });
+        })
+        .with_web_search_backend(per_turn_config.web_search_backend.clone())
+        .with_tool_filter(per_turn_config.tool_filter.clone())
+        .with_mcp_output_budgets(mcp_output_budgets_configured(per_turn_config.as_ref()));
 
         TurnContext {
             sub_id,
@@ -907,6 +962,9 @@ impl Session {
             config.active_profile.clone(),
         );
 
+        let transport_manager = TransportManager::for_session(&config, conversation_id)
+            .map_err(|err| anyhow::anyhow!("failed to set up API recording or replay: {err}"))?;
+
         let mut default_shell = shell::default_user_shell();
         // Create the mutable state for the Session.
         if config.features.enabled(Feature::ShellSnapshot) {
@@ -933,11 +991,16 @@ impl Session {
             mcp_connection_manager: Arc::new(RwLock::new(McpConnectionManager::default())),
             mcp_startup_cancellation_token: Mutex::new(CancellationToken::new()),
             unified_exec_manager: UnifiedExecProcessManager::default(),
+            persistent_shell: PersistentShellManager::default(),
             analytics_events_client: AnalyticsEventsClient::new(
                 Arc::clone(&config),
                 Arc::clone(&auth_manager),
             ),
-            notifier: UserNotifier::new(config.notify.clone()),
+            notifier: UserNotifier::new(
+                config.notify.clone(),
+                config.notify_mode,
+                Redactor::from_config(&config.redaction),
+            ),
             rollout: Mutex::new(rollout_recorder),
             user_shell: Arc::new(default_shell),
             show_raw_agent_reasoning: config.show_raw_agent_reasoning,
@@ -949,7 +1012,24 @@ impl Session {
             skills_manager,
             agent_control,
             state_db: state_db_ctx.clone(),
-            transport_manager: TransportManager::new(),
+            transport_manager,
+            trash: Mutex::new(
+                config
+                    .trash
+                    .enabled
+                    .then(|| SessionTrash::new(&config.codex_home, conversation_id)),
+            ),
+            saved_outputs: Mutex::new(SessionOutputs::new(
+                &config.codex_home,
+                conversation_id,
+                config.session_encryption,
+            )),
+            lsp: LspManager::new(config.lsp.clone()),
+            container: ContainerManager::new(config.container.clone(), conversation_id),
+            remote: RemoteManager::new(config.remote.clone()),
+            network_proxy: NetworkProxyManager::default(),
+            write_protected: Arc::default(),
+            code_index: Arc::default(),
         };
 
         let sess = Arc::new(Session {
@@ -1015,6 +1095,27 @@ impl Session {
 
         // record_initial_history can emit events. We record only after the SessionConfiguredEvent is emitted.
         sess.record_initial_history(initial_history).await;
+        sess.spawn_pinned_snapshot_check(&config);
+
+        if sess.services.container.is_enabled() {
+            let sess = Arc::clone(&sess);
+            let cwd = session_configuration.cwd.clone();
+            let policy = session_configuration.sandbox_policy.get().clone();
+            tokio::spawn(async move {
+                if let Err(err) = sess.services.container.start(&cwd, &policy).await {
+                    warn!("failed to start the session container: {err}");
+                }
+            });
+        }
+        if sess.services.remote.is_enabled() {
+            let sess = Arc::clone(&sess);
+            let cwd = session_configuration.cwd.clone();
+            tokio::spawn(async move {
+                if let Err(err) = sess.services.remote.sync_up(&cwd).await {
+                    warn!("failed to sync the workspace to the remote host: {err}");
+                }
+            });
+        }
 
         Ok(sess)
     }
@@ -1052,6 +1153,45 @@ impl Session {
         state.get_total_token_usage(state.server_reasoning_included())
     }
 
+    /// Token usage reported by the provider over the whole session.
+    async fn cumulative_token_usage(&self) -> TokenUsage {
+        let state = self.state.lock().await;
+        state
+            .token_info()
+            .map(|info| info.total_token_usage)
+            .unwrap_or_default()
+    }
+
+    /// After a model switch, warn when the history no longer fits under the
+    /// new model's auto-compact limit. The next turn compacts it before
+    /// sampling.
+    async fn validate_context_window_for_model(&self, sub_id: &str, model: &str) {
+        let config = self.get_config().await;
+        let model_info = self
+            .services
+            .models_manager
+            .get_model_info(model, &config)
+            .await;
+        let (Some(context_window), Some(auto_compact_limit)) = (
+            model_info.context_window,
+            model_info.auto_compact_token_limit(),
+        ) else {
+            return;
+        };
+        let total_usage_tokens = self.get_total_token_usage().await;
+        if total_usage_tokens < auto_compact_limit {
+            return;
+        }
+        let message = format!(
+            "This conversation uses {total_usage_tokens} tokens, more than `{model}` can keep in its {context_window}-token context window. It will be compacted before the next turn."
+        );
+        self.send_event_raw(Event {
+            id: sub_id.to_string(),
+            msg: EventMsg::Warning(WarningEvent { message }),
+        })
+        .await;
+    }
+
     pub(crate) async fn get_base_instructions(&self) -> BaseInstructions {
         let state = self.state.lock().await;
         BaseInstructions {
@@ -1531,6 +1671,48 @@ impl Session {
         Ok(())
     }
 
+    /// Saves an `allow` rule for patches that only touch `globs`, so later
+    /// sessions approve them without asking. This session already caches the
+    /// approval per file.
+    pub(crate) async fn persist_always_approved_paths(
+        &self,
+        globs: &[String],
+    ) -> anyhow::Result<()> {
+        let codex_home = self
+            .state
+            .lock()
+            .await
+            .session_configuration
+            .codex_home()
+            .clone();
+        ConfigEditsBuilder::new(&codex_home)
+            .with_edits([ConfigEdit::AppendApprovalRule(ApprovalRuleToml {
+                tool: Some(ApprovalRuleTool::ApplyPatch),
+                command: None,
+                paths: Some(globs.to_vec()),
+                sandbox: None,
+                decision: ApprovalRuleDecision::Allow,
+                reason: None,
+            })])
+            .apply()
+            .await
+    }
+
+    fn notify_approval_decided(
+        &self,
+        call_id: &str,
+        kind: &'static str,
+        decision: &ReviewDecision,
+    ) {
+        self.notifier().notify(&UserNotification::ApprovalDecided {
+            thread_id: self.conversation_id.to_string(),
+            call_id: call_id.to_string(),
+            kind,
+            decision: decision.to_opaque_string(),
+            scope: decision.scope(),
+        });
+    }
+
     async fn turn_context_for_sub_id(&self, sub_id: &str) -> Option<Arc<TurnContext>> {
         let active = self.active_turn.lock().await;
         active
@@ -1583,6 +1765,32 @@ impl Session {
         cwd: PathBuf,
         reason: Option<String>,
         proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
+    ) -> ReviewDecision {
+        self.request_command_approval_with_file_operation(
+            turn_context,
+            call_id,
+            command,
+            cwd,
+            reason,
+            proposed_execpolicy_amendment,
+            None,
+        )
+        .await
+    }
+
+    /// Like [`Session::request_command_approval`], for a request that covers
+    /// a file operation (such as a read outside the workspace) rather than a
+    /// command.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn request_command_approval_with_file_operation(
+        &self,
+        turn_context: &TurnContext,
+        call_id: String,
+        command: Vec<String>,
+        cwd: PathBuf,
+        reason: Option<String>,
+        proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
+        file_operation: Option<FileOperation>,
     ) -> ReviewDecision {
         let sub_id = turn_context.sub_id.clone();
         // Add the tx_approve callback to the map before sending the request.
@@ -1611,6 +1819,7 @@ impl Session {
             reason,
             proposed_execpolicy_amendment,
             parsed_cmd,
+            file_operation,
         });
         self.send_event(turn_context, event).await;
         rx_approve.await.unwrap_or_default()
@@ -1623,6 +1832,28 @@ impl Session {
         changes: HashMap<PathBuf, FileChange>,
         reason: Option<String>,
         grant_root: Option<PathBuf>,
+    ) -> oneshot::Receiver<ReviewDecision> {
+        self.request_patch_approval_with_hunks(
+            turn_context,
+            call_id,
+            changes,
+            reason,
+            grant_root,
+            Vec::new(),
+        )
+        .await
+    }
+
+    /// Like [`Session::request_patch_approval`], with the patch's dry-run
+    /// report so the client can flag fuzzy and conflicting hunks.
+    pub async fn request_patch_approval_with_hunks(
+        &self,
+        turn_context: &TurnContext,
+        call_id: String,
+        changes: HashMap<PathBuf, FileChange>,
+        reason: Option<String>,
+        grant_root: Option<PathBuf>,
+        hunks: Vec<PatchHunkReport>,
     ) -> oneshot::Receiver<ReviewDecision> {
         let sub_id = turn_context.sub_id.clone();
         // Add the tx_approve callback to the map before sending the request.
@@ -1642,12 +1873,15 @@ impl Session {
             warn!("Overwriting existing pending approval for sub_id: {event_id}");
         }
 
+        let file_operations = file_change_operations(&changes);
         let event = EventMsg::ApplyPatchApprovalRequest(ApplyPatchApprovalRequestEvent {
             call_id,
             turn_id: turn_context.sub_id.clone(),
             changes,
             reason,
             grant_root,
+            hunks,
+            file_operations,
         });
         self.send_event(turn_context, event).await;
         rx_approve
@@ -1808,6 +2042,14 @@ impl Session {
                 RolloutItem::EventMsg(EventMsg::ThreadRolledBack(rollback)) => {
                     history.drop_last_n_user_turns(rollback.num_turns);
                 }
+                RolloutItem::EventMsg(EventMsg::ContextEdited(edited)) => match edited.edit {
+                    ContextEdit::Compact => {}
+                    ContextEdit::Clear | ContextEdit::DropTurns { .. } => {
+                        if let Ok(turns) = history.user_turns_affected_by(edited.edit) {
+                            history.drop_user_turns(turns);
+                        }
+                    }
+                },
                 _ => {}
             }
         }
@@ -1912,11 +2154,16 @@ impl Session {
             items.push(DeveloperInstructions::new(developer_instructions.to_string()).into());
         }
         // Add developer instructions from collaboration_mode if they exist and are non-empty
-        let (collaboration_mode, base_instructions) = {
+        let (collaboration_mode, base_instructions, attribution) = {
             let state = self.state.lock().await;
             (
                 state.session_configuration.collaboration_mode.clone(),
                 state.session_configuration.base_instructions.clone(),
+                state
+                    .session_configuration
+                    .original_config_do_not_use
+                    .attribution
+                    .clone(),
             )
         };
         if let Some(collab_instructions) =
@@ -1924,6 +2171,11 @@ impl Session {
         {
             items.push(collab_instructions.into());
         }
+        if let Some(attribution_instructions) =
+            attribution::developer_instructions(&attribution, &self.conversation_id)
+        {
+            items.push(DeveloperInstructions::new(attribution_instructions).into());
+        }
         if self.features.enabled(Feature::Personality)
             && let Some(personality) = turn_context.personality
         {
@@ -1972,6 +2224,99 @@ impl Session {
         state.clone_history()
     }
 
+    /// The checkpoint (ghost snapshot) taken when the current user turn started.
+    pub(crate) async fn current_turn_ghost_snapshot(&self) -> Option<GhostCommit> {
+        let state = self.state.lock().await;
+        state.history.ghost_snapshot_before_last_n_user_turns(1)
+    }
+
+    /// Items to send with the next request: the recorded history with the
+    /// pinned context inserted.
+    pub(crate) async fn prompt_input(&self) -> Vec<ResponseItem> {
+        let (history, pinned_context, repo_map) = {
+            let state = self.state.lock().await;
+            (
+                state.clone_history(),
+                state.pinned_context.clone(),
+                state.repo_map.rendered().map(str::to_string),
+            )
+        };
+        let mut input = history.for_prompt();
+        pinned_context.insert_into(&mut input);
+        if let Some(repo_map) = repo_map {
+            insert_repo_map(&mut input, repo_map);
+        }
+        input
+    }
+
+    /// Brings the repository map up to date for a new turn, re-parsing only
+    /// the files that changed since the previous one.
+    async fn refresh_repo_map(&self, turn_context: &TurnContext) {
+        if !self.enabled(Feature::RepoMap) {
+            return;
+        }
+        let mut repo_map = std::mem::take(&mut self.state.lock().await.repo_map);
+        let cwd = turn_context.cwd.clone();
+        let max_tokens = turn_context.client.config().repo_map_max_tokens;
+        let refreshed = tokio::task::spawn_blocking(move || {
+            repo_map.refresh(&cwd, max_tokens);
+            repo_map
+        })
+        .await;
+        let repo_map = refreshed.unwrap_or_else(|err| {
+            warn!("failed to refresh the repo map: {err}");
+            RepoMap::default()
+        });
+        self.state.lock().await.repo_map = repo_map;
+    }
+
+    /// Records which files changed outside the session since the previous
+    /// turn ended, if any did.
+    async fn record_external_edits(&self, turn_context: &TurnContext) {
+        if !self.enabled(Feature::ExternalEdits) {
+            return;
+        }
+        let watcher = std::mem::take(&mut self.state.lock().await.external_edits);
+        let cwd = turn_context.cwd.clone();
+        let checked = tokio::task::spawn_blocking(move || {
+            let edits = watcher.changes(&cwd);
+            let message = watcher
+                .root()
+                .and_then(|root| external_edits_message(root, &edits));
+            (watcher, message)
+        })
+        .await;
+        let (watcher, message) = checked.unwrap_or_else(|err| {
+            warn!("failed to check for external edits: {err}");
+            (ExternalEditWatcher::default(), None)
+        });
+        self.state.lock().await.external_edits = watcher;
+        if let Some(message) = message {
+            self.record_conversation_items(turn_context, &[message])
+                .await;
+        }
+    }
+
+    /// Remembers the working tree as a turn leaves it, so the next turn can
+    /// tell what changed in between.
+    pub(crate) async fn snapshot_external_edits(&self, turn_context: &TurnContext) {
+        if !self.enabled(Feature::ExternalEdits) {
+            return;
+        }
+        let cwd = turn_context.cwd.clone();
+        let snapshot = tokio::task::spawn_blocking(move || {
+            let mut watcher = ExternalEditWatcher::default();
+            watcher.snapshot(&cwd);
+            watcher
+        })
+        .await;
+        let watcher = snapshot.unwrap_or_else(|err| {
+            warn!("failed to snapshot the working tree: {err}");
+            ExternalEditWatcher::default()
+        });
+        self.state.lock().await.external_edits = watcher;
+    }
+
     pub(crate) async fn update_token_usage_info(
         &self,
         turn_context: &TurnContext,
@@ -1984,6 +2329,9 @@ impl Session {
                     token_usage,
                     turn_context.client.get_model_context_window(),
                 );
+                if let Some(budget) = &turn_context.client.config().run_budget {
+                    budget.record_usage(token_usage);
+                }
             }
         }
         self.send_token_count_event(turn_context).await;
@@ -2057,11 +2405,249 @@ impl Session {
         state.set_dependency_env(values);
     }
 
+    /// Records the output of a finished command and diffs it against the
+    /// previous run of the same command in the same cwd. Returns `None` the
+    /// first time a command runs and an empty diff when nothing changed.
+    pub(crate) async fn diff_against_previous_run(
+        &self,
+        command: &[String],
+        cwd: &Path,
+        output: &str,
+    ) -> Option<String> {
+        let previous = {
+            let mut state = self.state.lock().await;
+            state.replace_command_output(command, cwd, output.to_string())
+        }?;
+        Some(diff_command_output(&previous, output))
+    }
+
+    /// With `output_paging` enabled, saves `output` when it is over the
+    /// `policy` budget and returns it cut down to whole head and tail lines
+    /// with a pointer to the rest. Returns `None` when the caller should
+    /// truncate as usual.
+    pub(crate) async fn page_output(
+        &self,
+        output: &str,
+        policy: TruncationPolicy,
+    ) -> Option<String> {
+        if !self.enabled(Feature::OutputPaging) || output.len() <= policy.byte_budget() {
+            return None;
+        }
+        let saved = self.services.saved_outputs.lock().await.save(output);
+        match saved {
+            Ok(output_id) => Some(page_for_model(output, &output_id, policy)),
+            Err(err) => {
+                warn!("failed to save command output for paging: {err}");
+                None
+            }
+        }
+    }
+
+    /// Remembers a patch that was just applied so `/undo` can revert it.
+    pub(crate) async fn record_applied_patch(&self, patch: AppliedPatch) {
+        self.state.lock().await.push_applied_patch(patch);
+    }
+
+    /// The most recently applied patch not yet undone, removed from the
+    /// undo stack.
+    pub(crate) async fn take_last_applied_patch(&self) -> Option<AppliedPatch> {
+        self.state.lock().await.pop_applied_patch()
+    }
+
     pub(crate) async fn set_server_reasoning_included(&self, included: bool) {
         let mut state = self.state.lock().await;
         state.set_server_reasoning_included(included);
     }
 
+    /// Builds a copy of `turn_context` that sends requests to `fallback` and
+    /// announces the switch, so the rest of the turn is answered there.
+    async fn failover_turn_context(
+        &self,
+        turn_context: &TurnContext,
+        fallback: &ModelFallback,
+        err: &CodexErr,
+    ) -> Arc<TurnContext> {
+        let from_provider = turn_context.client.config().model_provider_id.clone();
+        let from_model = turn_context.client.get_model();
+        let to_model = fallback.model.clone().unwrap_or_else(|| from_model.clone());
+
+        let mut per_turn_config = (*turn_context.client.config()).clone();
+        per_turn_config.model = Some(to_model.clone());
+        per_turn_config.model_provider_id = fallback.provider_id.clone();
+        per_turn_config.model_provider = fallback.provider.clone();
+        let model_info = self
+            .services
+            .models_manager
+            .get_model_info(&to_model, &per_turn_config)
+            .await;
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &per_turn_config.features,
+            web_search_mode: turn_context.tools_config.web_search_mode,
+        })
+        .with_web_search_backend(turn_context.tools_config.web_search_backend.clone())
+        .with_tool_filter(turn_context.tools_config.tool_filter.clone())
+        .with_mcp_output_budgets(mcp_output_budgets_configured(&per_turn_config));
+        let truncation_policy = model_info.truncation_policy.into();
+        let client = turn_context.client.with_provider(
+            Arc::new(per_turn_config),
+            fallback.provider.clone(),
+            model_info,
+        );
+
+        let failover = Arc::new(TurnContext {
+            sub_id: turn_context.sub_id.clone(),
+            client,
+            cwd: turn_context.cwd.clone(),
+            developer_instructions: turn_context.developer_instructions.clone(),
+            compact_prompt: turn_context.compact_prompt.clone(),
+            user_instructions: turn_context.user_instructions.clone(),
+            collaboration_mode: turn_context.collaboration_mode.clone(),
+            personality: turn_context.personality,
+            approval_policy: turn_context.approval_policy,
+            sandbox_policy: turn_context.sandbox_policy.clone(),
+            windows_sandbox_level: turn_context.windows_sandbox_level,
+            shell_environment_policy: turn_context.shell_environment_policy.clone(),
+            tools_config,
+            ghost_snapshot: turn_context.ghost_snapshot.clone(),
+            final_output_json_schema: turn_context.final_output_json_schema.clone(),
+            codex_linux_sandbox_exe: turn_context.codex_linux_sandbox_exe.clone(),
+            tool_call_gate: Arc::clone(&turn_context.tool_call_gate),
+            truncation_policy,
+            dynamic_tools: turn_context.dynamic_tools.clone(),
+        });
+
+        warn!(
+            "model provider {from_provider} kept failing, failing over to {}: {err:#}",
+            fallback.provider_id
+        );
+        self.send_event(
+            &failover,
+            EventMsg::ModelFailover(ModelFailoverEvent {
+                from_provider,
+                from_model,
+                to_provider: fallback.provider_id.clone(),
+                to_model,
+                reason: err.to_string(),
+            }),
+        )
+        .await;
+        failover
+    }
+
+    /// Records the model snapshot the provider served for this turn in the
+    /// rollout, warning once per session when a pinned snapshot was swapped.
+    pub(crate) async fn record_served_model(
+        &self,
+        turn_context: &TurnContext,
+        served_model: String,
+    ) {
+        let requested_model = turn_context.client.get_model();
+        let pinned_snapshot = turn_context
+            .client
+            .config()
+            .model_snapshot
+            .clone()
+            .filter(|snapshot| *snapshot == requested_model && *snapshot != served_model);
+        let warn_pinned = {
+            let mut state = self.state.lock().await;
+            if !state.record_served_model(&turn_context.sub_id, &served_model) {
+                return;
+            }
+            pinned_snapshot.is_some() && state.take_pinned_snapshot_warning()
+        };
+
+        self.send_event(
+            turn_context,
+            EventMsg::ModelSnapshot(ModelSnapshotEvent {
+                requested_model,
+                served_model: served_model.clone(),
+            }),
+        )
+        .await;
+        if warn_pinned && let Some(pinned) = pinned_snapshot {
+            warn!("pinned model snapshot {pinned} was served as {served_model}");
+            let message = format!(
+                "Pinned model snapshot `{pinned}` is no longer served; the provider answered with `{served_model}`. Update `model_snapshot` to keep results reproducible."
+            );
+            self.send_event(turn_context, EventMsg::Warning(WarningEvent { message }))
+                .await;
+        }
+    }
+
+    /// Warns once when a turn finished without the provider reporting which
+    /// model served it, since the pinned `model_snapshot` cannot be checked.
+    async fn warn_if_served_model_unknown(&self, turn_context: &TurnContext) {
+        let requested_model = turn_context.client.get_model();
+        let Some(pinned) = turn_context
+            .client
+            .config()
+            .model_snapshot
+            .clone()
+            .filter(|snapshot| *snapshot == requested_model)
+        else {
+            return;
+        };
+        let warn = {
+            let mut state = self.state.lock().await;
+            !state.served_model_recorded(&turn_context.sub_id)
+                && state.take_pinned_snapshot_warning()
+        };
+        if warn {
+            let message = format!(
+                "The provider did not report which model answered, so the pinned model snapshot `{pinned}` cannot be verified."
+            );
+            self.send_event(turn_context, EventMsg::Warning(WarningEvent { message }))
+                .await;
+        }
+    }
+
+    /// Checks in the background that the provider still serves the pinned
+    /// `model_snapshot`, so a retired pin is reported before the first turn.
+    fn spawn_pinned_snapshot_check(self: &Arc<Self>, config: &Config) {
+        let Some(pinned) = config.model_snapshot.clone() else {
+            return;
+        };
+        // The ChatGPT backend does not expose the OpenAI models endpoint; the
+        // per-turn check still covers these sessions.
+        if config.model_provider.requires_openai_auth
+            && matches!(
+                self.services.auth_manager.get_internal_auth_mode(),
+                Some(AuthMode::Chatgpt)
+            )
+        {
+            return;
+        }
+        let sess = Arc::clone(self);
+        let provider = config.model_provider.clone();
+        tokio::spawn(async move {
+            match model_served(&provider, &sess.services.auth_manager, &pinned).await {
+                Ok(true) => {}
+                Ok(false) => {
+                    if !sess.state.lock().await.take_pinned_snapshot_warning() {
+                        return;
+                    }
+                    warn!(
+                        "pinned model snapshot {pinned} is not served by {}",
+                        provider.name
+                    );
+                    let message = format!(
+                        "Pinned model snapshot `{pinned}` is no longer served by {}. Update `model_snapshot` to keep results reproducible.",
+                        provider.name
+                    );
+                    sess.send_event_raw(Event {
+                        id: INITIAL_SUBMIT_ID.to_owned(),
+                        msg: EventMsg::Warning(WarningEvent { message }),
+                    })
+                    .await;
+                }
+                Err(err) => {
+                    warn!("could not check pinned model snapshot {pinned}: {err}");
+                }
+            }
+        });
+    }
+
     async fn send_token_count_event(&self, turn_context: &TurnContext) {
         let (info, rate_limits) = {
             let state = self.state.lock().await;
@@ -2221,6 +2807,27 @@ impl Session {
         }
     }
 
+    /// Takes up to `wanted` bytes from what is left of this turn's MCP output
+    /// budget of `limit` bytes. Outside a turn everything is granted.
+    pub(crate) async fn take_mcp_output_budget(&self, limit: usize, wanted: usize) -> usize {
+        let active = self.active_turn.lock().await;
+        match active.as_ref() {
+            Some(at) => {
+                let mut ts = at.turn_state.lock().await;
+                ts.take_mcp_output_bytes(limit, wanted)
+            }
+            None => wanted,
+        }
+    }
+
+    pub(crate) async fn return_mcp_output_budget(&self, unused: usize) {
+        let active = self.active_turn.lock().await;
+        if let Some(at) = active.as_ref() {
+            let mut ts = at.turn_state.lock().await;
+            ts.return_mcp_output_bytes(unused);
+        }
+    }
+
     pub async fn list_resources(
         &self,
         server: &str,
@@ -2260,17 +2867,18 @@ impl Session {
             .await
     }
 
-    pub async fn call_tool(
+    pub(crate) async fn call_tool_with_progress(
         &self,
         server: &str,
         tool: &str,
         arguments: Option<serde_json::Value>,
+        progress: Option<ToolCallProgressSender>,
     ) -> anyhow::Result<CallToolResult> {
         self.services
             .mcp_connection_manager
             .read()
             .await
-            .call_tool(server, tool, arguments)
+            .call_tool_with_progress(server, tool, arguments, progress)
             .await
     }
 
@@ -2432,6 +3040,7 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                 summary,
                 collaboration_mode,
                 personality,
+                verbosity,
             } => {
                 let collaboration_mode = if let Some(collab_mode) = collaboration_mode {
                     collab_mode
@@ -2454,6 +3063,7 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                         collaboration_mode: Some(collaboration_mode),
                         reasoning_summary: summary,
                         personality,
+                        verbosity,
                         ..Default::default()
                     },
                 )
@@ -2488,12 +3098,45 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
             Op::RefreshMcpServers { config } => {
                 handlers::refresh_mcp_servers(&sess, config).await;
             }
+            Op::McpLogin { name } => {
+                handlers::mcp_login(&sess, &config, sub.id.clone(), name).await;
+            }
+            Op::McpServerStatus => {
+                handlers::mcp_server_status(&sess, sub.id.clone()).await;
+            }
             Op::ListCustomPrompts => {
                 handlers::list_custom_prompts(&sess, sub.id.clone()).await;
             }
             Op::ListSkills { cwds, force_reload } => {
                 handlers::list_skills(&sess, sub.id.clone(), cwds, force_reload).await;
             }
+            Op::ListTrash => {
+                handlers::list_trash(&sess, sub.id.clone()).await;
+            }
+            Op::RestoreTrash { paths } => {
+                handlers::restore_trash(&sess, sub.id.clone(), paths).await;
+            }
+            Op::PurgeTrash => {
+                handlers::purge_trash(&sess, sub.id.clone()).await;
+            }
+            Op::TakeOverTerminal { process_id } => {
+                handlers::take_over_terminal(&sess, sub.id.clone(), process_id).await;
+            }
+            Op::TerminalInput { process_id, data } => {
+                handlers::terminal_input(&sess, sub.id.clone(), process_id, data).await;
+            }
+            Op::ReleaseTerminal { process_id } => {
+                handlers::release_terminal(&sess, sub.id.clone(), process_id).await;
+            }
+            Op::ListJobs => {
+                handlers::list_jobs(&sess, sub.id.clone()).await;
+            }
+            Op::StartJob { command } => {
+                handlers::start_job(&sess, sub.id.clone(), command).await;
+            }
+            Op::StopJob { job_id } => {
+                handlers::stop_job(&sess, sub.id.clone(), job_id).await;
+            }
             Op::Undo => {
                 handlers::undo(&sess, sub.id.clone()).await;
             }
@@ -2503,6 +3146,24 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
             Op::ThreadRollback { num_turns } => {
                 handlers::thread_rollback(&sess, sub.id.clone(), num_turns).await;
             }
+            Op::Rewind { num_turns } => {
+                handlers::rewind(&sess, sub.id.clone(), num_turns).await;
+            }
+            Op::PreviewContextEdit { edit } => {
+                handlers::preview_context_edit(&sess, sub.id.clone(), edit).await;
+            }
+            Op::ApplyContextEdit { edit } => {
+                handlers::apply_context_edit(&sess, sub.id.clone(), edit).await;
+            }
+            Op::Pin { target } => {
+                handlers::pin(&sess, sub.id.clone(), target).await;
+            }
+            Op::Unpin { id } => {
+                handlers::unpin(&sess, sub.id.clone(), id).await;
+            }
+            Op::ListPins => {
+                handlers::send_pinned_context(&sess, sub.id.clone()).await;
+            }
             Op::SetThreadName { name } => {
                 handlers::set_thread_name(&sess, sub.id.clone(), name).await;
             }
@@ -2541,28 +3202,48 @@ mod handlers {
     use crate::codex::Session;
     use crate::codex::SessionSettingsUpdate;
     use crate::codex::TurnContext;
+    use crate::codex::get_last_assistant_message_from_turn;
 
     use crate::codex::spawn_review_thread;
     use crate::config::Config;
+    use crate::features::Feature;
 
+    use crate::mcp::auth::McpOAuthLoginSupport;
     use crate::mcp::auth::compute_auth_statuses;
+    use crate::mcp::auth::oauth_login_support;
     use crate::mcp::collect_mcp_snapshot_from_manager;
     use crate::mcp::effective_mcp_servers;
     use crate::review_prompts::resolve_review_request;
+    use crate::rollout::encryption::session_cipher;
     use crate::rollout::session_index;
+    use crate::rollout::session_summary;
+    use crate::session_prefix::TERMINAL_TAKEOVER_OPEN_TAG;
     use crate::tasks::CompactTask;
     use crate::tasks::RegularTask;
     use crate::tasks::UndoTask;
     use crate::tasks::UserShellCommandTask;
+    use crate::trash::RestoreOutcome;
+    use crate::unified_exec::UnifiedExecError;
     use codex_protocol::custom_prompts::CustomPrompt;
+    use codex_protocol::models::ContentItem;
+    use codex_protocol::models::ResponseInputItem;
     use codex_protocol::protocol::CodexErrorInfo;
+    use codex_protocol::protocol::ContextEdit;
+    use codex_protocol::protocol::ContextEditPreviewEvent;
+    use codex_protocol::protocol::ContextEditedEvent;
     use codex_protocol::protocol::ErrorEvent;
     use codex_protocol::protocol::Event;
     use codex_protocol::protocol::EventMsg;
     use codex_protocol::protocol::ListCustomPromptsResponseEvent;
+    use codex_protocol::protocol::ListJobsResponseEvent;
     use codex_protocol::protocol::ListSkillsResponseEvent;
+    use codex_protocol::protocol::ListTrashResponseEvent;
+    use codex_protocol::protocol::McpLoginCompletedEvent;
     use codex_protocol::protocol::McpServerRefreshConfig;
+    use codex_protocol::protocol::McpServerStatusResponseEvent;
     use codex_protocol::protocol::Op;
+    use codex_protocol::protocol::PinTarget;
+    use codex_protocol::protocol::PinnedContextEvent;
     use codex_protocol::protocol::ReviewDecision;
     use codex_protocol::protocol::ReviewRequest;
     use codex_protocol::protocol::SkillsListEntry;
@@ -2573,6 +3254,8 @@ mod handlers {
     use codex_protocol::request_user_input::RequestUserInputResponse;
 
     use crate::context_manager::is_user_turn_boundary;
+    use codex_git::RestoreGhostCommitOptions;
+    use codex_git::restore_ghost_commit_with_options;
     use codex_protocol::config_types::CollaborationMode;
     use codex_protocol::config_types::ModeKind;
     use codex_protocol::config_types::Settings;
@@ -2580,7 +3263,9 @@ mod handlers {
     use codex_protocol::user_input::UserInput;
     use codex_rmcp_client::ElicitationAction;
     use codex_rmcp_client::ElicitationResponse;
+    use codex_rmcp_client::perform_oauth_login_return_url;
     use mcp_types::RequestId;
+    use std::path::Path;
     use std::path::PathBuf;
     use std::sync::Arc;
     use tracing::info;
@@ -2595,6 +3280,7 @@ mod handlers {
         sub_id: String,
         updates: SessionSettingsUpdate,
     ) {
+        let previous_model = sess.current_collaboration_mode().await.model().to_string();
         if let Err(err) = sess.update_settings(updates).await {
             sess.send_event_raw(Event {
                 id: sub_id,
@@ -2604,6 +3290,12 @@ mod handlers {
                 }),
             })
             .await;
+            return;
+        }
+        let model = sess.current_collaboration_mode().await.model().to_string();
+        if model != previous_model {
+            sess.validate_context_window_for_model(&sub_id, &model)
+                .await;
         }
     }
 
@@ -2647,6 +3339,7 @@ mod handlers {
                         reasoning_summary: Some(summary),
                         final_output_json_schema: Some(final_output_json_schema),
                         personality,
+                        verbosity: None,
                     },
                 )
             }
@@ -2762,6 +3455,7 @@ mod handlers {
                 }
             }
         }
+        sess.notify_approval_decided(&id, "exec", &decision);
         match decision {
             ReviewDecision::Abort => {
                 sess.interrupt_task().await;
@@ -2771,6 +3465,19 @@ mod handlers {
     }
 
     pub async fn patch_approval(sess: &Arc<Session>, id: String, decision: ReviewDecision) {
+        if let ReviewDecision::ApprovedAlwaysForPaths { globs } = &decision
+            && let Err(err) = sess.persist_always_approved_paths(globs).await
+        {
+            let message = format!("Failed to save the approval rule: {err}");
+            tracing::warn!("{message}");
+            let warning = EventMsg::Warning(WarningEvent { message });
+            sess.send_event_raw(Event {
+                id: id.clone(),
+                msg: warning,
+            })
+            .await;
+        }
+        sess.notify_approval_decided(&id, "patch", &decision);
         match decision {
             ReviewDecision::Abort => {
                 sess.interrupt_task().await;
@@ -2847,6 +3554,123 @@ mod handlers {
         *guard = Some(refresh_config);
     }
 
+    pub async fn mcp_login(
+        sess: &Arc<Session>,
+        config: &Arc<Config>,
+        sub_id: String,
+        name: String,
+    ) {
+        let auth = sess.services.auth_manager.auth().await;
+        let mcp_servers = effective_mcp_servers(config, auth.as_ref());
+        let Some(server) = mcp_servers.get(&name).cloned() else {
+            let error = format!("No MCP server named '{name}' is configured.");
+            send_mcp_login_completed(sess, sub_id, name, Some(error)).await;
+            return;
+        };
+        let oauth_config = match oauth_login_support(&server.transport).await {
+            McpOAuthLoginSupport::Supported(oauth_config) => oauth_config,
+            McpOAuthLoginSupport::Unsupported => {
+                let error = format!("MCP server '{name}' does not support OAuth login.");
+                send_mcp_login_completed(sess, sub_id, name, Some(error)).await;
+                return;
+            }
+            McpOAuthLoginSupport::Unknown(err) => {
+                let error = format!("Could not check whether '{name}' supports OAuth: {err}");
+                send_mcp_login_completed(sess, sub_id, name, Some(error)).await;
+                return;
+            }
+        };
+        let handle = match perform_oauth_login_return_url(
+            &name,
+            &oauth_config.url,
+            config.mcp_oauth_credentials_store_mode,
+            oauth_config.http_headers,
+            oauth_config.env_http_headers,
+            server.scopes.as_deref().unwrap_or_default(),
+            None,
+            config.mcp_oauth_callback_port,
+        )
+        .await
+        {
+            Ok(handle) => handle,
+            Err(err) => {
+                send_mcp_login_completed(sess, sub_id, name, Some(format!("{err:#}"))).await;
+                return;
+            }
+        };
+        if !handle.open_in_browser() {
+            let url = handle.authorization_url();
+            sess.send_event_raw(Event {
+                id: sub_id.clone(),
+                msg: EventMsg::Warning(WarningEvent {
+                    message: format!("Open this URL in your browser to log in to '{name}': {url}"),
+                }),
+            })
+            .await;
+        }
+
+        // The callback can take minutes, so wait for it off the submission loop.
+        let sess = Arc::clone(sess);
+        let store_mode = config.mcp_oauth_credentials_store_mode;
+        tokio::spawn(async move {
+            let error = match handle.wait().await {
+                Ok(()) => {
+                    // Reconnect on the next turn so the server starts with the new tokens.
+                    match (
+                        serde_json::to_value(&mcp_servers),
+                        serde_json::to_value(store_mode),
+                    ) {
+                        (Ok(mcp_servers), Ok(mcp_oauth_credentials_store_mode)) => {
+                            refresh_mcp_servers(
+                                &sess,
+                                McpServerRefreshConfig {
+                                    mcp_servers,
+                                    mcp_oauth_credentials_store_mode,
+                                },
+                            )
+                            .await;
+                        }
+                        _ => warn!("failed to queue MCP server refresh after logging in to {name}"),
+                    }
+                    None
+                }
+                Err(err) => Some(format!("{err:#}")),
+            };
+            send_mcp_login_completed(&sess, sub_id, name, error).await;
+        });
+    }
+
+    async fn send_mcp_login_completed(
+        sess: &Session,
+        sub_id: String,
+        name: String,
+        error: Option<String>,
+    ) {
+        sess.send_event_raw(Event {
+            id: sub_id,
+            msg: EventMsg::McpLoginCompleted(McpLoginCompletedEvent {
+                name,
+                success: error.is_none(),
+                error,
+            }),
+        })
+        .await;
+    }
+
+    pub async fn mcp_server_status(sess: &Session, sub_id: String) {
+        let servers = sess
+            .services
+            .mcp_connection_manager
+            .read()
+            .await
+            .server_statuses();
+        sess.send_event_raw(Event {
+            id: sub_id,
+            msg: EventMsg::McpServerStatusResponse(McpServerStatusResponseEvent { servers }),
+        })
+        .await;
+    }
+
     pub async fn list_mcp_tools(sess: &Session, config: &Arc<Config>, sub_id: String) {
         let mcp_connection_manager = sess.services.mcp_connection_manager.read().await;
         let auth = sess.services.auth_manager.auth().await;
@@ -2865,12 +3689,16 @@ mod handlers {
     }
 
     pub async fn list_custom_prompts(sess: &Session, sub_id: String) {
-        let custom_prompts: Vec<CustomPrompt> =
+        let mut custom_prompts: Vec<CustomPrompt> =
             if let Some(dir) = crate::custom_prompts::default_prompts_dir() {
                 crate::custom_prompts::discover_prompts_in(&dir).await
             } else {
                 Vec::new()
             };
+        if let Some(dir) = crate::custom_prompts::default_commands_dir() {
+            let cwd = sess.state.lock().await.session_configuration.cwd.clone();
+            custom_prompts.extend(crate::custom_prompts::discover_commands_in(&dir, &cwd).await);
+        }
 
         let event = Event {
             id: sub_id,
@@ -2907,73 +3735,510 @@ mod handlers {
             });
         }
 
-        let event = Event {
+        let event = Event {
+            id: sub_id,
+            msg: EventMsg::ListSkillsResponse(ListSkillsResponseEvent { skills }),
+        };
+        sess.send_event_raw(event).await;
+    }
+
+    pub async fn list_trash(sess: &Session, sub_id: String) {
+        let entries = {
+            let guard = sess.services.trash.lock().await;
+            guard.as_ref().map(crate::trash::SessionTrash::entries)
+        };
+        match entries {
+            Some(entries) => send_trash_entries(sess, sub_id, entries).await,
+            None => send_trash_disabled_warning(sess, sub_id).await,
+        }
+    }
+
+    pub async fn restore_trash(sess: &Session, sub_id: String, paths: Vec<PathBuf>) {
+        let result = {
+            let mut guard = sess.services.trash.lock().await;
+            guard
+                .as_mut()
+                .map(|trash| (trash.restore(&paths), trash.entries()))
+        };
+        let Some((outcomes, entries)) = result else {
+            send_trash_disabled_warning(sess, sub_id).await;
+            return;
+        };
+        for outcome in outcomes {
+            match outcome {
+                RestoreOutcome::Restored(path) => {
+                    info!("restored {} from trash", path.display());
+                }
+                RestoreOutcome::Skipped { path, reason } => {
+                    sess.send_event_raw(Event {
+                        id: sub_id.clone(),
+                        msg: EventMsg::Warning(WarningEvent {
+                            message: format!("Could not restore {}: {reason}", path.display()),
+                        }),
+                    })
+                    .await;
+                }
+            }
+        }
+        send_trash_entries(sess, sub_id, entries).await;
+    }
+
+    pub async fn purge_trash(sess: &Session, sub_id: String) {
+        let result = {
+            let mut guard = sess.services.trash.lock().await;
+            guard.as_mut().map(crate::trash::SessionTrash::purge)
+        };
+        match result {
+            Some(Ok(purged)) => {
+                info!("purged {purged} files from trash");
+                send_trash_entries(sess, sub_id, Vec::new()).await;
+            }
+            Some(Err(err)) => {
+                sess.send_event_raw(Event {
+                    id: sub_id,
+                    msg: EventMsg::Error(ErrorEvent {
+                        message: format!("Failed to purge trash: {err}"),
+                        codex_error_info: Some(CodexErrorInfo::Other),
+                    }),
+                })
+                .await;
+            }
+            None => send_trash_disabled_warning(sess, sub_id).await,
+        }
+    }
+
+    async fn send_trash_entries(
+        sess: &Session,
+        sub_id: String,
+        entries: Vec<codex_protocol::protocol::TrashEntry>,
+    ) {
+        sess.send_event_raw(Event {
+            id: sub_id,
+            msg: EventMsg::ListTrashResponse(ListTrashResponseEvent { entries }),
+        })
+        .await;
+    }
+
+    async fn send_trash_disabled_warning(sess: &Session, sub_id: String) {
+        sess.send_event_raw(Event {
+            id: sub_id,
+            msg: EventMsg::Warning(WarningEvent {
+                message: "Trash is disabled. Set `[trash] enabled = true` in config.toml to keep deleted files recoverable.".to_string(),
+            }),
+        })
+        .await;
+    }
+
+    pub async fn take_over_terminal(sess: &Session, sub_id: String, process_id: String) {
+        let result = sess
+            .services
+            .unified_exec_manager
+            .set_user_control(&process_id, true)
+            .await;
+        if let Err(err) = result {
+            send_terminal_warning(sess, sub_id, &process_id, err).await;
+            return;
+        }
+        notify_model_of_terminal_takeover(
+            sess,
+            format!(
+                "The user has taken over terminal session {process_id} to type into it directly. Do not write to it until they hand it back; poll it with empty chars if you need its output."
+            ),
+        )
+        .await;
+    }
+
+    pub async fn terminal_input(sess: &Session, sub_id: String, process_id: String, data: String) {
+        let result = sess
+            .services
+            .unified_exec_manager
+            .write_user_input(&process_id, data.as_bytes())
+            .await;
+        if let Err(err) = result {
+            send_terminal_warning(sess, sub_id, &process_id, err).await;
+        }
+    }
+
+    pub async fn release_terminal(sess: &Session, sub_id: String, process_id: String) {
+        let result = sess
+            .services
+            .unified_exec_manager
+            .set_user_control(&process_id, false)
+            .await;
+        if let Err(err) = result {
+            send_terminal_warning(sess, sub_id, &process_id, err).await;
+            return;
+        }
+        notify_model_of_terminal_takeover(
+            sess,
+            format!(
+                "The user has handed terminal session {process_id} back to you. They may have answered prompts or run commands in it; poll it with empty chars to see its current state before continuing."
+            ),
+        )
+        .await;
+    }
+
+    /// Tell the running turn about a takeover. What the user typed is never
+    /// included, since it may be a password. Terminals close when their turn
+    /// ends, so without an active turn there is nobody to tell.
+    async fn notify_model_of_terminal_takeover(sess: &Session, message: String) {
+        let item = ResponseInputItem::Message {
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: format!("{TERMINAL_TAKEOVER_OPEN_TAG}\n{message}\n</terminal_takeover>"),
+            }],
+        };
+        let _ = sess.inject_response_items(vec![item]).await;
+    }
+
+    async fn send_terminal_warning(
+        sess: &Session,
+        sub_id: String,
+        process_id: &str,
+        err: UnifiedExecError,
+    ) {
+        let message = match err {
+            UnifiedExecError::UnknownProcessId { .. } => {
+                format!("Terminal session {process_id} is no longer running.")
+            }
+            UnifiedExecError::StdinClosed => {
+                format!(
+                    "Terminal session {process_id} was started without a TTY and cannot take input."
+                )
+            }
+            other => format!("Terminal session {process_id}: {other}"),
+        };
+        sess.send_event_raw(Event {
+            id: sub_id,
+            msg: EventMsg::Warning(WarningEvent { message }),
+        })
+        .await;
+    }
+
+    pub async fn list_jobs(sess: &Session, sub_id: String) {
+        send_jobs(sess, sub_id).await;
+    }
+
+    pub async fn start_job(sess: &Session, sub_id: String, command: String) {
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id.clone()).await;
+        let result = sess
+            .services
+            .unified_exec_manager
+            .start_user_job(sess, turn_context.as_ref(), &command)
+            .await;
+        match result {
+            Ok(job_id) => info!("started background job {job_id}: {command}"),
+            Err(err) => {
+                sess.send_event_raw(Event {
+                    id: sub_id.clone(),
+                    msg: EventMsg::Warning(WarningEvent {
+                        message: format!("Could not start `{command}`: {err}"),
+                    }),
+                })
+                .await;
+            }
+        }
+        send_jobs(sess, sub_id).await;
+    }
+
+    pub async fn stop_job(sess: &Session, sub_id: String, job_id: String) {
+        let stopped = sess
+            .services
+            .unified_exec_manager
+            .jobs()
+            .stop(&job_id)
+            .await;
+        if stopped.is_none() {
+            sess.send_event_raw(Event {
+                id: sub_id.clone(),
+                msg: EventMsg::Warning(WarningEvent {
+                    message: format!("No background job {job_id}."),
+                }),
+            })
+            .await;
+        }
+        send_jobs(sess, sub_id).await;
+    }
+
+    async fn send_jobs(sess: &Session, sub_id: String) {
+        let jobs = sess.services.unified_exec_manager.jobs().list().await;
+        sess.send_event_raw(Event {
+            id: sub_id,
+            msg: EventMsg::ListJobsResponse(ListJobsResponseEvent { jobs }),
+        })
+        .await;
+    }
+
+    pub async fn undo(sess: &Arc<Session>, sub_id: String) {
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+        sess.spawn_task(turn_context, Vec::new(), UndoTask::new())
+            .await;
+    }
+
+    pub async fn compact(sess: &Arc<Session>, sub_id: String) {
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+
+        sess.spawn_task(
+            Arc::clone(&turn_context),
+            vec![UserInput::Text {
+                text: turn_context.compact_prompt().to_string(),
+                // Compaction prompt is synthesized; no UI element ranges to preserve.
+                text_elements: Vec::new(),
+            }],
+            CompactTask,
+        )
+        .await;
+    }
+
+    pub async fn thread_rollback(sess: &Arc<Session>, sub_id: String, num_turns: u32) {
+        if let Some(message) = rollback_rejection(sess, num_turns).await {
+            send_rollback_failed(sess, sub_id, message).await;
+            return;
+        }
+
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+        drop_rolled_back_turns(sess, &turn_context, num_turns).await;
+    }
+
+    /// Roll back `num_turns` and restore the workspace to the ghost snapshot
+    /// taken when the earliest of them started. The history is left alone
+    /// unless the files were restored.
+    pub async fn rewind(sess: &Arc<Session>, sub_id: String, num_turns: u32) {
+        if let Some(message) = rollback_rejection(sess, num_turns).await {
+            send_rollback_failed(sess, sub_id, message).await;
+            return;
+        }
+        if !sess.enabled(Feature::GhostCommit) {
+            let message = "Rewind needs the `undo` feature, which snapshots the workspace at the start of each turn.".to_string();
+            send_rollback_failed(sess, sub_id, message).await;
+            return;
+        }
+
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+        let history = sess.clone_history().await;
+        let Some(ghost_commit) = history.ghost_snapshot_before_last_n_user_turns(num_turns) else {
+            let message =
+                "No workspace snapshot was recorded for that turn, so its files cannot be restored."
+                    .to_string();
+            send_rollback_failed(sess, turn_context.sub_id.clone(), message).await;
+            return;
+        };
+
+        let commit_id = ghost_commit.id().to_string();
+        let repo_path = turn_context.cwd.clone();
+        let ghost_snapshot = turn_context.ghost_snapshot.clone();
+        let restore_result = tokio::task::spawn_blocking(move || {
+            let options = RestoreGhostCommitOptions::new(&repo_path).ghost_snapshot(ghost_snapshot);
+            restore_ghost_commit_with_options(&options, &ghost_commit)
+        })
+        .await;
+        let error = match restore_result {
+            Ok(Ok(())) => None,
+            Ok(Err(err)) => Some(err.to_string()),
+            Err(err) => Some(err.to_string()),
+        };
+        if let Some(err) = error {
+            let message = format!("Failed to restore snapshot {commit_id}: {err}");
+            warn!("{message}");
+            send_rollback_failed(sess, turn_context.sub_id.clone(), message).await;
+            return;
+        }
+
+        info!(commit_id, num_turns, "rewind restored ghost snapshot");
+        drop_rolled_back_turns(sess, &turn_context, num_turns).await;
+    }
+
+    async fn rollback_rejection(sess: &Arc<Session>, num_turns: u32) -> Option<String> {
+        if num_turns == 0 {
+            return Some("num_turns must be >= 1".to_string());
+        }
+        let has_active_turn = { sess.active_turn.lock().await.is_some() };
+        has_active_turn.then(|| "Cannot rollback while a turn is in progress.".to_string())
+    }
+
+    async fn send_rollback_failed(sess: &Arc<Session>, sub_id: String, message: String) {
+        sess.send_event_raw(Event {
             id: sub_id,
-            msg: EventMsg::ListSkillsResponse(ListSkillsResponseEvent { skills }),
-        };
-        sess.send_event_raw(event).await;
+            msg: EventMsg::Error(ErrorEvent {
+                message,
+                codex_error_info: Some(CodexErrorInfo::ThreadRollbackFailed),
+            }),
+        })
+        .await;
     }
 
-    pub async fn undo(sess: &Arc<Session>, sub_id: String) {
-        let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
-        sess.spawn_task(turn_context, Vec::new(), UndoTask::new())
-            .await;
+    async fn drop_rolled_back_turns(
+        sess: &Arc<Session>,
+        turn_context: &TurnContext,
+        num_turns: u32,
+    ) {
+        let mut history = sess.clone_history().await;
+        history.drop_last_n_user_turns(num_turns);
+
+        // Replace with the raw items. We don't want to replace with a normalized
+        // version of the history.
+        sess.replace_history(history.raw_items().to_vec()).await;
+        sess.recompute_token_usage(turn_context).await;
+
+        sess.send_event_raw_flushed(Event {
+            id: turn_context.sub_id.clone(),
+            msg: EventMsg::ThreadRolledBack(ThreadRolledBackEvent { num_turns }),
+        })
+        .await;
     }
 
-    pub async fn compact(sess: &Arc<Session>, sub_id: String) {
+    pub async fn preview_context_edit(sess: &Arc<Session>, sub_id: String, edit: ContextEdit) {
         let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+        let history = sess.clone_history().await;
+        let turns = match history.user_turns_affected_by(edit) {
+            Ok(turns) => turns,
+            Err(message) => {
+                send_context_edit_error(sess, &turn_context.sub_id, message).await;
+                return;
+            }
+        };
 
-        sess.spawn_task(
-            Arc::clone(&turn_context),
-            vec![UserInput::Text {
-                text: turn_context.compact_prompt().to_string(),
-                // Compaction prompt is synthesized; no UI element ranges to preserve.
-                text_elements: Vec::new(),
-            }],
-            CompactTask,
-        )
+        let mut after = history.clone();
+        match edit {
+            ContextEdit::Compact => {
+                let user_messages = crate::compact::collect_user_messages(history.raw_items());
+                let initial_context = sess.build_initial_context(turn_context.as_ref()).await;
+                after.replace(crate::compact::build_compacted_history(
+                    initial_context,
+                    &user_messages,
+                    "",
+                ));
+            }
+            ContextEdit::Clear | ContextEdit::DropTurns { .. } => {
+                after.drop_user_turns(turns.clone());
+            }
+        }
+
+        let previews = history.user_turn_previews();
+        sess.send_event_raw(Event {
+            id: turn_context.sub_id.clone(),
+            msg: EventMsg::ContextEditPreview(ContextEditPreviewEvent {
+                edit,
+                total_turns: u32::try_from(previews.len()).unwrap_or(u32::MAX),
+                removed_turns: previews[turns].to_vec(),
+                tokens_before: history
+                    .estimate_token_count(turn_context.as_ref())
+                    .unwrap_or_default(),
+                tokens_after: after
+                    .estimate_token_count(turn_context.as_ref())
+                    .unwrap_or_default(),
+            }),
+        })
         .await;
     }
 
-    pub async fn thread_rollback(sess: &Arc<Session>, sub_id: String, num_turns: u32) {
-        if num_turns == 0 {
-            sess.send_event_raw(Event {
-                id: sub_id,
-                msg: EventMsg::Error(ErrorEvent {
-                    message: "num_turns must be >= 1".to_string(),
-                    codex_error_info: Some(CodexErrorInfo::ThreadRollbackFailed),
-                }),
-            })
-            .await;
+    pub async fn apply_context_edit(sess: &Arc<Session>, sub_id: String, edit: ContextEdit) {
+        if edit == ContextEdit::Compact {
+            compact(sess, sub_id).await;
             return;
         }
 
         let has_active_turn = { sess.active_turn.lock().await.is_some() };
         if has_active_turn {
-            sess.send_event_raw(Event {
-                id: sub_id,
-                msg: EventMsg::Error(ErrorEvent {
-                    message: "Cannot rollback while a turn is in progress.".to_string(),
-                    codex_error_info: Some(CodexErrorInfo::ThreadRollbackFailed),
-                }),
-            })
+            send_context_edit_error(
+                sess,
+                &sub_id,
+                "Cannot edit the context while a turn is in progress.".to_string(),
+            )
             .await;
             return;
         }
 
         let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
-
         let mut history = sess.clone_history().await;
-        history.drop_last_n_user_turns(num_turns);
+        let turns = match history.user_turns_affected_by(edit) {
+            Ok(turns) => turns,
+            Err(message) => {
+                send_context_edit_error(sess, &turn_context.sub_id, message).await;
+                return;
+            }
+        };
+        let tokens_before = history
+            .estimate_token_count(turn_context.as_ref())
+            .unwrap_or_default();
+        let removed_turns = u32::try_from(turns.len()).unwrap_or(u32::MAX);
+        history.drop_user_turns(turns);
+        let tokens_after = history
+            .estimate_token_count(turn_context.as_ref())
+            .unwrap_or_default();
 
-        // Replace with the raw items. We don't want to replace with a normalized
-        // version of the history.
         sess.replace_history(history.raw_items().to_vec()).await;
         sess.recompute_token_usage(turn_context.as_ref()).await;
 
+        // Flushed like `ThreadRolledBack`: the rollout marker is what lets a
+        // resumed session drop the same turns.
         sess.send_event_raw_flushed(Event {
             id: turn_context.sub_id.clone(),
-            msg: EventMsg::ThreadRolledBack(ThreadRolledBackEvent { num_turns }),
+            msg: EventMsg::ContextEdited(ContextEditedEvent {
+                edit,
+                removed_turns,
+                tokens_before,
+                tokens_after,
+            }),
+        })
+        .await;
+    }
+
+    pub async fn pin(sess: &Arc<Session>, sub_id: String, target: PinTarget) {
+        let target = match target {
+            PinTarget::File { path } => {
+                let turn_context = sess.new_default_turn_with_sub_id(sub_id.clone()).await;
+                let path = turn_context.cwd.join(path);
+                if !path.is_file() {
+                    let message = format!("Cannot pin {}: not a file.", path.display());
+                    send_context_edit_error(sess, &sub_id, message).await;
+                    return;
+                }
+                PinTarget::File { path }
+            }
+            PinTarget::Message { text: Some(text) } if !text.trim().is_empty() => {
+                PinTarget::Message { text: Some(text) }
+            }
+            PinTarget::Message { .. } => {
+                let history = sess.clone_history().await;
+                let Some(text) = get_last_assistant_message_from_turn(history.raw_items()) else {
+                    let message = "There is no agent message to pin yet.".to_string();
+                    send_context_edit_error(sess, &sub_id, message).await;
+                    return;
+                };
+                PinTarget::Message { text: Some(text) }
+            }
+        };
+        sess.state.lock().await.pinned_context.pin(target);
+        send_pinned_context(sess, sub_id).await;
+    }
+
+    pub async fn unpin(sess: &Arc<Session>, sub_id: String, id: Option<u32>) {
+        let result = sess.state.lock().await.pinned_context.unpin(id);
+        match result {
+            Ok(()) => send_pinned_context(sess, sub_id).await,
+            Err(message) => send_context_edit_error(sess, &sub_id, message).await,
+        }
+    }
+
+    pub async fn send_pinned_context(sess: &Arc<Session>, sub_id: String) {
+        let pinned_context = sess.state.lock().await.pinned_context.clone();
+        sess.send_event_raw(Event {
+            id: sub_id,
+            msg: EventMsg::PinnedContextUpdated(PinnedContextEvent {
+                pins: pinned_context.entries(),
+            }),
+        })
+        .await;
+    }
+
+    async fn send_context_edit_error(sess: &Session, sub_id: &str, message: String) {
+        sess.send_event_raw(Event {
+            id: sub_id.to_string(),
+            msg: EventMsg::Error(ErrorEvent {
+                message,
+                codex_error_info: Some(CodexErrorInfo::BadRequest),
+            }),
         })
         .await;
     }
@@ -3050,6 +4315,11 @@ mod handlers {
             .unified_exec_manager
             .terminate_all_processes()
             .await;
+        sess.services
+            .unified_exec_manager
+            .jobs()
+            .terminate_all()
+            .await;
         info!("Shutting down Codex instance");
         let history = sess.clone_history().await;
         let turn_count = history
@@ -3069,20 +4339,31 @@ mod handlers {
             let mut guard = sess.services.rollout.lock().await;
             guard.take()
         };
-        if let Some(rec) = recorder_opt
-            && let Err(e) = rec.shutdown().await
-        {
-            warn!("failed to shutdown rollout recorder: {e}");
-            let event = Event {
-                id: sub_id.clone(),
-                msg: EventMsg::Error(ErrorEvent {
-                    message: "Failed to shutdown rollout recorder".to_string(),
-                    codex_error_info: Some(CodexErrorInfo::Other),
-                }),
-            };
-            sess.send_event_raw(event).await;
+        if let Some(rec) = recorder_opt {
+            if let Err(e) = rec.shutdown().await {
+                warn!("failed to shutdown rollout recorder: {e}");
+                let event = Event {
+                    id: sub_id.clone(),
+                    msg: EventMsg::Error(ErrorEvent {
+                        message: "Failed to shutdown rollout recorder".to_string(),
+                        codex_error_info: Some(CodexErrorInfo::Other),
+                    }),
+                };
+                sess.send_event_raw(event).await;
+            } else if turn_count > 0 {
+                store_session_summary(&sess.get_config().await, rec.rollout_path()).await;
+            }
+        }
+
+        sess.services.container.stop().await;
+        if let Err(err) = sess.services.saved_outputs.lock().await.purge() {
+            warn!("failed to remove saved command output: {err}");
         }
 
+        sess.notifier()
+            .shutdown(sess.conversation_id.to_string())
+            .await;
+
         let event = Event {
             id: sub_id,
             msg: EventMsg::ShutdownComplete,
@@ -3091,6 +4372,33 @@ mod handlers {
         true
     }
 
+    /// Extracts the two-line summary listed by `codex sessions list` and the
+    /// resume picker from the flushed rollout.
+    /// The summary is sealed like the rollout when session encryption is on.
+    async fn store_session_summary(config: &Config, rollout_path: &Path) {
+        let summary = match session_summary::summarize_rollout_file(rollout_path).await {
+            Ok(Some(summary)) => summary,
+            Ok(None) => return,
+            Err(err) => {
+                warn!("failed to summarize session: {err}");
+                return;
+            }
+        };
+        let cipher = match session_cipher(&config.codex_home, config.session_encryption) {
+            Ok(cipher) => cipher,
+            Err(err) => {
+                warn!("failed to store session summary: {err}");
+                return;
+            }
+        };
+        if let Err(err) =
+            session_summary::append_session_summary(&config.codex_home, &summary, cipher.as_deref())
+                .await
+        {
+            warn!("failed to store session summary: {err}");
+        }
+    }
+
     pub async fn review(
         sess: &Arc<Session>,
         config: &Arc<Config>,
@@ -3151,7 +4459,9 @@ async fn spawn_review_thread(
         model_info: &review_model_info,
         features: &review_features,
         web_search_mode: Some(review_web_search_mode),
-    });
+    })
+    .with_tool_filter(parent_turn_context.tools_config.tool_filter.clone())
+    .with_mcp_output_budgets(mcp_output_budgets_configured(&config));
 
     let review_prompt = resolved.prompt.clone();
     let provider = parent_turn_context.client.get_provider();
@@ -3293,7 +4603,7 @@ fn errors_to_info(errors: &[SkillError]) -> Vec<SkillErrorInfo> {
 ///
 pub(crate) async fn run_turn(
     sess: Arc<Session>,
-    turn_context: Arc<TurnContext>,
+    mut turn_context: Arc<TurnContext>,
     input: Vec<UserInput>,
     cancellation_token: CancellationToken,
 ) -> Option<String> {
@@ -3304,6 +4614,14 @@ pub(crate) async fn run_turn(
     let model_info = turn_context.client.get_model_info();
     let auto_compact_limit = model_info.auto_compact_token_limit().unwrap_or(i64::MAX);
     let total_usage_tokens = sess.get_total_token_usage().await;
+    let usage_at_turn_start = sess.cumulative_token_usage().await;
+    // Spawned sub-agents stop once they have used `[agents] token_budget`.
+    let sub_agent_token_budget = matches!(
+        turn_context.client.get_session_source(),
+        SessionSource::SubAgent(SubAgentSource::ThreadSpawn { .. })
+    )
+    .then(|| turn_context.client.config().agent_token_budget)
+    .flatten();
     let event = EventMsg::TurnStarted(TurnStartedEvent {
         model_context_window: turn_context.client.get_model_context_window(),
         collaboration_mode_kind: turn_context.collaboration_mode.mode,
@@ -3384,7 +4702,22 @@ pub(crate) async fn run_turn(
     )
     .await;
 
-    for message in skill_warnings {
+    let mcp_mentions = collect_mcp_mentions(&input);
+    let McpMentionInjections {
+        items: mcp_mention_items,
+        warnings: mcp_mention_warnings,
+    } = if mcp_mentions.is_empty() {
+        McpMentionInjections::default()
+    } else {
+        build_mcp_mention_injections(
+            &*sess.services.mcp_connection_manager.read().await,
+            &mcp_mentions,
+            turn_context.truncation_policy,
+        )
+        .await
+    };
+
+    for message in skill_warnings.into_iter().chain(mcp_mention_warnings) {
         sess.send_event(&turn_context, EventMsg::Warning(WarningEvent { message }))
             .await;
     }
@@ -3398,9 +4731,15 @@ pub(crate) async fn run_turn(
         sess.record_conversation_items(&turn_context, &skill_items)
             .await;
     }
+    if !mcp_mention_items.is_empty() {
+        sess.record_conversation_items(&turn_context, &mcp_mention_items)
+            .await;
+    }
 
     sess.maybe_start_ghost_snapshot(Arc::clone(&turn_context), cancellation_token.child_token())
         .await;
+    sess.record_external_edits(&turn_context).await;
+    sess.refresh_repo_map(&turn_context).await;
     let mut last_agent_message: Option<String> = None;
     // Although from the perspective of codex.rs, TurnDiffTracker has the lifecycle of a Task which contains
     // many turns, from the perspective of the user, it is a single turn.
@@ -3409,6 +4748,13 @@ pub(crate) async fn run_turn(
     let mut client_session = turn_context
         .client
         .new_session(Some(turn_context.cwd.clone()));
+    let mut model_fallbacks = turn_context
+        .client
+        .config()
+        .model_fallbacks
+        .clone()
+        .into_iter();
+    let mut compacted_after_overflow = false;
 
     loop {
         // Note that pending_input would be something like a message the user
@@ -3425,7 +4771,7 @@ pub(crate) async fn run_turn(
         let sampling_request_input: Vec<ResponseItem> = {
             sess.record_conversation_items(&turn_context, &pending_input)
                 .await;
-            sess.clone_history().await.for_prompt()
+            sess.prompt_input().await
         };
 
         let sampling_request_input_messages = sampling_request_input
@@ -3456,6 +4802,26 @@ pub(crate) async fn run_turn(
                     needs_follow_up,
                     last_agent_message: sampling_request_last_agent_message,
                 } = sampling_request_output;
+                if needs_follow_up
+                    && let Some(budget) = sub_agent_token_budget
+                    && sess.cumulative_token_usage().await.total_tokens >= budget
+                {
+                    let message = format!(
+                        "Stopped before finishing: this sub-agent used its budget of {budget} tokens."
+                    );
+                    sess.send_event(
+                        &turn_context,
+                        EventMsg::Warning(WarningEvent {
+                            message: message.clone(),
+                        }),
+                    )
+                    .await;
+                    last_agent_message = Some(match sampling_request_last_agent_message {
+                        Some(partial) => format!("{partial}\n\n{message}"),
+                        None => message,
+                    });
+                    break;
+                }
                 let total_usage_tokens = sess.get_total_token_usage().await;
                 let token_limit_reached = total_usage_tokens >= auto_compact_limit;
 
@@ -3467,6 +4833,10 @@ pub(crate) async fn run_turn(
 
                 if !needs_follow_up {
                     last_agent_message = sampling_request_last_agent_message;
+                    let token_usage = TurnTokenUsage::between(
+                        &usage_at_turn_start,
+                        &sess.cumulative_token_usage().await,
+                    );
                     sess.notifier()
                         .notify(&UserNotification::AgentTurnComplete {
                             thread_id: sess.conversation_id.to_string(),
@@ -3474,15 +4844,38 @@ pub(crate) async fn run_turn(
                             cwd: turn_context.cwd.display().to_string(),
                             input_messages: sampling_request_input_messages,
                             last_assistant_message: last_agent_message.clone(),
+                            token_usage,
                         });
                     break;
                 }
                 continue;
             }
+            Err(CodexErr::ContextWindowExceeded) if !compacted_after_overflow => {
+                // The usage estimate fell short of the real prompt size. Compact once and
+                // retry instead of ending the turn; a failed compaction reports its own error.
+                compacted_after_overflow = true;
+                if run_auto_compact(&sess, &turn_context).await {
+                    continue;
+                }
+                break;
+            }
             Err(CodexErr::TurnAborted) => {
                 // Aborted turn is reported via a different event.
                 break;
             }
+            Err(CodexErr::Blocked { category, message }) => {
+                info!("Turn blocked ({category}): {message}");
+                sess.notifier().notify(&UserNotification::TurnBlocked {
+                    thread_id: sess.conversation_id.to_string(),
+                    turn_id: turn_context.sub_id.clone(),
+                    cwd: turn_context.cwd.display().to_string(),
+                    category: category.clone(),
+                    message: message.clone(),
+                });
+                let event = EventMsg::TurnBlocked(TurnBlockedEvent { category, message });
+                sess.send_event(&turn_context, event).await;
+                break;
+            }
             Err(CodexErr::InvalidImageRequest()) => {
                 let mut state = sess.state.lock().await;
                 error_or_panic(
@@ -3500,6 +4893,17 @@ pub(crate) async fn run_turn(
                 break;
             }
             Err(e) => {
+                if e.is_provider_unavailable()
+                    && let Some(fallback) = model_fallbacks.next()
+                {
+                    turn_context = sess
+                        .failover_turn_context(&turn_context, &fallback, &e)
+                        .await;
+                    client_session = turn_context
+                        .client
+                        .new_session(Some(turn_context.cwd.clone()));
+                    continue;
+                }
                 info!("Turn error: {e:#}");
                 let event = EventMsg::Error(e.to_error_event(None));
                 sess.send_event(&turn_context, event).await;
@@ -3512,12 +4916,32 @@ pub(crate) async fn run_turn(
     last_agent_message
 }
 
-async fn run_auto_compact(sess: &Arc<Session>, turn_context: &Arc<TurnContext>) {
-    if should_use_remote_compact_task(sess.as_ref(), &turn_context.client.get_provider()) {
-        run_inline_remote_auto_compact_task(Arc::clone(sess), Arc::clone(turn_context)).await;
-    } else {
-        run_inline_auto_compact_task(Arc::clone(sess), Arc::clone(turn_context)).await;
+/// Compacts the history so it fits the context window again and tells the
+/// user what was summarized. Returns whether the history was compacted.
+async fn run_auto_compact(sess: &Arc<Session>, turn_context: &Arc<TurnContext>) -> bool {
+    let items_before = sess
+        .clone_history()
+        .await
+        .raw_items()
+        .iter()
+        .filter(|item| !matches!(item, ResponseItem::GhostSnapshot { .. }))
+        .count();
+    let tokens_before = sess.get_total_token_usage().await;
+    let compacted =
+        if should_use_remote_compact_task(sess.as_ref(), &turn_context.client.get_provider()) {
+            run_inline_remote_auto_compact_task(Arc::clone(sess), Arc::clone(turn_context)).await
+        } else {
+            run_inline_auto_compact_task(Arc::clone(sess), Arc::clone(turn_context)).await
+        };
+    if compacted {
+        let tokens_after = sess.get_total_token_usage().await;
+        let message = format!(
+            "Context was nearly full, so {items_before} earlier messages and tool calls were summarized (about {tokens_before} tokens down to {tokens_after})."
+        );
+        sess.send_event(turn_context, EventMsg::Warning(WarningEvent { message }))
+            .await;
     }
+    compacted
 }
 
 fn filter_connectors_for_input(
@@ -3635,6 +5059,9 @@ async fn run_sampling_request(
     tool_selection: SamplingRequestToolSelection<'_>,
     cancellation_token: CancellationToken,
 ) -> CodexResult<SamplingRequestResult> {
+    if let Some(budget) = &turn_context.client.config().run_budget {
+        budget.start_response(turn_context.is_primary_thread())?;
+    }
     let mut mcp_tools = sess
         .services
         .mcp_connection_manager
@@ -3657,6 +5084,8 @@ async fn run_sampling_request(
     if let Some(connectors) = connectors_for_tools.as_ref() {
         mcp_tools = filter_codex_apps_mcp_tools(mcp_tools, connectors);
     }
+    let tool_filter = &turn_context.tools_config.tool_filter;
+    mcp_tools.retain(|name, tool| tool_filter.allows_mcp_tool(&tool.server_name, name));
     let router = Arc::new(ToolRouter::from_config(
         &turn_context.tools_config,
         Some(
@@ -3714,12 +5143,14 @@ async fn run_sampling_request(
             Err(err) => err,
         };
 
-        if !err.is_retryable() {
+        // Nothing from this attempt reached history, so resending the request is safe.
+        let provider = turn_context.client.get_provider();
+        if !provider.is_retryable(&err) {
             return Err(err);
         }
 
         // Use the configured provider-specific stream retry budget.
-        let max_retries = turn_context.client.get_provider().stream_max_retries();
+        let max_retries = provider.stream_max_retries();
         if retries >= max_retries && client_session.try_switch_fallback_transport() {
             sess.send_event(
                 &turn_context,
@@ -3735,9 +5166,9 @@ async fn run_sampling_request(
             retries += 1;
             let delay = match &err {
                 CodexErr::Stream(_, requested_delay) => {
-                    requested_delay.unwrap_or_else(|| backoff(retries))
+                    requested_delay.unwrap_or_else(|| provider.retry_delay(retries))
                 }
-                _ => backoff(retries),
+                _ => provider.retry_delay(retries),
             };
             warn!(
                 "stream disconnected - retrying sampling request ({retries}/{max_retries} in {delay:?})...",
@@ -4200,6 +5631,7 @@ async fn try_run_sampling_request(
         effort = turn_context.client.get_reasoning_effort(),
         auth_mode = sess.services.auth_manager.get_auth_mode(),
         features = sess.features.enabled_features(),
+        feature_overrides = sess.features.overridden_flags(),
     );
 
     sess.persist_rollout_items(&[rollout_item]).await;
@@ -4221,6 +5653,7 @@ async fn try_run_sampling_request(
     let mut last_agent_message: Option<String> = None;
     let mut active_item: Option<TurnItem> = None;
     let mut should_emit_turn_diff = false;
+    let mut completed_items = 0usize;
     let plan_mode = turn_context.collaboration_mode.mode == ModeKind::Plan;
     let mut plan_mode_state = plan_mode.then(|| PlanModeStreamState::new(&turn_context.sub_id));
     let receiving_span = trace_span!("receiving_stream");
@@ -4244,7 +5677,8 @@ async fn try_run_sampling_request(
         };
 
         let event = match event {
-            Some(res) => res?,
+            Some(Ok(event)) => event,
+            Some(Err(err)) => break Err(err),
             None => {
                 break Err(CodexErr::Stream(
                     "stream closed before response.completed".into(),
@@ -4260,6 +5694,7 @@ async fn try_run_sampling_request(
         match event {
             ResponseEvent::Created => {}
             ResponseEvent::OutputItemDone(item) => {
+                completed_items += 1;
                 let previously_active_item = active_item.take();
                 if let Some(state) = plan_mode_state.as_mut() {
                     if let Some(previous) = previously_active_item.as_ref() {
@@ -4329,6 +5764,9 @@ async fn try_run_sampling_request(
                 // token usage is available to avoid duplicate TokenCount events.
                 sess.update_rate_limits(&turn_context, snapshot).await;
             }
+            ResponseEvent::ServedModel(model) => {
+                sess.record_served_model(&turn_context, model).await;
+            }
             ResponseEvent::ModelsEtag(etag) => {
                 // Update internal state with latest models etag
                 let config = sess.get_config().await;
@@ -4346,6 +5784,7 @@ async fn try_run_sampling_request(
                 }
                 sess.update_token_usage_info(&turn_context, token_usage.as_ref())
                     .await;
+                sess.warn_if_served_model_unknown(&turn_context).await;
                 should_emit_turn_diff = true;
 
                 needs_follow_up |= sess.has_pending_input().await;
@@ -4435,6 +5874,44 @@ async fn try_run_sampling_request(
 
     drain_in_flight(&mut in_flight, sess.clone(), turn_context.clone()).await?;
 
+    // Items completed before a dropped connection are already in history and
+    // their tool calls have run, so resending the request would repeat them.
+    // This is not a resume: replaying a stream from a sequence number needs a
+    // stored background response, and requests are sent with `store: false`,
+    // so no response id or offset is sent. A new request
+    // goes out with those items in history, and the item that was still
+    // streaming is lost. These follow-ups share the turn's
+    // `stream_max_retries` budget and back off like other retries.
+    let outcome = match outcome {
+        Err(err)
+            if completed_items > 0 && turn_context.client.get_provider().is_retryable(&err) =>
+        {
+            let provider = turn_context.client.get_provider();
+            match client_session.try_continue_dropped_stream() {
+                Some(attempt) => {
+                    let max_retries = provider.stream_max_retries();
+                    let delay = provider.retry_delay(attempt);
+                    warn!(
+                        "stream disconnected after {completed_items} items - sending a follow-up request ({attempt}/{max_retries} in {delay:?})..."
+                    );
+                    sess.notify_stream_error(
+                        &turn_context,
+                        format!("Reconnecting... {attempt}/{max_retries}"),
+                        err,
+                    )
+                    .await;
+                    tokio::time::sleep(delay).await;
+                    Ok(SamplingRequestResult {
+                        needs_follow_up: true,
+                        last_agent_message: None,
+                    })
+                }
+                None => Err(CodexErr::StreamInterrupted(err.to_string())),
+            }
+        }
+        outcome => outcome,
+    };
+
     if should_emit_turn_diff {
         let unified_diff = {
             let mut tracker = turn_diff_tracker.lock().await;
@@ -4480,6 +5957,9 @@ mod tests {
     use crate::CodexAuth;
     use crate::config::ConfigBuilder;
     use crate::config::test_config;
+    use crate::config::types::ContainerConfig;
+    use crate::config::types::NotifyMode;
+    use crate::config::types::RemoteConfig;
     use crate::exec::ExecToolCallOutput;
     use crate::function_tool::FunctionCallError;
     use crate::shell::default_user_shell;
@@ -4864,6 +6344,43 @@ mod tests {
         assert_eq!(expected, history.raw_items());
     }
 
+    #[tokio::test]
+    async fn override_turn_context_warns_when_history_exceeds_new_model_window() {
+        let (sess, _tc, rx) = make_session_and_context_with_rx().await;
+        {
+            let mut state = sess.state.lock().await;
+            state.set_token_usage_full(10_000_000);
+        }
+
+        let collaboration_mode = sess.current_collaboration_mode().await.with_updates(
+            Some("gpt-5.1-codex-mini".to_string()),
+            None,
+            None,
+        );
+        handlers::override_turn_context(
+            &sess,
+            "sub-1".to_string(),
+            SessionSettingsUpdate {
+                collaboration_mode: Some(collaboration_mode),
+                ..Default::default()
+            },
+        )
+        .await;
+
+        assert_eq!(
+            sess.current_collaboration_mode().await.model(),
+            "gpt-5.1-codex-mini"
+        );
+        let event = rx.try_recv().expect("warning event");
+        let EventMsg::Warning(WarningEvent { message }) = event.msg else {
+            panic!("expected warning, got {:?}", event.msg);
+        };
+        assert!(
+            message.contains("compacted before the next turn"),
+            "{message}"
+        );
+    }
+
     #[tokio::test]
     async fn thread_rollback_clears_history_when_num_turns_exceeds_existing_turns() {
         let (sess, tc, rx) = make_session_and_context_with_rx().await;
@@ -4891,6 +6408,54 @@ mod tests {
         assert_eq!(initial_context, history.raw_items());
     }
 
+    #[tokio::test]
+    async fn apply_context_edit_drops_middle_turn_and_replays_from_rollout() {
+        let (sess, tc, rx) = make_session_and_context_with_rx().await;
+
+        let initial_context = sess.build_initial_context(tc.as_ref()).await;
+        sess.record_into_history(&initial_context, tc.as_ref())
+            .await;
+        let turns: Vec<ResponseItem> = ["turn 1 user", "turn 2 user", "turn 3 user"]
+            .into_iter()
+            .map(user_message)
+            .collect();
+        sess.record_into_history(&turns, tc.as_ref()).await;
+
+        let edit = ContextEdit::DropTurns { first: 2, last: 2 };
+        handlers::apply_context_edit(&sess, "sub-1".to_string(), edit).await;
+
+        let edited = loop {
+            let evt = tokio::time::timeout(StdDuration::from_secs(2), rx.recv())
+                .await
+                .expect("timeout waiting for event")
+                .expect("event");
+            if let EventMsg::ContextEdited(payload) = evt.msg {
+                break payload;
+            }
+        };
+        assert_eq!(edited.edit, edit);
+        assert_eq!(edited.removed_turns, 1);
+        assert!(edited.tokens_after < edited.tokens_before);
+
+        let mut expected = initial_context.clone();
+        expected.push(turns[0].clone());
+        expected.push(turns[2].clone());
+        let history = sess.clone_history().await;
+        assert_eq!(expected, history.raw_items());
+
+        let mut rollout_items: Vec<RolloutItem> = initial_context
+            .iter()
+            .chain(turns.iter())
+            .cloned()
+            .map(RolloutItem::ResponseItem)
+            .collect();
+        rollout_items.push(RolloutItem::EventMsg(EventMsg::ContextEdited(edited)));
+        let reconstructed = sess
+            .reconstruct_history_from_rollout(tc.as_ref(), &rollout_items)
+            .await;
+        assert_eq!(expected, reconstructed);
+    }
+
     #[tokio::test]
     async fn thread_rollback_fails_when_turn_in_progress() {
         let (sess, tc, rx) = make_session_and_context_with_rx().await;
@@ -4933,6 +6498,26 @@ mod tests {
         assert_eq!(initial_context, history.raw_items());
     }
 
+    #[tokio::test]
+    async fn rewind_without_snapshots_leaves_history_untouched() {
+        let (sess, tc, rx) = make_session_and_context_with_rx().await;
+
+        let initial_context = sess.build_initial_context(tc.as_ref()).await;
+        sess.record_into_history(&initial_context, tc.as_ref())
+            .await;
+
+        handlers::rewind(&sess, "sub-1".to_string(), 1).await;
+
+        let error_event = wait_for_thread_rollback_failed(&rx).await;
+        assert_eq!(
+            error_event.message,
+            "Rewind needs the `undo` feature, which snapshots the workspace at the start of each turn."
+        );
+
+        let history = sess.clone_history().await;
+        assert_eq!(initial_context, history.raw_items());
+    }
+
     #[tokio::test]
     async fn set_rate_limits_retains_previous_credits() {
         let codex_home = tempfile::tempdir().expect("create temp dir");
@@ -4953,6 +6538,7 @@ mod tests {
             provider: config.model_provider.clone(),
             collaboration_mode,
             model_reasoning_summary: config.model_reasoning_summary,
+            model_verbosity: config.model_verbosity,
             developer_instructions: config.developer_instructions.clone(),
             user_instructions: config.user_instructions.clone(),
             personality: config.personality,
@@ -5036,6 +6622,7 @@ mod tests {
             provider: config.model_provider.clone(),
             collaboration_mode,
             model_reasoning_summary: config.model_reasoning_summary,
+            model_verbosity: config.model_verbosity,
             developer_instructions: config.developer_instructions.clone(),
             user_instructions: config.user_instructions.clone(),
             personality: config.personality,
@@ -5303,6 +6890,7 @@ mod tests {
             provider: config.model_provider.clone(),
             collaboration_mode,
             model_reasoning_summary: config.model_reasoning_summary,
+            model_verbosity: config.model_verbosity,
             developer_instructions: config.developer_instructions.clone(),
             user_instructions: config.user_instructions.clone(),
             personality: config.personality,
@@ -5341,11 +6929,12 @@ mod tests {
             mcp_connection_manager: Arc::new(RwLock::new(McpConnectionManager::default())),
             mcp_startup_cancellation_token: Mutex::new(CancellationToken::new()),
             unified_exec_manager: UnifiedExecProcessManager::default(),
+            persistent_shell: PersistentShellManager::default(),
             analytics_events_client: AnalyticsEventsClient::new(
                 Arc::clone(&config),
                 Arc::clone(&auth_manager),
             ),
-            notifier: UserNotifier::new(None),
+            notifier: UserNotifier::new(None, NotifyMode::PerEvent, None),
             rollout: Mutex::new(None),
             user_shell: Arc::new(default_user_shell()),
             show_raw_agent_reasoning: config.show_raw_agent_reasoning,
@@ -5358,6 +6947,18 @@ mod tests {
             agent_control,
             state_db: None,
             transport_manager: TransportManager::new(),
+            trash: Mutex::new(None),
+            saved_outputs: Mutex::new(SessionOutputs::new(
+                &config.codex_home,
+                conversation_id,
+                config.session_encryption,
+            )),
+            lsp: LspManager::default(),
+            container: ContainerManager::new(ContainerConfig::default(), conversation_id),
+            remote: RemoteManager::new(RemoteConfig::default()),
+            network_proxy: NetworkProxyManager::default(),
+            write_protected: Arc::default(),
+            code_index: Arc::default(),
         };
 
         let turn_context = Session::make_turn_context(
@@ -5423,6 +7024,7 @@ mod tests {
             provider: config.model_provider.clone(),
             collaboration_mode,
             model_reasoning_summary: config.model_reasoning_summary,
+            model_verbosity: config.model_verbosity,
             developer_instructions: config.developer_instructions.clone(),
             user_instructions: config.user_instructions.clone(),
             personality: config.personality,
@@ -5461,11 +7063,12 @@ mod tests {
             mcp_connection_manager: Arc::new(RwLock::new(McpConnectionManager::default())),
             mcp_startup_cancellation_token: Mutex::new(CancellationToken::new()),
             unified_exec_manager: UnifiedExecProcessManager::default(),
+            persistent_shell: PersistentShellManager::default(),
             analytics_events_client: AnalyticsEventsClient::new(
                 Arc::clone(&config),
                 Arc::clone(&auth_manager),
             ),
-            notifier: UserNotifier::new(None),
+            notifier: UserNotifier::new(None, NotifyMode::PerEvent, None),
             rollout: Mutex::new(None),
             user_shell: Arc::new(default_user_shell()),
             show_raw_agent_reasoning: config.show_raw_agent_reasoning,
@@ -5478,6 +7081,18 @@ mod tests {
             agent_control,
             state_db: None,
             transport_manager: TransportManager::new(),
+            trash: Mutex::new(None),
+            saved_outputs: Mutex::new(SessionOutputs::new(
+                &config.codex_home,
+                conversation_id,
+                config.session_encryption,
+            )),
+            lsp: LspManager::default(),
+            container: ContainerManager::new(ContainerConfig::default(), conversation_id),
+            remote: RemoteManager::new(RemoteConfig::default()),
+            network_proxy: NetworkProxyManager::default(),
+            write_protected: Arc::default(),
+            code_index: Arc::default(),
         };
 
         let turn_context = Arc::new(Session::make_turn_context(
@@ -5917,6 +7532,7 @@ mod tests {
 
     #[tokio::test]
     async fn rejects_escalated_permissions_when_policy_not_on_request() {
+        use crate::exec::ExecLimits;
         use crate::exec::ExecParams;
         use crate::protocol::AskForApproval;
         use crate::protocol::SandboxPolicy;
@@ -5953,6 +7569,7 @@ mod tests {
             windows_sandbox_level: turn_context.windows_sandbox_level,
             justification: Some("test".to_string()),
             arg0: None,
+            limits: ExecLimits::default(),
         };
 
         let params2 = ExecParams {
@@ -5964,6 +7581,7 @@ mod tests {
             windows_sandbox_level: turn_context.windows_sandbox_level,
             justification: params.justification.clone(),
             arg0: None,
+            limits: ExecLimits::default(),
         };
 
         let turn_diff_tracker = Arc::new(tokio::sync::Mutex::new(TurnDiffTracker::new()));
@@ -313,13 +313,14 @@ async fn handle_exec_approval(
     cancel_token: &CancellationToken,
 ) {
     // Race approval with cancellation and timeout to avoid hangs.
-    let approval_fut = parent_session.request_command_approval(
+    let approval_fut = parent_session.request_command_approval_with_file_operation(
         parent_ctx,
         parent_ctx.sub_id.clone(),
         event.command,
         event.cwd,
         event.reason,
         event.proposed_execpolicy_amendment,
+        event.file_operation,
     );
     let decision = await_approval_with_cancel(
         approval_fut,
@@ -342,12 +343,13 @@ async fn handle_patch_approval(
     cancel_token: &CancellationToken,
 ) {
     let decision_rx = parent_session
-        .request_patch_approval(
+        .request_patch_approval_with_hunks(
             parent_ctx,
             parent_ctx.sub_id.clone(),
             event.changes,
             event.reason,
             event.grant_root,
+            event.hunks,
         )
         .await;
     let decision = await_approval_with_cancel(
@@ -0,0 +1,161 @@
+use crate::bash::parse_shell_lc_plain_commands;
+use crate::command_safety::is_dangerous_command::command_might_be_dangerous;
+use crate::command_safety::is_dangerous_command::short_flag_group_contains;
+use crate::command_safety::is_safe_command::is_known_safe_command;
+
+/// How much harm a command can do, judged from the command line alone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandRisk {
+    /// Only reads; runs without approval.
+    ReadOnly,
+    /// May write, but is not known to destroy anything. The sandbox and the
+    /// approval policy decide.
+    LowRisk,
+    /// Deletes data, rewrites remote history, or escalates privileges. Always
+    /// asks for approval, even when the approval policy would not.
+    Destructive,
+}
+
+/// Classifies `command`, looking into `bash -lc "<script>"` scripts. A script
+/// is as risky as its riskiest command.
+pub fn classify_command(command: &[String]) -> CommandRisk {
+    if is_known_safe_command(command) {
+        return CommandRisk::ReadOnly;
+    }
+    if command_might_be_dangerous(command) || is_destructive(command) {
+        return CommandRisk::Destructive;
+    }
+    if let Some(all_commands) = parse_shell_lc_plain_commands(command)
+        && all_commands.iter().any(|cmd| is_destructive(cmd))
+    {
+        return CommandRisk::Destructive;
+    }
+    CommandRisk::LowRisk
+}
+
+/// Whether `command` starts with one of the `[approval] safe_commands`
+/// prefixes.
+pub fn matches_safe_command(command: &[String], safe_commands: &[Vec<String>]) -> bool {
+    safe_commands
+        .iter()
+        .any(|prefix| !prefix.is_empty() && command.starts_with(prefix))
+}
+
+/// Destructive patterns beyond those in `command_might_be_dangerous`.
+fn is_destructive(command: &[String]) -> bool {
+    let Some(cmd0) = command.first() else {
+        return false;
+    };
+    let program = std::path::Path::new(cmd0)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(cmd0);
+    let args = &command[1..];
+
+    match program {
+        // Anything run with elevated privileges escapes the sandbox's intent.
+        "sudo" | "doas" | "su" | "pkexec" => true,
+        "rm" => args.iter().any(|arg| {
+            arg == "--force"
+                || arg == "--recursive"
+                || short_flag_group_contains(arg, 'f')
+                || short_flag_group_contains(arg, 'r')
+                || short_flag_group_contains(arg, 'R')
+        }),
+        "shred" | "wipefs" | "mkswap" => true,
+        mkfs if mkfs == "mkfs" || mkfs.starts_with("mkfs.") => true,
+        "dd" => args.iter().any(|arg| arg.starts_with("of=")),
+        "find" => {
+            let execs_rm = |pair: &[String]| {
+                matches!(pair[0].as_str(), "-exec" | "-execdir") && pair[1] == "rm"
+            };
+            args.iter().any(|arg| arg == "-delete") || args.windows(2).any(execs_rm)
+        }
+        "chmod" | "chown" | "chgrp" => args
+            .iter()
+            .any(|arg| arg == "--recursive" || short_flag_group_contains(arg, 'R')),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn vec_str(items: &[&str]) -> Vec<String> {
+        items.iter().map(std::string::ToString::to_string).collect()
+    }
+
+    #[test]
+    fn classifies_commands_by_risk() {
+        for (command, expected) in [
+            (vec_str(&["ls", "-la"]), CommandRisk::ReadOnly),
+            (
+                vec_str(&["bash", "-lc", "git status && cat README.md"]),
+                CommandRisk::ReadOnly,
+            ),
+            (vec_str(&["cargo", "build"]), CommandRisk::LowRisk),
+            (vec_str(&["rm", "notes.txt"]), CommandRisk::LowRisk),
+            (
+                vec_str(&["rm", "-r", "-f", "target"]),
+                CommandRisk::Destructive,
+            ),
+            (
+                vec_str(&["rm", "--recursive", "build"]),
+                CommandRisk::Destructive,
+            ),
+            (
+                vec_str(&["git", "push", "--force"]),
+                CommandRisk::Destructive,
+            ),
+            (
+                vec_str(&["sudo", "apt-get", "install", "jq"]),
+                CommandRisk::Destructive,
+            ),
+            (
+                vec_str(&["/usr/bin/dd", "if=/dev/zero", "of=disk.img"]),
+                CommandRisk::Destructive,
+            ),
+            (
+                vec_str(&["mkfs.ext4", "/dev/sdb1"]),
+                CommandRisk::Destructive,
+            ),
+            (
+                vec_str(&["find", ".", "-name", "*.o", "-delete"]),
+                CommandRisk::Destructive,
+            ),
+            (
+                vec_str(&["bash", "-lc", "cargo fmt && sudo make install"]),
+                CommandRisk::Destructive,
+            ),
+        ] {
+            assert_eq!(classify_command(&command), expected, "{command:?}");
+        }
+    }
+
+    #[test]
+    fn safe_commands_match_by_prefix() {
+        let safe_commands = vec![
+            vec_str(&["cargo", "test"]),
+            vec_str(&["npm", "run", "lint"]),
+        ];
+
+        assert!(matches_safe_command(
+            &vec_str(&["cargo", "test", "-p", "core"]),
+            &safe_commands
+        ));
+        assert!(matches_safe_command(
+            &vec_str(&["npm", "run", "lint"]),
+            &safe_commands
+        ));
+        assert!(!matches_safe_command(
+            &vec_str(&["cargo", "testing"]),
+            &safe_commands
+        ));
+        assert!(!matches_safe_command(
+            &vec_str(&["npm", "run"]),
+            &safe_commands
+        ));
+    }
+}
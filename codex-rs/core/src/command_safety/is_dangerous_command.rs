@@ -144,7 +144,7 @@ fn git_branch_is_delete(branch_args: &[String]) -> bool {
     })
 }
 
-fn short_flag_group_contains(arg: &str, target: char) -> bool {
+pub(crate) fn short_flag_group_contains(arg: &str, target: char) -> bool {
     arg.starts_with('-') && !arg.starts_with("--") && arg.chars().skip(1).any(|c| c == target)
 }
 
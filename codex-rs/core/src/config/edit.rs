@@ -1,4 +1,5 @@
 use crate::config::CONFIG_TOML_FILE;
+use crate::config::types::ApprovalRuleToml;
 use crate::config::types::McpServerConfig;
 use crate::config::types::Notice;
 use crate::path_utils::resolve_symlink_write_paths;
@@ -6,6 +7,7 @@ use crate::path_utils::write_atomically;
 use anyhow::Context;
 use codex_protocol::config_types::Personality;
 use codex_protocol::config_types::TrustLevel;
+use codex_protocol::config_types::Verbosity;
 use codex_protocol::openai_models::ReasoningEffort;
 use std::collections::BTreeMap;
 use std::path::Path;
@@ -27,6 +29,8 @@ pub enum ConfigEdit {
     },
     /// Update the active (or default) model personality.
     SetModelPersonality { personality: Option<Personality> },
+    /// Update the active (or default) model output verbosity.
+    SetModelVerbosity { verbosity: Option<Verbosity> },
     /// Toggle the acknowledgement flag under `[notice]`.
     SetNoticeHideFullAccessWarning(bool),
     /// Toggle the Windows world-writable directories warning acknowledgement flag.
@@ -43,6 +47,8 @@ pub enum ConfigEdit {
     ReplaceMcpServers(BTreeMap<String, McpServerConfig>),
     /// Set or clear a skill config entry under `[[skills.config]]`.
     SetSkillConfig { path: PathBuf, enabled: bool },
+    /// Append an entry to `[[approval.rules]]`.
+    AppendApprovalRule(ApprovalRuleToml),
     /// Set trust_level under `[projects."<path>"]`,
     /// migrating inline tables to explicit tables.
     SetProjectTrustLevel { path: PathBuf, level: TrustLevel },
@@ -130,8 +136,17 @@ mod document_helpers {
                 bearer_token_env_var,
                 http_headers,
                 env_http_headers,
+            }
+            | McpServerTransportConfig::Sse {
+                url,
+                bearer_token_env_var,
+                http_headers,
+                env_http_headers,
             } => {
                 entry["url"] = value(url.clone());
+                if matches!(config.transport, McpServerTransportConfig::Sse { .. }) {
+                    entry["transport"] = value("sse");
+                }
                 if let Some(env_var) = bearer_token_env_var {
                     entry["bearer_token_env_var"] = value(env_var.clone());
                 }
@@ -172,6 +187,42 @@ mod document_helpers {
         {
             entry["scopes"] = array_from_iter(scopes.iter().cloned());
         }
+        if config.lazy_start {
+            entry["lazy_start"] = value(true);
+        }
+        if let Some(aliases) = &config.tool_aliases
+            && !aliases.is_empty()
+        {
+            entry["tool_aliases"] = table_from_pairs(aliases.iter());
+        }
+        if let Some(approval) = config.tool_approval {
+            entry["tool_approval"] = value(approval.as_str());
+        }
+        if let Some(approvals) = &config.tool_approvals
+            && !approvals.is_empty()
+        {
+            let approvals: Vec<(String, String)> = approvals
+                .iter()
+                .map(|(tool, approval)| (tool.clone(), approval.to_string()))
+                .collect();
+            entry["tool_approvals"] =
+                table_from_pairs(approvals.iter().map(|(tool, approval)| (tool, approval)));
+        }
+        if let Some(limit) = config.tool_output_token_limit {
+            entry["tool_output_token_limit"] = value(i64::try_from(limit).unwrap_or(i64::MAX));
+        }
+        if let Some(limits) = &config.tool_output_token_limits
+            && !limits.is_empty()
+        {
+            let mut table = TomlTable::new();
+            table.set_implicit(false);
+            let mut limits: Vec<_> = limits.iter().collect();
+            limits.sort_by(|(a, _), (b, _)| a.cmp(b));
+            for (tool, limit) in limits {
+                table.insert(tool, value(i64::try_from(*limit).unwrap_or(i64::MAX)));
+            }
+            entry["tool_output_token_limits"] = TomlItem::Table(table);
+        }
 
         entry
     }
@@ -281,6 +332,10 @@ impl ConfigDocument {
                 &["personality"],
                 personality.map(|personality| value(personality.to_string())),
             )),
+            ConfigEdit::SetModelVerbosity { verbosity } => Ok(self.write_profile_value(
+                &["model_verbosity"],
+                verbosity.map(|verbosity| value(verbosity.to_string())),
+            )),
             ConfigEdit::SetNoticeHideFullAccessWarning(acknowledged) => Ok(self.write_value(
                 Scope::Global,
                 &[Notice::TABLE_KEY, "hide_full_access_warning"],
@@ -317,6 +372,7 @@ impl ConfigDocument {
             ConfigEdit::SetSkillConfig { path, enabled } => {
                 Ok(self.set_skill_config(path.as_path(), *enabled))
             }
+            ConfigEdit::AppendApprovalRule(rule) => self.append_approval_rule(rule),
             ConfigEdit::SetPath { segments, value } => Ok(self.insert(segments, value.clone())),
             ConfigEdit::ClearPath { segments } => Ok(self.clear_owned(segments)),
             ConfigEdit::SetProjectTrustLevel { path, level } => {
@@ -406,6 +462,31 @@ impl ConfigDocument {
         true
     }
 
+    fn append_approval_rule(&mut self, rule: &ApprovalRuleToml) -> anyhow::Result<bool> {
+        let value = toml::Value::try_from(rule).context("failed to serialize approval rule")?;
+        let TomlItem::Table(mut rule_table) = crate::config::service::toml_value_to_item(&value)?
+        else {
+            return Ok(false);
+        };
+        rule_table.set_implicit(false);
+
+        let root = self.doc.as_table_mut();
+        let approval_item = root
+            .entry("approval")
+            .or_insert_with(|| TomlItem::Table(document_helpers::new_implicit_table()));
+        let Some(approval_table) = document_helpers::ensure_table_for_write(approval_item) else {
+            return Ok(false);
+        };
+        let rules_item = approval_table
+            .entry("rules")
+            .or_insert_with(|| TomlItem::ArrayOfTables(ArrayOfTables::new()));
+        let TomlItem::ArrayOfTables(rules) = rules_item else {
+            return Ok(false);
+        };
+        rules.push(rule_table);
+        Ok(true)
+    }
+
     fn set_skill_config(&mut self, path: &Path, enabled: bool) -> bool {
         let normalized_path = normalize_skill_config_path(path);
         let mut remove_skills_table = false;
@@ -730,6 +811,11 @@ impl ConfigEditsBuilder {
         self
     }
 
+    pub fn set_verbosity(mut self, verbosity: Option<Verbosity>) -> Self {
+        self.edits.push(ConfigEdit::SetModelVerbosity { verbosity });
+        self
+    }
+
     pub fn set_hide_full_access_warning(mut self, acknowledged: bool) -> Self {
         self.edits
             .push(ConfigEdit::SetNoticeHideFullAccessWarning(acknowledged));
@@ -798,6 +884,26 @@ impl ConfigEditsBuilder {
         self
     }
 
+    /// Enable or disable a feature flag by key under the `[experimental]`
+    /// table, dropping any `[features]` entry for it so the two cannot disagree.
+    pub fn set_experiment_enabled(mut self, key: &str, enabled: bool) -> Self {
+        self.edits.push(ConfigEdit::ClearPath {
+            segments: vec!["features".to_string(), key.to_string()],
+        });
+        self.edits.push(ConfigEdit::SetPath {
+            segments: vec!["experimental".to_string(), key.to_string()],
+            value: value(enabled),
+        });
+        self
+    }
+
+    /// Set the value at the exact dotted path, e.g. `["tui", "notifications"]`.
+    pub fn set_path(mut self, segments: Vec<String>, value: &toml::Value) -> anyhow::Result<Self> {
+        let value = crate::config::service::toml_value_to_item(value)?;
+        self.edits.push(ConfigEdit::SetPath { segments, value });
+        Ok(self)
+    }
+
     pub fn with_edits<I>(mut self, edits: I) -> Self
     where
         I: IntoIterator<Item = ConfigEdit>,
@@ -824,6 +930,8 @@ impl ConfigEditsBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::types::ApprovalRuleDecision;
+    use crate::config::types::ApprovalRuleTool;
     use crate::config::types::McpServerTransportConfig;
     use codex_protocol::openai_models::ReasoningEffort;
     use pretty_assertions::assert_eq;
@@ -873,6 +981,35 @@ model_reasoning_effort = "high"
         assert_eq!(contents, "enabled = true\n");
     }
 
+    #[test]
+    fn set_experiment_enabled_moves_flag_out_of_features() {
+        let tmp = tempdir().expect("tmpdir");
+        let codex_home = tmp.path();
+        std::fs::write(
+            codex_home.join(CONFIG_TOML_FILE),
+            r#"[features]
+steer = false
+shell_tool = true
+"#,
+        )
+        .expect("seed config");
+
+        ConfigEditsBuilder::new(codex_home)
+            .set_experiment_enabled("steer", true)
+            .apply_blocking()
+            .expect("persist");
+
+        let contents =
+            std::fs::read_to_string(codex_home.join(CONFIG_TOML_FILE)).expect("read config");
+        let expected = r#"[features]
+shell_tool = true
+
+[experimental]
+steer = true
+"#;
+        assert_eq!(contents, expected);
+    }
+
     #[test]
     fn set_skill_config_writes_disabled_entry() {
         let tmp = tempdir().expect("tmpdir");
@@ -895,6 +1032,43 @@ enabled = false
         assert_eq!(contents, expected);
     }
 
+    #[test]
+    fn append_approval_rule_adds_array_of_tables_entry() {
+        let tmp = tempdir().expect("tmpdir");
+        let codex_home = tmp.path();
+        std::fs::write(
+            codex_home.join(CONFIG_TOML_FILE),
+            r#"[approval]
+safe_commands = ["cargo test"]
+"#,
+        )
+        .expect("seed config");
+
+        ConfigEditsBuilder::new(codex_home)
+            .with_edits([ConfigEdit::AppendApprovalRule(ApprovalRuleToml {
+                tool: Some(ApprovalRuleTool::ApplyPatch),
+                command: None,
+                paths: Some(vec!["/repo/src/lib.rs".to_string()]),
+                sandbox: None,
+                decision: ApprovalRuleDecision::Allow,
+                reason: None,
+            })])
+            .apply_blocking()
+            .expect("persist");
+
+        let contents =
+            std::fs::read_to_string(codex_home.join(CONFIG_TOML_FILE)).expect("read config");
+        let expected = r#"[approval]
+safe_commands = ["cargo test"]
+
+[[approval.rules]]
+tool = "apply_patch"
+paths = ["/repo/src/lib.rs"]
+decision = "allow"
+"#;
+        assert_eq!(contents, expected);
+    }
+
     #[test]
     fn set_skill_config_removes_entry_when_enabled() {
         let tmp = tempdir().expect("tmpdir");
@@ -1165,6 +1339,40 @@ model = "o5-preview"
         assert_eq!(contents, expected);
     }
 
+    #[test]
+    fn blocking_set_verbosity_scopes_to_active_profile() {
+        let tmp = tempdir().expect("tmpdir");
+        let codex_home = tmp.path();
+        std::fs::write(
+            codex_home.join(CONFIG_TOML_FILE),
+            r#"profile = "team"
+
+[profiles.team]
+model = "gpt-5.2"
+"#,
+        )
+        .expect("seed");
+
+        apply_blocking(
+            codex_home,
+            None,
+            &[ConfigEdit::SetModelVerbosity {
+                verbosity: Some(Verbosity::Low),
+            }],
+        )
+        .expect("persist");
+
+        let contents =
+            std::fs::read_to_string(codex_home.join(CONFIG_TOML_FILE)).expect("read config");
+        let expected = r#"profile = "team"
+
+[profiles.team]
+model = "gpt-5.2"
+model_verbosity = "low"
+"#;
+        assert_eq!(contents, expected);
+    }
+
     #[test]
     fn blocking_set_model_with_explicit_profile() {
         let tmp = tempdir().expect("tmpdir");
@@ -1379,6 +1587,12 @@ gpt-5 = "gpt-5.1"
                 enabled_tools: Some(vec!["one".to_string(), "two".to_string()]),
                 disabled_tools: None,
                 scopes: None,
+                lazy_start: false,
+                tool_aliases: None,
+                tool_approval: None,
+                tool_approvals: None,
+                tool_output_token_limit: None,
+                tool_output_token_limits: None,
             },
         );
 
@@ -1402,6 +1616,12 @@ gpt-5 = "gpt-5.1"
                 enabled_tools: None,
                 disabled_tools: Some(vec!["forbidden".to_string()]),
                 scopes: None,
+                lazy_start: false,
+                tool_aliases: None,
+                tool_approval: None,
+                tool_approvals: None,
+                tool_output_token_limit: None,
+                tool_output_token_limits: None,
             },
         );
 
@@ -1468,6 +1688,12 @@ foo = { command = "cmd" }
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                lazy_start: false,
+                tool_aliases: None,
+                tool_approval: None,
+                tool_approvals: None,
+                tool_output_token_limit: None,
+                tool_output_token_limits: None,
             },
         );
 
@@ -1513,6 +1739,12 @@ foo = { command = "cmd" } # keep me
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                lazy_start: false,
+                tool_aliases: None,
+                tool_approval: None,
+                tool_approvals: None,
+                tool_output_token_limit: None,
+                tool_output_token_limits: None,
             },
         );
 
@@ -1557,6 +1789,12 @@ foo = { command = "cmd", args = ["--flag"] } # keep me
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                lazy_start: false,
+                tool_aliases: None,
+                tool_approval: None,
+                tool_approvals: None,
+                tool_output_token_limit: None,
+                tool_output_token_limits: None,
             },
         );
 
@@ -1602,6 +1840,12 @@ foo = { command = "cmd" }
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                lazy_start: false,
+                tool_aliases: None,
+                tool_approval: None,
+                tool_approvals: None,
+                tool_output_token_limit: None,
+                tool_output_token_limits: None,
             },
         );
 
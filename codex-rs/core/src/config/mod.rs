@@ -1,23 +1,69 @@
 use crate::auth::AuthCredentialsStoreMode;
+use crate::auth::account_auth_home;
+use crate::auth::validate_account_name;
 use crate::config::edit::ConfigEdit;
 use crate::config::edit::ConfigEditsBuilder;
+use crate::config::types::ApprovalConfig;
+use crate::config::types::ApprovalToml;
+use crate::config::types::AttributionConfig;
+use crate::config::types::AttributionToml;
+use crate::config::types::CodeIndexConfig;
+use crate::config::types::CodeIndexToml;
+use crate::config::types::ContainerConfig;
+use crate::config::types::ContainerToml;
+use crate::config::types::CredentialsConfig;
+use crate::config::types::CredentialsToml;
 use crate::config::types::DEFAULT_OTEL_ENVIRONMENT;
+use crate::config::types::DEFAULT_PASTE_ATTACH_THRESHOLD;
+use crate::config::types::ExecLimitsConfig;
+use crate::config::types::ExecLimitsToml;
+use crate::config::types::FileApprovalsConfig;
+use crate::config::types::FileApprovalsToml;
 use crate::config::types::History;
+use crate::config::types::HooksConfig;
+use crate::config::types::HooksToml;
+use crate::config::types::LinuxSandboxConfig;
+use crate::config::types::LinuxSandboxToml;
+use crate::config::types::LspConfig;
+use crate::config::types::LspToml;
 use crate::config::types::McpServerConfig;
 use crate::config::types::McpServerDisabledReason;
 use crate::config::types::McpServerTransportConfig;
+use crate::config::types::ModelFallback;
+use crate::config::types::ModelFallbackToml;
+use crate::config::types::ModelPrice;
+use crate::config::types::NetworkProxyToml;
 use crate::config::types::Notice;
 use crate::config::types::NotificationMethod;
 use crate::config::types::Notifications;
+use crate::config::types::NotifyMode;
 use crate::config::types::OtelConfig;
 use crate::config::types::OtelConfigToml;
 use crate::config::types::OtelExporterKind;
+use crate::config::types::PatchLimitsConfig;
+use crate::config::types::PatchLimitsToml;
+use crate::config::types::PromptCacheKeyScope;
+use crate::config::types::PullRequestsConfig;
+use crate::config::types::PullRequestsToml;
+use crate::config::types::RedactionConfig;
+use crate::config::types::RedactionToml;
+use crate::config::types::RemoteConfig;
+use crate::config::types::RemoteToml;
 use crate::config::types::SandboxWorkspaceWrite;
+use crate::config::types::SessionEncryptionMode;
+use crate::config::types::SessionRetentionConfig;
+use crate::config::types::SessionRetentionToml;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::config::types::ShellEnvironmentPolicyToml;
 use crate::config::types::SkillsConfig;
+use crate::config::types::ToolFilter;
+use crate::config::types::TrashConfig;
+use crate::config::types::TrashToml;
+use crate::config::types::TrustPreset;
+use crate::config::types::TrustPresetsToml;
 use crate::config::types::Tui;
 use crate::config::types::UriBasedFileOpener;
+use crate::config::types::WebSearchBackend;
 use crate::config_loader::CloudRequirementsLoader;
 use crate::config_loader::ConfigLayerStack;
 use crate::config_loader::ConfigRequirements;
@@ -41,9 +87,12 @@ use crate::project_doc::DEFAULT_PROJECT_DOC_FILENAME;
 use crate::project_doc::LOCAL_PROJECT_DOC_FILENAME;
 use crate::protocol::AskForApproval;
 use crate::protocol::SandboxPolicy;
+use crate::redaction::Redactor;
+use crate::run_budget::RunBudget;
 use crate::windows_sandbox::WindowsSandboxLevelExt;
 use codex_app_server_protocol::Tools;
 use codex_app_server_protocol::UserSavedConfig;
+use codex_apply_patch::DEFAULT_FUZZ_FACTOR;
 use codex_protocol::config_types::AltScreenMode;
 use codex_protocol::config_types::ForcedLoginMethod;
 use codex_protocol::config_types::ModeKind;
@@ -65,8 +114,10 @@ use similar::DiffableStr;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::io::ErrorKind;
+use std::net::SocketAddr;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::Arc;
 #[cfg(test)]
 use tempfile::tempdir;
 
@@ -94,6 +145,8 @@ pub use codex_git::GhostSnapshotConfig;
 /// the context window.
 pub(crate) const PROJECT_DOC_MAX_BYTES: usize = 32 * 1024; // 32 KiB
 pub(crate) const DEFAULT_AGENT_MAX_THREADS: Option<usize> = Some(6);
+/// Default budget for the repository map; see `repo_map_max_tokens`.
+pub(crate) const DEFAULT_REPO_MAP_MAX_TOKENS: usize = 2_000;
 
 pub const CONFIG_TOML_FILE: &str = "config.toml";
 
@@ -118,6 +171,10 @@ pub struct Config {
     /// Optional override of model selection.
     pub model: Option<String>,
 
+    /// Dated model snapshot the session is pinned to, when `model` comes from
+    /// a `model_snapshot` pin rather than a plain model name.
+    pub model_snapshot: Option<String>,
+
     /// Model used specifically for review sessions.
     pub review_model: Option<String>,
 
@@ -127,12 +184,24 @@ pub struct Config {
     /// Token usage threshold triggering auto-compaction of conversation history.
     pub model_auto_compact_token_limit: Option<i64>,
 
+    /// Prices per model, used to estimate spend for `codex exec --max-cost`.
+    pub model_prices: HashMap<String, ModelPrice>,
+
+    /// Limits on the whole run from `codex exec --max-turns`, `--max-tokens`
+    /// and `--max-cost`. Never read from config.toml; sub-agents inherit it
+    /// with the rest of the config and share its totals.
+    pub run_budget: Option<Arc<RunBudget>>,
+
     /// Key into the model_providers map that specifies which provider to use.
     pub model_provider_id: String,
 
     /// Info needed to make an API request to the model.
     pub model_provider: ModelProviderInfo,
 
+    /// Providers to retry a turn on, in order, when the primary provider keeps
+    /// returning server errors or rate limits.
+    pub model_fallbacks: Vec<ModelFallback>,
+
     /// Optionally specify the personality of the model
     pub personality: Option<Personality>,
 
@@ -199,8 +268,12 @@ pub struct Config {
     /// If unset the feature is disabled.
     pub notify: Option<Vec<String>>,
 
+    /// Whether `notify` runs once per notification or stays running for the
+    /// whole session, reading NDJSON payloads from stdin.
+    pub notify_mode: NotifyMode,
+
     /// TUI notifications preference. When set, the TUI will send terminal notifications on
-    /// approvals and turn completions when not focused.
+    /// approvals, turn completions, and blocked turns when not focused.
     pub tui_notifications: Notifications,
 
     /// Notification method for terminal notifications (osc9 or bel).
@@ -223,6 +296,11 @@ pub struct Config {
     /// - `never`: Never use alternate screen (inline mode, preserves scrollback).
     pub tui_alternate_screen: AltScreenMode,
 
+    /// Pastes longer than this many characters prompt to be attached as a
+    /// file instead of inserted inline (`tui.paste_attach_threshold`). `0`
+    /// disables the prompt.
+    pub tui_paste_attach_threshold: usize,
+
     /// The directory that should be treated as the current working directory
     /// for the session. All relative paths inside the business-logic layer are
     /// resolved against this path.
@@ -234,6 +312,10 @@ pub struct Config {
     /// auto: Use the OS-specific keyring service if available, otherwise use a file.
     pub cli_auth_credentials_store_mode: AuthCredentialsStoreMode,
 
+    /// Named login account whose credentials this session uses, as created by
+    /// `codex login --name <account>`. `None` uses the default login.
+    pub account: Option<String>,
+
     /// Definition for MCP servers that Codex can reach out to for tool calls.
     pub mcp_servers: Constrained<HashMap<String, McpServerConfig>>,
 
@@ -263,9 +345,16 @@ pub struct Config {
     /// Token budget applied when storing tool/function outputs in the context manager.
     pub tool_output_token_limit: Option<usize>,
 
+    /// Token budget shared by all MCP tool results in one turn. Results past
+    /// it are cut down and saved for `read_output`.
+    pub mcp_turn_output_token_limit: Option<usize>,
+
     /// Maximum number of agent threads that can be open concurrently.
     pub agent_max_threads: Option<usize>,
 
+    /// Tokens each spawned sub-agent may use before its turn is stopped.
+    pub agent_token_budget: Option<i64>,
+
     /// Directory containing all Codex state (defaults to `~/.codex` but can be
     /// overridden by the `CODEX_HOME` environment variable).
     pub codex_home: PathBuf,
@@ -276,10 +365,22 @@ pub struct Config {
     /// When true, session is not persisted on disk. Default to `false`
     pub ephemeral: bool,
 
+    /// Directory that raw model API requests and responses are recorded to
+    /// (`--record-api`), one JSON Lines file per session.
+    pub record_api_dir: Option<PathBuf>,
+
+    /// Recording whose responses are served instead of calling the model
+    /// API (`--replay-api`).
+    pub replay_api_path: Option<PathBuf>,
+
     /// Optional URI-based file opener. If set, citations to files in the model
     /// output will be hyperlinked using the specified URI scheme.
     pub file_opener: UriBasedFileOpener,
 
+    /// Command run when the agent asks to show the user a file (`open_file`
+    /// tool). `{path}` and `{line}` in its arguments are replaced.
+    pub open_file_command: Option<Vec<String>>,
+
     /// Path to the `codex-linux-sandbox` executable. This must be set if
     /// [`crate::exec::SandboxType::LinuxSeccomp`] is used. Note that this
     /// cannot be set in the config file: it must be set in code via
@@ -299,9 +400,15 @@ pub struct Config {
     /// Optional override to force-enable reasoning summaries for the configured model.
     pub model_supports_reasoning_summaries: Option<bool>,
 
+    /// Optional override for whether the model may issue parallel tool calls.
+    pub model_supports_parallel_tool_calls: Option<bool>,
+
     /// Optional verbosity control for GPT-5 models (Responses API `text.verbosity`).
     pub model_verbosity: Option<Verbosity>,
 
+    /// How the prompt cache key sent with model requests is derived.
+    pub prompt_cache_key: PromptCacheKeyScope,
+
     /// Base URL for requests to ChatGPT (as opposed to the OpenAI API).
     pub chatgpt_base_url: String,
 
@@ -319,12 +426,88 @@ pub struct Config {
     /// Explicit or feature-derived web search mode.
     pub web_search_mode: Option<WebSearchMode>,
 
+    /// Search backend behind the `web_search` tool.
+    pub web_search_backend: WebSearchBackend,
+
     /// If set to `true`, used only the experimental unified exec tool.
     pub use_experimental_unified_exec_tool: bool,
 
     /// Settings for ghost snapshots (used for undo).
     pub ghost_snapshot: GhostSnapshotConfig,
 
+    /// Settings for the session trash that preserves deleted files.
+    pub trash: TrashConfig,
+
+    /// Embedding endpoint used to build and query the code index.
+    pub code_index: CodeIndexConfig,
+
+    /// Default timeout and resource limits for shell tool commands.
+    pub exec_limits: ExecLimitsConfig,
+
+    /// Loopback address of the network proxy that sandboxed commands without
+    /// network access may connect to, when `[network_proxy]` is enabled.
+    pub network_proxy: Option<SocketAddr>,
+
+    /// Extra restrictions for the Linux sandbox from `[linux_sandbox]`.
+    pub linux_sandbox: LinuxSandboxConfig,
+
+    /// Container the shell tools run in, from `[container]`.
+    pub container: ContainerConfig,
+
+    /// Remote host the shell tools run on, from `[remote]`.
+    pub remote: RemoteConfig,
+
+    /// Per-operation approval policies for file reads, writes, and deletions.
+    pub file_approvals: FileApprovalsConfig,
+
+    /// Shell command approval settings from `[approval]`.
+    pub approval: ApprovalConfig,
+
+    /// Whether agent commands may use the SSH agent and git credential helpers.
+    pub credentials: CredentialsConfig,
+
+    /// Attribution trailers added to commits and patches the agent creates.
+    pub attribution: AttributionConfig,
+
+    /// Secret masking applied to prompts, rollouts, logs, and notifications.
+    pub redaction: RedactionConfig,
+
+    /// How rollout files are encrypted at rest.
+    pub session_encryption: SessionEncryptionMode,
+
+    /// Limits enforced when old sessions and logs are pruned.
+    pub session_retention: SessionRetentionConfig,
+
+    /// Run new interactive sessions in their own git worktree and branch.
+    pub session_worktree: bool,
+
+    /// Remote and credentials used to open pull requests.
+    pub pull_requests: PullRequestsConfig,
+
+    /// Token budget for the repository map inserted by the `repo_map` feature.
+    pub repo_map_max_tokens: usize,
+
+    /// Language servers that report diagnostics after patches are applied.
+    pub lsp: LspConfig,
+
+    /// Commands run after applied patches and before commits.
+    pub hooks: HooksConfig,
+
+    /// Context lines a patch hunk may drop from each end when its full
+    /// context is not found; zero requires exact context.
+    pub apply_patch_fuzz: usize,
+
+    /// Globs for paths that patches may only change with the user's explicit
+    /// approval and that sandboxed commands see as read-only.
+    pub write_protected: Vec<String>,
+
+    /// Tools offered to the model, from `enabled_tools` and `disabled_tools`.
+    pub tool_filter: ToolFilter,
+
+    /// Size limits that stop patches from rewriting binary or very large
+    /// files wholesale.
+    pub patch_limits: PatchLimitsConfig,
+
     /// Centralized feature flags; source of truth for feature gating.
     pub features: Features,
 
@@ -338,6 +521,9 @@ pub struct Config {
     /// is (1) part of a git repo, (2) a git worktree, or (3) just using the cwd
     pub active_project: ProjectConfig,
 
+    /// Capabilities from the active project's trust level, if it has one.
+    pub trust_preset: Option<TrustPreset>,
+
     /// Tracks whether the Windows onboarding screen has been acknowledged.
     pub windows_wsl_setup_acknowledged: bool,
 
@@ -455,12 +641,14 @@ impl ConfigBuilder {
                 return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, err));
             }
         };
-        Config::load_config_with_layer_stack(
+        let config = Config::load_config_with_layer_stack(
             config_toml,
             harness_overrides,
             codex_home,
             config_layer_stack,
-        )
+        )?;
+        crate::rollout::encryption::load_session_key(&config);
+        Ok(config)
     }
 }
 
@@ -679,7 +867,7 @@ pub(crate) fn set_project_trust_level_inner(
     //
     // [projects]
     // [projects."/path/to/project"]
-    // trust_level = "trusted" or "untrusted"
+    // trust_level = "trusted", "limited", or "untrusted"
     //
     // rather than inline tables like:
     //
@@ -788,18 +976,31 @@ pub fn set_default_oss_provider(codex_home: &Path, provider: &str) -> std::io::R
 pub struct ConfigToml {
     /// Optional override of model selection.
     pub model: Option<String>,
+    /// Exact dated model snapshot to pin (for example `gpt-5-2025-08-07`).
+    /// Takes precedence over `model`; Codex warns when the provider no longer
+    /// serves it.
+    pub model_snapshot: Option<String>,
     /// Review model override used by the `/review` feature.
     pub review_model: Option<String>,
 
     /// Provider to use from the model_providers map.
     pub model_provider: Option<String>,
 
+    /// Providers to retry a turn on, in order, when `model_provider` keeps
+    /// returning 5xx or 429 errors after its retries are exhausted.
+    /// Defaults to `[]`.
+    pub model_fallbacks: Option<Vec<ModelFallbackToml>>,
+
     /// Size of the context window for the model, in tokens.
     pub model_context_window: Option<i64>,
 
     /// Token usage threshold triggering auto-compaction of conversation history.
     pub model_auto_compact_token_limit: Option<i64>,
 
+    /// Prices in USD per million tokens, keyed by model name. Only used to
+    /// estimate spend for `codex exec --max-cost`.
+    pub model_prices: Option<HashMap<String, ModelPrice>>,
+
     /// Default approval policy for executing commands.
     pub approval_policy: Option<AskForApproval>,
 
@@ -816,6 +1017,11 @@ pub struct ConfigToml {
     #[serde(default)]
     pub notify: Option<Vec<String>>,
 
+    /// Run `notify` once per notification (`per-event`) or once per session
+    /// with one JSON payload per stdin line (`persistent`).
+    /// Defaults to `per-event`.
+    pub notify_mode: Option<NotifyMode>,
+
     /// System instructions.
     pub instructions: Option<String>,
 
@@ -847,6 +1053,10 @@ pub struct ConfigToml {
     #[serde(default)]
     pub cli_auth_credentials_store: Option<AuthCredentialsStoreMode>,
 
+    /// Named login account to use, as created by `codex login --name <account>`.
+    /// Defaults to the unnamed login.
+    pub account: Option<String>,
+
     /// Definition for MCP servers that Codex can reach out to for tool calls.
     #[serde(default)]
     // Uses the raw MCP input shape (custom deserialization) rather than `McpServerConfig`.
@@ -878,6 +1088,10 @@ pub struct ConfigToml {
     /// Token budget applied when storing tool/function outputs in the context manager.
     pub tool_output_token_limit: Option<usize>,
 
+    /// Token budget shared by all MCP tool results in one turn. Results past
+    /// it are cut down and saved for `read_output`.
+    pub mcp_turn_output_token_limit: Option<usize>,
+
     /// Profile to use from the `profiles` map.
     pub profile: Option<String>,
 
@@ -893,6 +1107,12 @@ pub struct ConfigToml {
     /// output will be hyperlinked using the specified URI scheme.
     pub file_opener: Option<UriBasedFileOpener>,
 
+    /// Command run when the agent asks to show the user a file (`open_file`
+    /// tool), e.g. `["code", "--goto", "{path}:{line}"]`. `{path}` and
+    /// `{line}` in its arguments are replaced. When unset, the request is left
+    /// to the host editor, and the TUI shows the location as a link.
+    pub open_file_command: Option<Vec<String>>,
+
     /// Collection of settings that are specific to the TUI.
     pub tui: Option<Tui>,
 
@@ -909,9 +1129,17 @@ pub struct ConfigToml {
     /// Optional verbosity control for GPT-5 models (Responses API `text.verbosity`).
     pub model_verbosity: Option<Verbosity>,
 
+    /// Scope of the `prompt_cache_key` sent with model requests: `session`,
+    /// `workspace` (shared by sessions in the same directory with the same
+    /// model), or `disabled`. Defaults to `session`.
+    pub prompt_cache_key: Option<PromptCacheKeyScope>,
+
     /// Override to force-enable reasoning summaries for the configured model.
     pub model_supports_reasoning_summaries: Option<bool>,
 
+    /// Override whether the configured model may issue parallel tool calls.
+    pub model_supports_parallel_tool_calls: Option<bool>,
+
     /// Optionally specify a personality for the model
     pub personality: Option<Personality>,
 
@@ -920,9 +1148,17 @@ pub struct ConfigToml {
 
     pub projects: Option<HashMap<String, ProjectConfig>>,
 
+    /// Overrides for the sandbox, approval policy, MCP servers, and web
+    /// search granted at each project trust level.
+    pub trust_levels: Option<TrustPresetsToml>,
+
     /// Controls the web search tool mode: disabled, cached, or live.
     pub web_search: Option<WebSearchMode>,
 
+    /// Search backend behind the `web_search` tool: `native` (the model
+    /// provider's search, the default), `searxng`, or `brave`.
+    pub web_search_backend: Option<WebSearchBackend>,
+
     /// Nested tools section for feature toggles
     pub tools: Option<ToolsToml>,
 
@@ -938,6 +1174,12 @@ pub struct ConfigToml {
     #[schemars(schema_with = "crate::config::schema::features_schema")]
     pub features: Option<FeaturesToml>,
 
+    /// Toggles for experimental features, as set from the `/experimental`
+    /// menu. Applied after `[features]`.
+    #[serde(default)]
+    #[schemars(schema_with = "crate::config::schema::experimental_features_schema")]
+    pub experimental: Option<FeaturesToml>,
+
     /// Suppress warnings about unstable (under development) features.
     pub suppress_unstable_features_warning: Option<bool>,
 
@@ -945,6 +1187,125 @@ pub struct ConfigToml {
     #[serde(default)]
     pub ghost_snapshot: Option<GhostSnapshotToml>,
 
+    /// Settings for the session trash that preserves files deleted by the agent.
+    #[serde(default)]
+    pub trash: Option<TrashToml>,
+
+    /// Embedding endpoint used by `codex index build` and `search_code`.
+    #[serde(default)]
+    pub code_index: Option<CodeIndexToml>,
+
+    /// Default timeout and resource limits for shell tool commands.
+    #[serde(default)]
+    pub exec_limits: Option<ExecLimitsToml>,
+
+    /// Local network proxy for limited network access from the sandbox.
+    #[serde(default)]
+    pub network_proxy: Option<NetworkProxyToml>,
+
+    /// Extra restrictions for the Linux sandbox.
+    #[serde(default)]
+    pub linux_sandbox: Option<LinuxSandboxToml>,
+
+    /// Run the shell tools in a container.
+    #[serde(default)]
+    pub container: Option<ContainerToml>,
+
+    /// Run the shell tools on a remote host over SSH.
+    #[serde(default)]
+    pub remote: Option<RemoteToml>,
+
+    /// Approval policies for reads outside the workspace, file writes, and
+    /// file deletions, each independent of `approval_policy`.
+    #[serde(default)]
+    pub file_approvals: Option<FileApprovalsToml>,
+
+    /// Approval settings for shell commands.
+    #[serde(default)]
+    pub approval: Option<ApprovalToml>,
+
+    /// Whether commands run by the agent can use the SSH agent and git
+    /// credential helpers.
+    #[serde(default)]
+    pub credentials: Option<CredentialsToml>,
+
+    /// Attribution trailers (`Co-authored-by`, session id) for commits and
+    /// patches created by the agent.
+    #[serde(default)]
+    pub attribution: Option<AttributionToml>,
+
+    /// Secret patterns masked in model prompts, rollout files, session logs,
+    /// and notification payloads.
+    #[serde(default)]
+    pub redaction: Option<RedactionToml>,
+
+    /// Encrypt rollout files at rest: `off`, `keyring` (key kept in the OS
+    /// keychain), or `passphrase` (key derived from the
+    /// `CODEX_SESSION_PASSPHRASE` environment variable).
+    /// Defaults to `off`.
+    pub session_encryption: Option<SessionEncryptionMode>,
+
+    /// Limits on the age and size of recorded sessions and logs, enforced in
+    /// the background and by `codex sessions prune`.
+    #[serde(default)]
+    pub session_retention: Option<SessionRetentionToml>,
+
+    /// Run each new interactive session in a dedicated git worktree on a fresh
+    /// branch, leaving the checkout untouched until the work is merged with
+    /// `/merge`. Defaults to `false`.
+    pub session_worktree: Option<bool>,
+
+    /// Remote and token sources used by `/pr` to push the current branch and
+    /// open a GitHub pull request or GitLab merge request.
+    #[serde(default)]
+    pub pull_requests: Option<PullRequestsToml>,
+
+    /// Approximate number of tokens the repository map may use when the
+    /// `repo_map` feature is enabled. Defaults to 2000.
+    pub repo_map_max_tokens: Option<usize>,
+
+    /// Language servers started on demand after `apply_patch` edits a file
+    /// they handle; their diagnostics are returned with the patch result.
+    #[serde(default)]
+    pub lsp: Option<LspToml>,
+
+    /// Commands such as formatters or linters run after the agent applies a
+    /// patch (`[[hooks.post_patch]]`) or before it commits
+    /// (`[[hooks.pre_commit]]`).
+    #[serde(default)]
+    pub hooks: Option<HooksToml>,
+
+    /// Context lines `apply_patch` may drop from each end of a hunk whose
+    /// surrounding lines have drifted, and whether a hunk may be placed
+    /// without its `@@` line. Fuzzy matches must be unique. Defaults to 2; set
+    /// to 0 to require every context line.
+    pub apply_patch_fuzz: Option<usize>,
+
+    /// Globs, relative to the working directory unless they start with `/`,
+    /// for paths the agent may not change without asking: patches touching
+    /// them always need explicit approval (and are rejected when
+    /// `approval_policy` is `never`), and sandboxed commands cannot write them.
+    #[serde(default)]
+    pub write_protected: Option<Vec<String>>,
+
+    /// Only offer the model these tools: built-in tool names (`shell` covers
+    /// every command tool), `mcp:<server>` for all of a server's tools, or
+    /// qualified MCP tool names. When unset, all tools are offered.
+    #[serde(default)]
+    pub enabled_tools: Option<Vec<String>>,
+
+    /// Tools never offered to the model, using the same names as
+    /// `enabled_tools`. Applied after `enabled_tools`.
+    #[serde(default)]
+    pub disabled_tools: Option<Vec<String>>,
+
+    /// Limits on the files `apply_patch` may edit: files over
+    /// `max_file_bytes` and binary files are refused, and files longer than
+    /// `max_rewrite_lines` only accept targeted hunks rather than wholesale
+    /// rewrites.
+    #[serde(default)]
+    pub patch_limits: Option<PatchLimitsToml>,
+
     /// Markers used to detect the project root when searching parent
     /// directories for `.codex` folders. Defaults to [".git"] when unset.
     #[serde(default)]
@@ -1048,6 +1409,12 @@ pub struct AgentsToml {
     /// When unset, no limit is enforced.
     #[schemars(range(min = 1))]
     pub max_threads: Option<usize>,
+
+    /// Tokens each spawned sub-agent may use. When a sub-agent reaches it,
+    /// its turn stops and it reports what it has so far. When unset, no
+    /// limit is enforced.
+    #[schemars(range(min = 1))]
+    pub token_budget: Option<i64>,
 }
 
 impl From<ToolsToml> for Tools {
@@ -1092,14 +1459,9 @@ impl ConfigToml {
             .or(profile_sandbox_mode)
             .or(self.sandbox_mode)
             .or_else(|| {
-                // if no sandbox_mode is set, but user has marked directory as trusted or untrusted, use WorkspaceWrite
-                self.get_active_project(resolved_cwd).and_then(|p| {
-                    if p.is_trusted() || p.is_untrusted() {
-                        Some(SandboxMode::WorkspaceWrite)
-                    } else {
-                        None
-                    }
-                })
+                // if no sandbox_mode is set, the project's trust level decides
+                self.trust_preset(resolved_cwd)
+                    .map(|preset| preset.sandbox_mode)
             })
             .unwrap_or_default();
         let mut sandbox_policy = match resolved_sandbox_mode {
@@ -1135,6 +1497,12 @@ impl ConfigToml {
         }
     }
 
+    /// The trust preset for the project at `resolved_cwd`, if it has a trust level.
+    pub fn trust_preset(&self, resolved_cwd: &Path) -> Option<TrustPreset> {
+        let trust_level = self.get_active_project(resolved_cwd)?.trust_level?;
+        Some(self.trust_levels.unwrap_or_default().resolve(trust_level))
+    }
+
     /// Resolves the cwd to an existing project, or returns None if ConfigToml
     /// does not contain a project corresponding to cwd or a git repo for cwd
     pub fn get_active_project(&self, resolved_cwd: &Path) -> Option<ProjectConfig> {
@@ -1177,6 +1545,19 @@ impl ConfigToml {
             None => Ok(ConfigProfile::default()),
         }
     }
+
+    /// Directory whose credential store holds the login selected by `account`,
+    /// honoring an `account` set on the active profile.
+    pub fn auth_home(&self, codex_home: &Path, override_profile: Option<String>) -> PathBuf {
+        let profile_account = self
+            .get_config_profile(override_profile)
+            .ok()
+            .and_then(|profile| profile.account);
+        account_auth_home(
+            codex_home,
+            profile_account.or(self.account.clone()).as_deref(),
+        )
+    }
 }
 
 /// Optional overrides for user configuration (e.g., from CLI flags).
@@ -1193,11 +1574,15 @@ pub struct ConfigOverrides {
     pub base_instructions: Option<String>,
     pub developer_instructions: Option<String>,
     pub personality: Option<Personality>,
+    pub model_reasoning_effort: Option<ReasoningEffort>,
+    pub model_verbosity: Option<Verbosity>,
     pub compact_prompt: Option<String>,
     pub include_apply_patch_tool: Option<bool>,
     pub show_raw_agent_reasoning: Option<bool>,
     pub tools_web_search_request: Option<bool>,
     pub ephemeral: Option<bool>,
+    pub record_api_dir: Option<PathBuf>,
+    pub replay_api_path: Option<PathBuf>,
     /// Additional directories that should be treated as writable roots for this session.
     pub additional_writable_roots: Vec<PathBuf>,
 }
@@ -1300,11 +1685,15 @@ impl Config {
             base_instructions,
             developer_instructions,
             personality,
+            model_reasoning_effort: model_reasoning_effort_override,
+            model_verbosity: model_verbosity_override,
             compact_prompt,
             include_apply_patch_tool: include_apply_patch_tool_override,
             show_raw_agent_reasoning,
             tools_web_search_request: override_tools_web_search_request,
             ephemeral,
+            record_api_dir,
+            replay_api_path,
             additional_writable_roots,
         } = overrides;
 
@@ -1357,6 +1746,7 @@ impl Config {
         let active_project = cfg
             .get_active_project(&resolved_cwd)
             .unwrap_or(ProjectConfig { trust_level: None });
+        let trust_preset = cfg.trust_preset(&resolved_cwd);
 
         let windows_sandbox_level = WindowsSandboxLevel::from_features(&features);
         let SandboxPolicyResolution {
@@ -1378,16 +1768,23 @@ impl Config {
         let approval_policy = approval_policy_override
             .or(config_profile.approval_policy)
             .or(cfg.approval_policy)
-            .unwrap_or_else(|| {
-                if active_project.is_trusted() {
-                    AskForApproval::OnRequest
-                } else if active_project.is_untrusted() {
-                    AskForApproval::UnlessTrusted
-                } else {
-                    AskForApproval::default()
-                }
-            });
-        let web_search_mode = resolve_web_search_mode(&cfg, &config_profile, &features);
+            .or(trust_preset.map(|preset| preset.approval_policy))
+            .unwrap_or_default();
+        // Unlike the sandbox and approval policy, which the preset only
+        // defaults, a preset that turns web search or MCP servers off wins over
+        // explicit configuration: keeping them off is what the level is for.
+        let web_search_mode = if trust_preset.is_some_and(|preset| !preset.web_search) {
+            Some(WebSearchMode::Disabled)
+        } else {
+            resolve_web_search_mode(&cfg, &config_profile, &features)
+        };
+        let tool_filter = ToolFilter {
+            enabled: config_profile.enabled_tools.or(cfg.enabled_tools),
+            disabled: config_profile
+                .disabled_tools
+                .or(cfg.disabled_tools)
+                .unwrap_or_default(),
+        };
         // TODO(dylan): We should be able to leverage ConfigLayerStack so that
         // we can reliably check this at every config level.
         let did_user_set_custom_approval_policy_or_sandbox_mode = approval_policy_override
@@ -1417,6 +1814,27 @@ impl Config {
                 )
             })?
             .clone();
+        let model_fallbacks = cfg
+            .model_fallbacks
+            .unwrap_or_default()
+            .into_iter()
+            .map(|fallback| {
+                let provider = model_providers
+                    .get(&fallback.provider)
+                    .ok_or_else(|| {
+                        std::io::Error::new(
+                            std::io::ErrorKind::NotFound,
+                            format!("Fallback model provider `{}` not found", fallback.provider),
+                        )
+                    })?
+                    .clone();
+                Ok(ModelFallback {
+                    provider_id: fallback.provider,
+                    provider,
+                    model: fallback.model,
+                })
+            })
+            .collect::<std::io::Result<Vec<_>>>()?;
 
         let shell_environment_policy = cfg.shell_environment_policy.into();
 
@@ -1433,6 +1851,13 @@ impl Config {
                 "agents.max_threads must be at least 1",
             ));
         }
+        let agent_token_budget = cfg.agents.as_ref().and_then(|agents| agents.token_budget);
+        if agent_token_budget.is_some_and(|budget| budget < 1) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "agents.token_budget must be at least 1",
+            ));
+        }
 
         let ghost_snapshot = {
             let mut config = GhostSnapshotConfig::default();
@@ -1474,7 +1899,59 @@ impl Config {
 
         let forced_login_method = cfg.forced_login_method;
 
-        let model = model.or(config_profile.model).or(cfg.model);
+        let redaction = cfg.redaction.map(RedactionConfig::from).unwrap_or_default();
+        Redactor::validate(&redaction)
+            .map_err(|message| std::io::Error::new(std::io::ErrorKind::InvalidData, message))?;
+
+        let remote = cfg.remote.map(RemoteConfig::from).unwrap_or_default();
+        if remote.host.is_some() && !remote.remote_dir.starts_with('/') {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "remote.remote_dir must be an absolute path when remote.host is set",
+            ));
+        }
+
+        if let Some(pattern) = cfg
+            .approval
+            .as_ref()
+            .and_then(|approval| approval.safe_commands.as_ref())
+            .into_iter()
+            .flatten()
+            .find(|pattern| shlex::split(pattern).is_none_or(|words| words.is_empty()))
+        {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("approval.safe_commands: `{pattern}` is not a valid command prefix"),
+            ));
+        }
+
+        let approval = cfg
+            .approval
+            .map(ApprovalConfig::try_from)
+            .transpose()
+            .map_err(|message| std::io::Error::new(std::io::ErrorKind::InvalidData, message))?
+            .unwrap_or_default();
+
+        let network_proxy = crate::sandboxing::sandbox_proxy_addr(
+            cfg.network_proxy
+                .as_ref()
+                .unwrap_or(&NetworkProxyToml::default()),
+        )
+        .map_err(|message| std::io::Error::new(std::io::ErrorKind::InvalidData, message))?;
+
+        let account = config_profile.account.or(cfg.account);
+        if let Some(account) = account.as_deref() {
+            validate_account_name(account)
+                .map_err(|message| std::io::Error::new(std::io::ErrorKind::InvalidData, message))?;
+        }
+
+        let pinned_snapshot = config_profile.model_snapshot.or(cfg.model_snapshot);
+        let model = model
+            .or_else(|| pinned_snapshot.clone())
+            .or(config_profile.model)
+            .or(cfg.model);
+        // A `--model` override replaces the pin rather than being checked against it.
+        let model_snapshot = pinned_snapshot.filter(|snapshot| model.as_ref() == Some(snapshot));
 
         let compact_prompt = compact_prompt.or(cfg.compact_prompt).and_then(|value| {
             let trimmed = value.trim();
@@ -1536,16 +2013,25 @@ impl Config {
             .set(sandbox_policy)
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("{e}")))?;
 
-        let mcp_servers = constrain_mcp_servers(cfg.mcp_servers.clone(), mcp_servers.as_ref())
+        let configured_mcp_servers = if trust_preset.is_some_and(|preset| !preset.mcp_servers) {
+            HashMap::new()
+        } else {
+            cfg.mcp_servers.clone()
+        };
+        let mcp_servers = constrain_mcp_servers(configured_mcp_servers, mcp_servers.as_ref())
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("{e}")))?;
 
         let config = Self {
             model,
+            model_snapshot,
             review_model,
             model_context_window: cfg.model_context_window,
             model_auto_compact_token_limit: cfg.model_auto_compact_token_limit,
+            model_prices: cfg.model_prices.unwrap_or_default(),
+            run_budget: None,
             model_provider_id,
             model_provider,
+            model_fallbacks,
             cwd: resolved_cwd,
             approval_policy: constrained_approval_policy,
             sandbox_policy: constrained_sandbox_policy,
@@ -1554,6 +2040,7 @@ impl Config {
             forced_auto_mode_downgraded_on_windows,
             shell_environment_policy,
             notify: cfg.notify,
+            notify_mode: cfg.notify_mode.unwrap_or_default(),
             user_instructions,
             base_instructions,
             personality,
@@ -1562,6 +2049,7 @@ impl Config {
             // The config.toml omits "_mode" because it's a config file. However, "_mode"
             // is important in code to differentiate the mode from the store implementation.
             cli_auth_credentials_store_mode: cfg.cli_auth_credentials_store.unwrap_or_default(),
+            account,
             mcp_servers,
             // The config.toml omits "_mode" because it's a config file. However, "_mode"
             // is important in code to differentiate the mode from the store implementation.
@@ -1583,12 +2071,17 @@ impl Config {
                 })
                 .collect(),
             tool_output_token_limit: cfg.tool_output_token_limit,
+            mcp_turn_output_token_limit: cfg.mcp_turn_output_token_limit,
             agent_max_threads,
+            agent_token_budget,
             codex_home,
             config_layer_stack,
             history,
             ephemeral: ephemeral.unwrap_or_default(),
+            record_api_dir,
+            replay_api_path,
             file_opener: cfg.file_opener.unwrap_or(UriBasedFileOpener::VsCode),
+            open_file_command: cfg.open_file_command,
             codex_linux_sandbox_exe,
 
             hide_agent_reasoning: cfg.hide_agent_reasoning.unwrap_or(false),
@@ -1596,15 +2089,19 @@ impl Config {
                 .show_raw_agent_reasoning
                 .or(show_raw_agent_reasoning)
                 .unwrap_or(false),
-            model_reasoning_effort: config_profile
-                .model_reasoning_effort
+            model_reasoning_effort: model_reasoning_effort_override
+                .or(config_profile.model_reasoning_effort)
                 .or(cfg.model_reasoning_effort),
             model_reasoning_summary: config_profile
                 .model_reasoning_summary
                 .or(cfg.model_reasoning_summary)
                 .unwrap_or_default(),
             model_supports_reasoning_summaries: cfg.model_supports_reasoning_summaries,
-            model_verbosity: config_profile.model_verbosity.or(cfg.model_verbosity),
+            model_supports_parallel_tool_calls: cfg.model_supports_parallel_tool_calls,
+            model_verbosity: model_verbosity_override
+                .or(config_profile.model_verbosity)
+                .or(cfg.model_verbosity),
+            prompt_cache_key: cfg.prompt_cache_key.unwrap_or_default(),
             chatgpt_base_url: config_profile
                 .chatgpt_base_url
                 .or(cfg.chatgpt_base_url)
@@ -1613,14 +2110,68 @@ impl Config {
             forced_login_method,
             include_apply_patch_tool: include_apply_patch_tool_flag,
             web_search_mode,
+            web_search_backend: cfg.web_search_backend.unwrap_or_default(),
             use_experimental_unified_exec_tool,
             ghost_snapshot,
+            trash: cfg.trash.map(TrashConfig::from).unwrap_or_default(),
+            code_index: cfg
+                .code_index
+                .map(CodeIndexConfig::from)
+                .unwrap_or_default(),
+            exec_limits: cfg
+                .exec_limits
+                .map(ExecLimitsConfig::from)
+                .unwrap_or_default(),
+            network_proxy,
+            linux_sandbox: cfg
+                .linux_sandbox
+                .map(LinuxSandboxConfig::from)
+                .unwrap_or_default(),
+            container: cfg.container.map(ContainerConfig::from).unwrap_or_default(),
+            remote,
+            file_approvals: cfg
+                .file_approvals
+                .map(FileApprovalsConfig::from)
+                .unwrap_or_default(),
+            approval,
+            credentials: cfg
+                .credentials
+                .map(CredentialsConfig::from)
+                .unwrap_or_default(),
+            attribution: cfg
+                .attribution
+                .map(AttributionConfig::from)
+                .unwrap_or_default(),
+            redaction,
+            session_encryption: cfg.session_encryption.unwrap_or_default(),
+            session_retention: cfg
+                .session_retention
+                .map(SessionRetentionConfig::from)
+                .unwrap_or_default(),
+            session_worktree: cfg.session_worktree.unwrap_or(false),
+            pull_requests: cfg
+                .pull_requests
+                .map(PullRequestsConfig::from)
+                .unwrap_or_default(),
+            repo_map_max_tokens: cfg
+                .repo_map_max_tokens
+                .unwrap_or(DEFAULT_REPO_MAP_MAX_TOKENS),
+            lsp: cfg.lsp.map(LspConfig::from).unwrap_or_default(),
+            hooks: cfg.hooks.map(HooksConfig::from).unwrap_or_default(),
+            apply_patch_fuzz: cfg.apply_patch_fuzz.unwrap_or(DEFAULT_FUZZ_FACTOR),
+            write_protected: cfg.write_protected.unwrap_or_default(),
+            tool_filter,
+            patch_limits: cfg
+                .patch_limits
+                .map(PatchLimitsConfig::from)
+                .unwrap_or_default(),
             features,
             suppress_unstable_features_warning: cfg
                 .suppress_unstable_features_warning
                 .unwrap_or(false),
             active_profile: active_profile_name,
             active_project,
+            trust_preset,
             windows_wsl_setup_acknowledged: cfg.windows_wsl_setup_acknowledged.unwrap_or(false),
             notices: cfg.notice.unwrap_or_default(),
             check_for_update_on_startup,
@@ -1653,6 +2204,11 @@ impl Config {
                 .as_ref()
                 .map(|t| t.alternate_screen)
                 .unwrap_or_default(),
+            tui_paste_attach_threshold: cfg
+                .tui
+                .as_ref()
+                .and_then(|t| t.paste_attach_threshold)
+                .unwrap_or(DEFAULT_PASTE_ATTACH_THRESHOLD),
             otel: {
                 let t: OtelConfigToml = cfg.otel.unwrap_or_default();
                 let log_user_prompt = t.log_user_prompt.unwrap_or(false);
@@ -1717,6 +2273,11 @@ impl Config {
         }
     }
 
+    /// Directory whose credential store holds the login selected by `account`.
+    pub fn auth_home(&self) -> PathBuf {
+        account_auth_home(&self.codex_home, self.account.as_deref())
+    }
+
     pub fn set_windows_sandbox_enabled(&mut self, value: bool) {
         if value {
             self.features.enable(Feature::WindowsSandbox);
@@ -1790,6 +2351,7 @@ mod tests {
     use crate::config::types::McpServerTransportConfig;
     use crate::config::types::NotificationMethod;
     use crate::config::types::Notifications;
+    use crate::config::types::TrustPresetToml;
     use crate::config_loader::RequirementSource;
     use crate::features::Feature;
 
@@ -1818,6 +2380,12 @@ mod tests {
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
+            lazy_start: false,
+            tool_aliases: None,
+            tool_approval: None,
+            tool_approvals: None,
+            tool_output_token_limit: None,
+            tool_output_token_limits: None,
         }
     }
 
@@ -1836,6 +2404,12 @@ mod tests {
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
+            lazy_start: false,
+            tool_aliases: None,
+            tool_approval: None,
+            tool_approvals: None,
+            tool_output_token_limit: None,
+            tool_output_token_limits: None,
         }
     }
 
@@ -1890,6 +2464,7 @@ persistence = "none"
                 show_tooltips: true,
                 experimental_mode: None,
                 alternate_screen: AltScreenMode::Auto,
+                paste_attach_threshold: None,
             }
         );
     }
@@ -2196,30 +2771,22 @@ trust_level = "trusted"
         )?;
 
         let expected_backend = AbsolutePathBuf::try_from(backend).unwrap();
-        if cfg!(target_os = "windows") {
-            assert!(
-                config.forced_auto_mode_downgraded_on_windows,
-                "expected workspace-write request to be downgraded on Windows"
-            );
-            match config.sandbox_policy.get() {
-                &SandboxPolicy::ReadOnly => {}
-                other => panic!("expected read-only policy on Windows, got {other:?}"),
-            }
-        } else {
-            match config.sandbox_policy.get() {
-                SandboxPolicy::WorkspaceWrite { writable_roots, .. } => {
-                    assert_eq!(
-                        writable_roots
-                            .iter()
-                            .filter(|root| **root == expected_backend)
-                            .count(),
-                        1,
-                        "expected single writable root entry for {}",
-                        expected_backend.display()
-                    );
-                }
-                other => panic!("expected workspace-write policy, got {other:?}"),
+        // The Windows sandbox is on by default, so workspace-write is not
+        // downgraded there either.
+        assert!(!config.forced_auto_mode_downgraded_on_windows);
+        match config.sandbox_policy.get() {
+            SandboxPolicy::WorkspaceWrite { writable_roots, .. } => {
+                assert_eq!(
+                    writable_roots
+                        .iter()
+                        .filter(|root| **root == expected_backend)
+                        .count(),
+                    1,
+                    "expected single writable root entry for {}",
+                    expected_backend.display()
+                );
             }
+            other => panic!("expected workspace-write policy, got {other:?}"),
         }
 
         Ok(())
@@ -2405,6 +2972,109 @@ trust_level = "trusted"
         Ok(())
     }
 
+    #[test]
+    fn profile_account_selects_auth_home() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let mut profiles = HashMap::new();
+        profiles.insert(
+            "work".to_string(),
+            ConfigProfile {
+                account: Some("work".to_string()),
+                ..Default::default()
+            },
+        );
+        let cfg = ConfigToml {
+            account: Some("personal".to_string()),
+            profiles,
+            profile: Some("work".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            cfg.auth_home(codex_home.path(), None),
+            codex_home.path().join("accounts").join("work")
+        );
+        assert_eq!(
+            cfg.auth_home(codex_home.path(), Some("missing".to_string())),
+            codex_home.path().join("accounts").join("personal")
+        );
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(config.account.as_deref(), Some("work"));
+        assert_eq!(
+            config.auth_home(),
+            codex_home.path().join("accounts").join("work")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_account_name_is_rejected() {
+        let codex_home = TempDir::new().unwrap();
+        let cfg = ConfigToml {
+            account: Some("../escape".to_string()),
+            ..Default::default()
+        };
+
+        let err = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )
+        .expect_err("invalid account name should fail");
+
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn profile_model_snapshot_pins_model_unless_overridden() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let mut profiles = HashMap::new();
+        profiles.insert(
+            "repro".to_string(),
+            ConfigProfile {
+                model: Some("gpt-5".to_string()),
+                model_snapshot: Some("gpt-5-2025-08-07".to_string()),
+                ..Default::default()
+            },
+        );
+        let cfg = ConfigToml {
+            profiles,
+            profile: Some("repro".to_string()),
+            ..Default::default()
+        };
+
+        let pinned = Config::load_from_base_config_with_overrides(
+            cfg.clone(),
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+        let overridden = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides {
+                model: Some("o3".to_string()),
+                ..Default::default()
+            },
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(
+            (pinned.model.as_deref(), pinned.model_snapshot.as_deref()),
+            (Some("gpt-5-2025-08-07"), Some("gpt-5-2025-08-07"))
+        );
+        assert_eq!(
+            (overridden.model.as_deref(), overridden.model_snapshot),
+            (Some("o3"), None)
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn project_profile_overrides_user_profile() -> std::io::Result<()> {
         let codex_home = TempDir::new()?;
@@ -2529,6 +3199,45 @@ profile = "project"
         Ok(())
     }
 
+    #[test]
+    fn cli_reasoning_and_verbosity_take_precedence_over_profile() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let mut profiles = HashMap::new();
+        profiles.insert(
+            "work".to_string(),
+            ConfigProfile {
+                model_reasoning_effort: Some(ReasoningEffort::High),
+                model_verbosity: Some(Verbosity::High),
+                ..Default::default()
+            },
+        );
+        let cfg = ConfigToml {
+            profiles,
+            profile: Some("work".to_string()),
+            model_verbosity: Some(Verbosity::Medium),
+            ..Default::default()
+        };
+
+        let overrides = ConfigOverrides {
+            model_reasoning_effort: Some(ReasoningEffort::Minimal),
+            model_verbosity: Some(Verbosity::Low),
+            ..Default::default()
+        };
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            overrides,
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(
+            config.model_reasoning_effort,
+            Some(ReasoningEffort::Minimal)
+        );
+        assert_eq!(config.model_verbosity, Some(Verbosity::Low));
+        Ok(())
+    }
+
     #[test]
     fn feature_table_overrides_legacy_flags() -> std::io::Result<()> {
         let codex_home = TempDir::new()?;
@@ -2551,6 +3260,41 @@ profile = "project"
         Ok(())
     }
 
+    #[test]
+    fn experimental_table_overrides_features_for_experimental_flags_only() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let features = BTreeMap::from([("steer".to_string(), false)]);
+        let experimental = BTreeMap::from([
+            ("steer".to_string(), true),
+            ("shell_tool".to_string(), false),
+        ]);
+        let cfg = ConfigToml {
+            features: Some(crate::features::FeaturesToml { entries: features }),
+            experimental: Some(crate::features::FeaturesToml {
+                entries: experimental,
+            }),
+            ..Default::default()
+        };
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert!(config.features.enabled(Feature::Steer));
+        assert!(config.features.enabled(Feature::ShellTool));
+        let overridden = config.features.overridden_flags();
+        assert!(overridden.contains(&"steer=true".to_string()));
+        assert!(
+            !overridden
+                .iter()
+                .any(|flag| flag.starts_with("shell_tool="))
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn legacy_toggles_map_to_features() -> std::io::Result<()> {
         let codex_home = TempDir::new()?;
@@ -2705,6 +3449,12 @@ profile = "project"
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                lazy_start: false,
+                tool_aliases: None,
+                tool_approval: None,
+                tool_approvals: None,
+                tool_output_token_limit: None,
+                tool_output_token_limits: None,
             },
         );
 
@@ -2861,6 +3611,12 @@ bearer_token = "secret"
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                lazy_start: false,
+                tool_aliases: None,
+                tool_approval: None,
+                tool_approvals: None,
+                tool_output_token_limit: None,
+                tool_output_token_limits: None,
             },
         )]);
 
@@ -2931,6 +3687,12 @@ ZIG_VAR = "3"
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                lazy_start: false,
+                tool_aliases: None,
+                tool_approval: None,
+                tool_approvals: None,
+                tool_output_token_limit: None,
+                tool_output_token_limits: None,
             },
         )]);
 
@@ -2981,6 +3743,12 @@ ZIG_VAR = "3"
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                lazy_start: false,
+                tool_aliases: None,
+                tool_approval: None,
+                tool_approvals: None,
+                tool_output_token_limit: None,
+                tool_output_token_limits: None,
             },
         )]);
 
@@ -3029,6 +3797,12 @@ ZIG_VAR = "3"
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                lazy_start: false,
+                tool_aliases: None,
+                tool_approval: None,
+                tool_approvals: None,
+                tool_output_token_limit: None,
+                tool_output_token_limits: None,
             },
         )]);
 
@@ -3093,6 +3867,12 @@ startup_timeout_sec = 2.0
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                lazy_start: false,
+                tool_aliases: None,
+                tool_approval: None,
+                tool_approvals: None,
+                tool_output_token_limit: None,
+                tool_output_token_limits: None,
             },
         )]);
         apply_blocking(
@@ -3169,6 +3949,12 @@ X-Auth = "DOCS_AUTH"
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                lazy_start: false,
+                tool_aliases: None,
+                tool_approval: None,
+                tool_approvals: None,
+                tool_output_token_limit: None,
+                tool_output_token_limits: None,
             },
         )]);
 
@@ -3198,6 +3984,12 @@ X-Auth = "DOCS_AUTH"
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                lazy_start: false,
+                tool_aliases: None,
+                tool_approval: None,
+                tool_approvals: None,
+                tool_output_token_limit: None,
+                tool_output_token_limits: None,
             },
         );
         apply_blocking(
@@ -3265,6 +4057,12 @@ url = "https://example.com/mcp"
                     enabled_tools: None,
                     disabled_tools: None,
                     scopes: None,
+                    lazy_start: false,
+                    tool_aliases: None,
+                    tool_approval: None,
+                    tool_approvals: None,
+                    tool_output_token_limit: None,
+                    tool_output_token_limits: None,
                 },
             ),
             (
@@ -3284,6 +4082,12 @@ url = "https://example.com/mcp"
                     enabled_tools: None,
                     disabled_tools: None,
                     scopes: None,
+                    lazy_start: false,
+                    tool_aliases: None,
+                    tool_approval: None,
+                    tool_approvals: None,
+                    tool_output_token_limit: None,
+                    tool_output_token_limits: None,
                 },
             ),
         ]);
@@ -3366,6 +4170,12 @@ url = "https://example.com/mcp"
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                lazy_start: false,
+                tool_aliases: None,
+                tool_approval: None,
+                tool_approvals: None,
+                tool_output_token_limit: None,
+                tool_output_token_limits: None,
             },
         )]);
 
@@ -3410,6 +4220,12 @@ url = "https://example.com/mcp"
                 enabled_tools: Some(vec!["allowed".to_string()]),
                 disabled_tools: Some(vec!["blocked".to_string()]),
                 scopes: None,
+                lazy_start: false,
+                tool_aliases: None,
+                tool_approval: None,
+                tool_approvals: None,
+                tool_output_token_limit: None,
+                tool_output_token_limits: None,
             },
         )]);
 
@@ -3714,11 +4530,17 @@ model_verbosity = "high"
             wire_api: crate::WireApi::Chat,
             env_key_instructions: None,
             experimental_bearer_token: None,
+            auth_header: None,
+            auth_scheme: None,
+            prompt_cache_key_header: None,
             query_params: None,
             http_headers: None,
             env_http_headers: None,
             request_max_retries: Some(4),
             stream_max_retries: Some(10),
+            retry_base_delay_ms: None,
+            retry_max_delay_ms: None,
+            retry_status_codes: None,
             stream_idle_timeout_ms: Some(300_000),
             requires_openai_auth: false,
             supports_websockets: false,
@@ -3776,11 +4598,15 @@ model_verbosity = "high"
         assert_eq!(
             Config {
                 model: Some("o3".to_string()),
+                model_snapshot: None,
                 review_model: None,
                 model_context_window: None,
                 model_auto_compact_token_limit: None,
+                model_prices: HashMap::new(),
+                run_budget: None,
                 model_provider_id: "openai".to_string(),
                 model_provider: fixture.openai_provider.clone(),
+                model_fallbacks: Vec::new(),
                 approval_policy: Constrained::allow_any(AskForApproval::Never),
                 sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
                 enforce_residency: Constrained::allow_any(None),
@@ -3789,8 +4615,10 @@ model_verbosity = "high"
                 shell_environment_policy: ShellEnvironmentPolicy::default(),
                 user_instructions: None,
                 notify: None,
+                notify_mode: NotifyMode::PerEvent,
                 cwd: fixture.cwd(),
                 cli_auth_credentials_store_mode: Default::default(),
+                account: None,
                 mcp_servers: Constrained::allow_any(HashMap::new()),
                 mcp_oauth_credentials_store_mode: Default::default(),
                 mcp_oauth_callback_port: None,
@@ -3798,18 +4626,24 @@ model_verbosity = "high"
                 project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
                 project_doc_fallback_filenames: Vec::new(),
                 tool_output_token_limit: None,
+                mcp_turn_output_token_limit: None,
                 agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
+                agent_token_budget: None,
                 codex_home: fixture.codex_home(),
                 config_layer_stack: Default::default(),
                 history: History::default(),
                 ephemeral: false,
+                record_api_dir: None,
+                replay_api_path: None,
                 file_opener: UriBasedFileOpener::VsCode,
+                open_file_command: None,
                 codex_linux_sandbox_exe: None,
                 hide_agent_reasoning: false,
                 show_raw_agent_reasoning: false,
                 model_reasoning_effort: Some(ReasoningEffort::High),
                 model_reasoning_summary: ReasoningSummary::Detailed,
                 model_supports_reasoning_summaries: None,
+                model_supports_parallel_tool_calls: None,
                 model_verbosity: None,
                 personality: Some(Personality::Friendly),
                 chatgpt_base_url: "https://chatgpt.com/backend-api/".to_string(),
@@ -3820,12 +4654,38 @@ model_verbosity = "high"
                 forced_login_method: None,
                 include_apply_patch_tool: false,
                 web_search_mode: None,
+                web_search_backend: WebSearchBackend::default(),
                 use_experimental_unified_exec_tool: false,
                 ghost_snapshot: GhostSnapshotConfig::default(),
+                trash: TrashConfig::default(),
+                code_index: CodeIndexConfig::default(),
+                exec_limits: ExecLimitsConfig::default(),
+                network_proxy: None,
+                linux_sandbox: LinuxSandboxConfig::default(),
+                container: ContainerConfig::default(),
+                remote: RemoteConfig::default(),
+                file_approvals: FileApprovalsConfig::default(),
+                approval: ApprovalConfig::default(),
+                credentials: CredentialsConfig::default(),
+                prompt_cache_key: PromptCacheKeyScope::default(),
+                attribution: AttributionConfig::default(),
+                redaction: RedactionConfig::default(),
+                session_encryption: SessionEncryptionMode::default(),
+                session_retention: SessionRetentionConfig::default(),
+                session_worktree: false,
+                pull_requests: PullRequestsConfig::default(),
+                repo_map_max_tokens: DEFAULT_REPO_MAP_MAX_TOKENS,
+                lsp: LspConfig::default(),
+                hooks: HooksConfig::default(),
+                apply_patch_fuzz: DEFAULT_FUZZ_FACTOR,
+                write_protected: Vec::new(),
+                tool_filter: ToolFilter::default(),
+                patch_limits: PatchLimitsConfig::default(),
                 features: Features::with_defaults(),
                 suppress_unstable_features_warning: false,
                 active_profile: Some("o3".to_string()),
                 active_project: ProjectConfig { trust_level: None },
+                trust_preset: None,
                 windows_wsl_setup_acknowledged: false,
                 notices: Default::default(),
                 check_for_update_on_startup: true,
@@ -3838,6 +4698,7 @@ model_verbosity = "high"
                 analytics_enabled: Some(true),
                 feedback_enabled: true,
                 tui_alternate_screen: AltScreenMode::Auto,
+                tui_paste_attach_threshold: DEFAULT_PASTE_ATTACH_THRESHOLD,
                 otel: OtelConfig::default(),
             },
             o3_profile_config
@@ -3861,11 +4722,15 @@ model_verbosity = "high"
         )?;
         let expected_gpt3_profile_config = Config {
             model: Some("gpt-3.5-turbo".to_string()),
+            model_snapshot: None,
             review_model: None,
             model_context_window: None,
             model_auto_compact_token_limit: None,
+            model_prices: HashMap::new(),
+            run_budget: None,
             model_provider_id: "openai-chat-completions".to_string(),
             model_provider: fixture.openai_chat_completions_provider.clone(),
+            model_fallbacks: Vec::new(),
             approval_policy: Constrained::allow_any(AskForApproval::UnlessTrusted),
             sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
             enforce_residency: Constrained::allow_any(None),
@@ -3874,8 +4739,10 @@ model_verbosity = "high"
             shell_environment_policy: ShellEnvironmentPolicy::default(),
             user_instructions: None,
             notify: None,
+            notify_mode: NotifyMode::PerEvent,
             cwd: fixture.cwd(),
             cli_auth_credentials_store_mode: Default::default(),
+            account: None,
             mcp_servers: Constrained::allow_any(HashMap::new()),
             mcp_oauth_credentials_store_mode: Default::default(),
             mcp_oauth_callback_port: None,
@@ -3883,18 +4750,24 @@ model_verbosity = "high"
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
+            mcp_turn_output_token_limit: None,
             agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
+            agent_token_budget: None,
             codex_home: fixture.codex_home(),
             config_layer_stack: Default::default(),
             history: History::default(),
             ephemeral: false,
+            record_api_dir: None,
+            replay_api_path: None,
             file_opener: UriBasedFileOpener::VsCode,
+            open_file_command: None,
             codex_linux_sandbox_exe: None,
             hide_agent_reasoning: false,
             show_raw_agent_reasoning: false,
             model_reasoning_effort: None,
             model_reasoning_summary: ReasoningSummary::default(),
             model_supports_reasoning_summaries: None,
+            model_supports_parallel_tool_calls: None,
             model_verbosity: None,
             personality: Some(Personality::Friendly),
             chatgpt_base_url: "https://chatgpt.com/backend-api/".to_string(),
@@ -3905,12 +4778,38 @@ model_verbosity = "high"
             forced_login_method: None,
             include_apply_patch_tool: false,
             web_search_mode: None,
+            web_search_backend: WebSearchBackend::default(),
             use_experimental_unified_exec_tool: false,
             ghost_snapshot: GhostSnapshotConfig::default(),
+            trash: TrashConfig::default(),
+            code_index: CodeIndexConfig::default(),
+            exec_limits: ExecLimitsConfig::default(),
+            network_proxy: None,
+            linux_sandbox: LinuxSandboxConfig::default(),
+            container: ContainerConfig::default(),
+            remote: RemoteConfig::default(),
+            file_approvals: FileApprovalsConfig::default(),
+            approval: ApprovalConfig::default(),
+            credentials: CredentialsConfig::default(),
+            prompt_cache_key: PromptCacheKeyScope::default(),
+            attribution: AttributionConfig::default(),
+            redaction: RedactionConfig::default(),
+            session_encryption: SessionEncryptionMode::default(),
+            session_retention: SessionRetentionConfig::default(),
+            session_worktree: false,
+            pull_requests: PullRequestsConfig::default(),
+            repo_map_max_tokens: DEFAULT_REPO_MAP_MAX_TOKENS,
+            lsp: LspConfig::default(),
+            hooks: HooksConfig::default(),
+            apply_patch_fuzz: DEFAULT_FUZZ_FACTOR,
+            write_protected: Vec::new(),
+            tool_filter: ToolFilter::default(),
+            patch_limits: PatchLimitsConfig::default(),
             features: Features::with_defaults(),
             suppress_unstable_features_warning: false,
             active_profile: Some("gpt3".to_string()),
             active_project: ProjectConfig { trust_level: None },
+            trust_preset: None,
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
             check_for_update_on_startup: true,
@@ -3923,6 +4822,7 @@ model_verbosity = "high"
             analytics_enabled: Some(true),
             feedback_enabled: true,
             tui_alternate_screen: AltScreenMode::Auto,
+            tui_paste_attach_threshold: DEFAULT_PASTE_ATTACH_THRESHOLD,
             otel: OtelConfig::default(),
         };
 
@@ -3961,11 +4861,15 @@ model_verbosity = "high"
         )?;
         let expected_zdr_profile_config = Config {
             model: Some("o3".to_string()),
+            model_snapshot: None,
             review_model: None,
             model_context_window: None,
             model_auto_compact_token_limit: None,
+            model_prices: HashMap::new(),
+            run_budget: None,
             model_provider_id: "openai".to_string(),
             model_provider: fixture.openai_provider.clone(),
+            model_fallbacks: Vec::new(),
             approval_policy: Constrained::allow_any(AskForApproval::OnFailure),
             sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
             enforce_residency: Constrained::allow_any(None),
@@ -3974,8 +4878,10 @@ model_verbosity = "high"
             shell_environment_policy: ShellEnvironmentPolicy::default(),
             user_instructions: None,
             notify: None,
+            notify_mode: NotifyMode::PerEvent,
             cwd: fixture.cwd(),
             cli_auth_credentials_store_mode: Default::default(),
+            account: None,
             mcp_servers: Constrained::allow_any(HashMap::new()),
             mcp_oauth_credentials_store_mode: Default::default(),
             mcp_oauth_callback_port: None,
@@ -3983,18 +4889,24 @@ model_verbosity = "high"
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
+            mcp_turn_output_token_limit: None,
             agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
+            agent_token_budget: None,
             codex_home: fixture.codex_home(),
             config_layer_stack: Default::default(),
             history: History::default(),
             ephemeral: false,
+            record_api_dir: None,
+            replay_api_path: None,
             file_opener: UriBasedFileOpener::VsCode,
+            open_file_command: None,
             codex_linux_sandbox_exe: None,
             hide_agent_reasoning: false,
             show_raw_agent_reasoning: false,
             model_reasoning_effort: None,
             model_reasoning_summary: ReasoningSummary::default(),
             model_supports_reasoning_summaries: None,
+            model_supports_parallel_tool_calls: None,
             model_verbosity: None,
             personality: Some(Personality::Friendly),
             chatgpt_base_url: "https://chatgpt.com/backend-api/".to_string(),
@@ -4005,12 +4917,38 @@ model_verbosity = "high"
             forced_login_method: None,
             include_apply_patch_tool: false,
             web_search_mode: None,
+            web_search_backend: WebSearchBackend::default(),
             use_experimental_unified_exec_tool: false,
             ghost_snapshot: GhostSnapshotConfig::default(),
+            trash: TrashConfig::default(),
+            code_index: CodeIndexConfig::default(),
+            exec_limits: ExecLimitsConfig::default(),
+            network_proxy: None,
+            linux_sandbox: LinuxSandboxConfig::default(),
+            container: ContainerConfig::default(),
+            remote: RemoteConfig::default(),
+            file_approvals: FileApprovalsConfig::default(),
+            approval: ApprovalConfig::default(),
+            credentials: CredentialsConfig::default(),
+            prompt_cache_key: PromptCacheKeyScope::default(),
+            attribution: AttributionConfig::default(),
+            redaction: RedactionConfig::default(),
+            session_encryption: SessionEncryptionMode::default(),
+            session_retention: SessionRetentionConfig::default(),
+            session_worktree: false,
+            pull_requests: PullRequestsConfig::default(),
+            repo_map_max_tokens: DEFAULT_REPO_MAP_MAX_TOKENS,
+            lsp: LspConfig::default(),
+            hooks: HooksConfig::default(),
+            apply_patch_fuzz: DEFAULT_FUZZ_FACTOR,
+            write_protected: Vec::new(),
+            tool_filter: ToolFilter::default(),
+            patch_limits: PatchLimitsConfig::default(),
             features: Features::with_defaults(),
             suppress_unstable_features_warning: false,
             active_profile: Some("zdr".to_string()),
             active_project: ProjectConfig { trust_level: None },
+            trust_preset: None,
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
             check_for_update_on_startup: true,
@@ -4023,6 +4961,7 @@ model_verbosity = "high"
             analytics_enabled: Some(false),
             feedback_enabled: true,
             tui_alternate_screen: AltScreenMode::Auto,
+            tui_paste_attach_threshold: DEFAULT_PASTE_ATTACH_THRESHOLD,
             otel: OtelConfig::default(),
         };
 
@@ -4047,11 +4986,15 @@ model_verbosity = "high"
         )?;
         let expected_gpt5_profile_config = Config {
             model: Some("gpt-5.1".to_string()),
+            model_snapshot: None,
             review_model: None,
             model_context_window: None,
             model_auto_compact_token_limit: None,
+            model_prices: HashMap::new(),
+            run_budget: None,
             model_provider_id: "openai".to_string(),
             model_provider: fixture.openai_provider.clone(),
+            model_fallbacks: Vec::new(),
             approval_policy: Constrained::allow_any(AskForApproval::OnFailure),
             sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
             enforce_residency: Constrained::allow_any(None),
@@ -4060,8 +5003,10 @@ model_verbosity = "high"
             shell_environment_policy: ShellEnvironmentPolicy::default(),
             user_instructions: None,
             notify: None,
+            notify_mode: NotifyMode::PerEvent,
             cwd: fixture.cwd(),
             cli_auth_credentials_store_mode: Default::default(),
+            account: None,
             mcp_servers: Constrained::allow_any(HashMap::new()),
             mcp_oauth_credentials_store_mode: Default::default(),
             mcp_oauth_callback_port: None,
@@ -4069,18 +5014,24 @@ model_verbosity = "high"
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
+            mcp_turn_output_token_limit: None,
             agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
+            agent_token_budget: None,
             codex_home: fixture.codex_home(),
             config_layer_stack: Default::default(),
             history: History::default(),
             ephemeral: false,
+            record_api_dir: None,
+            replay_api_path: None,
             file_opener: UriBasedFileOpener::VsCode,
+            open_file_command: None,
             codex_linux_sandbox_exe: None,
             hide_agent_reasoning: false,
             show_raw_agent_reasoning: false,
             model_reasoning_effort: Some(ReasoningEffort::High),
             model_reasoning_summary: ReasoningSummary::Detailed,
             model_supports_reasoning_summaries: None,
+            model_supports_parallel_tool_calls: None,
             model_verbosity: Some(Verbosity::High),
             personality: Some(Personality::Friendly),
             chatgpt_base_url: "https://chatgpt.com/backend-api/".to_string(),
@@ -4091,12 +5042,38 @@ model_verbosity = "high"
             forced_login_method: None,
             include_apply_patch_tool: false,
             web_search_mode: None,
+            web_search_backend: WebSearchBackend::default(),
             use_experimental_unified_exec_tool: false,
             ghost_snapshot: GhostSnapshotConfig::default(),
+            trash: TrashConfig::default(),
+            code_index: CodeIndexConfig::default(),
+            exec_limits: ExecLimitsConfig::default(),
+            network_proxy: None,
+            linux_sandbox: LinuxSandboxConfig::default(),
+            container: ContainerConfig::default(),
+            remote: RemoteConfig::default(),
+            file_approvals: FileApprovalsConfig::default(),
+            approval: ApprovalConfig::default(),
+            credentials: CredentialsConfig::default(),
+            prompt_cache_key: PromptCacheKeyScope::default(),
+            attribution: AttributionConfig::default(),
+            redaction: RedactionConfig::default(),
+            session_encryption: SessionEncryptionMode::default(),
+            session_retention: SessionRetentionConfig::default(),
+            session_worktree: false,
+            pull_requests: PullRequestsConfig::default(),
+            repo_map_max_tokens: DEFAULT_REPO_MAP_MAX_TOKENS,
+            lsp: LspConfig::default(),
+            hooks: HooksConfig::default(),
+            apply_patch_fuzz: DEFAULT_FUZZ_FACTOR,
+            write_protected: Vec::new(),
+            tool_filter: ToolFilter::default(),
+            patch_limits: PatchLimitsConfig::default(),
             features: Features::with_defaults(),
             suppress_unstable_features_warning: false,
             active_profile: Some("gpt5".to_string()),
             active_project: ProjectConfig { trust_level: None },
+            trust_preset: None,
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
             check_for_update_on_startup: true,
@@ -4109,6 +5086,7 @@ model_verbosity = "high"
             analytics_enabled: Some(true),
             feedback_enabled: true,
             tui_alternate_screen: AltScreenMode::Auto,
+            tui_paste_attach_threshold: DEFAULT_PASTE_ATTACH_THRESHOLD,
             otel: OtelConfig::default(),
         };
 
@@ -4409,7 +5387,8 @@ mcp_oauth_callback_port = 5678
     }
 
     #[test]
-    fn test_untrusted_project_gets_unless_trusted_approval_policy() -> anyhow::Result<()> {
+    fn test_untrusted_project_gets_unless_trusted_approval_policy_and_read_only_sandbox()
+    -> anyhow::Result<()> {
         let codex_home = TempDir::new()?;
         let test_project_dir = TempDir::new()?;
         let test_path = test_project_dir.path();
@@ -4438,21 +5417,110 @@ mcp_oauth_callback_port = 5678
             "Expected UnlessTrusted approval policy for untrusted project"
         );
 
-        // Verify that untrusted projects still get WorkspaceWrite sandbox (or ReadOnly on Windows)
-        if cfg!(target_os = "windows") {
-            assert!(
-                matches!(config.sandbox_policy.get(), SandboxPolicy::ReadOnly),
-                "Expected ReadOnly on Windows"
-            );
-        } else {
-            assert!(
-                matches!(
-                    config.sandbox_policy.get(),
-                    SandboxPolicy::WorkspaceWrite { .. }
-                ),
-                "Expected WorkspaceWrite sandbox for untrusted project"
-            );
-        }
+        // Verify that untrusted projects get a read-only sandbox
+        assert!(
+            matches!(config.sandbox_policy.get(), SandboxPolicy::ReadOnly),
+            "Expected ReadOnly sandbox for untrusted project"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn limited_project_gets_read_only_preset_without_mcp_servers_or_web_search()
+    -> anyhow::Result<()> {
+        let codex_home = TempDir::new()?;
+        let test_project_dir = TempDir::new()?;
+        let test_path = test_project_dir.path();
+        let cfg = ConfigToml {
+            projects: Some(HashMap::from([(
+                test_path.to_string_lossy().to_string(),
+                ProjectConfig {
+                    trust_level: Some(TrustLevel::Limited),
+                },
+            )])),
+            mcp_servers: HashMap::from([("docs".to_string(), stdio_mcp("docs-server"))]),
+            web_search: Some(WebSearchMode::Live),
+            ..Default::default()
+        };
+        let overrides = ConfigOverrides {
+            cwd: Some(test_path.to_path_buf()),
+            ..Default::default()
+        };
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg.clone(),
+            overrides.clone(),
+            codex_home.path().to_path_buf(),
+        )?;
+        assert_eq!(config.approval_policy.value(), AskForApproval::OnRequest);
+        assert!(matches!(
+            config.sandbox_policy.get(),
+            SandboxPolicy::ReadOnly
+        ));
+        assert!(config.mcp_servers.get().is_empty());
+        assert_eq!(config.web_search_mode, Some(WebSearchMode::Disabled));
+
+        // `[trust_levels.limited]` can grant capabilities back.
+        let config = Config::load_from_base_config_with_overrides(
+            ConfigToml {
+                trust_levels: Some(TrustPresetsToml {
+                    limited: Some(TrustPresetToml {
+                        mcp_servers: Some(true),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+                ..cfg
+            },
+            overrides,
+            codex_home.path().to_path_buf(),
+        )?;
+        assert_eq!(
+            config.mcp_servers.get().keys().collect::<Vec<_>>(),
+            vec!["docs"]
+        );
+        assert_eq!(config.web_search_mode, Some(WebSearchMode::Disabled));
+
+        Ok(())
+    }
+
+    #[test]
+    fn explicit_approval_and_sandbox_win_over_trust_preset() -> anyhow::Result<()> {
+        let codex_home = TempDir::new()?;
+        let test_project_dir = TempDir::new()?;
+        let test_path = test_project_dir.path();
+        let cfg = ConfigToml {
+            projects: Some(HashMap::from([(
+                test_path.to_string_lossy().to_string(),
+                ProjectConfig {
+                    trust_level: Some(TrustLevel::Limited),
+                },
+            )])),
+            approval_policy: Some(AskForApproval::Never),
+            sandbox_mode: Some(SandboxMode::DangerFullAccess),
+            mcp_servers: HashMap::from([("docs".to_string(), stdio_mcp("docs-server"))]),
+            web_search: Some(WebSearchMode::Live),
+            ..Default::default()
+        };
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides {
+                cwd: Some(test_path.to_path_buf()),
+                ..Default::default()
+            },
+            codex_home.path().to_path_buf(),
+        )?;
+        // The preset only defaults the approval policy and sandbox...
+        assert_eq!(config.approval_policy.value(), AskForApproval::Never);
+        assert!(matches!(
+            config.sandbox_policy.get(),
+            SandboxPolicy::DangerFullAccess
+        ));
+        // ...but keeps MCP servers and web search off even when configured.
+        assert!(config.mcp_servers.get().is_empty());
+        assert_eq!(config.web_search_mode, Some(WebSearchMode::Disabled));
 
         Ok(())
     }
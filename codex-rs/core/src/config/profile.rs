@@ -17,6 +17,11 @@ use codex_protocol::openai_models::ReasoningEffort;
 #[schemars(deny_unknown_fields)]
 pub struct ConfigProfile {
     pub model: Option<String>,
+    /// Exact dated model snapshot this profile is pinned to. Takes precedence
+    /// over `model`; Codex warns when the provider no longer serves it.
+    pub model_snapshot: Option<String>,
+    /// Named login account this profile authenticates with.
+    pub account: Option<String>,
     /// The key in the `model_providers` map identifying the
     /// [`ModelProviderInfo`] to use.
     pub model_provider: Option<String>,
@@ -39,12 +44,22 @@ pub struct ConfigProfile {
     pub tools_web_search: Option<bool>,
     pub tools_view_image: Option<bool>,
     pub web_search: Option<WebSearchMode>,
+    /// Only offer the model these tools. Replaces the top-level
+    /// `enabled_tools` for this profile.
+    pub enabled_tools: Option<Vec<String>>,
+    /// Tools never offered to the model. Replaces the top-level
+    /// `disabled_tools` for this profile.
+    pub disabled_tools: Option<Vec<String>>,
     pub analytics: Option<crate::config::types::AnalyticsConfigToml>,
     /// Optional feature toggles scoped to this profile.
     #[serde(default)]
     // Injects known feature keys into the schema and forbids unknown keys.
     #[schemars(schema_with = "crate::config::schema::features_schema")]
     pub features: Option<crate::features::FeaturesToml>,
+    /// Experimental feature toggles scoped to this profile.
+    #[serde(default)]
+    #[schemars(schema_with = "crate::config::schema::experimental_features_schema")]
+    pub experimental: Option<crate::features::FeaturesToml>,
     pub oss_provider: Option<String>,
 }
 
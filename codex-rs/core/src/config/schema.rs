@@ -36,6 +36,26 @@ pub(crate) fn features_schema(schema_gen: &mut SchemaGenerator) -> Schema {
     Schema::Object(object)
 }
 
+/// Schema for the `[experimental]` map: experimental and in-development
+/// feature keys only.
+pub(crate) fn experimental_features_schema(schema_gen: &mut SchemaGenerator) -> Schema {
+    let mut object = SchemaObject {
+        instance_type: Some(InstanceType::Object.into()),
+        ..Default::default()
+    };
+
+    let mut validation = ObjectValidation::default();
+    for feature in FEATURES.iter().filter(|spec| spec.id.is_experimental()) {
+        validation
+            .properties
+            .insert(feature.key.to_string(), schema_gen.subschema_for::<bool>());
+    }
+    validation.additional_properties = Some(Box::new(Schema::Bool(false)));
+    object.object = Some(Box::new(validation));
+
+    Schema::Object(object)
+}
+
 /// Schema for the `[mcp_servers]` map using the raw input shape.
 pub(crate) fn mcp_servers_schema(schema_gen: &mut SchemaGenerator) -> Schema {
     let mut object = SchemaObject {
@@ -95,10 +115,131 @@ pub fn write_config_schema(out_path: &Path) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Result of looking up one key of a config table in the schema.
+pub(crate) enum SchemaLookup<'a> {
+    /// The key is declared; holds the schema for its value.
+    Known(&'a Value),
+    /// The table forbids the key. `suggestion` is the closest declared key, if any.
+    Unknown { suggestion: Option<String> },
+    /// The schema does not constrain this key (free-form maps, unions).
+    Unchecked,
+}
+
+/// The `config.toml` schema as JSON, used to detect unknown keys.
+pub(crate) fn config_schema_value() -> Value {
+    serde_json::to_value(config_schema()).unwrap_or(Value::Null)
+}
+
+/// Follows `$ref` and single-entry `allOf` wrappers to the schema they point at.
+fn resolve_schema<'a>(root: &'a Value, mut schema: &'a Value) -> &'a Value {
+    loop {
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str)
+            && let Some(name) = reference.strip_prefix("#/definitions/")
+            && let Some(target) = root.get("definitions").and_then(|defs| defs.get(name))
+        {
+            schema = target;
+            continue;
+        }
+        if let Some([inner]) = schema
+            .get("allOf")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+        {
+            schema = inner;
+            continue;
+        }
+        return schema;
+    }
+}
+
+/// Looks up `key` inside the table described by `schema`.
+pub(crate) fn lookup_key<'a>(root: &'a Value, schema: &'a Value, key: &str) -> SchemaLookup<'a> {
+    let schema = resolve_schema(root, schema);
+    let properties = schema.get("properties").and_then(Value::as_object);
+    if let Some(child) = properties.and_then(|properties| properties.get(key)) {
+        return SchemaLookup::Known(child);
+    }
+    match schema.get("additionalProperties") {
+        Some(Value::Bool(false)) => SchemaLookup::Unknown {
+            suggestion: properties.and_then(|properties| closest_key(key, properties.keys())),
+        },
+        Some(additional @ Value::Object(_)) => SchemaLookup::Known(additional),
+        _ => SchemaLookup::Unchecked,
+    }
+}
+
+/// Returns the schema for elements of the array described by `schema`.
+pub(crate) fn array_items<'a>(root: &'a Value, schema: &'a Value) -> Option<&'a Value> {
+    resolve_schema(root, schema)
+        .get("items")
+        .filter(|items| items.is_object())
+}
+
+/// Checks that every segment of a dotted config key is declared in the schema.
+pub fn validate_key_path(segments: &[String]) -> Result<(), String> {
+    let root = config_schema_value();
+    let mut schema = &root;
+    for (index, segment) in segments.iter().enumerate() {
+        match lookup_key(&root, schema, segment) {
+            SchemaLookup::Known(child) => schema = child,
+            SchemaLookup::Unchecked => return Ok(()),
+            SchemaLookup::Unknown { suggestion } => {
+                return Err(unknown_key_message(
+                    &segments[..index],
+                    segment,
+                    suggestion.as_deref(),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Formats the error for an undeclared key found under `parents`.
+pub(crate) fn unknown_key_message(
+    parents: &[String],
+    key: &str,
+    suggestion: Option<&str>,
+) -> String {
+    let prefix: String = parents.iter().map(|parent| format!("{parent}.")).collect();
+    match suggestion {
+        Some(suggestion) => {
+            format!("unknown config key `{prefix}{key}`; did you mean `{prefix}{suggestion}`?")
+        }
+        None => format!("unknown config key `{prefix}{key}`"),
+    }
+}
+
+/// Picks the declared key closest to `key`, if it is plausibly a typo.
+fn closest_key<'a>(key: &str, candidates: impl Iterator<Item = &'a String>) -> Option<String> {
+    let max_distance = (key.chars().count() / 3).max(2);
+    candidates
+        .map(|candidate| (edit_distance(key, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by(|(left, a), (right, b)| left.cmp(right).then_with(|| a.cmp(b)))
+        .map(|(_, candidate)| candidate.clone())
+}
+
+fn edit_distance(left: &str, right: &str) -> usize {
+    let right: Vec<char> = right.chars().collect();
+    let mut previous: Vec<usize> = (0..=right.len()).collect();
+    for (i, left_char) in left.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, right_char) in right.iter().enumerate() {
+            let substitution = previous[j] + usize::from(left_char != *right_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[right.len()]
+}
+
 #[cfg(test)]
 mod tests {
     use super::canonicalize;
     use super::config_schema_json;
+    use super::validate_key_path;
+    use pretty_assertions::assert_eq;
 
     use similar::TextDiff;
 
@@ -129,4 +270,28 @@ Run `just write-config-schema` to overwrite with your changes.\n\n{diff}"
             );
         }
     }
+
+    #[test]
+    fn validate_key_path_accepts_known_keys() {
+        let segments = |path: &str| path.split('.').map(str::to_string).collect::<Vec<_>>();
+        assert_eq!(validate_key_path(&segments("model")), Ok(()));
+        assert_eq!(validate_key_path(&segments("tui.notifications")), Ok(()));
+        assert_eq!(
+            validate_key_path(&segments("mcp_servers.docs.command")),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn validate_key_path_suggests_close_matches() {
+        let segments = |path: &str| path.split('.').map(str::to_string).collect::<Vec<_>>();
+        assert_eq!(
+            validate_key_path(&segments("modle")),
+            Err("unknown config key `modle`; did you mean `model`?".to_string())
+        );
+        assert_eq!(
+            validate_key_path(&segments("tui.animation")),
+            Err("unknown config key `tui.animation`; did you mean `tui.animations`?".to_string())
+        );
+    }
 }
@@ -539,7 +539,7 @@ fn clear_path(root: &mut TomlValue, segments: &[String]) -> Result<bool, MergeEr
     Ok(parent.remove(last).is_some())
 }
 
-fn toml_value_to_item(value: &TomlValue) -> anyhow::Result<TomlItem> {
+pub(crate) fn toml_value_to_item(value: &TomlValue) -> anyhow::Result<TomlItem> {
     match value {
         TomlValue::Table(table) => {
             let mut table_item = toml_edit::Table::new();
@@ -4,10 +4,16 @@
 // definitions that do not contain business logic.
 
 use crate::config_loader::RequirementSource;
+use crate::exec::ExecLimits;
+use crate::model_provider_info::ModelProviderInfo;
 pub use codex_protocol::config_types::AltScreenMode;
 pub use codex_protocol::config_types::ModeKind;
 pub use codex_protocol::config_types::Personality;
+use codex_protocol::config_types::SandboxMode;
+use codex_protocol::config_types::TrustLevel;
 pub use codex_protocol::config_types::WebSearchMode;
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::TokenUsage;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
@@ -20,7 +26,9 @@ use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Deserializer;
 use serde::Serialize;
+use serde::Serializer;
 use serde::de::Error as SerdeError;
+use serde::ser::SerializeMap;
 
 pub const DEFAULT_OTEL_ENVIRONMENT: &str = "dev";
 
@@ -77,6 +85,81 @@ pub struct McpServerConfig {
     /// Optional OAuth scopes to request during MCP login.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub scopes: Option<Vec<String>>,
+
+    /// When `true`, the server is started the first time its tools are needed
+    /// instead of when the session opens.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub lazy_start: bool,
+
+    /// Names the model sees for individual tools, keyed by the server's tool
+    /// name. The server prefix is kept: `mcp__<server>__<alias>`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_aliases: Option<HashMap<String, String>>,
+
+    /// Whether calls to this server's tools run without asking, always ask,
+    /// or are refused. When unset, the session's approval policy applies.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_approval: Option<McpToolApproval>,
+
+    /// Per-tool overrides of `tool_approval`, keyed by the server's tool name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_approvals: Option<HashMap<String, McpToolApproval>>,
+
+    /// Token budget for each tool result from this server. Larger results are
+    /// cut down and saved for `read_output`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_output_token_limit: Option<usize>,
+
+    /// Per-tool overrides of `tool_output_token_limit`, keyed by the server's
+    /// tool name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_output_token_limits: Option<HashMap<String, usize>>,
+}
+
+impl McpServerConfig {
+    /// The approval setting that applies to `tool`, if any.
+    pub fn tool_approval_for(&self, tool: &str) -> Option<McpToolApproval> {
+        self.tool_approvals
+            .as_ref()
+            .and_then(|approvals| approvals.get(tool).copied())
+            .or(self.tool_approval)
+    }
+
+    /// The output token budget that applies to each result of `tool`, if any.
+    pub fn tool_output_token_limit_for(&self, tool: &str) -> Option<usize> {
+        self.tool_output_token_limits
+            .as_ref()
+            .and_then(|limits| limits.get(tool).copied())
+            .or(self.tool_output_token_limit)
+    }
+}
+
+/// How Codex treats calls to an MCP server's tools.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum McpToolApproval {
+    /// Run the tool without asking.
+    Auto,
+    /// Ask before every call, whatever the session's approval policy.
+    Ask,
+    /// Never run the tool.
+    Deny,
+}
+
+impl McpToolApproval {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            McpToolApproval::Auto => "auto",
+            McpToolApproval::Ask => "ask",
+            McpToolApproval::Deny => "deny",
+        }
+    }
+}
+
+impl fmt::Display for McpToolApproval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 // Raw MCP config shape used for deserialization and JSON Schema generation.
@@ -98,10 +181,12 @@ pub(crate) struct RawMcpServerConfig {
     #[serde(default)]
     pub env_http_headers: Option<HashMap<String, String>>,
 
-    // streamable_http
+    // streamable_http and sse
     pub url: Option<String>,
     pub bearer_token: Option<String>,
     pub bearer_token_env_var: Option<String>,
+    #[serde(default)]
+    pub transport: Option<McpHttpTransport>,
 
     // shared
     #[serde(default)]
@@ -119,6 +204,18 @@ pub(crate) struct RawMcpServerConfig {
     pub disabled_tools: Option<Vec<String>>,
     #[serde(default)]
     pub scopes: Option<Vec<String>>,
+    #[serde(default)]
+    pub lazy_start: Option<bool>,
+    #[serde(default)]
+    pub tool_aliases: Option<HashMap<String, String>>,
+    #[serde(default)]
+    pub tool_approval: Option<McpToolApproval>,
+    #[serde(default)]
+    pub tool_approvals: Option<HashMap<String, McpToolApproval>>,
+    #[serde(default)]
+    pub tool_output_token_limit: Option<usize>,
+    #[serde(default)]
+    pub tool_output_token_limits: Option<HashMap<String, usize>>,
 }
 
 impl<'de> Deserialize<'de> for McpServerConfig {
@@ -141,6 +238,12 @@ impl<'de> Deserialize<'de> for McpServerConfig {
         let enabled_tools = raw.enabled_tools.clone();
         let disabled_tools = raw.disabled_tools.clone();
         let scopes = raw.scopes.clone();
+        let lazy_start = raw.lazy_start.unwrap_or_default();
+        let tool_aliases = raw.tool_aliases.clone();
+        let tool_approval = raw.tool_approval;
+        let tool_approvals = raw.tool_approvals.clone();
+        let tool_output_token_limit = raw.tool_output_token_limit;
+        let tool_output_token_limits = raw.tool_output_token_limits.clone();
 
         fn throw_if_set<E, T>(transport: &str, field: &str, value: Option<&T>) -> Result<(), E>
         where
@@ -164,6 +267,7 @@ impl<'de> Deserialize<'de> for McpServerConfig {
             throw_if_set("stdio", "bearer_token", raw.bearer_token.as_ref())?;
             throw_if_set("stdio", "http_headers", raw.http_headers.as_ref())?;
             throw_if_set("stdio", "env_http_headers", raw.env_http_headers.as_ref())?;
+            throw_if_set("stdio", "transport", raw.transport.as_ref())?;
             McpServerTransportConfig::Stdio {
                 command,
                 args: raw.args.clone().unwrap_or_default(),
@@ -177,11 +281,19 @@ impl<'de> Deserialize<'de> for McpServerConfig {
             throw_if_set("streamable_http", "env_vars", raw.env_vars.as_ref())?;
             throw_if_set("streamable_http", "cwd", raw.cwd.as_ref())?;
             throw_if_set("streamable_http", "bearer_token", raw.bearer_token.as_ref())?;
-            McpServerTransportConfig::StreamableHttp {
-                url,
-                bearer_token_env_var: raw.bearer_token_env_var.clone(),
-                http_headers: raw.http_headers.clone(),
-                env_http_headers: raw.env_http_headers.take(),
+            match raw.transport.unwrap_or_default() {
+                McpHttpTransport::StreamableHttp => McpServerTransportConfig::StreamableHttp {
+                    url,
+                    bearer_token_env_var: raw.bearer_token_env_var.clone(),
+                    http_headers: raw.http_headers.clone(),
+                    env_http_headers: raw.env_http_headers.take(),
+                },
+                McpHttpTransport::Sse => McpServerTransportConfig::Sse {
+                    url,
+                    bearer_token_env_var: raw.bearer_token_env_var.clone(),
+                    http_headers: raw.http_headers.clone(),
+                    env_http_headers: raw.env_http_headers.take(),
+                },
             }
         } else {
             return Err(SerdeError::custom("invalid transport"));
@@ -196,6 +308,12 @@ impl<'de> Deserialize<'de> for McpServerConfig {
             enabled_tools,
             disabled_tools,
             scopes,
+            lazy_start,
+            tool_aliases,
+            tool_approval,
+            tool_approvals,
+            tool_output_token_limit,
+            tool_output_token_limits,
         })
     }
 }
@@ -234,6 +352,61 @@ pub enum McpServerTransportConfig {
         #[serde(default, skip_serializing_if = "Option::is_none")]
         env_http_headers: Option<HashMap<String, String>>,
     },
+    /// https://modelcontextprotocol.io/specification/2024-11-05/basic/transports#http-with-sse
+    ///
+    /// Selected in config with `transport = "sse"` next to `url`. The fields
+    /// match [`McpServerTransportConfig::StreamableHttp`].
+    // `serialize_sse_transport` skips unset fields itself; serde rejects
+    // field-level `skip_serializing_if` next to a variant `serialize_with`.
+    #[serde(serialize_with = "serialize_sse_transport")]
+    Sse {
+        url: String,
+        #[serde(default)]
+        bearer_token_env_var: Option<String>,
+        #[serde(default)]
+        http_headers: Option<HashMap<String, String>>,
+        #[serde(default)]
+        env_http_headers: Option<HashMap<String, String>>,
+    },
+}
+
+/// Writes the `transport = "sse"` marker so the config reads back as SSE
+/// rather than streamable HTTP.
+#[allow(clippy::ptr_arg)]
+fn serialize_sse_transport<S>(
+    url: &String,
+    bearer_token_env_var: &Option<String>,
+    http_headers: &Option<HashMap<String, String>>,
+    env_http_headers: &Option<HashMap<String, String>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let mut map = serializer.serialize_map(None)?;
+    map.serialize_entry("url", url)?;
+    map.serialize_entry("transport", "sse")?;
+    if let Some(env_var) = bearer_token_env_var {
+        map.serialize_entry("bearer_token_env_var", env_var)?;
+    }
+    if let Some(headers) = http_headers {
+        map.serialize_entry("http_headers", headers)?;
+    }
+    if let Some(headers) = env_http_headers {
+        map.serialize_entry("env_http_headers", headers)?;
+    }
+    map.end()
+}
+
+/// HTTP transport used for an MCP server configured with `url`.
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum McpHttpTransport {
+    #[default]
+    StreamableHttp,
+    /// The HTTP+SSE transport from the 2024-11-05 MCP spec, still used by
+    /// many hosted servers.
+    Sse,
 }
 
 mod option_duration_secs {
@@ -315,6 +488,19 @@ pub enum HistoryPersistence {
     None,
 }
 
+/// How the `notify` command is run.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum NotifyMode {
+    /// Spawn the command for every notification, with the JSON payload as its
+    /// last argument.
+    #[default]
+    PerEvent,
+    /// Spawn the command once per session and write one JSON payload per line
+    /// to its stdin.
+    Persistent,
+}
+
 // ===== Analytics configuration =====
 
 /// Analytics settings loaded from config.toml. Fields are optional so we can apply defaults.
@@ -435,6 +621,7 @@ pub enum NotificationMethod {
     Auto,
     Osc9,
     Bel,
+    Native,
 }
 
 impl fmt::Display for NotificationMethod {
@@ -443,6 +630,7 @@ impl fmt::Display for NotificationMethod {
             NotificationMethod::Auto => write!(f, "auto"),
             NotificationMethod::Osc9 => write!(f, "osc9"),
             NotificationMethod::Bel => write!(f, "bel"),
+            NotificationMethod::Native => write!(f, "native"),
         }
     }
 }
@@ -457,7 +645,8 @@ pub struct Tui {
     pub notifications: Notifications,
 
     /// Notification method to use for unfocused terminal notifications.
-    /// Defaults to `auto`.
+    /// `native` uses the platform notifier, which can show Approve/Deny
+    /// buttons on approval requests. Defaults to `auto`.
     #[serde(default)]
     pub notification_method: NotificationMethod,
 
@@ -486,8 +675,17 @@ pub struct Tui {
     /// scrollback in terminal multiplexers like Zellij that follow the xterm spec.
     #[serde(default)]
     pub alternate_screen: AltScreenMode,
+
+    /// Pastes longer than this many characters prompt to be saved to a file
+    /// and referenced instead of sent inline. `0` disables the prompt.
+    /// Defaults to `40000` (roughly 10k tokens).
+    #[serde(default)]
+    pub paste_attach_threshold: Option<usize>,
 }
 
+/// Default for `tui.paste_attach_threshold`.
+pub const DEFAULT_PASTE_ATTACH_THRESHOLD: usize = 40_000;
+
 const fn default_true() -> bool {
     true
 }
@@ -666,6 +864,1063 @@ impl Default for ShellEnvironmentPolicy {
     }
 }
 
+/// Settings for the session trash that preserves files the agent deletes.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct TrashToml {
+    /// Copy files into the session trash before a patch deletes or overwrites
+    /// them, and after a shell command deletes them (recovered from the turn's
+    /// `undo` snapshot). Defaults to `false`.
+    pub enabled: Option<bool>,
+    /// Offer to permanently delete the session trash when the user quits the
+    /// TUI. Defaults to `false`, which keeps trashed files under
+    /// `CODEX_HOME/trash`.
+    pub purge_on_exit: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TrashConfig {
+    pub enabled: bool,
+    pub purge_on_exit: bool,
+}
+
+impl From<TrashToml> for TrashConfig {
+    fn from(toml: TrashToml) -> Self {
+        Self {
+            enabled: toml.enabled.unwrap_or(false),
+            purge_on_exit: toml.purge_on_exit.unwrap_or(false),
+        }
+    }
+}
+
+/// Embedding model behind `codex index build` and the `search_code` tool,
+/// from `[code_index]`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct CodeIndexToml {
+    /// Base URL of an OpenAI-compatible embeddings API; `/embeddings` is
+    /// appended. Defaults to a local Ollama server at
+    /// `http://localhost:11434/v1`.
+    pub base_url: Option<String>,
+    /// Embedding model to request. Defaults to `nomic-embed-text`.
+    pub model: Option<String>,
+    /// Environment variable holding a bearer token, for endpoints that need
+    /// one.
+    pub api_key_env: Option<String>,
+}
+
+pub const DEFAULT_CODE_INDEX_BASE_URL: &str = "http://localhost:11434/v1";
+pub const DEFAULT_CODE_INDEX_MODEL: &str = "nomic-embed-text";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeIndexConfig {
+    pub base_url: String,
+    pub model: String,
+    pub api_key_env: Option<String>,
+}
+
+impl Default for CodeIndexConfig {
+    fn default() -> Self {
+        CodeIndexToml::default().into()
+    }
+}
+
+impl From<CodeIndexToml> for CodeIndexConfig {
+    fn from(toml: CodeIndexToml) -> Self {
+        Self {
+            base_url: toml
+                .base_url
+                .unwrap_or_else(|| DEFAULT_CODE_INDEX_BASE_URL.to_string()),
+            model: toml
+                .model
+                .unwrap_or_else(|| DEFAULT_CODE_INDEX_MODEL.to_string()),
+            api_key_env: toml.api_key_env,
+        }
+    }
+}
+
+/// Default limits for commands run with the `shell` and `shell_command`
+/// tools. A call can shorten the timeout and tighten the other limits.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ExecLimitsToml {
+    /// Wall-clock time a command may run, in milliseconds, when the call sets
+    /// no `timeout_ms`. Defaults to 10 seconds.
+    pub timeout_ms: Option<u64>,
+    /// Bytes of stdout and stderr kept from a command. Defaults to 1 MiB.
+    pub max_output_bytes: Option<usize>,
+    /// CPU time a command may use, in seconds. Unlimited by default.
+    pub cpu_seconds: Option<u64>,
+    /// Memory a command may use, in megabytes. Unlimited by default.
+    pub memory_mb: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExecLimitsConfig {
+    pub timeout_ms: Option<u64>,
+    pub limits: ExecLimits,
+}
+
+impl From<ExecLimitsToml> for ExecLimitsConfig {
+    fn from(toml: ExecLimitsToml) -> Self {
+        Self {
+            timeout_ms: toml.timeout_ms,
+            limits: ExecLimits {
+                max_output_bytes: toml.max_output_bytes,
+                cpu_seconds: toml.cpu_seconds,
+                memory_mb: toml.memory_mb,
+            },
+        }
+    }
+}
+
+/// The settings in `[network_proxy]` that Codex uses to route sandboxed
+/// commands through the network proxy. The rest of the table, including the
+/// allowlist, is read by `codex-network-proxy` itself.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+pub struct NetworkProxyToml {
+    /// Let sandboxed commands without network access use the proxy.
+    /// Defaults to `false`.
+    pub enabled: Option<bool>,
+    /// Address of the proxy's HTTP listener; must be a loopback address.
+    /// Defaults to `http://127.0.0.1:3128`.
+    pub proxy_url: Option<String>,
+}
+
+/// Syscall groups `[linux_sandbox]` can refuse with seccomp.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum SyscallClass {
+    /// `mount`, `umount2`, `pivot_root` and the new mount API.
+    Mount,
+    /// `unshare` and `setns`.
+    Namespaces,
+    /// Loading and unloading kernel modules.
+    KernelModules,
+    /// `bpf`.
+    Bpf,
+    /// `perf_event_open`.
+    PerfEvents,
+    /// `add_key`, `request_key` and `keyctl`.
+    Keyring,
+    /// `reboot`, `kexec_load` and `kexec_file_load`.
+    Reboot,
+    /// Setting the system clock.
+    Clock,
+    /// `swapon` and `swapoff`.
+    Swap,
+}
+
+/// Extra Linux sandbox restrictions, applied by `codex-linux-sandbox` on top
+/// of the sandbox policy.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct LinuxSandboxToml {
+    /// Directories that are writable in addition to the policy's writable
+    /// roots.
+    pub writable_roots: Option<Vec<AbsolutePathBuf>>,
+    /// When set, reads are limited to system directories, the working
+    /// directory, the writable roots, and these directories.
+    pub readable_roots: Option<Vec<AbsolutePathBuf>>,
+    /// Syscall groups that fail with `EPERM`.
+    pub blocked_syscalls: Option<Vec<SyscallClass>>,
+    /// Refuse `ptrace` and cross-process memory access even when the network
+    /// is enabled. Defaults to `false`.
+    pub deny_ptrace: Option<bool>,
+    /// Refuse to create Unix domain sockets, so commands cannot connect to
+    /// local daemons such as Docker or an SSH agent. Defaults to `false`.
+    pub deny_unix_socket_connect: Option<bool>,
+}
+
+/// Resolved `[linux_sandbox]` settings. Passed to `codex-linux-sandbox` as
+/// JSON, like the sandbox policy.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct LinuxSandboxConfig {
+    pub writable_roots: Vec<AbsolutePathBuf>,
+    pub readable_roots: Vec<AbsolutePathBuf>,
+    pub blocked_syscalls: Vec<SyscallClass>,
+    pub deny_ptrace: bool,
+    pub deny_unix_socket_connect: bool,
+}
+
+impl From<LinuxSandboxToml> for LinuxSandboxConfig {
+    fn from(toml: LinuxSandboxToml) -> Self {
+        Self {
+            writable_roots: toml.writable_roots.unwrap_or_default(),
+            readable_roots: toml.readable_roots.unwrap_or_default(),
+            blocked_syscalls: toml.blocked_syscalls.unwrap_or_default(),
+            deny_ptrace: toml.deny_ptrace.unwrap_or(false),
+            deny_unix_socket_connect: toml.deny_unix_socket_connect.unwrap_or(false),
+        }
+    }
+}
+
+impl std::str::FromStr for LinuxSandboxConfig {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s)
+    }
+}
+
+/// Container engine used by `[container]`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ContainerRuntime {
+    #[default]
+    Docker,
+    Podman,
+}
+
+impl ContainerRuntime {
+    pub fn program(self) -> &'static str {
+        match self {
+            Self::Docker => "docker",
+            Self::Podman => "podman",
+        }
+    }
+}
+
+/// Runs the shell tools in a container instead of on the host.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ContainerToml {
+    /// Image to run commands in. Setting it turns the container backend on.
+    pub image: Option<String>,
+    /// Container engine. Defaults to `docker`.
+    pub runtime: Option<ContainerRuntime>,
+    /// Where the workspace is mounted in the container. Defaults to
+    /// `/workspace`.
+    pub workdir: Option<String>,
+    /// Shell that runs scripts in the container. Defaults to `/bin/sh`.
+    pub shell: Option<String>,
+    /// Extra arguments for `docker run`, such as `--memory=4g` or further
+    /// volumes.
+    pub run_args: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainerConfig {
+    pub image: Option<String>,
+    pub runtime: ContainerRuntime,
+    pub workdir: String,
+    pub shell: String,
+    pub run_args: Vec<String>,
+}
+
+impl Default for ContainerConfig {
+    fn default() -> Self {
+        ContainerToml::default().into()
+    }
+}
+
+impl From<ContainerToml> for ContainerConfig {
+    fn from(toml: ContainerToml) -> Self {
+        Self {
+            image: toml.image,
+            runtime: toml.runtime.unwrap_or_default(),
+            workdir: toml.workdir.unwrap_or_else(|| "/workspace".to_string()),
+            shell: toml.shell.unwrap_or_else(|| "/bin/sh".to_string()),
+            run_args: toml.run_args.unwrap_or_default(),
+        }
+    }
+}
+
+/// How `[remote]` keeps the remote directory in step with the workspace.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RemoteSync {
+    /// Copy the workspace up before each command and changes back after it.
+    #[default]
+    Rsync,
+    /// `remote_dir` is a checkout kept up to date by the user.
+    Checkout,
+}
+
+/// Runs the shell tools on another machine over SSH.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct RemoteToml {
+    /// SSH destination, such as `builder` or `me@build.example.com`. Setting it
+    /// turns the remote backend on.
+    pub host: Option<String>,
+    /// Absolute path of the workspace on the remote host.
+    pub remote_dir: Option<String>,
+    /// Defaults to `rsync`.
+    pub sync: Option<RemoteSync>,
+    /// rsync patterns that are never copied, such as `target/`.
+    pub exclude: Option<Vec<String>>,
+    /// Extra arguments for `ssh`, such as `-p 2222`.
+    pub ssh_args: Option<Vec<String>>,
+    /// Shell that runs scripts on the remote host. Defaults to `/bin/sh`.
+    pub shell: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RemoteConfig {
+    pub host: Option<String>,
+    pub remote_dir: String,
+    pub sync: RemoteSync,
+    pub exclude: Vec<String>,
+    pub ssh_args: Vec<String>,
+    pub shell: String,
+}
+
+impl From<RemoteToml> for RemoteConfig {
+    fn from(toml: RemoteToml) -> Self {
+        Self {
+            host: toml.host,
+            remote_dir: toml
+                .remote_dir
+                .map(|dir| dir.trim_end_matches('/').to_string())
+                .unwrap_or_default(),
+            sync: toml.sync.unwrap_or_default(),
+            exclude: toml.exclude.unwrap_or_default(),
+            ssh_args: toml.ssh_args.unwrap_or_default(),
+            shell: toml.shell.unwrap_or_else(|| "/bin/sh".to_string()),
+        }
+    }
+}
+
+/// Co-author credited by attribution trailers when none is configured.
+pub const DEFAULT_ATTRIBUTION_CO_AUTHOR: &str = "Codex <noreply@openai.com>";
+
+/// Settings for attribution trailers on commits and patches created by the agent.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct AttributionToml {
+    /// Append attribution trailers to commits and exported patches.
+    /// Defaults to `false`.
+    pub enabled: Option<bool>,
+    /// Identity used for the `Co-authored-by` trailer.
+    /// Defaults to `Codex <noreply@openai.com>`.
+    pub co_author: Option<String>,
+    /// Also add a `Codex-Session` trailer with the session id.
+    /// Defaults to `true`.
+    pub session_id: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttributionConfig {
+    pub enabled: bool,
+    pub co_author: String,
+    pub session_id: bool,
+}
+
+impl Default for AttributionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            co_author: DEFAULT_ATTRIBUTION_CO_AUTHOR.to_string(),
+            session_id: true,
+        }
+    }
+}
+
+impl From<AttributionToml> for AttributionConfig {
+    fn from(toml: AttributionToml) -> Self {
+        let defaults = Self::default();
+        Self {
+            enabled: toml.enabled.unwrap_or(defaults.enabled),
+            co_author: toml
+                .co_author
+                .filter(|co_author| !co_author.trim().is_empty())
+                .unwrap_or(defaults.co_author),
+            session_id: toml.session_id.unwrap_or(defaults.session_id),
+        }
+    }
+}
+
+/// Settings for masking secrets before they leave the process.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct RedactionToml {
+    /// Mask secrets in model prompts, rollout files, session logs, and
+    /// notification payloads. Defaults to `false`.
+    pub enabled: Option<bool>,
+    /// Mask common API keys, cloud credentials, and private keys.
+    /// Defaults to `true`.
+    pub builtin_patterns: Option<bool>,
+    /// Additional regular expressions whose matches are masked. A capture
+    /// group named `secret` limits masking to that group.
+    /// Defaults to `[]`.
+    pub patterns: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedactionConfig {
+    pub enabled: bool,
+    pub builtin_patterns: bool,
+    pub patterns: Vec<String>,
+}
+
+impl Default for RedactionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            builtin_patterns: true,
+            patterns: Vec::new(),
+        }
+    }
+}
+
+impl From<RedactionToml> for RedactionConfig {
+    fn from(toml: RedactionToml) -> Self {
+        let defaults = Self::default();
+        Self {
+            enabled: toml.enabled.unwrap_or(defaults.enabled),
+            builtin_patterns: toml.builtin_patterns.unwrap_or(defaults.builtin_patterns),
+            patterns: toml.patterns.unwrap_or(defaults.patterns),
+        }
+    }
+}
+
+/// How rollout files are encrypted at rest.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash, Default, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionEncryptionMode {
+    /// Write rollouts as plain JSONL.
+    #[default]
+    Off,
+    /// Encrypt with a random key stored in the OS keychain, created on first use.
+    Keyring,
+    /// Encrypt with a key derived from the `CODEX_SESSION_PASSPHRASE`
+    /// environment variable.
+    Passphrase,
+}
+
+/// Limits on how much session history is kept under `CODEX_HOME`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct SessionRetentionToml {
+    /// Delete rollout files and logs not modified for this many days.
+    pub max_age_days: Option<u64>,
+    /// Delete the oldest rollout files once all of them together exceed this
+    /// many megabytes.
+    pub max_total_size_mb: Option<u64>,
+    /// Keep at most this many sessions per working directory.
+    pub max_sessions_per_project: Option<usize>,
+}
+
+/// Resolved [`SessionRetentionToml`]; every limit is off by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SessionRetentionConfig {
+    pub max_age_days: Option<u64>,
+    pub max_total_bytes: Option<u64>,
+    pub max_sessions_per_project: Option<usize>,
+}
+
+impl SessionRetentionConfig {
+    pub fn is_unbounded(&self) -> bool {
+        self.max_age_days.is_none()
+            && self.max_total_bytes.is_none()
+            && self.max_sessions_per_project.is_none()
+    }
+}
+
+impl From<SessionRetentionToml> for SessionRetentionConfig {
+    fn from(toml: SessionRetentionToml) -> Self {
+        Self {
+            max_age_days: toml.max_age_days,
+            max_total_bytes: toml
+                .max_total_size_mb
+                .map(|megabytes| megabytes.saturating_mul(1024 * 1024)),
+            max_sessions_per_project: toml.max_sessions_per_project,
+        }
+    }
+}
+
+/// Settings for opening pull requests (GitHub) and merge requests (GitLab).
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct PullRequestsToml {
+    /// Remote to push to and open pull requests against. Defaults to `origin`.
+    pub remote: Option<String>,
+    /// Environment variable holding a GitHub token. Defaults to `GITHUB_TOKEN`;
+    /// when it is unset, the token from `gh auth token` is used.
+    pub github_token_env_var: Option<String>,
+    /// Environment variable holding a GitLab token. Defaults to `GITLAB_TOKEN`;
+    /// when it is unset, the token from `glab config get token` is used.
+    pub gitlab_token_env_var: Option<String>,
+    /// GitHub Enterprise hosts, such as `github.example.com`, besides
+    /// `github.com`.
+    pub github_hosts: Option<Vec<String>>,
+    /// Self-hosted GitLab hosts, such as `gitlab.example.com`, besides
+    /// `gitlab.com`.
+    pub gitlab_hosts: Option<Vec<String>>,
+}
+
+/// Resolved [`PullRequestsToml`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PullRequestsConfig {
+    pub remote: String,
+    pub github_token_env_var: String,
+    pub gitlab_token_env_var: String,
+    pub github_hosts: Vec<String>,
+    pub gitlab_hosts: Vec<String>,
+}
+
+impl Default for PullRequestsConfig {
+    fn default() -> Self {
+        PullRequestsToml::default().into()
+    }
+}
+
+impl From<PullRequestsToml> for PullRequestsConfig {
+    fn from(toml: PullRequestsToml) -> Self {
+        Self {
+            remote: toml.remote.unwrap_or_else(|| "origin".to_string()),
+            github_token_env_var: toml
+                .github_token_env_var
+                .unwrap_or_else(|| "GITHUB_TOKEN".to_string()),
+            gitlab_token_env_var: toml
+                .gitlab_token_env_var
+                .unwrap_or_else(|| "GITLAB_TOKEN".to_string()),
+            github_hosts: toml.github_hosts.unwrap_or_default(),
+            gitlab_hosts: toml.gitlab_hosts.unwrap_or_default(),
+        }
+    }
+}
+
+/// Commands run after the agent applies a patch or before it commits.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct HooksToml {
+    /// Run after each patch the agent applies, e.g. a formatter.
+    #[serde(default)]
+    pub post_patch: Vec<HookToml>,
+    /// Run before the `git_commit` tool stages its files; a failing hook
+    /// stops the commit.
+    #[serde(default)]
+    pub pre_commit: Vec<HookToml>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct HookToml {
+    /// Program and arguments. An argument that is exactly `{files}` is
+    /// replaced by the affected files.
+    pub command: Vec<String>,
+    /// Glob patterns, e.g. `["*.rs"]`; the hook only runs when an affected
+    /// file matches one. Defaults to running for every file.
+    #[serde(default)]
+    pub paths: Vec<String>,
+    /// Defaults to 60000.
+    pub timeout_ms: Option<u64>,
+    /// Return the hook's output to the model. Defaults to `true`.
+    pub feedback: Option<bool>,
+}
+
+pub const DEFAULT_HOOK_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Resolved [`HooksToml`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HooksConfig {
+    pub post_patch: Vec<HookConfig>,
+    pub pre_commit: Vec<HookConfig>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookConfig {
+    pub command: Vec<String>,
+    pub paths: Vec<String>,
+    pub timeout: Duration,
+    pub feedback: bool,
+}
+
+impl From<HooksToml> for HooksConfig {
+    fn from(toml: HooksToml) -> Self {
+        Self {
+            post_patch: toml.post_patch.into_iter().map(HookConfig::from).collect(),
+            pre_commit: toml.pre_commit.into_iter().map(HookConfig::from).collect(),
+        }
+    }
+}
+
+impl From<HookToml> for HookConfig {
+    fn from(toml: HookToml) -> Self {
+        Self {
+            command: toml.command,
+            paths: toml.paths,
+            timeout: toml
+                .timeout_ms
+                .map_or(DEFAULT_HOOK_TIMEOUT, Duration::from_millis),
+            feedback: toml.feedback.unwrap_or(true),
+        }
+    }
+}
+
+/// Language servers queried for diagnostics after the agent edits files.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct LspToml {
+    /// Servers keyed by a name of your choosing, e.g. `[lsp.servers.rust]`.
+    #[serde(default)]
+    pub servers: BTreeMap<String, LspServerToml>,
+    /// How long to wait for a server to report diagnostics after an edit.
+    /// Defaults to 5000.
+    pub diagnostics_timeout_ms: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct LspServerToml {
+    /// Executable that speaks LSP over stdio, e.g. `rust-analyzer`.
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// File extensions this server handles, without the dot, e.g. `["rs"]`.
+    pub extensions: Vec<String>,
+    /// Sent as `initializationOptions` in the `initialize` request.
+    pub initialization_options: Option<serde_json::Value>,
+}
+
+pub const DEFAULT_LSP_DIAGNOSTICS_TIMEOUT: Duration = Duration::from_millis(5_000);
+
+/// Resolved [`LspToml`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LspConfig {
+    pub servers: BTreeMap<String, LspServerToml>,
+    pub diagnostics_timeout: Duration,
+}
+
+impl Default for LspConfig {
+    fn default() -> Self {
+        LspToml::default().into()
+    }
+}
+
+impl From<LspToml> for LspConfig {
+    fn from(toml: LspToml) -> Self {
+        Self {
+            servers: toml.servers,
+            diagnostics_timeout: toml
+                .diagnostics_timeout_ms
+                .map_or(DEFAULT_LSP_DIAGNOSTICS_TIMEOUT, Duration::from_millis),
+        }
+    }
+}
+
+/// Approval policy for one class of file operation.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum FileApprovalPolicy {
+    /// Follow `approval_policy` and the sandbox, as for any other change.
+    #[default]
+    Default,
+    /// Allow without asking. The operation still runs in the sandbox.
+    Auto,
+    /// Always ask, even when the sandbox would allow the operation.
+    Ask,
+    /// Always reject.
+    Deny,
+}
+
+/// Class of file operation that `file_approvals` assigns a policy to.
+pub use codex_protocol::protocol::FileOperation;
+
+/// Per-operation approval policies for file access by the agent.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct FileApprovalsToml {
+    /// Reads outside the session's working directory by `read_file`,
+    /// `list_dir`, and `grep_files`. Defaults to `default`.
+    pub read: Option<FileApprovalPolicy>,
+    /// Files created or modified by `apply_patch`. Defaults to `default`.
+    pub write: Option<FileApprovalPolicy>,
+    /// Files deleted (or moved away) by `apply_patch`. Defaults to `default`.
+    pub delete: Option<FileApprovalPolicy>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FileApprovalsConfig {
+    pub read: FileApprovalPolicy,
+    pub write: FileApprovalPolicy,
+    pub delete: FileApprovalPolicy,
+}
+
+impl FileApprovalsConfig {
+    pub fn policy(&self, operation: FileOperation) -> FileApprovalPolicy {
+        match operation {
+            FileOperation::Read => self.read,
+            FileOperation::Write => self.write,
+            FileOperation::Delete => self.delete,
+        }
+    }
+}
+
+impl From<FileApprovalsToml> for FileApprovalsConfig {
+    fn from(toml: FileApprovalsToml) -> Self {
+        Self {
+            read: toml.read.unwrap_or_default(),
+            write: toml.write.unwrap_or_default(),
+            delete: toml.delete.unwrap_or_default(),
+        }
+    }
+}
+
+/// Approval settings for shell commands.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ApprovalToml {
+    /// Command prefixes, such as `"cargo test"`, that run without approval
+    /// (still sandboxed) unless the command is destructive.
+    pub safe_commands: Option<Vec<String>>,
+    /// Rules checked, in order, before a shell command or patch is approved
+    /// or prompted for. The first matching rule decides.
+    pub rules: Option<Vec<ApprovalRuleToml>>,
+}
+
+/// What an approval rule does with a matching request.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ApprovalRuleDecision {
+    /// Run without asking, still sandboxed unless the rule sets
+    /// `sandbox = "unsandboxed"`.
+    Allow,
+    /// Reject without asking.
+    Deny,
+    /// Always ask, even when the request would otherwise be approved.
+    Ask,
+}
+
+/// Kind of tool call an approval rule applies to.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalRuleTool {
+    /// Commands from `shell`, `shell_command`, and `exec_command`.
+    Shell,
+    /// Patches from `apply_patch`.
+    ApplyPatch,
+}
+
+/// Whether the request would run inside the sandbox.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ApprovalRuleSandbox {
+    Sandboxed,
+    Unsandboxed,
+}
+
+/// One `[[approval.rules]]` entry. Every matcher that is set must match.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ApprovalRuleToml {
+    /// Kind of tool call the rule applies to. Unset matches both.
+    pub tool: Option<ApprovalRuleTool>,
+    /// Regex searched for in each command of the command line or `bash -lc`
+    /// script. `allow` rules match only when every command matches; `deny`
+    /// and `ask` rules match when any command or the whole script does.
+    /// Rules with `command` never match patches.
+    pub command: Option<String>,
+    /// Globs for the files a patch changes, relative to the working
+    /// directory. Rules with `paths` never match shell commands.
+    pub paths: Option<Vec<String>>,
+    /// Whether the request would run inside the sandbox.
+    pub sandbox: Option<ApprovalRuleSandbox>,
+    pub decision: ApprovalRuleDecision,
+    /// Shown to the user and the model when the rule denies or asks.
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApprovalRule {
+    pub tool: Option<ApprovalRuleTool>,
+    pub command: Option<ApprovalRuleCommand>,
+    pub paths: Vec<String>,
+    pub sandbox: Option<ApprovalRuleSandbox>,
+    pub decision: ApprovalRuleDecision,
+    pub reason: Option<String>,
+}
+
+impl TryFrom<ApprovalRuleToml> for ApprovalRule {
+    type Error = String;
+
+    fn try_from(toml: ApprovalRuleToml) -> Result<Self, Self::Error> {
+        Ok(Self {
+            tool: toml.tool,
+            command: toml.command.map(ApprovalRuleCommand::new).transpose()?,
+            paths: toml.paths.unwrap_or_default(),
+            sandbox: toml.sandbox,
+            decision: toml.decision,
+            reason: toml.reason,
+        })
+    }
+}
+
+/// An approval rule's `command` regex, compiled once when the config loads.
+#[derive(Debug, Clone)]
+pub struct ApprovalRuleCommand {
+    pattern: String,
+    regex: regex_lite::Regex,
+}
+
+impl ApprovalRuleCommand {
+    pub fn new(pattern: String) -> Result<Self, String> {
+        match regex_lite::Regex::new(&pattern) {
+            Ok(regex) => Ok(Self { pattern, regex }),
+            Err(err) => Err(format!("invalid approval rule command `{pattern}`: {err}")),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    pub fn is_match(&self, text: &str) -> bool {
+        self.regex.is_match(text)
+    }
+}
+
+impl PartialEq for ApprovalRuleCommand {
+    fn eq(&self, other: &Self) -> bool {
+        self.pattern == other.pattern
+    }
+}
+
+impl Eq for ApprovalRuleCommand {}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ApprovalConfig {
+    /// `safe_commands`, split into words.
+    pub safe_commands: Vec<Vec<String>>,
+    pub rules: Vec<ApprovalRule>,
+}
+
+impl TryFrom<ApprovalToml> for ApprovalConfig {
+    type Error = String;
+
+    fn try_from(toml: ApprovalToml) -> Result<Self, Self::Error> {
+        Ok(Self {
+            safe_commands: toml
+                .safe_commands
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|pattern| shlex::split(pattern))
+                .filter(|words| !words.is_empty())
+                .collect(),
+            rules: toml
+                .rules
+                .unwrap_or_default()
+                .into_iter()
+                .map(ApprovalRule::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+pub const DEFAULT_PATCH_MAX_FILE_BYTES: u64 = 4 * 1024 * 1024;
+pub const DEFAULT_PATCH_MAX_REWRITE_LINES: usize = 400;
+
+/// Limits that keep `apply_patch` from silently rewriting large or binary
+/// files.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct PatchLimitsToml {
+    /// Files larger than this many bytes are not patched; the model is told
+    /// to make a targeted edit with a shell command instead. Defaults to
+    /// 4194304 (4 MiB); 0 disables the limit.
+    pub max_file_bytes: Option<u64>,
+    /// Files longer than this many lines may not be replaced wholesale, by
+    /// `*** Add File` over the existing file or by hunks replacing nearly all
+    /// of it; only targeted hunks are accepted. Defaults to 400; 0 disables
+    /// the limit.
+    pub max_rewrite_lines: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PatchLimitsConfig {
+    /// Zero means no limit.
+    pub max_file_bytes: u64,
+    /// Zero means no limit.
+    pub max_rewrite_lines: usize,
+}
+
+impl Default for PatchLimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_file_bytes: DEFAULT_PATCH_MAX_FILE_BYTES,
+            max_rewrite_lines: DEFAULT_PATCH_MAX_REWRITE_LINES,
+        }
+    }
+}
+
+impl From<PatchLimitsToml> for PatchLimitsConfig {
+    fn from(toml: PatchLimitsToml) -> Self {
+        Self {
+            max_file_bytes: toml.max_file_bytes.unwrap_or(DEFAULT_PATCH_MAX_FILE_BYTES),
+            max_rewrite_lines: toml
+                .max_rewrite_lines
+                .unwrap_or(DEFAULT_PATCH_MAX_REWRITE_LINES),
+        }
+    }
+}
+
+/// Whether commands run by the agent may use one kind of the user's
+/// credentials.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum CredentialPassthrough {
+    /// Pass the credentials through to every command.
+    #[default]
+    Allow,
+    /// Ask before passing them to a command that talks to a remote (`git
+    /// push`, `ssh`, ...); withhold them from all other commands.
+    Ask,
+    /// Never pass them through.
+    Deny,
+}
+
+/// Access to the SSH agent and git credential helpers for agent commands.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct CredentialsToml {
+    /// The SSH agent socket (`SSH_AUTH_SOCK`). Defaults to `allow`.
+    pub ssh_agent: Option<CredentialPassthrough>,
+    /// Git credential helpers and askpass programs. Defaults to `allow`.
+    pub git_credential_helper: Option<CredentialPassthrough>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CredentialsConfig {
+    pub ssh_agent: CredentialPassthrough,
+    pub git_credential_helper: CredentialPassthrough,
+}
+
+impl From<CredentialsToml> for CredentialsConfig {
+    fn from(toml: CredentialsToml) -> Self {
+        Self {
+            ssh_agent: toml.ssh_agent.unwrap_or_default(),
+            git_credential_helper: toml.git_credential_helper.unwrap_or_default(),
+        }
+    }
+}
+
+/// How the `prompt_cache_key` sent with model requests is derived. Requests
+/// that share a key are routed to the same prompt cache, so turns that repeat
+/// the previous prompt as a prefix are billed as cached input.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum PromptCacheKeyScope {
+    /// One key per session, shared by every turn of the conversation.
+    #[default]
+    Session,
+    /// One key per working directory and model, shared by every session
+    /// started there, so new sessions reuse the cached instructions and tools.
+    Workspace,
+    /// Do not send a cache key.
+    Disabled,
+}
+
+/// A provider to retry a turn on when the primary provider keeps failing.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ModelFallbackToml {
+    /// Key into the `model_providers` map.
+    pub provider: String,
+    /// Model to request from `provider`. Defaults to the session's model.
+    pub model: Option<String>,
+}
+
+/// A resolved entry of the `model_fallbacks` chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelFallback {
+    pub provider_id: String,
+    pub provider: ModelProviderInfo,
+    pub model: Option<String>,
+}
+
+/// Prices for one model from `[model_prices]`, in USD per million tokens.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ModelPrice {
+    pub input: f64,
+    /// Price for cached input tokens. Defaults to `input`.
+    pub cached_input: Option<f64>,
+    pub output: f64,
+}
+
+impl ModelPrice {
+    /// Estimated spend in USD for `usage`.
+    pub fn cost_usd(&self, usage: &TokenUsage) -> f64 {
+        let cached_input = self.cached_input.unwrap_or(self.input);
+        (usage.non_cached_input() as f64 * self.input
+            + usage.cached_input() as f64 * cached_input
+            + usage.output_tokens.max(0) as f64 * self.output)
+            / 1_000_000.0
+    }
+}
+
+/// Where the `web_search` tool gets its results, from `[web_search_backend]`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum WebSearchBackend {
+    /// The model provider's own search tool.
+    #[default]
+    Native,
+    /// A SearxNG instance with its JSON output format enabled.
+    Searxng {
+        /// Base URL of the instance, e.g. `https://searx.example.com`.
+        url: String,
+    },
+    /// The Brave Search API.
+    Brave {
+        /// Environment variable holding the API key. Defaults to
+        /// `BRAVE_API_KEY`.
+        #[serde(default)]
+        api_key_env: Option<String>,
+    },
+}
+
+/// Tools that run commands. `shell` in `enabled_tools` or `disabled_tools`
+/// stands for all of them, whichever variant the model is given.
+const SHELL_TOOL_NAMES: &[&str] = &[
+    "shell",
+    "shell_command",
+    "local_shell",
+    "container.exec",
+    "exec_command",
+    "write_stdin",
+    "start_job",
+    "read_job_output",
+    "stop_job",
+];
+
+/// Which tools the model is offered, from `enabled_tools` and
+/// `disabled_tools`. Entries name a built-in tool (`web_search`,
+/// `apply_patch`, `shell`, ...), an MCP server as `mcp:<server>`, or a single
+/// MCP tool by its qualified name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ToolFilter {
+    /// When set, only these tools are offered.
+    pub enabled: Option<Vec<String>>,
+    /// Tools removed after applying `enabled`.
+    pub disabled: Vec<String>,
+}
+
+impl ToolFilter {
+    /// Whether the built-in tool `name` may be offered.
+    pub fn allows(&self, name: &str) -> bool {
+        let matches = |entry: &String| {
+            entry == name || (entry == "shell" && SHELL_TOOL_NAMES.contains(&name))
+        };
+        self.enabled
+            .as_ref()
+            .is_none_or(|enabled| enabled.iter().any(matches))
+            && !self.disabled.iter().any(matches)
+    }
+
+    /// Whether the MCP tool `qualified_name` from `server` may be offered.
+    pub fn allows_mcp_tool(&self, server: &str, qualified_name: &str) -> bool {
+        let matches =
+            |entry: &String| entry == qualified_name || entry.strip_prefix("mcp:") == Some(server);
+        self.enabled
+            .as_ref()
+            .is_none_or(|enabled| enabled.iter().any(matches))
+            && !self.disabled.iter().any(matches)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -799,6 +2054,61 @@ mod tests {
         assert!(!cfg.enabled);
     }
 
+    #[test]
+    fn deserialize_server_tool_approvals() {
+        let cfg: McpServerConfig = toml::from_str(
+            r#"
+            command = "echo"
+            tool_approval = "auto"
+            tool_approvals = { delete_page = "deny", publish = "ask" }
+        "#,
+        )
+        .expect("should deserialize tool approvals");
+
+        assert_eq!(cfg.tool_approval_for("search"), Some(McpToolApproval::Auto));
+        assert_eq!(
+            cfg.tool_approval_for("delete_page"),
+            Some(McpToolApproval::Deny)
+        );
+        assert_eq!(cfg.tool_approval_for("publish"), Some(McpToolApproval::Ask));
+    }
+
+    #[test]
+    fn deserialize_server_tool_output_token_limits() {
+        let cfg: McpServerConfig = toml::from_str(
+            r#"
+            command = "echo"
+            tool_output_token_limit = 2000
+            tool_output_token_limits = { fetch_logs = 500 }
+        "#,
+        )
+        .expect("should deserialize tool output limits");
+
+        assert_eq!(cfg.tool_output_token_limit_for("search"), Some(2000));
+        assert_eq!(cfg.tool_output_token_limit_for("fetch_logs"), Some(500));
+    }
+
+    #[test]
+    fn model_price_counts_cached_input_separately() {
+        let price: ModelPrice = toml::from_str(
+            r#"
+            input = 2.0
+            cached_input = 0.5
+            output = 8.0
+        "#,
+        )
+        .expect("should deserialize model price");
+        let usage = TokenUsage {
+            input_tokens: 1_000_000,
+            cached_input_tokens: 400_000,
+            output_tokens: 250_000,
+            reasoning_output_tokens: 100_000,
+            total_tokens: 1_250_000,
+        };
+
+        assert_eq!(price.cost_usd(&usage), 3.4);
+    }
+
     #[test]
     fn deserialize_streamable_http_server_config() {
         let cfg: McpServerConfig = toml::from_str(
@@ -842,6 +2152,46 @@ mod tests {
         assert!(cfg.enabled);
     }
 
+    #[test]
+    fn deserialize_sse_server_config() {
+        let cfg: McpServerConfig = toml::from_str(
+            r#"
+            url = "https://example.com/sse"
+            transport = "sse"
+            bearer_token_env_var = "EXAMPLE_TOKEN"
+        "#,
+        )
+        .expect("should deserialize sse config");
+
+        assert_eq!(
+            cfg.transport,
+            McpServerTransportConfig::Sse {
+                url: "https://example.com/sse".to_string(),
+                bearer_token_env_var: Some("EXAMPLE_TOKEN".to_string()),
+                http_headers: None,
+                env_http_headers: None,
+            }
+        );
+
+        let round_trip: McpServerConfig =
+            serde_json::from_value(serde_json::to_value(&cfg).expect("serialize sse config"))
+                .expect("deserialize serialized sse config");
+        assert_eq!(round_trip, cfg);
+
+        let err = toml::from_str::<McpServerConfig>(
+            r#"
+            command = "echo"
+            transport = "sse"
+        "#,
+        )
+        .expect_err("stdio servers should reject transport");
+        assert!(
+            err.to_string()
+                .contains("transport is not supported for stdio"),
+            "unexpected error: {err}"
+        );
+    }
+
     #[test]
     fn deserialize_streamable_http_server_config_with_headers() {
         let cfg: McpServerConfig = toml::from_str(
@@ -938,4 +2288,104 @@ mod tests {
             "unexpected error: {err}"
         );
     }
+
+    #[test]
+    fn tool_filter_applies_allow_then_deny() {
+        let filter = ToolFilter {
+            enabled: Some(vec![
+                "shell".to_string(),
+                "web_search".to_string(),
+                "mcp:docs".to_string(),
+            ]),
+            disabled: vec!["write_stdin".to_string(), "mcp__docs__delete".to_string()],
+        };
+
+        assert!(filter.allows("exec_command"));
+        assert!(filter.allows("web_search"));
+        assert!(!filter.allows("write_stdin"));
+        assert!(!filter.allows("apply_patch"));
+        assert!(filter.allows_mcp_tool("docs", "mcp__docs__search"));
+        assert!(!filter.allows_mcp_tool("docs", "mcp__docs__delete"));
+        assert!(!filter.allows_mcp_tool("github", "mcp__github__search"));
+        assert!(ToolFilter::default().allows("apply_patch"));
+    }
+}
+
+/// Overrides for the capabilities granted at one trust level. Unset fields
+/// keep the built-in preset for that level.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct TrustPresetToml {
+    /// Sandbox used when no `sandbox_mode` is configured.
+    pub sandbox_mode: Option<SandboxMode>,
+    /// Approval policy used when no `approval_policy` is configured.
+    pub approval_policy: Option<AskForApproval>,
+    /// Whether configured MCP servers are started. `false` applies even when
+    /// `mcp_servers` are configured explicitly.
+    pub mcp_servers: Option<bool>,
+    /// Whether the web search tool is offered to the model. `false` applies
+    /// even when `web_search` is configured explicitly.
+    pub web_search: Option<bool>,
+}
+
+/// `[trust_levels]`: per-level overrides of the trust presets.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct TrustPresetsToml {
+    pub trusted: Option<TrustPresetToml>,
+    pub limited: Option<TrustPresetToml>,
+    pub untrusted: Option<TrustPresetToml>,
+}
+
+/// Capabilities a directory gets from its trust level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrustPreset {
+    pub sandbox_mode: SandboxMode,
+    pub approval_policy: AskForApproval,
+    pub mcp_servers: bool,
+    pub web_search: bool,
+}
+
+impl TrustPreset {
+    pub fn builtin(level: TrustLevel) -> Self {
+        match level {
+            TrustLevel::Trusted => Self {
+                sandbox_mode: SandboxMode::WorkspaceWrite,
+                approval_policy: AskForApproval::OnRequest,
+                mcp_servers: true,
+                web_search: true,
+            },
+            TrustLevel::Limited => Self {
+                sandbox_mode: SandboxMode::ReadOnly,
+                approval_policy: AskForApproval::OnRequest,
+                mcp_servers: false,
+                web_search: false,
+            },
+            TrustLevel::Untrusted => Self {
+                sandbox_mode: SandboxMode::ReadOnly,
+                approval_policy: AskForApproval::UnlessTrusted,
+                mcp_servers: false,
+                web_search: false,
+            },
+        }
+    }
+}
+
+impl TrustPresetsToml {
+    /// The built-in preset for `level` with any configured overrides applied.
+    pub fn resolve(&self, level: TrustLevel) -> TrustPreset {
+        let builtin = TrustPreset::builtin(level);
+        let overrides = match level {
+            TrustLevel::Trusted => self.trusted,
+            TrustLevel::Limited => self.limited,
+            TrustLevel::Untrusted => self.untrusted,
+        }
+        .unwrap_or_default();
+        TrustPreset {
+            sandbox_mode: overrides.sandbox_mode.unwrap_or(builtin.sandbox_mode),
+            approval_policy: overrides.approval_policy.unwrap_or(builtin.approval_policy),
+            mcp_servers: overrides.mcp_servers.unwrap_or(builtin.mcp_servers),
+            web_search: overrides.web_search.unwrap_or(builtin.web_search),
+        }
+    }
 }
@@ -3,8 +3,14 @@
 
 use crate::config::CONFIG_TOML_FILE;
 use crate::config::ConfigToml;
+use crate::config::schema::SchemaLookup;
+use crate::config::schema::array_items;
+use crate::config::schema::config_schema_value;
+use crate::config::schema::lookup_key;
+use crate::config::schema::unknown_key_message;
 use codex_app_server_protocol::ConfigLayerSource;
 use codex_utils_absolute_path::AbsolutePathBufGuard;
+use serde_json::Value as JsonValue;
 use serde_path_to_error::Path as SerdePath;
 use serde_path_to_error::Segment as SerdeSegment;
 use std::fmt;
@@ -15,11 +21,13 @@ use std::path::PathBuf;
 use toml_edit::Document;
 use toml_edit::Item;
 use toml_edit::Table;
+use toml_edit::TableLike;
 use toml_edit::Value;
 
 use super::ConfigLayerEntry;
 use super::ConfigLayerStack;
 use super::ConfigLayerStackOrdering;
+use super::env_interpolation::ValuePathSegment;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct TextPosition {
@@ -136,6 +144,98 @@ pub(crate) fn config_error_from_config_toml(
     }
 }
 
+/// Validates the contents of a config file, returning every problem found.
+///
+/// Besides the type errors that fail config loading, this reports keys the
+/// config schema does not declare. Loading ignores those, so a typo such as
+/// `modle = "o3"` would otherwise be dropped without any warning.
+pub fn validate_config_contents(path: impl AsRef<Path>, contents: &str) -> Vec<ConfigError> {
+    let path = path.as_ref();
+    // Relative paths in the file resolve against its directory, as in loading.
+    let _guard = path.parent().map(AbsolutePathBufGuard::new);
+    let Ok(doc) = contents.parse::<Document<String>>() else {
+        return config_error_from_config_toml(path, contents)
+            .into_iter()
+            .collect();
+    };
+
+    let mut errors = Vec::new();
+    if let Some(table) = doc.as_item().as_table_like() {
+        let schema = config_schema_value();
+        let mut walker = UnknownKeyWalker {
+            root: &schema,
+            path,
+            contents,
+            parents: Vec::new(),
+            errors: &mut errors,
+        };
+        walker.visit_table(&schema, table);
+    }
+    errors.extend(config_error_from_config_toml(path, contents));
+    errors.sort_by_key(|error| (error.range.start.line, error.range.start.column));
+    errors
+}
+
+struct UnknownKeyWalker<'a> {
+    root: &'a JsonValue,
+    path: &'a Path,
+    contents: &'a str,
+    parents: Vec<String>,
+    errors: &'a mut Vec<ConfigError>,
+}
+
+impl<'a> UnknownKeyWalker<'a> {
+    fn visit_table(&mut self, schema: &'a JsonValue, table: &dyn TableLike) {
+        for (key, item) in table.iter() {
+            match lookup_key(self.root, schema, key) {
+                SchemaLookup::Known(child) => {
+                    self.parents.push(key.to_string());
+                    self.visit_item(child, item);
+                    self.parents.pop();
+                }
+                SchemaLookup::Unchecked => {}
+                SchemaLookup::Unknown { suggestion } => {
+                    let range = table
+                        .key(key)
+                        .and_then(toml_edit::Key::span)
+                        .or_else(|| item.span())
+                        .map(|span| text_range_from_span(self.contents, span))
+                        .unwrap_or_else(default_range);
+                    self.errors.push(ConfigError::new(
+                        self.path.to_path_buf(),
+                        range,
+                        unknown_key_message(&self.parents, key, suggestion.as_deref()),
+                    ));
+                }
+            }
+        }
+    }
+
+    fn visit_item(&mut self, schema: &'a JsonValue, item: &Item) {
+        match item {
+            Item::Table(table) => self.visit_table(schema, table),
+            Item::Value(Value::InlineTable(table)) => self.visit_table(schema, table),
+            Item::Value(Value::Array(array)) => {
+                if let Some(items) = array_items(self.root, schema) {
+                    for value in array.iter() {
+                        if let Value::InlineTable(table) = value {
+                            self.visit_table(items, table);
+                        }
+                    }
+                }
+            }
+            Item::ArrayOfTables(array) => {
+                if let Some(items) = array_items(self.root, schema) {
+                    for table in array.iter() {
+                        self.visit_table(items, table);
+                    }
+                }
+            }
+            Item::Value(_) | Item::None => {}
+        }
+    }
+}
+
 pub(crate) async fn first_layer_config_error(layers: &ConfigLayerStack) -> Option<ConfigError> {
     // When the merged config fails schema validation, we surface the first concrete
     // per-file error to point users at a specific file and range rather than an
@@ -196,6 +296,20 @@ fn config_path_for_layer(layer: &ConfigLayerEntry) -> Option<PathBuf> {
     }
 }
 
+/// Builds a [`ConfigError`] located at the value reached by `segments`,
+/// falling back to the start of the file when it cannot be found.
+pub(super) fn config_error_for_value_path(
+    path: impl AsRef<Path>,
+    contents: &str,
+    segments: &[ValuePathSegment],
+    message: impl Into<String>,
+) -> ConfigError {
+    let range = span_for_value_path(contents, segments)
+        .map(|span| text_range_from_span(contents, span))
+        .unwrap_or_else(default_range);
+    ConfigError::new(path.as_ref().to_path_buf(), range, message)
+}
+
 fn text_range_from_span(contents: &str, span: std::ops::Range<usize>) -> TextRange {
     let start = position_for_offset(contents, span.start);
     let end_index = if span.end > span.start {
@@ -305,6 +419,25 @@ fn span_for_path(contents: &str, path: &SerdePath) -> Option<std::ops::Range<usi
     }
 }
 
+fn span_for_value_path(
+    contents: &str,
+    segments: &[ValuePathSegment],
+) -> Option<std::ops::Range<usize>> {
+    let doc = contents.parse::<Document<String>>().ok()?;
+    let mut node = TomlNode::Item(doc.as_item());
+    for segment in segments {
+        node = match segment {
+            ValuePathSegment::Key(key) => map_child(&node, key)?,
+            ValuePathSegment::Index(index) => seq_child(&node, *index)?,
+        };
+    }
+    match node {
+        TomlNode::Item(item) => item.span(),
+        TomlNode::Table(table) => table.span(),
+        TomlNode::Value(value) => value.span(),
+    }
+}
+
 fn span_for_config_path(contents: &str, path: &SerdePath) -> Option<std::ops::Range<usize>> {
     if is_features_table_path(path)
         && let Some(span) = span_for_features_value(contents)
@@ -0,0 +1,344 @@
+//! `${VAR}` interpolation for string values in config files.
+//!
+//! Config files are interpolated when they are loaded, before relative paths
+//! are resolved, so a checked-in project config can reference secrets and
+//! machine-specific values without hardcoding them. Supported forms:
+//!
+//! - `${VAR}`: the value of `VAR`; fails to load the config when it is unset.
+//! - `${VAR:-default}`: `default` when `VAR` is unset or empty.
+//! - `${VAR:?message}`: fails to load the config with `message` when `VAR` is
+//!   unset or empty.
+//! - `$${`: a literal `${`.
+//!
+//! Only the values in [`INTERPOLATED_FIELDS`] are interpolated, so a `${` in
+//! any other setting, such as a prompt, is kept as it is.
+
+use std::io;
+use std::path::Path;
+
+use toml::Value as TomlValue;
+
+use super::diagnostics::config_error_for_value_path;
+use super::diagnostics::io_error_from_config_error;
+
+/// Values that are interpolated, as paths from the document root. `*`
+/// matches any key or array index.
+const INTERPOLATED_FIELDS: &[&[&str]] = &[
+    &["model_providers", "*", "base_url"],
+    &["model_providers", "*", "http_headers", "*"],
+    &["model_providers", "*", "query_params", "*"],
+    &["mcp_servers", "*", "command"],
+    &["mcp_servers", "*", "args", "*"],
+    &["mcp_servers", "*", "env", "*"],
+    &["mcp_servers", "*", "url"],
+    &["mcp_servers", "*", "http_headers", "*"],
+    &["notify", "*"],
+];
+
+/// One step from the document root to an interpolated value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum ValuePathSegment {
+    Key(String),
+    Index(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct InterpolationError {
+    pub(super) path: Vec<ValuePathSegment>,
+    pub(super) message: String,
+}
+
+/// Interpolates the config parsed from `contents` at `path`, reporting
+/// failures at the offending value.
+pub(super) fn interpolate_config_file(
+    path: &Path,
+    contents: &str,
+    mut value: TomlValue,
+) -> io::Result<TomlValue> {
+    let lookup = |name: &str| std::env::var(name).ok();
+    match interpolate_env_vars(&mut value, &lookup) {
+        Ok(()) => Ok(value),
+        Err(err) => {
+            let config_error = config_error_for_value_path(path, contents, &err.path, err.message);
+            Err(io_error_from_config_error(
+                io::ErrorKind::InvalidData,
+                config_error,
+                None,
+            ))
+        }
+    }
+}
+
+pub(super) fn interpolate_env_vars(
+    value: &mut TomlValue,
+    lookup: &impl Fn(&str) -> Option<String>,
+) -> Result<(), InterpolationError> {
+    let mut path = Vec::new();
+    interpolate_value(value, lookup, &mut path)
+}
+
+fn interpolate_value(
+    value: &mut TomlValue,
+    lookup: &impl Fn(&str) -> Option<String>,
+    path: &mut Vec<ValuePathSegment>,
+) -> Result<(), InterpolationError> {
+    if !INTERPOLATED_FIELDS
+        .iter()
+        .any(|field| field_matches(&field[..path.len().min(field.len())], path))
+    {
+        return Ok(());
+    }
+    match value {
+        TomlValue::String(text) => {
+            let is_field = INTERPOLATED_FIELDS
+                .iter()
+                .any(|field| field_matches(field, path));
+            if is_field && text.contains('$') {
+                *text = interpolate_str(text, lookup).map_err(|message| InterpolationError {
+                    path: path.clone(),
+                    message,
+                })?;
+            }
+        }
+        TomlValue::Array(items) => {
+            for (index, item) in items.iter_mut().enumerate() {
+                path.push(ValuePathSegment::Index(index));
+                interpolate_value(item, lookup, path)?;
+                path.pop();
+            }
+        }
+        TomlValue::Table(table) => {
+            for (key, item) in table.iter_mut() {
+                path.push(ValuePathSegment::Key(key.clone()));
+                interpolate_value(item, lookup, path)?;
+                path.pop();
+            }
+        }
+        TomlValue::Integer(_)
+        | TomlValue::Float(_)
+        | TomlValue::Boolean(_)
+        | TomlValue::Datetime(_) => {}
+    }
+    Ok(())
+}
+
+/// Whether `path` is exactly `field`; with a prefix of a field, whether
+/// `path` leads to it.
+fn field_matches(field: &[&str], path: &[ValuePathSegment]) -> bool {
+    field.len() == path.len()
+        && field
+            .iter()
+            .zip(path)
+            .all(|(expected, segment)| match segment {
+                _ if *expected == "*" => true,
+                ValuePathSegment::Key(key) => key == expected,
+                ValuePathSegment::Index(_) => false,
+            })
+}
+
+fn interpolate_str(
+    input: &str,
+    lookup: &impl Fn(&str) -> Option<String>,
+) -> Result<String, String> {
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(dollar) = rest.find('$') {
+        output.push_str(&rest[..dollar]);
+        let after = &rest[dollar + 1..];
+        if let Some(escaped) = after.strip_prefix("${") {
+            output.push_str("${");
+            rest = escaped;
+            continue;
+        }
+        let Some(body) = after.strip_prefix('{') else {
+            output.push('$');
+            rest = after;
+            continue;
+        };
+        let Some(end) = body.find('}') else {
+            return Err(format!("unterminated `${{` in config value `{input}`"));
+        };
+        output.push_str(&resolve_expression(&body[..end], lookup)?);
+        rest = &body[end + 1..];
+    }
+    output.push_str(rest);
+    Ok(output)
+}
+
+fn resolve_expression(
+    expression: &str,
+    lookup: &impl Fn(&str) -> Option<String>,
+) -> Result<String, String> {
+    let (name, modifier) = match expression.split_once(':') {
+        Some((name, modifier)) => (name, Some(modifier)),
+        None => (expression, None),
+    };
+    if !is_valid_name(name) {
+        return Err(format!(
+            "invalid environment variable reference `${{{expression}}}`"
+        ));
+    }
+    let set_value = lookup(name).filter(|value| !value.is_empty());
+    match modifier {
+        None => lookup(name).ok_or_else(|| {
+            format!(
+                "environment variable `{name}` is not set; use `${{{name}:-}}` to default to an empty value"
+            )
+        }),
+        Some(modifier) => {
+            if let Some(default) = modifier.strip_prefix('-') {
+                Ok(set_value.unwrap_or_else(|| default.to_string()))
+            } else if let Some(message) = modifier.strip_prefix('?') {
+                set_value.ok_or_else(|| {
+                    if message.is_empty() {
+                        format!("environment variable `{name}` is required but not set")
+                    } else {
+                        format!("environment variable `{name}` is required: {message}")
+                    }
+                })
+            } else {
+                Err(format!(
+                    "invalid environment variable reference `${{{expression}}}`; use `:-` for a default or `:?` for a required variable"
+                ))
+            }
+        }
+    }
+}
+
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "API_KEY" => Some("sk-test".to_string()),
+            "EMPTY" => Some(String::new()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn interpolates_plain_default_and_escaped_references() {
+        let mut value: TomlValue = toml::from_str(
+            r#"
+[model_providers.corp]
+base_url = "${BASE_URL:-https://example.com}/v1"
+http_headers = { "X-Key" = "${API_KEY}", "X-Empty" = "${EMPTY:-fallback}", "X-Missing" = "[${MISSING:-}]" }
+query_params = { note = "$${API_KEY} costs $5" }
+
+[mcp_servers.docs]
+command = "docs-server"
+args = ["--key", "${API_KEY}"]
+"#,
+        )
+        .expect("parse");
+
+        interpolate_env_vars(&mut value, &lookup).expect("interpolate");
+
+        let expected: TomlValue = toml::from_str(
+            r#"
+[model_providers.corp]
+base_url = "https://example.com/v1"
+http_headers = { "X-Key" = "sk-test", "X-Empty" = "fallback", "X-Missing" = "[]" }
+query_params = { note = "${API_KEY} costs $5" }
+
+[mcp_servers.docs]
+command = "docs-server"
+args = ["--key", "sk-test"]
+"#,
+        )
+        .expect("parse expected");
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn other_settings_are_left_alone() {
+        let contents = r#"
+developer_instructions = "Write shell like ${HOME} and ${1}"
+
+[model_providers.corp]
+name = "${NAME}"
+"#;
+        let mut value: TomlValue = toml::from_str(contents).expect("parse");
+
+        interpolate_env_vars(&mut value, &lookup).expect("interpolate");
+
+        assert_eq!(value, toml::from_str::<TomlValue>(contents).expect("parse"));
+    }
+
+    #[test]
+    fn interpolates_notify_arguments() {
+        let mut value: TomlValue =
+            toml::from_str(r#"notify = ["${NOTIFIER:-notify-send}", "--token", "${API_KEY}"]"#)
+                .expect("parse");
+
+        interpolate_env_vars(&mut value, &lookup).expect("interpolate");
+
+        let expected: TomlValue =
+            toml::from_str(r#"notify = ["notify-send", "--token", "sk-test"]"#)
+                .expect("parse expected");
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn unset_variable_without_default_is_an_error() {
+        let mut value: TomlValue =
+            toml::from_str("[mcp_servers.docs]\nurl = \"${MISSING}/mcp\"\n").expect("parse");
+
+        let err = interpolate_env_vars(&mut value, &lookup).expect_err("unset variable");
+
+        assert_eq!(
+            err.message,
+            "environment variable `MISSING` is not set; use `${MISSING:-}` to default to an empty value"
+        );
+    }
+
+    #[test]
+    fn required_variable_reports_value_path() {
+        let mut value: TomlValue = toml::from_str(
+            r#"
+[model_providers.corp]
+env_http_headers = { "X-Key" = "ok" }
+base_url = "${CORP_URL:?set CORP_URL to the gateway URL}"
+"#,
+        )
+        .expect("parse");
+
+        let err = interpolate_env_vars(&mut value, &lookup).expect_err("missing variable");
+
+        assert_eq!(
+            err,
+            InterpolationError {
+                path: vec![
+                    ValuePathSegment::Key("model_providers".to_string()),
+                    ValuePathSegment::Key("corp".to_string()),
+                    ValuePathSegment::Key("base_url".to_string()),
+                ],
+                message:
+                    "environment variable `CORP_URL` is required: set CORP_URL to the gateway URL"
+                        .to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_references() {
+        assert_eq!(
+            interpolate_str("${API_KEY", &lookup),
+            Err("unterminated `${` in config value `${API_KEY`".to_string())
+        );
+        assert_eq!(
+            interpolate_str("${1BAD}", &lookup),
+            Err("invalid environment variable reference `${1BAD}`".to_string())
+        );
+    }
+}
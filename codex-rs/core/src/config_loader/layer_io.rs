@@ -1,6 +1,7 @@
 use super::LoaderOverrides;
 use super::diagnostics::config_error_from_toml;
 use super::diagnostics::io_error_from_config_error;
+use super::env_interpolation::interpolate_config_file;
 #[cfg(target_os = "macos")]
 use super::macos::load_managed_admin_config_layer;
 use codex_utils_absolute_path::AbsolutePathBuf;
@@ -74,7 +75,7 @@ pub(super) async fn read_config_from_path(
 ) -> io::Result<Option<TomlValue>> {
     match fs::read_to_string(path.as_ref()).await {
         Ok(contents) => match toml::from_str::<TomlValue>(&contents) {
-            Ok(value) => Ok(Some(value)),
+            Ok(value) => interpolate_config_file(path.as_ref(), &contents, value).map(Some),
             Err(err) => {
                 tracing::error!("Failed to parse {}: {err}", path.as_ref().display());
                 let config_error = config_error_from_toml(path.as_ref(), &contents, err.clone());
@@ -1,6 +1,7 @@
 mod cloud_requirements;
 mod config_requirements;
 mod diagnostics;
+mod env_interpolation;
 mod fingerprint;
 mod layer_io;
 #[cfg(target_os = "macos")]
@@ -17,6 +18,7 @@ use crate::config::CONFIG_TOML_FILE;
 use crate::config::ConfigToml;
 use crate::config::deserialize_config_toml_with_base;
 use crate::config_loader::config_requirements::ConfigRequirementsWithSources;
+use crate::config_loader::env_interpolation::interpolate_config_file;
 use crate::config_loader::layer_io::LoadedConfigLayers;
 use crate::git_info::resolve_root_git_project_for_trust;
 use codex_app_server_protocol::ConfigLayerSource;
@@ -50,6 +52,7 @@ pub(crate) use diagnostics::first_layer_config_error_from_entries;
 pub use diagnostics::format_config_error;
 pub use diagnostics::format_config_error_with_source;
 pub(crate) use diagnostics::io_error_from_config_error;
+pub use diagnostics::validate_config_contents;
 pub use merge::merge_toml_values;
 pub(crate) use overrides::build_cli_overrides_layer;
 pub use state::ConfigLayerEntry;
@@ -308,6 +311,7 @@ async fn load_config_toml_for_required_layer(
                 let config_error = config_error_from_toml(toml_file, &contents, err.clone());
                 io_error_from_config_error(io::ErrorKind::InvalidData, config_error, Some(err))
             })?;
+            let config = interpolate_config_file(toml_file, &contents, config)?;
             let config_parent = toml_file.parent().ok_or_else(|| {
                 io::Error::new(
                     io::ErrorKind::InvalidData,
@@ -526,10 +530,10 @@ impl ProjectTrustContext {
         let trust_key = decision.trust_key.as_str();
         let user_config_file = self.user_config_file.as_path().display();
         match decision.trust_level {
-            Some(TrustLevel::Untrusted) => Some(format!(
-                "{trust_key} is marked as untrusted in {user_config_file}. To load config.toml, mark it trusted."
+            Some(trust_level @ (TrustLevel::Limited | TrustLevel::Untrusted)) => Some(format!(
+                "{trust_key} is marked as {trust_level} in {user_config_file}. To load config.toml, mark it trusted."
             )),
-            _ => Some(format!(
+            Some(TrustLevel::Trusted) | None => Some(format!(
                 "To load config.toml, add {trust_key} as a trusted project in {user_config_file}."
             )),
         }
@@ -739,6 +743,14 @@ async fn load_project_layers(
                         continue;
                     }
                 };
+                // Untrusted layers are disabled, so a missing variable there
+                // must not prevent the rest of the config from loading.
+                let config = match interpolate_config_file(config_file.as_path(), &contents, config)
+                {
+                    Ok(config) => config,
+                    Err(err) if decision.is_trusted() => return Err(err),
+                    Err(_) => TomlValue::Table(toml::map::Map::new()),
+                };
                 let config =
                     resolve_relative_paths_in_config_toml(config, dot_codex_abs.as_path())?;
                 let entry =
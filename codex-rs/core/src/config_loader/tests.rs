@@ -150,6 +150,41 @@ fn schema_error_points_to_feature_value() {
     assert_eq!(error.range.start.column, value_column);
 }
 
+#[test]
+fn validate_config_contents_reports_unknown_keys_with_suggestions() {
+    let tmp = tempdir().expect("tempdir");
+    let contents = "modle = \"gpt-5\"\n\n[tui]\nanimation = false\n";
+    let config_path = tmp.path().join(CONFIG_TOML_FILE);
+
+    let errors = super::validate_config_contents(&config_path, contents);
+
+    let summary: Vec<(usize, usize, String)> = errors
+        .into_iter()
+        .map(|error| {
+            (
+                error.range.start.line,
+                error.range.start.column,
+                error.message,
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (
+                1,
+                1,
+                "unknown config key `modle`; did you mean `model`?".to_string()
+            ),
+            (
+                4,
+                1,
+                "unknown config key `tui.animation`; did you mean `tui.animations`?".to_string()
+            ),
+        ]
+    );
+}
+
 #[tokio::test]
 async fn merges_managed_config_layer_on_top() {
     let tmp = tempdir().expect("tempdir");
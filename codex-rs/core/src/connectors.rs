@@ -7,12 +7,12 @@ pub use codex_app_server_protocol::AppInfo;
 use codex_protocol::protocol::SandboxPolicy;
 use tokio_util::sync::CancellationToken;
 
-use crate::AuthManager;
 use crate::SandboxState;
 use crate::config::Config;
 use crate::features::Feature;
 use crate::mcp::CODEX_APPS_MCP_SERVER_NAME;
 use crate::mcp::auth::compute_auth_statuses;
+use crate::mcp::auth_manager_from_config;
 use crate::mcp::with_codex_apps_mcp;
 use crate::mcp_connection_manager::DEFAULT_STARTUP_TIMEOUT;
 use crate::mcp_connection_manager::McpConnectionManager;
@@ -69,14 +69,6 @@ pub async fn list_accessible_connectors_from_mcp_tools(
     Ok(accessible_connectors_from_mcp_tools(&tools))
 }
 
-fn auth_manager_from_config(config: &Config) -> std::sync::Arc<AuthManager> {
-    AuthManager::shared(
-        config.codex_home.clone(),
-        false,
-        config.cli_auth_credentials_store_mode,
-    )
-}
-
 pub fn connector_display_label(connector: &AppInfo) -> String {
     format_connector_label(&connector.name, &connector.id)
 }
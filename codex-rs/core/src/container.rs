@@ -0,0 +1,540 @@
+//! Container-backed execution for the shell tools.
+//!
+//! With `[container] image` set, the shell tools, `exec_command` sessions, and
+//! `apply_patch` run in one long-lived container per session instead of on
+//! the host. The session's working directory is bind-mounted at `workdir`.
+//! Host paths in a command are rewritten to the mount, and the mount is
+//! rewritten back to the host path in the command's output, so the model only
+//! ever deals with host paths. `apply_patch` computes the new file contents on
+//! the host and writes them from inside the container.
+//!
+//! The container replaces the native sandbox for these commands: the mount is
+//! read-only under a read-only policy and networking is off when the policy
+//! has no network access. Both are decided when the container starts, at the
+//! start of the session, and the container is removed at shutdown.
+
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::Instant;
+
+use codex_apply_patch::AffectedPaths;
+use codex_apply_patch::ApplyPatchAction;
+use codex_apply_patch::ApplyPatchFileChange;
+use codex_apply_patch::print_summary;
+use codex_protocol::ThreadId;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tokio::sync::Mutex;
+use tracing::warn;
+
+use crate::bash::extract_bash_command;
+use crate::config::types::ContainerConfig;
+use crate::exec::ExecToolCallOutput;
+use crate::exec::StreamOutput;
+use crate::protocol::SandboxPolicy;
+
+/// Runs the command with its PID recorded in the file named by `$0`, so it
+/// can be killed inside the container when the `exec` client is.
+const RECORD_PID_SCRIPT: &str = r#"echo $$ > "$0" 2>/dev/null; exec "$@""#;
+
+/// Kills the process, and its process group where it leads one, whose PID is
+/// in the file named by `$1`.
+const KILL_SCRIPT: &str =
+    r#"pid=$(cat "$1") && { kill -KILL -- "-$pid" 2>/dev/null || kill -KILL "$pid"; }; rm -f "$1""#;
+
+enum ContainerState {
+    NotStarted,
+    Running(PathMapping),
+    /// Starting failed; the error is reported for every later command.
+    Failed(String),
+}
+
+/// Maps the host workspace to its mount inside the container, or to its copy
+/// on the remote host.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct PathMapping {
+    host_root: String,
+    container_root: String,
+}
+
+impl PathMapping {
+    pub(crate) fn new(host_root: &Path, container_root: &str) -> Self {
+        Self {
+            host_root: host_root.to_string_lossy().into_owned(),
+            container_root: container_root.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// `path` inside the container, or `None` when it is outside the
+    /// workspace.
+    pub(crate) fn container_path(&self, path: &Path) -> Option<String> {
+        let relative = path.strip_prefix(&self.host_root).ok()?;
+        let mut mapped = self.container_root.clone();
+        for component in relative.components() {
+            let Component::Normal(part) = component else {
+                return None;
+            };
+            mapped.push('/');
+            mapped.push_str(&part.to_string_lossy());
+        }
+        Some(mapped)
+    }
+
+    pub(crate) fn to_container(&self, text: &str) -> String {
+        replace_path_prefix(text, &self.host_root, &self.container_root)
+    }
+
+    fn to_host(&self, text: &str) -> String {
+        replace_path_prefix(text, &self.container_root, &self.host_root)
+    }
+
+    /// Rewrites container paths in a command's output to host paths.
+    pub(crate) fn output_to_host(&self, mut output: ExecToolCallOutput) -> ExecToolCallOutput {
+        for stream in [
+            &mut output.stdout,
+            &mut output.stderr,
+            &mut output.aggregated_output,
+        ] {
+            stream.text = self.to_host(&stream.text);
+        }
+        output
+    }
+}
+
+/// Replaces `from` with `to` where it is a whole path prefix: not part of a
+/// longer name on either side, so `/work/app` does not match `/work/app2` or
+/// `/old/work/app`.
+fn replace_path_prefix(text: &str, from: &str, to: &str) -> String {
+    if from.is_empty() {
+        return text.to_string();
+    }
+    let is_name_char = |c: char| c.is_alphanumeric() || matches!(c, '_' | '-' | '.');
+    let mut replaced = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(index) = rest.find(from) {
+        replaced.push_str(&rest[..index]);
+        let after = &rest[index + from.len()..];
+        let starts_path = !replaced
+            .chars()
+            .next_back()
+            .is_some_and(|c| is_name_char(c) || c == '/');
+        let ends_component = !after.chars().next().is_some_and(is_name_char);
+        replaced.push_str(if starts_path && ends_component {
+            to
+        } else {
+            from
+        });
+        rest = after;
+    }
+    replaced.push_str(rest);
+    replaced
+}
+
+/// A command prepared to run in the container.
+pub(crate) struct ContainerExec {
+    /// Host command that runs it through the runtime's `exec`.
+    pub(crate) argv: Vec<String>,
+    pub(crate) mapping: PathMapping,
+    /// File in the container that holds the command's PID.
+    pid_file: String,
+}
+
+pub(crate) struct ContainerManager {
+    config: ContainerConfig,
+    name: String,
+    state: Mutex<ContainerState>,
+    next_exec: AtomicU64,
+}
+
+impl ContainerManager {
+    pub(crate) fn new(config: ContainerConfig, conversation_id: ThreadId) -> Self {
+        Self {
+            config,
+            name: format!("codex-{conversation_id}"),
+            state: Mutex::new(ContainerState::NotStarted),
+            next_exec: AtomicU64::new(0),
+        }
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.config.image.is_some()
+    }
+
+    /// Starts the container with `host_root` mounted, unless it is already
+    /// running or failed to start.
+    pub(crate) async fn start(
+        &self,
+        host_root: &Path,
+        policy: &SandboxPolicy,
+    ) -> Result<PathMapping, String> {
+        let Some(image) = self.config.image.as_deref() else {
+            return Err("no container image is configured".to_string());
+        };
+        let mut state = self.state.lock().await;
+        match &*state {
+            ContainerState::Running(mapping) => return Ok(mapping.clone()),
+            ContainerState::Failed(err) => return Err(err.clone()),
+            ContainerState::NotStarted => {}
+        }
+        let result = self.run_container(image, host_root, policy).await;
+        *state = match &result {
+            Ok(mapping) => ContainerState::Running(mapping.clone()),
+            Err(err) => ContainerState::Failed(err.clone()),
+        };
+        result
+    }
+
+    async fn run_container(
+        &self,
+        image: &str,
+        host_root: &Path,
+        policy: &SandboxPolicy,
+    ) -> Result<PathMapping, String> {
+        if host_root.parent().is_none() {
+            return Err("the workspace cannot be the filesystem root".to_string());
+        }
+        let workdir = &self.config.workdir;
+        let read_only = if policy.has_full_disk_write_access()
+            || !policy.get_writable_roots_with_cwd(host_root).is_empty()
+        {
+            ""
+        } else {
+            ":ro"
+        };
+        let mut args = vec![
+            "run".to_string(),
+            "--detach".to_string(),
+            "--rm".to_string(),
+            "--name".to_string(),
+            self.name.clone(),
+            "--volume".to_string(),
+            format!("{}:{workdir}{read_only}", host_root.display()),
+            "--workdir".to_string(),
+            workdir.clone(),
+        ];
+        if !policy.has_full_network_access() {
+            args.push("--network=none".to_string());
+        }
+        // Run as the host user so files written to the mount keep their owner.
+        #[cfg(unix)]
+        {
+            let (uid, gid) = unsafe { (libc::getuid(), libc::getgid()) };
+            args.push("--user".to_string());
+            args.push(format!("{uid}:{gid}"));
+        }
+        args.extend(self.config.run_args.iter().cloned());
+        args.extend([
+            "--entrypoint".to_string(),
+            "sleep".to_string(),
+            image.to_string(),
+            "infinity".to_string(),
+        ]);
+
+        let runtime = self.config.runtime.program();
+        let output = Command::new(runtime)
+            .args(&args)
+            .stdin(Stdio::null())
+            .output()
+            .await
+            .map_err(|err| format!("failed to run `{runtime}`: {err}"))?;
+        if !output.status.success() {
+            return Err(format!(
+                "`{runtime} run` failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(PathMapping::new(host_root, workdir))
+    }
+
+    /// The host command that runs `command` in the container from `cwd`,
+    /// with host paths rewritten to the mount. `tty` allocates a terminal
+    /// for interactive sessions.
+    pub(crate) async fn exec_command(
+        &self,
+        command: &[String],
+        cwd: &Path,
+        host_root: &Path,
+        policy: &SandboxPolicy,
+        tty: bool,
+    ) -> Result<ContainerExec, String> {
+        let mapping = self.start(host_root, policy).await?;
+        let Some(container_cwd) = mapping.container_path(cwd) else {
+            return Err(format!(
+                "{} is outside the workspace mounted in the container",
+                cwd.display()
+            ));
+        };
+        // The host shell may not exist in the image, so scripts run under
+        // the container's `shell`.
+        let command = match extract_bash_command(command) {
+            Some((_, script)) => vec![
+                self.config.shell.clone(),
+                "-c".to_string(),
+                script.to_string(),
+            ],
+            None => command.to_vec(),
+        };
+        let pid_file = format!(
+            "/tmp/{}-exec-{}.pid",
+            self.name,
+            self.next_exec.fetch_add(1, Ordering::Relaxed)
+        );
+        let mut argv = vec![
+            self.config.runtime.program().to_string(),
+            "exec".to_string(),
+            "--interactive".to_string(),
+        ];
+        if tty {
+            argv.push("--tty".to_string());
+        }
+        argv.extend([
+            "--workdir".to_string(),
+            container_cwd,
+            self.name.clone(),
+            "sh".to_string(),
+            "-c".to_string(),
+            RECORD_PID_SCRIPT.to_string(),
+            pid_file.clone(),
+        ]);
+        argv.extend(command.iter().map(|arg| mapping.to_container(arg)));
+        Ok(ContainerExec {
+            argv,
+            mapping,
+            pid_file,
+        })
+    }
+
+    /// Kills a command started with [`Self::exec_command`]. Killing the
+    /// runtime's `exec` client on the host leaves the command running in the
+    /// container.
+    pub(crate) async fn kill(&self, exec: &ContainerExec) {
+        let result = Command::new(self.config.runtime.program())
+            .args(["exec", &self.name, "sh", "-c", KILL_SCRIPT, "sh"])
+            .arg(&exec.pid_file)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .await;
+        if let Err(err) = result {
+            warn!("failed to kill a command in container {}: {err}", self.name);
+        }
+    }
+
+    /// Applies a verified patch by writing the changed files from inside the
+    /// container, so the read-only mount and the container user apply to it
+    /// as they do to commands. Fails like `apply_patch` does, with exit code
+    /// 1 and the error on stderr, when a file cannot be written.
+    pub(crate) async fn apply_patch(
+        &self,
+        action: &ApplyPatchAction,
+        host_root: &Path,
+        policy: &SandboxPolicy,
+    ) -> Result<ExecToolCallOutput, String> {
+        let started = Instant::now();
+        let mapping = self.start(host_root, policy).await?;
+        let mut affected = AffectedPaths {
+            added: Vec::new(),
+            modified: Vec::new(),
+            deleted: Vec::new(),
+        };
+        let mut changes: Vec<_> = action.changes().iter().collect();
+        changes.sort_by(|(a, _), (b, _)| a.cmp(b));
+        for (path, change) in changes {
+            let result = match change {
+                ApplyPatchFileChange::Add { content } => {
+                    affected.added.push(display_path(path, &action.cwd));
+                    self.write_file(&mapping, path, content).await
+                }
+                ApplyPatchFileChange::Delete { .. } => {
+                    affected.deleted.push(display_path(path, &action.cwd));
+                    self.remove_file(&mapping, path).await
+                }
+                ApplyPatchFileChange::Update {
+                    move_path: Some(destination),
+                    new_content,
+                    ..
+                } => {
+                    affected
+                        .modified
+                        .push(display_path(destination, &action.cwd));
+                    match self.write_file(&mapping, destination, new_content).await {
+                        Ok(()) => self.remove_file(&mapping, path).await,
+                        Err(err) => Err(err),
+                    }
+                }
+                ApplyPatchFileChange::Update {
+                    move_path: None,
+                    new_content,
+                    ..
+                } => {
+                    affected.modified.push(display_path(path, &action.cwd));
+                    self.write_file(&mapping, path, new_content).await
+                }
+            };
+            if let Err(stderr) = result {
+                return Ok(patch_output(1, String::new(), stderr, started));
+            }
+        }
+        let mut summary = Vec::new();
+        print_summary(&affected, &mut summary).map_err(|err| err.to_string())?;
+        Ok(patch_output(
+            0,
+            String::from_utf8_lossy(&summary).into_owned(),
+            String::new(),
+            started,
+        ))
+    }
+
+    async fn write_file(
+        &self,
+        mapping: &PathMapping,
+        path: &Path,
+        content: &str,
+    ) -> Result<(), String> {
+        let target = self.container_target(mapping, path)?;
+        let mut child = Command::new(self.config.runtime.program())
+            .args(["exec", "--interactive", &self.name, "sh", "-c"])
+            .arg(r#"mkdir -p -- "$(dirname -- "$1")" && cat > "$1""#)
+            .args(["sh", &target])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|err| format!("failed to write {}: {err}", path.display()))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(content.as_bytes())
+                .await
+                .map_err(|err| format!("failed to write {}: {err}", path.display()))?;
+        }
+        Self::check_file_command(child.wait_with_output().await, path)
+    }
+
+    async fn remove_file(&self, mapping: &PathMapping, path: &Path) -> Result<(), String> {
+        let target = self.container_target(mapping, path)?;
+        let output = Command::new(self.config.runtime.program())
+            .args(["exec", &self.name, "rm", "--", &target])
+            .stdin(Stdio::null())
+            .output()
+            .await;
+        Self::check_file_command(output, path)
+    }
+
+    fn container_target(&self, mapping: &PathMapping, path: &Path) -> Result<String, String> {
+        mapping.container_path(path).ok_or_else(|| {
+            format!(
+                "{} is outside the workspace mounted in the container",
+                path.display()
+            )
+        })
+    }
+
+    fn check_file_command(
+        output: std::io::Result<std::process::Output>,
+        path: &Path,
+    ) -> Result<(), String> {
+        match output {
+            Ok(output) if output.status.success() => Ok(()),
+            Ok(output) => Err(format!(
+                "failed to write {}: {}",
+                path.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            )),
+            Err(err) => Err(format!("failed to write {}: {err}", path.display())),
+        }
+    }
+
+    /// Removes the container if it was started.
+    pub(crate) async fn stop(&self) {
+        let mut state = self.state.lock().await;
+        if !matches!(*state, ContainerState::Running(_)) {
+            return;
+        }
+        *state = ContainerState::NotStarted;
+        let runtime = self.config.runtime.program();
+        let result = Command::new(runtime)
+            .args(["rm", "--force", &self.name])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .await;
+        if let Err(err) = result {
+            warn!("failed to remove container {}: {err}", self.name);
+        }
+    }
+}
+
+/// `path` as the `apply_patch` summary shows it: relative to the patch's
+/// working directory when it is inside it.
+fn display_path(path: &Path, cwd: &Path) -> PathBuf {
+    path.strip_prefix(cwd)
+        .map_or_else(|_| path.to_path_buf(), Path::to_path_buf)
+}
+
+fn patch_output(
+    exit_code: i32,
+    stdout: String,
+    stderr: String,
+    started: Instant,
+) -> ExecToolCallOutput {
+    ExecToolCallOutput {
+        exit_code,
+        aggregated_output: StreamOutput::new(format!("{stdout}{stderr}")),
+        stdout: StreamOutput::new(stdout),
+        stderr: StreamOutput::new(stderr),
+        duration: started.elapsed(),
+        timed_out: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn maps_paths_between_host_and_container() {
+        let mapping = PathMapping::new(Path::new("/home/me/project"), "/workspace/");
+
+        assert_eq!(
+            mapping.container_path(Path::new("/home/me/project/src/lib")),
+            Some("/workspace/src/lib".to_string())
+        );
+        assert_eq!(
+            mapping.container_path(Path::new("/home/me/project")),
+            Some("/workspace".to_string())
+        );
+        assert_eq!(mapping.container_path(Path::new("/home/me/other")), None);
+        assert_eq!(
+            mapping.to_container("cat /home/me/project/README.md"),
+            "cat /workspace/README.md"
+        );
+        assert_eq!(
+            mapping.to_host("/workspace/src/main.rs:3: error"),
+            "/home/me/project/src/main.rs:3: error"
+        );
+    }
+
+    #[test]
+    fn maps_only_whole_path_prefixes() {
+        let mapping = PathMapping::new(Path::new("/home/me/project"), "/workspace");
+
+        assert_eq!(
+            mapping.to_container(
+                "diff /home/me/project/a /home/me/project2/a /old/home/me/project '/home/me/project'"
+            ),
+            "diff /workspace/a /home/me/project2/a /old/home/me/project '/workspace'"
+        );
+        assert_eq!(
+            mapping.to_host("/workspace-cache /workspace: /workspace.bak/x"),
+            "/workspace-cache /home/me/project: /workspace.bak/x"
+        );
+    }
+}
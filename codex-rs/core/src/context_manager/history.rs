@@ -2,6 +2,7 @@ use crate::codex::TurnContext;
 use crate::context_manager::normalize;
 use crate::instructions::SkillInstructions;
 use crate::instructions::UserInstructions;
+use crate::mcp::mention::is_mcp_mention_contents;
 use crate::session_prefix::is_session_prefix;
 use crate::truncate::TruncationPolicy;
 use crate::truncate::approx_token_count;
@@ -9,13 +10,16 @@ use crate::truncate::approx_tokens_from_byte_count;
 use crate::truncate::truncate_function_output_items_with_policy;
 use crate::truncate::truncate_text;
 use crate::user_shell_command::is_user_shell_command_text;
+use codex_git::GhostCommit;
 use codex_protocol::models::ContentItem;
 use codex_protocol::models::FunctionCallOutputContentItem;
 use codex_protocol::models::FunctionCallOutputPayload;
 use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::ContextEdit;
 use codex_protocol::protocol::TokenUsage;
 use codex_protocol::protocol::TokenUsageInfo;
 use std::ops::Deref;
+use std::ops::Range;
 
 /// Transcript of thread history
 #[derive(Debug, Clone, Default)]
@@ -195,6 +199,84 @@ impl ContextManager {
         self.replace(snapshot[..cut_idx].to_vec());
     }
 
+    /// The workspace snapshot taken when the earliest of the last `num_turns`
+    /// user turns started, i.e. the state `drop_last_n_user_turns(num_turns)`
+    /// returns the files to. `None` when that turn recorded no snapshot.
+    pub(crate) fn ghost_snapshot_before_last_n_user_turns(
+        &self,
+        num_turns: u32,
+    ) -> Option<GhostCommit> {
+        let user_positions = user_message_positions(&self.items);
+        let n_from_end = usize::try_from(num_turns).unwrap_or(usize::MAX);
+        let first_dropped = user_positions.len().saturating_sub(n_from_end);
+        let start = *user_positions.get(first_dropped)?;
+        let end = user_positions
+            .get(first_dropped + 1)
+            .copied()
+            .unwrap_or(self.items.len());
+        self.items[start..end].iter().find_map(|item| match item {
+            ResponseItem::GhostSnapshot { ghost_commit } => Some(ghost_commit.clone()),
+            _ => None,
+        })
+    }
+
+    /// First line of the message that opens each user turn, oldest first.
+    pub(crate) fn user_turn_previews(&self) -> Vec<String> {
+        user_message_positions(&self.items)
+            .into_iter()
+            .map(|idx| match &self.items[idx] {
+                ResponseItem::Message { content, .. } => content
+                    .iter()
+                    .find_map(|item| match item {
+                        ContentItem::InputText { text } => text.lines().next(),
+                        ContentItem::InputImage { .. } | ContentItem::OutputText { .. } => None,
+                    })
+                    .unwrap_or("[image]")
+                    .to_string(),
+                _ => String::new(),
+            })
+            .collect()
+    }
+
+    /// The user turns `edit` removes or summarizes, as indices into this
+    /// history's user turns. Errors when a turn range does not fit.
+    pub(crate) fn user_turns_affected_by(&self, edit: ContextEdit) -> Result<Range<usize>, String> {
+        let total = user_message_positions(&self.items).len();
+        match edit {
+            ContextEdit::Compact | ContextEdit::Clear => Ok(0..total),
+            ContextEdit::DropTurns { first, last } => {
+                let first = usize::try_from(first).unwrap_or(usize::MAX);
+                let last = usize::try_from(last).unwrap_or(usize::MAX);
+                if first == 0 || first > last {
+                    return Err(format!("invalid turn range {first}-{last}"));
+                }
+                if last > total {
+                    return Err(format!(
+                        "turn {last} does not exist; the context has {total} turns"
+                    ));
+                }
+                Ok(first - 1..last)
+            }
+        }
+    }
+
+    /// Drop the user turns in `turns` (indices into the user turns, oldest
+    /// first) along with everything recorded during them. Items before the
+    /// first user turn, such as the session instructions, are kept.
+    pub(crate) fn drop_user_turns(&mut self, turns: Range<usize>) {
+        let user_positions = user_message_positions(&self.items);
+        let Some(&start) = user_positions.get(turns.start) else {
+            return;
+        };
+        let end = user_positions
+            .get(turns.end)
+            .copied()
+            .unwrap_or(self.items.len());
+        if start < end {
+            self.items.drain(start..end);
+        }
+    }
+
     pub(crate) fn update_token_info(
         &mut self,
         usage: &TokenUsage,
@@ -343,6 +425,7 @@ pub(crate) fn is_user_turn_boundary(item: &ResponseItem) -> bool {
 
     if UserInstructions::is_user_instructions(content)
         || SkillInstructions::is_skill_instructions(content)
+        || is_mcp_mention_contents(content)
     {
         return false;
     }
@@ -332,6 +332,107 @@ fn drop_last_n_user_turns_preserves_prefix() {
     );
 }
 
+#[test]
+fn ghost_snapshot_before_last_n_user_turns_uses_the_earliest_dropped_turn() {
+    let ghost = |id: &str| ResponseItem::GhostSnapshot {
+        ghost_commit: GhostCommit::new(id.to_string(), None, Vec::new(), Vec::new()),
+    };
+    let history = create_history_with_items(vec![
+        assistant_msg("session prefix item"),
+        user_msg("u1"),
+        ghost("ghost-1"),
+        assistant_msg("a1"),
+        user_msg("u2"),
+        assistant_msg("a2"),
+        user_msg("u3"),
+        ghost("ghost-3"),
+        assistant_msg("a3"),
+    ]);
+
+    let snapshot_id = |num_turns| {
+        history
+            .ghost_snapshot_before_last_n_user_turns(num_turns)
+            .map(|commit| commit.id().to_string())
+    };
+    assert_eq!(snapshot_id(1), Some("ghost-3".to_string()));
+    // Turn 2 ran without a snapshot, so its starting state is unknown.
+    assert_eq!(snapshot_id(2), None);
+    assert_eq!(snapshot_id(3), Some("ghost-1".to_string()));
+    assert_eq!(snapshot_id(99), Some("ghost-1".to_string()));
+}
+
+#[test]
+fn drop_user_turns_removes_a_middle_range() {
+    let mut history = create_history_with_items(vec![
+        assistant_msg("session prefix item"),
+        user_msg("u1"),
+        assistant_msg("a1"),
+        user_msg("u2\nmore detail"),
+        assistant_msg("a2"),
+        user_msg("u3"),
+        assistant_msg("a3"),
+    ]);
+    assert_eq!(history.user_turn_previews(), vec!["u1", "u2", "u3"]);
+
+    let turns = history
+        .user_turns_affected_by(ContextEdit::DropTurns { first: 2, last: 2 })
+        .expect("range fits");
+    history.drop_user_turns(turns);
+    assert_eq!(
+        history.for_prompt(),
+        vec![
+            assistant_msg("session prefix item"),
+            user_msg("u1"),
+            assistant_msg("a1"),
+            user_msg("u3"),
+            assistant_msg("a3"),
+        ]
+    );
+}
+
+#[test]
+fn clear_keeps_only_the_session_prefix() {
+    let mut history = create_history_with_items(vec![
+        user_input_text_msg("<environment_context>ctx</environment_context>"),
+        user_input_text_msg("turn 1 user"),
+        assistant_msg("turn 1 assistant"),
+        user_input_text_msg("turn 2 user"),
+    ]);
+
+    let turns = history
+        .user_turns_affected_by(ContextEdit::Clear)
+        .expect("clear always fits");
+    assert_eq!(turns, 0..2);
+    history.drop_user_turns(turns);
+    assert_eq!(
+        history.for_prompt(),
+        vec![user_input_text_msg(
+            "<environment_context>ctx</environment_context>"
+        )]
+    );
+}
+
+#[test]
+fn user_turns_affected_by_rejects_ranges_outside_the_history() {
+    let history = create_history_with_items(vec![user_msg("u1"), assistant_msg("a1")]);
+
+    assert!(
+        history
+            .user_turns_affected_by(ContextEdit::DropTurns { first: 0, last: 1 })
+            .is_err()
+    );
+    assert!(
+        history
+            .user_turns_affected_by(ContextEdit::DropTurns { first: 2, last: 1 })
+            .is_err()
+    );
+    assert!(
+        history
+            .user_turns_affected_by(ContextEdit::DropTurns { first: 1, last: 2 })
+            .is_err()
+    );
+}
+
 #[test]
 fn drop_last_n_user_turns_ignores_session_prefix_user_messages() {
     let items = vec![
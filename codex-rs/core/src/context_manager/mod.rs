@@ -1,5 +1,7 @@
 mod history;
 mod normalize;
+mod pins;
 
 pub(crate) use history::ContextManager;
 pub(crate) use history::is_user_turn_boundary;
+pub(crate) use pins::PinnedContext;
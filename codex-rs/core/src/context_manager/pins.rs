@@ -0,0 +1,191 @@
+//! Files and messages the user pinned into the model's context.
+//!
+//! Pins are kept outside the recorded history and rendered into one
+//! session-prefix message that is inserted into every prompt, so compaction
+//! and context edits never drop them. Pinned files are re-read for each
+//! request so the model sees their current contents.
+
+use crate::context_manager::is_user_turn_boundary;
+use crate::session_prefix::PINNED_CONTEXT_OPEN_TAG;
+use crate::truncate::TruncationPolicy;
+use crate::truncate::approx_token_count;
+use crate::truncate::truncate_text;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::PinTarget;
+use codex_protocol::protocol::PinnedContextEntry;
+
+/// Budget for a single pinned file; larger files are truncated in the middle.
+const PINNED_FILE_MAX_TOKENS: usize = 8_000;
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PinnedContext {
+    next_id: u32,
+    /// Oldest first. File paths are absolute and message text is filled in.
+    pins: Vec<(u32, PinTarget)>,
+}
+
+impl PinnedContext {
+    /// Adds `target` and returns its id. The caller resolves file paths and
+    /// message text before pinning.
+    pub(crate) fn pin(&mut self, target: PinTarget) -> u32 {
+        if let Some((id, _)) = self.pins.iter().find(|(_, pinned)| *pinned == target) {
+            return *id;
+        }
+        self.next_id += 1;
+        self.pins.push((self.next_id, target));
+        self.next_id
+    }
+
+    /// Removes the pin with `id`, or every pin when `id` is `None`.
+    pub(crate) fn unpin(&mut self, id: Option<u32>) -> Result<(), String> {
+        let Some(id) = id else {
+            self.pins.clear();
+            return Ok(());
+        };
+        let Some(index) = self.pins.iter().position(|(pin_id, _)| *pin_id == id) else {
+            return Err(format!("No pin with id {id}."));
+        };
+        self.pins.remove(index);
+        Ok(())
+    }
+
+    pub(crate) fn entries(&self) -> Vec<PinnedContextEntry> {
+        self.pins
+            .iter()
+            .map(|(id, target)| PinnedContextEntry {
+                id: *id,
+                target: target.clone(),
+                tokens: i64::try_from(approx_token_count(&render_pin(target))).unwrap_or(i64::MAX),
+            })
+            .collect()
+    }
+
+    /// Inserts the pinned context ahead of the first user turn in `items`, so
+    /// it follows the session instructions and stays in the cached prefix.
+    pub(crate) fn insert_into(&self, items: &mut Vec<ResponseItem>) {
+        if self.pins.is_empty() {
+            return;
+        }
+        let mut text = format!(
+            "{PINNED_CONTEXT_OPEN_TAG}\nThe user pinned the following to keep it in view for the rest of the session.\n"
+        );
+        for (_, target) in &self.pins {
+            text.push_str(&render_pin(target));
+        }
+        text.push_str("</pinned_context>");
+
+        let index = items
+            .iter()
+            .position(is_user_turn_boundary)
+            .unwrap_or(items.len());
+        items.insert(
+            index,
+            ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![ContentItem::InputText { text }],
+                end_turn: None,
+            },
+        );
+    }
+}
+
+fn render_pin(target: &PinTarget) -> String {
+    match target {
+        PinTarget::File { path } => {
+            let contents = match std::fs::read_to_string(path) {
+                Ok(contents) => {
+                    truncate_text(&contents, TruncationPolicy::Tokens(PINNED_FILE_MAX_TOKENS))
+                }
+                Err(err) => format!("(could not read file: {err})"),
+            };
+            format!("<file path=\"{}\">\n{contents}\n</file>\n", path.display())
+        }
+        PinTarget::Message { text } => {
+            format!(
+                "<message>\n{}\n</message>\n",
+                text.as_deref().unwrap_or_default()
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn user_message(text: &str) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: text.to_string(),
+            }],
+            end_turn: None,
+        }
+    }
+
+    fn message_text(item: &ResponseItem) -> &str {
+        let ResponseItem::Message { content, .. } = item else {
+            panic!("expected a message, got {item:?}");
+        };
+        let [ContentItem::InputText { text }] = content.as_slice() else {
+            panic!("expected a single text item, got {content:?}");
+        };
+        text
+    }
+
+    #[test]
+    fn pinned_context_goes_before_first_user_turn_and_rereads_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("notes.md");
+        std::fs::write(&path, "first draft").expect("write file");
+
+        let mut pins = PinnedContext::default();
+        let file_id = pins.pin(PinTarget::File { path: path.clone() });
+        let message_id = pins.pin(PinTarget::Message {
+            text: Some("Use tabs.".to_string()),
+        });
+        assert_eq!((file_id, message_id), (1, 2));
+        assert_eq!(pins.pin(PinTarget::File { path: path.clone() }), file_id);
+
+        std::fs::write(&path, "second draft").expect("write file");
+        let mut items = vec![
+            user_message("<environment_context>cwd</environment_context>"),
+            user_message("first request"),
+        ];
+        pins.insert_into(&mut items);
+
+        assert_eq!(items.len(), 3);
+        assert_eq!(message_text(&items[2]), "first request");
+        let pinned = message_text(&items[1]);
+        assert!(pinned.starts_with(PINNED_CONTEXT_OPEN_TAG));
+        assert!(pinned.contains("second draft"));
+        assert!(!pinned.contains("first draft"));
+        assert!(pinned.contains("<message>\nUse tabs.\n</message>"));
+        assert!(!is_user_turn_boundary(&items[1]));
+    }
+
+    #[test]
+    fn unpin_removes_one_or_all_pins() {
+        let mut pins = PinnedContext::default();
+        for text in ["a", "b", "c"] {
+            pins.pin(PinTarget::Message {
+                text: Some(text.to_string()),
+            });
+        }
+
+        pins.unpin(Some(2)).expect("pin 2 exists");
+        let ids: Vec<u32> = pins.entries().iter().map(|entry| entry.id).collect();
+        assert_eq!(ids, vec![1, 3]);
+        assert_eq!(pins.unpin(Some(2)), Err("No pin with id 2.".to_string()));
+
+        pins.unpin(None).expect("clearing always succeeds");
+        assert!(pins.entries().is_empty());
+        let mut items = vec![user_message("request")];
+        pins.insert_into(&mut items);
+        assert_eq!(items.len(), 1);
+    }
+}
@@ -0,0 +1,249 @@
+//! Applies the `[credentials]` policy to the environment of commands the
+//! agent runs, so the SSH agent and git credential helpers are only handed to
+//! the commands the user intends.
+//!
+//! This is best-effort: only the environment changes, and the sandbox policy
+//! is untouched. A command that goes looking can still find the agent socket,
+//! clear the `GIT_CONFIG_*` overrides, or read credential stores directly.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::bash::extract_bash_command;
+use crate::bash::parse_shell_lc_plain_commands;
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::config::types::CredentialPassthrough;
+use crate::config::types::CredentialsConfig;
+use crate::protocol::AskForApproval;
+use crate::protocol::ReviewDecision;
+use crate::tools::sandboxing::with_cached_approval;
+
+/// Variables that point commands at the SSH agent.
+const SSH_AGENT_VARS: &[&str] = &["SSH_AUTH_SOCK", "SSH_AGENT_PID"];
+
+/// Variables that name programs git and ssh run to prompt for credentials.
+const ASKPASS_VARS: &[&str] = &["GIT_ASKPASS", "SSH_ASKPASS"];
+
+/// Git subcommands that authenticate against a remote.
+const REMOTE_GIT_SUBCOMMANDS: &[&str] = &[
+    "clone",
+    "fetch",
+    "ls-remote",
+    "pull",
+    "push",
+    "remote",
+    "submodule",
+];
+
+/// Session approval cache key for passing credentials to one command.
+#[derive(Serialize)]
+struct CredentialApprovalKey<'a> {
+    credentials: &'a [String],
+}
+
+/// Removes credentials from `env` that the `[credentials]` policy does not
+/// grant to `command`. With `ask`, the user is asked once per command when it
+/// looks like it talks to a remote; every other command runs without them.
+pub(crate) async fn apply_credential_policy(
+    session: &Session,
+    turn: &TurnContext,
+    call_id: &str,
+    tool_name: &str,
+    command: &[String],
+    env: &mut HashMap<String, String>,
+) {
+    let policy = turn.client.config().credentials;
+    let asks =
+        [policy.ssh_agent, policy.git_credential_helper].contains(&CredentialPassthrough::Ask);
+    let granted = asks
+        && turn.approval_policy != AskForApproval::Never
+        && uses_remote_credentials(command)
+        && request_credentials(session, turn, call_id, tool_name, command, policy).await;
+    let withhold = |passthrough| match passthrough {
+        CredentialPassthrough::Allow => false,
+        CredentialPassthrough::Ask => !granted,
+        CredentialPassthrough::Deny => true,
+    };
+    if withhold(policy.ssh_agent) {
+        withhold_ssh_agent(env);
+    }
+    if withhold(policy.git_credential_helper) {
+        withhold_git_credential_helpers(env);
+    }
+}
+
+async fn request_credentials(
+    session: &Session,
+    turn: &TurnContext,
+    call_id: &str,
+    tool_name: &str,
+    command: &[String],
+    policy: CredentialsConfig,
+) -> bool {
+    let credentials = match (policy.ssh_agent, policy.git_credential_helper) {
+        (CredentialPassthrough::Ask, CredentialPassthrough::Ask) => {
+            "your SSH agent and git credentials"
+        }
+        (CredentialPassthrough::Ask, _) => "your SSH agent",
+        _ => "your git credentials",
+    };
+    let decision = with_cached_approval(
+        &session.services,
+        tool_name,
+        vec![CredentialApprovalKey {
+            credentials: command,
+        }],
+        || async {
+            session
+                .request_command_approval(
+                    turn,
+                    call_id.to_string(),
+                    command.to_vec(),
+                    turn.cwd.clone(),
+                    Some(format!("Allow this command to use {credentials}?")),
+                    None,
+                )
+                .await
+        },
+    )
+    .await;
+    match decision {
+        ReviewDecision::Approved
+        | ReviewDecision::ApprovedExecpolicyAmendment { .. }
+        | ReviewDecision::ApprovedForSession
+        | ReviewDecision::ApprovedAlwaysForPaths { .. } => true,
+        ReviewDecision::ApprovedHunks { .. } | ReviewDecision::Denied | ReviewDecision::Abort => {
+            false
+        }
+    }
+}
+
+fn withhold_ssh_agent(env: &mut HashMap<String, String>) {
+    for var in SSH_AGENT_VARS {
+        env.remove(*var);
+    }
+}
+
+/// Disables credential helpers through git's environment config (git 2.31+),
+/// which takes precedence over every config file, and stops git from
+/// prompting on the terminal instead.
+fn withhold_git_credential_helpers(env: &mut HashMap<String, String>) {
+    for var in ASKPASS_VARS {
+        env.remove(*var);
+    }
+    env.insert("GIT_TERMINAL_PROMPT".to_string(), "0".to_string());
+    let index = env
+        .get("GIT_CONFIG_COUNT")
+        .and_then(|count| count.parse::<usize>().ok())
+        .unwrap_or(0);
+    // An empty `credential.helper` clears the helpers configured so far.
+    env.insert(
+        format!("GIT_CONFIG_KEY_{index}"),
+        "credential.helper".to_string(),
+    );
+    env.insert(format!("GIT_CONFIG_VALUE_{index}"), String::new());
+    env.insert("GIT_CONFIG_COUNT".to_string(), (index + 1).to_string());
+}
+
+/// Whether `command` looks like it authenticates against a remote: `ssh`,
+/// `scp`, `sftp`, `rsync`, or a git subcommand that contacts a remote.
+fn uses_remote_credentials(command: &[String]) -> bool {
+    let commands = parse_shell_lc_plain_commands(command).unwrap_or_else(|| {
+        match extract_bash_command(command) {
+            Some((_, script)) => vec![
+                script
+                    .split(|c: char| c.is_whitespace() || ";&|()".contains(c))
+                    .filter(|word| !word.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+            ],
+            None => vec![command.to_vec()],
+        }
+    });
+    commands.iter().any(|words| {
+        words.iter().enumerate().any(|(index, word)| {
+            let program = Path::new(word)
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or(word);
+            match program {
+                "ssh" | "scp" | "sftp" | "rsync" => true,
+                "git" => git_subcommand(&words[index + 1..])
+                    .is_some_and(|subcommand| REMOTE_GIT_SUBCOMMANDS.contains(&subcommand)),
+                _ => false,
+            }
+        })
+    })
+}
+
+/// The subcommand of a git invocation, skipping global options.
+fn git_subcommand(args: &[String]) -> Option<&str> {
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-C" | "-c" | "--git-dir" | "--work-tree" | "--namespace" => {
+                args.next();
+            }
+            arg if arg.starts_with('-') => {}
+            subcommand => return Some(subcommand),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn shell(script: &str) -> Vec<String> {
+        vec!["bash".to_string(), "-lc".to_string(), script.to_string()]
+    }
+
+    #[test]
+    fn detects_commands_that_use_remote_credentials() {
+        assert!(uses_remote_credentials(&shell("git push origin main")));
+        assert!(uses_remote_credentials(&shell(
+            "git -C repo fetch && git status"
+        )));
+        assert!(uses_remote_credentials(&shell("ssh host uptime")));
+        assert!(uses_remote_credentials(&shell(
+            "for r in a b; do git -C $r pull; done"
+        )));
+        assert!(!uses_remote_credentials(&shell("git status && git diff")));
+        assert!(!uses_remote_credentials(&shell("./deploy.sh")));
+        assert!(!uses_remote_credentials(&[
+            "git".to_string(),
+            "log".to_string(),
+        ]));
+    }
+
+    #[test]
+    fn withholding_git_helpers_appends_to_existing_env_config() {
+        let mut env = HashMap::from([
+            ("GIT_ASKPASS".to_string(), "/usr/bin/askpass".to_string()),
+            ("GIT_CONFIG_COUNT".to_string(), "1".to_string()),
+            ("GIT_CONFIG_KEY_0".to_string(), "core.pager".to_string()),
+            ("GIT_CONFIG_VALUE_0".to_string(), "cat".to_string()),
+        ]);
+        withhold_git_credential_helpers(&mut env);
+
+        assert_eq!(
+            env,
+            HashMap::from([
+                ("GIT_TERMINAL_PROMPT".to_string(), "0".to_string()),
+                ("GIT_CONFIG_COUNT".to_string(), "2".to_string()),
+                ("GIT_CONFIG_KEY_0".to_string(), "core.pager".to_string()),
+                ("GIT_CONFIG_VALUE_0".to_string(), "cat".to_string()),
+                (
+                    "GIT_CONFIG_KEY_1".to_string(),
+                    "credential.helper".to_string()
+                ),
+                ("GIT_CONFIG_VALUE_1".to_string(), String::new()),
+            ])
+        );
+    }
+}
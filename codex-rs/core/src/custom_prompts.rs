@@ -1,9 +1,17 @@
 use codex_protocol::custom_prompts::CustomPrompt;
+use regex_lite::Regex;
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::LazyLock;
 use tokio::fs;
 
+static FILE_INCLUDE_REGEX: LazyLock<Option<Regex>> =
+    LazyLock::new(|| Regex::new(r"@\{([^}\n]+)\}").ok());
+static NAMED_ARG_REGEX: LazyLock<Option<Regex>> =
+    LazyLock::new(|| Regex::new(r"\$([A-Z][A-Z0-9_]*)").ok());
+
 /// Return the default prompts directory: `$CODEX_HOME/prompts`.
 /// If `CODEX_HOME` cannot be resolved, returns `None`.
 pub fn default_prompts_dir() -> Option<PathBuf> {
@@ -12,6 +20,126 @@ pub fn default_prompts_dir() -> Option<PathBuf> {
         .map(|home| home.join("prompts"))
 }
 
+/// Return the default commands directory: `$CODEX_HOME/commands`.
+/// If `CODEX_HOME` cannot be resolved, returns `None`.
+pub fn default_commands_dir() -> Option<PathBuf> {
+    crate::config::find_codex_home()
+        .ok()
+        .map(|home| home.join("commands"))
+}
+
+/// Discover custom slash commands in the given directory. Commands are prompt
+/// files invoked as `/name`; their `@{path}` includes are read relative to
+/// `cwd` now, so the command carries the files as they are at load time.
+pub async fn discover_commands_in(dir: &Path, cwd: &Path) -> Vec<CustomPrompt> {
+    let mut commands = discover_prompts_in(dir).await;
+    for command in &mut commands {
+        command.command = true;
+        command.content = expand_file_includes(&command.content, cwd).await;
+    }
+    commands
+}
+
+/// Replace every `@{path}` in `content` with the contents of that file,
+/// resolved relative to `cwd`. A file that cannot be read is replaced with a
+/// note saying so, so the model knows something is missing.
+pub async fn expand_file_includes(content: &str, cwd: &Path) -> String {
+    let Some(regex) = FILE_INCLUDE_REGEX.as_ref() else {
+        return content.to_string();
+    };
+    let mut out = String::with_capacity(content.len());
+    let mut last = 0;
+    for captures in regex.captures_iter(content) {
+        let (Some(whole), Some(path)) = (captures.get(0), captures.get(1)) else {
+            continue;
+        };
+        out.push_str(&content[last..whole.start()]);
+        let path = path.as_str().trim();
+        match fs::read_to_string(cwd.join(path)).await {
+            Ok(text) => out.push_str(&text),
+            Err(err) => out.push_str(&format!("[could not include {path}: {err}]")),
+        }
+        last = whole.end();
+    }
+    out.push_str(&content[last..]);
+    out
+}
+
+/// Named `$NAME` placeholders in `content`, in order of first use. `$ARGUMENTS`
+/// is not a named placeholder.
+pub fn prompt_argument_names(content: &str) -> Vec<String> {
+    let Some(regex) = NAMED_ARG_REGEX.as_ref() else {
+        return Vec::new();
+    };
+    let mut seen = HashSet::new();
+    let mut names = Vec::new();
+    for captures in regex.captures_iter(content) {
+        let (Some(whole), Some(name)) = (captures.get(0), captures.get(1)) else {
+            continue;
+        };
+        // `$$NAME` is an escaped dollar sign.
+        if whole.start() > 0 && content.as_bytes()[whole.start() - 1] == b'$' {
+            continue;
+        }
+        let name = name.as_str();
+        if name != "ARGUMENTS" && seen.insert(name.to_string()) {
+            names.push(name.to_string());
+        }
+    }
+    names
+}
+
+/// Expand a prompt body for clients that pass arguments by name, such as MCP
+/// `prompts/get`. `$NAME` takes the argument `NAME`, `$ARGUMENTS` takes the
+/// argument `ARGUMENTS`, and `$1`..`$9` take its whitespace-separated words.
+/// Placeholders without a value expand to nothing; `$$` is kept as is.
+pub fn expand_prompt_arguments(content: &str, args: &HashMap<String, String>) -> String {
+    let all = args
+        .get("ARGUMENTS")
+        .map(String::as_str)
+        .unwrap_or_default();
+    let positional: Vec<String> = shlex::Shlex::new(all).collect();
+    let mut out = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(offset) = rest.find('$') {
+        out.push_str(&rest[..offset]);
+        let tail = &rest[offset + 1..];
+        if let Some(after) = tail.strip_prefix('$') {
+            out.push_str("$$");
+            rest = after;
+            continue;
+        }
+        if let Some(digit) = tail.chars().next().filter(|c| ('1'..='9').contains(c)) {
+            let index = digit as usize - '1' as usize;
+            if let Some(arg) = positional.get(index) {
+                out.push_str(arg);
+            }
+            rest = &tail[1..];
+            continue;
+        }
+        let name_len = tail
+            .char_indices()
+            .take_while(|(i, c)| {
+                c.is_ascii_uppercase() || (*i > 0 && (c.is_ascii_digit() || *c == '_'))
+            })
+            .count();
+        if name_len == 0 {
+            out.push('$');
+            rest = tail;
+            continue;
+        }
+        let name = &tail[..name_len];
+        if name == "ARGUMENTS" {
+            out.push_str(all);
+        } else if let Some(value) = args.get(name) {
+            out.push_str(value);
+        }
+        rest = &tail[name_len..];
+    }
+    out.push_str(rest);
+    out
+}
+
 /// Discover prompt files in the given directory, returning entries sorted by name.
 /// Non-files are ignored. If the directory does not exist or cannot be read, returns empty.
 pub async fn discover_prompts_in(dir: &Path) -> Vec<CustomPrompt> {
@@ -69,6 +197,7 @@ pub async fn discover_prompts_in_excluding(
             content: body,
             description,
             argument_hint,
+            command: false,
         });
     }
     out.sort_by(|a, b| a.name.cmp(&b.name));
@@ -147,6 +276,7 @@ fn parse_frontmatter(content: &str) -> (Option<String>, Option<String>, String)
 #[cfg(test)]
 mod tests {
     use super::*;
+    use pretty_assertions::assert_eq;
     use std::fs;
     use tempfile::tempdir;
 
@@ -241,4 +371,47 @@ mod tests {
         assert_eq!(hint.as_deref(), Some("[arg]"));
         assert_eq!(body, "First line\r\nSecond line\r\n");
     }
+
+    #[tokio::test]
+    async fn commands_include_files_relative_to_cwd() {
+        let tmp = tempdir().expect("create TempDir");
+        let commands = tmp.path().join("commands");
+        let cwd = tmp.path().join("repo");
+        fs::create_dir_all(&commands).unwrap();
+        fs::create_dir_all(&cwd).unwrap();
+        fs::write(cwd.join("STYLE.md"), "Use tabs.").unwrap();
+        fs::write(
+            commands.join("review.md"),
+            "Review $ARGUMENTS.\n@{STYLE.md}\n@{missing.md}",
+        )
+        .unwrap();
+
+        let found = discover_commands_in(&commands, &cwd).await;
+        assert_eq!(found.len(), 1);
+        assert!(found[0].command);
+        assert_eq!(found[0].slash_name(), "review");
+        let content = &found[0].content;
+        assert!(
+            content.starts_with("Review $ARGUMENTS.\nUse tabs.\n[could not include missing.md: "),
+            "{content}"
+        );
+    }
+
+    #[test]
+    fn expands_arguments_by_name() {
+        let content = "Fix $1 in $FILE ($ARGUMENTS), costs $$5 and $x";
+        assert_eq!(prompt_argument_names(content), vec!["FILE".to_string()]);
+
+        let args = HashMap::from([
+            ("FILE".to_string(), "lib.rs".to_string()),
+            (
+                "ARGUMENTS".to_string(),
+                "\"the parser\" quickly".to_string(),
+            ),
+        ]);
+        assert_eq!(
+            expand_prompt_arguments(content, &args),
+            "Fix the parser in lib.rs (\"the parser\" quickly), costs $$5 and $x"
+        );
+    }
 }
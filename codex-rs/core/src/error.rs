@@ -70,6 +70,12 @@ pub enum CodexErr {
     #[error("stream disconnected before completion: {0}")]
     Stream(String, Option<Duration>),
 
+    /// The stream dropped after the model had already produced output and the
+    /// turn has no continuations left. Not retried: resending the request would
+    /// repeat that output and its tool calls.
+    #[error("stream disconnected after partial output; retry budget exhausted: {0}")]
+    StreamInterrupted(String),
+
     #[error(
         "Codex ran out of room in the model's context window. Start a new thread or clear earlier history before retrying."
     )]
@@ -81,6 +87,10 @@ pub enum CodexErr {
     #[error("agent thread limit reached (max {max_threads})")]
     AgentLimitReached { max_threads: usize },
 
+    /// A `codex exec` budget refused another model response or tool call.
+    #[error("stopped early: {0}")]
+    BudgetExceeded(String),
+
     #[error("session configured event was not the first event in the stream")]
     SessionConfiguredNotFirstEvent,
 
@@ -106,6 +116,11 @@ pub enum CodexErr {
     #[error("{0}")]
     InvalidRequest(String),
 
+    /// The provider refused the request or a safety system blocked its output.
+    /// Reported to clients as `EventMsg::TurnBlocked` rather than as an error.
+    #[error("The response was blocked ({category}): {message}")]
+    Blocked { category: String, message: String },
+
     /// Invalid image.
     #[error("Image poisoning")]
     InvalidImageRequest(),
@@ -198,14 +213,17 @@ impl CodexErr {
             | CodexErr::QuotaExceeded
             | CodexErr::InvalidImageRequest()
             | CodexErr::InvalidRequest(_)
+            | CodexErr::Blocked { .. }
             | CodexErr::RefreshTokenFailed(_)
             | CodexErr::UnsupportedOperation(_)
             | CodexErr::Sandbox(_)
             | CodexErr::LandlockSandboxExecutableNotProvided
             | CodexErr::RetryLimit(_)
+            | CodexErr::StreamInterrupted(_)
             | CodexErr::ContextWindowExceeded
             | CodexErr::ThreadNotFound(_)
             | CodexErr::AgentLimitReached { .. }
+            | CodexErr::BudgetExceeded(_)
             | CodexErr::Spawn
             | CodexErr::SessionConfiguredNotFirstEvent
             | CodexErr::UsageLimitReached(_)
@@ -224,6 +242,21 @@ impl CodexErr {
             CodexErr::LandlockRuleset(_) | CodexErr::LandlockPathFd(_) => false,
         }
     }
+
+    /// Whether the provider kept answering with server errors or rate limits
+    /// after its own retries, so the turn may move to a fallback provider.
+    pub fn is_provider_unavailable(&self) -> bool {
+        match self {
+            CodexErr::InternalServerError
+            | CodexErr::RetryLimit(_)
+            | CodexErr::ModelCap(_)
+            | CodexErr::UsageLimitReached(_) => true,
+            CodexErr::UnexpectedStatus(err) => {
+                err.status.is_server_error() || err.status == StatusCode::TOO_MANY_REQUESTS
+            }
+            _ => false,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -543,15 +576,20 @@ impl CodexErr {
                 model: err.model.clone(),
                 reset_after_seconds: err.reset_after_seconds,
             },
-            CodexErr::RetryLimit(_) => CodexErrorInfo::ResponseTooManyFailedAttempts {
-                http_status_code: self.http_status_code_value(),
-            },
+            CodexErr::RetryLimit(_) | CodexErr::StreamInterrupted(_) => {
+                CodexErrorInfo::ResponseTooManyFailedAttempts {
+                    http_status_code: self.http_status_code_value(),
+                }
+            }
             CodexErr::ConnectionFailed(_) => CodexErrorInfo::HttpConnectionFailed {
                 http_status_code: self.http_status_code_value(),
             },
             CodexErr::ResponseStreamFailed(_) => CodexErrorInfo::ResponseStreamConnectionFailed {
                 http_status_code: self.http_status_code_value(),
             },
+            CodexErr::Stream(..) => CodexErrorInfo::ResponseStreamDisconnected {
+                http_status_code: self.http_status_code_value(),
+            },
             CodexErr::RefreshTokenFailed(_) => CodexErrorInfo::Unauthorized,
             CodexErr::SessionConfiguredNotFirstEvent
             | CodexErr::InternalServerError
@@ -673,6 +711,29 @@ mod tests {
         })
     }
 
+    #[test]
+    fn provider_unavailable_covers_server_errors_and_rate_limits() {
+        let unexpected = |status| {
+            CodexErr::UnexpectedStatus(UnexpectedResponseError {
+                status,
+                body: String::new(),
+                url: None,
+                request_id: None,
+            })
+        };
+        let retry_limit = CodexErr::RetryLimit(RetryLimitReachedError {
+            status: StatusCode::TOO_MANY_REQUESTS,
+            request_id: None,
+        });
+
+        assert!(CodexErr::InternalServerError.is_provider_unavailable());
+        assert!(retry_limit.is_provider_unavailable());
+        assert!(unexpected(StatusCode::SERVICE_UNAVAILABLE).is_provider_unavailable());
+        assert!(unexpected(StatusCode::TOO_MANY_REQUESTS).is_provider_unavailable());
+        assert!(!unexpected(StatusCode::UNAUTHORIZED).is_provider_unavailable());
+        assert!(!CodexErr::ContextWindowExceeded.is_provider_unavailable());
+    }
+
     #[test]
     fn usage_limit_reached_error_formats_plus_plan() {
         let err = UsageLimitReachedError {
@@ -726,6 +787,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn stream_error_maps_to_stream_disconnected() {
+        let err = CodexErr::Stream("stream closed before response.completed".to_string(), None);
+        assert_eq!(
+            err.to_codex_protocol_error(),
+            CodexErrorInfo::ResponseStreamDisconnected {
+                http_status_code: None,
+            }
+        );
+    }
+
     #[test]
     fn sandbox_denied_uses_aggregated_output_when_stderr_empty() {
         let output = ExecToolCallOutput {
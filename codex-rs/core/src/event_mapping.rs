@@ -19,6 +19,7 @@ use uuid::Uuid;
 
 use crate::instructions::SkillInstructions;
 use crate::instructions::UserInstructions;
+use crate::mcp::mention::is_mcp_mention_contents;
 use crate::session_prefix::is_session_prefix;
 use crate::user_shell_command::is_user_shell_command_text;
 use crate::web_search::web_search_action_detail;
@@ -26,6 +27,7 @@ use crate::web_search::web_search_action_detail;
 fn parse_user_message(message: &[ContentItem]) -> Option<UserMessageItem> {
     if UserInstructions::is_user_instructions(message)
         || SkillInstructions::is_skill_instructions(message)
+        || is_mcp_mention_contents(message)
     {
         return None;
     }
@@ -16,9 +16,11 @@ use tokio::io::BufReader;
 use tokio::process::Child;
 use tokio_util::sync::CancellationToken;
 
+use crate::config::types::LinuxSandboxConfig;
 use crate::error::CodexErr;
 use crate::error::Result;
 use crate::error::SandboxErr;
+pub use crate::exec_limits::ExecLimits;
 use crate::get_platform_sandbox;
 use crate::protocol::Event;
 use crate::protocol::EventMsg;
@@ -29,6 +31,8 @@ use crate::sandboxing::CommandSpec;
 use crate::sandboxing::ExecEnv;
 use crate::sandboxing::SandboxManager;
 use crate::sandboxing::SandboxPermissions;
+use crate::sandboxing::SandboxTransformOptions;
+use crate::spawn::SpawnChildRequest;
 use crate::spawn::StdioPolicy;
 use crate::spawn::spawn_child_async;
 use crate::text_encoding::bytes_to_string_smart;
@@ -51,7 +55,7 @@ const AGGREGATE_BUFFER_INITIAL_CAPACITY: usize = 8 * 1024; // 8 KiB
 ///
 /// This mirrors unified exec's output cap so a single runaway command cannot
 /// OOM the process by dumping huge amounts of data to stdout/stderr.
-const EXEC_OUTPUT_MAX_BYTES: usize = 1024 * 1024; // 1 MiB
+pub(crate) const EXEC_OUTPUT_MAX_BYTES: usize = 1024 * 1024; // 1 MiB
 
 /// Limit the number of ExecCommandOutputDelta events emitted per exec call.
 /// Aggregation still collects full output; only the live event stream is capped.
@@ -67,6 +71,7 @@ pub struct ExecParams {
     pub windows_sandbox_level: codex_protocol::config_types::WindowsSandboxLevel,
     pub justification: Option<String>,
     pub arg0: Option<String>,
+    pub limits: ExecLimits,
 }
 
 /// Mechanism to terminate an exec invocation before it finishes naturally.
@@ -163,6 +168,7 @@ pub async fn process_exec_tool_call(
         windows_sandbox_level,
         justification,
         arg0: _,
+        limits,
     } = params;
 
     let (program, args) = command.split_first().ok_or_else(|| {
@@ -180,6 +186,7 @@ pub async fn process_exec_tool_call(
         expiration,
         sandbox_permissions,
         justification,
+        limits,
     };
 
     let manager = SandboxManager::new();
@@ -189,8 +196,13 @@ pub async fn process_exec_tool_call(
             sandbox_policy,
             sandbox_type,
             sandbox_cwd,
-            codex_linux_sandbox_exe.as_ref(),
             windows_sandbox_level,
+            SandboxTransformOptions {
+                codex_linux_sandbox_exe: codex_linux_sandbox_exe.as_ref(),
+                linux_sandbox: &LinuxSandboxConfig::default(),
+                read_only_paths: &[],
+                network_proxy: None,
+            },
         )
         .map_err(CodexErr::from)?;
 
@@ -213,6 +225,7 @@ pub(crate) async fn execute_exec_env(
         sandbox_permissions,
         justification,
         arg0,
+        limits,
     } = env;
 
     let params = ExecParams {
@@ -224,6 +237,7 @@ pub(crate) async fn execute_exec_env(
         windows_sandbox_level,
         justification,
         arg0,
+        limits,
     };
 
     let start = Instant::now();
@@ -314,6 +328,7 @@ async fn exec_windows_sandbox(
         env,
         expiration,
         windows_sandbox_level,
+        limits,
         ..
     } = params;
     // TODO(iceweasel-oai): run_windows_sandbox_capture should support all
@@ -379,14 +394,11 @@ async fn exec_windows_sandbox(
     };
 
     let exit_status = synthetic_exit_status(capture.exit_code);
+    let max_bytes = limits.output_byte_cap();
     let mut stdout_text = capture.stdout;
-    if stdout_text.len() > EXEC_OUTPUT_MAX_BYTES {
-        stdout_text.truncate(EXEC_OUTPUT_MAX_BYTES);
-    }
+    stdout_text.truncate(max_bytes);
     let mut stderr_text = capture.stderr;
-    if stderr_text.len() > EXEC_OUTPUT_MAX_BYTES {
-        stderr_text.truncate(EXEC_OUTPUT_MAX_BYTES);
-    }
+    stderr_text.truncate(max_bytes);
     let stdout = StreamOutput {
         text: stdout_text,
         truncated_after_lines: None,
@@ -395,7 +407,7 @@ async fn exec_windows_sandbox(
         text: stderr_text,
         truncated_after_lines: None,
     };
-    let aggregated_output = aggregate_output(&stdout, &stderr);
+    let aggregated_output = aggregate_output(&stdout, &stderr, max_bytes);
 
     Ok(RawExecToolCallOutput {
         exit_status,
@@ -592,9 +604,9 @@ fn append_capped(dst: &mut Vec<u8>, src: &[u8], max_bytes: usize) {
 fn aggregate_output(
     stdout: &StreamOutput<Vec<u8>>,
     stderr: &StreamOutput<Vec<u8>>,
+    max_bytes: usize,
 ) -> StreamOutput<Vec<u8>> {
     let total_len = stdout.text.len().saturating_add(stderr.text.len());
-    let max_bytes = EXEC_OUTPUT_MAX_BYTES;
     let mut aggregated = Vec::with_capacity(total_len.min(max_bytes));
 
     if total_len <= max_bytes {
@@ -668,6 +680,7 @@ async fn exec(
         arg0,
         expiration,
         windows_sandbox_level: _,
+        limits,
         ..
     } = params;
 
@@ -678,17 +691,18 @@ async fn exec(
         ))
     })?;
     let arg0_ref = arg0.as_deref();
-    let child = spawn_child_async(
-        PathBuf::from(program),
-        args.into(),
-        arg0_ref,
+    let child = spawn_child_async(SpawnChildRequest {
+        program: PathBuf::from(program),
+        args: args.into(),
+        arg0: arg0_ref,
         cwd,
         sandbox_policy,
-        StdioPolicy::RedirectForShellTool,
+        stdio_policy: StdioPolicy::RedirectForShellTool,
         env,
-    )
+        limits,
+    })
     .await?;
-    consume_truncated_output(child, expiration, stdout_stream).await
+    consume_truncated_output(child, expiration, limits.output_byte_cap(), stdout_stream).await
 }
 
 /// Consumes the output of a child process, truncating it so it is suitable for
@@ -696,6 +710,7 @@ async fn exec(
 async fn consume_truncated_output(
     mut child: Child,
     expiration: ExecExpiration,
+    max_bytes: usize,
     stdout_stream: Option<StdoutStream>,
 ) -> Result<RawExecToolCallOutput> {
     // Both stdout and stderr were configured with `Stdio::piped()`
@@ -717,11 +732,13 @@ async fn consume_truncated_output(
         BufReader::new(stdout_reader),
         stdout_stream.clone(),
         false,
+        max_bytes,
     ));
     let stderr_handle = tokio::spawn(read_capped(
         BufReader::new(stderr_reader),
         stdout_stream.clone(),
         true,
+        max_bytes,
     ));
 
     let (exit_status, timed_out) = tokio::select! {
@@ -787,7 +804,7 @@ async fn consume_truncated_output(
         Duration::from_millis(IO_DRAIN_TIMEOUT_MS),
     )
     .await?;
-    let aggregated_output = aggregate_output(&stdout, &stderr);
+    let aggregated_output = aggregate_output(&stdout, &stderr, max_bytes);
 
     Ok(RawExecToolCallOutput {
         exit_status,
@@ -802,8 +819,9 @@ async fn read_capped<R: AsyncRead + Unpin + Send + 'static>(
     mut reader: R,
     stream: Option<StdoutStream>,
     is_stderr: bool,
+    max_bytes: usize,
 ) -> io::Result<StreamOutput<Vec<u8>>> {
-    let mut buf = Vec::with_capacity(AGGREGATE_BUFFER_INITIAL_CAPACITY.min(EXEC_OUTPUT_MAX_BYTES));
+    let mut buf = Vec::with_capacity(AGGREGATE_BUFFER_INITIAL_CAPACITY.min(max_bytes));
     let mut tmp = [0u8; READ_CHUNK_SIZE];
     let mut emitted_deltas: usize = 0;
 
@@ -835,7 +853,7 @@ async fn read_capped<R: AsyncRead + Unpin + Send + 'static>(
             emitted_deltas += 1;
         }
 
-        append_capped(&mut buf, &tmp[..n], EXEC_OUTPUT_MAX_BYTES);
+        append_capped(&mut buf, &tmp[..n], max_bytes);
         // Continue reading to EOF to avoid back-pressure
     }
 
@@ -934,7 +952,9 @@ mod tests {
             writer.write_all(&bytes).await.expect("write");
         });
 
-        let out = read_capped(reader, None, false).await.expect("read");
+        let out = read_capped(reader, None, false, EXEC_OUTPUT_MAX_BYTES)
+            .await
+            .expect("read");
         assert_eq!(out.text.len(), EXEC_OUTPUT_MAX_BYTES);
     }
 
@@ -949,7 +969,7 @@ mod tests {
             truncated_after_lines: None,
         };
 
-        let aggregated = aggregate_output(&stdout, &stderr);
+        let aggregated = aggregate_output(&stdout, &stderr, EXEC_OUTPUT_MAX_BYTES);
         let stdout_cap = EXEC_OUTPUT_MAX_BYTES / 3;
         let stderr_cap = EXEC_OUTPUT_MAX_BYTES.saturating_sub(stdout_cap);
 
@@ -970,7 +990,7 @@ mod tests {
             truncated_after_lines: None,
         };
 
-        let aggregated = aggregate_output(&stdout, &stderr);
+        let aggregated = aggregate_output(&stdout, &stderr, EXEC_OUTPUT_MAX_BYTES);
         let stderr_cap = EXEC_OUTPUT_MAX_BYTES.saturating_sub(stdout_len);
 
         assert_eq!(aggregated.text.len(), EXEC_OUTPUT_MAX_BYTES);
@@ -989,7 +1009,7 @@ mod tests {
             truncated_after_lines: None,
         };
 
-        let aggregated = aggregate_output(&stdout, &stderr);
+        let aggregated = aggregate_output(&stdout, &stderr, EXEC_OUTPUT_MAX_BYTES);
         let stdout_len = EXEC_OUTPUT_MAX_BYTES.saturating_sub(1);
 
         assert_eq!(aggregated.text.len(), EXEC_OUTPUT_MAX_BYTES);
@@ -1008,7 +1028,7 @@ mod tests {
             truncated_after_lines: None,
         };
 
-        let aggregated = aggregate_output(&stdout, &stderr);
+        let aggregated = aggregate_output(&stdout, &stderr, EXEC_OUTPUT_MAX_BYTES);
         let mut expected = Vec::new();
         expected.extend_from_slice(&stdout.text);
         expected.extend_from_slice(&stderr.text);
@@ -1052,6 +1072,7 @@ mod tests {
             windows_sandbox_level: codex_protocol::config_types::WindowsSandboxLevel::Disabled,
             justification: None,
             arg0: None,
+            limits: ExecLimits::default(),
         };
 
         let output = exec(params, SandboxType::None, &SandboxPolicy::ReadOnly, None).await?;
@@ -1082,6 +1103,34 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn cpu_limit_ends_busy_command() -> Result<()> {
+        let params = ExecParams {
+            command: vec![
+                "/bin/sh".to_string(),
+                "-c".to_string(),
+                "while :; do :; done".to_string(),
+            ],
+            cwd: std::env::current_dir()?,
+            expiration: 30_000.into(),
+            env: std::env::vars().collect(),
+            sandbox_permissions: SandboxPermissions::UseDefault,
+            windows_sandbox_level: codex_protocol::config_types::WindowsSandboxLevel::Disabled,
+            justification: None,
+            arg0: None,
+            limits: ExecLimits {
+                cpu_seconds: Some(1),
+                ..ExecLimits::default()
+            },
+        };
+
+        let output = exec(params, SandboxType::None, &SandboxPolicy::ReadOnly, None).await?;
+        assert!(!output.timed_out);
+        assert!(!output.exit_status.success());
+        Ok(())
+    }
+
     #[tokio::test]
     async fn process_exec_tool_call_respects_cancellation_token() -> Result<()> {
         let command = long_running_command();
@@ -1098,6 +1147,7 @@ mod tests {
             windows_sandbox_level: codex_protocol::config_types::WindowsSandboxLevel::Disabled,
             justification: None,
             arg0: None,
+            limits: ExecLimits::default(),
         };
         tokio::spawn(async move {
             tokio::time::sleep(Duration::from_millis(1_000)).await;
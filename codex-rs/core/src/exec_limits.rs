@@ -0,0 +1,320 @@
+//! Per-command output and resource limits for the shell tools.
+//!
+//! Defaults come from `[exec_limits]` in config.toml, and the model can tighten
+//! them for a single `shell` or `shell_command` call; `exec_command` sessions
+//! get the configured limits. CPU and memory limits are set on the spawned
+//! process and inherited by everything it starts.
+//!
+//! On Unix, CPU time is capped with `RLIMIT_CPU`. On Linux, memory is capped
+//! with a cgroup v2 `memory.max` when Codex's cgroup tree delegates the memory
+//! controller. Otherwise it falls back to `RLIMIT_DATA`, which counts memory a
+//! process has made writable rather than address space it has only reserved,
+//! so JITs and VMs that reserve large regions up front still start. On Windows
+//! a job object enforces both; the process is created suspended and only
+//! resumed once it is in the job.
+
+use std::io;
+
+/// Limits for one command. `None` means the default: [`EXEC_OUTPUT_MAX_BYTES`]
+/// of output and no CPU or memory limit.
+///
+/// [`EXEC_OUTPUT_MAX_BYTES`]: crate::exec::EXEC_OUTPUT_MAX_BYTES
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExecLimits {
+    /// Bytes of stdout and stderr kept from the command.
+    pub max_output_bytes: Option<usize>,
+    /// CPU time the command may use, in seconds.
+    pub cpu_seconds: Option<u64>,
+    /// Address space the command may use, in megabytes.
+    pub memory_mb: Option<u64>,
+}
+
+impl ExecLimits {
+    /// Limits for a call that asks for `requested`, where `self` holds the
+    /// configured limits. A call can only tighten a configured limit.
+    pub fn tightened_by(self, requested: ExecLimits) -> ExecLimits {
+        fn tighter<T: Ord>(configured: Option<T>, requested: Option<T>) -> Option<T> {
+            match (configured, requested) {
+                (Some(configured), Some(requested)) => Some(configured.min(requested)),
+                (configured, None) => configured,
+                (None, requested) => requested,
+            }
+        }
+        ExecLimits {
+            max_output_bytes: tighter(self.max_output_bytes, requested.max_output_bytes),
+            cpu_seconds: tighter(self.cpu_seconds, requested.cpu_seconds),
+            memory_mb: tighter(self.memory_mb, requested.memory_mb),
+        }
+    }
+
+    /// Bytes of output to keep from the command.
+    pub(crate) fn output_byte_cap(self) -> usize {
+        self.max_output_bytes
+            .unwrap_or(crate::exec::EXEC_OUTPUT_MAX_BYTES)
+    }
+
+    /// Whether the command has a CPU or memory limit, which has to be set on
+    /// a process spawned for it.
+    pub(crate) fn has_resource_limits(self) -> bool {
+        self.cpu_seconds.is_some() || self.memory_mb.is_some()
+    }
+
+    pub(crate) fn memory_bytes(self) -> Option<u64> {
+        self.memory_mb.map(|mb| mb.saturating_mul(1024 * 1024))
+    }
+
+    /// These limits with the memory limit left to something else, such as a
+    /// cgroup.
+    #[cfg(target_os = "linux")]
+    pub(crate) fn without_memory(self) -> ExecLimits {
+        ExecLimits {
+            memory_mb: None,
+            ..self
+        }
+    }
+}
+
+/// Wraps `command` in a shell that lowers the CPU and memory rlimits before
+/// running it. This is for `exec_command` sessions, whose processes Codex
+/// does not fork itself; the wrapper runs inside the sandbox.
+#[cfg(unix)]
+pub(crate) fn wrap_with_rlimits(limits: ExecLimits, command: Vec<String>) -> Vec<String> {
+    if !limits.has_resource_limits() {
+        return command;
+    }
+    // `-S` lowers only the soft limit; a hard limit that is already lower
+    // wins, so a failure to raise it is ignored.
+    let mut script = String::new();
+    if let Some(cpu_seconds) = limits.cpu_seconds {
+        script.push_str(&format!("ulimit -S -t {cpu_seconds} 2>/dev/null; "));
+    }
+    if let Some(memory_mb) = limits.memory_mb {
+        let memory_kb = memory_mb.saturating_mul(1024);
+        script.push_str(&format!("ulimit -S -d {memory_kb} 2>/dev/null; "));
+    }
+    script.push_str("exec \"$@\"");
+    let mut wrapped = vec![
+        "/bin/sh".to_string(),
+        "-c".to_string(),
+        script,
+        "codex-exec-limits".to_string(),
+    ];
+    wrapped.extend(command);
+    wrapped
+}
+
+/// A cgroup v2 leaf with `memory.max` set, created for one command.
+#[cfg(target_os = "linux")]
+pub(crate) struct MemoryCgroup {
+    dir: std::path::PathBuf,
+}
+
+#[cfg(target_os = "linux")]
+impl MemoryCgroup {
+    /// Creates the cgroup next to Codex's own, or returns `None` when cgroup
+    /// v2 is not mounted or the memory controller is not delegated to Codex.
+    pub(crate) fn create(memory_bytes: u64) -> Option<Self> {
+        use std::sync::atomic::AtomicU64;
+        use std::sync::atomic::Ordering;
+
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+        let own = std::fs::read_to_string("/proc/self/cgroup").ok()?;
+        let own = own.lines().find_map(|line| line.strip_prefix("0::"))?;
+        let own = std::path::Path::new("/sys/fs/cgroup").join(own.trim_start_matches('/'));
+        // A cgroup with processes cannot have children with controllers, so
+        // the new cgroup is a sibling of Codex's unless Codex is at the root.
+        let parent = match own.parent() {
+            Some(parent) if parent.starts_with("/sys/fs/cgroup") => parent.to_path_buf(),
+            _ => own,
+        };
+        let controllers = std::fs::read_to_string(parent.join("cgroup.subtree_control")).ok()?;
+        if !controllers.split_whitespace().any(|name| name == "memory") {
+            return None;
+        }
+        let dir = parent.join(format!(
+            "codex-exec-{}-{}",
+            std::process::id(),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::create_dir(&dir).ok()?;
+        if std::fs::write(dir.join("memory.max"), memory_bytes.to_string()).is_err() {
+            let _ = std::fs::remove_dir(&dir);
+            return None;
+        }
+        // Without swap accounting this file does not exist; memory.max still
+        // applies then.
+        let _ = std::fs::write(dir.join("memory.swap.max"), "0");
+        Some(Self { dir })
+    }
+
+    /// The cgroup's `cgroup.procs`, for [`join_cgroup`] in the forked child.
+    pub(crate) fn procs_path(&self) -> std::ffi::CString {
+        use std::os::unix::ffi::OsStrExt;
+
+        // Built from `/proc/self/cgroup` and a generated name, neither of
+        // which contains a NUL byte.
+        std::ffi::CString::new(self.dir.join("cgroup.procs").as_os_str().as_bytes())
+            .unwrap_or_default()
+    }
+
+    /// Removes the cgroup once the command and everything it started have
+    /// exited.
+    pub(crate) fn remove_when_empty(self) {
+        const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(POLL_INTERVAL).await;
+                let events = tokio::fs::read_to_string(self.dir.join("cgroup.events")).await;
+                let populated = match &events {
+                    Ok(events) => events.lines().any(|line| line == "populated 1"),
+                    Err(_) => false,
+                };
+                if !populated {
+                    let _ = tokio::fs::remove_dir(&self.dir).await;
+                    return;
+                }
+            }
+        });
+    }
+}
+
+/// Moves the current process into the cgroup whose `cgroup.procs` is
+/// `procs`. Runs in the child between `fork` and `exec`.
+#[cfg(target_os = "linux")]
+pub(crate) fn join_cgroup(procs: &std::ffi::CStr) -> io::Result<()> {
+    // Writing 0 moves the writing process.
+    let fd = unsafe { libc::open(procs.as_ptr(), libc::O_WRONLY | libc::O_CLOEXEC) };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let written = unsafe { libc::write(fd, b"0".as_ptr().cast(), 1) };
+    let result = if written == 1 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    };
+    unsafe { libc::close(fd) };
+    result
+}
+
+/// Lowers the CPU and memory limits of the current process. Runs in the child
+/// between `fork` and `exec`, so it only makes plain system calls.
+#[cfg(unix)]
+pub(crate) fn apply_in_child(limits: ExecLimits) -> io::Result<()> {
+    // `RLIMIT_*` are `c_int` on some targets and `c_uint` on others, so this
+    // is a macro rather than a function taking the resource.
+    macro_rules! lower_rlimit {
+        ($resource:expr, $value:expr) => {{
+            let mut current = libc::rlimit {
+                rlim_cur: 0,
+                rlim_max: 0,
+            };
+            if unsafe { libc::getrlimit($resource, &mut current) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let value = $value as libc::rlim_t;
+            let limit = libc::rlimit {
+                rlim_cur: value.min(current.rlim_max),
+                rlim_max: current.rlim_max,
+            };
+            if unsafe { libc::setrlimit($resource, &limit) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+        }};
+    }
+
+    if let Some(cpu_seconds) = limits.cpu_seconds {
+        // Exceeding the soft limit sends SIGXCPU, which ends the command.
+        lower_rlimit!(libc::RLIMIT_CPU, cpu_seconds);
+    }
+    if let Some(memory_bytes) = limits.memory_bytes() {
+        lower_rlimit!(libc::RLIMIT_DATA, memory_bytes);
+    }
+    Ok(())
+}
+
+/// `CREATE_SUSPENDED` from the Win32 process creation flags.
+#[cfg(windows)]
+pub(crate) const CREATE_SUSPENDED: u32 = 0x0000_0004;
+
+/// Puts `child`, created with [`CREATE_SUSPENDED`], in a job object that
+/// enforces the CPU and memory limits, then lets it run. The job lives as
+/// long as any process in it.
+#[cfg(windows)]
+pub(crate) fn apply_to_suspended_child(
+    limits: ExecLimits,
+    child: &tokio::process::Child,
+) -> io::Result<()> {
+    let (Some(handle), Some(pid)) = (child.raw_handle(), child.id()) else {
+        return Err(io::Error::other(
+            "the command exited before its limits were applied",
+        ));
+    };
+    codex_windows_sandbox::apply_job_limits(
+        handle as isize,
+        limits.cpu_seconds,
+        limits.memory_bytes(),
+    )
+    .and_then(|()| codex_windows_sandbox::resume_process(pid))
+    .map_err(|err| io::Error::other(format!("failed to apply command limits: {err}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn calls_can_only_tighten_configured_limits() {
+        let configured = ExecLimits {
+            max_output_bytes: None,
+            cpu_seconds: Some(60),
+            memory_mb: Some(2048),
+        };
+        let requested = ExecLimits {
+            max_output_bytes: Some(4096),
+            cpu_seconds: Some(600),
+            memory_mb: Some(512),
+        };
+        assert_eq!(
+            configured.tightened_by(requested),
+            ExecLimits {
+                max_output_bytes: Some(4096),
+                cpu_seconds: Some(60),
+                memory_mb: Some(512),
+            }
+        );
+        assert_eq!(configured.tightened_by(ExecLimits::default()), configured);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn exec_command_sessions_are_wrapped_only_when_limited() {
+        let command = vec!["bash".to_string(), "-lc".to_string(), "make".to_string()];
+        assert_eq!(
+            wrap_with_rlimits(ExecLimits::default(), command.clone()),
+            command
+        );
+        assert_eq!(
+            wrap_with_rlimits(
+                ExecLimits {
+                    max_output_bytes: None,
+                    cpu_seconds: Some(60),
+                    memory_mb: Some(512),
+                },
+                command
+            ),
+            vec![
+                "/bin/sh".to_string(),
+                "-c".to_string(),
+                "ulimit -S -t 60 2>/dev/null; ulimit -S -d 524288 2>/dev/null; exec \"$@\""
+                    .to_string(),
+                "codex-exec-limits".to_string(),
+                "bash".to_string(),
+                "-lc".to_string(),
+                "make".to_string(),
+            ]
+        );
+    }
+}
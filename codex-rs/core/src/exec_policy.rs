@@ -5,10 +5,16 @@ use std::sync::Arc;
 
 use arc_swap::ArcSwap;
 
+use crate::approval_rules::RuleSubject;
+use crate::approval_rules::deciding_rule;
+use crate::approval_rules::rule_reason;
+use crate::command_risk::CommandRisk;
+use crate::command_risk::classify_command;
+use crate::command_risk::matches_safe_command;
+use crate::config::types::ApprovalRule;
+use crate::config::types::ApprovalRuleDecision;
 use crate::config_loader::ConfigLayerStack;
 use crate::config_loader::ConfigLayerStackOrdering;
-use crate::is_dangerous_command::command_might_be_dangerous;
-use crate::is_safe_command::is_known_safe_command;
 use codex_execpolicy::AmendError;
 use codex_execpolicy::Decision;
 use codex_execpolicy::Error as ExecPolicyRuleError;
@@ -94,6 +100,10 @@ pub(crate) struct ExecApprovalRequest<'a> {
     pub(crate) sandbox_policy: &'a SandboxPolicy,
     pub(crate) sandbox_permissions: SandboxPermissions,
     pub(crate) prefix_rule: Option<Vec<String>>,
+    /// `[approval] safe_commands` prefixes.
+    pub(crate) safe_commands: &'a [Vec<String>],
+    /// `[[approval.rules]]`, checked before everything else.
+    pub(crate) approval_rules: &'a [ApprovalRule],
 }
 
 impl ExecPolicyManager {
@@ -130,7 +140,13 @@ impl ExecPolicyManager {
             sandbox_policy,
             sandbox_permissions,
             prefix_rule,
+            safe_commands,
+            approval_rules,
         } = req;
+
+        let sandboxed = !sandbox_permissions.requires_escalated_permissions()
+            && !matches!(sandbox_policy, SandboxPolicy::DangerFullAccess);
+        let rule = deciding_rule(approval_rules, &RuleSubject::Command(command), sandboxed);
         let exec_policy = self.current();
         let commands =
             parse_shell_lc_plain_commands(command).unwrap_or_else(|| vec![command.to_vec()]);
@@ -140,10 +156,37 @@ impl ExecPolicyManager {
                 sandbox_policy,
                 cmd,
                 sandbox_permissions,
+                safe_commands,
             )
         };
         let evaluation = exec_policy.check_multiple(commands.iter(), &exec_policy_fallback);
 
+        // `allow` and `ask` rules cannot override a command the execpolicy or
+        // the requirements forbid.
+        if let Some(rule) = rule
+            && (rule.decision == ApprovalRuleDecision::Deny
+                || evaluation.decision != Decision::Forbidden)
+        {
+            return match rule.decision {
+                ApprovalRuleDecision::Allow => ExecApprovalRequirement::Skip {
+                    bypass_sandbox: false,
+                    proposed_execpolicy_amendment: None,
+                },
+                ApprovalRuleDecision::Deny => ExecApprovalRequirement::Forbidden {
+                    reason: rule_reason(rule),
+                },
+                ApprovalRuleDecision::Ask if approval_policy == AskForApproval::Never => {
+                    ExecApprovalRequirement::Forbidden {
+                        reason: PROMPT_CONFLICT_REASON.to_string(),
+                    }
+                }
+                ApprovalRuleDecision::Ask => ExecApprovalRequirement::NeedsApproval {
+                    reason: Some(rule_reason(rule)),
+                    proposed_execpolicy_amendment: None,
+                },
+            };
+        }
+
         let requested_amendment = derive_requested_execpolicy_amendment(
             features,
             prefix_rule.as_ref(),
@@ -300,8 +343,20 @@ pub fn render_decision_for_unmatched_command(
     sandbox_policy: &SandboxPolicy,
     command: &[String],
     sandbox_permissions: SandboxPermissions,
+    safe_commands: &[Vec<String>],
 ) -> Decision {
-    if is_known_safe_command(command) {
+    let risk = classify_command(command);
+    if risk == CommandRisk::ReadOnly {
+        return Decision::Allow;
+    }
+
+    // Configured safe commands skip the prompt but still run in the sandbox,
+    // so a request to run one outside it is still asked about. Destructive
+    // commands never count as safe.
+    if risk == CommandRisk::LowRisk
+        && !sandbox_permissions.requires_escalated_permissions()
+        && matches_safe_command(command, safe_commands)
+    {
         return Decision::Allow;
     }
 
@@ -310,13 +365,14 @@ pub fn render_decision_for_unmatched_command(
     let runtime_sandbox_provides_safety =
         cfg!(windows) && matches!(sandbox_policy, SandboxPolicy::ReadOnly);
 
-    // If the command is flagged as dangerous or we have no sandbox protection,
-    // we should never allow it to run without user approval.
+    // If the command is flagged as destructive or we have no sandbox
+    // protection, we should never allow it to run without user approval, even
+    // in permissive modes.
     //
     // We prefer to prompt the user rather than outright forbid the command,
     // but if the user has explicitly disabled prompts, we must
     // forbid the command.
-    if command_might_be_dangerous(command) || runtime_sandbox_provides_safety {
+    if risk == CommandRisk::Destructive || runtime_sandbox_provides_safety {
         return if matches!(approval_policy, AskForApproval::Never) {
             Decision::Forbidden
         } else {
@@ -819,6 +875,8 @@ prefix_rule(pattern=["rm"], decision="forbidden")
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                safe_commands: &[],
+                approval_rules: &[],
             })
             .await;
 
@@ -830,6 +888,89 @@ prefix_rule(pattern=["rm"], decision="forbidden")
         );
     }
 
+    #[tokio::test]
+    async fn allow_rules_do_not_override_forbidden_commands() {
+        let policy_src = r#"
+prefix_rule(pattern=["rm"], decision="forbidden")
+"#;
+        let mut parser = PolicyParser::new();
+        parser
+            .parse("test.rules", policy_src)
+            .expect("parse policy");
+        let manager = ExecPolicyManager::new(Arc::new(parser.build()));
+        let allow_everything = vec![crate::config::types::ApprovalRule {
+            tool: None,
+            command: Some(
+                crate::config::types::ApprovalRuleCommand::new(".*".to_string())
+                    .expect("valid regex"),
+            ),
+            paths: Vec::new(),
+            sandbox: None,
+            decision: ApprovalRuleDecision::Allow,
+            reason: None,
+        }];
+        let command = vec!["rm".to_string(), "-rf".to_string(), "/tmp/x".to_string()];
+
+        let requirement = manager
+            .create_exec_approval_requirement_for_command(ExecApprovalRequest {
+                features: &Features::with_defaults(),
+                command: &command,
+                approval_policy: AskForApproval::OnRequest,
+                sandbox_policy: &SandboxPolicy::new_workspace_write_policy(),
+                sandbox_permissions: SandboxPermissions::UseDefault,
+                prefix_rule: None,
+                safe_commands: &[],
+                approval_rules: &allow_everything,
+            })
+            .await;
+
+        assert_eq!(
+            requirement,
+            ExecApprovalRequirement::Forbidden {
+                reason: "`rm -rf /tmp/x` rejected: policy forbids commands starting with `rm`"
+                    .to_string()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn allow_rules_do_not_skip_approval_for_escalated_commands() {
+        let manager = ExecPolicyManager::default();
+        let allow_cargo_test = vec![crate::config::types::ApprovalRule {
+            tool: None,
+            command: Some(
+                crate::config::types::ApprovalRuleCommand::new(r"^cargo test\b".to_string())
+                    .expect("valid regex"),
+            ),
+            paths: Vec::new(),
+            sandbox: None,
+            decision: ApprovalRuleDecision::Allow,
+            reason: None,
+        }];
+        let command = vec_str(&["cargo", "test"]);
+
+        let requirement = manager
+            .create_exec_approval_requirement_for_command(ExecApprovalRequest {
+                features: &Features::with_defaults(),
+                command: &command,
+                approval_policy: AskForApproval::OnRequest,
+                sandbox_policy: &SandboxPolicy::new_workspace_write_policy(),
+                sandbox_permissions: SandboxPermissions::RequireEscalated,
+                prefix_rule: None,
+                safe_commands: &[],
+                approval_rules: &allow_cargo_test,
+            })
+            .await;
+
+        assert_eq!(
+            requirement,
+            ExecApprovalRequirement::NeedsApproval {
+                reason: None,
+                proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(command)),
+            }
+        );
+    }
+
     #[tokio::test]
     async fn justification_is_included_in_forbidden_exec_approval_requirement() {
         let policy_src = r#"
@@ -858,6 +999,8 @@ prefix_rule(
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                safe_commands: &[],
+                approval_rules: &[],
             })
             .await;
 
@@ -888,6 +1031,8 @@ prefix_rule(
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                safe_commands: &[],
+                approval_rules: &[],
             })
             .await;
 
@@ -919,6 +1064,8 @@ prefix_rule(
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                safe_commands: &[],
+                approval_rules: &[],
             })
             .await;
 
@@ -943,6 +1090,8 @@ prefix_rule(
                 sandbox_policy: &SandboxPolicy::ReadOnly,
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                safe_commands: &[],
+                approval_rules: &[],
             })
             .await;
 
@@ -974,6 +1123,8 @@ prefix_rule(
                 sandbox_policy: &SandboxPolicy::ReadOnly,
                 sandbox_permissions: SandboxPermissions::RequireEscalated,
                 prefix_rule: Some(vec!["cargo".to_string(), "install".to_string()]),
+                safe_commands: &[],
+                approval_rules: &[],
             })
             .await;
 
@@ -1012,6 +1163,8 @@ prefix_rule(
                     sandbox_policy: &SandboxPolicy::DangerFullAccess,
                     sandbox_permissions: SandboxPermissions::UseDefault,
                     prefix_rule: None,
+                    safe_commands: &[],
+                    approval_rules: &[],
                 })
                 .await,
             ExecApprovalRequirement::NeedsApproval {
@@ -1087,6 +1240,8 @@ prefix_rule(
                 sandbox_policy: &SandboxPolicy::ReadOnly,
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                safe_commands: &[],
+                approval_rules: &[],
             })
             .await;
 
@@ -1115,6 +1270,8 @@ prefix_rule(
                 sandbox_policy: &SandboxPolicy::ReadOnly,
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                safe_commands: &[],
+                approval_rules: &[],
             })
             .await;
 
@@ -1146,6 +1303,8 @@ prefix_rule(
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                safe_commands: &[],
+                approval_rules: &[],
             })
             .await;
 
@@ -1174,6 +1333,8 @@ prefix_rule(
                 sandbox_policy: &SandboxPolicy::ReadOnly,
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                safe_commands: &[],
+                approval_rules: &[],
             })
             .await;
 
@@ -1213,6 +1374,8 @@ prefix_rule(
                     sandbox_policy: &SandboxPolicy::ReadOnly,
                     sandbox_permissions: SandboxPermissions::UseDefault,
                     prefix_rule: None,
+                    safe_commands: &[],
+                    approval_rules: &[],
                 })
                 .await,
             ExecApprovalRequirement::NeedsApproval {
@@ -1237,6 +1400,8 @@ prefix_rule(
                 sandbox_policy: &SandboxPolicy::ReadOnly,
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                safe_commands: &[],
+                approval_rules: &[],
             })
             .await;
 
@@ -1268,6 +1433,8 @@ prefix_rule(
                 sandbox_policy: &SandboxPolicy::ReadOnly,
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                safe_commands: &[],
+                approval_rules: &[],
             })
             .await;
 
@@ -1292,6 +1459,8 @@ prefix_rule(
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                safe_commands: &[],
+                approval_rules: &[],
             })
             .await;
 
@@ -1304,6 +1473,40 @@ prefix_rule(
         );
     }
 
+    #[tokio::test]
+    async fn safe_commands_still_prompt_for_escalated_requests() {
+        let command = vec_str(&["cargo", "test", "-p", "core"]);
+        let safe_commands = vec![vec_str(&["cargo", "test"])];
+        let manager = ExecPolicyManager::default();
+        let requirement_for = |sandbox_permissions| {
+            manager.create_exec_approval_requirement_for_command(ExecApprovalRequest {
+                features: &Features::with_defaults(),
+                command: &command,
+                approval_policy: AskForApproval::UnlessTrusted,
+                sandbox_policy: &SandboxPolicy::new_workspace_write_policy(),
+                sandbox_permissions,
+                prefix_rule: None,
+                safe_commands: &safe_commands,
+                approval_rules: &[],
+            })
+        };
+
+        assert_eq!(
+            requirement_for(SandboxPermissions::UseDefault).await,
+            ExecApprovalRequirement::Skip {
+                bypass_sandbox: false,
+                proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(command.clone())),
+            }
+        );
+        assert_eq!(
+            requirement_for(SandboxPermissions::RequireEscalated).await,
+            ExecApprovalRequirement::NeedsApproval {
+                reason: None,
+                proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(command.clone())),
+            }
+        );
+    }
+
     fn vec_str(items: &[&str]) -> Vec<String> {
         items.iter().map(std::string::ToString::to_string).collect()
     }
@@ -1360,6 +1563,8 @@ prefix_rule(
                     sandbox_policy: &SandboxPolicy::ReadOnly,
                     sandbox_permissions: permissions,
                     prefix_rule: None,
+                    safe_commands: &[],
+                    approval_rules: &[],
                 })
                 .await,
             "{pwsh_approval_reason}"
@@ -1384,6 +1589,8 @@ prefix_rule(
                     sandbox_policy: &SandboxPolicy::ReadOnly,
                     sandbox_permissions: permissions,
                     prefix_rule: None,
+                    safe_commands: &[],
+                    approval_rules: &[],
                 })
                 .await,
             r#"On all platforms, a forbidden command should require approval
@@ -1404,6 +1611,8 @@ prefix_rule(
                     sandbox_policy: &SandboxPolicy::ReadOnly,
                     sandbox_permissions: permissions,
                     prefix_rule: None,
+                    safe_commands: &[],
+                    approval_rules: &[],
                 })
                 .await,
             r#"On all platforms, a forbidden command should require approval
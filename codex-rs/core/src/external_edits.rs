@@ -0,0 +1,238 @@
+//! Tell the model about files changed outside the session.
+//!
+//! With the `external_edits` feature, every turn ends by recording the size
+//! and modification time of the files in the git repository (tracked, or
+//! untracked but not ignored). The next turn compares the working tree with
+//! that snapshot; files added, changed, or removed in between, by the user in
+//! an editor or by another tool, are listed in an `<external_edits>` message
+//! recorded after the user's input, so the model re-reads them rather than
+//! patching content it saw before they changed.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use tracing::warn;
+
+use crate::git_info::get_git_repo_root;
+use crate::repo_map::list_files;
+use crate::session_prefix::EXTERNAL_EDITS_OPEN_TAG;
+
+/// Repositories with more files than this are watched partially.
+const MAX_FILES: usize = 20_000;
+/// Changed files named in the notice; the rest are only counted.
+const MAX_LISTED: usize = 50;
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ExternalEditWatcher {
+    root: Option<PathBuf>,
+    /// Keyed by path relative to `root`.
+    files: HashMap<PathBuf, FileStamp>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileStamp {
+    modified: SystemTime,
+    len: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ExternalEditKind {
+    Added,
+    Modified,
+    Deleted,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ExternalEdit {
+    /// Relative to the repository root.
+    pub(crate) path: PathBuf,
+    pub(crate) kind: ExternalEditKind,
+}
+
+impl ExternalEditWatcher {
+    /// Remember the current state of the repository containing `cwd`.
+    /// Outside a git repository nothing is watched.
+    pub(crate) fn snapshot(&mut self, cwd: &Path) {
+        *self = match scan(cwd) {
+            Some((root, files)) => Self {
+                root: Some(root),
+                files,
+            },
+            None => Self::default(),
+        };
+    }
+
+    /// Files that differ from the last snapshot. Empty before the first
+    /// snapshot or when `cwd` is now in a different repository.
+    pub(crate) fn changes(&self, cwd: &Path) -> Vec<ExternalEdit> {
+        let Some(snapshot_root) = &self.root else {
+            return Vec::new();
+        };
+        let Some((root, current)) = scan(cwd) else {
+            return Vec::new();
+        };
+        if &root != snapshot_root {
+            return Vec::new();
+        }
+
+        let mut edits: Vec<ExternalEdit> = current
+            .iter()
+            .filter_map(|(path, stamp)| {
+                let kind = match self.files.get(path) {
+                    None => ExternalEditKind::Added,
+                    Some(previous) if previous != stamp => ExternalEditKind::Modified,
+                    Some(_) => return None,
+                };
+                Some(ExternalEdit {
+                    path: path.clone(),
+                    kind,
+                })
+            })
+            .chain(
+                self.files
+                    .keys()
+                    .filter(|path| !current.contains_key(*path))
+                    .map(|path| ExternalEdit {
+                        path: path.clone(),
+                        kind: ExternalEditKind::Deleted,
+                    }),
+            )
+            .collect();
+        edits.sort_by(|a, b| a.path.cmp(&b.path));
+        edits
+    }
+
+    pub(crate) fn root(&self) -> Option<&Path> {
+        self.root.as_deref()
+    }
+}
+
+fn scan(cwd: &Path) -> Option<(PathBuf, HashMap<PathBuf, FileStamp>)> {
+    let root = get_git_repo_root(cwd)?;
+    let listed = match list_files(&root) {
+        Ok(listed) => listed,
+        Err(err) => {
+            warn!("failed to list files to watch for external edits: {err}");
+            return None;
+        }
+    };
+    let files = listed
+        .into_iter()
+        .take(MAX_FILES)
+        .filter_map(|relative| {
+            let metadata = std::fs::metadata(root.join(&relative))
+                .ok()
+                .filter(std::fs::Metadata::is_file)?;
+            let stamp = FileStamp {
+                modified: metadata.modified().ok()?,
+                len: metadata.len(),
+            };
+            Some((relative, stamp))
+        })
+        .collect();
+    Some((root, files))
+}
+
+/// The message telling the model which files changed, or `None` when none did.
+pub(crate) fn external_edits_message(root: &Path, edits: &[ExternalEdit]) -> Option<ResponseItem> {
+    if edits.is_empty() {
+        return None;
+    }
+    let mut text = format!(
+        "{EXTERNAL_EDITS_OPEN_TAG}\nThese files in {} changed outside this session since your last turn. Re-read any you plan to edit; what you saw of them before may be out of date.\n",
+        root.display()
+    );
+    for edit in edits.iter().take(MAX_LISTED) {
+        let label = match edit.kind {
+            ExternalEditKind::Added => "added",
+            ExternalEditKind::Modified => "modified",
+            ExternalEditKind::Deleted => "deleted",
+        };
+        let _ = writeln!(text, "{label}: {}", edit.path.display());
+    }
+    if edits.len() > MAX_LISTED {
+        let _ = writeln!(text, "({} more files not shown)", edits.len() - MAX_LISTED);
+    }
+    text.push_str("</external_edits>");
+    Some(ResponseItem::Message {
+        id: None,
+        role: "user".to_string(),
+        content: vec![ContentItem::InputText { text }],
+        end_turn: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::process::Command;
+
+    #[test]
+    fn reports_files_changed_since_the_snapshot() {
+        let repo = tempfile::tempdir().expect("tempdir");
+        let status = Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(repo.path())
+            .status()
+            .expect("git init");
+        assert!(status.success());
+        let root = get_git_repo_root(repo.path()).expect("repo root");
+        std::fs::write(root.join("kept.rs"), "fn kept() {}\n").expect("write");
+        std::fs::write(root.join("edited.rs"), "fn edited() {}\n").expect("write");
+        std::fs::write(root.join("removed.rs"), "fn removed() {}\n").expect("write");
+
+        let mut watcher = ExternalEditWatcher::default();
+        assert_eq!(watcher.changes(&root), Vec::new());
+        watcher.snapshot(&root);
+        assert_eq!(watcher.changes(&root), Vec::new());
+
+        std::fs::write(root.join("edited.rs"), "fn edited_elsewhere() {}\n").expect("write");
+        std::fs::remove_file(root.join("removed.rs")).expect("remove");
+        std::fs::write(root.join("added.rs"), "fn added() {}\n").expect("write");
+        let edits = watcher.changes(&root);
+        assert_eq!(
+            edits,
+            vec![
+                ExternalEdit {
+                    path: PathBuf::from("added.rs"),
+                    kind: ExternalEditKind::Added,
+                },
+                ExternalEdit {
+                    path: PathBuf::from("edited.rs"),
+                    kind: ExternalEditKind::Modified,
+                },
+                ExternalEdit {
+                    path: PathBuf::from("removed.rs"),
+                    kind: ExternalEditKind::Deleted,
+                },
+            ]
+        );
+
+        let Some(ResponseItem::Message { content, .. }) = external_edits_message(&root, &edits)
+        else {
+            panic!("expected a notice");
+        };
+        let [ContentItem::InputText { text }] = content.as_slice() else {
+            panic!("expected text");
+        };
+        let body: Vec<&str> = text.lines().skip(2).collect();
+        assert_eq!(
+            body,
+            vec![
+                "added: added.rs",
+                "modified: edited.rs",
+                "deleted: removed.rs",
+                "</external_edits>",
+            ]
+        );
+
+        watcher.snapshot(&root);
+        assert_eq!(watcher.changes(&root), Vec::new());
+    }
+}
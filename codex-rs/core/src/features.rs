@@ -91,7 +91,7 @@ pub enum Feature {
     ExecPolicy,
     /// Allow the model to request approval and propose exec rules.
     RequestRule,
-    /// Enable Windows sandbox (restricted token) on Windows.
+    /// Enable Windows sandbox (restricted token) on Windows. On by default there.
     WindowsSandbox,
     /// Use the elevated Windows sandbox pipeline (setup + runner).
     WindowsSandboxElevated,
@@ -127,6 +127,30 @@ pub enum Feature {
     Personality,
     /// Use the Responses API WebSocket transport for OpenAI by default.
     ResponsesWebsockets,
+    /// Load `.codex/memory.md` into the instructions and expose the `memory` tool.
+    ProjectMemory,
+    /// Expose the `git_commit` tool and the `/commit` command.
+    GitCommitTool,
+    /// Expose the `create_pull_request` tool and the `/pr` command.
+    PullRequestTool,
+    /// Insert an outline of the repository's definitions into every prompt.
+    RepoMap,
+    /// Expose the `search_code` tool backed by the local embedding index.
+    CodeSearch,
+    /// Expose the `edit_definition` tool for edits addressed by definition name.
+    StructuredEdit,
+    /// Tell the model which files changed outside the session between turns.
+    ExternalEdits,
+    /// Run `shell` tool commands in one long-lived shell per session.
+    PersistentShell,
+    /// Give the model tools to run long-lived commands as background jobs.
+    BackgroundJobs,
+    /// Save long command output to disk and let the model page through it.
+    OutputPaging,
+    /// Expose the `fetch_url` tool that reads web pages as text.
+    FetchUrl,
+    /// Expose the `open_file` tool that shows the user a file in their editor.
+    OpenFileTool,
 }
 
 impl Feature {
@@ -142,6 +166,14 @@ impl Feature {
         self.info().default_enabled
     }
 
+    /// Whether the feature can be toggled from the `[experimental]` table.
+    pub fn is_experimental(self) -> bool {
+        matches!(
+            self.stage(),
+            Stage::Experimental { .. } | Stage::UnderDevelopment
+        )
+    }
+
     fn info(self) -> &'static FeatureSpec {
         FEATURES
             .iter()
@@ -283,6 +315,30 @@ impl Features {
         }
     }
 
+    /// Apply an `[experimental]` table. Only experimental and in-development
+    /// features are accepted; other flags belong in `[features]`.
+    pub fn apply_experimental_map(&mut self, m: &BTreeMap<String, bool>) {
+        for (k, v) in m {
+            match feature_for_key(k) {
+                Some(feat) if feat.is_experimental() => {
+                    if *v {
+                        self.enable(feat);
+                    } else {
+                        self.disable(feat);
+                    }
+                }
+                Some(_) => {
+                    tracing::warn!(
+                        "`[experimental].{k}` is not an experimental feature; set it under `[features]`"
+                    );
+                }
+                None => {
+                    tracing::warn!("unknown experimental feature key in config: {k}");
+                }
+            }
+        }
+    }
+
     pub fn from_config(
         cfg: &ConfigToml,
         config_profile: &ConfigProfile,
@@ -301,6 +357,9 @@ impl Features {
         if let Some(base_features) = cfg.features.as_ref() {
             features.apply_map(&base_features.entries);
         }
+        if let Some(base_experimental) = cfg.experimental.as_ref() {
+            features.apply_experimental_map(&base_experimental.entries);
+        }
 
         let profile_legacy = LegacyFeatureToggles {
             include_apply_patch_tool: config_profile.include_apply_patch_tool,
@@ -314,6 +373,9 @@ impl Features {
         if let Some(profile_features) = config_profile.features.as_ref() {
             features.apply_map(&profile_features.entries);
         }
+        if let Some(profile_experimental) = config_profile.experimental.as_ref() {
+            features.apply_experimental_map(&profile_experimental.entries);
+        }
 
         overrides.apply(&mut features);
 
@@ -323,6 +385,16 @@ impl Features {
     pub fn enabled_features(&self) -> Vec<Feature> {
         self.enabled.iter().copied().collect()
     }
+
+    /// Flags whose state differs from the built-in default, as `key=true` or
+    /// `key=false`, for feedback and bug reports.
+    pub fn overridden_flags(&self) -> Vec<String> {
+        FEATURES
+            .iter()
+            .filter(|spec| self.enabled(spec.id) != spec.default_enabled)
+            .map(|spec| format!("{}={}", spec.key, self.enabled(spec.id)))
+            .collect()
+    }
 }
 
 fn legacy_usage_notice(alias: &str, feature: Feature) -> (String, Option<String>) {
@@ -478,7 +550,13 @@ pub const FEATURES: &[FeatureSpec] = &[
     FeatureSpec {
         id: Feature::WindowsSandbox,
         key: "experimental_windows_sandbox",
+        #[cfg(windows)]
+        stage: Stage::Stable,
+        #[cfg(windows)]
+        default_enabled: true,
+        #[cfg(not(windows))]
         stage: Stage::UnderDevelopment,
+        #[cfg(not(windows))]
         default_enabled: false,
     },
     FeatureSpec {
@@ -577,6 +655,126 @@ pub const FEATURES: &[FeatureSpec] = &[
         stage: Stage::UnderDevelopment,
         default_enabled: false,
     },
+    FeatureSpec {
+        id: Feature::ProjectMemory,
+        key: "project_memory",
+        stage: Stage::Experimental {
+            name: "Project memory",
+            menu_description: "Let Codex keep notes about this project in .codex/memory.md, with your approval, and read them in every session.",
+            announcement: "NEW! Codex can remember project conventions across sessions. Enable Project memory in /experimental!",
+        },
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::GitCommitTool,
+        key: "git_commit",
+        stage: Stage::Experimental {
+            name: "Git commits",
+            menu_description: "Let Codex commit the files it changed, with a generated message, after you approve the diff. Adds /commit.",
+            announcement: "NEW! /commit lets Codex commit its changes with a generated message. Enable Git commits in /experimental!",
+        },
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::PullRequestTool,
+        key: "pull_request",
+        stage: Stage::Experimental {
+            name: "Pull requests",
+            menu_description: "Let Codex push the branch and open a GitHub pull request or GitLab merge request, after you approve. Adds /pr.",
+            announcement: "NEW! /pr opens a pull request with a description written from your session. Enable Pull requests in /experimental!",
+        },
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::RepoMap,
+        key: "repo_map",
+        stage: Stage::Experimental {
+            name: "Repository map",
+            menu_description: "Give Codex an outline of the functions and types in this repository, kept up to date as files change.",
+            announcement: "NEW! A repository map helps Codex find its way in large codebases. Enable Repository map in /experimental!",
+        },
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::CodeSearch,
+        key: "code_search",
+        stage: Stage::Experimental {
+            name: "Code search",
+            menu_description: "Let Codex search this repository by meaning using the local embedding index built by `codex index build`.",
+            announcement: "NEW! Codex can find code by what it does once you run `codex index build`. Enable Code search in /experimental!",
+        },
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::StructuredEdit,
+        key: "structured_edit",
+        stage: Stage::Experimental {
+            name: "Structured edits",
+            menu_description: "Let Codex edit functions and classes by name, located with a syntax tree instead of line context.",
+            announcement: "NEW! Structured edits let Codex replace functions and classes by name. Enable Structured edits in /experimental!",
+        },
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::ExternalEdits,
+        key: "external_edits",
+        stage: Stage::Experimental {
+            name: "External edits",
+            menu_description: "Tell Codex which files you or other tools changed between turns, so it re-reads them before editing.",
+            announcement: "NEW! Codex can notice files you edit between turns and re-read them. Enable External edits in /experimental!",
+        },
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::PersistentShell,
+        key: "persistent_shell",
+        stage: Stage::Experimental {
+            name: "Persistent shell",
+            menu_description: "Run Codex's commands in one shell that stays open, so exported variables, activated virtualenvs, and cd carry over between commands.",
+            announcement: "NEW! A persistent shell keeps environment variables and the working directory between Codex's commands. Enable Persistent shell in /experimental!",
+        },
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::BackgroundJobs,
+        key: "background_jobs",
+        stage: Stage::Experimental {
+            name: "Background jobs",
+            menu_description: "Let Codex start dev servers and watch builds as background jobs that keep running across turns, and read their logs when needed.",
+            announcement: "NEW! Codex can run dev servers and watch builds as background jobs and check their logs on demand. Enable Background jobs in /experimental!",
+        },
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::OutputPaging,
+        key: "output_paging",
+        stage: Stage::Experimental {
+            name: "Output paging",
+            menu_description: "Keep the full output of long commands so Codex can read the lines that were cut from the middle.",
+            announcement: "NEW! Codex can now page through the middle of huge build logs instead of only seeing the start and end. Enable Output paging in /experimental!",
+        },
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::FetchUrl,
+        key: "fetch_url",
+        stage: Stage::Experimental {
+            name: "Fetch URL",
+            menu_description: "Let Codex download web pages, such as linked docs and issues, and read them as text.",
+            announcement: "NEW! Codex can read the docs and issues you link to without you pasting them. Enable Fetch URL in /experimental!",
+        },
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::OpenFileTool,
+        key: "open_file",
+        stage: Stage::Experimental {
+            name: "Open in editor",
+            menu_description: "Let Codex open the file and line it is talking about in your editor.",
+            announcement: "NEW! Codex can jump your editor to the code it is describing. Enable Open in editor in /experimental!",
+        },
+        default_enabled: false,
+    },
 ];
 
 /// Push a warning event if any under-development features are enabled.
@@ -0,0 +1,790 @@
+//! Downloading web pages for the `fetch_url` tool.
+//!
+//! Only public `http`/`https` addresses are fetched: every hop of a redirect
+//! is resolved and checked before connecting, and the connection is pinned to
+//! the checked address. System proxy settings are ignored; when the sandbox
+//! limits the network to the network proxy, requests go through it instead, so
+//! its allowlist applies. `robots.txt` is honored, downloads are capped, and
+//! pages are cached for a few minutes so reading a long page in pieces does
+//! not download it again. HTML is reduced to Markdown-like text with the
+//! navigation, scripts, and other page chrome left out.
+
+use std::collections::HashMap;
+use std::io;
+use std::net::IpAddr;
+use std::net::Ipv4Addr;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use reqwest::header::CONTENT_TYPE;
+use reqwest::header::LOCATION;
+use url::Host;
+use url::Url;
+
+use crate::default_client::get_codex_user_agent;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+const MAX_REDIRECTS: usize = 5;
+const MAX_DOWNLOAD_BYTES: usize = 5 * 1024 * 1024;
+const CACHE_TTL: Duration = Duration::from_secs(15 * 60);
+const CACHE_CAPACITY: usize = 64;
+/// Name matched against `User-agent` lines in `robots.txt`.
+const ROBOTS_AGENT: &str = "codex";
+
+/// Elements dropped with everything inside them.
+const SKIPPED_ELEMENTS: &[&str] = &[
+    "script", "style", "noscript", "template", "svg", "iframe", "nav", "header", "footer", "aside",
+    "form", "button", "select",
+];
+
+/// A downloaded page reduced to text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FetchedPage {
+    /// Address the page was served from, after redirects.
+    pub url: String,
+    pub title: Option<String>,
+    pub text: String,
+    /// Whether the download stopped at the size limit.
+    pub truncated: bool,
+}
+
+#[derive(Default)]
+struct Cache {
+    pages: HashMap<String, (Instant, Arc<FetchedPage>)>,
+    robots: HashMap<String, (Instant, Arc<RobotsRules>)>,
+}
+
+static CACHE: LazyLock<Mutex<Cache>> = LazyLock::new(|| Mutex::new(Cache::default()));
+
+fn cached<T>(entries: &HashMap<String, (Instant, Arc<T>)>, key: &str) -> Option<Arc<T>> {
+    entries
+        .get(key)
+        .filter(|(fetched_at, _)| fetched_at.elapsed() < CACHE_TTL)
+        .map(|(_, value)| Arc::clone(value))
+}
+
+fn remember<T>(entries: &mut HashMap<String, (Instant, Arc<T>)>, key: String, value: Arc<T>) {
+    entries.retain(|_, (fetched_at, _)| fetched_at.elapsed() < CACHE_TTL);
+    if entries.len() >= CACHE_CAPACITY
+        && let Some(oldest) = entries
+            .iter()
+            .min_by_key(|(_, (fetched_at, _))| *fetched_at)
+            .map(|(key, _)| key.clone())
+    {
+        entries.remove(&oldest);
+    }
+    entries.insert(key, (Instant::now(), value));
+}
+
+/// Downloads `url` and returns it as text, from the cache when it was
+/// fetched recently. With `proxy`, the request goes through the network proxy
+/// and the cache is bypassed, so the proxy's allowlist decides every fetch.
+pub(crate) async fn fetch_page(
+    url: &str,
+    proxy: Option<SocketAddr>,
+) -> io::Result<Arc<FetchedPage>> {
+    let url = Url::parse(url).map_err(|err| io::Error::other(format!("invalid URL: {err}")))?;
+    if proxy.is_none()
+        && let Some(page) = CACHE
+            .lock()
+            .ok()
+            .and_then(|cache| cached(&cache.pages, url.as_str()))
+    {
+        return Ok(page);
+    }
+
+    if !robots_rules(&url, proxy).await.allows(&robots_path(&url)) {
+        return Err(io::Error::other(format!(
+            "{}'s robots.txt does not allow fetching {}",
+            url.host_str().unwrap_or_default(),
+            url.path()
+        )));
+    }
+
+    let (final_url, response) = get_public(url.clone(), proxy).await?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(io::Error::other(format!("{final_url} returned {status}")));
+    }
+    let content_type = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    let is_html = content_type.contains("html");
+    let is_text = content_type.is_empty()
+        || content_type.starts_with("text/")
+        || ["json", "xml", "markdown", "yaml", "javascript"]
+            .iter()
+            .any(|kind| content_type.contains(kind));
+    if !is_html && !is_text {
+        return Err(io::Error::other(format!(
+            "{final_url} is {content_type}, which cannot be read as text"
+        )));
+    }
+    let (body, truncated) = read_capped(response).await?;
+    let body = String::from_utf8_lossy(&body);
+    let (title, text) = if is_html {
+        html_to_markdown(&body, &final_url)
+    } else {
+        (None, body.into_owned())
+    };
+
+    let page = Arc::new(FetchedPage {
+        url: final_url.to_string(),
+        title,
+        text,
+        truncated,
+    });
+    if proxy.is_none()
+        && let Ok(mut cache) = CACHE.lock()
+    {
+        remember(&mut cache.pages, url.to_string(), Arc::clone(&page));
+    }
+    Ok(page)
+}
+
+/// Sends a GET to `url`, following redirects itself so that each hop is
+/// checked to be a public address. Goes through `proxy` when given and
+/// connects directly otherwise, whatever the proxy environment variables say.
+async fn get_public(
+    mut url: Url,
+    proxy: Option<SocketAddr>,
+) -> io::Result<(Url, reqwest::Response)> {
+    for _ in 0..=MAX_REDIRECTS {
+        let addr = resolve_public(&url).await?;
+        let mut builder = reqwest::Client::builder()
+            .user_agent(get_codex_user_agent())
+            .redirect(reqwest::redirect::Policy::none())
+            .timeout(REQUEST_TIMEOUT);
+        builder = match proxy {
+            Some(proxy) => builder
+                .proxy(reqwest::Proxy::all(format!("http://{proxy}")).map_err(io::Error::other)?),
+            None => builder.no_proxy(),
+        };
+        if proxy.is_none()
+            && let Some(Host::Domain(domain)) = url.host()
+        {
+            // Connect to the address that was checked rather than resolving
+            // the name again.
+            builder = builder.resolve(domain, addr);
+        }
+        let client = builder.build().map_err(io::Error::other)?;
+        let response = client
+            .get(url.clone())
+            .send()
+            .await
+            .map_err(io::Error::other)?;
+        if !response.status().is_redirection() {
+            return Ok((url, response));
+        }
+        let location = response
+            .headers()
+            .get(LOCATION)
+            .and_then(|value| value.to_str().ok())
+            .ok_or_else(|| {
+                io::Error::other(format!("{url} redirected without a Location header"))
+            })?;
+        url = url
+            .join(location)
+            .map_err(|err| io::Error::other(format!("{url} redirected to {location}: {err}")))?;
+    }
+    Err(io::Error::other(format!(
+        "stopped after {MAX_REDIRECTS} redirects"
+    )))
+}
+
+/// Resolves the host of `url`, refusing schemes other than `http` and
+/// `https` and hosts with any non-public address.
+async fn resolve_public(url: &Url) -> io::Result<SocketAddr> {
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(io::Error::other(format!(
+            "only http and https URLs can be fetched, not {}",
+            url.scheme()
+        )));
+    }
+    let port = url.port_or_known_default().unwrap_or(80);
+    let addrs: Vec<SocketAddr> = match url.host() {
+        Some(Host::Domain(domain)) => tokio::net::lookup_host((domain, port)).await?.collect(),
+        Some(Host::Ipv4(ip)) => vec![SocketAddr::new(IpAddr::V4(ip), port)],
+        Some(Host::Ipv6(ip)) => vec![SocketAddr::new(IpAddr::V6(ip), port)],
+        None => return Err(io::Error::other(format!("{url} has no host"))),
+    };
+    if let Some(private) = addrs.iter().find(|addr| !is_public_ip(addr.ip())) {
+        return Err(io::Error::other(format!(
+            "refusing to fetch {url}: it resolves to the non-public address {}",
+            private.ip()
+        )));
+    }
+    addrs
+        .first()
+        .copied()
+        .ok_or_else(|| io::Error::other(format!("{url} did not resolve to any address")))
+}
+
+/// Whether `ip` is a globally routable address, so fetching it cannot reach
+/// the local machine or its network.
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, c, _] = ip.octets();
+            !(ip.is_private()
+                || ip.is_loopback()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_multicast()
+                || ip.is_documentation()
+                || a == 0
+                // Shared address space (carrier-grade NAT).
+                || (a == 100 && (64..128).contains(&b))
+                || (a == 192 && b == 0 && c == 0)
+                // Benchmarking.
+                || (a == 198 && (b == 18 || b == 19))
+                || a >= 240)
+        }
+        IpAddr::V6(ip) => {
+            let segments = ip.segments();
+            let octets = ip.octets();
+            if let Some(ip) = ip.to_ipv4_mapped() {
+                return is_public_ip(IpAddr::V4(ip));
+            }
+            // NAT64 (64:ff9b::/96) and 6to4 (2002::/16) addresses carry an
+            // IPv4 address that the gateway connects to.
+            if segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0] {
+                let [.., a, b, c, d] = octets;
+                return is_public_ip(IpAddr::V4(Ipv4Addr::new(a, b, c, d)));
+            }
+            if segments[0] == 0x2002 {
+                let [_, _, a, b, c, d, ..] = octets;
+                return is_public_ip(IpAddr::V4(Ipv4Addr::new(a, b, c, d)));
+            }
+            !(ip.is_loopback()
+                || ip.is_unspecified()
+                || ip.is_multicast()
+                || ip.is_unique_local()
+                || ip.is_unicast_link_local()
+                // Deprecated site-local (fec0::/10).
+                || (segments[0] & 0xffc0) == 0xfec0
+                // Local-use NAT64 (64:ff9b:1::/48).
+                || segments[..3] == [0x64, 0xff9b, 1]
+                // Documentation.
+                || (segments[0] == 0x2001 && segments[1] == 0xdb8))
+        }
+    }
+}
+
+async fn read_capped(mut response: reqwest::Response) -> io::Result<(Vec<u8>, bool)> {
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(io::Error::other)? {
+        let room = MAX_DOWNLOAD_BYTES - body.len();
+        if chunk.len() >= room {
+            body.extend_from_slice(&chunk[..room]);
+            return Ok((body, true));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok((body, false))
+}
+
+/// The `Allow` and `Disallow` rules of a site's `robots.txt` that apply to
+/// Codex.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct RobotsRules {
+    /// `(allow, pattern)` pairs.
+    rules: Vec<(bool, String)>,
+}
+
+impl RobotsRules {
+    fn parse(body: &str) -> Self {
+        let mut specific = Vec::new();
+        let mut wildcard = Vec::new();
+        let mut saw_specific = false;
+        let mut agents: Vec<String> = Vec::new();
+        let mut in_rules = false;
+        for line in body.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+            match key.trim().to_ascii_lowercase().as_str() {
+                "user-agent" => {
+                    if in_rules {
+                        agents.clear();
+                        in_rules = false;
+                    }
+                    let agent = value.to_ascii_lowercase();
+                    saw_specific |= agent.contains(ROBOTS_AGENT);
+                    agents.push(agent);
+                }
+                key @ ("allow" | "disallow") => {
+                    in_rules = true;
+                    // An empty `Disallow` allows everything.
+                    if value.is_empty() {
+                        continue;
+                    }
+                    let rule = (key == "allow", value.to_string());
+                    if agents.iter().any(|agent| agent.contains(ROBOTS_AGENT)) {
+                        specific.push(rule);
+                    } else if agents.iter().any(|agent| agent == "*") {
+                        wildcard.push(rule);
+                    }
+                }
+                _ => {}
+            }
+        }
+        Self {
+            rules: if saw_specific { specific } else { wildcard },
+        }
+    }
+
+    /// The longest matching rule decides; `Allow` wins ties.
+    fn allows(&self, path: &str) -> bool {
+        self.rules
+            .iter()
+            .filter(|(_, pattern)| robots_pattern_matches(pattern, path))
+            .max_by_key(|(allow, pattern)| (pattern.len(), *allow))
+            .is_none_or(|(allow, _)| *allow)
+    }
+}
+
+fn robots_path(url: &Url) -> String {
+    match url.query() {
+        Some(query) => format!("{}?{query}", url.path()),
+        None => url.path().to_string(),
+    }
+}
+
+/// Matches a `robots.txt` path pattern, where `*` matches any run of
+/// characters and a trailing `$` anchors the end.
+fn robots_pattern_matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(pattern) => (pattern, true),
+        None => (pattern, false),
+    };
+    let mut parts = pattern.split('*');
+    let Some(mut rest) = path.strip_prefix(parts.next().unwrap_or_default()) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return !anchored || rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    if anchored {
+        rest.ends_with(last)
+    } else {
+        rest.contains(last)
+    }
+}
+
+/// The rules of the `robots.txt` for `url`'s origin. A missing or unreadable
+/// file allows everything.
+async fn robots_rules(url: &Url, proxy: Option<SocketAddr>) -> Arc<RobotsRules> {
+    let origin = url.origin().ascii_serialization();
+    if let Some(rules) = CACHE
+        .lock()
+        .ok()
+        .and_then(|cache| cached(&cache.robots, &origin))
+    {
+        return rules;
+    }
+    let mut rules = RobotsRules::default();
+    if let Ok(robots_url) = url.join("/robots.txt")
+        && let Ok((_, response)) = get_public(robots_url, proxy).await
+        && response.status().is_success()
+        && let Ok((body, _)) = read_capped(response).await
+    {
+        rules = RobotsRules::parse(&String::from_utf8_lossy(&body));
+    }
+    let rules = Arc::new(rules);
+    if let Ok(mut cache) = CACHE.lock() {
+        remember(&mut cache.robots, origin, Arc::clone(&rules));
+    }
+    rules
+}
+
+/// Reduces an HTML document to Markdown-like text: headings, paragraphs,
+/// lists, links, and code blocks are kept, and page chrome is dropped. When
+/// the page has a `<main>` or `<article>` element only its content is kept.
+fn html_to_markdown(html: &str, base: &Url) -> (Option<String>, String) {
+    let lower = html.to_ascii_lowercase();
+    let title = element_range(&lower, "title", false)
+        .map(|(start, end)| collapse_whitespace(&decode_entities(&html[start..end])))
+        .filter(|title| !title.is_empty());
+    let (start, end) = ["main", "article", "body"]
+        .iter()
+        .find_map(|name| element_range(&lower, name, true))
+        .unwrap_or((0, html.len()));
+
+    let mut writer = MarkdownWriter {
+        base,
+        out: String::new(),
+        pre_depth: 0,
+        links: Vec::new(),
+    };
+    let mut pos = start;
+    while let Some(offset) = html[pos..end].find('<') {
+        writer.text(&html[pos..pos + offset]);
+        pos += offset;
+        if lower[pos..end].starts_with("<!--") {
+            pos = lower[pos..end]
+                .find("-->")
+                .map_or(end, |close| pos + close + 3);
+            continue;
+        }
+        let Some(close) = html[pos..end].find('>') else {
+            pos = end;
+            break;
+        };
+        let tag = &html[pos + 1..pos + close];
+        pos += close + 1;
+        let closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        if !closing && !tag.ends_with('/') && SKIPPED_ELEMENTS.contains(&name.as_str()) {
+            let end_tag = format!("</{name}");
+            pos = lower[pos..end]
+                .find(&end_tag)
+                .and_then(|found| {
+                    let after = pos + found;
+                    lower[after..end].find('>').map(|gt| after + gt + 1)
+                })
+                .unwrap_or(end);
+            continue;
+        }
+        writer.tag(&name, closing, tag);
+    }
+    writer.text(&html[pos..end]);
+    (title, writer.finish())
+}
+
+/// Byte range of the first `<name>` element in `lower`, either its content
+/// or, with `outer`, from its opening tag to the start of its last closing
+/// tag.
+fn element_range(lower: &str, name: &str, outer: bool) -> Option<(usize, usize)> {
+    let open = format!("<{name}");
+    let close = format!("</{name}");
+    let start = lower
+        .match_indices(&open)
+        .map(|(index, _)| index)
+        .find(|index| {
+            lower[index + open.len()..]
+                .chars()
+                .next()
+                .is_some_and(|c| c == '>' || c.is_whitespace())
+        })?;
+    let (start, end) = if outer {
+        (start, lower.rfind(&close)?)
+    } else {
+        let content_start = start + lower[start..].find('>')? + 1;
+        (
+            content_start,
+            content_start + lower[content_start..].find(&close)?,
+        )
+    };
+    (start < end).then_some((start, end))
+}
+
+struct MarkdownWriter<'a> {
+    base: &'a Url,
+    out: String,
+    pre_depth: usize,
+    /// Targets of the open `<a>` elements; `None` for links that are not
+    /// written out.
+    links: Vec<Option<String>>,
+}
+
+impl MarkdownWriter<'_> {
+    fn text(&mut self, raw: &str) {
+        let text = decode_entities(raw);
+        if self.pre_depth > 0 {
+            self.out.push_str(&text);
+            return;
+        }
+        for c in text.chars() {
+            if c.is_whitespace() {
+                if !self.out.is_empty() && !self.out.ends_with([' ', '\n']) {
+                    self.out.push(' ');
+                }
+            } else {
+                self.out.push(c);
+            }
+        }
+    }
+
+    /// Ends the current line and adds blank lines until there are `count`
+    /// line breaks.
+    fn break_lines(&mut self, count: usize) {
+        if self.pre_depth > 0 {
+            return;
+        }
+        let trimmed = self.out.trim_end_matches(' ').len();
+        self.out.truncate(trimmed);
+        if self.out.is_empty() {
+            return;
+        }
+        let existing = self.out.len() - self.out.trim_end_matches('\n').len();
+        for _ in existing..count {
+            self.out.push('\n');
+        }
+    }
+
+    fn tag(&mut self, name: &str, closing: bool, raw: &str) {
+        match name {
+            "p" | "div" | "section" | "article" | "main" | "table" | "ul" | "ol" | "dl"
+            | "blockquote" | "figure" | "hr" => self.break_lines(2),
+            "br" | "tr" | "dt" | "dd" | "figcaption" => self.break_lines(1),
+            "li" => {
+                self.break_lines(1);
+                if !closing {
+                    self.out.push_str("- ");
+                }
+            }
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                self.break_lines(2);
+                if !closing {
+                    let level = usize::from(name.as_bytes()[1] - b'0');
+                    self.out.push_str(&"#".repeat(level));
+                    self.out.push(' ');
+                }
+            }
+            "pre" if !closing => {
+                self.break_lines(2);
+                self.out.push_str("```\n");
+                self.pre_depth += 1;
+            }
+            "pre" => {
+                self.pre_depth = self.pre_depth.saturating_sub(1);
+                if !self.out.ends_with('\n') {
+                    self.out.push('\n');
+                }
+                self.out.push_str("```");
+                self.break_lines(2);
+            }
+            "code" if self.pre_depth == 0 => self.out.push('`'),
+            "td" | "th" if !closing => {
+                if !self.out.is_empty() && !self.out.ends_with([' ', '\n']) {
+                    self.out.push(' ');
+                }
+            }
+            "a" if !closing => {
+                let href = attribute(raw, "href")
+                    .filter(|href| !href.starts_with('#') && !href.starts_with("javascript:"))
+                    .and_then(|href| self.base.join(&href).ok())
+                    .map(String::from);
+                if href.is_some() {
+                    self.out.push('[');
+                }
+                self.links.push(href);
+            }
+            "a" => {
+                if let Some(Some(href)) = self.links.pop() {
+                    let trimmed = self.out.trim_end_matches(' ').len();
+                    self.out.truncate(trimmed);
+                    if self.out.ends_with('[') {
+                        // Nothing to show for the link, e.g. an icon.
+                        self.out.pop();
+                    } else {
+                        self.out.push_str(&format!("]({href})"));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn finish(self) -> String {
+        let mut out = String::with_capacity(self.out.len());
+        let mut blank_lines = 0;
+        for line in self.out.lines() {
+            let line = line.trim_end();
+            if line.is_empty() {
+                blank_lines += 1;
+                if blank_lines > 1 {
+                    continue;
+                }
+            } else {
+                blank_lines = 0;
+            }
+            out.push_str(line);
+            out.push('\n');
+        }
+        out.trim().to_string()
+    }
+}
+
+/// Value of the attribute `name` in the inside of a start tag.
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let lower = tag.to_ascii_lowercase();
+    let mut search = 0;
+    while let Some(found) = lower[search..].find(name) {
+        let start = search + found;
+        search = start + name.len();
+        if start == 0 || !lower.as_bytes()[start - 1].is_ascii_whitespace() {
+            continue;
+        }
+        let Some(value) = tag[search..].trim_start().strip_prefix('=') else {
+            continue;
+        };
+        let value = value.trim_start();
+        let value = match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => value[1..].split(quote).next(),
+            _ => value.split(char::is_whitespace).next(),
+        };
+        return value.map(decode_entities);
+    }
+    None
+}
+
+fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest[1..]
+            .find(';')
+            .filter(|len| *len <= 10)
+            .and_then(|len| decode_entity(&rest[1..1 + len]).map(|c| (c, len + 2)));
+        match decoded {
+            Some((c, len)) => {
+                out.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn decode_entity(entity: &str) -> Option<char> {
+    let c = match entity {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => ' ',
+        "mdash" => '—',
+        "ndash" => '–',
+        "hellip" => '…',
+        "lsquo" => '‘',
+        "rsquo" => '’',
+        "ldquo" => '“',
+        "rdquo" => '”',
+        "copy" => '©',
+        _ => {
+            let code = match entity.strip_prefix("#x").or(entity.strip_prefix("#X")) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => entity.strip_prefix('#')?.parse().ok()?,
+            };
+            return char::from_u32(code);
+        }
+    };
+    Some(c)
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::net::Ipv6Addr;
+
+    #[test]
+    fn html_is_reduced_to_markdown() {
+        let html = r#"<!doctype html>
+<html><head><title>Install &amp; setup</title><style>body { color: red }</style></head>
+<body>
+  <nav><a href="/">Home</a> <a href="/docs">Docs</a></nav>
+  <main>
+    <h1>Install</h1>
+    <p>Run the <code>install</code> script from
+       <a href="/downloads?os=linux">the downloads page</a>.</p>
+    <!-- <p>hidden</p> -->
+    <ul><li>Linux</li><li>macOS &mdash; Homebrew</li></ul>
+    <pre>cargo install codex
+codex --help</pre>
+    <script>if (a < b) { document.write("<p>no</p>") }</script>
+  </main>
+  <footer>Copyright</footer>
+</body></html>"#;
+        let base = Url::parse("https://example.com/docs/install").expect("url");
+
+        let (title, text) = html_to_markdown(html, &base);
+
+        assert_eq!(title.as_deref(), Some("Install & setup"));
+        assert_eq!(
+            text,
+            "# Install\n\nRun the `install` script from [the downloads page](https://example.com/downloads?os=linux).\n\n- Linux\n- macOS — Homebrew\n\n```\ncargo install codex\ncodex --help\n```"
+        );
+    }
+
+    #[test]
+    fn only_public_addresses_are_fetched() {
+        for ip in [
+            IpAddr::V4(Ipv4Addr::LOCALHOST),
+            IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)),
+            IpAddr::V4(Ipv4Addr::new(169, 254, 169, 254)),
+            IpAddr::V4(Ipv4Addr::new(100, 100, 0, 1)),
+            IpAddr::V6(Ipv6Addr::LOCALHOST),
+            IpAddr::V6("fd00::1".parse().expect("ipv6")),
+            IpAddr::V6("::ffff:127.0.0.1".parse().expect("ipv6")),
+            IpAddr::V6("64:ff9b::a9fe:a9fe".parse().expect("ipv6")),
+            IpAddr::V6("64:ff9b:1::1".parse().expect("ipv6")),
+            IpAddr::V6("2002:7f00:1::".parse().expect("ipv6")),
+            IpAddr::V6("2002:c0a8:101::1".parse().expect("ipv6")),
+            IpAddr::V6("fec0::1".parse().expect("ipv6")),
+        ] {
+            assert!(!is_public_ip(ip), "{ip} should be refused");
+        }
+        assert!(is_public_ip(IpAddr::V4(Ipv4Addr::new(93, 184, 216, 34))));
+        assert!(is_public_ip(IpAddr::V6(
+            "2606:4700::6810:84e5".parse().expect("ipv6")
+        )));
+        assert!(is_public_ip(IpAddr::V6(
+            "64:ff9b::5db8:d822".parse().expect("ipv6")
+        )));
+    }
+
+    #[test]
+    fn robots_rules_pick_the_most_specific_group_and_rule() {
+        let rules = RobotsRules::parse(
+            "User-agent: *\nDisallow: /\n\nUser-agent: Codex\nUser-agent: other\nDisallow: /private\nAllow: /private/docs\nDisallow: /*.pdf$\n",
+        );
+
+        assert!(rules.allows("/docs/install"));
+        assert!(!rules.allows("/private/keys"));
+        assert!(rules.allows("/private/docs/page"));
+        assert!(!rules.allows("/files/manual.pdf"));
+        assert!(rules.allows("/files/manual.pdf?download=1"));
+        assert!(!RobotsRules::parse("User-agent: *\nDisallow: /\n").allows("/"));
+        assert!(RobotsRules::parse("User-agent: *\nDisallow:\n").allows("/"));
+    }
+}
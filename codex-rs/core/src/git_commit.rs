@@ -0,0 +1,203 @@
+//! Committing the agent's changes from inside a session.
+//!
+//! A commit is planned first: the files to commit are resolved against the
+//! repository and turned into [`FileChange`]s, so the exact change can be
+//! shown for approval before anything is staged. Committing then stages and
+//! commits only those files, leaving anything else in the index alone, and
+//! appends the `[attribution]` trailers to the message.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::attribution::append_trailers;
+use crate::git_info::get_git_repo_root;
+use crate::protocol::FileChange;
+use crate::worktree::run_git;
+
+#[derive(Debug)]
+pub(crate) struct CommitPlan {
+    repo_root: PathBuf,
+    /// Repository-relative paths to commit.
+    pathspecs: Vec<String>,
+    /// What the commit will record, keyed by absolute path.
+    pub(crate) changes: HashMap<PathBuf, FileChange>,
+}
+
+impl CommitPlan {
+    /// Plan a commit of `paths`, relative to `cwd` or absolute. The paths are
+    /// required, so unrelated changes in the checkout never end up in the
+    /// commit.
+    pub(crate) async fn new(cwd: &Path, paths: &[String]) -> io::Result<Self> {
+        if paths.is_empty() {
+            return Err(io::Error::other("paths must list the files to commit"));
+        }
+        let Some(repo_root) = get_git_repo_root(cwd) else {
+            return Err(io::Error::other(format!(
+                "{} is not inside a git repository",
+                cwd.display()
+            )));
+        };
+        let pathspecs = paths
+            .iter()
+            .map(|path| {
+                let absolute = cwd.join(path);
+                absolute
+                    .strip_prefix(&repo_root)
+                    .map(|relative| relative.to_string_lossy().into_owned())
+                    .map_err(|_| io::Error::other(format!("{path} is outside the repository")))
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let mut args = vec![
+            "status",
+            "--porcelain",
+            "-z",
+            "--no-renames",
+            "--untracked-files=all",
+            "--",
+        ];
+        args.extend(pathspecs.iter().map(String::as_str));
+        let status = run_git(&repo_root, &args).await?;
+        let mut changes = HashMap::new();
+        for entry in String::from_utf8_lossy(&status.stdout).split('\0') {
+            // Entries are `XY <path>`.
+            let Some(path) = entry.get(3..).filter(|path| !path.is_empty()) else {
+                continue;
+            };
+            let absolute = repo_root.join(path);
+            let on_disk = tokio::fs::read(&absolute)
+                .await
+                .ok()
+                .map(|bytes| String::from_utf8_lossy(&bytes).into_owned());
+            let spec = format!("HEAD:{path}");
+            let in_head = run_git(&repo_root, &["show", &spec])
+                .await
+                .ok()
+                .map(|output| String::from_utf8_lossy(&output.stdout).into_owned());
+            let change = match (in_head, on_disk) {
+                (Some(previous), Some(content)) => FileChange::Update {
+                    unified_diff: similar::TextDiff::from_lines(&previous, &content)
+                        .unified_diff()
+                        .to_string(),
+                    move_path: None,
+                },
+                (None, Some(content)) => FileChange::Add { content },
+                (Some(content), None) => FileChange::Delete { content },
+                (None, None) => continue,
+            };
+            changes.insert(absolute, change);
+        }
+
+        Ok(Self {
+            repo_root,
+            pathspecs,
+            changes,
+        })
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Stage the planned files and commit them with `message` followed by
+    /// `trailers`, returning the abbreviated hash of the new commit.
+    pub(crate) async fn commit(&self, message: &str, trailers: &[String]) -> io::Result<String> {
+        let mut add = vec!["add", "-A", "--"];
+        add.extend(self.pathspecs.iter().map(String::as_str));
+        run_git(&self.repo_root, &add).await?;
+        let message = append_trailers(message, trailers);
+        let mut commit = vec!["commit", "-q", "-m", message.as_str(), "--"];
+        commit.extend(self.pathspecs.iter().map(String::as_str));
+        run_git(&self.repo_root, &commit).await?;
+        let output = run_git(&self.repo_root, &["rev-parse", "--short", "HEAD"]).await?;
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::worktree::init_test_repo;
+    use crate::worktree::test_git;
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn commit_records_only_the_planned_files() {
+        let repo = tempfile::tempdir().expect("repo");
+        let root = repo.path();
+        init_test_repo(root).await;
+        std::fs::write(root.join("old.txt"), "old\n").expect("write");
+        test_git(root, &["add", "."]).await;
+        test_git(root, &["commit", "-q", "-m", "add old.txt"]).await;
+        let root = get_git_repo_root(root).expect("repo root");
+
+        std::fs::write(root.join("README.md"), "hello\nworld\n").expect("write");
+        std::fs::remove_file(root.join("old.txt")).expect("remove");
+        std::fs::create_dir(root.join("src")).expect("mkdir");
+        std::fs::write(root.join("src/new.rs"), "fn main() {}\n").expect("write");
+        std::fs::write(root.join("scratch.txt"), "not mine\n").expect("write");
+
+        let plan = CommitPlan::new(
+            &root.join("src"),
+            &[
+                "new.rs".to_string(),
+                root.join("README.md").to_string_lossy().into_owned(),
+                root.join("old.txt").to_string_lossy().into_owned(),
+            ],
+        )
+        .await
+        .expect("plan");
+        assert_eq!(
+            plan.changes,
+            HashMap::from([
+                (
+                    root.join("README.md"),
+                    FileChange::Update {
+                        unified_diff: "@@ -1 +1,2 @@\n hello\n+world\n".to_string(),
+                        move_path: None,
+                    },
+                ),
+                (
+                    root.join("old.txt"),
+                    FileChange::Delete {
+                        content: "old\n".to_string(),
+                    },
+                ),
+                (
+                    root.join("src/new.rs"),
+                    FileChange::Add {
+                        content: "fn main() {}\n".to_string(),
+                    },
+                ),
+            ])
+        );
+
+        plan.commit(
+            "Add new.rs",
+            &["Co-authored-by: Codex <codex@openai.com>".to_string()],
+        )
+        .await
+        .expect("commit");
+        assert_eq!(
+            test_git(&root, &["log", "-1", "--format=%B"]).await,
+            "Add new.rs\n\nCo-authored-by: Codex <codex@openai.com>"
+        );
+        assert_eq!(
+            test_git(&root, &["status", "--porcelain"]).await,
+            "?? scratch.txt"
+        );
+    }
+
+    #[tokio::test]
+    async fn planning_requires_paths() {
+        let repo = tempfile::tempdir().expect("repo");
+        test_git(repo.path(), &["init", "-q"]).await;
+
+        let err = CommitPlan::new(repo.path(), &[])
+            .await
+            .expect_err("empty paths");
+        assert_eq!(err.to_string(), "paths must list the files to commit");
+    }
+}
@@ -0,0 +1,195 @@
+//! User-configured commands run around the agent's edits.
+//!
+//! `[[hooks.post_patch]]` commands (formatters, `eslint --fix`, a quick test)
+//! run after every patch the agent applies; `[[hooks.pre_commit]]` commands
+//! run before the `git_commit` tool stages its files, and a failure stops the
+//! commit. Hooks run in the session's working directory outside the sandbox,
+//! like `notify`, since they come from the user's own configuration. Unless a
+//! hook sets `feedback = false`, what it printed is returned to the model
+//! with the tool result.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+
+use tokio::process::Command;
+use tracing::warn;
+use wildmatch::WildMatchPattern;
+
+use crate::config::types::HookConfig;
+use crate::truncate::TruncationPolicy;
+use crate::truncate::formatted_truncate_text;
+
+/// Argument replaced by the affected files.
+const FILES_PLACEHOLDER: &str = "{files}";
+/// Output returned to the model per hook.
+const MAX_FEEDBACK_BYTES: usize = 4 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum HookStatus {
+    Succeeded,
+    Failed(Option<i32>),
+    TimedOut,
+    /// The command could not be started.
+    Error(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct HookRun {
+    pub command: String,
+    pub status: HookStatus,
+    /// Stdout followed by stderr.
+    pub output: String,
+    pub feedback: bool,
+}
+
+impl HookRun {
+    pub(crate) fn succeeded(&self) -> bool {
+        self.status == HookStatus::Succeeded
+    }
+}
+
+/// Run, in order, each hook whose `paths` match one of `files`.
+pub(crate) async fn run_hooks(hooks: &[HookConfig], cwd: &Path, files: &[PathBuf]) -> Vec<HookRun> {
+    let relative: Vec<String> = files
+        .iter()
+        .map(|file| file.strip_prefix(cwd).unwrap_or(file).display().to_string())
+        .collect();
+    let mut runs = Vec::new();
+    for hook in hooks {
+        let Some((program, args)) = hook.command.split_first() else {
+            continue;
+        };
+        let matched: Vec<&str> = relative
+            .iter()
+            .map(String::as_str)
+            .filter(|file| matches_paths(&hook.paths, file))
+            .collect();
+        if matched.is_empty() {
+            continue;
+        }
+        let args: Vec<&str> = args
+            .iter()
+            .flat_map(|arg| {
+                if arg == FILES_PLACEHOLDER {
+                    matched.clone()
+                } else {
+                    vec![arg.as_str()]
+                }
+            })
+            .collect();
+        let command = std::iter::once(program.as_str())
+            .chain(args.iter().copied())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let mut child = Command::new(program);
+        child
+            .args(&args)
+            .current_dir(cwd)
+            .stdin(Stdio::null())
+            .kill_on_drop(true);
+        let (status, output) = match tokio::time::timeout(hook.timeout, child.output()).await {
+            Err(_) => (HookStatus::TimedOut, String::new()),
+            Ok(Err(err)) => (HookStatus::Error(err.to_string()), String::new()),
+            Ok(Ok(output)) => {
+                let status = if output.status.success() {
+                    HookStatus::Succeeded
+                } else {
+                    HookStatus::Failed(output.status.code())
+                };
+                let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+                text.push_str(&String::from_utf8_lossy(&output.stderr));
+                (status, text)
+            }
+        };
+        if status != HookStatus::Succeeded {
+            warn!("hook `{command}` did not succeed: {status:?}");
+        }
+        runs.push(HookRun {
+            command,
+            status,
+            output,
+            feedback: hook.feedback,
+        });
+    }
+    runs
+}
+
+/// What the hooks in `runs` reported, for appending to a tool result. `stage`
+/// names the hook list, e.g. `post_patch`.
+pub(crate) fn hook_report(stage: &str, runs: &[HookRun]) -> Option<String> {
+    let sections: Vec<String> = runs
+        .iter()
+        .filter(|run| run.feedback)
+        .map(|run| {
+            let outcome = match &run.status {
+                HookStatus::Succeeded => "succeeded".to_string(),
+                HookStatus::Failed(Some(code)) => format!("failed with exit code {code}"),
+                HookStatus::Failed(None) => "was killed by a signal".to_string(),
+                HookStatus::TimedOut => "timed out".to_string(),
+                HookStatus::Error(err) => format!("could not start: {err}"),
+            };
+            let output = run.output.trim();
+            if output.is_empty() {
+                format!("{stage} hook `{}` {outcome}.", run.command)
+            } else {
+                format!(
+                    "{stage} hook `{}` {outcome}:\n{}",
+                    run.command,
+                    formatted_truncate_text(output, TruncationPolicy::Bytes(MAX_FEEDBACK_BYTES))
+                )
+            }
+        })
+        .collect();
+    (!sections.is_empty()).then(|| sections.join("\n\n"))
+}
+
+fn matches_paths(patterns: &[String], file: &str) -> bool {
+    patterns.is_empty()
+        || patterns
+            .iter()
+            .any(|pattern| WildMatchPattern::<'*', '?'>::new(pattern).matches(file))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    fn hook(command: &[&str], paths: &[&str]) -> HookConfig {
+        HookConfig {
+            command: command.iter().map(ToString::to_string).collect(),
+            paths: paths.iter().map(ToString::to_string).collect(),
+            timeout: Duration::from_secs(10),
+            feedback: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn runs_matching_hooks_with_their_files() {
+        let cwd = tempfile::tempdir().expect("tempdir");
+        let files = vec![cwd.path().join("src/lib.rs"), cwd.path().join("app.py")];
+        let hooks = vec![
+            hook(&["sh", "-c", "echo fmt \"$@\"", "sh", "{files}"], &["*.rs"]),
+            hook(&["sh", "-c", "echo lint >&2; exit 3"], &[]),
+            hook(&["sh", "-c", "echo never"], &["*.ts"]),
+        ];
+
+        let runs = run_hooks(&hooks, cwd.path(), &files).await;
+        assert_eq!(
+            runs.iter()
+                .map(|run| run.status.clone())
+                .collect::<Vec<_>>(),
+            vec![HookStatus::Succeeded, HookStatus::Failed(Some(3))]
+        );
+        assert_eq!(
+            hook_report("post_patch", &runs).as_deref(),
+            Some(
+                "post_patch hook `sh -c echo fmt \"$@\" sh src/lib.rs` succeeded:\nfmt src/lib.rs\n\n\
+                 post_patch hook `sh -c echo lint >&2; exit 3` failed with exit code 3:\nlint"
+            )
+        );
+    }
+}
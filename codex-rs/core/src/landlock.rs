@@ -1,7 +1,11 @@
+use crate::config::types::LinuxSandboxConfig;
+use crate::exec_limits::ExecLimits;
 use crate::protocol::SandboxPolicy;
+use crate::spawn::SpawnChildRequest;
 use crate::spawn::StdioPolicy;
 use crate::spawn::spawn_child_async;
 use std::collections::HashMap;
+use std::net::SocketAddr;
 use std::path::Path;
 use std::path::PathBuf;
 use tokio::process::Child;
@@ -25,25 +29,39 @@ pub async fn spawn_command_under_linux_sandbox<P>(
 where
     P: AsRef<Path>,
 {
-    let args = create_linux_sandbox_command_args(command, sandbox_policy, sandbox_policy_cwd);
+    let args = create_linux_sandbox_command_args(
+        command,
+        sandbox_policy,
+        sandbox_policy_cwd,
+        &[],
+        None,
+        &LinuxSandboxConfig::default(),
+    );
     let arg0 = Some("codex-linux-sandbox");
-    spawn_child_async(
-        codex_linux_sandbox_exe.as_ref().to_path_buf(),
+    spawn_child_async(SpawnChildRequest {
+        program: codex_linux_sandbox_exe.as_ref().to_path_buf(),
         args,
         arg0,
-        command_cwd,
+        cwd: command_cwd,
         sandbox_policy,
         stdio_policy,
         env,
-    )
+        limits: ExecLimits::default(),
+    })
     .await
 }
 
 /// Converts the sandbox policy into the CLI invocation for `codex-linux-sandbox`.
+/// Each of `read_only_paths` is passed as `--read-only-path`, and
+/// `network_proxy` as `--network-proxy-addr`. Non-default `[linux_sandbox]`
+/// settings are passed as JSON.
 pub(crate) fn create_linux_sandbox_command_args(
     command: Vec<String>,
     sandbox_policy: &SandboxPolicy,
     sandbox_policy_cwd: &Path,
+    read_only_paths: &[PathBuf],
+    network_proxy: Option<SocketAddr>,
+    linux_sandbox: &LinuxSandboxConfig,
 ) -> Vec<String> {
     #[expect(clippy::expect_used)]
     let sandbox_policy_cwd = sandbox_policy_cwd
@@ -60,13 +78,70 @@ pub(crate) fn create_linux_sandbox_command_args(
         sandbox_policy_cwd,
         "--sandbox-policy".to_string(),
         sandbox_policy_json,
-        // Separator so that command arguments starting with `-` are not parsed as
-        // options of the helper itself.
-        "--".to_string(),
     ];
+    for path in read_only_paths {
+        linux_cmd.push("--read-only-path".to_string());
+        linux_cmd.push(path.to_string_lossy().into_owned());
+    }
+    if let Some(proxy) = network_proxy {
+        linux_cmd.push("--network-proxy-addr".to_string());
+        linux_cmd.push(proxy.to_string());
+    }
+    if *linux_sandbox != LinuxSandboxConfig::default() {
+        #[expect(clippy::expect_used)]
+        let linux_sandbox_json = serde_json::to_string(linux_sandbox)
+            .expect("Failed to serialize LinuxSandboxConfig to JSON");
+        linux_cmd.push("--linux-sandbox-config".to_string());
+        linux_cmd.push(linux_sandbox_json);
+    }
+    // Separator so that command arguments starting with `-` are not parsed as
+    // options of the helper itself.
+    linux_cmd.push("--".to_string());
 
     // Append the original tool command.
     linux_cmd.extend(command);
 
     linux_cmd
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::types::SyscallClass;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn linux_sandbox_config_is_passed_only_when_set() {
+        let policy = SandboxPolicy::new_read_only_policy();
+        let cwd = Path::new("/tmp");
+        let command = vec!["true".to_string()];
+
+        let args = create_linux_sandbox_command_args(
+            command.clone(),
+            &policy,
+            cwd,
+            &[],
+            None,
+            &LinuxSandboxConfig::default(),
+        );
+        assert!(!args.contains(&"--linux-sandbox-config".to_string()));
+
+        let linux_sandbox = LinuxSandboxConfig {
+            blocked_syscalls: vec![SyscallClass::Mount, SyscallClass::Bpf],
+            deny_ptrace: true,
+            ..Default::default()
+        };
+        let args =
+            create_linux_sandbox_command_args(command, &policy, cwd, &[], None, &linux_sandbox);
+        let index = args
+            .iter()
+            .position(|arg| arg == "--linux-sandbox-config")
+            .expect("flag present");
+        assert_eq!(
+            args[index + 1]
+                .parse::<LinuxSandboxConfig>()
+                .expect("parses"),
+            linux_sandbox
+        );
+    }
+}
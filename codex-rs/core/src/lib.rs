@@ -8,8 +8,11 @@
 mod analytics_client;
 pub mod api_bridge;
 mod apply_patch;
+mod approval_rules;
+mod attribution;
 pub mod auth;
 pub mod bash;
+pub mod batch;
 mod client;
 mod client_common;
 pub mod codex;
@@ -18,24 +21,33 @@ mod compact_remote;
 pub use codex_thread::CodexThread;
 pub use codex_thread::ThreadConfigSnapshot;
 mod agent;
+pub mod code_index;
 mod codex_delegate;
 mod command_safety;
 pub mod config;
 pub mod config_loader;
 pub mod connectors;
+mod container;
 mod context_manager;
+mod credentials;
 pub mod custom_prompts;
 pub mod env;
 mod environment_context;
 pub mod error;
 pub mod exec;
 pub mod exec_env;
+mod exec_limits;
 mod exec_policy;
+mod external_edits;
 pub mod features;
+mod fetch_url;
 mod flags;
+mod git_commit;
 pub mod git_info;
+mod hooks;
 pub mod instructions;
 pub mod landlock;
+mod lsp;
 pub mod mcp;
 mod mcp_connection_manager;
 pub mod models_manager;
@@ -52,9 +64,11 @@ pub mod path_utils;
 pub mod personality_migration;
 pub mod powershell;
 mod proposed_plan_parser;
+pub mod sandbox_check;
 pub mod sandboxing;
 mod session_prefix;
 mod stream_events_utils;
+mod syntax_outline;
 mod tagged_block_parser;
 mod text_encoding;
 pub mod token_data;
@@ -89,25 +103,43 @@ pub type CodexConversation = CodexThread;
 pub use auth::AuthManager;
 pub use auth::CodexAuth;
 pub mod default_client;
+mod output_store;
+mod patch_limits;
+mod patch_undo;
+mod persistent_shell;
 pub mod project_doc;
+pub mod project_memory;
+mod pull_request;
+pub mod redaction;
+mod remote_exec;
+mod repo_map;
 mod rollout;
+pub mod run_budget;
 pub(crate) mod safety;
 pub mod seatbelt;
+pub mod session_worktree;
 pub mod shell;
 pub mod shell_snapshot;
 pub mod skills;
 pub mod spawn;
 pub mod state_db;
+mod structured_edit;
 pub mod terminal;
 mod tools;
+mod trash;
 pub mod turn_diff_tracker;
 mod turn_metadata;
+pub mod workers;
+mod worktree;
+mod write_protected;
 pub use rollout::ARCHIVED_SESSIONS_SUBDIR;
 pub use rollout::INTERACTIVE_SESSION_SOURCES;
 pub use rollout::RolloutRecorder;
 pub use rollout::RolloutRecorderParams;
 pub use rollout::SESSIONS_SUBDIR;
 pub use rollout::SessionMeta;
+pub use rollout::encryption::SESSION_PASSPHRASE_ENV_VAR;
+pub use rollout::encryption::decode_rollout_line;
 pub use rollout::find_archived_thread_path_by_id_str;
 #[deprecated(note = "use find_thread_path_by_id_str")]
 pub use rollout::find_conversation_path_by_id_str;
@@ -120,8 +152,17 @@ pub use rollout::list::ThreadsPage;
 pub use rollout::list::parse_cursor;
 pub use rollout::list::read_head_for_summary;
 pub use rollout::list::read_session_meta_line;
+pub use rollout::retention::PruneReport;
+pub use rollout::retention::prune_sessions;
 pub use rollout::rollout_date_parts;
 pub use rollout::session_index::find_thread_names_by_ids;
+pub use rollout::session_summary::SessionSummary;
+pub use rollout::session_summary::find_session_summaries_by_ids;
+pub use rollout::session_summary::summarize_rollout_file;
+pub use rollout::transcript::TranscriptEntry;
+pub use rollout::transcript::TranscriptRole;
+pub use rollout::transcript::read_session_transcript;
+pub use rollout::transcript::transcript_to_markdown;
 pub use transport_manager::TransportManager;
 mod function_tool;
 mod state;
@@ -133,6 +174,7 @@ pub mod util;
 pub use apply_patch::CODEX_APPLY_PATCH_ARG1;
 pub use client::WEB_SEARCH_ELIGIBLE_HEADER;
 pub use client::X_CODEX_TURN_METADATA_HEADER;
+pub use command_safety::command_risk;
 pub use command_safety::is_dangerous_command;
 pub use command_safety::is_safe_command;
 pub use exec_policy::ExecPolicyError;
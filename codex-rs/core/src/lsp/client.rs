@@ -0,0 +1,407 @@
+//! A minimal LSP client: JSON-RPC over a language server's stdio, with only
+//! what is needed to open documents and collect published diagnostics.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::sync::atomic::AtomicI64;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde_json::Value;
+use serde_json::json;
+use tokio::io::AsyncBufRead;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWrite;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+use tokio::process::Child;
+use tokio::process::ChildStdin;
+use tokio::process::Command;
+use tokio::sync::Mutex;
+use tokio::sync::Notify;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tracing::debug;
+use tracing::warn;
+
+use crate::config::types::LspServerToml;
+
+/// How long a server may take to answer `initialize`.
+const INITIALIZE_TIMEOUT: Duration = Duration::from_secs(30);
+
+type PendingRequests = HashMap<i64, oneshot::Sender<Result<Value, String>>>;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub(crate) struct Position {
+    pub line: u32,
+    pub character: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub(crate) struct Range {
+    pub start: Position,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub(crate) struct Diagnostic {
+    pub range: Range,
+    /// 1 error, 2 warning, 3 information, 4 hint; missing means error.
+    #[serde(default)]
+    pub severity: Option<u8>,
+    pub message: String,
+    #[serde(default)]
+    pub source: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PublishDiagnosticsParams {
+    uri: String,
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// The latest diagnostics a server published for one document.
+#[derive(Debug, Clone)]
+struct Published {
+    /// Value of the client-wide publish counter when these arrived.
+    generation: u64,
+    diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Default)]
+struct Shared {
+    pending: Mutex<PendingRequests>,
+    published: Mutex<HashMap<String, Published>>,
+    generation: AtomicU64,
+    published_notify: Notify,
+}
+
+pub(crate) struct LspClient {
+    name: String,
+    stdin: Arc<Mutex<ChildStdin>>,
+    shared: Arc<Shared>,
+    next_id: AtomicI64,
+    /// Open documents and their current version.
+    documents: Mutex<HashMap<String, i32>>,
+    reader: JoinHandle<()>,
+    _child: Child,
+}
+
+impl LspClient {
+    /// Spawn the server and complete the `initialize` handshake for `root`.
+    pub(crate) async fn start(name: &str, server: &LspServerToml, root: &Path) -> io::Result<Self> {
+        let mut child = Command::new(&server.command)
+            .args(&server.args)
+            .current_dir(root)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|err| {
+                io::Error::other(format!("failed to start `{}`: {err}", server.command))
+            })?;
+        let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            return Err(io::Error::other("language server stdio is not piped"));
+        };
+        let stdin = Arc::new(Mutex::new(stdin));
+        let shared = Arc::new(Shared::default());
+        let reader = tokio::spawn(read_loop(
+            name.to_string(),
+            BufReader::new(stdout),
+            Arc::clone(&stdin),
+            Arc::clone(&shared),
+        ));
+        let client = Self {
+            name: name.to_string(),
+            stdin,
+            shared,
+            next_id: AtomicI64::new(1),
+            documents: Mutex::new(HashMap::new()),
+            reader,
+            _child: child,
+        };
+
+        let root_uri = file_uri(root)?;
+        let params = json!({
+            "processId": std::process::id(),
+            "clientInfo": { "name": "codex" },
+            "rootUri": root_uri,
+            "workspaceFolders": [{ "uri": root_uri, "name": root_name(root) }],
+            "capabilities": {
+                "textDocument": {
+                    "synchronization": { "didSave": true },
+                    "publishDiagnostics": { "relatedInformation": false },
+                },
+                "workspace": { "workspaceFolders": true, "configuration": true },
+            },
+            "initializationOptions": server.initialization_options,
+        });
+        tokio::time::timeout(INITIALIZE_TIMEOUT, client.request("initialize", params))
+            .await
+            .map_err(|_| io::Error::other(format!("`{name}` did not answer initialize")))??;
+        client.notify("initialized", json!({})).await?;
+        Ok(client)
+    }
+
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Open or update `path` with `text` and save it, so the server
+    /// re-checks it. Returns the publish counter to pass to
+    /// [`Self::diagnostics_since`].
+    pub(crate) async fn sync_document(
+        &self,
+        path: &Path,
+        language_id: &str,
+        text: &str,
+    ) -> io::Result<u64> {
+        let uri = file_uri(path)?;
+        let generation = self.shared.generation.load(Ordering::SeqCst);
+        let mut documents = self.documents.lock().await;
+        match documents.get_mut(&uri) {
+            Some(version) => {
+                *version += 1;
+                let params = json!({
+                    "textDocument": { "uri": uri, "version": *version },
+                    "contentChanges": [{ "text": text }],
+                });
+                self.notify("textDocument/didChange", params).await?;
+            }
+            None => {
+                let params = json!({
+                    "textDocument": {
+                        "uri": uri,
+                        "languageId": language_id,
+                        "version": 1,
+                        "text": text,
+                    },
+                });
+                self.notify("textDocument/didOpen", params).await?;
+                documents.insert(uri.clone(), 1);
+            }
+        }
+        drop(documents);
+        let params = json!({ "textDocument": { "uri": uri }, "text": text });
+        self.notify("textDocument/didSave", params).await?;
+        Ok(generation)
+    }
+
+    /// Diagnostics for `path` published after `generation`. Once the first
+    /// batch arrives, later batches within `settle` replace it, since servers
+    /// often report syntax errors before semantic ones. `None` when nothing
+    /// arrives before `deadline`.
+    pub(crate) async fn diagnostics_since(
+        &self,
+        path: &Path,
+        generation: u64,
+        settle: Duration,
+        deadline: tokio::time::Instant,
+    ) -> Option<Vec<Diagnostic>> {
+        let uri = file_uri(path).ok()?;
+        let mut latest: Option<Published> = None;
+        loop {
+            let notified = self.shared.published_notify.notified();
+            if let Some(published) = self.shared.published.lock().await.get(&uri)
+                && published.generation > latest.as_ref().map_or(generation, |p| p.generation)
+            {
+                latest = Some(published.clone());
+            }
+            let wait_until = match &latest {
+                Some(_) => (tokio::time::Instant::now() + settle).min(deadline),
+                None => deadline,
+            };
+            if tokio::time::timeout_at(wait_until, notified).await.is_err() {
+                return latest.map(|published| published.diagnostics);
+            }
+        }
+    }
+
+    async fn request(&self, method: &str, params: Value) -> io::Result<Value> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let (tx, rx) = oneshot::channel();
+        self.shared.pending.lock().await.insert(id, tx);
+        let message = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+        write_message(&mut *self.stdin.lock().await, &message).await?;
+        match rx.await {
+            Ok(Ok(result)) => Ok(result),
+            Ok(Err(err)) => Err(io::Error::other(format!(
+                "`{}` failed {method}: {err}",
+                self.name
+            ))),
+            Err(_) => Err(io::Error::other(format!("`{}` exited", self.name))),
+        }
+    }
+
+    async fn notify(&self, method: &str, params: Value) -> io::Result<()> {
+        let message = json!({ "jsonrpc": "2.0", "method": method, "params": params });
+        write_message(&mut *self.stdin.lock().await, &message).await
+    }
+}
+
+impl Drop for LspClient {
+    fn drop(&mut self) {
+        self.reader.abort();
+    }
+}
+
+async fn read_loop<R: AsyncBufRead + Unpin>(
+    name: String,
+    mut reader: R,
+    stdin: Arc<Mutex<ChildStdin>>,
+    shared: Arc<Shared>,
+) {
+    loop {
+        let message = match read_message(&mut reader).await {
+            Ok(Some(message)) => message,
+            Ok(None) => break,
+            Err(err) => {
+                warn!("language server `{name}`: {err}");
+                break;
+            }
+        };
+        let method = message.get("method").and_then(Value::as_str);
+        let id = message.get("id").cloned();
+        match (method, id) {
+            (Some("textDocument/publishDiagnostics"), None) => {
+                let Some(params) = message.get("params").cloned().and_then(|params| {
+                    serde_json::from_value::<PublishDiagnosticsParams>(params).ok()
+                }) else {
+                    continue;
+                };
+                let generation = shared.generation.fetch_add(1, Ordering::SeqCst) + 1;
+                shared.published.lock().await.insert(
+                    params.uri,
+                    Published {
+                        generation,
+                        diagnostics: params.diagnostics,
+                    },
+                );
+                shared.published_notify.notify_waiters();
+            }
+            (Some(method), Some(id)) => {
+                // Requests from the server (configuration, progress, dynamic
+                // registration) get an empty answer so it does not stall.
+                let result = if method == "workspace/configuration" {
+                    let items = message
+                        .pointer("/params/items")
+                        .and_then(Value::as_array)
+                        .map_or(0, Vec::len);
+                    Value::Array(vec![Value::Null; items])
+                } else {
+                    Value::Null
+                };
+                let reply = json!({ "jsonrpc": "2.0", "id": id, "result": result });
+                if let Err(err) = write_message(&mut *stdin.lock().await, &reply).await {
+                    warn!("language server `{name}`: {err}");
+                    break;
+                }
+            }
+            (None, Some(id)) => {
+                let Some(id) = id.as_i64() else {
+                    continue;
+                };
+                let Some(tx) = shared.pending.lock().await.remove(&id) else {
+                    continue;
+                };
+                let result = match message.get("error") {
+                    Some(error) => Err(error
+                        .get("message")
+                        .and_then(Value::as_str)
+                        .unwrap_or("unknown error")
+                        .to_string()),
+                    None => Ok(message.get("result").cloned().unwrap_or(Value::Null)),
+                };
+                let _ = tx.send(result);
+            }
+            (Some(method), None) => debug!("language server `{name}` sent {method}"),
+            (None, None) => {}
+        }
+    }
+    // Fail outstanding requests instead of leaving them waiting.
+    shared.pending.lock().await.clear();
+}
+
+/// Read one `Content-Length`-framed message. `None` at end of stream.
+async fn read_message<R: AsyncBufRead + Unpin>(reader: &mut R) -> io::Result<Option<Value>> {
+    let mut content_length = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).await? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            content_length = value.trim().parse::<usize>().ok();
+        }
+    }
+    let Some(length) = content_length else {
+        return Err(io::Error::other("message without Content-Length"));
+    };
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).await?;
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(io::Error::other)
+}
+
+async fn write_message<W: AsyncWrite + Unpin>(writer: &mut W, message: &Value) -> io::Result<()> {
+    let body = serde_json::to_vec(message).map_err(io::Error::other)?;
+    writer
+        .write_all(format!("Content-Length: {}\r\n\r\n", body.len()).as_bytes())
+        .await?;
+    writer.write_all(&body).await?;
+    writer.flush().await
+}
+
+pub(crate) fn file_uri(path: &Path) -> io::Result<String> {
+    url::Url::from_file_path(path)
+        .map(String::from)
+        .map_err(|()| io::Error::other(format!("{} is not an absolute path", path.display())))
+}
+
+fn root_name(root: &Path) -> String {
+    root.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| root.display().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn frames_round_trip() {
+        let first = json!({ "jsonrpc": "2.0", "id": 1, "result": null });
+        let second = json!({ "jsonrpc": "2.0", "method": "initialized", "params": {} });
+        let mut buffer = Vec::new();
+        write_message(&mut buffer, &first).await.expect("write");
+        // Headers are case-insensitive and may include a content type.
+        let body = serde_json::to_vec(&second).expect("serialize");
+        buffer.extend_from_slice(
+            format!(
+                "content-length: {}\r\nContent-Type: application/vscode-jsonrpc; charset=utf-8\r\n\r\n",
+                body.len()
+            )
+            .as_bytes(),
+        );
+        buffer.extend_from_slice(&body);
+
+        let mut reader = BufReader::new(buffer.as_slice());
+        assert_eq!(read_message(&mut reader).await.expect("read"), Some(first));
+        assert_eq!(read_message(&mut reader).await.expect("read"), Some(second));
+        assert_eq!(read_message(&mut reader).await.expect("read"), None);
+    }
+}
@@ -0,0 +1,242 @@
+//! Diagnostics from language servers after the agent edits files.
+//!
+//! Servers configured under `[lsp.servers]` are started on demand, one per
+//! server and workspace root, the first time a patch touches a file with one
+//! of their extensions. After each applied patch the edited files are synced
+//! to their servers and the errors and warnings published within
+//! `diagnostics_timeout_ms` are appended to the patch result, so the model can
+//! fix what it broke without running a full build.
+
+mod client;
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::Mutex;
+use tracing::warn;
+
+use crate::config::types::LspConfig;
+use crate::git_info::get_git_repo_root;
+use client::Diagnostic;
+use client::LspClient;
+
+/// Quiet period after a publish before its diagnostics are taken as final.
+const SETTLE: Duration = Duration::from_millis(300);
+/// Maximum number of diagnostics listed after one patch.
+const MAX_DIAGNOSTICS: usize = 50;
+
+#[derive(Default)]
+pub(crate) struct LspManager {
+    config: LspConfig,
+    /// Keyed by server name and workspace root.
+    clients: Mutex<HashMap<(String, PathBuf), Arc<LspClient>>>,
+    /// Servers that failed to start; they are not retried this session.
+    failed: Mutex<HashSet<(String, PathBuf)>>,
+}
+
+impl LspManager {
+    pub(crate) fn new(config: LspConfig) -> Self {
+        Self {
+            config,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        !self.config.servers.is_empty()
+    }
+
+    /// Sync `paths` (absolute, already written) to their servers and return
+    /// the reported errors and warnings, formatted for the model. `None` when
+    /// no configured server handles the files or none answered in time.
+    pub(crate) async fn diagnostics_after_edit(
+        &self,
+        paths: &[PathBuf],
+        cwd: &Path,
+    ) -> Option<String> {
+        let deadline = tokio::time::Instant::now() + self.config.diagnostics_timeout;
+        let mut synced = Vec::new();
+        for path in paths {
+            let Some((name, extension)) = self.server_for(path) else {
+                continue;
+            };
+            let root = get_git_repo_root(path.parent().unwrap_or(cwd))
+                .unwrap_or_else(|| cwd.to_path_buf());
+            let Some(client) = self.client(&name, &root).await else {
+                continue;
+            };
+            let Ok(text) = tokio::fs::read_to_string(path).await else {
+                continue;
+            };
+            match client
+                .sync_document(path, language_id(&extension), &text)
+                .await
+            {
+                Ok(generation) => synced.push((path, client, generation)),
+                Err(err) => warn!("failed to sync {} to `{name}`: {err}", path.display()),
+            }
+        }
+        if synced.is_empty() {
+            return None;
+        }
+
+        let mut reports = Vec::new();
+        for (path, client, generation) in synced {
+            if let Some(diagnostics) = client
+                .diagnostics_since(path, generation, SETTLE, deadline)
+                .await
+            {
+                reports.push((path.as_path(), client.name().to_string(), diagnostics));
+            }
+        }
+        if reports.is_empty() {
+            return None;
+        }
+        Some(format_diagnostics(&reports, cwd))
+    }
+
+    /// The first configured server, by name, that handles `path`'s extension.
+    fn server_for(&self, path: &Path) -> Option<(String, String)> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        self.config
+            .servers
+            .iter()
+            .find(|(_, server)| {
+                server.extensions.iter().any(|candidate| {
+                    candidate
+                        .trim_start_matches('.')
+                        .eq_ignore_ascii_case(&extension)
+                })
+            })
+            .map(|(name, _)| (name.clone(), extension))
+    }
+
+    async fn client(&self, name: &str, root: &Path) -> Option<Arc<LspClient>> {
+        let key = (name.to_string(), root.to_path_buf());
+        if self.failed.lock().await.contains(&key) {
+            return None;
+        }
+        let mut clients = self.clients.lock().await;
+        if let Some(client) = clients.get(&key) {
+            return Some(Arc::clone(client));
+        }
+        let server = self.config.servers.get(name)?;
+        match LspClient::start(name, server, root).await {
+            Ok(client) => {
+                let client = Arc::new(client);
+                clients.insert(key, Arc::clone(&client));
+                Some(client)
+            }
+            Err(err) => {
+                warn!(
+                    "language server `{name}` unavailable for {}: {err}",
+                    root.display()
+                );
+                self.failed.lock().await.insert(key);
+                None
+            }
+        }
+    }
+}
+
+/// The LSP `languageId` for a file extension.
+fn language_id(extension: &str) -> &str {
+    match extension {
+        "rs" => "rust",
+        "py" | "pyi" => "python",
+        "js" | "mjs" | "cjs" => "javascript",
+        "jsx" => "javascriptreact",
+        "ts" | "mts" | "cts" => "typescript",
+        "tsx" => "typescriptreact",
+        "go" => "go",
+        "c" | "h" => "c",
+        "cc" | "cpp" | "cxx" | "hpp" | "hh" => "cpp",
+        "java" => "java",
+        "rb" => "ruby",
+        "cs" => "csharp",
+        "sh" | "bash" => "shellscript",
+        other => other,
+    }
+}
+
+fn format_diagnostics(reports: &[(&Path, String, Vec<Diagnostic>)], cwd: &Path) -> String {
+    let mut lines = Vec::new();
+    let mut total = 0;
+    for (path, server, diagnostics) in reports {
+        let display = path.strip_prefix(cwd).unwrap_or(path).display();
+        for diagnostic in diagnostics {
+            let severity = match diagnostic.severity {
+                Some(1) | None => "error",
+                Some(2) => "warning",
+                _ => continue,
+            };
+            total += 1;
+            if lines.len() < MAX_DIAGNOSTICS {
+                let source = diagnostic.source.as_deref().unwrap_or(server);
+                lines.push(format!(
+                    "{display}:{}:{}: {severity} ({source}): {}",
+                    diagnostic.range.start.line + 1,
+                    diagnostic.range.start.character + 1,
+                    diagnostic.message.lines().next().unwrap_or_default()
+                ));
+            }
+        }
+    }
+    if total == 0 {
+        return "Language servers report no errors or warnings in the edited files.".to_string();
+    }
+    let mut text = format!("Language servers report {total} problem(s) after this edit:\n");
+    text.push_str(&lines.join("\n"));
+    if total > lines.len() {
+        text.push_str(&format!("\n... and {} more", total - lines.len()));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn formats_errors_and_warnings_only() {
+        let diagnostics: Vec<Diagnostic> = serde_json::from_value(serde_json::json!([
+            {
+                "range": { "start": { "line": 11, "character": 4 }, "end": { "line": 11, "character": 9 } },
+                "severity": 1,
+                "source": "rustc",
+                "message": "mismatched types\nexpected `i32`, found `&str`"
+            },
+            {
+                "range": { "start": { "line": 2, "character": 0 }, "end": { "line": 2, "character": 3 } },
+                "severity": 2,
+                "message": "unused import"
+            },
+            {
+                "range": { "start": { "line": 5, "character": 0 }, "end": { "line": 5, "character": 1 } },
+                "severity": 4,
+                "message": "consider renaming"
+            }
+        ]))
+        .expect("diagnostics");
+        let cwd = Path::new("/repo");
+        let path = Path::new("/repo/src/lib.rs");
+        let reports = vec![(path, "rust-analyzer".to_string(), diagnostics)];
+
+        assert_eq!(
+            format_diagnostics(&reports, cwd),
+            "Language servers report 2 problem(s) after this edit:\n\
+             src/lib.rs:12:5: error (rustc): mismatched types\n\
+             src/lib.rs:3:1: warning (rust-analyzer): unused import"
+        );
+        let clean = vec![(path, "rust-analyzer".to_string(), Vec::new())];
+        assert_eq!(
+            format_diagnostics(&clean, cwd),
+            "Language servers report no errors or warnings in the edited files."
+        );
+    }
+}
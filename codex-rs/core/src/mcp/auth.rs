@@ -109,5 +109,15 @@ async fn compute_auth_status(
             )
             .await
         }
+        // Only bearer tokens are supported over SSE; OAuth login needs
+        // streamable HTTP.
+        McpServerTransportConfig::Sse {
+            bearer_token_env_var,
+            ..
+        } => Ok(if bearer_token_env_var.is_some() {
+            McpAuthStatus::BearerToken
+        } else {
+            McpAuthStatus::Unsupported
+        }),
     }
 }
@@ -0,0 +1,351 @@
+//! `@server:target` mentions of MCP resources and prompts.
+//!
+//! Clients send a mention as a [`UserInput::Mention`] whose path names a
+//! resource (`mcp://server/uri`) or a prompt (`mcp-prompt://server/name`).
+//! At the start of the turn each one is read from its server and recorded
+//! as a context message, the same way mentioned skills are. The contents are
+//! truncated with the model's tool output policy, so a large resource cannot
+//! fill the context window.
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::user_input::UserInput;
+use mcp_types::BlobResourceContents;
+use mcp_types::ContentBlock;
+use mcp_types::EmbeddedResourceResource;
+use mcp_types::GetPromptRequestParams;
+use mcp_types::GetPromptResult;
+use mcp_types::ReadResourceRequestParams;
+use mcp_types::ReadResourceResult;
+use mcp_types::ReadResourceResultContents;
+use mcp_types::Role;
+
+use crate::mcp_connection_manager::McpConnectionManager;
+use crate::truncate::TruncationPolicy;
+use crate::truncate::formatted_truncate_text;
+
+pub const MCP_RESOURCE_MENTION_SCHEME: &str = "mcp://";
+pub const MCP_PROMPT_MENTION_SCHEME: &str = "mcp-prompt://";
+
+const MCP_RESOURCE_OPEN_TAG: &str = "<mcp_resource>";
+const MCP_PROMPT_OPEN_TAG: &str = "<mcp_prompt>";
+
+/// Mention path for the resource `uri` on `server`.
+pub fn mcp_resource_mention_path(server: &str, uri: &str) -> String {
+    format!("{MCP_RESOURCE_MENTION_SCHEME}{server}/{uri}")
+}
+
+/// Mention path for the prompt `name` on `server`.
+pub fn mcp_prompt_mention_path(server: &str, name: &str) -> String {
+    format!("{MCP_PROMPT_MENTION_SCHEME}{server}/{name}")
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum McpMention {
+    Resource { server: String, uri: String },
+    Prompt { server: String, name: String },
+}
+
+impl McpMention {
+    fn parse(path: &str) -> Option<Self> {
+        if let Some(rest) = path.strip_prefix(MCP_RESOURCE_MENTION_SCHEME) {
+            let (server, uri) = split_server(rest)?;
+            return Some(Self::Resource { server, uri });
+        }
+        let rest = path.strip_prefix(MCP_PROMPT_MENTION_SCHEME)?;
+        let (server, name) = split_server(rest)?;
+        Some(Self::Prompt { server, name })
+    }
+}
+
+fn split_server(rest: &str) -> Option<(String, String)> {
+    let (server, target) = rest.split_once('/')?;
+    if server.is_empty() || target.is_empty() {
+        return None;
+    }
+    Some((server.to_string(), target.to_string()))
+}
+
+/// MCP resources and prompts mentioned in `input`, without duplicates.
+pub(crate) fn collect_mcp_mentions(input: &[UserInput]) -> Vec<McpMention> {
+    let mut mentions: Vec<McpMention> = Vec::new();
+    for item in input {
+        if let UserInput::Mention { path, .. } = item
+            && let Some(mention) = McpMention::parse(path)
+            && !mentions.contains(&mention)
+        {
+            mentions.push(mention);
+        }
+    }
+    mentions
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct McpMentionInjections {
+    pub(crate) items: Vec<ResponseItem>,
+    pub(crate) warnings: Vec<String>,
+}
+
+/// Read each mentioned resource or prompt into a context message. Mentions
+/// that cannot be read become warnings instead.
+pub(crate) async fn build_mcp_mention_injections(
+    manager: &McpConnectionManager,
+    mentions: &[McpMention],
+    truncation_policy: TruncationPolicy,
+) -> McpMentionInjections {
+    let mut result = McpMentionInjections::default();
+    for mention in mentions {
+        match mention {
+            McpMention::Resource { server, uri } => {
+                let params = ReadResourceRequestParams { uri: uri.clone() };
+                match manager.read_resource(server, params).await {
+                    Ok(contents) => result.items.push(resource_message(
+                        server,
+                        uri,
+                        &contents,
+                        truncation_policy,
+                    )),
+                    Err(err) => result.warnings.push(format!(
+                        "Failed to read MCP resource {uri} from {server}: {err:#}"
+                    )),
+                }
+            }
+            McpMention::Prompt { server, name } => {
+                let params = GetPromptRequestParams {
+                    arguments: None,
+                    name: name.clone(),
+                };
+                match manager.get_prompt(server, params).await {
+                    Ok(prompt) => {
+                        result
+                            .items
+                            .push(prompt_message(server, name, &prompt, truncation_policy))
+                    }
+                    Err(err) => result.warnings.push(format!(
+                        "Failed to get MCP prompt {name} from {server}: {err:#}"
+                    )),
+                }
+            }
+        }
+    }
+    result
+}
+
+/// Whether `message` is a resource or prompt injected by
+/// [`build_mcp_mention_injections`] rather than something the user typed.
+pub(crate) fn is_mcp_mention_contents(message: &[ContentItem]) -> bool {
+    if let [ContentItem::InputText { text }] = message {
+        text.starts_with(MCP_RESOURCE_OPEN_TAG) || text.starts_with(MCP_PROMPT_OPEN_TAG)
+    } else {
+        false
+    }
+}
+
+fn resource_message(
+    server: &str,
+    uri: &str,
+    result: &ReadResourceResult,
+    truncation_policy: TruncationPolicy,
+) -> ResponseItem {
+    let body = result
+        .contents
+        .iter()
+        .map(|contents| match contents {
+            ReadResourceResultContents::TextResourceContents(text) => text.text.clone(),
+            ReadResourceResultContents::BlobResourceContents(blob) => blob_placeholder(blob),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let body = formatted_truncate_text(&body, truncation_policy);
+    context_message(format!(
+        "{MCP_RESOURCE_OPEN_TAG}\n<server>{server}</server>\n<uri>{uri}</uri>\n{body}\n</mcp_resource>"
+    ))
+}
+
+fn prompt_message(
+    server: &str,
+    name: &str,
+    result: &GetPromptResult,
+    truncation_policy: TruncationPolicy,
+) -> ResponseItem {
+    let body = result
+        .messages
+        .iter()
+        .map(|message| {
+            let role = match message.role {
+                Role::Assistant => "assistant",
+                Role::User => "user",
+            };
+            let text = match &message.content {
+                ContentBlock::TextContent(text) => text.text.clone(),
+                ContentBlock::EmbeddedResource(embedded) => match &embedded.resource {
+                    EmbeddedResourceResource::TextResourceContents(text) => text.text.clone(),
+                    EmbeddedResourceResource::BlobResourceContents(blob) => blob_placeholder(blob),
+                },
+                ContentBlock::ResourceLink(link) => format!("[resource: {}]", link.uri),
+                ContentBlock::ImageContent(_) => "[image omitted]".to_string(),
+                ContentBlock::AudioContent(_) => "[audio omitted]".to_string(),
+            };
+            format!("{role}: {text}")
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let body = formatted_truncate_text(&body, truncation_policy);
+    context_message(format!(
+        "{MCP_PROMPT_OPEN_TAG}\n<server>{server}</server>\n<name>{name}</name>\n{body}\n</mcp_prompt>"
+    ))
+}
+
+fn blob_placeholder(blob: &BlobResourceContents) -> String {
+    let mime_type = blob
+        .mime_type
+        .as_deref()
+        .unwrap_or("application/octet-stream");
+    format!("[binary content ({mime_type}) omitted]")
+}
+
+fn context_message(text: String) -> ResponseItem {
+    ResponseItem::Message {
+        id: None,
+        role: "user".to_string(),
+        content: vec![ContentItem::InputText { text }],
+        end_turn: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcp_types::PromptMessage;
+    use mcp_types::TextContent;
+    use mcp_types::TextResourceContents;
+    use pretty_assertions::assert_eq;
+
+    fn text_of(item: &ResponseItem) -> &str {
+        let ResponseItem::Message { content, .. } = item else {
+            panic!("expected a message");
+        };
+        let [ContentItem::InputText { text }] = content.as_slice() else {
+            panic!("expected one text item");
+        };
+        text
+    }
+
+    #[test]
+    fn mention_paths_round_trip() {
+        let input = vec![
+            UserInput::Mention {
+                name: "file:///notes.md".to_string(),
+                path: mcp_resource_mention_path("docs", "file:///notes.md"),
+            },
+            UserInput::Mention {
+                name: "review".to_string(),
+                path: mcp_prompt_mention_path("docs", "review"),
+            },
+            UserInput::Mention {
+                name: "again".to_string(),
+                path: "mcp://docs/file:///notes.md".to_string(),
+            },
+            UserInput::Mention {
+                name: "calendar".to_string(),
+                path: "app://calendar".to_string(),
+            },
+        ];
+
+        assert_eq!(
+            collect_mcp_mentions(&input),
+            vec![
+                McpMention::Resource {
+                    server: "docs".to_string(),
+                    uri: "file:///notes.md".to_string(),
+                },
+                McpMention::Prompt {
+                    server: "docs".to_string(),
+                    name: "review".to_string(),
+                },
+            ]
+        );
+        assert_eq!(McpMention::parse("mcp://docs/"), None);
+    }
+
+    #[test]
+    fn resources_and_prompts_render_as_hidden_context() {
+        let resource = resource_message(
+            "docs",
+            "file:///notes.md",
+            &ReadResourceResult {
+                contents: vec![
+                    ReadResourceResultContents::TextResourceContents(TextResourceContents {
+                        mime_type: None,
+                        text: "# Notes".to_string(),
+                        uri: "file:///notes.md".to_string(),
+                    }),
+                    ReadResourceResultContents::BlobResourceContents(BlobResourceContents {
+                        blob: "AAAA".to_string(),
+                        mime_type: Some("image/png".to_string()),
+                        uri: "file:///logo.png".to_string(),
+                    }),
+                ],
+            },
+            TruncationPolicy::Bytes(10_000),
+        );
+        assert_eq!(
+            text_of(&resource),
+            "<mcp_resource>\n<server>docs</server>\n<uri>file:///notes.md</uri>\n# Notes\n[binary content (image/png) omitted]\n</mcp_resource>"
+        );
+
+        let prompt = prompt_message(
+            "docs",
+            "review",
+            &GetPromptResult {
+                description: None,
+                messages: vec![PromptMessage {
+                    content: ContentBlock::TextContent(TextContent {
+                        annotations: None,
+                        text: "Review the notes.".to_string(),
+                        r#type: "text".to_string(),
+                    }),
+                    role: Role::User,
+                }],
+            },
+            TruncationPolicy::Bytes(10_000),
+        );
+        assert_eq!(
+            text_of(&prompt),
+            "<mcp_prompt>\n<server>docs</server>\n<name>review</name>\nuser: Review the notes.\n</mcp_prompt>"
+        );
+
+        let ResponseItem::Message { content, .. } = &prompt else {
+            panic!("expected a message");
+        };
+        assert!(is_mcp_mention_contents(content));
+    }
+
+    #[test]
+    fn large_resources_are_truncated() {
+        let text = (1..=2_000)
+            .map(|line| format!("line {line}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let resource = resource_message(
+            "docs",
+            "file:///big.log",
+            &ReadResourceResult {
+                contents: vec![ReadResourceResultContents::TextResourceContents(
+                    TextResourceContents {
+                        mime_type: None,
+                        text,
+                        uri: "file:///big.log".to_string(),
+                    },
+                )],
+            },
+            TruncationPolicy::Bytes(200),
+        );
+        let text = text_of(&resource);
+        assert!(text.len() < 1_000, "not truncated: {} bytes", text.len());
+        assert!(text.starts_with(
+            "<mcp_resource>\n<server>docs</server>\n<uri>file:///big.log</uri>\nTotal output lines: 2000\n\nline 1\n"
+        ));
+        assert!(text.ends_with("line 2000\n</mcp_resource>"));
+    }
+}
@@ -1,4 +1,6 @@
 pub mod auth;
+pub mod mention;
+pub(crate) mod output_budget;
 mod skill_dependencies;
 
 pub(crate) use skill_dependencies::maybe_prompt_and_install_mcp_dependencies;
@@ -6,6 +8,7 @@ pub(crate) use skill_dependencies::maybe_prompt_and_install_mcp_dependencies;
 use std::collections::HashMap;
 use std::env;
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Duration;
 
 use async_channel::unbounded;
@@ -103,6 +106,12 @@ fn codex_apps_mcp_server_config(config: &Config, auth: Option<&CodexAuth>) -> Mc
         enabled_tools: None,
         disabled_tools: None,
         scopes: None,
+        lazy_start: false,
+        tool_aliases: None,
+        tool_approval: None,
+        tool_approvals: None,
+        tool_output_token_limit: None,
+        tool_output_token_limits: None,
     }
 }
 
@@ -135,12 +144,18 @@ pub(crate) fn effective_mcp_servers(
     )
 }
 
-pub async fn collect_mcp_snapshot(config: &Config) -> McpListToolsResponseEvent {
-    let auth_manager = AuthManager::shared(
-        config.codex_home.clone(),
+/// Auth manager for the account selected by `config`, used outside a session
+/// to reach MCP servers and connectors.
+pub(crate) fn auth_manager_from_config(config: &Config) -> Arc<AuthManager> {
+    AuthManager::shared(
+        config.auth_home(),
         false,
         config.cli_auth_credentials_store_mode,
-    );
+    )
+}
+
+pub async fn collect_mcp_snapshot(config: &Config) -> McpListToolsResponseEvent {
+    let auth_manager = auth_manager_from_config(config);
     let auth = auth_manager.auth().await;
     let mcp_servers = effective_mcp_servers(config, auth.as_ref());
     if mcp_servers.is_empty() {
@@ -148,6 +163,7 @@ pub async fn collect_mcp_snapshot(config: &Config) -> McpListToolsResponseEvent
             tools: HashMap::new(),
             resources: HashMap::new(),
             resource_templates: HashMap::new(),
+            prompts: HashMap::new(),
             auth_statuses: HashMap::new(),
         };
     }
@@ -219,10 +235,11 @@ pub(crate) async fn collect_mcp_snapshot_from_manager(
     mcp_connection_manager: &McpConnectionManager,
     auth_status_entries: HashMap<String, crate::mcp::auth::McpAuthStatusEntry>,
 ) -> McpListToolsResponseEvent {
-    let (tools, resources, resource_templates) = tokio::join!(
+    let (tools, resources, resource_templates, prompts) = tokio::join!(
         mcp_connection_manager.list_all_tools(),
         mcp_connection_manager.list_all_resources(),
         mcp_connection_manager.list_all_resource_templates(),
+        mcp_connection_manager.list_all_prompts(),
     );
 
     let auth_statuses = auth_status_entries
@@ -237,6 +254,7 @@ pub(crate) async fn collect_mcp_snapshot_from_manager(
             .collect(),
         resources,
         resource_templates,
+        prompts,
         auth_statuses,
     }
 }
@@ -262,6 +280,50 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn auth_manager_uses_the_profile_account() -> anyhow::Result<()> {
+        use crate::auth::AuthCredentialsStoreMode;
+        use crate::auth::account_auth_home;
+        use crate::auth::login_with_api_key;
+        use crate::config::ConfigOverrides;
+        use crate::config::ConfigToml;
+        use crate::config::profile::ConfigProfile;
+
+        let codex_home = tempfile::tempdir()?;
+        login_with_api_key(
+            codex_home.path(),
+            "sk-default",
+            AuthCredentialsStoreMode::File,
+        )?;
+        login_with_api_key(
+            &account_auth_home(codex_home.path(), Some("work")),
+            "sk-work",
+            AuthCredentialsStoreMode::File,
+        )?;
+        let config = Config::load_from_base_config_with_overrides(
+            ConfigToml {
+                profiles: HashMap::from([(
+                    "client".to_string(),
+                    ConfigProfile {
+                        account: Some("work".to_string()),
+                        ..Default::default()
+                    },
+                )]),
+                profile: Some("client".to_string()),
+                ..Default::default()
+            },
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        let auth = auth_manager_from_config(&config)
+            .auth()
+            .await
+            .expect("work account is logged in");
+        assert_eq!(auth.api_key(), Some("sk-work"));
+        Ok(())
+    }
+
     #[test]
     fn split_qualified_tool_name_returns_server_and_tool() {
         assert_eq!(
@@ -0,0 +1,300 @@
+//! Size budgets for MCP tool results shown to the model.
+//!
+//! A server can cap each of its tool results with `tool_output_token_limit`
+//! (or per tool with `tool_output_token_limits`), and
+//! `mcp_turn_output_token_limit` caps all MCP results in one turn. A result
+//! over its budget is saved to the session's output store and the model sees
+//! a cut-down copy: structured content keeps its shape with long arrays and
+//! strings shortened, and text keeps whole lines from its head and tail. The
+//! `read_output` tool reads the saved result by line number.
+
+use mcp_types::CallToolResult;
+use mcp_types::ContentBlock;
+use mcp_types::EmbeddedResourceResource;
+use mcp_types::TextContent;
+use serde_json::Value;
+
+use crate::config::Config;
+use crate::output_store::page_for_model;
+use crate::truncate::TruncationPolicy;
+
+/// Budgets below this leave no room for anything but the note.
+const MIN_PREVIEW_BYTES: usize = 256;
+const INITIAL_MAX_ITEMS: usize = 64;
+const INITIAL_MAX_STRING_BYTES: usize = 4096;
+const MIN_STRING_BYTES: usize = 32;
+/// Room for the `{"truncated": ..., "content": ...}` wrapper around a
+/// shortened structured result.
+const WRAPPER_BYTES: usize = 32;
+
+/// Whether any MCP output budget is configured, so `read_output` is needed.
+pub(crate) fn mcp_output_budgets_configured(config: &Config) -> bool {
+    config.mcp_turn_output_token_limit.is_some()
+        || config.mcp_servers.get().values().any(|server| {
+            server.tool_output_token_limit.is_some()
+                || server
+                    .tool_output_token_limits
+                    .as_ref()
+                    .is_some_and(|limits| !limits.is_empty())
+        })
+}
+
+/// Bytes `result` takes up in the model's context. Images and other binary
+/// blocks are not counted.
+pub(crate) fn mcp_result_size(result: &CallToolResult) -> usize {
+    if let Some(structured) = structured_content(result) {
+        return serde_json::to_string(structured).map_or(0, |json| json.len());
+    }
+    result
+        .content
+        .iter()
+        .filter_map(block_text)
+        .map(str::len)
+        .sum()
+}
+
+/// The full result as text for the output store: structured content as
+/// pretty-printed JSON so it can be read by line, otherwise the text blocks.
+pub(crate) fn mcp_result_full_text(result: &CallToolResult) -> String {
+    if let Some(structured) = structured_content(result) {
+        return serde_json::to_string_pretty(structured).unwrap_or_default();
+    }
+    result
+        .content
+        .iter()
+        .filter_map(block_text)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// `result` cut down to about `byte_budget` bytes. `output_id` names where
+/// the full result was saved, if it could be.
+pub(crate) fn truncate_mcp_result(
+    result: &CallToolResult,
+    output_id: Option<&str>,
+    byte_budget: usize,
+) -> CallToolResult {
+    let total_bytes = mcp_result_size(result);
+    let pointer = match output_id {
+        Some(output_id) => format!(
+            "The full result is saved as {output_id}; read it by line number with read_output."
+        ),
+        None => "The full result could not be saved.".to_string(),
+    };
+
+    if byte_budget < MIN_PREVIEW_BYTES {
+        return text_result(
+            result,
+            format!("Result is {total_bytes} bytes, over the MCP output budget. {pointer}"),
+            Vec::new(),
+        );
+    }
+
+    if let Some(structured) = structured_content(result) {
+        let note = format!(
+            "Result is {total_bytes} bytes and was shortened to fit the MCP output budget; cut arrays and strings are marked. {pointer}"
+        );
+        let budget = byte_budget.saturating_sub(note.len() + WRAPPER_BYTES);
+        if let Some(shrunk) = shrink_json_to_fit(structured, budget) {
+            return CallToolResult {
+                content: Vec::new(),
+                is_error: result.is_error,
+                structured_content: Some(serde_json::json!({
+                    "truncated": note,
+                    "content": shrunk,
+                })),
+            };
+        }
+        // Too many keys to shorten structurally; page the pretty JSON instead.
+        let full = mcp_result_full_text(result);
+        return text_result(result, page_text(&full, output_id, byte_budget), Vec::new());
+    }
+
+    let text = mcp_result_full_text(result);
+    let other_blocks = result
+        .content
+        .iter()
+        .filter(|block| block_text(block).is_none())
+        .cloned()
+        .collect();
+    text_result(
+        result,
+        page_text(&text, output_id, byte_budget),
+        other_blocks,
+    )
+}
+
+fn page_text(text: &str, output_id: Option<&str>, byte_budget: usize) -> String {
+    page_for_model(
+        text,
+        output_id.unwrap_or("(not saved)"),
+        TruncationPolicy::Bytes(byte_budget),
+    )
+}
+
+fn text_result(result: &CallToolResult, text: String, rest: Vec<ContentBlock>) -> CallToolResult {
+    let mut content = vec![ContentBlock::TextContent(TextContent {
+        annotations: None,
+        text,
+        r#type: "text".to_string(),
+    })];
+    content.extend(rest);
+    CallToolResult {
+        content,
+        is_error: result.is_error,
+        structured_content: None,
+    }
+}
+
+fn structured_content(result: &CallToolResult) -> Option<&Value> {
+    result
+        .structured_content
+        .as_ref()
+        .filter(|value| !value.is_null())
+}
+
+fn block_text(block: &ContentBlock) -> Option<&str> {
+    match block {
+        ContentBlock::TextContent(text) => Some(&text.text),
+        ContentBlock::EmbeddedResource(embedded) => match &embedded.resource {
+            EmbeddedResourceResource::TextResourceContents(text) => Some(&text.text),
+            EmbeddedResourceResource::BlobResourceContents(_) => None,
+        },
+        ContentBlock::ImageContent(_)
+        | ContentBlock::AudioContent(_)
+        | ContentBlock::ResourceLink(_) => None,
+    }
+}
+
+/// Shortens arrays and strings in `value`, halving the limits until the JSON
+/// fits `budget`. Returns `None` when even the smallest limits do not fit.
+fn shrink_json_to_fit(value: &Value, budget: usize) -> Option<Value> {
+    let mut max_items = INITIAL_MAX_ITEMS;
+    let mut max_string_bytes = INITIAL_MAX_STRING_BYTES;
+    loop {
+        let shrunk = shrink_json(value, max_items, max_string_bytes);
+        if serde_json::to_string(&shrunk).is_ok_and(|json| json.len() <= budget) {
+            return Some(shrunk);
+        }
+        if max_items == 1 && max_string_bytes == MIN_STRING_BYTES {
+            return None;
+        }
+        max_items = (max_items / 2).max(1);
+        max_string_bytes = (max_string_bytes / 2).max(MIN_STRING_BYTES);
+    }
+}
+
+fn shrink_json(value: &Value, max_items: usize, max_string_bytes: usize) -> Value {
+    match value {
+        Value::Array(items) => {
+            let mut shrunk: Vec<Value> = items
+                .iter()
+                .take(max_items)
+                .map(|item| shrink_json(item, max_items, max_string_bytes))
+                .collect();
+            if items.len() > max_items {
+                shrunk.push(Value::String(format!(
+                    "[... {} more items omitted]",
+                    items.len() - max_items
+                )));
+            }
+            Value::Array(shrunk)
+        }
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| (key.clone(), shrink_json(value, max_items, max_string_bytes)))
+                .collect(),
+        ),
+        Value::String(text) if text.len() > max_string_bytes => {
+            let cut = (0..=max_string_bytes)
+                .rev()
+                .find(|index| text.is_char_boundary(*index))
+                .unwrap_or(0);
+            Value::String(format!(
+                "{}[... {} bytes omitted]",
+                &text[..cut],
+                text.len() - cut
+            ))
+        }
+        Value::Null | Value::Bool(_) | Value::Number(_) | Value::String(_) => value.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn structured(value: Value) -> CallToolResult {
+        CallToolResult {
+            content: Vec::new(),
+            is_error: None,
+            structured_content: Some(value),
+        }
+    }
+
+    #[test]
+    fn structured_results_keep_their_shape() {
+        let rows: Vec<Value> = (0..200)
+            .map(|n| json!({ "id": n, "body": "x".repeat(500) }))
+            .collect();
+        let result = structured(json!({ "rows": rows, "total": 200 }));
+
+        let truncated = truncate_mcp_result(&result, Some("out-2"), 2_000);
+        let value = truncated.structured_content.expect("structured content");
+        assert!(mcp_result_size(&truncated) <= 2_000);
+        assert_eq!(value["content"]["total"], json!(200));
+        let kept = value["content"]["rows"].as_array().expect("rows");
+        assert_eq!(kept[0]["id"], json!(0));
+        assert_eq!(
+            kept.last(),
+            Some(&json!(format!(
+                "[... {} more items omitted]",
+                200 - (kept.len() - 1)
+            )))
+        );
+        assert!(
+            value["truncated"]
+                .as_str()
+                .is_some_and(|note| note.contains("out-2"))
+        );
+    }
+
+    #[test]
+    fn text_results_keep_head_and_tail_lines() {
+        let text: String = (1..=100).map(|n| format!("line {n:03}\n")).collect();
+        let result = CallToolResult {
+            content: vec![ContentBlock::TextContent(TextContent {
+                annotations: None,
+                text,
+                r#type: "text".to_string(),
+            })],
+            is_error: None,
+            structured_content: None,
+        };
+
+        let truncated = truncate_mcp_result(&result, Some("out-1"), 300);
+        let [ContentBlock::TextContent(text)] = truncated.content.as_slice() else {
+            panic!("expected one text block");
+        };
+        assert!(text.text.contains("line 001\n"));
+        assert!(text.text.contains("line 100\n"));
+        assert!(!text.text.contains("line 050\n"));
+        assert!(text.text.contains("read_output using output_id \"out-1\""));
+    }
+
+    #[test]
+    fn exhausted_budget_leaves_only_a_pointer() {
+        let result = structured(json!({ "rows": [1, 2, 3] }));
+        let truncated = truncate_mcp_result(&result, Some("out-3"), 0);
+        assert_eq!(truncated.structured_content, None);
+        let [ContentBlock::TextContent(text)] = truncated.content.as_slice() else {
+            panic!("expected one text block");
+        };
+        assert_eq!(
+            text.text,
+            "Result is 16 bytes, over the MCP output budget. The full result is saved as out-3; read it by line number with read_output."
+        );
+    }
+}
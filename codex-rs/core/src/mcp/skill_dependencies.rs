@@ -283,6 +283,7 @@ fn canonical_mcp_server_key(name: &str, config: &McpServerConfig) -> String {
         McpServerTransportConfig::StreamableHttp { url, .. } => {
             canonical_mcp_key("streamable_http", url, name)
         }
+        McpServerTransportConfig::Sse { url, .. } => canonical_mcp_key("sse", url, name),
     }
 }
 
@@ -385,6 +386,12 @@ fn mcp_dependency_to_server_config(
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
+            lazy_start: false,
+            tool_aliases: None,
+            tool_approval: None,
+            tool_approvals: None,
+            tool_output_token_limit: None,
+            tool_output_token_limits: None,
         });
     }
 
@@ -408,6 +415,12 @@ fn mcp_dependency_to_server_config(
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
+            lazy_start: false,
+            tool_aliases: None,
+            tool_approval: None,
+            tool_approvals: None,
+            tool_output_token_limit: None,
+            tool_output_token_limits: None,
         });
     }
 
@@ -461,6 +474,12 @@ mod tests {
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                lazy_start: false,
+                tool_aliases: None,
+                tool_approval: None,
+                tool_approvals: None,
+                tool_output_token_limit: None,
+                tool_output_token_limits: None,
             },
         )]);
 
@@ -508,6 +527,12 @@ mod tests {
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                lazy_start: false,
+                tool_aliases: None,
+                tool_approval: None,
+                tool_approvals: None,
+                tool_output_token_limit: None,
+                tool_output_token_limits: None,
             },
         )]);
 
@@ -5,14 +5,20 @@
 //! helpers to query the available tools across *all* servers and returns them
 //! in a single aggregated map using the fully-qualified tool name
 //! `"<server><MCP_TOOL_NAME_DELIMITER><tool>"` as the key.
+//!
+//! Servers start when the manager is initialized, or on first use when
+//! configured with `lazy_start`. Ready servers are health-checked
+//! periodically and restarted with backoff when they stop responding.
 
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::collections::VecDeque;
 use std::env;
 use std::ffi::OsString;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
+use std::time::Instant;
 
 use crate::mcp::CODEX_APPS_MCP_SERVER_NAME;
 use crate::mcp::auth::McpAuthStatusEntry;
@@ -25,24 +31,32 @@ use codex_async_utils::OrCancelExt;
 use codex_protocol::approvals::ElicitationRequestEvent;
 use codex_protocol::protocol::Event;
 use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::McpServerState;
+use codex_protocol::protocol::McpServerStatusEntry;
 use codex_protocol::protocol::McpStartupCompleteEvent;
 use codex_protocol::protocol::McpStartupFailure;
 use codex_protocol::protocol::McpStartupStatus;
 use codex_protocol::protocol::McpStartupUpdateEvent;
 use codex_protocol::protocol::SandboxPolicy;
+use codex_protocol::protocol::WarningEvent;
 use codex_rmcp_client::ElicitationResponse;
 use codex_rmcp_client::OAuthCredentialsStoreMode;
 use codex_rmcp_client::RmcpClient;
 use codex_rmcp_client::SendElicitation;
+use codex_rmcp_client::ToolCallProgressSender;
 use futures::future::BoxFuture;
 use futures::future::FutureExt;
 use futures::future::Shared;
 use mcp_types::ClientCapabilities;
+use mcp_types::GetPromptRequestParams;
+use mcp_types::GetPromptResult;
 use mcp_types::Implementation;
+use mcp_types::ListPromptsRequestParams;
 use mcp_types::ListResourceTemplatesRequestParams;
 use mcp_types::ListResourceTemplatesResult;
 use mcp_types::ListResourcesRequestParams;
 use mcp_types::ListResourcesResult;
+use mcp_types::Prompt;
 use mcp_types::ReadResourceRequestParams;
 use mcp_types::ReadResourceResult;
 use mcp_types::RequestId;
@@ -65,6 +79,8 @@ use tracing::warn;
 use crate::codex::INITIAL_SUBMIT_ID;
 use crate::config::types::McpServerConfig;
 use crate::config::types::McpServerTransportConfig;
+use crate::config::types::McpToolApproval;
+use crate::util::backoff;
 
 /// Delimiter used to separate the server name from the tool name in a fully
 /// qualified tool name.
@@ -111,48 +127,128 @@ fn qualify_tools<I>(tools: I) -> HashMap<String, ToolInfo>
 where
     I: IntoIterator<Item = ToolInfo>,
 {
-    let mut used_names = HashSet::new();
+    qualify_tools_reporting_conflicts(tools).0
+}
+
+/// A tool whose fully-qualified name was already taken by another tool, and
+/// the name it is exposed under instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ToolNameConflict {
+    pub(crate) server_name: String,
+    pub(crate) tool_name: String,
+    pub(crate) contested_name: String,
+    pub(crate) taken_by: String,
+    pub(crate) resolved_name: String,
+}
+
+impl std::fmt::Display for ToolNameConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "MCP tool `{}` on server `{}` would be named `{}`, which is already used by `{}`; it is available as `{}` instead.",
+            self.tool_name,
+            self.server_name,
+            self.contested_name,
+            self.taken_by,
+            self.resolved_name
+        )
+    }
+}
+
+/// Assign each tool its fully-qualified name, `mcp__<server>__<tool or alias>`.
+///
+/// Tools are named in (server, tool) order so the result does not depend on
+/// which server answered first. A tool whose name is already taken, e.g. when
+/// `foo.bar` and `foo_bar` both sanitize to `foo_bar` or an alias matches
+/// another tool, gets a hash suffix instead of being dropped, and is reported
+/// as a [`ToolNameConflict`].
+fn qualify_tools_reporting_conflicts<I>(
+    tools: I,
+) -> (HashMap<String, ToolInfo>, Vec<ToolNameConflict>)
+where
+    I: IntoIterator<Item = ToolInfo>,
+{
+    let mut tools: Vec<ToolInfo> = tools.into_iter().collect();
+    tools.sort_by(|a, b| {
+        (a.server_name.as_str(), a.tool_name.as_str())
+            .cmp(&(b.server_name.as_str(), b.tool_name.as_str()))
+    });
+
     let mut seen_raw_names = HashSet::new();
-    let mut qualified_tools = HashMap::new();
+    let mut qualified_tools: HashMap<String, ToolInfo> = HashMap::new();
+    let mut conflicts = Vec::new();
     for tool in tools {
+        let exposed_name = tool.alias.as_deref().unwrap_or(&tool.tool_name);
         let qualified_name_raw = format!(
-            "mcp{}{}{}{}",
-            MCP_TOOL_NAME_DELIMITER, tool.server_name, MCP_TOOL_NAME_DELIMITER, tool.tool_name
+            "mcp{MCP_TOOL_NAME_DELIMITER}{}{MCP_TOOL_NAME_DELIMITER}{exposed_name}",
+            tool.server_name
         );
-        if !seen_raw_names.insert(qualified_name_raw.clone()) {
+        // A server listing the same tool twice.
+        if !seen_raw_names.insert((tool.server_name.clone(), tool.tool_name.clone())) {
             warn!("skipping duplicated tool {}", qualified_name_raw);
             continue;
         }
 
-        // Start from a "pretty" name (sanitized), then deterministically disambiguate on
-        // collisions by appending a hash of the *raw* (unsanitized) qualified name. This
-        // ensures tools like `foo.bar` and `foo_bar` don't collapse to the same key.
-        let mut qualified_name = sanitize_responses_api_tool_name(&qualified_name_raw);
-
-        // Enforce length constraints early; use the raw name for the hash input so the
+        // Start from a "pretty" name (sanitized). Hash the *raw* (unsanitized)
+        // qualified name when it has to be shortened or disambiguated, so the
         // output remains stable even when sanitization changes.
+        let mut qualified_name = sanitize_responses_api_tool_name(&qualified_name_raw);
         if qualified_name.len() > MAX_TOOL_NAME_LENGTH {
-            let sha1_str = sha1_hex(&qualified_name_raw);
-            let prefix_len = MAX_TOOL_NAME_LENGTH - sha1_str.len();
-            qualified_name = format!("{}{}", &qualified_name[..prefix_len], sha1_str);
+            qualified_name = with_hash_suffix(&qualified_name, &qualified_name_raw);
         }
 
-        if used_names.contains(&qualified_name) {
-            warn!("skipping duplicated tool {}", qualified_name);
-            continue;
+        if let Some(existing) = qualified_tools.get(&qualified_name) {
+            let resolved_name = with_hash_suffix(&qualified_name, &qualified_name_raw);
+            let conflict = ToolNameConflict {
+                server_name: tool.server_name.clone(),
+                tool_name: tool.tool_name.clone(),
+                contested_name: qualified_name,
+                taken_by: format!("{}/{}", existing.server_name, existing.tool_name),
+                resolved_name: resolved_name.clone(),
+            };
+            warn!("{conflict}");
+            conflicts.push(conflict);
+            qualified_name = resolved_name;
         }
 
-        used_names.insert(qualified_name.clone());
         qualified_tools.insert(qualified_name, tool);
     }
 
-    qualified_tools
+    (qualified_tools, conflicts)
+}
+
+/// `name` shortened as needed to end in the SHA-1 of `raw_name` within
+/// [`MAX_TOOL_NAME_LENGTH`].
+fn with_hash_suffix(name: &str, raw_name: &str) -> String {
+    let sha1_str = sha1_hex(raw_name);
+    let prefix_len = (MAX_TOOL_NAME_LENGTH - sha1_str.len()).min(name.len());
+    format!("{}{}", &name[..prefix_len], sha1_str)
+}
+
+/// Apply a server's `tool_aliases` to its tools.
+fn apply_tool_aliases(
+    tools: Vec<ToolInfo>,
+    aliases: Option<&HashMap<String, String>>,
+) -> Vec<ToolInfo> {
+    let Some(aliases) = aliases else {
+        return tools;
+    };
+    tools
+        .into_iter()
+        .map(|mut tool| {
+            tool.alias = aliases.get(&tool.tool_name).cloned();
+            tool
+        })
+        .collect()
 }
 
 #[derive(Clone)]
 pub(crate) struct ToolInfo {
     pub(crate) server_name: String,
     pub(crate) tool_name: String,
+    /// Name from the server's `tool_aliases`, used in place of `tool_name`
+    /// in the qualified name the model sees.
+    pub(crate) alias: Option<String>,
     pub(crate) tool: Tool,
     pub(crate) connector_id: Option<String>,
     pub(crate) connector_name: Option<String>,
@@ -218,6 +314,7 @@ struct ManagedClient {
     tool_filter: ToolFilter,
     tool_timeout: Option<Duration>,
     server_supports_sandbox_state_capability: bool,
+    server_supports_prompts: bool,
 }
 
 impl ManagedClient {
@@ -238,20 +335,109 @@ impl ManagedClient {
     }
 }
 
+type StartupFuture = Shared<BoxFuture<'static, Result<ManagedClient, StartupOutcomeError>>>;
+
+/// How often ready servers are probed with `tools/list`.
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+const INITIAL_RESTART_DELAY: Duration = Duration::from_secs(1);
+const MAX_RESTART_DELAY: Duration = Duration::from_secs(30);
+/// Restart attempts in a row before a server is left stopped.
+const MAX_RESTART_ATTEMPTS: u32 = 5;
+/// Errors kept per server for `/mcp status`.
+const MAX_RECENT_ERRORS: usize = 5;
+
+/// One configured server. It is started right away, or on first use with
+/// `lazy_start`, and restarted with backoff when it stops responding.
 #[derive(Clone)]
 struct AsyncManagedClient {
-    client: Shared<BoxFuture<'static, Result<ManagedClient, StartupOutcomeError>>>,
+    lifecycle: Arc<ServerLifecycle>,
+}
+
+struct ServerLifecycle {
+    server_name: String,
+    config: McpServerConfig,
+    store_mode: OAuthCredentialsStoreMode,
+    auth_entry: Option<McpAuthStatusEntry>,
+    cancel_token: CancellationToken,
+    tx_event: Sender<Event>,
+    elicitation_requests: ElicitationRequestManager,
+    state: std::sync::Mutex<LifecycleState>,
+}
+
+struct LifecycleState {
+    /// The current start attempt. `None` until a lazy server is first used,
+    /// and while a restart is pending.
+    startup: Option<StartupFuture>,
+    /// Whether the server has been ready at least once.
+    was_ready: bool,
+    restarts: u32,
+    /// Restart attempts since the server was last ready.
+    restart_attempts: u32,
+    restart_at: Option<Instant>,
+    gave_up: bool,
+    recent_errors: VecDeque<String>,
+    sandbox_state: SandboxState,
 }
 
 impl AsyncManagedClient {
+    #[allow(clippy::too_many_arguments)]
     fn new(
         server_name: String,
         config: McpServerConfig,
         store_mode: OAuthCredentialsStoreMode,
+        auth_entry: Option<McpAuthStatusEntry>,
         cancel_token: CancellationToken,
         tx_event: Sender<Event>,
         elicitation_requests: ElicitationRequestManager,
+        sandbox_state: SandboxState,
     ) -> Self {
+        let lazy_start = config.lazy_start;
+        let managed = Self {
+            lifecycle: Arc::new(ServerLifecycle {
+                server_name,
+                config,
+                store_mode,
+                auth_entry,
+                cancel_token,
+                tx_event,
+                elicitation_requests,
+                state: std::sync::Mutex::new(LifecycleState {
+                    startup: None,
+                    was_ready: false,
+                    restarts: 0,
+                    restart_attempts: 0,
+                    restart_at: None,
+                    gave_up: false,
+                    recent_errors: VecDeque::new(),
+                    sandbox_state,
+                }),
+            }),
+        };
+        if !lazy_start {
+            let mut state = managed.lock_state();
+            let _ = managed.start(&mut state);
+        }
+        managed
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, LifecycleState> {
+        self.lifecycle
+            .state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Begin a start attempt and drive it in the background so restarts make
+    /// progress even when nothing is waiting on them.
+    fn start(&self, state: &mut LifecycleState) -> StartupFuture {
+        let lifecycle = &self.lifecycle;
+        let server_name = lifecycle.server_name.clone();
+        let config = lifecycle.config.clone();
+        let store_mode = lifecycle.store_mode;
+        let cancel_token = lifecycle.cancel_token.clone();
+        let tx_event = lifecycle.tx_event.clone();
+        let elicitation_requests = lifecycle.elicitation_requests.clone();
         let tool_filter = ToolFilter::from_config(&config);
         let fut = async move {
             if let Err(error) = validate_mcp_server_name(&server_name) {
@@ -276,21 +462,237 @@ impl AsyncManagedClient {
                 Err(CancelErr::Cancelled) => Err(StartupOutcomeError::Cancelled),
             }
         };
-        Self {
-            client: fut.boxed().shared(),
+        let startup = fut.boxed().shared();
+        state.startup = Some(startup.clone());
+
+        let lifecycle = Arc::downgrade(&self.lifecycle);
+        let driver = startup.clone();
+        tokio::spawn(async move {
+            let outcome = driver.await;
+            if let Some(lifecycle) = lifecycle.upgrade() {
+                Self { lifecycle }.on_start_finished(outcome).await;
+            }
+        });
+        startup
+    }
+
+    async fn on_start_finished(&self, outcome: Result<ManagedClient, StartupOutcomeError>) {
+        let server_name = &self.lifecycle.server_name;
+        match outcome {
+            Ok(managed) => {
+                let sandbox_state = {
+                    let mut state = self.lock_state();
+                    state.was_ready = true;
+                    state.restart_attempts = 0;
+                    state.sandbox_state.clone()
+                };
+                if let Err(err) = managed.notify_sandbox_state_change(&sandbox_state).await {
+                    warn!("Failed to notify sandbox state to MCP server {server_name}: {err:#}");
+                }
+            }
+            Err(StartupOutcomeError::Cancelled) => {
+                push_recent_error(&mut self.lock_state(), "startup was cancelled".to_string());
+            }
+            Err(error) => {
+                let message =
+                    mcp_init_error_display(server_name, self.lifecycle.auth_entry.as_ref(), &error);
+                let mut state = self.lock_state();
+                if state.was_ready {
+                    self.schedule_restart(&mut state, message);
+                } else {
+                    push_recent_error(&mut state, message.clone());
+                    // Eager servers report this through the startup events.
+                    if self.lifecycle.config.lazy_start {
+                        self.send_warning(message);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Record `error` and start the server again after a backoff, or give up
+    /// once it has failed too many times in a row.
+    fn schedule_restart(&self, state: &mut LifecycleState, error: String) {
+        let server_name = &self.lifecycle.server_name;
+        warn!("MCP server {server_name} stopped: {error}");
+        push_recent_error(state, error);
+        state.startup = None;
+        if state.restart_attempts >= MAX_RESTART_ATTEMPTS {
+            state.gave_up = true;
+            self.send_warning(format!(
+                "MCP server `{server_name}` was stopped after {MAX_RESTART_ATTEMPTS} failed restarts. Run `/mcp status` for details."
+            ));
+            return;
+        }
+        if state.restart_attempts == 0 {
+            self.send_warning(format!(
+                "MCP server `{server_name}` stopped responding and is being restarted."
+            ));
+        }
+        let delay = restart_delay(state.restart_attempts);
+        state.restart_attempts += 1;
+        state.restart_at = Some(Instant::now() + delay);
+
+        let lifecycle = Arc::downgrade(&self.lifecycle);
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            if let Some(lifecycle) = lifecycle.upgrade() {
+                let _ = Self { lifecycle }.current_startup();
+            }
+        });
+    }
+
+    fn send_warning(&self, message: String) {
+        let _ = self.lifecycle.tx_event.try_send(Event {
+            id: INITIAL_SUBMIT_ID.to_owned(),
+            msg: EventMsg::Warning(WarningEvent { message }),
+        });
+    }
+
+    /// The current start attempt, beginning one when a lazy server is first
+    /// used or a pending restart is due.
+    fn current_startup(&self) -> Result<StartupFuture, StartupOutcomeError> {
+        let server_name = &self.lifecycle.server_name;
+        let mut state = self.lock_state();
+        if state.gave_up {
+            return Err(StartupOutcomeError::Failed {
+                error: format!(
+                    "MCP server `{server_name}` was stopped after {MAX_RESTART_ATTEMPTS} failed restarts"
+                ),
+            });
+        }
+        if let Some(restart_at) = state.restart_at {
+            if Instant::now() < restart_at {
+                return Err(StartupOutcomeError::Failed {
+                    error: format!("MCP server `{server_name}` is restarting"),
+                });
+            }
+            state.restart_at = None;
+            state.restarts += 1;
         }
+        if let Some(startup) = &state.startup {
+            return Ok(startup.clone());
+        }
+        Ok(self.start(&mut state))
     }
 
     async fn client(&self) -> Result<ManagedClient, StartupOutcomeError> {
-        self.client.clone().await
+        self.current_startup()?.await
+    }
+
+    /// The client if the server is ready, without waiting for it to start.
+    fn ready_client(&self) -> Option<ManagedClient> {
+        match self.current_startup().ok()?.now_or_never() {
+            Some(Ok(client)) => Some(client),
+            _ => None,
+        }
+    }
+
+    /// Probe a ready server with `tools/list` and restart it if it does not
+    /// answer. Servers that are not ready are left alone.
+    async fn check_health(&self) {
+        let managed = {
+            let state = self.lock_state();
+            match state.startup.as_ref().and_then(Shared::peek) {
+                Some(Ok(managed)) => managed.clone(),
+                _ => return,
+            }
+        };
+        if let Err(err) = managed
+            .client
+            .list_tools(None, Some(HEALTH_CHECK_TIMEOUT))
+            .await
+        {
+            let mut state = self.lock_state();
+            // Another check may already have replaced this client.
+            let is_current = matches!(
+                state.startup.as_ref().and_then(Shared::peek),
+                Some(Ok(current)) if Arc::ptr_eq(&current.client, &managed.client)
+            );
+            if is_current {
+                self.schedule_restart(&mut state, format!("health check failed: {err:#}"));
+            }
+        }
+    }
+
+    fn status(&self) -> McpServerStatusEntry {
+        let state = self.lock_state();
+        let current = state.startup.as_ref().and_then(Shared::peek);
+        let server_state = if state.gave_up {
+            McpServerState::Failed
+        } else if state.restart_at.is_some() {
+            McpServerState::Restarting
+        } else {
+            match current {
+                None if state.startup.is_none() => McpServerState::NotStarted,
+                None if state.was_ready => McpServerState::Restarting,
+                None => McpServerState::Starting,
+                Some(Ok(_)) => McpServerState::Ready,
+                Some(Err(_)) => McpServerState::Failed,
+            }
+        };
+        let mut tools: Vec<String> = match current {
+            Some(Ok(managed)) if server_state == McpServerState::Ready => {
+                filter_tools(managed.tools.clone(), managed.tool_filter.clone())
+                    .into_iter()
+                    .map(|tool| tool.tool_name)
+                    .collect()
+            }
+            _ => Vec::new(),
+        };
+        tools.sort();
+        McpServerStatusEntry {
+            name: self.lifecycle.server_name.clone(),
+            state: server_state,
+            tools,
+            restarts: state.restarts,
+            recent_errors: state.recent_errors.iter().cloned().collect(),
+        }
     }
 
     async fn notify_sandbox_state_change(&self, sandbox_state: &SandboxState) -> Result<()> {
-        let managed = self.client().await?;
+        let startup = {
+            let mut state = self.lock_state();
+            state.sandbox_state = sandbox_state.clone();
+            state.startup.clone()
+        };
+        // Servers that are not running get the new state when they start.
+        let Some(startup) = startup else {
+            return Ok(());
+        };
+        let managed = startup.await?;
         managed.notify_sandbox_state_change(sandbox_state).await
     }
 }
 
+/// The backoff before restart attempt `attempt` (counting from zero).
+fn restart_delay(attempt: u32) -> Duration {
+    backoff(INITIAL_RESTART_DELAY, u64::from(attempt) + 1).min(MAX_RESTART_DELAY)
+}
+
+fn push_recent_error(state: &mut LifecycleState, error: String) {
+    if state.recent_errors.len() == MAX_RECENT_ERRORS {
+        state.recent_errors.pop_front();
+    }
+    state.recent_errors.push_back(error);
+}
+
+/// Probe every server until the manager that owns them is dropped.
+async fn run_health_checks(servers: Vec<std::sync::Weak<ServerLifecycle>>) {
+    loop {
+        tokio::time::sleep(HEALTH_CHECK_INTERVAL).await;
+        let servers: Vec<AsyncManagedClient> = servers
+            .iter()
+            .filter_map(std::sync::Weak::upgrade)
+            .map(|lifecycle| AsyncManagedClient { lifecycle })
+            .collect();
+        if servers.is_empty() {
+            return;
+        }
+        futures::future::join_all(servers.iter().map(AsyncManagedClient::check_health)).await;
+    }
+}
+
 pub const MCP_SANDBOX_STATE_CAPABILITY: &str = "codex/sandbox-state";
 
 /// Custom MCP request to push sandbox state updates.
@@ -329,46 +731,46 @@ impl McpConnectionManager {
         let mut join_set = JoinSet::new();
         let elicitation_requests = ElicitationRequestManager::default();
         let mcp_servers = mcp_servers.clone();
+        let tool_aliases: HashMap<String, HashMap<String, String>> = mcp_servers
+            .iter()
+            .filter_map(|(name, cfg)| Some((name.clone(), cfg.tool_aliases.clone()?)))
+            .collect();
         for (server_name, cfg) in mcp_servers.into_iter().filter(|(_, cfg)| cfg.enabled) {
             let cancel_token = cancel_token.child_token();
-            let _ = emit_update(
-                &tx_event,
-                McpStartupUpdateEvent {
-                    server: server_name.clone(),
-                    status: McpStartupStatus::Starting,
-                },
-            )
-            .await;
+            let auth_entry = auth_entries.get(&server_name).cloned();
+            let lazy_start = cfg.lazy_start;
+            if !lazy_start {
+                let _ = emit_update(
+                    &tx_event,
+                    McpStartupUpdateEvent {
+                        server: server_name.clone(),
+                        status: McpStartupStatus::Starting,
+                    },
+                )
+                .await;
+            }
             let async_managed_client = AsyncManagedClient::new(
                 server_name.clone(),
                 cfg,
                 store_mode,
+                auth_entry.clone(),
                 cancel_token.clone(),
                 tx_event.clone(),
                 elicitation_requests.clone(),
+                initial_sandbox_state.clone(),
             );
             clients.insert(server_name.clone(), async_managed_client.clone());
+            if lazy_start {
+                continue;
+            }
             let tx_event = tx_event.clone();
-            let auth_entry = auth_entries.get(&server_name).cloned();
-            let sandbox_state = initial_sandbox_state.clone();
             join_set.spawn(async move {
                 let outcome = async_managed_client.client().await;
                 if cancel_token.is_cancelled() {
                     return (server_name, Err(StartupOutcomeError::Cancelled));
                 }
                 let status = match &outcome {
-                    Ok(_) => {
-                        // Send sandbox state notification immediately after Ready
-                        if let Err(e) = async_managed_client
-                            .notify_sandbox_state_change(&sandbox_state)
-                            .await
-                        {
-                            warn!(
-                                "Failed to notify sandbox state to MCP server {server_name}: {e:#}",
-                            );
-                        }
-                        McpStartupStatus::Ready
-                    }
+                    Ok(_) => McpStartupStatus::Ready,
                     Err(error) => {
                         let error_str = mcp_init_error_display(
                             server_name.as_str(),
@@ -391,14 +793,28 @@ impl McpConnectionManager {
                 (server_name, outcome)
             });
         }
+        if !clients.is_empty() {
+            let servers = clients
+                .values()
+                .map(|client| Arc::downgrade(&client.lifecycle))
+                .collect();
+            tokio::spawn(run_health_checks(servers));
+        }
         self.clients = clients;
         self.elicitation_requests = elicitation_requests.clone();
         tokio::spawn(async move {
             let outcomes = join_set.join_all().await;
             let mut summary = McpStartupCompleteEvent::default();
+            let mut ready_tools = Vec::new();
             for (server_name, outcome) in outcomes {
                 match outcome {
-                    Ok(_) => summary.ready.push(server_name),
+                    Ok(managed) => {
+                        ready_tools.extend(apply_tool_aliases(
+                            filter_tools(managed.tools, managed.tool_filter),
+                            tool_aliases.get(&server_name),
+                        ));
+                        summary.ready.push(server_name);
+                    }
                     Err(StartupOutcomeError::Cancelled) => summary.cancelled.push(server_name),
                     Err(StartupOutcomeError::Failed { error }) => {
                         summary.failed.push(McpStartupFailure {
@@ -408,6 +824,17 @@ impl McpConnectionManager {
                     }
                 }
             }
+            let (_, conflicts) = qualify_tools_reporting_conflicts(ready_tools);
+            for conflict in conflicts {
+                let _ = tx_event
+                    .send(Event {
+                        id: INITIAL_SUBMIT_ID.to_owned(),
+                        msg: EventMsg::Warning(WarningEvent {
+                            message: conflict.to_string(),
+                        }),
+                    })
+                    .await;
+            }
             let _ = tx_event
                 .send(Event {
                     id: INITIAL_SUBMIT_ID.to_owned(),
@@ -448,29 +875,60 @@ impl McpConnectionManager {
         }
     }
 
+    /// The approval setting configured for `tool_name` on `server_name`.
+    pub(crate) fn tool_approval(
+        &self,
+        server_name: &str,
+        tool_name: &str,
+    ) -> Option<McpToolApproval> {
+        self.clients
+            .get(server_name)?
+            .lifecycle
+            .config
+            .tool_approval_for(tool_name)
+    }
+
+    /// The output token budget configured for each result of `tool_name` on
+    /// `server_name`.
+    pub(crate) fn tool_output_token_limit(
+        &self,
+        server_name: &str,
+        tool_name: &str,
+    ) -> Option<usize> {
+        self.clients
+            .get(server_name)?
+            .lifecycle
+            .config
+            .tool_output_token_limit_for(tool_name)
+    }
+
     /// Returns a single map that contains all tools. Each key is the
     /// fully-qualified name for the tool.
+    ///
+    /// Servers are waited on together. This is where `lazy_start` servers
+    /// are first started, and their startup is awaited so the turn that lists
+    /// tools can already call theirs.
     #[instrument(level = "trace", skip_all)]
     pub async fn list_all_tools(&self) -> HashMap<String, ToolInfo> {
-        let mut tools = HashMap::new();
-        for (server_name, managed_client) in &self.clients {
-            let client = if server_name == CODEX_APPS_MCP_SERVER_NAME {
-                // Avoid blocking on codex_apps_mcp startup; use tools only when ready.
-                match managed_client.client.clone().now_or_never() {
-                    Some(Ok(client)) => Some(client),
-                    _ => None,
-                }
-            } else {
-                managed_client.client().await.ok()
-            };
-            if let Some(client) = client {
-                tools.extend(qualify_tools(filter_tools(
-                    client.tools,
-                    client.tool_filter,
-                )));
-            }
-        }
-        tools
+        let per_server = futures::future::join_all(self.clients.iter().map(
+            |(server_name, managed_client)| async move {
+                let client = if server_name == CODEX_APPS_MCP_SERVER_NAME {
+                    // Avoid blocking on codex_apps_mcp startup; use tools only when ready.
+                    managed_client.ready_client()
+                } else {
+                    managed_client.client().await.ok()
+                }?;
+                let config = &managed_client.lifecycle.config;
+                Some(apply_tool_aliases(
+                    filter_denied_tools(filter_tools(client.tools, client.tool_filter), config),
+                    config.tool_aliases.as_ref(),
+                ))
+            },
+        ))
+        .await;
+        // Qualify every server's tools together so that names colliding across
+        // servers are disambiguated rather than overwritten.
+        qualify_tools(per_server.into_iter().flatten().flatten().collect())
     }
 
     /// Returns a single map that contains all resources. Each key is the
@@ -609,12 +1067,65 @@ impl McpConnectionManager {
         aggregated
     }
 
-    /// Invoke the tool indicated by the (server, tool) pair.
-    pub async fn call_tool(
+    /// Returns a single map that contains all prompts. Each key is the
+    /// server name and the value is a vector of prompts.
+    ///
+    /// Servers are asked together, and only those that have already started:
+    /// listing prompts does not start `lazy_start` servers or wait on slow
+    /// ones.
+    pub async fn list_all_prompts(&self) -> HashMap<String, Vec<Prompt>> {
+        let per_server = futures::future::join_all(self.clients.iter().map(
+            |(server_name, managed_client)| async move {
+                let managed_client = managed_client.ready_client()?;
+                if !managed_client.server_supports_prompts {
+                    return None;
+                }
+                let client = managed_client.client;
+                let timeout = managed_client.tool_timeout;
+                let mut collected: Vec<Prompt> = Vec::new();
+                let mut cursor: Option<String> = None;
+
+                loop {
+                    let params = cursor.as_ref().map(|next| ListPromptsRequestParams {
+                        cursor: Some(next.clone()),
+                    });
+                    let response = match client.list_prompts(params, timeout).await {
+                        Ok(result) => result,
+                        Err(err) => {
+                            warn!("Failed to list prompts for MCP server '{server_name}': {err:#}");
+                            return None;
+                        }
+                    };
+
+                    collected.extend(response.prompts);
+
+                    match response.next_cursor {
+                        Some(next) => {
+                            if cursor.as_ref() == Some(&next) {
+                                warn!(
+                                    "Failed to list prompts for MCP server '{server_name}': prompts/list returned duplicate cursor"
+                                );
+                                return None;
+                            }
+                            cursor = Some(next);
+                        }
+                        None => return Some((server_name.clone(), collected)),
+                    }
+                }
+            },
+        ))
+        .await;
+        per_server.into_iter().flatten().collect()
+    }
+
+    /// Invoke the tool, forwarding the server's progress notifications for
+    /// the call to `progress`.
+    pub async fn call_tool_with_progress(
         &self,
         server: &str,
         tool: &str,
         arguments: Option<serde_json::Value>,
+        progress: Option<ToolCallProgressSender>,
     ) -> Result<mcp_types::CallToolResult> {
         let client = self.client_by_name(server).await?;
         if !client.tool_filter.allows(tool) {
@@ -623,11 +1134,28 @@ impl McpConnectionManager {
             ));
         }
 
-        client
+        let result = client
             .client
-            .call_tool(tool.to_string(), arguments, client.tool_timeout)
-            .await
-            .with_context(|| format!("tool call failed for `{server}/{tool}`"))
+            .call_tool_with_progress(tool.to_string(), arguments, client.tool_timeout, progress)
+            .await;
+        if result.is_err()
+            && let Some(managed_client) = self.clients.get(server)
+        {
+            // A failed call may mean the server has crashed.
+            managed_client.check_health().await;
+        }
+        result.with_context(|| format!("tool call failed for `{server}/{tool}`"))
+    }
+
+    /// Lifecycle state of each server, sorted by name.
+    pub(crate) fn server_statuses(&self) -> Vec<McpServerStatusEntry> {
+        let mut statuses: Vec<McpServerStatusEntry> = self
+            .clients
+            .values()
+            .map(AsyncManagedClient::status)
+            .collect();
+        statuses.sort_by(|a, b| a.name.cmp(&b.name));
+        statuses
     }
 
     /// List resources from the specified server.
@@ -679,6 +1207,23 @@ impl McpConnectionManager {
             .with_context(|| format!("resources/read failed for `{server}` ({uri})"))
     }
 
+    /// Fetch a prompt's messages from the specified server.
+    pub async fn get_prompt(
+        &self,
+        server: &str,
+        params: GetPromptRequestParams,
+    ) -> Result<GetPromptResult> {
+        let managed = self.client_by_name(server).await?;
+        let client = managed.client.clone();
+        let timeout = managed.tool_timeout;
+        let name = params.name.clone();
+
+        client
+            .get_prompt(params, timeout)
+            .await
+            .with_context(|| format!("prompts/get failed for `{server}` ({name})"))
+    }
+
     pub async fn parse_tool_name(&self, tool_name: &str) -> Option<(String, String)> {
         self.list_all_tools()
             .await
@@ -769,6 +1314,15 @@ fn filter_tools(tools: Vec<ToolInfo>, filter: ToolFilter) -> Vec<ToolInfo> {
         .collect()
 }
 
+/// Drops tools whose `tool_approval` is `deny`, so the model is never offered
+/// a tool it cannot call.
+fn filter_denied_tools(tools: Vec<ToolInfo>, config: &McpServerConfig) -> Vec<ToolInfo> {
+    tools
+        .into_iter()
+        .filter(|tool| config.tool_approval_for(&tool.tool_name) != Some(McpToolApproval::Deny))
+        .collect()
+}
+
 fn normalize_codex_apps_tool_title(
     server_name: &str,
     connector_name: Option<&str>,
@@ -887,6 +1441,7 @@ async fn start_server_task(
         .as_ref()
         .and_then(|exp| exp.get(MCP_SANDBOX_STATE_CAPABILITY))
         .is_some();
+    let server_supports_prompts = initialize_result.capabilities.prompts.is_some();
 
     let managed = ManagedClient {
         client: Arc::clone(&client),
@@ -894,6 +1449,7 @@ async fn start_server_task(
         tool_timeout: Some(tool_timeout),
         tool_filter,
         server_supports_sandbox_state_capability,
+        server_supports_prompts,
     };
 
     Ok(managed)
@@ -940,6 +1496,20 @@ async fn make_rmcp_client(
             .await
             .map_err(StartupOutcomeError::from)
         }
+        McpServerTransportConfig::Sse {
+            url,
+            http_headers,
+            env_http_headers,
+            bearer_token_env_var,
+        } => {
+            let resolved_bearer_token =
+                match resolve_bearer_token(server_name, bearer_token_env_var.as_deref()) {
+                    Ok(token) => token,
+                    Err(error) => return Err(error.into()),
+                };
+            RmcpClient::new_sse_client(&url, resolved_bearer_token, http_headers, env_http_headers)
+                .map_err(StartupOutcomeError::from)
+        }
     }
 }
 
@@ -965,6 +1535,7 @@ async fn list_tools_for_client(
             ToolInfo {
                 server_name: server_name.to_owned(),
                 tool_name: tool_def.name.clone(),
+                alias: None,
                 tool: tool_def,
                 connector_id: tool.connector_id,
                 connector_name,
@@ -1048,12 +1619,14 @@ mod tests {
     use super::*;
     use codex_protocol::protocol::McpAuthStatus;
     use mcp_types::ToolInputSchema;
+    use pretty_assertions::assert_eq;
     use std::collections::HashSet;
 
     fn create_test_tool(server_name: &str, tool_name: &str) -> ToolInfo {
         ToolInfo {
             server_name: server_name.to_string(),
             tool_name: tool_name.to_string(),
+            alias: None,
             tool: Tool {
                 annotations: None,
                 description: Some(format!("Test tool: {tool_name}")),
@@ -1156,6 +1729,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_qualify_tools_reports_conflicts_deterministically() {
+        let mut aliased = create_test_tool("docs", "search_v2");
+        aliased.alias = Some("search".to_string());
+        let tools = vec![
+            create_test_tool("docs", "tool_one"),
+            create_test_tool("docs", "tool.one"),
+            aliased,
+            create_test_tool("docs", "search"),
+        ];
+
+        let (qualified_tools, conflicts) = qualify_tools_reporting_conflicts(tools);
+
+        assert_eq!(qualified_tools.len(), 4);
+        assert_eq!(
+            qualified_tools
+                .get("mcp__docs__search")
+                .map(|tool| tool.tool_name.as_str()),
+            Some("search")
+        );
+        assert_eq!(
+            qualified_tools
+                .get("mcp__docs__tool_one")
+                .map(|tool| tool.tool_name.as_str()),
+            Some("tool.one")
+        );
+        let renamed: Vec<(&str, &str)> = conflicts
+            .iter()
+            .map(|conflict| (conflict.tool_name.as_str(), conflict.taken_by.as_str()))
+            .collect();
+        assert_eq!(
+            renamed,
+            vec![("search_v2", "docs/search"), ("tool_one", "docs/tool.one")]
+        );
+        for conflict in &conflicts {
+            assert!(qualified_tools.contains_key(&conflict.resolved_name));
+            assert!(conflict.resolved_name.len() <= MAX_TOOL_NAME_LENGTH);
+        }
+    }
+
     #[test]
     fn tool_filter_allows_by_default() {
         let filter = ToolFilter::default();
@@ -1223,6 +1836,30 @@ mod tests {
         assert_eq!(filtered[0].tool_name, "tool_a");
     }
 
+    #[test]
+    fn filter_denied_tools_drops_tools_set_to_deny() {
+        let tools = vec![
+            create_test_tool("docs", "search"),
+            create_test_tool("docs", "delete_page"),
+            create_test_tool("docs", "publish"),
+        ];
+        let config: McpServerConfig = toml::from_str(
+            r#"
+            command = "docs-mcp"
+            tool_approval = "deny"
+            tool_approvals = { search = "auto", publish = "ask" }
+        "#,
+        )
+        .expect("should deserialize tool approvals");
+
+        let names: Vec<_> = filter_denied_tools(tools, &config)
+            .into_iter()
+            .map(|tool| tool.tool_name)
+            .collect();
+
+        assert_eq!(names, vec!["search".to_string(), "publish".to_string()]);
+    }
+
     #[test]
     fn mcp_init_error_display_prompts_for_github_pat() {
         let server_name = "github";
@@ -1241,6 +1878,12 @@ mod tests {
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                lazy_start: false,
+                tool_aliases: None,
+                tool_approval: None,
+                tool_approvals: None,
+                tool_output_token_limit: None,
+                tool_output_token_limits: None,
             },
             auth_status: McpAuthStatus::Unsupported,
         };
@@ -1287,6 +1930,12 @@ mod tests {
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                lazy_start: false,
+                tool_aliases: None,
+                tool_approval: None,
+                tool_approvals: None,
+                tool_output_token_limit: None,
+                tool_output_token_limits: None,
             },
             auth_status: McpAuthStatus::Unsupported,
         };
@@ -1311,4 +1960,158 @@ mod tests {
             display
         );
     }
+
+    fn lazy_test_client(tx_event: Sender<Event>) -> AsyncManagedClient {
+        AsyncManagedClient::new(
+            "docs".to_string(),
+            McpServerConfig {
+                transport: McpServerTransportConfig::Stdio {
+                    command: "codex-test-missing-docs-server".to_string(),
+                    args: Vec::new(),
+                    env: None,
+                    env_vars: Vec::new(),
+                    cwd: None,
+                },
+                enabled: true,
+                disabled_reason: None,
+                startup_timeout_sec: None,
+                tool_timeout_sec: None,
+                enabled_tools: None,
+                disabled_tools: None,
+                scopes: None,
+                lazy_start: true,
+                tool_aliases: None,
+                tool_approval: None,
+                tool_approvals: None,
+                tool_output_token_limit: None,
+                tool_output_token_limits: None,
+            },
+            OAuthCredentialsStoreMode::default(),
+            None,
+            CancellationToken::new(),
+            tx_event,
+            ElicitationRequestManager::default(),
+            test_sandbox_state(),
+        )
+    }
+
+    fn test_sandbox_state() -> SandboxState {
+        SandboxState {
+            sandbox_policy: SandboxPolicy::new_read_only_policy(),
+            codex_linux_sandbox_exe: None,
+            sandbox_cwd: PathBuf::from("/tmp"),
+        }
+    }
+
+    fn warning_messages(rx_event: &async_channel::Receiver<Event>) -> Vec<String> {
+        std::iter::from_fn(|| rx_event.try_recv().ok())
+            .filter_map(|event| match event.msg {
+                EventMsg::Warning(WarningEvent { message }) => Some(message),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn lazy_servers_are_not_started_before_first_use() {
+        let (tx_event, _rx_event) = async_channel::unbounded();
+        let client = lazy_test_client(tx_event);
+
+        // Sandbox updates are kept for later rather than starting the server.
+        client
+            .notify_sandbox_state_change(&test_sandbox_state())
+            .await
+            .expect("sandbox state update");
+
+        let status = client.status();
+        assert_eq!(status.state, McpServerState::NotStarted);
+        assert_eq!(status.tools, Vec::<String>::new());
+        assert_eq!(status.restarts, 0);
+    }
+
+    #[tokio::test]
+    async fn lazy_servers_start_on_first_use() {
+        let (tx_event, _rx_event) = async_channel::unbounded();
+        let client = lazy_test_client(tx_event);
+
+        // The first use starts the server and waits for the outcome.
+        assert!(client.client().await.is_err());
+
+        assert_eq!(client.status().state, McpServerState::Failed);
+    }
+
+    #[test]
+    fn restart_backoff_doubles_up_to_the_cap() {
+        // `backoff` adds up to 10% jitter either way.
+        for (attempt, secs) in [(0, 1), (1, 2), (2, 4), (3, 8), (4, 16)] {
+            let delay = restart_delay(attempt);
+            let expected = Duration::from_secs(secs);
+            assert!(
+                delay >= expected.mul_f64(0.9) && delay <= expected.mul_f64(1.1),
+                "attempt {attempt}: {delay:?}"
+            );
+        }
+        assert!(restart_delay(5) <= MAX_RESTART_DELAY);
+        assert_eq!(restart_delay(6), MAX_RESTART_DELAY);
+    }
+
+    #[tokio::test]
+    async fn crashed_servers_restart_after_backoff() {
+        let (tx_event, rx_event) = async_channel::unbounded();
+        let client = lazy_test_client(tx_event);
+        client.lock_state().was_ready = true;
+
+        client
+            .on_start_finished(Err(StartupOutcomeError::Failed {
+                error: "server exited".to_string(),
+            }))
+            .await;
+
+        let status = client.status();
+        assert_eq!(status.state, McpServerState::Restarting);
+        assert_eq!(status.restarts, 0);
+        assert_eq!(status.recent_errors.len(), 1);
+        assert_eq!(
+            warning_messages(&rx_event),
+            vec!["MCP server `docs` stopped responding and is being restarted.".to_string()]
+        );
+        // Uses during the backoff fail fast instead of waiting.
+        assert!(client.current_startup().is_err());
+
+        // Once the backoff has passed, the next use starts the server again.
+        client.lock_state().restart_at = Some(Instant::now());
+        assert!(client.current_startup().is_ok());
+        let status = client.status();
+        assert_eq!(status.restarts, 1);
+        assert_ne!(status.state, McpServerState::NotStarted);
+    }
+
+    #[tokio::test]
+    async fn servers_stay_stopped_after_repeated_failed_restarts() {
+        let (tx_event, rx_event) = async_channel::unbounded();
+        let client = lazy_test_client(tx_event);
+
+        for attempt in 0..MAX_RESTART_ATTEMPTS {
+            let mut state = client.lock_state();
+            client.schedule_restart(&mut state, format!("crash {attempt}"));
+            assert_eq!(state.restart_attempts, attempt + 1);
+            assert!(!state.gave_up);
+        }
+        {
+            let mut state = client.lock_state();
+            client.schedule_restart(&mut state, "crash again".to_string());
+            assert!(state.gave_up);
+        }
+
+        let status = client.status();
+        assert_eq!(status.state, McpServerState::Failed);
+        assert_eq!(status.recent_errors.len(), MAX_RECENT_ERRORS);
+        assert_eq!(
+            warning_messages(&rx_event).last().map(String::as_str),
+            Some(
+                "MCP server `docs` was stopped after 5 failed restarts. Run `/mcp status` for details."
+            )
+        );
+        assert!(client.current_startup().is_err());
+    }
 }
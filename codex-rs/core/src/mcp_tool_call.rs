@@ -1,3 +1,4 @@
+use std::collections::VecDeque;
 use std::time::Duration;
 use std::time::Instant;
 
@@ -5,11 +6,17 @@ use tracing::error;
 
 use crate::codex::Session;
 use crate::codex::TurnContext;
+use crate::config::types::McpToolApproval;
 use crate::mcp::CODEX_APPS_MCP_SERVER_NAME;
+use crate::mcp::output_budget::mcp_result_full_text;
+use crate::mcp::output_budget::mcp_result_size;
+use crate::mcp::output_budget::truncate_mcp_result;
 use crate::protocol::EventMsg;
 use crate::protocol::McpInvocation;
 use crate::protocol::McpToolCallBeginEvent;
 use crate::protocol::McpToolCallEndEvent;
+use crate::protocol::McpToolCallProgressEvent;
+use crate::truncate::approx_bytes_for_tokens;
 use codex_protocol::models::FunctionCallOutputPayload;
 use codex_protocol::models::ResponseInputItem;
 use codex_protocol::protocol::AskForApproval;
@@ -18,8 +25,12 @@ use codex_protocol::request_user_input::RequestUserInputArgs;
 use codex_protocol::request_user_input::RequestUserInputQuestion;
 use codex_protocol::request_user_input::RequestUserInputQuestionOption;
 use codex_protocol::request_user_input::RequestUserInputResponse;
+use mcp_types::CallToolResult;
+use mcp_types::ContentBlock;
+use mcp_types::TextContent;
 use mcp_types::ToolAnnotations;
 use std::sync::Arc;
+use tokio::sync::mpsc;
 
 /// Handles the specified tool call dispatches the appropriate
 /// `McpToolCallBegin` and `McpToolCallEnd` events to the `Session`.
@@ -72,10 +83,16 @@ pub(crate) async fn handle_mcp_tool_call(
                     .await;
 
                 let start = Instant::now();
-                let result = sess
-                    .call_tool(&server, &tool_name, arguments_value.clone())
-                    .await
-                    .map_err(|e| format!("tool call error: {e:?}"));
+                let (result, progress_log) = call_tool_reporting_progress(
+                    sess.as_ref(),
+                    turn_context,
+                    &call_id,
+                    &server,
+                    &tool_name,
+                    arguments_value.clone(),
+                )
+                .await;
+                let result = result.map_err(|e| format!("tool call error: {e:?}"));
                 if let Err(e) = &result {
                     tracing::warn!("MCP tool call error: {e:?}");
                 }
@@ -91,7 +108,7 @@ pub(crate) async fn handle_mcp_tool_call(
                     tool_call_end_event.clone(),
                 )
                 .await;
-                result
+                with_progress_log(result, &progress_log)
             }
             McpToolApprovalDecision::Decline => {
                 let message = "user rejected MCP tool call".to_string();
@@ -115,6 +132,16 @@ pub(crate) async fn handle_mcp_tool_call(
                 )
                 .await
             }
+            McpToolApprovalDecision::Deny(message) => {
+                notify_mcp_tool_call_skip(
+                    sess.as_ref(),
+                    turn_context,
+                    &call_id,
+                    invocation,
+                    message,
+                )
+                .await
+            }
         };
 
         let status = if result.is_ok() { "ok" } else { "error" };
@@ -123,6 +150,8 @@ pub(crate) async fn handle_mcp_tool_call(
             .get_otel_manager()
             .counter("codex.mcp.call", 1, &[("status", status)]);
 
+        let result =
+            apply_mcp_output_budget(sess.as_ref(), turn_context, &server, &tool_name, result).await;
         return ResponseInputItem::McpToolCallOutput { call_id, result };
     }
 
@@ -134,10 +163,16 @@ pub(crate) async fn handle_mcp_tool_call(
 
     let start = Instant::now();
     // Perform the tool call.
-    let result = sess
-        .call_tool(&server, &tool_name, arguments_value.clone())
-        .await
-        .map_err(|e| format!("tool call error: {e:?}"));
+    let (result, progress_log) = call_tool_reporting_progress(
+        sess.as_ref(),
+        turn_context,
+        &call_id,
+        &server,
+        &tool_name,
+        arguments_value.clone(),
+    )
+    .await;
+    let result = result.map_err(|e| format!("tool call error: {e:?}"));
     if let Err(e) = &result {
         tracing::warn!("MCP tool call error: {e:?}");
     }
@@ -156,18 +191,157 @@ pub(crate) async fn handle_mcp_tool_call(
         .get_otel_manager()
         .counter("codex.mcp.call", 1, &[("status", status)]);
 
+    let result = with_progress_log(result, &progress_log);
+    let result =
+        apply_mcp_output_budget(sess.as_ref(), turn_context, &server, &tool_name, result).await;
     ResponseInputItem::McpToolCallOutput { call_id, result }
 }
 
+/// Cuts a result down to the configured MCP output budgets before the model
+/// sees it, saving the full result for `read_output`. Clients already got the
+/// full result in `McpToolCallEnd`.
+async fn apply_mcp_output_budget(
+    sess: &Session,
+    turn_context: &TurnContext,
+    server: &str,
+    tool_name: &str,
+    result: Result<CallToolResult, String>,
+) -> Result<CallToolResult, String> {
+    let Ok(call_result) = result else {
+        return result;
+    };
+    let call_limit = sess
+        .services
+        .mcp_connection_manager
+        .read()
+        .await
+        .tool_output_token_limit(server, tool_name)
+        .map(approx_bytes_for_tokens);
+    let turn_limit = turn_context
+        .client
+        .config()
+        .mcp_turn_output_token_limit
+        .map(approx_bytes_for_tokens);
+    if call_limit.is_none() && turn_limit.is_none() {
+        return Ok(call_result);
+    }
+
+    let size = mcp_result_size(&call_result);
+    let mut budget = call_limit.map_or(size, |limit| limit.min(size));
+    if let Some(turn_limit) = turn_limit {
+        budget = sess.take_mcp_output_budget(turn_limit, budget).await;
+    }
+    if budget >= size {
+        return Ok(call_result);
+    }
+
+    let full_text = mcp_result_full_text(&call_result);
+    let saved = sess.services.saved_outputs.lock().await.save(&full_text);
+    let output_id = match saved {
+        Ok(output_id) => Some(output_id),
+        Err(err) => {
+            tracing::warn!("failed to save MCP tool output: {err}");
+            None
+        }
+    };
+    let truncated = truncate_mcp_result(&call_result, output_id.as_deref(), budget);
+    if turn_limit.is_some() {
+        let unused = budget.saturating_sub(mcp_result_size(&truncated));
+        sess.return_mcp_output_budget(unused).await;
+    }
+    Ok(truncated)
+}
+
 async fn notify_mcp_tool_call_event(sess: &Session, turn_context: &TurnContext, event: EventMsg) {
     sess.send_event(turn_context, event).await;
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Progress lines kept per call for the model; older ones are dropped.
+const MAX_PROGRESS_LOG_LINES: usize = 20;
+
+/// Call the tool, emitting an `McpToolCallProgress` event for each progress
+/// notification the server sends while it runs. The notifications are also
+/// returned as a log so the model and the rollout see them, not only the UI.
+async fn call_tool_reporting_progress(
+    sess: &Session,
+    turn_context: &TurnContext,
+    call_id: &str,
+    server: &str,
+    tool_name: &str,
+    arguments: Option<serde_json::Value>,
+) -> (anyhow::Result<CallToolResult>, VecDeque<String>) {
+    let (progress_tx, mut progress_rx) = mpsc::unbounded_channel();
+    let call = sess.call_tool_with_progress(server, tool_name, arguments, Some(progress_tx));
+    tokio::pin!(call);
+    let mut progress_log = VecDeque::new();
+    loop {
+        tokio::select! {
+            result = &mut call => return (result, progress_log),
+            Some(progress) = progress_rx.recv() => {
+                let event = McpToolCallProgressEvent {
+                    call_id: call_id.to_string(),
+                    progress: progress.progress,
+                    total: progress.total,
+                    message: progress.message,
+                };
+                if progress_log.len() == MAX_PROGRESS_LOG_LINES {
+                    progress_log.pop_front();
+                }
+                progress_log.push_back(progress_line(&event));
+                notify_mcp_tool_call_event(sess, turn_context, EventMsg::McpToolCallProgress(event))
+                    .await;
+            }
+        }
+    }
+}
+
+fn progress_line(event: &McpToolCallProgressEvent) -> String {
+    let amount = match event.total {
+        Some(total) => format!("{}/{total}", event.progress),
+        None => event.progress.to_string(),
+    };
+    match &event.message {
+        Some(message) => format!("[{amount}] {message}"),
+        None => format!("[{amount}]"),
+    }
+}
+
+/// Append the progress a server reported to the result the model sees, so a
+/// failed call still shows how far it got.
+fn with_progress_log(
+    result: Result<CallToolResult, String>,
+    progress_log: &VecDeque<String>,
+) -> Result<CallToolResult, String> {
+    if progress_log.is_empty() {
+        return result;
+    }
+    let log = progress_log
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join("\n");
+    match result {
+        Ok(mut call_result) => {
+            call_result
+                .content
+                .push(ContentBlock::TextContent(TextContent {
+                    annotations: None,
+                    text: format!("Progress reported during the call:\n{log}"),
+                    r#type: "text".to_string(),
+                }));
+            Ok(call_result)
+        }
+        Err(error) => Err(format!("{error}\nProgress before the failure:\n{log}")),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 enum McpToolApprovalDecision {
     Accept,
     Decline,
     Cancel,
+    /// Refused without asking; the message explains why.
+    Deny(String),
 }
 
 struct McpToolApprovalMetadata {
@@ -188,6 +362,38 @@ async fn maybe_request_mcp_tool_approval(
     server: &str,
     tool_name: &str,
 ) -> Option<McpToolApprovalDecision> {
+    // A per-server setting overrides the session's approval policy, including
+    // full-access mode.
+    let configured = sess
+        .services
+        .mcp_connection_manager
+        .read()
+        .await
+        .tool_approval(server, tool_name);
+    match configured {
+        Some(McpToolApproval::Auto) => return None,
+        Some(McpToolApproval::Deny) => {
+            return Some(McpToolApprovalDecision::Deny(format!(
+                "MCP tool call blocked: tool_approval for \"{tool_name}\" on server \"{server}\" is set to deny"
+            )));
+        }
+        Some(McpToolApproval::Ask) => {
+            if matches!(turn_context.approval_policy, AskForApproval::Never) {
+                return Some(McpToolApprovalDecision::Deny(format!(
+                    "MCP tool call blocked: \"{tool_name}\" on server \"{server}\" requires approval, but approval_policy is never"
+                )));
+            }
+            let question_id = format!("{MCP_TOOL_APPROVAL_QUESTION_ID_PREFIX}_{call_id}");
+            let question =
+                build_configured_mcp_tool_approval_question(question_id.clone(), server, tool_name);
+            return Some(
+                request_mcp_tool_approval(sess, turn_context, call_id, question, &question_id)
+                    .await,
+            );
+        }
+        None => {}
+    }
+
     if is_full_access_mode(turn_context) {
         return None;
     }
@@ -208,13 +414,23 @@ async fn maybe_request_mcp_tool_approval(
         metadata.connector_name.as_deref(),
         &metadata.annotations,
     );
+    Some(request_mcp_tool_approval(sess, turn_context, call_id, question, &question_id).await)
+}
+
+async fn request_mcp_tool_approval(
+    sess: &Session,
+    turn_context: &TurnContext,
+    call_id: &str,
+    question: RequestUserInputQuestion,
+    question_id: &str,
+) -> McpToolApprovalDecision {
     let args = RequestUserInputArgs {
         questions: vec![question],
     };
     let response = sess
         .request_user_input(turn_context, call_id.to_string(), args)
         .await;
-    Some(parse_mcp_tool_approval_response(response, &question_id))
+    parse_mcp_tool_approval_response(response, question_id)
 }
 
 fn is_full_access_mode(turn_context: &TurnContext) -> bool {
@@ -225,6 +441,14 @@ fn is_full_access_mode(turn_context: &TurnContext) -> bool {
         )
 }
 
+/// Whether the server annotates `tool_name` as read-only. Tools without the
+/// annotation are assumed to change something.
+pub(crate) async fn is_read_only_mcp_tool(sess: &Session, server: &str, tool_name: &str) -> bool {
+    lookup_mcp_tool_metadata(sess, server, tool_name)
+        .await
+        .is_some_and(|metadata| metadata.annotations.read_only_hint == Some(true))
+}
+
 async fn lookup_mcp_tool_metadata(
     sess: &Session,
     server: &str,
@@ -284,23 +508,46 @@ fn build_mcp_tool_approval_question(
         question,
         is_other: false,
         is_secret: false,
-        options: Some(vec![
-            RequestUserInputQuestionOption {
-                label: MCP_TOOL_APPROVAL_ACCEPT.to_string(),
-                description: "Run the tool and continue.".to_string(),
-            },
-            RequestUserInputQuestionOption {
-                label: MCP_TOOL_APPROVAL_DECLINE.to_string(),
-                description: "Decline this tool call and continue.".to_string(),
-            },
-            RequestUserInputQuestionOption {
-                label: MCP_TOOL_APPROVAL_CANCEL.to_string(),
-                description: "Cancel this tool call".to_string(),
-            },
-        ]),
+        options: Some(mcp_tool_approval_options()),
+    }
+}
+
+/// Question for a server whose `tool_approval` is `ask`; names the setting so
+/// the user knows why they are being asked.
+fn build_configured_mcp_tool_approval_question(
+    question_id: String,
+    server: &str,
+    tool_name: &str,
+) -> RequestUserInputQuestion {
+    RequestUserInputQuestion {
+        id: question_id,
+        header: "Approve MCP tool call?".to_string(),
+        question: format!(
+            "The {server} MCP server wants to run the tool \"{tool_name}\". This server is configured with tool_approval = \"ask\". Allow this action?"
+        ),
+        is_other: false,
+        is_secret: false,
+        options: Some(mcp_tool_approval_options()),
     }
 }
 
+fn mcp_tool_approval_options() -> Vec<RequestUserInputQuestionOption> {
+    vec![
+        RequestUserInputQuestionOption {
+            label: MCP_TOOL_APPROVAL_ACCEPT.to_string(),
+            description: "Run the tool and continue.".to_string(),
+        },
+        RequestUserInputQuestionOption {
+            label: MCP_TOOL_APPROVAL_DECLINE.to_string(),
+            description: "Decline this tool call and continue.".to_string(),
+        },
+        RequestUserInputQuestionOption {
+            label: MCP_TOOL_APPROVAL_CANCEL.to_string(),
+            description: "Cancel this tool call".to_string(),
+        },
+    ]
+}
+
 fn parse_mcp_tool_approval_response(
     response: Option<RequestUserInputResponse>,
     question_id: &str,
@@ -363,6 +610,52 @@ mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
 
+    #[test]
+    fn progress_log_is_appended_to_the_model_result() {
+        let progress_log = VecDeque::from([
+            progress_line(&McpToolCallProgressEvent {
+                call_id: "call-1".to_string(),
+                progress: 1.0,
+                total: Some(3.0),
+                message: Some("indexing".to_string()),
+            }),
+            progress_line(&McpToolCallProgressEvent {
+                call_id: "call-1".to_string(),
+                progress: 2.0,
+                total: None,
+                message: None,
+            }),
+        ]);
+
+        let failed =
+            with_progress_log(Err("tool call error: timed out".to_string()), &progress_log);
+        assert_eq!(
+            failed,
+            Err(
+                "tool call error: timed out\nProgress before the failure:\n[1/3] indexing\n[2]"
+                    .to_string()
+            )
+        );
+
+        let succeeded = with_progress_log(
+            Ok(CallToolResult {
+                content: Vec::new(),
+                is_error: Some(false),
+                structured_content: None,
+            }),
+            &progress_log,
+        )
+        .expect("successful result");
+        assert_eq!(
+            succeeded.content,
+            vec![ContentBlock::TextContent(TextContent {
+                annotations: None,
+                text: "Progress reported during the call:\n[1/3] indexing\n[2]".to_string(),
+                r#type: "text".to_string(),
+            })]
+        );
+    }
+
     fn annotations(
         read_only: Option<bool>,
         destructive: Option<bool>,
@@ -394,4 +687,15 @@ mod tests {
         let annotations = annotations(Some(true), Some(true), Some(true));
         assert_eq!(requires_mcp_tool_approval(&annotations), false);
     }
+
+    #[test]
+    fn configured_approval_question_names_server_and_setting() {
+        let question =
+            build_configured_mcp_tool_approval_question("q".to_string(), "docs", "delete_page");
+        assert_eq!(question.header, "Approve MCP tool call?");
+        assert_eq!(
+            question.question,
+            "The docs MCP server wants to run the tool \"delete_page\". This server is configured with tool_approval = \"ask\". Allow this action?"
+        );
+    }
 }
@@ -15,6 +15,9 @@
 //! that writes up to `PIPE_BUF` bytes are atomic in that case.
 //! Note: `conversation_id` stores the thread id; the field name is preserved for
 //! backwards compatibility with existing history files.
+//!
+//! When `session_encryption` is on, each line is sealed with the session key
+//! the same way rollout lines are, and lookups decrypt it again.
 
 use std::fs::File;
 use std::fs::OpenOptions;
@@ -37,6 +40,8 @@ use tokio::io::AsyncReadExt;
 
 use crate::config::Config;
 use crate::config::types::HistoryPersistence;
+use crate::rollout::encryption::decode_rollout_line;
+use crate::rollout::encryption::session_cipher;
 
 use codex_protocol::ThreadId;
 #[cfg(unix)]
@@ -106,6 +111,9 @@ pub(crate) async fn append_entry(
     };
     let mut line = serde_json::to_string(&entry)
         .map_err(|e| std::io::Error::other(format!("failed to serialise history entry: {e}")))?;
+    if let Some(cipher) = session_cipher(&config.codex_home, config.session_encryption)? {
+        line = cipher.encrypt_line(&line)?;
+    }
     line.push('\n');
 
     // Open the history file for read/write access (append-only on Unix).
@@ -358,6 +366,13 @@ fn lookup_history_entry(path: &Path, log_id: u64, offset: usize) -> Option<Histo
                     };
 
                     if idx == offset {
+                        let line = match decode_rollout_line(&line) {
+                            Ok(line) => line,
+                            Err(e) => {
+                                tracing::warn!(error = %e, "failed to decrypt history entry");
+                                return None;
+                            }
+                        };
                         match serde_json::from_str::<HistoryEntry>(&line) {
                             Ok(entry) => return Some(entry),
                             Err(e) => {
@@ -6,7 +6,9 @@
 //!      key. These override or extend the defaults at runtime.
 
 use crate::auth::AuthMode;
+use crate::error::CodexErr;
 use crate::error::EnvVarError;
+use crate::util::backoff;
 use codex_api::Provider as ApiProvider;
 use codex_api::WireApi as ApiWireApi;
 use codex_api::is_azure_responses_wire_base_url;
@@ -24,6 +26,8 @@ use std::time::Duration;
 const DEFAULT_STREAM_IDLE_TIMEOUT_MS: u64 = 300_000;
 const DEFAULT_STREAM_MAX_RETRIES: u64 = 5;
 const DEFAULT_REQUEST_MAX_RETRIES: u64 = 4;
+const DEFAULT_RETRY_BASE_DELAY_MS: u64 = 200;
+const DEFAULT_RETRY_MAX_DELAY_MS: u64 = 30_000;
 /// Hard cap for user-configured `stream_max_retries`.
 const MAX_STREAM_MAX_RETRIES: u64 = 100;
 /// Hard cap for user-configured `request_max_retries`.
@@ -47,6 +51,9 @@ pub enum WireApi {
     /// Regular Chat Completions compatible with `/v1/chat/completions`.
     #[default]
     Chat,
+
+    /// The Anthropic Messages API at `/v1/messages`.
+    Anthropic,
 }
 
 /// Serializable representation of a provider definition.
@@ -69,6 +76,16 @@ pub struct ModelProviderInfo {
     /// this may be necessary when using this programmatically.
     pub experimental_bearer_token: Option<String>,
 
+    /// Header that carries the API key or token. Defaults to `Authorization`,
+    /// or `x-api-key` for `wire_api = "anthropic"`.
+    pub auth_header: Option<String>,
+
+    /// Scheme written before the token in `auth_header`. Defaults to
+    /// `Bearer` (no scheme for `wire_api = "anthropic"`); set to an empty
+    /// string to send the raw token (for example `auth_header = "api-key"`,
+    /// `auth_scheme = ""`).
+    pub auth_scheme: Option<String>,
+
     /// Which wire protocol this provider expects.
     #[serde(default)]
     pub wire_api: WireApi,
@@ -86,12 +103,28 @@ pub struct ModelProviderInfo {
     /// value is empty, the header will not be included in the request.
     pub env_http_headers: Option<HashMap<String, String>>,
 
+    /// Header that also carries the prompt cache key, for providers that
+    /// route requests to their prompt cache by a header rather than the
+    /// `prompt_cache_key` request field.
+    pub prompt_cache_key_header: Option<String>,
+
     /// Maximum number of times to retry a failed HTTP request to this provider.
     pub request_max_retries: Option<u64>,
 
     /// Number of times to retry reconnecting a dropped streaming response before failing.
     pub stream_max_retries: Option<u64>,
 
+    /// Delay (in milliseconds) before the first retry of a request or stream;
+    /// each further retry doubles it. Defaults to 200.
+    pub retry_base_delay_ms: Option<u64>,
+
+    /// Upper bound (in milliseconds) on the delay before any single retry.
+    /// Defaults to 30000.
+    pub retry_max_delay_ms: Option<u64>,
+
+    /// HTTP status codes that are retried. Defaults to every 5xx status.
+    pub retry_status_codes: Option<Vec<u16>>,
+
     /// Idle timeout (in milliseconds) to wait for activity on a streaming response before treating
     /// the connection as lost.
     pub stream_idle_timeout_ms: Option<u64>,
@@ -149,12 +182,18 @@ impl ModelProviderInfo {
             .unwrap_or_else(|| default_base_url.to_string());
 
         let headers = self.build_header_map()?;
+        let (retry_5xx, retry_statuses) = match &self.retry_status_codes {
+            Some(codes) => (false, codes.clone()),
+            None => (true, Vec::new()),
+        };
         let retry = ApiRetryConfig {
             max_attempts: self.request_max_retries(),
-            base_delay: Duration::from_millis(200),
+            base_delay: self.retry_base_delay(),
+            max_delay: self.retry_max_delay(),
             retry_429: false,
-            retry_5xx: true,
+            retry_5xx,
             retry_transport: true,
+            retry_statuses,
         };
 
         Ok(ApiProvider {
@@ -164,6 +203,7 @@ impl ModelProviderInfo {
             wire: match self.wire_api {
                 WireApi::Responses => ApiWireApi::Responses,
                 WireApi::Chat => ApiWireApi::Chat,
+                WireApi::Anthropic => ApiWireApi::Anthropic,
             },
             headers,
             retry,
@@ -175,6 +215,7 @@ impl ModelProviderInfo {
         let wire = match self.wire_api {
             WireApi::Responses => ApiWireApi::Responses,
             WireApi::Chat => ApiWireApi::Chat,
+            WireApi::Anthropic => ApiWireApi::Anthropic,
         };
 
         is_azure_responses_wire_base_url(wire, &self.name, self.base_url.as_deref())
@@ -206,6 +247,28 @@ impl ModelProviderInfo {
         }
     }
 
+    /// Header name and scheme used to send this provider's credential.
+    pub(crate) fn auth_header_format(&self) -> crate::error::Result<(HeaderName, Option<String>)> {
+        let name = match self.auth_header.as_deref() {
+            Some(name) => HeaderName::try_from(name).map_err(|err| {
+                crate::error::CodexErr::InvalidRequest(format!(
+                    "invalid auth_header `{name}` for provider `{}`: {err}",
+                    self.name
+                ))
+            })?,
+            // The Messages API takes the raw key in `x-api-key`.
+            None if self.wire_api == WireApi::Anthropic => HeaderName::from_static("x-api-key"),
+            None => http::header::AUTHORIZATION,
+        };
+        let scheme = match self.auth_scheme.as_deref() {
+            Some(scheme) if scheme.trim().is_empty() => None,
+            Some(scheme) => Some(scheme.trim().to_string()),
+            None if self.wire_api == WireApi::Anthropic && self.auth_header.is_none() => None,
+            None => Some("Bearer".to_string()),
+        };
+        Ok((name, scheme))
+    }
+
     /// Effective maximum number of request retries for this provider.
     pub fn request_max_retries(&self) -> u64 {
         self.request_max_retries
@@ -226,6 +289,34 @@ impl ModelProviderInfo {
             .map(Duration::from_millis)
             .unwrap_or(Duration::from_millis(DEFAULT_STREAM_IDLE_TIMEOUT_MS))
     }
+
+    /// Effective delay before the first retry.
+    pub fn retry_base_delay(&self) -> Duration {
+        self.retry_base_delay_ms
+            .map(Duration::from_millis)
+            .unwrap_or(Duration::from_millis(DEFAULT_RETRY_BASE_DELAY_MS))
+    }
+
+    /// Effective upper bound on the delay before any single retry.
+    pub fn retry_max_delay(&self) -> Duration {
+        self.retry_max_delay_ms
+            .map(Duration::from_millis)
+            .unwrap_or(Duration::from_millis(DEFAULT_RETRY_MAX_DELAY_MS))
+    }
+
+    /// Exponential backoff before retry number `attempt` (starting at 1).
+    pub(crate) fn retry_delay(&self, attempt: u64) -> Duration {
+        backoff(self.retry_base_delay(), attempt).min(self.retry_max_delay())
+    }
+
+    /// Whether a failed request or stream to this provider is worth retrying.
+    /// HTTP errors are limited to `retry_status_codes` when it is set.
+    pub(crate) fn is_retryable(&self, err: &CodexErr) -> bool {
+        match (err, &self.retry_status_codes) {
+            (CodexErr::UnexpectedStatus(err), Some(codes)) => codes.contains(&err.status.as_u16()),
+            _ => err.is_retryable(),
+        }
+    }
     pub fn create_openai_provider() -> ModelProviderInfo {
         ModelProviderInfo {
             name: OPENAI_PROVIDER_NAME.into(),
@@ -240,6 +331,9 @@ impl ModelProviderInfo {
             env_key: None,
             env_key_instructions: None,
             experimental_bearer_token: None,
+            auth_header: None,
+            auth_scheme: None,
+            prompt_cache_key_header: None,
             wire_api: WireApi::Responses,
             query_params: None,
             http_headers: Some(
@@ -261,6 +355,9 @@ impl ModelProviderInfo {
             // Use global defaults for retry/timeout unless overridden in config.toml.
             request_max_retries: None,
             stream_max_retries: None,
+            retry_base_delay_ms: None,
+            retry_max_delay_ms: None,
+            retry_status_codes: None,
             stream_idle_timeout_ms: None,
             requires_openai_auth: true,
             supports_websockets: true,
@@ -334,12 +431,18 @@ pub fn create_oss_provider_with_base_url(base_url: &str, wire_api: WireApi) -> M
         env_key: None,
         env_key_instructions: None,
         experimental_bearer_token: None,
+        auth_header: None,
+        auth_scheme: None,
+        prompt_cache_key_header: None,
         wire_api,
         query_params: None,
         http_headers: None,
         env_http_headers: None,
         request_max_retries: None,
         stream_max_retries: None,
+        retry_base_delay_ms: None,
+        retry_max_delay_ms: None,
+        retry_status_codes: None,
         stream_idle_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
@@ -363,12 +466,18 @@ base_url = "http://localhost:11434/v1"
             env_key: None,
             env_key_instructions: None,
             experimental_bearer_token: None,
+            auth_header: None,
+            auth_scheme: None,
+            prompt_cache_key_header: None,
             wire_api: WireApi::Chat,
             query_params: None,
             http_headers: None,
             env_http_headers: None,
             request_max_retries: None,
             stream_max_retries: None,
+            retry_base_delay_ms: None,
+            retry_max_delay_ms: None,
+            retry_status_codes: None,
             stream_idle_timeout_ms: None,
             requires_openai_auth: false,
             supports_websockets: false,
@@ -378,6 +487,38 @@ base_url = "http://localhost:11434/v1"
         assert_eq!(expected_provider, provider);
     }
 
+    #[test]
+    fn retry_policy_follows_provider_config() {
+        let provider: ModelProviderInfo = toml::from_str(
+            r#"
+name = "Gateway"
+retry_base_delay_ms = 1000
+retry_max_delay_ms = 5000
+retry_status_codes = [429, 503]
+        "#,
+        )
+        .unwrap();
+        let status_error = |status| {
+            CodexErr::UnexpectedStatus(crate::error::UnexpectedResponseError {
+                status,
+                body: String::new(),
+                url: None,
+                request_id: None,
+            })
+        };
+
+        assert!(provider.is_retryable(&status_error(http::StatusCode::TOO_MANY_REQUESTS)));
+        assert!(provider.is_retryable(&status_error(http::StatusCode::SERVICE_UNAVAILABLE)));
+        assert!(!provider.is_retryable(&status_error(http::StatusCode::INTERNAL_SERVER_ERROR)));
+        assert!(provider.is_retryable(&CodexErr::Stream("closed".to_string(), None)));
+        assert_eq!(provider.retry_delay(10), Duration::from_millis(5000));
+
+        let api_retry = provider.to_api_provider(None).unwrap().retry;
+        assert_eq!(api_retry.base_delay, Duration::from_millis(1000));
+        assert!(!api_retry.retry_5xx);
+        assert_eq!(api_retry.retry_statuses, vec![429, 503]);
+    }
+
     #[test]
     fn test_deserialize_azure_model_provider_toml() {
         let azure_provider_toml = r#"
@@ -392,6 +533,9 @@ query_params = { api-version = "2025-04-01-preview" }
             env_key: Some("AZURE_OPENAI_API_KEY".into()),
             env_key_instructions: None,
             experimental_bearer_token: None,
+            auth_header: None,
+            auth_scheme: None,
+            prompt_cache_key_header: None,
             wire_api: WireApi::Chat,
             query_params: Some(maplit::hashmap! {
                 "api-version".to_string() => "2025-04-01-preview".to_string(),
@@ -400,6 +544,9 @@ query_params = { api-version = "2025-04-01-preview" }
             env_http_headers: None,
             request_max_retries: None,
             stream_max_retries: None,
+            retry_base_delay_ms: None,
+            retry_max_delay_ms: None,
+            retry_status_codes: None,
             stream_idle_timeout_ms: None,
             requires_openai_auth: false,
             supports_websockets: false,
@@ -424,6 +571,9 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
             env_key: Some("API_KEY".into()),
             env_key_instructions: None,
             experimental_bearer_token: None,
+            auth_header: None,
+            auth_scheme: None,
+            prompt_cache_key_header: None,
             wire_api: WireApi::Chat,
             query_params: None,
             http_headers: Some(maplit::hashmap! {
@@ -434,6 +584,9 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
             }),
             request_max_retries: None,
             stream_max_retries: None,
+            retry_base_delay_ms: None,
+            retry_max_delay_ms: None,
+            retry_status_codes: None,
             stream_idle_timeout_ms: None,
             requires_openai_auth: false,
             supports_websockets: false,
@@ -442,4 +595,22 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
         let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
         assert_eq!(expected_provider, provider);
     }
+
+    #[test]
+    fn anthropic_provider_sends_raw_key_in_x_api_key() {
+        let provider: ModelProviderInfo = toml::from_str(
+            r#"
+name = "Anthropic"
+base_url = "https://api.anthropic.com/v1"
+env_key = "ANTHROPIC_API_KEY"
+wire_api = "anthropic"
+        "#,
+        )
+        .unwrap();
+
+        assert_eq!(provider.wire_api, WireApi::Anthropic);
+        let (name, scheme) = provider.auth_header_format().unwrap();
+        assert_eq!(name.as_str(), "x-api-key");
+        assert_eq!(scheme, None);
+    }
 }
@@ -31,6 +31,24 @@ const MODEL_CACHE_FILE: &str = "models_cache.json";
 const DEFAULT_MODEL_CACHE_TTL: Duration = Duration::from_secs(300);
 const MODELS_REFRESH_TIMEOUT: Duration = Duration::from_secs(5);
 
+/// Asks `provider` whether it still serves `model`, for the startup check of
+/// a pinned `model_snapshot`.
+pub(crate) async fn model_served(
+    provider: &ModelProviderInfo,
+    auth_manager: &AuthManager,
+    model: &str,
+) -> CoreResult<bool> {
+    let auth = auth_manager.auth().await;
+    let api_provider = provider.to_api_provider(auth_manager.get_internal_auth_mode())?;
+    let api_auth = auth_provider_from_auth(auth, provider)?;
+    let transport = ReqwestTransport::new(build_reqwest_client());
+    let client = ModelsClient::new(transport, api_provider, api_auth);
+    timeout(MODELS_REFRESH_TIMEOUT, client.model_served(model))
+        .await
+        .map_err(|_| CodexErr::Timeout)?
+        .map_err(map_api_error)
+}
+
 /// Strategy for refreshing available models.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RefreshStrategy {
@@ -428,12 +446,18 @@ mod tests {
             env_key: None,
             env_key_instructions: None,
             experimental_bearer_token: None,
+            auth_header: None,
+            auth_scheme: None,
+            prompt_cache_key_header: None,
             wire_api: WireApi::Responses,
             query_params: None,
             http_headers: None,
             env_http_headers: None,
             request_max_retries: Some(0),
             stream_max_retries: Some(0),
+            retry_base_delay_ms: None,
+            retry_max_delay_ms: None,
+            retry_status_codes: None,
             stream_idle_timeout_ms: Some(5_000),
             requires_openai_auth: false,
             supports_websockets: false,
@@ -0,0 +1,219 @@
+//! Full output of commands that was too long to show the model.
+//!
+//! With the `output_paging` feature enabled, command output over the turn's
+//! truncation budget is written to `CODEX_HOME/outputs/<thread_id>/` and the
+//! model is shown whole lines from its head and tail with a marker naming the
+//! lines left out. The `read_output` tool reads any range of the saved output
+//! by line number, so the middle of a long build log is one call away. Saved
+//! output is deleted when the session shuts down, and is sealed with the
+//! session key when `session_encryption` is on.
+
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_protocol::ThreadId;
+
+use crate::config::types::SessionEncryptionMode;
+use crate::rollout::encryption::decode_rollout_line;
+use crate::rollout::encryption::session_cipher;
+use crate::truncate::TruncationPolicy;
+use crate::truncate::truncate_text;
+
+/// Name of the directory (under `CODEX_HOME`) that holds per-session output.
+pub(crate) const OUTPUTS_SUBDIR: &str = "outputs";
+
+const OUTPUT_ID_PREFIX: &str = "out-";
+
+#[derive(Debug)]
+pub(crate) struct SessionOutputs {
+    codex_home: PathBuf,
+    root: PathBuf,
+    encryption: SessionEncryptionMode,
+    next_id: u64,
+}
+
+/// Lines read back from a saved output, numbered from 1.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct OutputPage {
+    pub lines: Vec<(usize, String)>,
+    pub total_lines: usize,
+}
+
+impl SessionOutputs {
+    pub(crate) fn new(
+        codex_home: &Path,
+        thread_id: ThreadId,
+        encryption: SessionEncryptionMode,
+    ) -> Self {
+        Self {
+            codex_home: codex_home.to_path_buf(),
+            root: codex_home.join(OUTPUTS_SUBDIR).join(thread_id.to_string()),
+            encryption,
+            next_id: 1,
+        }
+    }
+
+    /// Writes `output` to disk and returns the id to read it back with.
+    pub(crate) fn save(&mut self, output: &str) -> io::Result<String> {
+        // Fails, rather than writing plain text, when the key cannot be loaded.
+        let sealed = match session_cipher(&self.codex_home, self.encryption)? {
+            Some(cipher) => Some(cipher.encrypt_line(output)?),
+            None => None,
+        };
+        std::fs::create_dir_all(&self.root)?;
+        // Skip ids left behind by an earlier run of a resumed session.
+        while self.path_for(self.next_id).exists() {
+            self.next_id += 1;
+        }
+        std::fs::write(
+            self.path_for(self.next_id),
+            sealed.as_deref().unwrap_or(output),
+        )?;
+        let id = format!("{OUTPUT_ID_PREFIX}{}", self.next_id);
+        self.next_id += 1;
+        Ok(id)
+    }
+
+    /// Lines `start_line..=end_line` of saved output `id`, clamped to the
+    /// lines it has.
+    pub(crate) fn read(
+        &self,
+        id: &str,
+        start_line: usize,
+        end_line: usize,
+    ) -> io::Result<OutputPage> {
+        let number = id
+            .strip_prefix(OUTPUT_ID_PREFIX)
+            .and_then(|number| number.parse::<u64>().ok())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("{id} is not an output id; expected one like \"{OUTPUT_ID_PREFIX}1\""),
+                )
+            })?;
+        let saved = std::fs::read_to_string(self.path_for(number)).map_err(|err| {
+            if err.kind() == io::ErrorKind::NotFound {
+                io::Error::new(err.kind(), format!("no saved output {id}"))
+            } else {
+                err
+            }
+        })?;
+        let output = decode_rollout_line(&saved)?;
+        let total_lines = output.lines().count();
+        let start = start_line.max(1);
+        let lines = output
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line))
+            .skip(start - 1)
+            .take(end_line.saturating_add(1).saturating_sub(start))
+            .map(|(number, line)| (number, line.to_string()))
+            .collect();
+        Ok(OutputPage { lines, total_lines })
+    }
+
+    pub(crate) fn purge(&mut self) -> io::Result<()> {
+        match std::fs::remove_dir_all(&self.root) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn path_for(&self, number: u64) -> PathBuf {
+        self.root.join(format!("{number}.log"))
+    }
+}
+
+/// `content`, saved as `output_id`, cut down to whole lines from its head and
+/// tail that fit `policy`, with a marker saying which lines were left out.
+pub(crate) fn page_for_model(content: &str, output_id: &str, policy: TruncationPolicy) -> String {
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let total_lines = lines.len();
+    let total_bytes = content.len();
+    let budget = policy.byte_budget();
+    let head_budget = budget / 2;
+    let tail_budget = budget - head_budget;
+
+    let mut head = 0;
+    let mut head_bytes = 0;
+    while head < total_lines && head_bytes + lines[head].len() <= head_budget {
+        head_bytes += lines[head].len();
+        head += 1;
+    }
+    let mut tail_start = total_lines;
+    let mut tail_bytes = 0;
+    while tail_start > head && tail_bytes + lines[tail_start - 1].len() <= tail_budget {
+        tail_start -= 1;
+        tail_bytes += lines[tail_start].len();
+    }
+
+    if head == 0 || tail_start == total_lines {
+        // A single line is larger than half the budget, e.g. minified output,
+        // so fall back to cutting by size.
+        return format!(
+            "Output is {total_lines} lines ({total_bytes} bytes) and was truncated. The full output is saved as {output_id}; read it by line number with read_output.\n{}",
+            truncate_text(content, policy)
+        );
+    }
+
+    let omitted_bytes = total_bytes - head_bytes - tail_bytes;
+    let mut paged = format!(
+        "Output is {total_lines} lines ({total_bytes} bytes); showing lines 1-{head} and {}-{total_lines}. The full output is saved as {output_id}.\n",
+        tail_start + 1
+    );
+    paged.extend(lines[..head].iter().copied());
+    paged.push_str(&format!(
+        "[... lines {}-{tail_start} ({omitted_bytes} bytes) omitted; read them with read_output using output_id \"{output_id}\" ...]\n",
+        head + 1
+    ));
+    paged.extend(lines[tail_start..].iter().copied());
+    paged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    #[test]
+    fn pages_keep_whole_head_and_tail_lines() {
+        let content: String = (1..=100).map(|n| format!("line {n:03}\n")).collect();
+        // Each line is 9 bytes, so 40 bytes fit four lines on each side.
+        let paged = page_for_model(&content, "out-1", TruncationPolicy::Bytes(80));
+        assert_eq!(
+            paged,
+            "Output is 100 lines (900 bytes); showing lines 1-4 and 97-100. The full output is saved as out-1.\n\
+             line 001\nline 002\nline 003\nline 004\n\
+             [... lines 5-96 (828 bytes) omitted; read them with read_output using output_id \"out-1\" ...]\n\
+             line 097\nline 098\nline 099\nline 100\n"
+        );
+    }
+
+    #[test]
+    fn saved_output_reads_back_by_line_range() {
+        let codex_home = tempdir().expect("codex home");
+        let mut outputs = SessionOutputs::new(
+            codex_home.path(),
+            ThreadId::new(),
+            SessionEncryptionMode::Off,
+        );
+        let id = outputs.save("a\nb\nc\nd\n").expect("save");
+        assert_eq!(id, "out-1");
+
+        assert_eq!(
+            outputs.read(&id, 2, 3).expect("read"),
+            OutputPage {
+                lines: vec![(2, "b".to_string()), (3, "c".to_string())],
+                total_lines: 4,
+            }
+        );
+        assert_eq!(outputs.read(&id, 4, 100).expect("read").lines.len(), 1);
+        assert!(outputs.read("../secrets", 1, 1).is_err());
+
+        outputs.purge().expect("purge");
+        assert!(outputs.read(&id, 1, 1).is_err());
+    }
+}
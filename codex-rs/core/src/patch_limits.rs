@@ -0,0 +1,194 @@
+//! Refuse patches that would silently mangle binary or very large files.
+//!
+//! Runs on the parsed patch before it is verified, since verification reads
+//! every file the patch updates into memory. Binary files and files over
+//! `patch_limits.max_file_bytes` are refused outright; files longer than
+//! `patch_limits.max_rewrite_lines` may only be changed by targeted hunks, not
+//! replaced wholesale with `*** Add File` or with hunks covering nearly all of
+//! them. Each refusal tells the model how to make the edit instead.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use codex_apply_patch::Hunk;
+use codex_apply_patch::parse_patch;
+
+use crate::config::types::PatchLimitsConfig;
+
+/// Bytes inspected when deciding whether a file is binary.
+const BINARY_SNIFF_BYTES: usize = 8 * 1024;
+/// Share of a file's lines, in percent, that a patch must replace to count as
+/// rewriting it.
+const REWRITE_PERCENT: usize = 90;
+
+/// Check `patch` against `limits`. An unparsable patch passes, leaving
+/// verification to report the problem.
+pub(crate) fn check_patch_limits(
+    patch: &str,
+    cwd: &Path,
+    limits: &PatchLimitsConfig,
+) -> Result<(), String> {
+    let Ok(args) = parse_patch(patch) else {
+        return Ok(());
+    };
+    let cwd = match &args.workdir {
+        Some(workdir) => cwd.join(workdir),
+        None => cwd.to_path_buf(),
+    };
+    for hunk in &args.hunks {
+        let path = hunk.resolve_path(&cwd);
+        let display = path.strip_prefix(&cwd).unwrap_or(&path).display();
+        // Deleting a file is always allowed, whatever its size or contents.
+        let replaced_lines = match hunk {
+            Hunk::DeleteFile { .. } => continue,
+            Hunk::AddFile { .. } => None,
+            Hunk::UpdateFile { chunks, .. } => Some(
+                chunks
+                    .iter()
+                    .map(|chunk| replaced_line_count(&chunk.old_lines, &chunk.new_lines))
+                    .sum::<usize>(),
+            ),
+        };
+        let Ok(metadata) = std::fs::metadata(&path) else {
+            continue;
+        };
+        if !metadata.is_file() {
+            continue;
+        }
+        if limits.max_file_bytes > 0 && metadata.len() > limits.max_file_bytes {
+            return Err(format!(
+                "{display} is {} bytes, over the {} byte limit for apply_patch \
+                 (patch_limits.max_file_bytes). Make a targeted edit with a shell command \
+                 instead, for example `sed -i` on the lines that change.",
+                metadata.len(),
+                limits.max_file_bytes
+            ));
+        }
+        if is_binary_file(&path) {
+            return Err(format!(
+                "{display} is a binary file; apply_patch only edits text. Regenerate it with \
+                 the tool that produces it, or use a shell command."
+            ));
+        }
+        if limits.max_rewrite_lines == 0 {
+            continue;
+        }
+        let Ok(contents) = std::fs::read(&path) else {
+            continue;
+        };
+        let lines = contents.iter().filter(|byte| **byte == b'\n').count()
+            + usize::from(!contents.is_empty() && !contents.ends_with(b"\n"));
+        if lines <= limits.max_rewrite_lines {
+            continue;
+        }
+        let rewrite = match replaced_lines {
+            None => Some(format!(
+                "*** Add File would replace all {lines} lines of the existing {display}"
+            )),
+            Some(replaced) if replaced * 100 >= lines * REWRITE_PERCENT => Some(format!(
+                "this patch would replace {replaced} of the {lines} lines of {display}"
+            )),
+            Some(_) => None,
+        };
+        if let Some(rewrite) = rewrite {
+            return Err(format!(
+                "{rewrite}. Files over {} lines (patch_limits.max_rewrite_lines) cannot be \
+                 rewritten wholesale; send *** Update File hunks covering only the lines that \
+                 change.",
+                limits.max_rewrite_lines
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Lines of `old` replaced in `new`, ignoring the context they share at
+/// either end.
+fn replaced_line_count(old: &[String], new: &[String]) -> usize {
+    let prefix = old
+        .iter()
+        .zip(new.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    old.len() - prefix - suffix
+}
+
+/// A file is binary if its first bytes contain NUL or are not UTF-8. A
+/// character cut off at the end of the sample does not count.
+fn is_binary_file(path: &Path) -> bool {
+    let Ok(file) = File::open(path) else {
+        return false;
+    };
+    let mut sample = Vec::with_capacity(BINARY_SNIFF_BYTES);
+    if file
+        .take(BINARY_SNIFF_BYTES as u64)
+        .read_to_end(&mut sample)
+        .is_err()
+    {
+        return false;
+    }
+    sample.contains(&0) || std::str::from_utf8(&sample).is_err_and(|err| err.error_len().is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const LIMITS: PatchLimitsConfig = PatchLimitsConfig {
+        max_file_bytes: 64 * 1024,
+        max_rewrite_lines: 10,
+    };
+
+    fn numbered_lines(count: usize) -> String {
+        (1..=count).map(|n| format!("line {n}\n")).collect()
+    }
+
+    #[test]
+    fn refuses_binary_large_and_rewritten_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let cwd = dir.path();
+        std::fs::write(cwd.join("logo.png"), b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR").expect("write");
+        std::fs::write(cwd.join("big.txt"), "x".repeat(65 * 1024)).expect("write");
+        std::fs::write(cwd.join("long.txt"), numbered_lines(20)).expect("write");
+        std::fs::write(cwd.join("short.txt"), numbered_lines(5)).expect("write");
+
+        let check = |patch: &str| check_patch_limits(patch, cwd, &LIMITS);
+
+        let binary = check("*** Begin Patch\n*** Update File: logo.png\n@@\n-a\n+b\n*** End Patch");
+        assert!(binary.is_err_and(|err| err.starts_with("logo.png is a binary file")));
+
+        let large = check("*** Begin Patch\n*** Update File: big.txt\n@@\n-x\n+y\n*** End Patch");
+        assert!(large.is_err_and(|err| err.contains("over the 65536 byte limit")));
+
+        let add_over = check("*** Begin Patch\n*** Add File: long.txt\n+new\n*** End Patch");
+        assert!(add_over.is_err_and(|err| {
+            err.starts_with("*** Add File would replace all 20 lines of the existing long.txt.")
+        }));
+
+        let whole: String = (1..=20)
+            .map(|n| format!("-line {n}\n+LINE {n}\n"))
+            .collect();
+        let rewrite = check(&format!(
+            "*** Begin Patch\n*** Update File: long.txt\n@@\n{whole}*** End Patch"
+        ));
+        assert!(rewrite.is_err_and(|err| err.contains("replace 20 of the 20 lines")));
+
+        // Targeted hunks, short files, deletions, and new files are fine.
+        for patch in [
+            "*** Begin Patch\n*** Update File: long.txt\n@@\n line 1\n-line 2\n+LINE 2\n line 3\n*** End Patch",
+            "*** Begin Patch\n*** Add File: short.txt\n+new\n*** End Patch",
+            "*** Begin Patch\n*** Delete File: logo.png\n*** End Patch",
+            "*** Begin Patch\n*** Add File: new.txt\n+new\n*** End Patch",
+        ] {
+            assert_eq!(check(patch), Ok(()), "{patch}");
+        }
+    }
+}
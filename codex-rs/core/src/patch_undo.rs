@@ -0,0 +1,175 @@
+//! Reverting patches applied by `apply_patch`, newest first (`/undo`).
+//!
+//! Before a patch runs, the contents of every file it touches are read; once
+//! it has been applied they are read again. Each undo level restores the
+//! earlier contents of one patch, provided the files still hold what the
+//! patch left there, so an undo never discards edits made afterwards.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_apply_patch::ApplyPatchAction;
+use codex_apply_patch::ApplyPatchFileChange;
+
+/// Applied patches kept for undo; older ones are forgotten.
+pub(crate) const MAX_UNDO_LEVELS: usize = 20;
+
+/// File contents captured before a patch was applied.
+#[derive(Debug)]
+pub(crate) struct PatchCheckpoint {
+    files: Vec<(PathBuf, Option<Vec<u8>>)>,
+}
+
+/// A patch that was applied, with the contents of each file it changed
+/// before and after. `None` means the file did not exist.
+#[derive(Debug, Clone)]
+pub(crate) struct AppliedPatch {
+    files: Vec<FileRevision>,
+}
+
+#[derive(Debug, Clone)]
+struct FileRevision {
+    path: PathBuf,
+    before: Option<Vec<u8>>,
+    after: Option<Vec<u8>>,
+}
+
+impl PatchCheckpoint {
+    /// Reads every file `action` adds, deletes, updates, or moves to.
+    pub(crate) fn capture(action: &ApplyPatchAction) -> Self {
+        let mut paths: Vec<PathBuf> = Vec::new();
+        for (path, change) in action.changes() {
+            paths.push(action.cwd.join(path));
+            if let ApplyPatchFileChange::Update {
+                move_path: Some(dest),
+                ..
+            } = change
+            {
+                paths.push(action.cwd.join(dest));
+            }
+        }
+        paths.sort();
+        paths.dedup();
+        Self {
+            files: paths
+                .into_iter()
+                .map(|path| {
+                    let contents = read_contents(&path);
+                    (path, contents)
+                })
+                .collect(),
+        }
+    }
+
+    /// Compares the captured files with their current contents. `None` when
+    /// the patch changed nothing.
+    pub(crate) fn finish(self) -> Option<AppliedPatch> {
+        let files: Vec<FileRevision> = self
+            .files
+            .into_iter()
+            .filter_map(|(path, before)| {
+                let after = read_contents(&path);
+                (after != before).then_some(FileRevision {
+                    path,
+                    before,
+                    after,
+                })
+            })
+            .collect();
+        (!files.is_empty()).then_some(AppliedPatch { files })
+    }
+}
+
+impl AppliedPatch {
+    /// The files the patch changed.
+    pub(crate) fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.files.iter().map(|file| file.path.as_path())
+    }
+
+    /// Restores every file to its contents before the patch. Nothing is
+    /// written unless all of them still match what the patch produced.
+    pub(crate) fn revert(&self) -> Result<(), String> {
+        if let Some(changed) = self
+            .files
+            .iter()
+            .find(|file| read_contents(&file.path) != file.after)
+        {
+            return Err(format!(
+                "{} has changed since the patch was applied",
+                changed.path.display()
+            ));
+        }
+        for file in &self.files {
+            let result = match &file.before {
+                Some(contents) => file
+                    .path
+                    .parent()
+                    .map_or(Ok(()), std::fs::create_dir_all)
+                    .and_then(|()| std::fs::write(&file.path, contents)),
+                None => std::fs::remove_file(&file.path),
+            };
+            result.map_err(|err| format!("failed to restore {}: {err}", file.path.display()))?;
+        }
+        Ok(())
+    }
+}
+
+fn read_contents(path: &Path) -> Option<Vec<u8>> {
+    std::fs::read(path).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_apply_patch::MaybeApplyPatchVerified;
+    use codex_apply_patch::maybe_parse_apply_patch_verified;
+    use pretty_assertions::assert_eq;
+
+    fn action(patch: &str, cwd: &Path) -> ApplyPatchAction {
+        let argv = vec!["apply_patch".to_string(), patch.to_string()];
+        match maybe_parse_apply_patch_verified(&argv, cwd) {
+            MaybeApplyPatchVerified::Body(action) => action,
+            other => panic!("expected a patch, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn reverts_a_patch_unless_files_changed_since() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let cwd = dir.path();
+        std::fs::write(cwd.join("edit.txt"), "one\ntwo\n").expect("write");
+        std::fs::write(cwd.join("gone.txt"), "bye\n").expect("write");
+
+        let patch = "*** Begin Patch\n*** Update File: edit.txt\n@@\n one\n-two\n+TWO\n*** Add File: new.txt\n+hello\n*** Delete File: gone.txt\n*** End Patch";
+        let checkpoint = PatchCheckpoint::capture(&action(patch, cwd));
+        std::fs::write(cwd.join("edit.txt"), "one\nTWO\n").expect("write");
+        std::fs::write(cwd.join("new.txt"), "hello\n").expect("write");
+        std::fs::remove_file(cwd.join("gone.txt")).expect("remove");
+        let applied = checkpoint.finish().expect("patch changed files");
+
+        std::fs::write(cwd.join("new.txt"), "edited later\n").expect("write");
+        assert_eq!(
+            applied.revert(),
+            Err(format!(
+                "{} has changed since the patch was applied",
+                cwd.join("new.txt").display()
+            ))
+        );
+        assert_eq!(
+            std::fs::read_to_string(cwd.join("edit.txt")).expect("read"),
+            "one\nTWO\n"
+        );
+
+        std::fs::write(cwd.join("new.txt"), "hello\n").expect("write");
+        assert_eq!(applied.revert(), Ok(()));
+        assert_eq!(
+            std::fs::read_to_string(cwd.join("edit.txt")).expect("read"),
+            "one\ntwo\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(cwd.join("gone.txt")).expect("read"),
+            "bye\n"
+        );
+        assert!(!cwd.join("new.txt").exists());
+    }
+}
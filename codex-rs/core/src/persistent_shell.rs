@@ -0,0 +1,382 @@
+//! One long-lived shell per session for the `shell` tools.
+//!
+//! With the `persistent_shell` feature, bash and zsh commands from the
+//! `shell` and `shell_command` tools run in a single login shell on a PTY
+//! instead of a fresh process each, so `export`, `cd`, `source venv/bin/activate`
+//! and the like carry over to the next command. The shell is started under the
+//! sandbox of the first command that needs it and restarted when the sandbox
+//! policy changes, when it exits, or when a command times out or is
+//! interrupted; the command that triggers a restart is told its earlier state
+//! is gone. A command approved to run outside a sandboxed shell runs in a
+//! fresh process as before.
+//!
+//! Each command is `eval`ed with stdin from `/dev/null`, followed by a line
+//! that prints a marker unique to the command along with its exit status;
+//! everything the PTY produced before the marker is the command's output.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
+
+use codex_utils_pty::ExecCommandSession;
+use rand::Rng;
+use tokio::sync::Mutex;
+use tokio::sync::broadcast;
+use tokio::sync::oneshot;
+
+use crate::error::CodexErr;
+use crate::error::SandboxErr;
+use crate::exec::DEFAULT_EXEC_COMMAND_TIMEOUT_MS;
+use crate::exec::ExecExpiration;
+use crate::exec::ExecLimits;
+use crate::exec::ExecToolCallOutput;
+use crate::exec::SandboxType;
+use crate::exec::StreamOutput;
+use crate::exec::is_likely_sandbox_denied;
+use crate::protocol::SandboxPolicy;
+use crate::sandboxing::SandboxPermissions;
+use crate::tools::runtimes::build_command_spec;
+use crate::tools::sandboxing::SandboxAttempt;
+use crate::tools::sandboxing::ToolError;
+
+/// How long a new shell may take to load its profile and print the first marker.
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(15);
+/// Output kept per command; older output is dropped once a command prints more.
+const MAX_OUTPUT_BYTES: usize = 1024 * 1024;
+/// Quiets the prompt, echo, and bracketed paste so only command output remains.
+const SETUP: &str = "stty -echo 2>/dev/null; PS1=; PS2=; PROMPT=; RPROMPT=; PROMPT_COMMAND=; PROMPT_EOL_MARK=; unset HISTFILE zle_bracketed_paste; bind 'set enable-bracketed-paste off' 2>/dev/null";
+
+#[derive(Default)]
+pub(crate) struct PersistentShellManager {
+    shell: Mutex<Option<PersistentShell>>,
+}
+
+struct PersistentShell {
+    process: ExecCommandSession,
+    output_rx: broadcast::Receiver<Vec<u8>>,
+    exit_rx: oneshot::Receiver<i32>,
+    shell_path: String,
+    sandbox: SandboxType,
+    policy: SandboxPolicy,
+    /// Directory the previous command asked for; a command asking for a
+    /// different one starts with a `cd`, otherwise the shell stays where the
+    /// last command left it.
+    requested_cwd: PathBuf,
+    nonce: u64,
+    commands: u64,
+    /// Set while a command runs; still set afterwards means the command was
+    /// abandoned midway and the shell is in an unknown state.
+    busy: bool,
+}
+
+/// How a command ended inside the shell.
+enum Completion {
+    Exited(i32),
+    ShellExited(i32),
+    TimedOut,
+}
+
+impl PersistentShellManager {
+    /// Runs `script` in the session's shell, starting one under `attempt` when
+    /// there is none. Returns `None` when the command should run in a fresh
+    /// process instead: the attempt is unsandboxed but the shell is not.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) async fn run(
+        &self,
+        shell_path: &str,
+        script: &str,
+        cwd: &Path,
+        env: &HashMap<String, String>,
+        timeout_ms: Option<u64>,
+        sandbox_permissions: SandboxPermissions,
+        attempt: &SandboxAttempt<'_>,
+    ) -> Option<Result<ExecToolCallOutput, ToolError>> {
+        let mut guard = self.shell.lock().await;
+        let mut restarted = false;
+        if let Some(shell) = guard.as_ref() {
+            let reusable = shell.shell_path == shell_path
+                && shell.sandbox == attempt.sandbox
+                && &shell.policy == attempt.policy;
+            if !reusable && attempt.sandbox == SandboxType::None {
+                return None;
+            }
+            if !reusable || shell.busy || shell.process.has_exited() {
+                restarted = true;
+                *guard = None;
+            }
+        }
+        if guard.is_none() {
+            match PersistentShell::start(shell_path, cwd, env, sandbox_permissions, attempt).await {
+                Ok(shell) => *guard = Some(shell),
+                Err(err) => return Some(Err(err)),
+            }
+        }
+        let shell = guard.as_mut()?;
+
+        let timeout = Duration::from_millis(timeout_ms.unwrap_or(DEFAULT_EXEC_COMMAND_TIMEOUT_MS));
+        let started = Instant::now();
+        let (mut text, completion) = shell.run(script, cwd, timeout).await;
+        if restarted {
+            text.insert_str(
+                0,
+                "(Started a new persistent shell: the previous one exited, was interrupted, or ran under a different sandbox, so earlier environment and directory changes are gone.)\n",
+            );
+        }
+        let (exit_code, timed_out) = match completion {
+            Completion::Exited(code) => (code, false),
+            Completion::ShellExited(code) => {
+                *guard = None;
+                text.push_str(
+                    "\n(The persistent shell exited; the next command starts a new one.)",
+                );
+                (code, false)
+            }
+            Completion::TimedOut => {
+                *guard = None;
+                text.push_str("\n(The command timed out and the persistent shell was stopped; the next command starts a new one.)");
+                (-1, true)
+            }
+        };
+        let sandbox = attempt.sandbox;
+        let output = ExecToolCallOutput {
+            exit_code,
+            stdout: StreamOutput::new(text.clone()),
+            stderr: StreamOutput::new(String::new()),
+            aggregated_output: StreamOutput::new(text),
+            duration: started.elapsed(),
+            timed_out,
+        };
+        Some(if timed_out {
+            Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Timeout {
+                output: Box::new(output),
+            })))
+        } else if is_likely_sandbox_denied(sandbox, &output) {
+            Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied {
+                output: Box::new(output),
+            })))
+        } else {
+            Ok(output)
+        })
+    }
+}
+
+impl PersistentShell {
+    async fn start(
+        shell_path: &str,
+        cwd: &Path,
+        env: &HashMap<String, String>,
+        sandbox_permissions: SandboxPermissions,
+        attempt: &SandboxAttempt<'_>,
+    ) -> Result<Self, ToolError> {
+        let spec = build_command_spec(
+            &[shell_path.to_string(), "-l".to_string()],
+            cwd,
+            env,
+            ExecExpiration::DefaultTimeout,
+            sandbox_permissions,
+            None,
+            ExecLimits::default(),
+        )?;
+        let exec_env = attempt
+            .env_for(spec)
+            .map_err(|err| ToolError::Codex(err.into()))?;
+        let (program, args) = exec_env
+            .command
+            .split_first()
+            .ok_or_else(|| ToolError::Rejected("command args are empty".to_string()))?;
+        let spawned = codex_utils_pty::pty::spawn_process(
+            program,
+            args,
+            exec_env.cwd.as_path(),
+            &exec_env.env,
+            &exec_env.arg0,
+        )
+        .await
+        .map_err(|err| {
+            ToolError::Rejected(format!("failed to start the persistent shell: {err}"))
+        })?;
+        let mut shell = Self {
+            process: spawned.session,
+            output_rx: spawned.output_rx,
+            exit_rx: spawned.exit_rx,
+            shell_path: shell_path.to_string(),
+            sandbox: attempt.sandbox,
+            policy: attempt.policy.clone(),
+            requested_cwd: cwd.to_path_buf(),
+            nonce: rand::rng().random(),
+            commands: 0,
+            busy: false,
+        };
+        let marker = shell.next_marker();
+        shell
+            .write(format!("{SETUP}\nprintf '\\n%s:0\\n' '{marker}'\n"))
+            .await;
+        match shell.read_until(&marker, STARTUP_TIMEOUT).await.1 {
+            Completion::Exited(_) => Ok(shell),
+            Completion::ShellExited(code) => Err(ToolError::Rejected(format!(
+                "the persistent shell exited with code {code} while starting"
+            ))),
+            Completion::TimedOut => Err(ToolError::Rejected(
+                "the persistent shell did not start in time".to_string(),
+            )),
+        }
+    }
+
+    async fn run(&mut self, script: &str, cwd: &Path, timeout: Duration) -> (String, Completion) {
+        let marker = self.next_marker();
+        let mut input = String::new();
+        if cwd != self.requested_cwd {
+            let quoted = shlex::try_quote(&cwd.to_string_lossy())
+                .map(std::borrow::Cow::into_owned)
+                .unwrap_or_else(|_| cwd.to_string_lossy().into_owned());
+            input.push_str(&format!("cd -- {quoted}\n"));
+            self.requested_cwd = cwd.to_path_buf();
+        }
+        let delimiter = format!("CODEX_SCRIPT_{marker}");
+        input.push_str(&format!(
+            "eval \"$(cat <<'{delimiter}'\n{script}\n{delimiter}\n)\" </dev/null; printf '\\n%s:%s\\n' '{marker}' \"$?\"\n"
+        ));
+        self.busy = true;
+        self.write(input).await;
+        let result = self.read_until(&marker, timeout).await;
+        self.busy = false;
+        result
+    }
+
+    fn next_marker(&mut self) -> String {
+        self.commands += 1;
+        format!("__CODEX_DONE_{:016x}_{}__", self.nonce, self.commands)
+    }
+
+    async fn write(&self, input: String) {
+        let _ = self.process.writer_sender().send(input.into_bytes()).await;
+    }
+
+    /// Collects output until `marker` and the exit status after it, the shell
+    /// exits, or `timeout` passes.
+    async fn read_until(&mut self, marker: &str, timeout: Duration) -> (String, Completion) {
+        let deadline = tokio::time::Instant::now() + timeout;
+        let mut output: Vec<u8> = Vec::new();
+        let mut dropped = false;
+        loop {
+            if let Some((body, status)) = split_at_marker(&output, marker, dropped) {
+                return (body, Completion::Exited(status));
+            }
+            tokio::select! {
+                chunk = self.output_rx.recv() => match chunk {
+                    Ok(chunk) => {
+                        output.extend_from_slice(&chunk);
+                        if output.len() > MAX_OUTPUT_BYTES {
+                            output.drain(..output.len() - MAX_OUTPUT_BYTES);
+                            dropped = true;
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(_)) => dropped = true,
+                    Err(broadcast::error::RecvError::Closed) => {
+                        let code = self.process.exit_code().unwrap_or(-1);
+                        let text = String::from_utf8_lossy(&output);
+                        return (clean_output(&text, dropped), Completion::ShellExited(code));
+                    }
+                },
+                code = &mut self.exit_rx => {
+                    while let Ok(chunk) = self.output_rx.try_recv() {
+                        output.extend_from_slice(&chunk);
+                    }
+                    let text = String::from_utf8_lossy(&output);
+                    return (clean_output(&text, dropped), Completion::ShellExited(code.unwrap_or(-1)));
+                }
+                () = tokio::time::sleep_until(deadline) => {
+                    let text = String::from_utf8_lossy(&output);
+                    return (clean_output(&text, dropped), Completion::TimedOut);
+                }
+            }
+        }
+    }
+}
+
+/// The output before `marker` and the exit status printed after it, once the
+/// marker line is complete.
+fn split_at_marker(output: &[u8], marker: &str, dropped: bool) -> Option<(String, i32)> {
+    let text = String::from_utf8_lossy(output);
+    let start = text.find(&format!("{marker}:"))?;
+    let end = start + text[start..].find('\n')?;
+    let status = text[start + marker.len() + 1..end]
+        .trim()
+        .parse()
+        .unwrap_or(-1);
+    Some((clean_output(&text[..start], dropped), status))
+}
+
+/// PTY output as the model should see it: `\n` line endings, no terminal
+/// escape sequences, and without the newline the marker line starts with.
+fn clean_output(raw: &str, dropped: bool) -> String {
+    let mut text = String::with_capacity(raw.len());
+    let mut chars = raw.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\u{1b}' => {
+                // CSI sequences end with a byte in `@`..=`~`; OSC ones with BEL.
+                match chars.next() {
+                    Some('[') => {
+                        for next in chars.by_ref() {
+                            if ('@'..='~').contains(&next) {
+                                break;
+                            }
+                        }
+                    }
+                    Some(']') => {
+                        for next in chars.by_ref() {
+                            if next == '\u{7}' {
+                                break;
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            '\r' => {}
+            _ => text.push(ch),
+        }
+    }
+    if text.ends_with('\n') {
+        text.pop();
+    }
+    if dropped {
+        text.insert_str(0, "(earlier output was dropped)\n");
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn splits_output_at_the_marker() {
+        let marker = "__CODEX_DONE_00000000000000ab_2__";
+        assert_eq!(split_at_marker(b"partial\r\n", marker, false), None);
+        assert_eq!(
+            split_at_marker(
+                format!("one\r\ntwo\r\n\r\n{marker}:3\r\n").as_bytes(),
+                marker,
+                false
+            ),
+            Some(("one\ntwo\n".to_string(), 3))
+        );
+    }
+
+    #[test]
+    fn clean_output_strips_terminal_noise() {
+        assert_eq!(
+            clean_output("\u{1b}[?2004lhello\r\n\u{1b}[31mred\u{1b}[0m\r\n", false),
+            "hello\nred"
+        );
+        assert_eq!(
+            clean_output("\u{1b}]0;title\u{7}tail\r\n", true),
+            "(earlier output was dropped)\ntail"
+        );
+    }
+}
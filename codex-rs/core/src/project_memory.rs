@@ -0,0 +1,147 @@
+//! Per-project memory.
+//!
+//! Notes live in `.codex/memory.md` at the project root (the git root, or the
+//! working directory outside a repository), one `- ` bullet per note. When the
+//! `project_memory` feature is enabled they are appended to the session
+//! instructions, and the agent can add or remove notes with the `memory` tool
+//! after the user approves each change. Users can edit the file by hand.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_utils_string::take_bytes_at_char_boundary;
+
+use crate::config::Config;
+use crate::git_info::resolve_root_git_project_for_trust;
+
+/// Location of the memory file relative to the project root.
+pub const PROJECT_MEMORY_FILE: &str = ".codex/memory.md";
+
+/// Upper bound on how much of the memory file is added to the instructions.
+const PROJECT_MEMORY_MAX_BYTES: usize = 16 * 1024;
+
+const NOTE_PREFIX: &str = "- ";
+
+pub fn project_memory_path(cwd: &Path) -> PathBuf {
+    resolve_root_git_project_for_trust(cwd)
+        .unwrap_or_else(|| cwd.to_path_buf())
+        .join(PROJECT_MEMORY_FILE)
+}
+
+/// The memory section of the session instructions, or `None` when the project
+/// has no notes. Like AGENTS.md, memory is ignored in untrusted projects.
+pub(crate) async fn read_project_memory(config: &Config) -> Option<String> {
+    if config.active_project.is_untrusted() {
+        return None;
+    }
+    let contents = tokio::fs::read_to_string(project_memory_path(&config.cwd))
+        .await
+        .ok()?;
+    let contents = contents.trim();
+    if contents.is_empty() {
+        return None;
+    }
+    let contents = take_bytes_at_char_boundary(contents, PROJECT_MEMORY_MAX_BYTES);
+    Some(format!(
+        "# Project memory\n\nNotes saved in {PROJECT_MEMORY_FILE} during earlier sessions in this project. Keep them up to date with the `memory` tool.\n\n{contents}"
+    ))
+}
+
+/// Returns `contents` with `note` appended, or unchanged if the note is
+/// already recorded.
+pub(crate) fn add_note(contents: &str, note: &str) -> Result<String, String> {
+    let note = normalize_note(note)?;
+    if notes(contents).any(|existing| existing == note) {
+        return Ok(contents.to_string());
+    }
+    let mut updated = contents.to_string();
+    if !updated.is_empty() && !updated.ends_with('\n') {
+        updated.push('\n');
+    }
+    updated.push_str(NOTE_PREFIX);
+    updated.push_str(&note);
+    updated.push('\n');
+    Ok(updated)
+}
+
+/// Returns `contents` without the bullet whose text is `note`.
+pub(crate) fn remove_note(contents: &str, note: &str) -> Result<String, String> {
+    let note = normalize_note(note)?;
+    let mut removed = false;
+    let mut updated = String::with_capacity(contents.len());
+    for line in contents.split_inclusive('\n') {
+        if !removed && note_text(line) == Some(note.as_str()) {
+            removed = true;
+            continue;
+        }
+        updated.push_str(line);
+    }
+    if removed {
+        Ok(updated)
+    } else {
+        Err(format!(
+            "no note in {PROJECT_MEMORY_FILE} matches \"{note}\""
+        ))
+    }
+}
+
+fn normalize_note(note: &str) -> Result<String, String> {
+    let note = note.split_whitespace().collect::<Vec<_>>().join(" ");
+    if note.is_empty() {
+        Err("note must not be empty".to_string())
+    } else {
+        Ok(note)
+    }
+}
+
+fn notes(contents: &str) -> impl Iterator<Item = &str> {
+    contents.lines().filter_map(note_text)
+}
+
+fn note_text(line: &str) -> Option<&str> {
+    line.trim()
+        .strip_prefix(NOTE_PREFIX.trim_end())
+        .map(str::trim)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn add_note_appends_bullets_and_skips_duplicates() {
+        let contents = add_note("", "Run `just fmt`\nbefore committing.").expect("valid note");
+        assert_eq!(contents, "- Run `just fmt` before committing.\n");
+
+        let contents = add_note(&contents, "Tests use insta snapshots.").expect("valid note");
+        assert_eq!(
+            add_note(&contents, "  Tests use insta snapshots. ").expect("valid note"),
+            contents
+        );
+        assert_eq!(
+            contents,
+            "- Run `just fmt` before committing.\n- Tests use insta snapshots.\n"
+        );
+        assert_eq!(
+            add_note(&contents, " \n"),
+            Err("note must not be empty".to_string())
+        );
+    }
+
+    #[test]
+    fn remove_note_keeps_hand_written_content() {
+        let contents = "# Conventions\n\n- Use tabs.\n-  Prefer `anyhow`.\n";
+
+        assert_eq!(
+            remove_note(contents, "Prefer `anyhow`.").expect("note exists"),
+            "# Conventions\n\n- Use tabs.\n"
+        );
+        assert_eq!(
+            remove_note(contents, "Use spaces."),
+            Err(format!(
+                "no note in {PROJECT_MEMORY_FILE} matches \"Use spaces.\""
+            ))
+        );
+    }
+}
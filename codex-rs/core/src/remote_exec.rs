@@ -0,0 +1,443 @@
+//! Remote execution of the shell tools over SSH.
+//!
+//! With `[remote] host` set, the `shell` and `shell_command` tools run their
+//! commands on that host, in `remote_dir`, while the session itself stays
+//! local. With `sync = "rsync"` the workspace is copied to `remote_dir` before
+//! a command when something changed locally since the last copy, and the
+//! command's changes are copied back afterwards, so `apply_patch` keeps
+//! editing local files; with `sync = "checkout"` `remote_dir` is a checkout
+//! the user keeps up to date. Paths are mapped between the two directories
+//! the same way as for the container backend.
+//!
+//! Sandboxed commands run under bubblewrap on the remote host, with the same
+//! read-only, workspace-write, and network rules as the local sandbox.
+//! Copying back never touches `.git` or `write_protected` paths.
+
+use std::path::Path;
+use std::process::Stdio;
+use std::time::SystemTime;
+
+use codex_protocol::protocol::SandboxPolicy;
+use tokio::process::Command;
+use tokio::sync::Mutex;
+use wildmatch::WildMatchPattern;
+
+use crate::bash::extract_bash_command;
+use crate::config::types::RemoteConfig;
+use crate::config::types::RemoteSync;
+use crate::container::PathMapping;
+
+enum RemoteState {
+    NotStarted,
+    Ready {
+        mapping: PathMapping,
+        /// When the last copy to the remote host started.
+        synced_at: Option<SystemTime>,
+    },
+    Failed(String),
+}
+
+pub(crate) struct RemoteManager {
+    config: RemoteConfig,
+    /// Also serializes syncs, so two commands never copy at the same time.
+    state: Mutex<RemoteState>,
+}
+
+impl RemoteManager {
+    pub(crate) fn new(config: RemoteConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(RemoteState::NotStarted),
+        }
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.config.host.is_some()
+    }
+
+    /// Creates `remote_dir` on the first call and, with rsync, copies the
+    /// workspace to it when anything in it changed since the last copy.
+    pub(crate) async fn sync_up(&self, host_root: &Path) -> Result<PathMapping, String> {
+        let mut state = self.state.lock().await;
+        if let RemoteState::NotStarted = &*state {
+            *state = match self.prepare(host_root).await {
+                Ok(mapping) => RemoteState::Ready {
+                    mapping,
+                    synced_at: None,
+                },
+                Err(err) => RemoteState::Failed(err),
+            };
+        }
+        let (mapping, synced_at) = match &mut *state {
+            RemoteState::Ready { mapping, synced_at } => (mapping.clone(), synced_at),
+            RemoteState::Failed(err) => return Err(err.clone()),
+            RemoteState::NotStarted => return Err("remote host is not prepared".to_string()),
+        };
+        if self.config.sync != RemoteSync::Rsync {
+            return Ok(mapping);
+        }
+        if let Some(since) = *synced_at {
+            let root = host_root.to_path_buf();
+            let exclude = self.config.exclude.clone();
+            let changed =
+                tokio::task::spawn_blocking(move || changed_since(&root, since, &exclude))
+                    .await
+                    .unwrap_or(true);
+            if !changed {
+                return Ok(mapping);
+            }
+        }
+        let started = SystemTime::now();
+        let remote = format!("{}:{}/", self.host()?, self.config.remote_dir);
+        // `--delete` keeps files deleted locally from coming back.
+        self.rsync(&["--delete"], &format!("{}/", host_root.display()), &remote)
+            .await?;
+        *synced_at = Some(started);
+        Ok(mapping)
+    }
+
+    /// With rsync, copies what the last command changed back to the
+    /// workspace, deleting what it deleted. `.git` and the `write_protected`
+    /// paths are left alone.
+    pub(crate) async fn sync_down(
+        &self,
+        host_root: &Path,
+        write_protected: &[String],
+    ) -> Result<(), String> {
+        if self.config.sync != RemoteSync::Rsync {
+            return Ok(());
+        }
+        let _state = self.state.lock().await;
+        let remote = format!("{}:{}/", self.host()?, self.config.remote_dir);
+        let mut args = vec!["--delete".to_string()];
+        for pattern in protected_excludes(host_root, write_protected) {
+            args.push("--exclude".to_string());
+            args.push(pattern);
+        }
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        // What this copies is newer than the last copy up, so the next
+        // command copies up again, which also restores any protected file
+        // the command changed on the remote host.
+        self.rsync(&args, &remote, &format!("{}/", host_root.display()))
+            .await
+    }
+
+    async fn prepare(&self, host_root: &Path) -> Result<PathMapping, String> {
+        let remote_dir = quote(&self.config.remote_dir);
+        let output = Command::new("ssh")
+            .args(&self.config.ssh_args)
+            .arg(self.host()?)
+            .arg(format!("mkdir -p {remote_dir}"))
+            .stdin(Stdio::null())
+            .output()
+            .await
+            .map_err(|err| format!("failed to run `ssh`: {err}"))?;
+        if !output.status.success() {
+            return Err(format!(
+                "could not create {} on the remote host: {}",
+                self.config.remote_dir,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(PathMapping::new(host_root, &self.config.remote_dir))
+    }
+
+    async fn rsync(&self, extra_args: &[&str], source: &str, dest: &str) -> Result<(), String> {
+        let ssh = std::iter::once("ssh")
+            .chain(self.config.ssh_args.iter().map(String::as_str))
+            .map(quote)
+            .collect::<Vec<_>>()
+            .join(" ");
+        let output = Command::new("rsync")
+            .args(["--archive", "--compress", "--rsh", &ssh])
+            .args(extra_args)
+            .args(
+                self.config
+                    .exclude
+                    .iter()
+                    .flat_map(|pattern| ["--exclude", pattern.as_str()]),
+            )
+            .arg(source)
+            .arg(dest)
+            .stdin(Stdio::null())
+            .output()
+            .await
+            .map_err(|err| format!("failed to run `rsync`: {err}"))?;
+        if !output.status.success() {
+            return Err(format!(
+                "`rsync` failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(())
+    }
+
+    /// The local `ssh` command that runs `command` on the remote host from
+    /// the remote counterpart of `cwd`. With a `sandbox` policy the command
+    /// runs under bubblewrap; without one it runs as is.
+    pub(crate) fn exec_command(
+        &self,
+        command: &[String],
+        cwd: &Path,
+        mapping: &PathMapping,
+        sandbox: Option<&SandboxPolicy>,
+    ) -> Result<Vec<String>, String> {
+        let Some(remote_cwd) = mapping.container_path(cwd) else {
+            return Err(format!(
+                "{} is outside the workspace mirrored on the remote host",
+                cwd.display()
+            ));
+        };
+        let mut remote_argv = match sandbox {
+            Some(policy) => self.bwrap_args(policy, mapping),
+            None => Vec::new(),
+        };
+        match extract_bash_command(command) {
+            Some((_, script)) => remote_argv.extend([
+                self.config.shell.clone(),
+                "-c".to_string(),
+                mapping.to_container(script),
+            ]),
+            None => remote_argv.extend(command.iter().map(|arg| mapping.to_container(arg))),
+        }
+        // ssh hands the remote login shell a single string, so the command
+        // is quoted into one.
+        let mut remote_command = format!(
+            "cd {} && {}",
+            quote(&remote_cwd),
+            remote_argv
+                .iter()
+                .map(|arg| quote(arg))
+                .collect::<Vec<_>>()
+                .join(" ")
+        );
+        if sandbox.is_some() {
+            remote_command = format!(
+                "command -v bwrap >/dev/null || {{ echo {} >&2; exit 1; }}; {remote_command}",
+                quote(
+                    "bwrap not found on the remote host; it is needed to run commands there in the sandbox"
+                )
+            );
+        }
+        let mut argv = vec!["ssh".to_string()];
+        argv.extend(self.config.ssh_args.iter().cloned());
+        argv.push(self.host()?.to_string());
+        argv.push(remote_command);
+        Ok(argv)
+    }
+
+    /// The bubblewrap prefix that applies `policy` on the remote host: the
+    /// whole filesystem read-only, plus the workspace, its writable roots,
+    /// and `/tmp` writable under `workspace-write`.
+    fn bwrap_args(&self, policy: &SandboxPolicy, mapping: &PathMapping) -> Vec<String> {
+        let mut args: Vec<String> = [
+            "bwrap",
+            "--ro-bind",
+            "/",
+            "/",
+            "--dev",
+            "/dev",
+            "--proc",
+            "/proc",
+            "--die-with-parent",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect();
+        if let SandboxPolicy::WorkspaceWrite {
+            writable_roots,
+            exclude_slash_tmp,
+            ..
+        } = policy
+        {
+            let mut writable = vec![self.config.remote_dir.clone()];
+            writable.extend(
+                writable_roots
+                    .iter()
+                    .filter_map(|root| mapping.container_path(root.as_path())),
+            );
+            if !exclude_slash_tmp {
+                writable.push("/tmp".to_string());
+            }
+            for dir in writable {
+                args.extend(["--bind".to_string(), dir.clone(), dir]);
+            }
+        }
+        if !policy.has_full_network_access() {
+            args.push("--unshare-net".to_string());
+        }
+        args.push("--".to_string());
+        args
+    }
+
+    fn host(&self) -> Result<&str, String> {
+        self.config
+            .host
+            .as_deref()
+            .ok_or_else(|| "no remote host is configured".to_string())
+    }
+}
+
+/// rsync excludes that keep copying back from touching `.git` or the
+/// `write_protected` paths inside `host_root`.
+fn protected_excludes(host_root: &Path, write_protected: &[String]) -> Vec<String> {
+    let root = host_root.to_string_lossy();
+    let mut excludes = vec!["/.git".to_string()];
+    for pattern in write_protected {
+        let relative = if pattern.starts_with('/') {
+            // Absolute patterns only matter inside the workspace.
+            match pattern.strip_prefix(root.as_ref()) {
+                Some(relative) if relative.starts_with('/') => relative.to_string(),
+                _ => continue,
+            }
+        } else {
+            format!("/{pattern}")
+        };
+        // rsync's `*` stops at `/`; excluding a directory covers its contents.
+        excludes.push(
+            relative
+                .strip_suffix("/**")
+                .unwrap_or(&relative)
+                .to_string(),
+        );
+    }
+    excludes
+}
+
+/// Whether any file or directory under `root` changed after `since`, skipping
+/// entries whose name matches an `exclude` pattern.
+fn changed_since(root: &Path, since: SystemTime, exclude: &[String]) -> bool {
+    let excluded: Vec<WildMatchPattern<'*', '?'>> = exclude
+        .iter()
+        .map(|pattern| pattern.trim_matches('/'))
+        .filter(|pattern| !pattern.contains('/'))
+        .map(WildMatchPattern::new)
+        .collect();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            return true;
+        };
+        if std::fs::metadata(&dir)
+            .and_then(|metadata| metadata.modified())
+            .is_ok_and(|modified| modified > since)
+        {
+            return true;
+        }
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if excluded.iter().any(|pattern| pattern.matches(&name)) {
+                continue;
+            }
+            let Ok(metadata) = entry.metadata() else {
+                return true;
+            };
+            if metadata.is_dir() {
+                pending.push(entry.path());
+            } else if metadata.modified().is_ok_and(|modified| modified > since) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+fn quote(arg: &str) -> String {
+    shlex::try_quote(arg)
+        .map(std::borrow::Cow::into_owned)
+        .unwrap_or_else(|_| arg.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::types::RemoteToml;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn exec_command_runs_the_script_in_the_remote_workspace() {
+        let manager = RemoteManager::new(RemoteConfig::from(RemoteToml {
+            host: Some("builder".to_string()),
+            remote_dir: Some("/srv/work".to_string()),
+            ssh_args: Some(vec!["-p".to_string(), "2222".to_string()]),
+            ..Default::default()
+        }));
+        let mapping = PathMapping::new(Path::new("/home/me/project"), "/srv/work");
+        let command = vec![
+            "/bin/zsh".to_string(),
+            "-lc".to_string(),
+            "cat /home/me/project/README.md".to_string(),
+        ];
+
+        assert_eq!(
+            manager.exec_command(&command, Path::new("/home/me/project/docs"), &mapping, None),
+            Ok(vec![
+                "ssh".to_string(),
+                "-p".to_string(),
+                "2222".to_string(),
+                "builder".to_string(),
+                "cd /srv/work/docs && /bin/sh -c 'cat /srv/work/README.md'".to_string(),
+            ])
+        );
+        assert!(
+            manager
+                .exec_command(&command, Path::new("/tmp"), &mapping, None)
+                .is_err()
+        );
+
+        let sandboxed = manager
+            .exec_command(
+                &command,
+                Path::new("/home/me/project"),
+                &mapping,
+                Some(&SandboxPolicy::new_workspace_write_policy()),
+            )
+            .expect("sandboxed command");
+        assert_eq!(
+            sandboxed.last().map(String::as_str),
+            Some(
+                "command -v bwrap >/dev/null || { echo 'bwrap not found on the remote host; \
+                 it is needed to run commands there in the sandbox' >&2; exit 1; }; \
+                 cd /srv/work && bwrap --ro-bind / / \
+                 --dev /dev --proc /proc --die-with-parent --bind /srv/work /srv/work \
+                 --bind /tmp /tmp --unshare-net -- /bin/sh -c 'cat /srv/work/README.md'"
+            )
+        );
+    }
+
+    #[test]
+    fn copying_back_skips_git_and_write_protected_paths() {
+        assert_eq!(
+            protected_excludes(
+                Path::new("/home/me/project"),
+                &[
+                    "migrations/**".to_string(),
+                    ".env*".to_string(),
+                    "/home/me/project/secrets.toml".to_string(),
+                    "/etc/hosts".to_string(),
+                ],
+            ),
+            vec![
+                "/.git".to_string(),
+                "/migrations".to_string(),
+                "/.env*".to_string(),
+                "/secrets.toml".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn detects_local_changes_since_the_last_copy() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src")).expect("mkdir");
+        std::fs::create_dir_all(root.join("target")).expect("mkdir");
+        std::fs::write(root.join("src/lib.rs"), "").expect("write");
+        let exclude = vec!["target/".to_string()];
+        let after_write = SystemTime::now() + std::time::Duration::from_secs(1);
+
+        assert!(!changed_since(root, after_write, &exclude));
+        assert!(changed_since(root, SystemTime::UNIX_EPOCH, &exclude));
+    }
+}
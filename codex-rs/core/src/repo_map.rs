@@ -0,0 +1,294 @@
+//! A compact outline of the repository for the model's context.
+//!
+//! With the `repo_map` feature, every turn starts by refreshing an outline of
+//! the definitions in the git repository's source files (tracked, or untracked
+//! but not ignored). Only files whose size or modification time changed since
+//! the previous turn are parsed again. The outline is rendered within
+//! `repo_map_max_tokens`, files under the working directory and recently
+//! modified files first, into a session-prefix message that goes ahead of the
+//! first user turn, after the pinned context.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::SystemTime;
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use tracing::warn;
+
+use crate::context_manager::is_user_turn_boundary;
+use crate::git_info::get_git_repo_root;
+use crate::session_prefix::REPO_MAP_OPEN_TAG;
+use crate::syntax_outline::OutlineEntry;
+use crate::syntax_outline::outline;
+use crate::syntax_outline::supports_path;
+use crate::truncate::approx_token_count;
+
+/// Repositories with more files than this are mapped partially.
+const MAX_FILES: usize = 20_000;
+/// Definitions nested deeper than this are left out, so a file lists its
+/// items and their methods but not what is inside the methods.
+const MAX_DEPTH: usize = 1;
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RepoMap {
+    root: Option<PathBuf>,
+    /// Keyed by path relative to `root`.
+    files: HashMap<PathBuf, FileOutline>,
+    rendered: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct FileOutline {
+    modified: SystemTime,
+    len: u64,
+    entries: Vec<OutlineEntry>,
+}
+
+impl RepoMap {
+    /// Re-outline the files of the repository containing `cwd` that changed
+    /// since the last refresh, and render the map within `max_tokens`.
+    /// Outside a git repository the map is empty.
+    pub(crate) fn refresh(&mut self, cwd: &Path, max_tokens: usize) {
+        let Some(root) = get_git_repo_root(cwd) else {
+            *self = Self::default();
+            return;
+        };
+        if self.root.as_ref() != Some(&root) {
+            self.files.clear();
+            self.root = Some(root.clone());
+        }
+        let listed = match list_files(&root) {
+            Ok(listed) => listed,
+            Err(err) => {
+                warn!("failed to list files for the repo map: {err}");
+                self.rendered = None;
+                return;
+            }
+        };
+
+        let mut files = HashMap::new();
+        for relative in listed.into_iter().take(MAX_FILES) {
+            if !supports_path(&relative) {
+                continue;
+            }
+            let path = root.join(&relative);
+            let Some((modified, len)) = std::fs::metadata(&path)
+                .ok()
+                .filter(std::fs::Metadata::is_file)
+                .and_then(|metadata| Some((metadata.modified().ok()?, metadata.len())))
+            else {
+                continue;
+            };
+            let file = match self.files.remove(&relative) {
+                Some(previous) if previous.modified == modified && previous.len == len => previous,
+                _ => FileOutline {
+                    modified,
+                    len,
+                    entries: std::fs::read_to_string(&path)
+                        .ok()
+                        .and_then(|source| outline(&relative, &source))
+                        .unwrap_or_default(),
+                },
+            };
+            files.insert(relative, file);
+        }
+        self.files = files;
+        self.rendered = render(&root, cwd, &self.files, max_tokens);
+    }
+
+    /// The map as of the last refresh, or `None` when there is nothing to show.
+    pub(crate) fn rendered(&self) -> Option<&str> {
+        self.rendered.as_deref()
+    }
+}
+
+/// Inserts the rendered map ahead of the first user turn in `items`.
+pub(crate) fn insert_repo_map(items: &mut Vec<ResponseItem>, text: String) {
+    let index = items
+        .iter()
+        .position(is_user_turn_boundary)
+        .unwrap_or(items.len());
+    items.insert(
+        index,
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText { text }],
+            end_turn: None,
+        },
+    );
+}
+
+/// Files git would consider part of the working tree, relative to `root`.
+pub(crate) fn list_files(root: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let output = Command::new("git")
+        .args([
+            "ls-files",
+            "-z",
+            "--cached",
+            "--others",
+            "--exclude-standard",
+        ])
+        .current_dir(root)
+        .output()?;
+    if !output.status.success() {
+        return Err(std::io::Error::other(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .split('\0')
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+        .collect())
+}
+
+fn render(
+    root: &Path,
+    cwd: &Path,
+    files: &HashMap<PathBuf, FileOutline>,
+    max_tokens: usize,
+) -> Option<String> {
+    let cwd_relative = cwd
+        .strip_prefix(root)
+        .ok()
+        .filter(|relative| !relative.as_os_str().is_empty());
+    let near_cwd = |path: &Path| cwd_relative.is_some_and(|cwd| path.starts_with(cwd));
+    let mut ranked: Vec<_> = files
+        .iter()
+        .filter(|(_, file)| !file.entries.is_empty())
+        .collect();
+    ranked.sort_by(|(a_path, a), (b_path, b)| {
+        near_cwd(b_path)
+            .cmp(&near_cwd(a_path))
+            .then(b.modified.cmp(&a.modified))
+            .then(a_path.cmp(b_path))
+    });
+
+    let footer = "</repo_map>";
+    let mut text = format!(
+        "{REPO_MAP_OPEN_TAG}\nDefinitions in the repository at {}, with their starting lines. Paths are relative to the repository root; files near the working directory and recently modified files come first.\n",
+        root.display()
+    );
+    let mut used = approx_token_count(&text) + approx_token_count(footer);
+    let mut shown = 0;
+    for (path, file) in &ranked {
+        let mut block = format!("{}\n", path.display());
+        for entry in file.entries.iter().filter(|entry| entry.depth <= MAX_DEPTH) {
+            block.push_str(&format!(
+                "{}L{}: {}\n",
+                "  ".repeat(entry.depth + 1),
+                entry.start_line,
+                entry.label
+            ));
+        }
+        let tokens = approx_token_count(&block);
+        if used + tokens > max_tokens {
+            break;
+        }
+        used += tokens;
+        text.push_str(&block);
+        shown += 1;
+    }
+    if shown == 0 {
+        return None;
+    }
+    if shown < ranked.len() {
+        text.push_str(&format!(
+            "({} more files not shown)\n",
+            ranked.len() - shown
+        ));
+    }
+    text.push_str(footer);
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    fn write(path: &Path, contents: &str, modified: SystemTime) {
+        std::fs::write(path, contents).expect("write file");
+        std::fs::File::options()
+            .write(true)
+            .open(path)
+            .and_then(|file| file.set_modified(modified))
+            .expect("set mtime");
+    }
+
+    #[test]
+    fn refresh_reparses_changed_files_and_respects_the_budget() {
+        let repo = tempfile::tempdir().expect("tempdir");
+        let root = repo.path();
+        let status = Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(root)
+            .status()
+            .expect("git init");
+        assert!(status.success());
+        std::fs::write(root.join(".gitignore"), "target/\n").expect("write");
+        std::fs::create_dir(root.join("target")).expect("mkdir");
+        std::fs::write(root.join("target/gen.rs"), "fn generated() {}\n").expect("write");
+        let earlier = SystemTime::now() - Duration::from_secs(60);
+        write(&root.join("lib.rs"), "struct A;\n", earlier);
+        write(
+            &root.join("app.py"),
+            "class App:\n    def run(self):\n        pass\n",
+            earlier - Duration::from_secs(60),
+        );
+        let root = get_git_repo_root(root).expect("repo root");
+
+        let mut map = RepoMap::default();
+        map.refresh(&root, 1_000);
+        let rendered = map.rendered().expect("rendered map");
+        let body: Vec<&str> = rendered.lines().skip(2).collect();
+        assert_eq!(
+            body,
+            vec![
+                "lib.rs",
+                "  L1: struct A;",
+                "app.py",
+                "  L1: class App:",
+                "    L2: def run(self):",
+                "</repo_map>",
+            ]
+        );
+
+        write(
+            &root.join("app.py"),
+            "def main():\n    pass\n",
+            SystemTime::now(),
+        );
+        map.refresh(&root, 1_000);
+        let rendered = map.rendered().expect("rendered map");
+        let body: Vec<&str> = rendered.lines().skip(2).collect();
+        assert_eq!(
+            body,
+            vec![
+                "app.py",
+                "  L1: def main():",
+                "lib.rs",
+                "  L1: struct A;",
+                "</repo_map>"
+            ]
+        );
+
+        // Room for the header, the footer and the first file only.
+        let header = format!(
+            "{}\n",
+            rendered.lines().take(2).collect::<Vec<_>>().join("\n")
+        );
+        let budget = approx_token_count(&header)
+            + approx_token_count("</repo_map>")
+            + approx_token_count("app.py\n  L1: def main():\n");
+        map.refresh(&root, budget);
+        let rendered = map.rendered().expect("rendered map");
+        assert!(rendered.contains("app.py"), "{rendered}");
+        assert!(rendered.contains("(1 more files not shown)"), "{rendered}");
+    }
+}
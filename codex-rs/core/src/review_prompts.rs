@@ -19,6 +19,18 @@ const COMMIT_PROMPT_WITH_TITLE: &str = "Review the code changes introduced by co
 const COMMIT_PROMPT: &str =
     "Review the code changes introduced by commit {sha}. Provide prioritized, actionable findings.";
 
+const DIFF_RANGE_PROMPT: &str = "Review the code changes in the git revision range {range}. Run `git diff {range}` to inspect them. Provide prioritized, actionable findings.";
+
+/// A review of the changes in a git revision range such as `main...HEAD`.
+pub fn diff_range_review_request(range: &str) -> ReviewRequest {
+    ReviewRequest {
+        target: ReviewTarget::Custom {
+            instructions: DIFF_RANGE_PROMPT.replace("{range}", range),
+        },
+        user_facing_hint: Some(format!("changes in {range}")),
+    }
+}
+
 pub fn resolve_review_request(
     request: ReviewRequest,
     cwd: &Path,
@@ -0,0 +1,364 @@
+//! Encryption of rollout files at rest.
+//!
+//! When `session_encryption` is enabled every rollout line is sealed with
+//! AES-256-GCM before it is written, so files stay append-only JSONL but no
+//! longer contain readable transcripts. The same key seals `history.jsonl`
+//! entries and saved command output. The key is either a random key kept in
+//! the OS keychain or one derived from a passphrase with PBKDF2 and a salt
+//! stored in `CODEX_HOME`.
+//!
+//! Keys resolved in this process are registered by id, and every reader passes
+//! lines through [`decode_rollout_line`], so decryption on resume, listing, and
+//! search is transparent. Plain lines pass through unchanged, which keeps
+//! sessions recorded before encryption was enabled readable.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::num::NonZeroU32;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use codex_keyring_store::DefaultKeyringStore;
+use codex_keyring_store::KeyringStore;
+use ring::aead::AES_256_GCM;
+use ring::aead::Aad;
+use ring::aead::LessSafeKey;
+use ring::aead::NONCE_LEN;
+use ring::aead::Nonce;
+use ring::aead::UnboundKey;
+use ring::rand::SecureRandom;
+use ring::rand::SystemRandom;
+use sha2::Digest;
+use sha2::Sha256;
+
+use crate::config::Config;
+use crate::config::types::SessionEncryptionMode;
+
+/// Environment variable holding the passphrase for `session_encryption =
+/// "passphrase"`. It is never written to disk.
+pub const SESSION_PASSPHRASE_ENV_VAR: &str = "CODEX_SESSION_PASSPHRASE";
+
+/// Encrypted lines look like `codex-enc-v1:<key id>:<base64(nonce || sealed)>`.
+const ENCRYPTED_LINE_PREFIX: &str = "codex-enc-v1:";
+const KEYRING_SERVICE: &str = "Codex Session Encryption";
+const SALT_FILE: &str = "session_encryption.salt";
+const SALT_LEN: usize = 16;
+const KEY_LEN: usize = 32;
+const PBKDF2_ITERATIONS: u32 = 600_000;
+
+pub(crate) struct SessionCipher {
+    key_id: String,
+    key: LessSafeKey,
+}
+
+impl SessionCipher {
+    fn new(key: &[u8; KEY_LEN]) -> std::io::Result<Self> {
+        let unbound = UnboundKey::new(&AES_256_GCM, key)
+            .map_err(|_| std::io::Error::other("invalid session encryption key"))?;
+        let digest = format!("{:x}", Sha256::digest(key));
+        Ok(Self {
+            key_id: digest.get(..16).unwrap_or(&digest).to_string(),
+            key: LessSafeKey::new(unbound),
+        })
+    }
+
+    /// Seal one rollout line (without its trailing newline).
+    pub(crate) fn encrypt_line(&self, line: &str) -> std::io::Result<String> {
+        let mut nonce = [0u8; NONCE_LEN];
+        SystemRandom::new()
+            .fill(&mut nonce)
+            .map_err(|_| std::io::Error::other("failed to generate a nonce"))?;
+        let mut sealed = line.as_bytes().to_vec();
+        self.key
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(self.key_id.as_bytes()),
+                &mut sealed,
+            )
+            .map_err(|_| std::io::Error::other("failed to encrypt rollout line"))?;
+        let mut payload = nonce.to_vec();
+        payload.extend_from_slice(&sealed);
+        Ok(format!(
+            "{ENCRYPTED_LINE_PREFIX}{}:{}",
+            self.key_id,
+            BASE64_STANDARD.encode(payload)
+        ))
+    }
+
+    fn decrypt(&self, payload: &str) -> std::io::Result<String> {
+        let invalid =
+            || std::io::Error::new(std::io::ErrorKind::InvalidData, "corrupt rollout line");
+        let payload = BASE64_STANDARD.decode(payload).map_err(|_| invalid())?;
+        if payload.len() < NONCE_LEN {
+            return Err(invalid());
+        }
+        let (nonce, sealed) = payload.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| invalid())?;
+        let mut sealed = sealed.to_vec();
+        let plain = self
+            .key
+            .open_in_place(nonce, Aad::from(self.key_id.as_bytes()), &mut sealed)
+            .map_err(|_| invalid())?;
+        String::from_utf8(plain.to_vec()).map_err(|_| invalid())
+    }
+}
+
+/// Ciphers resolved in this process, by key id.
+static SESSION_CIPHERS: LazyLock<Mutex<HashMap<String, Arc<SessionCipher>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Resolved ciphers keyed by `CODEX_HOME` and mode.
+type ResolvedCiphers = HashMap<(PathBuf, SessionEncryptionMode), Arc<SessionCipher>>;
+
+/// Resolved ciphers by `CODEX_HOME` and mode, so the keychain is queried and
+/// the passphrase stretched once per process.
+static RESOLVED_CIPHERS: LazyLock<Mutex<ResolvedCiphers>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// The cipher for new rollout lines, or `None` when encryption is off.
+pub(crate) fn session_cipher(
+    codex_home: &Path,
+    mode: SessionEncryptionMode,
+) -> std::io::Result<Option<Arc<SessionCipher>>> {
+    session_cipher_with_keyring_store(codex_home, mode, &DefaultKeyringStore)
+}
+
+fn session_cipher_with_keyring_store(
+    codex_home: &Path,
+    mode: SessionEncryptionMode,
+    keyring_store: &dyn KeyringStore,
+) -> std::io::Result<Option<Arc<SessionCipher>>> {
+    let cache_key = (codex_home.to_path_buf(), mode);
+    if let Some(cipher) = RESOLVED_CIPHERS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(&cache_key)
+    {
+        return Ok(Some(cipher.clone()));
+    }
+
+    let key = match mode {
+        SessionEncryptionMode::Off => return Ok(None),
+        SessionEncryptionMode::Keyring => keyring_key(codex_home, keyring_store)?,
+        SessionEncryptionMode::Passphrase => {
+            let passphrase = std::env::var(SESSION_PASSPHRASE_ENV_VAR).map_err(|_| {
+                std::io::Error::other(format!(
+                    "session_encryption = \"passphrase\" requires {SESSION_PASSPHRASE_ENV_VAR} to be set"
+                ))
+            })?;
+            passphrase_key(codex_home, &passphrase)?
+        }
+    };
+    let cipher = Arc::new(SessionCipher::new(&key)?);
+    register_cipher(cipher.clone());
+    RESOLVED_CIPHERS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(cache_key, cipher.clone());
+    Ok(Some(cipher))
+}
+
+/// Resolve the configured key so sessions can be decrypted for listing and
+/// resuming. Failures are logged here and reported when a session is recorded.
+pub(crate) fn load_session_key(config: &Config) {
+    if let Err(err) = session_cipher(&config.codex_home, config.session_encryption) {
+        tracing::warn!("failed to load the session encryption key: {err}");
+    }
+}
+
+/// A registered cipher with a fixed key, for tests of the files it seals.
+#[cfg(test)]
+pub(crate) fn test_session_cipher() -> std::io::Result<Arc<SessionCipher>> {
+    let cipher = Arc::new(SessionCipher::new(&[42u8; KEY_LEN])?);
+    register_cipher(cipher.clone());
+    Ok(cipher)
+}
+
+fn register_cipher(cipher: Arc<SessionCipher>) {
+    SESSION_CIPHERS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(cipher.key_id.clone(), cipher);
+}
+
+/// Decrypt a rollout line if it is encrypted. Plain lines are returned as-is.
+/// Fails when the line was written with a key that has not been loaded.
+pub fn decode_rollout_line(line: &str) -> std::io::Result<Cow<'_, str>> {
+    let Some(rest) = line.strip_prefix(ENCRYPTED_LINE_PREFIX) else {
+        return Ok(Cow::Borrowed(line));
+    };
+    let Some((key_id, payload)) = rest.split_once(':') else {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "corrupt rollout line",
+        ));
+    };
+    let cipher = SESSION_CIPHERS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(key_id)
+        .cloned();
+    match cipher {
+        Some(cipher) => cipher.decrypt(payload).map(Cow::Owned),
+        None => Err(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            format!(
+                "session is encrypted with key {key_id}; set session_encryption in config.toml to read it"
+            ),
+        )),
+    }
+}
+
+fn keyring_key(
+    codex_home: &Path,
+    keyring_store: &dyn KeyringStore,
+) -> std::io::Result<[u8; KEY_LEN]> {
+    let account = keyring_account(codex_home);
+    let stored = keyring_store
+        .load(KEYRING_SERVICE, &account)
+        .map_err(|err| std::io::Error::other(err.message()))?;
+    if let Some(encoded) = stored {
+        return BASE64_STANDARD
+            .decode(encoded.trim())
+            .ok()
+            .and_then(|bytes| <[u8; KEY_LEN]>::try_from(bytes).ok())
+            .ok_or_else(|| std::io::Error::other("the keychain holds an invalid session key"));
+    }
+
+    let key: [u8; KEY_LEN] = random_bytes()?;
+    keyring_store
+        .save(KEYRING_SERVICE, &account, &BASE64_STANDARD.encode(key))
+        .map_err(|err| std::io::Error::other(err.message()))?;
+    Ok(key)
+}
+
+fn keyring_account(codex_home: &Path) -> String {
+    let canonical = codex_home
+        .canonicalize()
+        .unwrap_or_else(|_| codex_home.to_path_buf());
+    let digest = format!(
+        "{:x}",
+        Sha256::digest(canonical.to_string_lossy().as_bytes())
+    );
+    format!("sessions|{}", digest.get(..16).unwrap_or(&digest))
+}
+
+fn passphrase_key(codex_home: &Path, passphrase: &str) -> std::io::Result<[u8; KEY_LEN]> {
+    if passphrase.is_empty() {
+        return Err(std::io::Error::other(format!(
+            "{SESSION_PASSPHRASE_ENV_VAR} must not be empty"
+        )));
+    }
+    let salt = load_or_create_salt(codex_home)?;
+    let mut key = [0u8; KEY_LEN];
+    let iterations = NonZeroU32::new(PBKDF2_ITERATIONS).unwrap_or(NonZeroU32::MIN);
+    ring::pbkdf2::derive(
+        ring::pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        &salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+    Ok(key)
+}
+
+fn load_or_create_salt(codex_home: &Path) -> std::io::Result<[u8; SALT_LEN]> {
+    let path = codex_home.join(SALT_FILE);
+    match std::fs::read_to_string(&path) {
+        Ok(encoded) => BASE64_STANDARD
+            .decode(encoded.trim())
+            .ok()
+            .and_then(|bytes| <[u8; SALT_LEN]>::try_from(bytes).ok())
+            .ok_or_else(|| std::io::Error::other(format!("{} is corrupt", path.display()))),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            let salt: [u8; SALT_LEN] = random_bytes()?;
+            std::fs::create_dir_all(codex_home)?;
+            std::fs::write(&path, BASE64_STANDARD.encode(salt))?;
+            Ok(salt)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+fn random_bytes<const N: usize>() -> std::io::Result<[u8; N]> {
+    let mut bytes = [0u8; N];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| std::io::Error::other("failed to generate random bytes"))?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_keyring_store::tests::MockKeyringStore;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn keyring_key_round_trips_lines_and_is_reused() {
+        let codex_home = tempfile::tempdir().expect("tempdir");
+        let keyring_store = MockKeyringStore::default();
+        let cipher = session_cipher_with_keyring_store(
+            codex_home.path(),
+            SessionEncryptionMode::Keyring,
+            &keyring_store,
+        )
+        .expect("resolve key")
+        .expect("encryption enabled");
+
+        let line = r#"{"timestamp":"2025-01-01T00:00:00Z","type":"event_msg"}"#;
+        let encrypted = cipher.encrypt_line(line).expect("encrypt");
+        assert!(encrypted.starts_with(ENCRYPTED_LINE_PREFIX));
+        assert!(!encrypted.contains("event_msg"));
+        assert_ne!(cipher.encrypt_line(line).expect("encrypt"), encrypted);
+        assert_eq!(
+            decode_rollout_line(&encrypted).expect("decrypt").as_ref(),
+            line
+        );
+        assert_eq!(
+            decode_rollout_line(line).expect("plain line"),
+            Cow::Borrowed(line)
+        );
+
+        // The key lives in the keychain, so a fresh resolution reuses it.
+        let account = keyring_account(codex_home.path());
+        let stored = keyring_store.saved_value(&account).expect("key stored");
+        let key = <[u8; KEY_LEN]>::try_from(BASE64_STANDARD.decode(stored).expect("base64"))
+            .expect("key length");
+        let reloaded = SessionCipher::new(&key).expect("cipher");
+        assert_eq!(reloaded.key_id, cipher.key_id);
+        assert_eq!(
+            reloaded
+                .decrypt(encrypted.rsplit(':').next().expect("payload"))
+                .expect("decrypt"),
+            line
+        );
+    }
+
+    #[test]
+    fn lines_from_unknown_keys_or_tampered_payloads_are_rejected() {
+        let cipher = SessionCipher::new(&[7u8; KEY_LEN]).expect("cipher");
+        let encrypted = cipher.encrypt_line("secret").expect("encrypt");
+        let err = decode_rollout_line(&encrypted).expect_err("key not registered");
+        assert_eq!(err.kind(), std::io::ErrorKind::PermissionDenied);
+
+        register_cipher(Arc::new(
+            SessionCipher::new(&[7u8; KEY_LEN]).expect("cipher"),
+        ));
+        let mut tampered = encrypted.clone();
+        tampered.pop();
+        tampered.push(if encrypted.ends_with('A') { 'B' } else { 'A' });
+        let err = decode_rollout_line(&tampered).expect_err("tampered");
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(
+            decode_rollout_line(&encrypted).expect("decrypt").as_ref(),
+            "secret"
+        );
+    }
+}
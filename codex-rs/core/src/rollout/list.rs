@@ -15,6 +15,7 @@ use uuid::Uuid;
 
 use super::ARCHIVED_SESSIONS_SUBDIR;
 use super::SESSIONS_SUBDIR;
+use super::encryption::decode_rollout_line;
 use crate::instructions::UserInstructions;
 use crate::protocol::EventMsg;
 use crate::session_prefix::is_session_prefix_content;
@@ -964,7 +965,10 @@ async fn read_head_summary(path: &Path, head_limit: usize) -> io::Result<HeadTai
         }
         lines_scanned += 1;
 
-        let parsed: Result<RolloutLine, _> = serde_json::from_str(trimmed);
+        let Ok(trimmed) = decode_rollout_line(trimmed) else {
+            continue;
+        };
+        let parsed: Result<RolloutLine, _> = serde_json::from_str(&trimmed);
         let Ok(rollout_line) = parsed else { continue };
 
         match rollout_line.item {
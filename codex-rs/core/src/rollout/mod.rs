@@ -7,12 +7,17 @@ pub const ARCHIVED_SESSIONS_SUBDIR: &str = "archived_sessions";
 pub const INTERACTIVE_SESSION_SOURCES: &[SessionSource] =
     &[SessionSource::Cli, SessionSource::VSCode];
 
+pub(crate) mod encryption;
 pub(crate) mod error;
 pub mod list;
 pub(crate) mod metadata;
+pub(crate) mod normalize;
 pub(crate) mod policy;
 pub mod recorder;
+pub(crate) mod retention;
 pub(crate) mod session_index;
+pub(crate) mod session_summary;
+pub(crate) mod transcript;
 pub(crate) mod truncation;
 
 pub use codex_protocol::protocol::SessionMeta;
@@ -0,0 +1,209 @@
+//! Normalizes tool-call records before they are written to (and after they are
+//! read from) a rollout, so stored history does not depend on the provider
+//! that produced it. A session started against the Responses API can then be
+//! resumed against a Chat Completions or Anthropic provider, and vice versa.
+
+use codex_protocol::models::LocalShellAction;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::CompactedItem;
+use codex_protocol::protocol::RolloutItem;
+use serde_json::Map;
+use serde_json::Value;
+
+/// Name of the function tool that `local_shell` calls are stored as.
+const SHELL_TOOL_NAME: &str = "shell";
+
+pub(crate) fn normalize_rollout_item(item: RolloutItem) -> RolloutItem {
+    match item {
+        RolloutItem::ResponseItem(item) => RolloutItem::ResponseItem(normalize_tool_call(item)),
+        RolloutItem::Compacted(CompactedItem {
+            message,
+            replacement_history,
+        }) => RolloutItem::Compacted(CompactedItem {
+            message,
+            replacement_history: replacement_history
+                .map(|history| history.into_iter().map(normalize_tool_call).collect()),
+        }),
+        RolloutItem::SessionMeta(_) | RolloutItem::TurnContext(_) | RolloutItem::EventMsg(_) => {
+            item
+        }
+    }
+}
+
+/// Rewrites a tool call into the shape every provider can replay:
+/// - function call arguments are compact JSON, with `{}` for calls that
+///   streamed no arguments (Chat Completions and Anthropic stream them in
+///   provider-specific fragments);
+/// - `local_shell` calls, which only the Responses API understands, become
+///   `shell` function calls answered by the same `function_call_output`;
+/// - the Responses-only `status` of custom tool calls is dropped.
+pub(crate) fn normalize_tool_call(item: ResponseItem) -> ResponseItem {
+    match item {
+        ResponseItem::FunctionCall {
+            id,
+            name,
+            arguments,
+            call_id,
+        } => ResponseItem::FunctionCall {
+            id,
+            name,
+            arguments: normalize_arguments(arguments),
+            call_id,
+        },
+        ResponseItem::LocalShellCall {
+            id,
+            call_id,
+            status,
+            action,
+        } => match call_id.or(id) {
+            Some(call_id) => ResponseItem::FunctionCall {
+                id: None,
+                name: SHELL_TOOL_NAME.to_string(),
+                arguments: local_shell_arguments(action),
+                call_id,
+            },
+            None => ResponseItem::LocalShellCall {
+                id: None,
+                call_id: None,
+                status,
+                action,
+            },
+        },
+        ResponseItem::CustomToolCall {
+            id,
+            status: _,
+            call_id,
+            name,
+            input,
+        } => ResponseItem::CustomToolCall {
+            id,
+            status: None,
+            call_id,
+            name,
+            input,
+        },
+        ResponseItem::Message { .. }
+        | ResponseItem::Reasoning { .. }
+        | ResponseItem::FunctionCallOutput { .. }
+        | ResponseItem::CustomToolCallOutput { .. }
+        | ResponseItem::WebSearchCall { .. }
+        | ResponseItem::GhostSnapshot { .. }
+        | ResponseItem::Compaction { .. }
+        | ResponseItem::Other => item,
+    }
+}
+
+/// Malformed arguments are kept verbatim: the model is shown its own mistake
+/// alongside the error the tool returned for it.
+fn normalize_arguments(arguments: String) -> String {
+    if arguments.trim().is_empty() {
+        return "{}".to_string();
+    }
+    match serde_json::from_str::<Value>(&arguments) {
+        Ok(value) => value.to_string(),
+        Err(_) => arguments,
+    }
+}
+
+/// Arguments in the shape the `shell` tool accepts.
+fn local_shell_arguments(action: LocalShellAction) -> String {
+    let LocalShellAction::Exec(exec) = action;
+    let mut arguments = Map::new();
+    arguments.insert("command".to_string(), Value::from(exec.command));
+    if let Some(workdir) = exec.working_directory {
+        arguments.insert("workdir".to_string(), Value::from(workdir));
+    }
+    if let Some(timeout_ms) = exec.timeout_ms {
+        arguments.insert("timeout_ms".to_string(), Value::from(timeout_ms));
+    }
+    Value::Object(arguments).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::LocalShellExecAction;
+    use codex_protocol::models::LocalShellStatus;
+    use pretty_assertions::assert_eq;
+
+    fn function_call(name: &str, arguments: &str) -> ResponseItem {
+        ResponseItem::FunctionCall {
+            id: None,
+            name: name.to_string(),
+            arguments: arguments.to_string(),
+            call_id: "call-1".to_string(),
+        }
+    }
+
+    #[test]
+    fn function_call_arguments_are_compacted() {
+        assert_eq!(
+            normalize_tool_call(function_call(
+                "shell",
+                "{ \"command\": [\"ls\", \"-l\"],\n  \"workdir\": \"/tmp\" }"
+            )),
+            function_call("shell", r#"{"command":["ls","-l"],"workdir":"/tmp"}"#)
+        );
+        assert_eq!(
+            normalize_tool_call(function_call("list_mcp_resources", "")),
+            function_call("list_mcp_resources", "{}")
+        );
+        assert_eq!(
+            normalize_tool_call(function_call("shell", "{\"command\": [\"ls\"")),
+            function_call("shell", "{\"command\": [\"ls\"")
+        );
+    }
+
+    #[test]
+    fn local_shell_calls_become_shell_function_calls() {
+        let item = ResponseItem::LocalShellCall {
+            id: Some("lsh_1".to_string()),
+            call_id: Some("call-1".to_string()),
+            status: LocalShellStatus::Completed,
+            action: LocalShellAction::Exec(LocalShellExecAction {
+                command: vec!["ls".to_string(), "-l".to_string()],
+                timeout_ms: Some(1000),
+                working_directory: None,
+                env: None,
+                user: None,
+            }),
+        };
+
+        assert_eq!(
+            normalize_tool_call(item),
+            function_call("shell", r#"{"command":["ls","-l"],"timeout_ms":1000}"#)
+        );
+    }
+
+    #[test]
+    fn compacted_history_is_normalized() {
+        let item = RolloutItem::Compacted(CompactedItem {
+            message: "summary".to_string(),
+            replacement_history: Some(vec![ResponseItem::CustomToolCall {
+                id: None,
+                status: Some("completed".to_string()),
+                call_id: "call-1".to_string(),
+                name: "apply_patch".to_string(),
+                input: "*** Begin Patch".to_string(),
+            }]),
+        });
+
+        let RolloutItem::Compacted(CompactedItem {
+            replacement_history: Some(history),
+            ..
+        }) = normalize_rollout_item(item)
+        else {
+            panic!("expected a compacted item");
+        };
+        assert_eq!(
+            history,
+            vec![ResponseItem::CustomToolCall {
+                id: None,
+                status: None,
+                call_id: "call-1".to_string(),
+                name: "apply_patch".to_string(),
+                input: "*** Begin Patch".to_string(),
+            }]
+        );
+    }
+}
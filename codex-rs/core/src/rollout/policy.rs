@@ -42,11 +42,15 @@ pub(crate) fn should_persist_event_msg(ev: &EventMsg) -> bool {
         | EventMsg::AgentReasoning(_)
         | EventMsg::AgentReasoningRawContent(_)
         | EventMsg::TokenCount(_)
+        | EventMsg::ModelSnapshot(_)
+        | EventMsg::ModelFailover(_)
         | EventMsg::ContextCompacted(_)
         | EventMsg::EnteredReviewMode(_)
         | EventMsg::ExitedReviewMode(_)
         | EventMsg::ThreadRolledBack(_)
+        | EventMsg::ContextEdited(_)
         | EventMsg::UndoCompleted(_)
+        | EventMsg::CheckpointCreated(_)
         | EventMsg::TurnAborted(_) => true,
         EventMsg::ItemCompleted(event) => {
             // Plan items are derived from streaming tags and are not part of the
@@ -56,6 +60,7 @@ pub(crate) fn should_persist_event_msg(ev: &EventMsg) -> bool {
         }
         EventMsg::Error(_)
         | EventMsg::Warning(_)
+        | EventMsg::TurnBlocked(_)
         | EventMsg::TurnStarted(_)
         | EventMsg::TurnComplete(_)
         | EventMsg::AgentMessageDelta(_)
@@ -85,14 +90,22 @@ pub(crate) fn should_persist_event_msg(ev: &EventMsg) -> bool {
         | EventMsg::TurnDiff(_)
         | EventMsg::GetHistoryEntryResponse(_)
         | EventMsg::UndoStarted(_)
+        | EventMsg::ContextEditPreview(_)
+        | EventMsg::PinnedContextUpdated(_)
         | EventMsg::McpListToolsResponse(_)
+        | EventMsg::McpLoginCompleted(_)
+        | EventMsg::McpToolCallProgress(_)
+        | EventMsg::McpServerStatusResponse(_)
         | EventMsg::McpStartupUpdate(_)
         | EventMsg::McpStartupComplete(_)
         | EventMsg::ListCustomPromptsResponse(_)
         | EventMsg::ListSkillsResponse(_)
+        | EventMsg::ListTrashResponse(_)
+        | EventMsg::ListJobsResponse(_)
         | EventMsg::PlanUpdate(_)
         | EventMsg::ShutdownComplete
         | EventMsg::ViewImageToolCall(_)
+        | EventMsg::OpenFileRequest(_)
         | EventMsg::DeprecationNotice(_)
         | EventMsg::ItemStarted(_)
         | EventMsg::AgentMessageContentDelta(_)
@@ -5,6 +5,7 @@ use std::fs::{self};
 use std::io::Error as IoError;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use codex_protocol::ThreadId;
 use codex_protocol::dynamic_tools::DynamicToolSpec;
@@ -22,6 +23,9 @@ use tracing::warn;
 
 use super::ARCHIVED_SESSIONS_SUBDIR;
 use super::SESSIONS_SUBDIR;
+use super::encryption::SessionCipher;
+use super::encryption::decode_rollout_line;
+use super::encryption::session_cipher;
 use super::list::Cursor;
 use super::list::ThreadListConfig;
 use super::list::ThreadListLayout;
@@ -30,11 +34,14 @@ use super::list::ThreadsPage;
 use super::list::get_threads;
 use super::list::get_threads_in_root;
 use super::metadata;
+use super::normalize::normalize_rollout_item;
 use super::policy::is_persisted_response_item;
+use super::retention::spawn_session_gc;
 use crate::config::Config;
 use crate::default_client::originator;
 use crate::git_info::collect_git_info;
 use crate::path_utils;
+use crate::redaction::Redactor;
 use crate::state_db;
 use crate::state_db::StateDbHandle;
 use codex_protocol::protocol::InitialHistory;
@@ -271,6 +278,7 @@ impl RolloutRecorder {
                     conversation_id: session_id,
                     timestamp,
                 } = create_log_file(config, conversation_id)?;
+                spawn_session_gc(config);
 
                 let timestamp_format: &[FormatItem] = format_description!(
                     "[year]-[month]-[day]T[hour]:[minute]:[second].[subsecond digits:3]Z"
@@ -311,6 +319,10 @@ impl RolloutRecorder {
             ),
         };
 
+        // Resolve the encryption key up front so a missing passphrase or
+        // keychain fails session creation instead of writing plain text.
+        let cipher = session_cipher(&config.codex_home, config.session_encryption)?;
+
         // Clone the cwd for the spawned task to collect git info asynchronously
         let cwd = config.cwd.clone();
 
@@ -331,6 +343,8 @@ impl RolloutRecorder {
             state_db_ctx.clone(),
             state_builder,
             config.model_provider_id.clone(),
+            Redactor::from_config(&config.redaction),
+            cipher,
         ));
 
         Ok(Self {
@@ -355,7 +369,7 @@ impl RolloutRecorder {
             // "fully qualified MCP tool calls," so we could consider
             // reformatting them in that case.
             if is_persisted_response_item(item) {
-                filtered.push(item.clone());
+                filtered.push(normalize_rollout_item(item.clone()));
             }
         }
         if filtered.is_empty() {
@@ -394,7 +408,18 @@ impl RolloutRecorder {
             if line.trim().is_empty() {
                 continue;
             }
-            let v: Value = match serde_json::from_str(line) {
+            // A missing key fails the load; a corrupt line is skipped like
+            // malformed JSON.
+            let line = match decode_rollout_line(line) {
+                Ok(line) => line,
+                Err(e) if e.kind() == std::io::ErrorKind::InvalidData => {
+                    warn!("failed to decrypt rollout line: {e}");
+                    parse_errors = parse_errors.saturating_add(1);
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+            let v: Value = match serde_json::from_str(&line) {
                 Ok(v) => v,
                 Err(e) => {
                     warn!("failed to parse line as JSON: {line:?}, error: {e}");
@@ -414,11 +439,9 @@ impl RolloutRecorder {
                         }
                         items.push(RolloutItem::SessionMeta(session_meta_line));
                     }
-                    RolloutItem::ResponseItem(item) => {
-                        items.push(RolloutItem::ResponseItem(item));
-                    }
-                    RolloutItem::Compacted(item) => {
-                        items.push(RolloutItem::Compacted(item));
+                    // Older rollouts were written before tool calls were normalized.
+                    item @ (RolloutItem::ResponseItem(_) | RolloutItem::Compacted(_)) => {
+                        items.push(normalize_rollout_item(item));
                     }
                     RolloutItem::TurnContext(item) => {
                         items.push(RolloutItem::TurnContext(item));
@@ -535,8 +558,14 @@ async fn rollout_writer(
     state_db_ctx: Option<StateDbHandle>,
     mut state_builder: Option<ThreadMetadataBuilder>,
     default_provider: String,
+    redactor: Option<Redactor>,
+    cipher: Option<Arc<SessionCipher>>,
 ) -> std::io::Result<()> {
-    let mut writer = JsonlWriter { file };
+    let mut writer = JsonlWriter {
+        file,
+        redactor,
+        cipher,
+    };
     if let Some(builder) = state_builder.as_mut() {
         builder.rollout_path = rollout_path.clone();
     }
@@ -612,6 +641,10 @@ async fn rollout_writer(
 
 struct JsonlWriter {
     file: tokio::fs::File,
+    /// Masks secrets in every line before it reaches disk.
+    redactor: Option<Redactor>,
+    /// Encrypts every line when `session_encryption` is enabled.
+    cipher: Option<Arc<SessionCipher>>,
 }
 
 #[derive(serde::Serialize)]
@@ -637,7 +670,17 @@ impl JsonlWriter {
         self.write_line(&line).await
     }
     async fn write_line(&mut self, item: &impl serde::Serialize) -> std::io::Result<()> {
-        let mut json = serde_json::to_string(item)?;
+        let mut json = match &self.redactor {
+            Some(redactor) => {
+                let mut value = serde_json::to_value(item)?;
+                redactor.redact_json(&mut value);
+                serde_json::to_string(&value)?
+            }
+            None => serde_json::to_string(item)?,
+        };
+        if let Some(cipher) = &self.cipher {
+            json = cipher.encrypt_line(&json)?;
+        }
         json.push('\n');
         self.file.write_all(json.as_bytes()).await?;
         self.file.flush().await?;
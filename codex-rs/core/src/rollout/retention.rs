@@ -0,0 +1,363 @@
+//! Retention limits for recorded sessions and logs.
+//!
+//! `[session_retention]` bounds how much history accumulates under
+//! `CODEX_HOME`: rollout files (active and archived) and log files older than
+//! `max_age_days` are deleted, then the oldest rollouts go once the total
+//! exceeds `max_total_size_mb` or a working directory has more than
+//! `max_sessions_per_project` sessions. The stored summaries of deleted
+//! sessions are dropped with them. The limits are applied once per process in
+//! the background when a session starts, and on demand by
+//! `codex sessions prune`.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::io::BufRead;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::RolloutLine;
+use codex_protocol::protocol::SessionMeta;
+use tracing::info;
+use tracing::warn;
+
+use super::ARCHIVED_SESSIONS_SUBDIR;
+use super::SESSIONS_SUBDIR;
+use super::encryption::decode_rollout_line;
+use super::session_summary::remove_session_summaries;
+use crate::config::Config;
+use crate::config::log_dir;
+use crate::config::types::SessionRetentionConfig;
+
+/// Files modified this recently may belong to a running session, so they are
+/// never removed, although they count toward the size and per-project limits.
+const ACTIVE_SESSION_GRACE: Duration = Duration::from_secs(60 * 60);
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+static SESSION_GC_STARTED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PruneReport {
+    /// Rollout and log files that were deleted, or would be on a dry run.
+    pub removed: Vec<PathBuf>,
+    pub freed_bytes: u64,
+    /// Rollout files left in place.
+    pub kept: usize,
+}
+
+struct SessionFile {
+    path: PathBuf,
+    modified: SystemTime,
+    size: u64,
+}
+
+/// Apply `policy` to the sessions and logs under `codex_home`. With `dry_run`
+/// the report lists what would be deleted and nothing is touched.
+pub fn prune_sessions(
+    codex_home: &Path,
+    log_dir: &Path,
+    policy: &SessionRetentionConfig,
+    dry_run: bool,
+) -> std::io::Result<PruneReport> {
+    prune_sessions_at(codex_home, log_dir, policy, dry_run, SystemTime::now())
+}
+
+/// Prune in the background the first time a session is recorded in this
+/// process. Does nothing when no limit is configured.
+pub(crate) fn spawn_session_gc(config: &Config) {
+    if config.session_retention.is_unbounded() || SESSION_GC_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let Ok(log_dir) = log_dir(config) else {
+        return;
+    };
+    let codex_home = config.codex_home.clone();
+    let policy = config.session_retention;
+    tokio::task::spawn_blocking(move || {
+        match prune_sessions(&codex_home, &log_dir, &policy, false) {
+            Ok(report) if !report.removed.is_empty() => info!(
+                "pruned {} old session files ({} bytes)",
+                report.removed.len(),
+                report.freed_bytes
+            ),
+            Ok(_) => {}
+            Err(err) => warn!("failed to prune old sessions: {err}"),
+        }
+    });
+}
+
+fn prune_sessions_at(
+    codex_home: &Path,
+    log_dir: &Path,
+    policy: &SessionRetentionConfig,
+    dry_run: bool,
+    now: SystemTime,
+) -> std::io::Result<PruneReport> {
+    let max_age = policy
+        .max_age_days
+        .map(|days| Duration::from_secs(days.saturating_mul(SECONDS_PER_DAY)));
+    let mut report = PruneReport::default();
+
+    let mut files = Vec::new();
+    for subdir in [SESSIONS_SUBDIR, ARCHIVED_SESSIONS_SUBDIR] {
+        collect_rollout_files(&codex_home.join(subdir), &mut files)?;
+    }
+    // Newest first, so the size and per-project limits keep recent sessions.
+    files.sort_by(|a, b| b.modified.cmp(&a.modified));
+
+    let mut total_bytes = 0u64;
+    let mut sessions_per_project: HashMap<PathBuf, usize> = HashMap::new();
+    let mut removed_threads: HashSet<ThreadId> = HashSet::new();
+    for file in files {
+        let age = now.duration_since(file.modified).unwrap_or_default();
+        let project = policy
+            .max_sessions_per_project
+            .and_then(|_| session_meta(&file.path))
+            .map(|meta| meta.cwd);
+        let project_sessions = project.as_ref().map(|cwd| {
+            let count = sessions_per_project.entry(cwd.clone()).or_default();
+            *count += 1;
+            *count
+        });
+        total_bytes = total_bytes.saturating_add(file.size);
+
+        let over_limit = max_age.is_some_and(|max_age| age > max_age)
+            || policy
+                .max_total_bytes
+                .is_some_and(|max_bytes| total_bytes > max_bytes)
+            || policy
+                .max_sessions_per_project
+                .zip(project_sessions)
+                .is_some_and(|(max, count)| count > max);
+        if !over_limit || age < ACTIVE_SESSION_GRACE {
+            report.kept += 1;
+            continue;
+        }
+
+        if !dry_run {
+            if let Some(meta) = session_meta(&file.path) {
+                removed_threads.insert(meta.id);
+            }
+            std::fs::remove_file(&file.path)?;
+            remove_empty_parents(&file.path, codex_home);
+        }
+        total_bytes = total_bytes.saturating_sub(file.size);
+        if let Some(count) = project.and_then(|cwd| sessions_per_project.get_mut(&cwd)) {
+            *count -= 1;
+        }
+        report.freed_bytes = report.freed_bytes.saturating_add(file.size);
+        report.removed.push(file.path);
+    }
+
+    if !removed_threads.is_empty() {
+        remove_session_summaries(codex_home, &removed_threads)?;
+    }
+    if let Some(max_age) = max_age {
+        prune_logs(log_dir, max_age, dry_run, now, &mut report)?;
+    }
+    Ok(report)
+}
+
+fn prune_logs(
+    log_dir: &Path,
+    max_age: Duration,
+    dry_run: bool,
+    now: SystemTime,
+    report: &mut PruneReport,
+) -> std::io::Result<()> {
+    let entries = match std::fs::read_dir(log_dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err),
+    };
+    for entry in entries {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if !metadata.is_file() {
+            continue;
+        }
+        let age = now.duration_since(metadata.modified()?).unwrap_or_default();
+        if age <= max_age.max(ACTIVE_SESSION_GRACE) {
+            continue;
+        }
+        if !dry_run {
+            std::fs::remove_file(entry.path())?;
+        }
+        report.freed_bytes = report.freed_bytes.saturating_add(metadata.len());
+        report.removed.push(entry.path());
+    }
+    Ok(())
+}
+
+fn collect_rollout_files(dir: &Path, files: &mut Vec<SessionFile>) -> std::io::Result<()> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err),
+    };
+    for entry in entries {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        let path = entry.path();
+        if metadata.is_dir() {
+            collect_rollout_files(&path, files)?;
+            continue;
+        }
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if metadata.is_file() && name.starts_with("rollout-") && name.ends_with(".jsonl") {
+            files.push(SessionFile {
+                path,
+                modified: metadata.modified()?,
+                size: metadata.len(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// The session meta on the rollout's first line.
+fn session_meta(path: &Path) -> Option<SessionMeta> {
+    let file = std::fs::File::open(path).ok()?;
+    let mut first_line = String::new();
+    std::io::BufReader::new(file)
+        .read_line(&mut first_line)
+        .ok()?;
+    let line = decode_rollout_line(first_line.trim()).ok()?;
+    match serde_json::from_str::<RolloutLine>(&line).ok()?.item {
+        RolloutItem::SessionMeta(meta_line) => Some(meta_line.meta),
+        _ => None,
+    }
+}
+
+/// Remove the `YYYY/MM/DD` directories a deleted rollout leaves empty.
+fn remove_empty_parents(path: &Path, codex_home: &Path) {
+    let roots = [
+        codex_home.join(SESSIONS_SUBDIR),
+        codex_home.join(ARCHIVED_SESSIONS_SUBDIR),
+    ];
+    let mut dir = path.parent();
+    while let Some(current) = dir {
+        if roots.iter().any(|root| root == current) || std::fs::remove_dir(current).is_err() {
+            break;
+        }
+        dir = current.parent();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rollout::session_summary::SessionSummary;
+    use crate::rollout::session_summary::append_session_summary;
+    use crate::rollout::session_summary::find_session_summaries_by_ids;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    const DAY: Duration = Duration::from_secs(SECONDS_PER_DAY);
+
+    fn write_rollout(
+        codex_home: &Path,
+        day: &str,
+        id: &str,
+        cwd: &str,
+        modified: SystemTime,
+    ) -> PathBuf {
+        let dir = codex_home.join(SESSIONS_SUBDIR).join(day);
+        std::fs::create_dir_all(&dir).expect("create day dir");
+        let path = dir.join(format!("rollout-{day}-{id}.jsonl"));
+        let meta = json!({
+            "timestamp": "2025-01-01T00:00:00.000Z",
+            "type": "session_meta",
+            "payload": {
+                "id": ThreadId::new(),
+                "timestamp": "2025-01-01T00:00:00.000Z",
+                "cwd": cwd,
+                "originator": "codex_cli_rs",
+                "cli_version": "0.0.0",
+            },
+        });
+        std::fs::write(&path, format!("{meta}\n")).expect("write rollout");
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .and_then(|file| file.set_modified(modified))
+            .expect("set mtime");
+        path
+    }
+
+    #[test]
+    fn prune_applies_age_and_per_project_limits_to_old_sessions() {
+        let codex_home = tempfile::tempdir().expect("tempdir");
+        let home = codex_home.path();
+        let now = SystemTime::now();
+        let expired = write_rollout(home, "2025/01/01", "a", "/repo", now - DAY * 40);
+        let third_newest = write_rollout(home, "2025/02/01", "b", "/repo", now - DAY * 3);
+        let second_newest = write_rollout(home, "2025/02/02", "c", "/repo", now - DAY * 2);
+        let newest = write_rollout(home, "2025/02/03", "d", "/repo", now - DAY);
+        let other_project = write_rollout(home, "2025/02/01", "e", "/other", now - DAY * 5);
+        // Running sessions are never removed, even past the per-project cap.
+        let active = write_rollout(home, "2025/02/04", "f", "/repo", now);
+
+        let policy = SessionRetentionConfig {
+            max_age_days: Some(30),
+            max_total_bytes: None,
+            max_sessions_per_project: Some(2),
+        };
+        let log_dir = home.join("log");
+
+        let dry_run = prune_sessions_at(home, &log_dir, &policy, true, now).expect("dry run");
+        assert!(expired.exists());
+
+        let report = prune_sessions_at(home, &log_dir, &policy, false, now).expect("prune");
+        assert_eq!(dry_run, report);
+        let mut removed = report.removed.clone();
+        removed.sort();
+        let mut expected = vec![expired.clone(), third_newest.clone(), second_newest.clone()];
+        expected.sort();
+        assert_eq!(removed, expected);
+        assert_eq!(report.kept, 3);
+        for path in [&newest, &other_project, &active] {
+            assert!(path.exists(), "{} should be kept", path.display());
+        }
+        assert!(!home.join(SESSIONS_SUBDIR).join("2025/01").exists());
+    }
+
+    #[tokio::test]
+    async fn prune_drops_summaries_of_removed_sessions() -> std::io::Result<()> {
+        let codex_home = tempfile::tempdir()?;
+        let home = codex_home.path();
+        let now = SystemTime::now();
+        let expired = write_rollout(home, "2025/01/01", "a", "/repo", now - DAY * 40);
+        let recent = write_rollout(home, "2025/02/01", "b", "/repo", now - DAY);
+        let thread_id = |path: &Path| session_meta(path).expect("session meta").id;
+        let (expired_id, recent_id) = (thread_id(&expired), thread_id(&recent));
+        for id in [expired_id, recent_id] {
+            let summary = SessionSummary {
+                id,
+                title: "Fix the login test".to_string(),
+                outcome: "Done".to_string(),
+            };
+            append_session_summary(home, &summary, None).await?;
+        }
+
+        let policy = SessionRetentionConfig {
+            max_age_days: Some(30),
+            max_total_bytes: None,
+            max_sessions_per_project: None,
+        };
+        let report = prune_sessions_at(home, &home.join("log"), &policy, false, now)?;
+        assert_eq!(report.removed, vec![expired]);
+
+        let summaries =
+            find_session_summaries_by_ids(home, &HashSet::from([expired_id, recent_id])).await?;
+        assert_eq!(summaries.keys().collect::<Vec<_>>(), vec![&recent_id]);
+        Ok(())
+    }
+}
@@ -0,0 +1,297 @@
+//! Two-line session summaries shown by `codex sessions list` and the resume picker.
+//!
+//! Summaries are extracted from the rollout without calling a model: the first
+//! line is the opening user request and the second is the gist of the agent's
+//! last reply. They are appended to `CODEX_HOME/session_summaries.jsonl` when a
+//! session shuts down so listings do not need to read every rollout file. With
+//! `session_encryption` on, each line is sealed like a rollout line.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::RolloutItem;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncWriteExt;
+
+use super::RolloutRecorder;
+use super::encryption::SessionCipher;
+use super::encryption::decode_rollout_line;
+
+const SESSION_SUMMARIES_FILE: &str = "session_summaries.jsonl";
+const SUMMARY_LINE_MAX_CHARS: usize = 100;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SessionSummary {
+    pub id: ThreadId,
+    /// What the session was about: the first user request.
+    pub title: String,
+    /// How it ended: the first line of the last agent reply, or the turn count
+    /// when the agent never replied.
+    pub outcome: String,
+}
+
+/// Extract a summary from rollout items, or `None` when the session has no
+/// user messages yet.
+pub fn summarize_rollout_items(id: ThreadId, items: &[RolloutItem]) -> Option<SessionSummary> {
+    let mut title = None;
+    let mut last_reply = None;
+    let mut turns = 0usize;
+    for item in items {
+        match item {
+            RolloutItem::EventMsg(EventMsg::UserMessage(event)) => {
+                turns += 1;
+                if title.is_none() {
+                    title = summary_line(&event.message);
+                }
+            }
+            RolloutItem::EventMsg(EventMsg::AgentMessage(event)) => {
+                if let Some(line) = summary_line(&event.message) {
+                    last_reply = Some(line);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let title = title?;
+    let outcome = last_reply.unwrap_or_else(|| match turns {
+        1 => "1 turn, no reply yet".to_string(),
+        turns => format!("{turns} turns, no reply yet"),
+    });
+    Some(SessionSummary { id, title, outcome })
+}
+
+/// Read a rollout file and extract its summary.
+pub async fn summarize_rollout_file(path: &Path) -> std::io::Result<Option<SessionSummary>> {
+    let (items, thread_id, _parse_errors) = RolloutRecorder::load_rollout_items(path).await?;
+    Ok(thread_id.and_then(|thread_id| summarize_rollout_items(thread_id, &items)))
+}
+
+/// Append a summary to the summaries file, sealed with `cipher` when session
+/// encryption is on. The file is append-only; the most recent entry for a
+/// thread wins.
+pub(crate) async fn append_session_summary(
+    codex_home: &Path,
+    summary: &SessionSummary,
+    cipher: Option<&SessionCipher>,
+) -> std::io::Result<()> {
+    let path = session_summaries_path(codex_home);
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .await?;
+    let mut line = serde_json::to_string(summary).map_err(std::io::Error::other)?;
+    if let Some(cipher) = cipher {
+        line = cipher.encrypt_line(&line)?;
+    }
+    line.push('\n');
+    file.write_all(line.as_bytes()).await?;
+    file.flush().await?;
+    Ok(())
+}
+
+/// Find the latest stored summaries for a batch of thread ids.
+pub async fn find_session_summaries_by_ids(
+    codex_home: &Path,
+    thread_ids: &HashSet<ThreadId>,
+) -> std::io::Result<HashMap<ThreadId, SessionSummary>> {
+    let path = session_summaries_path(codex_home);
+    if thread_ids.is_empty() || !path.exists() {
+        return Ok(HashMap::new());
+    }
+
+    let file = tokio::fs::File::open(&path).await?;
+    let reader = tokio::io::BufReader::new(file);
+    let mut lines = reader.lines();
+    let mut summaries = HashMap::with_capacity(thread_ids.len());
+
+    while let Some(line) = lines.next_line().await? {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        // Lines sealed with a key that is not loaded are skipped; those
+        // sessions are summarized from their rollouts, or not at all.
+        let Ok(line) = decode_rollout_line(trimmed) else {
+            continue;
+        };
+        let Ok(summary) = serde_json::from_str::<SessionSummary>(&line) else {
+            continue;
+        };
+        if thread_ids.contains(&summary.id) {
+            summaries.insert(summary.id, summary);
+        }
+    }
+
+    Ok(summaries)
+}
+
+/// Drop the stored summaries of `thread_ids`, for sessions whose rollouts were
+/// deleted. Lines that cannot be decrypted are kept.
+pub(crate) fn remove_session_summaries(
+    codex_home: &Path,
+    thread_ids: &HashSet<ThreadId>,
+) -> std::io::Result<()> {
+    let path = session_summaries_path(codex_home);
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err),
+    };
+    let mut kept = String::with_capacity(contents.len());
+    let mut removed_any = false;
+    for line in contents.lines() {
+        let removed = decode_rollout_line(line.trim())
+            .ok()
+            .and_then(|line| serde_json::from_str::<SessionSummary>(&line).ok())
+            .is_some_and(|summary| thread_ids.contains(&summary.id));
+        if removed {
+            removed_any = true;
+        } else {
+            kept.push_str(line);
+            kept.push('\n');
+        }
+    }
+    if !removed_any {
+        return Ok(());
+    }
+    let tmp_path = path.with_extension("jsonl.tmp");
+    std::fs::write(&tmp_path, kept)?;
+    std::fs::rename(&tmp_path, &path)
+}
+
+fn session_summaries_path(codex_home: &Path) -> PathBuf {
+    codex_home.join(SESSION_SUMMARIES_FILE)
+}
+
+/// First non-empty line of `text` with markdown markers stripped, whitespace
+/// collapsed, and the result shortened to `SUMMARY_LINE_MAX_CHARS`.
+fn summary_line(text: &str) -> Option<String> {
+    let line = text
+        .lines()
+        .map(|line| {
+            line.trim_start_matches(['#', '*', '-', '>', ' ', '\t'])
+                .trim()
+        })
+        .find(|line| !line.is_empty() && !line.starts_with("```"))?;
+    let collapsed = line.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.chars().count() <= SUMMARY_LINE_MAX_CHARS {
+        return Some(collapsed);
+    }
+    let mut truncated: String = collapsed.chars().take(SUMMARY_LINE_MAX_CHARS - 1).collect();
+    truncated.push('…');
+    Some(truncated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rollout::encryption::test_session_cipher;
+    use codex_protocol::protocol::AgentMessageEvent;
+    use codex_protocol::protocol::UserMessageEvent;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn user(message: &str) -> RolloutItem {
+        RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+            message: message.to_string(),
+            images: None,
+            local_images: Vec::new(),
+            text_elements: Vec::new(),
+        }))
+    }
+
+    fn agent(message: &str) -> RolloutItem {
+        RolloutItem::EventMsg(EventMsg::AgentMessage(AgentMessageEvent {
+            message: message.to_string(),
+        }))
+    }
+
+    #[test]
+    fn summary_uses_first_request_and_last_reply() {
+        let id = ThreadId::new();
+        let items = vec![
+            user("  Fix the flaky\n\nlogin test"),
+            agent("Looking into it."),
+            user("also update the docs"),
+            agent("## Done\n\nThe   login test now waits for the server.\nMore details."),
+        ];
+
+        assert_eq!(
+            summarize_rollout_items(id, &items),
+            Some(SessionSummary {
+                id,
+                title: "Fix the flaky".to_string(),
+                outcome: "Done".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn summary_without_reply_reports_turns() {
+        let id = ThreadId::new();
+        let items = vec![user("first"), user("second")];
+
+        let summary = summarize_rollout_items(id, &items).expect("summary");
+        assert_eq!(summary.outcome, "2 turns, no reply yet");
+        assert_eq!(summarize_rollout_items(id, &[agent("hello")]), None);
+    }
+
+    #[test]
+    fn long_lines_are_truncated() {
+        let line = summary_line(&"word ".repeat(50)).expect("line");
+        assert_eq!(line.chars().count(), SUMMARY_LINE_MAX_CHARS);
+        assert!(line.ends_with('…'));
+    }
+
+    #[tokio::test]
+    async fn latest_stored_summary_wins() -> std::io::Result<()> {
+        let temp = TempDir::new()?;
+        let id = ThreadId::new();
+        let other = ThreadId::new();
+        for (thread_id, title) in [(id, "old"), (other, "other"), (id, "new")] {
+            append_session_summary(
+                temp.path(),
+                &SessionSummary {
+                    id: thread_id,
+                    title: title.to_string(),
+                    outcome: "done".to_string(),
+                },
+                None,
+            )
+            .await?;
+        }
+
+        let summaries = find_session_summaries_by_ids(temp.path(), &HashSet::from([id])).await?;
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[&id].title, "new");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn encrypted_summaries_are_not_stored_in_plain_text() -> std::io::Result<()> {
+        let temp = TempDir::new()?;
+        let cipher = test_session_cipher()?;
+        let id = ThreadId::new();
+        let summary = SessionSummary {
+            id,
+            title: "Rotate the production API keys".to_string(),
+            outcome: "Rotated both keys".to_string(),
+        };
+        append_session_summary(temp.path(), &summary, Some(&cipher)).await?;
+
+        let stored = tokio::fs::read_to_string(session_summaries_path(temp.path())).await?;
+        assert!(!stored.contains("production API keys"));
+        assert!(!stored.contains("Rotated both keys"));
+        let summaries = find_session_summaries_by_ids(temp.path(), &HashSet::from([id])).await?;
+        assert_eq!(summaries.get(&id), Some(&summary));
+        Ok(())
+    }
+}
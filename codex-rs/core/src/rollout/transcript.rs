@@ -0,0 +1,128 @@
+//! Plain-text transcripts of recorded sessions.
+//!
+//! The TUI history browser searches, previews, and exports these. Only the
+//! user requests and agent replies are kept; tool calls, reasoning, and
+//! session-prefix messages are left out.
+
+use std::path::Path;
+
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::RolloutItem;
+
+use super::RolloutRecorder;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscriptRole {
+    User,
+    Assistant,
+}
+
+impl TranscriptRole {
+    pub fn label(self) -> &'static str {
+        match self {
+            TranscriptRole::User => "User",
+            TranscriptRole::Assistant => "Codex",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranscriptEntry {
+    pub role: TranscriptRole,
+    pub text: String,
+}
+
+/// Extract the user requests and agent replies from rollout items, oldest
+/// first.
+pub fn transcript_from_rollout_items(items: &[RolloutItem]) -> Vec<TranscriptEntry> {
+    items
+        .iter()
+        .filter_map(|item| match item {
+            RolloutItem::EventMsg(EventMsg::UserMessage(event)) => {
+                Some((TranscriptRole::User, event.message.as_str()))
+            }
+            RolloutItem::EventMsg(EventMsg::AgentMessage(event)) => {
+                Some((TranscriptRole::Assistant, event.message.as_str()))
+            }
+            _ => None,
+        })
+        .filter_map(|(role, text)| {
+            let text = text.trim();
+            (!text.is_empty()).then(|| TranscriptEntry {
+                role,
+                text: text.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Read a rollout file and extract its transcript.
+pub async fn read_session_transcript(path: &Path) -> std::io::Result<Vec<TranscriptEntry>> {
+    let (items, _thread_id, _parse_errors) = RolloutRecorder::load_rollout_items(path).await?;
+    Ok(transcript_from_rollout_items(&items))
+}
+
+/// Render a transcript as a Markdown document headed by `title`.
+pub fn transcript_to_markdown(title: &str, entries: &[TranscriptEntry]) -> String {
+    let mut markdown = format!("# {title}\n");
+    for entry in entries {
+        markdown.push_str(&format!("\n## {}\n\n{}\n", entry.role.label(), entry.text));
+    }
+    markdown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::AgentMessageEvent;
+    use codex_protocol::protocol::UserMessageEvent;
+    use pretty_assertions::assert_eq;
+
+    fn user(message: &str) -> RolloutItem {
+        RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+            message: message.to_string(),
+            images: None,
+            local_images: Vec::new(),
+            text_elements: Vec::new(),
+        }))
+    }
+
+    fn agent(message: &str) -> RolloutItem {
+        RolloutItem::EventMsg(EventMsg::AgentMessage(AgentMessageEvent {
+            message: message.to_string(),
+        }))
+    }
+
+    #[test]
+    fn transcript_keeps_messages_in_order_and_renders_markdown() {
+        let items = vec![
+            user("Fix the flaky test\n"),
+            agent("  "),
+            agent("Done: the test now waits for the server."),
+            user("Thanks"),
+        ];
+
+        let entries = transcript_from_rollout_items(&items);
+        assert_eq!(
+            entries,
+            vec![
+                TranscriptEntry {
+                    role: TranscriptRole::User,
+                    text: "Fix the flaky test".to_string(),
+                },
+                TranscriptEntry {
+                    role: TranscriptRole::Assistant,
+                    text: "Done: the test now waits for the server.".to_string(),
+                },
+                TranscriptEntry {
+                    role: TranscriptRole::User,
+                    text: "Thanks".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            transcript_to_markdown("Session", &entries),
+            "# Session\n\n## User\n\nFix the flaky test\n\n## Codex\n\nDone: the test now waits for the server.\n\n## User\n\nThanks\n"
+        );
+    }
+}
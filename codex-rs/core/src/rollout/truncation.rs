@@ -6,6 +6,7 @@
 use crate::event_mapping;
 use codex_protocol::items::TurnItem;
 use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::ContextEdit;
 use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::RolloutItem;
 
@@ -14,9 +15,9 @@ use codex_protocol::protocol::RolloutItem;
 /// A user message boundary is a `RolloutItem::ResponseItem(ResponseItem::Message { .. })`
 /// whose parsed turn item is `TurnItem::UserMessage`.
 ///
-/// Rollouts can contain `ThreadRolledBack` markers. Those markers indicate that the
-/// last N user turns were removed from the effective thread history; we apply them here so
-/// indexing uses the post-rollback history rather than the raw stream.
+/// Rollouts can contain `ThreadRolledBack` and `ContextEdited` markers. Those markers
+/// indicate that user turns were removed from the effective thread history; we apply them
+/// here so indexing uses the post-rollback history rather than the raw stream.
 pub(crate) fn user_message_positions_in_rollout(items: &[RolloutItem]) -> Vec<usize> {
     let mut user_positions = Vec::new();
     for (idx, item) in items.iter().enumerate() {
@@ -34,6 +35,17 @@ pub(crate) fn user_message_positions_in_rollout(items: &[RolloutItem]) -> Vec<us
                 let new_len = user_positions.len().saturating_sub(num_turns);
                 user_positions.truncate(new_len);
             }
+            RolloutItem::EventMsg(EventMsg::ContextEdited(edited)) => match edited.edit {
+                ContextEdit::Compact => {}
+                ContextEdit::Clear => user_positions.clear(),
+                ContextEdit::DropTurns { first, last } => {
+                    let first = usize::try_from(first).unwrap_or(usize::MAX);
+                    let last = usize::try_from(last).unwrap_or(usize::MAX);
+                    if first >= 1 && first <= last && last <= user_positions.len() {
+                        user_positions.drain(first - 1..last);
+                    }
+                }
+            },
             _ => {}
         }
     }
@@ -0,0 +1,194 @@
+//! Limits on a whole run, set by `codex exec --max-turns`, `--max-tokens`
+//! and `--max-cost`.
+//!
+//! The budget travels in [`Config::run_budget`](crate::config::Config), so
+//! sub-agents spawned from the run share it. Token and cost totals add up
+//! every thread; turns count model responses on the thread the run started,
+//! one per round of tool calls. The turn loop asks the budget before every
+//! sampling request and every tool call, and refuses the request once a
+//! limit is reached. A final answer in the last allowed response therefore
+//! still finishes the run normally, while a tool call in it is refused, as
+//! there is no response left to act on its output.
+
+use std::sync::Mutex;
+use std::sync::PoisonError;
+
+use codex_protocol::protocol::TokenUsage;
+
+use crate::config::types::ModelPrice;
+use crate::error::CodexErr;
+use crate::error::Result;
+
+#[derive(Debug, Default)]
+pub struct RunBudget {
+    max_turns: Option<u32>,
+    max_tokens: Option<i64>,
+    /// Spend limit in USD and the price it is estimated with.
+    max_cost: Option<(f64, ModelPrice)>,
+    state: Mutex<BudgetState>,
+}
+
+#[derive(Debug, Default)]
+struct BudgetState {
+    /// Usage of every thread in the run.
+    usage: TokenUsage,
+    responses: u32,
+    exceeded: Option<String>,
+}
+
+/// Budgets compare by their limits, so configs carrying them stay comparable.
+impl PartialEq for RunBudget {
+    fn eq(&self, other: &Self) -> bool {
+        (self.max_turns, self.max_tokens, self.max_cost)
+            == (other.max_turns, other.max_tokens, other.max_cost)
+    }
+}
+
+impl RunBudget {
+    pub fn new(
+        max_turns: Option<u32>,
+        max_tokens: Option<i64>,
+        max_cost: Option<(f64, ModelPrice)>,
+    ) -> Self {
+        Self {
+            max_turns,
+            max_tokens,
+            max_cost,
+            ..Self::default()
+        }
+    }
+
+    /// Why a request was refused, once one has been.
+    pub fn exceeded(&self) -> Option<String> {
+        self.state().exceeded.clone()
+    }
+
+    /// Called before each sampling request. `primary` is false on
+    /// sub-agents, whose responses do not count as turns.
+    pub(crate) fn start_response(&self, primary: bool) -> Result<()> {
+        let mut state = self.state();
+        self.check(&mut state, primary)?;
+        if primary {
+            state.responses += 1;
+        }
+        Ok(())
+    }
+
+    /// Called before each tool call.
+    pub(crate) fn check_tool_call(&self, primary: bool) -> Result<()> {
+        self.check(&mut self.state(), primary)
+    }
+
+    /// Adds the usage of one model response.
+    pub(crate) fn record_usage(&self, usage: &TokenUsage) {
+        self.state().usage.add_assign(usage);
+    }
+
+    fn check(&self, state: &mut BudgetState, primary: bool) -> Result<()> {
+        let Some(reason) = self.exceeded_reason(state, primary) else {
+            return Ok(());
+        };
+        state.exceeded.get_or_insert_with(|| reason.clone());
+        Err(CodexErr::BudgetExceeded(reason))
+    }
+
+    fn exceeded_reason(&self, state: &BudgetState, primary: bool) -> Option<String> {
+        if primary
+            && let Some(max_turns) = self.max_turns
+            && state.responses >= max_turns
+        {
+            return Some(format!(
+                "the agent used all {max_turns} model responses allowed by --max-turns"
+            ));
+        }
+        let total_tokens = state.usage.total_tokens;
+        if let Some(max_tokens) = self.max_tokens
+            && total_tokens >= max_tokens
+        {
+            return Some(format!(
+                "the run used {total_tokens} tokens, reaching --max-tokens {max_tokens}"
+            ));
+        }
+        if let Some((max_cost, price)) = &self.max_cost {
+            let cost = price.cost_usd(&state.usage);
+            if cost >= *max_cost {
+                return Some(format!(
+                    "the run spent about ${cost:.2}, reaching --max-cost {max_cost:.2}"
+                ));
+            }
+        }
+        None
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, BudgetState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn usage(total_tokens: i64, cached_input_tokens: i64) -> TokenUsage {
+        TokenUsage {
+            input_tokens: total_tokens,
+            cached_input_tokens,
+            output_tokens: 0,
+            reasoning_output_tokens: 0,
+            total_tokens,
+        }
+    }
+
+    fn refusal(result: Result<()>) -> Option<String> {
+        match result {
+            Ok(()) => None,
+            Err(CodexErr::BudgetExceeded(reason)) => Some(reason),
+            Err(other) => panic!("expected CodexErr::BudgetExceeded, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn turn_limit_allows_a_final_answer_but_not_more_work() {
+        let budget = RunBudget::new(Some(2), None, None);
+
+        assert_eq!(refusal(budget.start_response(true)), None);
+        assert_eq!(refusal(budget.check_tool_call(true)), None);
+        assert_eq!(refusal(budget.start_response(true)), None);
+        // Sub-agents do not use up the run's turns.
+        assert_eq!(refusal(budget.start_response(false)), None);
+        assert_eq!(refusal(budget.check_tool_call(false)), None);
+        assert_eq!(budget.exceeded(), None);
+
+        let reason = "the agent used all 2 model responses allowed by --max-turns".to_string();
+        assert_eq!(refusal(budget.check_tool_call(true)), Some(reason.clone()));
+        assert_eq!(refusal(budget.start_response(true)), Some(reason.clone()));
+        assert_eq!(budget.exceeded(), Some(reason));
+    }
+
+    #[test]
+    fn token_and_cost_limits_add_up_every_thread() {
+        let tokens = RunBudget::new(None, Some(100), None);
+        tokens.record_usage(&usage(60, 0));
+        assert_eq!(refusal(tokens.start_response(true)), None);
+        tokens.record_usage(&usage(40, 0));
+        assert_eq!(
+            refusal(tokens.check_tool_call(false)),
+            Some("the run used 100 tokens, reaching --max-tokens 100".to_string())
+        );
+
+        let price = ModelPrice {
+            input: 2.0,
+            cached_input: Some(0.0),
+            output: 8.0,
+        };
+        let cost = RunBudget::new(None, None, Some((1.0, price)));
+        cost.record_usage(&usage(800_000, 400_000));
+        assert_eq!(refusal(cost.start_response(true)), None);
+        cost.record_usage(&usage(100_000, 0));
+        assert_eq!(
+            refusal(cost.start_response(false)),
+            Some("the run spent about $1.00, reaching --max-cost 1.00".to_string())
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::BTreeSet;
+use std::collections::HashMap;
 use std::path::Component;
 use std::path::Path;
 use std::path::PathBuf;
@@ -5,10 +7,14 @@ use std::path::PathBuf;
 use codex_apply_patch::ApplyPatchAction;
 use codex_apply_patch::ApplyPatchFileChange;
 
+use crate::config::types::FileApprovalPolicy;
+use crate::config::types::FileApprovalsConfig;
+use crate::config::types::FileOperation;
 use crate::exec::SandboxType;
 use crate::util::resolve_path;
 
 use crate::protocol::AskForApproval;
+use crate::protocol::FileChange;
 use crate::protocol::SandboxPolicy;
 use codex_protocol::config_types::WindowsSandboxLevel;
 
@@ -85,6 +91,94 @@ pub fn assess_patch_safety(
     }
 }
 
+/// Applies `file_approvals` to the operations in a patch.
+///
+/// Returns `None` when every operation follows the default policy (or some do
+/// and the rest are `auto`), leaving the decision to [`assess_patch_safety`].
+pub(crate) fn assess_patch_file_approvals(
+    action: &ApplyPatchAction,
+    approvals: FileApprovalsConfig,
+    policy: AskForApproval,
+) -> Option<SafetyCheck> {
+    file_approval_check(&patch_file_operations(action), approvals, policy)
+}
+
+fn patch_file_operations(action: &ApplyPatchAction) -> BTreeSet<FileOperation> {
+    action
+        .changes()
+        .values()
+        .flat_map(|change| match change {
+            ApplyPatchFileChange::Add { .. } => vec![FileOperation::Write],
+            ApplyPatchFileChange::Delete { .. } => vec![FileOperation::Delete],
+            ApplyPatchFileChange::Update {
+                move_path: None, ..
+            } => vec![FileOperation::Write],
+            // A move writes the destination and deletes the source.
+            ApplyPatchFileChange::Update {
+                move_path: Some(_), ..
+            } => vec![FileOperation::Write, FileOperation::Delete],
+        })
+        .collect()
+}
+
+/// Classes of file operation in the changes of a patch approval request,
+/// reported to clients alongside the changes themselves.
+pub(crate) fn file_change_operations(changes: &HashMap<PathBuf, FileChange>) -> Vec<FileOperation> {
+    changes
+        .values()
+        .flat_map(|change| match change {
+            FileChange::Add { .. } => vec![FileOperation::Write],
+            FileChange::Delete { .. } => vec![FileOperation::Delete],
+            FileChange::Update {
+                move_path: None, ..
+            } => vec![FileOperation::Write],
+            FileChange::Update {
+                move_path: Some(_), ..
+            } => vec![FileOperation::Write, FileOperation::Delete],
+        })
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+fn file_approval_check(
+    operations: &BTreeSet<FileOperation>,
+    approvals: FileApprovalsConfig,
+    policy: AskForApproval,
+) -> Option<SafetyCheck> {
+    let policy_for = |wanted: FileApprovalPolicy| {
+        operations
+            .iter()
+            .copied()
+            .find(|operation| approvals.policy(*operation) == wanted)
+    };
+    if let Some(operation) = policy_for(FileApprovalPolicy::Deny) {
+        return Some(SafetyCheck::Reject {
+            reason: format!("{operation} operations are denied by file_approvals"),
+        });
+    }
+    if let Some(operation) = policy_for(FileApprovalPolicy::Ask) {
+        return Some(if policy == AskForApproval::Never {
+            SafetyCheck::Reject {
+                reason: format!(
+                    "{operation} operations require approval, but approval_policy is never"
+                ),
+            }
+        } else {
+            SafetyCheck::AskUser
+        });
+    }
+    let all_auto = !operations.is_empty()
+        && operations
+            .iter()
+            .all(|operation| approvals.policy(*operation) == FileApprovalPolicy::Auto);
+    // Only the prompt is skipped: the patch still runs in the turn's sandbox.
+    all_auto.then_some(SafetyCheck::AutoApprove {
+        sandbox_type: SandboxType::None,
+        user_explicitly_approved: true,
+    })
+}
+
 pub fn get_platform_sandbox(windows_sandbox_enabled: bool) -> Option<SandboxType> {
     if cfg!(target_os = "macos") {
         Some(SandboxType::MacosSeatbelt)
@@ -175,6 +269,7 @@ fn is_write_patch_constrained_to_writable_paths(
 mod tests {
     use super::*;
     use codex_utils_absolute_path::AbsolutePathBuf;
+    use pretty_assertions::assert_eq;
     use tempfile::TempDir;
 
     #[test]
@@ -251,4 +346,82 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn file_approvals_deny_beats_ask_beats_auto() {
+        let approvals = FileApprovalsConfig {
+            read: FileApprovalPolicy::Default,
+            write: FileApprovalPolicy::Auto,
+            delete: FileApprovalPolicy::Ask,
+        };
+        let writes = BTreeSet::from([FileOperation::Write]);
+        let writes_and_deletes = BTreeSet::from([FileOperation::Write, FileOperation::Delete]);
+
+        assert_eq!(
+            file_approval_check(&writes, approvals, AskForApproval::OnRequest),
+            Some(SafetyCheck::AutoApprove {
+                sandbox_type: SandboxType::None,
+                user_explicitly_approved: true,
+            })
+        );
+        assert_eq!(
+            file_approval_check(&writes_and_deletes, approvals, AskForApproval::OnRequest),
+            Some(SafetyCheck::AskUser)
+        );
+        assert_eq!(
+            file_approval_check(&writes_and_deletes, approvals, AskForApproval::Never),
+            Some(SafetyCheck::Reject {
+                reason: "delete operations require approval, but approval_policy is never"
+                    .to_string(),
+            })
+        );
+
+        let deny_deletes = FileApprovalsConfig {
+            delete: FileApprovalPolicy::Deny,
+            ..approvals
+        };
+        assert_eq!(
+            file_approval_check(&writes_and_deletes, deny_deletes, AskForApproval::OnRequest),
+            Some(SafetyCheck::Reject {
+                reason: "delete operations are denied by file_approvals".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn file_change_operations_report_moves_as_write_and_delete() {
+        let changes = HashMap::from([
+            (
+                PathBuf::from("new.txt"),
+                FileChange::Add {
+                    content: String::new(),
+                },
+            ),
+            (
+                PathBuf::from("old.txt"),
+                FileChange::Update {
+                    unified_diff: String::new(),
+                    move_path: Some(PathBuf::from("moved.txt")),
+                },
+            ),
+        ]);
+        assert_eq!(
+            file_change_operations(&changes),
+            vec![FileOperation::Write, FileOperation::Delete]
+        );
+        assert_eq!(file_change_operations(&HashMap::new()), Vec::new());
+    }
+
+    #[test]
+    fn default_file_approvals_defer_to_patch_safety() {
+        let operations = BTreeSet::from([FileOperation::Write, FileOperation::Delete]);
+        assert_eq!(
+            file_approval_check(
+                &operations,
+                FileApprovalsConfig::default(),
+                AskForApproval::OnRequest
+            ),
+            None
+        );
+    }
 }
@@ -0,0 +1,237 @@
+//! Diagnostics for `codex sandbox check`: which native sandbox this machine
+//! can use for agent commands and what it enforces.
+//!
+//! This only reports on the sandboxes that already exist; it does not change
+//! how any of them run commands.
+
+use crate::config::Config;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Ok,
+    /// Usable, but weaker than on other platforms or not fully set up.
+    Warning,
+    Unavailable,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SandboxCheck {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl SandboxCheck {
+    fn new(name: &'static str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Probes the sandbox for the current platform.
+pub fn check_sandbox(config: &Config) -> Vec<SandboxCheck> {
+    platform_checks(config)
+}
+
+#[cfg(target_os = "macos")]
+fn platform_checks(_config: &Config) -> Vec<SandboxCheck> {
+    use crate::seatbelt::MACOS_PATH_TO_SEATBELT_EXECUTABLE;
+
+    let seatbelt = if std::path::Path::new(MACOS_PATH_TO_SEATBELT_EXECUTABLE).exists() {
+        SandboxCheck::new(
+            "seatbelt",
+            CheckStatus::Ok,
+            format!("{MACOS_PATH_TO_SEATBELT_EXECUTABLE} enforces file writes and network access"),
+        )
+    } else {
+        SandboxCheck::new(
+            "seatbelt",
+            CheckStatus::Unavailable,
+            format!("{MACOS_PATH_TO_SEATBELT_EXECUTABLE} not found; commands run unsandboxed"),
+        )
+    };
+    vec![seatbelt]
+}
+
+#[cfg(target_os = "linux")]
+fn platform_checks(config: &Config) -> Vec<SandboxCheck> {
+    let seccomp = if seccomp_available() {
+        SandboxCheck::new("seccomp", CheckStatus::Ok, "network syscalls are filtered")
+    } else {
+        SandboxCheck::new(
+            "seccomp",
+            CheckStatus::Unavailable,
+            "not enabled in this kernel; network access is not blocked",
+        )
+    };
+    vec![
+        helper_check(config.codex_linux_sandbox_exe.as_deref()),
+        landlock_check(landlock_abi_version()),
+        seccomp,
+    ]
+}
+
+#[cfg(target_os = "linux")]
+fn helper_check(exe: Option<&std::path::Path>) -> SandboxCheck {
+    match exe {
+        Some(exe) if exe.exists() => SandboxCheck::new(
+            "codex-linux-sandbox",
+            CheckStatus::Ok,
+            exe.display().to_string(),
+        ),
+        Some(exe) => SandboxCheck::new(
+            "codex-linux-sandbox",
+            CheckStatus::Unavailable,
+            format!("{} not found", exe.display()),
+        ),
+        None => SandboxCheck::new(
+            "codex-linux-sandbox",
+            CheckStatus::Unavailable,
+            "helper not configured; commands run unsandboxed",
+        ),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn landlock_check(abi: Option<i64>) -> SandboxCheck {
+    match abi {
+        Some(abi) if abi >= 4 => SandboxCheck::new(
+            "landlock",
+            CheckStatus::Ok,
+            format!("ABI v{abi}: file writes and TCP ports are restricted"),
+        ),
+        Some(abi) => SandboxCheck::new(
+            "landlock",
+            CheckStatus::Warning,
+            format!(
+                "ABI v{abi}: file writes are restricted; `[network_proxy]` is not limited to the proxy port without v4 (Linux 6.7)"
+            ),
+        ),
+        None => SandboxCheck::new(
+            "landlock",
+            CheckStatus::Unavailable,
+            "not enabled in this kernel; file writes are not restricted",
+        ),
+    }
+}
+
+/// The highest Landlock ABI the kernel supports, if Landlock is enabled.
+#[cfg(target_os = "linux")]
+fn landlock_abi_version() -> Option<i64> {
+    const LANDLOCK_CREATE_RULESET_VERSION: libc::c_uint = 1;
+    let abi = unsafe {
+        libc::syscall(
+            libc::SYS_landlock_create_ruleset,
+            std::ptr::null::<libc::c_void>(),
+            0usize,
+            LANDLOCK_CREATE_RULESET_VERSION,
+        )
+    };
+    (abi > 0).then_some(abi)
+}
+
+#[cfg(target_os = "linux")]
+fn seccomp_available() -> bool {
+    std::fs::read_to_string("/proc/self/status")
+        .is_ok_and(|status| status.lines().any(|line| line.starts_with("Seccomp:")))
+}
+
+#[cfg(target_os = "windows")]
+fn platform_checks(config: &Config) -> Vec<SandboxCheck> {
+    use crate::windows_sandbox::WindowsSandboxLevelExt;
+    use codex_protocol::config_types::WindowsSandboxLevel;
+
+    let token = match codex_windows_sandbox::probe_restricted_token() {
+        Ok(()) => SandboxCheck::new(
+            "restricted token",
+            CheckStatus::Ok,
+            "commands can run with a write-restricted token",
+        ),
+        Err(err) => SandboxCheck::new(
+            "restricted token",
+            CheckStatus::Unavailable,
+            format!("could not create a restricted token: {err}"),
+        ),
+    };
+
+    let level = match WindowsSandboxLevel::from_config(config) {
+        WindowsSandboxLevel::Disabled => SandboxCheck::new(
+            "windows sandbox",
+            CheckStatus::Unavailable,
+            "turned off with `experimental_windows_sandbox = false`; commands run unsandboxed",
+        ),
+        WindowsSandboxLevel::RestrictedToken => SandboxCheck::new(
+            "windows sandbox",
+            CheckStatus::Warning,
+            "restricted token: file writes are enforced, but network access is only discouraged through proxy variables; use `elevated_windows_sandbox` for a firewall block",
+        ),
+        WindowsSandboxLevel::Elevated => {
+            if crate::windows_sandbox::sandbox_setup_is_complete(&config.codex_home) {
+                SandboxCheck::new(
+                    "windows sandbox",
+                    CheckStatus::Ok,
+                    "elevated: sandbox users, file writes, and a firewall network block",
+                )
+            } else {
+                SandboxCheck::new(
+                    "windows sandbox",
+                    CheckStatus::Warning,
+                    "elevated, but setup has not completed; it runs on the next sandboxed command",
+                )
+            }
+        }
+    };
+    vec![token, level]
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+fn platform_checks(_config: &Config) -> Vec<SandboxCheck> {
+    vec![SandboxCheck::new(
+        "sandbox",
+        CheckStatus::Unavailable,
+        "no sandbox for this platform; commands run unsandboxed",
+    )]
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::path::Path;
+
+    #[test]
+    fn linux_helper_must_exist() {
+        assert_eq!(helper_check(None).status, CheckStatus::Unavailable);
+        assert_eq!(
+            helper_check(Some(Path::new("/nonexistent/codex-linux-sandbox"))),
+            SandboxCheck::new(
+                "codex-linux-sandbox",
+                CheckStatus::Unavailable,
+                "/nonexistent/codex-linux-sandbox not found",
+            )
+        );
+        let exe = std::env::current_exe().expect("current exe");
+        assert_eq!(helper_check(Some(&exe)).status, CheckStatus::Ok);
+    }
+
+    #[test]
+    fn landlock_status_follows_the_abi() {
+        let statuses: Vec<CheckStatus> = [None, Some(1), Some(3), Some(4), Some(6)]
+            .into_iter()
+            .map(|abi| landlock_check(abi).status)
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                CheckStatus::Unavailable,
+                CheckStatus::Warning,
+                CheckStatus::Warning,
+                CheckStatus::Ok,
+                CheckStatus::Ok,
+            ]
+        );
+    }
+}
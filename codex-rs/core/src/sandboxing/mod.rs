@@ -6,7 +6,9 @@ sandbox placement and transformation of portable CommandSpec into a
 ready‑to‑spawn environment.
 */
 
+use crate::config::types::LinuxSandboxConfig;
 use crate::exec::ExecExpiration;
+use crate::exec::ExecLimits;
 use crate::exec::ExecToolCallOutput;
 use crate::exec::SandboxType;
 use crate::exec::StdoutStream;
@@ -24,9 +26,16 @@ use crate::tools::sandboxing::SandboxablePreference;
 use codex_protocol::config_types::WindowsSandboxLevel;
 pub use codex_protocol::models::SandboxPermissions;
 use std::collections::HashMap;
+use std::net::SocketAddr;
 use std::path::Path;
 use std::path::PathBuf;
 
+mod network_proxy;
+
+pub(crate) use network_proxy::NetworkProxyManager;
+use network_proxy::proxy_env;
+pub(crate) use network_proxy::sandbox_proxy_addr;
+
 #[derive(Debug)]
 pub struct CommandSpec {
     pub program: String,
@@ -36,6 +45,7 @@ pub struct CommandSpec {
     pub expiration: ExecExpiration,
     pub sandbox_permissions: SandboxPermissions,
     pub justification: Option<String>,
+    pub limits: ExecLimits,
 }
 
 #[derive(Debug)]
@@ -49,6 +59,18 @@ pub struct ExecEnv {
     pub sandbox_permissions: SandboxPermissions,
     pub justification: Option<String>,
     pub arg0: Option<String>,
+    pub limits: ExecLimits,
+}
+
+/// Settings that only some sandbox types use when wrapping a command.
+#[derive(Clone, Copy)]
+pub(crate) struct SandboxTransformOptions<'a> {
+    pub codex_linux_sandbox_exe: Option<&'a PathBuf>,
+    pub linux_sandbox: &'a LinuxSandboxConfig,
+    /// Existing `write_protected` paths the sandbox keeps read-only.
+    pub read_only_paths: &'a [PathBuf],
+    /// Loopback proxy a command without network access may still reach.
+    pub network_proxy: Option<SocketAddr>,
 }
 
 pub enum SandboxPreference {
@@ -108,9 +130,15 @@ impl SandboxManager {
         policy: &SandboxPolicy,
         sandbox: SandboxType,
         sandbox_policy_cwd: &Path,
-        codex_linux_sandbox_exe: Option<&PathBuf>,
         windows_sandbox_level: WindowsSandboxLevel,
+        options: SandboxTransformOptions<'_>,
     ) -> Result<ExecEnv, SandboxTransformError> {
+        let SandboxTransformOptions {
+            codex_linux_sandbox_exe,
+            linux_sandbox,
+            read_only_paths,
+            network_proxy,
+        } = options;
         let mut env = spec.env;
         if !policy.has_full_network_access() {
             env.insert(
@@ -118,6 +146,18 @@ impl SandboxManager {
                 "1".to_string(),
             );
         }
+        // Only the seatbelt and Linux sandboxes can limit a command to the
+        // proxy; elsewhere the network stays fully on or off.
+        let network_proxy = network_proxy.filter(|_| {
+            !policy.has_full_network_access()
+                && matches!(
+                    sandbox,
+                    SandboxType::MacosSeatbelt | SandboxType::LinuxSeccomp
+                )
+        });
+        if let Some(proxy) = network_proxy {
+            env.extend(proxy_env(proxy));
+        }
 
         let mut command = Vec::with_capacity(1 + spec.args.len());
         command.push(spec.program);
@@ -129,8 +169,13 @@ impl SandboxManager {
             SandboxType::MacosSeatbelt => {
                 let mut seatbelt_env = HashMap::new();
                 seatbelt_env.insert(CODEX_SANDBOX_ENV_VAR.to_string(), "seatbelt".to_string());
-                let mut args =
-                    create_seatbelt_command_args(command.clone(), policy, sandbox_policy_cwd);
+                let mut args = create_seatbelt_command_args(
+                    command.clone(),
+                    policy,
+                    sandbox_policy_cwd,
+                    read_only_paths,
+                    network_proxy,
+                );
                 let mut full_command = Vec::with_capacity(1 + args.len());
                 full_command.push(MACOS_PATH_TO_SEATBELT_EXECUTABLE.to_string());
                 full_command.append(&mut args);
@@ -141,8 +186,14 @@ impl SandboxManager {
             SandboxType::LinuxSeccomp => {
                 let exe = codex_linux_sandbox_exe
                     .ok_or(SandboxTransformError::MissingLinuxSandboxExecutable)?;
-                let mut args =
-                    create_linux_sandbox_command_args(command.clone(), policy, sandbox_policy_cwd);
+                let mut args = create_linux_sandbox_command_args(
+                    command.clone(),
+                    policy,
+                    sandbox_policy_cwd,
+                    read_only_paths,
+                    network_proxy,
+                    linux_sandbox,
+                );
                 let mut full_command = Vec::with_capacity(1 + args.len());
                 full_command.push(exe.to_string_lossy().to_string());
                 full_command.append(&mut args);
@@ -174,6 +225,7 @@ impl SandboxManager {
             sandbox_permissions: spec.sandbox_permissions,
             justification: spec.justification,
             arg0: arg0_override,
+            limits: spec.limits,
         })
     }
 
@@ -0,0 +1,165 @@
+//! Limited network access through the local network proxy.
+//!
+//! With `[network_proxy]` enabled, sandboxed commands whose policy has no
+//! network access may still connect to the proxy (`codex-network-proxy`),
+//! which applies the domain and CIDR allowlist. The platform sandbox opens the
+//! proxy's loopback address and nothing else, and the usual proxy variables
+//! point HTTP clients at it. The proxy is started on first use unless one is
+//! already listening.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::net::Ipv4Addr;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Duration;
+
+use tokio::net::TcpStream;
+use tokio::process::Child;
+use tokio::process::Command;
+use tokio::sync::Mutex;
+use tracing::warn;
+use url::Host;
+use url::Url;
+
+use crate::config::types::NetworkProxyToml;
+
+const DEFAULT_PROXY_URL: &str = "http://127.0.0.1:3128";
+
+const PROXY_PROGRAM: &str = "codex-network-proxy";
+
+/// How long a freshly started proxy gets to start listening.
+const PROXY_STARTUP_TIMEOUT: Duration = Duration::from_secs(5);
+const PROXY_STARTUP_POLL: Duration = Duration::from_millis(100);
+
+const PROXY_ENV_VARS: [&str; 6] = [
+    "HTTP_PROXY",
+    "HTTPS_PROXY",
+    "ALL_PROXY",
+    "http_proxy",
+    "https_proxy",
+    "all_proxy",
+];
+
+/// The address sandboxed commands may connect to when `[network_proxy]` is
+/// enabled. The proxy has to listen on loopback: the sandboxes only open
+/// loopback addresses.
+pub(crate) fn sandbox_proxy_addr(toml: &NetworkProxyToml) -> Result<Option<SocketAddr>, String> {
+    if !toml.enabled.unwrap_or(false) {
+        return Ok(None);
+    }
+    let proxy_url = toml.proxy_url.as_deref().unwrap_or(DEFAULT_PROXY_URL);
+    let url = Url::parse(proxy_url)
+        .map_err(|err| format!("invalid network_proxy.proxy_url {proxy_url:?}: {err}"))?;
+    let ip = match url.host() {
+        Some(Host::Ipv4(ip)) => IpAddr::V4(ip),
+        Some(Host::Ipv6(ip)) => IpAddr::V6(ip),
+        Some(Host::Domain("localhost")) => IpAddr::V4(Ipv4Addr::LOCALHOST),
+        Some(Host::Domain(_)) | None => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+    };
+    if !ip.is_loopback() {
+        return Err(format!(
+            "network_proxy.proxy_url {proxy_url:?} must be a loopback address such as 127.0.0.1"
+        ));
+    }
+    let port = url
+        .port_or_known_default()
+        .ok_or_else(|| format!("network_proxy.proxy_url {proxy_url:?} has no port"))?;
+    Ok(Some(SocketAddr::new(ip, port)))
+}
+
+/// Variables that send a command's HTTP traffic through `proxy`.
+pub(crate) fn proxy_env(proxy: SocketAddr) -> HashMap<String, String> {
+    let url = format!("http://{proxy}");
+    PROXY_ENV_VARS
+        .iter()
+        .map(|name| ((*name).to_string(), url.clone()))
+        .collect()
+}
+
+/// Session-owned `codex-network-proxy` process, started the first time a
+/// sandboxed command needs the proxy and killed with the session.
+#[derive(Default)]
+pub(crate) struct NetworkProxyManager {
+    child: Mutex<Option<Child>>,
+}
+
+impl NetworkProxyManager {
+    /// Returns `proxy` once something is listening there, starting the proxy
+    /// if needed. Returns `None`, leaving the network blocked, when it could
+    /// not be started.
+    pub(crate) async fn ensure_running(&self, proxy: SocketAddr) -> Option<SocketAddr> {
+        let mut child = self.child.lock().await;
+        if TcpStream::connect(proxy).await.is_ok() {
+            return Some(proxy);
+        }
+        // Replacing a previous process that stopped listening kills it.
+        let spawned = Command::new(proxy_program())
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn();
+        let started = child.insert(match spawned {
+            Ok(started) => started,
+            Err(err) => {
+                warn!("failed to start {PROXY_PROGRAM}: {err}");
+                return None;
+            }
+        });
+        let deadline = tokio::time::Instant::now() + PROXY_STARTUP_TIMEOUT;
+        while tokio::time::Instant::now() < deadline {
+            tokio::time::sleep(PROXY_STARTUP_POLL).await;
+            if TcpStream::connect(proxy).await.is_ok() {
+                return Some(proxy);
+            }
+            if let Ok(Some(status)) = started.try_wait() {
+                warn!("{PROXY_PROGRAM} exited with {status} before listening on {proxy}");
+                return None;
+            }
+        }
+        warn!("{PROXY_PROGRAM} did not start listening on {proxy}");
+        None
+    }
+}
+
+/// Prefers the proxy installed next to the running binary over `PATH`.
+fn proxy_program() -> PathBuf {
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| {
+            exe.parent().map(|dir| {
+                dir.join(PROXY_PROGRAM)
+                    .with_extension(std::env::consts::EXE_EXTENSION)
+            })
+        })
+        .filter(|program| program.is_file())
+        .unwrap_or_else(|| PathBuf::from(PROXY_PROGRAM))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn proxy_must_be_enabled_and_on_loopback() {
+        assert_eq!(sandbox_proxy_addr(&NetworkProxyToml::default()), Ok(None));
+
+        let enabled = |proxy_url: Option<&str>| NetworkProxyToml {
+            enabled: Some(true),
+            proxy_url: proxy_url.map(str::to_string),
+        };
+        assert_eq!(
+            sandbox_proxy_addr(&enabled(None)),
+            Ok(Some(SocketAddr::from(([127, 0, 0, 1], 3128))))
+        );
+        assert_eq!(
+            sandbox_proxy_addr(&enabled(Some("http://localhost:8888"))),
+            Ok(Some(SocketAddr::from(([127, 0, 0, 1], 8888))))
+        );
+        assert!(sandbox_proxy_addr(&enabled(Some("http://10.0.0.5:3128"))).is_err());
+        assert!(sandbox_proxy_addr(&enabled(Some("http://proxy.example:3128"))).is_err());
+    }
+}
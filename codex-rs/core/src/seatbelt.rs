@@ -2,17 +2,22 @@
 
 use std::collections::HashMap;
 use std::ffi::CStr;
+use std::net::SocketAddr;
 use std::path::Path;
 use std::path::PathBuf;
 use tokio::process::Child;
 
+use crate::exec_limits::ExecLimits;
 use crate::protocol::SandboxPolicy;
 use crate::spawn::CODEX_SANDBOX_ENV_VAR;
+use crate::spawn::SpawnChildRequest;
 use crate::spawn::StdioPolicy;
 use crate::spawn::spawn_child_async;
 
 const MACOS_SEATBELT_BASE_POLICY: &str = include_str!("seatbelt_base_policy.sbpl");
 const MACOS_SEATBELT_NETWORK_POLICY: &str = include_str!("seatbelt_network_policy.sbpl");
+const MACOS_SEATBELT_PROXY_NETWORK_POLICY: &str =
+    include_str!("seatbelt_proxy_network_policy.sbpl");
 
 /// When working with `sandbox-exec`, only consider `sandbox-exec` in `/usr/bin`
 /// to defend against an attacker trying to inject a malicious version on the
@@ -28,25 +33,31 @@ pub async fn spawn_command_under_seatbelt(
     stdio_policy: StdioPolicy,
     mut env: HashMap<String, String>,
 ) -> std::io::Result<Child> {
-    let args = create_seatbelt_command_args(command, sandbox_policy, sandbox_policy_cwd);
+    let args = create_seatbelt_command_args(command, sandbox_policy, sandbox_policy_cwd, &[], None);
     let arg0 = None;
     env.insert(CODEX_SANDBOX_ENV_VAR.to_string(), "seatbelt".to_string());
-    spawn_child_async(
-        PathBuf::from(MACOS_PATH_TO_SEATBELT_EXECUTABLE),
+    spawn_child_async(SpawnChildRequest {
+        program: PathBuf::from(MACOS_PATH_TO_SEATBELT_EXECUTABLE),
         args,
         arg0,
-        command_cwd,
+        cwd: command_cwd,
         sandbox_policy,
         stdio_policy,
         env,
-    )
+        limits: ExecLimits::default(),
+    })
     .await
 }
 
+/// `read_only_paths` stay unwritable even inside a writable root; see
+/// `write_protected`. Without network access, `network_proxy` is the one
+/// loopback address the command may still connect to.
 pub(crate) fn create_seatbelt_command_args(
     command: Vec<String>,
     sandbox_policy: &SandboxPolicy,
     sandbox_policy_cwd: &Path,
+    read_only_paths: &[PathBuf],
+    network_proxy: Option<SocketAddr>,
 ) -> Vec<String> {
     let (file_write_policy, file_write_dir_params) = {
         if sandbox_policy.has_full_disk_write_access() {
@@ -105,6 +116,20 @@ pub(crate) fn create_seatbelt_command_args(
         }
     };
 
+    // Later rules win in SBPL, so these denials override the writable roots.
+    let (read_only_policy, read_only_params): (String, Vec<(String, PathBuf)>) = read_only_paths
+        .iter()
+        .enumerate()
+        .map(|(index, path)| {
+            let canonical = path.canonicalize().unwrap_or_else(|_| path.clone());
+            let param = format!("READ_ONLY_PATH_{index}");
+            (
+                format!("(deny file-write* (subpath (param \"{param}\")))\n"),
+                (param, canonical),
+            )
+        })
+        .unzip();
+
     let file_read_policy = if sandbox_policy.has_full_disk_read_access() {
         "; allow read-only file operations\n(allow file-read*)"
     } else {
@@ -113,16 +138,22 @@ pub(crate) fn create_seatbelt_command_args(
 
     // TODO(mbolin): apply_patch calls must also honor the SandboxPolicy.
     let network_policy = if sandbox_policy.has_full_network_access() {
-        MACOS_SEATBELT_NETWORK_POLICY
+        MACOS_SEATBELT_NETWORK_POLICY.to_string()
+    } else if let Some(proxy) = network_proxy {
+        // Seatbelt only accepts `*` or `localhost` as the host of a remote ip.
+        format!(
+            "{MACOS_SEATBELT_PROXY_NETWORK_POLICY}\n(allow network-outbound (remote ip \"localhost:{port}\"))\n",
+            port = proxy.port()
+        )
     } else {
-        ""
+        String::new()
     };
 
     let full_policy = format!(
-        "{MACOS_SEATBELT_BASE_POLICY}\n{file_read_policy}\n{file_write_policy}\n{network_policy}"
+        "{MACOS_SEATBELT_BASE_POLICY}\n{file_read_policy}\n{file_write_policy}\n{read_only_policy}{network_policy}"
     );
 
-    let dir_params = [file_write_dir_params, macos_dir_params()].concat();
+    let dir_params = [file_write_dir_params, read_only_params, macos_dir_params()].concat();
 
     let mut seatbelt_args: Vec<String> = vec!["-p".to_string(), full_policy];
     let definition_args = dir_params
@@ -227,7 +258,7 @@ mod tests {
         .iter()
         .map(std::string::ToString::to_string)
         .collect();
-        let args = create_seatbelt_command_args(shell_command.clone(), &policy, &cwd);
+        let args = create_seatbelt_command_args(shell_command.clone(), &policy, &cwd, &[], None);
 
         // Build the expected policy text using a raw string for readability.
         // Note that the policy includes:
@@ -315,7 +346,8 @@ mod tests {
         .iter()
         .map(std::string::ToString::to_string)
         .collect();
-        let write_hooks_file_args = create_seatbelt_command_args(shell_command_git, &policy, &cwd);
+        let write_hooks_file_args =
+            create_seatbelt_command_args(shell_command_git, &policy, &cwd, &[], None);
         let output = Command::new(MACOS_PATH_TO_SEATBELT_EXECUTABLE)
             .args(&write_hooks_file_args)
             .current_dir(&cwd)
@@ -346,7 +378,7 @@ mod tests {
         .map(std::string::ToString::to_string)
         .collect();
         let write_allowed_file_args =
-            create_seatbelt_command_args(shell_command_allowed, &policy, &cwd);
+            create_seatbelt_command_args(shell_command_allowed, &policy, &cwd, &[], None);
         let output = Command::new(MACOS_PATH_TO_SEATBELT_EXECUTABLE)
             .args(&write_allowed_file_args)
             .current_dir(&cwd)
@@ -371,6 +403,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn create_seatbelt_args_allow_only_the_network_proxy_port() {
+        let tmp = TempDir::new().expect("tempdir");
+        let policy = SandboxPolicy::new_read_only_policy();
+        let proxy = std::net::SocketAddr::from(([127, 0, 0, 1], 3128));
+
+        let args = create_seatbelt_command_args(
+            vec!["true".to_string()],
+            &policy,
+            tmp.path(),
+            &[],
+            Some(proxy),
+        );
+
+        let full_policy = &args[1];
+        assert!(full_policy.contains(r#"(allow network-outbound (remote ip "localhost:3128"))"#));
+        assert!(!full_policy.contains("(allow network-inbound)"));
+    }
+
     #[test]
     fn create_seatbelt_args_with_read_only_git_pointer_file() {
         let tmp = TempDir::new().expect("tempdir");
@@ -406,7 +457,7 @@ mod tests {
         .iter()
         .map(std::string::ToString::to_string)
         .collect();
-        let args = create_seatbelt_command_args(shell_command, &policy, &cwd);
+        let args = create_seatbelt_command_args(shell_command, &policy, &cwd, &[], None);
 
         let output = Command::new(MACOS_PATH_TO_SEATBELT_EXECUTABLE)
             .args(&args)
@@ -436,7 +487,8 @@ mod tests {
         .iter()
         .map(std::string::ToString::to_string)
         .collect();
-        let gitdir_args = create_seatbelt_command_args(shell_command_gitdir, &policy, &cwd);
+        let gitdir_args =
+            create_seatbelt_command_args(shell_command_gitdir, &policy, &cwd, &[], None);
         let output = Command::new(MACOS_PATH_TO_SEATBELT_EXECUTABLE)
             .args(&gitdir_args)
             .current_dir(&cwd)
@@ -492,8 +544,13 @@ mod tests {
         .iter()
         .map(std::string::ToString::to_string)
         .collect();
-        let args =
-            create_seatbelt_command_args(shell_command.clone(), &policy, vulnerable_root.as_path());
+        let args = create_seatbelt_command_args(
+            shell_command.clone(),
+            &policy,
+            vulnerable_root.as_path(),
+            &[],
+            None,
+        );
 
         let tmpdir_env_var = std::env::var("TMPDIR")
             .ok()
@@ -8,12 +8,23 @@ use codex_protocol::models::ContentItem;
 /// boundaries.
 pub(crate) const ENVIRONMENT_CONTEXT_OPEN_TAG: &str = "<environment_context>";
 pub(crate) const TURN_ABORTED_OPEN_TAG: &str = "<turn_aborted>";
+pub(crate) const PINNED_CONTEXT_OPEN_TAG: &str = "<pinned_context>";
+pub(crate) const REPO_MAP_OPEN_TAG: &str = "<repo_map>";
+pub(crate) const EXTERNAL_EDITS_OPEN_TAG: &str = "<external_edits>";
+pub(crate) const UNDO_OPEN_TAG: &str = "<undo>";
+pub(crate) const TERMINAL_TAKEOVER_OPEN_TAG: &str = "<terminal_takeover>";
 
 /// Returns true if `text` starts with a session prefix marker (case-insensitive).
 pub(crate) fn is_session_prefix(text: &str) -> bool {
     let trimmed = text.trim_start();
     let lowered = trimmed.to_ascii_lowercase();
-    lowered.starts_with(ENVIRONMENT_CONTEXT_OPEN_TAG) || lowered.starts_with(TURN_ABORTED_OPEN_TAG)
+    lowered.starts_with(ENVIRONMENT_CONTEXT_OPEN_TAG)
+        || lowered.starts_with(TURN_ABORTED_OPEN_TAG)
+        || lowered.starts_with(PINNED_CONTEXT_OPEN_TAG)
+        || lowered.starts_with(REPO_MAP_OPEN_TAG)
+        || lowered.starts_with(EXTERNAL_EDITS_OPEN_TAG)
+        || lowered.starts_with(UNDO_OPEN_TAG)
+        || lowered.starts_with(TERMINAL_TAKEOVER_OPEN_TAG)
 }
 
 /// Returns true if `text` starts with a session prefix marker (case-insensitive).
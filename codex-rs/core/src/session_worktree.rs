@@ -0,0 +1,157 @@
+//! Sessions that run in their own git worktree.
+//!
+//! With `session_worktree = true`, or `codex --worktree`, a new interactive
+//! session works in a dedicated worktree on a fresh `codex/session-<timestamp>`
+//! branch created from the current `HEAD`, so the user's checkout is untouched
+//! while the agent edits. Merging commits the session's changes and merges the
+//! branch into whatever the checkout has checked out; discarding drops them.
+//! Either way the worktree and its branch are removed afterwards.
+
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::git_info::get_git_repo_root;
+use crate::worktree;
+pub use crate::worktree::MergeOutcome;
+use crate::worktree::WORKTREES_SUBDIR;
+
+const COMMIT_MESSAGE: &str = "Codex session changes";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionWorktree {
+    repo_root: PathBuf,
+    original_cwd: PathBuf,
+    path: PathBuf,
+    branch: String,
+}
+
+impl SessionWorktree {
+    /// Create a worktree for a session launched from `cwd`. Returns `None`
+    /// when `cwd` is not inside a git repository.
+    pub async fn create(codex_home: &Path, cwd: &Path) -> io::Result<Option<Self>> {
+        let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+        Self::create_named(codex_home, cwd, &format!("session-{stamp}")).await
+    }
+
+    /// Like [`Self::create`], with the worktree directory and the
+    /// `codex/<name>` branch named `name`.
+    pub async fn create_named(
+        codex_home: &Path,
+        cwd: &Path,
+        name: &str,
+    ) -> io::Result<Option<Self>> {
+        let Some(repo_root) = get_git_repo_root(cwd) else {
+            return Ok(None);
+        };
+        let branch = format!("codex/{name}");
+        let path = codex_home.join(WORKTREES_SUBDIR).join(name);
+        worktree::add_worktree(&repo_root, &path, &branch).await?;
+        Ok(Some(Self {
+            repo_root,
+            original_cwd: cwd.to_path_buf(),
+            path,
+            branch,
+        }))
+    }
+
+    /// Working directory for the session inside the worktree.
+    pub fn cwd(&self) -> PathBuf {
+        worktree::worktree_cwd(&self.repo_root, &self.original_cwd, &self.path)
+    }
+
+    /// Directory the session was launched from, in the user's checkout.
+    pub fn original_cwd(&self) -> &Path {
+        &self.original_cwd
+    }
+
+    pub fn branch(&self) -> &str {
+        &self.branch
+    }
+
+    /// Commit everything in the worktree and merge the session branch into
+    /// the checkout. On failure, such as a conflict or uncommitted changes in
+    /// the checkout that the merge would overwrite, the checkout is left as it
+    /// was and the worktree is kept so the merge can be retried.
+    pub async fn merge(&self) -> io::Result<MergeOutcome> {
+        worktree::commit_all(&self.path, COMMIT_MESSAGE).await?;
+        let outcome = worktree::merge_into_checkout(&self.repo_root, &self.branch).await?;
+        self.discard().await?;
+        Ok(outcome)
+    }
+
+    /// Worktree directory.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Commit everything in the worktree to the session branch, keeping the
+    /// worktree. Returns the new commit, or `None` when nothing changed.
+    pub async fn commit(&self, message: &str) -> io::Result<Option<String>> {
+        worktree::commit_all(&self.path, message).await
+    }
+
+    /// Remove the worktree and the session branch, dropping the changes.
+    pub async fn discard(&self) -> io::Result<()> {
+        worktree::remove_worktree(&self.repo_root, &self.path, &self.branch).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::worktree::init_test_repo;
+    use crate::worktree::test_git;
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn merge_lands_session_changes_and_removes_the_worktree() {
+        let repo = tempfile::tempdir().expect("repo");
+        let codex_home = tempfile::tempdir().expect("codex home");
+        init_test_repo(repo.path()).await;
+
+        let session = SessionWorktree::create(codex_home.path(), repo.path())
+            .await
+            .expect("create worktree")
+            .expect("inside a repository");
+        std::fs::write(session.cwd().join("notes.txt"), "from codex\n").expect("write");
+        assert!(!repo.path().join("notes.txt").exists());
+
+        assert_eq!(
+            session.merge().await.expect("merge"),
+            MergeOutcome::Merged {
+                into: "main".to_string(),
+                commits: 1,
+            }
+        );
+        assert_eq!(
+            std::fs::read_to_string(repo.path().join("notes.txt")).expect("merged file"),
+            "from codex\n"
+        );
+        assert!(!session.cwd().exists());
+        assert_eq!(
+            test_git(repo.path(), &["branch", "--list", session.branch()]).await,
+            ""
+        );
+    }
+
+    #[tokio::test]
+    async fn discard_leaves_the_checkout_untouched() {
+        let repo = tempfile::tempdir().expect("repo");
+        let codex_home = tempfile::tempdir().expect("codex home");
+        init_test_repo(repo.path()).await;
+
+        let session = SessionWorktree::create(codex_home.path(), repo.path())
+            .await
+            .expect("create worktree")
+            .expect("inside a repository");
+        std::fs::write(session.cwd().join("README.md"), "changed\n").expect("write");
+
+        session.discard().await.expect("discard");
+        assert_eq!(
+            std::fs::read_to_string(repo.path().join("README.md")).expect("readme"),
+            "hello\n"
+        );
+        assert!(!session.cwd().exists());
+    }
+}
@@ -5,6 +5,7 @@ use tokio::process::Child;
 use tokio::process::Command;
 use tracing::trace;
 
+use crate::exec_limits::ExecLimits;
 use crate::protocol::SandboxPolicy;
 
 /// Experimental environment variable that will be set to some non-empty value
@@ -28,24 +29,38 @@ pub enum StdioPolicy {
     Inherit,
 }
 
+/// A process for [`spawn_child_async`] to start.
+pub(crate) struct SpawnChildRequest<'a> {
+    pub program: PathBuf,
+    pub args: Vec<String>,
+    pub arg0: Option<&'a str>,
+    pub cwd: PathBuf,
+    pub sandbox_policy: &'a SandboxPolicy,
+    pub stdio_policy: StdioPolicy,
+    pub env: HashMap<String, String>,
+    pub limits: ExecLimits,
+}
+
 /// Spawns the appropriate child process for the ExecParams and SandboxPolicy,
 /// ensuring the args and environment variables used to create the `Command`
 /// (and `Child`) honor the configuration.
 ///
-/// For now, we take `SandboxPolicy` as a parameter to spawn_child() because
-/// we need to determine whether to set the
-/// `CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR` environment variable.
-pub(crate) async fn spawn_child_async(
-    program: PathBuf,
-    args: Vec<String>,
-    #[cfg_attr(not(unix), allow(unused_variables))] arg0: Option<&str>,
-    cwd: PathBuf,
-    sandbox_policy: &SandboxPolicy,
-    stdio_policy: StdioPolicy,
-    env: HashMap<String, String>,
-) -> std::io::Result<Child> {
+/// For now, we take `SandboxPolicy` as part of the request because we need to
+/// determine whether to set the `CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR`
+/// environment variable.
+pub(crate) async fn spawn_child_async(request: SpawnChildRequest<'_>) -> std::io::Result<Child> {
+    let SpawnChildRequest {
+        program,
+        args,
+        arg0,
+        cwd,
+        sandbox_policy,
+        stdio_policy,
+        env,
+        limits,
+    } = request;
     trace!(
-        "spawn_child_async: {program:?} {args:?} {arg0:?} {cwd:?} {sandbox_policy:?} {stdio_policy:?} {env:?}"
+        "spawn_child_async: {program:?} {args:?} {arg0:?} {cwd:?} {sandbox_policy:?} {stdio_policy:?} {env:?} {limits:?}"
     );
 
     let mut cmd = Command::new(&program);
@@ -60,6 +75,11 @@ pub(crate) async fn spawn_child_async(
         cmd.env(CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR, "1");
     }
 
+    #[cfg(target_os = "linux")]
+    let memory_cgroup = limits
+        .memory_bytes()
+        .and_then(crate::exec_limits::MemoryCgroup::create);
+
     // If this Codex process dies (including being killed via SIGKILL), we want
     // any child processes that were spawned as part of a `"shell"` tool call
     // to also be terminated.
@@ -69,10 +89,21 @@ pub(crate) async fn spawn_child_async(
         let detach_from_tty = matches!(stdio_policy, StdioPolicy::RedirectForShellTool);
         #[cfg(target_os = "linux")]
         let parent_pid = libc::getpid();
+        // The cgroup caps memory when there is one; otherwise the rlimits do.
+        #[cfg(target_os = "linux")]
+        let (cgroup_procs, limits) = match &memory_cgroup {
+            Some(cgroup) => (Some(cgroup.procs_path()), limits.without_memory()),
+            None => (None, limits),
+        };
         cmd.pre_exec(move || {
             if detach_from_tty {
                 codex_utils_pty::process_group::detach_from_tty()?;
             }
+            #[cfg(target_os = "linux")]
+            if let Some(cgroup_procs) = &cgroup_procs {
+                crate::exec_limits::join_cgroup(cgroup_procs)?;
+            }
+            crate::exec_limits::apply_in_child(limits)?;
 
             // This relies on prctl(2), so it only works on Linux.
             #[cfg(target_os = "linux")]
@@ -103,5 +134,26 @@ pub(crate) async fn spawn_child_async(
         }
     }
 
-    cmd.kill_on_drop(true).spawn()
+    // Start suspended so the job object's limits are in place before the
+    // command runs any code.
+    #[cfg(windows)]
+    if limits.has_resource_limits() {
+        cmd.creation_flags(crate::exec_limits::CREATE_SUSPENDED);
+    }
+
+    let spawned = cmd.kill_on_drop(true).spawn();
+    #[cfg(target_os = "linux")]
+    if let Some(cgroup) = memory_cgroup {
+        cgroup.remove_when_empty();
+    }
+    let child = spawned?;
+    #[cfg(windows)]
+    if limits.has_resource_limits()
+        && let Err(err) = crate::exec_limits::apply_to_suspended_child(limits, &child)
+    {
+        let mut child = child;
+        let _ = child.start_kill();
+        return Err(err);
+    }
+    Ok(child)
 }
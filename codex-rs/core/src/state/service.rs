@@ -4,15 +4,24 @@ use crate::AuthManager;
 use crate::RolloutRecorder;
 use crate::agent::AgentControl;
 use crate::analytics_client::AnalyticsEventsClient;
+use crate::code_index::CodeIndexCache;
+use crate::container::ContainerManager;
 use crate::exec_policy::ExecPolicyManager;
+use crate::lsp::LspManager;
 use crate::mcp_connection_manager::McpConnectionManager;
 use crate::models_manager::manager::ModelsManager;
+use crate::output_store::SessionOutputs;
+use crate::persistent_shell::PersistentShellManager;
+use crate::remote_exec::RemoteManager;
+use crate::sandboxing::NetworkProxyManager;
 use crate::skills::SkillsManager;
 use crate::state_db::StateDbHandle;
 use crate::tools::sandboxing::ApprovalStore;
 use crate::transport_manager::TransportManager;
+use crate::trash::SessionTrash;
 use crate::unified_exec::UnifiedExecProcessManager;
 use crate::user_notification::UserNotifier;
+use crate::write_protected::WriteProtectedPaths;
 use codex_otel::OtelManager;
 use tokio::sync::Mutex;
 use tokio::sync::RwLock;
@@ -22,6 +31,8 @@ pub(crate) struct SessionServices {
     pub(crate) mcp_connection_manager: Arc<RwLock<McpConnectionManager>>,
     pub(crate) mcp_startup_cancellation_token: Mutex<CancellationToken>,
     pub(crate) unified_exec_manager: UnifiedExecProcessManager,
+    /// Shell kept alive across commands by the `persistent_shell` feature.
+    pub(crate) persistent_shell: PersistentShellManager,
     pub(crate) analytics_events_client: AnalyticsEventsClient,
     pub(crate) notifier: UserNotifier,
     pub(crate) rollout: Mutex<Option<RolloutRecorder>>,
@@ -36,4 +47,22 @@ pub(crate) struct SessionServices {
     pub(crate) agent_control: AgentControl,
     pub(crate) state_db: Option<StateDbHandle>,
     pub(crate) transport_manager: TransportManager,
+    /// Present when `[trash] enabled = true`.
+    pub(crate) trash: Mutex<Option<SessionTrash>>,
+    /// Command output saved for `read_output` by the `output_paging` feature.
+    pub(crate) saved_outputs: Mutex<SessionOutputs>,
+    /// Language servers from `[lsp.servers]`, started on first use.
+    pub(crate) lsp: LspManager,
+    /// Container the shell tools run in when `[container] image` is set.
+    pub(crate) container: ContainerManager,
+    /// SSH host the shell tools run on when `[remote] host` is set.
+    pub(crate) remote: RemoteManager,
+    /// Proxy sandboxed commands reach the network through when
+    /// `[network_proxy]` is enabled.
+    pub(crate) network_proxy: NetworkProxyManager,
+    /// Cached walk for the `write_protected` paths the sandbox keeps
+    /// read-only.
+    pub(crate) write_protected: Arc<std::sync::Mutex<WriteProtectedPaths>>,
+    /// Code indexes loaded by `search_code`, kept between searches.
+    pub(crate) code_index: Arc<CodeIndexCache>,
 }
@@ -3,22 +3,45 @@
 use codex_protocol::models::ResponseItem;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
 
 use crate::codex::SessionConfiguration;
 use crate::context_manager::ContextManager;
+use crate::context_manager::PinnedContext;
+use crate::external_edits::ExternalEditWatcher;
+use crate::patch_undo::AppliedPatch;
+use crate::patch_undo::MAX_UNDO_LEVELS;
 use crate::protocol::RateLimitSnapshot;
 use crate::protocol::TokenUsage;
 use crate::protocol::TokenUsageInfo;
+use crate::repo_map::RepoMap;
 use crate::truncate::TruncationPolicy;
 
 /// Persistent, session-scoped state previously stored directly on `Session`.
 pub(crate) struct SessionState {
     pub(crate) session_configuration: SessionConfiguration,
     pub(crate) history: ContextManager,
+    /// Files and messages inserted into every prompt; see `Op::Pin`.
+    pub(crate) pinned_context: PinnedContext,
+    /// Outline of the repository inserted into every prompt when the
+    /// `repo_map` feature is on; refreshed at the start of each turn.
+    pub(crate) repo_map: RepoMap,
+    /// Working tree as of the end of the last turn, for the
+    /// `external_edits` feature.
+    pub(crate) external_edits: ExternalEditWatcher,
     pub(crate) latest_rate_limits: Option<RateLimitSnapshot>,
     pub(crate) server_reasoning_included: bool,
+    /// Turn id and model snapshot the provider last reported serving.
+    served_model: Option<(String, String)>,
+    /// Whether the user was already warned that the pinned snapshot is not served.
+    pinned_snapshot_warned: bool,
     pub(crate) dependency_env: HashMap<String, String>,
     pub(crate) mcp_dependency_prompted: HashSet<String>,
+    /// Output of the latest run of each command, keyed by command and cwd.
+    command_outputs: HashMap<(Vec<String>, PathBuf), String>,
+    /// Patches `/undo` can revert, oldest first.
+    applied_patches: Vec<AppliedPatch>,
     /// Whether the session's initial context has been seeded into history.
     ///
     /// TODO(owen): This is a temporary solution to avoid updating a thread's updated_at
@@ -33,10 +56,17 @@ impl SessionState {
         Self {
             session_configuration,
             history,
+            pinned_context: PinnedContext::default(),
+            repo_map: RepoMap::default(),
+            external_edits: ExternalEditWatcher::default(),
             latest_rate_limits: None,
             server_reasoning_included: false,
+            served_model: None,
+            pinned_snapshot_warned: false,
             dependency_env: HashMap::new(),
             mcp_dependency_prompted: HashSet::new(),
+            command_outputs: HashMap::new(),
+            applied_patches: Vec::new(),
             initial_context_seeded: false,
         }
     }
@@ -105,6 +135,33 @@ impl SessionState {
         self.server_reasoning_included
     }
 
+    /// Records the model snapshot served during `turn_id`, returning `true`
+    /// the first time each snapshot is seen in a turn.
+    pub(crate) fn record_served_model(&mut self, turn_id: &str, served_model: &str) -> bool {
+        if self
+            .served_model
+            .as_ref()
+            .is_some_and(|(turn, model)| turn == turn_id && model == served_model)
+        {
+            return false;
+        }
+        self.served_model = Some((turn_id.to_string(), served_model.to_string()));
+        true
+    }
+
+    /// Whether the provider reported a served model during `turn_id`.
+    pub(crate) fn served_model_recorded(&self, turn_id: &str) -> bool {
+        self.served_model
+            .as_ref()
+            .is_some_and(|(turn, _)| turn == turn_id)
+    }
+
+    /// Returns `true` the first time it is called, so the pinned snapshot
+    /// warning is shown at most once per session.
+    pub(crate) fn take_pinned_snapshot_warning(&mut self) -> bool {
+        !std::mem::replace(&mut self.pinned_snapshot_warned, true)
+    }
+
     pub(crate) fn record_mcp_dependency_prompted<I>(&mut self, names: I)
     where
         I: IntoIterator<Item = String>,
@@ -125,6 +182,29 @@ impl SessionState {
     pub(crate) fn dependency_env(&self) -> HashMap<String, String> {
         self.dependency_env.clone()
     }
+
+    /// Records `output` as the latest output of `command` run in `cwd` and
+    /// returns the output of its previous run, if any.
+    pub(crate) fn replace_command_output(
+        &mut self,
+        command: &[String],
+        cwd: &Path,
+        output: String,
+    ) -> Option<String> {
+        self.command_outputs
+            .insert((command.to_vec(), cwd.to_path_buf()), output)
+    }
+
+    pub(crate) fn push_applied_patch(&mut self, patch: AppliedPatch) {
+        if self.applied_patches.len() == MAX_UNDO_LEVELS {
+            self.applied_patches.remove(0);
+        }
+        self.applied_patches.push(patch);
+    }
+
+    pub(crate) fn pop_applied_patch(&mut self) -> Option<AppliedPatch> {
+        self.applied_patches.pop()
+    }
 }
 
 // Sometimes new snapshots don't include credits or plan information.
@@ -73,6 +73,8 @@ pub(crate) struct TurnState {
     pending_user_input: HashMap<String, oneshot::Sender<RequestUserInputResponse>>,
     pending_dynamic_tools: HashMap<String, oneshot::Sender<DynamicToolResponse>>,
     pending_input: Vec<ResponseInputItem>,
+    /// Bytes of MCP tool output shown to the model so far this turn.
+    mcp_output_bytes: usize,
 }
 
 impl TurnState {
@@ -145,6 +147,19 @@ impl TurnState {
     pub(crate) fn has_pending_input(&self) -> bool {
         !self.pending_input.is_empty()
     }
+
+    /// Takes up to `wanted` bytes from what is left of a turn budget of
+    /// `limit` bytes of MCP output, returning how many were granted.
+    pub(crate) fn take_mcp_output_bytes(&mut self, limit: usize, wanted: usize) -> usize {
+        let granted = wanted.min(limit.saturating_sub(self.mcp_output_bytes));
+        self.mcp_output_bytes += granted;
+        granted
+    }
+
+    /// Gives back bytes taken with `take_mcp_output_bytes` that went unused.
+    pub(crate) fn return_mcp_output_bytes(&mut self, unused: usize) {
+        self.mcp_output_bytes = self.mcp_output_bytes.saturating_sub(unused);
+    }
 }
 
 impl ActiveTurn {
@@ -0,0 +1,447 @@
+//! Edits addressed by definition name instead of by line.
+//!
+//! The `edit_definition` tool names a function, class or impl block by its
+//! qualified name (`Foo::bar`, `App.run`), which is resolved with tree-sitter
+//! against the file as it is on disk, so the edit lands in the right place
+//! even after earlier edits moved it. The edited file is then expressed as an
+//! `apply_patch` patch, so it is approved, sandboxed and tracked like any
+//! other patch.
+
+use std::io;
+use std::ops::Range;
+use std::path::Path;
+
+use codex_apply_patch::ApplyPatchAction;
+use codex_apply_patch::ApplyPatchFileChange;
+use codex_apply_patch::MaybeApplyPatchVerified;
+use codex_apply_patch::maybe_parse_apply_patch_verified;
+use serde::Deserialize;
+use similar::ChangeTag;
+use similar::TextDiff;
+
+use crate::syntax_outline::Definition;
+use crate::syntax_outline::definitions;
+
+/// Context lines tried, in order, when expressing an edit as a patch. More
+/// context is only used when smaller hunks would match elsewhere in the file.
+const PATCH_CONTEXT_LINES: &[usize] = &[3, 12];
+/// Indentation for a body that is empty, so there is nothing to copy it from.
+const DEFAULT_INDENT_UNIT: &str = "    ";
+/// Maximum number of definitions listed when a symbol is not found.
+const MAX_LISTED_DEFINITIONS: usize = 30;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum EditAction {
+    /// Replace the whole definition, including its attributes or decorators.
+    Replace,
+    /// Replace what is inside the body, keeping the signature.
+    ReplaceBody,
+    Delete,
+    InsertBefore,
+    InsertAfter,
+}
+
+/// Apply `action` to the definition named `symbol` in `source` and return the
+/// edited file. `content` is re-indented to the definition (or, for
+/// [`EditAction::ReplaceBody`], to its body), so it may be written flush left.
+pub(crate) fn edit_definition(
+    path: &Path,
+    source: &str,
+    symbol: &str,
+    action: EditAction,
+    content: &str,
+) -> io::Result<String> {
+    let Some(definitions) = definitions(path, source) else {
+        return Err(io::Error::other(format!(
+            "{} is not a Rust, Python, JavaScript, TypeScript or Go file (or is too large); use apply_patch instead",
+            path.display()
+        )));
+    };
+    let definition = find_definition(&definitions, symbol)
+        .map_err(|err| io::Error::other(format!("{err} in {}", path.display())))?;
+    if action != EditAction::Delete && content.trim().is_empty() {
+        let body_is_braced = action == EditAction::ReplaceBody
+            && definition
+                .body
+                .as_ref()
+                .and_then(|body| source.get(body.clone()))
+                .is_some_and(|body| body.starts_with('{'));
+        if !body_is_braced {
+            return Err(io::Error::other("content must not be empty"));
+        }
+    }
+
+    let (range, replacement) = match action {
+        EditAction::Replace => {
+            let range = whole_lines(source, definition.range.clone());
+            let indent = indentation(source, definition.range.start);
+            (range.clone(), fit(source, range, content, indent))
+        }
+        EditAction::Delete => (whole_lines(source, definition.range.clone()), String::new()),
+        EditAction::InsertBefore => {
+            let start = whole_lines(source, definition.range.clone()).start;
+            let indent = indentation(source, definition.range.start);
+            (start..start, fit(source, start..start, content, indent))
+        }
+        EditAction::InsertAfter => {
+            let end = whole_lines(source, definition.range.clone()).end;
+            let indent = indentation(source, definition.range.start);
+            let mut replacement = reindent(content, indent);
+            if !source[..end].ends_with('\n') {
+                replacement.insert(0, '\n');
+            }
+            (end..end, replacement)
+        }
+        EditAction::ReplaceBody => {
+            let Some(body) = definition.body.clone() else {
+                return Err(io::Error::other(format!(
+                    "`{}` has no body; use the `replace` action",
+                    definition.qualified_name()
+                )));
+            };
+            replace_body(source, body, content)
+        }
+    };
+
+    let mut edited = source.to_string();
+    edited.replace_range(range, &replacement);
+    Ok(edited)
+}
+
+/// Resolve `symbol` to a single definition. Segments may be separated by
+/// `::` or `.`, a leading keyword such as `impl` or `def` is ignored, and a
+/// trailing part of the qualified name is enough when it is unambiguous.
+fn find_definition<'a>(definitions: &'a [Definition], symbol: &str) -> io::Result<&'a Definition> {
+    let segments = symbol_segments(symbol);
+    if segments.is_empty() {
+        return Err(io::Error::other("symbol must not be empty"));
+    }
+    let matches: Vec<&Definition> = definitions
+        .iter()
+        .filter(|definition| definition.path.ends_with(&segments))
+        .collect();
+    let exact: Vec<&Definition> = matches
+        .iter()
+        .copied()
+        .filter(|definition| definition.path == segments)
+        .collect();
+    let candidates = if exact.is_empty() { matches } else { exact };
+    match candidates.as_slice() {
+        [definition] => Ok(*definition),
+        [] => {
+            let mut known: Vec<String> = definitions
+                .iter()
+                .take(MAX_LISTED_DEFINITIONS)
+                .map(Definition::qualified_name)
+                .collect();
+            if definitions.len() > MAX_LISTED_DEFINITIONS {
+                known.push("...".to_string());
+            }
+            Err(io::Error::other(format!(
+                "no definition named `{symbol}` (known: {})",
+                known.join(", ")
+            )))
+        }
+        candidates => {
+            let listed: Vec<String> = candidates
+                .iter()
+                .map(|definition| {
+                    format!(
+                        "L{} {} ({})",
+                        definition.start_line,
+                        definition.qualified_name(),
+                        definition.kind
+                    )
+                })
+                .collect();
+            Err(io::Error::other(format!(
+                "`{symbol}` is ambiguous; qualify it further or use apply_patch (matches: {})",
+                listed.join(", ")
+            )))
+        }
+    }
+}
+
+/// Express the change from `old` to `new` as an `apply_patch` update of
+/// `path` (relative to `cwd`), checked to reproduce `new` exactly.
+pub(crate) fn patch_for_edit(
+    cwd: &Path,
+    path: &str,
+    old: &str,
+    new: &str,
+) -> io::Result<ApplyPatchAction> {
+    if old == new {
+        return Err(io::Error::other(format!("the edit does not change {path}")));
+    }
+    let expected = if new.ends_with('\n') {
+        new.to_string()
+    } else {
+        format!("{new}\n")
+    };
+    let diff = TextDiff::from_lines(old, new);
+    // With the whole file as context the patch can only match in one place.
+    let whole_file = old.lines().count() + 1;
+    let mut last_error = None;
+    for context in PATCH_CONTEXT_LINES.iter().copied().chain([whole_file]) {
+        let argv = vec![
+            "apply_patch".to_string(),
+            render_patch(path, &diff, context),
+        ];
+        match maybe_parse_apply_patch_verified(&argv, cwd) {
+            MaybeApplyPatchVerified::Body(action) => {
+                let reproduces = action.changes().values().any(|change| {
+                    matches!(
+                        change,
+                        ApplyPatchFileChange::Update { new_content, .. } if *new_content == expected
+                    )
+                });
+                if reproduces {
+                    return Ok(action);
+                }
+            }
+            MaybeApplyPatchVerified::CorrectnessError(err) => last_error = Some(err.to_string()),
+            MaybeApplyPatchVerified::ShellParseError(_)
+            | MaybeApplyPatchVerified::NotApplyPatch => {}
+        }
+    }
+    Err(io::Error::other(format!(
+        "could not express the edit of {path} as a patch{}",
+        last_error.map(|err| format!(": {err}")).unwrap_or_default()
+    )))
+}
+
+fn render_patch(path: &str, diff: &TextDiff<'_, '_, '_, str>, context: usize) -> String {
+    let mut patch = format!("*** Begin Patch\n*** Update File: {path}\n");
+    for group in diff.grouped_ops(context) {
+        patch.push_str("@@\n");
+        for op in &group {
+            for change in diff.iter_changes(op) {
+                let sign = match change.tag() {
+                    ChangeTag::Equal => ' ',
+                    ChangeTag::Delete => '-',
+                    ChangeTag::Insert => '+',
+                };
+                let line = change.value();
+                patch.push(sign);
+                patch.push_str(line.strip_suffix('\n').unwrap_or(line));
+                patch.push('\n');
+            }
+        }
+    }
+    patch.push_str("*** End Patch");
+    patch
+}
+
+fn symbol_segments(symbol: &str) -> Vec<String> {
+    let symbol = symbol.trim();
+    let symbol = ["impl ", "fn ", "def ", "class ", "func ", "function "]
+        .iter()
+        .find_map(|keyword| symbol.strip_prefix(keyword))
+        .unwrap_or(symbol);
+    symbol
+        .split("::")
+        .flat_map(|part| part.split('.'))
+        .map(str::trim)
+        .filter(|segment| !segment.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn replace_body(source: &str, body: Range<usize>, content: &str) -> (Range<usize>, String) {
+    let text = &source[body.clone()];
+    if text.starts_with('{') && text.ends_with('}') {
+        let inner = body.start + 1..body.end - 1;
+        let closing_indent = indentation(source, body.start);
+        if content.trim().is_empty() {
+            return (inner, String::new());
+        }
+        let body_indent = source[inner.clone()]
+            .lines()
+            .skip(1)
+            .find(|line| !line.trim().is_empty())
+            .map(|line| &line[..line.len() - line.trim_start().len()])
+            .filter(|indent| indent.len() > closing_indent.len())
+            .map_or_else(
+                || format!("{closing_indent}{DEFAULT_INDENT_UNIT}"),
+                str::to_string,
+            );
+        let replacement = format!("\n{}{closing_indent}", reindent(content, &body_indent));
+        return (inner, replacement);
+    }
+    // An indented block (Python): replace its lines.
+    let range = whole_lines(source, body.clone());
+    let indent = indentation(source, body.start);
+    (range.clone(), fit(source, range, content, indent))
+}
+
+/// `content` for `range`: re-indented lines when `range` covers whole lines,
+/// otherwise the trimmed text.
+fn fit(source: &str, range: Range<usize>, content: &str, indent: &str) -> String {
+    if range.start == line_start(source, range.start) {
+        reindent(content, indent)
+    } else {
+        content.trim().to_string()
+    }
+}
+
+fn line_start(source: &str, offset: usize) -> usize {
+    source[..offset].rfind('\n').map_or(0, |index| index + 1)
+}
+
+/// Leading whitespace of the line containing `offset`.
+fn indentation(source: &str, offset: usize) -> &str {
+    let line = &source[line_start(source, offset)..];
+    &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+}
+
+/// Extends `range` to whole lines, including the trailing newline, unless
+/// other code shares the first or last line.
+fn whole_lines(source: &str, range: Range<usize>) -> Range<usize> {
+    let first = line_start(source, range.start);
+    let start = if source[first..range.start].trim().is_empty() {
+        first
+    } else {
+        range.start
+    };
+    let line_end = source[range.end..]
+        .find('\n')
+        .map_or(source.len(), |index| range.end + index + 1);
+    let end = if source[range.end..line_end].trim().is_empty() {
+        line_end
+    } else {
+        range.end
+    };
+    start..end
+}
+
+/// Shift `content` so its least indented line starts at `indent`, ending with
+/// a newline.
+fn reindent(content: &str, indent: &str) -> String {
+    let content = content.trim_end();
+    let common = content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start_matches([' ', '\t']).len())
+        .min()
+        .unwrap_or(0);
+    let mut out = String::new();
+    for line in content.lines() {
+        if !line.trim().is_empty() {
+            out.push_str(indent);
+            out.push_str(&line[common..]);
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const RUST_SOURCE: &str = "\
+struct Foo;
+
+impl Foo {
+    /// Doubles.
+    fn bar(&self, x: i32) -> i32 {
+        x * 2
+    }
+}
+
+impl std::fmt::Display for Foo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, \"foo\")
+    }
+}
+";
+
+    #[test]
+    fn edits_definitions_by_qualified_name() {
+        let path = Path::new("lib.rs");
+        let edited = edit_definition(
+            path,
+            RUST_SOURCE,
+            "impl Foo::bar",
+            EditAction::ReplaceBody,
+            "let y = x + 1;\ny * 2",
+        )
+        .expect("replace body");
+        assert_eq!(
+            edited.lines().skip(4).take(4).collect::<Vec<_>>(),
+            vec![
+                "    fn bar(&self, x: i32) -> i32 {",
+                "        let y = x + 1;",
+                "        y * 2",
+                "    }",
+            ]
+        );
+
+        // Deleting a method takes its doc comment with it.
+        let edited =
+            edit_definition(path, RUST_SOURCE, "Foo.bar", EditAction::Delete, "").expect("delete");
+        assert!(edited.contains("impl Foo {\n}\n"), "{edited}");
+
+        let edited = edit_definition(
+            path,
+            RUST_SOURCE,
+            "Foo::bar",
+            EditAction::InsertAfter,
+            "\nfn baz(&self) {}",
+        )
+        .expect("insert after");
+        assert!(
+            edited.contains("        x * 2\n    }\n\n    fn baz(&self) {}\n}\n"),
+            "{edited}"
+        );
+
+        let err = edit_definition(path, RUST_SOURCE, "Foo", EditAction::Delete, "")
+            .expect_err("ambiguous");
+        assert!(err.to_string().contains("`Foo` is ambiguous"), "{err}");
+        let err = edit_definition(path, RUST_SOURCE, "Foo::qux", EditAction::Delete, "")
+            .expect_err("missing");
+        assert!(err.to_string().contains("Foo::bar"), "{err}");
+
+        let python = "class App:\n    @property\n    def name(self):\n        return 'a'\n";
+        let edited = edit_definition(
+            Path::new("app.py"),
+            python,
+            "App.name",
+            EditAction::Replace,
+            "def name(self):\n    return 'b'",
+        )
+        .expect("replace");
+        assert_eq!(
+            edited,
+            "class App:\n    def name(self):\n        return 'b'\n"
+        );
+    }
+
+    #[test]
+    fn patch_reproduces_edit_when_context_repeats() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let old = "fn a() {\n    x();\n    x();\n    x();\n}\n\nfn b() {\n    x();\n    x();\n    x();\n}\n";
+        std::fs::write(dir.path().join("lib.rs"), old).expect("write");
+        let new = edit_definition(
+            Path::new("lib.rs"),
+            old,
+            "b",
+            EditAction::ReplaceBody,
+            "y();",
+        )
+        .expect("edit");
+
+        let action = patch_for_edit(dir.path(), "lib.rs", old, &new).expect("patch");
+        let Some(ApplyPatchFileChange::Update { new_content, .. }) =
+            action.changes().get(&dir.path().join("lib.rs"))
+        else {
+            panic!("expected an update of lib.rs");
+        };
+        assert_eq!(
+            new_content,
+            "fn a() {\n    x();\n    x();\n    x();\n}\n\nfn b() {\n    y();\n}\n"
+        );
+    }
+}
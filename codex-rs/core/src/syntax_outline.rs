@@ -0,0 +1,535 @@
+//! Syntax-aware truncation for file reads that exceed their line budget.
+//!
+//! Instead of cutting a file mid-function, the read is shortened to the last
+//! function/class boundary inside the budget (found with tree-sitter), and the
+//! definitions that were left out are listed with their line ranges so the
+//! model can ask for exactly the region it needs. The same outline feeds the
+//! repository map (see `repo_map`), and named definitions are what structured
+//! edits target (see `structured_edit`).
+
+use std::ops::Range;
+use std::path::Path;
+
+use tree_sitter::Language;
+use tree_sitter::Node;
+use tree_sitter::Parser;
+use tree_sitter::Tree;
+
+/// Files larger than this are cut at the line budget without parsing.
+const MAX_OUTLINE_SOURCE_BYTES: usize = 2 * 1024 * 1024;
+/// Maximum number of omitted definitions listed in the outline.
+const MAX_OUTLINE_ENTRIES: usize = 40;
+const MAX_LABEL_CHARS: usize = 100;
+
+/// A definition (function, class, impl block, ...) and its 1-indexed,
+/// inclusive line range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct OutlineEntry {
+    pub start_line: usize,
+    pub end_line: usize,
+    /// Number of enclosing definitions, e.g. `1` for a method in a class.
+    pub depth: usize,
+    /// First line of the definition, trimmed.
+    pub label: String,
+}
+
+/// A named definition and the byte ranges an edit can replace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Definition {
+    /// Names of the enclosing definitions followed by this one's, e.g.
+    /// `["Foo", "bar"]` for method `bar` in `impl Foo`.
+    pub path: Vec<String>,
+    /// Node kind, e.g. `function_item`.
+    pub kind: String,
+    pub start_line: usize,
+    pub end_line: usize,
+    /// The whole definition, including Python decorators and Rust attributes
+    /// and doc comments directly above it.
+    pub range: Range<usize>,
+    /// The definition's body (block, class body, ...), when it has one.
+    pub body: Option<Range<usize>>,
+}
+
+impl Definition {
+    pub(crate) fn qualified_name(&self) -> String {
+        self.path.join("::")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SyntaxTruncation {
+    /// Last line (1-indexed, inclusive) to return.
+    pub end_line: usize,
+    /// Definitions that start after `end_line` or continue past it.
+    pub omitted: Vec<OutlineEntry>,
+}
+
+impl SyntaxTruncation {
+    /// Lines appended after the returned file content.
+    pub(crate) fn notice_lines(&self) -> Vec<String> {
+        let mut lines = vec![format!(
+            "[truncated after L{}; read from offset {} to continue. Omitted definitions:]",
+            self.end_line,
+            self.end_line + 1
+        )];
+        for entry in self.omitted.iter().take(MAX_OUTLINE_ENTRIES) {
+            let continued = if entry.start_line <= self.end_line {
+                " (continued)"
+            } else {
+                ""
+            };
+            lines.push(format!(
+                "L{}-L{}: {}{}{continued}",
+                entry.start_line,
+                entry.end_line,
+                "  ".repeat(entry.depth),
+                entry.label
+            ));
+        }
+        if self.omitted.len() > MAX_OUTLINE_ENTRIES {
+            lines.push(format!(
+                "... and {} more",
+                self.omitted.len() - MAX_OUTLINE_ENTRIES
+            ));
+        }
+        lines
+    }
+}
+
+struct LanguageSpec {
+    language: Language,
+    /// Node kinds listed in the outline and used as cut points.
+    definitions: &'static [&'static str],
+    /// Definitions whose bodies hold further definitions worth listing.
+    containers: &'static [&'static str],
+}
+
+fn language_for_path(path: &Path) -> Option<LanguageSpec> {
+    const RUST_DEFINITIONS: &[&str] = &[
+        "function_item",
+        "impl_item",
+        "trait_item",
+        "mod_item",
+        "struct_item",
+        "enum_item",
+        "union_item",
+        "macro_definition",
+    ];
+    const PYTHON_DEFINITIONS: &[&str] = &[
+        "function_definition",
+        "class_definition",
+        "decorated_definition",
+    ];
+    const JS_DEFINITIONS: &[&str] = &[
+        "function_declaration",
+        "generator_function_declaration",
+        "class_declaration",
+        "abstract_class_declaration",
+        "method_definition",
+        "interface_declaration",
+        "type_alias_declaration",
+        "enum_declaration",
+        "internal_module",
+    ];
+    const JS_CONTAINERS: &[&str] = &[
+        "class_declaration",
+        "abstract_class_declaration",
+        "internal_module",
+    ];
+    const GO_DEFINITIONS: &[&str] = &[
+        "function_declaration",
+        "method_declaration",
+        "type_declaration",
+    ];
+
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    let (language, definitions, containers): (Language, &[&str], &[&str]) = match extension.as_str()
+    {
+        "rs" => (
+            tree_sitter_rust::LANGUAGE.into(),
+            RUST_DEFINITIONS,
+            &["impl_item", "trait_item", "mod_item"],
+        ),
+        "py" | "pyi" => (
+            tree_sitter_python::LANGUAGE.into(),
+            PYTHON_DEFINITIONS,
+            &["class_definition"],
+        ),
+        "js" | "mjs" | "cjs" | "jsx" => (
+            tree_sitter_javascript::LANGUAGE.into(),
+            JS_DEFINITIONS,
+            JS_CONTAINERS,
+        ),
+        "ts" | "mts" | "cts" => (
+            tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            JS_DEFINITIONS,
+            JS_CONTAINERS,
+        ),
+        "tsx" => (
+            tree_sitter_typescript::LANGUAGE_TSX.into(),
+            JS_DEFINITIONS,
+            JS_CONTAINERS,
+        ),
+        "go" => (tree_sitter_go::LANGUAGE.into(), GO_DEFINITIONS, &[]),
+        _ => return None,
+    };
+    Some(LanguageSpec {
+        language,
+        definitions,
+        containers,
+    })
+}
+
+/// Decide where to cut a read of `limit` lines starting at `offset` (both
+/// 1-indexed) so it ends on a definition boundary.
+///
+/// Returns `None` when the window already reaches the end of the file or the
+/// language is not supported; callers then keep the plain line cut. When no
+/// boundary keeps at least half of the window, the cut stays at the budget
+/// and the definition it splits is listed as continued.
+pub(crate) fn truncate_at_syntax_boundary(
+    path: &Path,
+    source: &str,
+    offset: usize,
+    limit: usize,
+) -> Option<SyntaxTruncation> {
+    if source.len() > MAX_OUTLINE_SOURCE_BYTES {
+        return None;
+    }
+    let window_end = offset.checked_add(limit)?.checked_sub(1)?;
+    let total_lines = source.lines().count();
+    if total_lines <= window_end {
+        return None;
+    }
+
+    let (spec, tree) = parse(path, source)?;
+    let root = tree.root_node();
+
+    let mut entries = Vec::new();
+    collect_definitions(root, 0, &spec, source, &mut entries);
+
+    let straddles = |line: usize| {
+        entries
+            .iter()
+            .any(|entry| entry.start_line <= line && entry.end_line > line)
+    };
+    let end_line = if !straddles(window_end) {
+        window_end
+    } else {
+        let mut cursor = root.walk();
+        let top_level_ends = root
+            .named_children(&mut cursor)
+            .map(|node| node.end_position().row + 1)
+            .collect::<Vec<_>>();
+        entries
+            .iter()
+            .map(|entry| entry.end_line)
+            .chain(top_level_ends)
+            .filter(|line| (offset..=window_end).contains(line) && !straddles(*line))
+            .max()
+            .filter(|line| line - offset + 1 >= limit.div_ceil(2))
+            .or_else(|| {
+                // Fall back to any boundary when the only candidates are
+                // inside enclosing definitions (e.g. between two methods).
+                entries
+                    .iter()
+                    .map(|entry| entry.end_line)
+                    .filter(|line| (offset..=window_end).contains(line))
+                    .max()
+                    .filter(|line| line - offset + 1 >= limit.div_ceil(2))
+            })
+            .unwrap_or(window_end)
+    };
+
+    let omitted = entries
+        .into_iter()
+        .filter(|entry| entry.end_line > end_line)
+        .collect();
+    Some(SyntaxTruncation { end_line, omitted })
+}
+
+/// Whether [`outline`] understands the language of `path`.
+pub(crate) fn supports_path(path: &Path) -> bool {
+    language_for_path(path).is_some()
+}
+
+/// Every definition in `source`, in file order with nested definitions after
+/// their container. `None` when the language is not supported or the file is
+/// too large to parse.
+pub(crate) fn outline(path: &Path, source: &str) -> Option<Vec<OutlineEntry>> {
+    if source.len() > MAX_OUTLINE_SOURCE_BYTES {
+        return None;
+    }
+    let (spec, tree) = parse(path, source)?;
+    let mut entries = Vec::new();
+    collect_definitions(tree.root_node(), 0, &spec, source, &mut entries);
+    Some(entries)
+}
+
+/// Every definition in `source` that has a name, in file order with nested
+/// definitions after their container. `None` when the language is not
+/// supported or the file is too large to parse.
+pub(crate) fn definitions(path: &Path, source: &str) -> Option<Vec<Definition>> {
+    if source.len() > MAX_OUTLINE_SOURCE_BYTES {
+        return None;
+    }
+    let (spec, tree) = parse(path, source)?;
+    let mut out = Vec::new();
+    collect_named_definitions(tree.root_node(), &mut Vec::new(), &spec, source, &mut out);
+    Some(out)
+}
+
+fn parse(path: &Path, source: &str) -> Option<(LanguageSpec, Tree)> {
+    let spec = language_for_path(path)?;
+    let mut parser = Parser::new();
+    parser.set_language(&spec.language).ok()?;
+    let tree = parser.parse(source, None)?;
+    Some((spec, tree))
+}
+
+fn collect_definitions(
+    node: Node<'_>,
+    depth: usize,
+    spec: &LanguageSpec,
+    source: &str,
+    out: &mut Vec<OutlineEntry>,
+) {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        if !spec.definitions.contains(&child.kind()) {
+            collect_definitions(child, depth, spec, source, out);
+            continue;
+        }
+        // Python decorators belong to the definition's range, but the
+        // label should name the function or class itself.
+        let definition = if child.kind() == "decorated_definition" {
+            child.child_by_field_name("definition").unwrap_or(child)
+        } else {
+            child
+        };
+        out.push(OutlineEntry {
+            start_line: child.start_position().row + 1,
+            end_line: child.end_position().row + 1,
+            depth,
+            label: label_for(definition, source),
+        });
+        if spec.containers.contains(&definition.kind()) {
+            collect_definitions(definition, depth + 1, spec, source, out);
+        }
+    }
+}
+
+fn collect_named_definitions(
+    node: Node<'_>,
+    parents: &mut Vec<String>,
+    spec: &LanguageSpec,
+    source: &str,
+    out: &mut Vec<Definition>,
+) {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        if !spec.definitions.contains(&child.kind()) {
+            collect_named_definitions(child, parents, spec, source, out);
+            continue;
+        }
+        let definition = if child.kind() == "decorated_definition" {
+            child.child_by_field_name("definition").unwrap_or(child)
+        } else {
+            child
+        };
+        let Some(names) = names_for(definition, source) else {
+            continue;
+        };
+        // `export function f() {}` is edited as a whole.
+        let outer = child
+            .parent()
+            .filter(|parent| parent.kind() == "export_statement")
+            .unwrap_or(child);
+        let depth = parents.len();
+        parents.extend(names);
+        out.push(Definition {
+            path: parents.clone(),
+            kind: definition.kind().to_string(),
+            start_line: outer.start_position().row + 1,
+            end_line: outer.end_position().row + 1,
+            range: leading_start(outer, source)..outer.end_byte(),
+            body: definition
+                .child_by_field_name("body")
+                .map(|body| body.byte_range()),
+        });
+        if spec.containers.contains(&definition.kind()) {
+            collect_named_definitions(definition, parents, spec, source, out);
+        }
+        parents.truncate(depth);
+    }
+}
+
+/// The name segments a definition adds to the qualified name: usually one,
+/// but Go methods are qualified by their receiver type.
+fn names_for(node: Node<'_>, source: &str) -> Option<Vec<String>> {
+    let text = |node: Node<'_>| source.get(node.byte_range()).map(str::to_string);
+    match node.kind() {
+        // `impl<T> Display for Foo<T>` is named after `Foo`.
+        "impl_item" => {
+            let ty = text(node.child_by_field_name("type")?)?;
+            let ty = ty.split('<').next().unwrap_or_default().trim();
+            Some(vec![ty.to_string()])
+        }
+        "method_declaration" => {
+            let name = text(node.child_by_field_name("name")?)?;
+            match node
+                .child_by_field_name("receiver")
+                .and_then(|receiver| find_descendant(receiver, "type_identifier"))
+                .and_then(text)
+            {
+                Some(receiver) => Some(vec![receiver, name]),
+                None => Some(vec![name]),
+            }
+        }
+        "type_declaration" => {
+            let mut cursor = node.walk();
+            let spec = node
+                .named_children(&mut cursor)
+                .find(|child| child.kind() == "type_spec")?;
+            Some(vec![text(spec.child_by_field_name("name")?)?])
+        }
+        _ => Some(vec![text(node.child_by_field_name("name")?)?]),
+    }
+}
+
+fn find_descendant<'tree>(node: Node<'tree>, kind: &str) -> Option<Node<'tree>> {
+    if node.kind() == kind {
+        return Some(node);
+    }
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor)
+        .find_map(|child| find_descendant(child, kind))
+}
+
+/// Start of `node` extended over the Rust attributes and doc comments right
+/// above it, so edits move them together with the definition.
+fn leading_start(node: Node<'_>, source: &str) -> usize {
+    let mut start = node.start_byte();
+    let mut current = node;
+    while let Some(previous) = current.prev_named_sibling() {
+        let is_leading = match previous.kind() {
+            "attribute_item" => true,
+            "line_comment" | "block_comment" => {
+                let text = source.get(previous.byte_range()).unwrap_or_default();
+                (text.starts_with("///") && !text.starts_with("////"))
+                    || (text.starts_with("/**") && !text.starts_with("/**/"))
+            }
+            _ => false,
+        };
+        if !is_leading || previous.end_position().row + 1 < current.start_position().row {
+            break;
+        }
+        start = previous.start_byte();
+        current = previous;
+    }
+    start
+}
+
+fn label_for(node: Node<'_>, source: &str) -> String {
+    let text = source.get(node.byte_range()).unwrap_or_default();
+    let first_line = text.lines().next().unwrap_or_default().trim();
+    if first_line.chars().count() <= MAX_LABEL_CHARS {
+        return first_line.to_string();
+    }
+    let mut label: String = first_line.chars().take(MAX_LABEL_CHARS - 1).collect();
+    label.push('…');
+    label
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const RUST_SOURCE: &str = "\
+use std::fmt;
+
+fn first() {
+    let a = 1;
+    let b = 2;
+}
+
+struct Point {
+    x: i32,
+}
+
+impl Point {
+    fn new() -> Self {
+        Self { x: 0 }
+    }
+
+    fn x(&self) -> i32 {
+        self.x
+    }
+}
+";
+
+    #[test]
+    fn cuts_before_a_split_definition() {
+        // A 13-line budget ends inside `impl Point`, so the read stops after
+        // `struct Point` and lists the impl block and its methods.
+        let truncation =
+            truncate_at_syntax_boundary(Path::new("lib.rs"), RUST_SOURCE, 1, 13).expect("cut");
+
+        assert_eq!(truncation.end_line, 10);
+        assert_eq!(
+            truncation.omitted,
+            vec![
+                OutlineEntry {
+                    start_line: 12,
+                    end_line: 20,
+                    depth: 0,
+                    label: "impl Point {".to_string(),
+                },
+                OutlineEntry {
+                    start_line: 13,
+                    end_line: 15,
+                    depth: 1,
+                    label: "fn new() -> Self {".to_string(),
+                },
+                OutlineEntry {
+                    start_line: 17,
+                    end_line: 19,
+                    depth: 1,
+                    label: "fn x(&self) -> i32 {".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            truncation.notice_lines()[0],
+            "[truncated after L10; read from offset 11 to continue. Omitted definitions:]"
+        );
+    }
+
+    #[test]
+    fn cuts_between_methods_inside_a_large_block() {
+        let truncation =
+            truncate_at_syntax_boundary(Path::new("lib.rs"), RUST_SOURCE, 12, 6).expect("cut");
+
+        assert_eq!(truncation.end_line, 15);
+        assert_eq!(
+            truncation.notice_lines()[1..],
+            [
+                "L12-L20: impl Point { (continued)".to_string(),
+                "L17-L19:   fn x(&self) -> i32 {".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn whole_file_or_unknown_language_is_not_truncated() {
+        assert_eq!(
+            truncate_at_syntax_boundary(Path::new("lib.rs"), RUST_SOURCE, 1, 100),
+            None
+        );
+        assert_eq!(
+            truncate_at_syntax_boundary(Path::new("notes.txt"), RUST_SOURCE, 1, 5),
+            None
+        );
+    }
+}
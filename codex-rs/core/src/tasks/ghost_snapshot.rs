@@ -1,4 +1,5 @@
 use crate::codex::TurnContext;
+use crate::protocol::CheckpointCreatedEvent;
 use crate::protocol::EventMsg;
 use crate::protocol::WarningEvent;
 use crate::state::TaskKind;
@@ -110,6 +111,20 @@ impl SessionTask for GhostSnapshotTask {
                                     ghost_commit: ghost_commit.clone(),
                                 }])
                                 .await;
+                            if let Some(trash) =
+                                session.session.services.trash.lock().await.as_mut()
+                            {
+                                trash.start_checkpoint();
+                            }
+                            session
+                                .session
+                                .send_event(
+                                    &ctx_for_task,
+                                    EventMsg::CheckpointCreated(CheckpointCreatedEvent {
+                                        commit_id: ghost_commit.id().to_string(),
+                                    }),
+                                )
+                                .await;
                             info!("ghost commit captured: {}", ghost_commit.id());
                         }
                         Ok(Err(err)) => match err {
@@ -200,6 +200,7 @@ impl Session {
         if should_close_processes {
             self.close_unified_exec_processes().await;
         }
+        self.snapshot_external_edits(turn_context.as_ref()).await;
         let event = EventMsg::TurnComplete(TurnCompleteEvent { last_agent_message });
         self.send_event(turn_context.as_ref(), event).await;
     }
@@ -254,6 +255,8 @@ impl Session {
         session_task
             .abort(session_ctx, Arc::clone(&task.turn_context))
             .await;
+        self.snapshot_external_edits(task.turn_context.as_ref())
+            .await;
 
         if reason == TurnAbortReason::Interrupted {
             let marker = ResponseItem::Message {
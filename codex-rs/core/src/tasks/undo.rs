@@ -1,15 +1,20 @@
 use std::sync::Arc;
 
+use crate::codex::Session;
 use crate::codex::TurnContext;
+use crate::features::Feature;
+use crate::patch_undo::AppliedPatch;
 use crate::protocol::EventMsg;
 use crate::protocol::UndoCompletedEvent;
 use crate::protocol::UndoStartedEvent;
+use crate::session_prefix::UNDO_OPEN_TAG;
 use crate::state::TaskKind;
 use crate::tasks::SessionTask;
 use crate::tasks::SessionTaskContext;
 use async_trait::async_trait;
 use codex_git::RestoreGhostCommitOptions;
 use codex_git::restore_ghost_commit_with_options;
+use codex_protocol::models::ContentItem;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::user_input::UserInput;
 use tokio_util::sync::CancellationToken;
@@ -64,6 +69,13 @@ impl SessionTask for UndoTask {
             return None;
         }
 
+        if !sess.enabled(Feature::GhostCommit) {
+            let completed = undo_last_patch(&sess, ctx.as_ref()).await;
+            sess.send_event(ctx.as_ref(), EventMsg::UndoCompleted(completed))
+                .await;
+            return None;
+        }
+
         let history = sess.clone_history().await;
         let mut items = history.raw_items().to_vec();
         let mut completed = UndoCompletedEvent {
@@ -106,6 +118,12 @@ impl SessionTask for UndoTask {
                 info!(commit_id = commit_id, "Undo restored ghost snapshot");
                 completed.success = true;
                 completed.message = Some(format!("Undo restored snapshot {short_id}."));
+                record_undo_notice(
+                    &sess,
+                    ctx.as_ref(),
+                    "The user undid your last turn: every file is back to how it was before that turn started.",
+                )
+                .await;
             }
             Ok(Err(err)) => {
                 let message = format!("Failed to restore snapshot {commit_id}: {err}");
@@ -124,3 +142,72 @@ impl SessionTask for UndoTask {
         None
     }
 }
+
+/// Reverts the most recent patch still on the session's undo stack. A patch
+/// that cannot be reverted because its files changed since is dropped, so
+/// the next undo moves on to the one before it.
+async fn undo_last_patch(sess: &Session, ctx: &TurnContext) -> UndoCompletedEvent {
+    let Some(applied) = sess.take_last_applied_patch().await else {
+        return UndoCompletedEvent {
+            success: false,
+            message: Some("No applied patch left to undo.".to_string()),
+        };
+    };
+    let files = patch_files(&applied, ctx);
+    let reverted = tokio::task::spawn_blocking(move || applied.revert()).await;
+    match reverted {
+        Ok(Ok(())) => {
+            info!("Undo reverted the patch to {files}");
+            record_undo_notice(
+                sess,
+                ctx,
+                &format!("The user undid your most recent patch, restoring {files} to their contents before it. Do not reapply it unless asked."),
+            )
+            .await;
+            UndoCompletedEvent {
+                success: true,
+                message: Some(format!("Undo reverted the patch to {files}.")),
+            }
+        }
+        Ok(Err(err)) => {
+            warn!("failed to undo the patch to {files}: {err}");
+            UndoCompletedEvent {
+                success: false,
+                message: Some(format!("Could not undo the patch to {files}: {err}.")),
+            }
+        }
+        Err(err) => {
+            error!("failed to undo the patch to {files}: {err}");
+            UndoCompletedEvent {
+                success: false,
+                message: Some(format!("Could not undo the patch to {files}: {err}.")),
+            }
+        }
+    }
+}
+
+fn patch_files(applied: &AppliedPatch, ctx: &TurnContext) -> String {
+    applied
+        .paths()
+        .map(|path| {
+            path.strip_prefix(&ctx.cwd)
+                .unwrap_or(path)
+                .display()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Tells the model its changes were undone, so it does not build on them.
+async fn record_undo_notice(sess: &Session, ctx: &TurnContext, notice: &str) {
+    let item = ResponseItem::Message {
+        id: None,
+        role: "user".to_string(),
+        content: vec![ContentItem::InputText {
+            text: format!("{UNDO_OPEN_TAG}\n{notice}\n</undo>"),
+        }],
+        end_turn: None,
+    };
+    sess.record_conversation_items(ctx, &[item]).await;
+}
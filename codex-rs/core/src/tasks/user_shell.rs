@@ -10,6 +10,7 @@ use tracing::error;
 use uuid::Uuid;
 
 use crate::codex::TurnContext;
+use crate::exec::ExecLimits;
 use crate::exec::ExecToolCallOutput;
 use crate::exec::SandboxType;
 use crate::exec::StdoutStream;
@@ -114,6 +115,7 @@ impl SessionTask for UserShellCommandTask {
             sandbox_permissions: SandboxPermissions::UseDefault,
             justification: None,
             arg0: None,
+            limits: ExecLimits::default(),
         };
 
         let stdout_stream = Some(StdoutStream {
@@ -164,6 +166,7 @@ impl SessionTask for UserShellCommandTask {
                             exit_code: -1,
                             duration: Duration::ZERO,
                             formatted_output: aborted_message,
+                            previous_run_diff: None,
                         }),
                     )
                     .await;
@@ -190,6 +193,7 @@ impl SessionTask for UserShellCommandTask {
                                 &output,
                                 turn_context.truncation_policy,
                             ),
+                            previous_run_diff: None,
                         }),
                     )
                     .await;
@@ -235,6 +239,7 @@ impl SessionTask for UserShellCommandTask {
                                 &exec_output,
                                 turn_context.truncation_policy,
                             ),
+                            previous_run_diff: None,
                         }),
                     )
                     .await;
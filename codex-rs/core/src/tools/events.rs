@@ -146,6 +146,17 @@ impl ToolEmitter {
     }
 
     pub async fn emit(&self, ctx: ToolEventCtx<'_>, stage: ToolEventStage) {
+        self.emit_with_previous_run_diff(ctx, stage, None).await;
+    }
+
+    /// Like [`Self::emit`], attaching the diff against the command's previous
+    /// run to `ExecCommandEnd`.
+    pub(crate) async fn emit_with_previous_run_diff(
+        &self,
+        ctx: ToolEventCtx<'_>,
+        stage: ToolEventStage,
+        previous_run_diff: Option<String>,
+    ) {
         match (self, stage) {
             (
                 Self::Shell {
@@ -161,6 +172,7 @@ impl ToolEmitter {
                     ctx,
                     ExecCommandInput::new(command, cwd.as_path(), parsed_cmd, *source, None, None),
                     stage,
+                    previous_run_diff,
                 )
                 .await;
             }
@@ -245,6 +257,7 @@ impl ToolEmitter {
                         process_id.as_deref(),
                     ),
                     stage,
+                    previous_run_diff,
                 )
                 .await;
             }
@@ -255,16 +268,49 @@ impl ToolEmitter {
         self.emit(ctx, ToolEventStage::Begin).await;
     }
 
-    fn format_exec_output_for_model(
+    /// Records the output of a finished command and diffs it against the
+    /// previous run of the same command in this session.
+    pub(crate) async fn previous_run_diff(
+        &self,
+        ctx: ToolEventCtx<'_>,
+        output: &ExecToolCallOutput,
+    ) -> Option<String> {
+        match self {
+            Self::Shell { command, cwd, .. } | Self::UnifiedExec { command, cwd, .. } => {
+                ctx.session
+                    .diff_against_previous_run(command, cwd, &output.aggregated_output.text)
+                    .await
+            }
+            Self::ApplyPatch { .. } => None,
+        }
+    }
+
+    async fn format_exec_output_for_model(
         &self,
         output: &ExecToolCallOutput,
         ctx: ToolEventCtx<'_>,
+        previous_run_diff: Option<&str>,
     ) -> String {
+        let paged_output = ctx
+            .session
+            .page_output(
+                &super::build_content_with_timeout(output),
+                ctx.turn.truncation_policy,
+            )
+            .await;
         match self {
-            Self::Shell { freeform: true, .. } => {
-                super::format_exec_output_for_model_freeform(output, ctx.turn.truncation_policy)
-            }
-            _ => super::format_exec_output_for_model_structured(output, ctx.turn.truncation_policy),
+            Self::Shell { freeform: true, .. } => super::format_exec_output_for_model_freeform(
+                output,
+                ctx.turn.truncation_policy,
+                paged_output.as_deref(),
+                previous_run_diff,
+            ),
+            _ => super::format_exec_output_for_model_structured(
+                output,
+                ctx.turn.truncation_policy,
+                paged_output.as_deref(),
+                previous_run_diff,
+            ),
         }
     }
 
@@ -273,9 +319,12 @@ impl ToolEmitter {
         ctx: ToolEventCtx<'_>,
         out: Result<ExecToolCallOutput, ToolError>,
     ) -> Result<String, FunctionCallError> {
-        let (event, result) = match out {
+        let (event, result, previous_run_diff) = match out {
             Ok(output) => {
-                let content = self.format_exec_output_for_model(&output, ctx);
+                let previous_run_diff = self.previous_run_diff(ctx, &output).await;
+                let content = self
+                    .format_exec_output_for_model(&output, ctx, previous_run_diff.as_deref())
+                    .await;
                 let exit_code = output.exit_code;
                 let event = ToolEventStage::Success(output);
                 let result = if exit_code == 0 {
@@ -283,20 +332,20 @@ impl ToolEmitter {
                 } else {
                     Err(FunctionCallError::RespondToModel(content))
                 };
-                (event, result)
+                (event, result, previous_run_diff)
             }
             Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Timeout { output })))
             | Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied { output }))) => {
-                let response = self.format_exec_output_for_model(&output, ctx);
+                let response = self.format_exec_output_for_model(&output, ctx, None).await;
                 let event = ToolEventStage::Failure(ToolEventFailure::Output(*output));
                 let result = Err(FunctionCallError::RespondToModel(response));
-                (event, result)
+                (event, result, None)
             }
             Err(ToolError::Codex(err)) => {
                 let message = format!("execution error: {err:?}");
                 let event = ToolEventStage::Failure(ToolEventFailure::Message(message.clone()));
                 let result = Err(FunctionCallError::RespondToModel(message));
-                (event, result)
+                (event, result, None)
             }
             Err(ToolError::Rejected(msg)) => {
                 // Normalize common rejection messages for exec tools so tests and
@@ -313,10 +362,11 @@ impl ToolEmitter {
                 };
                 let event = ToolEventStage::Failure(ToolEventFailure::Message(normalized.clone()));
                 let result = Err(FunctionCallError::RespondToModel(normalized));
-                (event, result)
+                (event, result, None)
             }
         };
-        self.emit(ctx, event).await;
+        self.emit_with_previous_run_diff(ctx, event, previous_run_diff)
+            .await;
         result
     }
 }
@@ -357,12 +407,14 @@ struct ExecCommandResult {
     exit_code: i32,
     duration: Duration,
     formatted_output: String,
+    previous_run_diff: Option<String>,
 }
 
 async fn emit_exec_stage(
     ctx: ToolEventCtx<'_>,
     exec_input: ExecCommandInput<'_>,
     stage: ToolEventStage,
+    previous_run_diff: Option<String>,
 ) {
     match stage {
         ToolEventStage::Begin => {
@@ -386,6 +438,7 @@ async fn emit_exec_stage(
                 exit_code: output.exit_code,
                 duration: output.duration,
                 formatted_output: format_exec_output_str(&output, ctx.turn.truncation_policy),
+                previous_run_diff,
             };
             emit_exec_end(ctx, exec_input, exec_result).await;
         }
@@ -398,6 +451,7 @@ async fn emit_exec_stage(
                 exit_code: -1,
                 duration: Duration::ZERO,
                 formatted_output: text,
+                previous_run_diff: None,
             };
             emit_exec_end(ctx, exec_input, exec_result).await;
         }
@@ -427,6 +481,7 @@ async fn emit_exec_end(
                 exit_code: exec_result.exit_code,
                 duration: exec_result.duration,
                 formatted_output: exec_result.formatted_output,
+                previous_run_diff: exec_result.previous_run_diff,
             }),
         )
         .await;
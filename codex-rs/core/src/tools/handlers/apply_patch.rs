@@ -1,16 +1,24 @@
 use std::collections::BTreeMap;
 use std::path::Path;
+use std::path::PathBuf;
 
 use crate::apply_patch;
 use crate::apply_patch::InternalApplyPatchInvocation;
 use crate::apply_patch::convert_apply_patch_to_protocol;
+use crate::apply_patch::declined_hunks_note;
+use crate::apply_patch::inexact_hunk_summary;
 use crate::client_common::tools::FreeformTool;
 use crate::client_common::tools::FreeformToolFormat;
 use crate::client_common::tools::ResponsesApiTool;
 use crate::client_common::tools::ToolSpec;
 use crate::codex::Session;
 use crate::codex::TurnContext;
+use crate::features::Feature;
 use crate::function_tool::FunctionCallError;
+use crate::hooks::hook_report;
+use crate::hooks::run_hooks;
+use crate::patch_limits::check_patch_limits;
+use crate::patch_undo::PatchCheckpoint;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
@@ -103,69 +111,36 @@ impl ToolHandler for ApplyPatchHandler {
         // Avoid building temporary ExecParams/command vectors; derive directly from inputs.
         let cwd = turn.cwd.clone();
         let command = vec!["apply_patch".to_string(), patch_input.clone()];
-        match codex_apply_patch::maybe_parse_apply_patch_verified(&command, &cwd) {
+        let config = turn.client.config();
+        check_patch_limits(&patch_input, &cwd, &config.patch_limits).map_err(|reason| {
+            FunctionCallError::RespondToModel(format!("patch rejected: {reason}"))
+        })?;
+        let fuzz = config.apply_patch_fuzz;
+        match codex_apply_patch::maybe_parse_apply_patch_verified_with_fuzz(&command, &cwd, fuzz) {
             codex_apply_patch::MaybeApplyPatchVerified::Body(changes) => {
-                match apply_patch::apply_patch(turn.as_ref(), changes).await {
-                    InternalApplyPatchInvocation::Output(item) => {
-                        let content = item?;
-                        Ok(ToolOutput::Function {
-                            content,
-                            content_items: None,
-                            success: Some(true),
-                        })
-                    }
-                    InternalApplyPatchInvocation::DelegateToExec(apply) => {
-                        let changes = convert_apply_patch_to_protocol(&apply.action);
-                        let file_paths = file_paths_for_action(&apply.action);
-                        let emitter =
-                            ToolEmitter::apply_patch(changes.clone(), apply.auto_approved);
-                        let event_ctx = ToolEventCtx::new(
-                            session.as_ref(),
-                            turn.as_ref(),
-                            &call_id,
-                            Some(&tracker),
-                        );
-                        emitter.begin(event_ctx).await;
-
-                        let req = ApplyPatchRequest {
-                            action: apply.action,
-                            file_paths,
-                            changes,
-                            exec_approval_requirement: apply.exec_approval_requirement,
-                            timeout_ms: None,
-                            codex_exe: turn.codex_linux_sandbox_exe.clone(),
-                        };
-
-                        let mut orchestrator = ToolOrchestrator::new();
-                        let mut runtime = ApplyPatchRuntime::new();
-                        let tool_ctx = ToolCtx {
-                            session: session.as_ref(),
-                            turn: turn.as_ref(),
-                            call_id: call_id.clone(),
-                            tool_name: tool_name.to_string(),
-                        };
-                        let out = orchestrator
-                            .run(&mut runtime, &req, &tool_ctx, &turn, turn.approval_policy)
-                            .await;
-                        let event_ctx = ToolEventCtx::new(
-                            session.as_ref(),
-                            turn.as_ref(),
-                            &call_id,
-                            Some(&tracker),
-                        );
-                        let content = emitter.finish(event_ctx, out).await?;
-                        Ok(ToolOutput::Function {
-                            content,
-                            content_items: None,
-                            success: Some(true),
-                        })
-                    }
-                }
+                run_apply_patch_action(
+                    changes,
+                    session.as_ref(),
+                    turn.as_ref(),
+                    Some(&tracker),
+                    &call_id,
+                    &tool_name,
+                    None,
+                )
+                .await
             }
             codex_apply_patch::MaybeApplyPatchVerified::CorrectnessError(parse_error) => {
-                Err(FunctionCallError::RespondToModel(format!(
-                    "apply_patch verification failed: {parse_error}"
-                )))
+                let mut message = format!("apply_patch verification failed: {parse_error}");
+                // For multi-hunk patches, report every hunk rather than just
+                // the first failure so the model can fix the patch in one retry.
+                if let Ok(report) = codex_apply_patch::dry_run_patch(&patch_input, &cwd, fuzz)
+                    && report.hunks.len() > 1
+                    && report.has_conflicts()
+                {
+                    message.push_str("\n\nHunk report:\n");
+                    message.push_str(&report.summary(&cwd));
+                }
+                Err(FunctionCallError::RespondToModel(message))
             }
             codex_apply_patch::MaybeApplyPatchVerified::ShellParseError(error) => {
                 tracing::trace!("Failed to parse apply_patch input, {error:?}");
@@ -193,61 +168,27 @@ pub(crate) async fn intercept_apply_patch(
     call_id: &str,
     tool_name: &str,
 ) -> Result<Option<ToolOutput>, FunctionCallError> {
-    match codex_apply_patch::maybe_parse_apply_patch_verified(command, cwd) {
+    let config = turn.client.config();
+    match codex_apply_patch::maybe_parse_apply_patch_verified_with_fuzz(
+        command,
+        cwd,
+        config.apply_patch_fuzz,
+    ) {
         codex_apply_patch::MaybeApplyPatchVerified::Body(changes) => {
+            check_patch_limits(&changes.patch, &changes.cwd, &config.patch_limits).map_err(
+                |reason| FunctionCallError::RespondToModel(format!("patch rejected: {reason}")),
+            )?;
             session
                 .record_model_warning(
                     format!("apply_patch was requested via {tool_name}. Use the apply_patch tool instead of exec_command."),
                     turn,
                 )
                 .await;
-            match apply_patch::apply_patch(turn, changes).await {
-                InternalApplyPatchInvocation::Output(item) => {
-                    let content = item?;
-                    Ok(Some(ToolOutput::Function {
-                        content,
-                        content_items: None,
-                        success: Some(true),
-                    }))
-                }
-                InternalApplyPatchInvocation::DelegateToExec(apply) => {
-                    let changes = convert_apply_patch_to_protocol(&apply.action);
-                    let approval_keys = file_paths_for_action(&apply.action);
-                    let emitter = ToolEmitter::apply_patch(changes.clone(), apply.auto_approved);
-                    let event_ctx =
-                        ToolEventCtx::new(session, turn, call_id, tracker.as_ref().copied());
-                    emitter.begin(event_ctx).await;
-
-                    let req = ApplyPatchRequest {
-                        action: apply.action,
-                        file_paths: approval_keys,
-                        changes,
-                        exec_approval_requirement: apply.exec_approval_requirement,
-                        timeout_ms,
-                        codex_exe: turn.codex_linux_sandbox_exe.clone(),
-                    };
-
-                    let mut orchestrator = ToolOrchestrator::new();
-                    let mut runtime = ApplyPatchRuntime::new();
-                    let tool_ctx = ToolCtx {
-                        session,
-                        turn,
-                        call_id: call_id.to_string(),
-                        tool_name: tool_name.to_string(),
-                    };
-                    let out = orchestrator
-                        .run(&mut runtime, &req, &tool_ctx, turn, turn.approval_policy)
-                        .await;
-                    let event_ctx =
-                        ToolEventCtx::new(session, turn, call_id, tracker.as_ref().copied());
-                    let content = emitter.finish(event_ctx, out).await?;
-                    Ok(Some(ToolOutput::Function {
-                        content,
-                        content_items: None,
-                        success: Some(true),
-                    }))
-                }
-            }
+            run_apply_patch_action(
+                changes, session, turn, tracker, call_id, tool_name, timeout_ms,
+            )
+            .await
+            .map(Some)
         }
         codex_apply_patch::MaybeApplyPatchVerified::CorrectnessError(parse_error) => {
             Err(FunctionCallError::RespondToModel(format!(
@@ -262,6 +203,113 @@ pub(crate) async fn intercept_apply_patch(
     }
 }
 
+/// Apply a verified patch, either directly or through the orchestrator so
+/// it is approved and sandboxed like any other `apply_patch` call.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn run_apply_patch_action(
+    action: ApplyPatchAction,
+    session: &Session,
+    turn: &TurnContext,
+    tracker: Option<&SharedTurnDiffTracker>,
+    call_id: &str,
+    tool_name: &str,
+    timeout_ms: Option<u64>,
+) -> Result<ToolOutput, FunctionCallError> {
+    let config = turn.client.config();
+    let written_paths = written_paths_for_action(&action);
+    let inexact_hunks = inexact_hunk_summary(&action, config.apply_patch_fuzz);
+    // With ghost snapshots on, `/undo` restores whole turns instead.
+    let checkpoint =
+        (!session.enabled(Feature::GhostCommit)).then(|| PatchCheckpoint::capture(&action));
+    let content = match apply_patch::apply_patch(turn, action).await {
+        InternalApplyPatchInvocation::Output(item) => item?,
+        InternalApplyPatchInvocation::DelegateToExec(apply) => {
+            let changes = convert_apply_patch_to_protocol(&apply.action);
+            let approval_keys = file_paths_for_action(&apply.action);
+            let emitter = ToolEmitter::apply_patch(changes.clone(), apply.auto_approved);
+            let event_ctx = ToolEventCtx::new(session, turn, call_id, tracker);
+            emitter.begin(event_ctx).await;
+
+            let req = ApplyPatchRequest {
+                action: apply.action,
+                file_paths: approval_keys,
+                changes,
+                exec_approval_requirement: apply.exec_approval_requirement,
+                timeout_ms,
+                codex_exe: turn.codex_linux_sandbox_exe.clone(),
+                fuzz: config.apply_patch_fuzz,
+                write_protected: apply.write_protected,
+            };
+
+            let mut orchestrator = ToolOrchestrator::new();
+            let mut runtime = ApplyPatchRuntime::new();
+            let tool_ctx = ToolCtx {
+                session,
+                turn,
+                call_id: call_id.to_string(),
+                tool_name: tool_name.to_string(),
+            };
+            let out = orchestrator
+                .run(&mut runtime, &req, &tool_ctx, turn, turn.approval_policy)
+                .await;
+            let event_ctx = ToolEventCtx::new(session, turn, call_id, tracker);
+            let mut content = emitter.finish(event_ctx, out).await?;
+            if let Some(note) = declined_hunks_note(runtime.declined_hunks(), &req.action.cwd) {
+                content.push_str("\n\n");
+                content.push_str(&note);
+            }
+            content
+        }
+    };
+
+    if let Some(applied) = checkpoint.and_then(PatchCheckpoint::finish) {
+        session.record_applied_patch(applied).await;
+    }
+    let mut content = content;
+    if let Some(inexact_hunks) = inexact_hunks {
+        content.push_str("\n\n");
+        content.push_str(&inexact_hunks);
+    }
+    if !config.hooks.post_patch.is_empty() {
+        let runs = run_hooks(&config.hooks.post_patch, &turn.cwd, &written_paths).await;
+        if let Some(report) = hook_report("post_patch", &runs) {
+            content.push_str("\n\n");
+            content.push_str(&report);
+        }
+    }
+    if session.services.lsp.is_enabled()
+        && let Some(diagnostics) = session
+            .services
+            .lsp
+            .diagnostics_after_edit(&written_paths, &turn.cwd)
+            .await
+    {
+        content.push_str("\n\n");
+        content.push_str(&diagnostics);
+    }
+    Ok(ToolOutput::Function {
+        content,
+        content_items: None,
+        success: Some(true),
+    })
+}
+
+/// Files that exist after `action` is applied: added and updated files, at
+/// their destination when moved.
+fn written_paths_for_action(action: &ApplyPatchAction) -> Vec<PathBuf> {
+    action
+        .changes()
+        .iter()
+        .filter_map(|(path, change)| match change {
+            ApplyPatchFileChange::Add { .. } => Some(action.cwd.join(path)),
+            ApplyPatchFileChange::Update { move_path, .. } => {
+                Some(action.cwd.join(move_path.as_ref().unwrap_or(path)))
+            }
+            ApplyPatchFileChange::Delete { .. } => None,
+        })
+        .collect()
+}
+
 /// Returns a custom tool that can be used to edit files. Well-suited for GPT-5 models
 /// https://platform.openai.com/docs/guides/function-calling#custom-tools
 pub(crate) fn create_apply_patch_freeform_tool() -> ToolSpec {
@@ -0,0 +1,242 @@
+use std::fmt::Write as _;
+
+use async_trait::async_trait;
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::BackgroundJob;
+use regex_lite::Regex;
+use serde::Deserialize;
+use tokio::time::Duration;
+
+use crate::features::Feature;
+use crate::function_tool::FunctionCallError;
+use crate::sandboxing::SandboxPermissions;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+use crate::unified_exec::ExecCommandRequest;
+use crate::unified_exec::JobOutput;
+use crate::unified_exec::JobOutputQuery;
+use crate::unified_exec::UnifiedExecContext;
+
+pub struct BackgroundJobHandler;
+
+/// Lines returned by `read_job_output` when the model sets no limit.
+const DEFAULT_MAX_LINES: usize = 200;
+/// Lines of early output included when a job starts.
+const START_PREVIEW_LINES: usize = 20;
+/// How long `start_job` waits for early output, such as a failure to start.
+const START_OUTPUT_WAIT: Duration = Duration::from_millis(500);
+
+#[derive(Deserialize)]
+struct StartJobArgs {
+    cmd: String,
+    #[serde(default)]
+    workdir: Option<String>,
+    #[serde(default)]
+    sandbox_permissions: SandboxPermissions,
+    #[serde(default)]
+    justification: Option<String>,
+    #[serde(default)]
+    prefix_rule: Option<Vec<String>>,
+}
+
+#[derive(Deserialize)]
+struct ReadJobOutputArgs {
+    job_id: String,
+    #[serde(default)]
+    since_line: Option<usize>,
+    #[serde(default)]
+    filter: Option<String>,
+    #[serde(default)]
+    max_lines: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct StopJobArgs {
+    job_id: String,
+}
+
+#[async_trait]
+impl ToolHandler for BackgroundJobHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn is_mutating(&self, invocation: &ToolInvocation) -> bool {
+        invocation.tool_name != "read_job_output"
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            call_id,
+            tool_name,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "background job handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+        let jobs = session.services.unified_exec_manager.jobs();
+
+        let content = match tool_name.as_str() {
+            "start_job" => {
+                let StartJobArgs {
+                    cmd,
+                    workdir,
+                    sandbox_permissions,
+                    justification,
+                    prefix_rule,
+                } = parse_arguments(&arguments)?;
+                if sandbox_permissions.requires_escalated_permissions()
+                    && !matches!(turn.approval_policy, AskForApproval::OnRequest)
+                {
+                    let approval_policy = turn.approval_policy;
+                    return Err(FunctionCallError::RespondToModel(format!(
+                        "approval policy is {approval_policy:?}; reject command — you cannot ask for escalated permissions if the approval policy is {approval_policy:?}"
+                    )));
+                }
+                let prefix_rule = if session.features().enabled(Feature::RequestRule) {
+                    prefix_rule
+                } else {
+                    None
+                };
+                let workdir = workdir
+                    .filter(|dir| !dir.is_empty())
+                    .map(|dir| turn.resolve_path(Some(dir)));
+                let job_id = jobs.allocate_id().await;
+                let request = ExecCommandRequest {
+                    command: session.user_shell().derive_exec_args(&cmd, true),
+                    process_id: job_id.clone(),
+                    yield_time_ms: 0,
+                    max_output_tokens: None,
+                    workdir,
+                    tty: false,
+                    sandbox_permissions,
+                    justification,
+                    prefix_rule,
+                };
+                let context = UnifiedExecContext::new(session.clone(), turn.clone(), call_id);
+                session
+                    .services
+                    .unified_exec_manager
+                    .start_job(&request, cmd, &context)
+                    .await
+                    .map_err(|err| {
+                        FunctionCallError::RespondToModel(format!("start_job failed: {err}"))
+                    })?;
+                tokio::time::sleep(START_OUTPUT_WAIT).await;
+                let query = JobOutputQuery {
+                    since_line: None,
+                    filter: None,
+                    max_lines: START_PREVIEW_LINES,
+                };
+                match jobs.read(&job_id, &query).await {
+                    Some(output) => format!(
+                        "Started {job_id}. It keeps running across turns until stopped with stop_job; check on it with read_job_output.\n{}",
+                        format_job_output(&output)
+                    ),
+                    None => format!("Started {job_id}."),
+                }
+            }
+            "read_job_output" => {
+                let ReadJobOutputArgs {
+                    job_id,
+                    since_line,
+                    filter,
+                    max_lines,
+                } = parse_arguments(&arguments)?;
+                let filter = filter
+                    .filter(|pattern| !pattern.is_empty())
+                    .map(|pattern| Regex::new(&pattern))
+                    .transpose()
+                    .map_err(|err| {
+                        FunctionCallError::RespondToModel(format!("invalid filter: {err}"))
+                    })?;
+                let query = JobOutputQuery {
+                    since_line,
+                    filter,
+                    max_lines: max_lines.unwrap_or(DEFAULT_MAX_LINES),
+                };
+                match jobs.read(&job_id, &query).await {
+                    Some(output) => format_job_output(&output),
+                    None => return Err(unknown_job(&job_id, &jobs.list().await)),
+                }
+            }
+            "stop_job" => {
+                let StopJobArgs { job_id } = parse_arguments(&arguments)?;
+                match jobs.stop(&job_id).await {
+                    Some(BackgroundJob {
+                        exit_code: Some(code),
+                        ..
+                    }) => format!("{job_id} had already exited with code {code}; it is removed."),
+                    Some(_) => format!("Stopped {job_id}."),
+                    None => return Err(unknown_job(&job_id, &jobs.list().await)),
+                }
+            }
+            other => {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "unsupported background job function {other}"
+                )));
+            }
+        };
+
+        Ok(ToolOutput::Function {
+            content,
+            content_items: None,
+            success: Some(true),
+        })
+    }
+}
+
+fn unknown_job(job_id: &str, jobs: &[BackgroundJob]) -> FunctionCallError {
+    let known = if jobs.is_empty() {
+        "there are no background jobs".to_string()
+    } else {
+        let ids: Vec<&str> = jobs.iter().map(|job| job.id.as_str()).collect();
+        format!("current jobs: {}", ids.join(", "))
+    };
+    FunctionCallError::RespondToModel(format!("unknown job {job_id}; {known}"))
+}
+
+fn format_job_output(output: &JobOutput) -> String {
+    let job = &output.job;
+    let status = match job.exit_code {
+        Some(code) => format!("exited with code {code}"),
+        None => "is running".to_string(),
+    };
+    let mut text = format!("{} (`{}`) {status}.\n", job.id, job.command);
+    if output.lines.is_empty() {
+        text.push_str("No matching output.\n");
+    } else {
+        if output.omitted > 0 {
+            let _ = writeln!(
+                text,
+                "{} earlier matching lines not shown; narrow with since_line or filter.",
+                output.omitted
+            );
+        }
+        for (number, line) in &output.lines {
+            let _ = writeln!(text, "{number}: {line}");
+        }
+    }
+    if !output.partial.is_empty() {
+        let _ = writeln!(text, "Unterminated last line: {}", output.partial);
+    }
+    let _ = write!(
+        text,
+        "Output so far ends at line {}; pass since_line={} to read only newer output.",
+        output.last_line, output.last_line
+    );
+    text
+}
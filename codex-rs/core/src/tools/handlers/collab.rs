@@ -25,6 +25,8 @@ use codex_protocol::protocol::CollabWaitingBeginEvent;
 use codex_protocol::protocol::CollabWaitingEndEvent;
 use serde::Deserialize;
 use serde::Serialize;
+use std::path::Path;
+use std::path::PathBuf;
 
 pub struct CollabHandler;
 
@@ -48,6 +50,12 @@ impl ToolHandler for CollabHandler {
         matches!(payload, ToolPayload::Function { .. })
     }
 
+    /// Sub-agents do not run in the parent's collaboration mode, so starting
+    /// one or giving one new work counts as a change.
+    async fn is_mutating(&self, invocation: &ToolInvocation) -> bool {
+        matches!(invocation.tool_name.as_str(), "spawn_agent" | "send_input")
+    }
+
     async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
         let ToolInvocation {
             session,
@@ -82,6 +90,7 @@ impl ToolHandler for CollabHandler {
 mod spawn {
     use super::*;
     use crate::agent::AgentRole;
+    use crate::protocol::SandboxPolicy;
 
     use crate::agent::exceeds_thread_spawn_depth_limit;
     use crate::agent::next_thread_spawn_depth;
@@ -93,6 +102,9 @@ mod spawn {
     struct SpawnAgentArgs {
         message: String,
         agent_type: Option<AgentRole>,
+        #[serde(default)]
+        read_only: bool,
+        workdir: Option<String>,
     }
 
     #[derive(Debug, Serialize)]
@@ -140,6 +152,17 @@ mod spawn {
         agent_role
             .apply_to_config(&mut config)
             .map_err(FunctionCallError::RespondToModel)?;
+        if args.read_only {
+            config
+                .sandbox_policy
+                .set(SandboxPolicy::new_read_only_policy())
+                .map_err(|err| {
+                    FunctionCallError::RespondToModel(format!("sandbox_policy is invalid: {err}"))
+                })?;
+        }
+        if let Some(workdir) = &args.workdir {
+            config.cwd = scoped_workdir(&turn.cwd, workdir)?;
+        }
 
         let result = session
             .services
@@ -588,6 +611,31 @@ fn collab_agent_error(agent_id: ThreadId, err: CodexErr) -> FunctionCallError {
     }
 }
 
+/// Resolves a sub-agent's `workdir`, which must be a directory inside the
+/// parent's working directory. It becomes the sub-agent's workspace, so a
+/// `workspace-write` sandbox only lets it write there. Both paths are
+/// canonicalized before comparing, so neither `..` nor a symlink inside the
+/// working directory can point the sub-agent somewhere else.
+fn scoped_workdir(parent_cwd: &Path, workdir: &str) -> Result<PathBuf, FunctionCallError> {
+    let not_a_directory =
+        || FunctionCallError::RespondToModel(format!("workdir {workdir} is not a directory"));
+    let parent = parent_cwd.canonicalize().map_err(|_| not_a_directory())?;
+    let resolved = parent_cwd
+        .join(workdir)
+        .canonicalize()
+        .map_err(|_| not_a_directory())?;
+    let Ok(relative) = resolved.strip_prefix(&parent) else {
+        return Err(FunctionCallError::RespondToModel(format!(
+            "workdir {workdir} is outside the working directory {}",
+            parent_cwd.display()
+        )));
+    };
+    if !resolved.is_dir() {
+        return Err(not_a_directory());
+    }
+    Ok(parent_cwd.join(relative))
+}
+
 fn build_agent_spawn_config(
     base_instructions: &BaseInstructions,
     turn: &TurnContext,
@@ -1243,4 +1291,53 @@ mod tests {
 
         assert_eq!(config.user_instructions, base_config.user_instructions);
     }
+
+    #[test]
+    fn scoped_workdir_stays_inside_the_parent_cwd() {
+        let temp_dir = tempfile::tempdir().expect("temp dir");
+        let cwd = temp_dir.path();
+        std::fs::create_dir(cwd.join("core")).expect("create dir");
+
+        assert_eq!(
+            scoped_workdir(cwd, "core").expect("relative workdir"),
+            cwd.join("core")
+        );
+        assert_eq!(
+            scoped_workdir(cwd, &cwd.join("core").to_string_lossy()).expect("absolute workdir"),
+            cwd.join("core")
+        );
+        for workdir in ["../elsewhere", "/tmp", "missing"] {
+            assert!(
+                scoped_workdir(cwd, workdir).is_err(),
+                "{workdir} should be rejected"
+            );
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn scoped_workdir_rejects_symlinks_out_of_the_parent_cwd() {
+        let temp_dir = tempfile::tempdir().expect("temp dir");
+        let cwd = temp_dir.path().join("repo");
+        let outside = temp_dir.path().join("outside");
+        std::fs::create_dir_all(cwd.join("core")).expect("create dir");
+        std::fs::create_dir(&outside).expect("create dir");
+        std::os::unix::fs::symlink(&outside, cwd.join("escape")).expect("symlink");
+        std::os::unix::fs::symlink(cwd.join("core"), cwd.join("alias")).expect("symlink");
+
+        let Err(err) = scoped_workdir(&cwd, "escape") else {
+            panic!("a symlink out of the working directory should be rejected");
+        };
+        assert_eq!(
+            err,
+            FunctionCallError::RespondToModel(format!(
+                "workdir escape is outside the working directory {}",
+                cwd.display()
+            ))
+        );
+        assert_eq!(
+            scoped_workdir(&cwd, "alias").expect("symlink inside the working directory"),
+            cwd.join("core")
+        );
+    }
 }
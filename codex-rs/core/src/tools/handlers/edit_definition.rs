@@ -0,0 +1,81 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::function_tool::FunctionCallError;
+use crate::structured_edit::EditAction;
+use crate::structured_edit::edit_definition;
+use crate::structured_edit::patch_for_edit;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::apply_patch::run_apply_patch_action;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub struct EditDefinitionHandler;
+
+#[derive(Deserialize)]
+struct EditDefinitionArgs {
+    path: String,
+    symbol: String,
+    action: EditAction,
+    #[serde(default)]
+    content: String,
+}
+
+#[async_trait]
+impl ToolHandler for EditDefinitionHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn is_mutating(&self, _invocation: &ToolInvocation) -> bool {
+        true
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            tracker,
+            call_id,
+            tool_name,
+            payload,
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "edit_definition handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+        let EditDefinitionArgs {
+            path,
+            symbol,
+            action,
+            content,
+        } = parse_arguments(&arguments)?;
+
+        let abs_path = turn.resolve_path(Some(path.clone()));
+        let source = tokio::fs::read_to_string(&abs_path).await.map_err(|err| {
+            FunctionCallError::RespondToModel(format!("failed to read {path}: {err}"))
+        })?;
+        let patch = edit_definition(&abs_path, &source, &symbol, action, &content)
+            .and_then(|edited| patch_for_edit(&turn.cwd, &path, &source, &edited))
+            .map_err(|err| FunctionCallError::RespondToModel(err.to_string()))?;
+
+        run_apply_patch_action(
+            patch,
+            session.as_ref(),
+            turn.as_ref(),
+            Some(&tracker),
+            &call_id,
+            &tool_name,
+            None,
+        )
+        .await
+    }
+}
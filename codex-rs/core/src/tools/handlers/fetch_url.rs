@@ -0,0 +1,214 @@
+use std::net::SocketAddr;
+
+use async_trait::async_trait;
+use codex_protocol::items::TurnItem;
+use codex_protocol::items::WebSearchItem;
+use codex_protocol::models::WebSearchAction;
+use serde::Deserialize;
+use serde::Serialize;
+use url::Url;
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::fetch_url::FetchedPage;
+use crate::fetch_url::fetch_page;
+use crate::function_tool::FunctionCallError;
+use crate::protocol::AskForApproval;
+use crate::protocol::ReviewDecision;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+use crate::tools::sandboxing::with_cached_approval;
+
+pub struct FetchUrlHandler;
+
+/// Characters of page text returned per call.
+const PAGE_CHARS: usize = 20_000;
+
+/// Session approval cache key for fetching from a host while the sandbox has
+/// no network access.
+#[derive(Serialize)]
+struct FetchApprovalKey {
+    fetch: String,
+}
+
+#[derive(Deserialize)]
+struct FetchUrlArgs {
+    url: String,
+    #[serde(default)]
+    offset: usize,
+}
+
+#[async_trait]
+impl ToolHandler for FetchUrlHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            call_id,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "fetch_url handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+        let FetchUrlArgs { url, offset } = parse_arguments(&arguments)?;
+        let url = url.trim().to_string();
+        let proxy = authorize_fetch(&session, &turn, &call_id, &url).await?;
+
+        // Shown like a page opened by the provider's web search.
+        let item = TurnItem::WebSearch(WebSearchItem {
+            id: call_id,
+            query: url.clone(),
+            action: WebSearchAction::OpenPage {
+                url: Some(url.clone()),
+            },
+        });
+        session.emit_turn_item_started(&turn, &item).await;
+        let page = fetch_page(&url, proxy).await;
+        session.emit_turn_item_completed(&turn, item).await;
+
+        let page = page.map_err(|err| {
+            FunctionCallError::RespondToModel(format!("could not fetch {url}: {err}"))
+        })?;
+        Ok(ToolOutput::Function {
+            content: page_excerpt(&page, offset),
+            content_items: None,
+            success: Some(true),
+        })
+    }
+}
+
+/// Applies the turn's sandbox network policy to a fetch of `url`. With full
+/// network access the page is fetched directly; otherwise it goes through
+/// the network proxy when `[network_proxy]` is enabled, and needs approval
+/// when it is not. Returns the proxy to fetch through, if any.
+async fn authorize_fetch(
+    session: &Session,
+    turn: &TurnContext,
+    call_id: &str,
+    url: &str,
+) -> Result<Option<SocketAddr>, FunctionCallError> {
+    if turn.sandbox_policy.has_full_network_access() {
+        return Ok(None);
+    }
+    if let Some(proxy) = turn.client.config().network_proxy
+        && let Some(proxy) = session.services.network_proxy.ensure_running(proxy).await
+    {
+        return Ok(Some(proxy));
+    }
+    if turn.approval_policy == AskForApproval::Never {
+        return Err(FunctionCallError::RespondToModel(format!(
+            "fetching {url} needs network access, which the sandbox does not allow"
+        )));
+    }
+
+    let host = Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_else(|| url.to_string());
+    let decision = with_cached_approval(
+        &session.services,
+        "fetch_url",
+        vec![FetchApprovalKey {
+            fetch: host.clone(),
+        }],
+        || async {
+            session
+                .request_command_approval(
+                    turn,
+                    call_id.to_string(),
+                    vec!["fetch_url".to_string(), url.to_string()],
+                    turn.cwd.clone(),
+                    Some(format!(
+                        "Fetch {url}? The sandbox does not allow network access."
+                    )),
+                    None,
+                )
+                .await
+        },
+    )
+    .await;
+    match decision {
+        ReviewDecision::Approved
+        | ReviewDecision::ApprovedExecpolicyAmendment { .. }
+        | ReviewDecision::ApprovedForSession
+        | ReviewDecision::ApprovedAlwaysForPaths { .. } => Ok(None),
+        ReviewDecision::ApprovedHunks { .. } | ReviewDecision::Denied | ReviewDecision::Abort => {
+            Err(FunctionCallError::RespondToModel(format!(
+                "the user declined fetching {url}"
+            )))
+        }
+    }
+}
+
+/// Up to [`PAGE_CHARS`] characters of the page from `offset`, with a note
+/// on how to read the rest.
+fn page_excerpt(page: &FetchedPage, offset: usize) -> String {
+    let total = page.text.chars().count();
+    let start = offset.min(total);
+    let end = (start + PAGE_CHARS).min(total);
+    let excerpt: String = page.text.chars().skip(start).take(end - start).collect();
+
+    let mut out = format!("URL: {}\n", page.url);
+    if let Some(title) = &page.title {
+        out.push_str(&format!("Title: {title}\n"));
+    }
+    out.push('\n');
+    out.push_str(&excerpt);
+    if end < total {
+        out.push_str(&format!(
+            "\n\n[Characters {start}-{end} of {total}. Call fetch_url with offset {end} to read more.]"
+        ));
+    } else if start > 0 {
+        out.push_str(&format!("\n\n[Characters {start}-{end} of {total}.]"));
+    }
+    if page.truncated {
+        out.push_str("\n\n[The page was cut off at the download size limit.]");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn long_pages_are_returned_in_parts() {
+        let page = FetchedPage {
+            url: "https://example.com/docs".to_string(),
+            title: Some("Docs".to_string()),
+            text: "é".repeat(PAGE_CHARS + 5),
+            truncated: false,
+        };
+
+        let first = page_excerpt(&page, 0);
+        assert!(first.starts_with("URL: https://example.com/docs\nTitle: Docs\n\né"));
+        assert!(first.ends_with(&format!(
+            "[Characters 0-{PAGE_CHARS} of {}. Call fetch_url with offset {PAGE_CHARS} to read more.]",
+            PAGE_CHARS + 5
+        )));
+        assert_eq!(
+            page_excerpt(&page, PAGE_CHARS),
+            format!(
+                "URL: https://example.com/docs\nTitle: Docs\n\nééééé\n\n[Characters {PAGE_CHARS}-{} of {}.]",
+                PAGE_CHARS + 5,
+                PAGE_CHARS + 5
+            )
+        );
+    }
+}
@@ -0,0 +1,160 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::attribution;
+use crate::function_tool::FunctionCallError;
+use crate::git_commit::CommitPlan;
+use crate::hooks::HookRun;
+use crate::hooks::hook_report;
+use crate::hooks::run_hooks;
+use crate::protocol::AskForApproval;
+use crate::protocol::ReviewDecision;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub struct GitCommitHandler;
+
+#[derive(Deserialize)]
+struct GitCommitArgs {
+    message: String,
+    paths: Vec<String>,
+}
+
+#[async_trait]
+impl ToolHandler for GitCommitHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn is_mutating(&self, _invocation: &ToolInvocation) -> bool {
+        true
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            call_id,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "git_commit handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+        let GitCommitArgs { message, paths } = parse_arguments(&arguments)?;
+        let message = message.trim();
+        if message.is_empty() {
+            return Err(FunctionCallError::RespondToModel(
+                "message must not be empty".to_string(),
+            ));
+        }
+
+        let mut plan = plan_commit(&turn.cwd, &paths).await?;
+        if plan.is_empty() {
+            return Ok(no_changes());
+        }
+        if turn.approval_policy == AskForApproval::Never {
+            return Err(FunctionCallError::RespondToModel(
+                "committing requires approval, but approval_policy is never".to_string(),
+            ));
+        }
+
+        // Hooks run before the approval, and a formatter among them may
+        // rewrite files, so the commit is planned again afterwards: what the
+        // user approves is exactly what gets committed.
+        let config = turn.client.config();
+        let mut report = None;
+        if !config.hooks.pre_commit.is_empty() {
+            let files: Vec<PathBuf> = plan
+                .changes
+                .keys()
+                .filter(|path| path.exists())
+                .cloned()
+                .collect();
+            let runs = run_hooks(&config.hooks.pre_commit, &turn.cwd, &files).await;
+            report = hook_report("pre_commit", &runs);
+            if !runs.iter().all(HookRun::succeeded) {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "a pre_commit hook failed, so nothing was committed.{}",
+                    report
+                        .map(|report| format!("\n\n{report}"))
+                        .unwrap_or_default()
+                )));
+            }
+            plan = plan_commit(&turn.cwd, &paths).await?;
+            if plan.is_empty() {
+                return Ok(no_changes());
+            }
+        }
+
+        // Every commit is approved on its own: the diff and message differ
+        // each time, so an earlier approval says nothing about this one.
+        let decision = session
+            .request_patch_approval(
+                &turn,
+                call_id,
+                plan.changes.clone(),
+                Some(format!("Commit with message: {message}")),
+                None,
+            )
+            .await
+            .await
+            .unwrap_or_default();
+        match decision {
+            ReviewDecision::Approved
+            | ReviewDecision::ApprovedExecpolicyAmendment { .. }
+            | ReviewDecision::ApprovedForSession
+            | ReviewDecision::ApprovedAlwaysForPaths { .. } => {}
+            ReviewDecision::ApprovedHunks { .. }
+            | ReviewDecision::Denied
+            | ReviewDecision::Abort => {
+                return Err(FunctionCallError::RespondToModel(
+                    "the user declined the commit".to_string(),
+                ));
+            }
+        }
+
+        let trailers = attribution::commit_trailers(&config.attribution, &session.conversation_id);
+        let hash = plan
+            .commit(message, &trailers)
+            .await
+            .map_err(|err| FunctionCallError::RespondToModel(err.to_string()))?;
+        let mut content = format!("Committed {} file(s) as {hash}.", plan.changes.len());
+        if let Some(report) = report {
+            content.push_str("\n\n");
+            content.push_str(&report);
+        }
+        Ok(ToolOutput::Function {
+            content,
+            content_items: None,
+            success: Some(true),
+        })
+    }
+}
+
+async fn plan_commit(cwd: &Path, paths: &[String]) -> Result<CommitPlan, FunctionCallError> {
+    CommitPlan::new(cwd, paths)
+        .await
+        .map_err(|err| FunctionCallError::RespondToModel(err.to_string()))
+}
+
+fn no_changes() -> ToolOutput {
+    ToolOutput::Function {
+        content: "There are no changes to commit.".to_string(),
+        content_items: None,
+        success: Some(false),
+    }
+}
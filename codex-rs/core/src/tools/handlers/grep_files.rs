@@ -10,6 +10,7 @@ use crate::function_tool::FunctionCallError;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
 use crate::tools::context::ToolPayload;
+use crate::tools::handlers::authorize_read;
 use crate::tools::handlers::parse_arguments;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
@@ -42,7 +43,14 @@ impl ToolHandler for GrepFilesHandler {
     }
 
     async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
-        let ToolInvocation { payload, turn, .. } = invocation;
+        let ToolInvocation {
+            session,
+            turn,
+            call_id,
+            tool_name,
+            payload,
+            ..
+        } = invocation;
 
         let arguments = match payload {
             ToolPayload::Function { arguments } => arguments,
@@ -72,6 +80,7 @@ impl ToolHandler for GrepFilesHandler {
         let search_path = turn.resolve_path(args.path.clone());
 
         verify_path_exists(&search_path).await?;
+        authorize_read(&session, &turn, &call_id, &tool_name, &search_path).await?;
 
         let include = args.include.as_deref().map(str::trim).and_then(|val| {
             if val.is_empty() {
@@ -3,6 +3,7 @@ use std::sync::Arc;
 
 use crate::function_tool::FunctionCallError;
 use crate::mcp_tool_call::handle_mcp_tool_call;
+use crate::mcp_tool_call::is_read_only_mcp_tool;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
 use crate::tools::context::ToolPayload;
@@ -17,6 +18,13 @@ impl ToolHandler for McpHandler {
         ToolKind::Mcp
     }
 
+    async fn is_mutating(&self, invocation: &ToolInvocation) -> bool {
+        let ToolPayload::Mcp { server, tool, .. } = &invocation.payload else {
+            return true;
+        };
+        !is_read_only_mcp_tool(&invocation.session, server, tool).await
+    }
+
     async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
         let ToolInvocation {
             session,
@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::function_tool::FunctionCallError;
+use crate::project_memory::add_note;
+use crate::project_memory::project_memory_path;
+use crate::project_memory::remove_note;
+use crate::protocol::AskForApproval;
+use crate::protocol::FileChange;
+use crate::protocol::ReviewDecision;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+use crate::tools::sandboxing::with_cached_approval;
+
+pub struct MemoryHandler;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum MemoryAction {
+    Add,
+    Remove,
+}
+
+#[derive(Deserialize)]
+struct MemoryArgs {
+    action: MemoryAction,
+    note: String,
+}
+
+/// Session approval cache key: "approve for session" covers every later
+/// update to the same memory file.
+#[derive(Serialize)]
+struct MemoryApprovalKey {
+    memory: PathBuf,
+}
+
+#[async_trait]
+impl ToolHandler for MemoryHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn is_mutating(&self, _invocation: &ToolInvocation) -> bool {
+        true
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            call_id,
+            tool_name,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "memory handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+        let MemoryArgs { action, note } = parse_arguments(&arguments)?;
+
+        let path = project_memory_path(&turn.cwd);
+        let current = match tokio::fs::read_to_string(&path).await {
+            Ok(contents) => Some(contents),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+            Err(err) => {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "failed to read {}: {err}",
+                    path.display()
+                )));
+            }
+        };
+        let existing = current.as_deref().unwrap_or_default();
+        let updated = match action {
+            MemoryAction::Add => add_note(existing, &note),
+            MemoryAction::Remove => remove_note(existing, &note),
+        }
+        .map_err(FunctionCallError::RespondToModel)?;
+        if updated == existing {
+            return Ok(ToolOutput::Function {
+                content: "The note is already in project memory.".to_string(),
+                content_items: None,
+                success: Some(true),
+            });
+        }
+
+        if turn.approval_policy == AskForApproval::Never {
+            return Err(FunctionCallError::RespondToModel(
+                "updating project memory requires approval, but approval_policy is never"
+                    .to_string(),
+            ));
+        }
+        let change = match &current {
+            Some(previous) => FileChange::Update {
+                unified_diff: similar::TextDiff::from_lines(previous.as_str(), updated.as_str())
+                    .unified_diff()
+                    .context_radius(1)
+                    .to_string(),
+                move_path: None,
+            },
+            None => FileChange::Add {
+                content: updated.clone(),
+            },
+        };
+        let decision = with_cached_approval(
+            &session.services,
+            &tool_name,
+            vec![MemoryApprovalKey {
+                memory: path.clone(),
+            }],
+            || async {
+                session
+                    .request_patch_approval(
+                        &turn,
+                        call_id.clone(),
+                        HashMap::from([(path.clone(), change)]),
+                        Some("Update project memory?".to_string()),
+                        None,
+                    )
+                    .await
+                    .await
+                    .unwrap_or_default()
+            },
+        )
+        .await;
+        match decision {
+            ReviewDecision::Approved
+            | ReviewDecision::ApprovedExecpolicyAmendment { .. }
+            | ReviewDecision::ApprovedForSession
+            | ReviewDecision::ApprovedAlwaysForPaths { .. } => {}
+            ReviewDecision::ApprovedHunks { .. }
+            | ReviewDecision::Denied
+            | ReviewDecision::Abort => {
+                return Err(FunctionCallError::RespondToModel(
+                    "the user declined the project memory update".to_string(),
+                ));
+            }
+        }
+
+        let write = async {
+            if let Some(parent) = path.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            tokio::fs::write(&path, &updated).await
+        };
+        write.await.map_err(|err| {
+            FunctionCallError::RespondToModel(format!("failed to write {}: {err}", path.display()))
+        })?;
+
+        Ok(ToolOutput::Function {
+            content: format!("Updated project memory in {}.", path.display()),
+            content_items: None,
+            success: Some(true),
+        })
+    }
+}
@@ -1,37 +1,67 @@
 pub mod apply_patch;
+mod background_jobs;
 pub(crate) mod collab;
 mod dynamic;
+mod edit_definition;
+mod fetch_url;
+mod git_commit;
 mod grep_files;
 mod list_dir;
 mod mcp;
 mod mcp_resource;
+mod memory;
+mod open_file;
 mod plan;
+mod pull_request;
 mod read_file;
+mod read_output;
 mod request_user_input;
+mod search_code;
 mod shell;
 mod test_sync;
 mod unified_exec;
 mod view_image;
+mod web_search;
 
 pub use plan::PLAN_TOOL;
 use serde::Deserialize;
+use serde::Serialize;
+use std::path::Path;
+use std::path::PathBuf;
 
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::config::types::FileApprovalPolicy;
 use crate::function_tool::FunctionCallError;
+use crate::protocol::AskForApproval;
+use crate::protocol::FileOperation;
+use crate::protocol::ReviewDecision;
+use crate::tools::sandboxing::with_cached_approval;
 pub use apply_patch::ApplyPatchHandler;
+pub use background_jobs::BackgroundJobHandler;
 pub use collab::CollabHandler;
 pub use dynamic::DynamicToolHandler;
+pub use edit_definition::EditDefinitionHandler;
+pub use fetch_url::FetchUrlHandler;
+pub use git_commit::GitCommitHandler;
 pub use grep_files::GrepFilesHandler;
 pub use list_dir::ListDirHandler;
 pub use mcp::McpHandler;
 pub use mcp_resource::McpResourceHandler;
+pub use memory::MemoryHandler;
+pub use open_file::OpenFileHandler;
 pub use plan::PlanHandler;
+pub use pull_request::PullRequestHandler;
 pub use read_file::ReadFileHandler;
+pub use read_output::ReadOutputHandler;
 pub use request_user_input::RequestUserInputHandler;
+pub use search_code::SearchCodeHandler;
 pub use shell::ShellCommandHandler;
 pub use shell::ShellHandler;
 pub use test_sync::TestSyncHandler;
 pub use unified_exec::UnifiedExecHandler;
 pub use view_image::ViewImageHandler;
+pub use web_search::WebSearchHandler;
 
 fn parse_arguments<T>(arguments: &str) -> Result<T, FunctionCallError>
 where
@@ -41,3 +71,185 @@ where
         FunctionCallError::RespondToModel(format!("failed to parse function arguments: {err}"))
     })
 }
+
+/// Session approval cache key for a read outside the working directory.
+#[derive(Serialize)]
+struct ReadApprovalKey {
+    read: PathBuf,
+}
+
+/// What `file_approvals.read` requires before reading `path`.
+#[derive(Debug, PartialEq)]
+enum ReadCheck {
+    Allowed,
+    NeedsApproval,
+    Rejected(String),
+}
+
+/// Decides a read of `path` under `policy`. Both paths are expected to be
+/// canonical already.
+fn check_read(
+    policy: FileApprovalPolicy,
+    approval_policy: AskForApproval,
+    path: &Path,
+    cwd: &Path,
+) -> ReadCheck {
+    if path.starts_with(cwd) {
+        return ReadCheck::Allowed;
+    }
+    let display = path.display();
+    match policy {
+        FileApprovalPolicy::Default | FileApprovalPolicy::Auto => ReadCheck::Allowed,
+        FileApprovalPolicy::Deny => ReadCheck::Rejected(format!(
+            "reading {display} outside the workspace is denied by file_approvals"
+        )),
+        FileApprovalPolicy::Ask if approval_policy == AskForApproval::Never => {
+            ReadCheck::Rejected(format!(
+                "reading {display} outside the workspace requires approval, but approval_policy is never"
+            ))
+        }
+        FileApprovalPolicy::Ask => ReadCheck::NeedsApproval,
+    }
+}
+
+/// Applies `file_approvals.read` when a read tool targets `path` outside the
+/// turn's working directory. Symlinks are resolved before the check.
+async fn authorize_read(
+    session: &Session,
+    turn: &TurnContext,
+    call_id: &str,
+    tool_name: &str,
+    path: &Path,
+) -> Result<(), FunctionCallError> {
+    let policy = turn.client.config().file_approvals.read;
+    if matches!(
+        policy,
+        FileApprovalPolicy::Default | FileApprovalPolicy::Auto
+    ) {
+        return Ok(());
+    }
+    let path = tokio::fs::canonicalize(path)
+        .await
+        .unwrap_or_else(|_| path.to_path_buf());
+    let cwd = tokio::fs::canonicalize(&turn.cwd)
+        .await
+        .unwrap_or_else(|_| turn.cwd.clone());
+    match check_read(policy, turn.approval_policy, &path, &cwd) {
+        ReadCheck::Allowed => return Ok(()),
+        ReadCheck::Rejected(reason) => return Err(FunctionCallError::RespondToModel(reason)),
+        ReadCheck::NeedsApproval => {}
+    }
+
+    let display = path.display().to_string();
+    let decision = with_cached_approval(
+        &session.services,
+        tool_name,
+        vec![ReadApprovalKey { read: path.clone() }],
+        || async {
+            session
+                .request_command_approval_with_file_operation(
+                    turn,
+                    call_id.to_string(),
+                    vec![tool_name.to_string(), display.clone()],
+                    turn.cwd.clone(),
+                    Some(format!("Read {display} outside the workspace?")),
+                    None,
+                    Some(FileOperation::Read),
+                )
+                .await
+        },
+    )
+    .await;
+    match decision {
+        ReviewDecision::Approved
+        | ReviewDecision::ApprovedExecpolicyAmendment { .. }
+        | ReviewDecision::ApprovedForSession
+        | ReviewDecision::ApprovedAlwaysForPaths { .. } => Ok(()),
+        ReviewDecision::ApprovedHunks { .. } | ReviewDecision::Denied | ReviewDecision::Abort => {
+            Err(FunctionCallError::RespondToModel(format!(
+                "the user declined reading {display}"
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn reads_inside_the_working_directory_are_always_allowed() {
+        let cwd = Path::new("/work/project");
+        for policy in [
+            FileApprovalPolicy::Default,
+            FileApprovalPolicy::Auto,
+            FileApprovalPolicy::Ask,
+            FileApprovalPolicy::Deny,
+        ] {
+            assert_eq!(
+                check_read(
+                    policy,
+                    AskForApproval::Never,
+                    Path::new("/work/project/src/lib.rs"),
+                    cwd
+                ),
+                ReadCheck::Allowed,
+                "{policy:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn reads_outside_the_working_directory_follow_the_read_policy() {
+        let cwd = Path::new("/work/project");
+        let outside = Path::new("/work/project-secrets/key.pem");
+
+        assert_eq!(
+            check_read(
+                FileApprovalPolicy::Default,
+                AskForApproval::OnRequest,
+                outside,
+                cwd
+            ),
+            ReadCheck::Allowed
+        );
+        assert_eq!(
+            check_read(
+                FileApprovalPolicy::Auto,
+                AskForApproval::Never,
+                outside,
+                cwd
+            ),
+            ReadCheck::Allowed
+        );
+        assert_eq!(
+            check_read(
+                FileApprovalPolicy::Ask,
+                AskForApproval::OnRequest,
+                outside,
+                cwd
+            ),
+            ReadCheck::NeedsApproval
+        );
+        assert_eq!(
+            check_read(FileApprovalPolicy::Ask, AskForApproval::Never, outside, cwd),
+            ReadCheck::Rejected(
+                "reading /work/project-secrets/key.pem outside the workspace requires approval, but approval_policy is never"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            check_read(
+                FileApprovalPolicy::Deny,
+                AskForApproval::OnRequest,
+                outside,
+                cwd
+            ),
+            ReadCheck::Rejected(
+                "reading /work/project-secrets/key.pem outside the workspace is denied by file_approvals"
+                    .to_string()
+            )
+        );
+    }
+}
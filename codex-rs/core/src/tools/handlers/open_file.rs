@@ -0,0 +1,163 @@
+use std::path::Path;
+use std::process::Stdio;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use tokio::fs;
+use tracing::warn;
+
+use crate::function_tool::FunctionCallError;
+use crate::protocol::EventMsg;
+use crate::protocol::OpenFileRequestEvent;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub struct OpenFileHandler;
+
+#[derive(Deserialize)]
+struct OpenFileArgs {
+    path: String,
+    #[serde(default)]
+    line: Option<u32>,
+}
+
+#[async_trait]
+impl ToolHandler for OpenFileHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            payload,
+            call_id,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "open_file handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let args: OpenFileArgs = parse_arguments(&arguments)?;
+        let path = turn.resolve_path(Some(args.path));
+        let line = args.line.filter(|line| *line > 0);
+
+        let metadata = fs::metadata(&path).await.map_err(|error| {
+            FunctionCallError::RespondToModel(format!(
+                "unable to locate `{}`: {error}",
+                path.display()
+            ))
+        })?;
+        if !metadata.is_file() {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "`{}` is not a file",
+                path.display()
+            )));
+        }
+
+        let opened_by_command = turn
+            .client
+            .config()
+            .open_file_command
+            .as_deref()
+            .is_some_and(|command| spawn_open_command(command, &path, line));
+
+        session
+            .send_event(
+                turn.as_ref(),
+                EventMsg::OpenFileRequest(OpenFileRequestEvent {
+                    call_id,
+                    path: path.clone(),
+                    line,
+                    opened_by_command,
+                }),
+            )
+            .await;
+
+        let location = match line {
+            Some(line) => format!("{}:{line}", path.display()),
+            None => path.display().to_string(),
+        };
+        Ok(ToolOutput::Function {
+            content: format!("asked the user's editor to open {location}"),
+            content_items: None,
+            success: Some(true),
+        })
+    }
+}
+
+/// `command` with `{path}` and `{line}` filled in; `{line}` is 1 when the
+/// agent gave no line.
+fn open_command_argv(command: &[String], path: &Path, line: Option<u32>) -> Vec<String> {
+    let path = path.to_string_lossy();
+    let line = line.unwrap_or(1).to_string();
+    command
+        .iter()
+        .map(|arg| arg.replace("{path}", &path).replace("{line}", &line))
+        .collect()
+}
+
+/// Starts `open_file_command` without waiting for it, returning whether it
+/// could be spawned. A background task reaps the process once it exits.
+fn spawn_open_command(command: &[String], path: &Path, line: Option<u32>) -> bool {
+    let argv = open_command_argv(command, path, line);
+    let Some((program, args)) = argv.split_first() else {
+        return false;
+    };
+    // Keep the editor away from the TUI's terminal.
+    let spawned = tokio::process::Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    match spawned {
+        Ok(mut child) => {
+            let program = program.clone();
+            tokio::spawn(async move {
+                if let Err(err) = child.wait().await {
+                    warn!("failed to wait for open_file_command '{program}': {err}");
+                }
+            });
+            true
+        }
+        Err(err) => {
+            warn!("failed to spawn open_file_command '{program}': {err}");
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn fills_path_and_line_placeholders() {
+        let command = vec![
+            "code".to_string(),
+            "--goto".to_string(),
+            "{path}:{line}".to_string(),
+        ];
+        assert_eq!(
+            open_command_argv(&command, Path::new("/repo/src/lib.rs"), Some(42)),
+            vec!["code", "--goto", "/repo/src/lib.rs:42"]
+        );
+        assert_eq!(
+            open_command_argv(&command, Path::new("/repo/README.md"), None),
+            vec!["code", "--goto", "/repo/README.md:1"]
+        );
+    }
+}
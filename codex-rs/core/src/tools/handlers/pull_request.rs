@@ -0,0 +1,126 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::function_tool::FunctionCallError;
+use crate::protocol::AskForApproval;
+use crate::protocol::ReviewDecision;
+use crate::pull_request::PullRequestTarget;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub struct PullRequestHandler;
+
+#[derive(Deserialize)]
+struct PullRequestArgs {
+    title: String,
+    body: String,
+    #[serde(default)]
+    base: Option<String>,
+    #[serde(default)]
+    draft: bool,
+}
+
+#[async_trait]
+impl ToolHandler for PullRequestHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn is_mutating(&self, _invocation: &ToolInvocation) -> bool {
+        true
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            call_id,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "create_pull_request handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+        let PullRequestArgs {
+            title,
+            body,
+            base,
+            draft,
+        } = parse_arguments(&arguments)?;
+        let title = title.trim();
+        if title.is_empty() {
+            return Err(FunctionCallError::RespondToModel(
+                "title must not be empty".to_string(),
+            ));
+        }
+
+        let config = turn.client.config();
+        let target = PullRequestTarget::resolve(&turn.cwd, &config.pull_requests, base)
+            .await
+            .map_err(|err| FunctionCallError::RespondToModel(err.to_string()))?;
+        let noun = target.repo.forge.request_noun();
+
+        if turn.approval_policy == AskForApproval::Never {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "opening a {noun} requires approval, but approval_policy is never"
+            )));
+        }
+        // Pushing and opening the request publish the work, so each one is
+        // approved on its own.
+        let decision = session
+            .request_command_approval(
+                &turn,
+                call_id,
+                target.push_command(),
+                target.repo_root.clone(),
+                Some(format!(
+                    "Push {} and open a {noun} into {} on {}: {title}",
+                    target.branch, target.base, target.repo.path
+                )),
+                None,
+            )
+            .await;
+        match decision {
+            ReviewDecision::Approved
+            | ReviewDecision::ApprovedExecpolicyAmendment { .. }
+            | ReviewDecision::ApprovedForSession
+            | ReviewDecision::ApprovedAlwaysForPaths { .. } => {}
+            ReviewDecision::ApprovedHunks { .. }
+            | ReviewDecision::Denied
+            | ReviewDecision::Abort => {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "the user declined to open the {noun}"
+                )));
+            }
+        }
+
+        target
+            .push()
+            .await
+            .map_err(|err| FunctionCallError::RespondToModel(err.to_string()))?;
+        let url = target
+            .open(&config.pull_requests, title, &body, draft)
+            .await
+            .map_err(|err| {
+                FunctionCallError::RespondToModel(format!(
+                    "pushed {}, but opening the {noun} failed: {err}",
+                    target.branch
+                ))
+            })?;
+        Ok(ToolOutput::Function {
+            content: format!("Opened {noun} {url}"),
+            content_items: None,
+            success: Some(true),
+        })
+    }
+}
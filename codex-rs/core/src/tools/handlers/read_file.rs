@@ -1,4 +1,5 @@
 use std::collections::VecDeque;
+use std::path::Path;
 use std::path::PathBuf;
 
 use async_trait::async_trait;
@@ -6,9 +7,11 @@ use codex_utils_string::take_bytes_at_char_boundary;
 use serde::Deserialize;
 
 use crate::function_tool::FunctionCallError;
+use crate::syntax_outline::truncate_at_syntax_boundary;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
 use crate::tools::context::ToolPayload;
+use crate::tools::handlers::authorize_read;
 use crate::tools::handlers::parse_arguments;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
@@ -98,7 +101,14 @@ impl ToolHandler for ReadFileHandler {
     }
 
     async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
-        let ToolInvocation { payload, .. } = invocation;
+        let ToolInvocation {
+            session,
+            turn,
+            call_id,
+            tool_name,
+            payload,
+            ..
+        } = invocation;
 
         let arguments = match payload {
             ToolPayload::Function { arguments } => arguments,
@@ -137,9 +147,16 @@ impl ToolHandler for ReadFileHandler {
                 "file_path must be an absolute path".to_string(),
             ));
         }
+        authorize_read(&session, &turn, &call_id, &tool_name, &path).await?;
 
         let collected = match mode {
-            ReadMode::Slice => slice::read(&path, offset, limit).await?,
+            ReadMode::Slice => {
+                let mut collected = slice::read(&path, offset, limit).await?;
+                if collected.len() == limit {
+                    cut_at_syntax_boundary(&path, offset, limit, &mut collected).await;
+                }
+                collected
+            }
             ReadMode::Indentation => {
                 let indentation = indentation.unwrap_or_default();
                 indentation::read_block(&path, offset, limit, indentation).await?
@@ -153,6 +170,24 @@ impl ToolHandler for ReadFileHandler {
     }
 }
 
+/// When a slice fills the line budget and the file continues, end it on a
+/// definition boundary and append an outline of what was left out.
+async fn cut_at_syntax_boundary(
+    path: &Path,
+    offset: usize,
+    limit: usize,
+    collected: &mut Vec<String>,
+) {
+    let Ok(source) = tokio::fs::read_to_string(path).await else {
+        return;
+    };
+    let Some(truncation) = truncate_at_syntax_boundary(path, &source, offset, limit) else {
+        return;
+    };
+    collected.truncate(truncation.end_line + 1 - offset);
+    collected.extend(truncation.notice_lines());
+}
+
 mod slice {
     use crate::function_tool::FunctionCallError;
     use crate::tools::handlers::read_file::format_line;
@@ -567,6 +602,41 @@ third
         Ok(())
     }
 
+    #[tokio::test]
+    async fn full_slice_ends_on_a_definition_boundary() -> anyhow::Result<()> {
+        let mut temp = tempfile::Builder::new().suffix(".py").tempfile()?;
+        use std::io::Write as _;
+        write!(
+            temp,
+            "def first():
+    a = 1
+    b = 2
+    return a + b
+
+
+def second():
+    a = 1
+    return a
+"
+        )?;
+
+        let mut lines = read(temp.path(), 1, 8).await?;
+        cut_at_syntax_boundary(temp.path(), 1, 8, &mut lines).await;
+        assert_eq!(
+            lines,
+            vec![
+                "L1: def first():".to_string(),
+                "L2:     a = 1".to_string(),
+                "L3:     b = 2".to_string(),
+                "L4:     return a + b".to_string(),
+                "[truncated after L4; read from offset 5 to continue. Omitted definitions:]"
+                    .to_string(),
+                "L7-L9: def second():".to_string(),
+            ]
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn truncates_lines_longer_than_max_length() -> anyhow::Result<()> {
         let mut temp = NamedTempFile::new()?;
@@ -0,0 +1,97 @@
+use std::fmt::Write as _;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+use crate::truncate::truncate_text;
+
+pub struct ReadOutputHandler;
+
+/// Lines returned when the model gives no `end_line`.
+const DEFAULT_LINE_COUNT: usize = 200;
+
+#[derive(Deserialize)]
+struct ReadOutputArgs {
+    output_id: String,
+    #[serde(default = "default_start_line")]
+    start_line: usize,
+    #[serde(default)]
+    end_line: Option<usize>,
+}
+
+fn default_start_line() -> usize {
+    1
+}
+
+#[async_trait]
+impl ToolHandler for ReadOutputHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "read_output handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let ReadOutputArgs {
+            output_id,
+            start_line,
+            end_line,
+        } = parse_arguments(&arguments)?;
+        let start_line = start_line.max(1);
+        let end_line = end_line.unwrap_or(start_line.saturating_add(DEFAULT_LINE_COUNT - 1));
+        if end_line < start_line {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "end_line {end_line} is before start_line {start_line}"
+            )));
+        }
+
+        let page = session
+            .services
+            .saved_outputs
+            .lock()
+            .await
+            .read(&output_id, start_line, end_line)
+            .map_err(|err| FunctionCallError::RespondToModel(err.to_string()))?;
+
+        let mut content = match (page.lines.first(), page.lines.last()) {
+            (Some((first, _)), Some((last, _))) => format!(
+                "Lines {first}-{last} of {} from {output_id}:\n",
+                page.total_lines
+            ),
+            _ => format!(
+                "{output_id} has {} lines; none from {start_line}.\n",
+                page.total_lines
+            ),
+        };
+        for (number, line) in &page.lines {
+            let _ = writeln!(content, "{number}: {line}");
+        }
+
+        Ok(ToolOutput::Function {
+            content: truncate_text(&content, turn.truncation_policy),
+            content_items: None,
+            success: Some(true),
+        })
+    }
+}
@@ -0,0 +1,102 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::code_index::Embedder;
+use crate::function_tool::FunctionCallError;
+use crate::git_info::get_git_repo_root;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub struct SearchCodeHandler;
+
+const DEFAULT_LIMIT: usize = 8;
+const MAX_LIMIT: usize = 20;
+
+#[derive(Deserialize)]
+struct SearchCodeArgs {
+    query: String,
+    #[serde(default)]
+    limit: Option<usize>,
+}
+
+#[async_trait]
+impl ToolHandler for SearchCodeHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "search_code handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+        let SearchCodeArgs { query, limit } = parse_arguments(&arguments)?;
+        if query.trim().is_empty() {
+            return Err(FunctionCallError::RespondToModel(
+                "query must not be empty".to_string(),
+            ));
+        }
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+        let Some(repo_root) = get_git_repo_root(&turn.cwd) else {
+            return Err(FunctionCallError::RespondToModel(
+                "search_code only works inside a git repository".to_string(),
+            ));
+        };
+
+        let embedder = Embedder::new(&turn.client.config().code_index);
+        let search = session
+            .services
+            .code_index
+            .search(&embedder, &repo_root, &query, limit)
+            .await
+            .map_err(|err| FunctionCallError::RespondToModel(format!("search failed: {err}")))?;
+        let Some(hits) = search else {
+            return Err(FunctionCallError::RespondToModel(
+                "this repository has no code index; ask the user to run `codex index build`, or use grep_files or rg instead"
+                    .to_string(),
+            ));
+        };
+        if hits.is_empty() {
+            return Ok(ToolOutput::Function {
+                content: "No matching code found.".to_string(),
+                content_items: None,
+                success: Some(true),
+            });
+        }
+
+        let content = hits
+            .iter()
+            .map(|hit| {
+                format!(
+                    "{}:L{}-L{} (score {:.2})\n{}",
+                    hit.path.display(),
+                    hit.start_line,
+                    hit.end_line,
+                    hit.score,
+                    hit.snippet
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        Ok(ToolOutput::Function {
+            content,
+            content_items: None,
+            success: Some(true),
+        })
+    }
+}
@@ -4,6 +4,8 @@ use codex_protocol::models::ShellToolCallParams;
 use std::sync::Arc;
 
 use crate::codex::TurnContext;
+use crate::credentials::apply_credential_policy;
+use crate::exec::ExecLimits;
 use crate::exec::ExecParams;
 use crate::exec_env::create_env;
 use crate::exec_policy::ExecApprovalRequest;
@@ -24,6 +26,7 @@ use crate::tools::registry::ToolKind;
 use crate::tools::runtimes::shell::ShellRequest;
 use crate::tools::runtimes::shell::ShellRuntime;
 use crate::tools::sandboxing::ToolCtx;
+use crate::trash::stash_command_deletions;
 
 pub struct ShellHandler;
 
@@ -42,15 +45,22 @@ struct RunExecLikeArgs {
 
 impl ShellHandler {
     fn to_exec_params(params: &ShellToolCallParams, turn_context: &TurnContext) -> ExecParams {
+        let requested = ExecLimits {
+            max_output_bytes: params.max_output_bytes,
+            cpu_seconds: params.cpu_seconds,
+            memory_mb: params.memory_mb,
+        };
+        let (timeout_ms, limits) = exec_limits(params.timeout_ms, requested, turn_context);
         ExecParams {
             command: params.command.clone(),
             cwd: turn_context.resolve_path(params.workdir.clone()),
-            expiration: params.timeout_ms.into(),
+            expiration: timeout_ms.into(),
             env: create_env(&turn_context.shell_environment_policy),
             sandbox_permissions: params.sandbox_permissions.unwrap_or_default(),
             windows_sandbox_level: turn_context.windows_sandbox_level,
             justification: params.justification.clone(),
             arg0: None,
+            limits,
         }
     }
 }
@@ -68,20 +78,41 @@ impl ShellCommandHandler {
     ) -> ExecParams {
         let shell = session.user_shell();
         let command = Self::base_command(shell.as_ref(), &params.command, params.login);
+        let requested = ExecLimits {
+            max_output_bytes: params.max_output_bytes,
+            cpu_seconds: params.cpu_seconds,
+            memory_mb: params.memory_mb,
+        };
+        let (timeout_ms, limits) = exec_limits(params.timeout_ms, requested, turn_context);
 
         ExecParams {
             command,
             cwd: turn_context.resolve_path(params.workdir.clone()),
-            expiration: params.timeout_ms.into(),
+            expiration: timeout_ms.into(),
             env: create_env(&turn_context.shell_environment_policy),
             sandbox_permissions: params.sandbox_permissions.unwrap_or_default(),
             windows_sandbox_level: turn_context.windows_sandbox_level,
             justification: params.justification.clone(),
             arg0: None,
+            limits,
         }
     }
 }
 
+/// The timeout and limits for a call, from what it asked for and the
+/// configured `[exec_limits]`.
+fn exec_limits(
+    timeout_ms: Option<u64>,
+    requested: ExecLimits,
+    turn_context: &TurnContext,
+) -> (Option<u64>, ExecLimits) {
+    let configured = turn_context.client.config().exec_limits;
+    (
+        timeout_ms.or(configured.timeout_ms),
+        configured.limits.tightened_by(requested),
+    )
+}
+
 #[async_trait]
 impl ToolHandler for ShellHandler {
     fn kind(&self) -> ToolKind {
@@ -238,6 +269,15 @@ impl ShellHandler {
         if !dependency_env.is_empty() {
             exec_params.env.extend(dependency_env);
         }
+        apply_credential_policy(
+            session.as_ref(),
+            turn.as_ref(),
+            &call_id,
+            tool_name.as_str(),
+            &exec_params.command,
+            &mut exec_params.env,
+        )
+        .await;
 
         // Approval policy guard for explicit escalation in non-OnRequest modes.
         if exec_params
@@ -280,6 +320,7 @@ impl ShellHandler {
         let event_ctx = ToolEventCtx::new(session.as_ref(), turn.as_ref(), &call_id, None);
         emitter.begin(event_ctx).await;
 
+        let config = turn.client.config();
         let exec_approval_requirement = session
             .services
             .exec_policy
@@ -290,6 +331,8 @@ impl ShellHandler {
                 sandbox_policy: &turn.sandbox_policy,
                 sandbox_permissions: exec_params.sandbox_permissions,
                 prefix_rule,
+                safe_commands: &config.approval.safe_commands,
+                approval_rules: &config.approval.rules,
             })
             .await;
 
@@ -300,6 +343,7 @@ impl ShellHandler {
             env: exec_params.env.clone(),
             sandbox_permissions: exec_params.sandbox_permissions,
             justification: exec_params.justification.clone(),
+            limits: exec_params.limits,
             exec_approval_requirement,
         };
         let mut orchestrator = ToolOrchestrator::new();
@@ -313,6 +357,7 @@ impl ShellHandler {
         let out = orchestrator
             .run(&mut runtime, &req, &tool_ctx, &turn, turn.approval_policy)
             .await;
+        stash_command_deletions(session.as_ref(), &turn.cwd).await;
         let event_ctx = ToolEventCtx::new(session.as_ref(), turn.as_ref(), &call_id, None);
         let content = emitter.finish(event_ctx, out).await?;
         Ok(ToolOutput::Function {
@@ -413,6 +458,9 @@ mod tests {
             sandbox_permissions: Some(sandbox_permissions),
             prefix_rule: None,
             justification: justification.clone(),
+            max_output_bytes: None,
+            cpu_seconds: None,
+            memory_mb: None,
         };
 
         let exec_params = ShellCommandHandler::to_exec_params(&params, &session, &turn_context);
@@ -12,11 +12,14 @@ use crate::tools::handlers::apply_patch::intercept_apply_patch;
 use crate::tools::handlers::parse_arguments;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
+use crate::trash::stash_command_deletions;
+use crate::truncate::TruncationPolicy;
 use crate::unified_exec::ExecCommandRequest;
 use crate::unified_exec::UnifiedExecContext;
 use crate::unified_exec::UnifiedExecProcessManager;
 use crate::unified_exec::UnifiedExecResponse;
 use crate::unified_exec::WriteStdinRequest;
+use crate::unified_exec::resolve_max_tokens;
 use async_trait::async_trait;
 use serde::Deserialize;
 use std::path::PathBuf;
@@ -124,7 +127,7 @@ impl ToolHandler for UnifiedExecHandler {
         let manager: &UnifiedExecProcessManager = &session.services.unified_exec_manager;
         let context = UnifiedExecContext::new(session.clone(), turn.clone(), call_id.clone());
 
-        let response = match tool_name.as_str() {
+        let (mut response, max_output_tokens) = match tool_name.as_str() {
             "exec_command" => {
                 let args: ExecCommandArgs = parse_arguments(&arguments)?;
                 let process_id = manager.allocate_process_id().await;
@@ -183,7 +186,7 @@ impl ToolHandler for UnifiedExecHandler {
                     return Ok(output);
                 }
 
-                manager
+                let response = manager
                     .exec_command(
                         ExecCommandRequest {
                             command,
@@ -201,7 +204,8 @@ impl ToolHandler for UnifiedExecHandler {
                     .await
                     .map_err(|err| {
                         FunctionCallError::RespondToModel(format!("exec_command failed: {err:?}"))
-                    })?
+                    })?;
+                (response, max_output_tokens)
             }
             "write_stdin" => {
                 let args: WriteStdinArgs = parse_arguments(&arguments)?;
@@ -226,7 +230,7 @@ impl ToolHandler for UnifiedExecHandler {
                     .send_event(turn.as_ref(), EventMsg::TerminalInteraction(interaction))
                     .await;
 
-                response
+                (response, args.max_output_tokens)
             }
             other => {
                 return Err(FunctionCallError::RespondToModel(format!(
@@ -235,6 +239,20 @@ impl ToolHandler for UnifiedExecHandler {
             }
         };
 
+        if response.exit_code.is_some() {
+            stash_command_deletions(session.as_ref(), &turn.cwd).await;
+        }
+
+        if let Some(paged_output) = session
+            .page_output(
+                &String::from_utf8_lossy(&response.raw_output),
+                TruncationPolicy::Tokens(resolve_max_tokens(max_output_tokens)),
+            )
+            .await
+        {
+            response.output = paged_output;
+        }
+
         let content = format_response(&response);
 
         Ok(ToolOutput::Function {
@@ -283,6 +301,11 @@ fn format_response(response: &UnifiedExecResponse) -> String {
     sections.push("Output:".to_string());
     sections.push(response.output.clone());
 
+    if let Some(previous_run_diff) = &response.previous_run_diff {
+        sections.push("Changes since previous run:".to_string());
+        sections.push(previous_run_diff.clone());
+    }
+
     sections.join("\n")
 }
 
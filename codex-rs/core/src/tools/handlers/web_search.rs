@@ -0,0 +1,92 @@
+use async_trait::async_trait;
+use codex_protocol::items::TurnItem;
+use codex_protocol::items::WebSearchItem;
+use codex_protocol::models::WebSearchAction;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+use crate::web_search::search;
+
+/// Serves `web_search` from the backend in `[web_search_backend]` when it is
+/// not the model provider's native search.
+pub struct WebSearchHandler;
+
+const DEFAULT_MAX_RESULTS: usize = 5;
+const MAX_RESULTS: usize = 10;
+
+#[derive(Deserialize)]
+struct WebSearchArgs {
+    query: String,
+    #[serde(default)]
+    max_results: Option<usize>,
+}
+
+#[async_trait]
+impl ToolHandler for WebSearchHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            call_id,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "web_search handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+        let WebSearchArgs { query, max_results } = parse_arguments(&arguments)?;
+        let query = query.trim().to_string();
+        if query.is_empty() {
+            return Err(FunctionCallError::RespondToModel(
+                "query must not be empty".to_string(),
+            ));
+        }
+        let max_results = max_results
+            .unwrap_or(DEFAULT_MAX_RESULTS)
+            .clamp(1, MAX_RESULTS);
+
+        // Shown like the provider's own search so clients render it the same.
+        let item = TurnItem::WebSearch(WebSearchItem {
+            id: call_id,
+            query: query.clone(),
+            action: WebSearchAction::Search {
+                query: Some(query.clone()),
+                queries: None,
+            },
+        });
+        session.emit_turn_item_started(&turn, &item).await;
+        let backend = turn.client.config().web_search_backend.clone();
+        let results = search(&backend, &query, max_results).await;
+        session.emit_turn_item_completed(&turn, item).await;
+
+        let results = results.map_err(|err| {
+            FunctionCallError::RespondToModel(format!("web search failed: {err}"))
+        })?;
+        let content = json!({
+            "query": query,
+            "results": results,
+        });
+        Ok(ToolOutput::Function {
+            content: content.to_string(),
+            content_items: None,
+            success: Some(true),
+        })
+    }
+}
@@ -2,6 +2,7 @@ pub mod context;
 pub mod events;
 pub(crate) mod handlers;
 pub mod orchestrator;
+pub(crate) mod output_diff;
 pub mod parallel;
 pub mod registry;
 pub mod router;
@@ -24,9 +25,14 @@ pub(crate) const TELEMETRY_PREVIEW_TRUNCATION_NOTICE: &str =
 
 /// Format the combined exec output for sending back to the model.
 /// Includes exit code and duration metadata; truncates large bodies safely.
+/// `paged_output` replaces the truncated body when the full output was saved
+/// for `read_output`. `previous_run_diff` is the change since this command
+/// last ran, if it has.
 pub fn format_exec_output_for_model_structured(
     exec_output: &ExecToolCallOutput,
     truncation_policy: TruncationPolicy,
+    paged_output: Option<&str>,
+    previous_run_diff: Option<&str>,
 ) -> String {
     let ExecToolCallOutput {
         exit_code,
@@ -44,12 +50,17 @@ pub fn format_exec_output_for_model_structured(
     struct ExecOutput<'a> {
         output: &'a str,
         metadata: ExecMetadata,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        previous_run_diff: Option<String>,
     }
 
     // round to 1 decimal place
     let duration_seconds = ((duration.as_secs_f32()) * 10.0).round() / 10.0;
 
-    let formatted_output = format_exec_output_str(exec_output, truncation_policy);
+    let formatted_output = match paged_output {
+        Some(paged_output) => paged_output.to_string(),
+        None => format_exec_output_str(exec_output, truncation_policy),
+    };
 
     let payload = ExecOutput {
         output: &formatted_output,
@@ -57,6 +68,8 @@ pub fn format_exec_output_for_model_structured(
             exit_code: *exit_code,
             duration_seconds,
         },
+        previous_run_diff: previous_run_diff
+            .map(|diff| output_diff::describe_for_model(diff, truncation_policy)),
     };
 
     #[expect(clippy::expect_used)]
@@ -66,6 +79,8 @@ pub fn format_exec_output_for_model_structured(
 pub fn format_exec_output_for_model_freeform(
     exec_output: &ExecToolCallOutput,
     truncation_policy: TruncationPolicy,
+    paged_output: Option<&str>,
+    previous_run_diff: Option<&str>,
 ) -> String {
     // round to 1 decimal place
     let duration_seconds = ((exec_output.duration.as_secs_f32()) * 10.0).round() / 10.0;
@@ -74,18 +89,26 @@ pub fn format_exec_output_for_model_freeform(
 
     let total_lines = content.lines().count();
 
-    let formatted_output = truncate_text(&content, truncation_policy);
+    let formatted_output = match paged_output {
+        Some(paged_output) => paged_output.to_string(),
+        None => truncate_text(&content, truncation_policy),
+    };
 
     let mut sections = Vec::new();
 
     sections.push(format!("Exit code: {}", exec_output.exit_code));
     sections.push(format!("Wall time: {duration_seconds} seconds"));
-    if total_lines != formatted_output.lines().count() {
+    // Paged output reports its own line count.
+    if paged_output.is_none() && total_lines != formatted_output.lines().count() {
         sections.push(format!("Total output lines: {total_lines}"));
     }
 
     sections.push("Output:".to_string());
     sections.push(formatted_output);
+    if let Some(diff) = previous_run_diff {
+        sections.push("Changes since previous run:".to_string());
+        sections.push(output_diff::describe_for_model(diff, truncation_policy));
+    }
 
     sections.join("\n")
 }
@@ -17,9 +17,15 @@ use crate::tools::sandboxing::ToolCtx;
 use crate::tools::sandboxing::ToolError;
 use crate::tools::sandboxing::ToolRuntime;
 use crate::tools::sandboxing::default_exec_approval_requirement;
+use crate::write_protected::ExistingPaths;
+use crate::write_protected::created_without_approval;
 use codex_otel::ToolDecisionSource;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::ReviewDecision;
+use codex_protocol::protocol::SandboxPolicy;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use std::path::PathBuf;
+use std::sync::Arc;
 
 pub(crate) struct ToolOrchestrator {
     sandbox: SandboxManager,
@@ -68,6 +74,7 @@ impl ToolOrchestrator {
                     turn: turn_ctx,
                     call_id: &tool_ctx.call_id,
                     retry_reason: reason,
+                    skip_session_cache: false,
                 };
                 let decision = tool.start_approval_async(req, approval_ctx).await;
 
@@ -79,7 +86,9 @@ impl ToolOrchestrator {
                     }
                     ReviewDecision::Approved
                     | ReviewDecision::ApprovedExecpolicyAmendment { .. }
-                    | ReviewDecision::ApprovedForSession => {}
+                    | ReviewDecision::ApprovedForSession
+                    | ReviewDecision::ApprovedAlwaysForPaths { .. }
+                    | ReviewDecision::ApprovedHunks { .. } => {}
                 }
                 already_approved = true;
             }
@@ -95,18 +104,83 @@ impl ToolOrchestrator {
             ),
         };
 
+        let config = turn_ctx.client.config();
+        let guards_write_protected =
+            initial_sandbox != crate::exec::SandboxType::None && !config.write_protected.is_empty();
+        let write_protected = if guards_write_protected {
+            let existing = existing_write_protected(tool_ctx, turn_ctx).await;
+            // The sandbox cannot keep paths the walk missed read-only, so
+            // running anyway is up to the user.
+            if existing.truncated {
+                if approval_policy == AskForApproval::Never {
+                    return Err(ToolError::Rejected(TRUNCATED_WALK_REASON.to_string()));
+                }
+                let approval_ctx = ApprovalCtx {
+                    session: tool_ctx.session,
+                    turn: turn_ctx,
+                    call_id: &tool_ctx.call_id,
+                    retry_reason: Some(TRUNCATED_WALK_REASON.to_string()),
+                    skip_session_cache: true,
+                };
+                let decision = tool.start_approval_async(req, approval_ctx).await;
+                otel.tool_decision(otel_tn, otel_ci, &decision, otel_user.clone());
+                if matches!(decision, ReviewDecision::Denied | ReviewDecision::Abort) {
+                    return Err(ToolError::Rejected("rejected by user".to_string()));
+                }
+            }
+            existing.paths
+        } else {
+            Vec::new()
+        };
+        // Approved writes to protected paths stay in the sandbox: the paths
+        // are left writable and their directories added as writable roots.
+        let approved_writes = if already_approved {
+            tool.approved_writes(req)
+        } else {
+            Vec::new()
+        };
+        let read_only_paths: Vec<PathBuf> = write_protected
+            .iter()
+            .filter(|path| !approved_writes.iter().any(|write| write.starts_with(path)))
+            .cloned()
+            .collect();
+        let policy = with_writable_parents(&turn_ctx.sandbox_policy, &approved_writes);
+        let network_proxy = match config.network_proxy {
+            Some(proxy)
+                if initial_sandbox != crate::exec::SandboxType::None
+                    && !policy.has_full_network_access() =>
+            {
+                tool_ctx
+                    .session
+                    .services
+                    .network_proxy
+                    .ensure_running(proxy)
+                    .await
+            }
+            _ => None,
+        };
+
         // Platform-specific flag gating is handled by SandboxManager::select_initial
         // via crate::safety::get_platform_sandbox(..).
         let initial_attempt = SandboxAttempt {
             sandbox: initial_sandbox,
-            policy: &turn_ctx.sandbox_policy,
+            policy: &policy,
             manager: &self.sandbox,
             sandbox_cwd: &turn_ctx.cwd,
             codex_linux_sandbox_exe: turn_ctx.codex_linux_sandbox_exe.as_ref(),
             windows_sandbox_level: turn_ctx.windows_sandbox_level,
+            read_only_paths: &read_only_paths,
+            network_proxy,
+            linux_sandbox: &config.linux_sandbox,
         };
 
-        match tool.run(req, &initial_attempt, tool_ctx).await {
+        let initial_result = tool.run(req, &initial_attempt, tool_ctx).await;
+        if guards_write_protected {
+            let after = existing_write_protected(tool_ctx, turn_ctx).await;
+            check_created_write_protected(&write_protected, &after.paths, &approved_writes)?;
+        }
+
+        match initial_result {
             Ok(out) => {
                 // We have a successful initial result
                 Ok(out)
@@ -125,14 +199,39 @@ impl ToolOrchestrator {
                     })));
                 }
 
+                // Without the sandbox nothing keeps protected paths read-only,
+                // so lifting that needs the user's answer for this call, not
+                // an earlier approval or one cached for the session.
+                let protected_before = if config.write_protected.is_empty() {
+                    ExistingPaths::default()
+                } else {
+                    existing_write_protected(tool_ctx, turn_ctx).await
+                };
+                let lifted: Vec<PathBuf> = protected_before
+                    .paths
+                    .iter()
+                    .filter(|path| !approved_writes.iter().any(|write| write.starts_with(path)))
+                    .cloned()
+                    .collect();
+                let lifts_write_protection = !lifted.is_empty() || protected_before.truncated;
+
                 // Ask for approval before retrying without sandbox.
-                if !tool.should_bypass_approval(approval_policy, already_approved) {
-                    let reason_msg = build_denial_reason_from_output(output.as_ref());
+                if lifts_write_protection
+                    || !tool.should_bypass_approval(approval_policy, already_approved)
+                {
+                    let mut reason_msg = build_denial_reason_from_output(output.as_ref());
+                    if protected_before.truncated {
+                        reason_msg.push(' ');
+                        reason_msg.push_str(TRUNCATED_WALK_REASON);
+                    } else if lifts_write_protection {
+                        reason_msg.push_str(&lifted_write_protection_note(&lifted));
+                    }
                     let approval_ctx = ApprovalCtx {
                         session: tool_ctx.session,
                         turn: turn_ctx,
                         call_id: &tool_ctx.call_id,
                         retry_reason: Some(reason_msg),
+                        skip_session_cache: lifts_write_protection,
                     };
 
                     let decision = tool.start_approval_async(req, approval_ctx).await;
@@ -144,7 +243,9 @@ impl ToolOrchestrator {
                         }
                         ReviewDecision::Approved
                         | ReviewDecision::ApprovedExecpolicyAmendment { .. }
-                        | ReviewDecision::ApprovedForSession => {}
+                        | ReviewDecision::ApprovedForSession
+                        | ReviewDecision::ApprovedAlwaysForPaths { .. }
+                        | ReviewDecision::ApprovedHunks { .. } => {}
                     }
                 }
 
@@ -155,16 +256,103 @@ impl ToolOrchestrator {
                     sandbox_cwd: &turn_ctx.cwd,
                     codex_linux_sandbox_exe: None,
                     windows_sandbox_level: turn_ctx.windows_sandbox_level,
+                    read_only_paths: &[],
+                    network_proxy: None,
+                    linux_sandbox: &config.linux_sandbox,
                 };
 
                 // Second attempt.
-                (*tool).run(req, &escalated_attempt, tool_ctx).await
+                let escalated_result = (*tool).run(req, &escalated_attempt, tool_ctx).await;
+                if !config.write_protected.is_empty() {
+                    let after = existing_write_protected(tool_ctx, turn_ctx).await;
+                    check_created_write_protected(
+                        &protected_before.paths,
+                        &after.paths,
+                        &approved_writes,
+                    )?;
+                }
+                escalated_result
             }
             other => other,
         }
     }
 }
 
+/// Shown when the walk for `write_protected` paths stopped before finishing.
+const TRUNCATED_WALK_REASON: &str = "The working directory has too many files to find every write_protected path, so the sandbox may leave some of them writable.";
+
+/// The `write_protected` paths that exist now, from the session's cached walk.
+async fn existing_write_protected(
+    tool_ctx: &ToolCtx<'_>,
+    turn_ctx: &crate::codex::TurnContext,
+) -> ExistingPaths {
+    let patterns = turn_ctx.client.config().write_protected.clone();
+    let cwd = turn_ctx.cwd.clone();
+    let paths = Arc::clone(&tool_ctx.session.services.write_protected);
+    tokio::task::spawn_blocking(move || {
+        paths
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .existing(&patterns, &cwd)
+    })
+    .await
+    .unwrap_or_default()
+}
+
+/// Fails the call when it created protected paths it was not approved to
+/// write. They are left in place: the command may not be the only thing that
+/// wrote them.
+fn check_created_write_protected(
+    before: &[PathBuf],
+    after: &[PathBuf],
+    approved_writes: &[PathBuf],
+) -> Result<(), ToolError> {
+    let created = created_without_approval(before, after, approved_writes);
+    if created.is_empty() {
+        return Ok(());
+    }
+    let created = created
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    Err(ToolError::Rejected(format!(
+        "the command created write-protected {created} without approval; it was left in place for the user to review"
+    )))
+}
+
+/// Appended to the escalation prompt when running without the sandbox would
+/// make existing protected paths writable.
+fn lifted_write_protection_note(paths: &[PathBuf]) -> String {
+    const SHOWN: usize = 3;
+    let mut names = paths
+        .iter()
+        .take(SHOWN)
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    if paths.len() > SHOWN {
+        names.push_str(&format!(" and {} more", paths.len() - SHOWN));
+    }
+    format!(" This also lifts write_protected for {names}.")
+}
+
+/// `policy` with the directories of `paths` added as writable roots, so an
+/// approved write to a protected path works inside the sandbox.
+fn with_writable_parents(policy: &SandboxPolicy, paths: &[PathBuf]) -> SandboxPolicy {
+    let mut policy = policy.clone();
+    if let SandboxPolicy::WorkspaceWrite { writable_roots, .. } = &mut policy {
+        for parent in paths.iter().filter_map(|path| path.parent()) {
+            if let Ok(parent) = AbsolutePathBuf::from_absolute_path(parent)
+                && !writable_roots.contains(&parent)
+            {
+                writable_roots.push(parent);
+            }
+        }
+    }
+    policy
+}
+
 fn build_denial_reason_from_output(_output: &ExecToolCallOutput) -> String {
     // Keep approval reason terse and stable for UX/tests, but accept the
     // output so we can evolve heuristics later without touching call sites.
@@ -0,0 +1,80 @@
+//! Diffs a command's output against its previous run in the same session, so
+//! re-running e.g. a test suite makes it obvious whether anything changed.
+
+use similar::TextDiff;
+
+use crate::truncate::TruncationPolicy;
+use crate::truncate::formatted_truncate_text;
+
+/// Lines of unchanged output kept around each change.
+const CONTEXT_LINES: usize = 2;
+
+/// Upper bound on the diff lines carried in `ExecCommandEnd`.
+const MAX_DIFF_LINES: usize = 200;
+
+/// Unified diff (hunks only, no file headers) from `previous` to `current`.
+/// Empty when the output is unchanged.
+pub(crate) fn diff_command_output(previous: &str, current: &str) -> String {
+    if previous == current {
+        return String::new();
+    }
+    let diff = TextDiff::from_lines(previous, current)
+        .unified_diff()
+        .context_radius(CONTEXT_LINES)
+        .missing_newline_hint(false)
+        .to_string();
+    let total_lines = diff.lines().count();
+    if total_lines <= MAX_DIFF_LINES {
+        return diff;
+    }
+    let mut truncated: String = diff
+        .lines()
+        .take(MAX_DIFF_LINES)
+        .flat_map(|line| [line, "\n"])
+        .collect();
+    truncated.push_str(&format!(
+        "[... {} more diff lines ...]\n",
+        total_lines - MAX_DIFF_LINES
+    ));
+    truncated
+}
+
+/// How the previous-run diff is described to the model.
+pub(crate) fn describe_for_model(diff: &str, truncation_policy: TruncationPolicy) -> String {
+    if diff.is_empty() {
+        "Output unchanged since the previous run of this command.".to_string()
+    } else {
+        formatted_truncate_text(diff, truncation_policy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn diff_shows_changed_lines_with_context() {
+        let previous =
+            "running 3 tests\ntest a ... ok\ntest b ... FAILED\ntest c ... ok\n\nfailures: 1\n";
+        let current =
+            "running 3 tests\ntest a ... ok\ntest b ... ok\ntest c ... ok\n\nfailures: 0\n";
+
+        assert_eq!(
+            diff_command_output(previous, current),
+            "@@ -1,6 +1,6 @@\n running 3 tests\n test a ... ok\n-test b ... FAILED\n+test b ... ok\n test c ... ok\n \n-failures: 1\n+failures: 0\n"
+        );
+        assert_eq!(diff_command_output(current, current), "");
+    }
+
+    #[test]
+    fn long_diffs_are_capped() {
+        let previous: String = (0..500).map(|i| format!("old {i}\n")).collect();
+        let current: String = (0..500).map(|i| format!("new {i}\n")).collect();
+
+        let diff = diff_command_output(&previous, &current);
+
+        assert_eq!(diff.lines().count(), MAX_DIFF_LINES + 1);
+        assert!(diff.ends_with("[... 801 more diff lines ...]\n"));
+    }
+}
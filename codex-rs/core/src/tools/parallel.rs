@@ -51,6 +51,12 @@ impl ToolCallRuntime {
         call: ToolCall,
         cancellation_token: CancellationToken,
     ) -> impl std::future::Future<Output = Result<ResponseInputItem, CodexErr>> {
+        // A tool call the budget refuses is answered without running; the
+        // next sampling request is refused as well, which ends the turn.
+        let budget_check = match &self.turn_context.client.config().run_budget {
+            Some(budget) => budget.check_tool_call(self.turn_context.is_primary_thread()),
+            None => Ok(()),
+        };
         let supports_parallel = self.router.tool_supports_parallel(&call.tool_name);
 
         let router = Arc::clone(&self.router);
@@ -68,8 +74,9 @@ impl ToolCallRuntime {
             aborted = false,
         );
 
-        let handle: AbortOnDropHandle<Result<ResponseInputItem, FunctionCallError>> =
-            AbortOnDropHandle::new(tokio::spawn(async move {
+        let handle = match budget_check {
+            Err(err) => Err(Self::text_response(&call, err.to_string())),
+            Ok(()) => Ok(AbortOnDropHandle::new(tokio::spawn(async move {
                 tokio::select! {
                     _ = cancellation_token.cancelled() => {
                         let secs = started.elapsed().as_secs_f32().max(0.1);
@@ -89,9 +96,14 @@ impl ToolCallRuntime {
                             .await
                     } => res,
                 }
-            }));
+            }))),
+        };
 
         async move {
+            let handle = match handle {
+                Ok(handle) => handle,
+                Err(refused) => return Ok(refused),
+            };
             match handle.await {
                 Ok(Ok(response)) => Ok(response),
                 Ok(Err(FunctionCallError::Fatal(message))) => Err(CodexErr::Fatal(message)),
@@ -107,19 +119,23 @@ impl ToolCallRuntime {
 
 impl ToolCallRuntime {
     fn aborted_response(call: &ToolCall, secs: f32) -> ResponseInputItem {
+        Self::text_response(call, Self::abort_message(call, secs))
+    }
+
+    fn text_response(call: &ToolCall, content: String) -> ResponseInputItem {
         match &call.payload {
             ToolPayload::Custom { .. } => ResponseInputItem::CustomToolCallOutput {
                 call_id: call.call_id.clone(),
-                output: Self::abort_message(call, secs),
+                output: content,
             },
             ToolPayload::Mcp { .. } => ResponseInputItem::McpToolCallOutput {
                 call_id: call.call_id.clone(),
-                result: Err(Self::abort_message(call, secs)),
+                result: Err(content),
             },
             _ => ResponseInputItem::FunctionCallOutput {
                 call_id: call.call_id.clone(),
                 output: FunctionCallOutputPayload {
-                    content: Self::abort_message(call, secs),
+                    content,
                     ..Default::default()
                 },
             },
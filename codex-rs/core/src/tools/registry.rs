@@ -8,6 +8,7 @@ use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
 use crate::tools::context::ToolPayload;
 use async_trait::async_trait;
+use codex_protocol::config_types::ModeKind;
 use codex_protocol::models::ResponseInputItem;
 use codex_utils_readiness::Readiness;
 use tracing::warn;
@@ -104,6 +105,25 @@ impl ToolRegistry {
             return Err(FunctionCallError::Fatal(message));
         }
 
+        // Plan mode is read-only: anything that might change the environment
+        // is rejected before it runs, not merely discouraged by the prompt.
+        if invocation.turn.collaboration_mode.mode == ModeKind::Plan
+            && handler.is_mutating(&invocation).await
+        {
+            let message = format!(
+                "{tool_name} might change files or the system, which is not allowed in Plan mode; describe the change in the plan instead"
+            );
+            otel.tool_result(
+                tool_name.as_ref(),
+                &call_id_owned,
+                log_payload.as_ref(),
+                Duration::ZERO,
+                false,
+                &message,
+            );
+            return Err(FunctionCallError::RespondToModel(message));
+        }
+
         let output_cell = tokio::sync::Mutex::new(None);
 
         let result = otel
@@ -201,6 +221,12 @@ impl ToolRegistryBuilder {
         }
     }
 
+    /// Drops the specs and handlers of tools whose name fails `keep`.
+    pub fn retain(&mut self, keep: impl Fn(&str) -> bool) {
+        self.specs.retain(|spec| keep(spec.spec.name()));
+        self.handlers.retain(|name, _| keep(name));
+    }
+
     // TODO(jif) for dynamic tools.
     // pub fn register_many<I>(&mut self, names: I, handler: Arc<dyn ToolHandler>)
     // where
@@ -116,6 +116,9 @@ impl ToolRouter {
                             sandbox_permissions: Some(SandboxPermissions::UseDefault),
                             prefix_rule: None,
                             justification: None,
+                            max_output_bytes: None,
+                            cpu_seconds: None,
+                            memory_mb: None,
                         };
                         Ok(Some(ToolCall {
                             tool_name: "local_shell".to_string(),
@@ -5,6 +5,8 @@
 //! `codex --codex-run-as-apply-patch`, and runs under the current
 //! `SandboxAttempt` with a minimal environment.
 use crate::CODEX_APPLY_PATCH_ARG1;
+use crate::apply_patch::dry_run_hunks;
+use crate::exec::ExecLimits;
 use crate::exec::ExecToolCallOutput;
 use crate::sandboxing::CommandSpec;
 use crate::sandboxing::SandboxPermissions;
@@ -20,13 +22,19 @@ use crate::tools::sandboxing::ToolError;
 use crate::tools::sandboxing::ToolRuntime;
 use crate::tools::sandboxing::with_cached_approval;
 use codex_apply_patch::ApplyPatchAction;
+use codex_apply_patch::ApplyPatchFileChange;
+use codex_apply_patch::MaybeApplyPatchVerified;
+use codex_apply_patch::filter_patch;
+use codex_apply_patch::maybe_parse_apply_patch_verified_with_fuzz;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::FileChange;
+use codex_protocol::protocol::PatchHunkId;
 use codex_protocol::protocol::ReviewDecision;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use futures::future::BoxFuture;
 use std::collections::HashMap;
 use std::path::PathBuf;
+use tracing::warn;
 
 #[derive(Debug)]
 pub struct ApplyPatchRequest {
@@ -36,17 +44,76 @@ pub struct ApplyPatchRequest {
     pub exec_approval_requirement: ExecApprovalRequirement,
     pub timeout_ms: Option<u64>,
     pub codex_exe: Option<PathBuf>,
+    /// Fuzz factor used to locate hunks; see `apply_patch_fuzz`.
+    pub fuzz: usize,
+    /// The patch touches a `write_protected` path, which the sandbox keeps
+    /// read-only unless the patch is approved.
+    pub write_protected: bool,
 }
 
 #[derive(Default)]
-pub struct ApplyPatchRuntime;
+pub struct ApplyPatchRuntime {
+    /// Hunks the user declined when approving the patch.
+    declined: Vec<PatchHunkId>,
+    /// The patch without the declined hunks; applied instead of the request's.
+    partial: Option<ApplyPatchAction>,
+}
 
 impl ApplyPatchRuntime {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Hunks left out of the applied patch at the user's request.
+    pub fn declined_hunks(&self) -> &[PatchHunkId] {
+        &self.declined
+    }
+
+    /// Prepare the patch to apply for `decision`. A partial approval is
+    /// turned into a denial when it declines every hunk or when the remaining
+    /// hunks no longer apply on their own.
+    fn record_decision(
+        &mut self,
+        req: &ApplyPatchRequest,
+        decision: ReviewDecision,
+    ) -> ReviewDecision {
+        self.declined.clear();
+        self.partial = None;
+        let ReviewDecision::ApprovedHunks { declined } = decision else {
+            return decision;
+        };
+        let action = &req.action;
+        let filtered = filter_patch(&action.patch, &action.cwd, |path, hunk| {
+            !declined
+                .iter()
+                .any(|id| id.path == path && usize::try_from(id.hunk).ok() == Some(hunk))
+        });
+        let patch = match filtered {
+            Ok(Some(patch)) => patch,
+            Ok(None) => return ReviewDecision::Denied,
+            Err(err) => {
+                warn!("failed to drop declined hunks from patch: {err}");
+                return ReviewDecision::Denied;
+            }
+        };
+        let argv = vec!["apply_patch".to_string(), patch];
+        match maybe_parse_apply_patch_verified_with_fuzz(&argv, &action.cwd, req.fuzz) {
+            MaybeApplyPatchVerified::Body(partial) => {
+                self.partial = Some(partial);
+                self.declined = declined.clone();
+                ReviewDecision::ApprovedHunks { declined }
+            }
+            other => {
+                warn!("accepted hunks do not apply without the declined ones: {other:?}");
+                ReviewDecision::Denied
+            }
+        }
     }
 
-    fn build_command_spec(req: &ApplyPatchRequest) -> Result<CommandSpec, ToolError> {
+    fn build_command_spec(
+        req: &ApplyPatchRequest,
+        action: &ApplyPatchAction,
+    ) -> Result<CommandSpec, ToolError> {
         use std::env;
         let exe = if let Some(path) = &req.codex_exe {
             path.clone()
@@ -57,13 +124,18 @@ impl ApplyPatchRuntime {
         let program = exe.to_string_lossy().to_string();
         Ok(CommandSpec {
             program,
-            args: vec![CODEX_APPLY_PATCH_ARG1.to_string(), req.action.patch.clone()],
-            cwd: req.action.cwd.clone(),
+            args: vec![
+                CODEX_APPLY_PATCH_ARG1.to_string(),
+                action.patch.clone(),
+                req.fuzz.to_string(),
+            ],
+            cwd: action.cwd.clone(),
             expiration: req.timeout_ms.into(),
             // Run apply_patch with a minimal environment for determinism and to avoid leaks.
             env: HashMap::new(),
             sandbox_permissions: SandboxPermissions::UseDefault,
             justification: None,
+            limits: ExecLimits::default(),
         })
     }
 
@@ -103,26 +175,37 @@ impl Approvable<ApplyPatchRequest> for ApplyPatchRuntime {
         let retry_reason = ctx.retry_reason.clone();
         let approval_keys = self.approval_keys(req);
         let changes = req.changes.clone();
+        let hunks = dry_run_hunks(&req.action, req.fuzz);
         Box::pin(async move {
-            if let Some(reason) = retry_reason {
+            let decision = if let Some(reason) = retry_reason {
                 let rx_approve = session
-                    .request_patch_approval(turn, call_id, changes.clone(), Some(reason), None)
+                    .request_patch_approval_with_hunks(
+                        turn,
+                        call_id,
+                        changes.clone(),
+                        Some(reason),
+                        None,
+                        hunks,
+                    )
                     .await;
-                return rx_approve.await.unwrap_or_default();
-            }
-
-            with_cached_approval(
-                &session.services,
-                "apply_patch",
-                approval_keys,
-                || async move {
-                    let rx_approve = session
-                        .request_patch_approval(turn, call_id, changes, None, None)
-                        .await;
-                    rx_approve.await.unwrap_or_default()
-                },
-            )
-            .await
+                rx_approve.await.unwrap_or_default()
+            } else {
+                with_cached_approval(
+                    &session.services,
+                    "apply_patch",
+                    approval_keys,
+                    || async move {
+                        let rx_approve = session
+                            .request_patch_approval_with_hunks(
+                                turn, call_id, changes, None, None, hunks,
+                            )
+                            .await;
+                        rx_approve.await.unwrap_or_default()
+                    },
+                )
+                .await
+            };
+            self.record_decision(req, decision)
         })
     }
 
@@ -130,6 +213,17 @@ impl Approvable<ApplyPatchRequest> for ApplyPatchRuntime {
         !matches!(policy, AskForApproval::Never)
     }
 
+    fn approved_writes(&self, req: &ApplyPatchRequest) -> Vec<PathBuf> {
+        if req.write_protected {
+            req.file_paths
+                .iter()
+                .map(AbsolutePathBuf::to_path_buf)
+                .collect()
+        } else {
+            Vec::new()
+        }
+    }
+
     // apply_patch approvals are decided upstream by assess_patch_safety.
     //
     // This override ensures the orchestrator runs the patch approval flow when required instead
@@ -149,13 +243,67 @@ impl ToolRuntime<ApplyPatchRequest, ExecToolCallOutput> for ApplyPatchRuntime {
         attempt: &SandboxAttempt<'_>,
         ctx: &ToolCtx<'_>,
     ) -> Result<ExecToolCallOutput, ToolError> {
-        let spec = Self::build_command_spec(req)?;
-        let env = attempt
-            .env_for(spec)
-            .map_err(|err| ToolError::Codex(err.into()))?;
-        let out = execute_env(env, attempt.policy, Self::stdout_stream(ctx))
-            .await
-            .map_err(ToolError::Codex)?;
-        Ok(out)
+        let action = self.partial.as_ref().unwrap_or(&req.action);
+        let container = &ctx.session.services.container;
+        let trash_start = Self::stash_deleted_files(action, ctx).await?;
+        let out = if container.is_enabled() {
+            container
+                .apply_patch(action, &ctx.turn.cwd, &ctx.turn.sandbox_policy)
+                .await
+                .map_err(|err| ToolError::Rejected(format!("container unavailable: {err}")))
+        } else {
+            let spec = Self::build_command_spec(req, action)?;
+            let env = attempt
+                .env_for(spec)
+                .map_err(|err| ToolError::Codex(err.into()))?;
+            execute_env(env, attempt.policy, Self::stdout_stream(ctx))
+                .await
+                .map_err(ToolError::Codex)
+        };
+        if let Some(start) = trash_start
+            && let Some(trash) = ctx.session.services.trash.lock().await.as_mut()
+        {
+            trash.discard_unapplied(start);
+        }
+        out
+    }
+}
+
+impl ApplyPatchRuntime {
+    /// Copies files the patch is about to delete, and existing files a move
+    /// is about to overwrite, into the session trash.
+    ///
+    /// Returns the index of the first entry added so the caller can drop
+    /// entries whose deletion never happened. The patch is rejected when a
+    /// file cannot be preserved rather than deleting it unrecoverably.
+    async fn stash_deleted_files(
+        action: &ApplyPatchAction,
+        ctx: &ToolCtx<'_>,
+    ) -> Result<Option<usize>, ToolError> {
+        let mut guard = ctx.session.services.trash.lock().await;
+        let Some(trash) = guard.as_mut() else {
+            return Ok(None);
+        };
+        let start = trash.len();
+        for (path, change) in action.changes() {
+            let doomed = match change {
+                ApplyPatchFileChange::Delete { .. } => path,
+                ApplyPatchFileChange::Update {
+                    move_path: Some(dest),
+                    ..
+                } => dest,
+                _ => continue,
+            };
+            if doomed.is_file()
+                && let Err(err) = trash.stash(doomed)
+            {
+                trash.discard_unapplied(start);
+                return Err(ToolError::Rejected(format!(
+                    "failed to move {} to trash: {err}",
+                    doomed.display()
+                )));
+            }
+        }
+        Ok(Some(start))
     }
 }
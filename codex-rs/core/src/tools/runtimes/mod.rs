@@ -5,6 +5,7 @@ Concrete ToolRuntime implementations for specific tools. Each runtime stays
 small and focused and reuses the orchestrator for approvals + sandbox + retry.
 */
 use crate::exec::ExecExpiration;
+use crate::exec::ExecLimits;
 use crate::sandboxing::CommandSpec;
 use crate::sandboxing::SandboxPermissions;
 use crate::shell::Shell;
@@ -25,6 +26,7 @@ pub(crate) fn build_command_spec(
     expiration: ExecExpiration,
     sandbox_permissions: SandboxPermissions,
     justification: Option<String>,
+    limits: ExecLimits,
 ) -> Result<CommandSpec, ToolError> {
     let (program, args) = command
         .split_first()
@@ -37,6 +39,7 @@ pub(crate) fn build_command_spec(
         expiration,
         sandbox_permissions,
         justification,
+        limits,
     })
 }
 
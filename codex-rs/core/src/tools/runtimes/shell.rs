@@ -4,7 +4,13 @@ Runtime: shell
 Executes shell requests under the orchestrator: asks for approval when needed,
 builds a CommandSpec, and runs it under the current SandboxAttempt.
 */
+use crate::bash::extract_bash_command;
+use crate::error::CodexErr;
+use crate::error::SandboxErr;
+use crate::exec::ExecLimits;
 use crate::exec::ExecToolCallOutput;
+use crate::exec::SandboxType;
+use crate::exec::is_likely_sandbox_denied;
 use crate::features::Feature;
 use crate::powershell::prefix_powershell_script_with_utf8;
 use crate::sandboxing::SandboxPermissions;
@@ -26,6 +32,7 @@ use crate::tools::sandboxing::with_cached_approval;
 use codex_protocol::protocol::ReviewDecision;
 use futures::future::BoxFuture;
 use std::path::PathBuf;
+use tracing::warn;
 
 #[derive(Clone, Debug)]
 pub struct ShellRequest {
@@ -35,6 +42,7 @@ pub struct ShellRequest {
     pub env: std::collections::HashMap<String, String>,
     pub sandbox_permissions: SandboxPermissions,
     pub justification: Option<String>,
+    pub limits: ExecLimits,
     pub exec_approval_requirement: ExecApprovalRequirement,
 }
 
@@ -60,6 +68,96 @@ impl ShellRuntime {
             tx_event: ctx.session.get_tx_event(),
         })
     }
+
+    /// Runs the command in the session container, which takes the place of
+    /// the native sandbox.
+    async fn run_in_container(
+        req: &ShellRequest,
+        attempt: &SandboxAttempt<'_>,
+        ctx: &ToolCtx<'_>,
+    ) -> Result<ExecToolCallOutput, ToolError> {
+        let container = &ctx.session.services.container;
+        let exec = container
+            .exec_command(
+                &req.command,
+                &req.cwd,
+                &ctx.turn.cwd,
+                &ctx.turn.sandbox_policy,
+                false,
+            )
+            .await
+            .map_err(|err| ToolError::Rejected(format!("container unavailable: {err}")))?;
+        let out = Self::run_unsandboxed(&exec.argv, req, attempt, ctx).await;
+        if let Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Timeout { .. }))) = &out {
+            container.kill(&exec).await;
+        }
+        Ok(exec.mapping.output_to_host(out?))
+    }
+
+    /// Runs the command on the `[remote]` host, under bubblewrap there unless
+    /// the attempt is unsandboxed. The workspace is synced up first and,
+    /// unless the policy is read-only, the changes are synced back.
+    async fn run_remote(
+        req: &ShellRequest,
+        attempt: &SandboxAttempt<'_>,
+        ctx: &ToolCtx<'_>,
+    ) -> Result<ExecToolCallOutput, ToolError> {
+        let remote = &ctx.session.services.remote;
+        let host_root = &ctx.turn.cwd;
+        let mapping = remote
+            .sync_up(host_root)
+            .await
+            .map_err(|err| ToolError::Rejected(format!("remote host unavailable: {err}")))?;
+        let sandbox = (attempt.sandbox != SandboxType::None).then_some(attempt.policy);
+        let command = remote
+            .exec_command(&req.command, &req.cwd, &mapping, sandbox)
+            .map_err(ToolError::Rejected)?;
+        let out = Self::run_unsandboxed(&command, req, attempt, ctx).await?;
+        let policy = &ctx.turn.sandbox_policy;
+        if (policy.has_full_disk_write_access()
+            || !policy.get_writable_roots_with_cwd(host_root).is_empty())
+            && let Err(err) = remote
+                .sync_down(host_root, &ctx.turn.client.config().write_protected)
+                .await
+        {
+            warn!("failed to copy changes back from the remote host: {err}");
+        }
+        let out = mapping.output_to_host(out);
+        if is_likely_sandbox_denied(attempt.sandbox, &out) {
+            return Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied {
+                output: Box::new(out),
+            })));
+        }
+        Ok(out)
+    }
+
+    /// Runs a command that is isolated elsewhere, outside the native sandbox.
+    async fn run_unsandboxed(
+        command: &[String],
+        req: &ShellRequest,
+        attempt: &SandboxAttempt<'_>,
+        ctx: &ToolCtx<'_>,
+    ) -> Result<ExecToolCallOutput, ToolError> {
+        let spec = build_command_spec(
+            command,
+            &req.cwd,
+            &req.env,
+            req.timeout_ms.into(),
+            req.sandbox_permissions,
+            req.justification.clone(),
+            req.limits,
+        )?;
+        let attempt = SandboxAttempt {
+            sandbox: SandboxType::None,
+            ..*attempt
+        };
+        let env = attempt
+            .env_for(spec)
+            .map_err(|err| ToolError::Codex(err.into()))?;
+        execute_env(env, attempt.policy, Self::stdout_stream(ctx))
+            .await
+            .map_err(ToolError::Codex)
+    }
 }
 
 impl Sandboxable for ShellRuntime {
@@ -87,7 +185,13 @@ impl Approvable<ShellRequest> for ShellRuntime {
         req: &'a ShellRequest,
         ctx: ApprovalCtx<'a>,
     ) -> BoxFuture<'a, ReviewDecision> {
-        let keys = self.approval_keys(req);
+        // Without keys, the decision is neither read from nor stored in the
+        // session cache.
+        let keys = if ctx.skip_session_cache {
+            Vec::new()
+        } else {
+            self.approval_keys(req)
+        };
         let command = req.command.clone();
         let cwd = req.cwd.clone();
         let reason = ctx
@@ -144,7 +248,35 @@ impl ToolRuntime<ShellRequest, ExecToolCallOutput> for ShellRuntime {
         attempt: &SandboxAttempt<'_>,
         ctx: &ToolCtx<'_>,
     ) -> Result<ExecToolCallOutput, ToolError> {
+        if ctx.session.services.container.is_enabled() {
+            return Self::run_in_container(req, attempt, ctx).await;
+        }
+        if ctx.session.services.remote.is_enabled() {
+            return Self::run_remote(req, attempt, ctx).await;
+        }
         let base_command = &req.command;
+        // The persistent shell is already running, so per-command CPU and
+        // memory limits need a fresh process.
+        if ctx.session.features().enabled(Feature::PersistentShell)
+            && !req.limits.has_resource_limits()
+            && let Some((shell_path, script)) = extract_bash_command(base_command)
+            && let Some(result) = ctx
+                .session
+                .services
+                .persistent_shell
+                .run(
+                    shell_path,
+                    script,
+                    &req.cwd,
+                    &req.env,
+                    req.timeout_ms,
+                    req.sandbox_permissions,
+                    attempt,
+                )
+                .await
+        {
+            return result;
+        }
         let session_shell = ctx.session.user_shell();
         let command = maybe_wrap_shell_lc_with_snapshot(base_command, session_shell.as_ref());
         let command = if matches!(session_shell.shell_type, ShellType::PowerShell)
@@ -162,6 +294,7 @@ impl ToolRuntime<ShellRequest, ExecToolCallOutput> for ShellRuntime {
             req.timeout_ms.into(),
             req.sandbox_permissions,
             req.justification.clone(),
+            req.limits,
         )?;
         let env = attempt
             .env_for(spec)
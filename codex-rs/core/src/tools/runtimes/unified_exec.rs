@@ -7,8 +7,11 @@ the process manager to spawn PTYs once an ExecEnv is prepared.
 use crate::error::CodexErr;
 use crate::error::SandboxErr;
 use crate::exec::ExecExpiration;
+use crate::exec::ExecLimits;
+use crate::exec::SandboxType;
 use crate::features::Feature;
 use crate::powershell::prefix_powershell_script_with_utf8;
+use crate::sandboxing::CommandSpec;
 use crate::sandboxing::SandboxPermissions;
 use crate::shell::ShellType;
 use crate::tools::runtimes::build_command_spec;
@@ -110,7 +113,13 @@ impl Approvable<UnifiedExecRequest> for UnifiedExecRuntime<'_> {
         req: &'b UnifiedExecRequest,
         ctx: ApprovalCtx<'b>,
     ) -> BoxFuture<'b, ReviewDecision> {
-        let keys = self.approval_keys(req);
+        // Without keys, the decision is neither read from nor stored in the
+        // session cache.
+        let keys = if ctx.skip_session_cache {
+            Vec::new()
+        } else {
+            self.approval_keys(req)
+        };
         let session = ctx.session;
         let turn = ctx.turn;
         let call_id = ctx.call_id.to_string();
@@ -170,6 +179,16 @@ impl<'a> ToolRuntime<UnifiedExecRequest, UnifiedExecProcess> for UnifiedExecRunt
         attempt: &SandboxAttempt<'_>,
         ctx: &ToolCtx<'_>,
     ) -> Result<UnifiedExecProcess, ToolError> {
+        // Interactive sessions are not copied to and from the remote host, so
+        // running one locally would silently bypass `[remote]`.
+        if ctx.session.services.remote.is_enabled() {
+            return Err(ToolError::Rejected(
+                "exec_command is not available when commands run on a [remote] host; use the shell tool instead".to_string(),
+            ));
+        }
+        if ctx.session.services.container.is_enabled() {
+            return self.run_in_container(req, attempt, ctx).await;
+        }
         let base_command = &req.command;
         let session_shell = ctx.session.user_shell();
         let command = maybe_wrap_shell_lc_with_snapshot(base_command, session_shell.as_ref());
@@ -181,6 +200,12 @@ impl<'a> ToolRuntime<UnifiedExecRequest, UnifiedExecProcess> for UnifiedExecRunt
             command
         };
 
+        // Sessions are not forked by Codex, so the configured CPU and memory
+        // limits are applied by a wrapper inside the sandbox.
+        let limits = ctx.turn.client.config().exec_limits.limits;
+        #[cfg(unix)]
+        let command = crate::exec_limits::wrap_with_rlimits(limits, command);
+
         let spec = build_command_spec(
             &command,
             &req.cwd,
@@ -188,13 +213,63 @@ impl<'a> ToolRuntime<UnifiedExecRequest, UnifiedExecProcess> for UnifiedExecRunt
             ExecExpiration::DefaultTimeout,
             req.sandbox_permissions,
             req.justification.clone(),
+            limits,
+        )
+        .map_err(|_| ToolError::Rejected("missing command line for PTY".to_string()))?;
+        self.open_session(spec, req.tty, attempt).await
+    }
+}
+
+impl UnifiedExecRuntime<'_> {
+    /// Runs the session in the `[container]` container, which takes the
+    /// place of the native sandbox.
+    async fn run_in_container(
+        &self,
+        req: &UnifiedExecRequest,
+        attempt: &SandboxAttempt<'_>,
+        ctx: &ToolCtx<'_>,
+    ) -> Result<UnifiedExecProcess, ToolError> {
+        let exec = ctx
+            .session
+            .services
+            .container
+            .exec_command(
+                &req.command,
+                &req.cwd,
+                &ctx.turn.cwd,
+                &ctx.turn.sandbox_policy,
+                req.tty,
+            )
+            .await
+            .map_err(|err| ToolError::Rejected(format!("container unavailable: {err}")))?;
+        let spec = build_command_spec(
+            &exec.argv,
+            &req.cwd,
+            &req.env,
+            ExecExpiration::DefaultTimeout,
+            req.sandbox_permissions,
+            req.justification.clone(),
+            ExecLimits::default(),
         )
         .map_err(|_| ToolError::Rejected("missing command line for PTY".to_string()))?;
+        let attempt = SandboxAttempt {
+            sandbox: SandboxType::None,
+            ..*attempt
+        };
+        self.open_session(spec, req.tty, &attempt).await
+    }
+
+    async fn open_session(
+        &self,
+        spec: CommandSpec,
+        tty: bool,
+        attempt: &SandboxAttempt<'_>,
+    ) -> Result<UnifiedExecProcess, ToolError> {
         let exec_env = attempt
             .env_for(spec)
             .map_err(|err| ToolError::Codex(err.into()))?;
         self.manager
-            .open_session_with_exec_env(&exec_env, req.tty)
+            .open_session_with_exec_env(&exec_env, tty)
             .await
             .map_err(|err| match err {
                 UnifiedExecError::SandboxDenied { output, .. } => {
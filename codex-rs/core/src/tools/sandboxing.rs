@@ -11,6 +11,7 @@ use crate::protocol::SandboxPolicy;
 use crate::sandboxing::CommandSpec;
 use crate::sandboxing::SandboxManager;
 use crate::sandboxing::SandboxTransformError;
+use crate::sandboxing::SandboxTransformOptions;
 use crate::state::SessionServices;
 use codex_protocol::approvals::ExecPolicyAmendment;
 use codex_protocol::protocol::AskForApproval;
@@ -19,6 +20,7 @@ use std::collections::HashMap;
 use std::fmt::Debug;
 use std::hash::Hash;
 use std::path::Path;
+use std::path::PathBuf;
 
 use futures::Future;
 use futures::future::BoxFuture;
@@ -54,7 +56,8 @@ impl ApprovalStore {
 ///
 /// - If all keys are already approved for session, we skip prompting.
 /// - If the user approves for session, we store the decision for each key individually
-///   so future requests touching any subset can also skip prompting.
+///   so future requests touching any subset can also skip prompting. Approving always
+///   counts as approving for the rest of this session as well.
 pub(crate) async fn with_cached_approval<K, F, Fut>(
     services: &SessionServices,
     // Name of the tool, used for metrics collection.
@@ -93,7 +96,10 @@ where
         ],
     );
 
-    if matches!(decision, ReviewDecision::ApprovedForSession) {
+    if matches!(
+        decision,
+        ReviewDecision::ApprovedForSession | ReviewDecision::ApprovedAlwaysForPaths { .. }
+    ) {
         let mut store = services.tool_approvals.lock().await;
         for key in keys {
             store.put(key, ReviewDecision::ApprovedForSession);
@@ -109,6 +115,9 @@ pub(crate) struct ApprovalCtx<'a> {
     pub turn: &'a TurnContext,
     pub call_id: &'a str,
     pub retry_reason: Option<String>,
+    /// Ask the user even if this call was approved for the session, because
+    /// the answer covers more than the cached approval did.
+    pub skip_session_cache: bool,
 }
 
 // Specifies what tool orchestrator should do with a given tool call.
@@ -204,6 +213,12 @@ pub(crate) trait Approvable<Req> {
         SandboxOverride::NoOverride
     }
 
+    /// Paths the request writes that may be `write_protected`. Once the
+    /// request is approved, the sandbox leaves them writable.
+    fn approved_writes(&self, _req: &Req) -> Vec<PathBuf> {
+        Vec::new()
+    }
+
     fn should_bypass_approval(&self, policy: AskForApproval, already_approved: bool) -> bool {
         if already_approved {
             // We do not ask one more time
@@ -275,6 +290,11 @@ pub(crate) struct SandboxAttempt<'a> {
     pub(crate) sandbox_cwd: &'a Path,
     pub codex_linux_sandbox_exe: Option<&'a std::path::PathBuf>,
     pub windows_sandbox_level: codex_protocol::config_types::WindowsSandboxLevel,
+    /// Existing `write_protected` paths the sandbox keeps read-only.
+    pub(crate) read_only_paths: &'a [std::path::PathBuf],
+    /// Loopback proxy a command without network access may still reach.
+    pub(crate) network_proxy: Option<std::net::SocketAddr>,
+    pub(crate) linux_sandbox: &'a crate::config::types::LinuxSandboxConfig,
 }
 
 impl<'a> SandboxAttempt<'a> {
@@ -287,8 +307,13 @@ impl<'a> SandboxAttempt<'a> {
             self.policy,
             self.sandbox,
             self.sandbox_cwd,
-            self.codex_linux_sandbox_exe,
             self.windows_sandbox_level,
+            SandboxTransformOptions {
+                codex_linux_sandbox_exe: self.codex_linux_sandbox_exe,
+                linux_sandbox: self.linux_sandbox,
+                read_only_paths: self.read_only_paths,
+                network_proxy: self.network_proxy,
+            },
         )
     }
 }
@@ -1,6 +1,8 @@
 use crate::agent::AgentRole;
 use crate::client_common::tools::ResponsesApiTool;
 use crate::client_common::tools::ToolSpec;
+use crate::config::types::ToolFilter;
+use crate::config::types::WebSearchBackend;
 use crate::features::Feature;
 use crate::features::Features;
 use crate::tools::handlers::PLAN_TOOL;
@@ -28,10 +30,21 @@ pub(crate) struct ToolsConfig {
     pub shell_type: ConfigShellToolType,
     pub apply_patch_tool_type: Option<ApplyPatchToolType>,
     pub web_search_mode: Option<WebSearchMode>,
+    pub web_search_backend: WebSearchBackend,
     pub collab_tools: bool,
     pub collaboration_modes_tools: bool,
     pub request_rule_enabled: bool,
+    pub project_memory_tool: bool,
+    pub git_commit_tool: bool,
+    pub pull_request_tool: bool,
+    pub search_code_tool: bool,
+    pub edit_definition_tool: bool,
+    pub background_jobs_tools: bool,
+    pub read_output_tool: bool,
+    pub fetch_url_tool: bool,
+    pub open_file_tool: bool,
     pub experimental_supported_tools: Vec<String>,
+    pub tool_filter: ToolFilter,
 }
 
 pub(crate) struct ToolsConfigParams<'a> {
@@ -51,6 +64,15 @@ impl ToolsConfig {
         let include_collab_tools = features.enabled(Feature::Collab);
         let include_collaboration_modes_tools = features.enabled(Feature::CollaborationModes);
         let request_rule_enabled = features.enabled(Feature::RequestRule);
+        let include_project_memory_tool = features.enabled(Feature::ProjectMemory);
+        let include_git_commit_tool = features.enabled(Feature::GitCommitTool);
+        let include_pull_request_tool = features.enabled(Feature::PullRequestTool);
+        let include_search_code_tool = features.enabled(Feature::CodeSearch);
+        let include_edit_definition_tool = features.enabled(Feature::StructuredEdit);
+        let include_background_jobs_tools = features.enabled(Feature::BackgroundJobs);
+        let include_read_output_tool = features.enabled(Feature::OutputPaging);
+        let include_fetch_url_tool = features.enabled(Feature::FetchUrl);
+        let include_open_file_tool = features.enabled(Feature::OpenFileTool);
 
         let shell_type = if !features.enabled(Feature::ShellTool) {
             ConfigShellToolType::Disabled
@@ -81,12 +103,44 @@ impl ToolsConfig {
             shell_type,
             apply_patch_tool_type,
             web_search_mode: *web_search_mode,
+            web_search_backend: WebSearchBackend::default(),
             collab_tools: include_collab_tools,
             collaboration_modes_tools: include_collaboration_modes_tools,
             request_rule_enabled,
+            project_memory_tool: include_project_memory_tool,
+            git_commit_tool: include_git_commit_tool,
+            pull_request_tool: include_pull_request_tool,
+            search_code_tool: include_search_code_tool,
+            edit_definition_tool: include_edit_definition_tool,
+            background_jobs_tools: include_background_jobs_tools
+                && shell_type != ConfigShellToolType::Disabled,
+            read_output_tool: include_read_output_tool,
+            fetch_url_tool: include_fetch_url_tool,
+            open_file_tool: include_open_file_tool,
             experimental_supported_tools: model_info.experimental_supported_tools.clone(),
+            tool_filter: ToolFilter::default(),
         }
     }
+
+    /// Serves `web_search` from `web_search_backend` instead of the model
+    /// provider's native search when it is not `native`.
+    pub fn with_web_search_backend(mut self, web_search_backend: WebSearchBackend) -> Self {
+        self.web_search_backend = web_search_backend;
+        self
+    }
+
+    /// Offers `read_output` without the `output_paging` feature when MCP
+    /// output budgets are configured, so cut-down MCP results can be read in full.
+    pub fn with_mcp_output_budgets(mut self, configured: bool) -> Self {
+        self.read_output_tool |= configured;
+        self
+    }
+
+    /// Restricts the built-in tools to those `tool_filter` allows.
+    pub fn with_tool_filter(mut self, tool_filter: ToolFilter) -> Self {
+        self.tool_filter = tool_filter;
+        self
+    }
 }
 
 /// Generic JSON‑Schema subset needed for our tool definitions
@@ -187,6 +241,38 @@ fn create_approval_parameters(include_prefix_rule: bool) -> BTreeMap<String, Jso
     properties
 }
 
+fn create_exec_limit_parameters() -> BTreeMap<String, JsonSchema> {
+    BTreeMap::from([
+        (
+            "max_output_bytes".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Keep at most this many bytes of output. Can only lower the configured limit."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "cpu_seconds".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "CPU time the command may use, in seconds. Can only lower the configured limit."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "memory_mb".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Memory the command may use, in megabytes. Can only lower the configured limit."
+                        .to_string(),
+                ),
+            },
+        ),
+    ])
+}
+
 fn create_exec_command_tool(include_prefix_rule: bool) -> ToolSpec {
     let mut properties = BTreeMap::from([
         (
@@ -308,6 +394,151 @@ fn create_write_stdin_tool() -> ToolSpec {
     })
 }
 
+fn create_start_job_tool(include_prefix_rule: bool) -> ToolSpec {
+    let mut properties = BTreeMap::from([
+        (
+            "cmd".to_string(),
+            JsonSchema::String {
+                description: Some("Shell command to run as a background job.".to_string()),
+            },
+        ),
+        (
+            "workdir".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Optional working directory to run the command in; defaults to the turn cwd."
+                        .to_string(),
+                ),
+            },
+        ),
+    ]);
+    properties.extend(create_approval_parameters(include_prefix_rule));
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "start_job".to_string(),
+        description: "Starts a long-running command, such as a dev server or a watch build, as a background job and returns its id with its first lines of output. The job keeps running across turns until stopped with stop_job or the session ends. Read its output with read_job_output. Use exec_command for commands that finish on their own."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["cmd".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_read_job_output_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "job_id".to_string(),
+            JsonSchema::String {
+                description: Some("Id returned by start_job, e.g. \"job-1\".".to_string()),
+            },
+        ),
+        (
+            "since_line".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Only return lines after this line number, as reported by an earlier read."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "filter".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Only return lines matching this regular expression, e.g. \"error|warn\"."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "max_lines".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Maximum number of lines to return, the most recent ones. Defaults to 200."
+                        .to_string(),
+                ),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "read_job_output".to_string(),
+        description: "Reads the output of a background job started with start_job, and whether it is still running. Lines are numbered; pass the last line number as since_line to read only what is new."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["job_id".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_stop_job_tool() -> ToolSpec {
+    let properties = BTreeMap::from([(
+        "job_id".to_string(),
+        JsonSchema::String {
+            description: Some("Id returned by start_job.".to_string()),
+        },
+    )]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "stop_job".to_string(),
+        description: "Stops a background job started with start_job and discards its output."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["job_id".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_read_output_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "output_id".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Id of the saved output, as named in a truncated command or tool result, e.g. \"out-1\"."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "start_line".to_string(),
+            JsonSchema::Number {
+                description: Some("First line to return, counting from 1. Defaults to 1.".to_string()),
+            },
+        ),
+        (
+            "end_line".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Last line to return, inclusive. Defaults to 199 lines after start_line."
+                        .to_string(),
+                ),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "read_output".to_string(),
+        description: "Reads a range of lines from the full output of a command or MCP tool whose result was truncated. Truncated results name what was left out and give the output_id to pass here."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["output_id".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
 fn create_shell_tool(include_prefix_rule: bool) -> ToolSpec {
     let mut properties = BTreeMap::from([
         (
@@ -331,6 +562,7 @@ fn create_shell_tool(include_prefix_rule: bool) -> ToolSpec {
         ),
     ]);
     properties.extend(create_approval_parameters(include_prefix_rule));
+    properties.extend(create_exec_limit_parameters());
 
     let description  = if cfg!(windows) {
         r#"Runs a Powershell command (Windows) and returns its output. Arguments to `shell` will be passed to CreateProcessW(). Most commands should be prefixed with ["powershell.exe", "-Command"].
@@ -394,6 +626,7 @@ fn create_shell_command_tool(include_prefix_rule: bool) -> ToolSpec {
         ),
     ]);
     properties.extend(create_approval_parameters(include_prefix_rule));
+    properties.extend(create_exec_limit_parameters());
 
     let description = if cfg!(windows) {
         r#"Runs a Powershell command (Windows) and returns its output.
@@ -465,6 +698,24 @@ fn create_spawn_agent_tool() -> ToolSpec {
             )),
         },
     );
+    properties.insert(
+        "read_only".to_string(),
+        JsonSchema::Boolean {
+            description: Some(
+                "When true, the agent runs in a read-only sandbox. Use for research and review tasks."
+                    .to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "workdir".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Optional directory inside your working directory that becomes the agent's working directory and writable workspace, e.g. the module it owns."
+                    .to_string(),
+            ),
+        },
+    );
 
     ToolSpec::Function(ResponsesApiTool {
         name: "spawn_agent".to_string(),
@@ -635,6 +886,118 @@ fn create_request_user_input_tool() -> ToolSpec {
     })
 }
 
+fn create_memory_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "action".to_string(),
+        JsonSchema::String {
+            description: Some("`add` to record a note, `remove` to delete one.".to_string()),
+        },
+    );
+    properties.insert(
+        "note".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "One short, self-contained note. For `remove`, the exact text of an existing note."
+                    .to_string(),
+            ),
+        },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "memory".to_string(),
+        description: "Add or remove a note in the project memory file (.codex/memory.md), which is included in the instructions of every future session in this project. Record durable facts the user would otherwise have to repeat, such as build and test commands, conventions, and pitfalls; do not record task progress. The user approves each change."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["action".to_string(), "note".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_git_commit_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "message".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Commit message: a short imperative summary line, optionally followed by a blank line and a body explaining why."
+                    .to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "paths".to_string(),
+        JsonSchema::Array {
+            items: Box::new(JsonSchema::String { description: None }),
+            description: Some(
+                "Files to commit, relative to the working directory or absolute. List every file you changed that belongs in the commit."
+                    .to_string(),
+            ),
+        },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "git_commit".to_string(),
+        description: "Stage and commit changes in the current git repository. Only the listed files are staged and committed; anything else already staged is left alone. The user sees the full diff and the message and approves each commit."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["message".to_string(), "paths".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_pull_request_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "title".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Short summary of the change, like a commit summary line.".to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "body".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Markdown description: what changed and why, and how it was tested.".to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "base".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Branch to merge into. Defaults to the remote's default branch.".to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "draft".to_string(),
+        JsonSchema::Boolean {
+            description: Some("Open the request as a draft.".to_string()),
+        },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "create_pull_request".to_string(),
+        description: "Push the current branch and open a GitHub pull request or GitLab merge request for it. Only committed changes are included, so commit first. The user approves the push before anything is published."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["title".to_string(), "body".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
 fn create_close_agent_tool() -> ToolSpec {
     let mut properties = BTreeMap::new();
     properties.insert(
@@ -724,6 +1087,178 @@ fn create_test_sync_tool() -> ToolSpec {
     })
 }
 
+fn create_search_code_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "query".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "What the code does, in plain words, e.g. \"retry failed http requests\"."
+                    .to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "limit".to_string(),
+        JsonSchema::Number {
+            description: Some("Maximum number of results (default 8, at most 20).".to_string()),
+        },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "search_code".to_string(),
+        description: "Find code in the current repository by meaning rather than exact text, using the repository's local embedding index: describe the behavior you are looking for and matching code is found even when it uses different names. Returns the best-matching definitions or regions with their paths and line ranges. Use it to locate where a concept is implemented in an unfamiliar codebase; use grep_files or rg for exact strings."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["query".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_web_search_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "query".to_string(),
+        JsonSchema::String {
+            description: Some("What to search the web for.".to_string()),
+        },
+    );
+    properties.insert(
+        "max_results".to_string(),
+        JsonSchema::Number {
+            description: Some("Maximum number of results (default 5, at most 10).".to_string()),
+        },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "web_search".to_string(),
+        description: "Search the web. Returns results with a title, URL and snippet. Use it for information that may be newer than your training data or that is not in the repository. When your answer relies on a result, cite its URL."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["query".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_fetch_url_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "url".to_string(),
+        JsonSchema::String {
+            description: Some("The http or https URL to read.".to_string()),
+        },
+    );
+    properties.insert(
+        "offset".to_string(),
+        JsonSchema::Number {
+            description: Some(
+                "Character offset to continue reading a long page from (default 0).".to_string(),
+            ),
+        },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "fetch_url".to_string(),
+        description: "Download a public web page, such as documentation or an issue the user linked, and return its readable text as Markdown with navigation and scripts removed. Long pages are returned in parts; call again with the offset given at the end of the output to read more. Pages disallowed by the site's robots.txt and private network addresses cannot be fetched."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["url".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_open_file_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "path".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "File to open, absolute or relative to the working directory.".to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "line".to_string(),
+        JsonSchema::Number {
+            description: Some("1-based line to reveal.".to_string()),
+        },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "open_file".to_string(),
+        description: "Open a file in the user's editor, at a line when given, to show them code you are referring to. Use it when pointing the user at a specific location helps; it does not return the file's contents."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["path".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_edit_definition_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "path".to_string(),
+        JsonSchema::String {
+            description: Some("File to edit, relative to the working directory.".to_string()),
+        },
+    );
+    properties.insert(
+        "symbol".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Qualified name of the function, method, class, impl block or type to edit, e.g. \"Foo::bar\" for method bar in impl Foo, or \"App.run\". A trailing part of the name is enough when it is unique in the file."
+                    .to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "action".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "One of \"replace\" (the whole definition, including its attributes, doc comments or decorators), \"replace_body\" (only what is inside the body, keeping the signature), \"delete\", \"insert_before\" or \"insert_after\"."
+                    .to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "content".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "The new code. It is re-indented to match the definition (or its body for replace_body), so it can be written without the surrounding indentation. Omit for delete."
+                    .to_string(),
+            ),
+        },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "edit_definition".to_string(),
+        description: "Edit a Rust, Python, JavaScript, TypeScript or Go file by naming the definition to change instead of quoting the lines around it. The definition is located by parsing the file as it is now, so the edit does not depend on line numbers or context that earlier edits may have moved. The change is applied like an apply_patch call. Prefer it for replacing or removing whole functions, methods and classes; use apply_patch for smaller edits inside a definition."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec![
+                "path".to_string(),
+                "symbol".to_string(),
+                "action".to_string(),
+            ]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
 fn create_grep_files_tool() -> ToolSpec {
     let properties = BTreeMap::from([
         (
@@ -867,7 +1402,7 @@ fn create_read_file_tool() -> ToolSpec {
     ToolSpec::Function(ResponsesApiTool {
         name: "read_file".to_string(),
         description:
-            "Reads a local file with 1-indexed line numbers, supporting slice and indentation-aware block modes."
+            "Reads a local file with 1-indexed line numbers, supporting slice and indentation-aware block modes. When a slice of a source file is cut short by `limit`, it ends at a function or class boundary and lists the omitted definitions with their line ranges."
                 .to_string(),
         strict: false,
         parameters: JsonSchema::Object {
@@ -1085,6 +1620,27 @@ pub(crate) fn create_tools_json_for_chat_completions_api(
     Ok(tools_json)
 }
 
+/// Rewrites the function tools for the Anthropic Messages API, which takes
+/// `{"name", "description", "input_schema"}`. Freeform and built-in tools
+/// have no Messages API equivalent and are dropped.
+pub(crate) fn create_tools_json_for_anthropic_api(
+    tools: &[ToolSpec],
+) -> crate::error::Result<Vec<serde_json::Value>> {
+    let responses_api_tools_json = create_tools_json_for_responses_api(tools)?;
+    let tools_json = responses_api_tools_json
+        .into_iter()
+        .filter(|tool| tool.get("type").and_then(serde_json::Value::as_str) == Some("function"))
+        .map(|tool| {
+            json!({
+                "name": tool.get("name").cloned().unwrap_or_default(),
+                "description": tool.get("description").cloned().unwrap_or_default(),
+                "input_schema": tool.get("parameters").cloned().unwrap_or_default(),
+            })
+        })
+        .collect::<Vec<serde_json::Value>>();
+    Ok(tools_json)
+}
+
 pub(crate) fn mcp_tool_to_openai_tool(
     fully_qualified_name: String,
     tool: mcp_types::Tool,
@@ -1258,20 +1814,30 @@ pub(crate) fn build_specs(
     dynamic_tools: &[DynamicToolSpec],
 ) -> ToolRegistryBuilder {
     use crate::tools::handlers::ApplyPatchHandler;
+    use crate::tools::handlers::BackgroundJobHandler;
     use crate::tools::handlers::CollabHandler;
     use crate::tools::handlers::DynamicToolHandler;
+    use crate::tools::handlers::EditDefinitionHandler;
+    use crate::tools::handlers::FetchUrlHandler;
+    use crate::tools::handlers::GitCommitHandler;
     use crate::tools::handlers::GrepFilesHandler;
     use crate::tools::handlers::ListDirHandler;
     use crate::tools::handlers::McpHandler;
     use crate::tools::handlers::McpResourceHandler;
+    use crate::tools::handlers::MemoryHandler;
+    use crate::tools::handlers::OpenFileHandler;
     use crate::tools::handlers::PlanHandler;
+    use crate::tools::handlers::PullRequestHandler;
     use crate::tools::handlers::ReadFileHandler;
+    use crate::tools::handlers::ReadOutputHandler;
     use crate::tools::handlers::RequestUserInputHandler;
+    use crate::tools::handlers::SearchCodeHandler;
     use crate::tools::handlers::ShellCommandHandler;
     use crate::tools::handlers::ShellHandler;
     use crate::tools::handlers::TestSyncHandler;
     use crate::tools::handlers::UnifiedExecHandler;
     use crate::tools::handlers::ViewImageHandler;
+    use crate::tools::handlers::WebSearchHandler;
     use std::sync::Arc;
 
     let mut builder = ToolRegistryBuilder::new();
@@ -1316,6 +1882,21 @@ pub(crate) fn build_specs(
         builder.register_handler("shell_command", shell_command_handler);
     }
 
+    if config.background_jobs_tools {
+        let background_job_handler = Arc::new(BackgroundJobHandler);
+        builder.push_spec(create_start_job_tool(config.request_rule_enabled));
+        builder.push_spec(create_read_job_output_tool());
+        builder.push_spec(create_stop_job_tool());
+        builder.register_handler("start_job", background_job_handler.clone());
+        builder.register_handler("read_job_output", background_job_handler.clone());
+        builder.register_handler("stop_job", background_job_handler);
+    }
+
+    if config.read_output_tool {
+        builder.push_spec_with_parallel_support(create_read_output_tool(), true);
+        builder.register_handler("read_output", Arc::new(ReadOutputHandler));
+    }
+
     builder.push_spec_with_parallel_support(create_list_mcp_resources_tool(), true);
     builder.push_spec_with_parallel_support(create_list_mcp_resource_templates_tool(), true);
     builder.push_spec_with_parallel_support(create_read_mcp_resource_tool(), true);
@@ -1326,6 +1907,31 @@ pub(crate) fn build_specs(
     builder.push_spec(PLAN_TOOL.clone());
     builder.register_handler("update_plan", plan_handler);
 
+    if config.project_memory_tool {
+        builder.push_spec(create_memory_tool());
+        builder.register_handler("memory", Arc::new(MemoryHandler));
+    }
+
+    if config.git_commit_tool {
+        builder.push_spec(create_git_commit_tool());
+        builder.register_handler("git_commit", Arc::new(GitCommitHandler));
+    }
+
+    if config.pull_request_tool {
+        builder.push_spec(create_pull_request_tool());
+        builder.register_handler("create_pull_request", Arc::new(PullRequestHandler));
+    }
+
+    if config.search_code_tool {
+        builder.push_spec(create_search_code_tool());
+        builder.register_handler("search_code", Arc::new(SearchCodeHandler));
+    }
+
+    if config.edit_definition_tool {
+        builder.push_spec(create_edit_definition_tool());
+        builder.register_handler("edit_definition", Arc::new(EditDefinitionHandler));
+    }
+
     if config.collaboration_modes_tools {
         builder.push_spec(create_request_user_input_tool());
         builder.register_handler("request_user_input", request_user_input_handler);
@@ -1381,6 +1987,12 @@ pub(crate) fn build_specs(
     }
 
     match config.web_search_mode {
+        Some(WebSearchMode::Cached | WebSearchMode::Live)
+            if config.web_search_backend != WebSearchBackend::Native =>
+        {
+            builder.push_spec_with_parallel_support(create_web_search_tool(), true);
+            builder.register_handler("web_search", Arc::new(WebSearchHandler));
+        }
         Some(WebSearchMode::Cached) => {
             builder.push_spec(ToolSpec::WebSearch {
                 external_web_access: Some(false),
@@ -1394,9 +2006,19 @@ pub(crate) fn build_specs(
         Some(WebSearchMode::Disabled) | None => {}
     }
 
+    if config.fetch_url_tool {
+        builder.push_spec_with_parallel_support(create_fetch_url_tool(), true);
+        builder.register_handler("fetch_url", Arc::new(FetchUrlHandler));
+    }
+
     builder.push_spec_with_parallel_support(create_view_image_tool(), true);
     builder.register_handler("view_image", view_image_handler);
 
+    if config.open_file_tool {
+        builder.push_spec(create_open_file_tool());
+        builder.register_handler("open_file", Arc::new(OpenFileHandler));
+    }
+
     if config.collab_tools {
         let collab_handler = Arc::new(CollabHandler);
         builder.push_spec(create_spawn_agent_tool());
@@ -1409,6 +2031,11 @@ pub(crate) fn build_specs(
         builder.register_handler("close_agent", collab_handler);
     }
 
+    // MCP tools are filtered by server when they are listed; the filter here
+    // covers the built-in tools, including their handlers, so a disabled tool
+    // cannot be called even by name.
+    builder.retain(|name| config.tool_filter.allows(name));
+
     if let Some(mcp_tools) = mcp_tools {
         let mut entries: Vec<(String, mcp_types::Tool)> = mcp_tools.into_iter().collect();
         entries.sort_by(|a, b| a.0.cmp(&b.0));
@@ -1626,6 +2253,50 @@ mod tests {
         );
     }
 
+    #[test]
+    fn feature_gated_tools_require_their_feature() {
+        let config = test_config();
+        let model_info = ModelsManager::construct_model_info_offline("gpt-5-codex", &config);
+        let cases: &[(Feature, &[&str])] = &[
+            (Feature::ProjectMemory, &["memory"]),
+            (Feature::OpenFileTool, &["open_file"]),
+            (Feature::GitCommitTool, &["git_commit"]),
+            (Feature::PullRequestTool, &["create_pull_request"]),
+            (Feature::CodeSearch, &["search_code"]),
+            (Feature::StructuredEdit, &["edit_definition"]),
+            (
+                Feature::BackgroundJobs,
+                &["start_job", "read_job_output", "stop_job"],
+            ),
+            (Feature::OutputPaging, &["read_output"]),
+            (Feature::FetchUrl, &["fetch_url"]),
+        ];
+        for (feature, tool_names) in cases {
+            let mut features = Features::with_defaults();
+            let tools_config = ToolsConfig::new(&ToolsConfigParams {
+                model_info: &model_info,
+                features: &features,
+                web_search_mode: Some(WebSearchMode::Cached),
+            });
+            let (tools, _) = build_specs(&tools_config, None, &[]).build();
+            for name in *tool_names {
+                assert!(
+                    !tools.iter().any(|t| t.spec.name() == *name),
+                    "{name} should be disabled when {feature:?} is off"
+                );
+            }
+
+            features.enable(*feature);
+            let tools_config = ToolsConfig::new(&ToolsConfigParams {
+                model_info: &model_info,
+                features: &features,
+                web_search_mode: Some(WebSearchMode::Cached),
+            });
+            let (tools, _) = build_specs(&tools_config, None, &[]).build();
+            assert_contains_tool_names(&tools, tool_names);
+        }
+    }
+
     #[test]
     fn request_user_input_requires_collaboration_modes_feature() {
         let config = test_config();
@@ -1715,6 +2386,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn web_search_backend_replaces_native_search_with_function_tool() {
+        let config = test_config();
+        let model_info = ModelsManager::construct_model_info_offline("gpt-5-codex", &config);
+        let features = Features::with_defaults();
+
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Live),
+        })
+        .with_web_search_backend(WebSearchBackend::Searxng {
+            url: "https://searx.example.com".to_string(),
+        });
+        let (tools, registry) = build_specs(&tools_config, None, &[]).build();
+
+        let tool = find_tool(&tools, "web_search");
+        assert_eq!(tool.spec, create_web_search_tool());
+        assert!(registry.handler("web_search").is_some());
+    }
+
+    #[test]
+    fn tool_filter_removes_specs_and_handlers() {
+        let config = test_config();
+        let model_info = ModelsManager::construct_model_info_offline("gpt-5-codex", &config);
+        let features = Features::with_defaults();
+
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Live),
+        })
+        .with_tool_filter(ToolFilter {
+            enabled: None,
+            disabled: vec![
+                "shell".to_string(),
+                "apply_patch".to_string(),
+                "web_search".to_string(),
+            ],
+        });
+        let (tools, registry) = build_specs(&tools_config, None, &[]).build();
+
+        let names: Vec<&str> = tools.iter().map(|tool| tool_name(&tool.spec)).collect();
+        for removed in ["shell_command", "apply_patch", "web_search"] {
+            assert!(!names.contains(&removed), "{removed} still offered");
+        }
+        assert!(names.contains(&"update_plan"));
+        assert!(registry.handler("shell").is_none());
+        assert!(registry.handler("apply_patch").is_none());
+    }
+
     #[test]
     fn test_build_specs_gpt5_codex_default() {
         let mut features = Features::with_defaults();
@@ -2635,4 +3357,40 @@ Examples of valid command strings:
             })]
         );
     }
+
+    #[test]
+    fn anthropic_tools_use_input_schema() {
+        let properties =
+            BTreeMap::from([("foo".to_string(), JsonSchema::String { description: None })]);
+        let tools = vec![
+            ToolSpec::Function(ResponsesApiTool {
+                name: "demo".to_string(),
+                description: "A demo tool".to_string(),
+                strict: false,
+                parameters: JsonSchema::Object {
+                    properties,
+                    required: None,
+                    additional_properties: None,
+                },
+            }),
+            ToolSpec::WebSearch {
+                external_web_access: None,
+            },
+        ];
+
+        let anthropic_json = create_tools_json_for_anthropic_api(&tools).unwrap();
+        assert_eq!(
+            anthropic_json,
+            vec![json!({
+                "name": "demo",
+                "description": "A demo tool",
+                "input_schema": {
+                    "type": "object",
+                    "properties": {
+                        "foo": { "type": "string" }
+                    },
+                },
+            })]
+        );
+    }
 }
@@ -2,9 +2,31 @@ use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 
+use codex_api::ReqwestTransport;
+use codex_client::ApiRecorder;
+use codex_client::HttpTransport;
+use codex_client::RecordingTransport;
+use codex_client::ReplayTransport;
+use codex_protocol::ThreadId;
+
+use crate::config::Config;
+use crate::default_client::build_reqwest_client;
+
 #[derive(Clone, Debug, Default)]
 pub struct TransportManager {
     disable_websockets: Arc<AtomicBool>,
+    api_capture: ApiCapture,
+}
+
+/// Debug capture of the session's model API traffic.
+#[derive(Clone, Debug, Default)]
+enum ApiCapture {
+    #[default]
+    Off,
+    /// `--record-api`: requests and responses are appended to a recording.
+    Record(ApiRecorder),
+    /// `--replay-api`: responses are served from a recording.
+    Replay(Arc<ReplayTransport>),
 }
 
 impl TransportManager {
@@ -12,11 +34,47 @@ impl TransportManager {
         Self::default()
     }
 
+    /// A manager that records or replays API traffic as `config` asks.
+    /// Replay wins when both are set.
+    pub(crate) fn for_session(config: &Config, conversation_id: ThreadId) -> std::io::Result<Self> {
+        let api_capture = if let Some(path) = config.replay_api_path.as_ref() {
+            ApiCapture::Replay(Arc::new(ReplayTransport::load(path)?))
+        } else if let Some(dir) = config.record_api_dir.as_ref() {
+            let timestamp = chrono::Utc::now().format("%Y-%m-%dT%H-%M-%S");
+            let recorder =
+                ApiRecorder::create(&dir.join(format!("api-{timestamp}-{conversation_id}.jsonl")))?;
+            tracing::info!(path = %recorder.path().display(), "recording model API traffic");
+            ApiCapture::Record(recorder)
+        } else {
+            ApiCapture::Off
+        };
+        Ok(Self {
+            disable_websockets: Arc::default(),
+            api_capture,
+        })
+    }
+
+    /// WebSocket traffic is not captured, so recording and replay stay on HTTP.
     pub fn disable_websockets(&self) -> bool {
-        self.disable_websockets.load(Ordering::Relaxed)
+        match self.api_capture {
+            ApiCapture::Off => self.disable_websockets.load(Ordering::Relaxed),
+            ApiCapture::Record(_) | ApiCapture::Replay(_) => true,
+        }
     }
 
     pub fn activate_http_fallback(&self, websocket_enabled: bool) -> bool {
         websocket_enabled && !self.disable_websockets.swap(true, Ordering::Relaxed)
     }
+
+    /// The transport model API requests are sent through.
+    pub(crate) fn http_transport(&self) -> Arc<dyn HttpTransport> {
+        match &self.api_capture {
+            ApiCapture::Off => Arc::new(ReqwestTransport::new(build_reqwest_client())),
+            ApiCapture::Record(recorder) => Arc::new(RecordingTransport::new(
+                ReqwestTransport::new(build_reqwest_client()),
+                recorder.clone(),
+            )),
+            ApiCapture::Replay(replay) => replay.clone(),
+        }
+    }
 }
@@ -0,0 +1,474 @@
+//! Session-scoped trash for files deleted by the agent.
+//!
+//! When enabled via `[trash]` in config.toml, files that `apply_patch` deletes
+//! or overwrites with a move are copied into `CODEX_HOME/trash/<thread_id>/`
+//! before the patch runs so they can be restored with `/trash restore`.
+//! Deletions made by shell commands (`rm` and friends) are caught after the
+//! command exits: files in the turn's checkpoint (the ghost snapshot taken with
+//! the `undo` feature) that are now missing are copied out of the checkpoint.
+//! Files a command deletes without a checkpoint, or that were created and
+//! deleted within the same turn, cannot be recovered. The trash is only purged
+//! when the client sends `Op::PurgeTrash`, which the TUI does after the user
+//! confirms.
+
+use std::collections::HashSet;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::TrashEntry;
+use tracing::warn;
+
+use crate::codex::Session;
+
+/// Name of the directory (under `CODEX_HOME`) that holds per-session trash.
+pub(crate) const TRASH_SUBDIR: &str = "trash";
+
+#[derive(Debug)]
+pub(crate) struct SessionTrash {
+    root: PathBuf,
+    entries: Vec<StashedFile>,
+    next_slot: u64,
+    /// First slot used after the latest checkpoint; deletions stashed from
+    /// that slot on already belong to the current turn.
+    checkpoint_slot: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct StashedFile {
+    original_path: PathBuf,
+    trash_path: PathBuf,
+    slot: u64,
+}
+
+/// Outcome of restoring a single trash entry.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum RestoreOutcome {
+    Restored(PathBuf),
+    Skipped { path: PathBuf, reason: String },
+}
+
+impl SessionTrash {
+    pub(crate) fn new(codex_home: &Path, thread_id: ThreadId) -> Self {
+        Self {
+            root: codex_home.join(TRASH_SUBDIR).join(thread_id.to_string()),
+            entries: Vec::new(),
+            next_slot: 0,
+            checkpoint_slot: 0,
+        }
+    }
+
+    /// Marks the start of a turn whose checkpoint was just taken.
+    pub(crate) fn start_checkpoint(&mut self) {
+        self.checkpoint_slot = self.next_slot;
+    }
+
+    /// Copies `path` into the trash so it survives a subsequent deletion.
+    ///
+    /// Each stashed file gets its own numbered slot so deleting the same path
+    /// twice in a session keeps both versions.
+    pub(crate) fn stash(&mut self, path: &Path) -> io::Result<()> {
+        self.stash_with(path, |trash_path| {
+            std::fs::copy(path, trash_path).map(|_| ())
+        })
+    }
+
+    /// Stores `contents` as the deleted copy of `path`, unless a deletion of
+    /// `path` was already stashed since the latest checkpoint. Returns whether
+    /// a copy was stored.
+    pub(crate) fn stash_deleted_contents(
+        &mut self,
+        path: &Path,
+        contents: &[u8],
+    ) -> io::Result<bool> {
+        let already_stashed = self
+            .entries
+            .iter()
+            .any(|entry| entry.original_path == path && entry.slot >= self.checkpoint_slot);
+        if already_stashed {
+            return Ok(false);
+        }
+        self.stash_with(path, |trash_path| std::fs::write(trash_path, contents))?;
+        Ok(true)
+    }
+
+    fn stash_with(
+        &mut self,
+        path: &Path,
+        write: impl FnOnce(&Path) -> io::Result<()>,
+    ) -> io::Result<()> {
+        let file_name = path.file_name().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("cannot trash {}: path has no file name", path.display()),
+            )
+        })?;
+        // Skip slots left behind by an earlier run of a resumed session.
+        while self.root.join(self.next_slot.to_string()).exists() {
+            self.next_slot += 1;
+        }
+        let slot_dir = self.root.join(self.next_slot.to_string());
+        std::fs::create_dir_all(&slot_dir)?;
+        let trash_path = slot_dir.join(file_name);
+        if let Err(err) = write(&trash_path) {
+            let _ = std::fs::remove_dir_all(&slot_dir);
+            return Err(err);
+        }
+        self.entries.push(StashedFile {
+            original_path: path.to_path_buf(),
+            trash_path,
+            slot: self.next_slot,
+        });
+        self.next_slot += 1;
+        Ok(())
+    }
+
+    /// Number of files currently held in the trash.
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Drops entries stashed at or after `start` whose original file is still
+    /// there unchanged, e.g. because the patch that would have deleted or
+    /// overwritten it failed or is about to be retried.
+    pub(crate) fn discard_unapplied(&mut self, start: usize) {
+        let mut index = 0;
+        self.entries.retain(|entry| {
+            let keep = index < start || !same_contents(&entry.original_path, &entry.trash_path);
+            index += 1;
+            if !keep {
+                remove_slot(&entry.trash_path);
+            }
+            keep
+        });
+    }
+
+    /// Restores the most recently trashed copy of each requested path. An
+    /// empty `paths` restores everything in the trash.
+    pub(crate) fn restore(&mut self, paths: &[PathBuf]) -> Vec<RestoreOutcome> {
+        let mut outcomes = Vec::new();
+        let mut seen = HashSet::new();
+        for index in (0..self.entries.len()).rev() {
+            let original_path = self.entries[index].original_path.clone();
+            if !paths.is_empty() && !paths.contains(&original_path) {
+                continue;
+            }
+            if !seen.insert(original_path.clone()) {
+                continue;
+            }
+            if original_path.exists() {
+                outcomes.push(RestoreOutcome::Skipped {
+                    path: original_path,
+                    reason: "a file already exists at this path".to_string(),
+                });
+                continue;
+            }
+            match restore_file(&self.entries[index].trash_path, &original_path) {
+                Ok(()) => {
+                    let removed = self.entries.remove(index);
+                    remove_slot(&removed.trash_path);
+                    outcomes.push(RestoreOutcome::Restored(original_path));
+                }
+                Err(err) => outcomes.push(RestoreOutcome::Skipped {
+                    path: original_path,
+                    reason: err.to_string(),
+                }),
+            }
+        }
+        for path in paths {
+            if !seen.contains(path) {
+                outcomes.push(RestoreOutcome::Skipped {
+                    path: path.clone(),
+                    reason: "not found in trash".to_string(),
+                });
+            }
+        }
+        outcomes
+    }
+
+    /// Permanently deletes everything in the trash, returning how many files
+    /// were purged.
+    pub(crate) fn purge(&mut self) -> io::Result<usize> {
+        let purged = self.entries.len();
+        self.entries.clear();
+        self.next_slot = 0;
+        self.checkpoint_slot = 0;
+        match std::fs::remove_dir_all(&self.root) {
+            Ok(()) => Ok(purged),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(purged),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub(crate) fn entries(&self) -> Vec<TrashEntry> {
+        self.entries
+            .iter()
+            .map(|entry| TrashEntry {
+                path: entry.original_path.clone(),
+                trash_path: entry.trash_path.clone(),
+            })
+            .collect()
+    }
+}
+
+/// Copies files that a shell command deleted into the trash, taking their
+/// contents from the current turn's checkpoint. Only files under `cwd` that
+/// were in the checkpoint and are missing from disk now are considered.
+pub(crate) async fn stash_command_deletions(session: &Session, cwd: &Path) {
+    if session.services.trash.lock().await.is_none() {
+        return;
+    }
+    let Some(checkpoint) = session.current_turn_ghost_snapshot().await else {
+        return;
+    };
+    let cwd = cwd.to_path_buf();
+    let commit = checkpoint.id().to_string();
+    let deleted = match tokio::task::spawn_blocking(move || deleted_since(&cwd, &commit)).await {
+        Ok(Ok(deleted)) => deleted,
+        Ok(Err(err)) => {
+            warn!("failed to compare the workspace with its checkpoint: {err}");
+            return;
+        }
+        Err(err) => {
+            warn!("checkpoint comparison panicked: {err}");
+            return;
+        }
+    };
+    let mut guard = session.services.trash.lock().await;
+    let Some(trash) = guard.as_mut() else {
+        return;
+    };
+    for (path, contents) in deleted {
+        if let Err(err) = trash.stash_deleted_contents(&path, &contents) {
+            warn!("failed to move {} to trash: {err}", path.display());
+        }
+    }
+}
+
+/// Regular files under `cwd` recorded in `commit` that no longer exist, with
+/// their contents at `commit`.
+fn deleted_since(cwd: &Path, commit: &str) -> io::Result<Vec<(PathBuf, Vec<u8>)>> {
+    // Without `--full-name`, paths are limited to and relative to `cwd`.
+    let listing = git_stdout(cwd, &["ls-tree", "-r", "-z", commit])?;
+    let mut deleted = Vec::new();
+    for entry in listing.split(|byte| *byte == 0) {
+        // `<mode> SP <type> SP <object> TAB <path>`
+        let entry = String::from_utf8_lossy(entry);
+        let Some((meta, relative)) = entry.split_once('\t') else {
+            continue;
+        };
+        let mut fields = meta.split(' ');
+        let (Some(mode), Some("blob"), Some(object)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        // Symlinks (120000) are not files we can restore by copying.
+        if !matches!(mode, "100644" | "100755") {
+            continue;
+        }
+        let path = cwd.join(relative);
+        match std::fs::symlink_metadata(&path) {
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                deleted.push((path, git_stdout(cwd, &["cat-file", "blob", object])?));
+            }
+            _ => {}
+        }
+    }
+    Ok(deleted)
+}
+
+fn git_stdout(cwd: &Path, args: &[&str]) -> io::Result<Vec<u8>> {
+    let output = Command::new("git").current_dir(cwd).args(args).output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(output.stdout)
+}
+
+/// True when both files exist and hold the same bytes.
+fn same_contents(a: &Path, b: &Path) -> bool {
+    match (std::fs::read(a), std::fs::read(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+fn restore_file(trash_path: &Path, original_path: &Path) -> io::Result<()> {
+    if let Some(parent) = original_path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        std::fs::create_dir_all(parent)?;
+    }
+    // The trash usually lives on a different filesystem than the workspace,
+    // so fall back to copying when a rename is not possible.
+    if std::fs::rename(trash_path, original_path).is_err() {
+        std::fs::copy(trash_path, original_path)?;
+    }
+    Ok(())
+}
+
+fn remove_slot(trash_path: &Path) {
+    if let Some(slot) = trash_path.parent() {
+        let _ = std::fs::remove_dir_all(slot);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    fn trash_for(codex_home: &Path) -> SessionTrash {
+        SessionTrash::new(codex_home, ThreadId::new())
+    }
+
+    #[test]
+    fn stash_then_restore_round_trips_contents() {
+        let codex_home = tempdir().expect("codex home");
+        let workspace = tempdir().expect("workspace");
+        let path = workspace.path().join("src/lib.rs");
+        std::fs::create_dir_all(path.parent().expect("parent")).expect("mkdir");
+        std::fs::write(&path, "fn main() {}\n").expect("write");
+
+        let mut trash = trash_for(codex_home.path());
+        trash.stash(&path).expect("stash");
+        std::fs::remove_file(&path).expect("delete");
+        trash.discard_unapplied(0);
+        assert_eq!(trash.len(), 1);
+
+        let outcomes = trash.restore(&[]);
+        assert_eq!(outcomes, vec![RestoreOutcome::Restored(path.clone())]);
+        assert_eq!(
+            std::fs::read_to_string(&path).expect("read"),
+            "fn main() {}\n"
+        );
+        assert_eq!(trash.entries(), Vec::new());
+    }
+
+    #[test]
+    fn discard_unapplied_drops_files_that_were_not_deleted() {
+        let codex_home = tempdir().expect("codex home");
+        let workspace = tempdir().expect("workspace");
+        let path = workspace.path().join("keep.txt");
+        std::fs::write(&path, "still here").expect("write");
+
+        let mut trash = trash_for(codex_home.path());
+        trash.stash(&path).expect("stash");
+        trash.discard_unapplied(0);
+
+        assert_eq!(trash.entries(), Vec::new());
+    }
+
+    #[test]
+    fn discard_unapplied_keeps_overwritten_files() {
+        let codex_home = tempdir().expect("codex home");
+        let workspace = tempdir().expect("workspace");
+        let path = workspace.path().join("target.txt");
+        std::fs::write(&path, "before move").expect("write");
+
+        let mut trash = trash_for(codex_home.path());
+        trash.stash(&path).expect("stash");
+        std::fs::write(&path, "moved here").expect("overwrite");
+        trash.discard_unapplied(0);
+
+        assert_eq!(trash.len(), 1);
+    }
+
+    #[test]
+    fn deleted_contents_are_stashed_once_per_checkpoint() {
+        let codex_home = tempdir().expect("codex home");
+        let path = PathBuf::from("/workspace/gone.txt");
+
+        let mut trash = trash_for(codex_home.path());
+        assert!(trash.stash_deleted_contents(&path, b"v1").expect("stash"));
+        assert!(!trash.stash_deleted_contents(&path, b"v1").expect("stash"));
+
+        trash.start_checkpoint();
+        assert!(trash.stash_deleted_contents(&path, b"v2").expect("stash"));
+        assert_eq!(trash.len(), 2);
+        assert_eq!(
+            std::fs::read(&trash.entries()[1].trash_path).expect("read"),
+            b"v2"
+        );
+    }
+
+    #[test]
+    fn deleted_since_recovers_files_removed_after_the_checkpoint() {
+        let repo = tempdir().expect("repo");
+        let git = |args: &[&str]| {
+            git_stdout(repo.path(), args).expect("git");
+        };
+        git(&["init", "--quiet"]);
+        std::fs::create_dir_all(repo.path().join("src")).expect("mkdir");
+        std::fs::write(repo.path().join("src/lib.rs"), "fn lib() {}\n").expect("write");
+        std::fs::write(repo.path().join("keep.txt"), "keep\n").expect("write");
+        git(&["add", "--all"]);
+        git(&[
+            "-c",
+            "user.name=Codex",
+            "-c",
+            "user.email=codex@example.com",
+            "commit",
+            "--quiet",
+            "-m",
+            "checkpoint",
+        ]);
+        let commit =
+            String::from_utf8(git_stdout(repo.path(), &["rev-parse", "HEAD"]).expect("rev-parse"))
+                .expect("utf8");
+
+        std::fs::remove_dir_all(repo.path().join("src")).expect("rm -r");
+        let deleted = deleted_since(repo.path(), commit.trim()).expect("compare");
+
+        assert_eq!(
+            deleted,
+            vec![(repo.path().join("src/lib.rs"), b"fn lib() {}\n".to_vec())]
+        );
+    }
+
+    #[test]
+    fn restore_refuses_to_overwrite_existing_files() {
+        let codex_home = tempdir().expect("codex home");
+        let workspace = tempdir().expect("workspace");
+        let path = workspace.path().join("a.txt");
+        std::fs::write(&path, "old").expect("write");
+
+        let mut trash = trash_for(codex_home.path());
+        trash.stash(&path).expect("stash");
+        std::fs::write(&path, "new").expect("overwrite");
+
+        let outcomes = trash.restore(std::slice::from_ref(&path));
+        assert_eq!(
+            outcomes,
+            vec![RestoreOutcome::Skipped {
+                path: path.clone(),
+                reason: "a file already exists at this path".to_string(),
+            }]
+        );
+        assert_eq!(std::fs::read_to_string(&path).expect("read"), "new");
+    }
+
+    #[test]
+    fn purge_removes_trash_directory() {
+        let codex_home = tempdir().expect("codex home");
+        let workspace = tempdir().expect("workspace");
+        let path = workspace.path().join("gone.txt");
+        std::fs::write(&path, "bye").expect("write");
+
+        let mut trash = trash_for(codex_home.path());
+        trash.stash(&path).expect("stash");
+        std::fs::remove_file(&path).expect("delete");
+
+        assert_eq!(trash.purge().expect("purge"), 1);
+        assert_eq!(trash.entries(), Vec::new());
+        assert!(!trash.root.exists());
+    }
+}
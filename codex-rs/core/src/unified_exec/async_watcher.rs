@@ -173,7 +173,8 @@ async fn process_chunk(
 
 /// Emit an ExecCommandEnd event for a unified exec session, using the transcript
 /// as the primary source of aggregated_output and falling back to the provided
-/// text when the transcript is empty.
+/// text when the transcript is empty. Returns the diff against the previous run
+/// of the same command, if it ran before in this session.
 #[allow(clippy::too_many_arguments)]
 pub(crate) async fn emit_exec_end_for_unified_exec(
     session_ref: Arc<Session>,
@@ -186,7 +187,7 @@ pub(crate) async fn emit_exec_end_for_unified_exec(
     fallback_output: String,
     exit_code: i32,
     duration: Duration,
-) {
+) -> Option<String> {
     let aggregated_output = resolve_aggregated_output(&transcript, fallback_output).await;
     let output = ExecToolCallOutput {
         exit_code,
@@ -203,9 +204,15 @@ pub(crate) async fn emit_exec_end_for_unified_exec(
         ExecCommandSource::UnifiedExecStartup,
         process_id,
     );
+    let previous_run_diff = emitter.previous_run_diff(event_ctx, &output).await;
     emitter
-        .emit(event_ctx, ToolEventStage::Success(output))
+        .emit_with_previous_run_diff(
+            event_ctx,
+            ToolEventStage::Success(output),
+            previous_run_diff.clone(),
+        )
         .await;
+    previous_run_diff
 }
 
 fn split_valid_utf8_prefix(buffer: &mut Vec<u8>) -> Option<Vec<u8>> {
@@ -14,6 +14,10 @@ pub(crate) enum UnifiedExecError {
         "stdin is closed for this session; rerun exec_command with tty=true to keep stdin open"
     )]
     StdinClosed,
+    #[error(
+        "the user has taken over terminal session {process_id}; poll it with empty chars until they hand it back"
+    )]
+    UserControlled { process_id: String },
     #[error("missing command line for unified exec request")]
     MissingCommandLine,
     #[error("Command denied by sandbox: {message}")]
@@ -0,0 +1,301 @@
+//! Background jobs: long-running commands such as dev servers and watch
+//! builds that outlive the turn that started them.
+//!
+//! The model starts a job with `start_job`, which goes through the same
+//! approval and sandbox path as `exec_command` but returns as soon as the
+//! process is up; the user starts one with `/jobs start`, which runs outside
+//! the sandbox like a `!` command. Each job's output is kept in a bounded log
+//! that `read_job_output` reads on demand, optionally only the lines after an
+//! earlier read or those matching a pattern. Unlike `exec_command` processes,
+//! jobs are not terminated when a turn ends, only when stopped or when the
+//! session shuts down.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use codex_protocol::protocol::BackgroundJob;
+use regex_lite::Regex;
+use tokio::sync::Mutex;
+use tokio::time::Duration;
+
+use super::process::OutputHandles;
+use super::process::UnifiedExecProcess;
+
+/// Lines kept per job; older lines are dropped.
+const MAX_LOG_LINES: usize = 5_000;
+/// Longer lines are cut to this many bytes.
+const MAX_LINE_BYTES: usize = 4 * 1024;
+const POST_EXIT_OUTPUT_GRACE: Duration = Duration::from_millis(50);
+
+#[derive(Default)]
+pub(crate) struct BackgroundJobs {
+    store: Mutex<JobStore>,
+}
+
+#[derive(Default)]
+struct JobStore {
+    next_id: u64,
+    /// In start order.
+    jobs: Vec<Job>,
+}
+
+struct Job {
+    id: String,
+    command: String,
+    process: Arc<UnifiedExecProcess>,
+    log: Arc<Mutex<JobLog>>,
+}
+
+/// Which lines of a job's log to return.
+pub(crate) struct JobOutputQuery {
+    /// Only lines after this line number, as returned by an earlier read.
+    pub since_line: Option<usize>,
+    /// Only lines matching this pattern.
+    pub filter: Option<Regex>,
+    /// At most this many lines, the most recent ones.
+    pub max_lines: usize,
+}
+
+/// Lines read from a job's log. Lines are numbered from 1 over the life of
+/// the job, so numbers stay valid as old lines are dropped.
+#[derive(Debug)]
+pub(crate) struct JobOutput {
+    pub job: BackgroundJob,
+    pub lines: Vec<(usize, String)>,
+    /// Selected lines left out because of `max_lines`.
+    pub omitted: usize,
+    /// Number of the last complete line, to pass as `since_line` next time.
+    pub last_line: usize,
+    /// Output after the last newline, such as a prompt.
+    pub partial: String,
+}
+
+impl BackgroundJobs {
+    /// Reserve the id for the next job.
+    pub(crate) async fn allocate_id(&self) -> String {
+        let mut store = self.store.lock().await;
+        store.next_id += 1;
+        format!("job-{}", store.next_id)
+    }
+
+    /// Track `process` as job `id` and start capturing its output.
+    pub(crate) async fn insert(&self, id: String, command: String, process: UnifiedExecProcess) {
+        let process = Arc::new(process);
+        let log = Arc::new(Mutex::new(JobLog::default()));
+        spawn_log_reader(&process, Arc::clone(&log));
+        self.store.lock().await.jobs.push(Job {
+            id,
+            command,
+            process,
+            log,
+        });
+    }
+
+    pub(crate) async fn list(&self) -> Vec<BackgroundJob> {
+        let store = self.store.lock().await;
+        store.jobs.iter().map(Job::summary).collect()
+    }
+
+    pub(crate) async fn read(&self, id: &str, query: &JobOutputQuery) -> Option<JobOutput> {
+        let (job, log) = {
+            let store = self.store.lock().await;
+            let job = store.jobs.iter().find(|job| job.id == id)?;
+            (job.summary(), Arc::clone(&job.log))
+        };
+        let log = log.lock().await;
+        Some(log.read(job, query))
+    }
+
+    /// Terminate job `id` and forget it, returning its final state. Stopping
+    /// a job that already exited just removes it.
+    pub(crate) async fn stop(&self, id: &str) -> Option<BackgroundJob> {
+        let job = {
+            let mut store = self.store.lock().await;
+            let index = store.jobs.iter().position(|job| job.id == id)?;
+            store.jobs.remove(index)
+        };
+        job.process.terminate();
+        Some(job.summary())
+    }
+
+    pub(crate) async fn terminate_all(&self) {
+        let jobs = std::mem::take(&mut self.store.lock().await.jobs);
+        for job in jobs {
+            job.process.terminate();
+        }
+    }
+}
+
+impl Job {
+    fn summary(&self) -> BackgroundJob {
+        BackgroundJob {
+            id: self.id.clone(),
+            command: self.command.clone(),
+            exit_code: if self.process.has_exited() {
+                Some(self.process.exit_code().unwrap_or(-1))
+            } else {
+                None
+            },
+        }
+    }
+}
+
+/// Copy everything the process prints into `log` until it exits. Output is
+/// taken from the process's own buffer, which holds what was printed before
+/// the job was registered.
+fn spawn_log_reader(process: &UnifiedExecProcess, log: Arc<Mutex<JobLog>>) {
+    let OutputHandles {
+        output_buffer,
+        output_notify,
+        cancellation_token,
+    } = process.output_handles();
+    tokio::spawn(async move {
+        loop {
+            let notified = output_notify.notified();
+            let chunks = output_buffer.lock().await.drain_chunks();
+            if !chunks.is_empty() {
+                let mut log = log.lock().await;
+                for chunk in chunks {
+                    log.push(&chunk);
+                }
+                continue;
+            }
+            if cancellation_token.is_cancelled() {
+                // Output can trail the exit by a moment.
+                if tokio::time::timeout(POST_EXIT_OUTPUT_GRACE, notified)
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
+                continue;
+            }
+            tokio::select! {
+                _ = notified => {}
+                _ = cancellation_token.cancelled() => {}
+            }
+        }
+    });
+}
+
+#[derive(Debug, Default)]
+struct JobLog {
+    lines: VecDeque<String>,
+    /// Lines dropped from the front to stay under `MAX_LOG_LINES`.
+    dropped: usize,
+    partial: String,
+}
+
+impl JobLog {
+    fn push(&mut self, chunk: &[u8]) {
+        self.partial.push_str(&String::from_utf8_lossy(chunk));
+        while let Some(newline) = self.partial.find('\n') {
+            let rest = self.partial.split_off(newline + 1);
+            let mut line = std::mem::replace(&mut self.partial, rest);
+            line.pop();
+            if line.ends_with('\r') {
+                line.pop();
+            }
+            if line.len() > MAX_LINE_BYTES {
+                let mut cut = MAX_LINE_BYTES;
+                while !line.is_char_boundary(cut) {
+                    cut -= 1;
+                }
+                line.truncate(cut);
+                line.push_str(" [...]");
+            }
+            self.lines.push_back(line);
+            if self.lines.len() > MAX_LOG_LINES {
+                self.lines.pop_front();
+                self.dropped += 1;
+            }
+        }
+    }
+
+    fn read(&self, job: BackgroundJob, query: &JobOutputQuery) -> JobOutput {
+        let since = query.since_line.unwrap_or(0);
+        let selected: Vec<(usize, &String)> = self
+            .lines
+            .iter()
+            .enumerate()
+            .map(|(index, line)| (self.dropped + index + 1, line))
+            .filter(|(number, line)| {
+                *number > since
+                    && query
+                        .filter
+                        .as_ref()
+                        .is_none_or(|filter| filter.is_match(line))
+            })
+            .collect();
+        let omitted = selected.len().saturating_sub(query.max_lines);
+        JobOutput {
+            job,
+            lines: selected[omitted..]
+                .iter()
+                .map(|(number, line)| (*number, (*line).clone()))
+                .collect(),
+            omitted,
+            last_line: self.dropped + self.lines.len(),
+            partial: self.partial.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn reads_new_matching_and_recent_lines() {
+        let mut log = JobLog::default();
+        log.push(b"compiling\r\nwarning: unused\nlistening on :30");
+        log.push(b"00\nerror: boom\nwatch");
+        let job = BackgroundJob {
+            id: "job-1".to_string(),
+            command: "npm run dev".to_string(),
+            exit_code: None,
+        };
+        let read = |since_line, filter: Option<&str>, max_lines| {
+            log.read(
+                job.clone(),
+                &JobOutputQuery {
+                    since_line,
+                    filter: filter.map(|filter| Regex::new(filter).expect("regex")),
+                    max_lines,
+                },
+            )
+        };
+
+        let all = read(None, None, 10);
+        assert_eq!(
+            all.lines,
+            vec![
+                (1, "compiling".to_string()),
+                (2, "warning: unused".to_string()),
+                (3, "listening on :3000".to_string()),
+                (4, "error: boom".to_string()),
+            ]
+        );
+        assert_eq!((all.omitted, all.last_line), (0, 4));
+        assert_eq!(all.partial, "watch");
+
+        assert_eq!(
+            read(Some(2), None, 10).lines,
+            vec![
+                (3, "listening on :3000".to_string()),
+                (4, "error: boom".to_string()),
+            ]
+        );
+        assert_eq!(
+            read(None, Some("^(warning|error)"), 10).lines,
+            vec![
+                (2, "warning: unused".to_string()),
+                (4, "error: boom".to_string()),
+            ]
+        );
+        let tail = read(None, None, 1);
+        assert_eq!(tail.lines, vec![(4, "error: boom".to_string())]);
+        assert_eq!(tail.omitted, 3);
+    }
+}
@@ -20,6 +20,7 @@
 //! concerns remain isolated here. The implementation is split between:
 //! - `process.rs`: PTY process lifecycle + output buffering.
 //! - `process_manager.rs`: orchestration (approvals, sandboxing, reuse) and request handling.
+//! - `jobs.rs`: background jobs, processes that outlive the turn and are read on demand.
 
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -38,10 +39,14 @@ use crate::sandboxing::SandboxPermissions;
 mod async_watcher;
 mod errors;
 mod head_tail_buffer;
+mod jobs;
 mod process;
 mod process_manager;
 
 pub(crate) use errors::UnifiedExecError;
+pub(crate) use jobs::BackgroundJobs;
+pub(crate) use jobs::JobOutput;
+pub(crate) use jobs::JobOutputQuery;
 pub(crate) use process::UnifiedExecProcess;
 
 pub(crate) const MIN_YIELD_TIME_MS: u64 = 250;
@@ -105,6 +110,9 @@ pub(crate) struct UnifiedExecResponse {
     pub exit_code: Option<i32>,
     pub original_token_count: Option<usize>,
     pub session_command: Option<Vec<String>>,
+    /// Change in output since the previous run of this command, as described
+    /// to the model. Only set when the command finished within this call.
+    pub previous_run_diff: Option<String>,
 }
 
 #[derive(Default)]
@@ -122,12 +130,14 @@ impl ProcessStore {
 
 pub(crate) struct UnifiedExecProcessManager {
     process_store: Mutex<ProcessStore>,
+    jobs: BackgroundJobs,
 }
 
 impl Default for UnifiedExecProcessManager {
     fn default() -> Self {
         Self {
             process_store: Mutex::new(ProcessStore::default()),
+            jobs: BackgroundJobs::default(),
         }
     }
 }
@@ -138,6 +148,9 @@ struct ProcessEntry {
     process_id: String,
     command: Vec<String>,
     tty: bool,
+    /// Set while the user drives the terminal directly; the model may only
+    /// poll it.
+    user_control: bool,
     last_used: tokio::time::Instant,
 }
 
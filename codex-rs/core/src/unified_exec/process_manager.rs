@@ -10,20 +10,29 @@ use tokio::time::Duration;
 use tokio::time::Instant;
 use tokio_util::sync::CancellationToken;
 
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::credentials::apply_credential_policy;
+use crate::exec::ExecExpiration;
+use crate::exec::ExecLimits;
+use crate::exec::SandboxType;
 use crate::exec_env::create_env;
 use crate::exec_policy::ExecApprovalRequest;
 use crate::protocol::ExecCommandSource;
 use crate::sandboxing::ExecEnv;
+use crate::sandboxing::SandboxPermissions;
 use crate::tools::events::ToolEmitter;
 use crate::tools::events::ToolEventCtx;
 use crate::tools::events::ToolEventStage;
 use crate::tools::orchestrator::ToolOrchestrator;
+use crate::tools::output_diff::describe_for_model;
 use crate::tools::runtimes::unified_exec::UnifiedExecRequest as UnifiedExecToolRequest;
 use crate::tools::runtimes::unified_exec::UnifiedExecRuntime;
 use crate::tools::sandboxing::ToolCtx;
 use crate::truncate::TruncationPolicy;
 use crate::truncate::approx_token_count;
 use crate::truncate::formatted_truncate_text;
+use crate::unified_exec::BackgroundJobs;
 use crate::unified_exec::ExecCommandRequest;
 use crate::unified_exec::MAX_UNIFIED_EXEC_PROCESSES;
 use crate::unified_exec::MAX_YIELD_TIME_MS;
@@ -75,6 +84,7 @@ struct PreparedProcessHandles {
     command: Vec<String>,
     process_id: String,
     tty: bool,
+    user_control: bool,
 }
 
 impl UnifiedExecProcessManager {
@@ -179,12 +189,12 @@ impl UnifiedExecProcessManager {
         let has_exited = process.has_exited() || exit_code.is_some();
         let chunk_id = generate_chunk_id();
         let process_id = request.process_id.clone();
-        if has_exited {
+        let previous_run_diff = if has_exited {
             // Short‑lived command: emit ExecCommandEnd immediately using the
             // same helper as the background watcher, so all end events share
             // one implementation.
             let exit = exit_code.unwrap_or(-1);
-            emit_exec_end_for_unified_exec(
+            let previous_run_diff = emit_exec_end_for_unified_exec(
                 Arc::clone(&context.session),
                 Arc::clone(&context.turn),
                 context.call_id.clone(),
@@ -200,6 +210,7 @@ impl UnifiedExecProcessManager {
 
             self.release_process_id(&request.process_id).await;
             process.check_for_sandbox_denial_with_text(&text).await?;
+            previous_run_diff
         } else {
             // Long‑lived command: persist the process so write_stdin can reuse
             // it, and register a background watcher that will emit
@@ -216,6 +227,7 @@ impl UnifiedExecProcessManager {
                 Arc::clone(&transcript),
             )
             .await;
+            None
         };
 
         let original_token_count = approx_token_count(&text);
@@ -233,6 +245,8 @@ impl UnifiedExecProcessManager {
             exit_code,
             original_token_count: Some(original_token_count),
             session_command: Some(request.command.clone()),
+            previous_run_diff: previous_run_diff
+                .map(|diff| describe_for_model(&diff, TruncationPolicy::Tokens(max_tokens))),
         };
 
         Ok(response)
@@ -252,6 +266,7 @@ impl UnifiedExecProcessManager {
             command: session_command,
             process_id,
             tty,
+            user_control,
             ..
         } = self.prepare_process_handles(process_id.as_str()).await?;
 
@@ -259,6 +274,9 @@ impl UnifiedExecProcessManager {
             if !tty {
                 return Err(UnifiedExecError::StdinClosed);
             }
+            if user_control {
+                return Err(UnifiedExecError::UserControlled { process_id });
+            }
             Self::send_input(&writer_tx, request.input.as_bytes()).await?;
             // Give the remote process a brief window to react so that we are
             // more likely to capture its output in the poll below.
@@ -322,6 +340,7 @@ impl UnifiedExecProcessManager {
             exit_code,
             original_token_count: Some(original_token_count),
             session_command: Some(session_command.clone()),
+            previous_run_diff: None,
         };
 
         Ok(response)
@@ -380,9 +399,100 @@ impl UnifiedExecProcessManager {
             command: entry.command.clone(),
             process_id: entry.process_id.clone(),
             tty: entry.tty,
+            user_control: entry.user_control,
         })
     }
 
+    /// Hand the terminal of `process_id` to the user, or back to the model.
+    /// Only processes started with a TTY can be taken over.
+    pub(crate) async fn set_user_control(
+        &self,
+        process_id: &str,
+        user_control: bool,
+    ) -> Result<(), UnifiedExecError> {
+        let mut store = self.process_store.lock().await;
+        let entry =
+            store
+                .processes
+                .get_mut(process_id)
+                .ok_or(UnifiedExecError::UnknownProcessId {
+                    process_id: process_id.to_string(),
+                })?;
+        if !entry.tty {
+            return Err(UnifiedExecError::StdinClosed);
+        }
+        entry.user_control = user_control;
+        entry.last_used = Instant::now();
+        Ok(())
+    }
+
+    /// Forward input the user typed into a terminal they took over.
+    pub(crate) async fn write_user_input(
+        &self,
+        process_id: &str,
+        data: &[u8],
+    ) -> Result<(), UnifiedExecError> {
+        let PreparedProcessHandles { writer_tx, tty, .. } =
+            self.prepare_process_handles(process_id).await?;
+        if !tty {
+            return Err(UnifiedExecError::StdinClosed);
+        }
+        Self::send_input(&writer_tx, data).await
+    }
+
+    pub(crate) fn jobs(&self) -> &BackgroundJobs {
+        &self.jobs
+    }
+
+    /// Start `request.command` as background job `request.process_id`, with
+    /// the same approval and sandbox handling as `exec_command`.
+    pub(crate) async fn start_job(
+        &self,
+        request: &ExecCommandRequest,
+        command_display: String,
+        context: &UnifiedExecContext,
+    ) -> Result<(), UnifiedExecError> {
+        let cwd = request
+            .workdir
+            .clone()
+            .unwrap_or_else(|| context.turn.cwd.clone());
+        let process = self
+            .open_session_with_sandbox(request, cwd, context)
+            .await?;
+        self.jobs
+            .insert(request.process_id.clone(), command_display, process)
+            .await;
+        Ok(())
+    }
+
+    /// Start a background job for the user. Like a `!` command, it runs in
+    /// the user's shell outside the sandbox.
+    pub(crate) async fn start_user_job(
+        &self,
+        session: &Session,
+        turn: &TurnContext,
+        command_line: &str,
+    ) -> Result<String, UnifiedExecError> {
+        let exec_env = ExecEnv {
+            command: session.user_shell().derive_exec_args(command_line, true),
+            cwd: turn.cwd.clone(),
+            env: apply_unified_exec_env(create_env(&turn.shell_environment_policy)),
+            expiration: ExecExpiration::DefaultTimeout,
+            sandbox: SandboxType::None,
+            windows_sandbox_level: turn.windows_sandbox_level,
+            sandbox_permissions: SandboxPermissions::UseDefault,
+            justification: None,
+            arg0: None,
+            limits: ExecLimits::default(),
+        };
+        let process = self.open_session_with_exec_env(&exec_env, false).await?;
+        let id = self.jobs.allocate_id().await;
+        self.jobs
+            .insert(id.clone(), command_line.to_string(), process)
+            .await;
+        Ok(id)
+    }
+
     async fn send_input(
         writer_tx: &mpsc::Sender<Vec<u8>>,
         data: &[u8],
@@ -411,6 +521,7 @@ impl UnifiedExecProcessManager {
             process_id: process_id.clone(),
             command: command.to_vec(),
             tty,
+            user_control: false,
             last_used: started_at,
         };
         let number_processes = {
@@ -483,10 +594,20 @@ impl UnifiedExecProcessManager {
         cwd: PathBuf,
         context: &UnifiedExecContext,
     ) -> Result<UnifiedExecProcess, UnifiedExecError> {
-        let env = apply_unified_exec_env(create_env(&context.turn.shell_environment_policy));
+        let mut env = apply_unified_exec_env(create_env(&context.turn.shell_environment_policy));
+        apply_credential_policy(
+            context.session.as_ref(),
+            context.turn.as_ref(),
+            &context.call_id,
+            "exec_command",
+            &request.command,
+            &mut env,
+        )
+        .await;
         let features = context.session.features();
         let mut orchestrator = ToolOrchestrator::new();
         let mut runtime = UnifiedExecRuntime::new(self);
+        let config = context.turn.client.config();
         let exec_approval_requirement = context
             .session
             .services
@@ -498,6 +619,8 @@ impl UnifiedExecProcessManager {
                 sandbox_policy: &context.turn.sandbox_policy,
                 sandbox_permissions: request.sandbox_permissions,
                 prefix_rule: request.prefix_rule.clone(),
+                safe_commands: &config.approval.safe_commands,
+                approval_rules: &config.approval.rules,
             })
             .await;
         let req = UnifiedExecToolRequest::new(
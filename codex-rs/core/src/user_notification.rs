@@ -1,10 +1,41 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::config::types::NotifyMode;
+use crate::protocol::ApprovalScope;
+use crate::protocol::TokenUsage;
+use crate::redaction::Redactor;
+use crate::util::backoff;
 use serde::Serialize;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Child;
+use tokio::process::ChildStdin;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
 use tracing::error;
 use tracing::warn;
 
+/// Delay before the first restart of a persistent notifier that exited.
+const RESTART_BASE_DELAY: Duration = Duration::from_millis(500);
+/// Upper bound for the restart backoff.
+const RESTART_MAX_DELAY: Duration = Duration::from_secs(30);
+/// A notifier that stays up this long resets the restart backoff.
+const RESTART_RESET_AFTER: Duration = Duration::from_secs(30);
+/// Payloads kept while the notifier is down; older ones are dropped.
+const MAX_QUEUED_LINES: usize = 256;
+/// Writes attempted for one payload before it is dropped.
+const MAX_LINE_ATTEMPTS: u32 = 3;
+/// How long shutdown waits for a persistent notifier to drain its input.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
 #[derive(Debug, Default)]
 pub(crate) struct UserNotifier {
     notify_command: Option<Vec<String>>,
+    persistent: Option<PersistentNotifier>,
+    redactor: Option<Redactor>,
 }
 
 impl UserNotifier {
@@ -17,10 +48,19 @@ impl UserNotifier {
     }
 
     fn invoke_notify(&self, notify_command: &[String], notification: &UserNotification) {
-        let Ok(json) = serde_json::to_string(&notification) else {
+        let Ok(mut payload) = serde_json::to_value(notification) else {
             error!("failed to serialise notification payload");
             return;
         };
+        if let Some(redactor) = &self.redactor {
+            redactor.redact_json(&mut payload);
+        }
+        let json = payload.to_string();
+
+        if let Some(persistent) = &self.persistent {
+            persistent.send(json);
+            return;
+        }
 
         let mut command = std::process::Command::new(&notify_command[0]);
         if notify_command.len() > 1 {
@@ -34,16 +74,220 @@ impl UserNotifier {
         }
     }
 
-    pub(crate) fn new(notify: Option<Vec<String>>) -> Self {
+    /// Sends `session-end` to a persistent notifier, closes its stdin, and
+    /// waits briefly for it to exit. Per-event notifiers have nothing to stop.
+    pub(crate) async fn shutdown(&self, thread_id: String) {
+        if self.persistent.is_none() {
+            return;
+        }
+        self.notify(&UserNotification::SessionEnd { thread_id });
+        if let Some(persistent) = &self.persistent {
+            persistent.close().await;
+        }
+    }
+
+    pub(crate) fn new(
+        notify: Option<Vec<String>>,
+        mode: NotifyMode,
+        redactor: Option<Redactor>,
+    ) -> Self {
+        let persistent = match (&notify, mode) {
+            (Some(command), NotifyMode::Persistent) if !command.is_empty() => {
+                Some(PersistentNotifier::spawn(command.clone()))
+            }
+            _ => None,
+        };
         Self {
             notify_command: notify,
+            persistent,
+            redactor,
+        }
+    }
+}
+
+/// A notify command that runs for the whole session and reads one JSON
+/// payload per line from stdin. The command is restarted with backoff when it
+/// exits or stops accepting input; payloads sent meanwhile are queued, up to
+/// [`MAX_QUEUED_LINES`].
+#[derive(Debug)]
+struct PersistentNotifier {
+    queue: Arc<LineQueue>,
+    task: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl PersistentNotifier {
+    fn spawn(command: Vec<String>) -> Self {
+        let queue = Arc::new(LineQueue::default());
+        let task = tokio::spawn(run_persistent_notifier(command, Arc::clone(&queue)));
+        Self {
+            queue,
+            task: Mutex::new(Some(task)),
+        }
+    }
+
+    fn send(&self, line: String) {
+        self.queue.push(line);
+    }
+
+    async fn close(&self) {
+        // Closing the queue lets the task flush queued lines and close stdin.
+        self.queue.close();
+        let Some(mut task) = self.task.lock().ok().and_then(|mut task| task.take()) else {
+            return;
+        };
+        if tokio::time::timeout(SHUTDOWN_TIMEOUT, &mut task)
+            .await
+            .is_err()
+        {
+            warn!("persistent notifier did not exit in time");
+            task.abort();
+        }
+    }
+}
+
+/// Payloads waiting for the persistent notifier. When the notifier is down
+/// for long enough to fill the queue, the oldest payloads are dropped.
+#[derive(Debug, Default)]
+struct LineQueue {
+    state: Mutex<LineQueueState>,
+    ready: Notify,
+}
+
+#[derive(Debug, Default)]
+struct LineQueueState {
+    lines: VecDeque<String>,
+    closed: bool,
+}
+
+impl LineQueue {
+    fn push(&self, line: String) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        if state.closed {
+            return;
+        }
+        if state.lines.len() == MAX_QUEUED_LINES {
+            state.lines.pop_front();
+            warn!("persistent notifier queue is full; dropping the oldest payload");
+        }
+        state.lines.push_back(line);
+        drop(state);
+        self.ready.notify_one();
+    }
+
+    fn close(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.closed = true;
+        }
+        self.ready.notify_one();
+    }
+
+    /// Waits for the next payload. Returns `None` once the queue is closed
+    /// and drained.
+    async fn pop(&self) -> Option<String> {
+        loop {
+            {
+                let mut state = self.state.lock().ok()?;
+                if let Some(line) = state.lines.pop_front() {
+                    return Some(line);
+                }
+                if state.closed {
+                    return None;
+                }
+            }
+            self.ready.notified().await;
         }
     }
 }
 
+async fn run_persistent_notifier(command: Vec<String>, queue: Arc<LineQueue>) {
+    // The payload that failed to write, with the number of attempts so far.
+    let mut pending: Option<(String, u32)> = None;
+    let mut failures: u64 = 0;
+    loop {
+        if failures > 0 {
+            tokio::time::sleep(restart_delay(failures)).await;
+        }
+
+        let mut child = match spawn_persistent(&command) {
+            Ok(child) => child,
+            Err(e) => {
+                warn!("failed to spawn notifier '{}': {e}", command[0]);
+                failures += 1;
+                continue;
+            }
+        };
+        let Some(mut stdin) = child.stdin.take() else {
+            failures += 1;
+            continue;
+        };
+        let started = Instant::now();
+
+        loop {
+            let (line, attempts) = match pending.take() {
+                Some(pending) => pending,
+                None => tokio::select! {
+                    line = queue.pop() => match line {
+                        Some(line) => (line, 0),
+                        None => {
+                            drop(stdin);
+                            let _ = tokio::time::timeout(SHUTDOWN_TIMEOUT, child.wait()).await;
+                            return;
+                        }
+                    },
+                    status = child.wait() => {
+                        warn!("notifier '{}' exited ({status:?}); restarting", command[0]);
+                        break;
+                    }
+                },
+            };
+            if let Err(e) = write_line(&mut stdin, &line).await {
+                warn!("failed to write to notifier '{}': {e}", command[0]);
+                let attempts = attempts + 1;
+                if attempts < MAX_LINE_ATTEMPTS {
+                    pending = Some((line, attempts));
+                } else {
+                    warn!(
+                        "dropping a payload notifier '{}' failed to accept",
+                        command[0]
+                    );
+                }
+                break;
+            }
+        }
+
+        // Only a process that stayed up resets the backoff; one that accepts
+        // a line and then crashes keeps backing off.
+        if started.elapsed() >= RESTART_RESET_AFTER {
+            failures = 0;
+        }
+        failures += 1;
+    }
+}
+
+fn spawn_persistent(command: &[String]) -> std::io::Result<Child> {
+    tokio::process::Command::new(&command[0])
+        .args(&command[1..])
+        .stdin(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+}
+
+async fn write_line(stdin: &mut ChildStdin, line: &str) -> std::io::Result<()> {
+    stdin.write_all(line.as_bytes()).await?;
+    stdin.write_all(b"\n").await?;
+    stdin.flush().await
+}
+
+/// Backoff for the `failures`-th consecutive restart (1-based).
+fn restart_delay(failures: u64) -> Duration {
+    backoff(RESTART_BASE_DELAY, failures).min(RESTART_MAX_DELAY)
+}
+
 /// User can configure a program that will receive notifications. Each
 /// notification is serialized as JSON and passed as an argument to the
-/// program.
+/// program, or written as one line to its stdin in persistent mode.
 #[derive(Debug, Clone, PartialEq, Serialize)]
 #[serde(tag = "type", rename_all = "kebab-case")]
 pub(crate) enum UserNotification {
@@ -58,13 +302,73 @@ pub(crate) enum UserNotification {
 
         /// The last message sent by the assistant in the turn.
         last_assistant_message: Option<String>,
+
+        /// Tokens the model requests of this turn used.
+        token_usage: TurnTokenUsage,
+    },
+
+    /// The provider refused the request or a safety system blocked the
+    /// response, so the turn ended without an answer.
+    #[serde(rename_all = "kebab-case")]
+    TurnBlocked {
+        thread_id: String,
+        turn_id: String,
+        cwd: String,
+        category: String,
+        message: String,
+    },
+
+    /// The user answered an approval request.
+    #[serde(rename_all = "kebab-case")]
+    ApprovalDecided {
+        thread_id: String,
+        /// Id of the approval request.
+        call_id: String,
+        /// `exec` or `patch`.
+        kind: &'static str,
+        /// The decision, without the approved command or paths.
+        decision: &'static str,
+        /// How far the approval reaches; `null` when the request was denied.
+        scope: Option<ApprovalScope>,
     },
+
+    /// The session is shutting down. Only sent to persistent notifiers, as
+    /// the last line before their stdin is closed.
+    #[serde(rename_all = "kebab-case")]
+    SessionEnd { thread_id: String },
+}
+
+/// Tokens used by one turn. Cached input tokens were read from the
+/// provider's prompt cache; uncached ones were processed in full.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct TurnTokenUsage {
+    pub(crate) input_tokens: i64,
+    pub(crate) cached_input_tokens: i64,
+    pub(crate) uncached_input_tokens: i64,
+    pub(crate) output_tokens: i64,
+}
+
+impl TurnTokenUsage {
+    /// Usage accumulated between two snapshots of the session's total usage.
+    pub(crate) fn between(start: &TokenUsage, end: &TokenUsage) -> Self {
+        let input_tokens = (end.input_tokens - start.input_tokens).max(0);
+        let cached_input_tokens =
+            (end.cached_input() - start.cached_input()).clamp(0, input_tokens);
+        Self {
+            input_tokens,
+            cached_input_tokens,
+            uncached_input_tokens: input_tokens - cached_input_tokens,
+            output_tokens: (end.output_tokens - start.output_tokens).max(0),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use anyhow::Result;
+    use pretty_assertions::assert_eq;
 
     #[test]
     fn test_user_notification() -> Result<()> {
@@ -76,11 +380,128 @@ mod tests {
             last_assistant_message: Some(
                 "Rename complete and verified `cargo build` succeeds.".to_string(),
             ),
+            token_usage: TurnTokenUsage::between(
+                &TokenUsage {
+                    input_tokens: 1_000,
+                    cached_input_tokens: 200,
+                    output_tokens: 50,
+                    ..Default::default()
+                },
+                &TokenUsage {
+                    input_tokens: 4_000,
+                    cached_input_tokens: 2_600,
+                    output_tokens: 150,
+                    ..Default::default()
+                },
+            ),
+        };
+        let serialized = serde_json::to_string(&notification)?;
+        assert_eq!(
+            serialized,
+            r#"{"type":"agent-turn-complete","thread-id":"b5f6c1c2-1111-2222-3333-444455556666","turn-id":"12345","cwd":"/Users/example/project","input-messages":["Rename `foo` to `bar` and update the callsites."],"last-assistant-message":"Rename complete and verified `cargo build` succeeds.","token-usage":{"input-tokens":3000,"cached-input-tokens":2400,"uncached-input-tokens":600,"output-tokens":100}}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn restart_delay_backs_off_up_to_the_cap() {
+        assert!(restart_delay(1) <= Duration::from_millis(550));
+        assert!(restart_delay(3) >= Duration::from_millis(1800));
+        assert_eq!(restart_delay(20), RESTART_MAX_DELAY);
+    }
+
+    #[tokio::test]
+    async fn line_queue_drops_the_oldest_payload_when_full() {
+        let queue = LineQueue::default();
+        for i in 0..=MAX_QUEUED_LINES {
+            queue.push(i.to_string());
+        }
+        queue.close();
+
+        let mut lines = Vec::new();
+        while let Some(line) = queue.pop().await {
+            lines.push(line);
+        }
+        assert_eq!(lines.len(), MAX_QUEUED_LINES);
+        assert_eq!(lines.first().map(String::as_str), Some("1"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn persistent_notifier_streams_ndjson_and_session_end() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let output = dir.path().join("events.ndjson");
+        let notifier = UserNotifier::new(
+            Some(vec![
+                "sh".to_string(),
+                "-c".to_string(),
+                "cat >> \"$0\"".to_string(),
+                output.display().to_string(),
+            ]),
+            NotifyMode::Persistent,
+            None,
+        );
+
+        notifier.notify(&UserNotification::TurnBlocked {
+            thread_id: "thread".to_string(),
+            turn_id: "1".to_string(),
+            cwd: "/tmp".to_string(),
+            category: "refusal".to_string(),
+            message: "no".to_string(),
+        });
+        notifier.shutdown("thread".to_string()).await;
+
+        let lines = std::fs::read_to_string(&output)?
+            .lines()
+            .map(serde_json::from_str)
+            .collect::<Result<Vec<serde_json::Value>, _>>()?;
+        assert_eq!(
+            lines,
+            vec![
+                serde_json::json!({
+                    "type": "turn-blocked",
+                    "thread-id": "thread",
+                    "turn-id": "1",
+                    "cwd": "/tmp",
+                    "category": "refusal",
+                    "message": "no",
+                }),
+                serde_json::json!({"type": "session-end", "thread-id": "thread"}),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_turn_blocked_notification() -> Result<()> {
+        let notification = UserNotification::TurnBlocked {
+            thread_id: "b5f6c1c2-1111-2222-3333-444455556666".to_string(),
+            turn_id: "12345".to_string(),
+            cwd: "/Users/example/project".to_string(),
+            category: "refusal".to_string(),
+            message: "I can't help with that.".to_string(),
+        };
+        let serialized = serde_json::to_string(&notification)?;
+        assert_eq!(
+            serialized,
+            r#"{"type":"turn-blocked","thread-id":"b5f6c1c2-1111-2222-3333-444455556666","turn-id":"12345","cwd":"/Users/example/project","category":"refusal","message":"I can't help with that."}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_approval_decided_notification() -> Result<()> {
+        let notification = UserNotification::ApprovalDecided {
+            thread_id: "b5f6c1c2-1111-2222-3333-444455556666".to_string(),
+            call_id: "call-1".to_string(),
+            kind: "patch",
+            decision: "approved_for_session",
+            scope: Some(ApprovalScope::Session),
         };
         let serialized = serde_json::to_string(&notification)?;
         assert_eq!(
             serialized,
-            r#"{"type":"agent-turn-complete","thread-id":"b5f6c1c2-1111-2222-3333-444455556666","turn-id":"12345","cwd":"/Users/example/project","input-messages":["Rename `foo` to `bar` and update the callsites."],"last-assistant-message":"Rename complete and verified `cargo build` succeeds."}"#
+            r#"{"type":"approval-decided","thread-id":"b5f6c1c2-1111-2222-3333-444455556666","call-id":"call-1","kind":"patch","decision":"approved_for_session","scope":"session"}"#
         );
         Ok(())
     }
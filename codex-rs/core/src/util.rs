@@ -9,7 +9,6 @@ use tracing::error;
 
 use crate::parse_command::shlex_join;
 
-const INITIAL_DELAY_MS: u64 = 200;
 const BACKOFF_FACTOR: f64 = 2.0;
 
 /// Emit structured feedback metadata as key/value pairs.
@@ -37,9 +36,9 @@ macro_rules! feedback_tags {
     };
 }
 
-pub(crate) fn backoff(attempt: u64) -> Duration {
+pub(crate) fn backoff(initial_delay: Duration, attempt: u64) -> Duration {
     let exp = BACKOFF_FACTOR.powi(attempt.saturating_sub(1) as i32);
-    let base = (INITIAL_DELAY_MS as f64 * exp) as u64;
+    let base = (initial_delay.as_millis() as f64 * exp) as u64;
     let jitter = rand::rng().random_range(0.9..1.1);
     Duration::from_millis((base as f64 * jitter) as u64)
 }
@@ -1,4 +1,16 @@
+use std::io;
+use std::time::Duration;
+
 use codex_protocol::models::WebSearchAction;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::config::types::WebSearchBackend;
+use crate::default_client::create_client;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(20);
+const BRAVE_SEARCH_URL: &str = "https://api.search.brave.com/res/v1/web/search";
+const DEFAULT_BRAVE_API_KEY_ENV: &str = "BRAVE_API_KEY";
 
 fn search_action_detail(query: &Option<String>, queries: &Option<Vec<String>>) -> String {
     query.clone().filter(|q| !q.is_empty()).unwrap_or_else(|| {
@@ -37,3 +49,189 @@ pub fn web_search_detail(action: Option<&WebSearchAction>, query: &str) -> Strin
         detail
     }
 }
+
+/// One result returned by the `web_search` function tool.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct SearchResult {
+    pub title: String,
+    pub url: String,
+    pub snippet: String,
+}
+
+/// Runs `query` against a SearxNG or Brave backend. The native backend is
+/// served by the model provider and never reaches this function.
+pub(crate) async fn search(
+    backend: &WebSearchBackend,
+    query: &str,
+    max_results: usize,
+) -> io::Result<Vec<SearchResult>> {
+    let client = create_client();
+    let request = match backend {
+        WebSearchBackend::Native => {
+            return Err(io::Error::other(
+                "the native web search backend is run by the model provider",
+            ));
+        }
+        WebSearchBackend::Searxng { url } => {
+            let endpoint = format!("{}/search", url.trim_end_matches('/'));
+            let endpoint =
+                url::Url::parse_with_params(&endpoint, &[("q", query), ("format", "json")])
+                    .map_err(|err| io::Error::other(format!("invalid SearxNG url {url}: {err}")))?;
+            client.get(endpoint)
+        }
+        WebSearchBackend::Brave { api_key_env } => {
+            let env_var = api_key_env.as_deref().unwrap_or(DEFAULT_BRAVE_API_KEY_ENV);
+            let api_key = std::env::var(env_var).map_err(|_| {
+                io::Error::other(format!(
+                    "{env_var} is not set; it must hold a Brave Search API key"
+                ))
+            })?;
+            let count = max_results.to_string();
+            let endpoint = url::Url::parse_with_params(
+                BRAVE_SEARCH_URL,
+                &[("q", query), ("count", count.as_str())],
+            )
+            .map_err(io::Error::other)?;
+            client
+                .get(endpoint)
+                .header("Accept", "application/json")
+                .header("X-Subscription-Token", api_key)
+        }
+    };
+    let response = request
+        .timeout(REQUEST_TIMEOUT)
+        .send()
+        .await
+        .map_err(io::Error::other)?;
+    let status = response.status();
+    let body = response.text().await.map_err(io::Error::other)?;
+    if !status.is_success() {
+        return Err(io::Error::other(format!(
+            "search backend returned {status}: {}",
+            body.trim()
+        )));
+    }
+    let mut results = parse_results(backend, &body)?;
+    results.truncate(max_results);
+    Ok(results)
+}
+
+#[derive(Deserialize)]
+struct SearxngResponse {
+    #[serde(default)]
+    results: Vec<SearxngResult>,
+}
+
+#[derive(Deserialize)]
+struct SearxngResult {
+    #[serde(default)]
+    title: String,
+    url: String,
+    #[serde(default)]
+    content: String,
+}
+
+#[derive(Deserialize)]
+struct BraveResponse {
+    #[serde(default)]
+    web: Option<BraveWebResults>,
+}
+
+#[derive(Deserialize)]
+struct BraveWebResults {
+    #[serde(default)]
+    results: Vec<BraveResult>,
+}
+
+#[derive(Deserialize)]
+struct BraveResult {
+    #[serde(default)]
+    title: String,
+    url: String,
+    #[serde(default)]
+    description: String,
+}
+
+fn parse_results(backend: &WebSearchBackend, body: &str) -> io::Result<Vec<SearchResult>> {
+    let invalid = |err: serde_json::Error| {
+        io::Error::other(format!("search backend returned unexpected JSON: {err}"))
+    };
+    let results = match backend {
+        WebSearchBackend::Native => Vec::new(),
+        WebSearchBackend::Searxng { .. } => serde_json::from_str::<SearxngResponse>(body)
+            .map_err(invalid)?
+            .results
+            .into_iter()
+            .map(|result| SearchResult {
+                title: strip_tags(&result.title),
+                url: result.url,
+                snippet: strip_tags(&result.content),
+            })
+            .collect(),
+        WebSearchBackend::Brave { .. } => serde_json::from_str::<BraveResponse>(body)
+            .map_err(invalid)?
+            .web
+            .map(|web| web.results)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|result| SearchResult {
+                title: strip_tags(&result.title),
+                url: result.url,
+                snippet: strip_tags(&result.description),
+            })
+            .collect(),
+    };
+    Ok(results)
+}
+
+/// Removes the `<strong>` and similar highlighting markup backends put in
+/// titles and snippets.
+fn strip_tags(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut in_tag = false;
+    for ch in text.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => out.push(ch),
+            _ => {}
+        }
+    }
+    out.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_searxng_and_brave_results() {
+        let searxng = r#"{"query":"rust","results":[{"title":"Rust","url":"https://www.rust-lang.org/","content":"A <b>language</b> empowering everyone."}]}"#;
+        let brave = r#"{"web":{"results":[{"title":"The <strong>Rust</strong> Book","url":"https://doc.rust-lang.org/book/","description":"Learn Rust."}]}}"#;
+
+        assert_eq!(
+            parse_results(
+                &WebSearchBackend::Searxng {
+                    url: "https://searx.example.com".to_string(),
+                },
+                searxng,
+            )
+            .expect("searxng results"),
+            vec![SearchResult {
+                title: "Rust".to_string(),
+                url: "https://www.rust-lang.org/".to_string(),
+                snippet: "A language empowering everyone.".to_string(),
+            }]
+        );
+        assert_eq!(
+            parse_results(&WebSearchBackend::Brave { api_key_env: None }, brave)
+                .expect("brave results"),
+            vec![SearchResult {
+                title: "The Rust Book".to_string(),
+                url: "https://doc.rust-lang.org/book/".to_string(),
+                snippet: "Learn Rust.".to_string(),
+            }]
+        );
+    }
+}
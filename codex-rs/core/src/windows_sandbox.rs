@@ -143,9 +143,22 @@ mod tests {
     }
 
     #[test]
-    fn no_flags_means_no_sandbox() {
+    fn restricted_token_is_the_default_on_windows() {
         let features = Features::with_defaults();
 
+        let expected = if cfg!(windows) {
+            WindowsSandboxLevel::RestrictedToken
+        } else {
+            WindowsSandboxLevel::Disabled
+        };
+        assert_eq!(WindowsSandboxLevel::from_features(&features), expected);
+    }
+
+    #[test]
+    fn disabled_flags_mean_no_sandbox() {
+        let mut features = Features::with_defaults();
+        features.disable(Feature::WindowsSandbox);
+
         assert_eq!(
             WindowsSandboxLevel::from_features(&features),
             WindowsSandboxLevel::Disabled
@@ -0,0 +1,550 @@
+//! Orchestrator mode: several worker sessions draining a shared task queue.
+//!
+//! Each worker gets its own git worktree on a fresh branch so edits never
+//! collide. A worker claims the next pending task from the queue file, runs it
+//! as a single turn, commits whatever it changed, and moves on until the queue
+//! is empty. When a worker stops, its branch is merged into the checkout the
+//! pool was started from and its worktree is removed. Progress is published as
+//! an [`WorkerPoolSnapshot`] so a client can render a live coordinator view.
+//!
+//! Workers run unattended: they never ask for approval, and any request that
+//! still waits on a user (approvals, `request_user_input`, MCP elicitations,
+//! dynamic tool calls) is declined so the turn can finish.
+
+mod queue;
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use codex_protocol::ThreadId;
+use codex_protocol::dynamic_tools::DynamicToolResponse;
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::ElicitationAction;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::Op;
+use codex_protocol::protocol::ReviewDecision;
+use codex_protocol::request_user_input::RequestUserInputResponse;
+use codex_protocol::user_input::UserInput;
+use tokio::sync::Mutex;
+use tokio::sync::watch;
+use tokio_util::sync::CancellationToken;
+use tracing::warn;
+
+use crate::CodexThread;
+use crate::ThreadManager;
+use crate::attribution;
+use crate::config::Config;
+use crate::error::CodexErr;
+use crate::error::Result as CodexResult;
+use crate::git_info::get_git_repo_root;
+use crate::worktree;
+pub use crate::worktree::MergeOutcome;
+use crate::worktree::WORKTREES_SUBDIR;
+pub use queue::QueuedTask;
+use queue::TaskOutcome;
+use queue::TaskQueue;
+pub use queue::TaskStatus;
+
+const TASK_PROMPT_TEMPLATE: &str = include_str!("../../templates/workers/task_prompt.md");
+
+/// Upper bound on concurrent workers for a single pool.
+pub const MAX_WORKERS: usize = 16;
+
+#[derive(Debug, Clone)]
+pub struct WorkerPoolOptions {
+    pub queue_path: PathBuf,
+    pub workers: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorkerState {
+    Starting,
+    Idle,
+    Running { task_id: String },
+    Finished,
+    Stopped,
+    Failed { message: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkerSnapshot {
+    pub name: String,
+    pub branch: String,
+    pub worktree: PathBuf,
+    pub thread_id: Option<ThreadId>,
+    pub state: WorkerState,
+    pub completed: usize,
+    /// Set once the worker has stopped and its branch was merged back. On
+    /// error the merge was aborted and the branch is kept so it can be merged
+    /// by hand.
+    pub merge: Option<Result<MergeOutcome, String>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorkerPoolSnapshot {
+    pub queue_path: PathBuf,
+    pub workers: Vec<WorkerSnapshot>,
+    /// Queue contents as of the last claim or completion.
+    pub tasks: Vec<QueuedTask>,
+}
+
+impl WorkerPoolSnapshot {
+    /// True once every worker has stopped, successfully or not.
+    pub fn is_finished(&self) -> bool {
+        !self.workers.is_empty()
+            && self.workers.iter().all(|worker| {
+                matches!(
+                    worker.state,
+                    WorkerState::Finished | WorkerState::Stopped | WorkerState::Failed { .. }
+                )
+            })
+    }
+}
+
+/// Handle to a running pool of workers.
+pub struct WorkerPool {
+    snapshot_rx: watch::Receiver<WorkerPoolSnapshot>,
+    cancel: CancellationToken,
+}
+
+impl WorkerPool {
+    /// Validates the queue and repository, then starts `options.workers`
+    /// workers in the background.
+    pub async fn start(
+        thread_manager: Arc<ThreadManager>,
+        config: Config,
+        options: WorkerPoolOptions,
+    ) -> CodexResult<Self> {
+        let repo_root = get_git_repo_root(&config.cwd).ok_or_else(|| {
+            CodexErr::UnsupportedOperation("workers require a git repository".to_string())
+        })?;
+        let queue_path = options.queue_path.clone();
+        let queue = tokio::task::spawn_blocking(move || TaskQueue::open(&queue_path))
+            .await
+            .map_err(|err| CodexErr::Fatal(err.to_string()))??;
+        let tasks = read_tasks(&queue).await?;
+        let worker_count = options.workers.clamp(1, MAX_WORKERS);
+
+        let run_id = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
+        let worktrees_root = config.codex_home.join(WORKTREES_SUBDIR).join(&run_id);
+        let workers: Vec<WorkerSnapshot> = (1..=worker_count)
+            .map(|index| {
+                let name = format!("worker-{index}");
+                WorkerSnapshot {
+                    branch: format!("codex/workers-{run_id}/{name}"),
+                    worktree: worktrees_root.join(&name),
+                    name,
+                    thread_id: None,
+                    state: WorkerState::Starting,
+                    completed: 0,
+                    merge: None,
+                }
+            })
+            .collect();
+
+        let (snapshot_tx, snapshot_rx) = watch::channel(WorkerPoolSnapshot {
+            queue_path: queue.path().to_path_buf(),
+            workers: workers.clone(),
+            tasks,
+        });
+        let shared = Arc::new(PoolShared {
+            thread_manager,
+            config,
+            repo_root,
+            queue,
+            snapshot_tx,
+            merge_lock: Mutex::new(()),
+        });
+        let cancel = CancellationToken::new();
+        for (index, worker) in workers.into_iter().enumerate() {
+            let shared = Arc::clone(&shared);
+            let cancel = cancel.clone();
+            tokio::spawn(async move {
+                let state = match run_worker(&shared, index, &worker, &cancel).await {
+                    Ok(state) => state,
+                    Err(err) => {
+                        warn!("{} failed: {err}", worker.name);
+                        WorkerState::Failed {
+                            message: err.to_string(),
+                        }
+                    }
+                };
+                if let Err(err) = shared.release(&worker.name).await {
+                    warn!("failed to release tasks claimed by {}: {err}", worker.name);
+                }
+                let merge = shared.merge_and_remove(&worker).await;
+                shared.update_worker(index, |snapshot| {
+                    snapshot.state = state;
+                    snapshot.merge = merge;
+                });
+            });
+        }
+
+        Ok(Self {
+            snapshot_rx,
+            cancel,
+        })
+    }
+
+    pub fn subscribe(&self) -> watch::Receiver<WorkerPoolSnapshot> {
+        self.snapshot_rx.clone()
+    }
+
+    pub fn snapshot(&self) -> WorkerPoolSnapshot {
+        self.snapshot_rx.borrow().clone()
+    }
+
+    /// Interrupts every worker. Tasks that were in progress go back to the
+    /// queue as pending.
+    pub fn stop(&self) {
+        self.cancel.cancel();
+    }
+}
+
+struct PoolShared {
+    thread_manager: Arc<ThreadManager>,
+    config: Config,
+    repo_root: PathBuf,
+    queue: TaskQueue,
+    snapshot_tx: watch::Sender<WorkerPoolSnapshot>,
+    /// Serializes merges into the shared checkout.
+    merge_lock: Mutex<()>,
+}
+
+impl PoolShared {
+    fn update_worker(&self, index: usize, apply: impl FnOnce(&mut WorkerSnapshot)) {
+        self.snapshot_tx.send_modify(|snapshot| {
+            if let Some(worker) = snapshot.workers.get_mut(index) {
+                apply(worker);
+            }
+        });
+    }
+
+    async fn refresh_tasks(&self) {
+        match read_tasks(&self.queue).await {
+            Ok(tasks) => self
+                .snapshot_tx
+                .send_modify(|snapshot| snapshot.tasks = tasks),
+            Err(err) => warn!("failed to read task queue: {err}"),
+        }
+    }
+
+    async fn claim(&self, worker: &str) -> CodexResult<Option<QueuedTask>> {
+        let queue = self.queue.clone();
+        let worker = worker.to_string();
+        let task = tokio::task::spawn_blocking(move || queue.claim(&worker))
+            .await
+            .map_err(|err| CodexErr::Fatal(err.to_string()))??;
+        self.refresh_tasks().await;
+        Ok(task)
+    }
+
+    async fn finish(&self, id: &str, outcome: TaskOutcome) -> CodexResult<()> {
+        let queue = self.queue.clone();
+        let id = id.to_string();
+        tokio::task::spawn_blocking(move || queue.finish(&id, outcome))
+            .await
+            .map_err(|err| CodexErr::Fatal(err.to_string()))??;
+        self.refresh_tasks().await;
+        Ok(())
+    }
+
+    /// Merges the worker's branch into the checkout and removes its worktree.
+    /// Returns `None` when the worktree was never created.
+    async fn merge_and_remove(
+        &self,
+        worker: &WorkerSnapshot,
+    ) -> Option<Result<MergeOutcome, String>> {
+        if !tokio::fs::try_exists(&worker.worktree)
+            .await
+            .unwrap_or(false)
+        {
+            return None;
+        }
+        let _guard = self.merge_lock.lock().await;
+        let merge = worktree::merge_into_checkout(&self.repo_root, &worker.branch)
+            .await
+            .map_err(|err| {
+                warn!("failed to merge {}: {err}", worker.branch);
+                err.to_string()
+            });
+        let removed = match &merge {
+            Ok(_) => {
+                worktree::remove_worktree(&self.repo_root, &worker.worktree, &worker.branch).await
+            }
+            Err(_) => worktree::remove_checkout(&self.repo_root, &worker.worktree).await,
+        };
+        if let Err(err) = removed {
+            warn!("failed to remove the worktree of {}: {err}", worker.name);
+        }
+        Some(merge)
+    }
+
+    async fn release(&self, worker: &str) -> CodexResult<()> {
+        let queue = self.queue.clone();
+        let worker = worker.to_string();
+        tokio::task::spawn_blocking(move || queue.release(&worker))
+            .await
+            .map_err(|err| CodexErr::Fatal(err.to_string()))??;
+        self.refresh_tasks().await;
+        Ok(())
+    }
+}
+
+async fn read_tasks(queue: &TaskQueue) -> CodexResult<Vec<QueuedTask>> {
+    let queue = queue.clone();
+    let tasks = tokio::task::spawn_blocking(move || queue.tasks())
+        .await
+        .map_err(|err| CodexErr::Fatal(err.to_string()))??;
+    Ok(tasks)
+}
+
+/// How a single task turn ended.
+enum TurnResult {
+    Completed(Option<String>),
+    Failed(String),
+    Stopped,
+}
+
+async fn run_worker(
+    shared: &PoolShared,
+    index: usize,
+    worker: &WorkerSnapshot,
+    cancel: &CancellationToken,
+) -> CodexResult<WorkerState> {
+    worktree::add_worktree(&shared.repo_root, &worker.worktree, &worker.branch).await?;
+
+    let mut config = shared.config.clone();
+    config.cwd = worktree::worktree_cwd(&shared.repo_root, &config.cwd, &worker.worktree);
+    if let Err(err) = config.approval_policy.set(AskForApproval::Never) {
+        warn!(
+            "{} keeps its configured approval policy: {err}",
+            worker.name
+        );
+    }
+    let new_thread = shared.thread_manager.start_thread(config).await?;
+    let thread = new_thread.thread;
+    shared.update_worker(index, |snapshot| {
+        snapshot.thread_id = Some(new_thread.thread_id);
+    });
+
+    let mut state = WorkerState::Finished;
+    while !cancel.is_cancelled() {
+        let Some(task) = shared.claim(&worker.name).await? else {
+            break;
+        };
+        shared.update_worker(index, |snapshot| {
+            snapshot.state = WorkerState::Running {
+                task_id: task.id.clone(),
+            };
+        });
+
+        let prompt = TASK_PROMPT_TEMPLATE
+            .replace("{worker}", &worker.name)
+            .replace("{task_id}", &task.id)
+            .replace("{prompt}", &task.prompt);
+        thread
+            .submit(Op::UserInput {
+                items: vec![UserInput::Text {
+                    text: prompt,
+                    text_elements: Vec::new(),
+                }],
+                final_output_json_schema: None,
+            })
+            .await?;
+
+        let outcome = match run_task_turn(&thread, cancel).await? {
+            TurnResult::Stopped => {
+                state = WorkerState::Stopped;
+                break;
+            }
+            TurnResult::Failed(message) => TaskOutcome {
+                status: TaskStatus::Failed,
+                result: Some(message),
+                commit: None,
+            },
+            TurnResult::Completed(last_message) => {
+                let subject = task.prompt.lines().next().unwrap_or_default();
+                let message = attribution::append_trailers(
+                    &format!("{}: {subject}", task.id),
+                    &attribution::commit_trailers(
+                        &shared.config.attribution,
+                        &new_thread.thread_id,
+                    ),
+                );
+                match worktree::commit_all(&worker.worktree, &message).await {
+                    Ok(commit) => TaskOutcome {
+                        status: TaskStatus::Done,
+                        result: last_message,
+                        commit,
+                    },
+                    Err(err) => TaskOutcome {
+                        status: TaskStatus::Failed,
+                        result: Some(format!("failed to commit changes: {err}")),
+                        commit: None,
+                    },
+                }
+            }
+        };
+        let done = outcome.status == TaskStatus::Done;
+        shared.finish(&task.id, outcome).await?;
+        shared.update_worker(index, |snapshot| {
+            snapshot.state = WorkerState::Idle;
+            if done {
+                snapshot.completed += 1;
+            }
+        });
+    }
+    if cancel.is_cancelled() {
+        state = WorkerState::Stopped;
+    }
+
+    if let Err(err) = thread.submit(Op::Shutdown).await {
+        warn!("failed to shut down {}: {err}", worker.name);
+    }
+    shared
+        .thread_manager
+        .remove_thread(&new_thread.thread_id)
+        .await;
+    Ok(state)
+}
+
+/// Drives one turn to completion, declining every request that needs a user
+/// and honoring `cancel`.
+async fn run_task_turn(
+    thread: &CodexThread,
+    cancel: &CancellationToken,
+) -> CodexResult<TurnResult> {
+    let mut error = None;
+    let mut interrupted = false;
+    loop {
+        let event = tokio::select! {
+            event = thread.next_event() => event?,
+            _ = cancel.cancelled(), if !interrupted => {
+                interrupted = true;
+                thread.submit(Op::Interrupt).await?;
+                continue;
+            }
+        };
+        match event.msg {
+            EventMsg::TurnComplete(complete) => {
+                return Ok(match error {
+                    Some(message) => TurnResult::Failed(message),
+                    None => TurnResult::Completed(complete.last_agent_message),
+                });
+            }
+            EventMsg::TurnAborted(aborted) => {
+                if interrupted {
+                    return Ok(TurnResult::Stopped);
+                }
+                return Ok(TurnResult::Failed(format!(
+                    "turn aborted: {:?}",
+                    aborted.reason
+                )));
+            }
+            EventMsg::Error(err) => error = Some(err.message),
+            EventMsg::ExecApprovalRequest(_) => {
+                thread
+                    .submit(Op::ExecApproval {
+                        id: event.id,
+                        decision: ReviewDecision::Denied,
+                    })
+                    .await?;
+            }
+            EventMsg::ApplyPatchApprovalRequest(_) => {
+                thread
+                    .submit(Op::PatchApproval {
+                        id: event.id,
+                        decision: ReviewDecision::Denied,
+                    })
+                    .await?;
+            }
+            EventMsg::RequestUserInput(_) => {
+                thread
+                    .submit(Op::UserInputAnswer {
+                        id: event.id,
+                        response: RequestUserInputResponse {
+                            answers: HashMap::new(),
+                        },
+                    })
+                    .await?;
+            }
+            EventMsg::ElicitationRequest(request) => {
+                thread
+                    .submit(Op::ResolveElicitation {
+                        server_name: request.server_name,
+                        request_id: request.id,
+                        decision: ElicitationAction::Decline,
+                    })
+                    .await?;
+            }
+            EventMsg::DynamicToolCallRequest(request) => {
+                thread
+                    .submit(Op::DynamicToolResponse {
+                        id: request.call_id.clone(),
+                        response: DynamicToolResponse {
+                            call_id: request.call_id,
+                            output: "dynamic tools are unavailable to unattended workers"
+                                .to_string(),
+                            success: false,
+                        },
+                    })
+                    .await?;
+            }
+            EventMsg::ShutdownComplete => {
+                return Ok(TurnResult::Failed(
+                    "session shut down unexpectedly".to_string(),
+                ));
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::worktree::init_test_repo;
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn worker_branches_merge_into_the_checkout() {
+        let repo = tempfile::tempdir().expect("repo");
+        let worktrees = tempfile::tempdir().expect("worktrees");
+        init_test_repo(repo.path()).await;
+
+        let busy = worktrees.path().join("worker-1");
+        let idle = worktrees.path().join("worker-2");
+        worktree::add_worktree(repo.path(), &busy, "workers/worker-1")
+            .await
+            .expect("add worktree");
+        worktree::add_worktree(repo.path(), &idle, "workers/worker-2")
+            .await
+            .expect("add worktree");
+        std::fs::write(busy.join("notes.txt"), "from worker\n").expect("write");
+        worktree::commit_all(&busy, "task-1: notes")
+            .await
+            .expect("commit");
+
+        assert_eq!(
+            worktree::merge_into_checkout(repo.path(), "workers/worker-1")
+                .await
+                .expect("merge"),
+            MergeOutcome::Merged {
+                into: "main".to_string(),
+                commits: 1,
+            }
+        );
+        assert_eq!(
+            std::fs::read_to_string(repo.path().join("notes.txt")).expect("merged file"),
+            "from worker\n"
+        );
+        assert_eq!(
+            worktree::merge_into_checkout(repo.path(), "workers/worker-2")
+                .await
+                .expect("merge"),
+            MergeOutcome::NothingToMerge
+        );
+    }
+}
@@ -0,0 +1,232 @@
+//! The shared task queue file that workers drain.
+//!
+//! The queue is a JSON Lines file with one task per line. Hand-written queues
+//! may use plain text lines (each becomes a task prompt) or JSON objects with
+//! at least a `prompt`; tasks without an `id` are numbered by position. Every
+//! claim or completion rewrites the file under an exclusive lock, so several
+//! processes can safely share one queue.
+
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskStatus {
+    #[default]
+    Pending,
+    InProgress,
+    Done,
+    Failed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueuedTask {
+    #[serde(default)]
+    pub id: String,
+    pub prompt: String,
+    #[serde(default)]
+    pub status: TaskStatus,
+    /// Name of the worker that claimed the task.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worker: Option<String>,
+    /// Final agent message for the task, or the failure reason.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<String>,
+    /// Commit holding the task's changes on the worker's branch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+}
+
+/// Outcome recorded for a task once its worker is done with it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TaskOutcome {
+    pub(crate) status: TaskStatus,
+    pub(crate) result: Option<String>,
+    pub(crate) commit: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct TaskQueue {
+    path: PathBuf,
+}
+
+impl TaskQueue {
+    /// Opens the queue at `path`, failing if it cannot be read or parsed.
+    pub(crate) fn open(path: &Path) -> io::Result<Self> {
+        let queue = Self {
+            path: path.to_path_buf(),
+        };
+        queue.tasks()?;
+        Ok(queue)
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub(crate) fn tasks(&self) -> io::Result<Vec<QueuedTask>> {
+        let mut file = File::open(&self.path)?;
+        file.lock_shared()?;
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+        parse_tasks(&contents)
+    }
+
+    /// Marks the first pending task as in progress for `worker` and returns it.
+    pub(crate) fn claim(&self, worker: &str) -> io::Result<Option<QueuedTask>> {
+        self.update(|tasks| {
+            let task = tasks
+                .iter_mut()
+                .find(|task| task.status == TaskStatus::Pending)?;
+            task.status = TaskStatus::InProgress;
+            task.worker = Some(worker.to_string());
+            Some(task.clone())
+        })
+    }
+
+    pub(crate) fn finish(&self, id: &str, outcome: TaskOutcome) -> io::Result<()> {
+        self.update(|tasks| {
+            if let Some(task) = tasks.iter_mut().find(|task| task.id == id) {
+                task.status = outcome.status;
+                task.result = outcome.result;
+                task.commit = outcome.commit;
+            }
+        })
+    }
+
+    /// Returns tasks claimed by `worker` to the pending state, e.g. when the
+    /// worker was stopped before finishing them.
+    pub(crate) fn release(&self, worker: &str) -> io::Result<()> {
+        self.update(|tasks| {
+            for task in tasks.iter_mut() {
+                if task.status == TaskStatus::InProgress && task.worker.as_deref() == Some(worker) {
+                    task.status = TaskStatus::Pending;
+                    task.worker = None;
+                }
+            }
+        })
+    }
+
+    fn update<T>(&self, apply: impl FnOnce(&mut Vec<QueuedTask>) -> T) -> io::Result<T> {
+        let mut file = OpenOptions::new().read(true).write(true).open(&self.path)?;
+        file.lock()?;
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+        let mut tasks = parse_tasks(&contents)?;
+        let result = apply(&mut tasks);
+
+        let mut serialized = String::new();
+        for task in &tasks {
+            serialized.push_str(&serde_json::to_string(task)?);
+            serialized.push('\n');
+        }
+        file.set_len(0)?;
+        file.seek(SeekFrom::Start(0))?;
+        file.write_all(serialized.as_bytes())?;
+        file.flush()?;
+        Ok(result)
+    }
+}
+
+fn parse_tasks(contents: &str) -> io::Result<Vec<QueuedTask>> {
+    let mut tasks = Vec::new();
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let mut task = if line.starts_with('{') {
+            serde_json::from_str::<QueuedTask>(line).map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid task on line {}: {err}", index + 1),
+                )
+            })?
+        } else {
+            QueuedTask {
+                id: String::new(),
+                prompt: line.to_string(),
+                status: TaskStatus::Pending,
+                worker: None,
+                result: None,
+                commit: None,
+            }
+        };
+        if task.id.is_empty() {
+            task.id = format!("task-{}", tasks.len() + 1);
+        }
+        tasks.push(task);
+    }
+    Ok(tasks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    #[test]
+    fn parses_plain_text_and_json_lines() {
+        let tasks = parse_tasks(
+            "rename foo to bar in src/a.rs\n\n{\"id\":\"b\",\"prompt\":\"update docs\",\"status\":\"done\"}\n",
+        )
+        .expect("parse");
+        let summary: Vec<(&str, &str, TaskStatus)> = tasks
+            .iter()
+            .map(|task| (task.id.as_str(), task.prompt.as_str(), task.status))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "task-1",
+                    "rename foo to bar in src/a.rs",
+                    TaskStatus::Pending
+                ),
+                ("b", "update docs", TaskStatus::Done),
+            ]
+        );
+    }
+
+    #[test]
+    fn claim_finish_and_release_rewrite_the_queue() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("queue.jsonl");
+        std::fs::write(&path, "first\nsecond\n").expect("write queue");
+        let queue = TaskQueue::open(&path).expect("open");
+
+        let first = queue.claim("worker-1").expect("claim").expect("task");
+        let second = queue.claim("worker-2").expect("claim").expect("task");
+        assert_eq!(queue.claim("worker-3").expect("claim"), None);
+
+        queue
+            .finish(
+                &first.id,
+                TaskOutcome {
+                    status: TaskStatus::Done,
+                    result: Some("renamed".to_string()),
+                    commit: Some("abc123".to_string()),
+                },
+            )
+            .expect("finish");
+        queue.release("worker-2").expect("release");
+
+        let tasks = queue.tasks().expect("tasks");
+        assert_eq!(tasks[0].status, TaskStatus::Done);
+        assert_eq!(tasks[0].commit.as_deref(), Some("abc123"));
+        assert_eq!(tasks[1].id, second.id);
+        assert_eq!(tasks[1].status, TaskStatus::Pending);
+        assert_eq!(tasks[1].worker, None);
+    }
+}
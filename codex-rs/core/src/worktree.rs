@@ -0,0 +1,182 @@
+//! Git worktree helpers that give workers and sessions an isolated checkout.
+
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Output;
+
+use tokio::process::Command;
+
+/// Worktrees live under `CODEX_HOME/<WORKTREES_SUBDIR>/`.
+pub(crate) const WORKTREES_SUBDIR: &str = "worktrees";
+
+/// Result of merging a worktree branch back into the checkout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MergeOutcome {
+    /// `commits` commits from the branch were merged into `into`.
+    Merged { into: String, commits: usize },
+    /// The branch had no new commits, so there was nothing to merge.
+    NothingToMerge,
+}
+
+/// Creates a worktree at `path` on a new `branch` starting from the
+/// repository's current `HEAD`.
+pub(crate) async fn add_worktree(repo_root: &Path, path: &Path, branch: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let path = path.to_string_lossy();
+    run_git(repo_root, &["worktree", "add", "-b", branch, &path, "HEAD"]).await?;
+    Ok(())
+}
+
+/// Removes the worktree at `path`, discarding any uncommitted changes in it,
+/// and deletes `branch`.
+pub(crate) async fn remove_worktree(repo_root: &Path, path: &Path, branch: &str) -> io::Result<()> {
+    remove_checkout(repo_root, path).await?;
+    run_git(repo_root, &["branch", "-D", branch]).await?;
+    Ok(())
+}
+
+/// Removes the worktree at `path`, discarding any uncommitted changes in it,
+/// but keeps its branch.
+pub(crate) async fn remove_checkout(repo_root: &Path, path: &Path) -> io::Result<()> {
+    let path = path.to_string_lossy();
+    run_git(repo_root, &["worktree", "remove", "--force", &path]).await?;
+    Ok(())
+}
+
+/// Commits every change in the worktree, returning the new commit hash or
+/// `None` when there was nothing to commit.
+pub(crate) async fn commit_all(worktree: &Path, message: &str) -> io::Result<Option<String>> {
+    run_git(worktree, &["add", "-A"]).await?;
+    let staged = Command::new("git")
+        .args(["diff", "--cached", "--quiet"])
+        .current_dir(worktree)
+        .kill_on_drop(true)
+        .status()
+        .await?;
+    if staged.success() {
+        return Ok(None);
+    }
+    run_git(worktree, &["commit", "-q", "-m", message]).await?;
+    let output = run_git(worktree, &["rev-parse", "HEAD"]).await?;
+    Ok(Some(
+        String::from_utf8_lossy(&output.stdout).trim().to_string(),
+    ))
+}
+
+/// Name of the branch checked out in `repo_root`, or `HEAD` when detached.
+pub(crate) async fn current_branch(repo_root: &Path) -> io::Result<String> {
+    let output = run_git(repo_root, &["rev-parse", "--abbrev-ref", "HEAD"]).await?;
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Number of commits on `branch` that the checkout in `repo_root` lacks.
+pub(crate) async fn commits_ahead(repo_root: &Path, branch: &str) -> io::Result<usize> {
+    let range = format!("HEAD..{branch}");
+    let output = run_git(repo_root, &["rev-list", "--count", &range]).await?;
+    String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse()
+        .map_err(io::Error::other)
+}
+
+/// Merges `branch` into the checkout in `repo_root`. A failed merge is
+/// aborted so the checkout is left as it was.
+pub(crate) async fn merge_branch(repo_root: &Path, branch: &str) -> io::Result<()> {
+    if let Err(err) = run_git(repo_root, &["merge", "--no-edit", branch]).await {
+        let _ = run_git(repo_root, &["merge", "--abort"]).await;
+        return Err(err);
+    }
+    Ok(())
+}
+
+/// Merges `branch` into the checkout in `repo_root` when it has commits the
+/// checkout lacks. A failed merge is aborted, as in [`merge_branch`].
+pub(crate) async fn merge_into_checkout(
+    repo_root: &Path,
+    branch: &str,
+) -> io::Result<MergeOutcome> {
+    let commits = commits_ahead(repo_root, branch).await?;
+    if commits == 0 {
+        return Ok(MergeOutcome::NothingToMerge);
+    }
+    merge_branch(repo_root, branch).await?;
+    Ok(MergeOutcome::Merged {
+        into: current_branch(repo_root).await?,
+        commits,
+    })
+}
+
+/// Maps `cwd` into `worktree`, keeping any subdirectory of the repository the
+/// user launched from.
+pub(crate) fn worktree_cwd(repo_root: &Path, cwd: &Path, worktree: &Path) -> PathBuf {
+    match cwd.strip_prefix(repo_root) {
+        Ok(relative) => worktree.join(relative),
+        Err(_) => worktree.to_path_buf(),
+    }
+}
+
+pub(crate) async fn run_git(cwd: &Path, args: &[&str]) -> io::Result<Output> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(cwd)
+        .kill_on_drop(true)
+        .output()
+        .await?;
+    if output.status.success() {
+        Ok(output)
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(io::Error::other(format!(
+            "git {} failed: {}",
+            args.first().copied().unwrap_or_default(),
+            stderr.trim()
+        )))
+    }
+}
+
+/// Runs git in `cwd` for a test, returning its trimmed stdout.
+#[cfg(test)]
+pub(crate) async fn test_git(cwd: &Path, args: &[&str]) -> String {
+    let output = run_git(cwd, args).await.expect("run git");
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+/// Initializes a repository on `main` with a committed `README.md`.
+#[cfg(test)]
+pub(crate) async fn init_test_repo(repo: &Path) {
+    test_git(repo, &["init", "-q", "-b", "main"]).await;
+    test_git(repo, &["config", "user.email", "codex@example.com"]).await;
+    test_git(repo, &["config", "user.name", "Codex"]).await;
+    std::fs::write(repo.join("README.md"), "hello\n").expect("write file");
+    test_git(repo, &["add", "."]).await;
+    test_git(repo, &["commit", "-q", "-m", "init"]).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn worktree_cwd_keeps_subdirectory() {
+        assert_eq!(
+            worktree_cwd(
+                Path::new("/repo"),
+                Path::new("/repo/crates/app"),
+                Path::new("/home/u/.codex/worktrees/run/worker-1"),
+            ),
+            PathBuf::from("/home/u/.codex/worktrees/run/worker-1/crates/app")
+        );
+        assert_eq!(
+            worktree_cwd(
+                Path::new("/repo"),
+                Path::new("/elsewhere"),
+                Path::new("/wt/worker-1"),
+            ),
+            PathBuf::from("/wt/worker-1")
+        );
+    }
+}
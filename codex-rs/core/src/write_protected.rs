@@ -0,0 +1,441 @@
+//! Paths the agent may not change without asking (`write_protected`).
+//!
+//! Each entry is a glob matched against paths relative to the session's
+//! working directory (or against the absolute path for entries starting with
+//! `/`); `*` also matches `/`, so `migrations/**` covers the whole directory.
+//! Patches touching a protected path always go to the user, even when
+//! `file_approvals` or an earlier "approve for session" would let them
+//! through, and are rejected under `approval_policy = "never"`. Sandboxed
+//! commands see the protected files and directories that exist when they
+//! start as read-only. A protected path a command creates is left in place,
+//! since someone else may have created it meanwhile, and the command is
+//! reported as failed.
+//!
+//! Finding the existing protected paths walks the working directory. The
+//! walk is cached per directory and a directory is only listed again when
+//! its modification time changes, so later commands mostly just `stat` the
+//! directories visited before. Gitignored and vendored directories are not
+//! searched unless a pattern names a path inside them. A walk that still
+//! visits too many entries is reported as truncated, and the caller asks the
+//! user before running without the paths it missed.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::SystemTime;
+
+use tracing::warn;
+use wildmatch::WildMatchPattern;
+
+use crate::git_info::get_git_repo_root;
+
+/// Entries visited while looking for protected paths to make read-only.
+const MAX_WALK_ENTRIES: usize = 50_000;
+const GLOB_CHARS: &[char] = &['*', '?'];
+/// Directories of third-party code, skipped like gitignored ones.
+const VENDORED_DIRS: &[&str] = &["node_modules", "vendor", ".venv", "__pycache__"];
+
+/// Whether `path` is covered by one of `patterns`. Also used for the `paths`
+/// of approval rules.
+pub(crate) fn path_matches_globs(patterns: &[String], cwd: &Path, path: &Path) -> bool {
+    let absolute = path.to_string_lossy();
+    let relative = path
+        .strip_prefix(cwd)
+        .ok()
+        .map(|relative| relative.to_string_lossy().replace('\\', "/"));
+    patterns.iter().any(|pattern| {
+        if pattern.starts_with('/') {
+            pattern_matches(pattern, &absolute)
+        } else {
+            relative
+                .as_deref()
+                .is_some_and(|relative| pattern_matches(pattern, relative))
+        }
+    })
+}
+
+/// The first of `paths` covered by `patterns`, if any.
+pub(crate) fn first_write_protected<'a>(
+    patterns: &[String],
+    cwd: &Path,
+    paths: impl IntoIterator<Item = &'a Path>,
+) -> Option<&'a Path> {
+    if patterns.is_empty() {
+        return None;
+    }
+    paths
+        .into_iter()
+        .find(|path| path_matches_globs(patterns, cwd, path))
+}
+
+/// The protected paths found by [`WriteProtectedPaths::existing`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct ExistingPaths {
+    pub(crate) paths: Vec<PathBuf>,
+    /// The walk stopped after `MAX_WALK_ENTRIES` entries, so `paths` may
+    /// miss some protected paths.
+    pub(crate) truncated: bool,
+}
+
+/// Existing files and directories covered by `write_protected`, for the
+/// sandbox to mount read-only. A matching directory is returned instead of
+/// its contents.
+#[derive(Debug, Default)]
+pub(crate) struct WriteProtectedPaths {
+    patterns: Vec<String>,
+    cwd: PathBuf,
+    dirs: HashMap<PathBuf, DirListing>,
+}
+
+/// What the walk found in one directory, valid while its mtime is unchanged.
+#[derive(Debug)]
+struct DirListing {
+    modified: SystemTime,
+    entries: usize,
+    protected: Vec<PathBuf>,
+    subdirs: Vec<PathBuf>,
+}
+
+impl WriteProtectedPaths {
+    /// The protected paths that exist now.
+    pub(crate) fn existing(&mut self, patterns: &[String], cwd: &Path) -> ExistingPaths {
+        self.existing_within(patterns, cwd, MAX_WALK_ENTRIES)
+    }
+
+    fn existing_within(
+        &mut self,
+        patterns: &[String],
+        cwd: &Path,
+        max_entries: usize,
+    ) -> ExistingPaths {
+        if self.patterns != patterns || self.cwd != cwd {
+            *self = Self {
+                patterns: patterns.to_vec(),
+                cwd: cwd.to_path_buf(),
+                dirs: HashMap::new(),
+            };
+        }
+        let mut found: Vec<PathBuf> = patterns
+            .iter()
+            .filter(|pattern| pattern.starts_with('/') && !pattern.contains(GLOB_CHARS))
+            .map(PathBuf::from)
+            .filter(|path| path.exists())
+            .collect();
+        let relative: Vec<&String> = patterns
+            .iter()
+            .filter(|pattern| !pattern.starts_with('/'))
+            .collect();
+        if relative.is_empty() {
+            return ExistingPaths {
+                paths: found,
+                truncated: false,
+            };
+        }
+
+        let ignored = ignored_dirs(cwd);
+        let mut truncated = false;
+        let mut visited = 0;
+        let mut dirs = HashMap::new();
+        let mut pending = vec![cwd.to_path_buf()];
+        while let Some(dir) = pending.pop() {
+            if dir != cwd && skipped_dir(&dir, cwd, &ignored, &relative) {
+                continue;
+            }
+            let Ok(modified) = std::fs::metadata(&dir).and_then(|metadata| metadata.modified())
+            else {
+                continue;
+            };
+            let listing = match self.dirs.remove(&dir) {
+                Some(listing) if listing.modified == modified => listing,
+                _ => list_dir(&dir, cwd, &relative, modified),
+            };
+            visited += listing.entries;
+            if visited > max_entries {
+                warn!("stopped looking for write_protected paths after {max_entries} entries");
+                truncated = true;
+                break;
+            }
+            found.extend(listing.protected.iter().cloned());
+            pending.extend(listing.subdirs.iter().cloned());
+            dirs.insert(dir, listing);
+        }
+        self.dirs = dirs;
+        ExistingPaths {
+            paths: found,
+            truncated,
+        }
+    }
+}
+
+/// Directories under `cwd` that match a gitignore pattern, empty outside a
+/// git repository.
+fn ignored_dirs(cwd: &Path) -> HashSet<PathBuf> {
+    let Some(root) = get_git_repo_root(cwd) else {
+        return HashSet::new();
+    };
+    let Ok(output) = Command::new("git")
+        .args([
+            "status",
+            "--porcelain=v1",
+            "-z",
+            "--ignored=matching",
+            "--untracked-files=normal",
+            "--",
+            ".",
+        ])
+        .current_dir(cwd)
+        .output()
+    else {
+        return HashSet::new();
+    };
+    if !output.status.success() {
+        return HashSet::new();
+    }
+    // Paths are relative to the repository root; ignored directories end
+    // with `/`.
+    String::from_utf8_lossy(&output.stdout)
+        .split('\0')
+        .filter_map(|entry| entry.strip_prefix("!! "))
+        .filter_map(|path| path.strip_suffix('/'))
+        .map(|dir| root.join(dir))
+        .collect()
+}
+
+/// Whether the walk leaves `dir` out: it is gitignored or vendored, and no
+/// pattern names a path inside it.
+fn skipped_dir(dir: &Path, cwd: &Path, ignored: &HashSet<PathBuf>, relative: &[&String]) -> bool {
+    let vendored = dir
+        .file_name()
+        .is_some_and(|name| VENDORED_DIRS.iter().any(|vendored| name == *vendored));
+    if !vendored && !ignored.contains(dir) {
+        return false;
+    }
+    let Ok(relative_dir) = dir.strip_prefix(cwd) else {
+        return true;
+    };
+    let prefix = format!("{}/", relative_dir.to_string_lossy().replace('\\', "/"));
+    !relative
+        .iter()
+        .any(|pattern| literal_prefix(pattern).starts_with(&prefix))
+}
+
+fn list_dir(dir: &Path, cwd: &Path, relative: &[&String], modified: SystemTime) -> DirListing {
+    let mut listing = DirListing {
+        modified,
+        entries: 0,
+        protected: Vec::new(),
+        subdirs: Vec::new(),
+    };
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return listing;
+    };
+    for entry in entries.flatten() {
+        listing.entries += 1;
+        let path = entry.path();
+        let Ok(relative_path) = path.strip_prefix(cwd) else {
+            continue;
+        };
+        let relative_path = relative_path.to_string_lossy().replace('\\', "/");
+        // Symlinks are not followed; a protected symlink itself is returned.
+        let is_dir = entry.file_type().is_ok_and(|file_type| file_type.is_dir());
+        if relative
+            .iter()
+            .any(|pattern| pattern_matches(pattern, &relative_path))
+        {
+            listing.protected.push(path);
+        } else if is_dir
+            && entry.file_name() != ".git"
+            && relative
+                .iter()
+                .any(|pattern| may_match_below(pattern, &relative_path))
+        {
+            listing.subdirs.push(path);
+        }
+    }
+    listing
+}
+
+/// The protected paths in `after` that were not in `before`, except those
+/// covering one of `allowed`.
+pub(crate) fn created_without_approval(
+    before: &[PathBuf],
+    after: &[PathBuf],
+    allowed: &[PathBuf],
+) -> Vec<PathBuf> {
+    after
+        .iter()
+        .filter(|path| {
+            !before.contains(path) && !allowed.iter().any(|allowed| allowed.starts_with(path))
+        })
+        .cloned()
+        .collect()
+}
+
+/// `dir/**` also matches `dir` itself, so deleting or replacing the whole
+/// directory is covered.
+fn pattern_matches(pattern: &str, path: &str) -> bool {
+    WildMatchPattern::<'*', '?'>::new(pattern).matches(path)
+        || pattern
+            .strip_suffix("/**")
+            .is_some_and(|dir| WildMatchPattern::<'*', '?'>::new(dir).matches(path))
+}
+
+/// Whether something inside directory `dir` could match `pattern`: the
+/// pattern's literal prefix and `dir/` must agree up to the shorter of the two.
+fn may_match_below(pattern: &str, dir: &str) -> bool {
+    let literal = literal_prefix(pattern);
+    let dir = format!("{dir}/");
+    literal.starts_with(&dir) || dir.starts_with(literal)
+}
+
+/// The part of `pattern` before its first wildcard.
+fn literal_prefix(pattern: &str) -> &str {
+    pattern
+        .find(GLOB_CHARS)
+        .map_or(pattern, |index| &pattern[..index])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn patterns(globs: &[&str]) -> Vec<String> {
+        globs.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn matches_relative_and_absolute_patterns() {
+        let cwd = Path::new("/repo");
+        let globs = patterns(&["migrations/**", ".env*", "Cargo.lock", "/etc/hosts"]);
+        for (path, protected) in [
+            ("/repo/migrations/0001_init.sql", true),
+            ("/repo/migrations", true),
+            ("/repo/.env.local", true),
+            ("/repo/Cargo.lock", true),
+            ("/repo/crates/Cargo.lock", false),
+            ("/repo/src/main.rs", false),
+            ("/etc/hosts", true),
+        ] {
+            assert_eq!(
+                path_matches_globs(&globs, cwd, Path::new(path)),
+                protected,
+                "{path}"
+            );
+        }
+    }
+
+    #[test]
+    fn finds_existing_protected_paths() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path();
+        for file in ["migrations/0001.sql", "src/main.rs", ".env", "Cargo.lock"] {
+            let path = root.join(file);
+            std::fs::create_dir_all(path.parent().expect("parent")).expect("mkdir");
+            std::fs::write(path, "").expect("write");
+        }
+
+        let globs = patterns(&["migrations/**", ".env*", "*.lock"]);
+        let mut paths = WriteProtectedPaths::default();
+        let mut found = paths.existing(&globs, root).paths;
+        found.sort();
+        assert_eq!(
+            found,
+            vec![
+                root.join(".env"),
+                root.join("Cargo.lock"),
+                root.join("migrations"),
+            ]
+        );
+
+        // A file created later is found through the cached walk.
+        std::fs::write(root.join(".env.local"), "").expect("write");
+        let mut found = paths.existing(&globs, root).paths;
+        found.sort();
+        assert_eq!(
+            found,
+            vec![
+                root.join(".env"),
+                root.join(".env.local"),
+                root.join("Cargo.lock"),
+                root.join("migrations"),
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_vendored_and_gitignored_dirs_unless_named() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path();
+        let init = Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(root)
+            .status()
+            .expect("git init");
+        assert!(init.success());
+        std::fs::write(root.join(".gitignore"), "build/\n").expect("write");
+        for file in [
+            "node_modules/pkg/yarn.lock",
+            "build/out/Cargo.lock",
+            "vendor/lib/Cargo.lock",
+            "crates/a/Cargo.lock",
+        ] {
+            let path = root.join(file);
+            std::fs::create_dir_all(path.parent().expect("parent")).expect("mkdir");
+            std::fs::write(path, "").expect("write");
+        }
+
+        let globs = patterns(&["*.lock", "vendor/lib/*.lock"]);
+        let mut found = WriteProtectedPaths::default().existing(&globs, root);
+        found.paths.sort();
+        assert_eq!(
+            found,
+            ExistingPaths {
+                paths: vec![
+                    root.join("crates/a/Cargo.lock"),
+                    root.join("vendor/lib/Cargo.lock"),
+                ],
+                truncated: false,
+            }
+        );
+    }
+
+    #[test]
+    fn reports_a_walk_stopped_at_the_entry_limit() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path();
+        for index in 0..10 {
+            let path = root.join(format!("dir{index}/.env"));
+            std::fs::create_dir_all(path.parent().expect("parent")).expect("mkdir");
+            std::fs::write(path, "").expect("write");
+        }
+
+        let globs = patterns(&["*.env"]);
+        let mut paths = WriteProtectedPaths::default();
+        let partial = paths.existing_within(&globs, root, 5);
+        assert!(partial.truncated);
+        assert!(partial.paths.len() < 10);
+
+        let complete = paths.existing_within(&globs, root, 100);
+        assert_eq!(complete.paths.len(), 10);
+        assert!(!complete.truncated);
+    }
+
+    #[test]
+    fn reports_protected_paths_created_without_approval() {
+        let root = Path::new("/repo");
+        let env = root.join(".env");
+        let migrations = root.join("migrations");
+        let lockfile = root.join("Cargo.lock");
+
+        let created = created_without_approval(
+            &[lockfile.clone()],
+            &[env.clone(), migrations.clone(), lockfile],
+            &[migrations.join("0002.sql")],
+        );
+
+        assert_eq!(created, vec![env]);
+    }
+}
@@ -52,12 +52,18 @@ async fn run_request(input: Vec<ResponseItem>) -> Value {
         env_key: None,
         env_key_instructions: None,
         experimental_bearer_token: None,
+        auth_header: None,
+        auth_scheme: None,
+        prompt_cache_key_header: None,
         wire_api: WireApi::Chat,
         query_params: None,
         http_headers: None,
         env_http_headers: None,
         request_max_retries: Some(0),
         stream_max_retries: Some(0),
+        retry_base_delay_ms: None,
+        retry_max_delay_ms: None,
+        retry_status_codes: None,
         stream_idle_timeout_ms: Some(5_000),
         requires_openai_auth: false,
         supports_websockets: false,
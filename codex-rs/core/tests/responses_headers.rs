@@ -51,12 +51,18 @@ async fn responses_stream_includes_subagent_header_on_review() {
         env_key: None,
         env_key_instructions: None,
         experimental_bearer_token: None,
+        auth_header: None,
+        auth_scheme: None,
+        prompt_cache_key_header: None,
         wire_api: WireApi::Responses,
         query_params: None,
         http_headers: None,
         env_http_headers: None,
         request_max_retries: Some(0),
         stream_max_retries: Some(0),
+        retry_base_delay_ms: None,
+        retry_max_delay_ms: None,
+        retry_status_codes: None,
         stream_idle_timeout_ms: Some(5_000),
         requires_openai_auth: false,
         supports_websockets: false,
@@ -149,12 +155,18 @@ async fn responses_stream_includes_subagent_header_on_other() {
         env_key: None,
         env_key_instructions: None,
         experimental_bearer_token: None,
+        auth_header: None,
+        auth_scheme: None,
+        prompt_cache_key_header: None,
         wire_api: WireApi::Responses,
         query_params: None,
         http_headers: None,
         env_http_headers: None,
         request_max_retries: Some(0),
         stream_max_retries: Some(0),
+        retry_base_delay_ms: None,
+        retry_max_delay_ms: None,
+        retry_status_codes: None,
         stream_idle_timeout_ms: Some(5_000),
         requires_openai_auth: false,
         supports_websockets: false,
@@ -303,12 +315,18 @@ async fn responses_respects_model_info_overrides_from_config() {
         env_key: None,
         env_key_instructions: None,
         experimental_bearer_token: None,
+        auth_header: None,
+        auth_scheme: None,
+        prompt_cache_key_header: None,
         wire_api: WireApi::Responses,
         query_params: None,
         http_headers: None,
         env_http_headers: None,
         request_max_retries: Some(0),
         stream_max_retries: Some(0),
+        retry_base_delay_ms: None,
+        retry_max_delay_ms: None,
+        retry_status_codes: None,
         stream_idle_timeout_ms: Some(5_000),
         requires_openai_auth: false,
         supports_websockets: false,
@@ -411,12 +429,18 @@ async fn responses_stream_includes_turn_metadata_header_for_git_workspace_e2e()
         env_key: None,
         env_key_instructions: None,
         experimental_bearer_token: None,
+        auth_header: None,
+        auth_scheme: None,
+        prompt_cache_key_header: None,
         wire_api: WireApi::Responses,
         query_params: None,
         http_headers: None,
         env_http_headers: None,
         request_max_retries: Some(0),
         stream_max_retries: Some(0),
+        retry_base_delay_ms: None,
+        retry_max_delay_ms: None,
+        retry_status_codes: None,
         stream_idle_timeout_ms: Some(5_000),
         requires_openai_auth: false,
         supports_websockets: false,
@@ -0,0 +1,94 @@
+//! `--record-api` recordings, and replaying one in place of the model API.
+
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_once;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+
+async fn run_turn(test: &TestCodex) -> anyhow::Result<Option<String>> {
+    test.codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "hello".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await?;
+    let EventMsg::TurnComplete(complete) = wait_for_event(&test.codex, |event| {
+        matches!(event, EventMsg::TurnComplete(_))
+    })
+    .await
+    else {
+        unreachable!("wait_for_event returned a different event");
+    };
+    Ok(complete.last_agent_message)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn replay_redrives_a_recorded_turn() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    mount_sse_once(
+        &server,
+        sse(vec![
+            ev_response_created("resp-1"),
+            ev_assistant_message("msg-1", "recorded answer"),
+            ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+
+    let recordings = TempDir::new()?;
+    let record_dir = recordings.path().to_path_buf();
+    let recorded = test_codex()
+        .with_config(move |config| config.record_api_dir = Some(record_dir))
+        .build(&server)
+        .await?;
+    assert_eq!(
+        run_turn(&recorded).await?.as_deref(),
+        Some("recorded answer")
+    );
+
+    let recording = std::fs::read_dir(recordings.path())?
+        .next()
+        .expect("a recording file")?
+        .path();
+    let contents = std::fs::read_to_string(&recording)?;
+    assert!(contents.contains(r#""type":"request""#));
+    assert!(contents.contains("recorded answer"));
+
+    // The replay runs against a server with nothing mounted, so the answer
+    // can only come from the recording.
+    let empty_server = start_mock_server().await;
+    let replayed = test_codex()
+        .with_config(move |config| config.replay_api_path = Some(recording))
+        .build(&empty_server)
+        .await?;
+    assert_eq!(
+        run_turn(&replayed).await?.as_deref(),
+        Some("recorded answer")
+    );
+    let responses_requests = empty_server
+        .received_requests()
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|request| request.url.path().ends_with("/responses"))
+        .count();
+    assert_eq!(responses_requests, 0);
+
+    Ok(())
+}
@@ -17,6 +17,7 @@ use codex_core::auth::AuthCredentialsStoreMode;
 use codex_core::built_in_model_providers;
 use codex_core::default_client::originator;
 use codex_core::error::CodexErr;
+use codex_core::features::Feature;
 use codex_core::models_manager::manager::ModelsManager;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::Op;
@@ -1060,6 +1061,58 @@ async fn configured_verbosity_is_sent() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn override_turn_context_updates_verbosity() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+    let server = MockServer::start().await;
+
+    let resp_mock = mount_sse_once(&server, sse_completed("resp1")).await;
+    let TestCodex { codex, .. } = test_codex()
+        .with_model("gpt-5.1")
+        .with_config(|config| {
+            config.model_verbosity = Some(Verbosity::High);
+        })
+        .build(&server)
+        .await?;
+
+    codex
+        .submit(Op::OverrideTurnContext {
+            cwd: None,
+            approval_policy: None,
+            sandbox_policy: None,
+            windows_sandbox_level: None,
+            model: None,
+            effort: None,
+            summary: None,
+            collaboration_mode: None,
+            personality: None,
+            verbosity: Some(Verbosity::Low),
+        })
+        .await?;
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "hello".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await?;
+
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+
+    let request_body = resp_mock.single_request().body_json();
+    assert_eq!(
+        request_body
+            .get("text")
+            .and_then(|t| t.get("verbosity"))
+            .and_then(|v| v.as_str()),
+        Some("low")
+    );
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn includes_developer_instructions_message_in_request() {
     skip_if_no_network!();
@@ -1143,12 +1196,18 @@ async fn azure_responses_request_includes_store_and_reasoning_ids() {
         env_key: None,
         env_key_instructions: None,
         experimental_bearer_token: None,
+        auth_header: None,
+        auth_scheme: None,
+        prompt_cache_key_header: None,
         wire_api: WireApi::Responses,
         query_params: None,
         http_headers: None,
         env_http_headers: None,
         request_max_retries: Some(0),
         stream_max_retries: Some(0),
+        retry_base_delay_ms: None,
+        retry_max_delay_ms: None,
+        retry_status_codes: None,
         stream_idle_timeout_ms: Some(5_000),
         requires_openai_auth: false,
         supports_websockets: false,
@@ -1532,16 +1591,25 @@ async fn context_window_error_sets_total_tokens_to_model_window() -> anyhow::Res
 
     const EFFECTIVE_CONTEXT_WINDOW: i64 = (272_000 * 95) / 100;
 
-    mount_sse_once_match(
-        &server,
-        body_string_contains("trigger context window"),
-        sse_failed(
-            "resp_context_window",
-            "context_length_exceeded",
-            "Your input exceeds the context window of this model. Please adjust your input and try again.",
-        ),
-    )
-    .await;
+    // Every request carrying the oversized turn overflows, including the
+    // compaction attempted to recover from it.
+    Mock::given(method("POST"))
+        .and(path("/v1/responses"))
+        .and(body_string_contains("trigger context window"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_raw(
+                    sse_failed(
+                        "resp_context_window",
+                        "context_length_exceeded",
+                        "Your input exceeds the context window of this model. Please adjust your input and try again.",
+                    ),
+                    "text/event-stream",
+                ),
+        )
+        .mount(&server)
+        .await;
 
     mount_sse_once_match(
         &server,
@@ -1554,6 +1622,7 @@ async fn context_window_error_sets_total_tokens_to_model_window() -> anyhow::Res
         .with_config(|config| {
             config.model = Some("gpt-5.1".to_string());
             config.model_context_window = Some(272_000);
+            config.features.disable(Feature::RemoteCompaction);
         })
         .build(&server)
         .await?;
@@ -1658,6 +1727,9 @@ async fn azure_overrides_assign_properties_used_for_responses_url() {
         // Reuse the existing environment variable to avoid using unsafe code
         env_key: Some(existing_env_var_with_random_value.to_string()),
         experimental_bearer_token: None,
+        auth_header: None,
+        auth_scheme: None,
+        prompt_cache_key_header: None,
         query_params: Some(std::collections::HashMap::from([(
             "api-version".to_string(),
             "2025-04-01-preview".to_string(),
@@ -1671,6 +1743,9 @@ async fn azure_overrides_assign_properties_used_for_responses_url() {
         env_http_headers: None,
         request_max_retries: None,
         stream_max_retries: None,
+        retry_base_delay_ms: None,
+        retry_max_delay_ms: None,
+        retry_status_codes: None,
         stream_idle_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
@@ -1744,6 +1819,9 @@ async fn env_var_overrides_loaded_auth() {
         )])),
         env_key_instructions: None,
         experimental_bearer_token: None,
+        auth_header: None,
+        auth_scheme: None,
+        prompt_cache_key_header: None,
         wire_api: WireApi::Responses,
         http_headers: Some(std::collections::HashMap::from([(
             "Custom-Header".to_string(),
@@ -1752,6 +1830,9 @@ async fn env_var_overrides_loaded_auth() {
         env_http_headers: None,
         request_max_retries: None,
         stream_max_retries: None,
+        retry_base_delay_ms: None,
+        retry_max_delay_ms: None,
+        retry_status_codes: None,
         stream_idle_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
@@ -117,6 +117,7 @@ async fn user_input_includes_collaboration_instructions_after_override() -> Resu
             summary: None,
             collaboration_mode: Some(collaboration_mode),
             personality: None,
+            verbosity: None,
         })
         .await?;
 
@@ -199,6 +200,7 @@ async fn override_then_next_turn_uses_updated_collaboration_instructions() -> Re
             summary: None,
             collaboration_mode: Some(collaboration_mode),
             personality: None,
+            verbosity: None,
         })
         .await?;
 
@@ -245,6 +247,7 @@ async fn user_turn_overrides_collaboration_instructions_after_override() -> Resu
             summary: None,
             collaboration_mode: Some(base_mode),
             personality: None,
+            verbosity: None,
         })
         .await?;
 
@@ -300,6 +303,7 @@ async fn collaboration_mode_update_emits_new_instruction_message() -> Result<()>
             summary: None,
             collaboration_mode: Some(collab_mode_with_instructions(Some(first_text))),
             personality: None,
+            verbosity: None,
         })
         .await?;
 
@@ -325,6 +329,7 @@ async fn collaboration_mode_update_emits_new_instruction_message() -> Result<()>
             summary: None,
             collaboration_mode: Some(collab_mode_with_instructions(Some(second_text))),
             personality: None,
+            verbosity: None,
         })
         .await?;
 
@@ -371,6 +376,7 @@ async fn collaboration_mode_update_noop_does_not_append() -> Result<()> {
             summary: None,
             collaboration_mode: Some(collab_mode_with_instructions(Some(collab_text))),
             personality: None,
+            verbosity: None,
         })
         .await?;
 
@@ -396,6 +402,7 @@ async fn collaboration_mode_update_noop_does_not_append() -> Result<()> {
             summary: None,
             collaboration_mode: Some(collab_mode_with_instructions(Some(collab_text))),
             personality: None,
+            verbosity: None,
         })
         .await?;
 
@@ -444,6 +451,7 @@ async fn collaboration_mode_update_emits_new_instruction_message_when_mode_chang
                 Some(code_text),
             )),
             personality: None,
+            verbosity: None,
         })
         .await?;
 
@@ -472,6 +480,7 @@ async fn collaboration_mode_update_emits_new_instruction_message_when_mode_chang
                 Some(plan_text),
             )),
             personality: None,
+            verbosity: None,
         })
         .await?;
 
@@ -521,6 +530,7 @@ async fn collaboration_mode_update_noop_does_not_append_when_mode_is_unchanged()
                 Some(collab_text),
             )),
             personality: None,
+            verbosity: None,
         })
         .await?;
 
@@ -549,6 +559,7 @@ async fn collaboration_mode_update_noop_does_not_append_when_mode_is_unchanged()
                 Some(collab_text),
             )),
             personality: None,
+            verbosity: None,
         })
         .await?;
 
@@ -601,6 +612,7 @@ async fn resume_replays_collaboration_instructions() -> Result<()> {
             summary: None,
             collaboration_mode: Some(collab_mode_with_instructions(Some(collab_text))),
             personality: None,
+            verbosity: None,
         })
         .await?;
 
@@ -657,6 +669,7 @@ async fn empty_collaboration_instructions_are_ignored() -> Result<()> {
             summary: None,
             collaboration_mode: Some(collab_mode_with_instructions(Some(""))),
             personality: None,
+            verbosity: None,
         })
         .await?;
 
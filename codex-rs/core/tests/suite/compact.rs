@@ -1799,6 +1799,92 @@ async fn manual_compact_retries_after_context_window_error() {
     }
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn auto_compact_recovers_from_context_window_error_mid_turn() {
+    skip_if_no_network!();
+
+    let server = start_mock_server().await;
+
+    let first_turn = sse(vec![
+        ev_assistant_message("m1", FIRST_REPLY),
+        ev_completed("r1"),
+    ]);
+    let overflow = sse_failed(
+        "resp-overflow",
+        "context_length_exceeded",
+        CONTEXT_LIMIT_MESSAGE,
+    );
+    let summary = sse(vec![
+        ev_assistant_message("m2", AUTO_SUMMARY_TEXT),
+        ev_completed("r2"),
+    ]);
+    let retried_turn = sse(vec![
+        ev_assistant_message("m3", FINAL_REPLY),
+        ev_completed("r3"),
+    ]);
+    let request_log =
+        mount_sse_sequence(&server, vec![first_turn, overflow, summary, retried_turn]).await;
+
+    let model_provider = non_openai_model_provider(&server);
+    let mut builder = test_codex().with_config(move |config| {
+        config.model_provider = model_provider;
+        set_test_compact_prompt(config);
+    });
+    let codex = builder.build(&server).await.unwrap().codex;
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "first turn".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await
+        .unwrap();
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: SECOND_AUTO_MSG.into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await
+        .unwrap();
+    let notice = wait_for_event_match(&codex, |ev| match ev {
+        EventMsg::Warning(WarningEvent { message }) if message.contains("summarized") => {
+            Some(message.clone())
+        }
+        EventMsg::Error(err) => panic!("turn should recover from the overflow: {err:?}"),
+        _ => None,
+    })
+    .await;
+    assert!(
+        notice.starts_with("Context was nearly full"),
+        "unexpected compaction notice: {notice}"
+    );
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+
+    let requests = request_log.requests();
+    assert_eq!(
+        requests.len(),
+        4,
+        "expected two turns, one overflow, and one compaction"
+    );
+    let retry_body = requests[3].body_json().to_string();
+    assert!(
+        body_contains_text(&retry_body, AUTO_SUMMARY_TEXT),
+        "retried request should carry the summary: {retry_body}"
+    );
+    assert!(
+        body_contains_text(&retry_body, SECOND_AUTO_MSG),
+        "retried request should keep the user's message: {retry_body}"
+    );
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn manual_compact_twice_preserves_latest_user_messages() {
     skip_if_no_network!();
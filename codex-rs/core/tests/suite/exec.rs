@@ -3,6 +3,7 @@
 use std::collections::HashMap;
 use std::string::ToString;
 
+use codex_core::exec::ExecLimits;
 use codex_core::exec::ExecParams;
 use codex_core::exec::ExecToolCallOutput;
 use codex_core::exec::SandboxType;
@@ -40,6 +41,7 @@ async fn run_test_cmd(tmp: TempDir, cmd: Vec<&str>) -> Result<ExecToolCallOutput
         windows_sandbox_level: WindowsSandboxLevel::Disabled,
         justification: None,
         arg0: None,
+        limits: ExecLimits::default(),
     };
 
     let policy = SandboxPolicy::new_read_only_policy();
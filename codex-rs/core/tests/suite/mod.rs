@@ -16,6 +16,7 @@ pub static CODEX_ALIASES_TEMP_DIR: TempDir = unsafe {
 #[cfg(not(target_os = "windows"))]
 mod abort_tasks;
 mod agent_websocket;
+mod api_recording;
 mod apply_patch_cli;
 #[cfg(not(target_os = "windows"))]
 mod approvals;
@@ -40,6 +41,7 @@ mod json_result;
 mod list_dir;
 mod list_models;
 mod live_cli;
+mod model_failover;
 mod model_info_overrides;
 mod model_overrides;
 mod model_tools;
@@ -50,6 +52,7 @@ mod pending_input;
 mod permissions_messages;
 mod personality;
 mod personality_migration;
+mod plan_mode;
 mod prompt_caching;
 mod quota_exceeded;
 mod read_file;
@@ -83,3 +86,4 @@ mod user_shell_cmd;
 mod view_image;
 mod web_search;
 mod websocket_fallback;
+mod workers;
@@ -0,0 +1,129 @@
+use codex_core::ModelProviderInfo;
+use codex_core::WireApi;
+use codex_core::config::types::ModelFallback;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_protocol::user_input::UserInput;
+use core_test_support::load_sse_fixture_with_id;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use core_test_support::wait_for_event_match;
+use pretty_assertions::assert_eq;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+fn mock_provider(name: &str, base_url: String) -> ModelProviderInfo {
+    ModelProviderInfo {
+        name: name.into(),
+        base_url: Some(base_url),
+        env_key: Some("PATH".into()),
+        env_key_instructions: None,
+        experimental_bearer_token: None,
+        auth_header: None,
+        auth_scheme: None,
+        prompt_cache_key_header: None,
+        wire_api: WireApi::Responses,
+        query_params: None,
+        http_headers: None,
+        env_http_headers: None,
+        request_max_retries: Some(0),
+        stream_max_retries: Some(0),
+        retry_base_delay_ms: None,
+        retry_max_delay_ms: None,
+        retry_status_codes: None,
+        stream_idle_timeout_ms: Some(2_000),
+        requires_openai_auth: false,
+        supports_websockets: false,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn persistent_server_errors_fail_over_to_fallback_provider() {
+    skip_if_no_network!();
+
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/primary/v1/responses"))
+        .respond_with(ResponseTemplate::new(503))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/fallback/v1/responses"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_raw(
+                    load_sse_fixture_with_id("../fixtures/completed_template.json", "resp_ok"),
+                    "text/event-stream",
+                ),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let primary = mock_provider("primary", format!("{}/primary/v1", server.uri()));
+    let fallback = mock_provider("fallback", format!("{}/fallback/v1", server.uri()));
+    let TestCodex {
+        codex,
+        session_configured,
+        ..
+    } = test_codex()
+        .with_config(move |config| {
+            config.model_provider_id = "primary".to_string();
+            config.model_provider = primary;
+            config.model_fallbacks = vec![ModelFallback {
+                provider_id: "fallback".to_string(),
+                provider: fallback,
+                model: Some("gpt-5.1".to_string()),
+            }];
+        })
+        .build(&server)
+        .await
+        .unwrap();
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "hello".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await
+        .unwrap();
+
+    let failover = wait_for_event_match(&codex, |ev| match ev {
+        EventMsg::ModelFailover(event) => Some(event.clone()),
+        _ => None,
+    })
+    .await;
+    assert_eq!(
+        (
+            failover.from_provider,
+            failover.from_model,
+            failover.to_provider,
+            failover.to_model,
+        ),
+        (
+            "primary".to_string(),
+            session_configured.model,
+            "fallback".to_string(),
+            "gpt-5.1".to_string(),
+        )
+    );
+
+    // The fallback answers the turn, so no error reaches the user.
+    let completed = wait_for_event(&codex, |ev| {
+        matches!(ev, EventMsg::Error(_) | EventMsg::TurnComplete(_))
+    })
+    .await;
+    assert!(matches!(completed, EventMsg::TurnComplete(_)));
+}
@@ -35,6 +35,7 @@ async fn override_turn_context_does_not_persist_when_config_exists() {
             summary: None,
             collaboration_mode: None,
             personality: None,
+            verbosity: None,
         })
         .await
         .expect("submit override");
@@ -71,6 +72,7 @@ async fn override_turn_context_does_not_create_config_file() {
             summary: None,
             collaboration_mode: None,
             personality: None,
+            verbosity: None,
         })
         .await
         .expect("submit override");
@@ -123,6 +123,7 @@ async fn override_turn_context_without_user_turn_does_not_record_permissions_upd
             summary: None,
             collaboration_mode: None,
             personality: None,
+            verbosity: None,
         })
         .await?;
 
@@ -163,6 +164,7 @@ async fn override_turn_context_without_user_turn_does_not_record_environment_upd
             summary: None,
             collaboration_mode: None,
             personality: None,
+            verbosity: None,
         })
         .await?;
 
@@ -200,6 +202,7 @@ async fn override_turn_context_without_user_turn_does_not_record_collaboration_u
             summary: None,
             collaboration_mode: Some(collaboration_mode),
             personality: None,
+            verbosity: None,
         })
         .await?;
 
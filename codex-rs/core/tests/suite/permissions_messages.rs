@@ -114,6 +114,7 @@ async fn permissions_message_added_on_override_change() -> Result<()> {
             summary: None,
             collaboration_mode: None,
             personality: None,
+            verbosity: None,
         })
         .await?;
 
@@ -236,6 +237,7 @@ async fn resume_replays_permissions_messages() -> Result<()> {
             summary: None,
             collaboration_mode: None,
             personality: None,
+            verbosity: None,
         })
         .await?;
 
@@ -319,6 +321,7 @@ async fn resume_and_fork_append_permissions_messages() -> Result<()> {
             summary: None,
             collaboration_mode: None,
             personality: None,
+            verbosity: None,
         })
         .await?;
 
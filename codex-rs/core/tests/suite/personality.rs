@@ -225,6 +225,7 @@ async fn user_turn_personality_some_adds_update_message() -> anyhow::Result<()>
             summary: None,
             collaboration_mode: None,
             personality: Some(Personality::Pragmatic),
+            verbosity: None,
         })
         .await?;
 
@@ -322,6 +323,7 @@ async fn user_turn_personality_same_value_does_not_add_update_message() -> anyho
             summary: None,
             collaboration_mode: None,
             personality: Some(Personality::Pragmatic),
+            verbosity: None,
         })
         .await?;
 
@@ -428,6 +430,7 @@ async fn user_turn_personality_skips_if_feature_disabled() -> anyhow::Result<()>
             summary: None,
             collaboration_mode: None,
             personality: Some(Personality::Pragmatic),
+            verbosity: None,
         })
         .await?;
 
@@ -800,6 +803,7 @@ async fn user_turn_personality_remote_model_template_includes_update_message() -
             summary: None,
             collaboration_mode: None,
             personality: Some(Personality::Pragmatic),
+            verbosity: None,
         })
         .await?;
 
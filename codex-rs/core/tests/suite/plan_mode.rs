@@ -0,0 +1,244 @@
+#![allow(clippy::unwrap_used)]
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+use codex_core::CodexThread;
+use codex_core::config::types::McpServerConfig;
+use codex_core::config::types::McpServerTransportConfig;
+use codex_core::features::Feature;
+use codex_core::protocol::AskForApproval;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_core::protocol::SandboxPolicy;
+use codex_protocol::config_types::CollaborationMode;
+use codex_protocol::config_types::ModeKind;
+use codex_protocol::config_types::ReasoningSummary;
+use codex_protocol::config_types::Settings;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ResponseMock;
+use core_test_support::responses::ev_apply_patch_function_call;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_function_call;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::stdio_server_bin;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use serial_test::serial;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn plan_mode_rejects_mutating_tool_calls() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let TestCodex {
+        codex,
+        cwd,
+        session_configured,
+        ..
+    } = test_codex()
+        .with_config(|config| {
+            config.features.enable(Feature::CollaborationModes);
+            config.include_apply_patch_tool = true;
+        })
+        .build(&server)
+        .await?;
+
+    let call_id = "plan-patch";
+    let patch = "*** Begin Patch\n*** Add File: created.txt\n+hello\n*** End Patch";
+    let responses = mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_apply_patch_function_call(call_id, patch),
+                ev_completed("resp-1"),
+            ]),
+            sse(vec![
+                ev_assistant_message("msg-1", "planned"),
+                ev_completed("resp-2"),
+            ]),
+        ],
+    )
+    .await;
+
+    run_plan_turn(&codex, cwd.path(), &session_configured.model).await?;
+
+    assert_rejected_in_plan_mode(&responses, call_id);
+    assert!(!cwd.path().join("created.txt").exists());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[serial(mcp_test_value)]
+async fn plan_mode_rejects_mcp_tools_not_annotated_read_only() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let rmcp_test_server_bin = stdio_server_bin()?;
+    let TestCodex {
+        codex,
+        cwd,
+        session_configured,
+        ..
+    } = test_codex()
+        .with_config(move |config| {
+            config.features.enable(Feature::CollaborationModes);
+            let mut servers = config.mcp_servers.get().clone();
+            servers.insert(
+                "rmcp".to_string(),
+                McpServerConfig {
+                    transport: McpServerTransportConfig::Stdio {
+                        command: rmcp_test_server_bin,
+                        args: Vec::new(),
+                        env: Some(HashMap::from([(
+                            "MCP_TEST_VALUE".to_string(),
+                            "plan".to_string(),
+                        )])),
+                        env_vars: Vec::new(),
+                        cwd: None,
+                    },
+                    enabled: true,
+                    disabled_reason: None,
+                    startup_timeout_sec: Some(Duration::from_secs(10)),
+                    tool_timeout_sec: None,
+                    enabled_tools: None,
+                    disabled_tools: None,
+                    scopes: None,
+                    lazy_start: false,
+                    tool_aliases: None,
+                    tool_approval: None,
+                    tool_approvals: None,
+                    tool_output_token_limit: None,
+                    tool_output_token_limits: None,
+                },
+            );
+            config
+                .mcp_servers
+                .set(servers)
+                .expect("test mcp servers should accept any configuration");
+        })
+        .build(&server)
+        .await?;
+
+    let call_id = "plan-mcp";
+    let responses = mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_function_call(call_id, "mcp__rmcp__echo", "{\"message\":\"ping\"}"),
+                ev_completed("resp-1"),
+            ]),
+            sse(vec![
+                ev_assistant_message("msg-1", "planned"),
+                ev_completed("resp-2"),
+            ]),
+        ],
+    )
+    .await;
+
+    run_plan_turn(&codex, cwd.path(), &session_configured.model).await?;
+
+    assert_rejected_in_plan_mode(&responses, call_id);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn plan_mode_rejects_spawning_sub_agents() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let TestCodex {
+        codex,
+        cwd,
+        session_configured,
+        ..
+    } = test_codex()
+        .with_config(|config| {
+            config.features.enable(Feature::CollaborationModes);
+            config.features.enable(Feature::Collab);
+        })
+        .build(&server)
+        .await?;
+
+    let call_id = "plan-spawn";
+    let responses = mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_function_call(
+                    call_id,
+                    "spawn_agent",
+                    "{\"message\":\"create created.txt\"}",
+                ),
+                ev_completed("resp-1"),
+            ]),
+            sse(vec![
+                ev_assistant_message("msg-1", "planned"),
+                ev_completed("resp-2"),
+            ]),
+        ],
+    )
+    .await;
+
+    run_plan_turn(&codex, cwd.path(), &session_configured.model).await?;
+
+    assert_rejected_in_plan_mode(&responses, call_id);
+
+    Ok(())
+}
+
+async fn run_plan_turn(codex: &CodexThread, cwd: &Path, model: &str) -> anyhow::Result<()> {
+    codex
+        .submit(Op::UserTurn {
+            items: vec![UserInput::Text {
+                text: "plan the change".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+            cwd: cwd.to_path_buf(),
+            approval_policy: AskForApproval::Never,
+            sandbox_policy: SandboxPolicy::DangerFullAccess,
+            model: model.to_string(),
+            effort: None,
+            summary: ReasoningSummary::Auto,
+            collaboration_mode: Some(CollaborationMode {
+                mode: ModeKind::Plan,
+                settings: Settings {
+                    model: model.to_string(),
+                    reasoning_effort: None,
+                    developer_instructions: None,
+                },
+            }),
+            personality: None,
+        })
+        .await?;
+
+    wait_for_event(codex, |event| matches!(event, EventMsg::TurnComplete(_))).await;
+    Ok(())
+}
+
+fn assert_rejected_in_plan_mode(responses: &ResponseMock, call_id: &str) {
+    let requests = responses.requests();
+    let output = requests
+        .last()
+        .unwrap()
+        .function_call_output_content_and_success(call_id)
+        .and_then(|(content, _)| content)
+        .unwrap();
+    assert!(
+        output.contains("not allowed in Plan mode"),
+        "unexpected output: {output}"
+    );
+}
@@ -1,6 +1,7 @@
 #![allow(clippy::unwrap_used)]
 
 use codex_apply_patch::APPLY_PATCH_TOOL_INSTRUCTIONS;
+use codex_core::config::types::PromptCacheKeyScope;
 use codex_core::features::Feature;
 use codex_core::models_manager::model_info::BASE_INSTRUCTIONS;
 use codex_core::protocol::AskForApproval;
@@ -356,6 +357,7 @@ async fn overrides_turn_context_but_keeps_cached_prefix_and_key_constant() -> an
             summary: Some(ReasoningSummary::Detailed),
             collaboration_mode: None,
             personality: None,
+            verbosity: None,
         })
         .await?;
 
@@ -431,6 +433,7 @@ async fn override_before_first_turn_emits_environment_context() -> anyhow::Resul
             summary: None,
             collaboration_mode: Some(collaboration_mode),
             personality: None,
+            verbosity: None,
         })
         .await?;
 
@@ -844,3 +847,51 @@ async fn send_user_turn_with_changes_sends_environment_context() -> anyhow::Resu
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn workspace_prompt_cache_key_is_shared_across_sessions() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let req1 = mount_sse_once(&server, sse_completed("resp-1")).await;
+    let req2 = mount_sse_once(&server, sse_completed("resp-2")).await;
+    let workspace = TempDir::new()?;
+
+    let mut conversation_ids = Vec::new();
+    for text in ["hello from session 1", "hello from session 2"] {
+        let cwd = workspace.path().to_path_buf();
+        let test = test_codex()
+            .with_config(move |config| {
+                config.cwd = cwd;
+                config.prompt_cache_key = PromptCacheKeyScope::Workspace;
+                config.model_provider.prompt_cache_key_header = Some("x-cache-key".to_string());
+            })
+            .build(&server)
+            .await?;
+        conversation_ids.push(test.session_configured.session_id.to_string());
+        test.codex
+            .submit(Op::UserInput {
+                items: vec![UserInput::Text {
+                    text: text.into(),
+                    text_elements: Vec::new(),
+                }],
+                final_output_json_schema: None,
+            })
+            .await?;
+        wait_for_event(&test.codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+    }
+
+    let request1 = req1.single_request();
+    let request2 = req2.single_request();
+    let key = request1.body_json()["prompt_cache_key"]
+        .as_str()
+        .expect("prompt_cache_key")
+        .to_string();
+    assert!(key.starts_with("workspace-"), "unexpected key {key}");
+    assert!(!conversation_ids.contains(&key));
+    assert_eq!(request2.body_json()["prompt_cache_key"], key.as_str());
+    assert_eq!(request1.header("x-cache-key"), Some(key.clone()));
+    assert_eq!(request2.header("x-cache-key"), Some(key));
+
+    Ok(())
+}
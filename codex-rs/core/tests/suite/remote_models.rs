@@ -144,6 +144,7 @@ async fn remote_models_remote_model_uses_unified_exec() -> Result<()> {
             summary: None,
             collaboration_mode: None,
             personality: None,
+            verbosity: None,
         })
         .await?;
 
@@ -374,6 +375,7 @@ async fn remote_models_apply_remote_base_instructions() -> Result<()> {
             summary: None,
             collaboration_mode: None,
             personality: None,
+            verbosity: None,
         })
         .await?;
 
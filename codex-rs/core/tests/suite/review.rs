@@ -825,6 +825,7 @@ async fn review_uses_overridden_cwd_for_base_branch_merge_base() {
             summary: None,
             collaboration_mode: None,
             personality: None,
+            verbosity: None,
         })
         .await
         .unwrap();
@@ -94,6 +94,12 @@ async fn stdio_server_round_trip() -> anyhow::Result<()> {
                     enabled_tools: None,
                     disabled_tools: None,
                     scopes: None,
+                    lazy_start: false,
+                    tool_aliases: None,
+                    tool_approval: None,
+                    tool_approvals: None,
+                    tool_output_token_limit: None,
+                    tool_output_token_limits: None,
                 },
             );
             config
@@ -235,6 +241,12 @@ async fn stdio_image_responses_round_trip() -> anyhow::Result<()> {
                     enabled_tools: None,
                     disabled_tools: None,
                     scopes: None,
+                    lazy_start: false,
+                    tool_aliases: None,
+                    tool_approval: None,
+                    tool_approvals: None,
+                    tool_output_token_limit: None,
+                    tool_output_token_limits: None,
                 },
             );
             config
@@ -434,6 +446,12 @@ async fn stdio_image_completions_round_trip() -> anyhow::Result<()> {
                     enabled_tools: None,
                     disabled_tools: None,
                     scopes: None,
+                    lazy_start: false,
+                    tool_aliases: None,
+                    tool_approval: None,
+                    tool_approvals: None,
+                    tool_output_token_limit: None,
+                    tool_output_token_limits: None,
                 },
             );
             config
@@ -528,6 +546,120 @@ async fn stdio_image_completions_round_trip() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[serial(mcp_test_value)]
+async fn lazy_stdio_server_tools_are_available_on_first_turn() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = responses::start_mock_server().await;
+
+    let call_id = "call-lazy";
+    let server_name = "rmcp_lazy";
+    let tool_name = format!("mcp__{server_name}__echo");
+
+    let first = mount_sse_once(
+        &server,
+        responses::sse(vec![
+            responses::ev_response_created("resp-1"),
+            responses::ev_function_call(call_id, &tool_name, "{\"message\":\"ping\"}"),
+            responses::ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+    mount_sse_once(
+        &server,
+        responses::sse(vec![
+            responses::ev_assistant_message("msg-1", "rmcp echo tool completed successfully."),
+            responses::ev_completed("resp-2"),
+        ]),
+    )
+    .await;
+
+    let rmcp_test_server_bin = stdio_server_bin()?;
+
+    let fixture = test_codex()
+        .with_config(move |config| {
+            let mut servers = config.mcp_servers.get().clone();
+            servers.insert(
+                server_name.to_string(),
+                McpServerConfig {
+                    transport: McpServerTransportConfig::Stdio {
+                        command: rmcp_test_server_bin,
+                        args: Vec::new(),
+                        env: None,
+                        env_vars: Vec::new(),
+                        cwd: None,
+                    },
+                    enabled: true,
+                    disabled_reason: None,
+                    startup_timeout_sec: Some(Duration::from_secs(10)),
+                    tool_timeout_sec: None,
+                    enabled_tools: None,
+                    disabled_tools: None,
+                    scopes: None,
+                    lazy_start: true,
+                    tool_aliases: None,
+                    tool_approval: None,
+                    tool_approvals: None,
+                    tool_output_token_limit: None,
+                    tool_output_token_limits: None,
+                },
+            );
+            config
+                .mcp_servers
+                .set(servers)
+                .expect("test mcp servers should accept any configuration");
+        })
+        .build(&server)
+        .await?;
+    let session_model = fixture.session_configured.model.clone();
+
+    fixture
+        .codex
+        .submit(Op::UserTurn {
+            items: vec![UserInput::Text {
+                text: "call the rmcp echo tool".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+            cwd: fixture.cwd.path().to_path_buf(),
+            approval_policy: AskForApproval::Never,
+            sandbox_policy: SandboxPolicy::ReadOnly,
+            model: session_model,
+            effort: None,
+            summary: ReasoningSummary::Auto,
+            collaboration_mode: None,
+            personality: None,
+        })
+        .await?;
+
+    let end_event = wait_for_event(&fixture.codex, |ev| {
+        matches!(ev, EventMsg::McpToolCallEnd(_))
+    })
+    .await;
+    let EventMsg::McpToolCallEnd(end) = end_event else {
+        unreachable!("event guard guarantees McpToolCallEnd");
+    };
+    assert_eq!(end.invocation.server, server_name);
+    assert!(end.is_success(), "lazy echo call failed: {:?}", end.result);
+
+    wait_for_event(&fixture.codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+
+    // The lazy server was started for the first request, so its tools were
+    // already advertised to the model.
+    let body = first.single_request().body_json();
+    let advertised = body["tools"]
+        .as_array()
+        .expect("tools array")
+        .iter()
+        .any(|tool| tool["name"].as_str() == Some(tool_name.as_str()));
+    assert!(advertised, "{tool_name} missing from the first request");
+
+    server.verify().await;
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 #[serial(mcp_test_value)]
 async fn stdio_server_propagates_whitelisted_env_vars() -> anyhow::Result<()> {
@@ -581,6 +713,12 @@ async fn stdio_server_propagates_whitelisted_env_vars() -> anyhow::Result<()> {
                     enabled_tools: None,
                     disabled_tools: None,
                     scopes: None,
+                    lazy_start: false,
+                    tool_aliases: None,
+                    tool_approval: None,
+                    tool_approvals: None,
+                    tool_output_token_limit: None,
+                    tool_output_token_limits: None,
                 },
             );
             config
@@ -739,6 +877,12 @@ async fn streamable_http_tool_call_round_trip() -> anyhow::Result<()> {
                     enabled_tools: None,
                     disabled_tools: None,
                     scopes: None,
+                    lazy_start: false,
+                    tool_aliases: None,
+                    tool_approval: None,
+                    tool_approvals: None,
+                    tool_output_token_limit: None,
+                    tool_output_token_limits: None,
                 },
             );
             config
@@ -929,6 +1073,12 @@ async fn streamable_http_with_oauth_round_trip() -> anyhow::Result<()> {
                     enabled_tools: None,
                     disabled_tools: None,
                     scopes: None,
+                    lazy_start: false,
+                    tool_aliases: None,
+                    tool_approval: None,
+                    tool_approvals: None,
+                    tool_output_token_limit: None,
+                    tool_output_token_limits: None,
                 },
             );
             config
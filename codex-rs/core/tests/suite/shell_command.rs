@@ -298,3 +298,34 @@ async fn unicode_output_with_newlines(login: bool) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn rerun_command_reports_changes_since_previous_run() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+    skip_if_windows!(Ok(()));
+
+    let harness = shell_command_harness_with(|builder| builder.with_model("gpt-5.1")).await?;
+
+    let command = "echo run >> runs.log && cat runs.log";
+    mount_sse_sequence(
+        harness.server(),
+        [
+            shell_responses("first-run", command, Some(false)),
+            shell_responses("second-run", command, Some(false)),
+        ]
+        .concat(),
+    )
+    .await;
+    harness.submit("run the command").await?;
+    harness.submit("run it again").await?;
+
+    let first = harness.function_call_stdout("first-run").await;
+    assert_shell_command_output(&first, "run")?;
+    let second = harness.function_call_stdout("second-run").await;
+    assert!(
+        second.ends_with("Changes since previous run:\n@@ -1 +1,2 @@\n run\n+run\n"),
+        "expected diff against the first run, got: {second:?}"
+    );
+
+    Ok(())
+}
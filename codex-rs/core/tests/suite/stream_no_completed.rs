@@ -1,5 +1,6 @@
 //! Verifies that the agent retries when the SSE stream terminates before
-//! delivering a `response.completed` event.
+//! delivering a `response.completed` event, and continues from the items it
+//! already received instead of starting the response over.
 
 use codex_core::ModelProviderInfo;
 use codex_core::WireApi;
@@ -8,10 +9,17 @@ use codex_core::protocol::Op;
 use codex_protocol::user_input::UserInput;
 use core_test_support::load_sse_fixture;
 use core_test_support::load_sse_fixture_with_id;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
 use core_test_support::skip_if_no_network;
 use core_test_support::test_codex::TestCodex;
 use core_test_support::test_codex::test_codex;
 use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
 use wiremock::Mock;
 use wiremock::MockServer;
 use wiremock::Request;
@@ -72,6 +80,9 @@ async fn retries_on_early_close() {
         env_key: Some("PATH".into()),
         env_key_instructions: None,
         experimental_bearer_token: None,
+        auth_header: None,
+        auth_scheme: None,
+        prompt_cache_key_header: None,
         wire_api: WireApi::Responses,
         query_params: None,
         http_headers: None,
@@ -79,6 +90,9 @@ async fn retries_on_early_close() {
         // exercise retry path: first attempt yields incomplete stream, so allow 1 retry
         request_max_retries: Some(0),
         stream_max_retries: Some(1),
+        retry_base_delay_ms: None,
+        retry_max_delay_ms: None,
+        retry_status_codes: None,
         stream_idle_timeout_ms: Some(2000),
         requires_openai_auth: false,
         supports_websockets: false,
@@ -106,3 +120,57 @@ async fn retries_on_early_close() {
     // Wait until TurnComplete (should succeed after retry).
     wait_for_event(&codex, |event| matches!(event, EventMsg::TurnComplete(_))).await;
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn continues_from_received_items_after_early_close() {
+    skip_if_no_network!();
+
+    let server = MockServer::start().await;
+    let responses = mount_sse_sequence(
+        &server,
+        vec![
+            // The connection drops after the first message, before `response.completed`.
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_assistant_message("msg-1", "first half"),
+            ]),
+            sse(vec![
+                ev_response_created("resp-2"),
+                ev_assistant_message("msg-2", "second half"),
+                ev_completed("resp-2"),
+            ]),
+        ],
+    )
+    .await;
+
+    let TestCodex { codex, .. } = test_codex().build(&server).await.unwrap();
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "hello".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await
+        .unwrap();
+
+    let stream_error =
+        wait_for_event(&codex, |event| matches!(event, EventMsg::StreamError(_))).await;
+    let EventMsg::StreamError(stream_error) = stream_error else {
+        unreachable!();
+    };
+    assert_eq!(stream_error.message, "Reconnecting... 1/5");
+    wait_for_event(&codex, |event| matches!(event, EventMsg::TurnComplete(_))).await;
+
+    let requests = responses.requests();
+    assert_eq!(requests.len(), 2);
+    let assistant_texts: Vec<String> = requests[1]
+        .inputs_of_type("message")
+        .into_iter()
+        .filter(|item| item.get("role").and_then(Value::as_str) == Some("assistant"))
+        .filter_map(|item| item["content"][0]["text"].as_str().map(str::to_owned))
+        .collect();
+    assert_eq!(assistant_texts, vec!["first half".to_string()]);
+}
@@ -432,6 +432,12 @@ async fn mcp_tool_call_output_exceeds_limit_truncated_for_model() -> Result<()>
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                lazy_start: false,
+                tool_aliases: None,
+                tool_approval: None,
+                tool_approvals: None,
+                tool_output_token_limit: None,
+                tool_output_token_limits: None,
             },
         );
         config
@@ -525,6 +531,12 @@ async fn mcp_image_output_preserves_image_and_no_text_summary() -> Result<()> {
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                lazy_start: false,
+                tool_aliases: None,
+                tool_approval: None,
+                tool_approvals: None,
+                tool_output_token_limit: None,
+                tool_output_token_limits: None,
             },
         );
         config
@@ -789,6 +801,12 @@ async fn mcp_tool_call_output_not_truncated_with_custom_limit() -> Result<()> {
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                lazy_start: false,
+                tool_aliases: None,
+                tool_approval: None,
+                tool_approvals: None,
+                tool_output_token_limit: None,
+                tool_output_token_limits: None,
             },
         );
         config
@@ -0,0 +1,113 @@
+#![allow(clippy::unwrap_used)]
+
+use std::path::Path;
+use std::time::Duration;
+
+use codex_core::workers::TaskStatus;
+use codex_core::workers::WorkerPool;
+use codex_core::workers::WorkerPoolOptions;
+use core_test_support::responses;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_function_call;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+fn git(repo: &Path, args: &[&str]) {
+    let output = std::process::Command::new("git")
+        .env("GIT_CONFIG_GLOBAL", "/dev/null")
+        .env("GIT_CONFIG_NOSYSTEM", "1")
+        .args(args)
+        .current_dir(repo)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "git {args:?} failed: {output:?}");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn worker_finishes_when_the_model_requests_user_input() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let TestCodex {
+        home,
+        cwd,
+        config,
+        thread_manager,
+        ..
+    } = test_codex().build(&server).await?;
+
+    git(cwd.path(), &["init", "-q", "-b", "main"]);
+    git(cwd.path(), &["config", "user.email", "codex@example.com"]);
+    git(cwd.path(), &["config", "user.name", "Codex"]);
+    std::fs::write(cwd.path().join("README.md"), "hello\n")?;
+    git(cwd.path(), &["add", "."]);
+    git(cwd.path(), &["commit", "-q", "-m", "init"]);
+
+    let queue_path = home.path().join("tasks.jsonl");
+    std::fs::write(&queue_path, "ask before doing anything\n")?;
+
+    let call_id = "user-input-call";
+    let request_args = json!({
+        "questions": [{
+            "id": "confirm_path",
+            "header": "Confirm",
+            "question": "Proceed with the plan?",
+            "options": [{
+                "label": "Yes (Recommended)",
+                "description": "Continue the current plan."
+            }]
+        }]
+    })
+    .to_string();
+    responses::mount_sse_once(
+        &server,
+        sse(vec![
+            ev_response_created("resp-1"),
+            ev_function_call(call_id, "request_user_input", &request_args),
+            ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+    let second_mock = responses::mount_sse_once(
+        &server,
+        sse(vec![
+            ev_assistant_message("msg-1", "done without asking"),
+            ev_completed("resp-2"),
+        ]),
+    )
+    .await;
+
+    let pool = WorkerPool::start(
+        thread_manager,
+        config,
+        WorkerPoolOptions {
+            queue_path,
+            workers: 1,
+        },
+    )
+    .await?;
+    let mut snapshots = pool.subscribe();
+    tokio::time::timeout(
+        Duration::from_secs(30),
+        snapshots.wait_for(|snapshot| snapshot.is_finished()),
+    )
+    .await??;
+
+    let snapshot = pool.snapshot();
+    assert_eq!(snapshot.tasks.len(), 1);
+    assert_eq!(snapshot.tasks[0].status, TaskStatus::Done);
+    assert_eq!(
+        snapshot.tasks[0].result.as_deref(),
+        Some("done without asking")
+    );
+    second_mock.single_request().function_call_output(call_id);
+
+    Ok(())
+}
@@ -14,6 +14,7 @@ use std::sync::mpsc::Sender;
 
 use anyhow::Context;
 use anyhow::Result;
+use codex_app_server_protocol::APP_SERVER_PROTOCOL_VERSION;
 use codex_app_server_protocol::AskForApproval;
 use codex_app_server_protocol::ClientInfo;
 use codex_app_server_protocol::ClientNotification;
@@ -102,6 +103,8 @@ impl AppServerClient {
                 },
                 capabilities: Some(InitializeCapabilities {
                     experimental_api: true,
+                    protocol_version: Some(APP_SERVER_PROTOCOL_VERSION),
+                    notification_methods: None,
                 }),
             },
         };
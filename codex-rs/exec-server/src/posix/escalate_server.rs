@@ -91,6 +91,7 @@ impl EscalateServer {
                 windows_sandbox_level: WindowsSandboxLevel::Disabled,
                 justification: None,
                 arg0: None,
+                limits: codex_core::exec::ExecLimits::default(),
             },
             &sandbox_state.sandbox_policy,
             &sandbox_state.sandbox_cwd,
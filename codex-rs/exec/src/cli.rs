@@ -26,6 +26,16 @@ pub struct Cli {
     #[arg(long, short = 'm', global = true)]
     pub model: Option<String>,
 
+    /// Reasoning effort for reasoning-capable models. Overrides
+    /// `model_reasoning_effort` from config.toml and the active profile.
+    #[arg(long = "reasoning-effort", value_enum)]
+    pub reasoning_effort: Option<codex_common::ReasoningEffortCliArg>,
+
+    /// Output verbosity for models that support it. Overrides
+    /// `model_verbosity` from config.toml and the active profile.
+    #[arg(long = "verbosity", value_enum)]
+    pub verbosity: Option<codex_common::VerbosityCliArg>,
+
     /// Use open-source provider.
     #[arg(long = "oss", default_value_t = false)]
     pub oss: bool,
@@ -71,10 +81,41 @@ pub struct Cli {
     #[arg(long = "add-dir", value_name = "DIR", value_hint = clap::ValueHint::DirPath)]
     pub add_dir: Vec<PathBuf>,
 
+    /// Record every raw model API request and streamed response to a JSON Lines file in DIR.
+    #[arg(long = "record-api", value_name = "DIR", value_hint = clap::ValueHint::DirPath)]
+    pub record_api: Option<PathBuf>,
+
+    /// Serve model responses from a `--record-api` recording instead of calling the API.
+    #[arg(
+        long = "replay-api",
+        value_name = "FILE",
+        conflicts_with = "record_api"
+    )]
+    pub replay_api: Option<PathBuf>,
+
     /// Path to a JSON Schema file describing the model's final response shape.
     #[arg(long = "output-schema", value_name = "FILE")]
     pub output_schema: Option<PathBuf>,
 
+    /// Write the final message, parsed as JSON and checked against
+    /// `--output-schema`, to this file.
+    #[arg(long = "output-file", value_name = "FILE", requires = "output_schema")]
+    pub output_file: Option<PathBuf>,
+
+    /// Stop with exit code 3 once the agent has used this many model
+    /// responses (one per round of tool calls).
+    #[arg(long = "max-turns", value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    pub max_turns: Option<u32>,
+
+    /// Stop with exit code 3 once the run has used this many tokens.
+    #[arg(long = "max-tokens", value_name = "N", value_parser = clap::value_parser!(i64).range(1..))]
+    pub max_tokens: Option<i64>,
+
+    /// Stop with exit code 3 once estimated spend reaches this many US
+    /// dollars. Needs a `[model_prices]` entry for the model in config.toml.
+    #[arg(long = "max-cost", value_name = "USD")]
+    pub max_cost: Option<f64>,
+
     #[clap(skip)]
     pub config_overrides: CliConfigOverrides,
 
@@ -82,7 +123,7 @@ pub struct Cli {
     #[arg(long = "color", value_enum, default_value_t = Color::Auto)]
     pub color: Color,
 
-    /// Print events to stdout as JSONL.
+    /// Print the event stream to stdout as JSONL (see docs/exec.md).
     #[arg(
         long = "json",
         alias = "experimental-json",
@@ -95,8 +136,17 @@ pub struct Cli {
     #[arg(long = "output-last-message", short = 'o', value_name = "FILE")]
     pub last_message_file: Option<PathBuf>,
 
+    /// Replace `{NAME}` in the prompt with VALUE. May be repeated.
+    #[arg(
+        long = "var",
+        value_name = "NAME=VALUE",
+        value_parser = crate::prompt_template::parse_prompt_var
+    )]
+    pub vars: Vec<(String, String)>,
+
     /// Initial instructions for the agent. If not provided as an argument (or
-    /// if `-` is used), instructions are read from stdin.
+    /// if `-` is used), instructions are read from stdin. Otherwise `{stdin}`
+    /// in the prompt is replaced with piped input.
     #[arg(value_name = "PROMPT", value_hint = clap::ValueHint::Other)]
     pub prompt: Option<String>,
 }
@@ -231,11 +281,33 @@ pub struct ReviewArgs {
     #[arg(long = "title", value_name = "TITLE", requires = "commit")]
     pub commit_title: Option<String>,
 
+    /// Review the changes in a git revision range, e.g. `main...HEAD`.
+    #[arg(
+        long = "diff",
+        value_name = "RANGE",
+        conflicts_with_all = ["uncommitted", "base", "commit", "prompt"]
+    )]
+    pub diff: Option<String>,
+
+    /// Print the findings to stdout in this format instead of the review
+    /// text. The review runs in a read-only sandbox.
+    #[arg(long = "format", value_enum, value_name = "FORMAT")]
+    pub format: Option<ReviewFormat>,
+
     /// Custom review instructions. If `-` is used, read from stdin.
     #[arg(value_name = "PROMPT", value_hint = clap::ValueHint::Other)]
     pub prompt: Option<String>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum ReviewFormat {
+    /// SARIF 2.1.0, for code-scanning uploads.
+    Sarif,
+    /// Markdown, for PR comments.
+    Markdown,
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
 #[value(rename_all = "kebab-case")]
 pub enum Color {
@@ -24,11 +24,13 @@ use codex_core::protocol::ItemCompletedEvent;
 use codex_core::protocol::McpInvocation;
 use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
+use codex_core::protocol::ModelFailoverEvent;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PatchApplyEndEvent;
 use codex_core::protocol::SessionConfiguredEvent;
 use codex_core::protocol::StreamErrorEvent;
 use codex_core::protocol::TurnAbortReason;
+use codex_core::protocol::TurnBlockedEvent;
 use codex_core::protocol::TurnCompleteEvent;
 use codex_core::protocol::TurnDiffEvent;
 use codex_core::protocol::WarningEvent;
@@ -181,6 +183,10 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                 let prefix = "ERROR:".style(self.red);
                 ts_msg!(self, "{prefix} {message}");
             }
+            EventMsg::TurnBlocked(TurnBlockedEvent { category, message }) => {
+                let label = format!("BLOCKED ({category}):");
+                ts_msg!(self, "{} {message}", label.style(self.red));
+            }
             EventMsg::Warning(WarningEvent { message }) => {
                 ts_msg!(
                     self,
@@ -188,6 +194,19 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     "warning:".style(self.yellow).style(self.bold)
                 );
             }
+            EventMsg::ModelFailover(ModelFailoverEvent {
+                from_provider,
+                from_model,
+                to_provider,
+                to_model,
+                reason,
+            }) => {
+                ts_msg!(
+                    self,
+                    "{} {from_model} on {from_provider} kept failing ({reason}); continuing with {to_model} on {to_provider}",
+                    "failover:".style(self.yellow).style(self.bold)
+                );
+            }
             EventMsg::DeprecationNotice(DeprecationNoticeEvent { summary, details }) => {
                 ts_msg!(
                     self,
@@ -592,6 +611,13 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     view.path.display()
                 );
             }
+            EventMsg::OpenFileRequest(request) => {
+                let location = match request.line {
+                    Some(line) => format!("{}:{line}", request.path.display()),
+                    None => request.path.display().to_string(),
+                };
+                ts_msg!(self, "{} {location}", "open file".style(self.magenta));
+            }
             EventMsg::TurnAborted(abort_reason) => match abort_reason.reason {
                 TurnAbortReason::Interrupted => {
                     ts_msg!(self, "task interrupted");
@@ -764,14 +790,20 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             }
             EventMsg::ShutdownComplete => return CodexStatus::Shutdown,
             EventMsg::ThreadNameUpdated(_)
+            | EventMsg::ModelSnapshot(_)
             | EventMsg::ExecApprovalRequest(_)
             | EventMsg::ApplyPatchApprovalRequest(_)
             | EventMsg::TerminalInteraction(_)
             | EventMsg::ExecCommandOutputDelta(_)
             | EventMsg::GetHistoryEntryResponse(_)
             | EventMsg::McpListToolsResponse(_)
+            | EventMsg::McpLoginCompleted(_)
+            | EventMsg::McpToolCallProgress(_)
+            | EventMsg::McpServerStatusResponse(_)
             | EventMsg::ListCustomPromptsResponse(_)
             | EventMsg::ListSkillsResponse(_)
+            | EventMsg::ListTrashResponse(_)
+            | EventMsg::ListJobsResponse(_)
             | EventMsg::RawResponseItem(_)
             | EventMsg::UserMessage(_)
             | EventMsg::EnteredReviewMode(_)
@@ -787,8 +819,12 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             | EventMsg::ReasoningRawContentDelta(_)
             | EventMsg::SkillsUpdateAvailable
             | EventMsg::UndoCompleted(_)
+            | EventMsg::CheckpointCreated(_)
             | EventMsg::UndoStarted(_)
             | EventMsg::ThreadRolledBack(_)
+            | EventMsg::ContextEditPreview(_)
+            | EventMsg::PinnedContextUpdated(_)
+            | EventMsg::ContextEdited(_)
             | EventMsg::RequestUserInput(_)
             | EventMsg::DynamicToolCallRequest(_) => {}
         }
@@ -6,6 +6,9 @@ use crate::event_processor::CodexStatus;
 use crate::event_processor::EventProcessor;
 use crate::event_processor::handle_last_message;
 use crate::exec_events::AgentMessageItem;
+use crate::exec_events::ApprovalDecision;
+use crate::exec_events::ApprovalKind;
+use crate::exec_events::ApprovalResolvedEvent;
 use crate::exec_events::CollabAgentState;
 use crate::exec_events::CollabAgentStatus;
 use crate::exec_events::CollabTool;
@@ -34,6 +37,7 @@ use crate::exec_events::ThreadStartedEvent;
 use crate::exec_events::TodoItem;
 use crate::exec_events::TodoListItem;
 use crate::exec_events::TurnCompletedEvent;
+use crate::exec_events::TurnDiffUpdatedEvent;
 use crate::exec_events::TurnFailedEvent;
 use crate::exec_events::TurnStartedEvent;
 use crate::exec_events::Usage;
@@ -160,7 +164,48 @@ impl EventProcessorWithJsonOutput {
                 Vec::new()
             }
             protocol::EventMsg::TurnStarted(ev) => self.handle_task_started(ev),
-            protocol::EventMsg::TurnComplete(_) => self.handle_task_complete(),
+            protocol::EventMsg::TurnComplete(ev) => {
+                self.handle_task_complete(ev.last_agent_message.as_deref())
+            }
+            protocol::EventMsg::TurnDiff(ev) => {
+                vec![ThreadEvent::TurnDiffUpdated(TurnDiffUpdatedEvent {
+                    unified_diff: ev.unified_diff.clone(),
+                })]
+            }
+            protocol::EventMsg::ExecApprovalRequest(ev) => {
+                vec![ThreadEvent::ApprovalResolved(ApprovalResolvedEvent {
+                    kind: ApprovalKind::Command,
+                    id: ev.call_id.clone(),
+                    decision: ApprovalDecision::Denied,
+                    summary: ev.command.join(" "),
+                })]
+            }
+            protocol::EventMsg::ApplyPatchApprovalRequest(ev) => {
+                let mut paths: Vec<String> = ev
+                    .changes
+                    .keys()
+                    .map(|path| path.display().to_string())
+                    .collect();
+                paths.sort();
+                vec![ThreadEvent::ApprovalResolved(ApprovalResolvedEvent {
+                    kind: ApprovalKind::Patch,
+                    id: ev.call_id.clone(),
+                    decision: ApprovalDecision::Denied,
+                    summary: paths.join(", "),
+                })]
+            }
+            protocol::EventMsg::ElicitationRequest(ev) => {
+                let id = match &ev.id {
+                    mcp_types::RequestId::String(id) => id.clone(),
+                    mcp_types::RequestId::Integer(id) => id.to_string(),
+                };
+                vec![ThreadEvent::ApprovalResolved(ApprovalResolvedEvent {
+                    kind: ApprovalKind::Elicitation,
+                    id,
+                    decision: ApprovalDecision::Cancelled,
+                    summary: format!("{}: {}", ev.server_name, ev.message),
+                })]
+            }
             protocol::EventMsg::Error(ev) => {
                 let error = ThreadErrorEvent {
                     message: ev.message.clone(),
@@ -177,6 +222,15 @@ impl EventProcessorWithJsonOutput {
                 };
                 vec![ThreadEvent::ItemCompleted(ItemCompletedEvent { item })]
             }
+            protocol::EventMsg::TurnBlocked(ev) => {
+                let item = ThreadItem {
+                    id: self.get_next_item_id(),
+                    details: ThreadItemDetails::Error(ErrorItem {
+                        message: format!("Response blocked ({}): {}", ev.category, ev.message),
+                    }),
+                };
+                vec![ThreadEvent::ItemCompleted(ItemCompletedEvent { item })]
+            }
             protocol::EventMsg::StreamError(ev) => {
                 let message = match &ev.additional_details {
                     Some(details) if !details.trim().is_empty() => {
@@ -749,7 +803,7 @@ impl EventProcessorWithJsonOutput {
         vec![ThreadEvent::TurnStarted(TurnStartedEvent {})]
     }
 
-    fn handle_task_complete(&mut self) -> Vec<ThreadEvent> {
+    fn handle_task_complete(&mut self, last_agent_message: Option<&str>) -> Vec<ThreadEvent> {
         let usage = if let Some(u) = &self.last_total_token_usage {
             Usage {
                 input_tokens: u.input_tokens,
@@ -790,7 +844,13 @@ impl EventProcessorWithJsonOutput {
         if let Some(error) = self.last_critical_error.take() {
             items.push(ThreadEvent::TurnFailed(TurnFailedEvent { error }));
         } else {
-            items.push(ThreadEvent::TurnCompleted(TurnCompletedEvent { usage }));
+            let final_message = last_agent_message
+                .or(self.last_proposed_plan.as_deref())
+                .map(str::to_string);
+            items.push(ThreadEvent::TurnCompleted(TurnCompletedEvent {
+                usage,
+                final_message,
+            }));
         }
 
         items
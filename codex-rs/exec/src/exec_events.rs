@@ -32,6 +32,14 @@ pub enum ThreadEvent {
     /// Signals that an item has reached a terminal state—either success or failure.
     #[serde(rename = "item.completed")]
     ItemCompleted(ItemCompletedEvent),
+    /// Emitted whenever the turn's combined diff changes. Carries the diff of
+    /// every file change made so far in the turn, not just the latest one.
+    #[serde(rename = "turn.diff_updated")]
+    TurnDiffUpdated(TurnDiffUpdatedEvent),
+    /// Emitted when codex exec answers an approval request itself because
+    /// there is no one to ask.
+    #[serde(rename = "approval.resolved")]
+    ApprovalResolved(ApprovalResolvedEvent),
     /// Represents an unrecoverable error emitted directly by the event stream.
     #[serde(rename = "error")]
     Error(ThreadErrorEvent),
@@ -50,6 +58,10 @@ pub struct TurnStartedEvent {}
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
 pub struct TurnCompletedEvent {
     pub usage: Usage,
+    /// The agent's last message in the turn (or the proposed plan when the
+    /// turn ended with one). A JSON string when `--output-schema` is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub final_message: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
@@ -83,6 +95,41 @@ pub struct ItemUpdatedEvent {
     pub item: ThreadItem,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+pub struct TurnDiffUpdatedEvent {
+    /// Unified diff of all changes made to files during the turn so far.
+    pub unified_diff: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+pub struct ApprovalResolvedEvent {
+    pub kind: ApprovalKind,
+    /// Call id of the command or patch, or the MCP server's request id for an
+    /// elicitation.
+    pub id: String,
+    pub decision: ApprovalDecision,
+    /// What was asked for: the command line, the changed paths, or the
+    /// server's message.
+    pub summary: String,
+}
+
+/// The kind of approval codex exec answered.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalKind {
+    Command,
+    Patch,
+    Elicitation,
+}
+
+/// How codex exec answered an approval request.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalDecision {
+    Denied,
+    Cancelled,
+}
+
 /// Fatal error emitted by the stream.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
 pub struct ThreadErrorEvent {
@@ -0,0 +1,83 @@
+//! Exit codes `codex exec` reports to scripts and CI.
+
+use codex_core::protocol::CodexErrorInfo;
+
+/// How a run ended. When several things went wrong, the later variant wins,
+/// so a run stopped by a budget reports that rather than the errors the
+/// interruption caused.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum ExecOutcome {
+    #[default]
+    Success,
+    /// The agent reported an error or its output was unusable.
+    TaskFailed,
+    /// The agent asked for an approval or user input that exec cannot give.
+    NeedsApproval,
+    /// The model provider failed, rejected the credentials, or ran out of quota.
+    ProviderError,
+    /// `--max-turns`, `--max-tokens` or `--max-cost` stopped the run.
+    BudgetExceeded,
+}
+
+impl ExecOutcome {
+    pub(crate) fn exit_code(self) -> i32 {
+        match self {
+            ExecOutcome::Success => 0,
+            ExecOutcome::TaskFailed => 1,
+            // 2 is left to clap for usage errors.
+            ExecOutcome::BudgetExceeded => 3,
+            ExecOutcome::NeedsApproval => 4,
+            ExecOutcome::ProviderError => 5,
+        }
+    }
+
+    pub(crate) fn record(&mut self, outcome: ExecOutcome) {
+        *self = (*self).max(outcome);
+    }
+
+    /// The outcome for an error event with `info`.
+    pub(crate) fn for_error(info: Option<&CodexErrorInfo>) -> Self {
+        match info {
+            Some(
+                CodexErrorInfo::UsageLimitExceeded
+                | CodexErrorInfo::ModelCap { .. }
+                | CodexErrorInfo::HttpConnectionFailed { .. }
+                | CodexErrorInfo::ResponseStreamConnectionFailed { .. }
+                | CodexErrorInfo::InternalServerError
+                | CodexErrorInfo::Unauthorized
+                | CodexErrorInfo::ResponseStreamDisconnected { .. }
+                | CodexErrorInfo::ResponseTooManyFailedAttempts { .. },
+            ) => ExecOutcome::ProviderError,
+            Some(
+                CodexErrorInfo::ContextWindowExceeded
+                | CodexErrorInfo::BadRequest
+                | CodexErrorInfo::SandboxError
+                | CodexErrorInfo::ThreadRollbackFailed
+                | CodexErrorInfo::Other,
+            )
+            | None => ExecOutcome::TaskFailed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn most_specific_outcome_wins() {
+        let mut outcome = ExecOutcome::default();
+        outcome.record(ExecOutcome::for_error(Some(
+            &CodexErrorInfo::ResponseTooManyFailedAttempts {
+                http_status_code: Some(429),
+            },
+        )));
+        outcome.record(ExecOutcome::for_error(None));
+        assert_eq!(outcome.exit_code(), 5);
+
+        outcome.record(ExecOutcome::BudgetExceeded);
+        outcome.record(ExecOutcome::NeedsApproval);
+        assert_eq!(outcome.exit_code(), 3);
+    }
+}
@@ -9,13 +9,19 @@ mod event_processor;
 mod event_processor_with_human_output;
 pub mod event_processor_with_jsonl_output;
 pub mod exec_events;
+mod exit_code;
+mod output_schema;
+mod prompt_template;
+mod review_report;
 
 pub use cli::Cli;
 pub use cli::Command;
 pub use cli::ReviewArgs;
 use codex_cloud_requirements::cloud_requirements_loader;
+use codex_common::oss::apply_ollama_model_details;
 use codex_common::oss::ensure_oss_provider_ready;
 use codex_common::oss::get_default_model_for_oss_provider;
+use codex_common::oss::is_ollama_provider;
 use codex_common::oss::ollama_chat_deprecation_notice;
 use codex_core::AuthManager;
 use codex_core::LMSTUDIO_OSS_PROVIDER_ID;
@@ -38,9 +44,12 @@ use codex_core::protocol::AskForApproval;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::Op;
+use codex_core::protocol::ReviewDecision;
 use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::ReviewTarget;
 use codex_core::protocol::SessionSource;
+use codex_core::protocol::WarningEvent;
+use codex_core::run_budget::RunBudget;
 use codex_protocol::approvals::ElicitationAction;
 use codex_protocol::config_types::SandboxMode;
 use codex_protocol::user_input::UserInput;
@@ -51,6 +60,7 @@ use serde_json::Value;
 use std::collections::HashSet;
 use std::io::IsTerminal;
 use std::io::Read;
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use supports_color::Stream;
@@ -66,6 +76,7 @@ use uuid::Uuid;
 use crate::cli::Command as ExecCommand;
 use crate::event_processor::CodexStatus;
 use crate::event_processor::EventProcessor;
+use crate::exit_code::ExecOutcome;
 use codex_core::default_client::set_default_client_residency_requirement;
 use codex_core::default_client::set_default_originator;
 use codex_core::find_thread_path_by_id_str;
@@ -105,12 +116,21 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         cwd,
         skip_git_repo_check,
         add_dir,
+        record_api,
+        replay_api,
         color,
         last_message_file,
         json: json_mode,
         sandbox_mode: sandbox_mode_cli_arg,
+        vars,
         prompt,
         output_schema: output_schema_path,
+        output_file,
+        max_turns,
+        max_tokens,
+        max_cost,
+        reasoning_effort,
+        verbosity,
         config_overrides,
     } = cli;
 
@@ -136,7 +156,19 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         .with_writer(std::io::stderr)
         .with_filter(env_filter);
 
-    let sandbox_mode = if full_auto {
+    let review_format = match &command {
+        Some(ExecCommand::Review(args)) => args.format,
+        _ => None,
+    };
+    if review_format.is_some() && json_mode {
+        eprintln!("--format cannot be combined with --json.");
+        std::process::exit(1);
+    }
+
+    let sandbox_mode = if review_format.is_some() {
+        // Reviews for automation only read the checkout.
+        Some(SandboxMode::ReadOnly)
+    } else if full_auto {
         Some(SandboxMode::WorkspaceWrite)
     } else if dangerously_bypass_approvals_and_sandbox {
         Some(SandboxMode::DangerFullAccess)
@@ -197,7 +229,7 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     };
 
     let cloud_auth_manager = AuthManager::shared(
-        codex_home.clone(),
+        config_toml.auth_home(&codex_home, config_profile.clone()),
         false,
         config_toml.cli_auth_credentials_store.unwrap_or_default(),
     );
@@ -252,15 +284,19 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         base_instructions: None,
         developer_instructions: None,
         personality: None,
+        model_reasoning_effort: reasoning_effort.map(Into::into),
+        model_verbosity: verbosity.map(Into::into),
         compact_prompt: None,
         include_apply_patch_tool: None,
         show_raw_agent_reasoning: oss.then_some(true),
         tools_web_search_request: None,
         ephemeral: None,
+        record_api_dir: record_api,
+        replay_api_path: replay_api,
         additional_writable_roots: add_dir,
     };
 
-    let config = ConfigBuilder::default()
+    let mut config = ConfigBuilder::default()
         .cli_overrides(cli_kv_overrides)
         .harness_overrides(overrides)
         .cloud_requirements(cloud_requirements)
@@ -320,21 +356,21 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         });
     }
 
-    if oss {
-        // We're in the oss section, so provider_id should be Some
-        // Let's handle None case gracefully though just in case
-        let provider_id = match model_provider.as_ref() {
-            Some(id) => id,
-            None => {
-                error!("OSS provider unexpectedly not set when oss flag is used");
-                return Err(anyhow::anyhow!(
-                    "OSS provider not set but oss flag was used"
-                ));
-            }
-        };
-        ensure_oss_provider_ready(provider_id, &config)
+    // `--oss` selects an OSS provider; a configured Ollama provider gets the
+    // same pull-on-first-use and capability detection.
+    if oss || is_ollama_provider(&config.model_provider_id) {
+        let provider_id = config.model_provider_id.clone();
+        let model_details = ensure_oss_provider_ready(&provider_id, &config)
             .await
             .map_err(|e| anyhow::anyhow!("OSS setup failed: {e}"))?;
+        if let Some(details) = model_details {
+            for message in apply_ollama_model_details(&mut config, &details) {
+                event_processor.process_event(Event {
+                    id: String::new(),
+                    msg: EventMsg::Warning(WarningEvent { message }),
+                });
+            }
+        }
     }
 
     let default_cwd = config.cwd.to_path_buf();
@@ -354,7 +390,7 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     }
 
     let auth_manager = AuthManager::shared(
-        config.codex_home.clone(),
+        config.auth_home(),
         true,
         config.cli_auth_credentials_store_mode,
     );
@@ -368,6 +404,28 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         .get_default_model(&config.model, &config, RefreshStrategy::OnlineIfUncached)
         .await;
 
+    // Core refuses further model responses and tool calls once a budget is
+    // reached; sub-agents inherit the budget with the config.
+    let max_cost = match max_cost {
+        Some(max_cost) if !max_cost.is_finite() || max_cost <= 0.0 => {
+            eprintln!("--max-cost must be a positive number of US dollars.");
+            std::process::exit(1);
+        }
+        Some(max_cost) => match config.model_prices.get(&default_model) {
+            Some(price) => Some((max_cost, *price)),
+            None => {
+                eprintln!(
+                    "--max-cost needs a price for `{default_model}`; add one under [model_prices] in config.toml."
+                );
+                std::process::exit(1);
+            }
+        },
+        None => None,
+    };
+    let run_budget = (max_turns.is_some() || max_tokens.is_some() || max_cost.is_some())
+        .then(|| Arc::new(RunBudget::new(max_turns, max_tokens, max_cost)));
+    config.run_budget = run_budget.clone();
+
     // Handle resume subcommand by resolving a rollout path and using explicit resume API.
     let NewThread {
         thread_id: primary_thread_id,
@@ -386,10 +444,16 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     } else {
         thread_manager.start_thread(config.clone()).await?
     };
+
     let (initial_operation, prompt_summary) = match (command, prompt, images) {
         (Some(ExecCommand::Review(review_cli)), _, _) => {
+            if let Some(range) = &review_cli.diff {
+                verify_diff_range(&default_cwd, range)?;
+            }
             let review_request = build_review_request(review_cli)?;
-            let summary = codex_core::review_prompts::user_facing_hint(&review_request.target);
+            let summary = review_request.user_facing_hint.clone().unwrap_or_else(|| {
+                codex_core::review_prompts::user_facing_hint(&review_request.target)
+            });
             (InitialOperation::Review { review_request }, summary)
         }
         (Some(ExecCommand::Resume(args)), root_prompt, imgs) => {
@@ -404,7 +468,7 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
                     }
                 })
                 .or(root_prompt);
-            let prompt_text = resolve_prompt(prompt_arg);
+            let prompt_text = resolve_templated_prompt(prompt_arg, &vars);
             let mut items: Vec<UserInput> = imgs
                 .into_iter()
                 .chain(args.images.into_iter())
@@ -425,7 +489,7 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
             )
         }
         (None, root_prompt, imgs) => {
-            let prompt_text = resolve_prompt(root_prompt);
+            let prompt_text = resolve_templated_prompt(root_prompt, &vars);
             let mut items: Vec<UserInput> = imgs
                 .into_iter()
                 .map(|path| UserInput::LocalImage { path })
@@ -498,6 +562,11 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         });
     }
 
+    let final_output_schema = match &initial_operation {
+        InitialOperation::UserTurn { output_schema, .. } => output_schema.clone(),
+        InitialOperation::Review { .. } => None,
+    };
+
     match initial_operation {
         InitialOperation::UserTurn {
             items,
@@ -528,31 +597,99 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     };
 
     // Run the loop until the task is complete.
-    // Track whether a fatal error was reported by the server so we can
-    // exit with a non-zero status for automation-friendly signaling.
-    let mut error_seen = false;
+    // Track how the run went so the exit code tells automation why it
+    // stopped; see `ExecOutcome`.
+    let mut outcome = ExecOutcome::default();
+    let mut schema_retries_left = output_schema::MAX_OUTPUT_SCHEMA_RETRIES;
+    let mut review_output = None;
     while let Some(envelope) = rx.recv().await {
         let ThreadEventEnvelope {
             thread_id,
             thread,
             event,
         } = envelope;
-        if let EventMsg::ElicitationRequest(ev) = &event.msg {
-            // Automatically cancel elicitation requests in exec mode.
-            thread
-                .submit(Op::ResolveElicitation {
-                    server_name: ev.server_name.clone(),
-                    request_id: ev.id.clone(),
-                    decision: ElicitationAction::Cancel,
-                })
-                .await?;
+        // There is no one to ask in exec mode: cancel elicitations and deny
+        // approvals that an overridden approval policy still requests.
+        match &event.msg {
+            EventMsg::ElicitationRequest(ev) => {
+                outcome.record(ExecOutcome::NeedsApproval);
+                thread
+                    .submit(Op::ResolveElicitation {
+                        server_name: ev.server_name.clone(),
+                        request_id: ev.id.clone(),
+                        decision: ElicitationAction::Cancel,
+                    })
+                    .await?;
+            }
+            EventMsg::ExecApprovalRequest(_) => {
+                outcome.record(ExecOutcome::NeedsApproval);
+                thread
+                    .submit(Op::ExecApproval {
+                        id: event.id.clone(),
+                        decision: ReviewDecision::Denied,
+                    })
+                    .await?;
+            }
+            EventMsg::ApplyPatchApprovalRequest(_) => {
+                outcome.record(ExecOutcome::NeedsApproval);
+                thread
+                    .submit(Op::PatchApproval {
+                        id: event.id.clone(),
+                        decision: ReviewDecision::Denied,
+                    })
+                    .await?;
+            }
+            _ => {}
+        }
+        if let EventMsg::Error(err) = &event.msg {
+            outcome.record(ExecOutcome::for_error(err.codex_error_info.as_ref()));
         }
-        if matches!(event.msg, EventMsg::Error(_)) {
-            error_seen = true;
+        if thread_id == primary_thread_id
+            && let EventMsg::ExitedReviewMode(exited) = &event.msg
+        {
+            review_output = exited.review_output.clone();
         }
         if thread_id != primary_thread_id && matches!(&event.msg, EventMsg::TurnComplete(_)) {
             continue;
         }
+        if let Some(schema) = &final_output_schema
+            // Asking again would only be refused by the budget.
+            && run_budget.as_ref().and_then(|budget| budget.exceeded()).is_none()
+            && let EventMsg::TurnComplete(complete) = &event.msg
+        {
+            match output_schema::parse_final_output(complete.last_agent_message.as_deref(), schema)
+            {
+                Ok(value) => {
+                    if let Some(path) = &output_file
+                        && let Err(err) = output_schema::write_output_file(path, &value)
+                    {
+                        eprintln!("Failed to write output file {}: {err}", path.display());
+                        outcome.record(ExecOutcome::TaskFailed);
+                    }
+                }
+                Err(problem) if schema_retries_left > 0 => {
+                    schema_retries_left -= 1;
+                    warn!("final message does not match --output-schema, retrying: {problem}");
+                    // Let the processor report the turn, but keep the session
+                    // open for the corrected answer.
+                    event_processor.process_event(event);
+                    thread
+                        .submit(Op::UserInput {
+                            items: vec![UserInput::Text {
+                                text: output_schema::retry_prompt(&problem),
+                                text_elements: Vec::new(),
+                            }],
+                            final_output_json_schema: Some(schema.clone()),
+                        })
+                        .await?;
+                    continue;
+                }
+                Err(problem) => {
+                    eprintln!("Final message does not match --output-schema: {problem}");
+                    outcome.record(ExecOutcome::TaskFailed);
+                }
+            }
+        }
         let shutdown = event_processor.process_event(event);
         if thread_id != primary_thread_id && matches!(shutdown, CodexStatus::InitiateShutdown) {
             continue;
@@ -566,9 +703,30 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
             CodexStatus::Shutdown => continue,
         }
     }
-    event_processor.print_final_output();
-    if error_seen {
-        std::process::exit(1);
+    if let Some(reason) = run_budget.as_ref().and_then(|budget| budget.exceeded()) {
+        eprintln!("Stopping early: {reason}.");
+        outcome.record(ExecOutcome::BudgetExceeded);
+    }
+    match review_format {
+        Some(format) => match &review_output {
+            Some(output) => {
+                let cwd = config.cwd.to_path_buf();
+                let root = get_git_repo_root(&cwd).unwrap_or(cwd);
+                let report = review_report::render_review_report(output, format, &root);
+                #[allow(clippy::print_stdout)]
+                {
+                    print!("{report}");
+                }
+            }
+            None => {
+                eprintln!("The review produced no output, so there is no report to print.");
+                outcome.record(ExecOutcome::TaskFailed);
+            }
+        },
+        None => event_processor.print_final_output(),
+    }
+    if outcome != ExecOutcome::Success {
+        std::process::exit(outcome.exit_code());
     }
 
     Ok(())
@@ -666,8 +824,8 @@ fn load_output_schema(path: Option<PathBuf>) -> Option<Value> {
         }
     };
 
-    match serde_json::from_str::<Value>(&schema_str) {
-        Ok(value) => Some(value),
+    let schema = match serde_json::from_str::<Value>(&schema_str) {
+        Ok(value) => value,
         Err(err) => {
             eprintln!(
                 "Output schema file {} is not valid JSON: {err}",
@@ -675,7 +833,15 @@ fn load_output_schema(path: Option<PathBuf>) -> Option<Value> {
             );
             std::process::exit(1);
         }
+    };
+    if let Err(err) = output_schema::check_schema(&schema) {
+        eprintln!(
+            "Output schema file {} cannot be enforced: {err}",
+            path.display()
+        );
+        std::process::exit(1);
     }
+    Some(schema)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -768,20 +934,7 @@ fn resolve_prompt(prompt_arg: Option<String>) -> String {
                 eprintln!("Reading prompt from stdin...");
             }
 
-            let mut bytes = Vec::new();
-            if let Err(e) = std::io::stdin().read_to_end(&mut bytes) {
-                eprintln!("Failed to read prompt from stdin: {e}");
-                std::process::exit(1);
-            }
-
-            let buffer = match decode_prompt_bytes(&bytes) {
-                Ok(s) => s,
-                Err(e) => {
-                    eprintln!("Failed to read prompt from stdin: {e}");
-                    std::process::exit(1);
-                }
-            };
-
+            let buffer = read_stdin_or_exit("prompt");
             if buffer.trim().is_empty() {
                 eprintln!("No prompt provided via stdin.");
                 std::process::exit(1);
@@ -791,7 +944,69 @@ fn resolve_prompt(prompt_arg: Option<String>) -> String {
     }
 }
 
+/// Resolves the prompt, then fills in `{stdin}` and `--var` placeholders.
+/// `{stdin}` is only expanded when the prompt itself came from the command
+/// line, since otherwise stdin has already been read as the prompt.
+fn resolve_templated_prompt(prompt_arg: Option<String>, vars: &[(String, String)]) -> String {
+    let prompt_from_args = matches!(&prompt_arg, Some(p) if p != "-");
+    let template = resolve_prompt(prompt_arg);
+
+    let stdin = if prompt_from_args && prompt_template::uses_stdin(&template) {
+        if std::io::stdin().is_terminal() {
+            eprintln!("The prompt uses {{stdin}}, but nothing was piped into stdin.");
+            std::process::exit(1);
+        }
+        Some(read_stdin_or_exit("input"))
+    } else {
+        None
+    };
+
+    for name in prompt_template::unused_vars(&template, vars) {
+        eprintln!("Warning: --var {name} is not used by the prompt (no {{{name}}} placeholder).");
+    }
+    prompt_template::render_prompt(&template, stdin.as_deref(), vars)
+}
+
+fn read_stdin_or_exit(what: &str) -> String {
+    let mut bytes = Vec::new();
+    if let Err(e) = std::io::stdin().read_to_end(&mut bytes) {
+        eprintln!("Failed to read {what} from stdin: {e}");
+        std::process::exit(1);
+    }
+
+    match decode_prompt_bytes(&bytes) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("Failed to read {what} from stdin: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Fails fast on a `--diff` range git cannot resolve.
+fn verify_diff_range(cwd: &Path, range: &str) -> anyhow::Result<()> {
+    if range.starts_with('-') {
+        anyhow::bail!("--diff expects a revision range such as main...HEAD, got `{range}`");
+    }
+    let output = std::process::Command::new("git")
+        .args(["rev-parse", range, "--"])
+        .current_dir(cwd)
+        .output()?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "--diff {range} is not a valid revision range: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
 fn build_review_request(args: ReviewArgs) -> anyhow::Result<ReviewRequest> {
+    if let Some(range) = args.diff {
+        return Ok(codex_core::review_prompts::diff_range_review_request(
+            &range,
+        ));
+    }
     let target = if args.uncommitted {
         ReviewTarget::UncommittedChanges
     } else if let Some(branch) = args.base {
@@ -811,7 +1026,7 @@ fn build_review_request(args: ReviewArgs) -> anyhow::Result<ReviewRequest> {
         }
     } else {
         anyhow::bail!(
-            "Specify --uncommitted, --base, --commit, or provide custom review instructions"
+            "Specify --uncommitted, --base, --commit, --diff, or provide custom review instructions"
         );
     };
 
@@ -833,6 +1048,8 @@ mod tests {
             base: None,
             commit: None,
             commit_title: None,
+            diff: None,
+            format: None,
             prompt: None,
         })
         .expect("builds uncommitted review request");
@@ -852,6 +1069,8 @@ mod tests {
             base: None,
             commit: Some("123456789".to_string()),
             commit_title: Some("Add review command".to_string()),
+            diff: None,
+            format: None,
             prompt: None,
         })
         .expect("builds commit review request");
@@ -874,6 +1093,8 @@ mod tests {
             base: None,
             commit: None,
             commit_title: None,
+            diff: None,
+            format: None,
             prompt: Some("  custom review instructions  ".to_string()),
         })
         .expect("builds custom review request");
@@ -0,0 +1,339 @@
+//! Checks the final message against `--output-schema`.
+//!
+//! Providers with structured outputs already constrain the response, but
+//! others (and models that ignore the constraint) can still return something
+//! else. The final message is parsed as JSON and validated here; on a
+//! mismatch exec asks the model to try again, up to
+//! [`MAX_OUTPUT_SCHEMA_RETRIES`] times.
+//!
+//! Validation covers the JSON Schema keywords structured outputs support:
+//! `type`, `enum`, `const`, `properties`, `required`,
+//! `additionalProperties`, `items`, and `anyOf`. A schema using any other
+//! keyword besides annotations such as `description` is refused up front by
+//! [`check_schema`], rather than having the keyword silently not enforced.
+
+use std::path::Path;
+
+use serde_json::Value;
+
+/// Follow-up turns sent when the final message does not match the schema.
+pub(crate) const MAX_OUTPUT_SCHEMA_RETRIES: u32 = 2;
+
+/// Keywords [`validate`] enforces.
+const SUPPORTED_KEYWORDS: &[&str] = &[
+    "type",
+    "enum",
+    "const",
+    "properties",
+    "required",
+    "additionalProperties",
+    "items",
+    "anyOf",
+];
+/// Keywords that describe a value without constraining it.
+const ANNOTATION_KEYWORDS: &[&str] = &[
+    "$schema",
+    "$id",
+    "$comment",
+    "title",
+    "description",
+    "default",
+    "examples",
+    "deprecated",
+    "readOnly",
+    "writeOnly",
+];
+
+/// Refuses a schema that uses keywords the final message could not be
+/// checked against, naming the first one found.
+pub(crate) fn check_schema(schema: &Value) -> Result<(), String> {
+    check_subschema(schema, "$")
+}
+
+fn check_subschema(schema: &Value, path: &str) -> Result<(), String> {
+    let map = match schema {
+        Value::Bool(_) => return Ok(()),
+        Value::Object(map) => map,
+        _ => return Err(format!("{path}: expected a schema object, got {schema}")),
+    };
+    for (keyword, value) in map {
+        if ANNOTATION_KEYWORDS.contains(&keyword.as_str()) {
+            continue;
+        }
+        if !SUPPORTED_KEYWORDS.contains(&keyword.as_str()) {
+            return Err(format!(
+                "{path}: unsupported keyword `{keyword}`; only {} can be checked",
+                SUPPORTED_KEYWORDS.join(", ")
+            ));
+        }
+        match (keyword.as_str(), value) {
+            ("properties", Value::Object(properties)) => {
+                for (name, property) in properties {
+                    check_subschema(property, &format!("{path}.properties.{name}"))?;
+                }
+            }
+            ("additionalProperties" | "items", _) => {
+                check_subschema(value, &format!("{path}.{keyword}"))?;
+            }
+            ("anyOf", Value::Array(options)) => {
+                for (index, option) in options.iter().enumerate() {
+                    check_subschema(option, &format!("{path}.anyOf[{index}]"))?;
+                }
+            }
+            ("properties" | "anyOf", _) => {
+                return Err(format!("{path}: `{keyword}` has the wrong form"));
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// The final message parsed as JSON, or why it does not match `schema`.
+pub(crate) fn parse_final_output(message: Option<&str>, schema: &Value) -> Result<Value, String> {
+    let message = message.ok_or_else(|| "there was no final message".to_string())?;
+    let value: Value = serde_json::from_str(strip_code_fence(message))
+        .map_err(|err| format!("the final message is not valid JSON ({err})"))?;
+    let mut errors = Vec::new();
+    validate(&value, schema, "$", &mut errors);
+    if errors.is_empty() {
+        Ok(value)
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+/// The follow-up prompt asking the model to fix its final message.
+pub(crate) fn retry_prompt(problem: &str) -> String {
+    format!(
+        "Your final response did not match the required JSON schema: {problem}. Reply again with only a JSON value that matches the schema, and no other text."
+    )
+}
+
+/// Writes `value` as pretty-printed JSON for `--output-file`.
+pub(crate) fn write_output_file(path: &Path, value: &Value) -> std::io::Result<()> {
+    let mut contents = serde_json::to_string_pretty(value)?;
+    contents.push('\n');
+    std::fs::write(path, contents)
+}
+
+/// Models sometimes wrap JSON in a Markdown code fence despite instructions.
+fn strip_code_fence(message: &str) -> &str {
+    let trimmed = message.trim();
+    let Some(rest) = trimmed.strip_prefix("```") else {
+        return trimmed;
+    };
+    let Some(body) = rest.strip_suffix("```") else {
+        return trimmed;
+    };
+    // Drop the info string (e.g. `json`) on the opening line.
+    body.split_once('\n').map_or(body, |(_, body)| body).trim()
+}
+
+fn validate(value: &Value, schema: &Value, path: &str, errors: &mut Vec<String>) {
+    let Some(schema) = schema.as_object() else {
+        // `true`, `{}` and unsupported forms accept anything.
+        if schema == &Value::Bool(false) {
+            errors.push(format!("{path}: no value is allowed here"));
+        }
+        return;
+    };
+
+    if let Some(options) = schema.get("anyOf").and_then(Value::as_array) {
+        let matches_any = options.iter().any(|option| {
+            let mut option_errors = Vec::new();
+            validate(value, option, path, &mut option_errors);
+            option_errors.is_empty()
+        });
+        if !matches_any {
+            errors.push(format!("{path}: does not match any of the allowed schemas"));
+        }
+    }
+
+    if let Some(expected) = schema.get("type")
+        && !matches_type(value, expected)
+    {
+        errors.push(format!(
+            "{path}: expected {}, got {}",
+            describe_type(expected),
+            type_name(value)
+        ));
+        return;
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array)
+        && !allowed.contains(value)
+    {
+        errors.push(format!("{path}: {value} is not one of the allowed values"));
+    }
+    if let Some(expected) = schema.get("const")
+        && expected != value
+    {
+        errors.push(format!("{path}: expected {expected}"));
+    }
+
+    match value {
+        Value::Object(map) => {
+            let properties = schema.get("properties").and_then(Value::as_object);
+            if let Some(required) = schema.get("required").and_then(Value::as_array) {
+                for key in required.iter().filter_map(Value::as_str) {
+                    if !map.contains_key(key) {
+                        errors.push(format!("{path}: missing required property \"{key}\""));
+                    }
+                }
+            }
+            // Sorted so errors come out in the same order however the map
+            // preserves keys.
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by_key(|(key, _)| *key);
+            for (key, child) in entries {
+                let child_path = format!("{path}.{key}");
+                match properties.and_then(|properties| properties.get(key)) {
+                    Some(child_schema) => validate(child, child_schema, &child_path, errors),
+                    None => match schema.get("additionalProperties") {
+                        Some(Value::Bool(false)) => {
+                            errors.push(format!("{path}: unexpected property \"{key}\""));
+                        }
+                        Some(additional) => validate(child, additional, &child_path, errors),
+                        None => {}
+                    },
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for (index, item) in items.iter().enumerate() {
+                    validate(item, item_schema, &format!("{path}[{index}]"), errors);
+                }
+            }
+        }
+        Value::Null | Value::Bool(_) | Value::Number(_) | Value::String(_) => {}
+    }
+}
+
+fn matches_type(value: &Value, expected: &Value) -> bool {
+    match expected {
+        Value::String(name) => matches_type_name(value, name),
+        Value::Array(names) => names
+            .iter()
+            .filter_map(Value::as_str)
+            .any(|name| matches_type_name(value, name)),
+        _ => true,
+    }
+}
+
+fn matches_type_name(value: &Value, name: &str) -> bool {
+    match name {
+        "integer" => value.as_i64().is_some() || value.as_u64().is_some(),
+        "number" => value.is_number(),
+        other => type_name(value) == other,
+    }
+}
+
+fn describe_type(expected: &Value) -> String {
+    match expected {
+        Value::Array(names) => names
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(" or "),
+        Value::String(name) => name.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "answer": { "type": "string" },
+                "confidence": { "type": "number" },
+                "tags": { "type": "array", "items": { "enum": ["bug", "feature"] } },
+            },
+            "required": ["answer", "confidence"],
+            "additionalProperties": false,
+        })
+    }
+
+    #[test]
+    fn check_schema_refuses_keywords_that_are_not_enforced() {
+        assert_eq!(check_schema(&schema()), Ok(()));
+        assert_eq!(
+            check_schema(&json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "description": "An answer",
+                "anyOf": [{ "type": "string", "title": "Text" }, true],
+            })),
+            Ok(())
+        );
+        assert_eq!(
+            check_schema(&json!({
+                "type": "object",
+                "properties": {
+                    "tags": { "type": "array", "items": { "type": "string", "pattern": "^[a-z]+$" } },
+                },
+            })),
+            Err(
+                "$.properties.tags.items: unsupported keyword `pattern`; only type, enum, const, properties, required, additionalProperties, items, anyOf can be checked"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            check_schema(&json!({ "allOf": [{ "type": "string" }], "minLength": 1 })),
+            Err(
+                "$: unsupported keyword `allOf`; only type, enum, const, properties, required, additionalProperties, items, anyOf can be checked"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            check_schema(&json!({ "items": [{ "type": "string" }] })),
+            Err("$.items: expected a schema object, got [{\"type\":\"string\"}]".to_string())
+        );
+    }
+
+    #[test]
+    fn accepts_matching_output_in_a_code_fence() {
+        let message =
+            "```json\n{\"answer\": \"42\", \"confidence\": 0.9, \"tags\": [\"bug\"]}\n```";
+        assert_eq!(
+            parse_final_output(Some(message), &schema()),
+            Ok(json!({ "answer": "42", "confidence": 0.9, "tags": ["bug"] }))
+        );
+    }
+
+    #[test]
+    fn reports_every_mismatch_with_its_path() {
+        let message = r#"{"answer": 42, "tags": ["chore"], "extra": true}"#;
+        assert_eq!(
+            parse_final_output(Some(message), &schema()),
+            Err(
+                "$: missing required property \"confidence\"; $.answer: expected string, got number; $: unexpected property \"extra\"; $.tags[0]: \"chore\" is not one of the allowed values"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            parse_final_output(Some("not json"), &schema()),
+            Err(
+                "the final message is not valid JSON (expected ident at line 1 column 2)"
+                    .to_string()
+            )
+        );
+    }
+}
@@ -0,0 +1,132 @@
+//! Placeholder substitution for the exec prompt.
+//!
+//! `{stdin}` in a prompt given on the command line is replaced with whatever
+//! was piped into exec, and `{name}` is replaced with the value of
+//! `--var name=value`. Substitution happens in one pass, so braces inside
+//! piped input or variable values are left alone. Braces that do not name a
+//! known placeholder are kept as written.
+
+/// The placeholder replaced with piped input.
+pub(crate) const STDIN_PLACEHOLDER: &str = "stdin";
+
+/// Parses a `--var NAME=VALUE` argument.
+pub(crate) fn parse_prompt_var(arg: &str) -> Result<(String, String), String> {
+    let Some((name, value)) = arg.split_once('=') else {
+        return Err(format!("expected NAME=VALUE, got `{arg}`"));
+    };
+    if !is_valid_name(name) {
+        return Err(format!(
+            "`{name}` is not a valid variable name; use letters, digits, `_` or `-`"
+        ));
+    }
+    if name == STDIN_PLACEHOLDER {
+        return Err("`stdin` is reserved for piped input".to_string());
+    }
+    Ok((name.to_string(), value.to_string()))
+}
+
+/// Whether `template` asks for piped input.
+pub(crate) fn uses_stdin(template: &str) -> bool {
+    template.contains(&format!("{{{STDIN_PLACEHOLDER}}}"))
+}
+
+/// Variables that `template` never mentions, so the user can be warned.
+pub(crate) fn unused_vars<'a>(template: &str, vars: &'a [(String, String)]) -> Vec<&'a str> {
+    vars.iter()
+        .map(|(name, _)| name.as_str())
+        .filter(|name| !template.contains(&format!("{{{name}}}")))
+        .collect()
+}
+
+/// Replaces `{stdin}` (when `stdin` is given) and each `{name}` from `vars`.
+/// When a variable is given more than once, the last value wins.
+pub(crate) fn render_prompt(
+    template: &str,
+    stdin: Option<&str>,
+    vars: &[(String, String)],
+) -> String {
+    let lookup = |name: &str| {
+        if name == STDIN_PLACEHOLDER {
+            return stdin;
+        }
+        vars.iter()
+            .rev()
+            .find(|(var, _)| var == name)
+            .map(|(_, value)| value.as_str())
+    };
+
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        rendered.push_str(&rest[..open]);
+        let after_open = &rest[open + 1..];
+        let replacement = after_open.find('}').and_then(|close| {
+            let name = &after_open[..close];
+            lookup(name).map(|value| (value, close))
+        });
+        match replacement {
+            Some((value, close)) => {
+                rendered.push_str(value);
+                rest = &after_open[close + 1..];
+            }
+            None => {
+                rendered.push('{');
+                rest = after_open;
+            }
+        }
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn substitutes_stdin_and_vars_in_one_pass() {
+        let vars = vec![
+            ("service".to_string(), "api {stdin}".to_string()),
+            ("env".to_string(), "staging".to_string()),
+            ("env".to_string(), "prod".to_string()),
+        ];
+        assert_eq!(
+            render_prompt(
+                "Diagnose {service} on {env}: {stdin} (see {missing} and {})",
+                Some("panic at {env}"),
+                &vars
+            ),
+            "Diagnose api {stdin} on prod: panic at {env} (see {missing} and {})"
+        );
+        assert_eq!(unused_vars("only {env}", &vars), vec!["service"]);
+    }
+
+    #[test]
+    fn parses_var_arguments() {
+        assert_eq!(
+            parse_prompt_var("ticket=ABC-1=2"),
+            Ok(("ticket".to_string(), "ABC-1=2".to_string()))
+        );
+        assert_eq!(
+            parse_prompt_var("ticket"),
+            Err("expected NAME=VALUE, got `ticket`".to_string())
+        );
+        assert_eq!(
+            parse_prompt_var("1st=x"),
+            Err("`1st` is not a valid variable name; use letters, digits, `_` or `-`".to_string())
+        );
+        assert_eq!(
+            parse_prompt_var("stdin=x"),
+            Err("`stdin` is reserved for piped input".to_string())
+        );
+    }
+}
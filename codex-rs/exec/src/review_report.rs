@@ -0,0 +1,264 @@
+//! `codex review --format`: review findings as SARIF or Markdown for PR
+//! automation.
+//!
+//! Paths are made relative to `root` (the repository root) so code-scanning
+//! uploads and PR comments line up with the files in the checkout. SARIF
+//! results carry a fingerprint of their path, title and first line so code
+//! scanning tracks a finding across runs instead of opening a new alert.
+
+use std::path::Path;
+
+use codex_core::protocol::ReviewFinding;
+use codex_core::protocol::ReviewOutputEvent;
+use serde_json::Value;
+use serde_json::json;
+
+use crate::cli::ReviewFormat;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Key of the fingerprint in each result's `partialFingerprints`.
+const FINGERPRINT_KEY: &str = "codexFinding/v1";
+
+pub(crate) fn render_review_report(
+    output: &ReviewOutputEvent,
+    format: ReviewFormat,
+    root: &Path,
+) -> String {
+    match format {
+        ReviewFormat::Sarif => {
+            let mut sarif = serde_json::to_string_pretty(&sarif_log(output, root))
+                .unwrap_or_else(|_| "{}".to_string());
+            sarif.push('\n');
+            sarif
+        }
+        ReviewFormat::Markdown => markdown(output, root),
+    }
+}
+
+/// P0 and P1 block a merge, P2 should be fixed, P3 is a nice-to-have.
+fn severity(priority: i32) -> &'static str {
+    match priority {
+        i32::MIN..=1 => "error",
+        2 => "warning",
+        _ => "note",
+    }
+}
+
+fn relative_path(finding: &ReviewFinding, root: &Path) -> String {
+    let path = &finding.code_location.absolute_file_path;
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// Lines as SARIF expects them: 1-based with `end >= start`.
+fn line_range(finding: &ReviewFinding) -> (u32, u32) {
+    let range = &finding.code_location.line_range;
+    let start = range.start.max(1);
+    (start, range.end.max(start))
+}
+
+/// A stable hash of the finding's path, title and first line, as hex. FNV-1a
+/// is used because it is fixed by its definition, unlike `std`'s hasher.
+fn fingerprint(path: &str, title: &str, start: u32) -> String {
+    let key = format!("{path}\0{title}\0{start}");
+    let hash = key.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{hash:016x}")
+}
+
+fn sarif_log(output: &ReviewOutputEvent, root: &Path) -> Value {
+    let rules: Vec<Value> = (0..=3)
+        .map(|priority| {
+            json!({
+                "id": format!("P{priority}"),
+                "shortDescription": { "text": format!("Priority {priority} review finding") },
+                "defaultConfiguration": { "level": severity(priority) },
+            })
+        })
+        .collect();
+    let results: Vec<Value> = output
+        .findings
+        .iter()
+        .map(|finding| {
+            let (start, end) = line_range(finding);
+            let path = relative_path(finding, root);
+            json!({
+                "ruleId": format!("P{}", finding.priority.clamp(0, 3)),
+                "level": severity(finding.priority),
+                "message": { "text": format!("{}\n\n{}", finding.title, finding.body.trim()) },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": path },
+                        "region": { "startLine": start, "endLine": end },
+                    },
+                }],
+                "partialFingerprints": {
+                    FINGERPRINT_KEY: fingerprint(&path, &finding.title, start),
+                },
+                "properties": { "confidence": finding.confidence_score },
+            })
+        })
+        .collect();
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "codex",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                },
+            },
+            "results": results,
+            "properties": {
+                "overallCorrectness": output.overall_correctness,
+                "overallExplanation": output.overall_explanation,
+                "overallConfidence": output.overall_confidence_score,
+            },
+        }],
+    })
+}
+
+fn markdown(output: &ReviewOutputEvent, root: &Path) -> String {
+    let mut lines = vec!["## Codex review".to_string(), String::new()];
+    let explanation = output.overall_explanation.trim();
+    if !explanation.is_empty() {
+        lines.push(explanation.to_string());
+        lines.push(String::new());
+    }
+    if !output.overall_correctness.is_empty() {
+        lines.push(format!(
+            "**Verdict:** {} (confidence {:.2})",
+            output.overall_correctness, output.overall_confidence_score
+        ));
+        lines.push(String::new());
+    }
+
+    if output.findings.is_empty() {
+        lines.push("No findings.".to_string());
+    }
+    for finding in &output.findings {
+        let (start, end) = line_range(finding);
+        let path = relative_path(finding, root);
+        let location = if start == end {
+            format!("{path}:{start}")
+        } else {
+            format!("{path}:{start}-{end}")
+        };
+        lines.push(format!("### {}", finding.title));
+        lines.push(String::new());
+        lines.push(format!(
+            "`{location}` · {} · confidence {:.2}",
+            severity(finding.priority),
+            finding.confidence_score
+        ));
+        lines.push(String::new());
+        lines.push(finding.body.trim().to_string());
+        lines.push(String::new());
+    }
+
+    let mut markdown = lines.join("\n");
+    markdown.truncate(markdown.trim_end().len());
+    markdown.push('\n');
+    markdown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::protocol::ReviewCodeLocation;
+    use codex_core::protocol::ReviewLineRange;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    fn review() -> ReviewOutputEvent {
+        ReviewOutputEvent {
+            findings: vec![ReviewFinding {
+                title: "[P1] Off-by-one in pagination".to_string(),
+                body: "The last page is skipped.\nUse `<=` here.\n".to_string(),
+                confidence_score: 0.75,
+                priority: 1,
+                code_location: ReviewCodeLocation {
+                    absolute_file_path: PathBuf::from("/repo/src/page.rs"),
+                    line_range: ReviewLineRange { start: 12, end: 14 },
+                },
+            }],
+            overall_correctness: "patch is incorrect".to_string(),
+            overall_explanation: "Pagination drops results.".to_string(),
+            overall_confidence_score: 0.5,
+        }
+    }
+
+    #[test]
+    fn sarif_results_use_repo_relative_paths() {
+        let sarif = sarif_log(&review(), Path::new("/repo"));
+        assert_eq!(
+            sarif["runs"][0]["results"][0],
+            json!({
+                "ruleId": "P1",
+                "level": "error",
+                "message": {
+                    "text": "[P1] Off-by-one in pagination\n\nThe last page is skipped.\nUse `<=` here."
+                },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": "src/page.rs" },
+                        "region": { "startLine": 12, "endLine": 14 },
+                    },
+                }],
+                "partialFingerprints": {
+                    "codexFinding/v1": fingerprint("src/page.rs", "[P1] Off-by-one in pagination", 12),
+                },
+                "properties": { "confidence": 0.75 },
+            })
+        );
+    }
+
+    #[test]
+    fn fingerprints_are_stable_and_depend_on_path_title_and_line() {
+        let title = "[P1] Off-by-one in pagination";
+        assert_eq!(fingerprint("", "", 0), "d94d02186c0f1487");
+        assert_eq!(
+            fingerprint("src/page.rs", title, 12),
+            fingerprint("src/page.rs", title, 12)
+        );
+        assert_ne!(
+            fingerprint("src/page.rs", title, 12),
+            fingerprint("src/page.rs", title, 13)
+        );
+        assert_ne!(
+            fingerprint("src/page.rs", title, 12),
+            fingerprint("src/list.rs", title, 12)
+        );
+        assert_ne!(
+            fingerprint("src/page.rs", title, 12),
+            fingerprint("src/page.rs", "[P2] Slow query", 12)
+        );
+    }
+
+    #[test]
+    fn markdown_lists_each_finding() {
+        assert_eq!(
+            markdown(&review(), Path::new("/repo")),
+            "## Codex review
+
+Pagination drops results.
+
+**Verdict:** patch is incorrect (confidence 0.50)
+
+### [P1] Off-by-one in pagination
+
+`src/page.rs:12-14` · error · confidence 0.75
+
+The last page is skipped.
+Use `<=` here.
+"
+        );
+    }
+}
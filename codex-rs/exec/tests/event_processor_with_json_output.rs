@@ -8,6 +8,7 @@ use codex_core::protocol::CollabWaitingEndEvent;
 use codex_core::protocol::ErrorEvent;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
+use codex_core::protocol::ExecApprovalRequestEvent;
 use codex_core::protocol::ExecCommandBeginEvent;
 use codex_core::protocol::ExecCommandEndEvent;
 use codex_core::protocol::ExecCommandSource;
@@ -19,11 +20,15 @@ use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PatchApplyEndEvent;
 use codex_core::protocol::SandboxPolicy;
 use codex_core::protocol::SessionConfiguredEvent;
+use codex_core::protocol::TurnDiffEvent;
 use codex_core::protocol::WarningEvent;
 use codex_core::protocol::WebSearchBeginEvent;
 use codex_core::protocol::WebSearchEndEvent;
 use codex_exec::event_processor_with_jsonl_output::EventProcessorWithJsonOutput;
 use codex_exec::exec_events::AgentMessageItem;
+use codex_exec::exec_events::ApprovalDecision;
+use codex_exec::exec_events::ApprovalKind;
+use codex_exec::exec_events::ApprovalResolvedEvent;
 use codex_exec::exec_events::CollabAgentState;
 use codex_exec::exec_events::CollabAgentStatus;
 use codex_exec::exec_events::CollabTool;
@@ -50,6 +55,7 @@ use codex_exec::exec_events::ThreadStartedEvent;
 use codex_exec::exec_events::TodoItem as ExecTodoItem;
 use codex_exec::exec_events::TodoListItem as ExecTodoListItem;
 use codex_exec::exec_events::TurnCompletedEvent;
+use codex_exec::exec_events::TurnDiffUpdatedEvent;
 use codex_exec::exec_events::TurnFailedEvent;
 use codex_exec::exec_events::TurnStartedEvent;
 use codex_exec::exec_events::Usage;
@@ -336,6 +342,7 @@ fn plan_update_emits_todo_list_started_updated_and_completed() {
             }),
             ThreadEvent::TurnCompleted(TurnCompletedEvent {
                 usage: Usage::default(),
+                final_message: None,
             }),
         ]
     );
@@ -903,6 +910,7 @@ fn exec_command_end_success_produces_completed_command_item() {
             exit_code: 0,
             duration: Duration::from_millis(5),
             formatted_output: String::new(),
+            previous_run_diff: None,
         }),
     );
     let out_ok = ep.collect_thread_events(&end_ok);
@@ -990,6 +998,7 @@ fn command_execution_output_delta_updates_item_progress() {
             exit_code: 0,
             duration: Duration::from_millis(3),
             formatted_output: String::new(),
+            previous_run_diff: None,
         }),
     );
     let out_end = ep.collect_thread_events(&end);
@@ -1063,6 +1072,7 @@ fn exec_command_end_failure_produces_failed_command_item() {
             exit_code: 1,
             duration: Duration::from_millis(2),
             formatted_output: String::new(),
+            previous_run_diff: None,
         }),
     );
     let out_fail = ep.collect_thread_events(&end_fail);
@@ -1104,6 +1114,7 @@ fn exec_command_end_without_begin_is_ignored() {
             exit_code: 0,
             duration: Duration::from_millis(1),
             formatted_output: String::new(),
+            previous_run_diff: None,
         }),
     );
     let out = ep.collect_thread_events(&end_only);
@@ -1294,6 +1305,48 @@ fn task_complete_produces_turn_completed_with_usage() {
                 cached_input_tokens: 200,
                 output_tokens: 345,
             },
+            final_message: Some("done".to_string()),
+        })]
+    );
+}
+
+#[test]
+fn turn_diff_and_auto_resolved_approvals_are_streamed() {
+    let mut ep = EventProcessorWithJsonOutput::new(None);
+
+    let diff = event(
+        "d1",
+        EventMsg::TurnDiff(TurnDiffEvent {
+            unified_diff: "--- a/a.txt\n+++ b/a.txt\n".to_string(),
+        }),
+    );
+    assert_eq!(
+        ep.collect_thread_events(&diff),
+        vec![ThreadEvent::TurnDiffUpdated(TurnDiffUpdatedEvent {
+            unified_diff: "--- a/a.txt\n+++ b/a.txt\n".to_string(),
+        })]
+    );
+
+    let approval = event(
+        "a1",
+        EventMsg::ExecApprovalRequest(ExecApprovalRequestEvent {
+            call_id: "call-1".to_string(),
+            turn_id: "turn-1".to_string(),
+            command: vec!["rm".to_string(), "-rf".to_string(), "build".to_string()],
+            cwd: PathBuf::from("/repo"),
+            reason: None,
+            proposed_execpolicy_amendment: None,
+            parsed_cmd: Vec::new(),
+            file_operation: None,
+        }),
+    );
+    assert_eq!(
+        ep.collect_thread_events(&approval),
+        vec![ThreadEvent::ApprovalResolved(ApprovalResolvedEvent {
+            kind: ApprovalKind::Command,
+            id: "call-1".to_string(),
+            decision: ApprovalDecision::Denied,
+            summary: "rm -rf build".to_string(),
         })]
     );
 }
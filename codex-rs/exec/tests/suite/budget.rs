@@ -0,0 +1,72 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use core_test_support::responses;
+use core_test_support::test_codex_exec::test_codex_exec;
+use pretty_assertions::assert_eq;
+
+/// Verify that `--max-turns` refuses the tool call in the last allowed
+/// response and the model request after it, and reports the budget-exceeded
+/// exit code.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn max_turns_stops_with_budget_exit_code() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+
+    let server = responses::start_mock_server().await;
+    let responses = responses::mount_sse_sequence(
+        &server,
+        vec![
+            responses::sse(vec![
+                responses::ev_response_created("resp1"),
+                responses::ev_function_call("call-1", "no_such_tool", "{}"),
+                responses::ev_completed_with_tokens("resp1", 100),
+            ]),
+            responses::sse(vec![
+                responses::ev_response_created("resp2"),
+                responses::ev_assistant_message("m2", "done"),
+                responses::ev_completed_with_tokens("resp2", 200),
+            ]),
+        ],
+    )
+    .await;
+
+    test.cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("--max-turns")
+        .arg("1")
+        .arg("keep going")
+        .assert()
+        .code(3)
+        .stderr(predicates::str::contains(
+            "Stopping early: the agent used all 1 model responses allowed by --max-turns.",
+        ));
+    assert_eq!(responses.requests().len(), 1);
+
+    Ok(())
+}
+
+/// Verify that a final answer within `--max-tokens` still succeeds.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn run_within_max_tokens_succeeds() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+
+    let server = responses::start_mock_server().await;
+    let body = responses::sse(vec![
+        responses::ev_response_created("resp1"),
+        responses::ev_assistant_message("m1", "done"),
+        responses::ev_completed_with_tokens("resp1", 100),
+    ]);
+    responses::mount_sse_once(&server, body).await;
+
+    test.cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("--max-tokens")
+        .arg("1000")
+        .arg("--max-turns")
+        .arg("1")
+        .arg("answer briefly")
+        .assert()
+        .success();
+
+    Ok(())
+}
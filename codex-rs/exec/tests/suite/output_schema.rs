@@ -3,6 +3,7 @@
 
 use core_test_support::responses;
 use core_test_support::test_codex_exec::test_codex_exec;
+use pretty_assertions::assert_eq;
 use serde_json::Value;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
@@ -60,3 +61,60 @@ async fn exec_includes_output_schema_in_request() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn exec_retries_until_final_message_matches_output_schema() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+
+    let schema_path = test.cwd_path().join("schema.json");
+    std::fs::write(
+        &schema_path,
+        serde_json::to_vec_pretty(&serde_json::json!({
+            "type": "object",
+            "properties": { "answer": { "type": "string" } },
+            "required": ["answer"],
+            "additionalProperties": false
+        }))?,
+    )?;
+    let output_path = test.cwd_path().join("result.json");
+
+    let server = responses::start_mock_server().await;
+    let response_mock = responses::mount_sse_sequence(
+        &server,
+        vec![
+            responses::sse(vec![
+                responses::ev_response_created("resp1"),
+                responses::ev_assistant_message("m1", "The answer is 42."),
+                responses::ev_completed("resp1"),
+            ]),
+            responses::sse(vec![
+                responses::ev_response_created("resp2"),
+                responses::ev_assistant_message("m2", r#"{"answer": "42"}"#),
+                responses::ev_completed("resp2"),
+            ]),
+        ],
+    )
+    .await;
+
+    test.cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("-C")
+        .arg(test.cwd_path())
+        .arg("--output-schema")
+        .arg(&schema_path)
+        .arg("--output-file")
+        .arg(&output_path)
+        .arg("what is the answer?")
+        .assert()
+        .success();
+
+    let requests = response_mock.requests();
+    assert_eq!(requests.len(), 2);
+    let retry_prompt = serde_json::to_string(&requests[1].body_json())?;
+    assert!(retry_prompt.contains("did not match the required JSON schema"));
+
+    let written: Value = serde_json::from_str(&std::fs::read_to_string(&output_path)?)?;
+    assert_eq!(written, serde_json::json!({ "answer": "42" }));
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use core_test_support::responses;
+use core_test_support::test_codex_exec::test_codex_exec;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn exec_fills_stdin_and_var_placeholders_in_prompt() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+
+    let server = responses::start_mock_server().await;
+    let body = responses::sse(vec![
+        responses::ev_response_created("resp1"),
+        responses::ev_assistant_message("m1", "looks like a null deref"),
+        responses::ev_completed("resp1"),
+    ]);
+    let response_mock = responses::mount_sse_once(&server, body).await;
+
+    test.cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("--var")
+        .arg("service=billing")
+        .arg("diagnose this {service} failure: {stdin}")
+        .write_stdin("panic at {service}.rs:12\n")
+        .assert()
+        .success();
+
+    let texts = response_mock.single_request().message_input_texts("user");
+    assert!(
+        texts
+            .iter()
+            .any(|text| text == "diagnose this billing failure: panic at {service}.rs:12\n"),
+        "user message was not templated: {texts:?}"
+    );
+
+    Ok(())
+}
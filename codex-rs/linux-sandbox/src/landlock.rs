@@ -1,6 +1,9 @@
 use std::collections::BTreeMap;
 use std::path::Path;
+use std::path::PathBuf;
 
+use codex_core::config::types::LinuxSandboxConfig;
+use codex_core::config::types::SyscallClass;
 use codex_core::error::CodexErr;
 use codex_core::error::Result;
 use codex_core::error::SandboxErr;
@@ -10,8 +13,10 @@ use codex_utils_absolute_path::AbsolutePathBuf;
 use landlock::ABI;
 use landlock::Access;
 use landlock::AccessFs;
+use landlock::AccessNet;
 use landlock::CompatLevel;
 use landlock::Compatible;
+use landlock::NetPort;
 use landlock::Ruleset;
 use landlock::RulesetAttr;
 use landlock::RulesetCreatedAttr;
@@ -25,18 +30,43 @@ use seccompiler::SeccompRule;
 use seccompiler::TargetArch;
 use seccompiler::apply_filter;
 
+/// Readable in addition to `readable_roots` when `[linux_sandbox]` limits
+/// reads, so that ordinary programs can still load.
+const SYSTEM_READABLE_ROOTS: [&str; 11] = [
+    "/bin", "/sbin", "/usr", "/lib", "/lib32", "/lib64", "/etc", "/dev", "/proc", "/sys", "/tmp",
+];
+
 /// Apply sandbox policies inside this thread so only the child inherits
-/// them, not the entire CLI process.
+/// them, not the entire CLI process. When the policy has no network access and
+/// `network_proxy_port` is set, the caller has already moved this process into
+/// a network namespace where only the proxy is reachable, and TCP connections
+/// to that port stay allowed so the command can use it. `linux_sandbox` adds the
+/// `[linux_sandbox]` restrictions on top of the policy.
 pub(crate) fn apply_sandbox_policy_to_current_thread(
     sandbox_policy: &SandboxPolicy,
     cwd: &Path,
+    network_proxy_port: Option<u16>,
+    linux_sandbox: &LinuxSandboxConfig,
 ) -> Result<()> {
-    if !sandbox_policy.has_full_disk_write_access() || !sandbox_policy.has_full_network_access() {
+    if !sandbox_policy.has_full_disk_write_access()
+        || !sandbox_policy.has_full_network_access()
+        || *linux_sandbox != LinuxSandboxConfig::default()
+    {
         set_no_new_privs()?;
     }
 
+    install_tuning_seccomp_filter_on_current_thread(linux_sandbox)?;
+
     if !sandbox_policy.has_full_network_access() {
-        install_network_seccomp_filter_on_current_thread()?;
+        match network_proxy_port {
+            Some(port) => {
+                // The network namespace already confines connections to the
+                // proxy; Landlock narrows them to its port where supported.
+                install_proxy_port_landlock_rules_on_current_thread(port)?;
+                install_proxy_network_seccomp_filter_on_current_thread()?;
+            }
+            None => install_network_seccomp_filter_on_current_thread()?,
+        }
     }
 
     if !sandbox_policy.has_full_disk_write_access() {
@@ -44,8 +74,23 @@ pub(crate) fn apply_sandbox_policy_to_current_thread(
             .get_writable_roots_with_cwd(cwd)
             .into_iter()
             .map(|writable_root| writable_root.root)
+            .chain(linux_sandbox.writable_roots.iter().cloned())
             .collect();
-        install_filesystem_landlock_rules_on_current_thread(writable_roots)?;
+        // Writable roots are readable through their own rule.
+        let readable_roots = (!linux_sandbox.readable_roots.is_empty()).then(|| {
+            SYSTEM_READABLE_ROOTS
+                .iter()
+                .map(PathBuf::from)
+                .chain(std::iter::once(cwd.to_path_buf()))
+                .chain(
+                    linux_sandbox
+                        .readable_roots
+                        .iter()
+                        .map(AbsolutePathBuf::to_path_buf),
+                )
+                .collect()
+        });
+        install_filesystem_landlock_rules_on_current_thread(writable_roots, readable_roots)?;
     }
 
     // TODO(ragona): Add appropriate restrictions if
@@ -63,13 +108,15 @@ fn set_no_new_privs() -> Result<()> {
 }
 
 /// Installs Landlock file-system rules on the current thread allowing read
-/// access to the entire file-system while restricting write access to
-/// `/dev/null` and the provided list of `writable_roots`.
+/// access to the entire file-system, or only to `readable_roots` when given,
+/// while restricting write access to `/dev/null` and the provided list of
+/// `writable_roots`.
 ///
 /// # Errors
 /// Returns [`CodexErr::Sandbox`] variants when the ruleset fails to apply.
 fn install_filesystem_landlock_rules_on_current_thread(
     writable_roots: Vec<AbsolutePathBuf>,
+    readable_roots: Option<Vec<PathBuf>>,
 ) -> Result<()> {
     let abi = ABI::V5;
     let access_rw = AccessFs::from_all(abi);
@@ -79,7 +126,10 @@ fn install_filesystem_landlock_rules_on_current_thread(
         .set_compatibility(CompatLevel::BestEffort)
         .handle_access(access_rw)?
         .create()?
-        .add_rules(landlock::path_beneath_rules(&["/"], access_ro))?
+        .add_rules(landlock::path_beneath_rules(
+            readable_roots.unwrap_or_else(|| vec![PathBuf::from("/")]),
+            access_ro,
+        ))?
         .add_rules(landlock::path_beneath_rules(&["/dev/null"], access_rw))?
         .set_no_new_privs(true);
 
@@ -96,6 +146,218 @@ fn install_filesystem_landlock_rules_on_current_thread(
     Ok(())
 }
 
+/// Installs a seccomp filter for the `[linux_sandbox]` syscall restrictions;
+/// does nothing when there are none.
+fn install_tuning_seccomp_filter_on_current_thread(
+    linux_sandbox: &LinuxSandboxConfig,
+) -> std::result::Result<(), SandboxErr> {
+    let mut rules: BTreeMap<i64, Vec<SeccompRule>> = BTreeMap::new();
+
+    for class in &linux_sandbox.blocked_syscalls {
+        for nr in syscalls_in_class(*class) {
+            rules.insert(*nr, vec![]);
+        }
+    }
+    if linux_sandbox.deny_ptrace {
+        for nr in [
+            libc::SYS_ptrace,
+            libc::SYS_process_vm_readv,
+            libc::SYS_process_vm_writev,
+        ] {
+            rules.insert(nr, vec![]);
+        }
+    }
+    // seccomp cannot see the address passed to `connect`, so refuse to create
+    // the socket instead. `socketpair` stays allowed since it reaches nothing
+    // outside the process tree.
+    if linux_sandbox.deny_unix_socket_connect {
+        rules.insert(
+            libc::SYS_socket,
+            vec![SeccompRule::new(vec![SeccompCondition::new(
+                0,
+                SeccompCmpArgLen::Dword,
+                SeccompCmpOp::Eq,
+                libc::AF_UNIX as u64,
+            )?])?],
+        );
+    }
+
+    if rules.is_empty() {
+        return Ok(());
+    }
+    apply_seccomp_rules(rules)
+}
+
+fn syscalls_in_class(class: SyscallClass) -> &'static [i64] {
+    match class {
+        SyscallClass::Mount => &[
+            libc::SYS_mount,
+            libc::SYS_umount2,
+            libc::SYS_pivot_root,
+            libc::SYS_open_tree,
+            libc::SYS_move_mount,
+            libc::SYS_fsopen,
+            libc::SYS_fsconfig,
+            libc::SYS_fsmount,
+            libc::SYS_fspick,
+            libc::SYS_mount_setattr,
+        ],
+        SyscallClass::Namespaces => &[libc::SYS_unshare, libc::SYS_setns],
+        SyscallClass::KernelModules => &[
+            libc::SYS_init_module,
+            libc::SYS_finit_module,
+            libc::SYS_delete_module,
+        ],
+        SyscallClass::Bpf => &[libc::SYS_bpf],
+        SyscallClass::PerfEvents => &[libc::SYS_perf_event_open],
+        SyscallClass::Keyring => &[libc::SYS_add_key, libc::SYS_request_key, libc::SYS_keyctl],
+        SyscallClass::Reboot => &[
+            libc::SYS_reboot,
+            libc::SYS_kexec_load,
+            libc::SYS_kexec_file_load,
+        ],
+        SyscallClass::Clock => &[
+            libc::SYS_settimeofday,
+            libc::SYS_clock_settime,
+            libc::SYS_adjtimex,
+            libc::SYS_clock_adjtime,
+        ],
+        SyscallClass::Swap => &[libc::SYS_swapon, libc::SYS_swapoff],
+    }
+}
+
+/// Installs Landlock network rules that refuse every TCP bind and every TCP
+/// connect except to `proxy_port`. Does nothing when the kernel does not
+/// support Landlock network rules (before Linux 6.7).
+fn install_proxy_port_landlock_rules_on_current_thread(proxy_port: u16) -> Result<()> {
+    let abi = ABI::V4;
+    let ruleset = match Ruleset::default()
+        .set_compatibility(CompatLevel::HardRequirement)
+        .handle_access(AccessNet::from_all(abi))
+        .and_then(Ruleset::create)
+    {
+        Ok(ruleset) => ruleset,
+        // Not supported by this kernel.
+        Err(_) => return Ok(()),
+    };
+    let status = ruleset
+        .add_rule(NetPort::new(proxy_port, AccessNet::ConnectTcp))?
+        .set_no_new_privs(true)
+        .restrict_self()?;
+
+    if status.ruleset != landlock::RulesetStatus::FullyEnforced {
+        return Err(CodexErr::Sandbox(SandboxErr::LandlockRestrict));
+    }
+
+    Ok(())
+}
+
+/// Installs a seccomp filter for commands that may reach the network only
+/// through the proxy. The network namespace limits TCP to the proxy, so this
+/// filter refuses every other kind of network socket (UDP, raw, SCTP, ...), TCP Fast
+/// Open (which connects without going through `connect`), and inbound
+/// connections.
+fn install_proxy_network_seccomp_filter_on_current_thread() -> std::result::Result<(), SandboxErr> {
+    let mut rules: BTreeMap<i64, Vec<SeccompRule>> = BTreeMap::new();
+
+    for nr in [
+        libc::SYS_accept,
+        libc::SYS_accept4,
+        libc::SYS_bind,
+        libc::SYS_listen,
+        libc::SYS_ptrace,
+    ] {
+        rules.insert(nr, vec![]);
+    }
+
+    // `socket`: only AF_UNIX, or AF_INET/AF_INET6 stream sockets using TCP.
+    let mut socket_rules = vec![SeccompRule::new(vec![
+        SeccompCondition::new(
+            0,
+            SeccompCmpArgLen::Dword,
+            SeccompCmpOp::Ne,
+            libc::AF_UNIX as u64,
+        )?,
+        SeccompCondition::new(
+            0,
+            SeccompCmpArgLen::Dword,
+            SeccompCmpOp::Ne,
+            libc::AF_INET as u64,
+        )?,
+        SeccompCondition::new(
+            0,
+            SeccompCmpArgLen::Dword,
+            SeccompCmpOp::Ne,
+            libc::AF_INET6 as u64,
+        )?,
+    ])?];
+    for domain in [libc::AF_INET, libc::AF_INET6] {
+        let domain =
+            SeccompCondition::new(0, SeccompCmpArgLen::Dword, SeccompCmpOp::Eq, domain as u64)?;
+        // The low bits of `type` hold the socket type; the rest are flags
+        // such as SOCK_CLOEXEC.
+        socket_rules.push(SeccompRule::new(vec![
+            domain.clone(),
+            SeccompCondition::new(
+                1,
+                SeccompCmpArgLen::Dword,
+                SeccompCmpOp::MaskedEq(0xf),
+                libc::SOCK_STREAM as u64,
+            )?,
+            SeccompCondition::new(2, SeccompCmpArgLen::Dword, SeccompCmpOp::Ne, 0)?,
+            SeccompCondition::new(
+                2,
+                SeccompCmpArgLen::Dword,
+                SeccompCmpOp::Ne,
+                libc::IPPROTO_TCP as u64,
+            )?,
+        ])?);
+        for socket_type in [
+            libc::SOCK_DGRAM,
+            libc::SOCK_RAW,
+            libc::SOCK_RDM,
+            libc::SOCK_SEQPACKET,
+            libc::SOCK_PACKET,
+        ] {
+            socket_rules.push(SeccompRule::new(vec![
+                domain.clone(),
+                SeccompCondition::new(
+                    1,
+                    SeccompCmpArgLen::Dword,
+                    SeccompCmpOp::MaskedEq(0xf),
+                    socket_type as u64,
+                )?,
+            ])?);
+        }
+    }
+    rules.insert(libc::SYS_socket, socket_rules);
+    rules.insert(
+        libc::SYS_socketpair,
+        vec![SeccompRule::new(vec![SeccompCondition::new(
+            0,
+            SeccompCmpArgLen::Dword,
+            SeccompCmpOp::Ne,
+            libc::AF_UNIX as u64,
+        )?])?],
+    );
+
+    // TCP Fast Open connects from `sendto`/`sendmsg`, bypassing Landlock's
+    // connect check.
+    let fast_open = |flags_arg: u8| -> std::result::Result<SeccompRule, SandboxErr> {
+        Ok(SeccompRule::new(vec![SeccompCondition::new(
+            flags_arg,
+            SeccompCmpArgLen::Dword,
+            SeccompCmpOp::MaskedEq(libc::MSG_FASTOPEN as u64),
+            libc::MSG_FASTOPEN as u64,
+        )?])?)
+    };
+    rules.insert(libc::SYS_sendto, vec![fast_open(3)?]);
+    rules.insert(libc::SYS_sendmsg, vec![fast_open(2)?]);
+    rules.insert(libc::SYS_sendmmsg, vec![fast_open(3)?]);
+
+    apply_seccomp_rules(rules)
+}
+
 /// Installs a seccomp filter that blocks outbound network access except for
 /// AF_UNIX domain sockets.
 fn install_network_seccomp_filter_on_current_thread() -> std::result::Result<(), SandboxErr> {
@@ -136,6 +398,14 @@ fn install_network_seccomp_filter_on_current_thread() -> std::result::Result<(),
     rules.insert(libc::SYS_socket, vec![unix_only_rule.clone()]);
     rules.insert(libc::SYS_socketpair, vec![unix_only_rule]); // always deny (Unix can use socketpair but fine, keep open?)
 
+    apply_seccomp_rules(rules)
+}
+
+/// Applies a filter that returns EPERM for calls matching `rules` and allows
+/// everything else.
+fn apply_seccomp_rules(
+    rules: BTreeMap<i64, Vec<SeccompRule>>,
+) -> std::result::Result<(), SandboxErr> {
     let filter = SeccompFilter::new(
         rules,
         SeccompAction::Allow,                     // default – allow
@@ -1,8 +1,11 @@
 use clap::Parser;
 use std::ffi::CString;
+use std::net::SocketAddr;
 use std::path::PathBuf;
 
 use crate::landlock::apply_sandbox_policy_to_current_thread;
+use crate::mounts::mount_paths_read_only;
+use crate::proxy_relay::isolate_network_with_proxy_relay;
 
 #[derive(Debug, Parser)]
 pub struct LandlockCommand {
@@ -14,6 +17,21 @@ pub struct LandlockCommand {
     #[arg(long = "sandbox-policy")]
     pub sandbox_policy: codex_core::protocol::SandboxPolicy,
 
+    /// Existing files or directories to keep read-only even inside a writable
+    /// root (the session's `write_protected` paths).
+    #[arg(long = "read-only-path")]
+    pub read_only_paths: Vec<PathBuf>,
+
+    /// Loopback address of the network proxy. When the policy has no network
+    /// access, the command runs in its own network namespace where only this
+    /// address is reachable.
+    #[arg(long = "network-proxy-addr")]
+    pub network_proxy_addr: Option<SocketAddr>,
+
+    /// Extra restrictions from `[linux_sandbox]`, as JSON.
+    #[arg(long = "linux-sandbox-config")]
+    pub linux_sandbox_config: Option<codex_core::config::types::LinuxSandboxConfig>,
+
     /// Full command args to run under landlock.
     #[arg(trailing_var_arg = true)]
     pub command: Vec<String>,
@@ -23,10 +41,35 @@ pub fn run_main() -> ! {
     let LandlockCommand {
         sandbox_policy_cwd,
         sandbox_policy,
+        read_only_paths,
+        network_proxy_addr,
+        linux_sandbox_config,
         command,
     } = LandlockCommand::parse();
 
-    if let Err(e) = apply_sandbox_policy_to_current_thread(&sandbox_policy, &sandbox_policy_cwd) {
+    // Mounts go first: once Landlock is in place the mount syscalls would be
+    // refused.
+    if let Err(e) = mount_paths_read_only(&read_only_paths) {
+        panic!("error protecting read-only paths: {e:?}");
+    }
+
+    // The relay needs the host network namespace to reach the proxy, so it
+    // also goes before Landlock and seccomp. Without network namespaces the
+    // proxy is left out and the network stays fully blocked.
+    let network_proxy_port = network_proxy_addr
+        .filter(|_| !sandbox_policy.has_full_network_access())
+        .filter(|proxy| match isolate_network_with_proxy_relay(*proxy) {
+            Ok(isolated) => isolated,
+            Err(e) => panic!("error isolating the network for the proxy: {e:?}"),
+        })
+        .map(|proxy| proxy.port());
+
+    if let Err(e) = apply_sandbox_policy_to_current_thread(
+        &sandbox_policy,
+        &sandbox_policy_cwd,
+        network_proxy_port,
+        &linux_sandbox_config.unwrap_or_default(),
+    ) {
         panic!("error running landlock: {e:?}");
     }
 
@@ -3,6 +3,7 @@
 use std::ffi::CString;
 use std::os::unix::ffi::OsStrExt;
 use std::path::Path;
+use std::path::PathBuf;
 
 use codex_core::error::CodexErr;
 use codex_core::error::Result;
@@ -22,6 +23,23 @@ pub(crate) fn apply_read_only_mounts(sandbox_policy: &SandboxPolicy, cwd: &Path)
         return Ok(());
     }
 
+    mount_read_only(&mount_targets)
+}
+
+/// Bind-mount each of `paths` read-only in a private mount namespace; see
+/// [`apply_read_only_mounts`]. Does nothing when `paths` is empty.
+pub(crate) fn mount_paths_read_only(paths: &[PathBuf]) -> Result<()> {
+    if paths.is_empty() {
+        return Ok(());
+    }
+    let targets = paths
+        .iter()
+        .map(AbsolutePathBuf::from_absolute_path)
+        .collect::<std::io::Result<Vec<_>>>()?;
+    mount_read_only(&targets)
+}
+
+fn mount_read_only(mount_targets: &[AbsolutePathBuf]) -> Result<()> {
     // Root can unshare the mount namespace directly; non-root needs a user
     // namespace to gain capabilities for remounting.
     if is_running_as_root() {
@@ -157,7 +175,7 @@ struct CapUserData {
 const LINUX_CAPABILITY_VERSION_3: u32 = 0x2008_0522;
 
 /// Map the provided uid/gid to root inside the user namespace.
-fn write_user_namespace_maps(uid: libc::uid_t, gid: libc::gid_t) -> Result<()> {
+pub(crate) fn write_user_namespace_maps(uid: libc::uid_t, gid: libc::gid_t) -> Result<()> {
     write_proc_file("/proc/self/setgroups", "deny\n")?;
 
     write_proc_file("/proc/self/uid_map", format!("0 {uid} 1\n"))?;
@@ -166,7 +184,7 @@ fn write_user_namespace_maps(uid: libc::uid_t, gid: libc::gid_t) -> Result<()> {
 }
 
 /// Drop all capabilities in the current user namespace.
-fn drop_caps() -> Result<()> {
+pub(crate) fn drop_caps() -> Result<()> {
     let mut header = CapUserHeader {
         version: LINUX_CAPABILITY_VERSION_3,
         pid: 0,
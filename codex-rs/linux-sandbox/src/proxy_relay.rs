@@ -0,0 +1,259 @@
+//! Loopback-only access to the network proxy.
+//!
+//! Landlock can limit TCP connections to the proxy's port but not to its
+//! address, so on its own it would let a command reach that port on any host
+//! and get around the proxy's allowlist. Instead the command runs in a new
+//! network namespace whose only interface is loopback. A relay process inside
+//! that namespace listens on the proxy's address and, for each connection,
+//! gets a socket already connected to the real proxy from a helper process
+//! that stays in the original namespace. The two talk over a Unix socket pair
+//! and the connected sockets are passed with `SCM_RIGHTS`.
+
+use std::io;
+use std::io::Read;
+use std::io::Write;
+use std::net::Shutdown;
+use std::net::SocketAddr;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::os::fd::AsRawFd;
+use std::os::fd::FromRawFd;
+use std::os::fd::OwnedFd;
+use std::os::fd::RawFd;
+use std::os::unix::net::UnixStream;
+
+use codex_core::error::Result;
+
+use crate::mounts::drop_caps;
+use crate::mounts::write_user_namespace_maps;
+
+enum Forked {
+    Parent,
+    Child,
+}
+
+/// Moves the current process into a network namespace where `proxy` is the
+/// only reachable address. Returns `false`, with the network namespace left
+/// alone, when namespaces are not available; the caller then blocks the
+/// network entirely.
+pub(crate) fn isolate_network_with_proxy_relay(proxy: SocketAddr) -> Result<bool> {
+    let (helper_end, relay_end) = UnixStream::pair()?;
+
+    // The helper stays in this namespace, where the proxy is reachable.
+    if let Forked::Child = fork_tied_to_parent()? {
+        drop(relay_end);
+        serve_proxy_connections(&helper_end, proxy);
+        unsafe { libc::_exit(0) };
+    }
+    drop(helper_end);
+
+    let Some(in_user_namespace) = enter_network_namespace()? else {
+        // Dropping `relay_end` makes the helper exit.
+        return Ok(false);
+    };
+    bring_up_loopback()?;
+    let listener = TcpListener::bind(proxy)?;
+    // Capabilities from the new user namespace are only needed above.
+    if in_user_namespace {
+        drop_caps()?;
+    }
+
+    if let Forked::Child = fork_tied_to_parent()? {
+        relay_connections(&listener, &relay_end);
+        unsafe { libc::_exit(0) };
+    }
+    Ok(true)
+}
+
+/// Forks a process that is killed when the current one, which goes on to
+/// exec the sandboxed command, exits.
+fn fork_tied_to_parent() -> io::Result<Forked> {
+    let parent = unsafe { libc::getpid() };
+    match unsafe { libc::fork() } {
+        -1 => Err(io::Error::last_os_error()),
+        0 => {
+            unsafe { libc::prctl(libc::PR_SET_PDEATHSIG, libc::SIGKILL) };
+            // The parent may have exited before the signal was armed.
+            if unsafe { libc::getppid() } != parent {
+                unsafe { libc::_exit(0) };
+            }
+            Ok(Forked::Child)
+        }
+        _ => Ok(Forked::Parent),
+    }
+}
+
+/// Returns whether a user namespace had to be created as well, or `None`
+/// when no network namespace could be created.
+fn enter_network_namespace() -> Result<Option<bool>> {
+    if unsafe { libc::unshare(libc::CLONE_NEWNET) } == 0 {
+        return Ok(Some(false));
+    }
+    // Without CAP_SYS_ADMIN, a new user namespace grants it.
+    let uid = unsafe { libc::geteuid() };
+    let gid = unsafe { libc::getegid() };
+    if unsafe { libc::unshare(libc::CLONE_NEWUSER | libc::CLONE_NEWNET) } != 0 {
+        return Ok(None);
+    }
+    write_user_namespace_maps(uid, gid)?;
+    Ok(Some(true))
+}
+
+/// A new network namespace starts with `lo` down.
+fn bring_up_loopback() -> io::Result<()> {
+    let socket = unsafe { libc::socket(libc::AF_INET, libc::SOCK_DGRAM | libc::SOCK_CLOEXEC, 0) };
+    if socket < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let socket = unsafe { OwnedFd::from_raw_fd(socket) };
+    let mut request: libc::ifreq = unsafe { std::mem::zeroed() };
+    for (dst, src) in request.ifr_name.iter_mut().zip(b"lo") {
+        *dst = *src as libc::c_char;
+    }
+    request.ifr_ifru.ifru_flags = (libc::IFF_UP | libc::IFF_RUNNING) as libc::c_short;
+    if unsafe { libc::ioctl(socket.as_raw_fd(), libc::SIOCSIFFLAGS as _, &request) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Helper loop: for every request byte, connects to the proxy and sends the
+/// socket back. A reply without a socket means the connection failed.
+fn serve_proxy_connections(requests: &UnixStream, proxy: SocketAddr) {
+    let mut request = [0u8; 1];
+    while matches!((&*requests).read(&mut request), Ok(1)) {
+        let sent = match TcpStream::connect(proxy) {
+            Ok(stream) => send_fd(requests, stream.as_raw_fd()),
+            Err(_) => (&*requests).write_all(&[0]),
+        };
+        if sent.is_err() {
+            return;
+        }
+    }
+}
+
+/// Relay loop: accepts connections inside the namespace and pipes each one
+/// to a proxy connection obtained from the helper.
+fn relay_connections(listener: &TcpListener, helper: &UnixStream) {
+    for client in listener.incoming() {
+        let Ok(client) = client else {
+            continue;
+        };
+        if (&*helper).write_all(&[1]).is_err() {
+            return;
+        }
+        let upstream = match recv_fd(helper) {
+            Ok(Some(fd)) => TcpStream::from(fd),
+            Ok(None) => continue,
+            Err(_) => return,
+        };
+        std::thread::spawn(move || pipe(client, upstream));
+    }
+}
+
+fn pipe(client: TcpStream, upstream: TcpStream) {
+    let (Ok(mut client_reader), Ok(mut upstream_writer)) =
+        (client.try_clone(), upstream.try_clone())
+    else {
+        return;
+    };
+    let upload = std::thread::spawn(move || {
+        let _ = io::copy(&mut client_reader, &mut upstream_writer);
+        let _ = upstream_writer.shutdown(Shutdown::Write);
+    });
+    let (mut upstream, mut client) = (upstream, client);
+    let _ = io::copy(&mut upstream, &mut client);
+    let _ = client.shutdown(Shutdown::Write);
+    let _ = upload.join();
+}
+
+/// Room for one `SCM_RIGHTS` message carrying a single descriptor, aligned
+/// for `cmsghdr`.
+fn control_buffer() -> (Vec<u64>, usize) {
+    let space = unsafe { libc::CMSG_SPACE(size_of::<RawFd>() as u32) } as usize;
+    (vec![0u64; space.div_ceil(size_of::<u64>())], space)
+}
+
+fn send_fd(socket: &UnixStream, fd: RawFd) -> io::Result<()> {
+    let mut byte = [1u8];
+    let mut iov = libc::iovec {
+        iov_base: byte.as_mut_ptr().cast(),
+        iov_len: byte.len(),
+    };
+    let (mut control, space) = control_buffer();
+    let mut message: libc::msghdr = unsafe { std::mem::zeroed() };
+    message.msg_iov = &mut iov;
+    message.msg_iovlen = 1;
+    message.msg_control = control.as_mut_ptr().cast();
+    message.msg_controllen = space as _;
+    unsafe {
+        let header = libc::CMSG_FIRSTHDR(&message);
+        (*header).cmsg_level = libc::SOL_SOCKET;
+        (*header).cmsg_type = libc::SCM_RIGHTS;
+        (*header).cmsg_len = libc::CMSG_LEN(size_of::<RawFd>() as u32) as _;
+        std::ptr::write_unaligned(libc::CMSG_DATA(header).cast::<RawFd>(), fd);
+    }
+    if unsafe { libc::sendmsg(socket.as_raw_fd(), &message, 0) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+fn recv_fd(socket: &UnixStream) -> io::Result<Option<OwnedFd>> {
+    let mut byte = [0u8];
+    let mut iov = libc::iovec {
+        iov_base: byte.as_mut_ptr().cast(),
+        iov_len: byte.len(),
+    };
+    let (mut control, space) = control_buffer();
+    let mut message: libc::msghdr = unsafe { std::mem::zeroed() };
+    message.msg_iov = &mut iov;
+    message.msg_iovlen = 1;
+    message.msg_control = control.as_mut_ptr().cast();
+    message.msg_controllen = space as _;
+    match unsafe { libc::recvmsg(socket.as_raw_fd(), &mut message, libc::MSG_CMSG_CLOEXEC) } {
+        received if received < 0 => return Err(io::Error::last_os_error()),
+        0 => return Err(io::ErrorKind::UnexpectedEof.into()),
+        _ => {}
+    }
+    let header = unsafe { libc::CMSG_FIRSTHDR(&message) };
+    if header.is_null()
+        || unsafe { (*header).cmsg_level } != libc::SOL_SOCKET
+        || unsafe { (*header).cmsg_type } != libc::SCM_RIGHTS
+    {
+        return Ok(None);
+    }
+    let fd = unsafe { std::ptr::read_unaligned(libc::CMSG_DATA(header).cast::<RawFd>()) };
+    Ok(Some(unsafe { OwnedFd::from_raw_fd(fd) }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn sockets_pass_between_processes_over_the_pair() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+        let proxy = listener.local_addr().expect("addr");
+        let (helper_end, relay_end) = UnixStream::pair().expect("pair");
+        let helper = std::thread::spawn(move || serve_proxy_connections(&helper_end, proxy));
+
+        (&relay_end).write_all(&[1]).expect("request");
+        let mut upstream = TcpStream::from(
+            recv_fd(&relay_end)
+                .expect("reply")
+                .expect("connected socket"),
+        );
+        let (mut accepted, _) = listener.accept().expect("accept");
+        upstream.write_all(b"ping").expect("write");
+        let mut received = [0u8; 4];
+        accepted.read_exact(&mut received).expect("read");
+        assert_eq!(&received, b"ping");
+
+        drop(relay_end);
+        helper
+            .join()
+            .expect("helper exits when the relay end closes");
+    }
+}
@@ -3,6 +3,7 @@
 use codex_core::config::types::ShellEnvironmentPolicy;
 use codex_core::error::CodexErr;
 use codex_core::error::SandboxErr;
+use codex_core::exec::ExecLimits;
 use codex_core::exec::ExecParams;
 use codex_core::exec::process_exec_tool_call;
 use codex_core::exec_env::create_env;
@@ -64,6 +65,7 @@ async fn run_cmd_output(
         windows_sandbox_level: WindowsSandboxLevel::Disabled,
         justification: None,
         arg0: None,
+        limits: ExecLimits::default(),
     };
 
     let sandbox_policy = SandboxPolicy::WorkspaceWrite {
@@ -182,6 +184,7 @@ async fn assert_network_blocked(cmd: &[&str]) {
         windows_sandbox_level: WindowsSandboxLevel::Disabled,
         justification: None,
         arg0: None,
+        limits: ExecLimits::default(),
     };
 
     let sandbox_policy = SandboxPolicy::new_read_only_policy();
@@ -0,0 +1,257 @@
+//! Handler for the `codex-sessions` tool-call, which lists recorded sessions
+//! that `codex-reply` can resume.
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_core::RolloutRecorder;
+use codex_core::ThreadSortKey;
+use codex_core::config::Config;
+use codex_core::config::ConfigBuilder;
+use codex_core::config::ConfigOverrides;
+use codex_core::find_session_summaries_by_ids;
+use codex_core::find_thread_names_by_ids;
+use codex_core::read_session_meta_line;
+use codex_core::summarize_rollout_file;
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::SessionMetaLine;
+use codex_protocol::protocol::SessionSource;
+use mcp_types::CallToolResult;
+use mcp_types::ContentBlock;
+use mcp_types::TextContent;
+use serde_json::json;
+
+const DEFAULT_SESSION_LIMIT: u32 = 20;
+
+/// Sessions a user started themselves; sub-agent threads are left out.
+const LISTED_SESSION_SOURCES: &[SessionSource] = &[
+    SessionSource::Cli,
+    SessionSource::VSCode,
+    SessionSource::Exec,
+    SessionSource::Mcp,
+];
+
+/// Loads the configuration a recorded session resumes with: the one that
+/// applies in the directory the session ran in, as if `codex` had been called
+/// with that `cwd`.
+pub(crate) async fn config_for_recorded_session(
+    config: &Config,
+    rollout_path: &Path,
+    codex_linux_sandbox_exe: Option<PathBuf>,
+) -> std::io::Result<Config> {
+    let meta_line = read_session_meta_line(rollout_path).await?;
+    ConfigBuilder::default()
+        .codex_home(config.codex_home.clone())
+        .harness_overrides(ConfigOverrides {
+            cwd: Some(meta_line.meta.cwd),
+            codex_linux_sandbox_exe,
+            ..Default::default()
+        })
+        .build()
+        .await
+}
+
+pub(crate) async fn list_codex_sessions(
+    config: &Config,
+    limit: Option<u32>,
+) -> anyhow::Result<CallToolResult> {
+    let limit = limit.unwrap_or(DEFAULT_SESSION_LIMIT).max(1) as usize;
+    let page = RolloutRecorder::list_threads(
+        &config.codex_home,
+        limit,
+        None,
+        ThreadSortKey::UpdatedAt,
+        LISTED_SESSION_SOURCES,
+        None,
+        &config.model_provider_id,
+    )
+    .await?;
+
+    let sessions: Vec<_> =
+        page.items
+            .iter()
+            .filter_map(|item| {
+                let meta = item.head.iter().find_map(|value| {
+                    serde_json::from_value::<SessionMetaLine>(value.clone()).ok()
+                })?;
+                Some((item, meta))
+            })
+            .collect();
+    let ids: HashSet<ThreadId> = sessions.iter().map(|(_, meta)| meta.meta.id).collect();
+    let names = find_thread_names_by_ids(&config.codex_home, &ids)
+        .await
+        .unwrap_or_default();
+    let summaries = find_session_summaries_by_ids(&config.codex_home, &ids)
+        .await
+        .unwrap_or_default();
+
+    let mut entries = Vec::with_capacity(sessions.len());
+    let mut lines = Vec::with_capacity(sessions.len());
+    for (item, meta) in sessions {
+        let id = meta.meta.id;
+        let updated_at = item.updated_at.as_ref().or(item.created_at.as_ref());
+        let name = names.get(&id);
+        let title = match summaries.get(&id) {
+            Some(summary) => Some(summary.title.clone()),
+            None => summarize_rollout_file(&item.path)
+                .await
+                .ok()
+                .flatten()
+                .map(|summary| summary.title),
+        };
+
+        let mut line = format!(
+            "{id}  {}  {}",
+            updated_at.map_or("-", String::as_str),
+            meta.meta.cwd.display()
+        );
+        if let Some(name) = name {
+            line.push_str(&format!("  ({name})"));
+        }
+        if let Some(title) = &title {
+            line.push_str(&format!("\n    {title}"));
+        }
+        lines.push(line);
+
+        let mut entry = json!({
+            "threadId": id,
+            "cwd": meta.meta.cwd,
+        });
+        if let Some(updated_at) = updated_at {
+            entry["updatedAt"] = json!(updated_at);
+        }
+        if let Some(name) = name {
+            entry["name"] = json!(name);
+        }
+        if let Some(title) = title {
+            entry["title"] = json!(title);
+        }
+        entries.push(entry);
+    }
+
+    let text = if lines.is_empty() {
+        "No sessions found.".to_string()
+    } else {
+        lines.join("\n")
+    };
+    Ok(CallToolResult {
+        content: vec![ContentBlock::TextContent(TextContent {
+            r#type: "text".to_string(),
+            text,
+            annotations: None,
+        })],
+        is_error: None,
+        structured_content: Some(json!({ "sessions": entries })),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::SessionMeta;
+    use codex_protocol::protocol::SubAgentSource;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    async fn test_config(codex_home: &Path) -> Config {
+        ConfigBuilder::default()
+            .codex_home(codex_home.to_path_buf())
+            .fallback_cwd(Some(codex_home.to_path_buf()))
+            .build()
+            .await
+            .expect("load default config")
+    }
+
+    /// Writes a minimal recording of a session that ran in `cwd`.
+    fn record_session(
+        config: &Config,
+        cwd: &Path,
+        source: SessionSource,
+        filename_ts: &str,
+    ) -> (ThreadId, PathBuf) {
+        let timestamp = "2025-01-02T10:00:00Z";
+        let id = ThreadId::new();
+        let dir = config.codex_home.join("sessions/2025/01/02");
+        std::fs::create_dir_all(&dir).expect("create sessions dir");
+        let path = dir.join(format!("rollout-{filename_ts}-{id}.jsonl"));
+        let meta = SessionMetaLine {
+            meta: SessionMeta {
+                id,
+                timestamp: timestamp.to_string(),
+                cwd: cwd.to_path_buf(),
+                originator: "codex".to_string(),
+                cli_version: "0.0.0".to_string(),
+                source,
+                model_provider: Some(config.model_provider_id.clone()),
+                ..Default::default()
+            },
+            git: None,
+        };
+        let lines = [
+            json!({
+                "timestamp": timestamp,
+                "type": "session_meta",
+                "payload": meta,
+            }),
+            json!({
+                "timestamp": timestamp,
+                "type": "event_msg",
+                "payload": { "type": "user_message", "message": "fix the build", "kind": "plain" },
+            }),
+        ];
+        let body = lines.map(|line| line.to_string()).join("\n") + "\n";
+        std::fs::write(&path, body).expect("write rollout");
+        (id, path)
+    }
+
+    #[tokio::test]
+    async fn lists_sessions_users_started_with_their_cwd() {
+        let codex_home = TempDir::new().expect("tempdir");
+        let project = TempDir::new().expect("tempdir");
+        let config = test_config(codex_home.path()).await;
+        let (id, _) = record_session(
+            &config,
+            project.path(),
+            SessionSource::Cli,
+            "2025-01-02T10-00-00",
+        );
+        record_session(
+            &config,
+            project.path(),
+            SessionSource::SubAgent(SubAgentSource::Review),
+            "2025-01-02T11-00-00",
+        );
+
+        let result = list_codex_sessions(&config, None)
+            .await
+            .expect("list sessions");
+        let sessions = result.structured_content.expect("structured content")["sessions"].clone();
+        let listed: Vec<_> = sessions
+            .as_array()
+            .expect("sessions array")
+            .iter()
+            .map(|entry| (entry["threadId"].clone(), entry["cwd"].clone()))
+            .collect();
+        assert_eq!(listed, vec![(json!(id), json!(project.path()))]);
+    }
+
+    #[tokio::test]
+    async fn recorded_sessions_resume_in_the_directory_they_ran_in() {
+        let codex_home = TempDir::new().expect("tempdir");
+        let project = TempDir::new().expect("tempdir");
+        let config = test_config(codex_home.path()).await;
+        let (_, path) = record_session(
+            &config,
+            project.path(),
+            SessionSource::Mcp,
+            "2025-01-02T10-00-00",
+        );
+
+        let resumed = config_for_recorded_session(&config, &path, None)
+            .await
+            .expect("load config for recorded session");
+        assert_eq!(resumed.cwd, project.path());
+        assert_eq!(resumed.codex_home, config.codex_home);
+    }
+}
@@ -258,6 +258,65 @@ pub(crate) fn create_tool_for_codex_tool_call_reply_param() -> Tool {
     }
 }
 
+/// Client-supplied parameters for a `codex-sessions` tool-call.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexSessionsParam {
+    /// Maximum number of sessions to return, newest first. Defaults to 20.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+/// Builds a `Tool` definition for the `codex-sessions` tool-call.
+pub(crate) fn create_tool_for_codex_sessions_param() -> Tool {
+    let schema = SchemaSettings::draft2019_09()
+        .with(|s| {
+            s.inline_subschemas = true;
+            s.option_add_null_type = false;
+        })
+        .into_generator()
+        .into_root_schema_for::<CodexSessionsParam>();
+
+    #[expect(clippy::expect_used)]
+    let schema_value =
+        serde_json::to_value(&schema).expect("Codex sessions tool schema should serialise to JSON");
+
+    let tool_input_schema =
+        serde_json::from_value::<ToolInputSchema>(schema_value).unwrap_or_else(|e| {
+            panic!("failed to create Tool from schema: {e}");
+        });
+
+    Tool {
+        name: "codex-sessions".to_string(),
+        title: Some("Codex Sessions".to_string()),
+        input_schema: tool_input_schema,
+        output_schema: Some(ToolOutputSchema {
+            properties: Some(serde_json::json!({
+                "sessions": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "threadId": { "type": "string" },
+                            "updatedAt": { "type": "string" },
+                            "cwd": { "type": "string" },
+                            "name": { "type": "string" },
+                            "title": { "type": "string" }
+                        },
+                        "required": ["threadId", "cwd"]
+                    }
+                }
+            })),
+            required: Some(vec!["sessions".to_string()]),
+            r#type: "object".to_string(),
+        }),
+        description: Some(
+            "List recorded Codex sessions, newest first. Pass a threadId to codex-reply to resume one.".to_string(),
+        ),
+        annotations: None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -407,4 +466,23 @@ mod tests {
         });
         assert_eq!(expected_tool_json, tool_json);
     }
+
+    #[test]
+    fn verify_codex_sessions_json_schema() {
+        let tool = create_tool_for_codex_sessions_param();
+        let tool_json = serde_json::to_value(&tool).expect("tool serializes");
+        let expected_input_schema = serde_json::json!({
+          "properties": {
+            "limit": {
+              "description": "Maximum number of sessions to return, newest first. Defaults to 20.",
+              "format": "uint32",
+              "minimum": 0.0,
+              "type": "integer"
+            }
+          },
+          "type": "object",
+        });
+        assert_eq!(expected_input_schema, tool_json["inputSchema"]);
+        assert_eq!(serde_json::json!("codex-sessions"), tool_json["name"]);
+    }
 }
@@ -20,6 +20,7 @@ use codex_core::protocol::EventMsg;
 use codex_core::protocol::ExecApprovalRequestEvent;
 use codex_core::protocol::Op;
 use codex_core::protocol::Submission;
+use codex_core::protocol::TurnBlockedEvent;
 use codex_core::protocol::TurnCompleteEvent;
 use codex_protocol::ThreadId;
 use codex_protocol::user_input::UserInput;
@@ -236,6 +237,7 @@ async fn run_codex_tool_session_inner(
                         reason: _,
                         proposed_execpolicy_amendment: _,
                         parsed_cmd,
+                        file_operation: _,
                     }) => {
                         handle_exec_approval_request(
                             command,
@@ -268,6 +270,15 @@ async fn run_codex_tool_session_inner(
                     EventMsg::Warning(_) => {
                         continue;
                     }
+                    EventMsg::TurnBlocked(TurnBlockedEvent { category, message }) => {
+                        let result = create_call_tool_result_with_thread_id(
+                            thread_id,
+                            format!("Response blocked ({category}): {message}"),
+                            Some(true),
+                        );
+                        outgoing.send_response(request_id.clone(), result).await;
+                        break;
+                    }
                     EventMsg::ElicitationRequest(_) => {
                         // TODO: forward elicitation requests to the client?
                         continue;
@@ -278,6 +289,8 @@ async fn run_codex_tool_session_inner(
                         reason,
                         grant_root,
                         changes,
+                        hunks: _,
+                        file_operations: _,
                     }) => {
                         handle_patch_approval_request(
                             call_id,
@@ -330,13 +343,20 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::AgentReasoningRawContentDelta(_)
                     | EventMsg::TurnStarted(_)
                     | EventMsg::TokenCount(_)
+                    | EventMsg::ModelSnapshot(_)
+                    | EventMsg::ModelFailover(_)
                     | EventMsg::AgentReasoning(_)
                     | EventMsg::AgentReasoningSectionBreak(_)
                     | EventMsg::McpToolCallBegin(_)
                     | EventMsg::McpToolCallEnd(_)
                     | EventMsg::McpListToolsResponse(_)
+                    | EventMsg::McpLoginCompleted(_)
+                    | EventMsg::McpToolCallProgress(_)
+                    | EventMsg::McpServerStatusResponse(_)
                     | EventMsg::ListCustomPromptsResponse(_)
                     | EventMsg::ListSkillsResponse(_)
+                    | EventMsg::ListTrashResponse(_)
+                    | EventMsg::ListJobsResponse(_)
                     | EventMsg::ExecCommandBegin(_)
                     | EventMsg::TerminalInteraction(_)
                     | EventMsg::ExecCommandOutputDelta(_)
@@ -354,6 +374,7 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::UserMessage(_)
                     | EventMsg::ShutdownComplete
                     | EventMsg::ViewImageToolCall(_)
+                    | EventMsg::OpenFileRequest(_)
                     | EventMsg::RawResponseItem(_)
                     | EventMsg::EnteredReviewMode(_)
                     | EventMsg::ItemStarted(_)
@@ -364,11 +385,15 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::SkillsUpdateAvailable
                     | EventMsg::UndoStarted(_)
                     | EventMsg::UndoCompleted(_)
+                    | EventMsg::CheckpointCreated(_)
                     | EventMsg::ExitedReviewMode(_)
                     | EventMsg::RequestUserInput(_)
                     | EventMsg::DynamicToolCallRequest(_)
                     | EventMsg::ContextCompacted(_)
                     | EventMsg::ThreadRolledBack(_)
+                    | EventMsg::ContextEditPreview(_)
+                    | EventMsg::PinnedContextUpdated(_)
+                    | EventMsg::ContextEdited(_)
                     | EventMsg::CollabAgentSpawnBegin(_)
                     | EventMsg::CollabAgentSpawnEnd(_)
                     | EventMsg::CollabAgentInteractionBegin(_)
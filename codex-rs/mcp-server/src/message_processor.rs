@@ -1,33 +1,56 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
 
+use crate::codex_sessions::config_for_recorded_session;
+use crate::codex_sessions::list_codex_sessions;
+use crate::codex_tool_config::CodexSessionsParam;
 use crate::codex_tool_config::CodexToolCallParam;
 use crate::codex_tool_config::CodexToolCallReplyParam;
+use crate::codex_tool_config::create_tool_for_codex_sessions_param;
 use crate::codex_tool_config::create_tool_for_codex_tool_call_param;
 use crate::codex_tool_config::create_tool_for_codex_tool_call_reply_param;
+use crate::error_code::INVALID_PARAMS_ERROR_CODE;
 use crate::error_code::INVALID_REQUEST_ERROR_CODE;
 use crate::outgoing_message::OutgoingMessageSender;
+use crate::outgoing_message::OutgoingNotificationMeta;
 use codex_protocol::ThreadId;
+use codex_protocol::custom_prompts::CustomPrompt;
 use codex_protocol::protocol::SessionSource;
 
 use codex_core::AuthManager;
+use codex_core::CodexThread;
+use codex_core::NewThread;
 use codex_core::ThreadManager;
 use codex_core::config::Config;
+use codex_core::custom_prompts::default_commands_dir;
+use codex_core::custom_prompts::discover_commands_in;
+use codex_core::custom_prompts::expand_prompt_arguments;
+use codex_core::custom_prompts::prompt_argument_names;
 use codex_core::default_client::USER_AGENT_SUFFIX;
 use codex_core::default_client::get_codex_user_agent;
+use codex_core::find_thread_path_by_id_str;
+use codex_core::protocol::Event;
+use codex_core::protocol::EventMsg;
 use codex_core::protocol::Submission;
 use mcp_types::CallToolRequestParams;
 use mcp_types::CallToolResult;
 use mcp_types::ClientRequest as McpClientRequest;
 use mcp_types::ContentBlock;
+use mcp_types::GetPromptResult;
 use mcp_types::JSONRPCError;
 use mcp_types::JSONRPCErrorError;
 use mcp_types::JSONRPCNotification;
 use mcp_types::JSONRPCRequest;
 use mcp_types::JSONRPCResponse;
+use mcp_types::ListPromptsResult;
 use mcp_types::ListToolsResult;
 use mcp_types::ModelContextProtocolRequest;
+use mcp_types::Prompt;
+use mcp_types::PromptArgument;
+use mcp_types::PromptMessage;
 use mcp_types::RequestId;
+use mcp_types::Role;
+use mcp_types::ServerCapabilitiesPrompts;
 use mcp_types::ServerCapabilitiesTools;
 use mcp_types::ServerNotification;
 use mcp_types::TextContent;
@@ -40,6 +63,10 @@ pub(crate) struct MessageProcessor {
     outgoing: Arc<OutgoingMessageSender>,
     initialized: bool,
     codex_linux_sandbox_exe: Option<PathBuf>,
+    /// Configuration the server was started with; recorded sessions are
+    /// listed from its `codex_home`.
+    config: Arc<Config>,
+    auth_manager: Arc<AuthManager>,
     thread_manager: Arc<ThreadManager>,
     running_requests_id_to_codex_uuid: Arc<Mutex<HashMap<RequestId, ThreadId>>>,
 }
@@ -54,19 +81,21 @@ impl MessageProcessor {
     ) -> Self {
         let outgoing = Arc::new(outgoing);
         let auth_manager = AuthManager::shared(
-            config.codex_home.clone(),
+            config.auth_home(),
             false,
             config.cli_auth_credentials_store_mode,
         );
         let thread_manager = Arc::new(ThreadManager::new(
             config.codex_home.clone(),
-            auth_manager,
+            auth_manager.clone(),
             SessionSource::Mcp,
         ));
         Self {
             outgoing,
             initialized: false,
             codex_linux_sandbox_exe,
+            config,
+            auth_manager,
             thread_manager,
             running_requests_id_to_codex_uuid: Arc::new(Mutex::new(HashMap::new())),
         }
@@ -108,10 +137,10 @@ impl MessageProcessor {
                 self.handle_unsubscribe(params);
             }
             McpClientRequest::ListPromptsRequest(params) => {
-                self.handle_list_prompts(params);
+                self.handle_list_prompts(request_id, params).await;
             }
             McpClientRequest::GetPromptRequest(params) => {
-                self.handle_get_prompt(params);
+                self.handle_get_prompt(request_id, params).await;
             }
             McpClientRequest::ListToolsRequest(params) => {
                 self.handle_list_tools(request_id, params).await;
@@ -211,7 +240,7 @@ impl MessageProcessor {
                 completions: None,
                 experimental: None,
                 logging: None,
-                prompts: None,
+                prompts: Some(ServerCapabilitiesPrompts { list_changed: None }),
                 resources: None,
                 tools: Some(ServerCapabilitiesTools {
                     list_changed: Some(true),
@@ -285,18 +314,120 @@ impl MessageProcessor {
         tracing::info!("resources/unsubscribe -> params: {:?}", params);
     }
 
-    fn handle_list_prompts(
+    /// Custom slash commands from `$CODEX_HOME/commands`, exposed as prompts.
+    async fn custom_commands(&self) -> Vec<CustomPrompt> {
+        match default_commands_dir() {
+            Some(dir) => discover_commands_in(&dir, &self.config.cwd).await,
+            None => Vec::new(),
+        }
+    }
+
+    async fn handle_list_prompts(
         &self,
+        id: RequestId,
         params: <mcp_types::ListPromptsRequest as mcp_types::ModelContextProtocolRequest>::Params,
     ) {
         tracing::info!("prompts/list -> params: {:?}", params);
+        let prompts = self
+            .custom_commands()
+            .await
+            .into_iter()
+            .map(|command| {
+                let mut arguments: Vec<PromptArgument> = prompt_argument_names(&command.content)
+                    .into_iter()
+                    .map(|name| PromptArgument {
+                        description: None,
+                        name,
+                        required: Some(true),
+                        title: None,
+                    })
+                    .collect();
+                arguments.push(PromptArgument {
+                    description: command
+                        .argument_hint
+                        .clone()
+                        .or_else(|| Some("Text for $ARGUMENTS and $1..$9".to_string())),
+                    name: "ARGUMENTS".to_string(),
+                    required: Some(false),
+                    title: None,
+                });
+                Prompt {
+                    arguments: Some(arguments),
+                    description: command.description,
+                    name: command.name,
+                    title: None,
+                }
+            })
+            .collect();
+        let result = ListPromptsResult {
+            next_cursor: None,
+            prompts,
+        };
+        self.send_response::<mcp_types::ListPromptsRequest>(id, result)
+            .await;
     }
 
-    fn handle_get_prompt(
+    async fn handle_get_prompt(
         &self,
+        id: RequestId,
         params: <mcp_types::GetPromptRequest as mcp_types::ModelContextProtocolRequest>::Params,
     ) {
         tracing::info!("prompts/get -> params: {:?}", params);
+        let Some(command) = self
+            .custom_commands()
+            .await
+            .into_iter()
+            .find(|command| command.name == params.name)
+        else {
+            let error = JSONRPCErrorError {
+                code: INVALID_PARAMS_ERROR_CODE,
+                message: format!("unknown prompt: {}", params.name),
+                data: None,
+            };
+            self.outgoing.send_error(id, error).await;
+            return;
+        };
+        let args: HashMap<String, String> = match params.arguments {
+            None => HashMap::new(),
+            Some(arguments) => match serde_json::from_value(arguments) {
+                Ok(args) => args,
+                Err(err) => {
+                    let error = JSONRPCErrorError {
+                        code: INVALID_PARAMS_ERROR_CODE,
+                        message: format!("prompt arguments must be strings: {err}"),
+                        data: None,
+                    };
+                    self.outgoing.send_error(id, error).await;
+                    return;
+                }
+            },
+        };
+        let missing: Vec<String> = prompt_argument_names(&command.content)
+            .into_iter()
+            .filter(|name| !args.contains_key(name))
+            .collect();
+        if !missing.is_empty() {
+            let error = JSONRPCErrorError {
+                code: INVALID_PARAMS_ERROR_CODE,
+                message: format!("missing prompt arguments: {}", missing.join(", ")),
+                data: None,
+            };
+            self.outgoing.send_error(id, error).await;
+            return;
+        }
+        let result = GetPromptResult {
+            description: command.description,
+            messages: vec![PromptMessage {
+                content: ContentBlock::TextContent(TextContent {
+                    annotations: None,
+                    text: expand_prompt_arguments(&command.content, &args),
+                    r#type: "text".to_string(),
+                }),
+                role: Role::User,
+            }],
+        };
+        self.send_response::<mcp_types::GetPromptRequest>(id, result)
+            .await;
     }
 
     async fn handle_list_tools(
@@ -309,6 +440,7 @@ impl MessageProcessor {
             tools: vec![
                 create_tool_for_codex_tool_call_param(),
                 create_tool_for_codex_tool_call_reply_param(),
+                create_tool_for_codex_sessions_param(),
             ],
             next_cursor: None,
         };
@@ -331,6 +463,7 @@ impl MessageProcessor {
                 self.handle_tool_call_codex_session_reply(id, arguments)
                     .await
             }
+            "codex-sessions" => self.handle_tool_call_codex_sessions(id, arguments).await,
             _ => {
                 let result = CallToolResult {
                     content: vec![ContentBlock::TextContent(TextContent {
@@ -496,16 +629,20 @@ impl MessageProcessor {
 
         let codex = match self.thread_manager.get_thread(thread_id).await {
             Ok(c) => c,
-            Err(_) => {
-                tracing::warn!("Session not found for thread_id: {thread_id}");
-                let result = crate::codex_tool_runner::create_call_tool_result_with_thread_id(
-                    thread_id,
-                    format!("Session not found for thread_id: {thread_id}"),
-                    Some(true),
-                );
-                outgoing.send_response(request_id, result).await;
-                return;
-            }
+            // Not running in this server: resume it from its recording.
+            Err(_) => match self.resume_recorded_thread(&request_id, thread_id).await {
+                Ok(c) => c,
+                Err(err) => {
+                    tracing::warn!("Session not found for thread_id: {thread_id}: {err:#}");
+                    let result = crate::codex_tool_runner::create_call_tool_result_with_thread_id(
+                        thread_id,
+                        format!("Session not found for thread_id: {thread_id}"),
+                        Some(true),
+                    );
+                    outgoing.send_response(request_id, result).await;
+                    return;
+                }
+            },
         };
 
         // Spawn the long-running reply handler.
@@ -528,6 +665,84 @@ impl MessageProcessor {
         });
     }
 
+    /// Load a recorded session so `codex-reply` can continue it, and tell the
+    /// client about it the same way a new `codex` session does.
+    async fn resume_recorded_thread(
+        &self,
+        request_id: &RequestId,
+        thread_id: ThreadId,
+    ) -> anyhow::Result<Arc<CodexThread>> {
+        let Some(path) =
+            find_thread_path_by_id_str(&self.config.codex_home, &thread_id.to_string()).await?
+        else {
+            anyhow::bail!("no recorded session for thread id {thread_id}");
+        };
+        let config =
+            config_for_recorded_session(&self.config, &path, self.codex_linux_sandbox_exe.clone())
+                .await?;
+        let NewThread {
+            thread,
+            session_configured,
+            ..
+        } = self
+            .thread_manager
+            .resume_thread_from_rollout(config, path, self.auth_manager.clone())
+            .await?;
+        let event = Event {
+            id: String::new(),
+            msg: EventMsg::SessionConfigured(session_configured),
+        };
+        self.outgoing
+            .send_event_as_notification(
+                &event,
+                Some(OutgoingNotificationMeta {
+                    request_id: Some(request_id.clone()),
+                    thread_id: Some(thread_id),
+                }),
+            )
+            .await;
+        Ok(thread)
+    }
+
+    async fn handle_tool_call_codex_sessions(
+        &self,
+        id: RequestId,
+        arguments: Option<serde_json::Value>,
+    ) {
+        let params = match arguments.map(serde_json::from_value::<CodexSessionsParam>) {
+            None => CodexSessionsParam::default(),
+            Some(Ok(params)) => params,
+            Some(Err(e)) => {
+                let result = CallToolResult {
+                    content: vec![ContentBlock::TextContent(TextContent {
+                        r#type: "text".to_owned(),
+                        text: format!("Failed to parse codex-sessions arguments: {e}"),
+                        annotations: None,
+                    })],
+                    is_error: Some(true),
+                    structured_content: None,
+                };
+                self.send_response::<mcp_types::CallToolRequest>(id, result)
+                    .await;
+                return;
+            }
+        };
+        let result = match list_codex_sessions(&self.config, params.limit).await {
+            Ok(result) => result,
+            Err(e) => CallToolResult {
+                content: vec![ContentBlock::TextContent(TextContent {
+                    r#type: "text".to_owned(),
+                    text: format!("Failed to list Codex sessions: {e}"),
+                    annotations: None,
+                })],
+                is_error: Some(true),
+                structured_content: None,
+            },
+        };
+        self.send_response::<mcp_types::CallToolRequest>(id, result)
+            .await;
+    }
+
     fn handle_set_level(
         &self,
         params: <mcp_types::SetLevelRequest as mcp_types::ModelContextProtocolRequest>::Params,
@@ -152,6 +152,7 @@ impl McpProcess {
                 id: RequestId::Integer(request_id),
                 result: json!({
                     "capabilities": {
+                        "prompts": {},
                         "tools": {
                             "listChanged": true
                         },
@@ -79,6 +79,55 @@ fn ipv4_in_cidr(ip: Ipv4Addr, base: [u8; 4], prefix: u8) -> bool {
     (ip & mask) == (base & mask)
 }
 
+/// An IP network from a CIDR policy entry such as `10.0.0.0/8`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct IpNetwork {
+    addr: IpAddr,
+    prefix: u8,
+}
+
+impl IpNetwork {
+    /// Parses `addr/prefix`; returns `None` for anything else, including domain patterns.
+    pub(crate) fn parse(pattern: &str) -> Option<Self> {
+        let (addr, prefix) = pattern.trim().split_once('/')?;
+        let addr: IpAddr = addr.parse().ok()?;
+        let prefix: u8 = prefix.parse().ok()?;
+        let max_prefix = if addr.is_ipv4() { 32 } else { 128 };
+        (prefix <= max_prefix).then_some(Self { addr, prefix })
+    }
+
+    pub(crate) fn contains(self, ip: IpAddr) -> bool {
+        match (self.addr, ip) {
+            (IpAddr::V4(base), IpAddr::V4(ip)) => ipv4_in_cidr(ip, base.octets(), self.prefix),
+            (IpAddr::V4(base), IpAddr::V6(ip)) => ip
+                .to_ipv4_mapped()
+                .is_some_and(|ip| ipv4_in_cidr(ip, base.octets(), self.prefix)),
+            (IpAddr::V6(base), IpAddr::V6(ip)) => {
+                let mask = if self.prefix == 0 {
+                    0
+                } else {
+                    u128::MAX << (128 - self.prefix)
+                };
+                (u128::from(ip) & mask) == (u128::from(base) & mask)
+            }
+            (IpAddr::V6(_), IpAddr::V4(_)) => false,
+        }
+    }
+}
+
+/// Returns true if `host` is an IP literal inside one of the CIDR entries in `patterns`.
+pub(crate) fn host_in_networks(patterns: &[String], host: &Host) -> bool {
+    let host = host.as_str();
+    let host = host.split_once('%').map(|(ip, _)| ip).unwrap_or(host);
+    let Ok(ip) = host.parse::<IpAddr>() else {
+        return false;
+    };
+    patterns
+        .iter()
+        .filter_map(|pattern| IpNetwork::parse(pattern))
+        .any(|network| network.contains(ip))
+}
+
 fn is_non_public_ipv6(ip: Ipv6Addr) -> bool {
     if let Some(v4) = ip.to_ipv4() {
         return is_non_public_ipv4(v4) || ip.is_loopback();
@@ -148,6 +197,10 @@ pub(crate) fn compile_globset(patterns: &[String]) -> Result<GlobSet> {
     let mut builder = GlobSetBuilder::new();
     let mut seen = HashSet::new();
     for pattern in patterns {
+        // CIDR entries are matched against IP literals by `host_in_networks`.
+        if IpNetwork::parse(pattern).is_some() {
+            continue;
+        }
         let pattern = normalize_pattern(pattern);
         // Supported domain patterns:
         // - "example.com": match the exact host
@@ -362,6 +415,21 @@ mod tests {
         assert_eq!(true, set.is_match("::1"));
     }
 
+    #[test]
+    fn ip_network_parses_cidr_entries_only() {
+        let network = IpNetwork::parse("10.0.0.0/8").unwrap();
+        assert!(network.contains("10.200.1.1".parse().unwrap()));
+        assert!(network.contains("::ffff:10.0.0.1".parse().unwrap()));
+        assert!(!network.contains("11.0.0.1".parse().unwrap()));
+
+        let network = IpNetwork::parse("fd00::/8").unwrap();
+        assert!(network.contains("fd12::1".parse().unwrap()));
+        assert!(!network.contains("fe80::1".parse().unwrap()));
+
+        assert_eq!(IpNetwork::parse("example.com"), None);
+        assert_eq!(IpNetwork::parse("10.0.0.0/33"), None);
+    }
+
     #[test]
     fn is_loopback_host_handles_localhost_variants() {
         assert!(is_loopback_host(&Host::parse("localhost").unwrap()));
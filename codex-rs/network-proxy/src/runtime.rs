@@ -1,6 +1,7 @@
 use crate::config::NetworkMode;
 use crate::config::NetworkProxyConfig;
 use crate::policy::Host;
+use crate::policy::host_in_networks;
 use crate::policy::is_loopback_host;
 use crate::policy::is_non_public_ip;
 use crate::policy::normalize_host;
@@ -204,7 +205,14 @@ impl NetworkProxyState {
             Ok(host) => host,
             Err(_) => return Ok(HostBlockDecision::Blocked(HostBlockReason::NotAllowed)),
         };
-        let (deny_set, allow_set, allow_local_binding, allowed_domains_empty, allowed_domains) = {
+        let (
+            deny_set,
+            allow_set,
+            allow_local_binding,
+            allowed_domains_empty,
+            allowed_domains,
+            denied_domains,
+        ) = {
             let guard = self.state.read().await;
             (
                 guard.deny_set.clone(),
@@ -212,6 +220,7 @@ impl NetworkProxyState {
                 guard.config.network_proxy.policy.allow_local_binding,
                 guard.config.network_proxy.policy.allowed_domains.is_empty(),
                 guard.config.network_proxy.policy.allowed_domains.clone(),
+                guard.config.network_proxy.policy.denied_domains.clone(),
             )
         };
 
@@ -221,11 +230,12 @@ impl NetworkProxyState {
         //  1) explicit deny always wins
         //  2) local/private networking is opt-in (defense-in-depth)
         //  3) allowlist is enforced when configured
-        if deny_set.is_match(host_str) {
+        if deny_set.is_match(host_str) || host_in_networks(&denied_domains, &host) {
             return Ok(HostBlockDecision::Blocked(HostBlockReason::Denied));
         }
 
-        let is_allowlisted = allow_set.is_match(host_str);
+        let is_allowlisted =
+            allow_set.is_match(host_str) || host_in_networks(&allowed_domains, &host);
         if !allow_local_binding {
             // If the intent is "prevent access to local/internal networks", we must not rely solely
             // on string checks like `localhost` / `127.0.0.1`. Attackers can use DNS rebinding or
@@ -464,6 +474,9 @@ fn log_domain_list_changes(list_name: &str, previous: &[String], next: &[String]
 }
 
 fn is_explicit_local_allowlisted(allowed_domains: &[String], host: &Host) -> bool {
+    if host_in_networks(allowed_domains, host) {
+        return true;
+    }
     let normalized_host = host.as_str();
     allowed_domains.iter().any(|pattern| {
         let pattern = pattern.trim();
@@ -559,6 +572,28 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn host_blocked_matches_ip_literals_against_cidr_entries() {
+        let state = network_proxy_state_for_policy(NetworkPolicy {
+            allowed_domains: vec!["10.0.0.0/8".to_string()],
+            denied_domains: vec!["10.9.0.0/16".to_string()],
+            ..NetworkPolicy::default()
+        });
+
+        assert_eq!(
+            state.host_blocked("10.1.2.3", 443).await.unwrap(),
+            HostBlockDecision::Allowed
+        );
+        assert_eq!(
+            state.host_blocked("10.9.0.1", 443).await.unwrap(),
+            HostBlockDecision::Blocked(HostBlockReason::Denied)
+        );
+        assert_eq!(
+            state.host_blocked("8.8.8.8", 443).await.unwrap(),
+            HostBlockDecision::Blocked(HostBlockReason::NotAllowed)
+        );
+    }
+
     #[tokio::test]
     async fn host_blocked_subdomain_wildcards_exclude_apex() {
         let state = network_proxy_state_for_policy(NetworkPolicy {
@@ -18,6 +18,49 @@ use codex_core::config::Config;
 
 const OLLAMA_CONNECTION_ERROR: &str = "No running Ollama server detected. Start it with: `ollama serve` (after installing). Install instructions: https://github.com/ollama/ollama?tab=readme-ov-file#ollama";
 
+/// Model metadata reported by `/api/show`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OllamaModelDetails {
+    /// Context length the model was trained with (`<arch>.context_length`).
+    pub context_length: Option<i64>,
+    /// Capabilities such as `completion`, `tools`, `thinking`, or `vision`.
+    /// Empty when the server is too old to report them.
+    pub capabilities: Vec<String>,
+}
+
+impl OllamaModelDetails {
+    fn from_show_response(val: &JsonValue) -> Self {
+        let context_length = val
+            .get("model_info")
+            .and_then(|info| info.as_object())
+            .and_then(|info| {
+                info.iter()
+                    .find(|(key, _)| key.ends_with(".context_length"))
+                    .and_then(|(_, value)| value.as_i64())
+            });
+        let capabilities = val
+            .get("capabilities")
+            .and_then(|c| c.as_array())
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|v| v.as_str())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            context_length,
+            capabilities,
+        }
+    }
+
+    /// Whether the model reports `capability`. Servers that predate
+    /// capability reporting are assumed to support everything.
+    pub fn supports(&self, capability: &str) -> bool {
+        self.capabilities.is_empty() || self.capabilities.iter().any(|c| c == capability)
+    }
+}
+
 /// Client for interacting with a local Ollama instance.
 pub struct OllamaClient {
     client: reqwest::Client,
@@ -54,7 +97,7 @@ impl OllamaClient {
     }
 
     /// Build a client from a provider definition and verify the server is reachable.
-    pub(crate) async fn try_from_provider(provider: &ModelProviderInfo) -> io::Result<Self> {
+    pub async fn try_from_provider(provider: &ModelProviderInfo) -> io::Result<Self> {
         #![expect(clippy::expect_used)]
         let base_url = provider
             .base_url
@@ -126,6 +169,24 @@ impl OllamaClient {
         Ok(names)
     }
 
+    /// Fetch metadata for `model` from `/api/show`, returning `None` when the
+    /// server does not know the model.
+    pub async fn fetch_model_details(&self, model: &str) -> io::Result<Option<OllamaModelDetails>> {
+        let show_url = format!("{}/api/show", self.host_root.trim_end_matches('/'));
+        let resp = self
+            .client
+            .post(show_url)
+            .json(&serde_json::json!({ "model": model }))
+            .send()
+            .await
+            .map_err(io::Error::other)?;
+        if !resp.status().is_success() {
+            return Ok(None);
+        }
+        let val = resp.json::<JsonValue>().await.map_err(io::Error::other)?;
+        Ok(Some(OllamaModelDetails::from_show_response(&val)))
+    }
+
     /// Query the server for its version string, returning `None` when unavailable.
     pub async fn fetch_version(&self) -> io::Result<Option<Version>> {
         let version_url = format!("{}/api/version", self.host_root.trim_end_matches('/'));
@@ -333,6 +394,46 @@ mod tests {
         assert_eq!(version, Some(Version::new(0, 14, 1)));
     }
 
+    #[tokio::test]
+    async fn test_fetch_model_details() {
+        if std::env::var(codex_core::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR).is_ok() {
+            tracing::info!(
+                "{} is set; skipping test_fetch_model_details",
+                codex_core::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR
+            );
+            return;
+        }
+
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/api/show"))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200).set_body_raw(
+                    serde_json::json!({
+                        "model_info": {
+                            "general.architecture": "qwen3",
+                            "qwen3.context_length": 40960
+                        },
+                        "capabilities": ["completion", "thinking"]
+                    })
+                    .to_string(),
+                    "application/json",
+                ),
+            )
+            .mount(&server)
+            .await;
+
+        let client = OllamaClient::from_host_root(server.uri());
+        let details = client
+            .fetch_model_details("qwen3:8b")
+            .await
+            .expect("show")
+            .expect("details");
+        assert_eq!(details.context_length, Some(40960));
+        assert!(details.supports("thinking"));
+        assert!(!details.supports("tools"));
+    }
+
     #[tokio::test]
     async fn test_probe_server_happy_path_openai_compat_and_native() {
         if std::env::var(codex_core::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR).is_ok() {
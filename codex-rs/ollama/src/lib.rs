@@ -4,6 +4,7 @@ mod pull;
 mod url;
 
 pub use client::OllamaClient;
+pub use client::OllamaModelDetails;
 use codex_core::ModelProviderInfo;
 use codex_core::WireApi;
 use codex_core::config::Config;
@@ -21,24 +22,27 @@ pub struct WireApiDetection {
     pub version: Option<Version>,
 }
 
-/// Prepare the local OSS environment when `--oss` is selected.
+/// Prepare a local Ollama model for the session.
 ///
-/// - Ensures a local Ollama server is reachable.
-/// - Checks if the model exists locally and pulls it if missing.
-pub async fn ensure_oss_ready(config: &Config) -> std::io::Result<()> {
-    // Only download when the requested model is the default OSS model (or when -m is not provided).
-    let model = match config.model.as_ref() {
-        Some(model) => model,
-        None => DEFAULT_OSS_MODEL,
+/// - Ensures the Ollama server behind the selected provider is reachable.
+/// - Pulls the model on first use when it is not present locally.
+/// - Returns the model's `/api/show` metadata so callers can adjust the
+///   session with [`apply_model_details`].
+pub async fn ensure_oss_ready(config: &Config) -> std::io::Result<Option<OllamaModelDetails>> {
+    let model = config.model.as_deref().unwrap_or(DEFAULT_OSS_MODEL);
+
+    // Verify local Ollama is reachable. Prefer the selected provider so a
+    // custom `base_url` for `ollama`/`ollama-chat` is honored.
+    let ollama_client = if config.model_provider.base_url.is_some() {
+        crate::OllamaClient::try_from_provider(&config.model_provider).await?
+    } else {
+        crate::OllamaClient::try_from_oss_provider(config).await?
     };
 
-    // Verify local Ollama is reachable.
-    let ollama_client = crate::OllamaClient::try_from_oss_provider(config).await?;
-
     // If the model is not present locally, pull it.
     match ollama_client.fetch_models().await {
         Ok(models) => {
-            if !models.iter().any(|m| m == model) {
+            if !is_model_available(&models, model) {
                 let mut reporter = crate::CliProgressReporter::new();
                 ollama_client
                     .pull_with_reporter(model, &mut reporter)
@@ -51,7 +55,56 @@ pub async fn ensure_oss_ready(config: &Config) -> std::io::Result<()> {
         }
     }
 
-    Ok(())
+    match ollama_client.fetch_model_details(model).await {
+        Ok(details) => Ok(details),
+        Err(err) => {
+            tracing::warn!("Failed to query Ollama model details for {model}: {err}");
+            Ok(None)
+        }
+    }
+}
+
+/// Adjust `config` to what the local model supports, leaving anything set
+/// explicitly in config untouched. Returns warnings to show the user.
+pub fn apply_model_details(config: &mut Config, details: &OllamaModelDetails) -> Vec<String> {
+    // Without an explicit model, use the default OSS model that
+    // `ensure_oss_ready` pulled rather than a hosted default.
+    let model = config
+        .model
+        .get_or_insert_with(|| DEFAULT_OSS_MODEL.to_string())
+        .clone();
+    let mut warnings = Vec::new();
+
+    if config.model_context_window.is_none() {
+        config.model_context_window = details.context_length;
+    }
+    // Local models are not tuned to emit several tool calls in one turn, so
+    // keep them sequential unless the user opts in.
+    if config.model_supports_parallel_tool_calls.is_none() {
+        config.model_supports_parallel_tool_calls = Some(false);
+    }
+    if !details.supports("thinking") {
+        // Ollama rejects reasoning options for models that cannot think.
+        config.model_reasoning_effort = None;
+        if config.model_supports_reasoning_summaries.is_none() {
+            config.model_supports_reasoning_summaries = Some(false);
+        }
+    }
+    if !details.supports("tools") {
+        warnings.push(format!(
+            "{model} does not support tool calling in Ollama, so Codex cannot run commands or edit files with it. Choose a model whose `ollama show` output lists the `tools` capability."
+        ));
+    }
+
+    warnings
+}
+
+/// Whether `model` is among the locally installed `models`. Ollama reports
+/// untagged models with an explicit `:latest` tag.
+fn is_model_available(models: &[String], model: &str) -> bool {
+    models
+        .iter()
+        .any(|m| m == model || (!model.contains(':') && *m == format!("{model}:latest")))
 }
 
 fn min_responses_version() -> Version {
@@ -90,6 +143,15 @@ mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
 
+    #[test]
+    fn test_is_model_available_matches_implicit_latest_tag() {
+        let models = vec!["llama3.2:latest".to_string(), "gpt-oss:20b".to_string()];
+        assert!(is_model_available(&models, "llama3.2"));
+        assert!(is_model_available(&models, "gpt-oss:20b"));
+        assert!(!is_model_available(&models, "gpt-oss"));
+        assert!(!is_model_available(&models, "llama3.2:3b"));
+    }
+
     #[test]
     fn test_wire_api_for_version_dev_zero_keeps_responses() {
         assert_eq!(
@@ -666,6 +666,7 @@ impl OtelManager {
             ResponseEvent::ServerReasoningIncluded(_) => "server_reasoning_included".into(),
             ResponseEvent::RateLimits(_) => "rate_limits".into(),
             ResponseEvent::ModelsEtag(_) => "models_etag".into(),
+            ResponseEvent::ServedModel(_) => "served_model".into(),
         }
     }
 
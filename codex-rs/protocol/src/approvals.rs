@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::fmt;
 use std::path::PathBuf;
 
 use crate::parse_command::ParsedCommand;
@@ -57,6 +58,11 @@ pub struct ExecApprovalRequestEvent {
     #[ts(optional)]
     pub proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
     pub parsed_cmd: Vec<ParsedCommand>,
+    /// Set when the request is for a file operation rather than a command,
+    /// such as a read outside the workspace under `file_approvals`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub file_operation: Option<FileOperation>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
@@ -92,4 +98,71 @@ pub struct ApplyPatchApprovalRequestEvent {
     /// When set, the agent is asking the user to allow writes under this root for the remainder of the session.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub grant_root: Option<PathBuf>,
+    /// Dry-run result for each hunk, so clients can flag hunks that only
+    /// matched fuzzily or that conflict before the user approves.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hunks: Vec<PatchHunkReport>,
+    /// Classes of file operation the patch performs, so clients can tell a
+    /// deletion from a write without inspecting `changes`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub file_operations: Vec<FileOperation>,
+}
+
+/// Class of file operation an approval request covers. These match the
+/// `file_approvals` policies in config.toml.
+#[derive(
+    Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Hash, PartialOrd, Ord, JsonSchema, TS,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum FileOperation {
+    /// Reading a file or directory outside the session's working directory.
+    Read,
+    /// Creating or modifying a file.
+    Write,
+    /// Deleting a file, including the source of a move.
+    Delete,
+}
+
+impl fmt::Display for FileOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            FileOperation::Read => "read",
+            FileOperation::Write => "write",
+            FileOperation::Delete => "delete",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Dry-run result for one hunk of a proposed patch.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct PatchHunkReport {
+    pub path: PathBuf,
+    /// 1-based position of the hunk among the hunks for `path`.
+    pub hunk: u32,
+    /// 1-based line of the original file where the hunk applies, if it does.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+    pub status: PatchHunkStatus,
+    /// How a fuzzy hunk matched, or why a hunk conflicts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// Identifies one hunk of a proposed patch, numbered as in [`PatchHunkReport`].
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash, JsonSchema, TS)]
+pub struct PatchHunkId {
+    pub path: PathBuf,
+    /// 1-based position of the hunk among the hunks for `path`.
+    pub hunk: u32,
+}
+
+/// Whether a hunk applies verbatim (`clean`), only after relaxing whitespace
+/// or punctuation (`fuzzy`), or not at all (`conflict`).
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum PatchHunkStatus {
+    Clean,
+    Fuzzy,
+    Conflict,
 }
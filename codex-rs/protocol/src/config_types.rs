@@ -50,7 +50,7 @@ pub enum Verbosity {
 }
 
 #[derive(
-    Deserialize, Debug, Clone, Copy, PartialEq, Default, Serialize, Display, JsonSchema, TS,
+    Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Display, JsonSchema, TS,
 )]
 #[serde(rename_all = "kebab-case")]
 #[strum(serialize_all = "kebab-case")]
@@ -127,6 +127,7 @@ pub enum ForcedLoginMethod {
 #[strum(serialize_all = "lowercase")]
 pub enum TrustLevel {
     Trusted,
+    Limited,
     Untrusted,
 }
 
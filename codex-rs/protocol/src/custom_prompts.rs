@@ -17,4 +17,19 @@ pub struct CustomPrompt {
     pub content: String,
     pub description: Option<String>,
     pub argument_hint: Option<String>,
+    /// Loaded from `$CODEX_HOME/commands` and invoked as `/name` rather than
+    /// `/prompts:name`.
+    #[serde(default)]
+    pub command: bool,
+}
+
+impl CustomPrompt {
+    /// Name typed after the `/` to invoke this prompt.
+    pub fn slash_name(&self) -> String {
+        if self.command {
+            self.name.clone()
+        } else {
+            format!("{PROMPTS_CMD_PREFIX}:{}", self.name)
+        }
+    }
 }
@@ -731,6 +731,18 @@ pub struct ShellToolCallParams {
     pub prefix_rule: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub justification: Option<String>,
+    /// Keep at most this many bytes of the command's output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub max_output_bytes: Option<usize>,
+    /// CPU time the command may use, in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub cpu_seconds: Option<u64>,
+    /// Memory the command may use, in megabytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub memory_mb: Option<u64>,
 }
 
 /// If the `name` of a `ResponseItem::FunctionCall` is `shell_command`, the
@@ -754,6 +766,18 @@ pub struct ShellCommandToolCallParams {
     pub prefix_rule: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub justification: Option<String>,
+    /// Keep at most this many bytes of the command's output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub max_output_bytes: Option<usize>,
+    /// CPU time the command may use, in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub cpu_seconds: Option<u64>,
+    /// Memory the command may use, in megabytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub memory_mb: Option<u64>,
 }
 
 /// Responses API compatible content items that can be returned by a tool call.
@@ -1316,6 +1340,9 @@ mod tests {
                 sandbox_permissions: None,
                 prefix_rule: None,
                 justification: None,
+                max_output_bytes: None,
+                cpu_seconds: None,
+                memory_mb: None,
             },
             params
         );
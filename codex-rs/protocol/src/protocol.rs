@@ -17,6 +17,7 @@ use crate::config_types::CollaborationMode;
 use crate::config_types::ModeKind;
 use crate::config_types::Personality;
 use crate::config_types::ReasoningSummary as ReasoningSummaryConfig;
+use crate::config_types::Verbosity;
 use crate::config_types::WindowsSandboxLevel;
 use crate::custom_prompts::CustomPrompt;
 use crate::dynamic_tools::DynamicToolCallRequest;
@@ -36,6 +37,7 @@ use crate::request_user_input::RequestUserInputResponse;
 use crate::user_input::UserInput;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use mcp_types::CallToolResult;
+use mcp_types::Prompt as McpPrompt;
 use mcp_types::RequestId;
 use mcp_types::Resource as McpResource;
 use mcp_types::ResourceTemplate as McpResourceTemplate;
@@ -53,6 +55,10 @@ pub use crate::approvals::ApplyPatchApprovalRequestEvent;
 pub use crate::approvals::ElicitationAction;
 pub use crate::approvals::ExecApprovalRequestEvent;
 pub use crate::approvals::ExecPolicyAmendment;
+pub use crate::approvals::FileOperation;
+pub use crate::approvals::PatchHunkId;
+pub use crate::approvals::PatchHunkReport;
+pub use crate::approvals::PatchHunkStatus;
 pub use crate::request_user_input::RequestUserInputEvent;
 
 /// Open/close tags for special user-input blocks. Used across crates to avoid
@@ -189,6 +195,10 @@ pub enum Op {
         /// Updated personality preference.
         #[serde(skip_serializing_if = "Option::is_none")]
         personality: Option<Personality>,
+
+        /// Updated output verbosity (honored only for models that support it).
+        #[serde(skip_serializing_if = "Option::is_none")]
+        verbosity: Option<Verbosity>,
     },
 
     /// Approve a command execution
@@ -253,6 +263,15 @@ pub enum Op {
     /// Request MCP servers to reinitialize and refresh cached tool lists.
     RefreshMcpServers { config: McpServerRefreshConfig },
 
+    /// Log in to an MCP server with OAuth, opening the authorization page in
+    /// the browser. The servers are reloaded before the next turn on success.
+    /// Reply is delivered via `EventMsg::McpLoginCompleted`.
+    McpLogin { name: String },
+
+    /// Request the lifecycle state of each running MCP server.
+    /// Reply is delivered via `EventMsg::McpServerStatusResponse`.
+    McpServerStatus,
+
     /// Request the list of available custom prompts.
     ListCustomPrompts,
 
@@ -288,6 +307,36 @@ pub enum Op {
     /// responsible for undoing any edits on disk.
     ThreadRollback { num_turns: u32 },
 
+    /// Like `ThreadRollback`, but also restores the workspace to the snapshot
+    /// taken when the earliest dropped turn started. Requires the `undo`
+    /// feature, which records those snapshots. Replies with
+    /// `EventMsg::ThreadRolledBack`, or an error with
+    /// `CodexErrorInfo::ThreadRollbackFailed` if nothing was changed.
+    Rewind { num_turns: u32 },
+
+    /// Report what `edit` would remove from the model's context and how many
+    /// tokens it would save, without changing anything. Reply is delivered
+    /// via `EventMsg::ContextEditPreview`.
+    PreviewContextEdit { edit: ContextEdit },
+
+    /// Apply `edit` to the model's context. `ContextEdit::Compact` behaves
+    /// like `Op::Compact`; the other edits reply with `EventMsg::ContextEdited`.
+    /// Like `ThreadRollback`, this leaves the transcript and files untouched.
+    ApplyContextEdit { edit: ContextEdit },
+
+    /// Pin a file or message into the model's context for the rest of the
+    /// session. Pins are re-sent with every request and survive compaction.
+    /// Reply is delivered via `EventMsg::PinnedContextUpdated`.
+    Pin { target: PinTarget },
+
+    /// Remove the pin with `id`, or every pin when `id` is `None`. Reply is
+    /// delivered via `EventMsg::PinnedContextUpdated`.
+    Unpin { id: Option<u32> },
+
+    /// Request the current pins. Reply is delivered via
+    /// `EventMsg::PinnedContextUpdated`.
+    ListPins,
+
     /// Request a code review from the agent.
     Review { review_request: ReviewRequest },
 
@@ -306,6 +355,49 @@ pub enum Op {
 
     /// Request the list of available models.
     ListModels,
+
+    /// Request the files currently held in the session trash.
+    /// Reply is delivered via `EventMsg::ListTrashResponse`.
+    ListTrash,
+
+    /// Restore files from the session trash to their original locations.
+    /// When `paths` is empty every trashed file is restored. Reply is
+    /// delivered via `EventMsg::ListTrashResponse`.
+    RestoreTrash {
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        paths: Vec<PathBuf>,
+    },
+
+    /// Permanently delete every file held in the session trash.
+    /// Reply is delivered via `EventMsg::ListTrashResponse`.
+    PurgeTrash,
+
+    /// Hand a running `exec_command` terminal to the user so they can answer
+    /// prompts the agent cannot, such as passwords. Until
+    /// `Op::ReleaseTerminal`, the model may poll the terminal but not write
+    /// to it.
+    TakeOverTerminal { process_id: String },
+
+    /// Raw input the user typed into a terminal taken over with
+    /// `Op::TakeOverTerminal`.
+    TerminalInput { process_id: String, data: String },
+
+    /// Return a terminal taken over with `Op::TakeOverTerminal` to the
+    /// agent, which is told the user interacted with it.
+    ReleaseTerminal { process_id: String },
+
+    /// List the session's background jobs.
+    /// Reply is delivered via `EventMsg::ListJobsResponse`.
+    ListJobs,
+
+    /// Start `command` in the user's shell as a background job. Like a
+    /// command the user runs with `!`, it runs outside the sandbox. Reply is
+    /// delivered via `EventMsg::ListJobsResponse`.
+    StartJob { command: String },
+
+    /// Stop a background job. Reply is delivered via
+    /// `EventMsg::ListJobsResponse`.
+    StopJob { job_id: String },
 }
 
 /// Determines the conditions under which the user is consulted to approve
@@ -695,12 +787,25 @@ pub enum EventMsg {
     /// indicates the turn continued but the user should still be notified.
     Warning(WarningEvent),
 
+    /// The provider refused the request or a safety system blocked the
+    /// response, ending the turn without an assistant message.
+    TurnBlocked(TurnBlockedEvent),
+
     /// Conversation history was compacted (either automatically or manually).
     ContextCompacted(ContextCompactedEvent),
 
     /// Conversation history was rolled back by dropping the last N user turns.
     ThreadRolledBack(ThreadRolledBackEvent),
 
+    /// Preview of a context edit, in response to `Op::PreviewContextEdit`.
+    ContextEditPreview(ContextEditPreviewEvent),
+
+    /// User turns were removed from the model's context by `Op::ApplyContextEdit`.
+    ContextEdited(ContextEditedEvent),
+
+    /// The pinned context changed, or was requested with `Op::ListPins`.
+    PinnedContextUpdated(PinnedContextEvent),
+
     /// Agent has started a turn.
     /// v1 wire format uses `task_started`; accept `turn_started` for v2 interop.
     #[serde(rename = "task_started", alias = "turn_started")]
@@ -715,6 +820,12 @@ pub enum EventMsg {
     /// Optional means unknown — UIs should not display when `None`.
     TokenCount(TokenCountEvent),
 
+    /// The exact model snapshot the provider served for the current turn.
+    ModelSnapshot(ModelSnapshotEvent),
+
+    /// The turn moved to a fallback provider after the previous one kept failing.
+    ModelFailover(ModelFailoverEvent),
+
     /// Agent text output message
     AgentMessage(AgentMessageEvent),
 
@@ -752,6 +863,9 @@ pub enum EventMsg {
 
     McpToolCallBegin(McpToolCallBeginEvent),
 
+    /// Progress notification from the MCP server for an in-flight tool call.
+    McpToolCallProgress(McpToolCallProgressEvent),
+
     McpToolCallEnd(McpToolCallEndEvent),
 
     WebSearchBegin(WebSearchBeginEvent),
@@ -772,6 +886,9 @@ pub enum EventMsg {
     /// Notification that the agent attached a local image via the view_image tool.
     ViewImageToolCall(ViewImageToolCallEvent),
 
+    /// Request for the host editor to open a file, sent by the open_file tool.
+    OpenFileRequest(OpenFileRequestEvent),
+
     ExecApprovalRequest(ExecApprovalRequestEvent),
 
     RequestUserInput(RequestUserInputEvent),
@@ -792,6 +909,10 @@ pub enum EventMsg {
 
     UndoCompleted(UndoCompletedEvent),
 
+    /// A checkpoint (ghost snapshot) of the workspace was taken at the start
+    /// of the turn.
+    CheckpointCreated(CheckpointCreatedEvent),
+
     /// Notification that a model stream experienced an error or disconnect
     /// and the system is handling it (e.g., retrying with backoff).
     StreamError(StreamErrorEvent),
@@ -811,12 +932,24 @@ pub enum EventMsg {
     /// List of MCP tools available to the agent.
     McpListToolsResponse(McpListToolsResponseEvent),
 
+    /// Result of `Op::McpLogin`.
+    McpLoginCompleted(McpLoginCompletedEvent),
+
+    /// Lifecycle state of each MCP server, in reply to `Op::McpServerStatus`.
+    McpServerStatusResponse(McpServerStatusResponseEvent),
+
     /// List of custom prompts available to the agent.
     ListCustomPromptsResponse(ListCustomPromptsResponseEvent),
 
     /// List of skills available to the agent.
     ListSkillsResponse(ListSkillsResponseEvent),
 
+    /// Files currently held in the session trash.
+    ListTrashResponse(ListTrashResponseEvent),
+
+    /// Background jobs running in this session.
+    ListJobsResponse(ListJobsResponseEvent),
+
     /// Notification that skill data may have been updated and clients may want to reload.
     SkillsUpdateAvailable,
 
@@ -1105,6 +1238,36 @@ pub struct WarningEvent {
     pub message: String,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct TurnBlockedEvent {
+    /// Why the response was blocked, e.g. `refusal` or `content_filter`.
+    pub category: String,
+    /// Explanation supplied by the provider.
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ModelSnapshotEvent {
+    /// Model name the turn requested, e.g. `gpt-5`.
+    pub requested_model: String,
+    /// Dated snapshot the provider reported serving, e.g. `gpt-5-2025-08-07`.
+    pub served_model: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ModelFailoverEvent {
+    /// Provider id that kept failing, e.g. `openai`.
+    pub from_provider: String,
+    /// Model requested from `from_provider`.
+    pub from_model: String,
+    /// Fallback provider id that answers the rest of the turn.
+    pub to_provider: String,
+    /// Model requested from `to_provider`.
+    pub to_model: String,
+    /// Error returned by the failing provider.
+    pub reason: String,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ContextCompactedEvent;
 
@@ -1405,6 +1568,18 @@ pub struct McpToolCallBeginEvent {
     pub invocation: McpInvocation,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
+pub struct McpToolCallProgressEvent {
+    /// Identifier of the McpToolCallBegin this progress belongs to.
+    pub call_id: String,
+    /// Progress so far; increases with each notification.
+    pub progress: f64,
+    /// Total amount of work, when the server knows it.
+    pub total: Option<f64>,
+    /// Human-readable description of the current step.
+    pub message: Option<String>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
 pub struct McpToolCallEndEvent {
     /// Identifier for the corresponding McpToolCallBegin that finished.
@@ -1905,6 +2080,12 @@ pub struct ExecCommandEndEvent {
     pub duration: Duration,
     /// Formatted output from the command, as seen by the model.
     pub formatted_output: String,
+    /// Unified diff of `aggregated_output` against the previous run of the
+    /// same command in this session. Empty when the output is unchanged;
+    /// absent the first time the command runs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub previous_run_diff: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
@@ -1915,6 +2096,19 @@ pub struct ViewImageToolCallEvent {
     pub path: PathBuf,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct OpenFileRequestEvent {
+    /// Identifier for the originating tool call.
+    pub call_id: String,
+    /// Absolute path of the file to open.
+    pub path: PathBuf,
+    /// 1-based line to reveal, when the agent gave one.
+    pub line: Option<u32>,
+    /// Whether `open_file_command` already opened the file. Hosts should only
+    /// open it themselves when this is false.
+    pub opened_by_command: bool,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecOutputStream {
@@ -1974,12 +2168,89 @@ pub struct UndoCompletedEvent {
     pub message: Option<String>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct CheckpointCreatedEvent {
+    /// Id of the ghost commit holding the workspace state.
+    pub commit_id: String,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ThreadRolledBackEvent {
     /// Number of user turns that were removed from context.
     pub num_turns: u32,
 }
 
+/// A user-requested change to the conversation context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type")]
+pub enum ContextEdit {
+    /// Summarize the conversation so far.
+    Compact,
+    /// Drop every user turn, keeping only the session instructions.
+    Clear,
+    /// Drop user turns `first..=last`, numbered from 1 (oldest first), along
+    /// with everything the model produced in them.
+    DropTurns { first: u32, last: u32 },
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ContextEditPreviewEvent {
+    pub edit: ContextEdit,
+    /// Number of user turns currently in context.
+    pub total_turns: u32,
+    /// First line of each user message the edit removes (or summarizes),
+    /// oldest first.
+    pub removed_turns: Vec<String>,
+    /// Estimated tokens in context now.
+    #[ts(type = "number")]
+    pub tokens_before: i64,
+    /// Estimated tokens in context after the edit. For compaction this
+    /// excludes the summary the model has yet to write.
+    #[ts(type = "number")]
+    pub tokens_after: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ContextEditedEvent {
+    pub edit: ContextEdit,
+    /// Number of user turns that were removed from context.
+    pub removed_turns: u32,
+    #[ts(type = "number")]
+    pub tokens_before: i64,
+    #[ts(type = "number")]
+    pub tokens_after: i64,
+}
+
+/// Content pinned into the model's context.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type")]
+pub enum PinTarget {
+    /// A file, re-read before every request. Relative paths resolve against
+    /// the session's working directory.
+    File { path: PathBuf },
+    /// A message. When `text` is omitted, the agent's last message is pinned.
+    Message { text: Option<String> },
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct PinnedContextEntry {
+    pub id: u32,
+    /// The pinned content, with file paths made absolute and message text
+    /// filled in.
+    pub target: PinTarget,
+    /// Estimated tokens the pin adds to every request.
+    #[ts(type = "number")]
+    pub tokens: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct PinnedContextEvent {
+    /// Current pins, oldest first.
+    pub pins: Vec<PinnedContextEntry>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct StreamErrorEvent {
     pub message: String,
@@ -2044,6 +2315,18 @@ pub struct GetHistoryEntryResponseEvent {
     pub entry: Option<HistoryEntry>,
 }
 
+/// Response payload for `Op::McpLogin`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct McpLoginCompletedEvent {
+    /// Name of the MCP server.
+    pub name: String,
+    pub success: bool,
+    /// Why the login failed, when it did.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub error: Option<String>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct McpListToolsResponseEvent {
     /// Fully qualified tool name -> tool definition.
@@ -2052,10 +2335,47 @@ pub struct McpListToolsResponseEvent {
     pub resources: std::collections::HashMap<String, Vec<McpResource>>,
     /// Known resource templates grouped by server name.
     pub resource_templates: std::collections::HashMap<String, Vec<McpResourceTemplate>>,
+    /// Known prompts grouped by server name.
+    pub prompts: std::collections::HashMap<String, Vec<McpPrompt>>,
     /// Authentication status for each configured MCP server.
     pub auth_statuses: std::collections::HashMap<String, McpAuthStatus>,
 }
 
+/// Response payload for `Op::McpServerStatus`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct McpServerStatusResponseEvent {
+    /// One entry per enabled server, sorted by name.
+    pub servers: Vec<McpServerStatusEntry>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct McpServerStatusEntry {
+    pub name: String,
+    pub state: McpServerState,
+    /// Names of the tools the server currently exposes.
+    pub tools: Vec<String>,
+    /// Times the server was restarted after it stopped responding.
+    pub restarts: u32,
+    /// Most recent errors, oldest first.
+    pub recent_errors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum McpServerState {
+    /// Configured with `lazy_start` and not needed yet.
+    NotStarted,
+    /// Being started for the first time.
+    Starting,
+    /// Connected and answering requests.
+    Ready,
+    /// Stopped responding and waiting to be started again.
+    Restarting,
+    /// Failed to start, or kept failing after restarts.
+    Failed,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct McpStartupUpdateEvent {
     /// Server name being started.
@@ -2121,6 +2441,38 @@ pub struct ListSkillsResponseEvent {
     pub skills: Vec<SkillsListEntry>,
 }
 
+/// Response payload for `Op::ListTrash`, `Op::RestoreTrash`, and `Op::PurgeTrash`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ListTrashResponseEvent {
+    pub entries: Vec<TrashEntry>,
+}
+
+/// A file the agent deleted that is still recoverable from the session trash.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct TrashEntry {
+    /// Location the file was deleted from.
+    pub path: PathBuf,
+    /// Location of the preserved copy inside the trash directory.
+    pub trash_path: PathBuf,
+}
+
+/// Response payload for `Op::ListJobs`, `Op::StartJob`, and `Op::StopJob`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ListJobsResponseEvent {
+    pub jobs: Vec<BackgroundJob>,
+}
+
+/// A long-running command kept alive across turns as a background job.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct BackgroundJob {
+    /// Identifier used to read the job's output or stop it.
+    pub id: String,
+    /// The command line the job was started with.
+    pub command: String,
+    /// Exit code once the job has exited; `None` while it is running.
+    pub exit_code: Option<i32>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
 #[serde(rename_all = "snake_case")]
 #[ts(rename_all = "snake_case")]
@@ -2276,6 +2628,15 @@ pub enum ReviewDecision {
     /// remainder of the session.
     ApprovedForSession,
 
+    /// User has approved a proposed patch except for the listed hunks, which
+    /// should be left out when it is applied.
+    ApprovedHunks { declined: Vec<PatchHunkId> },
+
+    /// User has approved a proposed patch and wants future patches that only
+    /// touch files matching `globs` approved without asking, in this and
+    /// later sessions. Persisted as an `allow` rule in `[[approval.rules]]`.
+    ApprovedAlwaysForPaths { globs: Vec<String> },
+
     /// User has denied this command and the agent should not execute it, but
     /// it should continue the session and try something else.
     #[default]
@@ -2294,10 +2655,39 @@ impl ReviewDecision {
             ReviewDecision::Approved => "approved",
             ReviewDecision::ApprovedExecpolicyAmendment { .. } => "approved_with_amendment",
             ReviewDecision::ApprovedForSession => "approved_for_session",
+            ReviewDecision::ApprovedHunks { .. } => "approved_hunks",
+            ReviewDecision::ApprovedAlwaysForPaths { .. } => "approved_always_for_paths",
             ReviewDecision::Denied => "denied",
             ReviewDecision::Abort => "abort",
         }
     }
+
+    /// How far an approval reaches, or `None` when the request was denied.
+    pub fn scope(&self) -> Option<ApprovalScope> {
+        match self {
+            ReviewDecision::Approved | ReviewDecision::ApprovedHunks { .. } => {
+                Some(ApprovalScope::Once)
+            }
+            ReviewDecision::ApprovedForSession => Some(ApprovalScope::Session),
+            ReviewDecision::ApprovedExecpolicyAmendment { .. }
+            | ReviewDecision::ApprovedAlwaysForPaths { .. } => Some(ApprovalScope::Always),
+            ReviewDecision::Denied | ReviewDecision::Abort => None,
+        }
+    }
+}
+
+/// How far an approval reaches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ApprovalScope {
+    /// Only the request that was approved.
+    Once,
+    /// Identical requests for the rest of the session.
+    Session,
+    /// Matching requests in this and later sessions, persisted in config or
+    /// rules.
+    Always,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
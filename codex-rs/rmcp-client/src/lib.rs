@@ -3,7 +3,9 @@ mod logging_client_handler;
 mod oauth;
 mod perform_oauth_login;
 mod program_resolver;
+mod progress;
 mod rmcp_client;
+mod sse_transport;
 mod utils;
 
 pub use auth_status::determine_streamable_http_auth_status;
@@ -18,6 +20,8 @@ pub use oauth::save_oauth_tokens;
 pub use perform_oauth_login::OauthLoginHandle;
 pub use perform_oauth_login::perform_oauth_login;
 pub use perform_oauth_login::perform_oauth_login_return_url;
+pub use progress::ToolCallProgress;
+pub use progress::ToolCallProgressSender;
 pub use rmcp::model::ElicitationAction;
 pub use rmcp_client::Elicitation;
 pub use rmcp_client::ElicitationResponse;
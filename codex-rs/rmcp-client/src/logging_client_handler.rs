@@ -18,19 +18,27 @@ use tracing::error;
 use tracing::info;
 use tracing::warn;
 
+use crate::progress::ProgressListeners;
+use crate::progress::ToolCallProgress;
 use crate::rmcp_client::SendElicitation;
 
 #[derive(Clone)]
 pub(crate) struct LoggingClientHandler {
     client_info: ClientInfo,
     send_elicitation: Arc<SendElicitation>,
+    progress: ProgressListeners,
 }
 
 impl LoggingClientHandler {
-    pub(crate) fn new(client_info: ClientInfo, send_elicitation: SendElicitation) -> Self {
+    pub(crate) fn new(
+        client_info: ClientInfo,
+        send_elicitation: SendElicitation,
+        progress: ProgressListeners,
+    ) -> Self {
         Self {
             client_info,
             send_elicitation: Arc::new(send_elicitation),
+            progress,
         }
     }
 }
@@ -66,10 +74,15 @@ impl ClientHandler for LoggingClientHandler {
         params: ProgressNotificationParam,
         _context: NotificationContext<RoleClient>,
     ) {
-        info!(
-            "MCP server progress notification (token: {:?}, progress: {}, total: {:?}, message: {:?})",
-            params.progress_token, params.progress, params.total, params.message
-        );
+        let token = params.progress_token.0.to_string();
+        let progress = ToolCallProgress {
+            progress: params.progress,
+            total: params.total,
+            message: params.message,
+        };
+        if !self.progress.dispatch(&token, progress) {
+            debug!("MCP server progress notification for unknown token {token}");
+        }
     }
 
     async fn on_resource_updated(
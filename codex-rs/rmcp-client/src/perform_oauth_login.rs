@@ -202,6 +202,12 @@ impl OauthLoginHandle {
         &self.authorization_url
     }
 
+    /// Open the authorization URL in the default browser. Returns `false`
+    /// when no browser could be launched.
+    pub fn open_in_browser(&self) -> bool {
+        webbrowser::open(&self.authorization_url).is_ok()
+    }
+
     pub fn into_parts(self) -> (String, oneshot::Receiver<Result<()>>) {
         (self.authorization_url, self.completion)
     }
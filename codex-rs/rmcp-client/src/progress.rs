@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::PoisonError;
+
+use tokio::sync::mpsc;
+
+/// A `notifications/progress` update for an in-flight tool call.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolCallProgress {
+    pub progress: f64,
+    pub total: Option<f64>,
+    pub message: Option<String>,
+}
+
+pub type ToolCallProgressSender = mpsc::UnboundedSender<ToolCallProgress>;
+
+/// Routes progress notifications to the call that owns their progress token.
+#[derive(Clone, Default)]
+pub(crate) struct ProgressListeners {
+    listeners: Arc<std::sync::Mutex<HashMap<String, ToolCallProgressSender>>>,
+}
+
+impl ProgressListeners {
+    pub(crate) fn register(&self, token: String, sender: ToolCallProgressSender) {
+        self.listeners
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(token, sender);
+    }
+
+    pub(crate) fn unregister(&self, token: &str) {
+        self.listeners
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(token);
+    }
+
+    /// Returns `false` when no call is waiting on `token`.
+    pub(crate) fn dispatch(&self, token: &str, progress: ToolCallProgress) -> bool {
+        self.listeners
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(token)
+            .is_some_and(|sender| sender.send(progress).is_ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn progress_reaches_only_the_registered_call() {
+        let listeners = ProgressListeners::default();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        listeners.register("1".to_string(), tx);
+        let progress = ToolCallProgress {
+            progress: 2.0,
+            total: Some(4.0),
+            message: Some("indexing".to_string()),
+        };
+
+        assert!(listeners.dispatch("1", progress.clone()));
+        assert!(!listeners.dispatch("2", progress.clone()));
+        assert_eq!(rx.try_recv().ok(), Some(progress.clone()));
+
+        listeners.unregister("1");
+        assert!(!listeners.dispatch("1", progress));
+    }
+}
@@ -12,8 +12,12 @@ use futures::FutureExt;
 use futures::future::BoxFuture;
 use mcp_types::CallToolRequestParams;
 use mcp_types::CallToolResult;
+use mcp_types::GetPromptRequestParams;
+use mcp_types::GetPromptResult;
 use mcp_types::InitializeRequestParams;
 use mcp_types::InitializeResult;
+use mcp_types::ListPromptsRequestParams;
+use mcp_types::ListPromptsResult;
 use mcp_types::ListResourceTemplatesRequestParams;
 use mcp_types::ListResourceTemplatesResult;
 use mcp_types::ListResourcesRequestParams;
@@ -24,8 +28,11 @@ use mcp_types::ReadResourceRequestParams;
 use mcp_types::ReadResourceResult;
 use mcp_types::RequestId;
 use mcp_types::Tool;
+use reqwest::header::AUTHORIZATION;
 use reqwest::header::HeaderMap;
+use reqwest::header::HeaderValue;
 use rmcp::model::CallToolRequestParam;
+use rmcp::model::CancelledNotificationParam;
 use rmcp::model::ClientNotification;
 use rmcp::model::ClientRequest;
 use rmcp::model::CreateElicitationRequestParam;
@@ -33,12 +40,18 @@ use rmcp::model::CreateElicitationResult;
 use rmcp::model::CustomNotification;
 use rmcp::model::CustomRequest;
 use rmcp::model::Extensions;
+use rmcp::model::GetPromptRequestParam;
 use rmcp::model::InitializeRequestParam;
 use rmcp::model::PaginatedRequestParam;
 use rmcp::model::ReadResourceRequestParam;
+use rmcp::model::Request;
+use rmcp::model::RequestId as RmcpRequestId;
 use rmcp::model::ServerResult;
+use rmcp::service::Peer;
+use rmcp::service::PeerRequestOptions;
 use rmcp::service::RoleClient;
 use rmcp::service::RunningService;
+use rmcp::service::ServiceError;
 use rmcp::service::{self};
 use rmcp::transport::StreamableHttpClientTransport;
 use rmcp::transport::auth::AuthClient;
@@ -60,6 +73,10 @@ use crate::oauth::OAuthCredentialsStoreMode;
 use crate::oauth::OAuthPersistor;
 use crate::oauth::StoredOAuthTokens;
 use crate::program_resolver;
+use crate::progress::ProgressListeners;
+use crate::progress::ToolCallProgressSender;
+use crate::sse_transport;
+use crate::sse_transport::SseTransport;
 use crate::utils::apply_default_headers;
 use crate::utils::build_default_headers;
 use crate::utils::convert_call_tool_result;
@@ -77,6 +94,7 @@ enum PendingTransport {
         transport: StreamableHttpClientTransport<AuthClient<reqwest::Client>>,
         oauth_persistor: OAuthPersistor,
     },
+    Sse(SseTransport),
 }
 
 enum ClientState {
@@ -112,6 +130,7 @@ pub struct ListToolsWithConnectorIdResult {
 /// https://github.com/modelcontextprotocol/rust-sdk
 pub struct RmcpClient {
     state: Mutex<ClientState>,
+    progress: ProgressListeners,
 }
 
 impl RmcpClient {
@@ -168,6 +187,7 @@ impl RmcpClient {
             state: Mutex::new(ClientState::Connecting {
                 transport: Some(PendingTransport::ChildProcess(transport)),
             }),
+            progress: ProgressListeners::default(),
         })
     }
 
@@ -222,6 +242,33 @@ impl RmcpClient {
             state: Mutex::new(ClientState::Connecting {
                 transport: Some(transport),
             }),
+            progress: ProgressListeners::default(),
+        })
+    }
+
+    /// Connect to a server using the HTTP+SSE transport. Must be called from
+    /// within a Tokio runtime: the event stream is opened right away.
+    pub fn new_sse_client(
+        url: &str,
+        bearer_token: Option<String>,
+        http_headers: Option<HashMap<String, String>>,
+        env_http_headers: Option<HashMap<String, String>>,
+    ) -> Result<Self> {
+        let mut default_headers = build_default_headers(http_headers, env_http_headers)?;
+        if let Some(bearer_token) = bearer_token {
+            default_headers.insert(
+                AUTHORIZATION,
+                HeaderValue::from_str(&format!("Bearer {bearer_token}"))?,
+            );
+        }
+        let http_client =
+            apply_default_headers(reqwest::Client::builder(), &default_headers).build()?;
+        let transport = sse_transport::connect(http_client, reqwest::Url::parse(url)?);
+        Ok(Self {
+            state: Mutex::new(ClientState::Connecting {
+                transport: Some(PendingTransport::Sse(transport)),
+            }),
+            progress: ProgressListeners::default(),
         })
     }
 
@@ -234,7 +281,8 @@ impl RmcpClient {
         send_elicitation: SendElicitation,
     ) -> Result<InitializeResult> {
         let rmcp_params: InitializeRequestParam = convert_to_rmcp(params.clone())?;
-        let client_handler = LoggingClientHandler::new(rmcp_params, send_elicitation);
+        let client_handler =
+            LoggingClientHandler::new(rmcp_params, send_elicitation, self.progress.clone());
 
         let (transport, oauth_persistor) = {
             let mut guard = self.state.lock().await;
@@ -248,6 +296,10 @@ impl RmcpClient {
                         service::serve_client(client_handler.clone(), transport).boxed(),
                         None,
                     ),
+                    Some(PendingTransport::Sse(transport)) => (
+                        service::serve_client(client_handler.clone(), transport).boxed(),
+                        None,
+                    ),
                     Some(PendingTransport::StreamableHttpWithOAuth {
                         transport,
                         oauth_persistor,
@@ -401,19 +453,91 @@ impl RmcpClient {
         Ok(converted)
     }
 
+    pub async fn list_prompts(
+        &self,
+        params: Option<ListPromptsRequestParams>,
+        timeout: Option<Duration>,
+    ) -> Result<ListPromptsResult> {
+        self.refresh_oauth_if_needed().await;
+        let service = self.service().await?;
+        let rmcp_params = params
+            .map(convert_to_rmcp::<_, PaginatedRequestParam>)
+            .transpose()?;
+
+        let fut = service.list_prompts(rmcp_params);
+        let result = run_with_timeout(fut, timeout, "prompts/list").await?;
+        let converted = convert_to_mcp(result)?;
+        self.persist_oauth_tokens().await;
+        Ok(converted)
+    }
+
+    pub async fn get_prompt(
+        &self,
+        params: GetPromptRequestParams,
+        timeout: Option<Duration>,
+    ) -> Result<GetPromptResult> {
+        self.refresh_oauth_if_needed().await;
+        let service = self.service().await?;
+        let rmcp_params: GetPromptRequestParam = convert_to_rmcp(params)?;
+        let fut = service.get_prompt(rmcp_params);
+        let result = run_with_timeout(fut, timeout, "prompts/get").await?;
+        let converted = convert_to_mcp(result)?;
+        self.persist_oauth_tokens().await;
+        Ok(converted)
+    }
+
     pub async fn call_tool(
         &self,
         name: String,
         arguments: Option<serde_json::Value>,
         timeout: Option<Duration>,
+    ) -> Result<CallToolResult> {
+        self.call_tool_with_progress(name, arguments, timeout, None)
+            .await
+    }
+
+    /// Like [`Self::call_tool`], but forwards the server's progress
+    /// notifications for this call to `progress`. Dropping the returned
+    /// future before it completes tells the server the call was cancelled.
+    pub async fn call_tool_with_progress(
+        &self,
+        name: String,
+        arguments: Option<serde_json::Value>,
+        timeout: Option<Duration>,
+        progress: Option<ToolCallProgressSender>,
     ) -> Result<CallToolResult> {
         self.refresh_oauth_if_needed().await;
         let service = self.service().await?;
         let params = CallToolRequestParams { arguments, name };
         let rmcp_params: CallToolRequestParam = convert_to_rmcp(params)?;
-        let fut = service.call_tool(rmcp_params);
-        let rmcp_result = run_with_timeout(fut, timeout, "tools/call").await?;
-        let converted = convert_call_tool_result(rmcp_result)?;
+        let handle = service
+            .send_request_with_option(
+                ClientRequest::CallToolRequest(Request::new(rmcp_params)),
+                PeerRequestOptions::no_options(),
+            )
+            .await
+            .map_err(|err| anyhow!("tools/call failed: {err}"))?;
+        let mut in_flight = InFlightToolCall {
+            listeners: self.progress.clone(),
+            token: handle.progress_token.0.to_string(),
+            request_id: handle.id.clone(),
+            peer: handle.peer.clone(),
+            finished: false,
+        };
+        if let Some(progress) = progress {
+            self.progress.register(in_flight.token.clone(), progress);
+        }
+        let fut = async move {
+            match handle.await_response().await? {
+                ServerResult::CallToolResult(result) => Ok(result),
+                _ => Err(ServiceError::UnexpectedResponse),
+            }
+        };
+        let result = run_with_timeout(fut, timeout, "tools/call").await;
+        // Also cancel calls that timed out; servers ignore cancellations for
+        // requests they already answered.
+        in_flight.finished = result.is_ok();
+        let converted = convert_call_tool_result(result?)?;
         self.persist_oauth_tokens().await;
         Ok(converted)
     }
@@ -486,6 +610,38 @@ impl RmcpClient {
     }
 }
 
+/// Stops routing progress for a `tools/call` once it is done, and sends
+/// `notifications/cancelled` if it is dropped before the server answered.
+struct InFlightToolCall {
+    listeners: ProgressListeners,
+    token: String,
+    request_id: RmcpRequestId,
+    peer: Peer<RoleClient>,
+    finished: bool,
+}
+
+impl Drop for InFlightToolCall {
+    fn drop(&mut self) {
+        self.listeners.unregister(&self.token);
+        if self.finished {
+            return;
+        }
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let peer = self.peer.clone();
+        let params = CancelledNotificationParam {
+            request_id: self.request_id.clone(),
+            reason: Some("cancelled by the user".to_string()),
+        };
+        runtime.spawn(async move {
+            if let Err(error) = peer.notify_cancelled(params).await {
+                warn!("failed to cancel MCP tool call: {error}");
+            }
+        });
+    }
+}
+
 async fn create_oauth_transport_and_runtime(
     server_name: &str,
     url: &str,
@@ -0,0 +1,398 @@
+//! Client side of the HTTP+SSE transport from the 2024-11-05 MCP spec.
+//!
+//! The server streams its messages over a long-lived `GET` whose first event
+//! names the endpoint that client messages are `POST`ed to. [`connect`]
+//! bridges that to newline-delimited JSON over an in-memory pipe, so rmcp
+//! drives it the same way it drives a child process's stdio.
+//!
+//! The endpoint must share the origin of the event stream URL, so a server
+//! cannot redirect client messages (and their `Authorization` header) to
+//! another host. Each reconnected stream is a new session on the server, so
+//! the client's `initialize` handshake is replayed on it before queued
+//! messages are sent.
+
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::time::Duration;
+
+use anyhow::Result;
+use anyhow::bail;
+use futures::StreamExt;
+use reqwest::Url;
+use reqwest::header::ACCEPT;
+use reqwest::header::CONTENT_TYPE;
+use serde_json::Value;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+use tokio::io::DuplexStream;
+use tokio::io::ReadHalf;
+use tokio::io::WriteHalf;
+use tokio::sync::watch;
+use tracing::debug;
+use tracing::warn;
+
+const PIPE_CAPACITY: usize = 1 << 20;
+/// Consecutive failed connection attempts before the transport gives up.
+const MAX_RECONNECT_ATTEMPTS: u32 = 5;
+const INITIAL_RECONNECT_DELAY: Duration = Duration::from_millis(500);
+
+pub(crate) type SseTransport = (ReadHalf<DuplexStream>, WriteHalf<DuplexStream>);
+
+/// The messages the client opened its session with, replayed after a
+/// reconnect.
+#[derive(Clone, Default)]
+struct Handshake {
+    initialize: Option<String>,
+    initialized: Option<String>,
+}
+
+type SharedHandshake = Arc<Mutex<Handshake>>;
+
+/// A replayed `initialize` waiting for its response before the new endpoint
+/// is handed to the client forwarder.
+struct PendingReplay {
+    request_id: Value,
+    endpoint: Url,
+    initialized: Option<String>,
+}
+
+/// Open the event stream at `url` and return the pipe rmcp reads server
+/// messages from and writes client messages to. When the stream drops it is
+/// reopened with exponential backoff; messages sent meanwhile wait for the
+/// server to announce the new endpoint.
+pub(crate) fn connect(client: reqwest::Client, url: Url) -> SseTransport {
+    let (rmcp_side, bridge_side) = tokio::io::duplex(PIPE_CAPACITY);
+    let (bridge_read, bridge_write) = tokio::io::split(bridge_side);
+    let (endpoint_tx, endpoint_rx) = watch::channel(None);
+    let handshake = SharedHandshake::default();
+    tokio::spawn(run_event_stream(
+        client.clone(),
+        url,
+        endpoint_tx,
+        bridge_write,
+        handshake.clone(),
+    ));
+    tokio::spawn(forward_client_messages(
+        client,
+        endpoint_rx,
+        bridge_read,
+        handshake,
+    ));
+    tokio::io::split(rmcp_side)
+}
+
+async fn run_event_stream(
+    client: reqwest::Client,
+    url: Url,
+    endpoint: watch::Sender<Option<Url>>,
+    mut pipe: WriteHalf<DuplexStream>,
+    handshake: SharedHandshake,
+) {
+    let mut failures = 0;
+    let mut ever_connected = false;
+    loop {
+        let mut connected = false;
+        let stream = StreamContext {
+            client: &client,
+            url: &url,
+            endpoint: &endpoint,
+            handshake: &handshake,
+            reconnect: ever_connected,
+        };
+        let result = tokio::select! {
+            result = stream_events(stream, &mut pipe, &mut connected) => result,
+            () = endpoint.closed() => return,
+        };
+        ever_connected |= connected;
+        endpoint.send_replace(None);
+        if endpoint.is_closed() {
+            return;
+        }
+        if connected {
+            failures = 0;
+        }
+        failures += 1;
+        match result {
+            Ok(()) => debug!("MCP SSE stream at {url} closed"),
+            Err(err) => warn!("MCP SSE stream at {url} failed: {err:#}"),
+        }
+        if failures > MAX_RECONNECT_ATTEMPTS {
+            warn!("giving up on MCP SSE stream at {url} after {MAX_RECONNECT_ATTEMPTS} attempts");
+            return;
+        }
+        tokio::time::sleep(INITIAL_RECONNECT_DELAY * 2u32.pow(failures - 1)).await;
+    }
+}
+
+/// State shared by one connection attempt of the event stream.
+struct StreamContext<'a> {
+    client: &'a reqwest::Client,
+    url: &'a Url,
+    endpoint: &'a watch::Sender<Option<Url>>,
+    handshake: &'a SharedHandshake,
+    /// Whether an earlier connection reached the server, so this one has to
+    /// replay the handshake.
+    reconnect: bool,
+}
+
+async fn stream_events(
+    stream: StreamContext<'_>,
+    pipe: &mut WriteHalf<DuplexStream>,
+    connected: &mut bool,
+) -> Result<()> {
+    let StreamContext {
+        client,
+        url,
+        endpoint,
+        handshake,
+        reconnect,
+    } = stream;
+    let response = client
+        .get(url.clone())
+        .header(ACCEPT, "text/event-stream")
+        .send()
+        .await?
+        .error_for_status()?;
+    let mut body = response.bytes_stream();
+    let mut parser = SseParser::default();
+    let mut pending_replay: Option<PendingReplay> = None;
+    while let Some(chunk) = body.next().await {
+        for event in parser.push(&chunk?) {
+            match event.event.as_str() {
+                "endpoint" => {
+                    let target = resolve_endpoint(url, &event.data)?;
+                    *connected = true;
+                    let Handshake {
+                        initialize,
+                        initialized,
+                    } = handshake
+                        .lock()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .clone();
+                    match initialize.filter(|_| reconnect) {
+                        Some(initialize) => {
+                            let request_id = request_id(&initialize).unwrap_or(Value::Null);
+                            post_message(client, &target, initialize).await?;
+                            pending_replay = Some(PendingReplay {
+                                request_id,
+                                endpoint: target,
+                                initialized,
+                            });
+                        }
+                        None => {
+                            endpoint.send_replace(Some(target));
+                        }
+                    }
+                }
+                "" | "message" => {
+                    // Re-encode so a message spread over several `data:`
+                    // lines still takes up exactly one line of the pipe.
+                    let message: Value = match serde_json::from_str(&event.data) {
+                        Ok(message) => message,
+                        Err(err) => {
+                            warn!("skipping malformed message from MCP SSE stream at {url}: {err}");
+                            continue;
+                        }
+                    };
+                    if let Some(replay) = pending_replay.take_if(|replay| {
+                        message.get("method").is_none()
+                            && message.get("id") == Some(&replay.request_id)
+                    }) {
+                        // The response to the replayed `initialize` is not
+                        // the client's to see; the session is ready now.
+                        if let Some(error) = message.get("error") {
+                            warn!("MCP server at {url} rejected the replayed initialize: {error}");
+                        }
+                        if let Some(initialized) = replay.initialized {
+                            post_message(client, &replay.endpoint, initialized).await?;
+                        }
+                        endpoint.send_replace(Some(replay.endpoint));
+                        continue;
+                    }
+                    let mut line = serde_json::to_string(&message)?;
+                    line.push('\n');
+                    pipe.write_all(line.as_bytes()).await?;
+                }
+                other => debug!("ignoring MCP SSE event `{other}`"),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The URL client messages go to, named relative to the stream URL by the
+/// `endpoint` event. Endpoints on another origin are refused.
+fn resolve_endpoint(url: &Url, data: &str) -> Result<Url> {
+    let target = url.join(data.trim())?;
+    if target.origin() != url.origin() {
+        bail!("refusing MCP SSE endpoint {target} on a different origin than {url}");
+    }
+    Ok(target)
+}
+
+fn request_id(line: &str) -> Option<Value> {
+    serde_json::from_str::<Value>(line).ok()?.get("id").cloned()
+}
+
+async fn post_message(client: &reqwest::Client, target: &Url, body: String) -> Result<()> {
+    client
+        .post(target.clone())
+        .header(CONTENT_TYPE, "application/json")
+        .body(body)
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
+
+async fn forward_client_messages(
+    client: reqwest::Client,
+    mut endpoint: watch::Receiver<Option<Url>>,
+    pipe: ReadHalf<DuplexStream>,
+    handshake: SharedHandshake,
+) {
+    let mut lines = BufReader::new(pipe).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        record_handshake(&handshake, &line);
+        let target = match endpoint.wait_for(Option::is_some).await {
+            Ok(target) => (*target).clone(),
+            Err(_) => return,
+        };
+        let Some(target) = target else {
+            return;
+        };
+        if let Err(err) = post_message(&client, &target, line).await {
+            warn!("failed to send message to MCP server at {target}: {err:#}");
+        }
+    }
+}
+
+/// Remember the client's `initialize` request and `initialized` notification
+/// so they can be replayed after a reconnect.
+fn record_handshake(handshake: &SharedHandshake, line: &str) {
+    let Ok(message) = serde_json::from_str::<Value>(line) else {
+        return;
+    };
+    let mut handshake = handshake.lock().unwrap_or_else(PoisonError::into_inner);
+    match message.get("method").and_then(Value::as_str) {
+        Some("initialize") => handshake.initialize = Some(line.to_string()),
+        Some("notifications/initialized") => handshake.initialized = Some(line.to_string()),
+        _ => {}
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct SseEvent {
+    event: String,
+    data: String,
+}
+
+/// Incremental parser for `text/event-stream` bodies.
+#[derive(Default)]
+struct SseParser {
+    buffer: Vec<u8>,
+    event: String,
+    data: Vec<String>,
+}
+
+impl SseParser {
+    /// Feed the next chunk of the body and return the events it completes.
+    fn push(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        self.buffer.extend_from_slice(chunk);
+        let mut events = Vec::new();
+        while let Some(end) = self.buffer.iter().position(|byte| *byte == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\n', '\r']);
+            if line.is_empty() {
+                let event = std::mem::take(&mut self.event);
+                if !self.data.is_empty() {
+                    events.push(SseEvent {
+                        event,
+                        data: std::mem::take(&mut self.data).join("\n"),
+                    });
+                }
+                continue;
+            }
+            if line.starts_with(':') {
+                continue;
+            }
+            let (field, value) = line.split_once(':').map_or((line, ""), |(field, value)| {
+                (field, value.strip_prefix(' ').unwrap_or(value))
+            });
+            match field {
+                "event" => self.event = value.to_string(),
+                "data" => self.data.push(value.to_string()),
+                _ => {}
+            }
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parser_assembles_events_split_across_chunks() {
+        let mut parser = SseParser::default();
+        assert_eq!(
+            parser.push(b": keep-alive\n\nevent: endpoint\ndata: /messages?session=1\n"),
+            Vec::new()
+        );
+        assert_eq!(
+            parser.push(b"\r\nevent: message\ndata: {\"jsonrpc\":\ndata:  \"2.0\"}\n\n"),
+            vec![
+                SseEvent {
+                    event: "endpoint".to_string(),
+                    data: "/messages?session=1".to_string(),
+                },
+                SseEvent {
+                    event: "message".to_string(),
+                    data: "{\"jsonrpc\":\n \"2.0\"}".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn endpoint_must_share_the_stream_origin() {
+        let url = Url::parse("https://mcp.example.com/sse").expect("url");
+        assert_eq!(
+            resolve_endpoint(&url, " /messages?session=1\n")
+                .expect("same origin")
+                .as_str(),
+            "https://mcp.example.com/messages?session=1"
+        );
+        for data in [
+            "https://attacker.example.net/messages",
+            "//attacker.example.net/messages",
+            "http://mcp.example.com/messages",
+            "https://mcp.example.com:8443/messages",
+        ] {
+            assert!(resolve_endpoint(&url, data).is_err(), "{data}");
+        }
+    }
+
+    #[test]
+    fn handshake_records_initialize_and_initialized() {
+        let handshake = SharedHandshake::default();
+        let initialize = r#"{"jsonrpc":"2.0","id":0,"method":"initialize","params":{}}"#;
+        let initialized = r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#;
+        record_handshake(&handshake, initialize);
+        record_handshake(&handshake, initialized);
+        record_handshake(
+            &handshake,
+            r#"{"jsonrpc":"2.0","id":1,"method":"tools/list"}"#,
+        );
+
+        let recorded = handshake.lock().expect("lock").clone();
+        assert_eq!(recorded.initialize.as_deref(), Some(initialize));
+        assert_eq!(recorded.initialized.as_deref(), Some(initialized));
+        assert_eq!(request_id(initialize), Some(serde_json::json!(0)));
+    }
+}
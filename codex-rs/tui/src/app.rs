@@ -14,7 +14,6 @@ use crate::bottom_pane::popup_consts::standard_popup_hint_line;
 use crate::chatwidget::ChatWidget;
 use crate::chatwidget::ExternalEditorState;
 use crate::cwd_prompt::CwdPromptAction;
-use crate::diff_render::DiffSummary;
 use crate::exec_command::strip_bash_lc_and_escape;
 use crate::external_editor;
 use crate::file_search::FileSearchManager;
@@ -22,13 +21,20 @@ use crate::history_cell;
 use crate::history_cell::HistoryCell;
 #[cfg(not(debug_assertions))]
 use crate::history_cell::UpdateAvailableHistoryCell;
+use crate::instructions_view::INSTRUCTIONS_VIEW_TITLE;
+use crate::instructions_view::instruction_lines;
 use crate::model_migration::ModelMigrationOutcome;
 use crate::model_migration::migration_copy_for_models;
 use crate::model_migration::run_model_migration_prompt;
+use crate::notifications::NotificationAction;
+use crate::pager_overlay::DiffReviewOverlay;
 use crate::pager_overlay::Overlay;
+use crate::pager_overlay::TerminalOverlay;
+use crate::pager_overlay::TimelineCheckpoint;
 use crate::render::highlight::highlight_bash_to_lines;
 use crate::render::renderable::Renderable;
 use crate::resume_picker::SessionSelection;
+use crate::settings;
 use crate::tui;
 use crate::tui::TuiEvent;
 use crate::update_action::UpdateAction;
@@ -37,6 +43,7 @@ use codex_app_server_protocol::ConfigLayerSource;
 use codex_core::AuthManager;
 use codex_core::CodexAuth;
 use codex_core::ThreadManager;
+use codex_core::config::CONFIG_TOML_FILE;
 use codex_core::config::Config;
 use codex_core::config::ConfigBuilder;
 use codex_core::config::ConfigOverrides;
@@ -58,8 +65,13 @@ use codex_core::protocol::SandboxPolicy;
 use codex_core::protocol::SessionSource;
 use codex_core::protocol::SkillErrorInfo;
 use codex_core::protocol::TokenUsage;
+use codex_core::session_worktree::MergeOutcome;
+use codex_core::session_worktree::SessionWorktree;
 #[cfg(target_os = "windows")]
 use codex_core::windows_sandbox::WindowsSandboxLevelExt;
+use codex_core::workers::TaskStatus;
+use codex_core::workers::WorkerPool;
+use codex_core::workers::WorkerPoolOptions;
 use codex_otel::OtelManager;
 use codex_protocol::ThreadId;
 use codex_protocol::config_types::Personality;
@@ -103,6 +115,7 @@ use toml::Value as TomlValue;
 
 const EXTERNAL_EDITOR_HINT: &str = "Save and close external editor to continue.";
 const THREAD_EVENT_CHANNEL_CAPACITY: usize = 32768;
+const NO_SESSION_WORKTREE_MESSAGE: &str = "This session is not running in a worktree. Start one with `codex --worktree` or `session_worktree = true`.";
 
 #[derive(Debug, Clone)]
 pub struct AppExitInfo {
@@ -530,6 +543,8 @@ pub(crate) struct App {
     pub(crate) file_search: FileSearchManager,
 
     pub(crate) transcript_cells: Vec<Arc<dyn HistoryCell>>,
+    /// Checkpoints core took during this thread, for the timeline's workspace diffs.
+    pub(crate) timeline_checkpoints: Vec<TimelineCheckpoint>,
 
     // Pager overlay state (Transcript or Static like Diff)
     pub(crate) overlay: Option<Overlay>,
@@ -565,6 +580,13 @@ pub(crate) struct App {
     primary_thread_id: Option<ThreadId>,
     primary_session_configured: Option<SessionConfiguredEvent>,
     pending_primary_events: VecDeque<Event>,
+
+    /// Worker pool started with `/workers start`, kept after it finishes so
+    /// its results stay viewable.
+    worker_pool: Option<WorkerPool>,
+
+    /// Worktree the session is running in, until `/merge` or `/discard`.
+    session_worktree: Option<SessionWorktree>,
 }
 
 #[derive(Default)]
@@ -627,6 +649,57 @@ impl App {
             .wrap_err_with(|| format!("Failed to rebuild config for cwd {cwd_display}"))
     }
 
+    /// Write a `/settings` edit and refresh the layer stack `/settings` reads
+    /// from. The running session keeps its config; new sessions pick it up.
+    async fn persist_setting(&mut self, key: &str, value: &str) {
+        let Some(setting) = settings::find_setting(key) else {
+            return;
+        };
+        let write = match settings::plan_setting_write(&self.config, setting, value) {
+            Ok(write) => write,
+            Err(err) => {
+                self.chat_widget
+                    .add_error_message(format!("Invalid value for {key}: {err}"));
+                return;
+            }
+        };
+        let path = write.segments.join(".");
+        let result = match ConfigEditsBuilder::new(&write.config_folder)
+            .set_path(write.segments, &write.value)
+        {
+            Ok(builder) => builder.apply().await,
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
+            tracing::error!(error = %err, "failed to persist setting");
+            self.chat_widget
+                .add_error_message(format!("Failed to save {key}: {err}"));
+            return;
+        }
+
+        let file = write.config_folder.join(CONFIG_TOML_FILE);
+        let mut message = format!(
+            "Saved {path} = {} to {}; takes effect in new sessions",
+            write.value,
+            file.display()
+        );
+        if let Some(source) = write.overridden_by {
+            message.push_str(&format!(" (still overridden by the {source} value)"));
+        }
+        self.chat_widget.add_info_message(message, None);
+
+        match self.rebuild_config_for_cwd(self.config.cwd.clone()).await {
+            Ok(config) => {
+                self.config.config_layer_stack = config.config_layer_stack.clone();
+                self.chat_widget
+                    .set_config_layer_stack(config.config_layer_stack);
+            }
+            Err(err) => {
+                tracing::warn!(error = %err, "failed to reload config after saving setting");
+            }
+        }
+    }
+
     fn apply_runtime_policy_overrides(&mut self, config: &mut Config) {
         if let Some(policy) = self.runtime_approval_policy_override.as_ref()
             && let Err(err) = config.approval_policy.set(*policy)
@@ -656,6 +729,122 @@ impl App {
         }
     }
 
+    /// Resume the session recorded at `path` in place of the current one,
+    /// asking which working directory to use when it differs from ours.
+    async fn resume_session_from_rollout(
+        &mut self,
+        tui: &mut tui::Tui,
+        path: PathBuf,
+    ) -> Result<()> {
+        let current_cwd = self.config.cwd.clone();
+        let resume_cwd = match crate::resolve_cwd_for_resume_or_fork(
+            tui,
+            &current_cwd,
+            &path,
+            CwdPromptAction::Resume,
+            true,
+        )
+        .await?
+        {
+            Some(cwd) => cwd,
+            None => current_cwd.clone(),
+        };
+        let mut resume_config = if crate::cwds_differ(&current_cwd, &resume_cwd) {
+            match self.rebuild_config_for_cwd(resume_cwd).await {
+                Ok(cfg) => cfg,
+                Err(err) => {
+                    self.chat_widget.add_error_message(format!(
+                        "Failed to rebuild configuration for resume: {err}"
+                    ));
+                    return Ok(());
+                }
+            }
+        } else {
+            // No rebuild needed: current_cwd comes from self.config.cwd.
+            self.config.clone()
+        };
+        self.apply_runtime_policy_overrides(&mut resume_config);
+        let summary = session_summary(
+            self.chat_widget.token_usage(),
+            self.chat_widget.thread_id(),
+            self.chat_widget.thread_name(),
+        );
+        match self
+            .server
+            .resume_thread_from_rollout(
+                resume_config.clone(),
+                path.clone(),
+                self.auth_manager.clone(),
+            )
+            .await
+        {
+            Ok(resumed) => {
+                self.shutdown_current_thread().await;
+                self.config = resume_config;
+                tui.set_notification_method(self.config.tui_notification_method);
+                self.file_search.update_search_dir(self.config.cwd.clone());
+                let init =
+                    self.chatwidget_init_for_forked_or_resumed_thread(tui, self.config.clone());
+                self.chat_widget =
+                    ChatWidget::new_from_existing(init, resumed.thread, resumed.session_configured);
+                self.reset_thread_event_state();
+                if let Some(summary) = summary {
+                    let mut lines: Vec<Line<'static>> = vec![summary.usage_line.clone().into()];
+                    if let Some(command) = summary.resume_command {
+                        let spans = vec!["To continue this session, run ".into(), command.cyan()];
+                        lines.push(spans.into());
+                    }
+                    self.chat_widget.add_plain_history_lines(lines);
+                }
+            }
+            Err(err) => {
+                let path_display = path.display();
+                self.chat_widget.add_error_message(format!(
+                    "Failed to resume session from {path_display}: {err}"
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Fork the session recorded at `path` into a new thread that replaces the
+    /// current one.
+    async fn fork_session_from_rollout(&mut self, tui: &mut tui::Tui, path: PathBuf) {
+        let summary = session_summary(
+            self.chat_widget.token_usage(),
+            self.chat_widget.thread_id(),
+            self.chat_widget.thread_name(),
+        );
+        match self
+            .server
+            .fork_thread(usize::MAX, self.config.clone(), path.clone())
+            .await
+        {
+            Ok(forked) => {
+                self.shutdown_current_thread().await;
+                let init =
+                    self.chatwidget_init_for_forked_or_resumed_thread(tui, self.config.clone());
+                self.chat_widget =
+                    ChatWidget::new_from_existing(init, forked.thread, forked.session_configured);
+                self.reset_thread_event_state();
+                if let Some(summary) = summary {
+                    let mut lines: Vec<Line<'static>> = vec![summary.usage_line.clone().into()];
+                    if let Some(command) = summary.resume_command {
+                        let spans = vec!["To continue this session, run ".into(), command.cyan()];
+                        lines.push(spans.into());
+                    }
+                    self.chat_widget.add_plain_history_lines(lines);
+                }
+            }
+            Err(err) => {
+                let path_display = path.display();
+                self.chat_widget.add_error_message(format!(
+                    "Failed to fork session from {path_display}: {err}"
+                ));
+            }
+        }
+    }
+
     fn ensure_thread_channel(&mut self, thread_id: ThreadId) -> &mut ThreadEventChannel {
         self.thread_event_channels
             .entry(thread_id)
@@ -858,6 +1047,7 @@ impl App {
     fn reset_for_thread_switch(&mut self, tui: &mut tui::Tui) -> Result<()> {
         self.overlay = None;
         self.transcript_cells.clear();
+        self.timeline_checkpoints.clear();
         self.deferred_history_lines.clear();
         self.has_emitted_history_lines = false;
         self.backtrack = BacktrackState::default();
@@ -927,12 +1117,18 @@ impl App {
         feedback: codex_feedback::CodexFeedback,
         is_first_run: bool,
         ollama_chat_support_notice: Option<DeprecationNoticeEvent>,
+        startup_warnings: Vec<String>,
     ) -> Result<AppExitInfo> {
         use tokio_stream::StreamExt;
         let (app_event_tx, mut app_event_rx) = unbounded_channel();
         let app_event_tx = AppEventSender::new(app_event_tx);
         emit_deprecation_notice(&app_event_tx, ollama_chat_support_notice);
         emit_project_config_warnings(&app_event_tx, &config);
+        for message in startup_warnings {
+            app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                crate::history_cell::new_warning_event(message),
+            )));
+        }
         tui.set_notification_method(config.tui_notification_method);
 
         let harness_overrides =
@@ -990,6 +1186,36 @@ impl App {
             SessionSource::Cli,
         );
 
+        let mut session_worktree = None;
+        if config.session_worktree && matches!(session_selection, SessionSelection::StartFresh) {
+            match SessionWorktree::create(&config.codex_home, &config.cwd).await {
+                Ok(Some(worktree)) => {
+                    config.cwd = worktree.cwd();
+                    app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                        crate::history_cell::new_info_event(
+                            format!("Working in a git worktree on branch {}.", worktree.branch()),
+                            Some(
+                                "Use /merge to land the changes in your checkout or /discard to drop them."
+                                    .to_string(),
+                            ),
+                        ),
+                    )));
+                    session_worktree = Some(worktree);
+                }
+                Ok(None) => app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                    crate::history_cell::new_warning_event(
+                        "session_worktree is enabled, but this directory is not in a git repository; working in place."
+                            .to_string(),
+                    ),
+                ))),
+                Err(err) => app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                    crate::history_cell::new_warning_event(format!(
+                        "Failed to create a session worktree ({err}); working in place."
+                    )),
+                ))),
+            }
+        }
+
         let enhanced_keys_supported = tui.enhanced_keys_supported();
         let mut chat_widget = match session_selection {
             SessionSelection::StartFresh | SessionSelection::Exit => {
@@ -1095,6 +1321,7 @@ impl App {
             file_search,
             enhanced_keys_supported,
             transcript_cells: Vec::new(),
+            timeline_checkpoints: Vec::new(),
             overlay: None,
             deferred_history_lines: Vec::new(),
             has_emitted_history_lines: false,
@@ -1112,6 +1339,8 @@ impl App {
             primary_thread_id: None,
             primary_session_configured: None,
             pending_primary_events: VecDeque::new(),
+            worker_pool: None,
+            session_worktree,
         };
 
         // On startup, if Agent mode (workspace-write) or ReadOnly is active, warn about world-writable dirs on Windows.
@@ -1227,7 +1456,14 @@ impl App {
         tui: &mut tui::Tui,
         event: TuiEvent,
     ) -> Result<AppRunControl> {
-        if self.overlay.is_some() {
+        if let Some(Overlay::Terminal(terminal)) = &mut self.overlay {
+            // Every key belongs to the process, so Esc must not start a backtrack.
+            terminal.handle_event(tui, event)?;
+            if terminal.is_done() {
+                self.close_transcript_overlay(tui);
+                tui.frame_requester().schedule_frame();
+            }
+        } else if self.overlay.is_some() {
             let _ = self.handle_backtrack_overlay_event(tui, event).await?;
         } else {
             match event {
@@ -1324,83 +1560,7 @@ impl App {
                 .await?
                 {
                     SessionSelection::Resume(path) => {
-                        let current_cwd = self.config.cwd.clone();
-                        let resume_cwd = match crate::resolve_cwd_for_resume_or_fork(
-                            tui,
-                            &current_cwd,
-                            &path,
-                            CwdPromptAction::Resume,
-                            true,
-                        )
-                        .await?
-                        {
-                            Some(cwd) => cwd,
-                            None => current_cwd.clone(),
-                        };
-                        let mut resume_config = if crate::cwds_differ(&current_cwd, &resume_cwd) {
-                            match self.rebuild_config_for_cwd(resume_cwd).await {
-                                Ok(cfg) => cfg,
-                                Err(err) => {
-                                    self.chat_widget.add_error_message(format!(
-                                        "Failed to rebuild configuration for resume: {err}"
-                                    ));
-                                    return Ok(AppRunControl::Continue);
-                                }
-                            }
-                        } else {
-                            // No rebuild needed: current_cwd comes from self.config.cwd.
-                            self.config.clone()
-                        };
-                        self.apply_runtime_policy_overrides(&mut resume_config);
-                        let summary = session_summary(
-                            self.chat_widget.token_usage(),
-                            self.chat_widget.thread_id(),
-                            self.chat_widget.thread_name(),
-                        );
-                        match self
-                            .server
-                            .resume_thread_from_rollout(
-                                resume_config.clone(),
-                                path.clone(),
-                                self.auth_manager.clone(),
-                            )
-                            .await
-                        {
-                            Ok(resumed) => {
-                                self.shutdown_current_thread().await;
-                                self.config = resume_config;
-                                tui.set_notification_method(self.config.tui_notification_method);
-                                self.file_search.update_search_dir(self.config.cwd.clone());
-                                let init = self.chatwidget_init_for_forked_or_resumed_thread(
-                                    tui,
-                                    self.config.clone(),
-                                );
-                                self.chat_widget = ChatWidget::new_from_existing(
-                                    init,
-                                    resumed.thread,
-                                    resumed.session_configured,
-                                );
-                                self.reset_thread_event_state();
-                                if let Some(summary) = summary {
-                                    let mut lines: Vec<Line<'static>> =
-                                        vec![summary.usage_line.clone().into()];
-                                    if let Some(command) = summary.resume_command {
-                                        let spans = vec![
-                                            "To continue this session, run ".into(),
-                                            command.cyan(),
-                                        ];
-                                        lines.push(spans.into());
-                                    }
-                                    self.chat_widget.add_plain_history_lines(lines);
-                                }
-                            }
-                            Err(err) => {
-                                let path_display = path.display();
-                                self.chat_widget.add_error_message(format!(
-                                    "Failed to resume session from {path_display}: {err}"
-                                ));
-                            }
-                        }
+                        self.resume_session_from_rollout(tui, path).await?;
                     }
                     SessionSelection::Exit
                     | SessionSelection::StartFresh
@@ -1411,49 +1571,8 @@ impl App {
                 tui.frame_requester().schedule_frame();
             }
             AppEvent::ForkCurrentSession => {
-                let summary = session_summary(
-                    self.chat_widget.token_usage(),
-                    self.chat_widget.thread_id(),
-                    self.chat_widget.thread_name(),
-                );
                 if let Some(path) = self.chat_widget.rollout_path() {
-                    match self
-                        .server
-                        .fork_thread(usize::MAX, self.config.clone(), path.clone())
-                        .await
-                    {
-                        Ok(forked) => {
-                            self.shutdown_current_thread().await;
-                            let init = self.chatwidget_init_for_forked_or_resumed_thread(
-                                tui,
-                                self.config.clone(),
-                            );
-                            self.chat_widget = ChatWidget::new_from_existing(
-                                init,
-                                forked.thread,
-                                forked.session_configured,
-                            );
-                            self.reset_thread_event_state();
-                            if let Some(summary) = summary {
-                                let mut lines: Vec<Line<'static>> =
-                                    vec![summary.usage_line.clone().into()];
-                                if let Some(command) = summary.resume_command {
-                                    let spans = vec![
-                                        "To continue this session, run ".into(),
-                                        command.cyan(),
-                                    ];
-                                    lines.push(spans.into());
-                                }
-                                self.chat_widget.add_plain_history_lines(lines);
-                            }
-                        }
-                        Err(err) => {
-                            let path_display = path.display();
-                            self.chat_widget.add_error_message(format!(
-                                "Failed to fork current session from {path_display}: {err}"
-                            ));
-                        }
-                    }
+                    self.fork_session_from_rollout(tui, path).await;
                 } else {
                     self.chat_widget
                         .add_error_message("Current session is not ready to fork yet.".to_string());
@@ -1461,6 +1580,31 @@ impl App {
 
                 tui.frame_requester().schedule_frame();
             }
+            AppEvent::Rewind { turn } => {
+                self.rewind_to_turn(turn);
+                tui.frame_requester().schedule_frame();
+            }
+            AppEvent::OpenHistoryBrowser => {
+                match crate::resume_picker::run_history_browser(
+                    tui,
+                    &self.config.codex_home,
+                    &self.config.model_provider_id,
+                    &self.config.cwd,
+                )
+                .await?
+                {
+                    SessionSelection::Resume(path) => {
+                        self.resume_session_from_rollout(tui, path).await?;
+                    }
+                    SessionSelection::Fork(path) => {
+                        self.fork_session_from_rollout(tui, path).await;
+                    }
+                    SessionSelection::Exit | SessionSelection::StartFresh => {}
+                }
+
+                // Leaving alt-screen may blank the inline viewport; force a redraw either way.
+                tui.frame_requester().schedule_frame();
+            }
             AppEvent::InsertHistoryCell(cell) => {
                 let cell: Arc<dyn HistoryCell> = cell.into();
                 if let Some(Overlay::Transcript(t)) = &mut self.overlay {
@@ -1524,19 +1668,45 @@ impl App {
             AppEvent::CodexOp(op) => {
                 self.chat_widget.submit_op(op);
             }
+            AppEvent::CheckpointCreated(commit_id) => {
+                self.timeline_checkpoints.push(TimelineCheckpoint {
+                    cell_count: self.transcript_cells.len(),
+                    commit_id,
+                });
+            }
+            AppEvent::TimelineDiffResult { target, diff } => {
+                if let Some(Overlay::Timeline(timeline)) = &mut self.overlay {
+                    timeline.on_diff_result(target, diff);
+                    tui.frame_requester().schedule_frame();
+                }
+            }
             AppEvent::DiffResult(text) => {
                 // Clear the in-progress state in the bottom pane
                 self.chat_widget.on_diff_complete();
-                // Enter alternate screen using TUI helper and build pager lines
+                // Enter alternate screen using TUI helper and open the diff review, falling
+                // back to plain lines for messages such as "not inside a git repository".
+                let _ = tui.enter_alt_screen();
+                self.overlay = Some(
+                    match DiffReviewOverlay::from_unified_diff(&text, "D I F F".to_string()) {
+                        Some(review) => Overlay::DiffReview(review),
+                        None => {
+                            let pager_lines: Vec<ratatui::text::Line<'static>> =
+                                if text.trim().is_empty() {
+                                    vec!["No changes detected.".italic().into()]
+                                } else {
+                                    text.lines().map(ansi_escape_line).collect()
+                                };
+                            Overlay::new_static_with_lines(pager_lines, "D I F F".to_string())
+                        }
+                    },
+                );
+                tui.frame_requester().schedule_frame();
+            }
+            AppEvent::InstructionsResult(layers) => {
                 let _ = tui.enter_alt_screen();
-                let pager_lines: Vec<ratatui::text::Line<'static>> = if text.trim().is_empty() {
-                    vec!["No changes detected.".italic().into()]
-                } else {
-                    text.lines().map(ansi_escape_line).collect()
-                };
                 self.overlay = Some(Overlay::new_static_with_lines(
-                    pager_lines,
-                    "D I F F".to_string(),
+                    instruction_lines(&layers, &self.config.cwd),
+                    INSTRUCTIONS_VIEW_TITLE.to_string(),
                 ));
                 tui.frame_requester().schedule_frame();
             }
@@ -1555,6 +1725,9 @@ impl App {
                     is_installed,
                 );
             }
+            AppEvent::ResolveLargePaste { text, attach } => {
+                self.chat_widget.resolve_large_paste(text, attach);
+            }
             AppEvent::StartFileSearch(query) => {
                 self.file_search.on_user_query(query);
             }
@@ -1579,9 +1752,19 @@ impl App {
             AppEvent::UpdatePersonality(personality) => {
                 self.on_update_personality(personality);
             }
+            AppEvent::UpdateVerbosity(verbosity) => {
+                self.config.model_verbosity = Some(verbosity);
+                self.chat_widget.set_verbosity(verbosity);
+            }
             AppEvent::OpenReasoningPopup { model } => {
                 self.chat_widget.open_reasoning_popup(model);
             }
+            AppEvent::OpenSettingEditor { key } => {
+                self.chat_widget.open_setting_editor(&key);
+            }
+            AppEvent::PersistSetting { key, value } => {
+                self.persist_setting(&key, &value).await;
+            }
             AppEvent::OpenAllModelsPopup { models } => {
                 self.chat_widget.open_all_models_popup(models);
             }
@@ -1779,6 +1962,7 @@ impl App {
                                         summary: None,
                                         collaboration_mode: None,
                                         personality: None,
+                                        verbosity: None,
                                     },
                                 ));
                                 self.app_event_tx.send(
@@ -1801,6 +1985,7 @@ impl App {
                                         summary: None,
                                         collaboration_mode: None,
                                         personality: None,
+                                        verbosity: None,
                                     },
                                 ));
                                 self.app_event_tx
@@ -1908,6 +2093,37 @@ impl App {
                     }
                 }
             }
+            AppEvent::PersistVerbositySelection { verbosity } => {
+                let profile = self.active_profile.as_deref();
+                match ConfigEditsBuilder::new(&self.config.codex_home)
+                    .with_profile(profile)
+                    .set_verbosity(Some(verbosity))
+                    .apply()
+                    .await
+                {
+                    Ok(()) => {
+                        let mut message = format!("Verbosity set to {verbosity}");
+                        if let Some(profile) = profile {
+                            message.push_str(" for ");
+                            message.push_str(profile);
+                            message.push_str(" profile");
+                        }
+                        self.chat_widget.add_info_message(message, None);
+                    }
+                    Err(err) => {
+                        tracing::error!(error = %err, "failed to persist verbosity selection");
+                        if let Some(profile) = profile {
+                            self.chat_widget.add_error_message(format!(
+                                "Failed to save verbosity for profile `{profile}`: {err}"
+                            ));
+                        } else {
+                            self.chat_widget.add_error_message(format!(
+                                "Failed to save default verbosity: {err}"
+                            ));
+                        }
+                    }
+                }
+            }
             AppEvent::UpdateAskForApprovalPolicy(policy) => {
                 self.runtime_approval_policy_override = Some(policy);
                 if let Err(err) = self.config.approval_policy.set(policy) {
@@ -1996,20 +2212,23 @@ impl App {
                         // Update the in-memory configs.
                         self.config.features.enable(*feature);
                         self.chat_widget.set_feature_enabled(*feature, true);
-                        builder = builder.set_feature_enabled(feature_key, true);
+                        builder = builder.set_experiment_enabled(feature_key, true);
                     } else {
                         // Update the in-memory configs.
                         self.config.features.disable(*feature);
                         self.chat_widget.set_feature_enabled(*feature, false);
                         if feature.default_enabled() {
-                            builder = builder.set_feature_enabled(feature_key, false);
+                            builder = builder.set_experiment_enabled(feature_key, false);
                         } else {
                             // If the feature already default to `false`, we drop the key
                             // in the config file so that the user does not miss the feature
                             // once it gets globally released.
-                            builder = builder.with_edits(vec![ConfigEdit::ClearPath {
-                                segments: vec!["features".to_string(), feature_key.to_string()],
-                            }]);
+                            builder =
+                                builder.with_edits(["features", "experimental"].map(|table| {
+                                    ConfigEdit::ClearPath {
+                                        segments: vec![table.to_string(), feature_key.to_string()],
+                                    }
+                                }));
                         }
                     }
                 }
@@ -2028,6 +2247,7 @@ impl App {
                                 summary: None,
                                 collaboration_mode: None,
                                 personality: None,
+                                verbosity: None,
                             }));
                     }
                 }
@@ -2120,6 +2340,61 @@ impl App {
             AppEvent::OpenAgentPicker => {
                 self.open_agent_picker();
             }
+            AppEvent::StartWorkers {
+                queue_path,
+                workers,
+            } => {
+                self.start_workers(tui, queue_path, workers).await;
+            }
+            AppEvent::MergeSessionWorktree => self.merge_session_worktree().await,
+            AppEvent::DiscardSessionWorktree { confirmed } => {
+                self.discard_session_worktree(confirmed).await;
+            }
+            AppEvent::StopWorkers => match &self.worker_pool {
+                Some(pool) if !pool.snapshot().is_finished() => {
+                    pool.stop();
+                    self.chat_widget.add_info_message(
+                        "Stopping workers; in-progress tasks return to the queue.".to_string(),
+                        None,
+                    );
+                }
+                _ => self
+                    .chat_widget
+                    .add_info_message("No workers are running.".to_string(), None),
+            },
+            AppEvent::OpenTerminal {
+                process_id,
+                call_id,
+                command_display,
+                recent_output,
+            } => {
+                let _ = tui.enter_alt_screen();
+                self.overlay = Some(Overlay::Terminal(TerminalOverlay::new(
+                    process_id,
+                    call_id,
+                    command_display,
+                    recent_output,
+                    self.app_event_tx.clone(),
+                )));
+                tui.frame_requester().schedule_frame();
+            }
+            AppEvent::OpenWorkersView => match &self.worker_pool {
+                Some(pool) => {
+                    let _ = tui.enter_alt_screen();
+                    self.overlay = Some(Overlay::new_workers(pool.subscribe()));
+                    tui.frame_requester().schedule_frame();
+                }
+                None => self.chat_widget.add_info_message(
+                    "No workers have been started.".to_string(),
+                    Some("Use /workers start <queue-file> [count].".to_string()),
+                ),
+            },
+            AppEvent::WorkersFinished { done, failed } => {
+                self.chat_widget.add_info_message(
+                    format!("Workers finished: {done} done, {failed} failed."),
+                    Some("Use /workers to review results and branches.".to_string()),
+                );
+            }
             AppEvent::SelectAgentThread(thread_id) => {
                 self.select_agent_thread(tui, thread_id).await?;
             }
@@ -2172,14 +2447,21 @@ impl App {
             AppEvent::ManageSkillsClosed => {
                 self.chat_widget.handle_manage_skills_closed();
             }
+            AppEvent::ApprovalNotificationAction { key, action } => match action {
+                NotificationAction::Approve => self.chat_widget.resolve_approval(&key, true),
+                NotificationAction::Deny => self.chat_widget.resolve_approval(&key, false),
+                // Open only dismisses the notification; the request stays
+                // in the approval overlay.
+                NotificationAction::Open => {}
+            },
             AppEvent::FullScreenApprovalRequest(request) => match request {
                 ApprovalRequest::ApplyPatch { cwd, changes, .. } => {
                     let _ = tui.enter_alt_screen();
-                    let diff_summary = DiffSummary::new(changes, cwd);
-                    self.overlay = Some(Overlay::new_static_with_renderables(
-                        vec![diff_summary.into()],
+                    self.overlay = Some(Overlay::DiffReview(DiffReviewOverlay::from_changes(
+                        &changes,
+                        &cwd,
                         "P A T C H".to_string(),
-                    ));
+                    )));
                 }
                 ApprovalRequest::Exec { command, .. } => {
                     let _ = tui.enter_alt_screen();
@@ -2232,6 +2514,22 @@ impl App {
     }
 
     fn handle_active_thread_event(&mut self, tui: &mut tui::Tui, event: Event) -> Result<()> {
+        if let Some(Overlay::Terminal(terminal)) = &mut self.overlay {
+            match &event.msg {
+                EventMsg::ExecCommandOutputDelta(delta) if delta.call_id == terminal.call_id() => {
+                    terminal.push_output(&delta.chunk);
+                    tui.frame_requester().schedule_frame();
+                }
+                // Emitted when the process exits, however it ended. Terminals
+                // outlive the turn that started them, so turn events say
+                // nothing about whether it still runs.
+                EventMsg::ExecCommandEnd(end) if end.call_id == terminal.call_id() => {
+                    terminal.mark_exited();
+                    tui.frame_requester().schedule_frame();
+                }
+                _ => {}
+            }
+        }
         self.handle_codex_event_now(event);
         if self.backtrack_render_pending {
             tui.frame_requester().schedule_frame();
@@ -2239,6 +2537,134 @@ impl App {
         Ok(())
     }
 
+    async fn merge_session_worktree(&mut self) {
+        let Some(worktree) = self.session_worktree.clone() else {
+            self.chat_widget
+                .add_error_message(NO_SESSION_WORKTREE_MESSAGE.to_string());
+            return;
+        };
+        match worktree.merge().await {
+            Ok(outcome) => {
+                self.leave_session_worktree(&worktree);
+                let message = match outcome {
+                    MergeOutcome::Merged { into, commits } => format!(
+                        "Merged {commits} commit(s) from {} into {into}.",
+                        worktree.branch()
+                    ),
+                    MergeOutcome::NothingToMerge => {
+                        "The session made no changes; removed its worktree.".to_string()
+                    }
+                };
+                self.chat_widget.add_info_message(message, None);
+            }
+            Err(err) => self.chat_widget.add_error_message(format!(
+                "Failed to merge {}: {err}. The worktree was kept, so you can fix the checkout and run /merge again.",
+                worktree.branch()
+            )),
+        }
+    }
+
+    async fn discard_session_worktree(&mut self, confirmed: bool) {
+        let Some(worktree) = self.session_worktree.clone() else {
+            self.chat_widget
+                .add_error_message(NO_SESSION_WORKTREE_MESSAGE.to_string());
+            return;
+        };
+        if !confirmed {
+            self.chat_widget
+                .open_discard_worktree_confirmation(worktree.branch());
+            return;
+        }
+        match worktree.discard().await {
+            Ok(()) => {
+                self.leave_session_worktree(&worktree);
+                self.chat_widget.add_info_message(
+                    format!("Discarded the changes on {}.", worktree.branch()),
+                    None,
+                );
+            }
+            Err(err) => self
+                .chat_widget
+                .add_error_message(format!("Failed to discard the worktree: {err}")),
+        }
+    }
+
+    /// Point the session back at the checkout it was launched from.
+    fn leave_session_worktree(&mut self, worktree: &SessionWorktree) {
+        self.session_worktree = None;
+        let cwd = worktree.original_cwd().to_path_buf();
+        self.config.cwd = cwd.clone();
+        self.file_search.update_search_dir(cwd.clone());
+        self.chat_widget.set_cwd(cwd.clone());
+        self.app_event_tx
+            .send(AppEvent::CodexOp(Op::OverrideTurnContext {
+                cwd: Some(cwd),
+                approval_policy: None,
+                sandbox_policy: None,
+                windows_sandbox_level: None,
+                model: None,
+                effort: None,
+                summary: None,
+                collaboration_mode: None,
+                personality: None,
+                verbosity: None,
+            }));
+    }
+
+    async fn start_workers(&mut self, tui: &mut tui::Tui, queue_path: PathBuf, workers: usize) {
+        if let Some(pool) = &self.worker_pool
+            && !pool.snapshot().is_finished()
+        {
+            self.chat_widget.add_error_message(
+                "Workers are already running; use /workers stop first.".to_string(),
+            );
+            return;
+        }
+        let options = WorkerPoolOptions {
+            queue_path,
+            workers,
+        };
+        let pool = match WorkerPool::start(self.server.clone(), self.config.clone(), options).await
+        {
+            Ok(pool) => pool,
+            Err(err) => {
+                self.chat_widget
+                    .add_error_message(format!("Failed to start workers: {err}"));
+                return;
+            }
+        };
+
+        // Redraw the coordinator view on every update and report once all workers stop.
+        let mut snapshot_rx = pool.subscribe();
+        let frame_requester = tui.frame_requester();
+        let app_event_tx = self.app_event_tx.clone();
+        tokio::spawn(async move {
+            while snapshot_rx.changed().await.is_ok() {
+                frame_requester.schedule_frame();
+                let snapshot = snapshot_rx.borrow_and_update().clone();
+                if snapshot.is_finished() {
+                    let count = |status| {
+                        snapshot
+                            .tasks
+                            .iter()
+                            .filter(|task| task.status == status)
+                            .count()
+                    };
+                    app_event_tx.send(AppEvent::WorkersFinished {
+                        done: count(TaskStatus::Done),
+                        failed: count(TaskStatus::Failed),
+                    });
+                    break;
+                }
+            }
+        });
+
+        let _ = tui.enter_alt_screen();
+        self.overlay = Some(Overlay::new_workers(pool.subscribe()));
+        self.worker_pool = Some(pool);
+        tui.frame_requester().schedule_frame();
+    }
+
     async fn handle_thread_created(&mut self, thread_id: ThreadId) -> Result<()> {
         if self.thread_event_channels.contains_key(&thread_id) {
             return Ok(());
@@ -2414,6 +2840,21 @@ impl App {
                 self.overlay = Some(Overlay::new_transcript(self.transcript_cells.clone()));
                 tui.frame_requester().schedule_frame();
             }
+            KeyEvent {
+                code: KeyCode::Char('o'),
+                modifiers: crossterm::event::KeyModifiers::CONTROL,
+                kind: KeyEventKind::Press,
+                ..
+            } => {
+                let _ = tui.enter_alt_screen();
+                self.overlay = Some(Overlay::new_timeline(
+                    self.transcript_cells.clone(),
+                    self.timeline_checkpoints.clone(),
+                    self.config.cwd.clone(),
+                    self.app_event_tx.clone(),
+                ));
+                tui.frame_requester().schedule_frame();
+            }
             KeyEvent {
                 code: KeyCode::Char('g'),
                 modifiers: crossterm::event::KeyModifiers::CONTROL,
@@ -2628,6 +3069,7 @@ mod tests {
             runtime_sandbox_policy_override: None,
             file_search,
             transcript_cells: Vec::new(),
+            timeline_checkpoints: Vec::new(),
             overlay: None,
             deferred_history_lines: Vec::new(),
             has_emitted_history_lines: false,
@@ -2646,6 +3088,8 @@ mod tests {
             primary_thread_id: None,
             primary_session_configured: None,
             pending_primary_events: VecDeque::new(),
+            worker_pool: None,
+            session_worktree: None,
         }
     }
 
@@ -2681,6 +3125,7 @@ mod tests {
                 runtime_sandbox_policy_override: None,
                 file_search,
                 transcript_cells: Vec::new(),
+                timeline_checkpoints: Vec::new(),
                 overlay: None,
                 deferred_history_lines: Vec::new(),
                 has_emitted_history_lines: false,
@@ -2699,6 +3144,8 @@ mod tests {
                 primary_thread_id: None,
                 primary_session_configured: None,
                 pending_primary_events: VecDeque::new(),
+                worker_pool: None,
+                session_worktree: None,
             },
             rx,
             op_rx,
@@ -3016,6 +3463,38 @@ mod tests {
         assert_eq!(rollback_turns, Some(1));
     }
 
+    #[tokio::test]
+    async fn rewind_requests_file_restore_for_turns_from_the_selected_one() {
+        let (mut app, _app_event_rx, mut op_rx) = make_test_app_with_channels().await;
+        let user_cell = |text: &str| -> Arc<dyn HistoryCell> {
+            Arc::new(UserHistoryCell {
+                message: text.to_string(),
+                text_elements: Vec::new(),
+                local_image_paths: Vec::new(),
+            }) as Arc<dyn HistoryCell>
+        };
+        app.transcript_cells = vec![user_cell("first"), user_cell("second"), user_cell("third")];
+
+        app.rewind_to_turn(Some(4));
+        assert!(op_rx.try_recv().is_err());
+
+        app.rewind_to_turn(Some(2));
+        let mut rewind_turns = None;
+        while let Ok(op) = op_rx.try_recv() {
+            if let Op::Rewind { num_turns } = op {
+                rewind_turns = Some(num_turns);
+            }
+        }
+        assert_eq!(rewind_turns, Some(2));
+        let pending = app
+            .backtrack
+            .pending_rollback
+            .as_ref()
+            .expect("pending rewind");
+        assert_eq!(pending.selection.nth_user_message, 1);
+        assert_eq!(pending.selection.prefill, "second");
+    }
+
     #[tokio::test]
     async fn new_session_requests_shutdown_for_previous_conversation() {
         let (mut app, mut app_event_rx, mut op_rx) = make_test_app_with_channels().await;
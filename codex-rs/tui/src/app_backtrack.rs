@@ -15,6 +15,9 @@
 //! - Only after receiving `EventMsg::ThreadRolledBack` do we trim local transcript state and
 //!   schedule a one-time scrollback refresh.
 //!
+//! `/rewind [N]` goes through the same pending-rollback path, but sends `Op::Rewind` so core
+//! also restores the workspace to the snapshot taken when turn N started.
+//!
 //! The transcript overlay (`Ctrl+T`) renders committed transcript cells plus a render-only live
 //! tail derived from the current in-flight `ChatWidget.active_cell`.
 //!
@@ -99,15 +102,21 @@ pub(crate) struct PendingBacktrackRollback {
 impl App {
     /// Route overlay events while the transcript overlay is active.
     ///
-    /// If backtrack preview is active, Esc / Left steps selection, Right steps forward, Enter
-    /// confirms. Otherwise, Esc begins preview mode and all other events are forwarded to the
-    /// overlay.
+    /// While the transcript's `/` search prompt is open, every event goes to the overlay so the
+    /// query can be typed, submitted, or cancelled. Otherwise, if backtrack preview is active,
+    /// Esc / Left steps selection, Right steps forward, Enter confirms; and outside preview, Esc
+    /// begins preview mode and all other events are forwarded to the overlay.
     pub(crate) async fn handle_backtrack_overlay_event(
         &mut self,
         tui: &mut tui::Tui,
         event: TuiEvent,
     ) -> Result<bool> {
-        if self.backtrack.overlay_preview_active {
+        if let Some(Overlay::Transcript(t)) = &self.overlay
+            && t.is_editing_search()
+        {
+            self.overlay_forward_event(tui, event)?;
+            Ok(true)
+        } else if self.backtrack.overlay_preview_active {
             match event {
                 TuiEvent::Key(KeyEvent {
                     code: KeyCode::Esc,
@@ -186,6 +195,31 @@ impl App {
     /// The composer prefill is applied immediately as a UX convenience; it does not imply that
     /// core has accepted the rollback.
     pub(crate) fn apply_backtrack_rollback(&mut self, selection: BacktrackSelection) {
+        self.request_rollback(selection, |num_turns| Op::ThreadRollback { num_turns });
+    }
+
+    /// Handle `/rewind`: roll back to just before user turn `turn` (1-based, oldest first),
+    /// or before the latest turn, and have core restore the files to that point too.
+    pub(crate) fn rewind_to_turn(&mut self, turn: Option<usize>) {
+        let user_total = user_count(&self.transcript_cells);
+        let turn = turn.unwrap_or(user_total);
+        if user_total == 0 {
+            self.chat_widget
+                .add_error_message("There are no turns to rewind.".to_string());
+            return;
+        }
+        if turn == 0 || turn > user_total {
+            self.chat_widget.add_error_message(format!(
+                "Turn {turn} does not exist; choose a turn from 1 to {user_total}."
+            ));
+            return;
+        }
+        let selection = self.user_message_selection(turn - 1);
+        self.request_rollback(selection, |num_turns| Op::Rewind { num_turns });
+    }
+
+    /// Send the rollback `op` for `selection` and remember it until core confirms.
+    fn request_rollback(&mut self, selection: BacktrackSelection, op: fn(u32) -> Op) {
         let user_total = user_count(&self.transcript_cells);
         if user_total == 0 {
             return;
@@ -210,7 +244,7 @@ impl App {
             selection,
             thread_id: self.chat_widget.thread_id(),
         });
-        self.chat_widget.submit_op(Op::ThreadRollback { num_turns });
+        self.chat_widget.submit_op(op(num_turns));
         if !prefill.is_empty() || !text_elements.is_empty() || !local_image_paths.is_empty() {
             self.chat_widget
                 .set_composer_text(prefill, text_elements, local_image_paths);
@@ -486,7 +520,10 @@ impl App {
         if self.chat_widget.thread_id() != Some(base_id) {
             return None;
         }
+        Some(self.user_message_selection(nth_user_message))
+    }
 
+    fn user_message_selection(&self, nth_user_message: usize) -> BacktrackSelection {
         let (prefill, text_elements, local_image_paths) =
             nth_user_position(&self.transcript_cells, nth_user_message)
                 .and_then(|idx| self.transcript_cells.get(idx))
@@ -500,17 +537,20 @@ impl App {
                 })
                 .unwrap_or_else(|| (String::new(), Vec::new(), Vec::new()));
 
-        Some(BacktrackSelection {
+        BacktrackSelection {
             nth_user_message,
             prefill,
             text_elements,
             local_image_paths,
-        })
+        }
     }
 
     /// Trim `transcript_cells` to preserve only content before the selected user message.
     fn trim_transcript_for_backtrack(&mut self, nth_user_message: usize) {
         trim_transcript_cells_to_nth_user(&mut self.transcript_cells, nth_user_message);
+        let kept = self.transcript_cells.len();
+        self.timeline_checkpoints
+            .retain(|checkpoint| checkpoint.cell_count <= kept);
     }
 }
 
@@ -12,6 +12,7 @@ use std::path::PathBuf;
 
 use codex_chatgpt::connectors::AppInfo;
 use codex_common::approval_presets::ApprovalPreset;
+use codex_core::project_doc::InstructionLayer;
 use codex_core::protocol::Event;
 use codex_core::protocol::RateLimitSnapshot;
 use codex_file_search::FileMatch;
@@ -19,13 +20,16 @@ use codex_protocol::ThreadId;
 use codex_protocol::openai_models::ModelPreset;
 
 use crate::bottom_pane::ApprovalRequest;
+use crate::bottom_pane::ApprovalRequestKey;
 use crate::history_cell::HistoryCell;
+use crate::notifications::NotificationAction;
 
 use codex_core::features::Feature;
 use codex_core::protocol::AskForApproval;
 use codex_core::protocol::SandboxPolicy;
 use codex_protocol::config_types::CollaborationModeMask;
 use codex_protocol::config_types::Personality;
+use codex_protocol::config_types::Verbosity;
 use codex_protocol::openai_models::ReasoningEffort;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -55,6 +59,32 @@ pub(crate) enum AppEvent {
     /// Switch the active thread to the selected agent.
     SelectAgentThread(ThreadId),
 
+    /// Start a worker pool draining the task queue at `queue_path`.
+    StartWorkers {
+        queue_path: PathBuf,
+        workers: usize,
+    },
+
+    /// Interrupt the running worker pool.
+    StopWorkers,
+
+    /// Open the worker coordinator view.
+    OpenWorkersView,
+
+    /// Take over a background terminal and show it full screen.
+    OpenTerminal {
+        process_id: String,
+        call_id: String,
+        command_display: String,
+        recent_output: String,
+    },
+
+    /// Every worker in the pool has stopped.
+    WorkersFinished {
+        done: usize,
+        failed: usize,
+    },
+
     /// Start a new session.
     NewSession,
 
@@ -64,6 +94,25 @@ pub(crate) enum AppEvent {
     /// Fork the current session into a new thread.
     ForkCurrentSession,
 
+    /// Open the `/history` session browser.
+    OpenHistoryBrowser,
+
+    /// Commit the session worktree's changes and merge its branch into the
+    /// checkout (`/merge`).
+    MergeSessionWorktree,
+
+    /// Drop the session worktree and its branch (`/discard`). Asks for
+    /// confirmation first unless `confirmed`.
+    DiscardSessionWorktree {
+        confirmed: bool,
+    },
+
+    /// Restore the conversation and workspace to just before user turn `turn`
+    /// (1-based), or before the latest turn when `None`.
+    Rewind {
+        turn: Option<usize>,
+    },
+
     /// Request to exit the application.
     ///
     /// Use `ShutdownFirst` for user-initiated quits so core cleanup runs and the
@@ -101,6 +150,20 @@ pub(crate) enum AppEvent {
     /// Result of computing a `/diff` command.
     DiffResult(String),
 
+    /// Core took a checkpoint (ghost snapshot) of the workspace; the
+    /// timeline diffs these to show the changes at each point.
+    CheckpointCreated(String),
+
+    /// Result of computing a timeline diff in the background; `target` is the
+    /// checkpoint index, or `None` for the working tree.
+    TimelineDiffResult {
+        target: Option<usize>,
+        diff: Result<String, String>,
+    },
+
+    /// Result of gathering the instruction layers for `/instructions`.
+    InstructionsResult(Vec<InstructionLayer>),
+
     /// Open the app link view in the bottom pane.
     OpenAppLink {
         title: String,
@@ -110,6 +173,13 @@ pub(crate) enum AppEvent {
         is_installed: bool,
     },
 
+    /// Resolve a paste held back for exceeding `tui.paste_attach_threshold`:
+    /// save it to a file and reference it (`attach`), or insert it inline.
+    ResolveLargePaste {
+        text: String,
+        attach: bool,
+    },
+
     InsertHistoryCell(Box<dyn HistoryCell>),
 
     StartCommitAnimation,
@@ -128,6 +198,9 @@ pub(crate) enum AppEvent {
     /// Update the current personality in the running app and widget.
     UpdatePersonality(Personality),
 
+    /// Update the current output verbosity in the running app and widget.
+    UpdateVerbosity(Verbosity),
+
     /// Persist the selected model and reasoning effort to the appropriate config.
     PersistModelSelection {
         model: String,
@@ -139,6 +212,22 @@ pub(crate) enum AppEvent {
         personality: Personality,
     },
 
+    /// Persist the selected output verbosity to the appropriate config.
+    PersistVerbositySelection {
+        verbosity: Verbosity,
+    },
+
+    /// Open the editor for one `/settings` entry.
+    OpenSettingEditor {
+        key: String,
+    },
+
+    /// Validate and write a `/settings` value to the config file it belongs in.
+    PersistSetting {
+        key: String,
+        value: String,
+    },
+
     /// Open the reasoning selection popup after picking a model.
     OpenReasoningPopup {
         model: ModelPreset,
@@ -279,6 +368,13 @@ pub(crate) enum AppEvent {
     /// Open the approval popup.
     FullScreenApprovalRequest(ApprovalRequest),
 
+    /// A button was clicked on the desktop notification for an approval
+    /// request.
+    ApprovalNotificationAction {
+        key: ApprovalRequestKey,
+        action: NotificationAction,
+    },
+
     /// Open the feedback note entry overlay after the user selects a category.
     OpenFeedbackNote {
         category: FeedbackCategory,
@@ -1,10 +1,13 @@
 use std::collections::HashMap;
+use std::path::Path;
 use std::path::PathBuf;
 
 use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
 use crate::bottom_pane::BottomPaneView;
 use crate::bottom_pane::CancellationEvent;
+use crate::bottom_pane::hunk_selection_view::HunkSelectionOutcome;
+use crate::bottom_pane::hunk_selection_view::HunkSelectionView;
 use crate::bottom_pane::list_selection_view::ListSelectionView;
 use crate::bottom_pane::list_selection_view::SelectionItem;
 use crate::bottom_pane::list_selection_view::SelectionViewParams;
@@ -22,6 +25,8 @@ use codex_core::protocol::ElicitationAction;
 use codex_core::protocol::ExecPolicyAmendment;
 use codex_core::protocol::FileChange;
 use codex_core::protocol::Op;
+use codex_core::protocol::PatchHunkReport;
+use codex_core::protocol::PatchHunkStatus;
 use codex_core::protocol::ReviewDecision;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
@@ -50,6 +55,8 @@ pub(crate) enum ApprovalRequest {
         reason: Option<String>,
         cwd: PathBuf,
         changes: HashMap<PathBuf, FileChange>,
+        /// Dry-run result per hunk; fuzzy and conflicting hunks are flagged.
+        hunks: Vec<PatchHunkReport>,
     },
     McpElicitation {
         server_name: String,
@@ -58,6 +65,35 @@ pub(crate) enum ApprovalRequest {
     },
 }
 
+impl ApprovalRequest {
+    pub(crate) fn key(&self) -> ApprovalRequestKey {
+        match self {
+            ApprovalRequest::Exec { id, .. } => ApprovalRequestKey::Exec(id.clone()),
+            ApprovalRequest::ApplyPatch { id, .. } => ApprovalRequestKey::ApplyPatch(id.clone()),
+            ApprovalRequest::McpElicitation {
+                server_name,
+                request_id,
+                ..
+            } => ApprovalRequestKey::McpElicitation {
+                server_name: server_name.clone(),
+                request_id: request_id.clone(),
+            },
+        }
+    }
+}
+
+/// Identifies an approval request so it can be answered from outside the
+/// overlay, e.g. from a desktop notification button.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum ApprovalRequestKey {
+    Exec(String),
+    ApplyPatch(String),
+    McpElicitation {
+        server_name: String,
+        request_id: RequestId,
+    },
+}
+
 /// Modal overlay asking the user to approve or deny one or more requests.
 pub(crate) struct ApprovalOverlay {
     current_request: Option<ApprovalRequest>,
@@ -69,6 +105,8 @@ pub(crate) struct ApprovalOverlay {
     current_complete: bool,
     done: bool,
     features: Features,
+    /// Shown in place of the options while choosing hunks of a patch.
+    hunk_selection: Option<HunkSelectionView>,
 }
 
 impl ApprovalOverlay {
@@ -83,6 +121,7 @@ impl ApprovalOverlay {
             current_complete: false,
             done: false,
             features,
+            hunk_selection: None,
         };
         view.set_current(request);
         view
@@ -97,6 +136,7 @@ impl ApprovalOverlay {
         let ApprovalRequestState { variant, header } = ApprovalRequestState::from(request);
         self.current_variant = Some(variant.clone());
         self.current_complete = false;
+        self.hunk_selection = None;
         let (options, params) = Self::build_options(variant, header, &self.features);
         self.options = options;
         self.list = ListSelectionView::new(params, self.app_event_tx.clone());
@@ -115,8 +155,8 @@ impl ApprovalOverlay {
                 exec_options(proposed_execpolicy_amendment.clone(), features),
                 "Would you like to run the following command?".to_string(),
             ),
-            ApprovalVariant::ApplyPatch { .. } => (
-                patch_options(),
+            ApprovalVariant::ApplyPatch { hunks, paths, .. } => (
+                patch_options(hunks.len() > 1, paths),
                 "Would you like to make the following edits?".to_string(),
             ),
             ApprovalVariant::McpElicitation { server_name, .. } => (
@@ -174,6 +214,10 @@ impl ApprovalOverlay {
                 (ApprovalVariant::ApplyPatch { id, .. }, ApprovalDecision::Review(decision)) => {
                     self.handle_patch_decision(id, decision.clone());
                 }
+                (ApprovalVariant::ApplyPatch { cwd, hunks, .. }, ApprovalDecision::ChooseHunks) => {
+                    self.hunk_selection = Some(HunkSelectionView::new(hunks.clone(), cwd.clone()));
+                    return;
+                }
                 (
                     ApprovalVariant::McpElicitation {
                         server_name,
@@ -221,6 +265,47 @@ impl ApprovalOverlay {
             }));
     }
 
+    /// Send a plain approve or deny decision for `request`.
+    fn send_quick_decision(&self, request: &ApprovalRequest, approve: bool) {
+        let review_decision = if approve {
+            ReviewDecision::Approved
+        } else {
+            ReviewDecision::Abort
+        };
+        match request {
+            ApprovalRequest::Exec { id, command, .. } => {
+                self.handle_exec_decision(id, command, review_decision);
+            }
+            ApprovalRequest::ApplyPatch { id, .. } => {
+                self.handle_patch_decision(id, review_decision);
+            }
+            ApprovalRequest::McpElicitation {
+                server_name,
+                request_id,
+                ..
+            } => {
+                let action = if approve {
+                    ElicitationAction::Accept
+                } else {
+                    ElicitationAction::Decline
+                };
+                self.handle_elicitation_decision(server_name, request_id, action);
+            }
+        }
+    }
+
+    fn handle_hunk_selection(&mut self, outcome: HunkSelectionOutcome) {
+        self.hunk_selection = None;
+        let HunkSelectionOutcome::Decided(decision) = outcome else {
+            return;
+        };
+        if let Some(ApprovalVariant::ApplyPatch { id, .. }) = self.current_variant.as_ref() {
+            self.handle_patch_decision(id, decision);
+        }
+        self.current_complete = true;
+        self.advance_queue();
+    }
+
     fn advance_queue(&mut self) {
         if let Some(next) = self.queue.pop() {
             self.set_current(next);
@@ -263,6 +348,12 @@ impl ApprovalOverlay {
 
 impl BottomPaneView for ApprovalOverlay {
     fn handle_key_event(&mut self, key_event: KeyEvent) {
+        if let Some(selection) = self.hunk_selection.as_mut() {
+            if let Some(outcome) = selection.handle_key_event(key_event) {
+                self.handle_hunk_selection(outcome);
+            }
+            return;
+        }
         if self.try_handle_shortcut(&key_event) {
             return;
         }
@@ -314,19 +405,48 @@ impl BottomPaneView for ApprovalOverlay {
         self.enqueue_request(request);
         None
     }
+
+    fn resolve_approval(&mut self, key: &ApprovalRequestKey, approve: bool) -> bool {
+        if !self.current_complete
+            && let Some(request) = self
+                .current_request
+                .clone()
+                .filter(|request| request.key() == *key)
+        {
+            self.send_quick_decision(&request, approve);
+            self.current_complete = true;
+            self.advance_queue();
+            return true;
+        }
+        if let Some(idx) = self.queue.iter().position(|request| request.key() == *key) {
+            let request = self.queue.remove(idx);
+            self.send_quick_decision(&request, approve);
+            return true;
+        }
+        false
+    }
 }
 
 impl Renderable for ApprovalOverlay {
     fn desired_height(&self, width: u16) -> u16 {
-        self.list.desired_height(width)
+        match &self.hunk_selection {
+            Some(selection) => selection.desired_height(width),
+            None => self.list.desired_height(width),
+        }
     }
 
     fn render(&self, area: Rect, buf: &mut Buffer) {
-        self.list.render(area, buf);
+        match &self.hunk_selection {
+            Some(selection) => selection.render(area, buf),
+            None => self.list.render(area, buf),
+        }
     }
 
     fn cursor_pos(&self, area: Rect) -> Option<(u16, u16)> {
-        self.list.cursor_pos(area)
+        match &self.hunk_selection {
+            Some(_) => None,
+            None => self.list.cursor_pos(area),
+        }
     }
 }
 
@@ -369,6 +489,7 @@ impl From<ApprovalRequest> for ApprovalRequestState {
                 reason,
                 cwd,
                 changes,
+                hunks,
             } => {
                 let mut header: Vec<Box<dyn Renderable>> = Vec::new();
                 if let Some(reason) = reason
@@ -380,9 +501,22 @@ impl From<ApprovalRequest> for ApprovalRequestState {
                     ));
                     header.push(Box::new(Line::from("")));
                 }
-                header.push(DiffSummary::new(changes, cwd).into());
+                let hunk_lines = hunk_report_lines(&hunks, &cwd);
+                if !hunk_lines.is_empty() {
+                    header.push(Box::new(
+                        Paragraph::new(hunk_lines).wrap(Wrap { trim: false }),
+                    ));
+                    header.push(Box::new(Line::from("")));
+                }
+                let paths = changed_paths(&changes, &cwd);
+                header.push(DiffSummary::new(changes, cwd.clone()).into());
                 Self {
-                    variant: ApprovalVariant::ApplyPatch { id },
+                    variant: ApprovalVariant::ApplyPatch {
+                        id,
+                        cwd,
+                        hunks,
+                        paths,
+                    },
                     header: Box::new(ColumnRenderable::with(header)),
                 }
             }
@@ -409,6 +543,34 @@ impl From<ApprovalRequest> for ApprovalRequestState {
     }
 }
 
+/// One line per hunk that did not match verbatim, conflicts first.
+fn hunk_report_lines(hunks: &[PatchHunkReport], cwd: &Path) -> Vec<Line<'static>> {
+    let mut flagged: Vec<&PatchHunkReport> = hunks
+        .iter()
+        .filter(|hunk| hunk.status != PatchHunkStatus::Clean)
+        .collect();
+    flagged.sort_by_key(|hunk| hunk.status != PatchHunkStatus::Conflict);
+    flagged
+        .into_iter()
+        .map(|hunk| {
+            let path = hunk.path.strip_prefix(cwd).unwrap_or(&hunk.path).display();
+            let at = hunk
+                .line
+                .map(|line| format!(" (line {line})"))
+                .unwrap_or_default();
+            let label = match hunk.status {
+                PatchHunkStatus::Conflict => "conflict".red().bold(),
+                PatchHunkStatus::Fuzzy | PatchHunkStatus::Clean => "fuzzy".dim(),
+            };
+            let mut spans = vec![label, format!(" {path} hunk {}{at}", hunk.hunk).into()];
+            if let Some(detail) = &hunk.detail {
+                spans.push(format!(": {detail}").dim());
+            }
+            Line::from(spans)
+        })
+        .collect()
+}
+
 #[derive(Clone)]
 enum ApprovalVariant {
     Exec {
@@ -418,6 +580,10 @@ enum ApprovalVariant {
     },
     ApplyPatch {
         id: String,
+        cwd: PathBuf,
+        hunks: Vec<PatchHunkReport>,
+        /// Absolute paths the patch writes, deletes, or moves to.
+        paths: Vec<String>,
     },
     McpElicitation {
         server_name: String,
@@ -429,6 +595,8 @@ enum ApprovalVariant {
 enum ApprovalDecision {
     Review(ReviewDecision),
     McpElicitation(ElicitationAction),
+    /// Open the hunk checklist instead of deciding right away.
+    ChooseHunks,
 }
 
 #[derive(Clone)]
@@ -490,8 +658,8 @@ fn exec_options(
     .collect()
 }
 
-fn patch_options() -> Vec<ApprovalOption> {
-    vec![
+fn patch_options(multiple_hunks: bool, paths: &[String]) -> Vec<ApprovalOption> {
+    let mut options = vec![
         ApprovalOption {
             label: "Yes, proceed".to_string(),
             decision: ApprovalDecision::Review(ReviewDecision::Approved),
@@ -504,13 +672,50 @@ fn patch_options() -> Vec<ApprovalOption> {
             display_shortcut: None,
             additional_shortcuts: vec![key_hint::plain(KeyCode::Char('a'))],
         },
-        ApprovalOption {
-            label: "No, and tell Codex what to do differently".to_string(),
-            decision: ApprovalDecision::Review(ReviewDecision::Abort),
-            display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
-            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
-        },
-    ]
+    ];
+    if !paths.is_empty() {
+        options.push(ApprovalOption {
+            label: "Yes, and always allow changes to these files".to_string(),
+            decision: ApprovalDecision::Review(ReviewDecision::ApprovedAlwaysForPaths {
+                globs: paths.to_vec(),
+            }),
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('r'))],
+        });
+    }
+    if multiple_hunks {
+        options.push(ApprovalOption {
+            label: "Yes, but only some of the hunks".to_string(),
+            decision: ApprovalDecision::ChooseHunks,
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('h'))],
+        });
+    }
+    options.push(ApprovalOption {
+        label: "No, and tell Codex what to do differently".to_string(),
+        decision: ApprovalDecision::Review(ReviewDecision::Abort),
+        display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
+        additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
+    });
+    options
+}
+
+/// Sorted absolute paths of the files in `changes`, including move targets.
+fn changed_paths(changes: &HashMap<PathBuf, FileChange>, cwd: &Path) -> Vec<String> {
+    let mut paths: Vec<String> = changes
+        .iter()
+        .flat_map(|(path, change)| {
+            let move_path = match change {
+                FileChange::Update { move_path, .. } => move_path.as_ref(),
+                FileChange::Add { .. } | FileChange::Delete { .. } => None,
+            };
+            std::iter::once(path).chain(move_path)
+        })
+        .map(|path| cwd.join(path).to_string_lossy().into_owned())
+        .collect();
+    paths.sort();
+    paths.dedup();
+    paths
 }
 
 fn elicitation_options() -> Vec<ApprovalOption> {
@@ -540,6 +745,7 @@ fn elicitation_options() -> Vec<ApprovalOption> {
 mod tests {
     use super::*;
     use crate::app_event::AppEvent;
+    use codex_core::protocol::PatchHunkId;
     use pretty_assertions::assert_eq;
     use tokio::sync::mpsc::unbounded_channel;
 
@@ -552,6 +758,49 @@ mod tests {
         }
     }
 
+    #[test]
+    fn hunk_report_lists_conflicts_before_fuzzy_hunks() {
+        let hunk = |index, line, status, detail: Option<&str>| PatchHunkReport {
+            path: PathBuf::from("/repo/src/lib.rs"),
+            hunk: index,
+            line,
+            status,
+            detail: detail.map(str::to_string),
+        };
+        let hunks = vec![
+            hunk(1, Some(3), PatchHunkStatus::Clean, None),
+            hunk(
+                2,
+                Some(40),
+                PatchHunkStatus::Fuzzy,
+                Some("matched ignoring indentation"),
+            ),
+            hunk(
+                3,
+                None,
+                PatchHunkStatus::Conflict,
+                Some("expected lines not found"),
+            ),
+        ];
+
+        let rendered: Vec<String> = hunk_report_lines(&hunks, Path::new("/repo"))
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            rendered,
+            vec![
+                "conflict src/lib.rs hunk 3: expected lines not found".to_string(),
+                "fuzzy src/lib.rs hunk 2 (line 40): matched ignoring indentation".to_string(),
+            ]
+        );
+    }
+
     #[test]
     fn ctrl_c_aborts_and_clears_queue() {
         let (tx, _rx) = unbounded_channel::<AppEvent>();
@@ -563,6 +812,26 @@ mod tests {
         assert!(view.is_complete());
     }
 
+    #[test]
+    fn notification_action_resolves_matching_request() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let mut view = ApprovalOverlay::new(make_exec_request(), tx, Features::with_defaults());
+
+        assert!(!view.resolve_approval(&ApprovalRequestKey::Exec("other".to_string()), true));
+        assert!(view.resolve_approval(&ApprovalRequestKey::Exec("test".to_string()), false));
+        assert!(view.is_complete());
+
+        let mut decision = None;
+        while let Ok(ev) = rx.try_recv() {
+            if let AppEvent::CodexOp(Op::ExecApproval { id, decision: d }) = ev {
+                assert_eq!(id, "test");
+                decision = Some(d);
+            }
+        }
+        assert_eq!(decision, Some(ReviewDecision::Abort));
+    }
+
     #[test]
     fn shortcut_triggers_selection() {
         let (tx, mut rx) = unbounded_channel::<AppEvent>();
@@ -619,6 +888,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn hunk_selection_sends_declined_hunks() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let hunk = |index| PatchHunkReport {
+            path: PathBuf::from("/repo/src/lib.rs"),
+            hunk: index,
+            line: Some(index * 10),
+            status: PatchHunkStatus::Clean,
+            detail: None,
+        };
+        let mut view = ApprovalOverlay::new(
+            ApprovalRequest::ApplyPatch {
+                id: "patch".to_string(),
+                reason: None,
+                cwd: PathBuf::from("/repo"),
+                changes: HashMap::new(),
+                hunks: vec![hunk(1), hunk(2)],
+            },
+            tx,
+            Features::with_defaults(),
+        );
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('h'), KeyModifiers::NONE));
+        assert!(
+            rx.try_recv().is_err(),
+            "choosing hunks should not decide yet"
+        );
+
+        view.handle_key_event(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE));
+        view.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        let mut decision = None;
+        while let Ok(ev) = rx.try_recv() {
+            if let AppEvent::CodexOp(Op::PatchApproval { decision: d, .. }) = ev {
+                decision = Some(d);
+            }
+        }
+        assert_eq!(
+            decision,
+            Some(ReviewDecision::ApprovedHunks {
+                declined: vec![PatchHunkId {
+                    path: PathBuf::from("/repo/src/lib.rs"),
+                    hunk: 1,
+                }],
+            })
+        );
+        assert!(view.is_complete());
+    }
+
     #[test]
     fn exec_prefix_option_hidden_when_execpolicy_disabled() {
         let (tx, mut rx) = unbounded_channel::<AppEvent>();
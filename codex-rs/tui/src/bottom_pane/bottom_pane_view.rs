@@ -1,4 +1,5 @@
 use crate::bottom_pane::ApprovalRequest;
+use crate::bottom_pane::ApprovalRequestKey;
 use crate::render::renderable::Renderable;
 use codex_protocol::request_user_input::RequestUserInputEvent;
 use crossterm::event::KeyEvent;
@@ -58,6 +59,12 @@ pub(crate) trait BottomPaneView: Renderable {
         Some(request)
     }
 
+    /// Answer the pending approval request identified by `key` with a plain
+    /// approve or deny. Return true if this view held the request.
+    fn resolve_approval(&mut self, _key: &ApprovalRequestKey, _approve: bool) -> bool {
+        false
+    }
+
     /// Try to handle request_user_input; return the original value if not
     /// consumed.
     fn try_consume_user_input_request(
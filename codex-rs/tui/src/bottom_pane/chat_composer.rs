@@ -125,6 +125,7 @@ use super::footer::render_footer_line;
 use super::footer::reset_mode_after_activity;
 use super::footer::single_line_footer_layout;
 use super::footer::toggle_shortcut_mode;
+use super::mcp_mention_catalog::McpMentionCatalog;
 use super::paste_burst::CharDecision;
 use super::paste_burst::PasteBurst;
 use super::skill_popup::MentionItem;
@@ -133,6 +134,7 @@ use super::slash_commands;
 use crate::bottom_pane::paste_burst::FlushResult;
 use crate::bottom_pane::prompt_args::expand_custom_prompt;
 use crate::bottom_pane::prompt_args::expand_if_numeric_with_positional_args;
+use crate::bottom_pane::prompt_args::find_prompt;
 use crate::bottom_pane::prompt_args::parse_slash_name;
 use crate::bottom_pane::prompt_args::prompt_argument_names;
 use crate::bottom_pane::prompt_args::prompt_command_with_arg_placeholders;
@@ -144,7 +146,6 @@ use crate::slash_command::SlashCommand;
 use crate::style::user_message_style;
 use codex_common::fuzzy_match::fuzzy_match;
 use codex_protocol::custom_prompts::CustomPrompt;
-use codex_protocol::custom_prompts::PROMPTS_CMD_PREFIX;
 use codex_protocol::models::local_image_label_text;
 use codex_protocol::user_input::ByteRange;
 use codex_protocol::user_input::TextElement;
@@ -156,18 +157,22 @@ use crate::bottom_pane::LocalImageAttachment;
 use crate::bottom_pane::textarea::TextArea;
 use crate::bottom_pane::textarea::TextAreaState;
 use crate::clipboard_paste::normalize_pasted_path;
+use crate::clipboard_paste::normalize_pasted_paths;
 use crate::clipboard_paste::pasted_image_format;
 use crate::history_cell;
 use crate::ui_consts::LIVE_PREFIX_COLS;
 use codex_chatgpt::connectors;
 use codex_chatgpt::connectors::AppInfo;
+use codex_core::protocol::Op;
 use codex_core::skills::model::SkillMetadata;
 use codex_file_search::FileMatch;
+use codex_protocol::num_format::format_si_suffix;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::VecDeque;
 use std::ops::Range;
+use std::path::Path;
 use std::path::PathBuf;
 use std::time::Duration;
 use std::time::Instant;
@@ -176,6 +181,19 @@ use std::time::Instant;
 /// placeholder in the UI.
 const LARGE_PASTE_CHAR_THRESHOLD: usize = 1000;
 
+/// Dropped or pasted text files up to this size are attached with their
+/// contents; larger ones are inserted as a path for the model to read.
+const MAX_ATTACHED_FILE_BYTES: u64 = 256 * 1024;
+
+fn format_file_size(bytes: u64) -> String {
+    const KIB: u64 = 1024;
+    if bytes >= KIB {
+        format!("{:.1} KiB", bytes as f64 / KIB as f64)
+    } else {
+        format!("{bytes} B")
+    }
+}
+
 /// Result returned when the user interacts with the text area.
 #[derive(Debug, PartialEq)]
 pub enum InputResult {
@@ -263,6 +281,8 @@ pub(crate) struct ChatComposer {
     use_shift_enter_hint: bool,
     dismissed_file_popup_token: Option<String>,
     current_file_query: Option<String>,
+    /// Set by `/image`: the file popup only lists images until it closes.
+    image_picker: bool,
     pending_pastes: Vec<(String, String)>,
     large_paste_counters: HashMap<usize, usize>,
     has_focus: bool,
@@ -277,14 +297,22 @@ pub(crate) struct ChatComposer {
     paste_burst: PasteBurst,
     // When true, disables paste-burst logic and inserts characters immediately.
     disable_paste_burst: bool,
+    /// Burst pastes longer than this many characters are held back for the
+    /// large-paste prompt instead of being inserted. Zero disables the check.
+    paste_attach_threshold: usize,
+    /// Burst paste held back by `paste_attach_threshold`, waiting for the
+    /// chat widget to take it.
+    pending_large_paste: Option<String>,
     custom_prompts: Vec<CustomPrompt>,
     footer_mode: FooterMode,
     footer_hint_override: Option<Vec<(String, String)>>,
     footer_flash: Option<FooterFlash>,
     context_window_percent: Option<i64>,
     context_window_used_tokens: Option<i64>,
+    prompt_cache_hit_percent: Option<i64>,
     skills: Option<Vec<SkillMetadata>>,
     connectors_snapshot: Option<ConnectorsSnapshot>,
+    mcp_mentions: McpMentionCatalog,
     dismissed_mention_popup_token: Option<String>,
     mention_paths: HashMap<String, String>,
     /// When enabled, `Enter` submits immediately and `Tab` requests queuing behavior.
@@ -357,6 +385,7 @@ impl ChatComposer {
             use_shift_enter_hint,
             dismissed_file_popup_token: None,
             current_file_query: None,
+            image_picker: false,
             pending_pastes: Vec::new(),
             large_paste_counters: HashMap::new(),
             has_focus: has_input_focus,
@@ -367,14 +396,18 @@ impl ChatComposer {
             input_disabled_placeholder: None,
             paste_burst: PasteBurst::default(),
             disable_paste_burst: false,
+            paste_attach_threshold: 0,
+            pending_large_paste: None,
             custom_prompts: Vec::new(),
             footer_mode: FooterMode::ComposerEmpty,
             footer_hint_override: None,
             footer_flash: None,
             context_window_percent: None,
             context_window_used_tokens: None,
+            prompt_cache_hit_percent: None,
             skills: None,
             connectors_snapshot: None,
+            mcp_mentions: McpMentionCatalog::default(),
             dismissed_mention_popup_token: None,
             mention_paths: HashMap::new(),
             steer_enabled: false,
@@ -398,6 +431,28 @@ impl ChatComposer {
         self.connectors_snapshot = connectors_snapshot;
     }
 
+    pub(crate) fn set_mcp_mention_servers(&mut self, servers: Vec<String>) {
+        self.mcp_mentions.set_servers(servers);
+    }
+
+    pub(crate) fn set_mcp_mention_snapshot(
+        &mut self,
+        resources: &HashMap<String, Vec<mcp_types::Resource>>,
+        prompts: &HashMap<String, Vec<mcp_types::Prompt>>,
+    ) {
+        self.mcp_mentions.set_snapshot(resources, prompts);
+        if let Some(query) = Self::current_at_token(&self.textarea)
+            && self.mcp_mentions.is_server_query(&query)
+            && matches!(self.active_popup, ActivePopup::File(_))
+        {
+            self.sync_mcp_mention_popup(query);
+        }
+    }
+
+    pub(crate) fn mcp_mentions(&self) -> &McpMentionCatalog {
+        &self.mcp_mentions
+    }
+
     pub(crate) fn take_mention_paths(&mut self) -> HashMap<String, String> {
         std::mem::take(&mut self.mention_paths)
     }
@@ -520,6 +575,9 @@ impl ChatComposer {
     ///   element (expanded on submit) and stores the full text in `pending_pastes`.
     /// - Otherwise, if the paste looks like an image path, attaches the image and inserts a
     ///   trailing space so the user can keep typing naturally.
+    /// - Otherwise, if the paste is a list of absolute file paths (a terminal drag-and-drop or
+    ///   a file manager's copied selection), attaches each file; see
+    ///   [`Self::handle_paste_file_paths`].
     /// - Otherwise, inserts the pasted text directly into the textarea.
     ///
     /// In all cases, clears any paste-burst Enter suppression state so a real paste cannot affect
@@ -536,6 +594,8 @@ impl ChatComposer {
             && self.handle_paste_image_path(pasted.clone())
         {
             self.textarea.insert_str(" ");
+        } else if char_count > 1 && self.handle_paste_file_paths(&pasted) {
+            self.textarea.insert_str(" ");
         } else {
             self.textarea.insert_str(&pasted);
         }
@@ -568,6 +628,69 @@ impl ChatComposer {
         }
     }
 
+    /// Attach every file in a pasted list of absolute paths. Text files become elements showing
+    /// their size and estimated tokens that expand to the file contents on submit, images are
+    /// attached as images, and other files are inserted as their path. Returns `false`, leaving
+    /// the paste to be inserted as text, unless every entry is an existing file.
+    fn handle_paste_file_paths(&mut self, pasted: &str) -> bool {
+        let Some(paths) = normalize_pasted_paths(pasted) else {
+            return false;
+        };
+        if !paths
+            .iter()
+            .all(|path| path.is_absolute() && path.is_file())
+        {
+            return false;
+        }
+        for (idx, path) in paths.into_iter().enumerate() {
+            if idx > 0 {
+                self.textarea.insert_str(" ");
+            }
+            if self.image_paste_enabled() && image::image_dimensions(&path).is_ok() {
+                self.attach_image(path);
+            } else if !self.attach_file(&path) {
+                self.textarea.insert_str(&path.display().to_string());
+            }
+        }
+        true
+    }
+
+    /// Insert an element for a text file that expands to its contents on submit. Returns `false`
+    /// for files that are too large or not UTF-8 text.
+    fn attach_file(&mut self, path: &Path) -> bool {
+        let Ok(metadata) = std::fs::metadata(path) else {
+            return false;
+        };
+        if metadata.len() > MAX_ATTACHED_FILE_BYTES {
+            return false;
+        }
+        let Ok(contents) = std::fs::read_to_string(path) else {
+            return false;
+        };
+        let name = path.file_name().map_or_else(
+            || path.display().to_string(),
+            |name| name.to_string_lossy().to_string(),
+        );
+        let tokens = contents.len().div_ceil(4);
+        let base = format!(
+            "[File {name} · {} · ~{} tokens]",
+            format_file_size(metadata.len()),
+            format_si_suffix(i64::try_from(tokens).unwrap_or(i64::MAX))
+        );
+        let mut placeholder = base.clone();
+        let mut suffix = 1;
+        while self.pending_pastes.iter().any(|(ph, _)| ph == &placeholder) {
+            suffix += 1;
+            placeholder = format!("{base} #{suffix}");
+        }
+        self.textarea.insert_element(&placeholder);
+        self.pending_pastes.push((
+            placeholder,
+            format!("<file path=\"{}\">\n{contents}\n</file>", path.display()),
+        ));
+        true
+    }
+
     /// Enable or disable paste-burst handling.
     ///
     /// `disable_paste_burst` is an escape hatch for terminals/platforms where the burst heuristic
@@ -591,12 +714,35 @@ impl ChatComposer {
         self.disable_paste_burst = disabled;
         if disabled && !was_disabled {
             if let Some(pasted) = self.paste_burst.flush_before_modified_input() {
-                self.handle_paste(pasted);
+                self.handle_burst_paste(pasted);
             }
             self.paste_burst.clear_after_explicit_paste();
         }
     }
 
+    /// Sets the size above which burst pastes are held back for the
+    /// large-paste prompt. Zero disables the check.
+    pub(crate) fn set_paste_attach_threshold(&mut self, threshold: usize) {
+        self.paste_attach_threshold = threshold;
+    }
+
+    /// Takes a burst paste that was held back because it exceeded the paste
+    /// attach threshold.
+    pub(crate) fn take_pending_large_paste(&mut self) -> Option<String> {
+        self.pending_large_paste.take()
+    }
+
+    /// Inserts text flushed from the paste-burst detector, holding it back
+    /// instead when it is larger than the paste attach threshold so it gets
+    /// the same prompt as a bracketed paste.
+    fn handle_burst_paste(&mut self, pasted: String) {
+        if self.paste_attach_threshold > 0 && pasted.chars().count() > self.paste_attach_threshold {
+            self.pending_large_paste = Some(pasted);
+            return;
+        }
+        self.handle_paste(pasted);
+    }
+
     /// Replace the composer content with text from an external editor.
     /// Clears pending paste placeholders and keeps only attachments whose
     /// placeholder labels still appear in the new text. Image placeholders
@@ -872,7 +1018,7 @@ impl ChatComposer {
     }
 
     /// Integrate results from an asynchronous file search.
-    pub(crate) fn on_file_search_result(&mut self, query: String, matches: Vec<FileMatch>) {
+    pub(crate) fn on_file_search_result(&mut self, query: String, mut matches: Vec<FileMatch>) {
         // Only apply if user is still editing a token starting with `query`.
         let current_opt = Self::current_at_token(&self.textarea);
         let Some(current_token) = current_opt else {
@@ -883,11 +1029,20 @@ impl ChatComposer {
             return;
         }
 
+        if self.image_picker {
+            matches.retain(|file| Self::is_image_path(&file.path.to_string_lossy()));
+        }
         if let ActivePopup::File(popup) = &mut self.active_popup {
             popup.set_matches(&query, matches);
         }
     }
 
+    /// Open the file popup listing only images; picking one attaches it.
+    pub(crate) fn open_image_picker(&mut self) {
+        self.image_picker = true;
+        self.insert_str("@");
+    }
+
     /// Show the transient "press again to quit" hint for `key`.
     ///
     /// The owner (`BottomPane`/`ChatWidget`) is responsible for scheduling a
@@ -1078,8 +1233,7 @@ impl ChatComposer {
                 }
                 let first_line = text.lines().next().unwrap_or("");
                 if let Some((name, _rest, _rest_offset)) = parse_slash_name(first_line)
-                    && let Some(prompt_name) = name.strip_prefix(&format!("{PROMPTS_CMD_PREFIX}:"))
-                    && let Some(prompt) = self.custom_prompts.iter().find(|p| p.name == prompt_name)
+                    && let Some(prompt) = find_prompt(&self.custom_prompts, name)
                     && let Some(expanded) =
                         expand_if_numeric_with_positional_args(prompt, first_line, &text_elements)
                 {
@@ -1202,7 +1356,7 @@ impl ChatComposer {
             // any existing burst buffer (including a pending first char from the ASCII path) so
             // we don't carry that transient state forward.
             if let Some(pasted) = self.paste_burst.flush_before_modified_input() {
-                self.handle_paste(pasted);
+                self.handle_burst_paste(pasted);
             }
             if let Some(decision) = self.paste_burst.on_plain_char_no_hold(now) {
                 match decision {
@@ -1237,7 +1391,7 @@ impl ChatComposer {
             }
         }
         if let Some(pasted) = self.paste_burst.flush_before_modified_input() {
-            self.handle_paste(pasted);
+            self.handle_burst_paste(pasted);
         }
         self.textarea.input(input);
 
@@ -1296,6 +1450,7 @@ impl ChatComposer {
                 if let Some(tok) = Self::current_at_token(&self.textarea) {
                     self.dismissed_file_popup_token = Some(tok);
                 }
+                self.image_picker = false;
                 self.active_popup = ActivePopup::None;
                 (InputResult::None, true)
             }
@@ -1313,6 +1468,13 @@ impl ChatComposer {
                 };
 
                 let sel_path = sel.to_string_lossy().to_string();
+                let full_path = popup.selected_full_path();
+                if self.mcp_mentions.entry(&sel_path).is_some() {
+                    // Keep the `@` so the mention is recognized on submit.
+                    self.insert_selected_path(&format!("@{sel_path}"));
+                    self.active_popup = ActivePopup::None;
+                    return (InputResult::None, true);
+                }
                 // If selected path looks like an image (png/jpeg), attach as image instead of inserting text.
                 let is_image = Self::is_image_path(&sel_path);
                 if is_image {
@@ -1321,31 +1483,7 @@ impl ChatComposer {
                     match image::image_dimensions(&path_buf) {
                         Ok((width, height)) => {
                             tracing::debug!("selected image dimensions={}x{}", width, height);
-                            // Remove the current @token (mirror logic from insert_selected_path without inserting text)
-                            // using the flat text and byte-offset cursor API.
-                            let cursor_offset = self.textarea.cursor();
-                            let text = self.textarea.text();
-                            // Clamp to a valid char boundary to avoid panics when slicing.
-                            let safe_cursor = Self::clamp_to_char_boundary(text, cursor_offset);
-                            let before_cursor = &text[..safe_cursor];
-                            let after_cursor = &text[safe_cursor..];
-
-                            // Determine token boundaries in the full text.
-                            let start_idx = before_cursor
-                                .char_indices()
-                                .rfind(|(_, c)| c.is_whitespace())
-                                .map(|(idx, c)| idx + c.len_utf8())
-                                .unwrap_or(0);
-                            let end_rel_idx = after_cursor
-                                .char_indices()
-                                .find(|(_, c)| c.is_whitespace())
-                                .map(|(idx, _)| idx)
-                                .unwrap_or(after_cursor.len());
-                            let end_idx = safe_cursor + end_rel_idx;
-
-                            self.textarea.replace_range(start_idx..end_idx, "");
-                            self.textarea.set_cursor(start_idx);
-
+                            self.remove_active_at_token();
                             self.attach_image(path_buf);
                             // Add a trailing space to keep typing fluid.
                             self.textarea.insert_str(" ");
@@ -1356,6 +1494,17 @@ impl ChatComposer {
                             self.insert_selected_path(&sel_path);
                         }
                     }
+                } else if let Some(full_path) = full_path
+                    && full_path.is_file()
+                {
+                    // Text files become an attachment that expands to their contents on submit;
+                    // anything else falls back to inserting the path.
+                    self.remove_active_at_token();
+                    if self.attach_file(&full_path) {
+                        self.textarea.insert_str(" ");
+                    } else {
+                        self.insert_selected_path(&sel_path);
+                    }
                 } else {
                     // Non-image: inserting file path.
                     self.insert_selected_path(&sel_path);
@@ -1691,6 +1840,32 @@ impl ChatComposer {
         Self::current_prefixed_token(&self.textarea, '$', true)
     }
 
+    /// Remove the active `@token` (the one under the cursor) and move the cursor to where it began.
+    fn remove_active_at_token(&mut self) {
+        let cursor_offset = self.textarea.cursor();
+        let text = self.textarea.text();
+        // Clamp to a valid char boundary to avoid panics when slicing.
+        let safe_cursor = Self::clamp_to_char_boundary(text, cursor_offset);
+        let before_cursor = &text[..safe_cursor];
+        let after_cursor = &text[safe_cursor..];
+
+        // Determine token boundaries in the full text.
+        let start_idx = before_cursor
+            .char_indices()
+            .rfind(|(_, c)| c.is_whitespace())
+            .map(|(idx, c)| idx + c.len_utf8())
+            .unwrap_or(0);
+        let end_rel_idx = after_cursor
+            .char_indices()
+            .find(|(_, c)| c.is_whitespace())
+            .map(|(idx, _)| idx)
+            .unwrap_or(after_cursor.len());
+        let end_idx = safe_cursor + end_rel_idx;
+
+        self.textarea.replace_range(start_idx..end_idx, "");
+        self.textarea.set_cursor(start_idx);
+    }
+
     /// Replace the active `@token` (the one under the cursor) with `path`.
     ///
     /// The algorithm mirrors `current_at_token` so replacement works no matter
@@ -1850,15 +2025,7 @@ impl ChatComposer {
                     self.windows_degraded_sandbox_active,
                 )
                 .is_some();
-                let prompt_prefix = format!("{PROMPTS_CMD_PREFIX}:");
-                let is_known_prompt = name
-                    .strip_prefix(&prompt_prefix)
-                    .map(|prompt_name| {
-                        self.custom_prompts
-                            .iter()
-                            .any(|prompt| prompt.name == prompt_name)
-                    })
-                    .unwrap_or(false);
+                let is_known_prompt = find_prompt(&self.custom_prompts, name).is_some();
                 if !is_builtin && !is_known_prompt {
                     let message = format!(
                         r#"Unrecognized command '/{name}'. Type "/" for a list of supported commands."#
@@ -2241,7 +2408,7 @@ impl ChatComposer {
     fn handle_paste_burst_flush(&mut self, now: Instant) -> bool {
         match self.paste_burst.flush_if_due(now) {
             FlushResult::Paste(pasted) => {
-                self.handle_paste(pasted);
+                self.handle_burst_paste(pasted);
                 true
             }
             FlushResult::Typed(ch) => {
@@ -2349,7 +2516,7 @@ impl ChatComposer {
                 }
             }
             if let Some(pasted) = self.paste_burst.flush_before_modified_input() {
-                self.handle_paste(pasted);
+                self.handle_burst_paste(pasted);
             }
         }
 
@@ -2362,7 +2529,7 @@ impl ChatComposer {
         if !matches!(input.code, KeyCode::Char(_) | KeyCode::Enter)
             && let Some(pasted) = self.paste_burst.flush_before_modified_input()
         {
-            self.handle_paste(pasted);
+            self.handle_burst_paste(pasted);
         }
         // For non-char inputs (or after flushing), handle normally.
         // Track element removals so we can drop any corresponding placeholders without scanning
@@ -2489,6 +2656,7 @@ impl ChatComposer {
             is_wsl,
             context_window_percent: self.context_window_percent,
             context_window_used_tokens: self.context_window_used_tokens,
+            prompt_cache_hit_percent: self.prompt_cache_hit_percent,
         }
     }
 
@@ -2590,6 +2758,7 @@ impl ChatComposer {
             self.current_file_query = None;
         }
         self.dismissed_file_popup_token = None;
+        self.image_picker = false;
         if matches!(
             self.active_popup,
             ActivePopup::File(_) | ActivePopup::Skill(_)
@@ -2669,15 +2838,7 @@ impl ChatComposer {
         if is_builtin {
             return true;
         }
-        if let Some(rest) = name.strip_prefix(PROMPTS_CMD_PREFIX)
-            && let Some(prompt_name) = rest.strip_prefix(':')
-        {
-            return self
-                .custom_prompts
-                .iter()
-                .any(|prompt| prompt.name == prompt_name);
-        }
-        false
+        find_prompt(&self.custom_prompts, name).is_some()
     }
 
     /// If the cursor is currently within a slash command on the first line,
@@ -2729,9 +2890,9 @@ impl ChatComposer {
             return true;
         }
 
-        self.custom_prompts.iter().any(|prompt| {
-            fuzzy_match(&format!("{PROMPTS_CMD_PREFIX}:{}", prompt.name), name).is_some()
-        })
+        self.custom_prompts
+            .iter()
+            .any(|prompt| fuzzy_match(&prompt.slash_name(), name).is_some())
     }
 
     /// Synchronize `self.command_popup` with the current text in the
@@ -2807,6 +2968,11 @@ impl ChatComposer {
             return;
         }
 
+        if self.mcp_mentions.is_server_query(&query) {
+            self.sync_mcp_mention_popup(query);
+            return;
+        }
+
         if query.is_empty() {
             self.app_event_tx
                 .send(AppEvent::StartFileSearch(String::new()));
@@ -2842,6 +3008,28 @@ impl ChatComposer {
         self.dismissed_file_popup_token = None;
     }
 
+    /// Show the resources and prompts of the server named by an `@server:` token.
+    fn sync_mcp_mention_popup(&mut self, query: String) {
+        if self.current_file_query.take().is_some() {
+            self.app_event_tx
+                .send(AppEvent::StartFileSearch(String::new()));
+        }
+        if self.mcp_mentions.take_fetch_request() {
+            self.app_event_tx.send(AppEvent::CodexOp(Op::ListMcpTools));
+        }
+
+        if !matches!(self.active_popup, ActivePopup::File(_)) {
+            self.active_popup = ActivePopup::File(FileSearchPopup::new());
+        }
+        if let ActivePopup::File(popup) = &mut self.active_popup {
+            popup.set_query(&query);
+            if let Some(matches) = self.mcp_mentions.matches(&query) {
+                popup.set_matches(&query, matches);
+            }
+        }
+        self.dismissed_file_popup_token = None;
+    }
+
     fn sync_mention_popup(&mut self, query: String) {
         if self.dismissed_mention_popup_token.as_ref() == Some(&query) {
             return;
@@ -2963,6 +3151,10 @@ impl ChatComposer {
         self.context_window_used_tokens = used_tokens;
     }
 
+    pub(crate) fn set_prompt_cache_hit_percent(&mut self, percent: Option<i64>) {
+        self.prompt_cache_hit_percent = percent;
+    }
+
     pub(crate) fn set_esc_backtrack_hint(&mut self, show: bool) {
         self.esc_backtrack_hint = show;
         if show {
@@ -3067,6 +3259,7 @@ impl ChatComposer {
                 let context_line = context_window_line(
                     footer_props.context_window_percent,
                     footer_props.context_window_used_tokens,
+                    footer_props.prompt_cache_hit_percent,
                 );
                 let context_width = context_line.width() as u16;
                 let custom_height = self.custom_footer_height();
@@ -3235,23 +3428,23 @@ fn prompt_selection_action(
         PromptSelectionMode::Completion => {
             if !named_args.is_empty() {
                 let (text, cursor) =
-                    prompt_command_with_arg_placeholders(&prompt.name, &named_args);
+                    prompt_command_with_arg_placeholders(&prompt.slash_name(), &named_args);
                 return PromptSelectionAction::Insert {
                     text,
                     cursor: Some(cursor),
                 };
             }
             if has_numeric {
-                let text = format!("/{PROMPTS_CMD_PREFIX}:{} ", prompt.name);
+                let text = format!("/{} ", prompt.slash_name());
                 return PromptSelectionAction::Insert { text, cursor: None };
             }
-            let text = format!("/{PROMPTS_CMD_PREFIX}:{}", prompt.name);
+            let text = format!("/{}", prompt.slash_name());
             PromptSelectionAction::Insert { text, cursor: None }
         }
         PromptSelectionMode::Submit => {
             if !named_args.is_empty() {
                 let (text, cursor) =
-                    prompt_command_with_arg_placeholders(&prompt.name, &named_args);
+                    prompt_command_with_arg_placeholders(&prompt.slash_name(), &named_args);
                 return PromptSelectionAction::Insert {
                     text,
                     cursor: Some(cursor),
@@ -3266,7 +3459,7 @@ fn prompt_selection_action(
                         text_elements: expanded.text_elements,
                     };
                 }
-                let text = format!("/{PROMPTS_CMD_PREFIX}:{} ", prompt.name);
+                let text = format!("/{} ", prompt.slash_name());
                 return PromptSelectionAction::Insert { text, cursor: None };
             }
             PromptSelectionAction::Submit {
@@ -3281,6 +3474,7 @@ fn prompt_selection_action(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use codex_protocol::custom_prompts::PROMPTS_CMD_PREFIX;
     use image::ImageBuffer;
     use image::Rgba;
     use pretty_assertions::assert_eq;
@@ -4292,6 +4486,45 @@ mod tests {
         assert_eq!(composer.textarea.text(), "hi\nthere");
     }
 
+    /// Behavior: a burst paste over the attach threshold is held back for the large-paste prompt
+    /// instead of landing in the textarea.
+    #[test]
+    fn large_burst_paste_is_held_back() {
+        use crossterm::event::KeyCode;
+        use crossterm::event::KeyEvent;
+        use crossterm::event::KeyModifiers;
+
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+        composer.set_paste_attach_threshold(5);
+
+        let mut now = Instant::now();
+        let step = Duration::from_millis(1);
+        for ch in "hello world".chars() {
+            now += step;
+            let _ = composer.handle_input_basic_with_time(
+                KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE),
+                now,
+            );
+        }
+
+        let flush_time = now + PasteBurst::recommended_active_flush_delay() + step;
+        assert!(composer.handle_paste_burst_flush(flush_time));
+        assert_eq!(composer.textarea.text(), "");
+        assert_eq!(
+            composer.take_pending_large_paste(),
+            Some("hello world".to_string())
+        );
+        assert_eq!(composer.take_pending_large_paste(), None);
+    }
+
     /// Behavior: even if Enter suppression would normally be active for a burst, Enter should
     /// still dispatch a built-in slash command when the first line begins with `/`.
     #[test]
@@ -4816,7 +5049,7 @@ mod tests {
     fn extract_args_supports_quoted_paths_single_arg() {
         let args = extract_positional_args_for_prompt_line(
             "/prompts:review \"docs/My File.md\"",
-            "review",
+            "prompts:review",
             &[],
         );
         assert_eq!(
@@ -4832,7 +5065,7 @@ mod tests {
     fn extract_args_supports_mixed_quoted_and_unquoted() {
         let args = extract_positional_args_for_prompt_line(
             "/prompts:cmd \"with spaces\" simple",
-            "cmd",
+            "prompts:cmd",
             &[],
         );
         assert_eq!(
@@ -5931,6 +6164,108 @@ mod tests {
         assert_eq!(imgs, vec![tmp_path]);
     }
 
+    #[test]
+    fn dropping_files_attaches_text_contents_and_images() {
+        let tmp = tempdir().expect("create TempDir");
+        let notes = tmp.path().join("notes.md");
+        std::fs::write(&notes, "hello\n").expect("write notes");
+        let screenshot = tmp.path().join("screen shot.png");
+        let img: ImageBuffer<Rgba<u8>, Vec<u8>> =
+            ImageBuffer::from_fn(3, 2, |_x, _y| Rgba([1, 2, 3, 255]));
+        img.save(&screenshot).expect("failed to write temp png");
+
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+
+        let dropped = format!("{}\n{}\n", notes.display(), screenshot.display());
+        assert!(composer.handle_paste(dropped));
+
+        let placeholder = "[File notes.md · 6 B · ~2 tokens]".to_string();
+        assert_eq!(
+            composer.textarea.text(),
+            format!("{placeholder} [Image #1] ")
+        );
+        assert_eq!(
+            composer.pending_pastes,
+            vec![(
+                placeholder,
+                format!("<file path=\"{}\">\nhello\n\n</file>", notes.display())
+            )]
+        );
+        assert_eq!(composer.take_recent_submission_images(), vec![screenshot]);
+    }
+
+    #[test]
+    fn accepting_a_file_completion_attaches_text_contents() {
+        use crossterm::event::KeyCode;
+        use crossterm::event::KeyEvent;
+        use crossterm::event::KeyModifiers;
+
+        let tmp = tempdir().expect("create TempDir");
+        std::fs::write(tmp.path().join("notes.md"), "hello\n").expect("write notes");
+
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+
+        composer.insert_str("see @notes");
+        composer.on_file_search_result(
+            "notes".to_string(),
+            vec![FileMatch {
+                score: 1,
+                path: PathBuf::from("notes.md"),
+                root: tmp.path().to_path_buf(),
+                indices: None,
+            }],
+        );
+        let (result, _) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
+        assert!(matches!(result, InputResult::None));
+
+        let placeholder = "[File notes.md · 6 B · ~2 tokens]".to_string();
+        assert_eq!(composer.textarea.text(), format!("see {placeholder} "));
+        assert_eq!(
+            composer.pending_pastes,
+            vec![(
+                placeholder,
+                format!(
+                    "<file path=\"{}\">\nhello\n\n</file>",
+                    tmp.path().join("notes.md").display()
+                )
+            )]
+        );
+    }
+
+    #[test]
+    fn pasting_a_path_that_does_not_exist_inserts_text() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+
+        assert!(composer.handle_paste("/no/such/file.txt".to_string()));
+        assert_eq!(composer.textarea.text(), "/no/such/file.txt");
+        assert!(composer.pending_pastes.is_empty());
+    }
+
     #[test]
     fn selecting_custom_prompt_without_args_submits_content() {
         let prompt_text = "Hello from saved prompt";
@@ -5953,6 +6288,7 @@ mod tests {
             content: prompt_text.to_string(),
             description: None,
             argument_hint: None,
+            command: false,
         }]);
 
         type_chars_humanlike(
@@ -5992,6 +6328,7 @@ mod tests {
             content: "Review $USER changes on $BRANCH".to_string(),
             description: None,
             argument_hint: None,
+            command: false,
         }]);
 
         composer
@@ -6028,6 +6365,7 @@ mod tests {
             content: "Pair $USER with $BRANCH".to_string(),
             description: None,
             argument_hint: None,
+            command: false,
         }]);
 
         composer
@@ -6068,6 +6406,7 @@ mod tests {
             content: "Review $IMG".to_string(),
             description: None,
             argument_hint: None,
+            command: false,
         }]);
 
         composer
@@ -6124,6 +6463,7 @@ mod tests {
             content: "Review $IMG".to_string(),
             description: None,
             argument_hint: None,
+            command: false,
         }]);
 
         composer
@@ -6181,6 +6521,7 @@ mod tests {
             content: "Review changes".to_string(),
             description: None,
             argument_hint: None,
+            command: false,
         }]);
 
         composer
@@ -6231,6 +6572,7 @@ mod tests {
             content: "Please review the following code:\n\n$1".to_string(),
             description: None,
             argument_hint: None,
+            command: false,
         }]);
 
         // Type the slash command
@@ -6295,6 +6637,7 @@ mod tests {
             content: "Review $IMG\n\n$CODE".to_string(),
             description: None,
             argument_hint: None,
+            command: false,
         }]);
 
         composer
@@ -6423,6 +6766,7 @@ mod tests {
             content: "Review $USER changes".to_string(),
             description: None,
             argument_hint: None,
+            command: false,
         }]);
 
         composer
@@ -6473,6 +6817,7 @@ mod tests {
             content: "Review $USER changes on $BRANCH".to_string(),
             description: None,
             argument_hint: None,
+            command: false,
         }]);
 
         // Provide only one of the required args
@@ -6529,6 +6874,7 @@ mod tests {
             content: prompt_text.to_string(),
             description: None,
             argument_hint: None,
+            command: false,
         }]);
 
         // Type the slash command with two args and hit Enter to submit.
@@ -6568,6 +6914,7 @@ mod tests {
             content: "Hello".to_string(),
             description: None,
             argument_hint: None,
+            command: false,
         }]);
 
         composer.attach_image(PathBuf::from("/tmp/unused.png"));
@@ -6607,6 +6954,7 @@ mod tests {
             content: "Hello $1".to_string(),
             description: None,
             argument_hint: None,
+            command: false,
         }]);
 
         type_chars_humanlike(
@@ -6651,6 +6999,7 @@ mod tests {
             content: "Echo: $1".to_string(),
             description: None,
             argument_hint: None,
+            command: false,
         }]);
 
         composer
@@ -6692,6 +7041,7 @@ mod tests {
             content: "Hello $1".to_string(),
             description: None,
             argument_hint: None,
+            command: false,
         }]);
 
         composer
@@ -6724,6 +7074,7 @@ mod tests {
             content: prompt_text.to_string(),
             description: None,
             argument_hint: None,
+            command: false,
         };
 
         let action = prompt_selection_action(
@@ -6765,6 +7116,7 @@ mod tests {
             content: "Echo: $ARGUMENTS".to_string(),
             description: None,
             argument_hint: None,
+            command: false,
         }]);
 
         // Type positional args; should submit with numeric expansion, no errors.
@@ -6801,6 +7153,7 @@ mod tests {
             content: prompt_text.to_string(),
             description: None,
             argument_hint: None,
+            command: false,
         }]);
 
         type_chars_humanlike(
@@ -6838,6 +7191,7 @@ mod tests {
             content: prompt_text.to_string(),
             description: None,
             argument_hint: None,
+            command: false,
         }]);
 
         type_chars_humanlike(
@@ -6877,6 +7231,7 @@ mod tests {
             content: prompt_text.to_string(),
             description: None,
             argument_hint: None,
+            command: false,
         }]);
 
         type_chars_humanlike(
@@ -7227,6 +7582,43 @@ mod tests {
         assert_eq!(composer.attached_images.len(), 1);
     }
 
+    #[test]
+    fn image_picker_lists_only_images() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+
+        composer.open_image_picker();
+        composer.insert_str("shot");
+        let file_match = |path: &str| FileMatch {
+            score: 1,
+            path: PathBuf::from(path),
+            root: PathBuf::from("/repo"),
+            indices: None,
+        };
+        composer.on_file_search_result(
+            "shot".to_string(),
+            vec![file_match("shot.txt"), file_match("docs/shot.png")],
+        );
+
+        let ActivePopup::File(popup) = &composer.active_popup else {
+            panic!("expected the file popup");
+        };
+        assert_eq!(
+            popup.selected_match(),
+            Some(&PathBuf::from("docs/shot.png"))
+        );
+
+        composer.set_text_content(String::new(), Vec::new(), Vec::new());
+        assert!(!composer.image_picker);
+    }
+
     #[test]
     fn input_disabled_ignores_keypresses_and_hides_cursor() {
         use crossterm::event::KeyCode;
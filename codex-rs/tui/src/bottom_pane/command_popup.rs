@@ -11,7 +11,6 @@ use crate::render::Insets;
 use crate::render::RectExt;
 use crate::slash_command::SlashCommand;
 use codex_protocol::custom_prompts::CustomPrompt;
-use codex_protocol::custom_prompts::PROMPTS_CMD_PREFIX;
 use std::collections::HashSet;
 
 // Hide alias commands in the default popup list so each unique action appears once.
@@ -143,7 +142,6 @@ impl CommandPopup {
         let filter_chars = filter.chars().count();
         let mut exact: Vec<(CommandItem, Option<Vec<usize>>)> = Vec::new();
         let mut prefix: Vec<(CommandItem, Option<Vec<usize>>)> = Vec::new();
-        let prompt_prefix_len = PROMPTS_CMD_PREFIX.chars().count() + 1;
         let indices_for = |offset| Some((offset..offset + filter_chars).collect());
 
         let mut push_match =
@@ -173,13 +171,15 @@ impl CommandPopup {
         // Support both search styles:
         // - Typing "name" should surface "/prompts:name" results.
         // - Typing "prompts:name" should also work.
+        // Custom commands are plain "/name".
         for (idx, p) in self.prompts.iter().enumerate() {
-            let display = format!("{PROMPTS_CMD_PREFIX}:{}", p.name);
+            let display = p.slash_name();
+            let name_offset = display.chars().count() - p.name.chars().count();
             push_match(
                 CommandItem::UserPrompt(idx),
                 &display,
                 Some(&p.name),
-                prompt_prefix_len,
+                name_offset,
             );
         }
 
@@ -209,10 +209,7 @@ impl CommandPopup {
                             .description
                             .clone()
                             .unwrap_or_else(|| "send saved prompt".to_string());
-                        (
-                            format!("/{PROMPTS_CMD_PREFIX}:{}", prompt.name),
-                            description,
-                        )
+                        (format!("/{}", prompt.slash_name()), description)
                     }
                 };
                 GenericDisplayRow {
@@ -345,6 +342,7 @@ mod tests {
                 content: "hello from foo".to_string(),
                 description: None,
                 argument_hint: None,
+                command: false,
             },
             CustomPrompt {
                 name: "bar".to_string(),
@@ -352,6 +350,7 @@ mod tests {
                 content: "hello from bar".to_string(),
                 description: None,
                 argument_hint: None,
+                command: false,
             },
         ];
         let popup = CommandPopup::new(prompts, CommandPopupFlags::default());
@@ -367,6 +366,25 @@ mod tests {
         assert_eq!(prompt_names, vec!["bar".to_string(), "foo".to_string()]);
     }
 
+    #[test]
+    fn custom_commands_are_listed_without_prefix() {
+        let mut popup = CommandPopup::new(
+            vec![CustomPrompt {
+                name: "review-pr".to_string(),
+                path: "/tmp/commands/review-pr.md".to_string().into(),
+                content: "Review $ARGUMENTS".to_string(),
+                description: None,
+                argument_hint: None,
+                command: true,
+            }],
+            CommandPopupFlags::default(),
+        );
+        popup.on_composer_text_change("/review-".to_string());
+        let rows = popup.rows_from_matches(popup.filtered());
+        let names: Vec<&str> = rows.iter().map(|row| row.name.as_str()).collect();
+        assert_eq!(names, vec!["/review-pr"]);
+    }
+
     #[test]
     fn prompt_name_collision_with_builtin_is_ignored() {
         // Create a prompt named like a builtin (e.g. "init").
@@ -377,6 +395,7 @@ mod tests {
                 content: "should be ignored".to_string(),
                 description: None,
                 argument_hint: None,
+                command: false,
             }],
             CommandPopupFlags::default(),
         );
@@ -400,6 +419,7 @@ mod tests {
                 content: "body".to_string(),
                 description: Some("Create feature branch, commit and open draft PR.".to_string()),
                 argument_hint: None,
+                command: false,
             }],
             CommandPopupFlags::default(),
         );
@@ -420,6 +440,7 @@ mod tests {
                 content: "body".to_string(),
                 description: None,
                 argument_hint: None,
+                command: false,
             }],
             CommandPopupFlags::default(),
         );
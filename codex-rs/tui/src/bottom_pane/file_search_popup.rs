@@ -98,6 +98,14 @@ impl FileSearchPopup {
             .map(|file_match| &file_match.path)
     }
 
+    /// The selected match joined onto the root it was found under.
+    pub(crate) fn selected_full_path(&self) -> Option<PathBuf> {
+        self.state
+            .selected_idx
+            .and_then(|idx| self.matches.get(idx))
+            .map(|file_match| file_match.root.join(&file_match.path))
+    }
+
     pub(crate) fn calculate_required_height(&self) -> u16 {
         // Row count depends on whether we already have matches. If no matches
         // yet (e.g. initial search or query with no results) reserve a single
@@ -68,6 +68,8 @@ pub(crate) struct FooterProps {
     pub(crate) quit_shortcut_key: KeyBinding,
     pub(crate) context_window_percent: Option<i64>,
     pub(crate) context_window_used_tokens: Option<i64>,
+    /// Share of the last request's input read from the provider's prompt cache.
+    pub(crate) prompt_cache_hit_percent: Option<i64>,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -741,18 +743,21 @@ fn build_columns(entries: Vec<Line<'static>>) -> Vec<Line<'static>> {
         .collect()
 }
 
-pub(crate) fn context_window_line(percent: Option<i64>, used_tokens: Option<i64>) -> Line<'static> {
-    if let Some(percent) = percent {
-        let percent = percent.clamp(0, 100);
-        return Line::from(vec![Span::from(format!("{percent}% context left")).dim()]);
-    }
-
-    if let Some(tokens) = used_tokens {
-        let used_fmt = format_tokens_compact(tokens);
-        return Line::from(vec![Span::from(format!("{used_fmt} used")).dim()]);
-    }
-
-    Line::from(vec![Span::from("100% context left").dim()])
+pub(crate) fn context_window_line(
+    percent: Option<i64>,
+    used_tokens: Option<i64>,
+    cache_hit_percent: Option<i64>,
+) -> Line<'static> {
+    let context = match (percent, used_tokens) {
+        (Some(percent), _) => format!("{}% context left", percent.clamp(0, 100)),
+        (None, Some(tokens)) => format!("{} used", format_tokens_compact(tokens)),
+        (None, None) => "100% context left".to_string(),
+    };
+    let text = match cache_hit_percent {
+        Some(cached) => format!("{context} · {}% cached", cached.clamp(0, 100)),
+        None => context,
+    };
+    Line::from(vec![Span::from(text).dim()])
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -978,6 +983,7 @@ mod tests {
                 let context_line = context_window_line(
                     props.context_window_percent,
                     props.context_window_used_tokens,
+                    props.prompt_cache_hit_percent,
                 );
                 let context_width = context_line.width() as u16;
                 let show_cycle_hint = !props.is_task_running;
@@ -1062,6 +1068,29 @@ mod tests {
         assert_snapshot!(name, terminal.backend());
     }
 
+    #[test]
+    fn context_line_reports_prompt_cache_hits() {
+        let text = |line: Line<'static>| {
+            line.spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<String>()
+        };
+
+        assert_eq!(
+            text(context_window_line(Some(72), None, Some(85))),
+            "72% context left · 85% cached"
+        );
+        assert_eq!(
+            text(context_window_line(None, Some(123_456), Some(0))),
+            "123K used · 0% cached"
+        );
+        assert_eq!(
+            text(context_window_line(Some(72), None, None)),
+            "72% context left"
+        );
+    }
+
     #[test]
     fn footer_snapshots() {
         snapshot_footer(
@@ -1077,6 +1106,7 @@ mod tests {
                 quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                prompt_cache_hit_percent: None,
             },
         );
 
@@ -1093,6 +1123,7 @@ mod tests {
                 quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                prompt_cache_hit_percent: None,
             },
         );
 
@@ -1109,6 +1140,7 @@ mod tests {
                 quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                prompt_cache_hit_percent: None,
             },
         );
 
@@ -1125,6 +1157,7 @@ mod tests {
                 quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                prompt_cache_hit_percent: None,
             },
         );
 
@@ -1141,6 +1174,7 @@ mod tests {
                 quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                prompt_cache_hit_percent: None,
             },
         );
 
@@ -1157,6 +1191,7 @@ mod tests {
                 quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                prompt_cache_hit_percent: None,
             },
         );
 
@@ -1173,6 +1208,7 @@ mod tests {
                 quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                prompt_cache_hit_percent: None,
             },
         );
 
@@ -1189,6 +1225,7 @@ mod tests {
                 quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
                 context_window_percent: Some(72),
                 context_window_used_tokens: None,
+                prompt_cache_hit_percent: None,
             },
         );
 
@@ -1205,6 +1242,7 @@ mod tests {
                 quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
                 context_window_percent: None,
                 context_window_used_tokens: Some(123_456),
+                prompt_cache_hit_percent: None,
             },
         );
 
@@ -1221,6 +1259,7 @@ mod tests {
                 quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                prompt_cache_hit_percent: None,
             },
         );
 
@@ -1237,6 +1276,7 @@ mod tests {
                 quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                prompt_cache_hit_percent: None,
             },
         );
 
@@ -1251,6 +1291,7 @@ mod tests {
             quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
             context_window_percent: None,
             context_window_used_tokens: None,
+            prompt_cache_hit_percent: None,
         };
 
         snapshot_footer_with_mode_indicator(
@@ -1278,6 +1319,7 @@ mod tests {
             quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
             context_window_percent: None,
             context_window_used_tokens: None,
+            prompt_cache_hit_percent: None,
         };
 
         snapshot_footer_with_mode_indicator(
@@ -0,0 +1,311 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyModifiers;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Constraint;
+use ratatui::layout::Layout;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::Block;
+use ratatui::widgets::Widget;
+
+use crate::key_hint;
+use crate::render::Insets;
+use crate::render::RectExt as _;
+use crate::render::renderable::ColumnRenderable;
+use crate::render::renderable::Renderable;
+use crate::style::user_message_style;
+
+use codex_core::protocol::PatchHunkId;
+use codex_core::protocol::PatchHunkReport;
+use codex_core::protocol::PatchHunkStatus;
+use codex_core::protocol::ReviewDecision;
+
+use super::popup_consts::MAX_POPUP_ROWS;
+use super::scroll_state::ScrollState;
+use super::selection_popup_common::GenericDisplayRow;
+use super::selection_popup_common::measure_rows_height;
+use super::selection_popup_common::render_rows;
+
+/// What the user did in a [`HunkSelectionView`].
+#[derive(Debug, PartialEq)]
+pub(crate) enum HunkSelectionOutcome {
+    /// Return to the approval options without deciding.
+    Back,
+    Decided(ReviewDecision),
+}
+
+struct HunkItem {
+    report: PatchHunkReport,
+    selected: bool,
+}
+
+/// Checklist of the hunks in a proposed patch, shown from the patch approval
+/// overlay so the user can apply only some of them.
+pub(crate) struct HunkSelectionView {
+    hunks: Vec<HunkItem>,
+    cwd: PathBuf,
+    state: ScrollState,
+    header: Box<dyn Renderable>,
+}
+
+impl HunkSelectionView {
+    /// Every hunk starts selected except those the dry run says conflict.
+    pub(crate) fn new(hunks: Vec<PatchHunkReport>, cwd: PathBuf) -> Self {
+        let mut header = ColumnRenderable::new();
+        header.push(Line::from("Select the hunks to apply".bold()));
+        header.push(Line::from(
+            "Unselected hunks are left out and Codex is told which ones.".dim(),
+        ));
+        let mut state = ScrollState::new();
+        if !hunks.is_empty() {
+            state.selected_idx = Some(0);
+        }
+        Self {
+            hunks: hunks
+                .into_iter()
+                .map(|report| HunkItem {
+                    selected: report.status != PatchHunkStatus::Conflict,
+                    report,
+                })
+                .collect(),
+            cwd,
+            state,
+            header: Box::new(header),
+        }
+    }
+
+    pub(crate) fn handle_key_event(&mut self, key_event: KeyEvent) -> Option<HunkSelectionOutcome> {
+        match key_event {
+            KeyEvent {
+                code: KeyCode::Up, ..
+            }
+            | KeyEvent {
+                code: KeyCode::Char('p'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            }
+            | KeyEvent {
+                code: KeyCode::Char('k'),
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => self.move_up(),
+            KeyEvent {
+                code: KeyCode::Down,
+                ..
+            }
+            | KeyEvent {
+                code: KeyCode::Char('n'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            }
+            | KeyEvent {
+                code: KeyCode::Char('j'),
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => self.move_down(),
+            KeyEvent {
+                code: KeyCode::Char(' '),
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => self.toggle_selected(),
+            KeyEvent {
+                code: KeyCode::Enter,
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => return Some(HunkSelectionOutcome::Decided(self.decision())),
+            KeyEvent {
+                code: KeyCode::Esc, ..
+            } => return Some(HunkSelectionOutcome::Back),
+            _ => {}
+        }
+        None
+    }
+
+    /// `Approved` when every hunk is selected and `Denied` when none is.
+    fn decision(&self) -> ReviewDecision {
+        let declined: Vec<PatchHunkId> = self
+            .hunks
+            .iter()
+            .filter(|item| !item.selected)
+            .map(|item| PatchHunkId {
+                path: item.report.path.clone(),
+                hunk: item.report.hunk,
+            })
+            .collect();
+        if declined.is_empty() {
+            ReviewDecision::Approved
+        } else if declined.len() == self.hunks.len() {
+            ReviewDecision::Denied
+        } else {
+            ReviewDecision::ApprovedHunks { declined }
+        }
+    }
+
+    fn move_up(&mut self) {
+        let len = self.hunks.len();
+        if len == 0 {
+            return;
+        }
+        self.state.move_up_wrap(len);
+        self.state.ensure_visible(len, MAX_POPUP_ROWS.min(len));
+    }
+
+    fn move_down(&mut self) {
+        let len = self.hunks.len();
+        if len == 0 {
+            return;
+        }
+        self.state.move_down_wrap(len);
+        self.state.ensure_visible(len, MAX_POPUP_ROWS.min(len));
+    }
+
+    fn toggle_selected(&mut self) {
+        if let Some(item) = self
+            .state
+            .selected_idx
+            .and_then(|idx| self.hunks.get_mut(idx))
+        {
+            item.selected = !item.selected;
+        }
+    }
+
+    fn build_rows(&self) -> Vec<GenericDisplayRow> {
+        self.hunks
+            .iter()
+            .enumerate()
+            .map(|(idx, item)| {
+                let prefix = if self.state.selected_idx == Some(idx) {
+                    '›'
+                } else {
+                    ' '
+                };
+                let marker = if item.selected { 'x' } else { ' ' };
+                GenericDisplayRow {
+                    name: format!(
+                        "{prefix} [{marker}] {}",
+                        hunk_label(&item.report, &self.cwd)
+                    ),
+                    description: hunk_description(&item.report),
+                    ..Default::default()
+                }
+            })
+            .collect()
+    }
+
+    fn rows_width(total_width: u16) -> u16 {
+        total_width.saturating_sub(2)
+    }
+}
+
+fn hunk_label(report: &PatchHunkReport, cwd: &Path) -> String {
+    let path = report.path.strip_prefix(cwd).unwrap_or(&report.path);
+    let at = report
+        .line
+        .map(|line| format!(" (line {line})"))
+        .unwrap_or_default();
+    format!("{} hunk {}{at}", path.display(), report.hunk)
+}
+
+fn hunk_description(report: &PatchHunkReport) -> Option<String> {
+    let status = match report.status {
+        PatchHunkStatus::Clean => return None,
+        PatchHunkStatus::Fuzzy => "fuzzy",
+        PatchHunkStatus::Conflict => "conflict",
+    };
+    Some(match &report.detail {
+        Some(detail) => format!("{status}: {detail}"),
+        None => status.to_string(),
+    })
+}
+
+impl Renderable for HunkSelectionView {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if area.height == 0 || area.width == 0 {
+            return;
+        }
+
+        let [content_area, footer_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+
+        Block::default()
+            .style(user_message_style())
+            .render(content_area, buf);
+
+        let header_height = self
+            .header
+            .desired_height(content_area.width.saturating_sub(4));
+        let rows = self.build_rows();
+        let rows_width = Self::rows_width(content_area.width);
+        let rows_height = measure_rows_height(
+            &rows,
+            &self.state,
+            MAX_POPUP_ROWS,
+            rows_width.saturating_add(1),
+        );
+        let [header_area, _, list_area] = Layout::vertical([
+            Constraint::Max(header_height),
+            Constraint::Max(1),
+            Constraint::Length(rows_height),
+        ])
+        .areas(content_area.inset(Insets::vh(1, 2)));
+
+        self.header.render(header_area, buf);
+
+        if list_area.height > 0 {
+            let render_area = Rect {
+                x: list_area.x.saturating_sub(2),
+                y: list_area.y,
+                width: rows_width.max(1),
+                height: list_area.height,
+            };
+            render_rows(
+                render_area,
+                buf,
+                &rows,
+                &self.state,
+                MAX_POPUP_ROWS,
+                "  No hunks in this patch",
+            );
+        }
+
+        let hint_area = Rect {
+            x: footer_area.x + 2,
+            y: footer_area.y,
+            width: footer_area.width.saturating_sub(2),
+            height: footer_area.height,
+        };
+        hunk_selection_hint_line().dim().render(hint_area, buf);
+    }
+
+    fn desired_height(&self, width: u16) -> u16 {
+        let rows = self.build_rows();
+        let rows_width = Self::rows_width(width);
+        let rows_height = measure_rows_height(
+            &rows,
+            &self.state,
+            MAX_POPUP_ROWS,
+            rows_width.saturating_add(1),
+        );
+
+        let mut height = self.header.desired_height(width.saturating_sub(4));
+        height = height.saturating_add(rows_height + 3);
+        height.saturating_add(1)
+    }
+}
+
+fn hunk_selection_hint_line() -> Line<'static> {
+    Line::from(vec![
+        "Press ".into(),
+        key_hint::plain(KeyCode::Char(' ')).into(),
+        " to toggle, ".into(),
+        key_hint::plain(KeyCode::Enter).into(),
+        " to apply the selected hunks, or ".into(),
+        key_hint::plain(KeyCode::Esc).into(),
+        " to go back".into(),
+    ])
+}
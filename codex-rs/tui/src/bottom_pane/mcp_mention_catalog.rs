@@ -0,0 +1,245 @@
+//! MCP resources and prompts offered by the `@server:` file popup.
+//!
+//! Typing `@` followed by a configured server name and `:` lists that
+//! server's resources and argument-free prompts instead of files. The
+//! servers are only asked for them the first time such a token is typed.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use codex_core::mcp::mention::mcp_prompt_mention_path;
+use codex_core::mcp::mention::mcp_resource_mention_path;
+use codex_file_search::FileMatch;
+use mcp_types::Prompt;
+use mcp_types::Resource;
+
+#[derive(Debug, Default)]
+pub(crate) struct McpMentionCatalog {
+    /// Configured server names; `@name:` opens the popup for these.
+    servers: Vec<String>,
+    /// `None` until the servers have reported their resources and prompts.
+    entries: Option<Vec<McpMentionEntry>>,
+    requested: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct McpMentionEntry {
+    /// Text after the `@`, e.g. `docs:file:///notes.md`.
+    pub(crate) token: String,
+    /// Resource URI or prompt name.
+    pub(crate) name: String,
+    /// Mention path core reads the resource or prompt from.
+    pub(crate) path: String,
+}
+
+impl McpMentionCatalog {
+    pub(crate) fn set_servers(&mut self, servers: Vec<String>) {
+        self.servers = servers;
+        self.entries = None;
+        self.requested = false;
+    }
+
+    pub(crate) fn set_snapshot(
+        &mut self,
+        resources: &HashMap<String, Vec<Resource>>,
+        prompts: &HashMap<String, Vec<Prompt>>,
+    ) {
+        let mut entries = Vec::new();
+        for (server, resources) in resources {
+            for resource in resources {
+                entries.push(McpMentionEntry {
+                    token: format!("{server}:{}", resource.uri),
+                    name: resource.uri.clone(),
+                    path: mcp_resource_mention_path(server, &resource.uri),
+                });
+            }
+        }
+        for (server, prompts) in prompts {
+            // Prompts that need arguments cannot be expanded from a mention.
+            let prompts = prompts.iter().filter(|prompt| {
+                !prompt
+                    .arguments
+                    .iter()
+                    .flatten()
+                    .any(|argument| argument.required == Some(true))
+            });
+            for prompt in prompts {
+                entries.push(McpMentionEntry {
+                    token: format!("{server}:{}", prompt.name),
+                    name: prompt.name.clone(),
+                    path: mcp_prompt_mention_path(server, &prompt.name),
+                });
+            }
+        }
+        // Tokens end at whitespace, so such entries could never be matched.
+        entries.retain(|entry| !entry.token.contains(char::is_whitespace));
+        entries.sort_by(|a, b| a.token.cmp(&b.token));
+        entries.dedup_by(|a, b| a.token == b.token);
+        self.entries = Some(entries);
+    }
+
+    /// Whether `query` (an `@` token without the `@`) names a configured server.
+    pub(crate) fn is_server_query(&self, query: &str) -> bool {
+        query
+            .split_once(':')
+            .is_some_and(|(server, _)| self.servers.iter().any(|name| name == server))
+    }
+
+    /// Returns `true` once, when the catalog still needs to be fetched.
+    pub(crate) fn take_fetch_request(&mut self) -> bool {
+        if self.entries.is_some() || self.requested {
+            return false;
+        }
+        self.requested = true;
+        true
+    }
+
+    /// Popup rows for `query`, or `None` while the catalog is still loading.
+    pub(crate) fn matches(&self, query: &str) -> Option<Vec<FileMatch>> {
+        let entries = self.entries.as_ref()?;
+        let (server, needle) = query.split_once(':')?;
+        let prefix = format!("{server}:");
+        let needle = needle.to_lowercase();
+        Some(
+            entries
+                .iter()
+                .filter(|entry| {
+                    entry
+                        .token
+                        .strip_prefix(&prefix)
+                        .is_some_and(|target| target.to_lowercase().contains(&needle))
+                })
+                .map(|entry| FileMatch {
+                    score: 0,
+                    path: PathBuf::from(&entry.token),
+                    root: PathBuf::new(),
+                    indices: None,
+                })
+                .collect(),
+        )
+    }
+
+    pub(crate) fn entry(&self, token: &str) -> Option<&McpMentionEntry> {
+        self.entries
+            .as_ref()?
+            .iter()
+            .find(|entry| entry.token == token)
+    }
+
+    /// Entries mentioned as `@server:target` in `text`, in order of first mention.
+    pub(crate) fn mentions_in(&self, text: &str) -> Vec<&McpMentionEntry> {
+        let mut mentioned: Vec<&McpMentionEntry> = Vec::new();
+        for token in text.split_whitespace() {
+            if let Some(entry) = token.strip_prefix('@').and_then(|token| self.entry(token))
+                && !mentioned.contains(&entry)
+            {
+                mentioned.push(entry);
+            }
+        }
+        mentioned
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcp_types::PromptArgument;
+    use pretty_assertions::assert_eq;
+
+    fn resource(uri: &str) -> Resource {
+        Resource {
+            annotations: None,
+            description: None,
+            mime_type: None,
+            name: uri.to_string(),
+            size: None,
+            title: None,
+            uri: uri.to_string(),
+        }
+    }
+
+    fn prompt(name: &str, required: Option<bool>) -> Prompt {
+        Prompt {
+            arguments: required.map(|required| {
+                vec![PromptArgument {
+                    description: None,
+                    name: "topic".to_string(),
+                    required: Some(required),
+                    title: None,
+                }]
+            }),
+            description: None,
+            name: name.to_string(),
+            title: None,
+        }
+    }
+
+    fn catalog() -> McpMentionCatalog {
+        let mut catalog = McpMentionCatalog::default();
+        catalog.set_servers(vec!["docs".to_string()]);
+        catalog.set_snapshot(
+            &HashMap::from([(
+                "docs".to_string(),
+                vec![resource("file:///Notes.md"), resource("file:///todo.txt")],
+            )]),
+            &HashMap::from([(
+                "docs".to_string(),
+                vec![prompt("review", Some(false)), prompt("explain", Some(true))],
+            )]),
+        );
+        catalog
+    }
+
+    #[test]
+    fn fetches_once_for_configured_servers() {
+        let mut catalog = McpMentionCatalog::default();
+        catalog.set_servers(vec!["docs".to_string()]);
+
+        assert!(catalog.is_server_query("docs:"));
+        assert!(!catalog.is_server_query("src/main.rs:10"));
+        assert_eq!(catalog.matches("docs:"), None);
+        assert!(catalog.take_fetch_request());
+        assert!(!catalog.take_fetch_request());
+    }
+
+    #[test]
+    fn matches_resources_and_argument_free_prompts() {
+        let catalog = catalog();
+        let tokens = |query: &str| -> Vec<String> {
+            catalog
+                .matches(query)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|m| m.path.to_string_lossy().into_owned())
+                .collect()
+        };
+
+        assert_eq!(
+            tokens("docs:"),
+            vec![
+                "docs:file:///Notes.md".to_string(),
+                "docs:file:///todo.txt".to_string(),
+                "docs:review".to_string(),
+            ]
+        );
+        assert_eq!(
+            tokens("docs:notes"),
+            vec!["docs:file:///Notes.md".to_string()]
+        );
+    }
+
+    #[test]
+    fn finds_mentions_in_submitted_text() {
+        let catalog = catalog();
+        let paths: Vec<&str> = catalog
+            .mentions_in("summarize @docs:file:///todo.txt using @docs:review and @docs:review")
+            .into_iter()
+            .map(|entry| entry.path.as_str())
+            .collect();
+
+        assert_eq!(
+            paths,
+            vec!["mcp://docs/file:///todo.txt", "mcp-prompt://docs/review"]
+        );
+    }
+}
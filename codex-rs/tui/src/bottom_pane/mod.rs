@@ -18,6 +18,7 @@ use std::path::PathBuf;
 
 use crate::app_event::ConnectorsSnapshot;
 use crate::app_event_sender::AppEventSender;
+use crate::bottom_pane::plan_progress::PlanProgress;
 use crate::bottom_pane::queued_user_messages::QueuedUserMessages;
 use crate::bottom_pane::unified_exec_footer::UnifiedExecFooter;
 use crate::key_hint;
@@ -30,6 +31,7 @@ use bottom_pane_view::BottomPaneView;
 use codex_core::features::Features;
 use codex_core::skills::model::SkillMetadata;
 use codex_file_search::FileMatch;
+use codex_protocol::plan_tool::PlanItemArg;
 use codex_protocol::request_user_input::RequestUserInputEvent;
 use codex_protocol::user_input::TextElement;
 use crossterm::event::KeyCode;
@@ -44,6 +46,7 @@ mod request_user_input;
 pub(crate) use app_link_view::AppLinkView;
 pub(crate) use approval_overlay::ApprovalOverlay;
 pub(crate) use approval_overlay::ApprovalRequest;
+pub(crate) use approval_overlay::ApprovalRequestKey;
 pub(crate) use request_user_input::RequestUserInputOverlay;
 mod bottom_pane_view;
 
@@ -59,13 +62,16 @@ pub mod custom_prompt_view;
 mod experimental_features_view;
 mod file_search_popup;
 mod footer;
+mod hunk_selection_view;
 mod list_selection_view;
+mod mcp_mention_catalog;
 mod prompt_args;
 mod skill_popup;
 mod skills_toggle_view;
 mod slash_commands;
 pub(crate) use footer::CollaborationModeIndicator;
 pub(crate) use list_selection_view::SelectionViewParams;
+pub(crate) use mcp_mention_catalog::McpMentionCatalog;
 mod feedback_view;
 pub(crate) use feedback_view::FeedbackAudience;
 pub(crate) use feedback_view::feedback_disabled_params;
@@ -74,6 +80,7 @@ pub(crate) use feedback_view::feedback_upload_consent_params;
 pub(crate) use skills_toggle_view::SkillsToggleItem;
 pub(crate) use skills_toggle_view::SkillsToggleView;
 mod paste_burst;
+mod plan_progress;
 pub mod popup_consts;
 mod queued_user_messages;
 mod scroll_state;
@@ -147,6 +154,8 @@ pub(crate) struct BottomPane {
     status: Option<StatusIndicatorWidget>,
     /// Unified exec session summary shown above the composer.
     unified_exec_footer: UnifiedExecFooter,
+    /// Unfinished steps of the latest `update_plan` checklist.
+    plan_progress: PlanProgress,
     /// Queued user messages to show above the composer while a turn is running.
     queued_user_messages: QueuedUserMessages,
     context_window_percent: Option<i64>,
@@ -196,6 +205,7 @@ impl BottomPane {
             is_task_running: false,
             status: None,
             unified_exec_footer: UnifiedExecFooter::new(),
+            plan_progress: PlanProgress::new(),
             queued_user_messages: QueuedUserMessages::new(),
             esc_backtrack_hint: false,
             animations_enabled,
@@ -214,6 +224,23 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    pub(crate) fn set_mcp_mention_servers(&mut self, servers: Vec<String>) {
+        self.composer.set_mcp_mention_servers(servers);
+    }
+
+    pub(crate) fn set_mcp_mention_snapshot(
+        &mut self,
+        resources: &HashMap<String, Vec<mcp_types::Resource>>,
+        prompts: &HashMap<String, Vec<mcp_types::Prompt>>,
+    ) {
+        self.composer.set_mcp_mention_snapshot(resources, prompts);
+        self.request_redraw();
+    }
+
+    pub(crate) fn mcp_mentions(&self) -> &McpMentionCatalog {
+        self.composer.mcp_mentions()
+    }
+
     pub fn take_mention_paths(&mut self) -> HashMap<String, String> {
         self.composer.take_mention_paths()
     }
@@ -401,6 +428,11 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    pub(crate) fn open_image_picker(&mut self) {
+        self.composer.open_image_picker();
+        self.request_redraw();
+    }
+
     /// Replace the composer text with `text`.
     pub(crate) fn set_composer_text(
         &mut self,
@@ -597,6 +629,12 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    /// Shows how much of the last request's input came from the prompt cache.
+    pub(crate) fn set_prompt_cache_hit_percent(&mut self, percent: Option<i64>) {
+        self.composer.set_prompt_cache_hit_percent(percent);
+        self.request_redraw();
+    }
+
     /// Show a generic list selection view with the provided items.
     pub(crate) fn show_selection_view(&mut self, params: list_selection_view::SelectionViewParams) {
         let view = list_selection_view::ListSelectionView::new(params, self.app_event_tx.clone());
@@ -615,6 +653,14 @@ impl BottomPane {
         }
     }
 
+    /// Show the latest plan checklist above the composer until every step
+    /// is done.
+    pub(crate) fn set_plan_steps(&mut self, steps: Vec<PlanItemArg>) {
+        if self.plan_progress.set_steps(steps) {
+            self.request_redraw();
+        }
+    }
+
     /// Update custom prompts available for the slash popup.
     pub(crate) fn set_custom_prompts(&mut self, prompts: Vec<CustomPrompt>) {
         self.composer.set_custom_prompts(prompts);
@@ -674,6 +720,25 @@ impl BottomPane {
         self.push_view(Box::new(modal));
     }
 
+    /// Answer a pending approval request from outside its overlay, e.g. from
+    /// a desktop notification button. Requests already answered are ignored.
+    pub(crate) fn resolve_approval(&mut self, key: &ApprovalRequestKey, approve: bool) {
+        let Some(idx) = self
+            .view_stack
+            .iter_mut()
+            .rposition(|view| view.resolve_approval(key, approve))
+        else {
+            return;
+        };
+        if self.view_stack[idx].is_complete() {
+            self.view_stack.remove(idx);
+            if idx == self.view_stack.len() {
+                self.on_active_view_complete();
+            }
+        }
+        self.request_redraw();
+    }
+
     /// Called when the agent requests user input.
     pub fn push_user_input_request(&mut self, request: RequestUserInputEvent) {
         let request = if let Some(view) = self.view_stack.last_mut() {
@@ -746,6 +811,16 @@ impl BottomPane {
         self.composer.flush_paste_burst_if_due()
     }
 
+    pub(crate) fn set_paste_attach_threshold(&mut self, threshold: usize) {
+        self.composer.set_paste_attach_threshold(threshold);
+    }
+
+    /// Burst paste the composer held back because it exceeded the paste
+    /// attach threshold.
+    pub(crate) fn take_pending_large_paste(&mut self) -> Option<String> {
+        self.composer.take_pending_large_paste()
+    }
+
     pub(crate) fn is_in_paste_burst(&self) -> bool {
         // A view can hold paste-burst state independently of the primary
         // composer, so check it first.
@@ -812,9 +887,13 @@ impl BottomPane {
             if !self.unified_exec_footer.is_empty() {
                 flex.push(0, RenderableItem::Borrowed(&self.unified_exec_footer));
             }
+            if !self.plan_progress.is_empty() {
+                flex.push(0, RenderableItem::Borrowed(&self.plan_progress));
+            }
             let has_queued_messages = !self.queued_user_messages.messages.is_empty();
-            let has_status_or_footer =
-                self.status.is_some() || !self.unified_exec_footer.is_empty();
+            let has_status_or_footer = self.status.is_some()
+                || !self.unified_exec_footer.is_empty()
+                || !self.plan_progress.is_empty();
             if has_queued_messages && has_status_or_footer {
                 flex.push(0, RenderableItem::Owned("".into()));
             }
@@ -0,0 +1,144 @@
+use codex_protocol::plan_tool::PlanItemArg;
+use codex_protocol::plan_tool::StepStatus;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::Paragraph;
+
+use crate::live_wrap::take_prefix_by_width;
+use crate::render::renderable::Renderable;
+
+/// Steps still to do that are listed before collapsing the rest.
+const MAX_REMAINING_STEPS: usize = 4;
+
+/// Live view of the latest `update_plan` checklist, shown above the composer
+/// while a turn runs and any step is unfinished.
+pub(crate) struct PlanProgress {
+    steps: Vec<PlanItemArg>,
+}
+
+impl PlanProgress {
+    pub(crate) fn new() -> Self {
+        Self { steps: Vec::new() }
+    }
+
+    pub(crate) fn set_steps(&mut self, steps: Vec<PlanItemArg>) -> bool {
+        if self.steps == steps {
+            return false;
+        }
+        self.steps = steps;
+        true
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.steps
+            .iter()
+            .all(|item| item.status == StepStatus::Completed)
+    }
+
+    fn render_lines(&self, width: u16) -> Vec<Line<'static>> {
+        if self.is_empty() || width < 4 {
+            return Vec::new();
+        }
+        let truncate = |text: String| take_prefix_by_width(&text, width as usize).0;
+
+        let done = self
+            .steps
+            .iter()
+            .filter(|item| item.status == StepStatus::Completed)
+            .count();
+        let total = self.steps.len();
+        let mut lines = vec![Line::from(
+            truncate(format!("  Plan · {done} of {total} done")).dim(),
+        )];
+
+        let remaining: Vec<&PlanItemArg> = self
+            .steps
+            .iter()
+            .filter(|item| item.status != StepStatus::Completed)
+            .collect();
+        for item in remaining.iter().take(MAX_REMAINING_STEPS) {
+            let text = truncate(format!("  □ {}", item.step));
+            lines.push(match item.status {
+                StepStatus::InProgress => Line::from(text.cyan().bold()),
+                StepStatus::Pending | StepStatus::Completed => Line::from(text.dim()),
+            });
+        }
+        if remaining.len() > MAX_REMAINING_STEPS {
+            let hidden = remaining.len() - MAX_REMAINING_STEPS;
+            lines.push(Line::from(truncate(format!("    … +{hidden} more")).dim()));
+        }
+        lines
+    }
+}
+
+impl Renderable for PlanProgress {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if area.is_empty() {
+            return;
+        }
+
+        Paragraph::new(self.render_lines(area.width)).render(area, buf);
+    }
+
+    fn desired_height(&self, width: u16) -> u16 {
+        self.render_lines(width).len() as u16
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn step(text: &str, status: StepStatus) -> PlanItemArg {
+        PlanItemArg {
+            step: text.to_string(),
+            status,
+        }
+    }
+
+    #[test]
+    fn hidden_when_every_step_is_done() {
+        let mut progress = PlanProgress::new();
+        assert_eq!(progress.desired_height(40), 0);
+
+        progress.set_steps(vec![step("Write the parser", StepStatus::Completed)]);
+        assert_eq!(progress.desired_height(40), 0);
+    }
+
+    #[test]
+    fn lists_remaining_steps() {
+        let mut progress = PlanProgress::new();
+        progress.set_steps(vec![
+            step("Read the config loader", StepStatus::Completed),
+            step("Add the new field", StepStatus::InProgress),
+            step("Update the schema", StepStatus::Pending),
+            step("Document the option", StepStatus::Pending),
+            step("Add a test", StepStatus::Pending),
+            step("Run the checks", StepStatus::Pending),
+        ]);
+        let lines: Vec<String> = progress
+            .render_lines(50)
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.clone())
+                    .collect::<String>()
+            })
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                "  Plan · 1 of 6 done",
+                "  □ Add the new field",
+                "  □ Update the schema",
+                "  □ Document the option",
+                "  □ Add a test",
+                "    … +1 more",
+            ]
+        );
+    }
+}
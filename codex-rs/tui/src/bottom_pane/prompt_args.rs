@@ -1,5 +1,4 @@
 use codex_protocol::custom_prompts::CustomPrompt;
-use codex_protocol::custom_prompts::PROMPTS_CMD_PREFIX;
 use codex_protocol::user_input::ByteRange;
 use codex_protocol::user_input::TextElement;
 use lazy_static::lazy_static;
@@ -183,10 +182,11 @@ pub fn parse_prompt_inputs(
     Ok(map)
 }
 
-/// Expands a message of the form `/prompts:name [value] [value] …` using a matching saved prompt.
+/// Expands a message of the form `/prompts:name [value] [value] …`, or `/name …` for a custom
+/// command, using a matching saved prompt.
 ///
-/// If the text does not start with `/prompts:`, or if no prompt named `name` exists,
-/// the function returns `Ok(None)`. On success it returns
+/// If the text does not start with the slash name of a saved prompt, the function returns
+/// `Ok(None)`. On success it returns
 /// `Ok(Some(expanded))`; otherwise it returns a descriptive error.
 pub fn expand_custom_prompt(
     text: &str,
@@ -197,15 +197,9 @@ pub fn expand_custom_prompt(
         return Ok(None);
     };
 
-    // Only handle custom prompts when using the explicit prompts prefix with a colon.
-    let Some(prompt_name) = name.strip_prefix(&format!("{PROMPTS_CMD_PREFIX}:")) else {
+    let Some(prompt) = find_prompt(custom_prompts, name) else {
         return Ok(None);
     };
-
-    let prompt = match custom_prompts.iter().find(|p| p.name == prompt_name) {
-        Some(prompt) => prompt,
-        None => return Ok(None),
-    };
     // If there are named placeholders, expect key=value inputs.
     let required = prompt_argument_names(&prompt.content);
     let local_elements: Vec<TextElement> = text_elements
@@ -252,6 +246,16 @@ pub fn expand_custom_prompt(
     )))
 }
 
+/// The saved prompt invoked as `/{slash_name}`, if any.
+pub fn find_prompt<'a>(
+    custom_prompts: &'a [CustomPrompt],
+    slash_name: &str,
+) -> Option<&'a CustomPrompt> {
+    custom_prompts
+        .iter()
+        .find(|prompt| prompt.slash_name() == slash_name)
+}
+
 /// Detect whether `content` contains numeric placeholders ($1..$9) or `$ARGUMENTS`.
 pub fn prompt_has_numeric_placeholders(content: &str) -> bool {
     if content.contains("$ARGUMENTS") {
@@ -271,11 +275,12 @@ pub fn prompt_has_numeric_placeholders(content: &str) -> bool {
     false
 }
 
-/// Extract positional arguments from a composer first line like "/name a b" for a given prompt name.
-/// Returns empty when the command name does not match or when there are no args.
+/// Extract positional arguments from a composer first line like "/name a b" for a prompt invoked
+/// as `/{slash_name}`. Returns empty when the command name does not match or when there are no
+/// args.
 pub fn extract_positional_args_for_prompt_line(
     line: &str,
-    prompt_name: &str,
+    slash_name: &str,
     text_elements: &[TextElement],
 ) -> Vec<PromptArg> {
     let trimmed = line.trim_start();
@@ -283,11 +288,7 @@ pub fn extract_positional_args_for_prompt_line(
     let Some((name, rest, rest_offset)) = parse_slash_name(trimmed) else {
         return Vec::new();
     };
-    // Require the explicit prompts prefix for custom prompt invocations.
-    let Some(after_prefix) = name.strip_prefix(&format!("{PROMPTS_CMD_PREFIX}:")) else {
-        return Vec::new();
-    };
-    if after_prefix != prompt_name {
+    if name != slash_name {
         return Vec::new();
     }
     let rest_trimmed_start = rest.trim_start();
@@ -324,7 +325,8 @@ pub fn expand_if_numeric_with_positional_args(
     if !prompt_has_numeric_placeholders(&prompt.content) {
         return None;
     }
-    let args = extract_positional_args_for_prompt_line(first_line, &prompt.name, text_elements);
+    let args =
+        extract_positional_args_for_prompt_line(first_line, &prompt.slash_name(), text_elements);
     if args.is_empty() {
         return None;
     }
@@ -545,10 +547,10 @@ fn append_joined_args_with_elements(
     }
 }
 
-/// Constructs a command text for a custom prompt with arguments.
+/// Constructs a command text for a custom prompt invoked as `/{slash_name}` with arguments.
 /// Returns the text and the cursor position (inside the first double quote).
-pub fn prompt_command_with_arg_placeholders(name: &str, args: &[String]) -> (String, usize) {
-    let mut text = format!("/{PROMPTS_CMD_PREFIX}:{name}");
+pub fn prompt_command_with_arg_placeholders(slash_name: &str, args: &[String]) -> (String, usize) {
+    let mut text = format!("/{slash_name}");
     let mut cursor: usize = text.len();
     for (i, arg) in args.iter().enumerate() {
         text.push_str(format!(" {arg}=\"\"").as_str());
@@ -562,6 +564,7 @@ pub fn prompt_command_with_arg_placeholders(name: &str, args: &[String]) -> (Str
 #[cfg(test)]
 mod tests {
     use super::*;
+    use codex_protocol::custom_prompts::PROMPTS_CMD_PREFIX;
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -572,6 +575,7 @@ mod tests {
             content: "Review $USER changes on $BRANCH".to_string(),
             description: None,
             argument_hint: None,
+            command: false,
         }];
 
         let out = expand_custom_prompt("/prompts:my-prompt USER=Alice BRANCH=main", &[], &prompts)
@@ -593,6 +597,7 @@ mod tests {
             content: "Pair $USER with $BRANCH".to_string(),
             description: None,
             argument_hint: None,
+            command: false,
         }];
 
         let out = expand_custom_prompt(
@@ -618,6 +623,7 @@ mod tests {
             content: "Review $USER changes".to_string(),
             description: None,
             argument_hint: None,
+            command: false,
         }];
         let err = expand_custom_prompt("/prompts:my-prompt USER=Alice stray", &[], &prompts)
             .unwrap_err()
@@ -633,6 +639,7 @@ mod tests {
             content: "Review $USER changes on $BRANCH".to_string(),
             description: None,
             argument_hint: None,
+            command: false,
         }];
         let err = expand_custom_prompt("/prompts:my-prompt USER=Alice", &[], &prompts)
             .unwrap_err()
@@ -661,6 +668,7 @@ mod tests {
             content: "literal $$USER".to_string(),
             description: None,
             argument_hint: None,
+            command: false,
         }];
 
         let out = expand_custom_prompt("/prompts:my-prompt", &[], &prompts).unwrap();
@@ -673,6 +681,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn custom_commands_expand_without_prefix() {
+        let prompts = vec![CustomPrompt {
+            name: "review-pr".to_string(),
+            path: "/tmp/commands/review-pr.md".to_string().into(),
+            content: "Review PR $1: $ARGUMENTS".to_string(),
+            description: None,
+            argument_hint: None,
+            command: true,
+        }];
+
+        let out = expand_custom_prompt("/review-pr 42 carefully", &[], &prompts).unwrap();
+        assert_eq!(
+            out,
+            Some(PromptExpansion {
+                text: "Review PR 42: 42 carefully".to_string(),
+                text_elements: Vec::new(),
+            })
+        );
+        assert_eq!(
+            expand_custom_prompt("/prompts:review-pr 42", &[], &prompts).unwrap(),
+            None
+        );
+    }
+
     #[test]
     fn positional_args_treat_placeholder_with_spaces_as_single_token() {
         let placeholder = "[Image #1]";
@@ -721,7 +754,11 @@ mod tests {
             Some(placeholder.to_string()),
         )];
 
-        let args = extract_positional_args_for_prompt_line(&line, "my-prompt", &text_elements);
+        let args = extract_positional_args_for_prompt_line(
+            &line,
+            &format!("{PROMPTS_CMD_PREFIX}:my-prompt"),
+            &text_elements,
+        );
         assert_eq!(
             args,
             vec![
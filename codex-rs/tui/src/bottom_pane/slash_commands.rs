@@ -27,7 +27,8 @@ pub(crate) fn builtins_for_input(
         .collect()
 }
 
-/// Find a single built-in command by exact name, after applying the gating rules.
+/// Find a single built-in command by exact name or alias (e.g. `experiments`),
+/// after applying the gating rules.
 pub(crate) fn find_builtin_command(
     name: &str,
     collaboration_modes_enabled: bool,
@@ -42,7 +43,7 @@ pub(crate) fn find_builtin_command(
         allow_elevate_sandbox,
     )
     .into_iter()
-    .find(|(command_name, _)| *command_name == name)
+    .find(|(command_name, cmd)| *command_name == name || name.parse() == Ok(*cmd))
     .map(|(_, cmd)| cmd)
 }
 
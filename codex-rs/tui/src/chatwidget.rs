@@ -35,12 +35,14 @@ use codex_chatgpt::connectors;
 use codex_core::config::Config;
 use codex_core::config::ConstraintResult;
 use codex_core::config::types::Notifications;
+use codex_core::config_loader::ConfigLayerStack;
 use codex_core::features::FEATURES;
 use codex_core::features::Feature;
 use codex_core::git_info::current_branch_name;
 use codex_core::git_info::local_git_branches;
 use codex_core::models_manager::manager::ModelsManager;
 use codex_core::project_doc::DEFAULT_PROJECT_DOC_FILENAME;
+use codex_core::project_doc::instruction_layers;
 use codex_core::protocol::AgentMessageDeltaEvent;
 use codex_core::protocol::AgentMessageEvent;
 use codex_core::protocol::AgentReasoningDeltaEvent;
@@ -50,6 +52,9 @@ use codex_core::protocol::AgentReasoningRawContentEvent;
 use codex_core::protocol::ApplyPatchApprovalRequestEvent;
 use codex_core::protocol::BackgroundEventEvent;
 use codex_core::protocol::CodexErrorInfo;
+use codex_core::protocol::ContextEdit;
+use codex_core::protocol::ContextEditPreviewEvent;
+use codex_core::protocol::ContextEditedEvent;
 use codex_core::protocol::CreditsSnapshot;
 use codex_core::protocol::DeprecationNoticeEvent;
 use codex_core::protocol::ErrorEvent;
@@ -62,15 +67,23 @@ use codex_core::protocol::ExecCommandOutputDeltaEvent;
 use codex_core::protocol::ExecCommandSource;
 use codex_core::protocol::ExitedReviewModeEvent;
 use codex_core::protocol::ListCustomPromptsResponseEvent;
+use codex_core::protocol::ListJobsResponseEvent;
 use codex_core::protocol::ListSkillsResponseEvent;
+use codex_core::protocol::ListTrashResponseEvent;
 use codex_core::protocol::McpListToolsResponseEvent;
+use codex_core::protocol::McpLoginCompletedEvent;
 use codex_core::protocol::McpStartupCompleteEvent;
 use codex_core::protocol::McpStartupStatus;
 use codex_core::protocol::McpStartupUpdateEvent;
 use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
+use codex_core::protocol::McpToolCallProgressEvent;
+use codex_core::protocol::ModelFailoverEvent;
 use codex_core::protocol::Op;
+use codex_core::protocol::OpenFileRequestEvent;
 use codex_core::protocol::PatchApplyBeginEvent;
+use codex_core::protocol::PinTarget;
+use codex_core::protocol::PinnedContextEvent;
 use codex_core::protocol::RateLimitSnapshot;
 use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::ReviewTarget;
@@ -80,6 +93,7 @@ use codex_core::protocol::TerminalInteractionEvent;
 use codex_core::protocol::TokenUsage;
 use codex_core::protocol::TokenUsageInfo;
 use codex_core::protocol::TurnAbortReason;
+use codex_core::protocol::TurnBlockedEvent;
 use codex_core::protocol::TurnCompleteEvent;
 use codex_core::protocol::TurnDiffEvent;
 use codex_core::protocol::UndoCompletedEvent;
@@ -101,6 +115,7 @@ use codex_protocol::config_types::CollaborationModeMask;
 use codex_protocol::config_types::ModeKind;
 use codex_protocol::config_types::Personality;
 use codex_protocol::config_types::Settings;
+use codex_protocol::config_types::Verbosity;
 #[cfg(target_os = "windows")]
 use codex_protocol::config_types::WindowsSandboxLevel;
 use codex_protocol::models::local_image_label_text;
@@ -130,7 +145,8 @@ const DEFAULT_MODEL_DISPLAY_NAME: &str = "loading";
 const PLAN_IMPLEMENTATION_TITLE: &str = "Implement this plan?";
 const PLAN_IMPLEMENTATION_YES: &str = "Yes, implement this plan";
 const PLAN_IMPLEMENTATION_NO: &str = "No, stay in Plan mode";
-const PLAN_IMPLEMENTATION_CODING_MESSAGE: &str = "Implement the plan.";
+const PLAN_IMPLEMENTATION_CODING_MESSAGE: &str =
+    "Implement the plan step by step, tracking progress with update_plan.";
 
 use crate::app_event::AppEvent;
 use crate::app_event::ConnectorsSnapshot;
@@ -140,6 +156,7 @@ use crate::app_event::WindowsSandboxEnableMode;
 use crate::app_event::WindowsSandboxFallbackReason;
 use crate::app_event_sender::AppEventSender;
 use crate::bottom_pane::ApprovalRequest;
+use crate::bottom_pane::ApprovalRequestKey;
 use crate::bottom_pane::BottomPane;
 use crate::bottom_pane::BottomPaneParams;
 use crate::bottom_pane::CancellationEvent;
@@ -156,7 +173,10 @@ use crate::bottom_pane::SelectionItem;
 use crate::bottom_pane::SelectionViewParams;
 use crate::bottom_pane::custom_prompt_view::CustomPromptView;
 use crate::bottom_pane::popup_consts::standard_popup_hint_line;
+use crate::clipboard_paste::normalize_pasted_path;
 use crate::clipboard_paste::paste_image_to_temp_png;
+use crate::clipboard_paste::save_text_paste_to_temp_file;
+use crate::clipboard_paste::text_paste_reference;
 use crate::collab;
 use crate::collaboration_modes;
 use crate::diff_render::display_path_for;
@@ -174,14 +194,17 @@ use crate::history_cell::WebSearchCell;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
 use crate::markdown::append_markdown;
+use crate::notifications::NotificationAction;
 use crate::render::Insets;
 use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::FlexRenderable;
 use crate::render::renderable::Renderable;
 use crate::render::renderable::RenderableExt;
 use crate::render::renderable::RenderableItem;
+use crate::settings;
 use crate::slash_command::SlashCommand;
 use crate::status::RateLimitSnapshotDisplay;
+use crate::status::format_tokens_compact;
 use crate::text_formatting::truncate_text;
 use crate::tui::FrameRequester;
 mod interrupts;
@@ -216,6 +239,8 @@ use strum::IntoEnumIterator;
 const USER_SHELL_COMMAND_HELP_TITLE: &str = "Prefix a command with ! to run it locally";
 const USER_SHELL_COMMAND_HELP_HINT: &str = "Example: !ls";
 const DEFAULT_OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
+/// Workers started by `/workers start` when no count is given.
+const DEFAULT_WORKER_COUNT: usize = 3;
 // Track information about an in-flight exec command.
 struct RunningCommand {
     command: Vec<String>,
@@ -491,6 +516,9 @@ pub(crate) struct ChatWidget {
     running_commands: HashMap<String, RunningCommand>,
     suppressed_exec_calls: HashSet<String>,
     skills_all: Vec<ProtocolSkillMetadata>,
+    /// Temp files written for large pastes attached as files; removed when
+    /// the widget (and with it the session) goes away.
+    saved_text_pastes: Vec<PathBuf>,
     skills_initial_state: Option<HashMap<PathBuf, bool>>,
     last_unified_wait: Option<UnifiedExecWaitState>,
     unified_exec_wait_streak: Option<UnifiedExecWaitStreak>,
@@ -507,6 +535,9 @@ pub(crate) struct ChatWidget {
     /// bottom pane is treated as "running" while this is populated, even if no agent turn is
     /// currently executing.
     mcp_startup_status: Option<HashMap<String, McpStartupStatus>>,
+    /// Set by `/mcp` so its `McpListToolsResponse` is printed; the composer
+    /// also fetches the listing silently for `@server:` mentions.
+    mcp_tools_output_requested: bool,
     connectors_cache: ConnectorsCacheState,
     // Queue of interruptive UI events deferred during an active write cycle
     interrupts: InterruptManager,
@@ -796,6 +827,8 @@ impl ChatWidget {
             .set_history_metadata(event.history_log_id, event.history_entry_count);
         self.set_skills(None);
         self.bottom_pane.set_connectors_snapshot(None);
+        self.bottom_pane
+            .set_mcp_mention_servers(self.config.mcp_servers.get().keys().cloned().collect());
         self.thread_id = Some(event.session_id);
         self.thread_name = event.thread_name.clone();
         self.forked_from = event.forked_from_id;
@@ -1065,6 +1098,7 @@ impl ChatWidget {
         self.last_unified_wait = None;
         self.unified_exec_wait_streak = None;
         self.clear_unified_exec_processes();
+        self.bottom_pane.set_plan_steps(Vec::new());
         self.request_redraw();
 
         if !from_replay && self.queued_user_messages.is_empty() {
@@ -1072,10 +1106,16 @@ impl ChatWidget {
         }
         // If there is a queued user message, send exactly one now to begin the next turn.
         self.maybe_send_next_queued_input();
-        // Emit a notification when the turn completes (suppressed if focused).
-        self.notify(Notification::AgentTurnComplete {
-            response: last_agent_message.unwrap_or_default(),
-        });
+        // Emit a notification when the turn completes (suppressed if focused),
+        // unless the turn was blocked and that notification is still pending.
+        if !matches!(
+            self.pending_notification,
+            Some(Notification::TurnBlocked { .. })
+        ) {
+            self.notify(Notification::AgentTurnComplete {
+                response: last_agent_message.unwrap_or_default(),
+            });
+        }
 
         self.maybe_show_pending_rate_limit_prompt();
     }
@@ -1159,6 +1199,7 @@ impl ChatWidget {
             Some(info) => self.apply_token_info(info),
             None => {
                 self.bottom_pane.set_context_window(None, None);
+                self.bottom_pane.set_prompt_cache_hit_percent(None);
                 self.token_info = None;
             }
         }
@@ -1168,9 +1209,19 @@ impl ChatWidget {
         let percent = self.context_remaining_percent(&info);
         let used_tokens = self.context_used_tokens(&info, percent.is_some());
         self.bottom_pane.set_context_window(percent, used_tokens);
+        self.bottom_pane
+            .set_prompt_cache_hit_percent(Self::prompt_cache_hit_percent(&info));
         self.token_info = Some(info);
     }
 
+    /// Share of the last request's input served from the prompt cache. Hidden
+    /// until the provider reports a cache hit, since some never report any.
+    fn prompt_cache_hit_percent(info: &TokenUsageInfo) -> Option<i64> {
+        let last = &info.last_token_usage;
+        (info.total_token_usage.cached_input() > 0 && last.input_tokens > 0)
+            .then(|| last.cached_input() * 100 / last.input_tokens)
+    }
+
     fn context_remaining_percent(&self, info: &TokenUsageInfo) -> Option<i64> {
         info.model_context_window.map(|window| {
             info.last_token_usage
@@ -1192,6 +1243,7 @@ impl ChatWidget {
                 Some(info) => self.apply_token_info(info),
                 None => {
                     self.bottom_pane.set_context_window(None, None);
+                    self.bottom_pane.set_prompt_cache_hit_percent(None);
                     self.token_info = None;
                 }
             }
@@ -1280,6 +1332,7 @@ impl ChatWidget {
         self.last_unified_wait = None;
         self.unified_exec_wait_streak = None;
         self.clear_unified_exec_processes();
+        self.bottom_pane.set_plan_steps(Vec::new());
         self.stream_controller = None;
         self.maybe_show_pending_rate_limit_prompt();
     }
@@ -1311,6 +1364,26 @@ impl ChatWidget {
         self.maybe_send_next_queued_input();
     }
 
+    fn on_turn_blocked(&mut self, event: TurnBlockedEvent) {
+        let TurnBlockedEvent { category, message } = event;
+        self.add_to_history(history_cell::new_turn_blocked_event(&category, message));
+        self.notify(Notification::TurnBlocked { category });
+        self.request_redraw();
+    }
+
+    fn on_model_failover(&mut self, event: ModelFailoverEvent) {
+        let ModelFailoverEvent {
+            from_provider,
+            from_model,
+            to_provider,
+            to_model,
+            reason,
+        } = event;
+        self.on_warning(format!(
+            "{from_model} on {from_provider} kept failing ({reason}); continuing with {to_model} on {to_provider}."
+        ));
+    }
+
     fn on_warning(&mut self, message: impl Into<String>) {
         self.add_to_history(history_cell::new_warning_event(message.into()));
         self.request_redraw();
@@ -1472,6 +1545,7 @@ impl ChatWidget {
 
     fn on_plan_update(&mut self, update: UpdatePlanArgs) {
         self.saw_plan_update_this_turn = true;
+        self.bottom_pane.set_plan_steps(update.plan.clone());
         self.add_to_history(history_cell::new_plan_update(update));
     }
 
@@ -1605,6 +1679,16 @@ impl ChatWidget {
         self.request_redraw();
     }
 
+    fn on_open_file_request(&mut self, event: OpenFileRequestEvent) {
+        self.flush_answer_stream_with_separator();
+        self.add_to_history(history_cell::new_open_file_request(
+            event,
+            &self.config.cwd,
+            self.config.file_opener,
+        ));
+        self.request_redraw();
+    }
+
     fn on_patch_apply_end(&mut self, event: codex_core::protocol::PatchApplyEndEvent) {
         let ev2 = event.clone();
         self.defer_or_handle(
@@ -1715,6 +1799,21 @@ impl ChatWidget {
         self.defer_or_handle(|q| q.push_mcp_begin(ev), |s| s.handle_mcp_begin_now(ev2));
     }
 
+    fn on_mcp_tool_call_progress(&mut self, ev: McpToolCallProgressEvent) {
+        let Some(cell) = self
+            .active_cell
+            .as_mut()
+            .and_then(|cell| cell.as_any_mut().downcast_mut::<McpToolCallCell>())
+            .filter(|cell| cell.call_id() == ev.call_id)
+        else {
+            return;
+        };
+        let summary = cell.record_progress(&ev);
+        self.bump_active_cell_revision();
+        self.set_status(self.current_status_header.clone(), Some(summary));
+        self.request_redraw();
+    }
+
     fn on_mcp_tool_call_end(&mut self, ev: McpToolCallEndEvent) {
         let ev2 = ev.clone();
         self.defer_or_handle(|q| q.push_mcp_end(ev), |s| s.handle_mcp_end_now(ev2));
@@ -1829,9 +1928,39 @@ impl ChatWidget {
         if self.retry_status_header.is_none() {
             self.retry_status_header = Some(self.current_status_header.clone());
         }
+        self.mark_interrupted_stream();
         self.set_status(message, additional_details);
     }
 
+    /// Closes off output that was still streaming when the connection dropped. The retry
+    /// starts a new response, so that text never becomes part of the conversation; it stays
+    /// in scrollback followed by a warning saying so.
+    fn mark_interrupted_stream(&mut self) {
+        let mut interrupted = false;
+        if let Some(mut controller) = self.stream_controller.take() {
+            interrupted = true;
+            if let Some(cell) = controller.finalize() {
+                self.add_boxed_history(cell);
+            }
+        }
+        if let Some(mut controller) = self.plan_stream_controller.take() {
+            interrupted = true;
+            if let Some(cell) = controller.finalize() {
+                self.add_boxed_history(cell);
+            }
+        }
+        self.reasoning_buffer.clear();
+        self.full_reasoning_buffer.clear();
+        if interrupted {
+            self.add_to_history(history_cell::new_warning_event(
+                "The connection dropped while this response was streaming. The text above was \
+                 cut off and is not part of the conversation; the model continues in a new \
+                 response."
+                    .to_string(),
+            ));
+        }
+    }
+
     /// Periodic tick to commit at most one queued line to history with a small delay,
     /// animating the output.
     pub(crate) fn on_commit_tick(&mut self) {
@@ -1989,6 +2118,13 @@ impl ChatWidget {
             cell.complete_call(&ev.call_id, output, ev.duration);
             if cell.should_flush() {
                 self.flush_active_cell();
+                // Exploring cells keep grouping calls, so only standalone
+                // commands get the comparison with their previous run.
+                if let Some(diff) = ev.previous_run_diff.as_deref()
+                    && !is_unified_exec_interaction
+                {
+                    self.add_to_history(history_cell::new_previous_run_diff(diff));
+                }
             } else {
                 self.bump_active_cell_revision();
                 self.request_redraw();
@@ -2015,14 +2151,17 @@ impl ChatWidget {
         self.flush_answer_stream_with_separator();
         let command = shlex::try_join(ev.command.iter().map(String::as_str))
             .unwrap_or_else(|_| ev.command.join(" "));
-        self.notify(Notification::ExecApprovalRequested { command });
-
         let request = ApprovalRequest::Exec {
             id,
             command: ev.command,
             reason: ev.reason,
             proposed_execpolicy_amendment: ev.proposed_execpolicy_amendment,
         };
+        self.notify(Notification::ExecApprovalRequested {
+            key: request.key(),
+            command,
+        });
+
         self.bottom_pane
             .push_approval_request(request, &self.config.features);
         self.request_redraw();
@@ -2040,11 +2179,14 @@ impl ChatWidget {
             reason: ev.reason,
             changes: ev.changes.clone(),
             cwd: self.config.cwd.clone(),
+            hunks: ev.hunks,
         };
+        let key = request.key();
         self.bottom_pane
             .push_approval_request(request, &self.config.features);
         self.request_redraw();
         self.notify(Notification::EditApprovalRequested {
+            key,
             cwd: self.config.cwd.clone(),
             changes: ev.changes.keys().cloned().collect(),
         });
@@ -2053,15 +2195,15 @@ impl ChatWidget {
     pub(crate) fn handle_elicitation_request_now(&mut self, ev: ElicitationRequestEvent) {
         self.flush_answer_stream_with_separator();
 
-        self.notify(Notification::ElicitationRequested {
-            server_name: ev.server_name.clone(),
-        });
-
         let request = ApprovalRequest::McpElicitation {
-            server_name: ev.server_name,
+            server_name: ev.server_name.clone(),
             request_id: ev.id,
             message: ev.message,
         };
+        self.notify(Notification::ElicitationRequested {
+            key: request.key(),
+            server_name: ev.server_name,
+        });
         self.bottom_pane
             .push_approval_request(request, &self.config.features);
         self.request_redraw();
@@ -2157,6 +2299,15 @@ impl ChatWidget {
             result,
         } = ev;
 
+        let showed_progress = self
+            .active_cell
+            .as_ref()
+            .and_then(|cell| cell.as_any().downcast_ref::<McpToolCallCell>())
+            .is_some_and(|cell| cell.call_id() == call_id && cell.has_progress());
+        if showed_progress {
+            self.set_status_header(self.current_status_header.clone());
+        }
+
         let extra_cell = match self
             .active_cell
             .as_mut()
@@ -2247,6 +2398,7 @@ impl ChatWidget {
             active_cell_revision: 0,
             config,
             skills_all: Vec::new(),
+            saved_text_pastes: Vec::new(),
             skills_initial_state: None,
             current_collaboration_mode,
             active_collaboration_mask,
@@ -2271,6 +2423,7 @@ impl ChatWidget {
             unified_exec_processes: Vec::new(),
             agent_turn_running: false,
             mcp_startup_status: None,
+            mcp_tools_output_requested: false,
             connectors_cache: ConnectorsCacheState::default(),
             interrupts: InterruptManager::new(),
             reasoning_buffer: String::new(),
@@ -2309,6 +2462,9 @@ impl ChatWidget {
         widget.bottom_pane.set_collaboration_modes_enabled(
             widget.config.features.enabled(Feature::CollaborationModes),
         );
+        widget
+            .bottom_pane
+            .set_paste_attach_threshold(widget.config.tui_paste_attach_threshold);
         widget.sync_personality_command_enabled();
         #[cfg(target_os = "windows")]
         widget.bottom_pane.set_windows_degraded_sandbox_active(
@@ -2392,6 +2548,7 @@ impl ChatWidget {
             active_cell_revision: 0,
             config,
             skills_all: Vec::new(),
+            saved_text_pastes: Vec::new(),
             skills_initial_state: None,
             current_collaboration_mode,
             active_collaboration_mask,
@@ -2416,6 +2573,7 @@ impl ChatWidget {
             unified_exec_processes: Vec::new(),
             agent_turn_running: false,
             mcp_startup_status: None,
+            mcp_tools_output_requested: false,
             connectors_cache: ConnectorsCacheState::default(),
             interrupts: InterruptManager::new(),
             reasoning_buffer: String::new(),
@@ -2454,6 +2612,9 @@ impl ChatWidget {
         widget.bottom_pane.set_collaboration_modes_enabled(
             widget.config.features.enabled(Feature::CollaborationModes),
         );
+        widget
+            .bottom_pane
+            .set_paste_attach_threshold(widget.config.tui_paste_attach_threshold);
         widget.sync_personality_command_enabled();
 
         widget
@@ -2526,6 +2687,7 @@ impl ChatWidget {
             active_cell_revision: 0,
             config,
             skills_all: Vec::new(),
+            saved_text_pastes: Vec::new(),
             skills_initial_state: None,
             current_collaboration_mode,
             active_collaboration_mask,
@@ -2550,6 +2712,7 @@ impl ChatWidget {
             unified_exec_processes: Vec::new(),
             agent_turn_running: false,
             mcp_startup_status: None,
+            mcp_tools_output_requested: false,
             connectors_cache: ConnectorsCacheState::default(),
             interrupts: InterruptManager::new(),
             reasoning_buffer: String::new(),
@@ -2588,6 +2751,9 @@ impl ChatWidget {
         widget.bottom_pane.set_collaboration_modes_enabled(
             widget.config.features.enabled(Feature::CollaborationModes),
         );
+        widget
+            .bottom_pane
+            .set_paste_attach_threshold(widget.config.tui_paste_attach_threshold);
         widget.sync_personality_command_enabled();
         #[cfg(target_os = "windows")]
         widget.bottom_pane.set_windows_degraded_sandbox_active(
@@ -2741,6 +2907,7 @@ impl ChatWidget {
                 InputResult::None => {}
             },
         }
+        self.open_pending_large_paste_prompt();
     }
 
     pub(crate) fn attach_image(&mut self, path: PathBuf) {
@@ -2813,6 +2980,9 @@ impl ChatWidget {
             SlashCommand::Fork => {
                 self.app_event_tx.send(AppEvent::ForkCurrentSession);
             }
+            SlashCommand::History => {
+                self.app_event_tx.send(AppEvent::OpenHistoryBrowser);
+            }
             SlashCommand::Init => {
                 let init_target = self.config.cwd.join(DEFAULT_PROJECT_DOC_FILENAME);
                 if init_target.exists() {
@@ -2826,8 +2996,24 @@ impl ChatWidget {
                 self.submit_user_message(INIT_PROMPT.to_string().into());
             }
             SlashCommand::Compact => {
-                self.clear_token_usage();
-                self.app_event_tx.send(AppEvent::CodexOp(Op::Compact));
+                self.submit_op(Op::PreviewContextEdit {
+                    edit: ContextEdit::Compact,
+                });
+            }
+            SlashCommand::ClearContext => {
+                self.submit_op(Op::PreviewContextEdit {
+                    edit: ContextEdit::Clear,
+                });
+            }
+            SlashCommand::Rewind => {
+                self.app_event_tx.send(AppEvent::Rewind { turn: None });
+            }
+            SlashCommand::Merge => {
+                self.app_event_tx.send(AppEvent::MergeSessionWorktree);
+            }
+            SlashCommand::Discard => {
+                self.app_event_tx
+                    .send(AppEvent::DiscardSessionWorktree { confirmed: false });
             }
             SlashCommand::Review => {
                 self.open_review_popup();
@@ -2841,6 +3027,9 @@ impl ChatWidget {
             SlashCommand::Personality => {
                 self.open_personality_popup();
             }
+            SlashCommand::Verbosity => {
+                self.open_verbosity_popup();
+            }
             SlashCommand::Plan => {
                 if !self.collaboration_modes_enabled() {
                     self.add_info_message(
@@ -2868,6 +3057,9 @@ impl ChatWidget {
             SlashCommand::Agent => {
                 self.app_event_tx.send(AppEvent::OpenAgentPicker);
             }
+            SlashCommand::Workers => {
+                self.app_event_tx.send(AppEvent::OpenWorkersView);
+            }
             SlashCommand::Approvals => {
                 self.open_approvals_popup();
             }
@@ -2922,21 +3114,24 @@ impl ChatWidget {
             SlashCommand::Experimental => {
                 self.open_experimental_popup();
             }
+            SlashCommand::Settings => {
+                self.open_settings_popup();
+            }
             SlashCommand::Quit | SlashCommand::Exit => {
-                self.request_quit_without_confirmation();
+                self.request_quit();
             }
             SlashCommand::Logout => {
                 if let Err(e) = codex_core::auth::logout(
-                    &self.config.codex_home,
+                    &self.config.auth_home(),
                     self.config.cli_auth_credentials_store_mode,
                 ) {
                     tracing::error!("failed to logout: {e}");
                 }
-                self.request_quit_without_confirmation();
+                self.request_quit();
+            }
+            SlashCommand::Undo => {
+                self.app_event_tx.send(AppEvent::CodexOp(Op::Undo));
             }
-            // SlashCommand::Undo => {
-            //     self.app_event_tx.send(AppEvent::CodexOp(Op::Undo));
-            // }
             SlashCommand::Diff => {
                 self.add_diff_in_progress();
                 let tx = self.app_event_tx.clone();
@@ -2954,9 +3149,42 @@ impl ChatWidget {
                     tx.send(AppEvent::DiffResult(text));
                 });
             }
+            SlashCommand::Instructions => {
+                let tx = self.app_event_tx.clone();
+                let config = self.config.clone();
+                tokio::spawn(async move {
+                    let layers = instruction_layers(&config).await;
+                    tx.send(AppEvent::InstructionsResult(layers));
+                });
+            }
+            SlashCommand::Commit => {
+                if !self.config.features.enabled(Feature::GitCommitTool) {
+                    self.add_info_message(
+                        "/commit needs the Git commits feature.".to_string(),
+                        Some("Enable it in /experimental.".to_string()),
+                    );
+                    return;
+                }
+                const COMMIT_PROMPT: &str = include_str!("../prompt_for_commit_command.md");
+                self.submit_user_message(COMMIT_PROMPT.to_string().into());
+            }
+            SlashCommand::Pr => {
+                if !self.config.features.enabled(Feature::PullRequestTool) {
+                    self.add_info_message(
+                        "/pr needs the Pull requests feature.".to_string(),
+                        Some("Enable it in /experimental.".to_string()),
+                    );
+                    return;
+                }
+                const PR_PROMPT: &str = include_str!("../prompt_for_pr_command.md");
+                self.submit_user_message(PR_PROMPT.to_string().into());
+            }
             SlashCommand::Mention => {
                 self.insert_str("@");
             }
+            SlashCommand::Image => {
+                self.bottom_pane.open_image_picker();
+            }
             SlashCommand::Skills => {
                 self.open_skills_menu();
             }
@@ -2966,6 +3194,18 @@ impl ChatWidget {
             SlashCommand::Ps => {
                 self.add_ps_output();
             }
+            SlashCommand::Terminal => {
+                self.take_over_terminal("");
+            }
+            SlashCommand::Pin => {
+                self.submit_op(Op::ListPins);
+            }
+            SlashCommand::Jobs => {
+                self.submit_op(Op::ListJobs);
+            }
+            SlashCommand::Trash => {
+                self.submit_op(Op::ListTrash);
+            }
             SlashCommand::Mcp => {
                 self.add_mcp_output();
             }
@@ -3017,6 +3257,8 @@ impl ChatWidget {
                         ]),
                         reason: None,
                         grant_root: Some(PathBuf::from("/tmp")),
+                        hunks: Vec::new(),
+                        file_operations: Vec::new(),
                     }),
                 }));
             }
@@ -3062,6 +3304,15 @@ impl ChatWidget {
                     .send(AppEvent::CodexOp(Op::SetThreadName { name }));
                 self.bottom_pane.drain_pending_submission_state();
             }
+            SlashCommand::Model if !trimmed.is_empty() => {
+                let Some((prepared_args, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
+                    return;
+                };
+                self.apply_model_from_args(&prepared_args);
+                self.bottom_pane.drain_pending_submission_state();
+            }
             SlashCommand::Plan if !trimmed.is_empty() => {
                 self.dispatch_command(cmd);
                 if self.active_mode_kind() != ModeKind::Plan {
@@ -3105,6 +3356,194 @@ impl ChatWidget {
                 });
                 self.bottom_pane.drain_pending_submission_state();
             }
+            SlashCommand::Trash if !trimmed.is_empty() => {
+                let Some((prepared_args, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
+                    return;
+                };
+                let mut words = prepared_args.split_whitespace();
+                match words.next() {
+                    Some("list") => self.submit_op(Op::ListTrash),
+                    Some("restore") => {
+                        let paths = words.map(|path| self.config.cwd.join(path)).collect();
+                        self.submit_op(Op::RestoreTrash { paths });
+                    }
+                    Some("purge") => self.open_purge_trash_confirmation(false),
+                    _ => self.add_error_message(
+                        "Usage: /trash [list | restore [path ...] | purge]".to_string(),
+                    ),
+                }
+                self.bottom_pane.drain_pending_submission_state();
+            }
+            SlashCommand::Jobs if !trimmed.is_empty() => {
+                let Some((prepared_args, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
+                    return;
+                };
+                let args = prepared_args.trim();
+                let (verb, rest) = args.split_once(char::is_whitespace).unwrap_or((args, ""));
+                let rest = rest.trim();
+                match verb {
+                    "list" if rest.is_empty() => self.submit_op(Op::ListJobs),
+                    "start" if !rest.is_empty() => self.submit_op(Op::StartJob {
+                        command: rest.to_string(),
+                    }),
+                    "stop" if !rest.is_empty() => self.submit_op(Op::StopJob {
+                        job_id: rest.to_string(),
+                    }),
+                    _ => self.add_error_message(
+                        "Usage: /jobs [list | start <command> | stop <id>]".to_string(),
+                    ),
+                }
+                self.bottom_pane.drain_pending_submission_state();
+            }
+            SlashCommand::Terminal if !trimmed.is_empty() => {
+                let Some((prepared_args, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
+                    return;
+                };
+                self.take_over_terminal(prepared_args.trim());
+                self.bottom_pane.drain_pending_submission_state();
+            }
+            SlashCommand::Image if !trimmed.is_empty() => {
+                let Some((prepared_args, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
+                    return;
+                };
+                self.bottom_pane.drain_pending_submission_state();
+                let Some(path) = normalize_pasted_path(prepared_args.trim()) else {
+                    self.add_error_message("Usage: /image [path]".to_string());
+                    return;
+                };
+                let path = self.config.cwd.join(path);
+                match image::image_dimensions(&path) {
+                    Ok(_) => self.attach_image(path),
+                    Err(err) => self
+                        .add_error_message(format!("Could not attach {}: {err}", path.display())),
+                }
+            }
+            SlashCommand::Mcp if !trimmed.is_empty() => {
+                let Some((prepared_args, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
+                    return;
+                };
+                let args = prepared_args.trim();
+                let (verb, rest) = args.split_once(char::is_whitespace).unwrap_or((args, ""));
+                let name = rest.trim();
+                match verb {
+                    "status" if name.is_empty() => self.submit_op(Op::McpServerStatus),
+                    "login" if !name.is_empty() => {
+                        self.add_info_message(
+                            format!("Opening your browser to log in to MCP server '{name}'..."),
+                            None,
+                        );
+                        self.submit_op(Op::McpLogin {
+                            name: name.to_string(),
+                        });
+                    }
+                    _ => {
+                        self.add_error_message("Usage: /mcp [status | login <server>]".to_string())
+                    }
+                }
+                self.bottom_pane.drain_pending_submission_state();
+            }
+            SlashCommand::Pin if !trimmed.is_empty() => {
+                let Some((prepared_args, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
+                    return;
+                };
+                let args = prepared_args.trim();
+                let (verb, rest) = args.split_once(char::is_whitespace).unwrap_or((args, ""));
+                let rest = rest.trim();
+                // `None` with `remove all` clears every pin.
+                let unpin_id = rest.parse::<u32>().ok();
+                match verb {
+                    "list" if rest.is_empty() => self.submit_op(Op::ListPins),
+                    "file" if !rest.is_empty() => self.submit_op(Op::Pin {
+                        target: PinTarget::File {
+                            path: PathBuf::from(rest),
+                        },
+                    }),
+                    "message" => self.submit_op(Op::Pin {
+                        target: PinTarget::Message {
+                            text: (!rest.is_empty()).then(|| rest.to_string()),
+                        },
+                    }),
+                    "remove" if rest == "all" || unpin_id.is_some() => {
+                        self.submit_op(Op::Unpin { id: unpin_id });
+                    }
+                    _ => self.add_error_message(
+                        "Usage: /pin [file <path> | message [text] | remove <id | all>]"
+                            .to_string(),
+                    ),
+                }
+                self.bottom_pane.drain_pending_submission_state();
+            }
+            SlashCommand::ClearContext if !trimmed.is_empty() => {
+                let Some((prepared_args, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
+                    return;
+                };
+                match parse_turn_range(&prepared_args) {
+                    Some((first, last)) => self.submit_op(Op::PreviewContextEdit {
+                        edit: ContextEdit::DropTurns { first, last },
+                    }),
+                    None => self.add_error_message("Usage: /clear-context [N | N-M]".to_string()),
+                }
+                self.bottom_pane.drain_pending_submission_state();
+            }
+            SlashCommand::Rewind if !trimmed.is_empty() => {
+                let Some((prepared_args, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
+                    return;
+                };
+                match prepared_args.trim().parse::<usize>() {
+                    Ok(turn) => self
+                        .app_event_tx
+                        .send(AppEvent::Rewind { turn: Some(turn) }),
+                    Err(_) => self.add_error_message("Usage: /rewind [N]".to_string()),
+                }
+                self.bottom_pane.drain_pending_submission_state();
+            }
+            SlashCommand::Workers if !trimmed.is_empty() => {
+                let Some((prepared_args, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
+                    return;
+                };
+                let mut words = prepared_args.split_whitespace();
+                match (words.next(), words.next(), words.next()) {
+                    (Some("start"), Some(queue), count) => {
+                        match count.map_or(Ok(DEFAULT_WORKER_COUNT), str::parse::<usize>) {
+                            Ok(workers) if workers > 0 => {
+                                self.app_event_tx.send(AppEvent::StartWorkers {
+                                    queue_path: self.config.cwd.join(queue),
+                                    workers,
+                                });
+                            }
+                            _ => self.add_error_message(
+                                "Worker count must be a positive number.".to_string(),
+                            ),
+                        }
+                    }
+                    (Some("stop"), None, None) => self.app_event_tx.send(AppEvent::StopWorkers),
+                    (Some("status"), None, None) => {
+                        self.app_event_tx.send(AppEvent::OpenWorkersView);
+                    }
+                    _ => self.add_error_message(
+                        "Usage: /workers [start <queue-file> [count] | stop | status]".to_string(),
+                    ),
+                }
+                self.bottom_pane.drain_pending_submission_state();
+            }
             _ => self.dispatch_command(cmd),
         }
     }
@@ -3142,13 +3581,95 @@ impl ChatWidget {
     }
 
     pub(crate) fn handle_paste(&mut self, text: String) {
+        let threshold = self.config.tui_paste_attach_threshold;
+        if threshold > 0
+            && self.bottom_pane.no_modal_or_popup_active()
+            && text.chars().count() > threshold
+        {
+            self.open_large_paste_prompt(text);
+            return;
+        }
         self.bottom_pane.handle_paste(text);
     }
 
+    /// Prompts for a large paste that arrived as a key burst rather than a
+    /// bracketed paste.
+    fn open_pending_large_paste_prompt(&mut self) {
+        if let Some(text) = self.bottom_pane.take_pending_large_paste() {
+            self.open_large_paste_prompt(text);
+        }
+    }
+
+    /// Ask whether a very large paste should be attached as a file instead of
+    /// going into the prompt verbatim. Dismissing the prompt drops the paste.
+    fn open_large_paste_prompt(&mut self, text: String) {
+        let lines = text.lines().count();
+        let chars = text.chars().count();
+        let attach_text = text.clone();
+        let attach_action: SelectionAction = Box::new(move |tx| {
+            tx.send(AppEvent::ResolveLargePaste {
+                text: attach_text.clone(),
+                attach: true,
+            });
+        });
+        let inline_action: SelectionAction = Box::new(move |tx| {
+            tx.send(AppEvent::ResolveLargePaste {
+                text: text.clone(),
+                attach: false,
+            });
+        });
+        let items = vec![
+            SelectionItem {
+                name: "Attach as file".to_string(),
+                description: Some(
+                    "Save the paste to a temp file and reference its path in the prompt."
+                        .to_string(),
+                ),
+                actions: vec![attach_action],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+            SelectionItem {
+                name: "Paste inline".to_string(),
+                description: Some("Insert the full text into the composer.".to_string()),
+                actions: vec![inline_action],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+        ];
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Large paste".to_string()),
+            subtitle: Some(format!(
+                "{lines} lines, {chars} characters. Press esc to discard it."
+            )),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    pub(crate) fn resolve_large_paste(&mut self, text: String, attach: bool) {
+        if !attach {
+            self.bottom_pane.handle_paste(text);
+            return;
+        }
+        match save_text_paste_to_temp_file(&text) {
+            Ok(path) => {
+                self.bottom_pane
+                    .insert_str(&text_paste_reference(&path, &text));
+                self.saved_text_pastes.push(path);
+            }
+            Err(err) => {
+                self.add_error_message(format!("Failed to save paste to a file: {err}"));
+            }
+        }
+    }
+
     // Returns true if caller should skip rendering this frame (a future frame is scheduled).
     pub(crate) fn handle_paste_burst_tick(&mut self, frame_requester: FrameRequester) -> bool {
         if self.bottom_pane.flush_paste_burst_if_due() {
             // A paste just flushed; request an immediate redraw and skip this frame.
+            self.open_pending_large_paste_prompt();
             self.request_redraw();
             true
         } else if self.bottom_pane.is_in_paste_burst() {
@@ -3282,6 +3803,13 @@ impl ChatWidget {
             }
         }
 
+        for entry in self.bottom_pane.mcp_mentions().mentions_in(&text) {
+            items.push(UserInput::Mention {
+                name: entry.name.clone(),
+                path: entry.path.clone(),
+            });
+        }
+
         let effective_mode = self.effective_collaboration_mode();
         let collaboration_mode = if self.collaboration_modes_enabled() {
             self.active_collaboration_mask
@@ -3414,6 +3942,8 @@ impl ChatWidget {
                 self.on_rate_limit_snapshot(ev.rate_limits);
             }
             EventMsg::Warning(WarningEvent { message }) => self.on_warning(message),
+            EventMsg::TurnBlocked(event) => self.on_turn_blocked(event),
+            EventMsg::ModelFailover(event) => self.on_model_failover(event),
             EventMsg::Error(ErrorEvent {
                 message,
                 codex_error_info,
@@ -3468,14 +3998,29 @@ impl ChatWidget {
             EventMsg::PatchApplyEnd(ev) => self.on_patch_apply_end(ev),
             EventMsg::ExecCommandEnd(ev) => self.on_exec_command_end(ev),
             EventMsg::ViewImageToolCall(ev) => self.on_view_image_tool_call(ev),
+            EventMsg::OpenFileRequest(ev) => self.on_open_file_request(ev),
             EventMsg::McpToolCallBegin(ev) => self.on_mcp_tool_call_begin(ev),
+            EventMsg::McpToolCallProgress(ev) => self.on_mcp_tool_call_progress(ev),
             EventMsg::McpToolCallEnd(ev) => self.on_mcp_tool_call_end(ev),
             EventMsg::WebSearchBegin(ev) => self.on_web_search_begin(ev),
             EventMsg::WebSearchEnd(ev) => self.on_web_search_end(ev),
             EventMsg::GetHistoryEntryResponse(ev) => self.on_get_history_entry_response(ev),
             EventMsg::McpListToolsResponse(ev) => self.on_list_mcp_tools(ev),
+            EventMsg::McpLoginCompleted(ev) => self.on_mcp_login_completed(ev),
+            EventMsg::McpServerStatusResponse(ev) => {
+                self.add_to_history(history_cell::new_mcp_server_status(ev.servers));
+            }
             EventMsg::ListCustomPromptsResponse(ev) => self.on_list_custom_prompts(ev),
             EventMsg::ListSkillsResponse(ev) => self.on_list_skills(ev),
+            EventMsg::ListTrashResponse(ev) => self.on_list_trash(ev),
+            EventMsg::ListJobsResponse(ev) => self.on_list_jobs(ev),
+            EventMsg::ContextEditPreview(ev) => {
+                if !from_replay {
+                    self.on_context_edit_preview(ev);
+                }
+            }
+            EventMsg::ContextEdited(ev) => self.on_context_edited(ev),
+            EventMsg::PinnedContextUpdated(ev) => self.on_pinned_context_updated(ev),
             EventMsg::SkillsUpdateAvailable => {
                 self.submit_op(Op::ListSkills {
                     cwds: Vec::new(),
@@ -3490,6 +4035,10 @@ impl ChatWidget {
             }
             EventMsg::UndoStarted(ev) => self.on_undo_started(ev),
             EventMsg::UndoCompleted(ev) => self.on_undo_completed(ev),
+            EventMsg::CheckpointCreated(ev) => {
+                self.app_event_tx
+                    .send(AppEvent::CheckpointCreated(ev.commit_id));
+            }
             EventMsg::StreamError(StreamErrorEvent {
                 message,
                 additional_details,
@@ -3517,6 +4066,7 @@ impl ChatWidget {
             EventMsg::CollabCloseEnd(ev) => self.on_collab_event(collab::close_end(ev)),
             EventMsg::ThreadRolledBack(_) => {}
             EventMsg::RawResponseItem(_)
+            | EventMsg::ModelSnapshot(_)
             | EventMsg::ItemStarted(_)
             | EventMsg::AgentMessageContentDelta(_)
             | EventMsg::ReasoningContentDelta(_)
@@ -3598,16 +4148,27 @@ impl ChatWidget {
 
     /// Exit the UI immediately without waiting for shutdown.
     ///
-    /// Prefer [`Self::request_quit_without_confirmation`] for user-initiated exits;
+    /// Prefer [`Self::request_quit`] for user-initiated exits;
     /// this is mainly a fallback for shutdown completion or emergency exits.
     fn request_immediate_exit(&self) {
         self.app_event_tx.send(AppEvent::Exit(ExitMode::Immediate));
     }
 
+    /// Request a user-initiated quit: `/quit`, `/exit`, `/logout`, and the
+    /// Ctrl+C/Ctrl+D shortcuts all go through here. With `purge_on_exit` set
+    /// this asks about the session trash first.
+    fn request_quit(&mut self) {
+        if self.config.trash.enabled && self.config.trash.purge_on_exit {
+            self.open_purge_trash_confirmation(true);
+        } else {
+            self.request_quit_without_confirmation();
+        }
+    }
+
     /// Request a shutdown-first quit.
     ///
-    /// This is used for explicit quit commands (`/quit`, `/exit`, `/logout`) and for
-    /// the double-press Ctrl+C/Ctrl+D quit shortcut.
+    /// Prefer [`Self::request_quit`], which asks about the session trash when
+    /// `purge_on_exit` is set.
     fn request_quit_without_confirmation(&self) {
         self.app_event_tx
             .send(AppEvent::Exit(ExitMode::ShutdownFirst));
@@ -3632,9 +4193,30 @@ impl ChatWidget {
     }
 
     pub(crate) fn maybe_post_pending_notification(&mut self, tui: &mut crate::tui::Tui) {
-        if let Some(notif) = self.pending_notification.take() {
-            tui.notify(notif.display());
-        }
+        let Some(notif) = self.pending_notification.take() else {
+            return;
+        };
+        match notif.approval_key() {
+            Some(key) => {
+                let tx = self.app_event_tx.clone();
+                tui.notify_with_actions(
+                    notif.display(),
+                    &NotificationAction::APPROVAL,
+                    Box::new(move |action| {
+                        tx.send(AppEvent::ApprovalNotificationAction { key, action });
+                    }),
+                );
+            }
+            None => {
+                tui.notify(notif.display());
+            }
+        }
+    }
+
+    /// Answer a pending approval from a desktop notification button.
+    pub(crate) fn resolve_approval(&mut self, key: &ApprovalRequestKey, approve: bool) {
+        self.bottom_pane.resolve_approval(key, approve);
+        self.request_redraw();
     }
 
     /// Mark the active cell as failed (✗) and flush it into history.
@@ -3717,6 +4299,31 @@ impl ChatWidget {
         self.add_to_history(history_cell::new_unified_exec_processes_output(processes));
     }
 
+    /// Open the most recently started background terminal whose command
+    /// contains `filter`, handing its keyboard to the user.
+    fn take_over_terminal(&mut self, filter: &str) {
+        let Some(process) = self
+            .unified_exec_processes
+            .iter()
+            .rev()
+            .find(|process| process.command_display.contains(filter))
+        else {
+            let message = if self.unified_exec_processes.is_empty() {
+                "No background terminals are running.".to_string()
+            } else {
+                format!("No background terminal is running `{filter}`; see /ps.")
+            };
+            self.add_info_message(message, None);
+            return;
+        };
+        self.app_event_tx.send(AppEvent::OpenTerminal {
+            process_id: process.key.clone(),
+            call_id: process.call_id.clone(),
+            command_display: process.command_display.clone(),
+            recent_output: process.recent_chunks.join("\n"),
+        });
+    }
+
     fn stop_rate_limit_poller(&mut self) {
         if let Some(handle) = self.rate_limit_poller.take() {
             handle.abort();
@@ -3828,6 +4435,7 @@ impl ChatWidget {
                 summary: None,
                 collaboration_mode: None,
                 personality: None,
+                verbosity: None,
             }));
             tx.send(AppEvent::UpdateModel(switch_model.clone()));
             tx.send(AppEvent::UpdateReasoningEffort(Some(default_effort)));
@@ -3909,6 +4517,60 @@ impl ChatWidget {
         self.open_model_popup_with_presets(presets);
     }
 
+    /// Handle `/model <model> [effort]`: switch the model for later turns
+    /// without opening the picker. The effort defaults to the model's own.
+    fn apply_model_from_args(&mut self, args: &str) {
+        if !self.is_session_configured() {
+            self.add_info_message(
+                "Model selection is disabled until startup completes.".to_string(),
+                None,
+            );
+            return;
+        }
+        let mut words = args.split_whitespace();
+        let (Some(model), effort, None) = (words.next(), words.next(), words.next()) else {
+            self.add_error_message("Usage: /model [<model> [effort]]".to_string());
+            return;
+        };
+        let Ok(presets) = self.models_manager.try_list_models(&self.config) else {
+            self.add_info_message(
+                "Models are being updated; please try /model again in a moment.".to_string(),
+                None,
+            );
+            return;
+        };
+        let Some(preset) = presets.into_iter().find(|preset| preset.model == model) else {
+            self.add_error_message(format!(
+                "Unknown model `{model}`. Run /model to choose from the available models."
+            ));
+            return;
+        };
+        let effort = match effort {
+            None => preset.default_reasoning_effort,
+            Some(effort) => {
+                let effort = effort.to_ascii_lowercase();
+                let Some(supported) = preset
+                    .supported_reasoning_efforts
+                    .iter()
+                    .find(|supported| supported.effort.to_string() == effort)
+                else {
+                    let supported = preset
+                        .supported_reasoning_efforts
+                        .iter()
+                        .map(|supported| supported.effort.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    self.add_error_message(format!(
+                        "`{model}` does not support reasoning effort `{effort}` (supported: {supported})."
+                    ));
+                    return;
+                };
+                supported.effort
+            }
+        };
+        self.apply_model_and_effort(preset.model, Some(effort));
+    }
+
     pub(crate) fn open_personality_popup(&mut self) {
         if !self.is_session_configured() {
             self.add_info_message(
@@ -3948,6 +4610,7 @@ impl ChatWidget {
                         collaboration_mode: None,
                         windows_sandbox_level: None,
                         personality: Some(personality),
+                        verbosity: None,
                     }));
                     tx.send(AppEvent::UpdatePersonality(personality));
                     tx.send(AppEvent::PersistPersonalitySelection { personality });
@@ -3978,6 +4641,79 @@ impl ChatWidget {
         });
     }
 
+    pub(crate) fn open_verbosity_popup(&mut self) {
+        if !self.is_session_configured() {
+            self.add_info_message(
+                "Verbosity selection is disabled until startup completes.".to_string(),
+                None,
+            );
+            return;
+        }
+        let current_verbosity = self.config.model_verbosity;
+        let items: Vec<SelectionItem> = [Verbosity::Low, Verbosity::Medium, Verbosity::High]
+            .into_iter()
+            .map(|verbosity| {
+                let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
+                    tx.send(AppEvent::CodexOp(Op::OverrideTurnContext {
+                        cwd: None,
+                        approval_policy: None,
+                        sandbox_policy: None,
+                        model: None,
+                        effort: None,
+                        summary: None,
+                        collaboration_mode: None,
+                        windows_sandbox_level: None,
+                        personality: None,
+                        verbosity: Some(verbosity),
+                    }));
+                    tx.send(AppEvent::UpdateVerbosity(verbosity));
+                    tx.send(AppEvent::PersistVerbositySelection { verbosity });
+                })];
+                SelectionItem {
+                    name: Self::verbosity_label(verbosity).to_string(),
+                    description: Some(Self::verbosity_description(verbosity).to_string()),
+                    is_current: current_verbosity == Some(verbosity),
+                    actions,
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        let mut header = ColumnRenderable::new();
+        header.push(Line::from("Select Verbosity".bold()));
+        header.push(Line::from(
+            "Choose how detailed responses should be. Applies to models that support it.".dim(),
+        ));
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            header: Box::new(header),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    fn verbosity_label(verbosity: Verbosity) -> &'static str {
+        match verbosity {
+            Verbosity::Low => "Low",
+            Verbosity::Medium => "Medium",
+            Verbosity::High => "High",
+        }
+    }
+
+    fn verbosity_description(verbosity: Verbosity) -> &'static str {
+        match verbosity {
+            Verbosity::Low => "Short, to-the-point answers",
+            Verbosity::Medium => "Balanced detail",
+            Verbosity::High => "Thorough explanations with more context",
+        }
+    }
+
+    pub(crate) fn set_verbosity(&mut self, verbosity: Verbosity) {
+        self.config.model_verbosity = Some(verbosity);
+    }
+
     fn model_menu_header(&self, title: &str, subtitle: &str) -> Box<dyn Renderable> {
         let title = title.to_string();
         let subtitle = subtitle.to_string();
@@ -4219,6 +4955,7 @@ impl ChatWidget {
                 summary: None,
                 collaboration_mode: None,
                 personality: None,
+                verbosity: None,
             }));
             tx.send(AppEvent::UpdateModel(model_for_action.clone()));
             tx.send(AppEvent::UpdateReasoningEffort(effort_for_action));
@@ -4393,6 +5130,7 @@ impl ChatWidget {
                 summary: None,
                 collaboration_mode: None,
                 personality: None,
+                verbosity: None,
             }));
         self.app_event_tx.send(AppEvent::UpdateModel(model.clone()));
         self.app_event_tx
@@ -4566,6 +5304,111 @@ impl ChatWidget {
         self.bottom_pane.show_view(Box::new(view));
     }
 
+    pub(crate) fn open_settings_popup(&mut self) {
+        let items: Vec<SelectionItem> = settings::SETTINGS
+            .iter()
+            .map(|setting| {
+                let effective = settings::effective_setting(&self.config, setting);
+                let value = effective.value.as_deref().unwrap_or("(unset)");
+                let key = setting.key;
+                let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
+                    tx.send(AppEvent::OpenSettingEditor {
+                        key: key.to_string(),
+                    });
+                })];
+                SelectionItem {
+                    name: setting.key.to_string(),
+                    description: Some(format!("{value} · {}", effective.source.label())),
+                    selected_description: Some(format!(
+                        "{value} · {} — {}",
+                        effective.source.label(),
+                        setting.description
+                    )),
+                    actions,
+                    dismiss_on_select: true,
+                    search_value: Some(setting.key.to_string()),
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Settings".to_string()),
+            subtitle: Some(
+                "Effective values and where they come from. Select one to edit it.".to_string(),
+            ),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            is_searchable: true,
+            search_placeholder: Some("Type to search settings".to_string()),
+            ..Default::default()
+        });
+    }
+
+    pub(crate) fn open_setting_editor(&mut self, key: &str) {
+        let Some(setting) = settings::find_setting(key) else {
+            return;
+        };
+        let effective = settings::effective_setting(&self.config, setting);
+        let choices: &[&str] = match setting.kind {
+            settings::SettingKind::Choice(choices) => choices,
+            settings::SettingKind::Bool => &["true", "false"],
+            settings::SettingKind::Text | settings::SettingKind::Integer => {
+                let tx = self.app_event_tx.clone();
+                let view = CustomPromptView::new(
+                    format!("Set {key}"),
+                    "Type a value and press Enter".to_string(),
+                    Some(format!(
+                        "Current: {} · {}",
+                        effective.value.as_deref().unwrap_or("(unset)"),
+                        effective.source.label()
+                    )),
+                    Box::new(move |value: String| {
+                        tx.send(AppEvent::PersistSetting {
+                            key: setting.key.to_string(),
+                            value,
+                        });
+                    }),
+                );
+                self.bottom_pane.show_view(Box::new(view));
+                return;
+            }
+        };
+
+        let items: Vec<SelectionItem> = choices
+            .iter()
+            .map(|choice| {
+                let value = choice.to_string();
+                let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
+                    tx.send(AppEvent::PersistSetting {
+                        key: setting.key.to_string(),
+                        value: value.clone(),
+                    });
+                })];
+                SelectionItem {
+                    name: choice.to_string(),
+                    is_current: effective.value.as_deref() == Some(choice),
+                    actions,
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some(format!("Set {key}")),
+            subtitle: Some(setting.description.to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    /// Refresh the widget's view of the config files after `/settings` saved one.
+    pub(crate) fn set_config_layer_stack(&mut self, stack: ConfigLayerStack) {
+        self.config.config_layer_stack = stack;
+    }
+
     fn approval_preset_actions(
         approval: AskForApproval,
         sandbox: SandboxPolicy,
@@ -4582,6 +5425,7 @@ impl ChatWidget {
                 summary: None,
                 collaboration_mode: None,
                 personality: None,
+                verbosity: None,
             }));
             tx.send(AppEvent::UpdateAskForApprovalPolicy(approval));
             tx.send(AppEvent::UpdateSandboxPolicy(sandbox_clone));
@@ -4640,6 +5484,100 @@ impl ChatWidget {
         None
     }
 
+    pub(crate) fn open_discard_worktree_confirmation(&mut self, branch: &str) {
+        let info_line = Line::from(format!(
+            "The worktree and the {branch} branch are deleted, including any commits on it. Your checkout is not touched."
+        ));
+        let header_children: Vec<Box<dyn Renderable>> = vec![
+            Box::new(Line::from("Discard this session's changes?").bold()),
+            Box::new(Paragraph::new(vec![info_line]).wrap(Wrap { trim: false })),
+        ];
+        let header = ColumnRenderable::with(header_children);
+        let items = vec![
+            SelectionItem {
+                name: "Discard".to_string(),
+                description: Some("Delete the worktree and branch".to_string()),
+                actions: vec![Box::new(|tx| {
+                    tx.send(AppEvent::DiscardSessionWorktree { confirmed: true });
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+            SelectionItem {
+                name: "Cancel".to_string(),
+                description: Some("Keep working in the worktree".to_string()),
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+        ];
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            header: Box::new(header),
+            ..Default::default()
+        });
+    }
+
+    /// Asks before permanently deleting the session trash. When `quitting` is
+    /// set the popup doubles as the exit prompt for `purge_on_exit`.
+    pub(crate) fn open_purge_trash_confirmation(&mut self, quitting: bool) {
+        let info_line = Line::from(
+            "Files in the session trash are deleted permanently and can no longer be restored.",
+        );
+        let header_children: Vec<Box<dyn Renderable>> = vec![
+            Box::new(Line::from("Purge the session trash?").bold()),
+            Box::new(Paragraph::new(vec![info_line]).wrap(Wrap { trim: false })),
+        ];
+        let header = ColumnRenderable::with(header_children);
+        let items = if quitting {
+            vec![
+                SelectionItem {
+                    name: "Purge and quit".to_string(),
+                    description: Some("Delete the trash, then exit".to_string()),
+                    actions: vec![Box::new(|tx| {
+                        tx.send(AppEvent::CodexOp(Op::PurgeTrash));
+                        tx.send(AppEvent::Exit(ExitMode::ShutdownFirst));
+                    })],
+                    dismiss_on_select: true,
+                    ..Default::default()
+                },
+                SelectionItem {
+                    name: "Keep and quit".to_string(),
+                    description: Some("Leave the trash under CODEX_HOME/trash".to_string()),
+                    actions: vec![Box::new(|tx| {
+                        tx.send(AppEvent::Exit(ExitMode::ShutdownFirst));
+                    })],
+                    dismiss_on_select: true,
+                    ..Default::default()
+                },
+            ]
+        } else {
+            vec![
+                SelectionItem {
+                    name: "Purge".to_string(),
+                    description: Some("Delete the trash permanently".to_string()),
+                    actions: vec![Box::new(|tx| {
+                        tx.send(AppEvent::CodexOp(Op::PurgeTrash));
+                    })],
+                    dismiss_on_select: true,
+                    ..Default::default()
+                },
+                SelectionItem {
+                    name: "Cancel".to_string(),
+                    description: Some("Keep the trash".to_string()),
+                    dismiss_on_select: true,
+                    ..Default::default()
+                },
+            ]
+        };
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            header: Box::new(header),
+            ..Default::default()
+        });
+    }
+
     pub(crate) fn open_full_access_confirmation(
         &mut self,
         preset: ApprovalPreset,
@@ -5129,6 +6067,11 @@ impl ChatWidget {
     #[allow(dead_code)]
     pub(crate) fn clear_forced_auto_mode_downgrade(&mut self) {}
 
+    /// Set the working directory in the widget's config copy.
+    pub(crate) fn set_cwd(&mut self, cwd: PathBuf) {
+        self.config.cwd = cwd;
+    }
+
     /// Set the approval policy in the widget's config copy.
     pub(crate) fn set_approval_policy(&mut self, policy: AskForApproval) {
         if let Err(err) = self.config.approval_policy.set(policy) {
@@ -5519,6 +6462,7 @@ impl ChatWidget {
         if self.config.mcp_servers.is_empty() {
             self.add_to_history(history_cell::empty_mcp_output());
         } else {
+            self.mcp_tools_output_requested = true;
             self.submit_op(Op::ListMcpTools);
         }
     }
@@ -5702,7 +6646,7 @@ impl ChatWidget {
             if self.is_cancellable_work_active() {
                 self.submit_op(Op::Interrupt);
             } else {
-                self.request_quit_without_confirmation();
+                self.request_quit();
             }
             return;
         }
@@ -5710,7 +6654,7 @@ impl ChatWidget {
         if self.quit_shortcut_active_for(key) {
             self.quit_shortcut_expires_at = None;
             self.quit_shortcut_key = None;
-            self.request_quit_without_confirmation();
+            self.request_quit();
             return;
         }
 
@@ -5733,14 +6677,14 @@ impl ChatWidget {
                 return false;
             }
 
-            self.request_quit_without_confirmation();
+            self.request_quit();
             return true;
         }
 
         if self.quit_shortcut_active_for(key) {
             self.quit_shortcut_expires_at = None;
             self.quit_shortcut_key = None;
-            self.request_quit_without_confirmation();
+            self.request_quit();
             return true;
         }
 
@@ -5827,17 +6771,48 @@ impl ChatWidget {
         if matches!(&op, Op::Review { .. }) && !self.bottom_pane.is_task_running() {
             self.bottom_pane.set_task_running(true);
         }
+        if matches!(
+            &op,
+            Op::ApplyContextEdit {
+                edit: ContextEdit::Compact
+            }
+        ) {
+            self.clear_token_usage();
+        }
         if let Err(e) = self.codex_op_tx.send(op) {
             tracing::error!("failed to submit op: {e}");
         }
     }
 
+    fn on_mcp_login_completed(&mut self, ev: McpLoginCompletedEvent) {
+        let McpLoginCompletedEvent {
+            name,
+            success,
+            error,
+        } = ev;
+        if success {
+            self.add_info_message(
+                format!("Logged in to MCP server '{name}'."),
+                Some("Its tools are available from your next message.".to_string()),
+            );
+        } else {
+            let error = error.unwrap_or_else(|| "unknown error".to_string());
+            self.add_error_message(format!("Failed to log in to MCP server '{name}': {error}"));
+        }
+    }
+
     fn on_list_mcp_tools(&mut self, ev: McpListToolsResponseEvent) {
+        self.bottom_pane
+            .set_mcp_mention_snapshot(&ev.resources, &ev.prompts);
+        if !std::mem::take(&mut self.mcp_tools_output_requested) {
+            return;
+        }
         self.add_to_history(history_cell::new_mcp_tools_output(
             &self.config,
             ev.tools,
             ev.resources,
             ev.resource_templates,
+            ev.prompts,
             &ev.auth_statuses,
         ));
     }
@@ -5853,6 +6828,189 @@ impl ChatWidget {
         self.set_skills_from_response(&ev);
     }
 
+    fn on_list_trash(&mut self, ev: ListTrashResponseEvent) {
+        if ev.entries.is_empty() {
+            self.add_info_message("Trash is empty.".to_string(), None);
+            return;
+        }
+        let mut lines: Vec<Line<'static>> = vec![vec!["• ".dim(), "Trash".bold()].into()];
+        for entry in ev.entries {
+            let path = display_path_for(&entry.path, &self.config.cwd);
+            lines.push(format!("  {path}").into());
+        }
+        lines.push(
+            "  Use /trash restore [path] to restore files or /trash purge to delete them."
+                .dim()
+                .into(),
+        );
+        self.add_plain_history_lines(lines);
+    }
+
+    fn on_list_jobs(&mut self, ev: ListJobsResponseEvent) {
+        if ev.jobs.is_empty() {
+            self.add_info_message(
+                "No background jobs.".to_string(),
+                Some("Use /jobs start <command> to start one.".to_string()),
+            );
+            return;
+        }
+        let mut lines: Vec<Line<'static>> = vec![vec!["• ".dim(), "Background jobs".bold()].into()];
+        for job in ev.jobs {
+            let status = match job.exit_code {
+                Some(0) => " (exited)".dim(),
+                Some(code) => format!(" (exited with code {code})").red(),
+                None => " (running)".dim(),
+            };
+            lines.push(vec![format!("  {} ", job.id).dim(), job.command.into(), status].into());
+        }
+        lines.push("  Use /jobs stop <id> to stop a job.".dim().into());
+        self.add_plain_history_lines(lines);
+    }
+
+    fn on_pinned_context_updated(&mut self, ev: PinnedContextEvent) {
+        if ev.pins.is_empty() {
+            self.add_info_message(
+                "Nothing is pinned.".to_string(),
+                Some("Use /pin file <path> or /pin message [text] to pin context.".to_string()),
+            );
+            return;
+        }
+        let mut lines: Vec<Line<'static>> = vec![vec!["• ".dim(), "Pinned context".bold()].into()];
+        for entry in ev.pins {
+            let label = match entry.target {
+                PinTarget::File { path } => display_path_for(&path, &self.config.cwd),
+                PinTarget::Message { text } => {
+                    let text = text.unwrap_or_default();
+                    let first_line = text.lines().next().unwrap_or_default();
+                    format!("message: {}", truncate_text(first_line, 60))
+                }
+            };
+            lines.push(
+                vec![
+                    format!("  {}. ", entry.id).dim(),
+                    label.into(),
+                    format!(" ({} tokens)", format_tokens_compact(entry.tokens)).dim(),
+                ]
+                .into(),
+            );
+        }
+        lines.push(
+            "  Use /pin remove <id> or /pin remove all to unpin."
+                .dim()
+                .into(),
+        );
+        self.add_plain_history_lines(lines);
+    }
+
+    fn on_context_edit_preview(&mut self, ev: ContextEditPreviewEvent) {
+        const MAX_LISTED_TURNS: usize = 5;
+
+        let ContextEditPreviewEvent {
+            edit,
+            total_turns,
+            removed_turns,
+            tokens_before,
+            tokens_after,
+        } = ev;
+        if removed_turns.is_empty() {
+            self.add_info_message("The context has no turns to remove.".to_string(), None);
+            return;
+        }
+
+        let (title, verb, confirm) = match edit {
+            ContextEdit::Compact => ("Compact conversation".to_string(), "summarized", "Compact"),
+            ContextEdit::Clear => ("Clear context".to_string(), "removed", "Clear context"),
+            ContextEdit::DropTurns { first, last } if first == last => {
+                (format!("Drop turn {first}"), "removed", "Drop turn")
+            }
+            ContextEdit::DropTurns { first, last } => (
+                format!("Drop turns {first}-{last}"),
+                "removed",
+                "Drop turns",
+            ),
+        };
+        let first_number = match edit {
+            ContextEdit::DropTurns { first, .. } => first,
+            ContextEdit::Compact | ContextEdit::Clear => 1,
+        };
+
+        let mut header = ColumnRenderable::new();
+        header.push(Line::from(title.bold()));
+        header.push(Line::from(
+            format!(
+                "{} of {total_turns} turns will be {verb}:",
+                removed_turns.len()
+            )
+            .dim(),
+        ));
+        for (number, preview) in (first_number..)
+            .zip(removed_turns.iter())
+            .take(MAX_LISTED_TURNS)
+        {
+            header.push(Line::from(vec![
+                format!("  {number}. ").dim(),
+                truncate_text(preview, 72).into(),
+            ]));
+        }
+        if removed_turns.len() > MAX_LISTED_TURNS {
+            header.push(Line::from(
+                format!("  … and {} more", removed_turns.len() - MAX_LISTED_TURNS).dim(),
+            ));
+        }
+        let saved = tokens_before.saturating_sub(tokens_after).max(0);
+        let mut savings = format!(
+            "About {} → {} tokens (saves ~{})",
+            format_tokens_compact(tokens_before),
+            format_tokens_compact(tokens_after),
+            format_tokens_compact(saved),
+        );
+        if edit == ContextEdit::Compact {
+            savings.push_str(", plus the summary");
+        }
+        header.push(Line::from(savings.dim()));
+        header.push(Line::from(
+            "Instructions are kept and the transcript above is not changed.".dim(),
+        ));
+
+        let items = vec![
+            SelectionItem {
+                name: confirm.to_string(),
+                actions: vec![Box::new(move |tx| {
+                    tx.send(AppEvent::CodexOp(Op::ApplyContextEdit { edit }));
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+            SelectionItem {
+                name: "Cancel".to_string(),
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+        ];
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            header: Box::new(header),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    fn on_context_edited(&mut self, ev: ContextEditedEvent) {
+        let turns = if ev.removed_turns == 1 {
+            "1 turn".to_string()
+        } else {
+            format!("{} turns", ev.removed_turns)
+        };
+        self.add_info_message(
+            format!("Removed {turns} from the context."),
+            Some(format!(
+                "About {} → {} tokens",
+                format_tokens_compact(ev.tokens_before),
+                format_tokens_compact(ev.tokens_after),
+            )),
+        );
+    }
+
     pub(crate) fn on_connectors_loaded(&mut self, result: Result<ConnectorsSnapshot, String>) {
         self.connectors_cache = match result {
             Ok(connectors) => ConnectorsCacheState::Ready(connectors),
@@ -6102,6 +7260,11 @@ impl ChatWidget {
 impl Drop for ChatWidget {
     fn drop(&mut self) {
         self.stop_rate_limit_poller();
+        for path in self.saved_text_pastes.drain(..) {
+            if let Err(err) = std::fs::remove_file(&path) {
+                tracing::warn!("failed to remove saved paste {}: {err}", path.display());
+            }
+        }
     }
 }
 
@@ -6121,10 +7284,25 @@ impl Renderable for ChatWidget {
 }
 
 enum Notification {
-    AgentTurnComplete { response: String },
-    ExecApprovalRequested { command: String },
-    EditApprovalRequested { cwd: PathBuf, changes: Vec<PathBuf> },
-    ElicitationRequested { server_name: String },
+    AgentTurnComplete {
+        response: String,
+    },
+    ExecApprovalRequested {
+        key: ApprovalRequestKey,
+        command: String,
+    },
+    EditApprovalRequested {
+        key: ApprovalRequestKey,
+        cwd: PathBuf,
+        changes: Vec<PathBuf>,
+    },
+    ElicitationRequested {
+        key: ApprovalRequestKey,
+        server_name: String,
+    },
+    TurnBlocked {
+        category: String,
+    },
 }
 
 impl Notification {
@@ -6134,10 +7312,10 @@ impl Notification {
                 Notification::agent_turn_preview(response)
                     .unwrap_or_else(|| "Agent turn complete".to_string())
             }
-            Notification::ExecApprovalRequested { command } => {
+            Notification::ExecApprovalRequested { command, .. } => {
                 format!("Approval requested: {}", truncate_text(command, 30))
             }
-            Notification::EditApprovalRequested { cwd, changes } => {
+            Notification::EditApprovalRequested { cwd, changes, .. } => {
                 format!(
                     "Codex wants to edit {}",
                     if changes.len() == 1 {
@@ -6148,9 +7326,23 @@ impl Notification {
                     }
                 )
             }
-            Notification::ElicitationRequested { server_name } => {
+            Notification::ElicitationRequested { server_name, .. } => {
                 format!("Approval requested by {server_name}")
             }
+            Notification::TurnBlocked { category } => {
+                format!("Response blocked ({category})")
+            }
+        }
+    }
+
+    /// The approval request a notification asks about, if any; these get
+    /// Approve/Deny buttons on backends that support them.
+    fn approval_key(&self) -> Option<ApprovalRequestKey> {
+        match self {
+            Notification::ExecApprovalRequested { key, .. }
+            | Notification::EditApprovalRequested { key, .. }
+            | Notification::ElicitationRequested { key, .. } => Some(key.clone()),
+            Notification::AgentTurnComplete { .. } | Notification::TurnBlocked { .. } => None,
         }
     }
 
@@ -6160,6 +7352,7 @@ impl Notification {
             Notification::ExecApprovalRequested { .. }
             | Notification::EditApprovalRequested { .. }
             | Notification::ElicitationRequested { .. } => "approval-requested",
+            Notification::TurnBlocked { .. } => "turn-blocked",
         }
     }
 
@@ -6286,6 +7479,15 @@ pub(crate) fn show_review_commit_picker_with_entries(
     });
 }
 
+/// Parses the `N` or `N-M` argument of `/clear-context`.
+fn parse_turn_range(args: &str) -> Option<(u32, u32)> {
+    let args = args.trim();
+    let (first, last) = args.split_once('-').unwrap_or((args, args));
+    let first = first.trim().parse().ok()?;
+    let last = last.trim().parse().ok()?;
+    (first >= 1 && first <= last).then_some((first, last))
+}
+
 fn format_duration_short(seconds: u64) -> String {
     if seconds < 60 {
         "less than a minute".to_string()
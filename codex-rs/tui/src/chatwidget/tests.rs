@@ -46,6 +46,7 @@ use codex_core::protocol::McpStartupUpdateEvent;
 use codex_core::protocol::Op;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PatchApplyEndEvent;
+use codex_core::protocol::PinnedContextEntry;
 use codex_core::protocol::RateLimitWindow;
 use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::ReviewTarget;
@@ -813,6 +814,7 @@ async fn make_chatwidget_manual(
         running_commands: HashMap::new(),
         suppressed_exec_calls: HashSet::new(),
         skills_all: Vec::new(),
+        saved_text_pastes: Vec::new(),
         skills_initial_state: None,
         last_unified_wait: None,
         unified_exec_wait_streak: None,
@@ -820,6 +822,7 @@ async fn make_chatwidget_manual(
         unified_exec_processes: Vec::new(),
         agent_turn_running: false,
         mcp_startup_status: None,
+        mcp_tools_output_requested: false,
         connectors_cache: ConnectorsCacheState::default(),
         interrupts: InterruptManager::new(),
         reasoning_buffer: String::new(),
@@ -1377,6 +1380,7 @@ async fn exec_approval_emits_proposed_command_and_decision_history() {
         ),
         proposed_execpolicy_amendment: None,
         parsed_cmd: vec![],
+        file_operation: None,
     };
     chat.handle_codex_event(Event {
         id: "sub-short".into(),
@@ -1421,6 +1425,7 @@ async fn exec_approval_decision_truncates_multiline_and_long_commands() {
         ),
         proposed_execpolicy_amendment: None,
         parsed_cmd: vec![],
+        file_operation: None,
     };
     chat.handle_codex_event(Event {
         id: "sub-multi".into(),
@@ -1471,6 +1476,7 @@ async fn exec_approval_decision_truncates_multiline_and_long_commands() {
         reason: None,
         proposed_execpolicy_amendment: None,
         parsed_cmd: vec![],
+        file_operation: None,
     };
     chat.handle_codex_event(Event {
         id: "sub-long".into(),
@@ -1600,6 +1606,7 @@ fn end_exec(
             exit_code,
             duration: std::time::Duration::from_millis(5),
             formatted_output: aggregated,
+            previous_run_diff: None,
         }),
     });
 }
@@ -1748,6 +1755,20 @@ async fn ctrl_d_quits_without_prompt() {
     assert_matches!(rx.try_recv(), Ok(AppEvent::Exit(ExitMode::ShutdownFirst)));
 }
 
+#[tokio::test]
+async fn ctrl_d_asks_before_purging_trash() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.config.trash.enabled = true;
+    chat.config.trash.purge_on_exit = true;
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL));
+    assert_matches!(rx.try_recv(), Err(TryRecvError::Empty));
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert_matches!(rx.try_recv(), Ok(AppEvent::Exit(ExitMode::ShutdownFirst)));
+}
+
 #[tokio::test]
 async fn ctrl_d_with_modal_open_does_not_quit() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -1843,6 +1864,42 @@ async fn exec_history_cell_shows_working_then_failed() {
     assert!(blob.to_lowercase().contains("bloop"), "expected error text");
 }
 
+#[tokio::test]
+async fn rerun_command_shows_diff_against_previous_run() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    let begin = begin_exec(&mut chat, "call-rerun", "cargo test");
+    chat.handle_codex_event(Event {
+        id: "call-rerun".to_string(),
+        msg: EventMsg::ExecCommandEnd(ExecCommandEndEvent {
+            call_id: begin.call_id,
+            process_id: None,
+            turn_id: begin.turn_id,
+            command: begin.command,
+            cwd: begin.cwd,
+            parsed_cmd: begin.parsed_cmd,
+            source: begin.source,
+            interaction_input: None,
+            stdout: "test a ... ok\n".to_string(),
+            stderr: String::new(),
+            aggregated_output: "test a ... ok\n".to_string(),
+            exit_code: 0,
+            duration: std::time::Duration::from_millis(5),
+            formatted_output: "test a ... ok\n".to_string(),
+            previous_run_diff: Some(
+                "@@ -1 +1 @@\n-test a ... FAILED\n+test a ... ok\n".to_string(),
+            ),
+        }),
+    });
+
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 2, "expected exec cell followed by diff cell");
+    assert_eq!(
+        lines_to_single_string(&cells[1]),
+        "\n• Output changed since the previous run\n  └ @@ -1 +1 @@\n    -test a ... FAILED\n    +test a ... ok\n"
+    );
+}
+
 #[tokio::test]
 async fn exec_end_without_begin_uses_event_command() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -1870,6 +1927,7 @@ async fn exec_end_without_begin_uses_event_command() {
             exit_code: 0,
             duration: std::time::Duration::from_millis(5),
             formatted_output: "done".to_string(),
+            previous_run_diff: None,
         }),
     });
 
@@ -2303,6 +2361,26 @@ async fn collab_slash_command_opens_picker_and_updates_mode() {
     }
 }
 
+#[tokio::test]
+async fn slash_commit_requires_git_commit_feature() {
+    let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(None).await;
+    chat.set_feature_enabled(Feature::GitCommitTool, false);
+
+    chat.dispatch_command(SlashCommand::Commit);
+
+    match op_rx.try_recv() {
+        Err(TryRecvError::Empty) => {}
+        other => panic!("expected no Codex op to be sent, got {other:?}"),
+    }
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1, "expected one info message");
+    let rendered = lines_to_single_string(&cells[0]);
+    assert!(
+        rendered.contains("/experimental"),
+        "info message should say how to enable the feature: {rendered:?}"
+    );
+}
+
 #[tokio::test]
 async fn plan_slash_command_switches_to_plan_mode() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -2543,6 +2621,20 @@ async fn slash_exit_requests_exit() {
     assert_matches!(rx.try_recv(), Ok(AppEvent::Exit(ExitMode::ShutdownFirst)));
 }
 
+#[tokio::test]
+async fn slash_quit_asks_before_purging_trash() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.config.trash.enabled = true;
+    chat.config.trash.purge_on_exit = true;
+
+    chat.dispatch_command(SlashCommand::Quit);
+    assert_matches!(rx.try_recv(), Err(TryRecvError::Empty));
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert_matches!(rx.try_recv(), Ok(AppEvent::CodexOp(Op::PurgeTrash)));
+    assert_matches!(rx.try_recv(), Ok(AppEvent::Exit(ExitMode::ShutdownFirst)));
+}
+
 #[tokio::test]
 async fn slash_resume_opens_picker() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -2778,6 +2870,48 @@ async fn custom_prompt_submit_sends_review_op() {
     }
 }
 
+/// Pastes over `tui.paste_attach_threshold` are held back behind a prompt and,
+/// when attached, replaced by a reference to a temp file that is removed when
+/// the session ends.
+#[tokio::test]
+async fn large_paste_prompts_and_attaches_as_file() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.config.tui_paste_attach_threshold = 10;
+
+    let text = "line one\nline two\n".to_string();
+    chat.handle_paste(text.clone());
+    assert_eq!(chat.bottom_pane.composer_text(), "");
+    let popup = render_bottom_popup(&chat, 80);
+    assert!(popup.contains("Large paste"), "popup: {popup}");
+
+    // The default selection attaches the paste.
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let (text, attach) = loop {
+        match rx.try_recv().expect("expected ResolveLargePaste") {
+            AppEvent::ResolveLargePaste { text, attach } => break (text, attach),
+            _ => continue,
+        }
+    };
+    assert!(attach);
+    chat.resolve_large_paste(text.clone(), attach);
+
+    let composer = chat.bottom_pane.composer_text();
+    assert!(
+        composer.starts_with("[Pasted text (2 lines, 18 characters) saved to "),
+        "composer: {composer}"
+    );
+    let path = composer
+        .split(" saved to ")
+        .nth(1)
+        .and_then(|rest| rest.split(';').next())
+        .expect("path in reference");
+    assert_eq!(std::fs::read_to_string(path).expect("read paste"), text);
+
+    // The temp file goes away with the session.
+    drop(chat);
+    assert!(!std::path::Path::new(path).exists());
+}
+
 /// Hitting Enter on an empty custom prompt view does not submit.
 #[tokio::test]
 async fn custom_prompt_enter_empty_does_not_send() {
@@ -3070,6 +3204,48 @@ async fn model_selection_popup_snapshot() {
     assert_snapshot!("model_selection_popup", popup);
 }
 
+#[tokio::test]
+async fn model_command_with_args_switches_model_without_popup() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(Some("gpt-5-codex")).await;
+    chat.thread_id = Some(ThreadId::new());
+
+    chat.bottom_pane.set_composer_text(
+        "/model gpt-5.1-codex-mini minimal".to_string(),
+        Vec::new(),
+        Vec::new(),
+    );
+    chat.handle_key_event(KeyEvent::from(KeyCode::Enter));
+    let cells = drain_insert_history(&mut rx);
+    let rendered = lines_to_single_string(cells.last().expect("error cell"));
+    assert!(
+        rendered.contains("does not support reasoning effort `minimal`"),
+        "{rendered}"
+    );
+
+    chat.bottom_pane.set_composer_text(
+        "/model gpt-5.1-codex-mini high".to_string(),
+        Vec::new(),
+        Vec::new(),
+    );
+    chat.handle_key_event(KeyEvent::from(KeyCode::Enter));
+    let mut events = Vec::new();
+    while let Ok(event) = rx.try_recv() {
+        events.push(event);
+    }
+    assert!(
+        events.iter().any(|event| matches!(
+            event,
+            AppEvent::CodexOp(Op::OverrideTurnContext {
+                model: Some(model),
+                effort: Some(Some(ReasoningEffortConfig::High)),
+                ..
+            }) if model == "gpt-5.1-codex-mini"
+        )),
+        "expected a model override, got {events:?}"
+    );
+    assert!(chat.bottom_pane.no_modal_or_popup_active());
+}
+
 #[tokio::test]
 async fn personality_selection_popup_snapshot() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual(Some("bengalfox")).await;
@@ -3080,6 +3256,39 @@ async fn personality_selection_popup_snapshot() {
     assert_snapshot!("personality_selection_popup", popup);
 }
 
+#[tokio::test]
+async fn verbosity_popup_overrides_and_persists_selection() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(Some("gpt-5.2")).await;
+    chat.thread_id = Some(ThreadId::new());
+    chat.open_verbosity_popup();
+
+    let popup = render_bottom_popup(&chat, 80);
+    assert!(popup.contains("Select Verbosity"), "{popup}");
+    assert!(popup.contains("High"), "{popup}");
+
+    chat.handle_key_event(KeyEvent::from(KeyCode::Enter));
+    let mut events = Vec::new();
+    while let Ok(event) = rx.try_recv() {
+        events.push(event);
+    }
+    assert!(
+        events.iter().any(|event| matches!(
+            event,
+            AppEvent::CodexOp(Op::OverrideTurnContext {
+                verbosity: Some(Verbosity::Low),
+                ..
+            })
+        )),
+        "expected a verbosity override, got {events:?}"
+    );
+    assert!(events.iter().any(|event| matches!(
+        event,
+        AppEvent::PersistVerbositySelection {
+            verbosity: Verbosity::Low
+        }
+    )));
+}
+
 #[tokio::test]
 async fn model_picker_hides_show_in_picker_false_models_from_cache() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual(Some("test-visible-model")).await;
@@ -3605,6 +3814,7 @@ async fn approval_modal_exec_snapshot() -> anyhow::Result<()> {
             "world".into(),
         ])),
         parsed_cmd: vec![],
+        file_operation: None,
     };
     chat.handle_codex_event(Event {
         id: "sub-approve".into(),
@@ -3658,6 +3868,7 @@ async fn approval_modal_exec_without_reason_snapshot() -> anyhow::Result<()> {
             "world".into(),
         ])),
         parsed_cmd: vec![],
+        file_operation: None,
     };
     chat.handle_codex_event(Event {
         id: "sub-approve-noreason".into(),
@@ -3698,6 +3909,7 @@ async fn approval_modal_exec_multiline_prefix_hides_execpolicy_option_snapshot()
         reason: None,
         proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(command)),
         parsed_cmd: vec![],
+        file_operation: None,
     };
     chat.handle_codex_event(Event {
         id: "sub-approve-multiline-trunc".into(),
@@ -3742,6 +3954,8 @@ async fn approval_modal_patch_snapshot() -> anyhow::Result<()> {
         changes,
         reason: Some("The model wants to apply changes".into()),
         grant_root: Some(PathBuf::from("/tmp")),
+        hunks: Vec::new(),
+        file_operations: Vec::new(),
     };
     chat.handle_codex_event(Event {
         id: "sub-approve-patch".into(),
@@ -3996,6 +4210,7 @@ async fn status_widget_and_approval_modal_snapshot() {
             "hello world".into(),
         ])),
         parsed_cmd: vec![],
+        file_operation: None,
     };
     chat.handle_codex_event(Event {
         id: "sub-approve-exec".into(),
@@ -4127,6 +4342,8 @@ async fn apply_patch_events_emit_history_cells() {
         changes,
         reason: None,
         grant_root: None,
+        hunks: Vec::new(),
+        file_operations: Vec::new(),
     };
     chat.handle_codex_event(Event {
         id: "s1".into(),
@@ -4226,6 +4443,8 @@ async fn apply_patch_manual_approval_adjusts_header() {
             changes: proposed_changes,
             reason: None,
             grant_root: None,
+            hunks: Vec::new(),
+            file_operations: Vec::new(),
         }),
     });
     drain_insert_history(&mut rx);
@@ -4275,6 +4494,8 @@ async fn apply_patch_manual_flow_snapshot() {
             changes: proposed_changes,
             reason: Some("Manual review required".into()),
             grant_root: None,
+            hunks: Vec::new(),
+            file_operations: Vec::new(),
         }),
     });
     let history_before_apply = drain_insert_history(&mut rx);
@@ -4326,6 +4547,8 @@ async fn apply_patch_approval_sends_op_with_submission_id() {
         changes,
         reason: None,
         grant_root: None,
+        hunks: Vec::new(),
+        file_operations: Vec::new(),
     };
     chat.handle_codex_event(Event {
         id: "sub-123".into(),
@@ -4366,6 +4589,8 @@ async fn apply_patch_full_flow_integration_like() {
             changes,
             reason: None,
             grant_root: None,
+            hunks: Vec::new(),
+            file_operations: Vec::new(),
         }),
     });
 
@@ -4446,6 +4671,8 @@ async fn apply_patch_untrusted_shows_approval_modal() -> anyhow::Result<()> {
             changes,
             reason: None,
             grant_root: None,
+            hunks: Vec::new(),
+            file_operations: Vec::new(),
         }),
     });
 
@@ -4497,6 +4724,8 @@ async fn apply_patch_request_shows_diff_summary() -> anyhow::Result<()> {
             changes,
             reason: None,
             grant_root: None,
+            hunks: Vec::new(),
+            file_operations: Vec::new(),
         }),
     });
 
@@ -4605,6 +4834,48 @@ async fn stream_error_updates_status_indicator() {
     assert_eq!(status.details(), Some(details));
 }
 
+#[tokio::test]
+async fn stream_error_marks_the_interrupted_message() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.handle_codex_event(Event {
+        id: "task".into(),
+        msg: EventMsg::TurnStarted(TurnStartedEvent {
+            model_context_window: None,
+            collaboration_mode_kind: ModeKind::Custom,
+        }),
+    });
+    chat.handle_codex_event(Event {
+        id: "delta".into(),
+        msg: EventMsg::AgentMessageDelta(AgentMessageDeltaEvent {
+            delta: "partial answer\nstill streaming".to_string(),
+        }),
+    });
+    drain_insert_history(&mut rx);
+
+    chat.handle_codex_event(Event {
+        id: "retry".into(),
+        msg: EventMsg::StreamError(StreamErrorEvent {
+            message: "Reconnecting... 1/5".to_string(),
+            codex_error_info: Some(CodexErrorInfo::ResponseStreamDisconnected {
+                http_status_code: None,
+            }),
+            additional_details: None,
+        }),
+    });
+
+    let cells = drain_insert_history(&mut rx);
+    let blob = lines_to_single_string(&cells.concat());
+    assert!(
+        blob.contains("still streaming"),
+        "partial text flushed: {blob}"
+    );
+    assert!(
+        blob.contains("cut off and is not part of the conversation"),
+        "interruption marked: {blob}"
+    );
+    assert!(chat.stream_controller.is_none());
+}
+
 #[tokio::test]
 async fn warning_event_adds_warning_history_cell() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -4858,6 +5129,7 @@ async fn chatwidget_exec_and_status_layout_vt100_snapshot() {
             exit_code: 0,
             duration: std::time::Duration::from_millis(16000),
             formatted_output: String::new(),
+            previous_run_diff: None,
         }),
     });
     chat.handle_codex_event(Event {
@@ -5051,3 +5323,97 @@ async fn review_queues_user_messages_snapshot() {
     .unwrap();
     assert_snapshot!(term.backend().vt100().screen().contents());
 }
+
+#[tokio::test]
+async fn context_edit_preview_lists_removed_turns_and_applies_on_confirm() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    let edit = ContextEdit::DropTurns { first: 2, last: 3 };
+    chat.handle_codex_event(Event {
+        id: "preview-1".to_string(),
+        msg: EventMsg::ContextEditPreview(ContextEditPreviewEvent {
+            edit,
+            total_turns: 4,
+            removed_turns: vec!["fix the login bug".to_string(), "now add tests".to_string()],
+            tokens_before: 42_000,
+            tokens_after: 12_000,
+        }),
+    });
+
+    let popup = render_bottom_popup(&chat, 80);
+    assert!(popup.contains("Drop turns 2-3"), "popup:\n{popup}");
+    assert!(
+        popup.contains("2 of 4 turns will be removed"),
+        "popup:\n{popup}"
+    );
+    assert!(popup.contains("3. now add tests"), "popup:\n{popup}");
+    assert!(popup.contains("saves ~"), "popup:\n{popup}");
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let mut applied = None;
+    while let Ok(event) = rx.try_recv() {
+        if let AppEvent::CodexOp(Op::ApplyContextEdit { edit }) = event {
+            applied = Some(edit);
+        }
+    }
+    assert_eq!(applied, Some(edit));
+}
+
+#[test]
+fn parse_turn_range_accepts_single_turns_and_ranges() {
+    assert_eq!(parse_turn_range("3"), Some((3, 3)));
+    assert_eq!(parse_turn_range(" 2-5 "), Some((2, 5)));
+    assert_eq!(parse_turn_range("0"), None);
+    assert_eq!(parse_turn_range("5-2"), None);
+    assert_eq!(parse_turn_range("last"), None);
+}
+
+#[tokio::test]
+async fn pin_command_sends_pin_ops_and_lists_pins() {
+    let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(None).await;
+
+    chat.bottom_pane
+        .set_composer_text("/pin file src/lib.rs".to_string(), Vec::new(), Vec::new());
+    chat.handle_key_event(KeyEvent::from(KeyCode::Enter));
+    assert_eq!(
+        next_submit_op(&mut op_rx),
+        Op::Pin {
+            target: PinTarget::File {
+                path: PathBuf::from("src/lib.rs"),
+            },
+        }
+    );
+
+    chat.bottom_pane
+        .set_composer_text("/pin remove all".to_string(), Vec::new(), Vec::new());
+    chat.handle_key_event(KeyEvent::from(KeyCode::Enter));
+    assert_eq!(next_submit_op(&mut op_rx), Op::Unpin { id: None });
+
+    chat.handle_codex_event(Event {
+        id: "pins".to_string(),
+        msg: EventMsg::PinnedContextUpdated(PinnedContextEvent {
+            pins: vec![
+                PinnedContextEntry {
+                    id: 1,
+                    target: PinTarget::File {
+                        path: chat.config.cwd.join("src/lib.rs"),
+                    },
+                    tokens: 1_200,
+                },
+                PinnedContextEntry {
+                    id: 2,
+                    target: PinTarget::Message {
+                        text: Some("Always use tabs.\nNever spaces.".to_string()),
+                    },
+                    tokens: 10,
+                },
+            ],
+        }),
+    });
+    let rendered = lines_to_single_string(&drain_insert_history(&mut rx).concat());
+    assert!(rendered.contains("1. src/lib.rs"), "rendered:\n{rendered}");
+    assert!(
+        rendered.contains("2. message: Always use tabs."),
+        "rendered:\n{rendered}"
+    );
+    assert!(!rendered.contains("Never spaces."), "rendered:\n{rendered}");
+}
@@ -53,6 +53,16 @@ pub struct Cli {
     #[arg(long, short = 'm')]
     pub model: Option<String>,
 
+    /// Reasoning effort for reasoning-capable models. Overrides
+    /// `model_reasoning_effort` from config.toml and the active profile.
+    #[arg(long = "reasoning-effort", value_enum)]
+    pub reasoning_effort: Option<codex_common::ReasoningEffortCliArg>,
+
+    /// Output verbosity for models that support it. Overrides
+    /// `model_verbosity` from config.toml and the active profile.
+    #[arg(long = "verbosity", value_enum)]
+    pub verbosity: Option<codex_common::VerbosityCliArg>,
+
     /// Convenience flag to select the local open source model provider. Equivalent to -c
     /// model_provider=oss; verifies a local LM Studio or Ollama server is running.
     #[arg(long = "oss", default_value_t = false)]
@@ -102,6 +112,23 @@ pub struct Cli {
     #[arg(long = "add-dir", value_name = "DIR", value_hint = ValueHint::DirPath)]
     pub add_dir: Vec<PathBuf>,
 
+    /// Record every raw model API request and streamed response to a JSON Lines file in DIR.
+    #[arg(long = "record-api", value_name = "DIR", value_hint = ValueHint::DirPath)]
+    pub record_api: Option<PathBuf>,
+
+    /// Serve model responses from a `--record-api` recording instead of calling the API.
+    #[arg(
+        long = "replay-api",
+        value_name = "FILE",
+        conflicts_with = "record_api"
+    )]
+    pub replay_api: Option<PathBuf>,
+
+    /// Run a new session in its own git worktree and branch, leaving the current checkout
+    /// untouched until the work is landed with `/merge`.
+    #[arg(long = "worktree", default_value_t = false)]
+    pub worktree: bool,
+
     /// Disable alternate screen mode
     ///
     /// Runs the TUI in inline mode, preserving terminal scrollback history. This is useful
@@ -149,6 +149,29 @@ pub fn paste_image_to_temp_png() -> Result<(PathBuf, PastedImageInfo), PasteImag
     }
 }
 
+/// Write a large text paste to a temp file so the prompt can reference it
+/// instead of carrying the full text.
+pub(crate) fn save_text_paste_to_temp_file(text: &str) -> std::io::Result<PathBuf> {
+    let tmp = Builder::new()
+        .prefix("codex-paste-")
+        .suffix(".txt")
+        .tempfile()?;
+    std::fs::write(tmp.path(), text)?;
+    let (_file, path) = tmp.keep().map_err(|e| e.error)?;
+    Ok(path)
+}
+
+/// Text inserted into the composer in place of a paste saved with
+/// [`save_text_paste_to_temp_file`].
+pub(crate) fn text_paste_reference(path: &Path, text: &str) -> String {
+    let lines = text.lines().count();
+    let chars = text.chars().count();
+    format!(
+        "[Pasted text ({lines} lines, {chars} characters) saved to {}; read the file instead of asking me to paste it again.]",
+        path.display()
+    )
+}
+
 /// Attempt WSL fallback for clipboard image paste.
 ///
 /// If clipboard is unavailable (common under WSL because arboard cannot access
@@ -280,6 +303,37 @@ pub fn normalize_pasted_path(pasted: &str) -> Option<PathBuf> {
     None
 }
 
+/// Normalize pasted text that may be a list of filesystem paths, such as
+/// several files dropped on the terminal at once (shell-escaped and separated
+/// by spaces) or a file manager's copied selection (one path per line).
+pub fn normalize_pasted_paths(pasted: &str) -> Option<Vec<PathBuf>> {
+    let pasted = pasted.trim();
+    let paths: Vec<PathBuf> = if pasted.contains('\n') {
+        pasted
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| normalize_pasted_path(line).unwrap_or_else(|| PathBuf::from(line)))
+            .collect()
+    } else if let Some(path) = normalize_pasted_path(pasted) {
+        vec![path]
+    } else {
+        let mut lexer = shlex::Shlex::new(pasted);
+        let parts: Vec<String> = lexer.by_ref().collect();
+        if lexer.had_error {
+            return None;
+        }
+        parts
+            .into_iter()
+            .map(|part| match url::Url::parse(&part) {
+                Ok(url) if url.scheme() == "file" => url.to_file_path().ok(),
+                _ => Some(PathBuf::from(part)),
+            })
+            .collect::<Option<_>>()?
+    };
+    (!paths.is_empty()).then_some(paths)
+}
+
 #[cfg(target_os = "linux")]
 pub(crate) fn is_probably_wsl() -> bool {
     // Primary: Check /proc/version for "microsoft" or "WSL" (most reliable for standard WSL).
@@ -380,6 +434,30 @@ mod pasted_paths_tests {
         assert_eq!(result, PathBuf::from("/tmp/example.png"));
     }
 
+    #[cfg(not(windows))]
+    #[test]
+    fn normalize_dropped_and_copied_file_lists() {
+        let dropped = r"/tmp/notes.md /tmp/my\ report.txt";
+        assert_eq!(
+            normalize_pasted_paths(dropped),
+            Some(vec![
+                PathBuf::from("/tmp/notes.md"),
+                PathBuf::from("/tmp/my report.txt"),
+            ])
+        );
+
+        let copied = "file:///tmp/notes.md\n/tmp/my report.txt\n";
+        assert_eq!(
+            normalize_pasted_paths(copied),
+            Some(vec![
+                PathBuf::from("/tmp/notes.md"),
+                PathBuf::from("/tmp/my report.txt"),
+            ])
+        );
+
+        assert_eq!(normalize_pasted_paths("'/tmp/unterminated"), None);
+    }
+
     #[test]
     fn normalize_file_url_windows() {
         let input = r"C:\Temp\example.png";
@@ -3,7 +3,8 @@
 //! The implementation mirrors the behaviour of the TypeScript version in
 //! `codex-cli`: it returns the diff for tracked changes as well as any
 //! untracked files. When the current directory is not inside a Git
-//! repository, the function returns `Ok((false, String::new()))`.
+//! repository, the function returns `Ok((false, String::new()))`. The diff is
+//! requested without color so the diff review overlay can parse it.
 
 use std::io;
 use std::path::Path;
@@ -22,7 +23,7 @@ pub(crate) async fn get_git_diff() -> io::Result<(bool, String)> {
 
     // Run tracked diff and untracked file listing in parallel.
     let (tracked_diff_res, untracked_output_res) = tokio::join!(
-        run_git_capture_diff(&["diff", "--color"]),
+        run_git_capture_diff(&["diff"]),
         run_git_capture_stdout(&["ls-files", "--others", "--exclude-standard"]),
     );
     let tracked_diff = tracked_diff_res?;
@@ -45,7 +46,7 @@ pub(crate) async fn get_git_diff() -> io::Result<(bool, String)> {
         let null_path = null_path.clone();
         let file = file.to_string();
         join_set.spawn(async move {
-            let args = ["diff", "--color", "--no-index", "--", &null_path, &file];
+            let args = ["diff", "--no-index", "--", &null_path, &file];
             run_git_capture_diff(&args).await
         });
     }
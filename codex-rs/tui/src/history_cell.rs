@@ -40,9 +40,14 @@ use base64::Engine;
 use codex_common::format_env_display::format_env_display;
 use codex_core::config::Config;
 use codex_core::config::types::McpServerTransportConfig;
+use codex_core::config::types::UriBasedFileOpener;
 use codex_core::protocol::FileChange;
 use codex_core::protocol::McpAuthStatus;
 use codex_core::protocol::McpInvocation;
+use codex_core::protocol::McpServerState;
+use codex_core::protocol::McpServerStatusEntry;
+use codex_core::protocol::McpToolCallProgressEvent;
+use codex_core::protocol::OpenFileRequestEvent;
 use codex_core::protocol::SessionConfiguredEvent;
 use codex_core::web_search::web_search_detail;
 use codex_otel::RuntimeMetricsSummary;
@@ -56,6 +61,7 @@ use codex_protocol::user_input::TextElement;
 use image::DynamicImage;
 use image::ImageReader;
 use mcp_types::EmbeddedResourceResource;
+use mcp_types::Prompt;
 use mcp_types::Resource;
 use mcp_types::ResourceLink;
 use mcp_types::ResourceTemplate;
@@ -755,6 +761,24 @@ pub fn new_approval_decision_cell(
                 ],
             )
         }
+        ApprovedHunks { declined } => (
+            "✔ ".green(),
+            vec![
+                "You ".into(),
+                "approved".bold(),
+                " the change except for ".into(),
+                format!("{} hunk(s)", declined.len()).dim(),
+            ],
+        ),
+        ApprovedAlwaysForPaths { globs } => (
+            "✔ ".green(),
+            vec![
+                "You ".into(),
+                "approved".bold(),
+                " codex to always change ".into(),
+                format!("{} file(s)", globs.len()).dim(),
+            ],
+        ),
         Denied => {
             let snippet = Span::from(exec_snippet(&command)).dim();
             (
@@ -801,6 +825,16 @@ pub(crate) struct PatchHistoryCell {
     cwd: PathBuf,
 }
 
+impl PatchHistoryCell {
+    pub(crate) fn changes(&self) -> &HashMap<PathBuf, FileChange> {
+        &self.changes
+    }
+
+    pub(crate) fn cwd(&self) -> &Path {
+        &self.cwd
+    }
+}
+
 impl HistoryCell for PatchHistoryCell {
     fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
         create_diff_summary(&self.changes, &self.cwd, width as usize)
@@ -1202,6 +1236,8 @@ pub(crate) struct McpToolCallCell {
     start_time: Instant,
     duration: Option<Duration>,
     result: Option<Result<mcp_types::CallToolResult, String>>,
+    /// Messages from the server's progress notifications, oldest first.
+    progress_messages: Vec<String>,
     animations_enabled: bool,
 }
 
@@ -1217,6 +1253,7 @@ impl McpToolCallCell {
             start_time: Instant::now(),
             duration: None,
             result: None,
+            progress_messages: Vec::new(),
             animations_enabled,
         }
     }
@@ -1225,6 +1262,34 @@ impl McpToolCallCell {
         &self.call_id
     }
 
+    /// Record a progress notification and return the summary to show in the
+    /// status line, e.g. `docs.index: 40% · scanning files`.
+    pub(crate) fn record_progress(&mut self, ev: &McpToolCallProgressEvent) -> String {
+        let amount = match ev.total {
+            Some(total) if total > 0.0 => {
+                format!("{:.0}%", (ev.progress / total * 100.0).clamp(0.0, 100.0))
+            }
+            _ => ev.progress.to_string(),
+        };
+        let mut summary = format!(
+            "{}.{}: {amount}",
+            self.invocation.server, self.invocation.tool
+        );
+        if let Some(message) = ev.message.as_deref().map(str::trim)
+            && !message.is_empty()
+        {
+            summary.push_str(&format!(" · {message}"));
+            if self.progress_messages.last().map(String::as_str) != Some(message) {
+                self.progress_messages.push(message.to_string());
+            }
+        }
+        summary
+    }
+
+    pub(crate) fn has_progress(&self) -> bool {
+        !self.progress_messages.is_empty()
+    }
+
     pub(crate) fn complete(
         &mut self,
         duration: Duration,
@@ -1248,7 +1313,13 @@ impl McpToolCallCell {
     pub(crate) fn mark_failed(&mut self) {
         let elapsed = self.start_time.elapsed();
         self.duration = Some(elapsed);
-        self.result = Some(Err("interrupted".to_string()));
+        // Keep whatever the server reported before the interrupt.
+        let mut message = "interrupted".to_string();
+        if !self.progress_messages.is_empty() {
+            message.push_str("; progress so far:\n");
+            message.push_str(&self.progress_messages.join("\n"));
+        }
+        self.result = Some(Err(message));
     }
 
     fn render_content_block(block: &mcp_types::ContentBlock, width: usize) -> String {
@@ -1314,6 +1385,19 @@ impl HistoryCell for McpToolCallCell {
         // Reserve four columns for the tree prefix ("  └ "/"    ") and ensure the wrapper still has at least one cell to work with.
         let detail_wrap_width = (width as usize).saturating_sub(4).max(1);
 
+        if self.result.is_none()
+            && let Some(message) = self.progress_messages.last()
+        {
+            let line = Line::from(message.clone().dim());
+            let wrapped = word_wrap_line(
+                &line,
+                RtOptions::new(detail_wrap_width)
+                    .initial_indent("".into())
+                    .subsequent_indent("    ".into()),
+            );
+            detail_lines.extend(wrapped.iter().map(line_to_static));
+        }
+
         if let Some(result) = &self.result {
             match result {
                 Ok(mcp_types::CallToolResult { content, .. }) => {
@@ -1339,14 +1423,16 @@ impl HistoryCell for McpToolCallCell {
                         TOOL_CALL_MAX_LINES,
                         width as usize,
                     );
-                    let err_line = Line::from(err_text.dim());
-                    let wrapped = word_wrap_line(
-                        &err_line,
-                        RtOptions::new(detail_wrap_width)
-                            .initial_indent("".into())
-                            .subsequent_indent("    ".into()),
-                    );
-                    detail_lines.extend(wrapped.iter().map(line_to_static));
+                    for segment in err_text.split('\n') {
+                        let err_line = Line::from(segment.to_string().dim());
+                        let wrapped = word_wrap_line(
+                            &err_line,
+                            RtOptions::new(detail_wrap_width)
+                                .initial_indent("".into())
+                                .subsequent_indent("    ".into()),
+                        );
+                        detail_lines.extend(wrapped.iter().map(line_to_static));
+                    }
                 }
             }
         }
@@ -1585,6 +1671,7 @@ pub(crate) fn new_mcp_tools_output(
     tools: HashMap<String, mcp_types::Tool>,
     resources: HashMap<String, Vec<Resource>>,
     resource_templates: HashMap<String, Vec<ResourceTemplate>>,
+    prompts: HashMap<String, Vec<Prompt>>,
     auth_statuses: &HashMap<String, McpAuthStatus>,
 ) -> PlainHistoryCell {
     let mut lines: Vec<Line<'static>> = vec![
@@ -1660,8 +1747,17 @@ pub(crate) fn new_mcp_tools_output(
                 http_headers,
                 env_http_headers,
                 ..
+            }
+            | McpServerTransportConfig::Sse {
+                url,
+                http_headers,
+                env_http_headers,
+                ..
             } => {
                 lines.push(vec!["    • URL: ".into(), url.clone().into()].into());
+                if matches!(cfg.transport, McpServerTransportConfig::Sse { .. }) {
+                    lines.push(vec!["    • Transport: ".into(), "sse".into()].into());
+                }
                 if let Some(headers) = http_headers.as_ref()
                     && !headers.is_empty()
                 {
@@ -1739,11 +1835,72 @@ pub(crate) fn new_mcp_tools_output(
             lines.push(spans.into());
         }
 
+        // Most servers offer no prompts, so only list them when present.
+        if let Some(server_prompts) = prompts.get(server.as_str())
+            && !server_prompts.is_empty()
+        {
+            let names: Vec<&str> = server_prompts
+                .iter()
+                .map(|prompt| prompt.name.as_str())
+                .collect();
+            lines.push(vec!["    • Prompts: ".into(), names.join(", ").into()].into());
+        }
+
+        lines.push(Line::from(""));
+    }
+
+    PlainHistoryCell { lines }
+}
+
+pub(crate) fn new_mcp_server_status(servers: Vec<McpServerStatusEntry>) -> PlainHistoryCell {
+    let mut lines: Vec<Line<'static>> = vec![
+        "/mcp status".magenta().into(),
+        "".into(),
+        vec!["🔌  ".into(), "MCP Servers".bold()].into(),
+        "".into(),
+    ];
+
+    if servers.is_empty() {
+        lines.push("  • No MCP servers are running.".italic().into());
+        lines.push("".into());
+    }
+
+    for server in servers {
+        let state: Span<'static> = match server.state {
+            McpServerState::NotStarted => "not started (starts on first use)".dim(),
+            McpServerState::Starting => "starting".dim(),
+            McpServerState::Ready => "ready".green(),
+            McpServerState::Restarting => "restarting".dim(),
+            McpServerState::Failed => "failed".red(),
+        };
+        lines.push(vec!["  • ".into(), server.name.into(), " ".into(), state].into());
+        if server.restarts > 0 {
+            let times = if server.restarts == 1 {
+                "time"
+            } else {
+                "times"
+            };
+            lines.push(format!("    • Restarted {} {times}", server.restarts).into());
+        }
+        if server.state == McpServerState::Ready {
+            if server.tools.is_empty() {
+                lines.push("    • Tools: (none)".into());
+            } else {
+                lines.push(vec!["    • Tools: ".into(), server.tools.join(", ").into()].into());
+            }
+        }
+        if !server.recent_errors.is_empty() {
+            lines.push("    • Recent errors:".into());
+            for error in server.recent_errors {
+                lines.push(vec!["      ".into(), error.red()].into());
+            }
+        }
         lines.push(Line::from(""));
     }
 
     PlainHistoryCell { lines }
 }
+
 pub(crate) fn new_info_event(message: String, hint: Option<String>) -> PlainHistoryCell {
     let mut line = vec!["• ".dim(), message.into()];
     if let Some(hint) = hint {
@@ -1762,6 +1919,22 @@ pub(crate) fn new_error_event(message: String) -> PlainHistoryCell {
     PlainHistoryCell { lines }
 }
 
+/// A turn that ended because the provider refused or a safety system blocked
+/// the response.
+pub(crate) fn new_turn_blocked_event(
+    category: &str,
+    message: String,
+) -> PrefixedWrappedHistoryCell {
+    PrefixedWrappedHistoryCell::new(
+        Line::from(vec![
+            format!("Response blocked ({category}): ").red().bold(),
+            message.red(),
+        ]),
+        "■ ".red(),
+        "  ",
+    )
+}
+
 /// Render a user‑friendly plan update styled like a checkbox todo list.
 pub(crate) fn new_plan_update(update: UpdatePlanArgs) -> PlanUpdateCell {
     let UpdatePlanArgs { explanation, plan } = update;
@@ -1924,6 +2097,45 @@ pub(crate) fn new_patch_apply_failure(stderr: String) -> PlainHistoryCell {
     PlainHistoryCell { lines }
 }
 
+/// Diff lines shown for a re-run command before the rest are elided.
+const PREVIOUS_RUN_DIFF_MAX_LINES: usize = 20;
+
+/// How a re-run command's output changed since its previous run.
+pub(crate) fn new_previous_run_diff(diff: &str) -> PlainHistoryCell {
+    if diff.is_empty() {
+        return PlainHistoryCell {
+            lines: vec![vec!["• ".dim(), "Output unchanged since the previous run".dim()].into()],
+        };
+    }
+
+    let mut lines: Vec<Line<'static>> =
+        vec![vec!["• ".dim(), "Output changed since the previous run".bold()].into()];
+    let diff_lines: Vec<&str> = diff.lines().collect();
+    for (index, line) in diff_lines
+        .iter()
+        .take(PREVIOUS_RUN_DIFF_MAX_LINES)
+        .enumerate()
+    {
+        let prefix = if index == 0 { "  └ " } else { "    " };
+        let content = if line.starts_with('+') {
+            line.to_string().green()
+        } else if line.starts_with('-') {
+            line.to_string().red()
+        } else if line.starts_with("@@") {
+            line.to_string().cyan()
+        } else {
+            line.to_string().dim()
+        };
+        lines.push(vec![prefix.dim(), content].into());
+    }
+    let omitted = diff_lines.len().saturating_sub(PREVIOUS_RUN_DIFF_MAX_LINES);
+    if omitted > 0 {
+        lines.push(vec!["    ".into(), format!("… +{omitted} lines").dim()].into());
+    }
+
+    PlainHistoryCell { lines }
+}
+
 pub(crate) fn new_view_image_tool_call(path: PathBuf, cwd: &Path) -> PlainHistoryCell {
     let display_path = display_path_for(&path, cwd);
 
@@ -1935,6 +2147,52 @@ pub(crate) fn new_view_image_tool_call(path: PathBuf, cwd: &Path) -> PlainHistor
     PlainHistoryCell { lines }
 }
 
+/// A file the agent asked to show. The location is an OSC 8 hyperlink, so
+/// terminals that support them open it on click when no `open_file_command`
+/// did.
+pub(crate) fn new_open_file_request(
+    event: OpenFileRequestEvent,
+    cwd: &Path,
+    file_opener: UriBasedFileOpener,
+) -> PlainHistoryCell {
+    // Control characters in a path could end the OSC 8 sequence early.
+    let display_path: String = display_path_for(&event.path, cwd)
+        .chars()
+        .filter(|c| !c.is_control())
+        .collect();
+    let location = match event.line {
+        Some(line) => format!("{display_path}:{line}"),
+        None => display_path,
+    };
+    let location = match editor_link(&event.path, event.line, file_opener) {
+        Some(url) => format!("\u{1b}]8;;{url}\u{7}{location}\u{1b}]8;;\u{7}").underlined(),
+        None => location.dim(),
+    };
+    let header = if event.opened_by_command {
+        "Opened in editor"
+    } else {
+        "Open in editor"
+    };
+
+    let lines: Vec<Line<'static>> = vec![
+        vec!["• ".dim(), header.bold()].into(),
+        vec!["  └ ".dim(), location].into(),
+    ];
+
+    PlainHistoryCell { lines }
+}
+
+/// `path` as a `file_opener` URI (`vscode://file/path:line`), or a `file://`
+/// URL when no opener is configured.
+fn editor_link(path: &Path, line: Option<u32>, file_opener: UriBasedFileOpener) -> Option<String> {
+    let file_url = url::Url::from_file_path(path).ok()?;
+    Some(match (file_opener.get_scheme(), line) {
+        (Some(scheme), Some(line)) => format!("{scheme}://file{}:{line}", file_url.path()),
+        (Some(scheme), None) => format!("{scheme}://file{}", file_url.path()),
+        (None, _) => file_url.to_string(),
+    })
+}
+
 pub(crate) fn new_reasoning_summary_block(full_reasoning_buffer: String) -> Box<dyn HistoryCell> {
     let full_reasoning_buffer = full_reasoning_buffer.trim();
     if let Some(open) = full_reasoning_buffer.find("**") {
@@ -2157,6 +2415,40 @@ mod tests {
         })
     }
 
+    #[test]
+    fn open_file_request_links_location_with_file_opener() {
+        let event = OpenFileRequestEvent {
+            call_id: "call-1".to_string(),
+            path: PathBuf::from("/repo/src/lib.rs"),
+            line: Some(42),
+            opened_by_command: false,
+        };
+        let cell = new_open_file_request(event, Path::new("/repo"), UriBasedFileOpener::VsCode);
+        assert_eq!(
+            render_transcript(&cell),
+            vec![
+                "• Open in editor".to_string(),
+                "  └ \u{1b}]8;;vscode://file/repo/src/lib.rs:42\u{7}src/lib.rs:42\u{1b}]8;;\u{7}"
+                    .to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn open_file_request_strips_control_characters_from_path() {
+        let event = OpenFileRequestEvent {
+            call_id: "call-1".to_string(),
+            path: PathBuf::from("/repo/evil\u{7}\u{1b}]8;;x\u{7}.rs"),
+            line: None,
+            opened_by_command: false,
+        };
+        let cell = new_open_file_request(event, Path::new("/repo"), UriBasedFileOpener::VsCode);
+        assert_eq!(
+            render_transcript(&cell)[1],
+            "  └ \u{1b}]8;;vscode://file/repo/evil%07%1B]8;;x%07.rs\u{7}evil]8;;x.rs\u{1b}]8;;\u{7}"
+        );
+    }
+
     #[test]
     fn unified_exec_interaction_cell_renders_input() {
         let cell =
@@ -2300,6 +2592,12 @@ mod tests {
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
+            lazy_start: false,
+            tool_aliases: None,
+            tool_approval: None,
+            tool_approvals: None,
+            tool_output_token_limit: None,
+            tool_output_token_limits: None,
         };
         let mut servers = config.mcp_servers.get().clone();
         servers.insert("docs".to_string(), stdio_config);
@@ -2322,6 +2620,12 @@ mod tests {
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
+            lazy_start: false,
+            tool_aliases: None,
+            tool_approval: None,
+            tool_approvals: None,
+            tool_output_token_limit: None,
+            tool_output_token_limits: None,
         };
         servers.insert("http".to_string(), http_config);
         config
@@ -2367,6 +2671,7 @@ mod tests {
             tools,
             HashMap::new(),
             HashMap::new(),
+            HashMap::new(),
             &auth_statuses,
         );
         let rendered = render_lines(&cell.display_lines(120)).join("\n");
@@ -2374,6 +2679,44 @@ mod tests {
         insta::assert_snapshot!(rendered);
     }
 
+    #[test]
+    fn mcp_server_status_lists_state_tools_and_errors() {
+        let cell = new_mcp_server_status(vec![
+            McpServerStatusEntry {
+                name: "docs".to_string(),
+                state: McpServerState::Ready,
+                tools: vec!["list".to_string(), "search".to_string()],
+                restarts: 0,
+                recent_errors: Vec::new(),
+            },
+            McpServerStatusEntry {
+                name: "flaky".to_string(),
+                state: McpServerState::Restarting,
+                tools: Vec::new(),
+                restarts: 2,
+                recent_errors: vec!["health check failed: timed out".to_string()],
+            },
+        ]);
+
+        assert_eq!(
+            render_lines(&cell.display_lines(80)),
+            vec![
+                "/mcp status",
+                "",
+                "🔌  MCP Servers",
+                "",
+                "  • docs ready",
+                "    • Tools: list, search",
+                "",
+                "  • flaky restarting",
+                "    • Restarted 2 times",
+                "    • Recent errors:",
+                "      health check failed: timed out",
+                "",
+            ]
+        );
+    }
+
     #[test]
     fn empty_agent_message_cell_transcript() {
         let cell = AgentMessageCell::new(vec![Line::default()], false);
@@ -2494,6 +2837,40 @@ mod tests {
         insta::assert_snapshot!(rendered);
     }
 
+    #[test]
+    fn interrupted_mcp_tool_call_keeps_progress() {
+        let invocation = McpInvocation {
+            server: "search".into(),
+            tool: "index".into(),
+            arguments: None,
+        };
+        let progress = |progress: f64, message: &str| McpToolCallProgressEvent {
+            call_id: "call-progress".into(),
+            progress,
+            total: Some(4.0),
+            message: Some(message.into()),
+        };
+
+        let mut cell = new_active_mcp_tool_call("call-progress".into(), invocation, false);
+        assert_eq!(
+            cell.record_progress(&progress(1.0, "scanning files")),
+            "search.index: 25% · scanning files"
+        );
+        cell.record_progress(&progress(2.0, "scanning files"));
+        cell.record_progress(&progress(3.0, "building index"));
+        cell.mark_failed();
+
+        let rendered = render_lines(&cell.display_lines(80));
+        assert_eq!(
+            rendered[1..].to_vec(),
+            vec![
+                "  └ Error: interrupted; progress so far:",
+                "    scanning files",
+                "    building index",
+            ]
+        );
+    }
+
     #[test]
     fn completed_mcp_tool_call_success_snapshot() {
         let invocation = McpInvocation {
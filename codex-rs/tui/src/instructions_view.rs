@@ -0,0 +1,83 @@
+//! Rendering for `/instructions`: every layer of the session's instructions,
+//! each headed by where it comes from.
+
+use std::path::Path;
+
+use codex_core::project_doc::InstructionLayer;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+
+use crate::diff_render::display_path_for;
+
+pub(crate) const INSTRUCTIONS_VIEW_TITLE: &str = "I N S T R U C T I O N S";
+
+pub(crate) fn instruction_lines(layers: &[InstructionLayer], cwd: &Path) -> Vec<Line<'static>> {
+    let mut lines: Vec<Line<'static>> = Vec::new();
+    for layer in layers {
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        let mut header: Vec<Span<'static>> = vec![layer.title.clone().bold()];
+        if let Some(path) = &layer.path {
+            header.push(" · ".dim());
+            header.push(display_path_for(path, cwd).cyan());
+        }
+        if let Some(note) = &layer.note {
+            header.push(" · ".dim());
+            header.push(note.clone().dim());
+        }
+        lines.push(Line::from(header));
+        if let Some(text) = &layer.text {
+            lines.push(Line::from(""));
+            lines.extend(text.lines().map(|line| Line::from(line.to_string())));
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    #[test]
+    fn layers_are_headed_by_their_source() {
+        let cwd = PathBuf::from("/repo/crate");
+        let layers = vec![
+            InstructionLayer {
+                title: "Base instructions".to_string(),
+                path: None,
+                text: None,
+                note: Some("built into Codex for the selected model".to_string()),
+            },
+            InstructionLayer {
+                title: "Project instructions".to_string(),
+                path: Some(cwd.join("AGENTS.md")),
+                text: Some("Run cargo fmt.\nKeep tests close.".to_string()),
+                note: None,
+            },
+        ];
+        let text: Vec<String> = instruction_lines(&layers, &cwd)
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.clone())
+                    .collect::<String>()
+            })
+            .collect();
+        assert_eq!(
+            text,
+            vec![
+                "Base instructions · built into Codex for the selected model",
+                "",
+                "Project instructions · AGENTS.md",
+                "",
+                "Run cargo fmt.",
+                "Keep tests close.",
+            ]
+        );
+    }
+}
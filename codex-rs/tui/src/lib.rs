@@ -9,8 +9,11 @@ pub use app::AppExitInfo;
 pub use app::ExitReason;
 use codex_app_server_protocol::AuthMode;
 use codex_cloud_requirements::cloud_requirements_loader;
+use codex_common::oss::OllamaModelDetails;
+use codex_common::oss::apply_ollama_model_details;
 use codex_common::oss::ensure_oss_provider_ready;
 use codex_common::oss::get_default_model_for_oss_provider;
+use codex_common::oss::is_ollama_provider;
 use codex_common::oss::ollama_chat_deprecation_notice;
 use codex_core::AuthManager;
 use codex_core::CodexAuth;
@@ -27,6 +30,7 @@ use codex_core::config::resolve_oss_provider;
 use codex_core::config_loader::CloudRequirementsLoader;
 use codex_core::config_loader::ConfigLoadError;
 use codex_core::config_loader::format_config_error_with_source;
+use codex_core::decode_rollout_line;
 use codex_core::default_client::set_default_client_residency_requirement;
 use codex_core::find_thread_path_by_id_str;
 use codex_core::find_thread_path_by_name_str;
@@ -77,6 +81,7 @@ mod frames;
 mod get_git_diff;
 mod history_cell;
 pub mod insert_history;
+mod instructions_view;
 mod key_hint;
 pub mod live_wrap;
 mod markdown;
@@ -92,6 +97,7 @@ mod render;
 mod resume_picker;
 mod selection_list;
 mod session_log;
+mod settings;
 mod shimmer;
 mod skills_helpers;
 mod slash_command;
@@ -114,7 +120,6 @@ mod wrapping;
 #[cfg(test)]
 pub mod test_backend;
 
-use crate::onboarding::TrustDirectorySelection;
 use crate::onboarding::onboarding_screen::OnboardingScreenArgs;
 use crate::onboarding::onboarding_screen::run_onboarding_app;
 use crate::tui::Tui;
@@ -217,7 +222,7 @@ pub async fn run_main(
     }
 
     let cloud_auth_manager = AuthManager::shared(
-        codex_home.to_path_buf(),
+        config_toml.auth_home(&codex_home, cli.config_profile.clone()),
         false,
         config_toml.cli_auth_credentials_store.unwrap_or_default(),
     );
@@ -273,7 +278,11 @@ pub async fn run_main(
         model_provider: model_provider_override.clone(),
         config_profile: cli.config_profile.clone(),
         codex_linux_sandbox_exe,
+        model_reasoning_effort: cli.reasoning_effort.map(Into::into),
+        model_verbosity: cli.verbosity.map(Into::into),
         show_raw_agent_reasoning: cli.oss.then_some(true),
+        record_api_dir: cli.record_api.clone(),
+        replay_api_path: cli.replay_api.clone(),
         additional_writable_roots: additional_dirs,
         ..Default::default()
     };
@@ -345,20 +354,13 @@ pub async fn run_main(
     let feedback_layer = feedback.logger_layer();
     let feedback_metadata_layer = feedback.metadata_layer();
 
-    if cli.oss && model_provider_override.is_some() {
-        // We're in the oss section, so provider_id should be Some
-        // Let's handle None case gracefully though just in case
-        let provider_id = match model_provider_override.as_ref() {
-            Some(id) => id,
-            None => {
-                error!("OSS provider unexpectedly not set when oss flag is used");
-                return Err(std::io::Error::other(
-                    "OSS provider not set but oss flag was used",
-                ));
-            }
-        };
-        ensure_oss_provider_ready(provider_id, &config).await?;
-    }
+    // `--oss` selects an OSS provider; a configured Ollama provider gets the
+    // same pull-on-first-use and capability detection.
+    let ollama_model_details = if cli.oss || is_ollama_provider(&config.model_provider_id) {
+        ensure_oss_provider_ready(&config.model_provider_id, &config).await?
+    } else {
+        None
+    };
 
     let otel = match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
         codex_core::otel_init::build_provider(&config, env!("CARGO_PKG_VERSION"), None, true)
@@ -404,6 +406,7 @@ pub async fn run_main(
         cli_kv_overrides,
         cloud_requirements,
         feedback,
+        ollama_model_details,
     )
     .await
     .map_err(|err| std::io::Error::other(err.to_string()))
@@ -416,6 +419,7 @@ async fn run_ratatui_app(
     cli_kv_overrides: Vec<(String, toml::Value)>,
     cloud_requirements: CloudRequirementsLoader,
     feedback: codex_feedback::CodexFeedback,
+    ollama_model_details: Option<OllamaModelDetails>,
 ) -> color_eyre::Result<AppExitInfo> {
     color_eyre::install()?;
 
@@ -461,7 +465,7 @@ async fn run_ratatui_app(
     session_log::maybe_init(&initial_config);
 
     let auth_manager = AuthManager::shared(
-        initial_config.codex_home.clone(),
+        initial_config.auth_home(),
         false,
         initial_config.cli_auth_credentials_store_mode,
     );
@@ -494,12 +498,9 @@ async fn run_ratatui_app(
                 exit_reason: ExitReason::UserRequested,
             });
         }
-        // if the user acknowledged windows or made an explicit decision ato trust the directory, reload the config accordingly
-        if onboarding_result
-            .directory_trust_decision
-            .map(|d| d == TrustDirectorySelection::Trust)
-            .unwrap_or(false)
-        {
+        // if the user made an explicit trust decision for the directory, reload the config so
+        // the trust level's preset applies
+        if onboarding_result.directory_trust_decision.is_some() {
             load_config_or_exit(
                 cli_kv_overrides.clone(),
                 overrides.clone(),
@@ -513,6 +514,11 @@ async fn run_ratatui_app(
         initial_config
     };
 
+    let mut config = config;
+    let ollama_warnings = ollama_model_details
+        .map(|details| apply_ollama_model_details(&mut config, &details))
+        .unwrap_or_default();
+
     let ollama_chat_support_notice = match ollama_chat_deprecation_notice(&config).await {
         Ok(notice) => notice,
         Err(err) => {
@@ -668,7 +674,7 @@ async fn run_ratatui_app(
         None => None,
     };
 
-    let config = match &session_selection {
+    let mut config = match &session_selection {
         resume_picker::SessionSelection::Resume(_) | resume_picker::SessionSelection::Fork(_) => {
             load_config_or_exit_with_fallback_cwd(
                 cli_kv_overrides.clone(),
@@ -687,8 +693,12 @@ async fn run_ratatui_app(
         prompt,
         images,
         no_alt_screen,
+        worktree,
         ..
     } = cli;
+    if worktree {
+        config.session_worktree = true;
+    }
 
     let use_alt_screen = determine_alt_screen_mode(no_alt_screen, config.tui_alternate_screen);
     tui.set_alt_screen_enabled(use_alt_screen);
@@ -706,6 +716,7 @@ async fn run_ratatui_app(
         feedback,
         should_show_trust_screen, // Proxy to: is it a first run in this directory?
         ollama_chat_support_notice,
+        ollama_warnings,
     )
     .await;
 
@@ -746,7 +757,10 @@ async fn parse_latest_turn_context_cwd(path: &Path) -> Option<PathBuf> {
         if trimmed.is_empty() {
             continue;
         }
-        let Ok(rollout_line) = serde_json::from_str::<RolloutLine>(trimmed) else {
+        let Ok(trimmed) = decode_rollout_line(trimmed) else {
+            continue;
+        };
+        let Ok(rollout_line) = serde_json::from_str::<RolloutLine>(&trimmed) else {
             continue;
         };
         if let RolloutItem::TurnContext(item) = rollout_line.item {
@@ -840,8 +854,8 @@ fn get_login_status(config: &Config) -> LoginStatus {
     if config.model_provider.requires_openai_auth {
         // Reading the OpenAI API key is an async operation because it may need
         // to refresh the token. Block on it.
-        let codex_home = config.codex_home.clone();
-        match CodexAuth::from_auth_storage(&codex_home, config.cli_auth_credentials_store_mode) {
+        let auth_home = config.auth_home();
+        match CodexAuth::from_auth_storage(&auth_home, config.cli_auth_credentials_store_mode) {
             Ok(Some(auth)) => LoginStatus::AuthMode(auth.api_auth_mode()),
             Ok(None) => LoginStatus::NotAuthenticated,
             Err(err) => {
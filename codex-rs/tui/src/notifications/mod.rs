@@ -1,4 +1,5 @@
 mod bel;
+mod native;
 mod osc9;
 
 use std::env;
@@ -6,26 +7,36 @@ use std::io;
 
 use bel::BelBackend;
 use codex_core::config::types::NotificationMethod;
+use native::ActionCallback;
+use native::NativeBackend;
+pub(crate) use native::NotificationAction;
 use osc9::Osc9Backend;
 
 #[derive(Debug)]
 pub enum DesktopNotificationBackend {
     Osc9(Osc9Backend),
     Bel(BelBackend),
+    Native(NativeBackend),
 }
 
 impl DesktopNotificationBackend {
     pub fn for_method(method: NotificationMethod) -> Self {
         match method {
-            NotificationMethod::Auto => {
-                if supports_osc9() {
-                    Self::Osc9(Osc9Backend)
-                } else {
-                    Self::Bel(BelBackend)
-                }
-            }
+            NotificationMethod::Auto => Self::terminal(),
             NotificationMethod::Osc9 => Self::Osc9(Osc9Backend),
             NotificationMethod::Bel => Self::Bel(BelBackend),
+            // Without a platform notifier, fall back to terminal notifications.
+            NotificationMethod::Native => NativeBackend::detect()
+                .map(Self::Native)
+                .unwrap_or_else(Self::terminal),
+        }
+    }
+
+    fn terminal() -> Self {
+        if supports_osc9() {
+            Self::Osc9(Osc9Backend)
+        } else {
+            Self::Bel(BelBackend)
         }
     }
 
@@ -33,6 +44,7 @@ impl DesktopNotificationBackend {
         match self {
             DesktopNotificationBackend::Osc9(_) => NotificationMethod::Osc9,
             DesktopNotificationBackend::Bel(_) => NotificationMethod::Bel,
+            DesktopNotificationBackend::Native(_) => NotificationMethod::Native,
         }
     }
 
@@ -40,6 +52,25 @@ impl DesktopNotificationBackend {
         match self {
             DesktopNotificationBackend::Osc9(backend) => backend.notify(message),
             DesktopNotificationBackend::Bel(backend) => backend.notify(message),
+            DesktopNotificationBackend::Native(backend) => backend.notify(message),
+        }
+    }
+
+    /// Like [`Self::notify`], but attaches `actions` as buttons when the
+    /// backend supports them; `on_action` receives the button clicked.
+    pub(crate) fn notify_with_actions(
+        &mut self,
+        message: &str,
+        actions: &[NotificationAction],
+        on_action: ActionCallback,
+    ) -> io::Result<()> {
+        match self {
+            DesktopNotificationBackend::Native(backend) => {
+                backend.notify_with_actions(message, actions, on_action)
+            }
+            DesktopNotificationBackend::Osc9(_) | DesktopNotificationBackend::Bel(_) => {
+                self.notify(message)
+            }
         }
     }
 }
@@ -0,0 +1,249 @@
+use std::io;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Stdio;
+
+const NOTIFICATION_TITLE: &str = "Codex";
+
+/// Button shown on a native notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NotificationAction {
+    Approve,
+    Deny,
+    Open,
+}
+
+impl NotificationAction {
+    pub(crate) const APPROVAL: [NotificationAction; 3] = [
+        NotificationAction::Approve,
+        NotificationAction::Deny,
+        NotificationAction::Open,
+    ];
+
+    fn key(self) -> &'static str {
+        match self {
+            NotificationAction::Approve => "approve",
+            NotificationAction::Deny => "deny",
+            NotificationAction::Open => "open",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            NotificationAction::Approve => "Approve",
+            NotificationAction::Deny => "Deny",
+            NotificationAction::Open => "Open",
+        }
+    }
+
+    /// Parse the notifier's output: `notify-send` prints the action key,
+    /// `alerter` prints the button label.
+    fn from_output(output: &str) -> Option<Self> {
+        let output = output.trim();
+        Self::APPROVAL.into_iter().find(|action| {
+            output.eq_ignore_ascii_case(action.key()) || output.eq_ignore_ascii_case(action.label())
+        })
+    }
+}
+
+/// Callback invoked (from a background thread) with the button the user
+/// clicked.
+pub(crate) type ActionCallback = Box<dyn FnOnce(NotificationAction) + Send + 'static>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Notifier {
+    /// libnotify's `notify-send`; actions need 0.7.9 or newer.
+    NotifySend,
+    /// `alerter` on macOS, which supports action buttons.
+    Alerter,
+    /// `osascript` on macOS; plain notifications only.
+    Osascript,
+}
+
+/// Posts notifications through the platform notifier program.
+#[derive(Debug)]
+pub struct NativeBackend {
+    program: PathBuf,
+    notifier: Notifier,
+}
+
+impl NativeBackend {
+    /// Locate a supported notifier, or `None` when the platform has none.
+    pub fn detect() -> Option<Self> {
+        let candidates: &[(&str, Notifier)] = if cfg!(target_os = "macos") {
+            &[
+                ("alerter", Notifier::Alerter),
+                ("osascript", Notifier::Osascript),
+            ]
+        } else if cfg!(unix) {
+            &[("notify-send", Notifier::NotifySend)]
+        } else {
+            &[]
+        };
+        candidates.iter().find_map(|(name, notifier)| {
+            which::which(name).ok().map(|program| Self {
+                program,
+                notifier: *notifier,
+            })
+        })
+    }
+
+    pub fn notify(&mut self, message: &str) -> io::Result<()> {
+        let child = self.command(message, &[]).spawn()?;
+        // Reap the notifier in the background; `alerter` blocks until the
+        // notification is dismissed.
+        std::thread::spawn(move || {
+            let _ = child.wait_with_output();
+        });
+        Ok(())
+    }
+
+    /// Post a notification with `actions` as buttons and call `on_action`
+    /// with the one the user clicks. Notifiers without button support post a
+    /// plain notification instead.
+    pub(crate) fn notify_with_actions(
+        &mut self,
+        message: &str,
+        actions: &[NotificationAction],
+        on_action: ActionCallback,
+    ) -> io::Result<()> {
+        if self.notifier == Notifier::Osascript || actions.is_empty() {
+            return self.notify(message);
+        }
+        let child = self
+            .command(message, actions)
+            .stdout(Stdio::piped())
+            .spawn()?;
+        let mut fallback = self.command(message, &[]);
+        std::thread::spawn(move || {
+            let Ok(output) = child.wait_with_output() else {
+                return;
+            };
+            if !output.status.success() {
+                // Older `notify-send` builds reject `--action`; post the
+                // message without buttons rather than dropping it.
+                if let Ok(child) = fallback.spawn() {
+                    let _ = child.wait_with_output();
+                }
+                return;
+            }
+            if let Some(action) =
+                NotificationAction::from_output(&String::from_utf8_lossy(&output.stdout))
+            {
+                on_action(action);
+            }
+        });
+        Ok(())
+    }
+
+    fn command(&self, message: &str, actions: &[NotificationAction]) -> Command {
+        let mut command = Command::new(&self.program);
+        command
+            .args(notifier_args(self.notifier, message, actions))
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        command
+    }
+}
+
+fn notifier_args(notifier: Notifier, message: &str, actions: &[NotificationAction]) -> Vec<String> {
+    match notifier {
+        Notifier::NotifySend => {
+            let mut args = vec![format!("--app-name={NOTIFICATION_TITLE}")];
+            if !actions.is_empty() {
+                args.push("--wait".to_string());
+                args.extend(
+                    actions
+                        .iter()
+                        .map(|action| format!("--action={}={}", action.key(), action.label())),
+                );
+            }
+            args.push(NOTIFICATION_TITLE.to_string());
+            args.push(message.to_string());
+            args
+        }
+        Notifier::Alerter => {
+            let mut args = vec![
+                "-title".to_string(),
+                NOTIFICATION_TITLE.to_string(),
+                "-message".to_string(),
+                message.to_string(),
+            ];
+            if actions.is_empty() {
+                args.extend(["-timeout".to_string(), "10".to_string()]);
+            } else {
+                let labels = actions
+                    .iter()
+                    .map(|action| action.label())
+                    .collect::<Vec<_>>()
+                    .join(",");
+                args.extend(["-actions".to_string(), labels]);
+            }
+            args
+        }
+        Notifier::Osascript => vec![
+            "-e".to_string(),
+            format!(
+                "display notification {} with title {}",
+                applescript_string(message),
+                applescript_string(NOTIFICATION_TITLE)
+            ),
+        ],
+    }
+}
+
+fn applescript_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn notify_send_actions_wait_for_a_choice() {
+        assert_eq!(
+            notifier_args(
+                Notifier::NotifySend,
+                "Approval requested: ls",
+                &NotificationAction::APPROVAL
+            ),
+            vec![
+                "--app-name=Codex",
+                "--wait",
+                "--action=approve=Approve",
+                "--action=deny=Deny",
+                "--action=open=Open",
+                "Codex",
+                "Approval requested: ls",
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_keys_and_labels_from_notifier_output() {
+        assert_eq!(
+            NotificationAction::from_output("approve\n"),
+            Some(NotificationAction::Approve)
+        );
+        assert_eq!(
+            NotificationAction::from_output("Deny"),
+            Some(NotificationAction::Deny)
+        );
+        // alerter reports dismissals and body clicks with `@` markers.
+        assert_eq!(NotificationAction::from_output("@CLOSED"), None);
+    }
+
+    #[test]
+    fn osascript_escapes_quotes() {
+        assert_eq!(
+            notifier_args(Notifier::Osascript, r#"run "ls""#, &[]),
+            vec![
+                "-e",
+                r#"display notification "run \"ls\"" with title "Codex""#,
+            ]
+        );
+    }
+}
@@ -36,9 +36,34 @@ pub(crate) struct TrustDirectoryWidget {
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum TrustDirectorySelection {
     Trust,
+    Limited,
     DontTrust,
 }
 
+impl TrustDirectorySelection {
+    const ORDER: [TrustDirectorySelection; 3] = [
+        TrustDirectorySelection::Trust,
+        TrustDirectorySelection::Limited,
+        TrustDirectorySelection::DontTrust,
+    ];
+
+    fn index(self) -> usize {
+        match self {
+            TrustDirectorySelection::Trust => 0,
+            TrustDirectorySelection::Limited => 1,
+            TrustDirectorySelection::DontTrust => 2,
+        }
+    }
+
+    fn previous(self) -> Self {
+        Self::ORDER[self.index().saturating_sub(1)]
+    }
+
+    fn next(self) -> Self {
+        Self::ORDER[(self.index() + 1).min(Self::ORDER.len() - 1)]
+    }
+}
+
 impl WidgetRef for &TrustDirectoryWidget {
     fn render_ref(&self, area: Rect, buf: &mut Buffer) {
         let mut column = ColumnRenderable::new();
@@ -69,6 +94,10 @@ impl WidgetRef for &TrustDirectoryWidget {
                 "Yes, allow Codex to work in this folder without asking for approval",
                 TrustDirectorySelection::Trust,
             ));
+            options.push((
+                "Limited, read-only without MCP servers or web search",
+                TrustDirectorySelection::Limited,
+            ));
             options.push((
                 "No, ask me to approve edits and commands",
                 TrustDirectorySelection::DontTrust,
@@ -78,6 +107,10 @@ impl WidgetRef for &TrustDirectoryWidget {
                 "Allow Codex to work in this folder without asking for approval",
                 TrustDirectorySelection::Trust,
             ));
+            options.push((
+                "Read-only, without MCP servers or web search",
+                TrustDirectorySelection::Limited,
+            ));
             options.push((
                 "Require approval of edits and commands",
                 TrustDirectorySelection::DontTrust,
@@ -125,15 +158,17 @@ impl KeyboardHandler for TrustDirectoryWidget {
 
         match key_event.code {
             KeyCode::Up | KeyCode::Char('k') => {
-                self.highlighted = TrustDirectorySelection::Trust;
+                self.highlighted = self.highlighted.previous();
             }
             KeyCode::Down | KeyCode::Char('j') => {
-                self.highlighted = TrustDirectorySelection::DontTrust;
+                self.highlighted = self.highlighted.next();
             }
             KeyCode::Char('1') | KeyCode::Char('y') => self.handle_trust(),
-            KeyCode::Char('2') | KeyCode::Char('n') => self.handle_dont_trust(),
+            KeyCode::Char('2') => self.handle_limited(),
+            KeyCode::Char('3') | KeyCode::Char('n') => self.handle_dont_trust(),
             KeyCode::Enter => match self.highlighted {
                 TrustDirectorySelection::Trust => self.handle_trust(),
+                TrustDirectorySelection::Limited => self.handle_limited(),
                 TrustDirectorySelection::DontTrust => self.handle_dont_trust(),
             },
             _ => {}
@@ -162,6 +197,21 @@ impl TrustDirectoryWidget {
         self.selection = Some(TrustDirectorySelection::Trust);
     }
 
+    fn handle_limited(&mut self) {
+        self.highlighted = TrustDirectorySelection::Limited;
+        let target =
+            resolve_root_git_project_for_trust(&self.cwd).unwrap_or_else(|| self.cwd.clone());
+        if let Err(e) = set_project_trust_level(&self.codex_home, &target, TrustLevel::Limited) {
+            tracing::error!("Failed to set project limited: {e:?}");
+            self.error = Some(format!(
+                "Failed to set limited trust for {}: {e}",
+                target.display()
+            ));
+        }
+
+        self.selection = Some(TrustDirectorySelection::Limited);
+    }
+
     fn handle_dont_trust(&mut self) {
         self.highlighted = TrustDirectorySelection::DontTrust;
         let target =
@@ -216,6 +266,28 @@ mod tests {
         assert_eq!(widget.selection, Some(TrustDirectorySelection::DontTrust));
     }
 
+    #[test]
+    fn limited_selection_records_limited_trust() {
+        let codex_home = TempDir::new().expect("temp home");
+        let mut widget = TrustDirectoryWidget {
+            codex_home: codex_home.path().to_path_buf(),
+            cwd: PathBuf::from("/workspace/project"),
+            is_git_repo: false,
+            selection: None,
+            highlighted: TrustDirectorySelection::DontTrust,
+            error: None,
+        };
+
+        widget.handle_key_event(KeyEvent::new(KeyCode::Up, KeyModifiers::NONE));
+        assert_eq!(widget.highlighted, TrustDirectorySelection::Limited);
+        widget.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(widget.selection, Some(TrustDirectorySelection::Limited));
+
+        let config = std::fs::read_to_string(codex_home.path().join("config.toml"))
+            .expect("read config.toml");
+        assert!(config.contains(r#"trust_level = "limited""#), "{config}");
+    }
+
     #[test]
     fn renders_snapshot_for_git_repo() {
         let codex_home = TempDir::new().expect("temp home");
@@ -14,11 +14,24 @@
 //! `TranscriptOverlay::sync_live_tail` uses the key to decide when the cached tail must be
 //! recomputed. `ChatWidget` is responsible for producing a key that changes when the active cell
 //! mutates in place or when its transcript output is time-dependent.
+//!
+//! The transcript overlay can be searched with `/` (matches are highlighted and `n`/`N` step
+//! between them, see `transcript_search`) and jumps between turn boundaries (`[`/`]`), tool calls
+//! (`t`), and diffs (`d`) using the same points as the timeline.
+//!
+//! The timeline overlay (`Ctrl+O`) lives in `timeline` and reuses the pager view to scrub through
+//! the same committed transcript cells, diffing the turn checkpoints for its changes pane. The worker coordinator view (`/workers`) lives in `workers`
+//! and re-renders from the worker pool's latest snapshot on every draw. The diff review (`/diff`
+//! and `Ctrl+A` on a patch approval) lives in `diff_review` and lays out its own rows so the old
+//! and new sides of a change can sit next to each other. The terminal takeover (`/terminal`) lives
+//! in `terminal` and forwards keys to a background process instead of scrolling.
 
 use std::io::Result;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 
+use crate::app_event_sender::AppEventSender;
 use crate::chatwidget::ActiveCellTranscriptKey;
 use crate::history_cell::HistoryCell;
 use crate::history_cell::UserHistoryCell;
@@ -30,8 +43,11 @@ use crate::render::renderable::Renderable;
 use crate::style::user_message_style;
 use crate::tui;
 use crate::tui::TuiEvent;
+use codex_core::workers::WorkerPoolSnapshot;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
 use ratatui::buffer::Buffer;
 use ratatui::buffer::Cell;
 use ratatui::layout::Rect;
@@ -45,10 +61,31 @@ use ratatui::widgets::Paragraph;
 use ratatui::widgets::Widget;
 use ratatui::widgets::WidgetRef;
 use ratatui::widgets::Wrap;
+use tokio::sync::watch;
+
+mod diff_review;
+mod terminal;
+mod timeline;
+mod transcript_search;
+mod workers;
+
+pub(crate) use diff_review::DiffReviewOverlay;
+pub(crate) use terminal::TerminalOverlay;
+pub(crate) use timeline::TimelineCheckpoint;
+pub(crate) use timeline::TimelineOverlay;
+pub(crate) use workers::WorkersOverlay;
+
+use timeline::TimelinePointKind;
+use transcript_search::SearchMatch;
+use transcript_search::TranscriptSearch;
 
 pub(crate) enum Overlay {
     Transcript(TranscriptOverlay),
     Static(StaticOverlay),
+    Timeline(TimelineOverlay),
+    Workers(WorkersOverlay),
+    DiffReview(DiffReviewOverlay),
+    Terminal(TerminalOverlay),
 }
 
 impl Overlay {
@@ -56,6 +93,19 @@ impl Overlay {
         Self::Transcript(TranscriptOverlay::new(cells))
     }
 
+    pub(crate) fn new_timeline(
+        cells: Vec<Arc<dyn HistoryCell>>,
+        checkpoints: Vec<TimelineCheckpoint>,
+        cwd: PathBuf,
+        app_event_tx: AppEventSender,
+    ) -> Self {
+        Self::Timeline(TimelineOverlay::new(cells, checkpoints, cwd, app_event_tx))
+    }
+
+    pub(crate) fn new_workers(snapshot_rx: watch::Receiver<WorkerPoolSnapshot>) -> Self {
+        Self::Workers(WorkersOverlay::new(snapshot_rx))
+    }
+
     pub(crate) fn new_static_with_lines(lines: Vec<Line<'static>>, title: String) -> Self {
         Self::Static(StaticOverlay::with_title(lines, title))
     }
@@ -71,6 +121,10 @@ impl Overlay {
         match self {
             Overlay::Transcript(o) => o.handle_event(tui, event),
             Overlay::Static(o) => o.handle_event(tui, event),
+            Overlay::Timeline(o) => o.handle_event(tui, event),
+            Overlay::Workers(o) => o.handle_event(tui, event),
+            Overlay::DiffReview(o) => o.handle_event(tui, event),
+            Overlay::Terminal(o) => o.handle_event(tui, event),
         }
     }
 
@@ -78,6 +132,10 @@ impl Overlay {
         match self {
             Overlay::Transcript(o) => o.is_done(),
             Overlay::Static(o) => o.is_done(),
+            Overlay::Timeline(o) => o.is_done(),
+            Overlay::Workers(o) => o.is_done(),
+            Overlay::DiffReview(o) => o.is_done(),
+            Overlay::Terminal(o) => o.is_done(),
         }
     }
 }
@@ -103,6 +161,15 @@ const KEY_ESC: KeyBinding = key_hint::plain(KeyCode::Esc);
 const KEY_ENTER: KeyBinding = key_hint::plain(KeyCode::Enter);
 const KEY_CTRL_T: KeyBinding = key_hint::ctrl(KeyCode::Char('t'));
 const KEY_CTRL_C: KeyBinding = key_hint::ctrl(KeyCode::Char('c'));
+const KEY_SLASH: KeyBinding = key_hint::plain(KeyCode::Char('/'));
+const KEY_N: KeyBinding = key_hint::plain(KeyCode::Char('n'));
+const KEY_SHIFT_N: KeyBinding = key_hint::shift(KeyCode::Char('n'));
+const KEY_PREV_TURN: KeyBinding = key_hint::plain(KeyCode::Char('['));
+const KEY_NEXT_TURN: KeyBinding = key_hint::plain(KeyCode::Char(']'));
+const KEY_TOOL: KeyBinding = key_hint::plain(KeyCode::Char('t'));
+const KEY_SHIFT_TOOL: KeyBinding = key_hint::shift(KeyCode::Char('t'));
+const KEY_DIFF: KeyBinding = key_hint::plain(KeyCode::Char('d'));
+const KEY_SHIFT_DIFF: KeyBinding = key_hint::shift(KeyCode::Char('d'));
 
 // Common pager navigation hints rendered on the first line
 const PAGER_KEY_HINTS: &[(&[KeyBinding], &str)] = &[
@@ -111,6 +178,12 @@ const PAGER_KEY_HINTS: &[(&[KeyBinding], &str)] = &[
     (&[KEY_HOME, KEY_END], "to jump"),
 ];
 
+/// Whether `e` presses `binding` (a shifted letter), also accepting the uppercase letter without
+/// SHIFT that some terminals report instead.
+fn is_shifted_press(binding: KeyBinding, upper: char, e: KeyEvent) -> bool {
+    binding.is_press(e) || (e.code == KeyCode::Char(upper) && e.kind != KeyEventKind::Release)
+}
+
 // Render a single line of key hints from (key(s), description) pairs.
 fn render_key_hints(area: Rect, buf: &mut Buffer, pairs: &[(&[KeyBinding], &str)]) {
     let mut spans: Vec<Span<'static>> = vec![" ".into()];
@@ -138,6 +211,7 @@ struct PagerView {
     scroll_offset: usize,
     title: String,
     last_content_height: Option<usize>,
+    last_content_width: Option<u16>,
     last_rendered_height: Option<usize>,
     /// If set, on next render ensure this chunk is visible.
     pending_scroll_chunk: Option<usize>,
@@ -150,6 +224,7 @@ impl PagerView {
             scroll_offset,
             title,
             last_content_height: None,
+            last_content_width: None,
             last_rendered_height: None,
             pending_scroll_chunk: None,
         }
@@ -167,6 +242,7 @@ impl PagerView {
         self.render_header(area, buf);
         let content_area = self.content_area(area);
         self.update_last_content_height(content_area.height);
+        self.last_content_width = Some(content_area.width);
         let content_height = self.content_height(content_area.width);
         self.last_rendered_height = Some(content_height);
         // If there is a pending request to scroll a specific chunk into view,
@@ -313,6 +389,12 @@ impl PagerView {
             .unwrap_or_else(|| self.content_area(viewport_area).height as usize)
     }
 
+    /// Returns the width content was last wrapped at, falling back to the viewport width before
+    /// the first render.
+    fn content_width(&self, viewport_area: Rect) -> u16 {
+        self.last_content_width.unwrap_or(viewport_area.width)
+    }
+
     fn update_last_content_height(&mut self, height: u16) {
         self.last_content_height = Some(height as usize);
     }
@@ -346,6 +428,14 @@ impl PagerView {
         self.scroll_offset >= max_scroll
     }
 
+    /// Returns the content row shown at the top of the view, resolving the "pinned to bottom"
+    /// offset against the last rendered page height.
+    fn top_row(&self, width: u16) -> usize {
+        let page = self.last_content_height.unwrap_or(0);
+        self.scroll_offset
+            .min(self.content_height(width).saturating_sub(page))
+    }
+
     /// Request that the given text chunk index be scrolled into view on next render.
     fn scroll_chunk_into_view(&mut self, chunk_index: usize) {
         self.pending_scroll_chunk = Some(chunk_index);
@@ -406,12 +496,20 @@ impl Renderable for CachedRenderable {
 struct CellRenderable {
     cell: Arc<dyn HistoryCell>,
     style: Style,
+    /// ASCII-lowercased search query whose matches are highlighted.
+    query: Option<String>,
 }
 
 impl Renderable for CellRenderable {
     fn render(&self, area: Rect, buf: &mut Buffer) {
-        let p =
-            Paragraph::new(Text::from(self.cell.transcript_lines(area.width))).style(self.style);
+        let mut lines = self.cell.transcript_lines(area.width);
+        if let Some(query) = &self.query {
+            lines = lines
+                .into_iter()
+                .map(|line| transcript_search::highlight_line(line, query))
+                .collect();
+        }
+        let p = Paragraph::new(Text::from(lines)).style(self.style);
         p.render(area, buf);
     }
 
@@ -431,9 +529,21 @@ pub(crate) struct TranscriptOverlay {
     highlight_cell: Option<usize>,
     /// Cache key for the render-only live tail appended after committed cells.
     live_tail_key: Option<LiveTailKey>,
+    /// `/` search state; kept after the query is submitted so `n`/`N` can step through matches.
+    search: Option<TranscriptSearch>,
     is_done: bool,
 }
 
+/// Which match `TranscriptOverlay::jump_to_match` selects.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum MatchStep {
+    /// For a newly submitted query: the last match above the bottom of the view, or the first
+    /// match below it when there is none.
+    First,
+    Next,
+    Previous,
+}
+
 /// Cache key for the active-cell "live tail" appended to the transcript overlay.
 ///
 /// Changing any field implies a different rendered tail.
@@ -457,13 +567,14 @@ impl TranscriptOverlay {
     pub(crate) fn new(transcript_cells: Vec<Arc<dyn HistoryCell>>) -> Self {
         Self {
             view: PagerView::new(
-                Self::render_cells(&transcript_cells, None),
+                Self::render_cells(&transcript_cells, None, None),
                 "T R A N S C R I P T".to_string(),
                 usize::MAX,
             ),
             cells: transcript_cells,
             highlight_cell: None,
             live_tail_key: None,
+            search: None,
             is_done: false,
         }
     }
@@ -471,7 +582,9 @@ impl TranscriptOverlay {
     fn render_cells(
         cells: &[Arc<dyn HistoryCell>],
         highlight_cell: Option<usize>,
+        query: Option<&str>,
     ) -> Vec<Box<dyn Renderable>> {
+        let query = query.map(str::to_ascii_lowercase);
         cells
             .iter()
             .enumerate()
@@ -485,11 +598,13 @@ impl TranscriptOverlay {
                         } else {
                             user_message_style()
                         },
+                        query: query.clone(),
                     })) as Box<dyn Renderable>
                 } else {
                     Box::new(CachedRenderable::new(CellRenderable {
                         cell: c.clone(),
                         style: Style::default(),
+                        query: query.clone(),
                     })) as Box<dyn Renderable>
                 };
                 if !c.is_stream_continuation() && i > 0 {
@@ -519,7 +634,8 @@ impl TranscriptOverlay {
         let had_prior_cells = !self.cells.is_empty();
         let tail_renderable = self.take_live_tail_renderable();
         self.cells.push(cell);
-        self.view.renderables = Self::render_cells(&self.cells, self.highlight_cell);
+        self.view.renderables =
+            Self::render_cells(&self.cells, self.highlight_cell, self.search_query());
         if let Some(tail) = tail_renderable {
             let tail = if !had_prior_cells
                 && self
@@ -606,7 +722,8 @@ impl TranscriptOverlay {
 
     fn rebuild_renderables(&mut self) {
         let tail_renderable = self.take_live_tail_renderable();
-        self.view.renderables = Self::render_cells(&self.cells, self.highlight_cell);
+        self.view.renderables =
+            Self::render_cells(&self.cells, self.highlight_cell, self.search_query());
         if let Some(tail) = tail_renderable {
             self.view.renderables.push(tail);
         }
@@ -634,10 +751,152 @@ impl TranscriptOverlay {
         renderable
     }
 
+    /// Returns the search query to highlight, if one has been typed.
+    fn search_query(&self) -> Option<&str> {
+        self.search
+            .as_ref()
+            .map(|search| search.query.as_str())
+            .filter(|query| !query.is_empty())
+    }
+
+    /// Returns whether the `/` prompt is taking keystrokes, in which case `App` must forward Esc,
+    /// Enter, and arrows here instead of treating them as backtrack keys.
+    pub(crate) fn is_editing_search(&self) -> bool {
+        self.search.as_ref().is_some_and(|search| search.editing)
+    }
+
+    fn start_search(&mut self) {
+        self.search = Some(TranscriptSearch::new());
+        self.rebuild_renderables();
+    }
+
+    fn handle_search_key(&mut self, key_event: KeyEvent, width: u16) -> bool {
+        let Some(search) = self.search.as_mut().filter(|search| search.editing) else {
+            return false;
+        };
+        if key_event.kind == KeyEventKind::Release {
+            return true;
+        }
+        match key_event.code {
+            KeyCode::Esc => {
+                self.search = None;
+                self.rebuild_renderables();
+            }
+            KeyCode::Enter if search.query.is_empty() => self.search = None,
+            KeyCode::Enter => {
+                search.editing = false;
+                self.jump_to_match(width, MatchStep::First);
+            }
+            KeyCode::Backspace => {
+                search.query.pop();
+                self.rebuild_renderables();
+            }
+            KeyCode::Char(ch) if !key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                search.query.push(ch);
+                self.rebuild_renderables();
+            }
+            _ => {}
+        }
+        true
+    }
+
+    /// Content row of the first line of each committed cell, below any spacing inset.
+    fn cell_rows(&self, width: u16) -> Vec<usize> {
+        let mut top = 0;
+        self.cells
+            .iter()
+            .zip(&self.view.renderables)
+            .map(|(cell, renderable)| {
+                let height = renderable.desired_height(width) as usize;
+                let row =
+                    top + height.saturating_sub(cell.desired_transcript_height(width) as usize);
+                top += height;
+                row
+            })
+            .collect()
+    }
+
+    /// Scrolls the match selected by `step` to the top of the view, wrapping around at either
+    /// end of the transcript.
+    fn jump_to_match(&mut self, width: u16, step: MatchStep) {
+        let Some(query) = self.search_query().map(str::to_ascii_lowercase) else {
+            return;
+        };
+        let matches = transcript_search::find_matches(&self.cells, width, &query);
+        let rows = self.cell_rows(width);
+        let row_of = |found: &SearchMatch| rows.get(found.cell).map_or(0, |row| row + found.line);
+        let previous = self
+            .search
+            .as_ref()
+            .and_then(TranscriptSearch::current_match);
+        let current = (!matches.is_empty()).then(|| match (step, previous) {
+            (MatchStep::Next, Some(previous)) => matches
+                .iter()
+                .position(|found| *found > previous)
+                .unwrap_or(0),
+            (MatchStep::Previous, Some(previous)) => matches
+                .iter()
+                .rposition(|found| *found < previous)
+                .unwrap_or(matches.len() - 1),
+            // Start from what is on screen and work back through the scrollback.
+            _ => {
+                let bottom =
+                    self.view.top_row(width) + self.view.last_content_height.unwrap_or_default();
+                matches
+                    .iter()
+                    .rposition(|found| row_of(found) < bottom)
+                    .unwrap_or(0)
+            }
+        });
+        if let Some(found) = current.and_then(|idx| matches.get(idx)) {
+            self.view.scroll_offset = row_of(found);
+        }
+        if let Some(search) = self.search.as_mut() {
+            search.matches = matches;
+            search.current = current;
+        }
+    }
+
+    /// Scrolls the next (or previous) turn boundary, tool call, or diff to the top of the view.
+    fn jump_to_point(&mut self, width: u16, kind: TimelinePointKind, forward: bool) {
+        let rows = self.cell_rows(width);
+        let top = self.view.top_row(width);
+        let mut targets = timeline::timeline_points(&self.cells)
+            .into_iter()
+            .filter(|point| point.kind == kind)
+            .filter_map(|point| rows.get(point.cell_index).copied());
+        let target = if forward {
+            targets.find(|row| *row > top)
+        } else {
+            targets.rev().find(|row| *row < top)
+        };
+        if let Some(row) = target {
+            self.view.scroll_offset = row;
+        }
+    }
+
     fn render_hints(&self, area: Rect, buf: &mut Buffer) {
         let line1 = Rect::new(area.x, area.y, area.width, 1);
         let line2 = Rect::new(area.x, area.y.saturating_add(1), area.width, 1);
-        render_key_hints(line1, buf, PAGER_KEY_HINTS);
+        let line3 = Rect::new(area.x, area.y.saturating_add(2), area.width, 1);
+        if let Some(search) = self.search.as_ref().filter(|search| search.editing) {
+            Line::from(vec![" /".into(), search.query.clone().into(), "▏".dim()])
+                .render_ref(line1, buf);
+            let pairs: Vec<(&[KeyBinding], &str)> =
+                vec![(&[KEY_ENTER], "to search"), (&[KEY_ESC], "to cancel")];
+            render_key_hints(line2, buf, &pairs);
+            return;
+        }
+        // While a search is active its match navigation takes the place of the scroll hints, so
+        // every row still fits a narrow terminal.
+        let match_hint = self.search.as_ref().map(|search| match search.current {
+            Some(idx) => format!("for matches ({}/{})", idx + 1, search.matches.len()),
+            None => "for matches (none)".to_string(),
+        });
+        match &match_hint {
+            Some(hint) => render_key_hints(line1, buf, &[(&[KEY_N, KEY_SHIFT_N], hint.as_str())]),
+            None => render_key_hints(line1, buf, PAGER_KEY_HINTS),
+        }
 
         let mut pairs: Vec<(&[KeyBinding], &str)> = vec![(&[KEY_Q], "to quit")];
         if self.highlight_cell.is_some() {
@@ -647,7 +906,16 @@ impl TranscriptOverlay {
         } else {
             pairs.push((&[KEY_ESC], "to edit prev"));
         }
+        pairs.push((&[KEY_SLASH], "search"));
         render_key_hints(line2, buf, &pairs);
+
+        // Shift steps backwards through tool calls and diffs; the hint leaves it out for width.
+        let pairs: Vec<(&[KeyBinding], &str)> = vec![
+            (&[KEY_PREV_TURN, KEY_NEXT_TURN], "turns"),
+            (&[KEY_TOOL], "tool calls"),
+            (&[KEY_DIFF], "diffs"),
+        ];
+        render_key_hints(line3, buf, &pairs);
     }
 
     pub(crate) fn render(&mut self, area: Rect, buf: &mut Buffer) {
@@ -662,20 +930,61 @@ impl TranscriptOverlay {
 impl TranscriptOverlay {
     pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
         match event {
-            TuiEvent::Key(key_event) => match key_event {
-                e if KEY_Q.is_press(e) || KEY_CTRL_C.is_press(e) || KEY_CTRL_T.is_press(e) => {
-                    self.is_done = true;
-                    Ok(())
+            TuiEvent::Key(key_event) => {
+                let width = self.view.content_width(tui.terminal.viewport_area);
+                if self.handle_search_key(key_event, width) {
+                    tui.frame_requester().schedule_frame();
+                    return Ok(());
                 }
-                other => self.view.handle_key_event(tui, other),
-            },
+                match key_event {
+                    e if KEY_Q.is_press(e) || KEY_CTRL_C.is_press(e) || KEY_CTRL_T.is_press(e) => {
+                        self.is_done = true;
+                        return Ok(());
+                    }
+                    e if KEY_SLASH.is_press(e) => self.start_search(),
+                    e if KEY_N.is_press(e) => self.jump_to_match(width, MatchStep::Next),
+                    e if is_shifted_press(KEY_SHIFT_N, 'N', e) => {
+                        self.jump_to_match(width, MatchStep::Previous);
+                    }
+                    e if KEY_PREV_TURN.is_press(e) => {
+                        self.jump_to_point(width, TimelinePointKind::UserTurn, false);
+                    }
+                    e if KEY_NEXT_TURN.is_press(e) => {
+                        self.jump_to_point(width, TimelinePointKind::UserTurn, true);
+                    }
+                    e if KEY_TOOL.is_press(e) => {
+                        self.jump_to_point(width, TimelinePointKind::ToolCall, true);
+                    }
+                    e if is_shifted_press(KEY_SHIFT_TOOL, 'T', e) => {
+                        self.jump_to_point(width, TimelinePointKind::ToolCall, false);
+                    }
+                    e if KEY_DIFF.is_press(e) => {
+                        self.jump_to_point(width, TimelinePointKind::Patch, true);
+                    }
+                    e if is_shifted_press(KEY_SHIFT_DIFF, 'D', e) => {
+                        self.jump_to_point(width, TimelinePointKind::Patch, false);
+                    }
+                    other => return self.view.handle_key_event(tui, other),
+                }
+                tui.frame_requester().schedule_frame();
+                Ok(())
+            }
             TuiEvent::Draw => {
                 tui.draw(u16::MAX, |frame| {
                     self.render(frame.area(), frame.buffer);
                 })?;
                 Ok(())
             }
-            _ => Ok(()),
+            TuiEvent::Paste(text) => {
+                if let Some(search) = self.search.as_mut().filter(|search| search.editing) {
+                    search
+                        .query
+                        .push_str(text.lines().next().unwrap_or_default());
+                    self.rebuild_renderables();
+                    tui.frame_requester().schedule_frame();
+                }
+                Ok(())
+            }
         }
     }
     pub(crate) fn is_done(&self) -> bool {
@@ -1051,6 +1360,52 @@ mod tests {
         assert_eq!(overlay.view.scroll_offset, 0);
     }
 
+    #[test]
+    fn transcript_overlay_search_and_jumps_scroll_to_targets() {
+        let user = |message: &str| {
+            Arc::new(UserHistoryCell {
+                message: message.to_string(),
+                text_elements: Vec::new(),
+                local_image_paths: Vec::new(),
+            }) as Arc<dyn HistoryCell>
+        };
+        let text = |line: String| {
+            Arc::new(TestCell {
+                lines: vec![Line::from(line)],
+            }) as Arc<dyn HistoryCell>
+        };
+        let mut cells = vec![user("first"), text("needle one".to_string())];
+        cells.extend((0..10).map(|i| text(format!("filler{i}"))));
+        cells.push(user("second"));
+        cells.push(text("a Needle two".to_string()));
+        let mut overlay = TranscriptOverlay::new(cells);
+        let mut term = Terminal::new(TestBackend::new(40, 12)).expect("term");
+        term.draw(|f| overlay.render(f.area(), f.buffer_mut()))
+            .expect("draw");
+        let rows = overlay.cell_rows(40);
+
+        overlay.start_search();
+        assert!(overlay.is_editing_search());
+        for ch in "NEEDLE".chars() {
+            overlay.handle_search_key(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE), 40);
+        }
+        overlay.handle_search_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE), 40);
+        assert!(!overlay.is_editing_search());
+        // Opened at the bottom, so the search starts from the latest match.
+        assert_eq!(overlay.view.scroll_offset, rows[13]);
+
+        overlay.jump_to_match(40, MatchStep::Next);
+        assert_eq!(overlay.view.scroll_offset, rows[1]);
+        overlay.jump_to_match(40, MatchStep::Previous);
+        assert_eq!(overlay.view.scroll_offset, rows[13]);
+
+        overlay.view.scroll_offset = 0;
+        overlay.jump_to_point(40, TimelinePointKind::UserTurn, true);
+        assert_eq!(overlay.view.scroll_offset, rows[12]);
+        overlay.jump_to_point(40, TimelinePointKind::UserTurn, false);
+        assert_eq!(overlay.view.scroll_offset, rows[0]);
+    }
+
     #[test]
     fn static_overlay_snapshot_basic() {
         // Prepare a static overlay with a few lines and a title
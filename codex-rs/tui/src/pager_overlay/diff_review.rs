@@ -0,0 +1,1416 @@
+//! Full-screen diff review used by `/diff` and by `Ctrl+A` on a patch approval.
+//!
+//! Each file's hunks are shown either side by side (old on the left, new on the right) or as a
+//! unified diff; `t` switches between the two. Removed and added lines that pair up get their
+//! changed words emphasised, and code is lightly highlighted by a per-line lexer chosen from the
+//! file extension. `[`/`]` jump between files and `/` searches the diff text. Long lines are not
+//! wrapped so both columns stay aligned; Left/Right scroll horizontally instead.
+
+use std::collections::HashMap;
+use std::io::Result;
+use std::ops::Range;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use super::KEY_CTRL_B;
+use super::KEY_CTRL_C;
+use super::KEY_CTRL_D;
+use super::KEY_CTRL_F;
+use super::KEY_CTRL_U;
+use super::KEY_DOWN;
+use super::KEY_END;
+use super::KEY_ENTER;
+use super::KEY_ESC;
+use super::KEY_HOME;
+use super::KEY_J;
+use super::KEY_K;
+use super::KEY_LEFT;
+use super::KEY_PAGE_DOWN;
+use super::KEY_PAGE_UP;
+use super::KEY_Q;
+use super::KEY_RIGHT;
+use super::KEY_SHIFT_SPACE;
+use super::KEY_SPACE;
+use super::KEY_UP;
+use super::PAGER_KEY_HINTS;
+use super::render_key_hints;
+use crate::diff_render::display_path_for;
+use crate::key_hint;
+use crate::key_hint::KeyBinding;
+use crate::tui;
+use crate::tui::TuiEvent;
+use codex_core::protocol::FileChange;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Modifier;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::widgets::Clear;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Widget;
+use ratatui::widgets::WidgetRef;
+use unicode_width::UnicodeWidthChar;
+
+const KEY_T: KeyBinding = key_hint::plain(KeyCode::Char('t'));
+const KEY_PREV_FILE: KeyBinding = key_hint::plain(KeyCode::Char('['));
+const KEY_NEXT_FILE: KeyBinding = key_hint::plain(KeyCode::Char(']'));
+const KEY_SLASH: KeyBinding = key_hint::plain(KeyCode::Char('/'));
+const KEY_N: KeyBinding = key_hint::plain(KeyCode::Char('n'));
+const KEY_SHIFT_N: KeyBinding = key_hint::shift(KeyCode::Char('n'));
+
+/// Columns moved by one Left/Right press.
+const HORIZONTAL_STEP: usize = 8;
+/// Columns a tab expands to.
+const TAB_WIDTH: usize = 4;
+/// Word diffs are skipped for line pairs with more tokens than this combined, to bound the
+/// quadratic matching.
+const MAX_WORD_DIFF_CELLS: usize = 40_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DiffLayout {
+    SideBySide,
+    Unified,
+}
+
+impl DiffLayout {
+    fn label(self) -> &'static str {
+        match self {
+            DiffLayout::SideBySide => "side-by-side",
+            DiffLayout::Unified => "unified",
+        }
+    }
+}
+
+/// One removed or added line, with the byte ranges that differ from its counterpart.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DiffSide {
+    line_number: usize,
+    text: String,
+    emphasis: Vec<Range<usize>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DiffBlock {
+    Context {
+        old: usize,
+        new: usize,
+        text: String,
+    },
+    /// A run of removed lines followed by the lines that replace them.
+    Change {
+        removed: Vec<DiffSide>,
+        added: Vec<DiffSide>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DiffHunk {
+    header: String,
+    blocks: Vec<DiffBlock>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DiffFile {
+    path: String,
+    language: Language,
+    added: usize,
+    removed: usize,
+    hunks: Vec<DiffHunk>,
+    /// Shown instead of hunks, e.g. for binary files.
+    note: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum RowKind {
+    Blank,
+    File,
+    Note(String),
+    Hunk(String),
+    Context {
+        old: usize,
+        new: usize,
+        text: String,
+    },
+    Removed(DiffSide),
+    Added(DiffSide),
+    Pair {
+        removed: Option<DiffSide>,
+        added: Option<DiffSide>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ReviewRow {
+    file: usize,
+    kind: RowKind,
+}
+
+impl ReviewRow {
+    /// Text searched by `/`.
+    fn search_text<'a>(&'a self, files: &'a [DiffFile]) -> Vec<&'a str> {
+        match &self.kind {
+            RowKind::Blank => Vec::new(),
+            RowKind::File => vec![files[self.file].path.as_str()],
+            RowKind::Note(text) | RowKind::Hunk(text) | RowKind::Context { text, .. } => {
+                vec![text.as_str()]
+            }
+            RowKind::Removed(side) | RowKind::Added(side) => vec![side.text.as_str()],
+            RowKind::Pair { removed, added } => removed
+                .iter()
+                .chain(added.iter())
+                .map(|side| side.text.as_str())
+                .collect(),
+        }
+    }
+}
+
+struct SearchState {
+    query: String,
+    editing: bool,
+}
+
+pub(crate) struct DiffReviewOverlay {
+    title: String,
+    files: Vec<DiffFile>,
+    layout: DiffLayout,
+    rows: Vec<ReviewRow>,
+    /// Row index of each file's header.
+    file_starts: Vec<usize>,
+    number_width: usize,
+    scroll: usize,
+    horizontal_scroll: usize,
+    search: Option<SearchState>,
+    last_content_height: usize,
+    is_done: bool,
+}
+
+impl DiffReviewOverlay {
+    /// Review of a proposed patch, one entry per file.
+    pub(crate) fn from_changes(
+        changes: &HashMap<PathBuf, FileChange>,
+        cwd: &Path,
+        title: String,
+    ) -> Self {
+        let mut paths: Vec<&PathBuf> = changes.keys().collect();
+        paths.sort();
+        let files = paths
+            .into_iter()
+            .filter_map(|path| {
+                let change = changes.get(path)?;
+                let mut display = display_path_for(path, cwd);
+                let hunks = match change {
+                    FileChange::Add { content } => vec![whole_file_hunk(content, true)],
+                    FileChange::Delete { content } => vec![whole_file_hunk(content, false)],
+                    FileChange::Update {
+                        unified_diff,
+                        move_path,
+                    } => {
+                        if let Some(move_path) = move_path {
+                            display = format!("{display} → {}", display_path_for(move_path, cwd));
+                        }
+                        parse_hunks(unified_diff).unwrap_or_default()
+                    }
+                };
+                Some(DiffFile::new(display, language_for(path), hunks, None))
+            })
+            .collect();
+        Self::new(files, title)
+    }
+
+    /// Review of `git diff` output. `None` when `text` holds no file diffs, e.g. an error
+    /// message.
+    pub(crate) fn from_unified_diff(text: &str, title: String) -> Option<Self> {
+        let files = parse_git_diff(text);
+        (!files.is_empty()).then(|| Self::new(files, title))
+    }
+
+    fn new(files: Vec<DiffFile>, title: String) -> Self {
+        let number_width = files
+            .iter()
+            .flat_map(|file| &file.hunks)
+            .flat_map(|hunk| &hunk.blocks)
+            .map(|block| match block {
+                DiffBlock::Context { old, new, .. } => (*old).max(*new),
+                DiffBlock::Change { removed, added } => removed
+                    .iter()
+                    .chain(added)
+                    .map(|side| side.line_number)
+                    .max()
+                    .unwrap_or_default(),
+            })
+            .max()
+            .unwrap_or_default()
+            .to_string()
+            .len();
+        let mut overlay = Self {
+            title,
+            files,
+            layout: DiffLayout::SideBySide,
+            rows: Vec::new(),
+            file_starts: Vec::new(),
+            number_width,
+            scroll: 0,
+            horizontal_scroll: 0,
+            search: None,
+            last_content_height: 0,
+            is_done: false,
+        };
+        overlay.rebuild_rows();
+        overlay
+    }
+
+    fn rebuild_rows(&mut self) {
+        let (rows, file_starts) = build_rows(&self.files, self.layout);
+        self.rows = rows;
+        self.file_starts = file_starts;
+    }
+
+    fn toggle_layout(&mut self) {
+        let file = self.current_file();
+        self.layout = match self.layout {
+            DiffLayout::SideBySide => DiffLayout::Unified,
+            DiffLayout::Unified => DiffLayout::SideBySide,
+        };
+        self.rebuild_rows();
+        // Stay on the file that was at the top; exact row positions differ between layouts.
+        if let Some(start) = self.file_starts.get(file) {
+            self.scroll = *start;
+        }
+    }
+
+    fn current_file(&self) -> usize {
+        self.rows
+            .get(self.scroll)
+            .map(|row| row.file)
+            .unwrap_or_default()
+    }
+
+    fn next_file(&mut self) {
+        if let Some(start) = self.file_starts.iter().find(|start| **start > self.scroll) {
+            self.scroll = *start;
+        }
+    }
+
+    fn previous_file(&mut self) {
+        if let Some(start) = self
+            .file_starts
+            .iter()
+            .rev()
+            .find(|start| **start < self.scroll)
+        {
+            self.scroll = *start;
+        }
+    }
+
+    fn query(&self) -> Option<&str> {
+        self.search
+            .as_ref()
+            .map(|search| search.query.as_str())
+            .filter(|query| !query.is_empty())
+    }
+
+    fn row_matches(&self, row: &ReviewRow, query: &str) -> bool {
+        row.search_text(&self.files)
+            .iter()
+            .any(|text| text.to_ascii_lowercase().contains(query))
+    }
+
+    /// Scroll to the next (or previous) row containing the search query. `include_current`
+    /// also accepts the row at the top of the view.
+    fn jump_to_match(&mut self, forward: bool, include_current: bool) {
+        let Some(query) = self.query().map(str::to_ascii_lowercase) else {
+            return;
+        };
+        let found = if forward {
+            let from = if include_current {
+                self.scroll
+            } else {
+                self.scroll.saturating_add(1)
+            };
+            (from..self.rows.len()).find(|idx| self.row_matches(&self.rows[*idx], &query))
+        } else {
+            (0..self.scroll.min(self.rows.len()))
+                .rev()
+                .find(|idx| self.row_matches(&self.rows[*idx], &query))
+        };
+        if let Some(idx) = found {
+            self.scroll = idx;
+        }
+    }
+
+    fn max_scroll(&self) -> usize {
+        self.rows.len().saturating_sub(self.last_content_height)
+    }
+
+    fn handle_search_key(&mut self, key_event: KeyEvent) -> bool {
+        let Some(search) = self.search.as_mut().filter(|search| search.editing) else {
+            return false;
+        };
+        if key_event.kind == KeyEventKind::Release {
+            return true;
+        }
+        match key_event.code {
+            KeyCode::Esc => self.search = None,
+            KeyCode::Enter => {
+                search.editing = false;
+                self.jump_to_match(true, true);
+            }
+            KeyCode::Backspace => {
+                search.query.pop();
+            }
+            KeyCode::Char(ch) if !key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                search.query.push(ch);
+            }
+            _ => {}
+        }
+        true
+    }
+
+    fn handle_key_event(&mut self, tui: &mut tui::Tui, key_event: KeyEvent) {
+        if self.handle_search_key(key_event) {
+            tui.frame_requester().schedule_frame();
+            return;
+        }
+        let page = self.last_content_height.max(1);
+        match key_event {
+            e if KEY_Q.is_press(e) || KEY_CTRL_C.is_press(e) => self.is_done = true,
+            e if KEY_ESC.is_press(e) => {
+                if self.search.take().is_none() {
+                    self.is_done = true;
+                }
+            }
+            e if KEY_T.is_press(e) => self.toggle_layout(),
+            e if KEY_NEXT_FILE.is_press(e) => self.next_file(),
+            e if KEY_PREV_FILE.is_press(e) => self.previous_file(),
+            e if KEY_SLASH.is_press(e) => {
+                self.search = Some(SearchState {
+                    query: String::new(),
+                    editing: true,
+                });
+            }
+            e if KEY_N.is_press(e) || KEY_ENTER.is_press(e) => self.jump_to_match(true, false),
+            e if KEY_SHIFT_N.is_press(e)
+                || (e.code == KeyCode::Char('N') && e.kind != KeyEventKind::Release) =>
+            {
+                self.jump_to_match(false, false);
+            }
+            e if KEY_LEFT.is_press(e) => {
+                self.horizontal_scroll = self.horizontal_scroll.saturating_sub(HORIZONTAL_STEP);
+            }
+            e if KEY_RIGHT.is_press(e) => {
+                self.horizontal_scroll = self.horizontal_scroll.saturating_add(HORIZONTAL_STEP);
+            }
+            e if KEY_UP.is_press(e) || KEY_K.is_press(e) => {
+                self.scroll = self.scroll.saturating_sub(1);
+            }
+            e if KEY_DOWN.is_press(e) || KEY_J.is_press(e) => {
+                self.scroll = self.scroll.saturating_add(1);
+            }
+            e if KEY_PAGE_UP.is_press(e)
+                || KEY_SHIFT_SPACE.is_press(e)
+                || KEY_CTRL_B.is_press(e) =>
+            {
+                self.scroll = self.scroll.saturating_sub(page);
+            }
+            e if KEY_PAGE_DOWN.is_press(e) || KEY_SPACE.is_press(e) || KEY_CTRL_F.is_press(e) => {
+                self.scroll = self.scroll.saturating_add(page);
+            }
+            e if KEY_CTRL_D.is_press(e) => {
+                self.scroll = self.scroll.saturating_add(page.div_ceil(2));
+            }
+            e if KEY_CTRL_U.is_press(e) => {
+                self.scroll = self.scroll.saturating_sub(page.div_ceil(2));
+            }
+            e if KEY_HOME.is_press(e) => self.scroll = 0,
+            e if KEY_END.is_press(e) => self.scroll = usize::MAX,
+            _ => return,
+        }
+        tui.frame_requester()
+            .schedule_frame_in(Duration::from_millis(16));
+    }
+
+    pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
+        match event {
+            TuiEvent::Key(key_event) => {
+                self.handle_key_event(tui, key_event);
+                Ok(())
+            }
+            TuiEvent::Draw => {
+                tui.draw(u16::MAX, |frame| {
+                    self.render(frame.area(), frame.buffer);
+                })?;
+                Ok(())
+            }
+            TuiEvent::Paste(text) => {
+                if let Some(search) = self.search.as_mut().filter(|search| search.editing) {
+                    search
+                        .query
+                        .push_str(text.lines().next().unwrap_or_default());
+                    tui.frame_requester().schedule_frame();
+                }
+                Ok(())
+            }
+        }
+    }
+
+    pub(crate) fn is_done(&self) -> bool {
+        self.is_done
+    }
+
+    pub(crate) fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+        if area.height < 5 || area.width == 0 {
+            return;
+        }
+        let header = Rect::new(area.x, area.y, area.width, 1);
+        let content = Rect::new(area.x, area.y + 1, area.width, area.height - 4);
+        let status = Rect::new(area.x, content.bottom(), area.width, 1);
+        let hints = Rect::new(area.x, status.y + 1, area.width, 2);
+
+        Span::from("/ ".repeat(area.width as usize / 2))
+            .dim()
+            .render_ref(header, buf);
+        format!("/ {}", self.title).dim().render_ref(header, buf);
+
+        self.last_content_height = content.height as usize;
+        self.scroll = self.scroll.min(self.max_scroll());
+        let query = self.query().map(str::to_ascii_lowercase);
+        let lines: Vec<Line<'static>> = self
+            .rows
+            .iter()
+            .skip(self.scroll)
+            .take(content.height as usize)
+            .map(|row| self.render_row(row, content.width as usize, query.as_deref()))
+            .collect();
+        Paragraph::new(lines).render(content, buf);
+
+        self.render_status(status, buf);
+        self.render_hints(hints, buf);
+    }
+
+    fn render_status(&self, area: Rect, buf: &mut Buffer) {
+        Span::from("─".repeat(area.width as usize))
+            .dim()
+            .render_ref(area, buf);
+        let max_scroll = self.max_scroll();
+        let percent = if max_scroll == 0 {
+            100
+        } else {
+            self.scroll.min(max_scroll) * 100 / max_scroll
+        };
+        let text = format!(
+            " file {}/{} · {} · {percent}% ",
+            self.current_file() + 1,
+            self.files.len(),
+            self.layout.label()
+        );
+        let width = text.chars().count() as u16;
+        if width < area.width {
+            Span::from(text)
+                .dim()
+                .render_ref(Rect::new(area.right() - width - 1, area.y, width, 1), buf);
+        }
+    }
+
+    fn render_hints(&self, area: Rect, buf: &mut Buffer) {
+        let line1 = Rect::new(area.x, area.y, area.width, 1);
+        let line2 = Rect::new(area.x, area.y.saturating_add(1), area.width, 1);
+        if let Some(search) = self.search.as_ref().filter(|search| search.editing) {
+            Line::from(vec![" /".into(), search.query.clone().into(), "▏".dim()])
+                .render_ref(line1, buf);
+            let pairs: Vec<(&[KeyBinding], &str)> =
+                vec![(&[KEY_ENTER], "to search"), (&[KEY_ESC], "to cancel")];
+            render_key_hints(line2, buf, &pairs);
+            return;
+        }
+        render_key_hints(line1, buf, PAGER_KEY_HINTS);
+        let pairs: Vec<(&[KeyBinding], &str)> = vec![
+            (&[KEY_T], "to toggle layout"),
+            (&[KEY_PREV_FILE, KEY_NEXT_FILE], "to switch files"),
+            (&[KEY_SLASH], "to search"),
+            (&[KEY_N, KEY_SHIFT_N], "for matches"),
+            (&[KEY_Q], "to quit"),
+        ];
+        render_key_hints(line2, buf, &pairs);
+    }
+
+    fn render_row(&self, row: &ReviewRow, width: usize, query: Option<&str>) -> Line<'static> {
+        let file = &self.files[row.file];
+        let content = ContentStyle {
+            language: file.language,
+            query,
+            horizontal_scroll: self.horizontal_scroll,
+        };
+        let number_width = self.number_width;
+        match &row.kind {
+            RowKind::Blank => Line::from(""),
+            RowKind::File => {
+                let mut path = Span::from(file.path.clone()).bold();
+                if query.is_some_and(|query| file.path.to_ascii_lowercase().contains(query)) {
+                    path = path.reversed();
+                }
+                Line::from(vec![
+                    path,
+                    " (".into(),
+                    format!("+{}", file.added).green(),
+                    " ".into(),
+                    format!("-{}", file.removed).red(),
+                    ")".into(),
+                ])
+            }
+            RowKind::Note(note) => Line::from(vec!["  ".into(), note.clone().dim().italic()]),
+            RowKind::Hunk(header) => Line::from(header.clone().dim()),
+            RowKind::Context { old, new, text } => match self.layout {
+                DiffLayout::Unified => {
+                    let mut spans = vec![gutter(Some(*new), number_width), "  ".into()];
+                    spans.extend(content.spans(
+                        text,
+                        Style::default(),
+                        &[],
+                        width.saturating_sub(number_width + 2),
+                        false,
+                    ));
+                    Line::from(spans)
+                }
+                DiffLayout::SideBySide => {
+                    let old_side = DiffSide {
+                        line_number: *old,
+                        text: text.clone(),
+                        emphasis: Vec::new(),
+                    };
+                    let new_side = DiffSide {
+                        line_number: *new,
+                        ..old_side.clone()
+                    };
+                    side_by_side(
+                        &content,
+                        width,
+                        number_width,
+                        (Some(&old_side), ' ', Style::default()),
+                        (Some(&new_side), ' ', Style::default()),
+                    )
+                }
+            },
+            RowKind::Removed(side) => unified_change(&content, side, '-', width, number_width),
+            RowKind::Added(side) => unified_change(&content, side, '+', width, number_width),
+            RowKind::Pair { removed, added } => side_by_side(
+                &content,
+                width,
+                number_width,
+                (removed.as_ref(), '-', style_removed()),
+                (added.as_ref(), '+', style_added()),
+            ),
+        }
+    }
+}
+
+impl DiffFile {
+    fn new(path: String, language: Language, hunks: Vec<DiffHunk>, note: Option<String>) -> Self {
+        let (added, removed) =
+            hunks
+                .iter()
+                .flat_map(|hunk| &hunk.blocks)
+                .fold((0, 0), |(added, removed), block| match block {
+                    DiffBlock::Context { .. } => (added, removed),
+                    DiffBlock::Change {
+                        removed: old,
+                        added: new,
+                    } => (added + new.len(), removed + old.len()),
+                });
+        Self {
+            path,
+            language,
+            added,
+            removed,
+            hunks,
+            note,
+        }
+    }
+}
+
+fn build_rows(files: &[DiffFile], layout: DiffLayout) -> (Vec<ReviewRow>, Vec<usize>) {
+    let mut rows = Vec::new();
+    let mut file_starts = Vec::new();
+    for (file_idx, file) in files.iter().enumerate() {
+        let row = |kind| ReviewRow {
+            file: file_idx,
+            kind,
+        };
+        if file_idx > 0 {
+            rows.push(row(RowKind::Blank));
+        }
+        file_starts.push(rows.len());
+        rows.push(row(RowKind::File));
+        if let Some(note) = &file.note {
+            rows.push(row(RowKind::Note(note.clone())));
+        }
+        for hunk in &file.hunks {
+            rows.push(row(RowKind::Hunk(hunk.header.clone())));
+            for block in &hunk.blocks {
+                match block {
+                    DiffBlock::Context { old, new, text } => rows.push(row(RowKind::Context {
+                        old: *old,
+                        new: *new,
+                        text: text.clone(),
+                    })),
+                    DiffBlock::Change { removed, added } => match layout {
+                        DiffLayout::Unified => {
+                            rows.extend(removed.iter().cloned().map(RowKind::Removed).map(row));
+                            rows.extend(added.iter().cloned().map(RowKind::Added).map(row));
+                        }
+                        DiffLayout::SideBySide => {
+                            for idx in 0..removed.len().max(added.len()) {
+                                rows.push(row(RowKind::Pair {
+                                    removed: removed.get(idx).cloned(),
+                                    added: added.get(idx).cloned(),
+                                }));
+                            }
+                        }
+                    },
+                }
+            }
+        }
+    }
+    (rows, file_starts)
+}
+
+/// A hunk covering a whole added (`added = true`) or deleted file.
+fn whole_file_hunk(content: &str, added: bool) -> DiffHunk {
+    let sides: Vec<DiffSide> = content
+        .lines()
+        .enumerate()
+        .map(|(idx, text)| DiffSide {
+            line_number: idx + 1,
+            text: text.to_string(),
+            emphasis: Vec::new(),
+        })
+        .collect();
+    let count = sides.len();
+    let (header, block) = if added {
+        (
+            format!("@@ -0,0 +1,{count} @@"),
+            DiffBlock::Change {
+                removed: Vec::new(),
+                added: sides,
+            },
+        )
+    } else {
+        (
+            format!("@@ -1,{count} +0,0 @@"),
+            DiffBlock::Change {
+                removed: sides,
+                added: Vec::new(),
+            },
+        )
+    };
+    DiffHunk {
+        header,
+        blocks: vec![block],
+    }
+}
+
+/// Hunks of a single-file unified diff.
+fn parse_hunks(unified_diff: &str) -> Option<Vec<DiffHunk>> {
+    let patch = diffy::Patch::from_str(unified_diff).ok()?;
+    Some(
+        patch
+            .hunks()
+            .iter()
+            .map(|hunk| {
+                let old_range = hunk.old_range();
+                let new_range = hunk.new_range();
+                let mut old_line = old_range.start();
+                let mut new_line = new_range.start();
+                let mut blocks = Vec::new();
+                let mut removed: Vec<DiffSide> = Vec::new();
+                let mut added: Vec<DiffSide> = Vec::new();
+                for line in hunk.lines() {
+                    match line {
+                        diffy::Line::Context(text) => {
+                            flush_change(&mut blocks, &mut removed, &mut added);
+                            blocks.push(DiffBlock::Context {
+                                old: old_line,
+                                new: new_line,
+                                text: text.trim_end_matches('\n').to_string(),
+                            });
+                            old_line += 1;
+                            new_line += 1;
+                        }
+                        diffy::Line::Delete(text) => {
+                            if !added.is_empty() {
+                                flush_change(&mut blocks, &mut removed, &mut added);
+                            }
+                            removed.push(DiffSide {
+                                line_number: old_line,
+                                text: text.trim_end_matches('\n').to_string(),
+                                emphasis: Vec::new(),
+                            });
+                            old_line += 1;
+                        }
+                        diffy::Line::Insert(text) => {
+                            added.push(DiffSide {
+                                line_number: new_line,
+                                text: text.trim_end_matches('\n').to_string(),
+                                emphasis: Vec::new(),
+                            });
+                            new_line += 1;
+                        }
+                    }
+                }
+                flush_change(&mut blocks, &mut removed, &mut added);
+                DiffHunk {
+                    header: format!(
+                        "@@ -{},{} +{},{} @@",
+                        old_range.start(),
+                        old_range.len(),
+                        new_range.start(),
+                        new_range.len()
+                    ),
+                    blocks,
+                }
+            })
+            .collect(),
+    )
+}
+
+fn flush_change(
+    blocks: &mut Vec<DiffBlock>,
+    removed: &mut Vec<DiffSide>,
+    added: &mut Vec<DiffSide>,
+) {
+    if removed.is_empty() && added.is_empty() {
+        return;
+    }
+    for (old, new) in removed.iter_mut().zip(added.iter_mut()) {
+        let (old_emphasis, new_emphasis) = word_diff(&old.text, &new.text);
+        old.emphasis = old_emphasis;
+        new.emphasis = new_emphasis;
+    }
+    blocks.push(DiffBlock::Change {
+        removed: std::mem::take(removed),
+        added: std::mem::take(added),
+    });
+}
+
+/// Split `git diff` output into files.
+fn parse_git_diff(text: &str) -> Vec<DiffFile> {
+    let mut sections: Vec<&str> = Vec::new();
+    let mut start = None;
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        if line.starts_with("diff --git ") {
+            if let Some(start) = start {
+                sections.push(&text[start..offset]);
+            }
+            start = Some(offset);
+        }
+        offset += line.len();
+    }
+    if let Some(start) = start {
+        sections.push(&text[start..]);
+    }
+    sections.into_iter().filter_map(parse_git_file).collect()
+}
+
+fn parse_git_file(section: &str) -> Option<DiffFile> {
+    let header = section.lines().next()?;
+    let mut path = header
+        .strip_prefix("diff --git ")?
+        .rsplit_once(" b/")
+        .map(|(_, new)| new.to_string())?;
+    let mut old_path = None;
+    for line in section.lines() {
+        if let Some(old) = line.strip_prefix("--- ") {
+            old_path = old.strip_prefix("a/").map(str::to_string);
+        } else if let Some(new) = line.strip_prefix("+++ ") {
+            match new.strip_prefix("b/") {
+                Some(new) => path = new.to_string(),
+                None => {
+                    if let Some(old) = old_path.take() {
+                        path = old;
+                    }
+                }
+            }
+            break;
+        }
+    }
+    let language = language_for(Path::new(&path));
+    let body = section
+        .find("\n--- ")
+        .map(|idx| &section[idx + 1..])
+        .and_then(parse_hunks);
+    Some(match body {
+        Some(hunks) if !hunks.is_empty() => DiffFile::new(path, language, hunks, None),
+        _ => {
+            let note = if section.contains("Binary files") {
+                "binary file changed"
+            } else {
+                "no textual changes"
+            };
+            DiffFile::new(path, language, Vec::new(), Some(note.to_string()))
+        }
+    })
+}
+
+/// Byte ranges of `old` and `new` that are not shared, by a longest common subsequence over
+/// word, whitespace, and punctuation tokens. Lines with nothing but whitespace in common get
+/// no emphasis since every word would light up.
+fn word_diff(old: &str, new: &str) -> (Vec<Range<usize>>, Vec<Range<usize>>) {
+    let old_tokens = tokenize(old);
+    let new_tokens = tokenize(new);
+    let (n, m) = (old_tokens.len(), new_tokens.len());
+    if n == 0 || m == 0 || n * m > MAX_WORD_DIFF_CELLS {
+        return (Vec::new(), Vec::new());
+    }
+    let token = |text: &str, range: &Range<usize>| text[range.clone()].to_string();
+    let old_words: Vec<String> = old_tokens.iter().map(|r| token(old, r)).collect();
+    let new_words: Vec<String> = new_tokens.iter().map(|r| token(new, r)).collect();
+
+    // lcs[i][j] = length of the LCS of old_words[i..] and new_words[j..].
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old_words[i] == new_words[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    let mut old_common = vec![false; n];
+    let mut new_common = vec![false; m];
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old_words[i] == new_words[j] {
+            old_common[i] = true;
+            new_common[j] = true;
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    let shares_words = old_words
+        .iter()
+        .zip(&old_common)
+        .any(|(word, common)| *common && !word.trim().is_empty());
+    if !shares_words {
+        return (Vec::new(), Vec::new());
+    }
+    (
+        changed_ranges(&old_tokens, &old_common),
+        changed_ranges(&new_tokens, &new_common),
+    )
+}
+
+fn tokenize(text: &str) -> Vec<Range<usize>> {
+    let mut tokens: Vec<Range<usize>> = Vec::new();
+    let class = |ch: char| {
+        if ch.is_alphanumeric() || ch == '_' {
+            0
+        } else if ch.is_whitespace() {
+            1
+        } else {
+            2
+        }
+    };
+    let mut previous = None;
+    for (idx, ch) in text.char_indices() {
+        let kind = class(ch);
+        let end = idx + ch.len_utf8();
+        match tokens.last_mut() {
+            Some(last) if previous == Some(kind) && kind != 2 => last.end = end,
+            _ => tokens.push(idx..end),
+        }
+        previous = Some(kind);
+    }
+    tokens
+}
+
+fn changed_ranges(tokens: &[Range<usize>], common: &[bool]) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    for (token, common) in tokens.iter().zip(common) {
+        if *common {
+            continue;
+        }
+        match ranges.last_mut() {
+            Some(last) if last.end == token.start => last.end = token.end,
+            _ => ranges.push(token.clone()),
+        }
+    }
+    ranges
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Language {
+    Rust,
+    /// C, C++, Java, JavaScript, TypeScript, Go, and similar brace languages.
+    CLike,
+    Python,
+    Shell,
+    /// TOML, YAML, and other `#`-commented configuration files.
+    Config,
+    Plain,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokenKind {
+    Keyword,
+    String,
+    Number,
+    Comment,
+}
+
+fn language_for(path: &Path) -> Language {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+    match extension.as_str() {
+        "rs" => Language::Rust,
+        "c" | "h" | "cc" | "cpp" | "cxx" | "hpp" | "hh" | "java" | "js" | "jsx" | "mjs" | "cjs"
+        | "ts" | "tsx" | "mts" | "cts" | "go" | "cs" | "kt" | "swift" | "scala" => Language::CLike,
+        "py" | "pyi" => Language::Python,
+        "sh" | "bash" | "zsh" => Language::Shell,
+        "toml" | "yaml" | "yml" | "ini" | "cfg" | "conf" => Language::Config,
+        _ => Language::Plain,
+    }
+}
+
+impl Language {
+    fn line_comment(self) -> Option<&'static str> {
+        match self {
+            Language::Rust | Language::CLike => Some("//"),
+            Language::Python | Language::Shell | Language::Config => Some("#"),
+            Language::Plain => None,
+        }
+    }
+
+    fn quotes(self) -> &'static [char] {
+        match self {
+            // Single quotes start lifetimes in Rust.
+            Language::Rust => &['"'],
+            Language::CLike | Language::Python | Language::Shell | Language::Config => {
+                &['"', '\'', '`']
+            }
+            Language::Plain => &[],
+        }
+    }
+
+    fn keywords(self) -> &'static [&'static str] {
+        match self {
+            Language::Rust => &[
+                "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else",
+                "enum", "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match",
+                "mod", "move", "mut", "pub", "ref", "return", "self", "Self", "static", "struct",
+                "super", "trait", "true", "type", "unsafe", "use", "where", "while",
+            ],
+            Language::CLike => &[
+                "break",
+                "case",
+                "catch",
+                "class",
+                "const",
+                "continue",
+                "default",
+                "do",
+                "else",
+                "enum",
+                "export",
+                "extends",
+                "false",
+                "final",
+                "for",
+                "func",
+                "function",
+                "go",
+                "if",
+                "import",
+                "interface",
+                "let",
+                "new",
+                "null",
+                "package",
+                "private",
+                "protected",
+                "public",
+                "return",
+                "static",
+                "struct",
+                "switch",
+                "this",
+                "throw",
+                "true",
+                "try",
+                "type",
+                "var",
+                "void",
+                "while",
+            ],
+            Language::Python => &[
+                "and", "as", "assert", "async", "await", "break", "class", "continue", "def",
+                "del", "elif", "else", "except", "False", "finally", "for", "from", "if", "import",
+                "in", "is", "lambda", "None", "not", "or", "pass", "raise", "return", "True",
+                "try", "while", "with", "yield",
+            ],
+            Language::Shell => &[
+                "case", "do", "done", "elif", "else", "esac", "export", "fi", "for", "function",
+                "if", "in", "local", "return", "then", "while",
+            ],
+            Language::Config => &["false", "true"],
+            Language::Plain => &[],
+        }
+    }
+
+    /// Token ranges of one line. Multi-line strings and block comments are not tracked.
+    fn tokens(self, line: &str) -> Vec<(Range<usize>, TokenKind)> {
+        if self == Language::Plain {
+            return Vec::new();
+        }
+        let comment = self.line_comment();
+        let quotes = self.quotes();
+        let keywords = self.keywords();
+        let mut tokens = Vec::new();
+        let mut chars = line.char_indices().peekable();
+        while let Some((start, ch)) = chars.next() {
+            if let Some(comment) = comment
+                && line[start..].starts_with(comment)
+            {
+                tokens.push((start..line.len(), TokenKind::Comment));
+                break;
+            }
+            if quotes.contains(&ch) {
+                let mut end = line.len();
+                let mut escaped = false;
+                for (idx, next) in chars.by_ref() {
+                    if escaped {
+                        escaped = false;
+                    } else if next == '\\' {
+                        escaped = true;
+                    } else if next == ch {
+                        end = idx + next.len_utf8();
+                        break;
+                    }
+                }
+                tokens.push((start..end, TokenKind::String));
+                continue;
+            }
+            if ch.is_alphanumeric() || ch == '_' {
+                let mut end = start + ch.len_utf8();
+                // Numbers keep their decimal point.
+                while let Some((idx, next)) = chars.next_if(|(_, next)| {
+                    next.is_alphanumeric() || *next == '_' || (*next == '.' && ch.is_ascii_digit())
+                }) {
+                    end = idx + next.len_utf8();
+                }
+                if ch.is_ascii_digit() {
+                    tokens.push((start..end, TokenKind::Number));
+                } else if keywords.contains(&&line[start..end]) {
+                    tokens.push((start..end, TokenKind::Keyword));
+                }
+            }
+        }
+        tokens
+    }
+}
+
+fn token_style(kind: TokenKind, colored: bool) -> Style {
+    match (kind, colored) {
+        (TokenKind::Keyword, _) => Style::default().add_modifier(Modifier::BOLD),
+        (TokenKind::Comment, _) => Style::default().add_modifier(Modifier::DIM | Modifier::ITALIC),
+        (TokenKind::String, true) => Style::default().cyan(),
+        (TokenKind::String, false) => Style::default().add_modifier(Modifier::ITALIC),
+        (TokenKind::Number, true) => Style::default().magenta(),
+        (TokenKind::Number, false) => Style::default(),
+    }
+}
+
+/// How code text is drawn in the current view.
+struct ContentStyle<'a> {
+    language: Language,
+    /// ASCII-lowercased search query whose matches are highlighted.
+    query: Option<&'a str>,
+    horizontal_scroll: usize,
+}
+
+impl ContentStyle<'_> {
+    /// Styled spans for `text` clipped to `columns` after horizontal scrolling. Context lines
+    /// (with a default `base`) get colored tokens; removed and added lines keep their color and
+    /// only take the token modifiers. With `pad`, the result is padded to exactly `columns`.
+    fn spans(
+        &self,
+        text: &str,
+        base: Style,
+        emphasis: &[Range<usize>],
+        columns: usize,
+        pad: bool,
+    ) -> Vec<Span<'static>> {
+        let colored = base == Style::default();
+        let tokens = self.language.tokens(text);
+        let hits: Vec<Range<usize>> = match self.query {
+            Some(query) => text
+                .to_ascii_lowercase()
+                .match_indices(query)
+                .map(|(idx, hit)| idx..idx + hit.len())
+                .collect(),
+            None => Vec::new(),
+        };
+
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        let mut run = String::new();
+        let mut run_style = base;
+        let mut column = 0;
+        let mut used = 0;
+        for (idx, ch) in text.char_indices() {
+            let mut style = base;
+            if let Some((_, kind)) = tokens.iter().find(|(range, _)| range.contains(&idx)) {
+                style = style.patch(token_style(*kind, colored));
+            }
+            if emphasis.iter().any(|range| range.contains(&idx)) {
+                style = style.add_modifier(Modifier::REVERSED);
+            }
+            if hits.iter().any(|range| range.contains(&idx)) {
+                style = style
+                    .cyan()
+                    .add_modifier(Modifier::REVERSED | Modifier::BOLD);
+            }
+            let (rendered, width) = if ch == '\t' {
+                (" ".repeat(TAB_WIDTH), TAB_WIDTH)
+            } else {
+                (ch.to_string(), ch.width().unwrap_or(0))
+            };
+            column += width;
+            if column <= self.horizontal_scroll {
+                continue;
+            }
+            if used + width > columns {
+                break;
+            }
+            used += width;
+            if style != run_style && !run.is_empty() {
+                spans.push(Span::styled(std::mem::take(&mut run), run_style));
+            }
+            run_style = style;
+            run.push_str(&rendered);
+        }
+        if !run.is_empty() {
+            spans.push(Span::styled(run, run_style));
+        }
+        if pad && used < columns {
+            spans.push(" ".repeat(columns - used).into());
+        }
+        spans
+    }
+}
+
+fn gutter(line_number: Option<usize>, number_width: usize) -> Span<'static> {
+    let text = match line_number {
+        Some(line_number) => format!("{line_number:>number_width$}"),
+        None => " ".repeat(number_width),
+    };
+    Span::from(text).dim()
+}
+
+fn unified_change(
+    content: &ContentStyle<'_>,
+    side: &DiffSide,
+    sign: char,
+    width: usize,
+    number_width: usize,
+) -> Line<'static> {
+    let style = if sign == '-' {
+        style_removed()
+    } else {
+        style_added()
+    };
+    let mut spans = vec![
+        gutter(Some(side.line_number), number_width),
+        " ".into(),
+        Span::styled(sign.to_string(), style),
+    ];
+    spans.extend(content.spans(
+        &side.text,
+        style,
+        &side.emphasis,
+        width.saturating_sub(number_width + 2),
+        false,
+    ));
+    Line::from(spans)
+}
+
+type Half<'a> = (Option<&'a DiffSide>, char, Style);
+
+fn side_by_side(
+    content: &ContentStyle<'_>,
+    width: usize,
+    number_width: usize,
+    left: Half<'_>,
+    right: Half<'_>,
+) -> Line<'static> {
+    let half = width.saturating_sub(1) / 2;
+    let mut spans = half_spans(content, half, number_width, left);
+    spans.push("│".dim());
+    spans.extend(half_spans(
+        content,
+        width.saturating_sub(half + 1),
+        number_width,
+        right,
+    ));
+    Line::from(spans)
+}
+
+fn half_spans(
+    content: &ContentStyle<'_>,
+    width: usize,
+    number_width: usize,
+    (side, sign, style): Half<'_>,
+) -> Vec<Span<'static>> {
+    let columns = width.saturating_sub(number_width + 2);
+    let Some(side) = side else {
+        return vec![" ".repeat(width).into()];
+    };
+    let mut spans = vec![
+        gutter(Some(side.line_number), number_width),
+        " ".into(),
+        Span::styled(sign.to_string(), style),
+    ];
+    spans.extend(content.spans(&side.text, style, &side.emphasis, columns, true));
+    spans
+}
+
+fn style_added() -> Style {
+    Style::default().green()
+}
+
+fn style_removed() -> Style {
+    Style::default().red()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn plain(line: &Line<'static>) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn word_diff_marks_only_changed_words() {
+        let (old, new) = word_diff("let total = count + 1;", "let total = count * 2;");
+        assert_eq!(old, vec![18..19, 20..21]);
+        assert_eq!(new, vec![18..19, 20..21]);
+
+        // Unrelated lines are not emphasised word by word.
+        assert_eq!(word_diff("alpha", "beta"), (Vec::new(), Vec::new()));
+    }
+
+    #[test]
+    fn parses_git_diff_into_files_and_pairs_changes() {
+        let diff = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,3 @@
+ fn main() {
+-    println!(\"hi\");
++    println!(\"hello\");
+ }
+diff --git a/logo.png b/logo.png
+Binary files a/logo.png and b/logo.png differ
+";
+        let overlay = DiffReviewOverlay::from_unified_diff(diff, "D I F F".to_string())
+            .expect("diff has files");
+        assert_eq!(
+            overlay
+                .files
+                .iter()
+                .map(|file| (
+                    file.path.as_str(),
+                    file.added,
+                    file.removed,
+                    file.note.clone()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                ("src/lib.rs", 1, 1, None),
+                ("logo.png", 0, 0, Some("binary file changed".to_string())),
+            ]
+        );
+        assert_eq!(overlay.file_starts, vec![0, 6]);
+
+        // Side by side, each half is clipped to its column and padded.
+        let rendered: Vec<String> = overlay
+            .rows
+            .iter()
+            .take(6)
+            .map(|row| {
+                plain(&overlay.render_row(row, 41, None))
+                    .trim_end()
+                    .to_string()
+            })
+            .collect();
+        assert_eq!(
+            rendered,
+            vec![
+                "src/lib.rs (+1 -1)",
+                "@@ -1,3 +1,3 @@",
+                "1  fn main() {      │1  fn main() {",
+                "2 -    println!(\"hi\"│2 +    println!(\"hel",
+                "3  }                │3  }",
+                "",
+            ]
+        );
+
+        assert!(DiffReviewOverlay::from_unified_diff("not a diff", String::new()).is_none());
+    }
+
+    #[test]
+    fn search_and_file_navigation_move_the_view() {
+        let diff = "\
+diff --git a/a.txt b/a.txt
+--- a/a.txt
++++ b/a.txt
+@@ -1 +1 @@
+-one
++uno
+diff --git a/b.txt b/b.txt
+--- a/b.txt
++++ b/b.txt
+@@ -1 +1 @@
+-two
++dos
+";
+        let mut overlay =
+            DiffReviewOverlay::from_unified_diff(diff, String::new()).expect("diff has files");
+        overlay.next_file();
+        assert_eq!(overlay.current_file(), 1);
+        overlay.previous_file();
+        assert_eq!(overlay.current_file(), 0);
+
+        overlay.search = Some(SearchState {
+            query: "DOS".to_string(),
+            editing: false,
+        });
+        overlay.jump_to_match(true, true);
+        assert_eq!(overlay.current_file(), 1);
+
+        overlay.toggle_layout();
+        assert_eq!(overlay.layout, DiffLayout::Unified);
+        assert_eq!(overlay.scroll, overlay.file_starts[1]);
+    }
+}
@@ -0,0 +1,325 @@
+//! Takeover view for a background terminal (`/terminal`).
+//!
+//! While open, every key is encoded the way a terminal would send it and forwarded to the process
+//! with `Op::TerminalInput`, so the user can answer prompts the agent cannot, such as passwords or
+//! `npm init` questions. Ctrl+] hands the terminal back with `Op::ReleaseTerminal`. Output arrives
+//! through `App`, which feeds the process's output deltas to `push_output`.
+
+use std::io::Result;
+
+use super::KEY_CTRL_C;
+use super::KEY_ESC;
+use super::KEY_Q;
+use super::render_key_hints;
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use crate::key_hint;
+use crate::key_hint::KeyBinding;
+use crate::tui;
+use crate::tui::TuiEvent;
+use codex_ansi_escape::ansi_escape_line;
+use codex_core::protocol::Op;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::widgets::Clear;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Widget;
+use ratatui::widgets::WidgetRef;
+
+/// Output kept for display; older output is dropped from the front.
+const MAX_OUTPUT_BYTES: usize = 256 * 1024;
+
+const KEY_RELEASE: KeyBinding = key_hint::ctrl(KeyCode::Char(']'));
+/// How crossterm reports Ctrl+] (0x1d) on terminals without enhanced key
+/// reporting.
+const KEY_RELEASE_LEGACY: KeyBinding = key_hint::ctrl(KeyCode::Char('5'));
+
+pub(crate) struct TerminalOverlay {
+    process_id: String,
+    call_id: String,
+    command_display: String,
+    output: String,
+    exited: bool,
+    app_event_tx: AppEventSender,
+    is_done: bool,
+}
+
+impl TerminalOverlay {
+    /// Take over `process_id`. `recent_output` seeds the view with what the
+    /// process printed before the takeover.
+    pub(crate) fn new(
+        process_id: String,
+        call_id: String,
+        command_display: String,
+        recent_output: String,
+        app_event_tx: AppEventSender,
+    ) -> Self {
+        app_event_tx.send(AppEvent::CodexOp(Op::TakeOverTerminal {
+            process_id: process_id.clone(),
+        }));
+        Self {
+            process_id,
+            call_id,
+            command_display,
+            output: recent_output,
+            exited: false,
+            app_event_tx,
+            is_done: false,
+        }
+    }
+
+    pub(crate) fn call_id(&self) -> &str {
+        &self.call_id
+    }
+
+    pub(crate) fn push_output(&mut self, chunk: &[u8]) {
+        self.output.push_str(&String::from_utf8_lossy(chunk));
+        if self.output.len() > MAX_OUTPUT_BYTES {
+            let mut cut = self.output.len() - MAX_OUTPUT_BYTES;
+            while !self.output.is_char_boundary(cut) {
+                cut += 1;
+            }
+            self.output.drain(..cut);
+        }
+    }
+
+    /// The process ended, so there is nothing left to hand back.
+    pub(crate) fn mark_exited(&mut self) {
+        self.exited = true;
+    }
+
+    fn send_input(&self, data: String) {
+        if self.exited || data.is_empty() {
+            return;
+        }
+        self.app_event_tx.send(AppEvent::CodexOp(Op::TerminalInput {
+            process_id: self.process_id.clone(),
+            data,
+        }));
+    }
+
+    fn release(&mut self) {
+        if !self.exited {
+            self.app_event_tx
+                .send(AppEvent::CodexOp(Op::ReleaseTerminal {
+                    process_id: self.process_id.clone(),
+                }));
+        }
+        self.is_done = true;
+    }
+
+    pub(crate) fn render(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+        let header = Rect::new(area.x, area.y, area.width, 1);
+        Span::from("/ ".repeat(area.width as usize / 2))
+            .dim()
+            .render_ref(header, buf);
+        format!("/ T E R M I N A L {}", self.process_id)
+            .dim()
+            .render_ref(header, buf);
+
+        let command = Rect::new(area.x, area.y.saturating_add(1), area.width, 1);
+        Line::from(vec!["$ ".dim(), self.command_display.clone().bold()]).render_ref(command, buf);
+
+        let body_h = area.height.saturating_sub(4);
+        let body = Rect::new(area.x, area.y.saturating_add(2), area.width, body_h);
+        let lines = screen_lines(&self.output);
+        let visible = lines.len().saturating_sub(body_h as usize);
+        Paragraph::new(lines[visible..].to_vec()).render_ref(body, buf);
+
+        let hints = Rect::new(area.x, area.y.saturating_add(2 + body_h), area.width, 1);
+        if self.exited {
+            let pairs: Vec<(&[KeyBinding], &str)> =
+                vec![(&[KEY_Q, KEY_ESC, KEY_RELEASE], "to close")];
+            Line::from("The process has exited.".dim()).render_ref(hints, buf);
+            let close = Rect::new(hints.x, hints.y.saturating_add(1), hints.width, 1);
+            render_key_hints(close, buf, &pairs);
+        } else {
+            let pairs: Vec<(&[KeyBinding], &str)> = vec![(&[KEY_RELEASE], "to hand back to Codex")];
+            Line::from("Keys go to the process.".dim()).render_ref(hints, buf);
+            let release = Rect::new(hints.x, hints.y.saturating_add(1), hints.width, 1);
+            render_key_hints(release, buf, &pairs);
+        }
+    }
+
+    pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
+        match event {
+            TuiEvent::Key(key_event)
+                if KEY_RELEASE.is_press(key_event) || KEY_RELEASE_LEGACY.is_press(key_event) =>
+            {
+                self.release();
+                Ok(())
+            }
+            TuiEvent::Key(key_event)
+                if self.exited
+                    && (KEY_Q.is_press(key_event)
+                        || KEY_ESC.is_press(key_event)
+                        || KEY_CTRL_C.is_press(key_event)) =>
+            {
+                self.is_done = true;
+                Ok(())
+            }
+            TuiEvent::Key(key_event) => {
+                if let Some(data) = key_input(key_event) {
+                    self.send_input(data);
+                }
+                Ok(())
+            }
+            TuiEvent::Paste(pasted) => {
+                self.send_input(pasted);
+                Ok(())
+            }
+            TuiEvent::Draw => {
+                tui.draw(u16::MAX, |frame| {
+                    self.render(frame.area(), frame.buffer);
+                })?;
+                Ok(())
+            }
+        }
+    }
+
+    pub(crate) fn is_done(&self) -> bool {
+        self.is_done
+    }
+}
+
+/// What a terminal would send for `key`, or `None` for keys with no input.
+fn key_input(key: KeyEvent) -> Option<String> {
+    if key.kind == KeyEventKind::Release {
+        return None;
+    }
+    let alt = key.modifiers.contains(KeyModifiers::ALT);
+    let input = match key.code {
+        KeyCode::Char(c) if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            let c = c.to_ascii_lowercase();
+            match c {
+                'a'..='z' => char::from(c as u8 - b'a' + 1).to_string(),
+                // Legacy reporting of Ctrl+\, Ctrl+], Ctrl+^, and Ctrl+_.
+                '4'..='7' => char::from(c as u8 - b'4' + 0x1c).to_string(),
+                '@' | ' ' => "\0".to_string(),
+                '[' => "\x1b".to_string(),
+                '\\' => "\x1c".to_string(),
+                '^' => "\x1e".to_string(),
+                '_' => "\x1f".to_string(),
+                _ => return None,
+            }
+        }
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::Enter => "\r".to_string(),
+        KeyCode::Tab => "\t".to_string(),
+        KeyCode::BackTab => "\x1b[Z".to_string(),
+        KeyCode::Backspace => "\x7f".to_string(),
+        KeyCode::Esc => "\x1b".to_string(),
+        KeyCode::Up => "\x1b[A".to_string(),
+        KeyCode::Down => "\x1b[B".to_string(),
+        KeyCode::Right => "\x1b[C".to_string(),
+        KeyCode::Left => "\x1b[D".to_string(),
+        KeyCode::Home => "\x1b[H".to_string(),
+        KeyCode::End => "\x1b[F".to_string(),
+        KeyCode::Insert => "\x1b[2~".to_string(),
+        KeyCode::Delete => "\x1b[3~".to_string(),
+        KeyCode::PageUp => "\x1b[5~".to_string(),
+        KeyCode::PageDown => "\x1b[6~".to_string(),
+        _ => return None,
+    };
+    Some(if alt { format!("\x1b{input}") } else { input })
+}
+
+/// Output as the lines a terminal would show, with each carriage return
+/// overwriting the line it is on.
+fn screen_lines(output: &str) -> Vec<Line<'static>> {
+    output
+        .split('\n')
+        .map(|line| {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            ansi_escape_line(line.rsplit('\r').next().unwrap_or(line))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn encodes_keys_like_a_terminal() {
+        let key = |code, modifiers| key_input(KeyEvent::new(code, modifiers));
+        assert_eq!(
+            key(KeyCode::Char('y'), KeyModifiers::NONE),
+            Some("y".to_string())
+        );
+        assert_eq!(
+            key(KeyCode::Char('c'), KeyModifiers::CONTROL),
+            Some("\x03".to_string())
+        );
+        assert_eq!(
+            key(KeyCode::Char('b'), KeyModifiers::ALT),
+            Some("\x1bb".to_string())
+        );
+        assert_eq!(
+            key(KeyCode::Enter, KeyModifiers::NONE),
+            Some("\r".to_string())
+        );
+        assert_eq!(
+            key(KeyCode::Up, KeyModifiers::NONE),
+            Some("\x1b[A".to_string())
+        );
+        assert_eq!(key(KeyCode::F(1), KeyModifiers::NONE), None);
+    }
+
+    #[test]
+    fn release_hands_back_a_running_terminal() {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let overlay = |tx: &AppEventSender| {
+            TerminalOverlay::new(
+                "7".to_string(),
+                "call-7".to_string(),
+                "npm init".to_string(),
+                String::new(),
+                tx.clone(),
+            )
+        };
+        let mut released_ops = || {
+            let mut released = Vec::new();
+            while let Ok(event) = rx.try_recv() {
+                if let AppEvent::CodexOp(Op::ReleaseTerminal { process_id }) = event {
+                    released.push(process_id);
+                }
+            }
+            released
+        };
+
+        let mut running = overlay(&tx);
+        running.release();
+        assert!(running.is_done());
+        assert_eq!(released_ops(), vec!["7".to_string()]);
+
+        let mut exited = overlay(&tx);
+        exited.mark_exited();
+        exited.release();
+        assert_eq!(released_ops(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn carriage_returns_overwrite_the_line() {
+        let plain: Vec<String> = screen_lines("Password: \r\nprogress 10%\rprogress 90%\n")
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(plain, vec!["Password: ", "progress 90%", ""]);
+    }
+}
@@ -0,0 +1,721 @@
+//! Time-travel scrubber over the session timeline (`Ctrl+O`).
+//!
+//! Every user turn, tool call, and applied patch in the committed transcript becomes a point on
+//! a horizontal axis. Scrubbing with Left/Right replays the conversation as it stood at the
+//! selected point, and Tab switches to the workspace diff at that point.
+//!
+//! The overlay works from the same committed transcript cells as the `Ctrl+T` transcript view,
+//! so it reflects exactly what the user saw; in-flight activity is not shown until it is flushed
+//! to history. Workspace diffs come from the checkpoints (ghost snapshots) core takes at the start
+//! of each turn with the `undo` feature: a point shows the diff from the session's first
+//! checkpoint to the next checkpoint after it, or to the current working tree in the last turn.
+//! Changes are therefore resolved per turn, not per tool call. Diffs are computed in the
+//! background and posted back as [`AppEvent::TimelineDiffResult`]; diffs between two checkpoints
+//! never change and are kept, while the working-tree diff is recomputed every time it is shown.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::io::Result;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::Arc;
+
+use super::CachedRenderable;
+use super::KEY_CTRL_C;
+use super::KEY_LEFT;
+use super::KEY_Q;
+use super::KEY_RIGHT;
+use super::PAGER_KEY_HINTS;
+use super::PagerView;
+use super::TranscriptOverlay;
+use super::render_key_hints;
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use crate::exec_cell::ExecCell;
+use crate::exec_command::strip_bash_lc_and_escape;
+use crate::history_cell::HistoryCell;
+use crate::history_cell::PatchHistoryCell;
+use crate::history_cell::UserHistoryCell;
+use crate::key_hint;
+use crate::key_hint::KeyBinding;
+use crate::render::renderable::Renderable;
+use crate::tui;
+use crate::tui::TuiEvent;
+use crossterm::event::KeyCode;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::text::Text;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::WidgetRef;
+use ratatui::widgets::Wrap;
+
+const KEY_H: KeyBinding = key_hint::plain(KeyCode::Char('h'));
+const KEY_L: KeyBinding = key_hint::plain(KeyCode::Char('l'));
+const KEY_TAB: KeyBinding = key_hint::plain(KeyCode::Tab);
+const KEY_CTRL_O: KeyBinding = key_hint::ctrl(KeyCode::Char('o'));
+
+/// Rows used by the scrub axis and the description of the selected point.
+const AXIS_HEIGHT: u16 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum TimelinePointKind {
+    UserTurn,
+    ToolCall,
+    Patch,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct TimelinePoint {
+    pub(super) kind: TimelinePointKind,
+    /// Index of the transcript cell this point was derived from.
+    pub(super) cell_index: usize,
+    /// 1-based user turn the point belongs to (0 before the first user message).
+    turn: usize,
+    label: String,
+}
+
+/// A checkpoint (ghost snapshot) core took of the workspace at the start of a turn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TimelineCheckpoint {
+    /// Transcript cells committed when the checkpoint arrived; later cells came after it.
+    pub(crate) cell_count: usize,
+    pub(crate) commit_id: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TimelinePane {
+    Conversation,
+    Changes,
+}
+
+pub(crate) struct TimelineOverlay {
+    view: PagerView,
+    cells: Vec<Arc<dyn HistoryCell>>,
+    checkpoints: Vec<TimelineCheckpoint>,
+    cwd: PathBuf,
+    app_event_tx: AppEventSender,
+    /// Rendered diffs keyed by the target checkpoint.
+    diffs: HashMap<usize, Vec<Line<'static>>>,
+    /// Rendered diff against the working tree, dropped whenever the selection or pane changes
+    /// so it is recomputed the next time it is shown.
+    working_tree_diff: Option<Vec<Line<'static>>>,
+    /// Targets whose diff is being computed in the background, `None` for the working tree.
+    pending: HashSet<Option<usize>>,
+    points: Vec<TimelinePoint>,
+    selected: usize,
+    pane: TimelinePane,
+    is_done: bool,
+}
+
+impl TimelineOverlay {
+    /// Creates a timeline over the committed transcript with the latest point selected.
+    pub(crate) fn new(
+        cells: Vec<Arc<dyn HistoryCell>>,
+        checkpoints: Vec<TimelineCheckpoint>,
+        cwd: PathBuf,
+        app_event_tx: AppEventSender,
+    ) -> Self {
+        let points = timeline_points(&cells);
+        let selected = points.len().saturating_sub(1);
+        let mut overlay = Self {
+            view: PagerView::new(Vec::new(), "T I M E L I N E".to_string(), usize::MAX),
+            cells,
+            checkpoints,
+            cwd,
+            app_event_tx,
+            diffs: HashMap::new(),
+            working_tree_diff: None,
+            pending: HashSet::new(),
+            points,
+            selected,
+            pane: TimelinePane::Conversation,
+            is_done: false,
+        };
+        overlay.rebuild_renderables();
+        overlay
+    }
+
+    fn select(&mut self, index: usize) {
+        let index = index.min(self.points.len().saturating_sub(1));
+        if index != self.selected {
+            self.selected = index;
+            self.working_tree_diff = None;
+            self.rebuild_renderables();
+        }
+    }
+
+    fn toggle_pane(&mut self) {
+        self.pane = match self.pane {
+            TimelinePane::Conversation => TimelinePane::Changes,
+            TimelinePane::Changes => TimelinePane::Conversation,
+        };
+        self.working_tree_diff = None;
+        self.rebuild_renderables();
+    }
+
+    fn rebuild_renderables(&mut self) {
+        let Some(point) = self.points.get(self.selected) else {
+            self.view.renderables = vec![placeholder("Nothing has happened in this session yet.")];
+            return;
+        };
+        let cell_index = point.cell_index;
+        match self.pane {
+            TimelinePane::Conversation => {
+                self.view.renderables =
+                    TranscriptOverlay::render_cells(&self.cells[..=cell_index], None, None);
+                // Pin to the selected moment rather than the start of the session.
+                self.view.scroll_offset = usize::MAX;
+            }
+            TimelinePane::Changes => {
+                self.view.renderables = self.render_changes(cell_index);
+                self.view.scroll_offset = 0;
+            }
+        }
+    }
+
+    /// Renders the workspace diff from the first checkpoint to the state after `cell_index`,
+    /// starting a background computation when it is not available yet.
+    fn render_changes(&mut self, cell_index: usize) -> Vec<Box<dyn Renderable>> {
+        if self.checkpoints.is_empty() {
+            return vec![placeholder(
+                "No checkpoints were taken in this session. Enable the `undo` feature to see workspace changes.",
+            )];
+        }
+        let target = target_checkpoint(&self.checkpoints, cell_index);
+        let lines = match target {
+            Some(index) => self.diffs.get(&index),
+            None => self.working_tree_diff.as_ref(),
+        };
+        let Some(lines) = lines.cloned() else {
+            self.request_diff(target);
+            return vec![placeholder("Computing the workspace diff…")];
+        };
+        if lines.is_empty() {
+            return vec![placeholder("No workspace changes at this point.")];
+        }
+        let paragraph = Paragraph::new(Text::from(lines)).wrap(Wrap { trim: false });
+        vec![Box::new(CachedRenderable::new(paragraph))]
+    }
+
+    /// Computes the diff to `target` off the UI thread; the result arrives as
+    /// [`AppEvent::TimelineDiffResult`].
+    fn request_diff(&mut self, target: Option<usize>) {
+        if !self.pending.insert(target) {
+            return;
+        }
+        let cwd = self.cwd.clone();
+        let base = self.checkpoints[0].commit_id.clone();
+        let target_commit = target.map(|index| self.checkpoints[index].commit_id.clone());
+        let tx = self.app_event_tx.clone();
+        tokio::task::spawn_blocking(move || {
+            let diff = checkpoint_diff(&cwd, &base, target_commit.as_deref())
+                .map_err(|err| err.to_string());
+            tx.send(AppEvent::TimelineDiffResult { target, diff });
+        });
+    }
+
+    /// Stores a diff computed by [`Self::request_diff`] and shows it if it is still selected.
+    pub(crate) fn on_diff_result(
+        &mut self,
+        target: Option<usize>,
+        diff: std::result::Result<String, String>,
+    ) {
+        self.pending.remove(&target);
+        let lines = match diff {
+            Ok(diff) => diff_lines(&diff),
+            Err(err) => vec![format!("Failed to compute the diff: {err}").dim().into()],
+        };
+        let selected_target = self
+            .points
+            .get(self.selected)
+            .map(|point| target_checkpoint(&self.checkpoints, point.cell_index));
+        let showing = self.pane == TimelinePane::Changes && selected_target == Some(target);
+        match target {
+            Some(index) => {
+                self.diffs.insert(index, lines);
+            }
+            // A working-tree diff is only worth keeping while its point is on screen.
+            None if showing => self.working_tree_diff = Some(lines),
+            None => return,
+        }
+        if showing {
+            self.rebuild_renderables();
+        }
+    }
+
+    fn render_axis(&self, area: Rect, buf: &mut Buffer) {
+        if area.height == 0 || area.width == 0 {
+            return;
+        }
+        let axis_row = Rect::new(area.x, area.y, area.width, 1);
+        let label_row = Rect::new(area.x, area.y.saturating_add(1), area.width, 1);
+        if self.points.is_empty() {
+            Span::from(" no timeline points")
+                .dim()
+                .render_ref(axis_row, buf);
+            return;
+        }
+
+        // Each point takes two columns ("●─"); keep the selection centered when the
+        // timeline is wider than the terminal.
+        let capacity = (usize::from(area.width).saturating_sub(2) / 2).max(1);
+        let start = self
+            .selected
+            .saturating_sub(capacity / 2)
+            .min(self.points.len().saturating_sub(capacity));
+        let end = (start + capacity).min(self.points.len());
+        let mut spans: Vec<Span<'static>> = vec![if start > 0 { "‹".dim() } else { " ".into() }];
+        for (index, point) in self.points[start..end].iter().enumerate() {
+            let mut glyph = match point.kind {
+                TimelinePointKind::UserTurn => "●".cyan(),
+                TimelinePointKind::ToolCall => "•".dim(),
+                TimelinePointKind::Patch => "±".green(),
+            };
+            if start + index == self.selected {
+                glyph = glyph.reversed().bold();
+            }
+            spans.push(glyph);
+            if start + index + 1 < end {
+                spans.push("─".dim());
+            }
+        }
+        if end < self.points.len() {
+            spans.push("›".dim());
+        }
+        Line::from(spans).render_ref(axis_row, buf);
+
+        let point = &self.points[self.selected];
+        let kind = match point.kind {
+            TimelinePointKind::UserTurn => "user",
+            TimelinePointKind::ToolCall => "tool",
+            TimelinePointKind::Patch => "patch",
+        };
+        let position = format!(" {}/{} ", self.selected + 1, self.points.len());
+        Line::from(vec![
+            position.dim(),
+            format!("turn {} ", point.turn).dim(),
+            format!("{kind}: ").bold(),
+            point.label.clone().into(),
+        ])
+        .render_ref(label_row, buf);
+    }
+
+    fn render_hints(&self, area: Rect, buf: &mut Buffer) {
+        let line1 = Rect::new(area.x, area.y, area.width, 1);
+        let line2 = Rect::new(area.x, area.y.saturating_add(1), area.width, 1);
+        render_key_hints(line1, buf, PAGER_KEY_HINTS);
+        let pane_hint = match self.pane {
+            TimelinePane::Conversation => "to show changes",
+            TimelinePane::Changes => "to show conversation",
+        };
+        let pairs: Vec<(&[KeyBinding], &str)> = vec![
+            (&[KEY_Q], "to quit"),
+            (&[KEY_LEFT, KEY_RIGHT], "to scrub"),
+            (&[KEY_TAB], pane_hint),
+        ];
+        render_key_hints(line2, buf, &pairs);
+    }
+
+    pub(crate) fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let axis_h = AXIS_HEIGHT.min(area.height);
+        let top_h = area.height.saturating_sub(3 + axis_h);
+        let axis = Rect::new(area.x, area.y, area.width, axis_h);
+        let top = Rect::new(area.x, area.y + axis_h, area.width, top_h);
+        let bottom = Rect::new(area.x, area.y + axis_h + top_h, area.width, 3);
+        self.view.render(top, buf);
+        self.render_axis(axis, buf);
+        self.render_hints(bottom, buf);
+    }
+
+    pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
+        match event {
+            TuiEvent::Key(key_event) => {
+                match key_event {
+                    e if KEY_Q.is_press(e) || KEY_CTRL_C.is_press(e) || KEY_CTRL_O.is_press(e) => {
+                        self.is_done = true;
+                        return Ok(());
+                    }
+                    e if KEY_LEFT.is_press(e) || KEY_H.is_press(e) => {
+                        self.select(self.selected.saturating_sub(1));
+                    }
+                    e if KEY_RIGHT.is_press(e) || KEY_L.is_press(e) => {
+                        self.select(self.selected.saturating_add(1));
+                    }
+                    e if KEY_TAB.is_press(e) => self.toggle_pane(),
+                    other => return self.view.handle_key_event(tui, other),
+                }
+                tui.frame_requester().schedule_frame();
+                Ok(())
+            }
+            TuiEvent::Draw => {
+                tui.draw(u16::MAX, |frame| {
+                    self.render(frame.area(), frame.buffer);
+                })?;
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn is_done(&self) -> bool {
+        self.is_done
+    }
+}
+
+/// Derives the scrubbable points from committed transcript cells.
+pub(super) fn timeline_points(cells: &[Arc<dyn HistoryCell>]) -> Vec<TimelinePoint> {
+    let mut points = Vec::new();
+    let mut turn = 0;
+    for (cell_index, cell) in cells.iter().enumerate() {
+        let any = cell.as_any();
+        if let Some(user) = any.downcast_ref::<UserHistoryCell>() {
+            turn += 1;
+            points.push(TimelinePoint {
+                kind: TimelinePointKind::UserTurn,
+                cell_index,
+                turn,
+                label: user.message.lines().next().unwrap_or_default().to_string(),
+            });
+        } else if let Some(exec) = any.downcast_ref::<ExecCell>() {
+            points.extend(exec.calls.iter().map(|call| TimelinePoint {
+                kind: TimelinePointKind::ToolCall,
+                cell_index,
+                turn,
+                label: strip_bash_lc_and_escape(&call.command),
+            }));
+        } else if let Some(patch) = any.downcast_ref::<PatchHistoryCell>() {
+            let mut files: Vec<String> = patch
+                .changes()
+                .keys()
+                .map(|path| crate::diff_render::display_path_for(path, patch.cwd()))
+                .collect();
+            files.sort();
+            points.push(TimelinePoint {
+                kind: TimelinePointKind::Patch,
+                cell_index,
+                turn,
+                label: files.join(", "),
+            });
+        }
+    }
+    points
+}
+
+/// Index of the first checkpoint taken after the cell at `cell_index`, or `None` when the
+/// cell belongs to the current turn and the working tree is the later state.
+fn target_checkpoint(checkpoints: &[TimelineCheckpoint], cell_index: usize) -> Option<usize> {
+    checkpoints
+        .iter()
+        .position(|checkpoint| checkpoint.cell_count > cell_index)
+}
+
+/// Unified diff from the `base` checkpoint to `target`, or to the working tree (including
+/// untracked files that are not ignored) when `target` is `None`.
+fn checkpoint_diff(cwd: &Path, base: &str, target: Option<&str>) -> std::io::Result<String> {
+    match target {
+        Some(target) => git(cwd, &["diff", "--no-color", base, target], None),
+        None => {
+            // Stage the working tree into a scratch index so the user's index is untouched.
+            let index_dir = tempfile::tempdir()?;
+            let index = index_dir.path().join("index");
+            git(cwd, &["add", "--all", ":/"], Some(&index))?;
+            let tree = git(cwd, &["write-tree"], Some(&index))?;
+            git(cwd, &["diff", "--no-color", base, tree.trim()], None)
+        }
+    }
+}
+
+fn git(cwd: &Path, args: &[&str], index: Option<&Path>) -> std::io::Result<String> {
+    let mut command = Command::new("git");
+    command.current_dir(cwd).args(args);
+    if let Some(index) = index {
+        command.env("GIT_INDEX_FILE", index);
+    }
+    let output = command.output()?;
+    if !output.status.success() {
+        return Err(std::io::Error::other(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn diff_lines(diff: &str) -> Vec<Line<'static>> {
+    diff.lines()
+        .map(|line| {
+            let text = line.to_string();
+            if line.starts_with("diff --git") {
+                text.bold().into()
+            } else if line.starts_with("+++")
+                || line.starts_with("---")
+                || line.starts_with("index ")
+                || line.starts_with("new file mode")
+                || line.starts_with("deleted file mode")
+            {
+                text.dim().into()
+            } else if line.starts_with("@@") {
+                text.cyan().into()
+            } else if line.starts_with('+') {
+                text.green().into()
+            } else if line.starts_with('-') {
+                text.red().into()
+            } else {
+                text.into()
+            }
+        })
+        .collect()
+}
+
+fn placeholder(message: &'static str) -> Box<dyn Renderable> {
+    let paragraph =
+        Paragraph::new(Text::from(Line::from(message.dim()))).wrap(Wrap { trim: false });
+    Box::new(CachedRenderable::new(paragraph))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exec_cell::ExecCall;
+    use crate::history_cell::new_patch_event;
+    use codex_core::protocol::ExecCommandSource;
+    use codex_core::protocol::FileChange;
+    use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    fn user(message: &str) -> Arc<dyn HistoryCell> {
+        Arc::new(UserHistoryCell {
+            message: message.to_string(),
+            text_elements: Vec::new(),
+            local_image_paths: Vec::new(),
+        })
+    }
+
+    fn exec(command: &str) -> Arc<dyn HistoryCell> {
+        Arc::new(ExecCell::new(
+            ExecCall {
+                call_id: "call".to_string(),
+                command: vec!["bash".into(), "-lc".into(), command.to_string()],
+                parsed: Vec::new(),
+                output: None,
+                source: ExecCommandSource::Agent,
+                start_time: None,
+                duration: None,
+                interaction_input: None,
+            },
+            false,
+        ))
+    }
+
+    fn patch(path: &str) -> Arc<dyn HistoryCell> {
+        let cwd = PathBuf::from("/repo");
+        let mut changes = HashMap::new();
+        changes.insert(
+            cwd.join(path),
+            FileChange::Add {
+                content: "hello\n".to_string(),
+            },
+        );
+        Arc::new(new_patch_event(changes, &cwd))
+    }
+
+    #[test]
+    fn points_follow_turns_tool_calls_and_patches() {
+        let cells = vec![
+            user("fix the build\nplease"),
+            exec("cargo build"),
+            patch("src/lib.rs"),
+            user("now run tests"),
+        ];
+
+        assert_eq!(
+            timeline_points(&cells),
+            vec![
+                TimelinePoint {
+                    kind: TimelinePointKind::UserTurn,
+                    cell_index: 0,
+                    turn: 1,
+                    label: "fix the build".to_string(),
+                },
+                TimelinePoint {
+                    kind: TimelinePointKind::ToolCall,
+                    cell_index: 1,
+                    turn: 1,
+                    label: "cargo build".to_string(),
+                },
+                TimelinePoint {
+                    kind: TimelinePointKind::Patch,
+                    cell_index: 2,
+                    turn: 1,
+                    label: "src/lib.rs".to_string(),
+                },
+                TimelinePoint {
+                    kind: TimelinePointKind::UserTurn,
+                    cell_index: 3,
+                    turn: 2,
+                    label: "now run tests".to_string(),
+                },
+            ]
+        );
+    }
+
+    fn git_commit_all(repo: &Path, message: &str) -> String {
+        for args in [
+            vec!["add", "--all"],
+            vec![
+                "-c",
+                "user.name=Codex",
+                "-c",
+                "user.email=codex@example.com",
+                "commit",
+                "--quiet",
+                "--allow-empty",
+                "-m",
+                message,
+            ],
+        ] {
+            let status = Command::new("git")
+                .current_dir(repo)
+                .args(&args)
+                .status()
+                .expect("run git");
+            assert!(status.success(), "git {args:?} failed");
+        }
+        git(repo, &["rev-parse", "HEAD"], None)
+            .expect("rev-parse")
+            .trim()
+            .to_string()
+    }
+
+    #[test]
+    fn points_map_to_the_next_checkpoint_or_the_working_tree() {
+        let checkpoints = vec![
+            TimelineCheckpoint {
+                cell_count: 1,
+                commit_id: "first".to_string(),
+            },
+            TimelineCheckpoint {
+                cell_count: 3,
+                commit_id: "second".to_string(),
+            },
+        ];
+
+        assert_eq!(target_checkpoint(&checkpoints, 0), Some(0));
+        assert_eq!(target_checkpoint(&checkpoints, 1), Some(1));
+        assert_eq!(target_checkpoint(&checkpoints, 2), Some(1));
+        assert_eq!(target_checkpoint(&checkpoints, 3), None);
+    }
+
+    #[test]
+    fn changes_pane_diffs_checkpoints_around_the_selection() {
+        let repo = tempfile::tempdir().expect("tempdir");
+        let status = Command::new("git")
+            .current_dir(repo.path())
+            .args(["init", "--quiet"])
+            .status()
+            .expect("git init");
+        assert!(status.success());
+        std::fs::write(repo.path().join("keep.txt"), "keep\n").expect("write");
+        let first = git_commit_all(repo.path(), "turn 1");
+        std::fs::write(repo.path().join("one.txt"), "one\n").expect("write");
+        let second = git_commit_all(repo.path(), "turn 2");
+        std::fs::write(repo.path().join("two.txt"), "two\n").expect("write");
+
+        let turn_one = checkpoint_diff(repo.path(), &first, Some(&second)).expect("diff");
+        assert!(turn_one.contains("+++ b/one.txt"), "{turn_one}");
+        assert!(!turn_one.contains("two.txt"), "{turn_one}");
+
+        // The current turn diffs against the working tree, including untracked files, and
+        // leaves the user's index alone.
+        let current = checkpoint_diff(repo.path(), &first, None).expect("diff");
+        assert!(current.contains("+++ b/one.txt"), "{current}");
+        assert!(current.contains("+++ b/two.txt"), "{current}");
+        let staged = git(repo.path(), &["diff", "--cached", "--name-only"], None).expect("diff");
+        assert_eq!(staged, "");
+    }
+
+    #[test]
+    fn changes_pane_explains_missing_checkpoints() {
+        let cells = vec![user("a"), patch("one.rs")];
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let mut overlay = TimelineOverlay::new(
+            cells,
+            Vec::new(),
+            PathBuf::from("/repo"),
+            AppEventSender::new(tx),
+        );
+        overlay.toggle_pane();
+        assert_eq!(overlay.view.renderables.len(), 1);
+        assert_eq!(overlay.diffs.len(), 0);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn changes_pane_computes_diffs_in_the_background() {
+        let repo = tempfile::tempdir().expect("tempdir");
+        let status = Command::new("git")
+            .current_dir(repo.path())
+            .args(["init", "--quiet"])
+            .status()
+            .expect("git init");
+        assert!(status.success());
+        let first = git_commit_all(repo.path(), "turn 1");
+        std::fs::write(repo.path().join("one.txt"), "one\n").expect("write");
+        let second = git_commit_all(repo.path(), "turn 2");
+        let checkpoints = vec![
+            TimelineCheckpoint {
+                cell_count: 0,
+                commit_id: first,
+            },
+            TimelineCheckpoint {
+                cell_count: 2,
+                commit_id: second,
+            },
+        ];
+        let cells = vec![user("a"), patch("one.txt"), user("b")];
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let mut overlay = TimelineOverlay::new(
+            cells,
+            checkpoints,
+            repo.path().to_path_buf(),
+            AppEventSender::new(tx),
+        );
+
+        // The last point diffs against the working tree: computed off the render path and
+        // never cached once the selection moves away.
+        overlay.toggle_pane();
+        assert_eq!(overlay.pending, HashSet::from([None]));
+        let Some(AppEvent::TimelineDiffResult { target, diff }) = rx.recv().await else {
+            panic!("expected a timeline diff");
+        };
+        assert_eq!(target, None);
+        overlay.on_diff_result(target, diff);
+        assert!(overlay.working_tree_diff.is_some());
+        assert!(overlay.pending.is_empty());
+
+        overlay.select(1);
+        assert_eq!(overlay.working_tree_diff, None);
+        let Some(AppEvent::TimelineDiffResult { target, diff }) = rx.recv().await else {
+            panic!("expected a timeline diff");
+        };
+        assert_eq!(target, Some(1));
+        overlay.on_diff_result(target, diff);
+        assert!(
+            overlay.diffs[&1]
+                .iter()
+                .any(|line| line.to_string() == "+++ b/one.txt")
+        );
+
+        // Checkpoint diffs are kept, so returning to the point needs no new computation.
+        overlay.select(2);
+        overlay.select(1);
+        assert_eq!(overlay.pending, HashSet::from([None]));
+    }
+}
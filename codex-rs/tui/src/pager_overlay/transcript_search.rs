@@ -0,0 +1,211 @@
+//! `/` search over the transcript overlay (`Ctrl+T`).
+//!
+//! Matching is a case-insensitive (ASCII) substring search over the text of each committed cell.
+//! The cell's rendered lines are trimmed and joined with single spaces, so a phrase that wraps
+//! onto the next line still matches, and a match is addressed by the cell and the wrapped line it
+//! starts on at the current width. Only matches that fit on one line are highlighted. The live
+//! tail is not searched; it becomes searchable once it is committed.
+
+use std::ops::Range;
+use std::sync::Arc;
+
+use crate::history_cell::HistoryCell;
+use ratatui::style::Modifier;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+
+pub(super) struct TranscriptSearch {
+    pub(super) query: String,
+    pub(super) editing: bool,
+    /// Matches found by the last jump, in transcript order.
+    pub(super) matches: Vec<SearchMatch>,
+    /// Index into `matches` of the match last jumped to.
+    pub(super) current: Option<usize>,
+}
+
+impl TranscriptSearch {
+    pub(super) fn new() -> Self {
+        Self {
+            query: String::new(),
+            editing: true,
+            matches: Vec::new(),
+            current: None,
+        }
+    }
+
+    pub(super) fn current_match(&self) -> Option<SearchMatch> {
+        self.current.and_then(|idx| self.matches.get(idx).copied())
+    }
+}
+
+/// A transcript line containing the search query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(super) struct SearchMatch {
+    /// Index of the committed transcript cell.
+    pub(super) cell: usize,
+    /// Line within the cell's transcript lines at the searched width.
+    pub(super) line: usize,
+}
+
+/// The line each match of `query` in `cells` starts on, once per line; `query` must already be
+/// ASCII-lowercased.
+pub(super) fn find_matches(
+    cells: &[Arc<dyn HistoryCell>],
+    width: u16,
+    query: &str,
+) -> Vec<SearchMatch> {
+    cells
+        .iter()
+        .enumerate()
+        .flat_map(|(cell, c)| {
+            let (text, line_starts) = joined_text(&c.transcript_lines(width));
+            let mut lines: Vec<usize> = text
+                .match_indices(query)
+                .map(|(idx, _)| {
+                    line_starts
+                        .partition_point(|start| *start <= idx)
+                        .saturating_sub(1)
+                })
+                .collect();
+            lines.dedup();
+            lines
+                .into_iter()
+                .map(move |line| SearchMatch { cell, line })
+        })
+        .collect()
+}
+
+/// `lines` ASCII-lowercased, trimmed and joined with single spaces, with the offset in the joined
+/// text where each line starts.
+fn joined_text(lines: &[Line<'_>]) -> (String, Vec<usize>) {
+    let mut text = String::new();
+    let mut line_starts = Vec::with_capacity(lines.len());
+    for line in lines {
+        let line = line_text(line).to_ascii_lowercase();
+        let line = line.trim();
+        if !text.is_empty() && !line.is_empty() {
+            text.push(' ');
+        }
+        line_starts.push(text.len());
+        text.push_str(line);
+    }
+    (text, line_starts)
+}
+
+/// `line` with every occurrence of `query` (ASCII-lowercased) drawn in the match style, keeping
+/// the styles of the surrounding text.
+pub(super) fn highlight_line(line: Line<'static>, query: &str) -> Line<'static> {
+    let hits: Vec<Range<usize>> = line_text(&line)
+        .to_ascii_lowercase()
+        .match_indices(query)
+        .map(|(idx, hit)| idx..idx + hit.len())
+        .collect();
+    if hits.is_empty() {
+        return line;
+    }
+
+    let Line {
+        style,
+        alignment,
+        spans,
+    } = line;
+    let mut highlighted: Vec<Span<'static>> = Vec::new();
+    let mut offset = 0;
+    for span in spans {
+        let content = span.content.as_ref();
+        let end = offset + content.len();
+        let mut cuts: Vec<usize> = hits
+            .iter()
+            .flat_map(|hit| [hit.start, hit.end])
+            .filter(|cut| *cut > offset && *cut < end)
+            .map(|cut| cut - offset)
+            .chain([content.len()])
+            .collect();
+        cuts.sort_unstable();
+        cuts.dedup();
+
+        let mut start = 0;
+        for cut in cuts {
+            if cut == start {
+                continue;
+            }
+            let in_hit = hits.iter().any(|hit| hit.contains(&(offset + start)));
+            let piece_style = if in_hit {
+                span.style.patch(match_style())
+            } else {
+                span.style
+            };
+            highlighted.push(Span::styled(content[start..cut].to_string(), piece_style));
+            start = cut;
+        }
+        offset = end;
+    }
+    Line {
+        style,
+        alignment,
+        spans: highlighted,
+    }
+}
+
+fn match_style() -> Style {
+    Style::default()
+        .cyan()
+        .add_modifier(Modifier::REVERSED | Modifier::BOLD)
+}
+
+fn line_text(line: &Line<'_>) -> String {
+    line.spans
+        .iter()
+        .map(|span| span.content.as_ref())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history_cell::PlainHistoryCell;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn matches_phrases_wrapped_across_lines() {
+        let cells: Vec<Arc<dyn HistoryCell>> = vec![
+            Arc::new(PlainHistoryCell::new(vec![
+                Line::from("• Ran cargo"),
+                Line::from("  test --all"),
+                Line::from(""),
+                Line::from("  └ ok: cargo test"),
+            ])),
+            Arc::new(PlainHistoryCell::new(vec![Line::from("no match here")])),
+        ];
+
+        assert_eq!(
+            find_matches(&cells, 80, "cargo test"),
+            vec![
+                SearchMatch { cell: 0, line: 0 },
+                SearchMatch { cell: 0, line: 3 },
+            ]
+        );
+        assert_eq!(
+            find_matches(&cells, 80, "--all"),
+            vec![SearchMatch { cell: 0, line: 1 }]
+        );
+    }
+
+    #[test]
+    fn highlight_splits_spans_at_matches() {
+        let line = Line::from(vec!["cargo ".dim(), "Test --all".into()]);
+        let highlighted = highlight_line(line, "o te");
+
+        assert_eq!(
+            highlighted.spans,
+            vec![
+                "carg".dim(),
+                Span::styled("o ", Style::default().dim().patch(match_style())),
+                Span::styled("Te", match_style()),
+                "st --all".into(),
+            ]
+        );
+    }
+}
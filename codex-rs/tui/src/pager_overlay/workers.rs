@@ -0,0 +1,311 @@
+//! Coordinator view for a worker pool (`/workers`).
+//!
+//! Shows each worker's branch and current task, followed by every task in the shared queue with
+//! its outcome, so the results of all workers can be reviewed in one place. The view re-renders
+//! from the pool's latest snapshot on every draw; `App` schedules a frame whenever it changes.
+
+use std::io::Result;
+
+use super::CachedRenderable;
+use super::KEY_CTRL_C;
+use super::KEY_Q;
+use super::PAGER_KEY_HINTS;
+use super::PagerView;
+use super::render_key_hints;
+use crate::key_hint::KeyBinding;
+use crate::tui;
+use crate::tui::TuiEvent;
+use codex_core::workers::MergeOutcome;
+use codex_core::workers::QueuedTask;
+use codex_core::workers::TaskStatus;
+use codex_core::workers::WorkerPoolSnapshot;
+use codex_core::workers::WorkerSnapshot;
+use codex_core::workers::WorkerState;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::text::Text;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Wrap;
+use tokio::sync::watch;
+
+/// Length of the commit hash prefix shown next to finished tasks.
+const SHORT_SHA_LEN: usize = 8;
+
+pub(crate) struct WorkersOverlay {
+    view: PagerView,
+    snapshot_rx: watch::Receiver<WorkerPoolSnapshot>,
+    rendered: Option<WorkerPoolSnapshot>,
+    is_done: bool,
+}
+
+impl WorkersOverlay {
+    pub(crate) fn new(snapshot_rx: watch::Receiver<WorkerPoolSnapshot>) -> Self {
+        Self {
+            view: PagerView::new(Vec::new(), "W O R K E R S".to_string(), 0),
+            snapshot_rx,
+            rendered: None,
+            is_done: false,
+        }
+    }
+
+    fn sync_snapshot(&mut self) {
+        let snapshot = self.snapshot_rx.borrow().clone();
+        if self.rendered.as_ref() == Some(&snapshot) {
+            return;
+        }
+        let paragraph =
+            Paragraph::new(Text::from(workers_lines(&snapshot))).wrap(Wrap { trim: false });
+        self.view.renderables = vec![Box::new(CachedRenderable::new(paragraph))];
+        self.rendered = Some(snapshot);
+    }
+
+    fn render_hints(&self, area: Rect, buf: &mut Buffer) {
+        let line1 = Rect::new(area.x, area.y, area.width, 1);
+        let line2 = Rect::new(area.x, area.y.saturating_add(1), area.width, 1);
+        render_key_hints(line1, buf, PAGER_KEY_HINTS);
+        let pairs: Vec<(&[KeyBinding], &str)> = vec![(&[KEY_Q], "to quit")];
+        render_key_hints(line2, buf, &pairs);
+    }
+
+    pub(crate) fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        self.sync_snapshot();
+        let top_h = area.height.saturating_sub(3);
+        let top = Rect::new(area.x, area.y, area.width, top_h);
+        let bottom = Rect::new(area.x, area.y + top_h, area.width, 3);
+        self.view.render(top, buf);
+        self.render_hints(bottom, buf);
+    }
+
+    pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
+        match event {
+            TuiEvent::Key(key_event) => match key_event {
+                e if KEY_Q.is_press(e) || KEY_CTRL_C.is_press(e) => {
+                    self.is_done = true;
+                    Ok(())
+                }
+                other => self.view.handle_key_event(tui, other),
+            },
+            TuiEvent::Draw => {
+                tui.draw(u16::MAX, |frame| {
+                    self.render(frame.area(), frame.buffer);
+                })?;
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn is_done(&self) -> bool {
+        self.is_done
+    }
+}
+
+fn workers_lines(snapshot: &WorkerPoolSnapshot) -> Vec<Line<'static>> {
+    let count = |status: TaskStatus| {
+        snapshot
+            .tasks
+            .iter()
+            .filter(|task| task.status == status)
+            .count()
+    };
+    let mut lines: Vec<Line<'static>> = vec![
+        Line::from(vec![
+            "Queue ".dim(),
+            snapshot.queue_path.display().to_string().into(),
+        ]),
+        Line::from(vec![
+            format!("{} done", count(TaskStatus::Done)).green(),
+            " · ".dim(),
+            format!("{} failed", count(TaskStatus::Failed)).red(),
+            " · ".dim(),
+            format!("{} running", count(TaskStatus::InProgress)).cyan(),
+            " · ".dim(),
+            format!("{} pending", count(TaskStatus::Pending)).into(),
+        ]),
+        Line::from(""),
+        "Workers".bold().into(),
+    ];
+
+    let name_width = snapshot
+        .workers
+        .iter()
+        .map(|worker| worker.name.len())
+        .max()
+        .unwrap_or_default();
+    lines.extend(
+        snapshot
+            .workers
+            .iter()
+            .map(|worker| worker_line(worker, name_width)),
+    );
+
+    lines.push(Line::from(""));
+    lines.push("Tasks".bold().into());
+    if snapshot.tasks.is_empty() {
+        lines.push("  The queue is empty.".dim().into());
+    }
+    for task in &snapshot.tasks {
+        lines.extend(task_lines(task));
+    }
+    lines
+}
+
+fn worker_line(worker: &WorkerSnapshot, name_width: usize) -> Line<'static> {
+    let state: Span<'static> = match &worker.state {
+        WorkerState::Starting => "starting".dim(),
+        WorkerState::Idle => "idle".dim(),
+        WorkerState::Running { task_id } => format!("running {task_id}").cyan(),
+        WorkerState::Finished => "finished".green(),
+        WorkerState::Stopped => "stopped".magenta(),
+        WorkerState::Failed { message } => format!("failed: {message}").red(),
+    };
+    let mut spans = vec![
+        format!("  {:<name_width$}  ", worker.name).into(),
+        state,
+        format!("  {} done  ", worker.completed).dim(),
+        worker.branch.clone().dim(),
+    ];
+    if let Some(merge) = &worker.merge {
+        spans.push("  ".into());
+        spans.push(match merge {
+            Ok(MergeOutcome::Merged { into, commits }) => {
+                format!("merged {commits} commit(s) into {into}").green()
+            }
+            Ok(MergeOutcome::NothingToMerge) => "nothing to merge".dim(),
+            Err(message) => format!("merge failed: {message}").red(),
+        });
+    }
+    Line::from(spans)
+}
+
+fn task_lines(task: &QueuedTask) -> Vec<Line<'static>> {
+    let marker = match task.status {
+        TaskStatus::Pending => "·".dim(),
+        TaskStatus::InProgress => "▶".cyan(),
+        TaskStatus::Done => "✓".green(),
+        TaskStatus::Failed => "✗".red(),
+    };
+    let prompt = task.prompt.lines().next().unwrap_or_default().to_string();
+    let mut header = vec![
+        "  ".into(),
+        marker,
+        " ".into(),
+        task.id.clone().bold(),
+        "  ".into(),
+        prompt.into(),
+    ];
+    let mut details = Vec::new();
+    if let Some(worker) = &task.worker {
+        details.push(worker.clone());
+    }
+    if let Some(commit) = &task.commit {
+        details.push(commit.chars().take(SHORT_SHA_LEN).collect());
+    }
+    if !details.is_empty() {
+        header.push(format!("  ({})", details.join(", ")).dim());
+    }
+
+    let mut lines = vec![Line::from(header)];
+    if let Some(result) = &task.result {
+        let summary = result.lines().find(|line| !line.trim().is_empty());
+        if let Some(summary) = summary {
+            let summary = format!("      {}", summary.trim());
+            lines.push(match task.status {
+                TaskStatus::Failed => summary.red().into(),
+                TaskStatus::Pending | TaskStatus::InProgress | TaskStatus::Done => {
+                    summary.dim().into()
+                }
+            });
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    fn plain(lines: &[Line<'static>]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn lists_workers_and_task_outcomes() {
+        let snapshot = WorkerPoolSnapshot {
+            queue_path: PathBuf::from("/repo/tasks.jsonl"),
+            workers: vec![
+                WorkerSnapshot {
+                    name: "worker-1".to_string(),
+                    branch: "codex/workers-run/worker-1".to_string(),
+                    worktree: PathBuf::from("/wt/worker-1"),
+                    thread_id: None,
+                    state: WorkerState::Running {
+                        task_id: "task-2".to_string(),
+                    },
+                    completed: 1,
+                    merge: None,
+                },
+                WorkerSnapshot {
+                    name: "worker-2".to_string(),
+                    branch: "codex/workers-run/worker-2".to_string(),
+                    worktree: PathBuf::from("/wt/worker-2"),
+                    thread_id: None,
+                    state: WorkerState::Finished,
+                    completed: 2,
+                    merge: Some(Ok(MergeOutcome::Merged {
+                        into: "main".to_string(),
+                        commits: 2,
+                    })),
+                },
+            ],
+            tasks: vec![
+                QueuedTask {
+                    id: "task-1".to_string(),
+                    prompt: "Rename foo".to_string(),
+                    status: TaskStatus::Done,
+                    worker: Some("worker-1".to_string()),
+                    result: Some("Renamed foo to bar.".to_string()),
+                    commit: Some("0123456789abcdef".to_string()),
+                },
+                QueuedTask {
+                    id: "task-2".to_string(),
+                    prompt: "Update docs".to_string(),
+                    status: TaskStatus::InProgress,
+                    worker: Some("worker-1".to_string()),
+                    result: None,
+                    commit: None,
+                },
+            ],
+        };
+
+        assert_eq!(
+            plain(&workers_lines(&snapshot)),
+            vec![
+                "Queue /repo/tasks.jsonl",
+                "1 done · 0 failed · 1 running · 0 pending",
+                "",
+                "Workers",
+                "  worker-1  running task-2  1 done  codex/workers-run/worker-1",
+                "  worker-2  finished  2 done  codex/workers-run/worker-2  merged 2 commit(s) into main",
+                "",
+                "Tasks",
+                "  ✓ task-1  Rename foo  (worker-1, 01234567)",
+                "      Renamed foo to bar.",
+                "  ▶ task-2  Update docs  (worker-1)",
+            ]
+        );
+    }
+}
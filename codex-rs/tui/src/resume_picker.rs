@@ -9,11 +9,18 @@ use chrono::Utc;
 use codex_core::Cursor;
 use codex_core::INTERACTIVE_SESSION_SOURCES;
 use codex_core::RolloutRecorder;
+use codex_core::SessionSummary;
 use codex_core::ThreadItem;
 use codex_core::ThreadSortKey;
 use codex_core::ThreadsPage;
+use codex_core::TranscriptEntry;
+use codex_core::TranscriptRole;
+use codex_core::find_session_summaries_by_ids;
 use codex_core::find_thread_names_by_ids;
 use codex_core::path_utils;
+use codex_core::read_session_transcript;
+use codex_core::summarize_rollout_file;
+use codex_core::transcript_to_markdown;
 use codex_protocol::items::TurnItem;
 use color_eyre::eyre::Result;
 use crossterm::event::KeyCode;
@@ -42,6 +49,8 @@ use codex_protocol::protocol::SessionMetaLine;
 
 const PAGE_SIZE: usize = 25;
 const LOAD_NEAR_THRESHOLD: usize = 5;
+const SUMMARY_PANE_HEIGHT: u16 = 2;
+const TRANSCRIPT_PREVIEW_HEIGHT: u16 = 8;
 #[derive(Debug, Clone)]
 pub enum SessionSelection {
     StartFresh,
@@ -54,6 +63,9 @@ pub enum SessionSelection {
 pub enum SessionPickerAction {
     Resume,
     Fork,
+    /// The `/history` browser: searches full transcripts, previews the
+    /// highlighted session, and can resume, fork, or export it.
+    History,
 }
 
 impl SessionPickerAction {
@@ -61,19 +73,22 @@ impl SessionPickerAction {
         match self {
             SessionPickerAction::Resume => "Resume a previous session",
             SessionPickerAction::Fork => "Fork a previous session",
+            SessionPickerAction::History => "Session history",
         }
     }
 
     fn action_label(self) -> &'static str {
         match self {
-            SessionPickerAction::Resume => "resume",
+            SessionPickerAction::Resume | SessionPickerAction::History => "resume",
             SessionPickerAction::Fork => "fork",
         }
     }
 
     fn selection(self, path: PathBuf) -> SessionSelection {
         match self {
-            SessionPickerAction::Resume => SessionSelection::Resume(path),
+            SessionPickerAction::Resume | SessionPickerAction::History => {
+                SessionSelection::Resume(path)
+            }
             SessionPickerAction::Fork => SessionSelection::Fork(path),
         }
     }
@@ -114,6 +129,7 @@ pub async fn run_resume_picker(
         default_provider,
         show_all,
         SessionPickerAction::Resume,
+        None,
     )
     .await
 }
@@ -130,6 +146,28 @@ pub async fn run_fork_picker(
         default_provider,
         show_all,
         SessionPickerAction::Fork,
+        None,
+    )
+    .await
+}
+
+/// Full-screen browser over every recorded session. Search matches the text of
+/// user requests and agent replies, the highlighted session's transcript is
+/// previewed below the list, and Ctrl+E exports it as Markdown into
+/// `export_dir`.
+pub async fn run_history_browser(
+    tui: &mut Tui,
+    codex_home: &Path,
+    default_provider: &str,
+    export_dir: &Path,
+) -> Result<SessionSelection> {
+    run_session_picker(
+        tui,
+        codex_home,
+        default_provider,
+        true,
+        SessionPickerAction::History,
+        Some(export_dir.to_path_buf()),
     )
     .await
 }
@@ -140,6 +178,7 @@ async fn run_session_picker(
     default_provider: &str,
     show_all: bool,
     action: SessionPickerAction,
+    export_dir: Option<PathBuf>,
 ) -> Result<SessionSelection> {
     let alt = AltScreenGuard::enter(tui);
     let (bg_tx, bg_rx) = mpsc::unbounded_channel();
@@ -183,6 +222,7 @@ async fn run_session_picker(
         filter_cwd,
         action,
     );
+    state.export_dir = export_dir;
     state.start_initial_load();
     state.request_frame();
 
@@ -203,7 +243,8 @@ async fn run_session_picker(
                     }
                     TuiEvent::Draw => {
                         if let Ok(size) = alt.tui.terminal.size() {
-                            let list_height = size.height.saturating_sub(4) as usize;
+                            let list_height =
+                                size.height.saturating_sub(4 + state.summary_height()) as usize;
                             state.update_view_rows(list_height);
                             state.ensure_minimum_rows_for_view(list_height);
                         }
@@ -261,6 +302,12 @@ struct PickerState {
     filter_cwd: Option<PathBuf>,
     action: SessionPickerAction,
     thread_name_cache: HashMap<ThreadId, Option<String>>,
+    summary_cache: HashMap<ThreadId, Option<SessionSummary>>,
+    /// Transcripts loaded for full-text search; only used by `/history`.
+    transcript_cache: HashMap<PathBuf, Arc<Vec<TranscriptEntry>>>,
+    export_dir: Option<PathBuf>,
+    /// Result of the last export, shown next to the title.
+    status: Option<String>,
 }
 
 struct PaginationState {
@@ -318,6 +365,8 @@ struct Row {
     preview: String,
     thread_id: Option<ThreadId>,
     thread_name: Option<String>,
+    summary: Option<SessionSummary>,
+    transcript: Option<Arc<Vec<TranscriptEntry>>>,
     created_at: Option<DateTime<Utc>>,
     updated_at: Option<DateTime<Utc>>,
     cwd: Option<PathBuf>,
@@ -338,6 +387,19 @@ impl Row {
         {
             return true;
         }
+        if let Some(summary) = self.summary.as_ref()
+            && (summary.title.to_lowercase().contains(query)
+                || summary.outcome.to_lowercase().contains(query))
+        {
+            return true;
+        }
+        if let Some(transcript) = self.transcript.as_ref()
+            && transcript
+                .iter()
+                .any(|entry| entry.text.to_lowercase().contains(query))
+        {
+            return true;
+        }
         false
     }
 }
@@ -377,6 +439,10 @@ impl PickerState {
             filter_cwd,
             action,
             thread_name_cache: HashMap::new(),
+            summary_cache: HashMap::new(),
+            transcript_cache: HashMap::new(),
+            export_dir: None,
+            status: None,
         }
     }
 
@@ -399,6 +465,25 @@ impl PickerState {
                     return Ok(Some(self.action.selection(row.path.clone())));
                 }
             }
+            KeyCode::Char('f')
+                if matches!(self.action, SessionPickerAction::History)
+                    && key
+                        .modifiers
+                        .contains(crossterm::event::KeyModifiers::CONTROL) =>
+            {
+                if let Some(row) = self.filtered_rows.get(self.selected) {
+                    return Ok(Some(SessionSelection::Fork(row.path.clone())));
+                }
+            }
+            KeyCode::Char('e')
+                if matches!(self.action, SessionPickerAction::History)
+                    && key
+                        .modifiers
+                        .contains(crossterm::event::KeyModifiers::CONTROL) =>
+            {
+                self.export_selected().await;
+                self.request_frame();
+            }
             KeyCode::Up => {
                 if self.selected > 0 {
                     self.selected -= 1;
@@ -496,6 +581,8 @@ impl PickerState {
                 let page = page.map_err(color_eyre::Report::from)?;
                 self.ingest_page(page);
                 self.update_thread_names().await;
+                self.update_summaries().await;
+                self.update_transcripts().await;
                 let completed_token = pending.search_token.or(search_token);
                 self.continue_search_if_token_matches(completed_token);
             }
@@ -576,6 +663,140 @@ impl PickerState {
         }
     }
 
+    async fn update_summaries(&mut self) {
+        let missing_ids: HashSet<ThreadId> = self
+            .all_rows
+            .iter()
+            .filter_map(|row| row.thread_id)
+            .filter(|thread_id| !self.summary_cache.contains_key(thread_id))
+            .collect();
+        if missing_ids.is_empty() {
+            return;
+        }
+
+        let mut summaries = find_session_summaries_by_ids(&self.codex_home, &missing_ids)
+            .await
+            .unwrap_or_default();
+        for thread_id in missing_ids {
+            let mut summary = summaries.remove(&thread_id);
+            // Sessions that ended without a clean shutdown have no stored summary yet.
+            if summary.is_none()
+                && let Some(row) = self
+                    .all_rows
+                    .iter()
+                    .find(|row| row.thread_id == Some(thread_id))
+            {
+                summary = summarize_rollout_file(&row.path).await.ok().flatten();
+            }
+            self.summary_cache.insert(thread_id, summary);
+        }
+
+        let mut updated = false;
+        for row in self.all_rows.iter_mut() {
+            let Some(thread_id) = row.thread_id else {
+                continue;
+            };
+            let summary = self.summary_cache.get(&thread_id).cloned().flatten();
+            if row.summary == summary {
+                continue;
+            }
+            row.summary = summary;
+            updated = true;
+        }
+
+        if updated {
+            self.apply_filter();
+        }
+    }
+
+    async fn update_transcripts(&mut self) {
+        if !matches!(self.action, SessionPickerAction::History) {
+            return;
+        }
+        let mut updated = false;
+        for row in self.all_rows.iter_mut() {
+            if row.transcript.is_some() {
+                continue;
+            }
+            let transcript = match self.transcript_cache.get(&row.path) {
+                Some(transcript) => transcript.clone(),
+                None => {
+                    let transcript =
+                        Arc::new(read_session_transcript(&row.path).await.unwrap_or_default());
+                    self.transcript_cache
+                        .insert(row.path.clone(), transcript.clone());
+                    transcript
+                }
+            };
+            row.transcript = Some(transcript);
+            updated = true;
+        }
+
+        if updated {
+            self.apply_filter();
+        }
+    }
+
+    /// Write the highlighted session's transcript to `export_dir` as Markdown.
+    async fn export_selected(&mut self) {
+        let Some(export_dir) = self.export_dir.clone() else {
+            return;
+        };
+        let Some(row) = self.filtered_rows.get(self.selected) else {
+            return;
+        };
+        let entries = match row.transcript.as_ref() {
+            Some(transcript) => transcript.as_ref().clone(),
+            None => read_session_transcript(&row.path).await.unwrap_or_default(),
+        };
+        let file_stem = row
+            .thread_id
+            .map(|thread_id| thread_id.to_string())
+            .or_else(|| {
+                row.path
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().into_owned())
+            })
+            .unwrap_or_else(|| "session".to_string());
+        let path = export_dir.join(format!("codex-session-{file_stem}.md"));
+        let markdown = transcript_to_markdown(row.display_preview(), &entries);
+        self.status = Some(match tokio::fs::write(&path, markdown).await {
+            Ok(()) => format!("Exported transcript to {}", path.display()),
+            Err(err) => format!("Failed to export transcript: {err}"),
+        });
+    }
+
+    /// Rows reserved below the list for the highlighted session's summary, or
+    /// for its transcript preview in `/history`.
+    fn summary_height(&self) -> u16 {
+        if matches!(self.action, SessionPickerAction::History) {
+            return if self.selected_transcript().is_some() {
+                TRANSCRIPT_PREVIEW_HEIGHT
+            } else {
+                0
+            };
+        }
+        if self.selected_summary().is_some() {
+            SUMMARY_PANE_HEIGHT
+        } else {
+            0
+        }
+    }
+
+    fn selected_summary(&self) -> Option<&SessionSummary> {
+        self.filtered_rows
+            .get(self.selected)
+            .and_then(|row| row.summary.as_ref())
+    }
+
+    fn selected_transcript(&self) -> Option<&[TranscriptEntry]> {
+        self.filtered_rows
+            .get(self.selected)
+            .and_then(|row| row.transcript.as_deref())
+            .map(Vec::as_slice)
+            .filter(|entries| !entries.is_empty())
+    }
+
     fn apply_filter(&mut self) {
         let base_iter = self
             .all_rows
@@ -788,6 +1009,8 @@ fn head_to_row(item: &ThreadItem) -> Row {
         preview,
         thread_id,
         thread_name: None,
+        summary: None,
+        transcript: None,
         created_at,
         updated_at,
         cwd,
@@ -847,17 +1070,24 @@ fn draw_picker(tui: &mut Tui, state: &PickerState) -> std::io::Result<()> {
     let height = tui.terminal.size()?.height;
     tui.draw(height, |frame| {
         let area = frame.area();
-        let [header, search, columns, list, hint] = Layout::vertical([
+        let summary_height = state.summary_height();
+        let [header, search, columns, list, summary, hint] = Layout::vertical([
             Constraint::Length(1),
             Constraint::Length(1),
             Constraint::Length(1),
-            Constraint::Min(area.height.saturating_sub(4)),
+            Constraint::Min(area.height.saturating_sub(4 + summary_height)),
+            Constraint::Length(summary_height),
             Constraint::Length(1),
         ])
         .areas(area);
 
         // Header
-        frame.render_widget_ref(Line::from(vec![state.action.title().bold().cyan()]), header);
+        let mut header_spans = vec![state.action.title().bold().cyan()];
+        if let Some(status) = state.status.as_ref() {
+            header_spans.push("  ".into());
+            header_spans.push(status.clone().dim());
+        }
+        frame.render_widget_ref(Line::from(header_spans), header);
 
         // Search line
         let q = if state.query.is_empty() {
@@ -872,24 +1102,51 @@ fn draw_picker(tui: &mut Tui, state: &PickerState) -> std::io::Result<()> {
         // Column headers and list
         render_column_headers(frame, columns, &metrics);
         render_list(frame, list, state, &metrics);
+        if matches!(state.action, SessionPickerAction::History) {
+            if let Some(entries) = state.selected_transcript() {
+                render_transcript_preview(frame, summary, entries, &state.query);
+            }
+        } else if let Some(session_summary) = state.selected_summary() {
+            render_summary(frame, summary, session_summary);
+        }
 
         // Hint line
         let action_label = state.action.action_label();
-        let hint_line: Line = vec![
-            key_hint::plain(KeyCode::Enter).into(),
-            format!(" to {action_label} ").dim(),
-            "    ".dim(),
-            key_hint::plain(KeyCode::Esc).into(),
-            " to start new ".dim(),
-            "    ".dim(),
-            key_hint::ctrl(KeyCode::Char('c')).into(),
-            " to quit ".dim(),
-            "    ".dim(),
-            key_hint::plain(KeyCode::Up).into(),
-            "/".dim(),
-            key_hint::plain(KeyCode::Down).into(),
-            " to browse".dim(),
-        ]
+        let hint_line: Line = match state.action {
+            SessionPickerAction::History => vec![
+                key_hint::plain(KeyCode::Enter).into(),
+                format!(" to {action_label} ").dim(),
+                "    ".dim(),
+                key_hint::ctrl(KeyCode::Char('f')).into(),
+                " to fork ".dim(),
+                "    ".dim(),
+                key_hint::ctrl(KeyCode::Char('e')).into(),
+                " to export ".dim(),
+                "    ".dim(),
+                key_hint::plain(KeyCode::Esc).into(),
+                " to close ".dim(),
+                "    ".dim(),
+                key_hint::plain(KeyCode::Up).into(),
+                "/".dim(),
+                key_hint::plain(KeyCode::Down).into(),
+                " to browse".dim(),
+            ],
+            SessionPickerAction::Resume | SessionPickerAction::Fork => vec![
+                key_hint::plain(KeyCode::Enter).into(),
+                format!(" to {action_label} ").dim(),
+                "    ".dim(),
+                key_hint::plain(KeyCode::Esc).into(),
+                " to start new ".dim(),
+                "    ".dim(),
+                key_hint::ctrl(KeyCode::Char('c')).into(),
+                " to quit ".dim(),
+                "    ".dim(),
+                key_hint::plain(KeyCode::Up).into(),
+                "/".dim(),
+                key_hint::plain(KeyCode::Down).into(),
+                " to browse".dim(),
+            ],
+        }
         .into();
         frame.render_widget_ref(hint_line, hint);
     })
@@ -1011,6 +1268,52 @@ fn render_list(
     }
 }
 
+fn render_summary(frame: &mut crate::custom_terminal::Frame, area: Rect, summary: &SessionSummary) {
+    let width = usize::from(area.width).saturating_sub(2);
+    let rows = area.y..area.y.saturating_add(area.height);
+    for (y, text) in rows.zip([&summary.title, &summary.outcome]) {
+        let line: Line = vec!["  ".into(), truncate_text(text, width).dim()].into();
+        frame.render_widget_ref(line, Rect::new(area.x, y, area.width, 1));
+    }
+}
+
+/// One line per message, starting at the first message that matches the
+/// search query so the preview shows why the session matched.
+fn render_transcript_preview(
+    frame: &mut crate::custom_terminal::Frame,
+    area: Rect,
+    entries: &[TranscriptEntry],
+    query: &str,
+) {
+    let query = query.to_lowercase();
+    let start = if query.is_empty() {
+        0
+    } else {
+        entries
+            .iter()
+            .position(|entry| entry.text.to_lowercase().contains(&query))
+            .unwrap_or(0)
+    };
+    let width = usize::from(area.width).saturating_sub(2);
+    let rows = area.y..area.y.saturating_add(area.height);
+    for (y, entry) in rows.zip(&entries[start..]) {
+        let label = match entry.role {
+            TranscriptRole::User => Span::from(entry.role.label()).cyan(),
+            TranscriptRole::Assistant => Span::from(entry.role.label()).magenta(),
+        };
+        let label_width = entry.role.label().width() + 2;
+        let text = entry.text.split_whitespace().collect::<Vec<_>>().join(" ");
+        let line: Line = vec![
+            "  ".into(),
+            label,
+            ": ".dim(),
+            truncate_text(&text, width.saturating_sub(label_width)).dim(),
+        ]
+        .into();
+        frame.render_widget_ref(line, Rect::new(area.x, y, area.width, 1));
+    }
+}
+
 fn render_empty_state_line(state: &PickerState) -> Line<'static> {
     if !state.query.is_empty() {
         if state.search_state.is_active()
@@ -1329,6 +1632,8 @@ mod tests {
             preview: String::from("first message"),
             thread_id: None,
             thread_name: Some(String::from("My session")),
+            summary: None,
+            transcript: None,
             created_at: None,
             updated_at: None,
             cwd: None,
@@ -1363,6 +1668,8 @@ mod tests {
                 preview: String::from("Fix resume picker timestamps"),
                 thread_id: None,
                 thread_name: None,
+                summary: None,
+                transcript: None,
                 created_at: Some(now - Duration::minutes(16)),
                 updated_at: Some(now - Duration::seconds(42)),
                 cwd: None,
@@ -1373,6 +1680,8 @@ mod tests {
                 preview: String::from("Investigate lazy pagination cap"),
                 thread_id: None,
                 thread_name: None,
+                summary: None,
+                transcript: None,
                 created_at: Some(now - Duration::hours(1)),
                 updated_at: Some(now - Duration::minutes(35)),
                 cwd: None,
@@ -1383,6 +1692,8 @@ mod tests {
                 preview: String::from("Explain the codebase"),
                 thread_id: None,
                 thread_name: None,
+                summary: None,
+                transcript: None,
                 created_at: Some(now - Duration::hours(2)),
                 updated_at: Some(now - Duration::hours(2)),
                 cwd: None,
@@ -1631,6 +1942,8 @@ mod tests {
                 preview: String::from("First message preview"),
                 thread_id: Some(id1),
                 thread_name: None,
+                summary: None,
+                transcript: None,
                 created_at: None,
                 updated_at: Some(now - Duration::days(2)),
                 cwd: None,
@@ -1641,6 +1954,8 @@ mod tests {
                 preview: String::from("Second message preview"),
                 thread_id: Some(id2),
                 thread_name: None,
+                summary: None,
+                transcript: None,
                 created_at: None,
                 updated_at: Some(now - Duration::days(3)),
                 cwd: None,
@@ -1678,6 +1993,187 @@ mod tests {
         assert_snapshot!("resume_picker_thread_names", snapshot);
     }
 
+    #[tokio::test]
+    async fn stored_summary_is_loaded_for_selected_row() {
+        let tempdir = tempfile::tempdir().expect("tempdir");
+        let id = ThreadId::from_string("33333333-3333-3333-3333-333333333333").expect("thread id");
+        let entry = json!({
+            "id": id,
+            "title": "Fix the flaky login test",
+            "outcome": "The login test now waits for the server.",
+        });
+        std::fs::write(
+            tempdir.path().join("session_summaries.jsonl"),
+            format!("{entry}\n"),
+        )
+        .expect("write session summaries");
+
+        let loader: PageLoader = Arc::new(|_| {});
+        let mut state = PickerState::new(
+            tempdir.path().to_path_buf(),
+            FrameRequester::test_dummy(),
+            loader,
+            String::from("openai"),
+            true,
+            None,
+            SessionPickerAction::Resume,
+        );
+        let row = Row {
+            path: PathBuf::from("/tmp/c.jsonl"),
+            preview: String::from("Fix the flaky login test"),
+            thread_id: Some(id),
+            thread_name: None,
+            summary: None,
+            transcript: None,
+            created_at: None,
+            updated_at: None,
+            cwd: None,
+            git_branch: None,
+        };
+        state.all_rows = vec![row.clone()];
+        state.filtered_rows = vec![row];
+        assert_eq!(state.summary_height(), 0);
+
+        state.update_summaries().await;
+
+        assert_eq!(
+            state
+                .selected_summary()
+                .map(|summary| summary.outcome.as_str()),
+            Some("The login test now waits for the server.")
+        );
+        assert_eq!(state.summary_height(), SUMMARY_PANE_HEIGHT);
+        assert!(state.filtered_rows[0].matches_query("waits for the server"));
+    }
+
+    #[tokio::test]
+    async fn summary_falls_back_to_the_rollout_file() {
+        let tempdir = tempfile::tempdir().expect("tempdir");
+        let id = ThreadId::from_string("44444444-4444-4444-4444-444444444444").expect("thread id");
+        let rollout = tempdir.path().join("rollout.jsonl");
+        let lines = [
+            json!({
+                "timestamp": "2025-01-01T00:00:00Z",
+                "type": "session_meta",
+                "payload": {
+                    "id": id,
+                    "timestamp": "2025-01-01T00:00:00Z",
+                    "cwd": ".",
+                    "originator": "test_originator",
+                    "cli_version": "test_version",
+                    "base_instructions": null,
+                },
+            }),
+            json!({
+                "timestamp": "2025-01-01T00:00:01Z",
+                "type": "event_msg",
+                "payload": {"type": "user_message", "message": "Fix the flaky login test"},
+            }),
+            json!({
+                "timestamp": "2025-01-01T00:00:05Z",
+                "type": "event_msg",
+                "payload": {"type": "agent_message", "message": "The test now waits for the server."},
+            }),
+        ]
+        .map(|line| line.to_string());
+        std::fs::write(&rollout, lines.join("\n")).expect("write rollout");
+
+        let loader: PageLoader = Arc::new(|_| {});
+        let mut state = PickerState::new(
+            tempdir.path().to_path_buf(),
+            FrameRequester::test_dummy(),
+            loader,
+            String::from("openai"),
+            true,
+            None,
+            SessionPickerAction::Resume,
+        );
+        let row = Row {
+            path: rollout,
+            preview: String::from("Fix the flaky login test"),
+            thread_id: Some(id),
+            thread_name: None,
+            summary: None,
+            transcript: None,
+            created_at: None,
+            updated_at: None,
+            cwd: None,
+            git_branch: None,
+        };
+        state.all_rows = vec![row.clone()];
+        state.filtered_rows = vec![row];
+
+        state.update_summaries().await;
+
+        assert_eq!(
+            state
+                .selected_summary()
+                .map(|summary| summary.outcome.as_str()),
+            Some("The test now waits for the server.")
+        );
+    }
+
+    #[tokio::test]
+    async fn history_searches_transcripts_and_exports_markdown() {
+        let tempdir = tempfile::tempdir().expect("tempdir");
+        let rollout = tempdir.path().join("rollout.jsonl");
+        let lines = [
+            json!({
+                "timestamp": "2025-01-01T00:00:00Z",
+                "type": "event_msg",
+                "payload": {"type": "user_message", "message": "Fix the flaky login test"},
+            }),
+            json!({
+                "timestamp": "2025-01-01T00:00:05Z",
+                "type": "event_msg",
+                "payload": {"type": "agent_message", "message": "The test now waits for the server."},
+            }),
+        ]
+        .map(|line| line.to_string());
+        std::fs::write(&rollout, lines.join("\n")).expect("write rollout");
+
+        let loader: PageLoader = Arc::new(|_| {});
+        let mut state = PickerState::new(
+            tempdir.path().to_path_buf(),
+            FrameRequester::test_dummy(),
+            loader,
+            String::from("openai"),
+            true,
+            None,
+            SessionPickerAction::History,
+        );
+        state.export_dir = Some(tempdir.path().to_path_buf());
+        state.all_rows = vec![Row {
+            path: rollout,
+            preview: String::from("Fix the flaky login test"),
+            thread_id: None,
+            thread_name: None,
+            summary: None,
+            transcript: None,
+            created_at: None,
+            updated_at: None,
+            cwd: None,
+            git_branch: None,
+        }];
+        state.set_query(String::from("waits for the SERVER"));
+        assert!(state.filtered_rows.is_empty());
+
+        state.update_transcripts().await;
+        assert_eq!(state.filtered_rows.len(), 1);
+        assert_eq!(state.summary_height(), TRANSCRIPT_PREVIEW_HEIGHT);
+
+        state.export_selected().await;
+        let exported = tempdir.path().join("codex-session-rollout.md");
+        assert_eq!(
+            state.status,
+            Some(format!("Exported transcript to {}", exported.display()))
+        );
+        assert_eq!(
+            std::fs::read_to_string(exported).expect("read export"),
+            "# Fix the flaky login test\n\n## User\n\nFix the flaky login test\n\n## Codex\n\nThe test now waits for the server.\n"
+        );
+    }
+
     #[test]
     fn pageless_scrolling_deduplicates_and_keeps_order() {
         let loader: PageLoader = Arc::new(|_| {});
@@ -8,6 +8,7 @@ use std::sync::OnceLock;
 
 use codex_core::config::Config;
 use codex_core::protocol::Op;
+use codex_core::redaction::Redactor;
 use serde::Serialize;
 use serde_json::json;
 
@@ -17,16 +18,18 @@ static LOGGER: LazyLock<SessionLogger> = LazyLock::new(SessionLogger::new);
 
 struct SessionLogger {
     file: OnceLock<Mutex<File>>,
+    redactor: OnceLock<Redactor>,
 }
 
 impl SessionLogger {
     fn new() -> Self {
         Self {
             file: OnceLock::new(),
+            redactor: OnceLock::new(),
         }
     }
 
-    fn open(&self, path: PathBuf) -> std::io::Result<()> {
+    fn open(&self, path: PathBuf, redactor: Option<Redactor>) -> std::io::Result<()> {
         let mut opts = OpenOptions::new();
         opts.create(true).truncate(true).write(true);
 
@@ -37,14 +40,20 @@ impl SessionLogger {
         }
 
         let file = opts.open(path)?;
+        if let Some(redactor) = redactor {
+            let _ = self.redactor.set(redactor);
+        }
         self.file.get_or_init(|| Mutex::new(file));
         Ok(())
     }
 
-    fn write_json_line(&self, value: serde_json::Value) {
+    fn write_json_line(&self, mut value: serde_json::Value) {
         let Some(mutex) = self.file.get() else {
             return;
         };
+        if let Some(redactor) = self.redactor.get() {
+            redactor.redact_json(&mut value);
+        }
         let mut guard = match mutex.lock() {
             Ok(g) => g,
             Err(poisoned) => poisoned.into_inner(),
@@ -100,7 +109,7 @@ pub(crate) fn maybe_init(config: &Config) {
         p
     };
 
-    if let Err(e) = LOGGER.open(path.clone()) {
+    if let Err(e) = LOGGER.open(path.clone(), Redactor::from_config(&config.redaction)) {
         tracing::error!("failed to open session log {:?}: {}", path, e);
         return;
     }
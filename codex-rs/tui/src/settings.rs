@@ -0,0 +1,313 @@
+//! Catalog behind the `/settings` screen: the config values it can edit, the
+//! layer each effective value comes from, and the file an edit is written to.
+
+use std::path::PathBuf;
+
+use codex_app_server_protocol::ConfigLayerSource;
+use codex_core::config::Config;
+use codex_core::config::ConfigToml;
+use codex_core::config_loader::ConfigLayerEntry;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use toml::Value as TomlValue;
+
+/// How a setting's value is entered and encoded in TOML.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SettingKind {
+    Text,
+    Integer,
+    Bool,
+    Choice(&'static [&'static str]),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Setting {
+    /// Dotted config key, e.g. `tui.notifications`.
+    pub key: &'static str,
+    pub description: &'static str,
+    pub kind: SettingKind,
+}
+
+/// Commonly changed settings, in display order.
+pub(crate) const SETTINGS: &[Setting] = &[
+    Setting {
+        key: "model",
+        description: "Model used for new sessions",
+        kind: SettingKind::Text,
+    },
+    Setting {
+        key: "model_reasoning_effort",
+        description: "How much the model reasons before answering",
+        kind: SettingKind::Choice(&["none", "minimal", "low", "medium", "high", "xhigh"]),
+    },
+    Setting {
+        key: "model_verbosity",
+        description: "How detailed responses are",
+        kind: SettingKind::Choice(&["low", "medium", "high"]),
+    },
+    Setting {
+        key: "approval_policy",
+        description: "When Codex asks before running commands",
+        kind: SettingKind::Choice(&["untrusted", "on-failure", "on-request", "never"]),
+    },
+    Setting {
+        key: "sandbox_mode",
+        description: "What commands may access",
+        kind: SettingKind::Choice(&["read-only", "workspace-write", "danger-full-access"]),
+    },
+    Setting {
+        key: "file_opener",
+        description: "Editor that file links open in",
+        kind: SettingKind::Choice(&["vscode", "vscode-insiders", "windsurf", "cursor", "none"]),
+    },
+    Setting {
+        key: "prompt_cache_key",
+        description: "Scope of the prompt cache key",
+        kind: SettingKind::Choice(&["session", "workspace", "disabled"]),
+    },
+    Setting {
+        key: "project_doc_max_bytes",
+        description: "Maximum size of AGENTS.md files included in the prompt",
+        kind: SettingKind::Integer,
+    },
+    Setting {
+        key: "hide_agent_reasoning",
+        description: "Hide reasoning summaries in the transcript",
+        kind: SettingKind::Bool,
+    },
+    Setting {
+        key: "tui.notifications",
+        description: "Desktop notifications when a turn needs attention",
+        kind: SettingKind::Bool,
+    },
+];
+
+pub(crate) fn find_setting(key: &str) -> Option<&'static Setting> {
+    SETTINGS.iter().find(|setting| setting.key == key)
+}
+
+/// The layer a setting's effective value comes from.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum SettingSource {
+    Default,
+    Layer(ConfigLayerSource),
+}
+
+impl SettingSource {
+    pub(crate) fn label(&self) -> &'static str {
+        match self {
+            SettingSource::Default => "default",
+            SettingSource::Layer(source) => match source {
+                ConfigLayerSource::System { .. } => "system",
+                ConfigLayerSource::User { .. } => "user",
+                ConfigLayerSource::Project { .. } => "project",
+                ConfigLayerSource::SessionFlags => "flag",
+                ConfigLayerSource::Mdm { .. }
+                | ConfigLayerSource::LegacyManagedConfigTomlFromFile { .. }
+                | ConfigLayerSource::LegacyManagedConfigTomlFromMdm => "managed",
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct EffectiveSetting {
+    /// The value as written in TOML, or `None` when the default applies.
+    pub value: Option<String>,
+    pub source: SettingSource,
+    /// Path the value was found at, e.g. `profiles.work.model`.
+    pub segments: Vec<String>,
+}
+
+/// Where an edit to a setting is written.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SettingWrite {
+    /// Folder whose `config.toml` receives the edit.
+    pub config_folder: PathBuf,
+    pub segments: Vec<String>,
+    pub value: TomlValue,
+    /// Source that keeps overriding the written value, if any.
+    pub overridden_by: Option<&'static str>,
+}
+
+/// Paths checked for a setting, most specific first: the active profile's
+/// table and then the top level.
+fn candidate_paths(config: &Config, setting: &Setting) -> Vec<Vec<String>> {
+    let key: Vec<String> = setting.key.split('.').map(str::to_string).collect();
+    let mut paths = Vec::new();
+    if let Some(profile) = config.active_profile.as_ref() {
+        let mut path = vec!["profiles".to_string(), profile.clone()];
+        path.extend(key.iter().cloned());
+        paths.push(path);
+    }
+    paths.push(key);
+    paths
+}
+
+fn value_at<'a>(value: &'a TomlValue, segments: &[String]) -> Option<&'a TomlValue> {
+    segments
+        .iter()
+        .try_fold(value, |value, segment| value.get(segment.as_str()))
+}
+
+fn display_value(value: &TomlValue) -> String {
+    match value {
+        TomlValue::String(value) => value.clone(),
+        other => other.to_string(),
+    }
+}
+
+pub(crate) fn effective_setting(config: &Config, setting: &Setting) -> EffectiveSetting {
+    let layers = config.config_layer_stack.layers_high_to_low();
+    for segments in candidate_paths(config, setting) {
+        for layer in &layers {
+            if let Some(value) = value_at(&layer.config, &segments) {
+                return EffectiveSetting {
+                    value: Some(display_value(value)),
+                    source: SettingSource::Layer(layer.name.clone()),
+                    segments,
+                };
+            }
+        }
+    }
+    let segments = candidate_paths(config, setting).pop().unwrap_or_default();
+    EffectiveSetting {
+        value: None,
+        source: SettingSource::Default,
+        segments,
+    }
+}
+
+/// Parses `raw` for `setting` and checks it against the config schema.
+pub(crate) fn parse_setting_value(setting: &Setting, raw: &str) -> Result<TomlValue, String> {
+    let raw = raw.trim();
+    let value = match setting.kind {
+        SettingKind::Text => {
+            if raw.is_empty() {
+                return Err("value cannot be empty".to_string());
+            }
+            TomlValue::String(raw.to_string())
+        }
+        SettingKind::Integer => raw
+            .parse::<i64>()
+            .map(TomlValue::Integer)
+            .map_err(|_| format!("`{raw}` is not a whole number"))?,
+        SettingKind::Bool => raw
+            .parse::<bool>()
+            .map(TomlValue::Boolean)
+            .map_err(|_| format!("`{raw}` is not `true` or `false`"))?,
+        SettingKind::Choice(choices) => {
+            if !choices.contains(&raw) {
+                return Err(format!("expected one of: {}", choices.join(", ")));
+            }
+            TomlValue::String(raw.to_string())
+        }
+    };
+
+    let mut document = value.clone();
+    for segment in setting.key.rsplit('.') {
+        let mut table = toml::map::Map::new();
+        table.insert(segment.to_string(), document);
+        document = TomlValue::Table(table);
+    }
+    document
+        .try_into::<ConfigToml>()
+        .map_err(|err| err.message().to_string())?;
+    Ok(value)
+}
+
+/// Decides where an edit goes: back to the user or project file the value
+/// came from, and otherwise to the user config (under the active profile,
+/// if any), since default, system, flag, and managed values are not stored
+/// in a file Codex should edit.
+pub(crate) fn plan_setting_write(
+    config: &Config,
+    setting: &Setting,
+    raw: &str,
+) -> Result<SettingWrite, String> {
+    let value = parse_setting_value(setting, raw)?;
+    let effective = effective_setting(config, setting);
+    let (config_folder, segments) = match &effective.source {
+        SettingSource::Layer(
+            ConfigLayerSource::User { .. } | ConfigLayerSource::Project { .. },
+        ) => {
+            let folder = config
+                .config_layer_stack
+                .layers_high_to_low()
+                .into_iter()
+                .find(|layer| SettingSource::Layer(layer.name.clone()) == effective.source)
+                .and_then(ConfigLayerEntry::config_folder)
+                .map(AbsolutePathBuf::into_path_buf)
+                .unwrap_or_else(|| config.codex_home.clone());
+            (folder, effective.segments)
+        }
+        SettingSource::Default | SettingSource::Layer(_) => (
+            config.codex_home.clone(),
+            candidate_paths(config, setting).remove(0),
+        ),
+    };
+    let overridden_by = match &effective.source {
+        SettingSource::Layer(
+            ConfigLayerSource::SessionFlags
+            | ConfigLayerSource::Mdm { .. }
+            | ConfigLayerSource::LegacyManagedConfigTomlFromFile { .. }
+            | ConfigLayerSource::LegacyManagedConfigTomlFromMdm,
+        ) => Some(effective.source.label()),
+        SettingSource::Default
+        | SettingSource::Layer(
+            ConfigLayerSource::System { .. }
+            | ConfigLayerSource::User { .. }
+            | ConfigLayerSource::Project { .. },
+        ) => None,
+    };
+    Ok(SettingWrite {
+        config_folder,
+        segments,
+        value,
+        overridden_by,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn values_are_validated_against_the_config_schema() {
+        let effort = find_setting("model_reasoning_effort").unwrap();
+        assert_eq!(
+            parse_setting_value(effort, "high"),
+            Ok(TomlValue::String("high".to_string()))
+        );
+        assert!(parse_setting_value(effort, "extreme").is_err());
+
+        let max_bytes = find_setting("project_doc_max_bytes").unwrap();
+        assert_eq!(
+            parse_setting_value(max_bytes, " 4096 "),
+            Ok(TomlValue::Integer(4096))
+        );
+        assert!(parse_setting_value(max_bytes, "-1").is_err());
+        assert!(parse_setting_value(max_bytes, "lots").is_err());
+
+        let notifications = find_setting("tui.notifications").unwrap();
+        assert_eq!(
+            parse_setting_value(notifications, "false"),
+            Ok(TomlValue::Boolean(false))
+        );
+    }
+
+    #[test]
+    fn every_choice_is_accepted_by_the_config_schema() {
+        for setting in SETTINGS {
+            if let SettingKind::Choice(choices) = setting.kind {
+                for choice in choices {
+                    assert!(
+                        parse_setting_value(setting, choice).is_ok(),
+                        "{} = {choice}",
+                        setting.key
+                    );
+                }
+            }
+        }
+    }
+}
@@ -17,21 +17,34 @@ pub enum SlashCommand {
     Permissions,
     #[strum(serialize = "setup-elevated-sandbox")]
     ElevateSandbox,
+    #[strum(to_string = "experimental", serialize = "experiments")]
     Experimental,
+    Settings,
     Skills,
     Review,
     Rename,
     New,
     Resume,
     Fork,
+    History,
     Init,
+    Instructions,
     Compact,
+    ClearContext,
+    Rewind,
+    Pin,
     Plan,
     Collab,
     Agent,
-    // Undo,
+    Workers,
+    Merge,
+    Discard,
+    Undo,
     Diff,
+    Commit,
+    Pr,
     Mention,
+    Image,
     Status,
     Mcp,
     Apps,
@@ -41,7 +54,11 @@ pub enum SlashCommand {
     Feedback,
     Rollout,
     Ps,
+    Terminal,
+    Jobs,
+    Trash,
     Personality,
+    Verbosity,
     TestApproval,
 }
 
@@ -52,27 +69,43 @@ impl SlashCommand {
             SlashCommand::Feedback => "send logs to maintainers",
             SlashCommand::New => "start a new chat during a conversation",
             SlashCommand::Init => "create an AGENTS.md file with instructions for Codex",
+            SlashCommand::Instructions => "show the instructions Codex follows and their sources",
             SlashCommand::Compact => "summarize conversation to prevent hitting the context limit",
+            SlashCommand::ClearContext => "clear the context, or drop turns N[-M] from it",
+            SlashCommand::Rewind => "restore the conversation and files to before turn N",
+            SlashCommand::Pin => "pin a file or message so it stays in the context",
             SlashCommand::Review => "review my current changes and find issues",
             SlashCommand::Rename => "rename the current thread",
             SlashCommand::Resume => "resume a saved chat",
             SlashCommand::Fork => "fork the current chat",
-            // SlashCommand::Undo => "ask Codex to undo a turn",
+            SlashCommand::History => "search, resume, fork, or export past sessions",
+            SlashCommand::Undo => "undo Codex's most recent changes",
             SlashCommand::Quit | SlashCommand::Exit => "exit Codex",
             SlashCommand::Diff => "show git diff (including untracked files)",
+            SlashCommand::Commit => "commit Codex's changes with a generated message",
+            SlashCommand::Pr => "push this branch and open a pull request",
             SlashCommand::Mention => "mention a file",
+            SlashCommand::Image => "attach an image by path, or pick one from the project",
             SlashCommand::Skills => "use skills to improve how Codex performs specific tasks",
             SlashCommand::Status => "show current session configuration and token usage",
             SlashCommand::Ps => "list background terminals",
+            SlashCommand::Terminal => "take over a background terminal to answer its prompts",
+            SlashCommand::Jobs => "start, list, or stop background jobs",
+            SlashCommand::Trash => "list or restore files deleted by Codex",
             SlashCommand::Model => "choose what model and reasoning effort to use",
             SlashCommand::Personality => "choose a communication style for Codex",
+            SlashCommand::Verbosity => "choose how detailed Codex's responses are",
             SlashCommand::Plan => "switch to Plan mode",
             SlashCommand::Collab => "change collaboration mode (experimental)",
             SlashCommand::Agent => "switch the active agent thread",
+            SlashCommand::Workers => "run a task queue across parallel worker sessions",
+            SlashCommand::Merge => "merge this session's worktree branch into your checkout",
+            SlashCommand::Discard => "discard this session's worktree and its changes",
             SlashCommand::Approvals => "choose what Codex can do without approval",
             SlashCommand::Permissions => "choose what Codex is allowed to do",
             SlashCommand::ElevateSandbox => "set up elevated agent sandbox",
             SlashCommand::Experimental => "toggle experimental features",
+            SlashCommand::Settings => "view and edit configuration",
             SlashCommand::Mcp => "list configured MCP tools",
             SlashCommand::Apps => "manage apps",
             SlashCommand::Logout => "log out of Codex",
@@ -91,7 +124,19 @@ impl SlashCommand {
     pub fn supports_inline_args(self) -> bool {
         matches!(
             self,
-            SlashCommand::Review | SlashCommand::Rename | SlashCommand::Plan
+            SlashCommand::Review
+                | SlashCommand::Rename
+                | SlashCommand::Model
+                | SlashCommand::Plan
+                | SlashCommand::Trash
+                | SlashCommand::Workers
+                | SlashCommand::ClearContext
+                | SlashCommand::Rewind
+                | SlashCommand::Pin
+                | SlashCommand::Terminal
+                | SlashCommand::Jobs
+                | SlashCommand::Image
+                | SlashCommand::Mcp
         )
     }
 
@@ -101,24 +146,39 @@ impl SlashCommand {
             SlashCommand::New
             | SlashCommand::Resume
             | SlashCommand::Fork
+            | SlashCommand::History
             | SlashCommand::Init
             | SlashCommand::Compact
-            // | SlashCommand::Undo
+            | SlashCommand::ClearContext
+            | SlashCommand::Rewind
+            | SlashCommand::Undo
             | SlashCommand::Model
             | SlashCommand::Personality
+            | SlashCommand::Verbosity
             | SlashCommand::Approvals
             | SlashCommand::Permissions
             | SlashCommand::ElevateSandbox
             | SlashCommand::Experimental
+            | SlashCommand::Settings
             | SlashCommand::Review
             | SlashCommand::Plan
+            | SlashCommand::Merge
+            | SlashCommand::Discard
+            | SlashCommand::Commit
+            | SlashCommand::Pr
             | SlashCommand::Logout => false,
             SlashCommand::Diff
+            | SlashCommand::Instructions
             | SlashCommand::Rename
             | SlashCommand::Mention
+            | SlashCommand::Image
             | SlashCommand::Skills
             | SlashCommand::Status
             | SlashCommand::Ps
+            | SlashCommand::Terminal
+            | SlashCommand::Jobs
+            | SlashCommand::Trash
+            | SlashCommand::Pin
             | SlashCommand::Mcp
             | SlashCommand::Apps
             | SlashCommand::Feedback
@@ -128,6 +188,7 @@ impl SlashCommand {
             SlashCommand::TestApproval => true,
             SlashCommand::Collab => true,
             SlashCommand::Agent => true,
+            SlashCommand::Workers => true,
         }
     }
 
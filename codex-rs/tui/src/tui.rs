@@ -39,6 +39,7 @@ pub use self::frame_requester::FrameRequester;
 use crate::custom_terminal;
 use crate::custom_terminal::Terminal as CustomTerminal;
 use crate::notifications::DesktopNotificationBackend;
+use crate::notifications::NotificationAction;
 use crate::notifications::detect_backend;
 use crate::tui::event_stream::EventBroker;
 use crate::tui::event_stream::TuiEventStream;
@@ -354,6 +355,26 @@ impl Tui {
     /// Emit a desktop notification now if the terminal is unfocused.
     /// Returns true if a notification was posted.
     pub fn notify(&mut self, message: impl AsRef<str>) -> bool {
+        let message = message.as_ref().to_string();
+        self.post_notification(|backend| backend.notify(&message))
+    }
+
+    /// Like [`Self::notify`], with `actions` shown as buttons when the
+    /// notification backend supports them.
+    pub(crate) fn notify_with_actions(
+        &mut self,
+        message: impl AsRef<str>,
+        actions: &[NotificationAction],
+        on_action: Box<dyn FnOnce(NotificationAction) + Send + 'static>,
+    ) -> bool {
+        let message = message.as_ref().to_string();
+        self.post_notification(|backend| backend.notify_with_actions(&message, actions, on_action))
+    }
+
+    fn post_notification(
+        &mut self,
+        post: impl FnOnce(&mut DesktopNotificationBackend) -> std::io::Result<()>,
+    ) -> bool {
         if self.terminal_focused.load(Ordering::Relaxed) {
             return false;
         }
@@ -362,8 +383,7 @@ impl Tui {
             return false;
         };
 
-        let message = message.as_ref().to_string();
-        match backend.notify(&message) {
+        match post(backend) {
             Ok(()) => true,
             Err(err) => {
                 let method = backend.method();
@@ -0,0 +1,103 @@
+use anyhow::Result;
+use windows_sys::Win32::Foundation::CloseHandle;
+use windows_sys::Win32::Foundation::HANDLE;
+use windows_sys::Win32::Foundation::INVALID_HANDLE_VALUE;
+use windows_sys::Win32::System::Diagnostics::ToolHelp::CreateToolhelp32Snapshot;
+use windows_sys::Win32::System::Diagnostics::ToolHelp::Thread32First;
+use windows_sys::Win32::System::Diagnostics::ToolHelp::Thread32Next;
+use windows_sys::Win32::System::Diagnostics::ToolHelp::TH32CS_SNAPTHREAD;
+use windows_sys::Win32::System::Diagnostics::ToolHelp::THREADENTRY32;
+use windows_sys::Win32::System::JobObjects::AssignProcessToJobObject;
+use windows_sys::Win32::System::JobObjects::CreateJobObjectW;
+use windows_sys::Win32::System::JobObjects::JobObjectExtendedLimitInformation;
+use windows_sys::Win32::System::JobObjects::SetInformationJobObject;
+use windows_sys::Win32::System::JobObjects::JOBOBJECT_EXTENDED_LIMIT_INFORMATION;
+use windows_sys::Win32::System::JobObjects::JOB_OBJECT_LIMIT_JOB_MEMORY;
+use windows_sys::Win32::System::JobObjects::JOB_OBJECT_LIMIT_JOB_TIME;
+use windows_sys::Win32::System::Threading::OpenThread;
+use windows_sys::Win32::System::Threading::ResumeThread;
+use windows_sys::Win32::System::Threading::THREAD_SUSPEND_RESUME;
+
+/// Job time limits are counted in 100-nanosecond intervals.
+const TICKS_PER_SECOND: u64 = 10_000_000;
+
+/// Assigns `process` to a new job object that limits the user-mode CPU time
+/// and committed memory of the process and everything it starts. The job is
+/// closed here; it stays alive while any process is assigned to it.
+pub fn apply_job_limits(
+    process: HANDLE,
+    cpu_seconds: Option<u64>,
+    memory_bytes: Option<u64>,
+) -> Result<()> {
+    unsafe {
+        let job = CreateJobObjectW(std::ptr::null_mut(), std::ptr::null());
+        if job == 0 {
+            return Err(anyhow::anyhow!("CreateJobObjectW failed"));
+        }
+        let result = set_limits_and_assign(job, process, cpu_seconds, memory_bytes);
+        CloseHandle(job);
+        result
+    }
+}
+
+unsafe fn set_limits_and_assign(
+    job: HANDLE,
+    process: HANDLE,
+    cpu_seconds: Option<u64>,
+    memory_bytes: Option<u64>,
+) -> Result<()> {
+    let mut limits: JOBOBJECT_EXTENDED_LIMIT_INFORMATION = std::mem::zeroed();
+    if let Some(cpu_seconds) = cpu_seconds {
+        limits.BasicLimitInformation.LimitFlags |= JOB_OBJECT_LIMIT_JOB_TIME;
+        limits.BasicLimitInformation.PerJobUserTimeLimit =
+            i64::try_from(cpu_seconds.saturating_mul(TICKS_PER_SECOND)).unwrap_or(i64::MAX);
+    }
+    if let Some(memory_bytes) = memory_bytes {
+        limits.BasicLimitInformation.LimitFlags |= JOB_OBJECT_LIMIT_JOB_MEMORY;
+        limits.JobMemoryLimit = usize::try_from(memory_bytes).unwrap_or(usize::MAX);
+    }
+    let ok = SetInformationJobObject(
+        job,
+        JobObjectExtendedLimitInformation,
+        &mut limits as *mut _ as *mut _,
+        std::mem::size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
+    );
+    if ok == 0 {
+        return Err(anyhow::anyhow!("SetInformationJobObject failed"));
+    }
+    if AssignProcessToJobObject(job, process) == 0 {
+        return Err(anyhow::anyhow!("AssignProcessToJobObject failed"));
+    }
+    Ok(())
+}
+
+/// Resumes the threads of process `pid`, which was created suspended so it
+/// could be put in a job before running.
+pub fn resume_process(pid: u32) -> Result<()> {
+    unsafe {
+        let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPTHREAD, 0);
+        if snapshot == INVALID_HANDLE_VALUE {
+            return Err(anyhow::anyhow!("CreateToolhelp32Snapshot failed"));
+        }
+        let mut entry: THREADENTRY32 = std::mem::zeroed();
+        entry.dwSize = std::mem::size_of::<THREADENTRY32>() as u32;
+        let mut resumed = false;
+        let mut more = Thread32First(snapshot, &mut entry) != 0;
+        while more {
+            if entry.th32OwnerProcessID == pid {
+                let thread = OpenThread(THREAD_SUSPEND_RESUME, 0, entry.th32ThreadID);
+                if thread != 0 {
+                    resumed |= ResumeThread(thread) != u32::MAX;
+                    CloseHandle(thread);
+                }
+            }
+            more = Thread32Next(snapshot, &mut entry) != 0;
+        }
+        CloseHandle(snapshot);
+        if resumed {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("failed to resume process {pid}"))
+        }
+    }
+}
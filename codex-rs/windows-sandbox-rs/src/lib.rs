@@ -5,8 +5,8 @@ macro_rules! windows_modules {
 }
 
 windows_modules!(
-    acl, allow, audit, cap, dpapi, env, hide_users, identity, logging, policy, process, token,
-    winutil
+    acl, allow, audit, cap, dpapi, env, hide_users, identity, job_limits, logging, policy, process,
+    token, winutil
 );
 
 #[cfg(target_os = "windows")]
@@ -50,6 +50,10 @@ pub use identity::require_logon_sandbox_creds;
 #[cfg(target_os = "windows")]
 pub use identity::sandbox_setup_is_complete;
 #[cfg(target_os = "windows")]
+pub use job_limits::apply_job_limits;
+#[cfg(target_os = "windows")]
+pub use job_limits::resume_process;
+#[cfg(target_os = "windows")]
 pub use logging::log_note;
 #[cfg(target_os = "windows")]
 pub use logging::LOG_FILE_NAME;
@@ -92,6 +96,8 @@ pub use token::create_workspace_write_token_with_cap_from;
 #[cfg(target_os = "windows")]
 pub use token::get_current_token_for_restriction;
 #[cfg(target_os = "windows")]
+pub use token::probe_restricted_token;
+#[cfg(target_os = "windows")]
 pub use windows_impl::run_windows_sandbox_capture;
 #[cfg(target_os = "windows")]
 pub use windows_impl::CaptureResult;
@@ -381,3 +381,15 @@ pub unsafe fn create_readonly_token_with_cap_from(
     enable_single_privilege(new_token, "SeChangeNotifyPrivilege")?;
     Ok((new_token, psid_capability))
 }
+
+/// Checks that a restricted token can be derived from the current process
+/// token, as the sandbox does for every command. The token is closed right
+/// away.
+pub fn probe_restricted_token() -> Result<()> {
+    unsafe {
+        let mut capability = world_sid()?;
+        let (token, _) = create_readonly_token_with_cap(capability.as_mut_ptr() as *mut c_void)?;
+        CloseHandle(token);
+    }
+    Ok(())
+}